@@ -0,0 +1,139 @@
+//! Instruction types
+
+use {
+    crate::find_tree_authority_address,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+/// Instructions supported by the example program
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExampleInstruction {
+    /// Appends the hash of `data` to the tree.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Merkle tree
+    ///   1. `[]` Tree authority PDA
+    ///   2. `[]` Noop program recorded in the tree header
+    ///   3. `[]` Account compression program
+    AppendData {
+        /// Data to store
+        data: Vec<u8>,
+    },
+    /// Fails unless the hash of `data` is the leaf at `index` of the tree.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` Merkle tree
+    ///   1. `[]` Account compression program
+    ///   2. ..2+N `[]` Proof nodes
+    VerifyData {
+        /// Root the proof was computed against
+        root: [u8; 32],
+        /// Index of the leaf
+        index: u32,
+        /// Stored data
+        data: Vec<u8>,
+    },
+}
+
+impl ExampleInstruction {
+    /// Unpacks a byte buffer into an
+    /// [ExampleInstruction](enum.ExampleInstruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match tag {
+            0 => Self::AppendData {
+                data: rest.to_vec(),
+            },
+            1 => {
+                if rest.len() < 36 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let (root, rest) = rest.split_at(32);
+                let (index, data) = rest.split_at(4);
+                Self::VerifyData {
+                    root: root
+                        .try_into()
+                        .map_err(|_| ProgramError::InvalidInstructionData)?,
+                    index: index
+                        .try_into()
+                        .map(u32::from_le_bytes)
+                        .map_err(|_| ProgramError::InvalidInstructionData)?,
+                    data: data.to_vec(),
+                }
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    /// Packs an [ExampleInstruction](enum.ExampleInstruction.html) into a
+    /// byte buffer.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        match self {
+            Self::AppendData { data } => {
+                buf.push(0);
+                buf.extend_from_slice(data);
+            }
+            Self::VerifyData { root, index, data } => {
+                buf.push(1);
+                buf.extend_from_slice(root);
+                buf.extend_from_slice(&index.to_le_bytes());
+                buf.extend_from_slice(data);
+            }
+        }
+        buf
+    }
+}
+
+/// Creates an `AppendData` instruction
+pub fn append_data(
+    program_id: &Pubkey,
+    merkle_tree: &Pubkey,
+    noop: &Pubkey,
+    data: Vec<u8>,
+) -> Instruction {
+    let (tree_authority, _bump_seed) = find_tree_authority_address(program_id, merkle_tree);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*merkle_tree, false),
+            AccountMeta::new_readonly(tree_authority, false),
+            AccountMeta::new_readonly(*noop, false),
+            AccountMeta::new_readonly(spl_account_compression_interface::id(), false),
+        ],
+        data: ExampleInstruction::AppendData { data }.pack(),
+    }
+}
+
+/// Creates a `VerifyData` instruction
+pub fn verify_data(
+    program_id: &Pubkey,
+    merkle_tree: &Pubkey,
+    root: [u8; 32],
+    index: u32,
+    data: Vec<u8>,
+    proof: &[[u8; 32]],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*merkle_tree, false),
+        AccountMeta::new_readonly(spl_account_compression_interface::id(), false),
+    ];
+    accounts.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(*node), false)),
+    );
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: ExampleInstruction::VerifyData { root, index, data }.pack(),
+    }
+}
@@ -0,0 +1,32 @@
+//! Crate defining an example program storing compressed data in an SPL
+//! ConcurrentMerkleTree using the SPL Account Compression interface.
+//!
+//! The program owns write access to its trees through a PDA authority. A tree
+//! is created with `init_empty_merkle_tree` by any authority, which then
+//! transfers it to the PDA returned by [find_tree_authority_address].
+
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+pub mod instruction;
+pub mod processor;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+
+use solana_program::pubkey::Pubkey;
+
+solana_program::declare_id!("CmprEx1111111111111111111111111111111111111");
+
+/// Seed of the tree authority PDA
+pub const TREE_AUTHORITY_SEED: &[u8] = b"tree-authority";
+
+/// Returns the tree authority PDA seeds
+pub(crate) fn get_tree_authority_seeds(merkle_tree: &Pubkey) -> [&[u8]; 2] {
+    [TREE_AUTHORITY_SEED, merkle_tree.as_ref()]
+}
+
+/// Finds the PDA that must be the authority of a tree used by the program
+pub fn find_tree_authority_address(program_id: &Pubkey, merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&get_tree_authority_seeds(merkle_tree), program_id)
+}
@@ -0,0 +1,102 @@
+//! Program state processor
+
+use {
+    crate::{
+        find_tree_authority_address, get_tree_authority_seeds, instruction::ExampleInstruction,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        keccak::hashv,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    spl_account_compression_interface::cpi::{self, ModifyAccounts},
+};
+
+fn check_compression_program(compression_program_info: &AccountInfo) -> ProgramResult {
+    if compression_program_info.key != &spl_account_compression_interface::id() {
+        msg!("Account compression program provided is not SPL Account Compression");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Leaf stored for `data`
+pub fn hash_data(data: &[u8]) -> [u8; 32] {
+    hashv(&[data]).to_bytes()
+}
+
+/// Processes an [AppendData](enum.ExampleInstruction.html) instruction
+pub fn process_append_data(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: Vec<u8>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let merkle_tree_info = next_account_info(account_info_iter)?;
+    let tree_authority_info = next_account_info(account_info_iter)?;
+    let noop_info = next_account_info(account_info_iter)?;
+    let compression_program_info = next_account_info(account_info_iter)?;
+
+    check_compression_program(compression_program_info)?;
+    let (tree_authority, bump_seed) = find_tree_authority_address(program_id, merkle_tree_info.key);
+    if tree_authority_info.key != &tree_authority {
+        msg!("Tree authority provided is not the tree authority PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // The account compression program checks that the PDA is the authority of
+    // the tree and that the noop program matches the tree header
+    let [seed, merkle_tree] = get_tree_authority_seeds(merkle_tree_info.key);
+    cpi::append(
+        ModifyAccounts {
+            compression_program: compression_program_info,
+            merkle_tree: merkle_tree_info,
+            authority: tree_authority_info,
+            noop: noop_info,
+        },
+        hash_data(&data),
+        &[&[seed, merkle_tree, &[bump_seed]]],
+    )
+}
+
+/// Processes a [VerifyData](enum.ExampleInstruction.html) instruction
+pub fn process_verify_data(
+    accounts: &[AccountInfo],
+    root: [u8; 32],
+    index: u32,
+    data: Vec<u8>,
+) -> ProgramResult {
+    let (merkle_tree_info, rest) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let (compression_program_info, proof) = rest
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_compression_program(compression_program_info)?;
+    cpi::verify_leaf(
+        compression_program_info,
+        merkle_tree_info,
+        root,
+        hash_data(&data),
+        index,
+        proof,
+    )
+}
+
+/// Processes an [ExampleInstruction](enum.ExampleInstruction.html).
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    match ExampleInstruction::unpack(input)? {
+        ExampleInstruction::AppendData { data } => {
+            msg!("Instruction: AppendData");
+            process_append_data(program_id, accounts, data)
+        }
+        ExampleInstruction::VerifyData { root, index, data } => {
+            msg!("Instruction: VerifyData");
+            process_verify_data(accounts, root, index, data)
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! CPI helpers for programs composing with the account compression program
+
+use {
+    crate::instruction,
+    solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        program::{get_return_data, invoke_signed},
+        program_error::ProgramError,
+    },
+};
+
+/// Accounts of the instructions modifying a tree
+#[derive(Clone)]
+pub struct ModifyAccounts<'a, 'info> {
+    /// Account compression program
+    pub compression_program: &'a AccountInfo<'info>,
+    /// Merkle tree, must be writable
+    pub merkle_tree: &'a AccountInfo<'info>,
+    /// Tree authority, signed for with `signer_seeds` if it is a PDA of the
+    /// calling program
+    pub authority: &'a AccountInfo<'info>,
+    /// Noop program recorded in the tree header
+    pub noop: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> ModifyAccounts<'a, 'info> {
+    fn to_account_infos(&self, proof: &[AccountInfo<'info>]) -> Vec<AccountInfo<'info>> {
+        let mut account_infos = vec![
+            self.merkle_tree.clone(),
+            self.authority.clone(),
+            self.noop.clone(),
+        ];
+        account_infos.extend_from_slice(proof);
+        account_infos
+    }
+
+    fn proof_nodes(proof: &[AccountInfo]) -> Vec<[u8; 32]> {
+        proof.iter().map(|node| node.key.to_bytes()).collect()
+    }
+}
+
+/// Replaces the leaf at `index` through a CPI
+pub fn replace_leaf<'info>(
+    accounts: ModifyAccounts<'_, 'info>,
+    root: [u8; 32],
+    previous_leaf: [u8; 32],
+    new_leaf: [u8; 32],
+    index: u32,
+    proof: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let instruction = instruction::replace_leaf(
+        accounts.compression_program.key,
+        accounts.merkle_tree.key,
+        accounts.authority.key,
+        accounts.noop.key,
+        root,
+        previous_leaf,
+        new_leaf,
+        index,
+        &ModifyAccounts::proof_nodes(proof),
+    );
+    invoke_signed(
+        &instruction,
+        &accounts.to_account_infos(proof),
+        signer_seeds,
+    )
+}
+
+/// Verifies that `leaf` is at `index` through a CPI
+pub fn verify_leaf<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+    proof: &[AccountInfo<'info>],
+) -> ProgramResult {
+    let instruction = instruction::verify_leaf(
+        compression_program.key,
+        merkle_tree.key,
+        root,
+        leaf,
+        index,
+        &ModifyAccounts::proof_nodes(proof),
+    );
+    let mut account_infos = vec![merkle_tree.clone()];
+    account_infos.extend_from_slice(proof);
+    invoke_signed(&instruction, &account_infos, &[])
+}
+
+/// Checks whether `leaf` is at `index` through a CPI, without failing on an
+/// invalid proof
+///
+/// Returns `InvalidAccountData` if the return data was not set by
+/// `compression_program`.
+pub fn prove_leaf_soft<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+    proof: &[AccountInfo<'info>],
+) -> Result<bool, ProgramError> {
+    let instruction = instruction::prove_leaf_soft(
+        compression_program.key,
+        merkle_tree.key,
+        root,
+        leaf,
+        index,
+        &ModifyAccounts::proof_nodes(proof),
+    );
+    let mut account_infos = vec![merkle_tree.clone()];
+    account_infos.extend_from_slice(proof);
+    invoke_signed(&instruction, &account_infos, &[])?;
+
+    match get_return_data() {
+        Some((program_id, data)) if program_id == *compression_program.key => {
+            match data.as_slice() {
+                [0] => Ok(false),
+                [1] => Ok(true),
+                _ => Err(ProgramError::InvalidAccountData),
+            }
+        }
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Appends `leaf` to the tree through a CPI
+pub fn append<'info>(
+    accounts: ModifyAccounts<'_, 'info>,
+    leaf: [u8; 32],
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let instruction = instruction::append(
+        accounts.compression_program.key,
+        accounts.merkle_tree.key,
+        accounts.authority.key,
+        accounts.noop.key,
+        leaf,
+    );
+    invoke_signed(&instruction, &accounts.to_account_infos(&[]), signer_seeds)
+}
+
+/// Appends `leaves` to the tree in order through a CPI
+pub fn append_batch<'info>(
+    accounts: ModifyAccounts<'_, 'info>,
+    leaves: Vec<[u8; 32]>,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let instruction = instruction::append_batch(
+        accounts.compression_program.key,
+        accounts.merkle_tree.key,
+        accounts.authority.key,
+        accounts.noop.key,
+        leaves,
+    );
+    invoke_signed(&instruction, &accounts.to_account_infos(&[]), signer_seeds)
+}
+
+/// Writes `leaf` at `index` if that leaf is empty, or appends it, through a
+/// CPI
+pub fn insert_or_append<'info>(
+    accounts: ModifyAccounts<'_, 'info>,
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+    proof: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let instruction = instruction::insert_or_append(
+        accounts.compression_program.key,
+        accounts.merkle_tree.key,
+        accounts.authority.key,
+        accounts.noop.key,
+        root,
+        leaf,
+        index,
+        &ModifyAccounts::proof_nodes(proof),
+    );
+    invoke_signed(
+        &instruction,
+        &accounts.to_account_infos(proof),
+        signer_seeds,
+    )
+}
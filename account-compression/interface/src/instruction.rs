@@ -0,0 +1,471 @@
+//! Instruction types
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Length of a node of the tree
+pub const NODE_LEN: usize = 32;
+
+/// Length of the instruction discriminator
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// Discriminator of `replace_leaf`, the first 8 bytes of
+/// `sha256("global:replace_leaf")`
+pub const REPLACE_LEAF_DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] =
+    [204, 165, 76, 100, 73, 147, 0, 128];
+/// Discriminator of `verify_leaf`, the first 8 bytes of
+/// `sha256("global:verify_leaf")`
+pub const VERIFY_LEAF_DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] =
+    [124, 220, 22, 223, 104, 10, 250, 224];
+/// Discriminator of `prove_leaf_soft`, the first 8 bytes of
+/// `sha256("global:prove_leaf_soft")`
+pub const PROVE_LEAF_SOFT_DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] =
+    [2, 14, 157, 68, 217, 119, 97, 150];
+/// Discriminator of `append`, the first 8 bytes of `sha256("global:append")`
+pub const APPEND_DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [149, 120, 18, 222, 236, 225, 88, 203];
+/// Discriminator of `append_batch`, the first 8 bytes of
+/// `sha256("global:append_batch")`
+pub const APPEND_BATCH_DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] =
+    [120, 189, 39, 45, 25, 212, 144, 174];
+/// Discriminator of `insert_or_append`, the first 8 bytes of
+/// `sha256("global:insert_or_append")`
+pub const INSERT_OR_APPEND_DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] =
+    [6, 42, 50, 190, 51, 109, 178, 168];
+
+/// Instructions of the account compression program available to composing
+/// programs.
+///
+/// Instructions modifying the tree expect the following accounts, followed by
+/// the proof nodes as read-only accounts:
+///
+///   0. `[w]` Merkle tree
+///   1. `[s]` Tree authority
+///   2. `[]` Noop program recorded in the tree header
+///
+/// `VerifyLeaf` and `ProveLeafSoft` only expect the merkle tree as a read-only
+/// account, followed by the proof nodes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccountCompressionInstruction {
+    /// Overwrites the leaf at `index`, which must currently be
+    /// `previous_leaf`.
+    ReplaceLeaf {
+        /// Root the proof was computed against
+        root: [u8; 32],
+        /// Current leaf at `index`
+        previous_leaf: [u8; 32],
+        /// Leaf to write at `index`
+        new_leaf: [u8; 32],
+        /// Index of the leaf
+        index: u32,
+    },
+    /// Fails unless `leaf` is at `index` in the tree.
+    VerifyLeaf {
+        /// Root the proof was computed against
+        root: [u8; 32],
+        /// Leaf to verify
+        leaf: [u8; 32],
+        /// Index of the leaf
+        index: u32,
+    },
+    /// Writes whether `leaf` is at `index` in the tree to return data, as a
+    /// borsh-encoded `bool`, instead of failing on an invalid proof.
+    ProveLeafSoft {
+        /// Root the proof was computed against
+        root: [u8; 32],
+        /// Leaf to verify
+        leaf: [u8; 32],
+        /// Index of the leaf
+        index: u32,
+    },
+    /// Appends `leaf` to the tree without a proof.
+    Append {
+        /// Leaf to append
+        leaf: [u8; 32],
+    },
+    /// Appends `leaves` to the tree in order without a proof.
+    AppendBatch {
+        /// Leaves to append, at least one
+        leaves: Vec<[u8; 32]>,
+    },
+    /// Writes `leaf` at `index` if that leaf is empty, appends it otherwise.
+    InsertOrAppend {
+        /// Root the proof was computed against
+        root: [u8; 32],
+        /// Leaf to write
+        leaf: [u8; 32],
+        /// Index of the leaf
+        index: u32,
+    },
+}
+
+impl AccountCompressionInstruction {
+    /// Unpacks a byte buffer into an
+    /// [AccountCompressionInstruction](enum.AccountCompressionInstruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        if input.len() < DISCRIMINATOR_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (discriminator, rest) = input.split_at(DISCRIMINATOR_LEN);
+        Ok(match discriminator {
+            d if d == REPLACE_LEAF_DISCRIMINATOR => {
+                let (root, rest) = Self::unpack_node(rest)?;
+                let (previous_leaf, rest) = Self::unpack_node(rest)?;
+                let (new_leaf, rest) = Self::unpack_node(rest)?;
+                let (index, _rest) = Self::unpack_u32(rest)?;
+                Self::ReplaceLeaf {
+                    root,
+                    previous_leaf,
+                    new_leaf,
+                    index,
+                }
+            }
+            d if d == VERIFY_LEAF_DISCRIMINATOR => {
+                let (root, rest) = Self::unpack_node(rest)?;
+                let (leaf, rest) = Self::unpack_node(rest)?;
+                let (index, _rest) = Self::unpack_u32(rest)?;
+                Self::VerifyLeaf { root, leaf, index }
+            }
+            d if d == PROVE_LEAF_SOFT_DISCRIMINATOR => {
+                let (root, rest) = Self::unpack_node(rest)?;
+                let (leaf, rest) = Self::unpack_node(rest)?;
+                let (index, _rest) = Self::unpack_u32(rest)?;
+                Self::ProveLeafSoft { root, leaf, index }
+            }
+            d if d == APPEND_DISCRIMINATOR => {
+                let (leaf, _rest) = Self::unpack_node(rest)?;
+                Self::Append { leaf }
+            }
+            d if d == APPEND_BATCH_DISCRIMINATOR => {
+                let (leaves_count, mut rest) = Self::unpack_u32(rest)?;
+                let mut leaves = Vec::new();
+                for _ in 0..leaves_count {
+                    let (leaf, next) = Self::unpack_node(rest)?;
+                    leaves.push(leaf);
+                    rest = next;
+                }
+                Self::AppendBatch { leaves }
+            }
+            d if d == INSERT_OR_APPEND_DISCRIMINATOR => {
+                let (root, rest) = Self::unpack_node(rest)?;
+                let (leaf, rest) = Self::unpack_node(rest)?;
+                let (index, _rest) = Self::unpack_u32(rest)?;
+                Self::InsertOrAppend { root, leaf, index }
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    fn unpack_node(input: &[u8]) -> Result<([u8; 32], &[u8]), ProgramError> {
+        if input.len() < NODE_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (node, rest) = input.split_at(NODE_LEN);
+        let node = node
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok((node, rest))
+    }
+
+    fn unpack_u32(input: &[u8]) -> Result<(u32, &[u8]), ProgramError> {
+        if input.len() < 4 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (value, rest) = input.split_at(4);
+        let value = value
+            .try_into()
+            .map(u32::from_le_bytes)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok((value, rest))
+    }
+
+    /// Packs an
+    /// [AccountCompressionInstruction](enum.AccountCompressionInstruction.html)
+    /// into a byte buffer.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        match self {
+            Self::ReplaceLeaf {
+                root,
+                previous_leaf,
+                new_leaf,
+                index,
+            } => {
+                buf.extend_from_slice(&REPLACE_LEAF_DISCRIMINATOR);
+                buf.extend_from_slice(root);
+                buf.extend_from_slice(previous_leaf);
+                buf.extend_from_slice(new_leaf);
+                buf.extend_from_slice(&index.to_le_bytes());
+            }
+            Self::VerifyLeaf { root, leaf, index } => {
+                buf.extend_from_slice(&VERIFY_LEAF_DISCRIMINATOR);
+                buf.extend_from_slice(root);
+                buf.extend_from_slice(leaf);
+                buf.extend_from_slice(&index.to_le_bytes());
+            }
+            Self::ProveLeafSoft { root, leaf, index } => {
+                buf.extend_from_slice(&PROVE_LEAF_SOFT_DISCRIMINATOR);
+                buf.extend_from_slice(root);
+                buf.extend_from_slice(leaf);
+                buf.extend_from_slice(&index.to_le_bytes());
+            }
+            Self::Append { leaf } => {
+                buf.extend_from_slice(&APPEND_DISCRIMINATOR);
+                buf.extend_from_slice(leaf);
+            }
+            Self::AppendBatch { leaves } => {
+                buf.extend_from_slice(&APPEND_BATCH_DISCRIMINATOR);
+                buf.extend_from_slice(&(leaves.len() as u32).to_le_bytes());
+                for leaf in leaves {
+                    buf.extend_from_slice(leaf);
+                }
+            }
+            Self::InsertOrAppend { root, leaf, index } => {
+                buf.extend_from_slice(&INSERT_OR_APPEND_DISCRIMINATOR);
+                buf.extend_from_slice(root);
+                buf.extend_from_slice(leaf);
+                buf.extend_from_slice(&index.to_le_bytes());
+            }
+        }
+        buf
+    }
+}
+
+fn modify_account_metas(
+    merkle_tree: &Pubkey,
+    authority: &Pubkey,
+    noop: &Pubkey,
+    proof: &[[u8; 32]],
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(*merkle_tree, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*noop, false),
+    ];
+    accounts.extend(proof_account_metas(proof));
+    accounts
+}
+
+fn proof_account_metas(proof: &[[u8; 32]]) -> impl Iterator<Item = AccountMeta> + '_ {
+    proof
+        .iter()
+        .map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(*node), false))
+}
+
+/// Creates a `ReplaceLeaf` instruction.
+///
+/// `proof` may omit the nodes cached in the canopy of the tree.
+#[allow(clippy::too_many_arguments)]
+pub fn replace_leaf(
+    program_id: &Pubkey,
+    merkle_tree: &Pubkey,
+    authority: &Pubkey,
+    noop: &Pubkey,
+    root: [u8; 32],
+    previous_leaf: [u8; 32],
+    new_leaf: [u8; 32],
+    index: u32,
+    proof: &[[u8; 32]],
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: modify_account_metas(merkle_tree, authority, noop, proof),
+        data: AccountCompressionInstruction::ReplaceLeaf {
+            root,
+            previous_leaf,
+            new_leaf,
+            index,
+        }
+        .pack(),
+    }
+}
+
+/// Creates a `VerifyLeaf` instruction.
+///
+/// `proof` may omit the nodes cached in the canopy of the tree.
+pub fn verify_leaf(
+    program_id: &Pubkey,
+    merkle_tree: &Pubkey,
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+    proof: &[[u8; 32]],
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(*merkle_tree, false)];
+    accounts.extend(proof_account_metas(proof));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AccountCompressionInstruction::VerifyLeaf { root, leaf, index }.pack(),
+    }
+}
+
+/// Creates a `ProveLeafSoft` instruction.
+///
+/// `proof` may omit the nodes cached in the canopy of the tree.
+pub fn prove_leaf_soft(
+    program_id: &Pubkey,
+    merkle_tree: &Pubkey,
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+    proof: &[[u8; 32]],
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(*merkle_tree, false)];
+    accounts.extend(proof_account_metas(proof));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AccountCompressionInstruction::ProveLeafSoft { root, leaf, index }.pack(),
+    }
+}
+
+/// Creates an `Append` instruction.
+pub fn append(
+    program_id: &Pubkey,
+    merkle_tree: &Pubkey,
+    authority: &Pubkey,
+    noop: &Pubkey,
+    leaf: [u8; 32],
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: modify_account_metas(merkle_tree, authority, noop, &[]),
+        data: AccountCompressionInstruction::Append { leaf }.pack(),
+    }
+}
+
+/// Creates an `AppendBatch` instruction.
+pub fn append_batch(
+    program_id: &Pubkey,
+    merkle_tree: &Pubkey,
+    authority: &Pubkey,
+    noop: &Pubkey,
+    leaves: Vec<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: modify_account_metas(merkle_tree, authority, noop, &[]),
+        data: AccountCompressionInstruction::AppendBatch { leaves }.pack(),
+    }
+}
+
+/// Creates an `InsertOrAppend` instruction.
+///
+/// `proof` may omit the nodes cached in the canopy of the tree.
+#[allow(clippy::too_many_arguments)]
+pub fn insert_or_append(
+    program_id: &Pubkey,
+    merkle_tree: &Pubkey,
+    authority: &Pubkey,
+    noop: &Pubkey,
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+    proof: &[[u8; 32]],
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: modify_account_metas(merkle_tree, authority, noop, proof),
+        data: AccountCompressionInstruction::InsertOrAppend { root, leaf, index }.pack(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, solana_program::hash::hashv};
+
+    fn discriminator(name: &str) -> [u8; DISCRIMINATOR_LEN] {
+        hashv(&[b"global:", name.as_bytes()]).to_bytes()[..DISCRIMINATOR_LEN]
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn discriminators_match_program() {
+        assert_eq!(REPLACE_LEAF_DISCRIMINATOR, discriminator("replace_leaf"));
+        assert_eq!(VERIFY_LEAF_DISCRIMINATOR, discriminator("verify_leaf"));
+        assert_eq!(
+            PROVE_LEAF_SOFT_DISCRIMINATOR,
+            discriminator("prove_leaf_soft")
+        );
+        assert_eq!(APPEND_DISCRIMINATOR, discriminator("append"));
+        assert_eq!(APPEND_BATCH_DISCRIMINATOR, discriminator("append_batch"));
+        assert_eq!(
+            INSERT_OR_APPEND_DISCRIMINATOR,
+            discriminator("insert_or_append")
+        );
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let instructions = [
+            AccountCompressionInstruction::ReplaceLeaf {
+                root: [1; 32],
+                previous_leaf: [2; 32],
+                new_leaf: [3; 32],
+                index: 4,
+            },
+            AccountCompressionInstruction::VerifyLeaf {
+                root: [1; 32],
+                leaf: [2; 32],
+                index: u32::MAX,
+            },
+            AccountCompressionInstruction::ProveLeafSoft {
+                root: [1; 32],
+                leaf: [2; 32],
+                index: 3,
+            },
+            AccountCompressionInstruction::Append { leaf: [1; 32] },
+            AccountCompressionInstruction::AppendBatch {
+                leaves: vec![[1; 32], [2; 32]],
+            },
+            AccountCompressionInstruction::InsertOrAppend {
+                root: [1; 32],
+                leaf: [2; 32],
+                index: 3,
+            },
+        ];
+        for instruction in instructions {
+            let packed = instruction.pack();
+            assert_eq!(
+                AccountCompressionInstruction::unpack(&packed).unwrap(),
+                instruction
+            );
+            assert_eq!(
+                AccountCompressionInstruction::unpack(&packed[..packed.len() - 1]),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+
+    #[test]
+    fn proof_is_passed_after_fixed_accounts() {
+        let merkle_tree = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let proof = [[7; 32], [8; 32]];
+        let instruction = replace_leaf(
+            &crate::id(),
+            &merkle_tree,
+            &authority,
+            &crate::spl_noop::id(),
+            [1; 32],
+            [2; 32],
+            [3; 32],
+            0,
+            &proof,
+        );
+
+        assert_eq!(instruction.accounts.len(), 5);
+        assert!(instruction.accounts[0].is_writable);
+        assert!(instruction.accounts[1].is_signer);
+        assert_eq!(instruction.accounts[2].pubkey, crate::spl_noop::id());
+        assert_eq!(
+            instruction.accounts[3].pubkey,
+            Pubkey::new_from_array(proof[0])
+        );
+        assert!(!instruction.accounts[4].is_writable);
+    }
+}
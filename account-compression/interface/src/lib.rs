@@ -0,0 +1,20 @@
+//! Crate defining the SPL Account Compression Interface
+//!
+//! Programs composing with `spl-account-compression` can use the instruction
+//! builders and CPI helpers of this crate to verify and append compressed
+//! state, without depending on the Anchor program crate.
+
+#![deny(missing_docs)]
+#![cfg_attr(not(test), forbid(unsafe_code))]
+
+pub mod cpi;
+pub mod instruction;
+
+solana_program::declare_id!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// Program id of SPL Noop, the default program trees emit their changelogs
+/// through. Trees can record a different noop program in their header, in
+/// which case that program must be passed instead.
+pub mod spl_noop {
+    solana_program::declare_id!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+}
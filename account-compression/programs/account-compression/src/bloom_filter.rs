@@ -0,0 +1,244 @@
+//! Bloom filter is an optional companion account of a SPL ConcurrentMerkleTree that lets the
+//! program reject leaves that were probably already inserted into the tree, e.g. nullifiers.
+//!
+//! A merkle tree alone cannot tell whether a leaf is already present without an indexer, since
+//! only the changelog buffer and the canopy are stored on-chain. The bloom filter is a PDA of the
+//! tree, derived from `[BLOOM_FILTER_SEED, merkle_tree]`, that stores a header followed by a bit
+//! array of `num_bits` bits. Each leaf written through `check_and_append` or
+//! `check_and_replace_leaf` sets `num_hashes` bits of the array, and the instruction fails if all
+//! of them were already set.
+//!
+//! A bloom filter has no false negatives, but it does have false positives: a leaf that was never
+//! inserted may be rejected as a duplicate. After inserting `n` leaves into a filter of `m` bits
+//! with `k` hashes, the probability of a false positive is approximately
+//! `(1 - e^(-k * n / m))^k`, which is minimized by `k = (m / n) * ln(2)`. For example, the
+//! largest filter (about 80,000 bits) rejects roughly 1% of new leaves after 8,000 insertions
+//! with 7 hashes, but more than half of them after 40,000 insertions. Size the filter for the
+//! number of leaves it is expected to hold, and treat a rejected leaf as "possibly a duplicate".
+//!
+//! Bits are never cleared, so leaves overwritten by `check_and_replace_leaf` remain in the filter.
+//! Leaves written with the regular `append`, `replace_leaf`, `insert_or_append` or
+//! `append_subtree` instructions are not recorded, so composing programs relying on the filter
+//! should only use the checked instructions on that tree.
+
+use crate::error::AccountCompressionError;
+use crate::state::CompressionAccountType;
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::MAX_PERMITTED_DATA_INCREASE, keccak::hashv};
+use spl_concurrent_merkle_tree::node::Node;
+
+/// Seed of the bloom filter PDA of a tree
+pub const BLOOM_FILTER_SEED: &[u8] = b"bloom_filter";
+
+/// Size of the serialized bloom filter header
+pub const BLOOM_FILTER_HEADER_SIZE: usize = 1 + 32 + 4 + 1 + 8;
+
+/// Maximum number of bits of a bloom filter, bound by the size of an account created by the
+/// program
+pub const MAX_BLOOM_FILTER_BITS: u32 =
+    ((MAX_PERMITTED_DATA_INCREASE - BLOOM_FILTER_HEADER_SIZE) * 8) as u32;
+
+/// Maximum number of hashes of a bloom filter
+pub const MAX_BLOOM_FILTER_HASHES: u8 = 16;
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct BloomFilterHeader {
+    /// Account type
+    pub account_type: CompressionAccountType,
+    /// Tree whose leaves are tracked by the bloom filter
+    pub merkle_tree: Pubkey,
+    /// Number of bits of the bit array following the header, a multiple of 8
+    pub num_bits: u32,
+    /// Number of bits set per leaf
+    pub num_hashes: u8,
+    /// Number of leaves inserted into the bloom filter, used to estimate the false positive rate
+    pub num_inserted: u64,
+}
+
+impl BloomFilterHeader {
+    pub fn new(merkle_tree: &Pubkey, num_bits: u32, num_hashes: u8) -> Self {
+        Self {
+            account_type: CompressionAccountType::BloomFilter,
+            merkle_tree: *merkle_tree,
+            num_bits,
+            num_hashes,
+            num_inserted: 0,
+        }
+    }
+}
+
+pub fn find_bloom_filter_address(merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BLOOM_FILTER_SEED, merkle_tree.as_ref()], &crate::id())
+}
+
+/// Returns the account size of a bloom filter of `num_bits` bits
+pub fn bloom_filter_get_size(num_bits: u32, num_hashes: u8) -> Result<usize> {
+    if num_bits == 0 || num_bits % 8 != 0 || num_bits > MAX_BLOOM_FILTER_BITS {
+        msg!(
+            "Bloom filter bits {} must be a non-zero multiple of 8, at most {}",
+            num_bits,
+            MAX_BLOOM_FILTER_BITS
+        );
+        return err!(AccountCompressionError::BloomFilterConfigError);
+    }
+    if num_hashes == 0 || num_hashes > MAX_BLOOM_FILTER_HASHES {
+        msg!(
+            "Bloom filter hashes {} must be between 1 and {}",
+            num_hashes,
+            MAX_BLOOM_FILTER_HASHES
+        );
+        return err!(AccountCompressionError::BloomFilterConfigError);
+    }
+    Ok(BLOOM_FILTER_HEADER_SIZE + num_bits as usize / 8)
+}
+
+/// Bit positions of a leaf, derived from a single keccak hash with double hashing.
+/// The tree address is hashed in so that leaves colliding in one filter don't collide in others.
+fn get_bit_indices(
+    merkle_tree: &Pubkey,
+    leaf: &Node,
+    num_bits: u32,
+    num_hashes: u8,
+) -> impl Iterator<Item = usize> {
+    let hash = hashv(&[merkle_tree.as_ref(), leaf]).to_bytes();
+    let h1 = u64::from_le_bytes(hash[..8].try_into().unwrap());
+    // an odd step never cycles back to the first position before `num_bits` steps
+    let h2 = u64::from_le_bytes(hash[8..16].try_into().unwrap()) | 1;
+    (0..num_hashes as u64)
+        .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits as u64) as usize)
+}
+
+/// Returns true if the leaf was probably inserted into the bit array already
+fn bits_contain(bits: &[u8], mut indices: impl Iterator<Item = usize>) -> bool {
+    indices.all(|index| bits[index / 8] & (1 << (index % 8)) != 0)
+}
+
+fn bits_insert(bits: &mut [u8], indices: impl Iterator<Item = usize>) {
+    for index in indices {
+        bits[index / 8] |= 1 << (index % 8);
+    }
+}
+
+/// Initializes the bloom filter account data of `merkle_tree`
+pub fn bloom_filter_initialize(
+    bloom_filter_bytes: &mut [u8],
+    merkle_tree: &Pubkey,
+    num_bits: u32,
+    num_hashes: u8,
+) -> Result<()> {
+    require_eq!(
+        bloom_filter_bytes.len(),
+        bloom_filter_get_size(num_bits, num_hashes)?,
+        AccountCompressionError::BloomFilterConfigError
+    );
+    let (mut header_bytes, bits) = bloom_filter_bytes.split_at_mut(BLOOM_FILTER_HEADER_SIZE);
+    BloomFilterHeader::new(merkle_tree, num_bits, num_hashes).serialize(&mut header_bytes)?;
+    bits.fill(0);
+    Ok(())
+}
+
+/// Inserts `leaf` into the bloom filter account data of `merkle_tree`, failing if the leaf was
+/// probably inserted already
+pub fn bloom_filter_check_and_insert(
+    bloom_filter_bytes: &mut [u8],
+    merkle_tree: &Pubkey,
+    leaf: &Node,
+) -> Result<()> {
+    let (mut header_bytes, bits) = bloom_filter_bytes.split_at_mut(BLOOM_FILTER_HEADER_SIZE);
+    let mut header = BloomFilterHeader::try_from_slice(header_bytes)?;
+    require!(
+        header.account_type == CompressionAccountType::BloomFilter,
+        AccountCompressionError::IncorrectAccountType
+    );
+    require_keys_eq!(
+        header.merkle_tree,
+        *merkle_tree,
+        AccountCompressionError::BloomFilterTreeMismatch
+    );
+    require_eq!(
+        bits.len() * 8,
+        header.num_bits as usize,
+        AccountCompressionError::BloomFilterConfigError
+    );
+
+    let indices = || get_bit_indices(merkle_tree, leaf, header.num_bits, header.num_hashes);
+    if bits_contain(bits, indices()) {
+        msg!(
+            "Leaf is probably a duplicate, {} leaves were inserted into the bloom filter",
+            header.num_inserted
+        );
+        return err!(AccountCompressionError::BloomFilterProbableDuplicate);
+    }
+    bits_insert(bits, indices());
+
+    header.num_inserted = header.num_inserted.saturating_add(1);
+    header.serialize(&mut header_bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_bloom_filter(merkle_tree: &Pubkey, num_bits: u32, num_hashes: u8) -> Vec<u8> {
+        let mut bytes = vec![0; bloom_filter_get_size(num_bits, num_hashes).unwrap()];
+        bloom_filter_initialize(&mut bytes, merkle_tree, num_bits, num_hashes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_header_size() {
+        let header = BloomFilterHeader::new(&Pubkey::new_unique(), 8, 1);
+        assert_eq!(header.try_to_vec().unwrap().len(), BLOOM_FILTER_HEADER_SIZE);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(bloom_filter_get_size(0, 1).is_err());
+        assert!(bloom_filter_get_size(12, 1).is_err());
+        assert!(bloom_filter_get_size(MAX_BLOOM_FILTER_BITS + 8, 1).is_err());
+        assert!(bloom_filter_get_size(64, 0).is_err());
+        assert!(bloom_filter_get_size(64, MAX_BLOOM_FILTER_HASHES + 1).is_err());
+        assert_eq!(
+            bloom_filter_get_size(MAX_BLOOM_FILTER_BITS, MAX_BLOOM_FILTER_HASHES).unwrap(),
+            MAX_PERMITTED_DATA_INCREASE
+        );
+    }
+
+    #[test]
+    fn test_check_and_insert_rejects_duplicate() {
+        let merkle_tree = Pubkey::new_unique();
+        let mut bytes = new_bloom_filter(&merkle_tree, 8192, 7);
+
+        for i in 0..100u8 {
+            bloom_filter_check_and_insert(&mut bytes, &merkle_tree, &[i; 32]).unwrap();
+        }
+        let header = BloomFilterHeader::try_from_slice(&bytes[..BLOOM_FILTER_HEADER_SIZE]).unwrap();
+        assert_eq!(header.num_inserted, 100);
+
+        for i in 0..100u8 {
+            assert!(bloom_filter_check_and_insert(&mut bytes, &merkle_tree, &[i; 32]).is_err());
+        }
+        let header = BloomFilterHeader::try_from_slice(&bytes[..BLOOM_FILTER_HEADER_SIZE]).unwrap();
+        assert_eq!(header.num_inserted, 100);
+    }
+
+    #[test]
+    fn test_check_and_insert_wrong_tree() {
+        let merkle_tree = Pubkey::new_unique();
+        let mut bytes = new_bloom_filter(&merkle_tree, 64, 1);
+        assert!(
+            bloom_filter_check_and_insert(&mut bytes, &Pubkey::new_unique(), &[1; 32]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_bit_indices_depend_on_tree() {
+        let leaf = [7; 32];
+        let indices = |merkle_tree| {
+            get_bit_indices(&merkle_tree, &leaf, MAX_BLOOM_FILTER_BITS, 8).collect::<Vec<_>>()
+        };
+        assert_ne!(indices(Pubkey::new_unique()), indices(Pubkey::new_unique()));
+    }
+}
@@ -41,6 +41,26 @@ pub fn check_canopy_bytes(canopy_bytes: &[u8]) -> Result<()> {
     }
 }
 
+/// Returns the byte length of a canopy caching `canopy_depth` levels of a tree of `max_depth`
+pub fn get_canopy_size(canopy_depth: u32, max_depth: u32) -> Result<usize> {
+    if canopy_depth > max_depth || max_depth > MAX_SUPPORTED_DEPTH as u32 {
+        msg!(
+            "Canopy depth {} is not supported for a tree of depth {}",
+            canopy_depth,
+            max_depth
+        );
+        return err!(AccountCompressionError::CanopyLengthMismatch);
+    }
+    Ok(((1 << (canopy_depth + 1)) - 2) * size_of::<Node>())
+}
+
+/// Returns the number of levels of the tree cached in the canopy
+pub fn get_canopy_depth(canopy_bytes: &[u8], max_depth: u32) -> Result<u32> {
+    check_canopy_bytes(canopy_bytes)?;
+    let canopy = cast_slice::<u8, Node>(canopy_bytes);
+    get_cached_path_length(canopy, max_depth)
+}
+
 #[inline(always)]
 fn get_cached_path_length(canopy: &[Node], max_depth: u32) -> Result<u32> {
     // The offset of 2 is applied because the canopy is a full binary tree without the root node
@@ -79,19 +99,38 @@ pub fn update_canopy(
     let canopy = cast_slice_mut::<u8, Node>(canopy_bytes);
     let path_len = get_cached_path_length(canopy, max_depth)?;
     if let Some(cl_event) = change_log {
-        match &*cl_event {
-            ChangeLogEvent::V1(cl) => {
-                // Update the canopy from the newest change log
-                for path_node in cl.path.iter().rev().skip(1).take(path_len as usize) {
-                    // node_idx - 2 maps to the canopy index
-                    canopy[(path_node.index - 2) as usize] = path_node.node;
-                }
-            }
+        // Update the canopy from the newest change log
+        for path_node in cl_event.path().iter().rev().skip(1).take(path_len as usize) {
+            // node_idx - 2 maps to the canopy index
+            canopy[(path_node.index - 2) as usize] = path_node.node;
         }
     }
     Ok(())
 }
 
+/// Checks that a subtree of `subtree_depth` fits entirely below the canopy. Canopy nodes inside
+/// an appended subtree can't be derived from the subtree's rightmost path, so the subtree root
+/// may at most replace a leaf node of the canopy.
+pub fn check_subtree_below_canopy(
+    canopy_bytes: &[u8],
+    max_depth: u32,
+    subtree_depth: u32,
+) -> Result<()> {
+    check_canopy_bytes(canopy_bytes)?;
+    let canopy = cast_slice::<u8, Node>(canopy_bytes);
+    let path_len = get_cached_path_length(canopy, max_depth)?;
+    if subtree_depth > max_depth.saturating_sub(path_len) {
+        msg!(
+            "Subtree depth {} overlaps the canopy of depth {} in a tree of depth {}",
+            subtree_depth,
+            path_len,
+            max_depth
+        );
+        return err!(AccountCompressionError::SubtreeOverlapsCanopy);
+    }
+    Ok(())
+}
+
 pub fn fill_in_proof_from_canopy(
     canopy_bytes: &[u8],
     max_depth: u32,
@@ -182,6 +221,103 @@ pub fn set_canopy_leaf_nodes(
     Ok(())
 }
 
+/// Rewrites the canopy nodes covering a range of leaf nodes of the canopy, after verifying them
+/// against the `root` of the tree. This restores a canopy holding inconsistent nodes without
+/// touching the tree itself.
+///
+/// Like in `set_canopy_leaf_nodes`, `start_index` is the 0-based index of the first provided leaf
+/// node of the canopy. The `proof` holds the siblings of the range that are needed to hash it up
+/// to the root: going up level by level, the left sibling of the range comes first when the range
+/// starts with a right child, followed by the right sibling when the range ends with a left child.
+/// The nodes of the range, the proof nodes at the canopy levels and all the computed parent nodes
+/// are written to the canopy.
+pub fn repair_canopy_nodes(
+    canopy_bytes: &mut [u8],
+    max_depth: u32,
+    root: &Node,
+    start_index: u32,
+    nodes: &[Node],
+    proof: &[Node],
+) -> Result<()> {
+    check_canopy_bytes(canopy_bytes)?;
+    let canopy = cast_slice_mut::<u8, Node>(canopy_bytes);
+    let path_len = get_cached_path_length(canopy, max_depth)?;
+    if path_len == 0 {
+        return err!(AccountCompressionError::CanopyNotAllocated);
+    }
+    if nodes.is_empty() {
+        return err!(AccountCompressionError::CanopyRepairProofMismatch);
+    }
+    let end_index = start_index
+        .checked_add(nodes.len() as u32 - 1)
+        .ok_or(AccountCompressionError::LeafIndexOutOfBounds)?;
+    check_index(end_index, path_len)?;
+
+    let mut empty_node_cache = Box::new([EMPTY; MAX_SUPPORTED_DEPTH]);
+    let mut level = max_depth - path_len;
+    let mut start_node = leaf_node_index_to_canopy_index(path_len, start_index)?;
+    // (node index, stored value) of every verified node, written once the root matches
+    let mut verified_nodes: Vec<(usize, Node)> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (start_node + i, *node))
+        .collect();
+    let mut range: Vec<Node> = nodes
+        .iter()
+        .map(|node| node_or_empty(node, level, &mut empty_node_cache))
+        .collect();
+    let mut proof = proof.iter();
+
+    while start_node > 1 {
+        if start_node % 2 == 1 {
+            let sibling = proof
+                .next()
+                .ok_or(AccountCompressionError::CanopyRepairProofMismatch)?;
+            start_node -= 1;
+            verified_nodes.push((start_node, *sibling));
+            range.insert(0, node_or_empty(sibling, level, &mut empty_node_cache));
+        }
+        if range.len() % 2 == 1 {
+            let sibling = proof
+                .next()
+                .ok_or(AccountCompressionError::CanopyRepairProofMismatch)?;
+            verified_nodes.push((start_node + range.len(), *sibling));
+            range.push(node_or_empty(sibling, level, &mut empty_node_cache));
+        }
+        range = range
+            .chunks(2)
+            .map(|pair| hashv(&[&pair[0], &pair[1]]).to_bytes())
+            .collect();
+        start_node >>= 1;
+        level += 1;
+        if start_node > 1 {
+            verified_nodes.extend(
+                range
+                    .iter()
+                    .enumerate()
+                    .map(|(i, node)| (start_node + i, *node)),
+            );
+        }
+    }
+    if proof.next().is_some() {
+        return err!(AccountCompressionError::CanopyRepairProofMismatch);
+    }
+    if range[0] != *root {
+        msg!(
+            "Canopy repair root mismatch. Expected: {:?}, Actual: {:?}",
+            root,
+            range[0]
+        );
+        return err!(AccountCompressionError::CanopyRootMismatch);
+    }
+
+    for (node_idx, node) in verified_nodes {
+        // node_idx - 2 maps to the canopy index
+        canopy[node_idx - 2] = node;
+    }
+    Ok(())
+}
+
 /// Checks the root of the canopy against the expected root.
 pub fn check_canopy_root(canopy_bytes: &[u8], expected_root: &Node, max_depth: u32) -> Result<()> {
     check_canopy_bytes(canopy_bytes)?;
@@ -267,6 +403,18 @@ fn get_value_for_node<const N: usize>(
     empty_node_cached_mut::<N>(level, empty_node_cache)
 }
 
+#[inline(always)]
+fn node_or_empty<const N: usize>(
+    node: &Node,
+    level: u32,
+    empty_node_cache: &mut [Node; N],
+) -> Node {
+    if *node != EMPTY {
+        return *node;
+    }
+    empty_node_cached_mut::<N>(level, empty_node_cache)
+}
+
 #[inline(always)]
 fn leaf_node_index_to_canopy_index(path_len: u32, index: u32) -> Result<usize> {
     check_index(index, path_len)?;
@@ -284,6 +432,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_canopy_size() {
+        assert_eq!(get_canopy_size(0, 10).unwrap(), 0);
+        for canopy_depth in 1..=10 {
+            let canopy_bytes = vec![0; get_canopy_size(canopy_depth, 10).unwrap()];
+            assert_eq!(get_canopy_depth(&canopy_bytes, 10).unwrap(), canopy_depth);
+        }
+        assert_eq!(
+            get_canopy_size(11, 10).unwrap_err(),
+            AccountCompressionError::CanopyLengthMismatch.into()
+        );
+    }
+
     #[test]
     fn test_zero_length_tree() {
         assert_eq!(
@@ -479,6 +640,117 @@ mod tests {
         );
     }
 
+    fn full_2level_canopy() -> (Vec<u8>, Node) {
+        let mut canopy_bytes = vec![0_u8; 6 * size_of::<Node>()];
+        let nodes = vec![[1_u8; 32], [2_u8; 32], [3_u8; 32], [4_u8; 32]];
+        set_canopy_leaf_nodes(&mut canopy_bytes, 2, 0, &nodes).unwrap();
+        let canopy = cast_slice::<u8, Node>(&canopy_bytes);
+        let root = hashv(&[&canopy[0], &canopy[1]]).to_bytes();
+        (canopy_bytes, root)
+    }
+
+    #[test]
+    fn test_success_repair_canopy_nodes() {
+        let (expected_canopy_bytes, root) = full_2level_canopy();
+        let mut canopy_bytes = expected_canopy_bytes.clone();
+        {
+            let canopy = cast_slice_mut::<u8, Node>(&mut canopy_bytes);
+            canopy[1] = [9_u8; 32];
+            canopy[4] = [9_u8; 32];
+        }
+        let proof = vec![cast_slice::<u8, Node>(&expected_canopy_bytes)[0]];
+        repair_canopy_nodes(
+            &mut canopy_bytes,
+            2,
+            &root,
+            2,
+            &[[3_u8; 32], [4_u8; 32]],
+            &proof,
+        )
+        .unwrap();
+        assert_eq!(canopy_bytes, expected_canopy_bytes);
+    }
+
+    #[test]
+    fn test_success_repair_canopy_nodes_single_node_in_deeper_tree() {
+        let mut expected_canopy_bytes = vec![0_u8; 14 * size_of::<Node>()];
+        let nodes = vec![[1_u8; 32], [2_u8; 32], [3_u8; 32], [4_u8; 32], [5_u8; 32]];
+        set_canopy_leaf_nodes(&mut expected_canopy_bytes, 10, 0, &nodes).unwrap();
+        let mut empty_node_cache = Box::new([EMPTY; MAX_SUPPORTED_DEPTH]);
+        let expected_canopy = cast_slice::<u8, Node>(&expected_canopy_bytes);
+        let right_root_child =
+            get_value_for_node::<MAX_SUPPORTED_DEPTH>(3, 9, expected_canopy, &mut empty_node_cache);
+        let root = hashv(&[&expected_canopy[0], &right_root_child]).to_bytes();
+
+        let mut canopy_bytes = expected_canopy_bytes.clone();
+        {
+            let canopy = cast_slice_mut::<u8, Node>(&mut canopy_bytes);
+            canopy[0] = [9_u8; 32];
+            canopy[3] = [9_u8; 32];
+            canopy[9] = [9_u8; 32];
+        }
+        // the path of leaf node 3 of the canopy goes through a right child, a right child and a
+        // left child, so the proof holds a left, a left and a right sibling
+        let proof = vec![expected_canopy[8], expected_canopy[2], expected_canopy[1]];
+        repair_canopy_nodes(&mut canopy_bytes, 10, &root, 3, &[[4_u8; 32]], &proof).unwrap();
+        let canopy = cast_slice::<u8, Node>(&canopy_bytes);
+        assert_eq!(canopy[0], expected_canopy[0]);
+        assert_eq!(canopy[3], expected_canopy[3]);
+        assert_eq!(canopy[9], expected_canopy[9]);
+    }
+
+    #[test]
+    fn test_failure_repair_canopy_nodes_root_mismatch() {
+        let (expected_canopy_bytes, mut root) = full_2level_canopy();
+        let mut canopy_bytes = expected_canopy_bytes.clone();
+        root[0] ^= 1;
+        let proof = vec![cast_slice::<u8, Node>(&expected_canopy_bytes)[0]];
+        assert_eq!(
+            repair_canopy_nodes(
+                &mut canopy_bytes,
+                2,
+                &root,
+                2,
+                &[[3_u8; 32], [4_u8; 32]],
+                &proof,
+            )
+            .unwrap_err(),
+            AccountCompressionError::CanopyRootMismatch.into()
+        );
+        assert_eq!(canopy_bytes, expected_canopy_bytes);
+    }
+
+    #[test]
+    fn test_failure_repair_canopy_nodes_proof_mismatch() {
+        let (expected_canopy_bytes, root) = full_2level_canopy();
+        let mut canopy_bytes = expected_canopy_bytes.clone();
+        let sibling = cast_slice::<u8, Node>(&expected_canopy_bytes)[0];
+        assert_eq!(
+            repair_canopy_nodes(
+                &mut canopy_bytes,
+                2,
+                &root,
+                2,
+                &[[3_u8; 32], [4_u8; 32]],
+                &[],
+            )
+            .unwrap_err(),
+            AccountCompressionError::CanopyRepairProofMismatch.into()
+        );
+        assert_eq!(
+            repair_canopy_nodes(
+                &mut canopy_bytes,
+                2,
+                &root,
+                2,
+                &[[3_u8; 32], [4_u8; 32]],
+                &[sibling, sibling],
+            )
+            .unwrap_err(),
+            AccountCompressionError::CanopyRepairProofMismatch.into()
+        );
+    }
+
     #[test]
     fn test_success_check_canopy_no_nodes_to_right_of_index_empty_tree_first_index() {
         let canopy_bytes = vec![0_u8; 6 * size_of::<Node>()];
@@ -558,4 +830,27 @@ mod tests {
         let canopy_bytes = vec![];
         check_canopy_no_nodes_to_right_of_index(&canopy_bytes, 20, 0).unwrap();
     }
+
+    #[test]
+    fn test_check_subtree_below_canopy() {
+        // 2-level canopy in a 10-level tree
+        let canopy_bytes = vec![0_u8; 6 * size_of::<Node>()];
+        check_subtree_below_canopy(&canopy_bytes, 10, 8).unwrap();
+        assert_eq!(
+            check_subtree_below_canopy(&canopy_bytes, 10, 9).unwrap_err(),
+            AccountCompressionError::SubtreeOverlapsCanopy.into()
+        );
+        check_subtree_below_canopy(&[], 10, 10).unwrap();
+    }
+
+    #[test]
+    fn test_get_canopy_depth() {
+        assert_eq!(get_canopy_depth(&[], 10).unwrap(), 0);
+        let canopy_bytes = vec![0_u8; 6 * size_of::<Node>()];
+        assert_eq!(get_canopy_depth(&canopy_bytes, 10).unwrap(), 2);
+        assert_eq!(
+            get_canopy_depth(&canopy_bytes[..5 * size_of::<Node>()], 10).unwrap_err(),
+            AccountCompressionError::CanopyLengthMismatch.into()
+        );
+    }
 }
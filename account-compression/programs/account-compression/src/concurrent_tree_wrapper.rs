@@ -14,8 +14,8 @@ pub use crate::error::AccountCompressionError;
 /// Exported for Anchor / Solita
 pub use spl_concurrent_merkle_tree::{
     concurrent_merkle_tree::{
-        ConcurrentMerkleTree, FillEmptyOrAppendArgs, InitializeWithRootArgs, ProveLeafArgs,
-        SetLeafArgs,
+        AppendSubtreeArgs, ConcurrentMerkleTree, FillEmptyOrAppendArgs, InitializeWithRootArgs,
+        ProveLeafArgs, SetLeafArgs,
     },
     error::ConcurrentMerkleTreeError,
     node::Node,
@@ -23,9 +23,13 @@ pub use spl_concurrent_merkle_tree::{
 };
 use {
     crate::{
-        events::ChangeLogEvent, macros::*, state::ConcurrentMerkleTreeHeader, zero_copy::ZeroCopy,
+        events::ChangeLogEvent,
+        macros::*,
+        state::{ChangeLogEntry, ConcurrentMerkleTreeHeader, RightmostProof},
+        zero_copy::ZeroCopy,
     },
     anchor_lang::prelude::*,
+    std::mem::size_of,
 };
 
 #[inline(never)]
@@ -87,6 +91,16 @@ pub fn merkle_tree_append_leaf(
     merkle_tree_apply_fn_mut!(header, tree_id, tree_bytes, append, *args)
 }
 
+#[inline(never)]
+pub fn merkle_tree_append_subtree(
+    header: &ConcurrentMerkleTreeHeader,
+    tree_id: Pubkey,
+    tree_bytes: &mut [u8],
+    args: &AppendSubtreeArgs,
+) -> Result<Box<ChangeLogEvent>> {
+    merkle_tree_apply_fn_mut!(header, tree_id, tree_bytes, append_subtree, args)
+}
+
 /// Checks whether the tree in not initialized yet without doing the deserialization. A rought
 /// equivalent to deserializing the tree and calling is_initialized() on it without the heavy
 /// lifting with macros. An empty account is a zero'd account. The tree is considered empty if the
@@ -97,6 +111,118 @@ pub fn tree_bytes_uninitialized(tree_bytes: &[u8]) -> bool {
     tree_bytes.iter().all(|&x| x == 0)
 }
 
+/// Reads the sequence number of the tree without deserializing it. The
+/// sequence number is the first field of the `#[repr(C)]` ConcurrentMerkleTree,
+/// so it sits at the start of the tree bytes for every depth and buffer size.
+pub fn merkle_tree_get_sequence_number(tree_bytes: &[u8]) -> Result<u64> {
+    read_u64(tree_bytes, 0)
+}
+
+/// Size of the three `u64` fields at the start of the `#[repr(C)]` ConcurrentMerkleTree:
+/// the sequence number, the active index and the buffer size
+const CHANGE_LOGS_OFFSET: usize = 24;
+
+/// Size of a changelog of the tree: root, path of max_depth nodes, index and padding
+fn change_log_size(header: &ConcurrentMerkleTreeHeader) -> usize {
+    size_of::<Node>() * (header.get_max_depth() as usize + 1) + 8
+}
+
+fn read_u64(tree_bytes: &[u8], offset: usize) -> Result<u64> {
+    tree_bytes
+        .get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or_else(|| AccountCompressionError::ZeroCopyError.into())
+}
+
+fn read_u32(tree_bytes: &[u8], offset: usize) -> Result<u32> {
+    tree_bytes
+        .get(offset..offset + 4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or_else(|| AccountCompressionError::ZeroCopyError.into())
+}
+
+fn read_node(tree_bytes: &[u8], offset: usize) -> Result<Node> {
+    tree_bytes
+        .get(offset..offset + size_of::<Node>())
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| AccountCompressionError::ZeroCopyError.into())
+}
+
+fn read_nodes(tree_bytes: &[u8], offset: usize, count: usize) -> Result<Vec<Node>> {
+    (0..count)
+        .map(|i| read_node(tree_bytes, offset + i * size_of::<Node>()))
+        .collect()
+}
+
+/// Reads the current root of the tree without deserializing it. The root is stored in the
+/// changelog at `active_index`, and the changelogs follow the three `u64` fields at the start of
+/// the `#[repr(C)]` ConcurrentMerkleTree.
+pub fn merkle_tree_get_root(
+    header: &ConcurrentMerkleTreeHeader,
+    tree_bytes: &[u8],
+) -> Result<Node> {
+    let active_index = read_u64(tree_bytes, 8)?;
+    if active_index >= header.get_max_buffer_size() as u64 {
+        return err!(AccountCompressionError::ZeroCopyError);
+    }
+    let root_offset = CHANGE_LOGS_OFFSET + active_index as usize * change_log_size(header);
+    read_node(tree_bytes, root_offset)
+}
+
+/// Reads the proof of the rightmost leaf of the tree without deserializing it. The rightmost
+/// proof follows the `max_buffer_size` changelogs of the `#[repr(C)]` ConcurrentMerkleTree.
+pub fn merkle_tree_get_rightmost_proof(
+    header: &ConcurrentMerkleTreeHeader,
+    tree_bytes: &[u8],
+) -> Result<RightmostProof> {
+    let max_depth = header.get_max_depth() as usize;
+    let proof_offset =
+        CHANGE_LOGS_OFFSET + header.get_max_buffer_size() as usize * change_log_size(header);
+    let leaf_offset = proof_offset + max_depth * size_of::<Node>();
+    Ok(RightmostProof {
+        proof: read_nodes(tree_bytes, proof_offset, max_depth)?,
+        leaf: read_node(tree_bytes, leaf_offset)?,
+        index: read_u32(tree_bytes, leaf_offset + size_of::<Node>())?,
+    })
+}
+
+/// Reads a changelog of the tree without deserializing it. `offset` counts back from the most
+/// recent changelog, and must be lower than the number of changelogs kept in the buffer.
+pub fn merkle_tree_get_change_log(
+    header: &ConcurrentMerkleTreeHeader,
+    tree_bytes: &[u8],
+    offset: u32,
+) -> Result<ChangeLogEntry> {
+    let sequence_number = read_u64(tree_bytes, 0)?;
+    let active_index = read_u64(tree_bytes, 8)?;
+    let buffer_size = read_u64(tree_bytes, 16)?;
+    let max_buffer_size = header.get_max_buffer_size() as u64;
+    if active_index >= max_buffer_size || buffer_size > max_buffer_size {
+        return err!(AccountCompressionError::ZeroCopyError);
+    }
+    if offset as u64 >= buffer_size {
+        msg!(
+            "Changelog {} is out of bounds, the tree keeps {} changelogs",
+            offset,
+            buffer_size
+        );
+        return err!(AccountCompressionError::ChangeLogIndexOutOfBounds);
+    }
+
+    let max_depth = header.get_max_depth() as usize;
+    let position = (active_index + max_buffer_size - offset as u64) % max_buffer_size;
+    let root_offset = CHANGE_LOGS_OFFSET + position as usize * change_log_size(header);
+    let path_offset = root_offset + size_of::<Node>();
+    Ok(ChangeLogEntry {
+        root: read_node(tree_bytes, root_offset)?,
+        path: read_nodes(tree_bytes, path_offset, max_depth)?,
+        index: read_u32(tree_bytes, path_offset + max_depth * size_of::<Node>())?,
+        sequence_number: sequence_number.saturating_sub(offset as u64),
+    })
+}
+
 #[inline(never)]
 pub fn assert_tree_is_empty(
     header: &ConcurrentMerkleTreeHeader,
@@ -113,3 +239,84 @@ pub fn assert_tree_is_empty(
     merkle_tree_apply_fn_mut!(header, tree_id, tree_bytes, prove_tree_is_empty,)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{
+        merkle_tree_get_size, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1, SUPPORTED_DEPTH_SIZE_PAIRS,
+    };
+
+    fn new_tree_with_leaves(
+        num_leaves: u8,
+    ) -> (ConcurrentMerkleTreeHeader, Box<ConcurrentMerkleTree<3, 8>>) {
+        let mut header =
+            ConcurrentMerkleTreeHeader::try_from_slice(&[0; CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1])
+                .unwrap();
+        header.initialize(3, 8, &Pubkey::new_unique(), 0, &spl_noop::id());
+
+        let mut tree = Box::<ConcurrentMerkleTree<3, 8>>::default();
+        tree.initialize().unwrap();
+        for i in 1..=num_leaves {
+            tree.append([i; 32]).unwrap();
+        }
+        (header, tree)
+    }
+
+    #[test]
+    fn test_get_rightmost_proof() {
+        let (header, tree) = new_tree_with_leaves(3);
+        let tree_bytes = bytemuck::bytes_of(tree.as_ref());
+
+        let rightmost_proof = merkle_tree_get_rightmost_proof(&header, tree_bytes).unwrap();
+        assert_eq!(rightmost_proof.proof, tree.rightmost_proof.proof.to_vec());
+        assert_eq!(rightmost_proof.leaf, [3; 32]);
+        assert_eq!(rightmost_proof.index, 3);
+    }
+
+    #[test]
+    fn test_get_change_log() {
+        let (header, tree) = new_tree_with_leaves(3);
+        let tree_bytes = bytemuck::bytes_of(tree.as_ref());
+
+        let latest = merkle_tree_get_change_log(&header, tree_bytes, 0).unwrap();
+        assert_eq!(latest.root, tree.get_root());
+        assert_eq!(latest.path[0], [3; 32]);
+        assert_eq!(latest.index, 2);
+        assert_eq!(latest.sequence_number, 3);
+        assert_eq!(
+            merkle_tree_get_root(&header, tree_bytes).unwrap(),
+            latest.root
+        );
+
+        let first_append = merkle_tree_get_change_log(&header, tree_bytes, 2).unwrap();
+        assert_eq!(first_append.path[0], [1; 32]);
+        assert_eq!(first_append.index, 0);
+        assert_eq!(first_append.sequence_number, 1);
+
+        // the buffer holds the initialization and the three appends
+        assert!(merkle_tree_get_change_log(&header, tree_bytes, 3).is_ok());
+        assert!(merkle_tree_get_change_log(&header, tree_bytes, 4).is_err());
+    }
+
+    #[test]
+    fn test_supported_depth_size_pairs() {
+        let mut header =
+            ConcurrentMerkleTreeHeader::try_from_slice(&[0; CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1])
+                .unwrap();
+        for &(max_depth, max_buffer_size) in SUPPORTED_DEPTH_SIZE_PAIRS {
+            header.initialize(
+                max_depth,
+                max_buffer_size,
+                &Pubkey::new_unique(),
+                0,
+                &spl_noop::id(),
+            );
+            assert!(max_buffer_size.is_power_of_two());
+            assert!(merkle_tree_get_size(&header).is_ok());
+        }
+
+        header.initialize(6, 8, &Pubkey::new_unique(), 0, &spl_noop::id());
+        assert!(merkle_tree_get_size(&header).is_err());
+    }
+}
@@ -67,6 +67,48 @@ pub enum AccountCompressionError {
     /// The canopy contains nodes to the right of the rightmost leaf of the tree
     #[msg("Canopy contains nodes to the right of the rightmost leaf of the tree")]
     CanopyRightmostLeafMismatch,
+
+    /// The provided noop program is not the program configured to receive the tree's events
+    #[msg("Provided noop program does not match the tree's configured noop program")]
+    IncorrectNoopProgram,
+
+    /// The appended subtree would cover nodes cached in the canopy
+    #[msg("Subtree depth exceeds the uncached depth of the tree")]
+    SubtreeOverlapsCanopy,
+
+    /// See [bloom_filter](/spl_account_compression/bloom_filter/index.html) for valid sizes
+    #[msg("An unsupported bloom filter size or number of hashes was provided")]
+    BloomFilterConfigError,
+
+    /// The bloom filter was initialized for a different tree
+    #[msg("Bloom filter does not belong to the provided tree")]
+    BloomFilterTreeMismatch,
+
+    /// All the bloom filter bits of the leaf are set, so the leaf was probably inserted already.
+    /// This may be a false positive, see [bloom_filter](/spl_account_compression/bloom_filter/index.html)
+    #[msg("Leaf was probably already inserted according to the bloom filter")]
+    BloomFilterProbableDuplicate,
+
+    /// The return data was not set by a `get_tree_config` call to this program
+    #[msg("Return data does not contain a tree config from this program")]
+    InvalidReturnData,
+
+    /// Trees storing 64-byte leaf pairs are only written with the pair instructions,
+    /// and other trees can't be written with them
+    #[msg("Instruction does not match the leaf type of the tree")]
+    IncorrectLeafType,
+
+    /// A batch append was given no leaves
+    #[msg("Leaf batch is empty")]
+    EmptyLeafBatch,
+
+    /// The proof of a canopy repair does not cover the given range of canopy nodes
+    #[msg("Canopy repair proof does not match the range of canopy nodes")]
+    CanopyRepairProofMismatch,
+
+    /// The requested changelog is older than the changelogs kept in the buffer of the tree
+    #[msg("Changelog index is out of bounds of the changelog buffer")]
+    ChangeLogIndexOutOfBounds,
 }
 
 impl From<&ConcurrentMerkleTreeError> for AccountCompressionError {
@@ -0,0 +1,72 @@
+use crate::events::ChangeLogEvent;
+use crate::state::ConcurrentMerkleTreeHeader;
+
+use anchor_lang::prelude::*;
+
+/// Schema version written in every [AccountCompressionEventV2].
+/// Bumped whenever fields are appended to the event, so indexers can tell which
+/// fields to expect before decoding the rest of the event.
+pub const ACCOUNT_COMPRESSION_EVENT_SCHEMA_VERSION: u8 = 1;
+
+/// Kind of tree an [AccountCompressionEventV2] was emitted for
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TreeType {
+    /// Tree storing 32-byte leaf nodes
+    ConcurrentMerkleTree,
+
+    /// Tree storing 64-byte (key, value) leaf pairs, see [crate::hash_leaf_pair]
+    ConcurrentMerkleTreeWithPairLeaves,
+}
+
+impl TreeType {
+    pub fn from_header(header: &ConcurrentMerkleTreeHeader) -> Self {
+        if header.get_has_pair_leaves() {
+            Self::ConcurrentMerkleTreeWithPairLeaves
+        } else {
+            Self::ConcurrentMerkleTree
+        }
+    }
+}
+
+/// Self-describing event, which records the schema it was written with and the
+/// program and tree it originates from, alongside the changelog and application data.
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct AccountCompressionEventV2 {
+    /// See [ACCOUNT_COMPRESSION_EVENT_SCHEMA_VERSION]
+    pub schema_version: u8,
+
+    /// Kind of the tree the event was emitted for
+    pub tree_type: TreeType,
+
+    /// Program which emitted the event, e.g. Bubblegum for events of its trees
+    pub application_id: Pubkey,
+
+    /// Changelog of the tree, if the event records a modification of the tree
+    pub change_log: Option<ChangeLogEvent>,
+
+    /// Data of the application, opaque to SPL Account Compression
+    pub application_data: Option<Vec<u8>>,
+}
+
+impl AccountCompressionEventV2 {
+    pub fn new(application_id: Pubkey, tree_type: TreeType) -> Self {
+        Self {
+            schema_version: ACCOUNT_COMPRESSION_EVENT_SCHEMA_VERSION,
+            tree_type,
+            application_id,
+            change_log: None,
+            application_data: None,
+        }
+    }
+
+    pub fn with_change_log(mut self, change_log: ChangeLogEvent) -> Self {
+        self.change_log = Some(change_log);
+        self
+    }
+
+    pub fn with_application_data(mut self, application_data: Vec<u8>) -> Self {
+        self.application_data = Some(application_data);
+        self
+    }
+}
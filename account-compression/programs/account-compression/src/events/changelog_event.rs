@@ -1,12 +1,13 @@
 use crate::state::PathNode;
 
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::keccak::hashv};
 use spl_concurrent_merkle_tree::changelog::ChangeLog;
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 #[repr(C)]
 pub enum ChangeLogEvent {
     V1(ChangeLogEventV1),
+    V2(ChangeLogEventV2),
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
@@ -25,6 +26,31 @@ pub struct ChangeLogEventV1 {
     pub index: u32,
 }
 
+/// Changelog of a tree storing 64-byte leaf pairs. Identical to [ChangeLogEventV1],
+/// with the addition of the preimage of the written leaf, so that indexers can
+/// reconstruct the key/value mapping without an off-chain hashing layer.
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ChangeLogEventV2 {
+    /// Public key of the ConcurrentMerkleTree
+    pub id: Pubkey,
+
+    /// Nodes of off-chain merkle tree needed by indexer
+    pub path: Vec<PathNode>,
+
+    /// Index corresponding to the number of successful operations on this tree.
+    /// Used by the off-chain indexer to figure out when there are gaps to be backfilled.
+    pub seq: u64,
+
+    /// Bitmap of node parity (used when hashing)
+    pub index: u32,
+
+    /// First half of the leaf preimage
+    pub leaf_key: [u8; 32],
+
+    /// Second half of the leaf preimage
+    pub leaf_value: [u8; 32],
+}
+
 impl ChangeLogEvent {
     pub fn new(id: Pubkey, path: Vec<PathNode>, seq: u64, index: u32) -> Self {
         Self::V1(ChangeLogEventV1 {
@@ -34,6 +60,55 @@ impl ChangeLogEvent {
             index,
         })
     }
+
+    /// Records the preimage of the written leaf in the changelog
+    pub fn with_leaf_pair(self, leaf_key: [u8; 32], leaf_value: [u8; 32]) -> Self {
+        match self {
+            Self::V1(ChangeLogEventV1 {
+                id,
+                path,
+                seq,
+                index,
+            })
+            | Self::V2(ChangeLogEventV2 {
+                id,
+                path,
+                seq,
+                index,
+                ..
+            }) => Self::V2(ChangeLogEventV2 {
+                id,
+                path,
+                seq,
+                index,
+                leaf_key,
+                leaf_value,
+            }),
+        }
+    }
+
+    /// Nodes of the path from the written leaf to the root
+    pub fn path(&self) -> &[PathNode] {
+        match self {
+            Self::V1(ChangeLogEventV1 { path, .. }) | Self::V2(ChangeLogEventV2 { path, .. }) => {
+                path
+            }
+        }
+    }
+
+    /// Index of the written leaf
+    pub fn index(&self) -> u32 {
+        match self {
+            Self::V1(ChangeLogEventV1 { index, .. }) | Self::V2(ChangeLogEventV2 { index, .. }) => {
+                *index
+            }
+        }
+    }
+}
+
+/// Hashes a 64-byte (key, value) leaf pair into the 32-byte leaf node stored in the tree
+pub fn hash_leaf_pair(leaf_key: &[u8; 32], leaf_value: &[u8; 32]) -> [u8; 32] {
+    hashv(&[leaf_key, leaf_value]).to_bytes()
 }
 
 impl<const MAX_DEPTH: usize> From<(Box<ChangeLog<MAX_DEPTH>>, Pubkey, u64)>
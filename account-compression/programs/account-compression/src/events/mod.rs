@@ -3,15 +3,24 @@
 
 use anchor_lang::prelude::*;
 
+mod account_compression_event_v2;
 mod application_data;
 mod changelog_event;
 
+pub use account_compression_event_v2::{
+    AccountCompressionEventV2, TreeType, ACCOUNT_COMPRESSION_EVENT_SCHEMA_VERSION,
+};
 pub use application_data::{ApplicationDataEvent, ApplicationDataEventV1};
-pub use changelog_event::{ChangeLogEvent, ChangeLogEventV1};
+pub use changelog_event::{hash_leaf_pair, ChangeLogEvent, ChangeLogEventV1, ChangeLogEventV2};
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 #[repr(C)]
 pub enum AccountCompressionEvent {
     ChangeLog(ChangeLogEvent),
     ApplicationData(ApplicationDataEvent),
+    /// Changelogs of the leaves of a batch append, in the order they were appended
+    ChangeLogBatch(Vec<ChangeLogEvent>),
+    /// Versioned event carrying its schema version and the tree type and program it
+    /// originates from, see [AccountCompressionEventV2]
+    V2(AccountCompressionEventV2),
 }
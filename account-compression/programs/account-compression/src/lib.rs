@@ -24,10 +24,15 @@
 
 use anchor_lang::{
     prelude::*,
-    solana_program::sysvar::{clock::Clock, rent::Rent},
+    solana_program::{
+        entrypoint::MAX_PERMITTED_DATA_INCREASE,
+        sysvar::{clock::Clock, rent::Rent},
+    },
+    system_program,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 
+pub mod bloom_filter;
 pub mod canopy;
 pub mod concurrent_tree_wrapper;
 pub mod error;
@@ -36,20 +41,34 @@ pub mod events;
 pub mod macros;
 mod noop;
 pub mod state;
+pub mod tree_metrics;
 pub mod zero_copy;
 
-pub use crate::noop::{wrap_application_data_v1, Noop};
+pub use crate::noop::{wrap_application_data_v1, wrap_application_data_v2, wrap_event_v2, Noop};
 
+use crate::bloom_filter::{
+    bloom_filter_check_and_insert, bloom_filter_get_size, bloom_filter_initialize,
+    BLOOM_FILTER_SEED,
+};
 use crate::canopy::{
     check_canopy_bytes, check_canopy_no_nodes_to_right_of_index, check_canopy_root,
-    fill_in_proof_from_canopy, set_canopy_leaf_nodes, update_canopy,
+    check_subtree_below_canopy, fill_in_proof_from_canopy, get_canopy_depth, get_canopy_size,
+    repair_canopy_nodes, set_canopy_leaf_nodes, update_canopy,
 };
 use crate::concurrent_tree_wrapper::*;
 pub use crate::error::AccountCompressionError;
-pub use crate::events::{AccountCompressionEvent, ChangeLogEvent};
-use crate::noop::wrap_event;
+pub use crate::events::{
+    hash_leaf_pair, AccountCompressionEvent, AccountCompressionEventV2, ChangeLogEvent, TreeType,
+};
+use crate::noop::{assert_valid_noop_program_account, wrap_event};
 use crate::state::{
-    merkle_tree_get_size, ConcurrentMerkleTreeHeader, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+    merkle_tree_get_header_size, merkle_tree_get_size, ConcurrentMerkleTreeHeader,
+    CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V3,
+};
+pub use crate::state::{ChangeLogEntry, RightmostProof, TreeConfig};
+use crate::tree_metrics::{
+    split_tree_metrics, tree_metrics_initialize, tree_metrics_record, TREE_METRICS_SEED,
+    TREE_METRICS_SIZE,
 };
 
 /// Exported for Anchor / Solita
@@ -74,14 +93,17 @@ pub struct Initialize<'info> {
     pub authority: Signer<'info>,
 
     /// Program used to emit changelogs as cpi instruction data.
-    pub noop: Program<'info, Noop>,
+    /// Defaults to SPL Noop, any other executable program is recorded in the tree header.
+    /// CHECK: This account is validated in the instruction
+    pub noop: UncheckedAccount<'info>,
 }
 
 /// Context for modifying a tree: inserting, appending, or replacing a leaf in
 /// the existing tree and setting the canopy or finalizing a prepared tree.
 ///
 /// Modification instructions also require the proof to the leaf to be provided
-/// as 32-byte nodes via "remaining accounts".
+/// as 32-byte nodes via "remaining accounts". The metrics account of the tree may
+/// be passed before the proof nodes, see [tree_metrics].
 #[derive(Accounts)]
 pub struct Modify<'info> {
     #[account(mut)]
@@ -93,7 +115,128 @@ pub struct Modify<'info> {
     pub authority: Signer<'info>,
 
     /// Program used to emit changelogs as cpi instruction data.
-    pub noop: Program<'info, Noop>,
+    /// CHECK: This account is validated against the tree header in the instruction
+    pub noop: UncheckedAccount<'info>,
+}
+
+/// Context for creating the bloom filter of a tree, see [bloom_filter]
+#[derive(Accounts)]
+pub struct InitBloomFilter<'info> {
+    /// CHECK: This account is validated in the instruction
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Authority that controls write-access to the tree
+    pub authority: Signer<'info>,
+
+    /// CHECK: This account is created in the instruction
+    #[account(
+        mut,
+        seeds = [BLOOM_FILTER_SEED, merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub bloom_filter: UncheckedAccount<'info>,
+
+    /// Pays for the rent of the bloom filter
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for modifying a tree with a bloom filter: appending or replacing a leaf only if it
+/// was not inserted into the tree already.
+///
+/// Like [Modify], replacing a leaf requires the proof to the leaf to be provided
+/// as 32-byte nodes via "remaining accounts".
+#[derive(Accounts)]
+pub struct ModifyWithBloomFilter<'info> {
+    #[account(mut)]
+    /// CHECK: This account is validated in the instruction
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Authority that controls write-access to the tree
+    /// Typically a program, e.g., the Bubblegum contract validates that leaves are valid NFTs.
+    pub authority: Signer<'info>,
+
+    /// Program used to emit changelogs as cpi instruction data.
+    /// CHECK: This account is validated against the tree header in the instruction
+    pub noop: UncheckedAccount<'info>,
+
+    /// CHECK: This account is validated in the instruction
+    #[account(
+        mut,
+        seeds = [BLOOM_FILTER_SEED, merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub bloom_filter: UncheckedAccount<'info>,
+}
+
+/// Context for closing the bloom filter of a tree
+#[derive(Accounts)]
+pub struct CloseBloomFilter<'info> {
+    /// CHECK: This account is validated in the instruction
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Authority that controls write-access to the tree
+    pub authority: Signer<'info>,
+
+    /// CHECK: This account is validated in the instruction
+    #[account(
+        mut,
+        seeds = [BLOOM_FILTER_SEED, merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub bloom_filter: AccountInfo<'info>,
+
+    /// CHECK: Recipient of funds after
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+/// Context for creating the metrics account of a tree, see [tree_metrics]
+#[derive(Accounts)]
+pub struct InitTreeMetrics<'info> {
+    /// CHECK: This account is validated in the instruction
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Authority that controls write-access to the tree
+    pub authority: Signer<'info>,
+
+    /// CHECK: This account is created in the instruction
+    #[account(
+        mut,
+        seeds = [TREE_METRICS_SEED, merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_metrics: UncheckedAccount<'info>,
+
+    /// Pays for the rent of the metrics account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for closing the metrics account of a tree
+#[derive(Accounts)]
+pub struct CloseTreeMetrics<'info> {
+    /// CHECK: This account is validated in the instruction
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Authority that controls write-access to the tree
+    pub authority: Signer<'info>,
+
+    /// CHECK: This account is validated in the instruction
+    #[account(
+        mut,
+        seeds = [TREE_METRICS_SEED, merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_metrics: AccountInfo<'info>,
+
+    /// CHECK: Recipient of funds after
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
 }
 
 /// Context for validating a provided proof against the SPL ConcurrentMerkleTree.
@@ -104,6 +247,20 @@ pub struct VerifyLeaf<'info> {
     pub merkle_tree: UncheckedAccount<'info>,
 }
 
+/// Context for reading the configuration of a SPL ConcurrentMerkleTree
+#[derive(Accounts)]
+pub struct GetTreeConfig<'info> {
+    /// CHECK: This account is validated in the instruction
+    pub merkle_tree: UncheckedAccount<'info>,
+}
+
+/// Context for reading the rightmost proof or the changelogs of a SPL ConcurrentMerkleTree
+#[derive(Accounts)]
+pub struct ReadTree<'info> {
+    /// CHECK: This account is validated in the instruction
+    pub merkle_tree: UncheckedAccount<'info>,
+}
+
 /// Context for transferring `authority`
 #[derive(Accounts)]
 pub struct TransferAuthority<'info> {
@@ -116,6 +273,41 @@ pub struct TransferAuthority<'info> {
     pub authority: Signer<'info>,
 }
 
+/// Context for rotating `authority` with an overlap window
+#[derive(Accounts)]
+pub struct RotateAuthority<'info> {
+    #[account(mut)]
+    /// CHECK: This account is validated in the instruction
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Authority that controls write-access to the tree
+    /// Typically a program, e.g., the Bubblegum contract validates that leaves are valid NFTs.
+    pub authority: Signer<'info>,
+
+    /// Pays for the rent of the larger tree header
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for growing the canopy of a tree
+#[derive(Accounts)]
+pub struct ResizeCanopy<'info> {
+    #[account(mut)]
+    /// CHECK: This account is validated in the instruction
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Authority that controls write-access to the tree
+    pub authority: Signer<'info>,
+
+    /// Pays for the rent of the larger canopy
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 /// Context for closing a tree
 #[derive(Accounts)]
 pub struct CloseTree<'info> {
@@ -157,18 +349,67 @@ pub mod spl_account_compression {
             crate::id(),
             AccountCompressionError::IncorrectAccountOwner
         );
+        assert_valid_noop_program_account(&ctx.accounts.noop)?;
         let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
 
-        let (mut header_bytes, rest) =
-            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let mut header = ConcurrentMerkleTreeHeader::try_from_slice(
+            &merkle_tree_bytes[..CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1],
+        )?;
+        header.initialize(
+            max_depth,
+            max_buffer_size,
+            &ctx.accounts.authority.key(),
+            Clock::get()?.slot,
+            &ctx.accounts.noop.key(),
+        );
+        let (mut header_bytes, rest) = merkle_tree_bytes.split_at_mut(header.get_header_size());
+        header.serialize(&mut header_bytes)?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+        let id = ctx.accounts.merkle_tree.key();
+
+        let change_log_event = merkle_tree_initialize_empty(&header, id, tree_bytes)?;
 
-        let mut header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        wrap_event(
+            &AccountCompressionEvent::ChangeLog(*change_log_event),
+            &ctx.accounts.noop.to_account_info(),
+        )?;
+        update_canopy(canopy_bytes, header.get_max_depth(), None)
+    }
+
+    /// Same as `init_empty_merkle_tree`, but the tree stores 64-byte (key, value) leaf pairs.
+    /// Each pair is hashed into the 32-byte leaf node on insert with `hash_leaf_pair`, and the
+    /// preimage is emitted in the changelog, so indexers can reconstruct the key/value mapping
+    /// directly.
+    ///
+    /// Leaves of the tree can only be written with `append_leaf_pair` and `replace_leaf_pair`,
+    /// or cleared with `replace_leaf`.
+    pub fn init_empty_merkle_tree_with_pair_leaves(
+        ctx: Context<Initialize>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        assert_valid_noop_program_account(&ctx.accounts.noop)?;
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+
+        let mut header = ConcurrentMerkleTreeHeader::try_from_slice(
+            &merkle_tree_bytes[..CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1],
+        )?;
         header.initialize(
             max_depth,
             max_buffer_size,
             &ctx.accounts.authority.key(),
             Clock::get()?.slot,
+            &ctx.accounts.noop.key(),
         );
+        header.enable_pair_leaves();
+        let (mut header_bytes, rest) = merkle_tree_bytes.split_at_mut(header.get_header_size());
         header.serialize(&mut header_bytes)?;
 
         let merkle_tree_size = merkle_tree_get_size(&header)?;
@@ -179,7 +420,7 @@ pub mod spl_account_compression {
 
         wrap_event(
             &AccountCompressionEvent::ChangeLog(*change_log_event),
-            &ctx.accounts.noop,
+            &ctx.accounts.noop.to_account_info(),
         )?;
         update_canopy(canopy_bytes, header.get_max_depth(), None)
     }
@@ -210,18 +451,20 @@ pub mod spl_account_compression {
             crate::id(),
             AccountCompressionError::IncorrectAccountOwner
         );
+        assert_valid_noop_program_account(&ctx.accounts.noop)?;
         let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
 
-        let (mut header_bytes, rest) =
-            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
-
-        let mut header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        let mut header = ConcurrentMerkleTreeHeader::try_from_slice(
+            &merkle_tree_bytes[..CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1],
+        )?;
         header.initialize_batched(
             max_depth,
             max_buffer_size,
             &ctx.accounts.authority.key(),
             Clock::get()?.slot,
+            &ctx.accounts.noop.key(),
         );
+        let (mut header_bytes, rest) = merkle_tree_bytes.split_at_mut(header.get_header_size());
         header.serialize(&mut header_bytes)?;
         let merkle_tree_size = merkle_tree_get_size(&header)?;
         let (_tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
@@ -254,8 +497,8 @@ pub mod spl_account_compression {
         );
         let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
 
-        let (header_bytes, rest) =
-            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
 
         let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
         header.assert_valid_authority(&ctx.accounts.authority.key())?;
@@ -299,11 +542,12 @@ pub mod spl_account_compression {
         );
         let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
 
-        let (header_bytes, rest) =
-            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
         // the header should already be initialized with prepare_batch_merkle_tree
         let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
         header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
         header.assert_is_batch_initialized()?;
         let merkle_tree_size = merkle_tree_get_size(&header)?;
         let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
@@ -341,7 +585,57 @@ pub mod spl_account_compression {
         update_canopy(canopy_bytes, header.get_max_depth(), Some(&change_log))?;
         wrap_event(
             &AccountCompressionEvent::ChangeLog(*change_log),
-            &ctx.accounts.noop,
+            &ctx.accounts.noop.to_account_info(),
+        )
+    }
+
+    /// This instruction allows the tree's `authority` to repair a canopy holding inconsistent
+    /// nodes, e.g. after a batch initialization was aborted halfway, without closing the tree.
+    ///
+    /// `canopy_nodes` is a range of the lowest level of the canopy starting at `start_index`, with
+    /// the indexing of `append_canopy_nodes`. The siblings needed to hash the range up to the root
+    /// are passed via "remaining accounts", bottom-up, with the left sibling of a level before its
+    /// right sibling. The range is verified against the current root of the tree and the canopy
+    /// nodes it covers are rewritten. The tree itself is not modified, so no changelog is emitted.
+    pub fn repair_canopy(
+        ctx: Context<Modify>,
+        start_index: u32,
+        canopy_nodes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+        // a prepared tree that is not initialized yet has no root to verify against, its canopy
+        // is filled with append_canopy_nodes instead
+        if tree_bytes_uninitialized(tree_bytes) {
+            msg!("Tree is not initialized");
+            return err!(AccountCompressionError::ConcurrentMerkleTreeError);
+        }
+        let root = merkle_tree_get_root(&header, tree_bytes)?;
+
+        let mut proof = vec![];
+        for node in ctx.remaining_accounts.iter() {
+            proof.push(node.key().to_bytes());
+        }
+        repair_canopy_nodes(
+            canopy_bytes,
+            header.get_max_depth(),
+            &root,
+            start_index,
+            &canopy_nodes,
+            &proof,
         )
     }
 
@@ -361,22 +655,28 @@ pub mod spl_account_compression {
             AccountCompressionError::IncorrectAccountOwner
         );
         let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
-        let (header_bytes, rest) =
-            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
 
         let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
         header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
+        // Leaves of pair trees may still be cleared
+        if new_leaf != EMPTY {
+            header.assert_node_leaves()?;
+        }
         header.assert_valid_leaf_index(index)?;
 
         let merkle_tree_size = merkle_tree_get_size(&header)?;
         let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
 
+        let id = ctx.accounts.merkle_tree.key();
+        let (tree_metrics, proof_accounts) = split_tree_metrics(ctx.remaining_accounts, &id);
         let mut proof = vec![];
-        for node in ctx.remaining_accounts.iter() {
+        for node in proof_accounts.iter() {
             proof.push(node.key().to_bytes());
         }
         fill_in_proof_from_canopy(canopy_bytes, header.get_max_depth(), index, &mut proof)?;
-        let id = ctx.accounts.merkle_tree.key();
         // A call is made to ConcurrentMerkleTree::set_leaf(root, previous_leaf, new_leaf, proof, index)
         let args = &SetLeafArgs {
             current_root: root,
@@ -392,9 +692,10 @@ pub mod spl_account_compression {
             header.get_max_depth(),
             Some(&change_log_event),
         )?;
+        record_tree_metrics(tree_metrics, 0, 1)?;
         wrap_event(
             &AccountCompressionEvent::ChangeLog(*change_log_event),
-            &ctx.accounts.noop,
+            &ctx.accounts.noop.to_account_info(),
         )
     }
 
@@ -410,11 +711,11 @@ pub mod spl_account_compression {
             AccountCompressionError::IncorrectAccountOwner
         );
         let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
-        let (mut header_bytes, _) =
-            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (mut header_bytes, _) = merkle_tree_bytes.split_at_mut(header_size);
 
         let mut header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
-        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_current_authority(&ctx.accounts.authority.key())?;
 
         header.set_new_authority(&new_authority);
         header.serialize(&mut header_bytes)?;
@@ -422,68 +723,249 @@ pub mod spl_account_compression {
         Ok(())
     }
 
+    /// Rotates `authority` to `new_authority` without breaking transactions in
+    /// flight: the current authority is still accepted for tree modifications
+    /// for `overlap_slots` slots, while only `new_authority` may transfer or
+    /// rotate the authority again, or close the tree.
+    /// Requires `authority` to sign
+    ///
+    /// The previous authority is recorded in a larger (V3) tree header, so the
+    /// tree account is resized and `payer` funds its additional rent the first
+    /// time the authority of a tree is rotated.
+    pub fn rotate_authority(
+        ctx: Context<RotateAuthority>,
+        new_authority: Pubkey,
+        overlap_slots: u64,
+    ) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        let (mut header, header_size) = {
+            let merkle_tree_bytes = merkle_tree.try_borrow_data()?;
+            let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+            let header =
+                ConcurrentMerkleTreeHeader::try_from_slice(&merkle_tree_bytes[..header_size])?;
+            (header, header_size)
+        };
+        header.assert_valid_current_authority(&ctx.accounts.authority.key())?;
+
+        let expiry_slot = Clock::get()?.slot.saturating_add(overlap_slots);
+        header.rotate_authority(&new_authority, expiry_slot);
+
+        // Make room for the V3 header by shifting the tree and canopy
+        if header_size < CONCURRENT_MERKLE_TREE_HEADER_SIZE_V3 {
+            let old_len = merkle_tree.data_len();
+            let new_len = old_len + CONCURRENT_MERKLE_TREE_HEADER_SIZE_V3 - header_size;
+            realloc_tree_account(
+                &merkle_tree,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                new_len,
+            )?;
+            merkle_tree
+                .try_borrow_mut_data()?
+                .copy_within(header_size..old_len, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V3);
+        }
+
+        let mut merkle_tree_bytes = merkle_tree.try_borrow_mut_data()?;
+        let (mut header_bytes, _) =
+            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V3);
+        header.serialize(&mut header_bytes)?;
+
+        Ok(())
+    }
+
+    /// Grows the canopy of a tree to `canopy_depth` levels, for trees whose proofs no longer fit
+    /// in transactions with the canopy they were created with, without migrating the tree.
+    /// Requires `authority` to sign, and `payer` funds the rent of the larger account.
+    ///
+    /// An account grows by at most `MAX_PERMITTED_DATA_INCREASE` bytes per instruction, so a
+    /// deep canopy takes several `resize_canopy` instructions with the same `canopy_depth`. They
+    /// should be sent in a single transaction, as the tree can't be modified until its canopy
+    /// reaches the new size.
+    ///
+    /// The cached levels are kept and the new levels start out empty, so modifications need
+    /// full proofs until the new levels are backfilled from proofs with `repair_canopy`.
+    pub fn resize_canopy(ctx: Context<ResizeCanopy>, canopy_depth: u32) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        let (tree_len, max_depth) = {
+            let merkle_tree_bytes = merkle_tree.try_borrow_data()?;
+            let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+            let header =
+                ConcurrentMerkleTreeHeader::try_from_slice(&merkle_tree_bytes[..header_size])?;
+            header.assert_valid_current_authority(&ctx.accounts.authority.key())?;
+            (
+                header_size + merkle_tree_get_size(&header)?,
+                header.get_max_depth(),
+            )
+        };
+
+        // The canopy is stored level by level from the top, so growing it appends the new
+        // levels after the cached ones
+        let old_len = merkle_tree.data_len();
+        let canopy_len = old_len.saturating_sub(tree_len);
+        let new_canopy_len = get_canopy_size(canopy_depth, max_depth)?;
+        if new_canopy_len <= canopy_len {
+            msg!(
+                "Canopy of {} bytes cannot be resized to depth {}",
+                canopy_len,
+                canopy_depth
+            );
+            return err!(AccountCompressionError::CanopyLengthMismatch);
+        }
+        let new_len = old_len + (new_canopy_len - canopy_len).min(MAX_PERMITTED_DATA_INCREASE);
+        realloc_tree_account(
+            &merkle_tree,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            new_len,
+        )
+    }
+
     /// Verifies a provided proof and leaf.
-    /// If invalid, throws an error.
+    /// If invalid, throws an error. If valid, writes `true` to return data.
     pub fn verify_leaf(
         ctx: Context<VerifyLeaf>,
         root: [u8; 32],
         leaf: [u8; 32],
         index: u32,
-    ) -> Result<()> {
+    ) -> Result<bool> {
+        let is_valid = prove_leaf(
+            &ctx.accounts.merkle_tree,
+            ctx.remaining_accounts,
+            root,
+            leaf,
+            index,
+        )?;
+        require!(is_valid, AccountCompressionError::ConcurrentMerkleTreeError);
+
+        Ok(true)
+    }
+
+    /// Verifies a provided proof and leaf without failing on an invalid proof.
+    /// Writes `true` to return data if the proof is valid and `false` otherwise,
+    /// so that programs composing with SPL Compression can branch on the result
+    /// of a CPI. Other errors, such as an invalid tree account, still throw.
+    pub fn prove_leaf_soft(
+        ctx: Context<VerifyLeaf>,
+        root: [u8; 32],
+        leaf: [u8; 32],
+        index: u32,
+    ) -> Result<bool> {
+        prove_leaf(
+            &ctx.accounts.merkle_tree,
+            ctx.remaining_accounts,
+            root,
+            leaf,
+            index,
+        )
+    }
+
+    /// Writes the [TreeConfig] of the tree to return data, so that programs
+    /// composing with SPL Compression can read the depth, buffer size, canopy
+    /// depth, authority, creation slot and sequence number of a tree through a
+    /// CPI. Use [TreeConfig::from_return_data] to decode it after the CPI.
+    pub fn get_tree_config(ctx: Context<GetTreeConfig>) -> Result<TreeConfig> {
         require_eq!(
             *ctx.accounts.merkle_tree.owner,
             crate::id(),
             AccountCompressionError::IncorrectAccountOwner
         );
         let merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_data()?;
-        let (header_bytes, rest) =
-            merkle_tree_bytes.split_at(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at(header_size);
 
         let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
         header.assert_valid()?;
-        header.assert_valid_leaf_index(index)?;
 
         let merkle_tree_size = merkle_tree_get_size(&header)?;
         let (tree_bytes, canopy_bytes) = rest.split_at(merkle_tree_size);
 
-        let mut proof = vec![];
-        for node in ctx.remaining_accounts.iter() {
-            proof.push(node.key().to_bytes());
-        }
-        fill_in_proof_from_canopy(canopy_bytes, header.get_max_depth(), index, &mut proof)?;
-        let id = ctx.accounts.merkle_tree.key();
-
-        let args = &ProveLeafArgs {
-            current_root: root,
-            leaf,
-            proof_vec: proof,
-            index,
-        };
-        merkle_tree_prove_leaf(&header, id, tree_bytes, args)?;
-
-        Ok(())
+        Ok(TreeConfig {
+            max_depth: header.get_max_depth(),
+            max_buffer_size: header.get_max_buffer_size(),
+            canopy_depth: get_canopy_depth(canopy_bytes, header.get_max_depth())?,
+            authority: header.get_authority(),
+            creation_slot: header.get_creation_slot(),
+            sequence_number: merkle_tree_get_sequence_number(tree_bytes)?,
+        })
     }
 
-    /// This instruction allows the tree's `authority` to append a new leaf to the tree
-    /// without having to supply a proof.
-    ///
-    /// Learn more about SPL
-    /// ConcurrentMerkleTree
-    /// [here](https://github.com/solana-labs/solana-program-library/tree/master/libraries/concurrent-merkle-tree)
-    pub fn append(ctx: Context<Modify>, leaf: [u8; 32]) -> Result<()> {
+    /// Writes the [RightmostProof] of the tree to return data. Together with the changelogs
+    /// read with `read_changelog_entry`, it lets off-chain services and composing programs
+    /// reconstruct proofs of small trees from chain state, without an indexer. Use
+    /// [RightmostProof::from_return_data] to decode it after a CPI.
+    pub fn read_rightmost_proof(ctx: Context<ReadTree>) -> Result<RightmostProof> {
         require_eq!(
             *ctx.accounts.merkle_tree.owner,
             crate::id(),
             AccountCompressionError::IncorrectAccountOwner
         );
-        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
-        let (header_bytes, rest) =
-            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at(header_size);
 
         let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
-        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid()?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, _canopy_bytes) = rest.split_at(merkle_tree_size);
+        merkle_tree_get_rightmost_proof(&header, tree_bytes)
+    }
+
+    /// Writes a [ChangeLogEntry] of the tree to return data. `index` counts back from the most
+    /// recent changelog, so reading indices from 0 up to the number of changelogs kept in the
+    /// buffer pages through the recent changes of the tree. Use
+    /// [ChangeLogEntry::from_return_data] to decode it after a CPI.
+    pub fn read_changelog_entry(ctx: Context<ReadTree>, index: u32) -> Result<ChangeLogEntry> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at(header_size);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid()?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, _canopy_bytes) = rest.split_at(merkle_tree_size);
+        merkle_tree_get_change_log(&header, tree_bytes, index)
+    }
+
+    /// This instruction allows the tree's `authority` to append a new leaf to the tree
+    /// without having to supply a proof.
+    ///
+    /// Learn more about SPL
+    /// ConcurrentMerkleTree
+    /// [here](https://github.com/solana-labs/solana-program-library/tree/master/libraries/concurrent-merkle-tree)
+    pub fn append(ctx: Context<Modify>, leaf: [u8; 32]) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
+        header.assert_node_leaves()?;
 
         let id = ctx.accounts.merkle_tree.key();
+        let (tree_metrics, _) = split_tree_metrics(ctx.remaining_accounts, &id);
         let merkle_tree_size = merkle_tree_get_size(&header)?;
         let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
         let change_log_event = merkle_tree_append_leaf(&header, id, tree_bytes, &leaf)?;
@@ -492,9 +974,118 @@ pub mod spl_account_compression {
             header.get_max_depth(),
             Some(&change_log_event),
         )?;
+        record_tree_metrics(tree_metrics, 1, 0)?;
         wrap_event(
             &AccountCompressionEvent::ChangeLog(*change_log_event),
-            &ctx.accounts.noop,
+            &ctx.accounts.noop.to_account_info(),
+        )
+    }
+
+    /// This instruction allows the tree's `authority` to append several leaves to the tree
+    /// without having to supply a proof, in the order they are given.
+    ///
+    /// The changelogs of all the appends are emitted together in a single
+    /// `ChangeLogBatch` event, saving the noop CPI of each leaf.
+    pub fn append_batch(ctx: Context<Modify>, leaves: Vec<[u8; 32]>) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        require!(!leaves.is_empty(), AccountCompressionError::EmptyLeafBatch);
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
+        header.assert_node_leaves()?;
+
+        let id = ctx.accounts.merkle_tree.key();
+        let (tree_metrics, _) = split_tree_metrics(ctx.remaining_accounts, &id);
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+        let mut change_log_events = Vec::with_capacity(leaves.len());
+        for leaf in leaves.iter() {
+            let change_log_event = merkle_tree_append_leaf(&header, id, tree_bytes, leaf)?;
+            update_canopy(
+                canopy_bytes,
+                header.get_max_depth(),
+                Some(&change_log_event),
+            )?;
+            change_log_events.push(*change_log_event);
+        }
+        record_tree_metrics(tree_metrics, leaves.len() as u64, 0)?;
+        wrap_event(
+            &AccountCompressionEvent::ChangeLogBatch(change_log_events),
+            &ctx.accounts.noop.to_account_info(),
+        )
+    }
+
+    /// This instruction allows the tree's `authority` to append the root of a subtree of depth
+    /// `subtree_depth`, occupying the next `power(2, subtree_depth)` leaf slots of the tree. This
+    /// allows consolidating many small trees into one large tree without replaying every leaf.
+    ///
+    /// The rightmost index of the tree must be a multiple of the subtree's leaf capacity, and the
+    /// subtree must fit below the canopy. The proof of the subtree's rightmost leaf within the
+    /// subtree (`subtree_depth` nodes) is passed via "remaining accounts"; nodes to the right of
+    /// that leaf must be empty, so that subsequent appends land right after it.
+    ///
+    /// Like `init_prepared_tree_with_root`, the leaves of the subtree are not emitted, so the
+    /// composing program should ensure they can be indexed, e.g. from the changelogs of the
+    /// consolidated tree.
+    pub fn append_subtree(
+        ctx: Context<Modify>,
+        subtree_root: [u8; 32],
+        rightmost_leaf: [u8; 32],
+        rightmost_index: u32,
+        subtree_depth: u32,
+    ) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+        check_subtree_below_canopy(canopy_bytes, header.get_max_depth(), subtree_depth)?;
+
+        let id = ctx.accounts.merkle_tree.key();
+        let (tree_metrics, proof_accounts) = split_tree_metrics(ctx.remaining_accounts, &id);
+        let mut proof = vec![];
+        for node in proof_accounts.iter() {
+            proof.push(node.key().to_bytes());
+        }
+        // A call is made to ConcurrentMerkleTree::append_subtree
+        let args = &AppendSubtreeArgs {
+            subtree_root,
+            subtree_depth,
+            rightmost_leaf,
+            proof_vec: proof,
+            index: rightmost_index,
+        };
+        let change_log_event = merkle_tree_append_subtree(&header, id, tree_bytes, args)?;
+
+        update_canopy(
+            canopy_bytes,
+            header.get_max_depth(),
+            Some(&change_log_event),
+        )?;
+        // Only the leaves up to the rightmost leaf are written, the slots to its right stay empty
+        // and are filled by subsequent appends
+        record_tree_metrics(tree_metrics, u64::from(rightmost_index) + 1, 0)?;
+        wrap_event(
+            &AccountCompressionEvent::ChangeLog(*change_log_event),
+            &ctx.accounts.noop.to_account_info(),
         )
     }
 
@@ -514,23 +1105,26 @@ pub mod spl_account_compression {
             AccountCompressionError::IncorrectAccountOwner
         );
         let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
-        let (header_bytes, rest) =
-            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
 
         let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
         header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
+        header.assert_node_leaves()?;
         header.assert_valid_leaf_index(index)?;
 
         let merkle_tree_size = merkle_tree_get_size(&header)?;
         let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
 
+        let id = ctx.accounts.merkle_tree.key();
+        let (tree_metrics, proof_accounts) = split_tree_metrics(ctx.remaining_accounts, &id);
         let mut proof = vec![];
-        for node in ctx.remaining_accounts.iter() {
+        for node in proof_accounts.iter() {
             proof.push(node.key().to_bytes());
         }
         fill_in_proof_from_canopy(canopy_bytes, header.get_max_depth(), index, &mut proof)?;
         // A call is made to ConcurrentMerkleTree::fill_empty_or_append
-        let id = ctx.accounts.merkle_tree.key();
         let args = &FillEmptyOrAppendArgs {
             current_root: root,
             leaf,
@@ -544,24 +1138,394 @@ pub mod spl_account_compression {
             header.get_max_depth(),
             Some(&change_log_event),
         )?;
+        if change_log_event.index() == index {
+            record_tree_metrics(tree_metrics, 0, 1)?;
+        } else {
+            record_tree_metrics(tree_metrics, 1, 0)?;
+        }
         wrap_event(
             &AccountCompressionEvent::ChangeLog(*change_log_event),
-            &ctx.accounts.noop,
+            &ctx.accounts.noop.to_account_info(),
         )
     }
 
-    pub fn close_empty_tree(ctx: Context<CloseTree>) -> Result<()> {
+    /// Appends the 64-byte leaf pair (`leaf_key`, `leaf_value`) to a tree created with
+    /// `init_empty_merkle_tree_with_pair_leaves`. The leaf node is `hash_leaf_pair(leaf_key,
+    /// leaf_value)`, and the pair is emitted in the changelog.
+    pub fn append_leaf_pair(
+        ctx: Context<Modify>,
+        leaf_key: [u8; 32],
+        leaf_value: [u8; 32],
+    ) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
+        header.assert_pair_leaves()?;
+
+        let id = ctx.accounts.merkle_tree.key();
+        let (tree_metrics, _) = split_tree_metrics(ctx.remaining_accounts, &id);
+        let leaf = hash_leaf_pair(&leaf_key, &leaf_value);
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+        let change_log_event = merkle_tree_append_leaf(&header, id, tree_bytes, &leaf)?;
+        update_canopy(
+            canopy_bytes,
+            header.get_max_depth(),
+            Some(&change_log_event),
+        )?;
+        record_tree_metrics(tree_metrics, 1, 0)?;
+        wrap_event(
+            &AccountCompressionEvent::ChangeLog(
+                change_log_event.with_leaf_pair(leaf_key, leaf_value),
+            ),
+            &ctx.accounts.noop.to_account_info(),
+        )
+    }
+
+    /// Same as `replace_leaf` for a tree created with `init_empty_merkle_tree_with_pair_leaves`:
+    /// overwrites the leaf node `previous_leaf` with the hash of the 64-byte leaf pair
+    /// (`leaf_key`, `leaf_value`), and emits the pair in the changelog.
+    pub fn replace_leaf_pair(
+        ctx: Context<Modify>,
+        root: [u8; 32],
+        previous_leaf: [u8; 32],
+        leaf_key: [u8; 32],
+        leaf_value: [u8; 32],
+        index: u32,
+    ) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
+        header.assert_pair_leaves()?;
+        header.assert_valid_leaf_index(index)?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+
+        let id = ctx.accounts.merkle_tree.key();
+        let (tree_metrics, proof_accounts) = split_tree_metrics(ctx.remaining_accounts, &id);
+        let mut proof = vec![];
+        for node in proof_accounts.iter() {
+            proof.push(node.key().to_bytes());
+        }
+        fill_in_proof_from_canopy(canopy_bytes, header.get_max_depth(), index, &mut proof)?;
+        let args = &SetLeafArgs {
+            current_root: root,
+            previous_leaf,
+            new_leaf: hash_leaf_pair(&leaf_key, &leaf_value),
+            proof_vec: proof,
+            index,
+        };
+        let change_log_event = merkle_tree_set_leaf(&header, id, tree_bytes, args)?;
+
+        update_canopy(
+            canopy_bytes,
+            header.get_max_depth(),
+            Some(&change_log_event),
+        )?;
+        record_tree_metrics(tree_metrics, 0, 1)?;
+        wrap_event(
+            &AccountCompressionEvent::ChangeLog(
+                change_log_event.with_leaf_pair(leaf_key, leaf_value),
+            ),
+            &ctx.accounts.noop.to_account_info(),
+        )
+    }
+
+    /// Creates the bloom filter of the tree, a PDA of `num_bits` bits that lets
+    /// `check_and_append` and `check_and_replace_leaf` reject leaves that were probably inserted
+    /// already. `num_bits` must be a multiple of 8.
+    ///
+    /// The filter has false positives, which grow with the number of inserted leaves. See
+    /// [bloom_filter] to choose `num_bits` and `num_hashes` for the expected number of leaves.
+    pub fn init_bloom_filter(
+        ctx: Context<InitBloomFilter>,
+        num_bits: u32,
+        num_hashes: u8,
+    ) -> Result<()> {
         require_eq!(
             *ctx.accounts.merkle_tree.owner,
             crate::id(),
             AccountCompressionError::IncorrectAccountOwner
         );
+        {
+            let merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_data()?;
+            let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+            let header =
+                ConcurrentMerkleTreeHeader::try_from_slice(&merkle_tree_bytes[..header_size])?;
+            header.assert_valid_current_authority(&ctx.accounts.authority.key())?;
+        }
+
+        let space = bloom_filter_get_size(num_bits, num_hashes)?;
+        let merkle_tree_key = ctx.accounts.merkle_tree.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            BLOOM_FILTER_SEED,
+            merkle_tree_key.as_ref(),
+            &[ctx.bumps.bloom_filter],
+        ]];
+        let bloom_filter = ctx.accounts.bloom_filter.to_account_info();
+        create_pda_account(
+            &ctx.accounts.payer.to_account_info(),
+            &bloom_filter,
+            &ctx.accounts.system_program.to_account_info(),
+            space,
+            signer_seeds,
+        )?;
+
+        let mut bloom_filter_bytes = bloom_filter.try_borrow_mut_data()?;
+        bloom_filter_initialize(
+            &mut bloom_filter_bytes,
+            &merkle_tree_key,
+            num_bits,
+            num_hashes,
+        )
+    }
+
+    /// Same as `append`, but fails if the bloom filter of the tree indicates the leaf was
+    /// probably inserted already, and records the leaf in the bloom filter otherwise.
+    pub fn check_and_append(ctx: Context<ModifyWithBloomFilter>, leaf: [u8; 32]) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        require_eq!(
+            *ctx.accounts.bloom_filter.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
         let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
-        let (header_bytes, rest) =
-            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
 
         let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
         header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
+        header.assert_node_leaves()?;
+
+        let id = ctx.accounts.merkle_tree.key();
+        let (tree_metrics, _) = split_tree_metrics(ctx.remaining_accounts, &id);
+        bloom_filter_check_and_insert(
+            &mut ctx.accounts.bloom_filter.try_borrow_mut_data()?,
+            &id,
+            &leaf,
+        )?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+        let change_log_event = merkle_tree_append_leaf(&header, id, tree_bytes, &leaf)?;
+        update_canopy(
+            canopy_bytes,
+            header.get_max_depth(),
+            Some(&change_log_event),
+        )?;
+        record_tree_metrics(tree_metrics, 1, 0)?;
+        wrap_event(
+            &AccountCompressionEvent::ChangeLog(*change_log_event),
+            &ctx.accounts.noop.to_account_info(),
+        )
+    }
+
+    /// Same as `replace_leaf`, but fails if the bloom filter of the tree indicates `new_leaf` was
+    /// probably inserted already, and records `new_leaf` in the bloom filter otherwise.
+    /// `previous_leaf` stays in the bloom filter, so it cannot be written back with this
+    /// instruction.
+    pub fn check_and_replace_leaf(
+        ctx: Context<ModifyWithBloomFilter>,
+        root: [u8; 32],
+        previous_leaf: [u8; 32],
+        new_leaf: [u8; 32],
+        index: u32,
+    ) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        require_eq!(
+            *ctx.accounts.bloom_filter.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_noop_program(&ctx.accounts.noop.key())?;
+        header.assert_node_leaves()?;
+        header.assert_valid_leaf_index(index)?;
+
+        let id = ctx.accounts.merkle_tree.key();
+        let (tree_metrics, proof_accounts) = split_tree_metrics(ctx.remaining_accounts, &id);
+        bloom_filter_check_and_insert(
+            &mut ctx.accounts.bloom_filter.try_borrow_mut_data()?,
+            &id,
+            &new_leaf,
+        )?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+
+        let mut proof = vec![];
+        for node in proof_accounts.iter() {
+            proof.push(node.key().to_bytes());
+        }
+        fill_in_proof_from_canopy(canopy_bytes, header.get_max_depth(), index, &mut proof)?;
+        let args = &SetLeafArgs {
+            current_root: root,
+            previous_leaf,
+            new_leaf,
+            proof_vec: proof,
+            index,
+        };
+        let change_log_event = merkle_tree_set_leaf(&header, id, tree_bytes, args)?;
+
+        update_canopy(
+            canopy_bytes,
+            header.get_max_depth(),
+            Some(&change_log_event),
+        )?;
+        record_tree_metrics(tree_metrics, 0, 1)?;
+        wrap_event(
+            &AccountCompressionEvent::ChangeLog(*change_log_event),
+            &ctx.accounts.noop.to_account_info(),
+        )
+    }
+
+    /// Closes the bloom filter of the tree and returns its rent to `recipient`.
+    /// Requires the tree `authority` to sign, so the bloom filter must be closed before the tree.
+    pub fn close_bloom_filter(ctx: Context<CloseBloomFilter>) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        require_eq!(
+            *ctx.accounts.bloom_filter.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(&merkle_tree_bytes[..header_size])?;
+        header.assert_valid_current_authority(&ctx.accounts.authority.key())?;
+
+        // Close bloom filter account
+        // 1. Move lamports
+        let dest_starting_lamports = ctx.accounts.recipient.lamports();
+        **ctx.accounts.recipient.lamports.borrow_mut() = dest_starting_lamports
+            .checked_add(ctx.accounts.bloom_filter.lamports())
+            .unwrap();
+        **ctx.accounts.bloom_filter.lamports.borrow_mut() = 0;
+
+        // 2. Set all bloom filter bytes to 0
+        ctx.accounts.bloom_filter.try_borrow_mut_data()?.fill(0);
+
+        Ok(())
+    }
+
+    /// Creates the metrics account of the tree, a PDA counting the leaves appended to and replaced
+    /// in the tree and recording the slot of the last write. Instructions writing leaves update
+    /// the metrics account when it is passed as the first of the "remaining accounts", see
+    /// [tree_metrics].
+    pub fn init_tree_metrics(ctx: Context<InitTreeMetrics>) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        {
+            let merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_data()?;
+            let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+            let header =
+                ConcurrentMerkleTreeHeader::try_from_slice(&merkle_tree_bytes[..header_size])?;
+            header.assert_valid_current_authority(&ctx.accounts.authority.key())?;
+        }
+
+        let merkle_tree_key = ctx.accounts.merkle_tree.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            TREE_METRICS_SEED,
+            merkle_tree_key.as_ref(),
+            &[ctx.bumps.tree_metrics],
+        ]];
+        let tree_metrics = ctx.accounts.tree_metrics.to_account_info();
+        create_pda_account(
+            &ctx.accounts.payer.to_account_info(),
+            &tree_metrics,
+            &ctx.accounts.system_program.to_account_info(),
+            TREE_METRICS_SIZE,
+            signer_seeds,
+        )?;
+
+        let mut tree_metrics_bytes = tree_metrics.try_borrow_mut_data()?;
+        tree_metrics_initialize(&mut tree_metrics_bytes, &merkle_tree_key)
+    }
+
+    /// Closes the metrics account of the tree and returns its rent to `recipient`.
+    /// Requires the tree `authority` to sign, so the metrics account must be closed before the
+    /// tree.
+    pub fn close_tree_metrics(ctx: Context<CloseTreeMetrics>) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        require_eq!(
+            *ctx.accounts.tree_metrics.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(&merkle_tree_bytes[..header_size])?;
+        header.assert_valid_current_authority(&ctx.accounts.authority.key())?;
+
+        // Close metrics account
+        // 1. Move lamports
+        let dest_starting_lamports = ctx.accounts.recipient.lamports();
+        **ctx.accounts.recipient.lamports.borrow_mut() = dest_starting_lamports
+            .checked_add(ctx.accounts.tree_metrics.lamports())
+            .unwrap();
+        **ctx.accounts.tree_metrics.lamports.borrow_mut() = 0;
+
+        // 2. Set all metrics bytes to 0
+        ctx.accounts.tree_metrics.try_borrow_mut_data()?.fill(0);
+
+        Ok(())
+    }
+
+    pub fn close_empty_tree(ctx: Context<CloseTree>) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+        let (header_bytes, rest) = merkle_tree_bytes.split_at_mut(header_size);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_current_authority(&ctx.accounts.authority.key())?;
 
         let merkle_tree_size = merkle_tree_get_size(&header)?;
         let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
@@ -585,3 +1549,157 @@ pub mod spl_account_compression {
         Ok(())
     }
 }
+
+/// Resizes the tree account to `new_len` bytes, with `payer` funding the rent
+/// of the additional bytes. The new bytes are zeroed.
+fn realloc_tree_account<'info>(
+    merkle_tree: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let required_lamports = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(merkle_tree.lamports());
+    if required_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: merkle_tree.clone(),
+                },
+            ),
+            required_lamports,
+        )?;
+    }
+    merkle_tree.realloc(new_len, false)?;
+    Ok(())
+}
+
+/// Proves a leaf of the tree against the given root, with the proof read from
+/// `proof_accounts` and completed from the canopy. Returns `false` if the
+/// concurrent merkle tree rejects the proof.
+fn prove_leaf(
+    merkle_tree: &AccountInfo,
+    proof_accounts: &[AccountInfo],
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+) -> Result<bool> {
+    require_eq!(
+        *merkle_tree.owner,
+        crate::id(),
+        AccountCompressionError::IncorrectAccountOwner
+    );
+    let merkle_tree_bytes = merkle_tree.try_borrow_data()?;
+    let header_size = merkle_tree_get_header_size(&merkle_tree_bytes)?;
+    let (header_bytes, rest) = merkle_tree_bytes.split_at(header_size);
+
+    let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+    header.assert_valid()?;
+    header.assert_valid_leaf_index(index)?;
+
+    let merkle_tree_size = merkle_tree_get_size(&header)?;
+    let (tree_bytes, canopy_bytes) = rest.split_at(merkle_tree_size);
+
+    let mut proof = vec![];
+    for node in proof_accounts.iter() {
+        proof.push(node.key().to_bytes());
+    }
+    fill_in_proof_from_canopy(canopy_bytes, header.get_max_depth(), index, &mut proof)?;
+    let id = merkle_tree.key();
+
+    let args = &ProveLeafArgs {
+        current_root: root,
+        leaf,
+        proof_vec: proof,
+        index,
+    };
+    match merkle_tree_prove_leaf(&header, id, tree_bytes, args) {
+        Ok(_) => Ok(true),
+        Err(err) if err == AccountCompressionError::ConcurrentMerkleTreeError.into() => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Records the leaves written by an instruction in the metrics account of the tree, if it was
+/// passed to the instruction
+fn record_tree_metrics(
+    tree_metrics: Option<&AccountInfo>,
+    num_appends: u64,
+    num_replaces: u64,
+) -> Result<()> {
+    if let Some(tree_metrics) = tree_metrics {
+        tree_metrics_record(
+            &mut tree_metrics.try_borrow_mut_data()?,
+            num_appends,
+            num_replaces,
+            Clock::get()?.slot,
+        )?;
+    }
+    Ok(())
+}
+
+/// Creates a rent-exempt PDA of `space` bytes owned by the program
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+    if current_lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            lamports,
+            space as u64,
+            &crate::id(),
+        )?;
+    } else {
+        // The address may have been funded ahead of time, which makes `create_account` fail
+        let required_lamports = lamports.saturating_sub(current_lamports);
+        if required_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: account.clone(),
+                    },
+                ),
+                required_lamports,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate {
+                    account_to_allocate: account.clone(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Assign {
+                    account_to_assign: account.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::id(),
+        )?;
+    }
+    Ok(())
+}
@@ -49,48 +49,102 @@ macro_rules! _merkle_tree_depth_size_apply_fn {
     };
 }
 
+/// Table of the (max depth, max buffer size) pairs supported by the program. It expands to
+/// `$callback!([(depth, buffer_size), ...], $($arg)*)`, so that every match on the tree
+/// constants is generated from this single list.
+///
+/// Note: max_buffer_size MUST be a power of 2
+#[macro_export]
+macro_rules! _merkle_tree_depth_size_pairs {
+    ($callback:ident, $($arg:tt)*) => {
+        $callback!(
+            [
+                (3, 8),
+                (4, 8),
+                (5, 8),
+                (5, 16),
+                (6, 16),
+                (6, 32),
+                (7, 16),
+                (8, 16),
+                (8, 32),
+                (8, 64),
+                (9, 16),
+                (10, 32),
+                (10, 64),
+                (11, 32),
+                (12, 32),
+                (12, 64),
+                (13, 32),
+                (13, 64),
+                (14, 64),
+                (14, 128),
+                (14, 256),
+                (14, 1024),
+                (14, 2048),
+                (15, 64),
+                (16, 64),
+                (16, 256),
+                (17, 64),
+                (18, 64),
+                (18, 256),
+                (19, 64),
+                (20, 64),
+                (20, 256),
+                (20, 1024),
+                (20, 2048),
+                (24, 64),
+                (24, 256),
+                (24, 512),
+                (24, 1024),
+                (24, 2048),
+                (26, 512),
+                (26, 1024),
+                (26, 2048),
+                (30, 512),
+                (30, 1024),
+                (30, 2048),
+            ],
+            $($arg)*
+        )
+    };
+}
+
+/// Expands the supported (max depth, max buffer size) pairs into a slice
+#[macro_export]
+macro_rules! _merkle_tree_depth_size_pairs_slice {
+    ([$(($max_depth:literal, $max_size:literal)),* $(,)?], ) => {
+        &[$(($max_depth, $max_size)),*]
+    };
+}
+
 /// This applies a given function on a ConcurrentMerkleTree by
 /// allowing the compiler to infer the size of the tree based
 /// upon the header information stored on-chain
 #[macro_export]
 macro_rules! _merkle_tree_apply_fn {
-    ($header:ident, $($arg:tt)*) => {
-        // Note: max_buffer_size MUST be a power of 2
+    ($header:ident, $id:ident, $bytes:ident, $func:ident, $($arg:tt)*) => {
+        _merkle_tree_depth_size_pairs!(
+            _merkle_tree_apply_fn_for_pairs,
+            $header,
+            $func,
+            ($id, $bytes, $func, $($arg)*)
+        )
+    };
+}
+
+/// Matches the tree constants of the header against the supported pairs, see
+/// [_merkle_tree_apply_fn]. The arguments of the function are grouped in a single token tree
+/// so that they can be repeated for every pair.
+#[macro_export]
+macro_rules! _merkle_tree_apply_fn_for_pairs {
+    ([$(($max_depth:literal, $max_size:literal)),* $(,)?], $header:ident, $func:ident, $args:tt) => {
         match ($header.get_max_depth(), $header.get_max_buffer_size()) {
-            (3, 8) => _merkle_tree_depth_size_apply_fn!(3, 8, $($arg)*),
-            (5, 8) => _merkle_tree_depth_size_apply_fn!(5, 8, $($arg)*),
-            (6, 16) => _merkle_tree_depth_size_apply_fn!(6, 16, $($arg)*),
-            (7, 16) => _merkle_tree_depth_size_apply_fn!(7, 16, $($arg)*),
-            (8, 16) => _merkle_tree_depth_size_apply_fn!(8, 16, $($arg)*),
-            (9, 16) => _merkle_tree_depth_size_apply_fn!(9, 16, $($arg)*),
-            (10, 32) => _merkle_tree_depth_size_apply_fn!(10, 32, $($arg)*),
-            (11, 32) => _merkle_tree_depth_size_apply_fn!(11, 32, $($arg)*),
-            (12, 32) => _merkle_tree_depth_size_apply_fn!(12, 32, $($arg)*),
-            (13, 32) => _merkle_tree_depth_size_apply_fn!(13, 32, $($arg)*),
-            (14, 64) => _merkle_tree_depth_size_apply_fn!(14, 64, $($arg)*),
-            (14, 256) => _merkle_tree_depth_size_apply_fn!(14, 256, $($arg)*),
-            (14, 1024) => _merkle_tree_depth_size_apply_fn!(14, 1024, $($arg)*),
-            (14, 2048) => _merkle_tree_depth_size_apply_fn!(14, 2048, $($arg)*),
-            (15, 64) => _merkle_tree_depth_size_apply_fn!(15, 64, $($arg)*),
-            (16, 64) => _merkle_tree_depth_size_apply_fn!(16, 64, $($arg)*),
-            (17, 64) => _merkle_tree_depth_size_apply_fn!(17, 64, $($arg)*),
-            (18, 64) => _merkle_tree_depth_size_apply_fn!(18, 64, $($arg)*),
-            (19, 64) => _merkle_tree_depth_size_apply_fn!(19, 64, $($arg)*),
-            (20, 64) => _merkle_tree_depth_size_apply_fn!(20, 64, $($arg)*),
-            (20, 256) => _merkle_tree_depth_size_apply_fn!(20, 256, $($arg)*),
-            (20, 1024) => _merkle_tree_depth_size_apply_fn!(20, 1024, $($arg)*),
-            (20, 2048) => _merkle_tree_depth_size_apply_fn!(20, 2048, $($arg)*),
-            (24, 64) => _merkle_tree_depth_size_apply_fn!(24, 64, $($arg)*),
-            (24, 256) => _merkle_tree_depth_size_apply_fn!(24, 256, $($arg)*),
-            (24, 512) => _merkle_tree_depth_size_apply_fn!(24, 512, $($arg)*),
-            (24, 1024) => _merkle_tree_depth_size_apply_fn!(24, 1024, $($arg)*),
-            (24, 2048) => _merkle_tree_depth_size_apply_fn!(24, 2048, $($arg)*),
-            (26, 512) => _merkle_tree_depth_size_apply_fn!(26, 512, $($arg)*),
-            (26, 1024) => _merkle_tree_depth_size_apply_fn!(26, 1024, $($arg)*),
-            (26, 2048) => _merkle_tree_depth_size_apply_fn!(26, 2048, $($arg)*),
-            (30, 512) => _merkle_tree_depth_size_apply_fn!(30, 512, $($arg)*),
-            (30, 1024) => _merkle_tree_depth_size_apply_fn!(30, 1024, $($arg)*),
-            (30, 2048) => _merkle_tree_depth_size_apply_fn!(30, 2048, $($arg)*),
+            $(
+                ($max_depth, $max_size) => {
+                    _merkle_tree_depth_size_apply_grouped_fn!($max_depth, $max_size, $args)
+                }
+            )*
             _ => {
                 msg!("Failed to apply {} on concurrent merkle tree with max depth {} and max buffer size {}",
                     stringify!($func),
@@ -103,6 +157,34 @@ macro_rules! _merkle_tree_apply_fn {
     };
 }
 
+/// Ungroups the arguments of [_merkle_tree_apply_fn_for_pairs] for a given pair
+#[macro_export]
+macro_rules! _merkle_tree_depth_size_apply_grouped_fn {
+    ($max_depth:literal, $max_size:literal, ($($arg:tt)*)) => {
+        _merkle_tree_depth_size_apply_fn!($max_depth, $max_size, $($arg)*)
+    };
+}
+
+/// Returns the size of the ConcurrentMerkleTree matching the tree constants of the header
+#[macro_export]
+macro_rules! _merkle_tree_get_size_for_pairs {
+    ([$(($max_depth:literal, $max_size:literal)),* $(,)?], $header:ident) => {
+        match ($header.get_max_depth(), $header.get_max_buffer_size()) {
+            $(
+                ($max_depth, $max_size) => Ok(size_of::<ConcurrentMerkleTree<$max_depth, $max_size>>()),
+            )*
+            _ => {
+                msg!(
+                    "Failed to get size of max depth {} and max buffer size {}",
+                    $header.get_max_depth(),
+                    $header.get_max_buffer_size()
+                );
+                err!(AccountCompressionError::ConcurrentMerkleTreeConstantsError)
+            }
+        }
+    };
+}
+
 /// This applies a given function on a mutable ConcurrentMerkleTree
 #[macro_export]
 macro_rules! merkle_tree_apply_fn_mut {
@@ -120,6 +202,8 @@ macro_rules! merkle_tree_apply_fn {
 }
 
 pub(crate) use {
-    _merkle_tree_apply_fn, _merkle_tree_depth_size_apply_fn, merkle_tree_apply_fn,
+    _merkle_tree_apply_fn, _merkle_tree_apply_fn_for_pairs, _merkle_tree_depth_size_apply_fn,
+    _merkle_tree_depth_size_apply_grouped_fn, _merkle_tree_depth_size_pairs,
+    _merkle_tree_depth_size_pairs_slice, _merkle_tree_get_size_for_pairs, merkle_tree_apply_fn,
     merkle_tree_apply_fn_mut,
 };
@@ -6,9 +6,18 @@
 //! This works because CPI instruction data is never truncated. Logging information is
 //! vital to the functioning of compression. When compression logs are truncated, indexers can fallback to
 //! deserializing the CPI instruction data.
+//!
+//! Trees emit to SPL Noop by default, but may be initialized with any executable
+//! program as their `wrapper`, which is then recorded in the tree header.
 
-use crate::events::{AccountCompressionEvent, ApplicationDataEvent, ApplicationDataEventV1};
-use anchor_lang::{prelude::*, solana_program::program::invoke};
+use crate::events::{
+    AccountCompressionEvent, AccountCompressionEventV2, ApplicationDataEvent,
+    ApplicationDataEventV1, TreeType,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke},
+};
 
 #[derive(Clone)]
 pub struct Noop;
@@ -19,13 +28,26 @@ impl anchor_lang::Id for Noop {
     }
 }
 
+/// Checks that the program provided to receive a tree's events is executable
+pub fn assert_valid_noop_program_account(noop_program: &AccountInfo) -> Result<()> {
+    if !noop_program.executable {
+        msg!("Noop program {} is not executable", noop_program.key);
+        return err!(anchor_lang::error::ErrorCode::InvalidProgramExecutable);
+    }
+    Ok(())
+}
+
 pub fn wrap_event<'info>(
     event: &AccountCompressionEvent,
-    noop_program: &Program<'info, Noop>,
+    noop_program: &AccountInfo<'info>,
 ) -> Result<()> {
     invoke(
-        &spl_noop::instruction(event.try_to_vec()?),
-        &[noop_program.to_account_info()],
+        &Instruction {
+            program_id: *noop_program.key,
+            accounts: vec![],
+            data: event.try_to_vec()?,
+        },
+        &[noop_program.clone()],
     )?;
     Ok(())
 }
@@ -40,6 +62,29 @@ pub fn wrap_application_data_v1<'info>(
     };
     wrap_event(
         &AccountCompressionEvent::ApplicationData(ApplicationDataEvent::V1(versioned_data)),
-        noop_program,
+        &noop_program.to_account_info(),
+    )
+}
+
+/// Wraps an event in the self-describing [AccountCompressionEventV2] layout
+pub fn wrap_event_v2<'info>(
+    event: AccountCompressionEventV2,
+    noop_program: &AccountInfo<'info>,
+) -> Result<()> {
+    wrap_event(&AccountCompressionEvent::V2(event), noop_program)
+}
+
+/// Wraps a custom event of the `application_id` program, written for a tree of the
+/// given `tree_type`, in an [AccountCompressionEventV2]
+pub fn wrap_application_data_v2<'info>(
+    application_id: Pubkey,
+    tree_type: TreeType,
+    custom_data: Vec<u8>,
+    noop_program: &Program<'info, Noop>,
+) -> Result<()> {
+    wrap_event_v2(
+        AccountCompressionEventV2::new(application_id, tree_type)
+            .with_application_data(custom_data),
+        &noop_program.to_account_info(),
     )
 }
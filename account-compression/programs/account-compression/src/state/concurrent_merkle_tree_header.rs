@@ -5,8 +5,11 @@ use spl_concurrent_merkle_tree::concurrent_merkle_tree::ConcurrentMerkleTree;
 use std::mem::size_of;
 
 use crate::error::AccountCompressionError;
+use crate::macros::*;
 
 pub const CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1: usize = 2 + 54;
+pub const CONCURRENT_MERKLE_TREE_HEADER_SIZE_V2: usize = 2 + 86;
+pub const CONCURRENT_MERKLE_TREE_HEADER_SIZE_V3: usize = 2 + 126;
 
 #[derive(Debug, Copy, Clone, PartialEq, BorshDeserialize, BorshSerialize)]
 #[repr(u8)]
@@ -16,6 +19,12 @@ pub enum CompressionAccountType {
 
     /// SPL ConcurrentMerkleTree data structure, may include a Canopy
     ConcurrentMerkleTree,
+
+    /// Bloom filter of the leaves of a SPL ConcurrentMerkleTree, see [crate::bloom_filter]
+    BloomFilter,
+
+    /// Write counters of a SPL ConcurrentMerkleTree, see [crate::tree_metrics]
+    TreeMetrics,
 }
 
 impl std::fmt::Display for CompressionAccountType {
@@ -29,16 +38,8 @@ impl anchor_lang::IdlBuild for CompressionAccountType {}
 
 /// Initialization parameters for an SPL ConcurrentMerkleTree.
 ///
-/// Only the following permutations are valid:
-///
-/// | max_depth | max_buffer_size       |
-/// | --------- | --------------------- |
-/// | 14        | (64, 256, 1024, 2048) |           
-/// | 20        | (64, 256, 1024, 2048) |           
-/// | 24        | (64, 256, 512, 1024, 2048) |           
-/// | 26        | (64, 256, 512, 1024, 2048) |           
-/// | 30        | (512, 1024, 2048) |           
-///
+/// Only the (max_depth, max_buffer_size) pairs listed in
+/// [SUPPORTED_DEPTH_SIZE_PAIRS] are valid.
 #[repr(C)]
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct ConcurrentMerkleTreeHeader {
@@ -73,24 +74,118 @@ pub struct ConcurrentMerkleTreeHeaderDataV1 {
     /// It takes 1 byte of space taken from the previous padding for existing accounts.
     is_batch_initialized: bool,
 
+    /// A flag indicating whether leaves are written as 64-byte (key, value) pairs,
+    /// see [ConcurrentMerkleTreeHeader::enable_pair_leaves].
+    /// It takes 1 byte of space taken from the previous padding for existing accounts.
+    has_pair_leaves: bool,
+
+    /// Needs padding for the account to be 8-byte aligned
+    /// 8-byte alignment is necessary to zero-copy the SPL ConcurrentMerkleTree
+    _padding: [u8; 4],
+}
+
+/// Header used by trees that emit their changelog events through a program other
+/// than SPL Noop. Identical to [ConcurrentMerkleTreeHeaderDataV1], with the
+/// addition of the program id receiving the event CPIs.
+#[repr(C)]
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ConcurrentMerkleTreeHeaderDataV2 {
+    /// Buffer of changelogs stored on-chain.
+    /// Must be a power of 2; see above table for valid combinations.
+    max_buffer_size: u32,
+
+    /// Depth of the SPL ConcurrentMerkleTree to store.
+    /// Tree capacity can be calculated as power(2, max_depth).
+    /// See above table for valid options.
+    max_depth: u32,
+
+    /// Authority that validates the content of the trees.
+    /// Typically a program, e.g., the Bubblegum contract validates that leaves are valid NFTs.
+    authority: Pubkey,
+
+    /// Slot corresponding to when the Merkle tree was created.
+    /// Provides a lower-bound on what slot to start (re-)building a tree from.
+    creation_slot: u64,
+
+    /// A flag indicating whether the tree has been initialized with a root.
+    is_batch_initialized: bool,
+
+    /// Program that changelog events are emitted to as CPI instruction data.
+    /// Set once at initialization and must be provided as the `noop` account
+    /// on every instruction that emits an event.
+    noop_program: Pubkey,
+
+    /// A flag indicating whether leaves are written as 64-byte (key, value) pairs.
+    has_pair_leaves: bool,
+
     /// Needs padding for the account to be 8-byte aligned
     /// 8-byte alignment is necessary to zero-copy the SPL ConcurrentMerkleTree
-    _padding: [u8; 5],
+    _padding: [u8; 4],
+}
+
+/// Header used by trees whose authority was rotated with `rotate_authority`.
+/// Identical to [ConcurrentMerkleTreeHeaderDataV2], with the addition of the
+/// previous authority which is still accepted until its expiry slot.
+#[repr(C)]
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ConcurrentMerkleTreeHeaderDataV3 {
+    /// Buffer of changelogs stored on-chain.
+    /// Must be a power of 2; see above table for valid combinations.
+    max_buffer_size: u32,
+
+    /// Depth of the SPL ConcurrentMerkleTree to store.
+    /// Tree capacity can be calculated as power(2, max_depth).
+    /// See above table for valid options.
+    max_depth: u32,
+
+    /// Authority that validates the content of the trees.
+    /// Typically a program, e.g., the Bubblegum contract validates that leaves are valid NFTs.
+    authority: Pubkey,
+
+    /// Slot corresponding to when the Merkle tree was created.
+    /// Provides a lower-bound on what slot to start (re-)building a tree from.
+    creation_slot: u64,
+
+    /// A flag indicating whether the tree has been initialized with a root.
+    is_batch_initialized: bool,
+
+    /// Program that changelog events are emitted to as CPI instruction data.
+    noop_program: Pubkey,
+
+    /// Authority before the last rotation, accepted for tree modifications
+    /// until `previous_authority_expiry_slot`, so that transactions signed
+    /// during the rotation do not fail.
+    previous_authority: Pubkey,
+
+    /// First slot at which `previous_authority` is no longer accepted
+    previous_authority_expiry_slot: u64,
+
+    /// A flag indicating whether leaves are written as 64-byte (key, value) pairs.
+    has_pair_leaves: bool,
+
+    /// Needs padding for the account to be 8-byte aligned
+    /// 8-byte alignment is necessary to zero-copy the SPL ConcurrentMerkleTree
+    _padding: [u8; 4],
 }
 
 #[repr(C)]
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub enum ConcurrentMerkleTreeHeaderData {
     V1(ConcurrentMerkleTreeHeaderDataV1),
+    V2(ConcurrentMerkleTreeHeaderDataV2),
+    V3(ConcurrentMerkleTreeHeaderDataV3),
 }
 
 impl ConcurrentMerkleTreeHeader {
+    /// Initializes the header. Trees emitting events through SPL Noop keep the
+    /// V1 layout, any other `noop_program` is recorded in a V2 header.
     pub fn initialize(
         &mut self,
         max_depth: u32,
         max_buffer_size: u32,
         authority: &Pubkey,
         creation_slot: u64,
+        noop_program: &Pubkey,
     ) {
         self.account_type = CompressionAccountType::ConcurrentMerkleTree;
 
@@ -105,6 +200,22 @@ impl ConcurrentMerkleTreeHeader {
                 header.creation_slot = creation_slot;
                 // is_batch_initialized is left false by default
             }
+            ConcurrentMerkleTreeHeaderData::V2(_) | ConcurrentMerkleTreeHeaderData::V3(_) => {
+                unreachable!("header deserialized from zero'd bytes is always V1")
+            }
+        }
+
+        if *noop_program != spl_noop::id() {
+            self.header = ConcurrentMerkleTreeHeaderData::V2(ConcurrentMerkleTreeHeaderDataV2 {
+                max_buffer_size,
+                max_depth,
+                authority: *authority,
+                creation_slot,
+                is_batch_initialized: false,
+                noop_program: *noop_program,
+                has_pair_leaves: false,
+                _padding: [0; 4],
+            });
         }
     }
 
@@ -116,36 +227,120 @@ impl ConcurrentMerkleTreeHeader {
         max_buffer_size: u32,
         authority: &Pubkey,
         creation_slot: u64,
+        noop_program: &Pubkey,
     ) {
-        self.initialize(max_depth, max_buffer_size, authority, creation_slot);
+        self.initialize(
+            max_depth,
+            max_buffer_size,
+            authority,
+            creation_slot,
+            noop_program,
+        );
         match self.header {
             ConcurrentMerkleTreeHeaderData::V1(ref mut header) => {
                 header.is_batch_initialized = true;
             }
+            ConcurrentMerkleTreeHeaderData::V2(ref mut header) => {
+                header.is_batch_initialized = true;
+            }
+            ConcurrentMerkleTreeHeaderData::V3(ref mut header) => {
+                header.is_batch_initialized = true;
+            }
+        }
+    }
+
+    /// Marks the tree as storing 64-byte (key, value) leaf pairs. Each pair is
+    /// hashed into the 32-byte leaf node on insert, and only the pair
+    /// instructions may write leaves to the tree.
+    pub fn enable_pair_leaves(&mut self) {
+        match self.header {
+            ConcurrentMerkleTreeHeaderData::V1(ref mut header) => {
+                header.has_pair_leaves = true;
+            }
+            ConcurrentMerkleTreeHeaderData::V2(ref mut header) => {
+                header.has_pair_leaves = true;
+            }
+            ConcurrentMerkleTreeHeaderData::V3(ref mut header) => {
+                header.has_pair_leaves = true;
+            }
+        }
+    }
+
+    /// Returns the number of bytes the header occupies at the start of the account
+    pub fn get_header_size(&self) -> usize {
+        match &self.header {
+            ConcurrentMerkleTreeHeaderData::V1(_) => CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+            ConcurrentMerkleTreeHeaderData::V2(_) => CONCURRENT_MERKLE_TREE_HEADER_SIZE_V2,
+            ConcurrentMerkleTreeHeaderData::V3(_) => CONCURRENT_MERKLE_TREE_HEADER_SIZE_V3,
         }
     }
 
     pub fn get_max_depth(&self) -> u32 {
         match &self.header {
             ConcurrentMerkleTreeHeaderData::V1(header) => header.max_depth,
+            ConcurrentMerkleTreeHeaderData::V2(header) => header.max_depth,
+            ConcurrentMerkleTreeHeaderData::V3(header) => header.max_depth,
         }
     }
 
     pub fn get_max_buffer_size(&self) -> u32 {
         match &self.header {
             ConcurrentMerkleTreeHeaderData::V1(header) => header.max_buffer_size,
+            ConcurrentMerkleTreeHeaderData::V2(header) => header.max_buffer_size,
+            ConcurrentMerkleTreeHeaderData::V3(header) => header.max_buffer_size,
+        }
+    }
+
+    pub fn get_authority(&self) -> Pubkey {
+        match &self.header {
+            ConcurrentMerkleTreeHeaderData::V1(header) => header.authority,
+            ConcurrentMerkleTreeHeaderData::V2(header) => header.authority,
+            ConcurrentMerkleTreeHeaderData::V3(header) => header.authority,
         }
     }
 
     pub fn get_creation_slot(&self) -> u64 {
         match &self.header {
             ConcurrentMerkleTreeHeaderData::V1(header) => header.creation_slot,
+            ConcurrentMerkleTreeHeaderData::V2(header) => header.creation_slot,
+            ConcurrentMerkleTreeHeaderData::V3(header) => header.creation_slot,
         }
     }
 
     pub fn get_is_batch_initialized(&self) -> bool {
         match &self.header {
             ConcurrentMerkleTreeHeaderData::V1(header) => header.is_batch_initialized,
+            ConcurrentMerkleTreeHeaderData::V2(header) => header.is_batch_initialized,
+            ConcurrentMerkleTreeHeaderData::V3(header) => header.is_batch_initialized,
+        }
+    }
+
+    pub fn get_has_pair_leaves(&self) -> bool {
+        match &self.header {
+            ConcurrentMerkleTreeHeaderData::V1(header) => header.has_pair_leaves,
+            ConcurrentMerkleTreeHeaderData::V2(header) => header.has_pair_leaves,
+            ConcurrentMerkleTreeHeaderData::V3(header) => header.has_pair_leaves,
+        }
+    }
+
+    /// Returns the program changelog events are emitted to
+    pub fn get_noop_program(&self) -> Pubkey {
+        match &self.header {
+            ConcurrentMerkleTreeHeaderData::V1(_) => spl_noop::id(),
+            ConcurrentMerkleTreeHeaderData::V2(header) => header.noop_program,
+            ConcurrentMerkleTreeHeaderData::V3(header) => header.noop_program,
+        }
+    }
+
+    /// Returns the authority before the last rotation and the first slot at
+    /// which it is no longer accepted, if the authority was ever rotated
+    pub fn get_previous_authority(&self) -> Option<(Pubkey, u64)> {
+        match &self.header {
+            ConcurrentMerkleTreeHeaderData::V1(_) | ConcurrentMerkleTreeHeaderData::V2(_) => None,
+            ConcurrentMerkleTreeHeaderData::V3(header) => Some((
+                header.previous_authority,
+                header.previous_authority_expiry_slot,
+            )),
         }
     }
 
@@ -155,9 +350,42 @@ impl ConcurrentMerkleTreeHeader {
                 header.authority = new_authority.clone();
                 msg!("Authority transferred to: {:?}", header.authority);
             }
+            ConcurrentMerkleTreeHeaderData::V2(ref mut header) => {
+                header.authority = new_authority.clone();
+                msg!("Authority transferred to: {:?}", header.authority);
+            }
+            ConcurrentMerkleTreeHeaderData::V3(ref mut header) => {
+                header.authority = new_authority.clone();
+                // A transfer is instantaneous, it ends any rotation in progress
+                header.previous_authority_expiry_slot = 0;
+                msg!("Authority transferred to: {:?}", header.authority);
+            }
         }
     }
 
+    /// Rotates the authority to `new_authority`, the current authority is still
+    /// accepted for tree modifications until `expiry_slot`. Converts the header
+    /// to V3, callers must make room for the larger header in the account.
+    pub fn rotate_authority(&mut self, new_authority: &Pubkey, expiry_slot: u64) {
+        self.header = ConcurrentMerkleTreeHeaderData::V3(ConcurrentMerkleTreeHeaderDataV3 {
+            max_buffer_size: self.get_max_buffer_size(),
+            max_depth: self.get_max_depth(),
+            authority: *new_authority,
+            creation_slot: self.get_creation_slot(),
+            is_batch_initialized: self.get_is_batch_initialized(),
+            noop_program: self.get_noop_program(),
+            previous_authority: self.get_authority(),
+            previous_authority_expiry_slot: expiry_slot,
+            has_pair_leaves: self.get_has_pair_leaves(),
+            _padding: [0; 4],
+        });
+        msg!(
+            "Authority rotated to: {:?}, previous authority accepted until slot {}",
+            new_authority,
+            expiry_slot
+        );
+    }
+
     pub fn assert_valid(&self) -> Result<()> {
         require_eq!(
             self.account_type,
@@ -167,17 +395,41 @@ impl ConcurrentMerkleTreeHeader {
         Ok(())
     }
 
+    /// Checks the authority of a tree modification, which may also be the
+    /// previous authority while a rotation is in progress
     pub fn assert_valid_authority(&self, expected_authority: &Pubkey) -> Result<()> {
         self.assert_valid()?;
-        match &self.header {
-            ConcurrentMerkleTreeHeaderData::V1(header) => {
-                require_eq!(
-                    header.authority,
-                    *expected_authority,
-                    AccountCompressionError::IncorrectAuthority,
-                );
+        if let Some((previous_authority, expiry_slot)) = self.get_previous_authority() {
+            if previous_authority == *expected_authority && Clock::get()?.slot < expiry_slot {
+                return Ok(());
             }
         }
+        require_eq!(
+            self.get_authority(),
+            *expected_authority,
+            AccountCompressionError::IncorrectAuthority,
+        );
+        Ok(())
+    }
+
+    /// Checks the authority of a tree administration instruction, which only
+    /// the current authority may sign
+    pub fn assert_valid_current_authority(&self, expected_authority: &Pubkey) -> Result<()> {
+        self.assert_valid()?;
+        require_eq!(
+            self.get_authority(),
+            *expected_authority,
+            AccountCompressionError::IncorrectAuthority,
+        );
+        Ok(())
+    }
+
+    pub fn assert_valid_noop_program(&self, noop_program: &Pubkey) -> Result<()> {
+        require_eq!(
+            self.get_noop_program(),
+            *noop_program,
+            AccountCompressionError::IncorrectNoopProgram,
+        );
         Ok(())
     }
 
@@ -188,63 +440,49 @@ impl ConcurrentMerkleTreeHeader {
         Ok(())
     }
 
+    /// Checks that the tree stores 32-byte leaves written as is
+    pub fn assert_node_leaves(&self) -> Result<()> {
+        require!(
+            !self.get_has_pair_leaves(),
+            AccountCompressionError::IncorrectLeafType
+        );
+        Ok(())
+    }
+
+    /// Checks that the tree stores 64-byte leaf pairs
+    pub fn assert_pair_leaves(&self) -> Result<()> {
+        require!(
+            self.get_has_pair_leaves(),
+            AccountCompressionError::IncorrectLeafType
+        );
+        Ok(())
+    }
+
     pub fn assert_is_batch_initialized(&self) -> Result<()> {
-        match &self.header {
-            ConcurrentMerkleTreeHeaderData::V1(header) => {
-                require!(
-                    header.is_batch_initialized,
-                    AccountCompressionError::BatchNotInitialized
-                );
-            }
-        }
+        require!(
+            self.get_is_batch_initialized(),
+            AccountCompressionError::BatchNotInitialized
+        );
         Ok(())
     }
 }
 
-pub fn merkle_tree_get_size(header: &ConcurrentMerkleTreeHeader) -> Result<usize> {
-    // Note: max_buffer_size MUST be a power of 2
-    match (header.get_max_depth(), header.get_max_buffer_size()) {
-        (3, 8) => Ok(size_of::<ConcurrentMerkleTree<3, 8>>()),
-        (5, 8) => Ok(size_of::<ConcurrentMerkleTree<5, 8>>()),
-        (6, 16) => Ok(size_of::<ConcurrentMerkleTree<6, 16>>()),
-        (7, 16) => Ok(size_of::<ConcurrentMerkleTree<7, 16>>()),
-        (8, 16) => Ok(size_of::<ConcurrentMerkleTree<8, 16>>()),
-        (9, 16) => Ok(size_of::<ConcurrentMerkleTree<9, 16>>()),
-        (10, 32) => Ok(size_of::<ConcurrentMerkleTree<10, 32>>()),
-        (11, 32) => Ok(size_of::<ConcurrentMerkleTree<11, 32>>()),
-        (12, 32) => Ok(size_of::<ConcurrentMerkleTree<12, 32>>()),
-        (13, 32) => Ok(size_of::<ConcurrentMerkleTree<13, 32>>()),
-        (14, 64) => Ok(size_of::<ConcurrentMerkleTree<14, 64>>()),
-        (14, 256) => Ok(size_of::<ConcurrentMerkleTree<14, 256>>()),
-        (14, 1024) => Ok(size_of::<ConcurrentMerkleTree<14, 1024>>()),
-        (14, 2048) => Ok(size_of::<ConcurrentMerkleTree<14, 2048>>()),
-        (15, 64) => Ok(size_of::<ConcurrentMerkleTree<15, 64>>()),
-        (16, 64) => Ok(size_of::<ConcurrentMerkleTree<16, 64>>()),
-        (17, 64) => Ok(size_of::<ConcurrentMerkleTree<17, 64>>()),
-        (18, 64) => Ok(size_of::<ConcurrentMerkleTree<18, 64>>()),
-        (19, 64) => Ok(size_of::<ConcurrentMerkleTree<19, 64>>()),
-        (20, 64) => Ok(size_of::<ConcurrentMerkleTree<20, 64>>()),
-        (20, 256) => Ok(size_of::<ConcurrentMerkleTree<20, 256>>()),
-        (20, 1024) => Ok(size_of::<ConcurrentMerkleTree<20, 1024>>()),
-        (20, 2048) => Ok(size_of::<ConcurrentMerkleTree<20, 2048>>()),
-        (24, 64) => Ok(size_of::<ConcurrentMerkleTree<24, 64>>()),
-        (24, 256) => Ok(size_of::<ConcurrentMerkleTree<24, 256>>()),
-        (24, 512) => Ok(size_of::<ConcurrentMerkleTree<24, 512>>()),
-        (24, 1024) => Ok(size_of::<ConcurrentMerkleTree<24, 1024>>()),
-        (24, 2048) => Ok(size_of::<ConcurrentMerkleTree<24, 2048>>()),
-        (26, 512) => Ok(size_of::<ConcurrentMerkleTree<26, 512>>()),
-        (26, 1024) => Ok(size_of::<ConcurrentMerkleTree<26, 1024>>()),
-        (26, 2048) => Ok(size_of::<ConcurrentMerkleTree<26, 2048>>()),
-        (30, 512) => Ok(size_of::<ConcurrentMerkleTree<30, 512>>()),
-        (30, 1024) => Ok(size_of::<ConcurrentMerkleTree<30, 1024>>()),
-        (30, 2048) => Ok(size_of::<ConcurrentMerkleTree<30, 2048>>()),
-        _ => {
-            msg!(
-                "Failed to get size of max depth {} and max buffer size {}",
-                header.get_max_depth(),
-                header.get_max_buffer_size()
-            );
-            err!(AccountCompressionError::ConcurrentMerkleTreeConstantsError)
-        }
+/// Returns the size of the versioned header stored at the start of the tree account.
+/// The version is read from the byte following the account type, so zero'd
+/// (uninitialized) accounts report the V1 size.
+pub fn merkle_tree_get_header_size(merkle_tree_bytes: &[u8]) -> Result<usize> {
+    match merkle_tree_bytes.get(1) {
+        Some(0) => Ok(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1),
+        Some(1) => Ok(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V2),
+        Some(2) => Ok(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V3),
+        _ => err!(AccountCompressionError::IncorrectAccountType),
     }
 }
+
+/// (max depth, max buffer size) pairs of the SPL ConcurrentMerkleTrees supported by the program
+pub const SUPPORTED_DEPTH_SIZE_PAIRS: &[(u32, u32)] =
+    _merkle_tree_depth_size_pairs!(_merkle_tree_depth_size_pairs_slice,);
+
+pub fn merkle_tree_get_size(header: &ConcurrentMerkleTreeHeader) -> Result<usize> {
+    _merkle_tree_depth_size_pairs!(_merkle_tree_get_size_for_pairs, header)
+}
@@ -1,6 +1,10 @@
 //! State needed to manipulate SPL ConcurrentMerkleTrees
 mod concurrent_merkle_tree_header;
 mod path_node;
+mod tree_config;
+mod tree_data;
 
 pub use concurrent_merkle_tree_header::*;
 pub use path_node::PathNode;
+pub use tree_config::TreeConfig;
+pub use tree_data::{ChangeLogEntry, RightmostProof};
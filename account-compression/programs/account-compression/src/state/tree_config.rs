@@ -0,0 +1,42 @@
+use anchor_lang::{prelude::*, solana_program::program::get_return_data};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::error::AccountCompressionError;
+
+/// Configuration of a SPL ConcurrentMerkleTree, written to return data by the
+/// `get_tree_config` instruction so that composing programs do not have to
+/// parse the tree header themselves.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq)]
+pub struct TreeConfig {
+    /// Depth of the tree, its capacity is power(2, max_depth)
+    pub max_depth: u32,
+
+    /// Size of the changelog buffer stored on-chain
+    pub max_buffer_size: u32,
+
+    /// Number of upper levels of the tree cached in the canopy
+    pub canopy_depth: u32,
+
+    /// Authority that controls write-access to the tree
+    pub authority: Pubkey,
+
+    /// Slot corresponding to when the tree was created
+    pub creation_slot: u64,
+
+    /// Number of modifications applied to the tree
+    pub sequence_number: u64,
+}
+
+impl TreeConfig {
+    /// Decodes the tree config from the return data left by a `get_tree_config` CPI
+    pub fn from_return_data() -> Result<Self> {
+        let (program_id, data) =
+            get_return_data().ok_or(AccountCompressionError::InvalidReturnData)?;
+        require_keys_eq!(
+            program_id,
+            crate::id(),
+            AccountCompressionError::InvalidReturnData
+        );
+        Self::try_from_slice(&data).map_err(|_| AccountCompressionError::InvalidReturnData.into())
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::{prelude::*, solana_program::program::get_return_data};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::error::AccountCompressionError;
+
+/// Proof of the rightmost leaf of a SPL ConcurrentMerkleTree, written to return
+/// data by the `read_rightmost_proof` instruction. Appending to the tree only
+/// requires this proof, so it can be used to rebuild the tree without an indexer.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq)]
+pub struct RightmostProof {
+    /// Sibling nodes from the rightmost leaf up to the root, `max_depth` nodes
+    pub proof: Vec<[u8; 32]>,
+
+    /// Rightmost leaf of the tree
+    pub leaf: [u8; 32],
+
+    /// Number of leaves appended to the tree, the index of the next append
+    pub index: u32,
+}
+
+impl RightmostProof {
+    /// Decodes the rightmost proof from the return data left by a
+    /// `read_rightmost_proof` CPI
+    pub fn from_return_data() -> Result<Self> {
+        decode_return_data()
+    }
+}
+
+/// Changelog of a SPL ConcurrentMerkleTree, written to return data by the
+/// `read_changelog_entry` instruction
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq)]
+pub struct ChangeLogEntry {
+    /// Root of the tree after the change
+    pub root: [u8; 32],
+
+    /// Nodes of the path from the changed leaf up to the root, excluded, starting
+    /// with the leaf
+    pub path: Vec<[u8; 32]>,
+
+    /// Index of the changed leaf
+    pub index: u32,
+
+    /// Sequence number of the tree after the change
+    pub sequence_number: u64,
+}
+
+impl ChangeLogEntry {
+    /// Decodes the changelog from the return data left by a
+    /// `read_changelog_entry` CPI
+    pub fn from_return_data() -> Result<Self> {
+        decode_return_data()
+    }
+}
+
+fn decode_return_data<T: BorshDeserialize>() -> Result<T> {
+    let (program_id, data) = get_return_data().ok_or(AccountCompressionError::InvalidReturnData)?;
+    require_keys_eq!(
+        program_id,
+        crate::id(),
+        AccountCompressionError::InvalidReturnData
+    );
+    T::try_from_slice(&data).map_err(|_| AccountCompressionError::InvalidReturnData.into())
+}
@@ -0,0 +1,148 @@
+//! Tree metrics is an optional companion account of a SPL ConcurrentMerkleTree that counts the
+//! leaves written to the tree, so that operators can monitor the usage of a tree without indexing
+//! every transaction.
+//!
+//! The metrics account is a PDA of the tree, derived from `[TREE_METRICS_SEED, merkle_tree]`. It
+//! is updated by the instructions writing leaves when passed as the first of the "remaining
+//! accounts", before the proof nodes, and ignored otherwise:
+//! - `append`, `append_batch`, `append_leaf_pair`, `append_subtree` and `check_and_append` count
+//!   the appended leaves
+//! - `replace_leaf`, `replace_leaf_pair` and `check_and_replace_leaf` count one replaced leaf
+//! - `insert_or_append` counts a replaced leaf if the leaf is written at the given index, and an
+//!   appended leaf otherwise
+
+use crate::error::AccountCompressionError;
+use crate::state::CompressionAccountType;
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Seed of the metrics PDA of a tree
+pub const TREE_METRICS_SEED: &[u8] = b"tree_metrics";
+
+/// Size of the serialized tree metrics
+pub const TREE_METRICS_SIZE: usize = 1 + 32 + 8 + 8 + 8;
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct TreeMetrics {
+    /// Account type
+    pub account_type: CompressionAccountType,
+    /// Tree whose writes are counted
+    pub merkle_tree: Pubkey,
+    /// Number of leaves appended to the tree
+    pub num_appends: u64,
+    /// Number of leaves replaced in the tree
+    pub num_replaces: u64,
+    /// Slot of the last write to the tree
+    pub last_write_slot: u64,
+}
+
+impl TreeMetrics {
+    pub fn new(merkle_tree: &Pubkey) -> Self {
+        Self {
+            account_type: CompressionAccountType::TreeMetrics,
+            merkle_tree: *merkle_tree,
+            num_appends: 0,
+            num_replaces: 0,
+            last_write_slot: 0,
+        }
+    }
+}
+
+pub fn find_tree_metrics_address(merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREE_METRICS_SEED, merkle_tree.as_ref()], &crate::id())
+}
+
+/// Initializes the metrics account data of `merkle_tree`
+pub fn tree_metrics_initialize(tree_metrics_bytes: &mut [u8], merkle_tree: &Pubkey) -> Result<()> {
+    require_eq!(
+        tree_metrics_bytes.len(),
+        TREE_METRICS_SIZE,
+        AccountCompressionError::IncorrectAccountType
+    );
+    let mut tree_metrics_bytes = tree_metrics_bytes;
+    TreeMetrics::new(merkle_tree).serialize(&mut tree_metrics_bytes)?;
+    Ok(())
+}
+
+/// Splits the metrics account of `merkle_tree` off the remaining accounts of an instruction, if
+/// it is the first of them. The other remaining accounts are the proof nodes.
+pub fn split_tree_metrics<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    merkle_tree: &Pubkey,
+) -> (Option<&'a AccountInfo<'info>>, &'a [AccountInfo<'info>]) {
+    match remaining_accounts.split_first() {
+        Some((first, rest)) if is_tree_metrics_account(first, merkle_tree) => (Some(first), rest),
+        _ => (None, remaining_accounts),
+    }
+}
+
+/// Proof nodes are arbitrary addresses that are not owned by the program, so only the metrics
+/// PDA of the tree can hold metrics data for it
+fn is_tree_metrics_account(account: &AccountInfo, merkle_tree: &Pubkey) -> bool {
+    if *account.owner != crate::id() {
+        return false;
+    }
+    let Ok(data) = account.try_borrow_data() else {
+        return false;
+    };
+    data.len() == TREE_METRICS_SIZE
+        && TreeMetrics::try_from_slice(&data).is_ok_and(|tree_metrics| {
+            tree_metrics.account_type == CompressionAccountType::TreeMetrics
+                && tree_metrics.merkle_tree == *merkle_tree
+        })
+}
+
+/// Adds `num_appends` appended and `num_replaces` replaced leaves written at `slot` to the
+/// metrics account data
+pub fn tree_metrics_record(
+    tree_metrics_bytes: &mut [u8],
+    num_appends: u64,
+    num_replaces: u64,
+    slot: u64,
+) -> Result<()> {
+    let mut tree_metrics = TreeMetrics::try_from_slice(tree_metrics_bytes)?;
+    require!(
+        tree_metrics.account_type == CompressionAccountType::TreeMetrics,
+        AccountCompressionError::IncorrectAccountType
+    );
+    tree_metrics.num_appends = tree_metrics.num_appends.saturating_add(num_appends);
+    tree_metrics.num_replaces = tree_metrics.num_replaces.saturating_add(num_replaces);
+    tree_metrics.last_write_slot = slot;
+
+    let mut tree_metrics_bytes = tree_metrics_bytes;
+    tree_metrics.serialize(&mut tree_metrics_bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_metrics_size() {
+        let tree_metrics = TreeMetrics::new(&Pubkey::new_unique());
+        assert_eq!(tree_metrics.try_to_vec().unwrap().len(), TREE_METRICS_SIZE);
+    }
+
+    #[test]
+    fn test_tree_metrics_record() {
+        let merkle_tree = Pubkey::new_unique();
+        let mut bytes = vec![0; TREE_METRICS_SIZE];
+        tree_metrics_initialize(&mut bytes, &merkle_tree).unwrap();
+
+        tree_metrics_record(&mut bytes, 3, 0, 10).unwrap();
+        tree_metrics_record(&mut bytes, 0, 1, 12).unwrap();
+
+        let tree_metrics = TreeMetrics::try_from_slice(&bytes).unwrap();
+        assert_eq!(tree_metrics.merkle_tree, merkle_tree);
+        assert_eq!(tree_metrics.num_appends, 3);
+        assert_eq!(tree_metrics.num_replaces, 1);
+        assert_eq!(tree_metrics.last_write_slot, 12);
+    }
+
+    #[test]
+    fn test_tree_metrics_record_uninitialized() {
+        let mut bytes = vec![0; TREE_METRICS_SIZE];
+        assert!(tree_metrics_record(&mut bytes, 1, 0, 1).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+import * as beet from '@metaplex-foundation/beet';
+import * as beetSolana from '@metaplex-foundation/beet-solana';
+import { Commitment, Connection, GetAccountInfoConfig, PublicKey } from '@solana/web3.js';
+import { BN } from 'bn.js';
+
+import { CompressionAccountType, compressionAccountTypeBeet, PROGRAM_ID } from '../generated';
+
+/**
+ * Seed of the bloom filter PDA of a tree
+ */
+export const BLOOM_FILTER_SEED = 'bloom_filter';
+
+/**
+ * Returns the address of the bloom filter of a tree
+ * @param merkleTree
+ * @param programId
+ * @returns
+ */
+export function getBloomFilterAddress(merkleTree: PublicKey, programId = PROGRAM_ID): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from(BLOOM_FILTER_SEED), merkleTree.toBuffer()], programId)[0];
+}
+
+type BloomFilterHeader = {
+    accountType: CompressionAccountType;
+    merkleTree: PublicKey;
+    numBits: number;
+    numHashes: number;
+    numInserted: beet.bignum;
+};
+
+const bloomFilterHeaderBeet = new beet.BeetArgsStruct<BloomFilterHeader>(
+    [
+        ['accountType', compressionAccountTypeBeet],
+        ['merkleTree', beetSolana.publicKey],
+        ['numBits', beet.u32],
+        ['numHashes', beet.u8],
+        ['numInserted', beet.u64],
+    ],
+    'BloomFilterHeader',
+);
+
+/**
+ * This class provides the getter methods to deserialize the header
+ * of the on-chain bloom filter of a ConcurrentMerkleTree
+ */
+export class BloomFilterAccount {
+    public header: BloomFilterHeader;
+
+    constructor(header: BloomFilterHeader) {
+        this.header = header;
+    }
+
+    static fromBuffer(buffer: Buffer): BloomFilterAccount {
+        const [header] = bloomFilterHeaderBeet.deserialize(buffer);
+        if (header.accountType !== CompressionAccountType.BloomFilter) {
+            throw new Error('Account is not a bloom filter');
+        }
+        return new BloomFilterAccount(header);
+    }
+
+    static async fromAccountAddress(
+        connection: Connection,
+        publicKey: PublicKey,
+        commitmentOrConfig?: Commitment | GetAccountInfoConfig,
+    ): Promise<BloomFilterAccount> {
+        const account = await connection.getAccountInfo(publicKey, commitmentOrConfig);
+        if (!account) {
+            throw new Error('Bloom filter account data unexpectedly null!');
+        }
+        return BloomFilterAccount.fromBuffer(account.data);
+    }
+
+    /**
+     * Returns the tree whose leaves are tracked by the bloom filter
+     * @returns
+     */
+    getMerkleTree(): PublicKey {
+        return this.header.merkleTree;
+    }
+
+    /**
+     * Returns the number of bits of the bloom filter
+     * @returns
+     */
+    getNumBits(): number {
+        return this.header.numBits;
+    }
+
+    /**
+     * Returns the number of bits set per leaf
+     * @returns
+     */
+    getNumHashes(): number {
+        return this.header.numHashes;
+    }
+
+    /**
+     * Returns the number of leaves inserted into the bloom filter
+     * @returns
+     */
+    getNumInserted(): BN {
+        return new BN.BN(this.header.numInserted);
+    }
+}
@@ -1,7 +1,15 @@
 import type { Commitment, Connection, GetAccountInfoConfig, PublicKey } from '@solana/web3.js';
 import { BN } from 'bn.js';
 
-import { ConcurrentMerkleTreeHeaderDataV1, concurrentMerkleTreeHeaderDataV1Beet } from '../generated';
+import { SPL_NOOP_PROGRAM_ID } from '../constants';
+import {
+    ConcurrentMerkleTreeHeaderDataV1,
+    concurrentMerkleTreeHeaderDataV1Beet,
+    ConcurrentMerkleTreeHeaderDataV2,
+    concurrentMerkleTreeHeaderDataV2Beet,
+    ConcurrentMerkleTreeHeaderDataV3,
+    concurrentMerkleTreeHeaderDataV3Beet,
+} from '../generated';
 import {
     ConcurrentMerkleTreeHeader,
     concurrentMerkleTreeHeaderBeet,
@@ -39,7 +47,10 @@ export class ConcurrentMerkleTreeAccount {
         return deserializeConcurrentMerkleTree(account.data);
     }
 
-    private getHeaderV1(): ConcurrentMerkleTreeHeaderDataV1 {
+    private getHeaderData():
+        | ConcurrentMerkleTreeHeaderDataV1
+        | ConcurrentMerkleTreeHeaderDataV2
+        | ConcurrentMerkleTreeHeaderDataV3 {
         return this.header.header.fields[0];
     }
 
@@ -48,7 +59,7 @@ export class ConcurrentMerkleTreeAccount {
      * @returns
      */
     getMaxBufferSize(): number {
-        return this.getHeaderV1().maxBufferSize;
+        return this.getHeaderData().maxBufferSize;
     }
 
     /**
@@ -56,7 +67,7 @@ export class ConcurrentMerkleTreeAccount {
      * @returns
      */
     getMaxDepth(): number {
-        return this.getHeaderV1().maxDepth;
+        return this.getHeaderData().maxDepth;
     }
 
     /**
@@ -92,7 +103,7 @@ export class ConcurrentMerkleTreeAccount {
      * @returns
      */
     getAuthority(): PublicKey {
-        return this.getHeaderV1().authority;
+        return this.getHeaderData().authority;
     }
 
     /**
@@ -101,7 +112,7 @@ export class ConcurrentMerkleTreeAccount {
      * @returns
      */
     getCreationSlot() {
-        return new BN(this.getHeaderV1().creationSlot);
+        return new BN(this.getHeaderData().creationSlot);
     }
 
     /**
@@ -127,7 +138,49 @@ export class ConcurrentMerkleTreeAccount {
      * @returns the flag
      */
     getIsBatchInitialized(): boolean {
-        return this.getHeaderV1().isBatchInitialized;
+        return this.getHeaderData().isBatchInitialized;
+    }
+
+    /**
+     * Returns whether the leaves of the tree are written as 64-byte (key, value)
+     * pairs, i.e. whether the tree was created with `init_empty_merkle_tree_with_pair_leaves`
+     * @returns
+     */
+    getHasPairLeaves(): boolean {
+        return this.getHeaderData().hasPairLeaves;
+    }
+
+    /**
+     * Returns the program that the tree emits its changelog events to, which
+     * is SPL Noop unless another program was provided when creating the tree
+     * @returns the program id
+     */
+    getNoopProgram(): PublicKey {
+        const header = this.header.header;
+        switch (header.__kind) {
+            case 'V1':
+                return SPL_NOOP_PROGRAM_ID;
+            case 'V2':
+            case 'V3':
+                return header.fields[0].noopProgram;
+        }
+    }
+
+    /**
+     * Returns the authority before the last rotation, which is still accepted
+     * for modifying the tree until `expirySlot`, or `null` if the authority of
+     * the tree was never rotated
+     * @returns the previous authority and the first slot it is no longer accepted
+     */
+    getPreviousAuthority(): { authority: PublicKey; expirySlot: BN } | null {
+        const header = this.header.header;
+        if (header.__kind !== 'V3') {
+            return null;
+        }
+        return {
+            authority: header.fields[0].previousAuthority,
+            expirySlot: new BN(header.fields[0].previousAuthorityExpirySlot),
+        };
     }
 }
 
@@ -150,10 +203,6 @@ function deserializeConcurrentMerkleTree(buffer: Buffer): ConcurrentMerkleTreeAc
     const [versionedHeader, offsetIncr] = concurrentMerkleTreeHeaderBeet.deserialize(buffer);
     offset = offsetIncr;
 
-    // Only 1 version available
-    if (versionedHeader.header.__kind !== 'V1') {
-        throw Error(`Header has unsupported version: ${versionedHeader.header.__kind}`);
-    }
     const header = versionedHeader.header.fields[0];
     const [tree, offsetIncr2] = concurrentMerkleTreeBeetFactory(header.maxDepth, header.maxBufferSize).deserialize(
         buffer,
@@ -182,7 +231,8 @@ function deserializeConcurrentMerkleTree(buffer: Buffer): ConcurrentMerkleTreeAc
  * @param maxDepth
  * @param maxBufferSize
  * @param canopyDepth
- * @param headerVersion
+ * @param headerVersion 'V2' for trees emitting their events to a program other than SPL Noop, 'V3' for trees
+ * whose authority was rotated
  * @returns
  */
 export function getConcurrentMerkleTreeAccountSize(
@@ -191,8 +241,19 @@ export function getConcurrentMerkleTreeAccountSize(
     canopyDepth?: number,
     headerVersion = 'V1',
 ): number {
-    if (headerVersion != 'V1') {
-        throw Error('Unsupported header version');
+    let headerDataSize: number;
+    switch (headerVersion) {
+        case 'V1':
+            headerDataSize = concurrentMerkleTreeHeaderDataV1Beet.byteSize;
+            break;
+        case 'V2':
+            headerDataSize = concurrentMerkleTreeHeaderDataV2Beet.byteSize;
+            break;
+        case 'V3':
+            headerDataSize = concurrentMerkleTreeHeaderDataV3Beet.byteSize;
+            break;
+        default:
+            throw Error('Unsupported header version');
     }
 
     // The additional 2 bytes are needed for
@@ -200,7 +261,7 @@ export function getConcurrentMerkleTreeAccountSize(
     // - the header version       (1 byte)
     return (
         2 +
-        concurrentMerkleTreeHeaderDataV1Beet.byteSize +
+        headerDataSize +
         concurrentMerkleTreeBeetFactory(maxDepth, maxBufferSize).byteSize +
         (canopyDepth ? canopyBeetFactory(canopyDepth).byteSize : 0)
     );
@@ -0,0 +1,104 @@
+import * as beet from '@metaplex-foundation/beet';
+import * as beetSolana from '@metaplex-foundation/beet-solana';
+import { Commitment, Connection, GetAccountInfoConfig, PublicKey } from '@solana/web3.js';
+import { BN } from 'bn.js';
+
+import { CompressionAccountType, compressionAccountTypeBeet, PROGRAM_ID } from '../generated';
+
+/**
+ * Seed of the metrics PDA of a tree
+ */
+export const TREE_METRICS_SEED = 'tree_metrics';
+
+/**
+ * Returns the address of the metrics account of a tree
+ * @param merkleTree
+ * @param programId
+ * @returns
+ */
+export function getTreeMetricsAddress(merkleTree: PublicKey, programId = PROGRAM_ID): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from(TREE_METRICS_SEED), merkleTree.toBuffer()], programId)[0];
+}
+
+type TreeMetrics = {
+    accountType: CompressionAccountType;
+    merkleTree: PublicKey;
+    numAppends: beet.bignum;
+    numReplaces: beet.bignum;
+    lastWriteSlot: beet.bignum;
+};
+
+const treeMetricsBeet = new beet.BeetArgsStruct<TreeMetrics>(
+    [
+        ['accountType', compressionAccountTypeBeet],
+        ['merkleTree', beetSolana.publicKey],
+        ['numAppends', beet.u64],
+        ['numReplaces', beet.u64],
+        ['lastWriteSlot', beet.u64],
+    ],
+    'TreeMetrics',
+);
+
+/**
+ * This class provides the getter methods to deserialize
+ * the on-chain metrics account of a ConcurrentMerkleTree
+ */
+export class TreeMetricsAccount {
+    public metrics: TreeMetrics;
+
+    constructor(metrics: TreeMetrics) {
+        this.metrics = metrics;
+    }
+
+    static fromBuffer(buffer: Buffer): TreeMetricsAccount {
+        const [metrics] = treeMetricsBeet.deserialize(buffer);
+        if (metrics.accountType !== CompressionAccountType.TreeMetrics) {
+            throw new Error('Account is not a tree metrics account');
+        }
+        return new TreeMetricsAccount(metrics);
+    }
+
+    static async fromAccountAddress(
+        connection: Connection,
+        publicKey: PublicKey,
+        commitmentOrConfig?: Commitment | GetAccountInfoConfig,
+    ): Promise<TreeMetricsAccount> {
+        const account = await connection.getAccountInfo(publicKey, commitmentOrConfig);
+        if (!account) {
+            throw new Error('Tree metrics account data unexpectedly null!');
+        }
+        return TreeMetricsAccount.fromBuffer(account.data);
+    }
+
+    /**
+     * Returns the tree whose writes are counted
+     * @returns
+     */
+    getMerkleTree(): PublicKey {
+        return this.metrics.merkleTree;
+    }
+
+    /**
+     * Returns the number of leaves appended to the tree
+     * @returns
+     */
+    getNumAppends(): BN {
+        return new BN.BN(this.metrics.numAppends);
+    }
+
+    /**
+     * Returns the number of leaves replaced in the tree
+     * @returns
+     */
+    getNumReplaces(): BN {
+        return new BN.BN(this.metrics.numReplaces);
+    }
+
+    /**
+     * Returns the slot of the last write to the tree
+     * @returns
+     */
+    getLastWriteSlot(): BN {
+        return new BN.BN(this.metrics.lastWriteSlot);
+    }
+}
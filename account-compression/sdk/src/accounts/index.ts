@@ -1 +1,3 @@
+export * from './BloomFilterAccount';
 export * from './ConcurrentMerkleTreeAccount';
+export * from './TreeMetricsAccount';
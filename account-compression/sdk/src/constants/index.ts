@@ -15,23 +15,34 @@ export type DepthSizePair = {
 
 const allPairs: number[][] = [
     [3, 8],
+    [4, 8],
     [5, 8],
+    [5, 16],
     [6, 16],
+    [6, 32],
     [7, 16],
     [8, 16],
+    [8, 32],
+    [8, 64],
     [9, 16],
     [10, 32],
+    [10, 64],
     [11, 32],
     [12, 32],
+    [12, 64],
     [13, 32],
+    [13, 64],
     [14, 64],
+    [14, 128],
     [14, 256],
     [14, 1024],
     [14, 2048],
     [15, 64],
     [16, 64],
+    [16, 256],
     [17, 64],
     [18, 64],
+    [18, 256],
     [19, 64],
     [20, 64],
     [20, 256],
@@ -62,6 +73,50 @@ export const ALL_DEPTH_SIZE_PAIRS: ValidDepthSizePair[] = allPairs.map(pair => {
 
 export type ValidDepthSizePair =
     | { maxBufferSize: 8; maxDepth: 3 }
+    | { maxBufferSize: 8; maxDepth: 4 }
+    | { maxBufferSize: 8; maxDepth: 5 }
+    | { maxBufferSize: 16; maxDepth: 5 }
+    | { maxBufferSize: 16; maxDepth: 6 }
+    | { maxBufferSize: 16; maxDepth: 7 }
+    | { maxBufferSize: 16; maxDepth: 8 }
+    | { maxBufferSize: 16; maxDepth: 9 }
+    | { maxBufferSize: 32; maxDepth: 6 }
+    | { maxBufferSize: 32; maxDepth: 8 }
+    | { maxBufferSize: 32; maxDepth: 10 }
+    | { maxBufferSize: 32; maxDepth: 11 }
+    | { maxBufferSize: 32; maxDepth: 12 }
+    | { maxBufferSize: 32; maxDepth: 13 }
+    | { maxBufferSize: 64; maxDepth: 8 }
+    | { maxBufferSize: 64; maxDepth: 10 }
+    | { maxBufferSize: 64; maxDepth: 12 }
+    | { maxBufferSize: 64; maxDepth: 13 }
+    | { maxBufferSize: 64; maxDepth: 14 }
+    | { maxBufferSize: 64; maxDepth: 15 }
+    | { maxBufferSize: 64; maxDepth: 16 }
+    | { maxBufferSize: 64; maxDepth: 17 }
+    | { maxBufferSize: 64; maxDepth: 18 }
+    | { maxBufferSize: 64; maxDepth: 19 }
+    | { maxBufferSize: 64; maxDepth: 20 }
+    | { maxBufferSize: 64; maxDepth: 24 }
+    | { maxBufferSize: 128; maxDepth: 14 }
+    | { maxBufferSize: 256; maxDepth: 14 }
+    | { maxBufferSize: 256; maxDepth: 16 }
+    | { maxBufferSize: 256; maxDepth: 18 }
+    | { maxBufferSize: 256; maxDepth: 20 }
+    | { maxBufferSize: 256; maxDepth: 24 }
+    | { maxBufferSize: 512; maxDepth: 24 }
+    | { maxBufferSize: 512; maxDepth: 26 }
+    | { maxBufferSize: 512; maxDepth: 30 }
+    | { maxBufferSize: 1024; maxDepth: 14 }
+    | { maxBufferSize: 1024; maxDepth: 20 }
+    | { maxBufferSize: 1024; maxDepth: 24 }
+    | { maxBufferSize: 1024; maxDepth: 26 }
+    | { maxBufferSize: 1024; maxDepth: 30 }
+    | { maxBufferSize: 2048; maxDepth: 14 }
+    | { maxBufferSize: 2048; maxDepth: 20 }
+    | { maxBufferSize: 2048; maxDepth: 24 }
+    | { maxBufferSize: 2048; maxDepth: 26 }
+    | { maxBufferSize: 2048; maxDepth: 30 }; maxDepth: 3 }
     | { maxBufferSize: 8; maxDepth: 5 }
     | { maxBufferSize: 16; maxDepth: 6 }
     | { maxBufferSize: 16; maxDepth: 7 }
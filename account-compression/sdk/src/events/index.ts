@@ -1,6 +1,6 @@
 import BN from 'bn.js';
 
-import { ApplicationDataEvent, ChangeLogEventV1 as CLV1 } from '../generated';
+import { AccountCompressionEventV2, ApplicationDataEvent, ChangeLogEventV1 as CLV1 } from '../generated';
 import { accountCompressionEventBeet } from '../generated/types/AccountCompressionEvent';
 import { ChangeLogEventV1 } from '../types';
 
@@ -40,3 +40,19 @@ export function deserializeApplicationDataEvent(data: Buffer): ApplicationDataEv
             throw Error('Unable to decode buffer as ApplicationDataEvent');
     }
 }
+
+/**
+ * Helper function for indexing events logged via `wrap_event_v2` or `wrap_application_data_v2`
+ * @param data
+ * @returns
+ */
+export function deserializeAccountCompressionEventV2(data: Buffer): AccountCompressionEventV2 {
+    const event = accountCompressionEventBeet.toFixedFromData(data, 0).read(data, 0);
+    switch (event.__kind) {
+        case 'V2': {
+            return event.fields[0];
+        }
+        default:
+            throw Error('Unable to decode buffer as AccountCompressionEventV2');
+    }
+}
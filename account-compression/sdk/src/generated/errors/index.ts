@@ -294,6 +294,206 @@ export class CanopyRightmostLeafMismatchError extends Error {
 createErrorFromCodeLookup.set(0x177d, () => new CanopyRightmostLeafMismatchError());
 createErrorFromNameLookup.set('CanopyRightmostLeafMismatch', () => new CanopyRightmostLeafMismatchError());
 
+/**
+ * IncorrectNoopProgram: 'Provided noop program does not match the tree's configured noop program'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class IncorrectNoopProgramError extends Error {
+    readonly code: number = 0x177e;
+    readonly name: string = 'IncorrectNoopProgram';
+    constructor() {
+        super("Provided noop program does not match the tree's configured noop program");
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, IncorrectNoopProgramError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x177e, () => new IncorrectNoopProgramError());
+createErrorFromNameLookup.set('IncorrectNoopProgram', () => new IncorrectNoopProgramError());
+
+/**
+ * SubtreeOverlapsCanopy: 'Subtree depth exceeds the uncached depth of the tree'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class SubtreeOverlapsCanopyError extends Error {
+    readonly code: number = 0x177f;
+    readonly name: string = 'SubtreeOverlapsCanopy';
+    constructor() {
+        super('Subtree depth exceeds the uncached depth of the tree');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, SubtreeOverlapsCanopyError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x177f, () => new SubtreeOverlapsCanopyError());
+createErrorFromNameLookup.set('SubtreeOverlapsCanopy', () => new SubtreeOverlapsCanopyError());
+
+/**
+ * BloomFilterConfigError: 'An unsupported bloom filter size or number of hashes was provided'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class BloomFilterConfigErrorError extends Error {
+    readonly code: number = 0x1780;
+    readonly name: string = 'BloomFilterConfigError';
+    constructor() {
+        super('An unsupported bloom filter size or number of hashes was provided');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, BloomFilterConfigErrorError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x1780, () => new BloomFilterConfigErrorError());
+createErrorFromNameLookup.set('BloomFilterConfigError', () => new BloomFilterConfigErrorError());
+
+/**
+ * BloomFilterTreeMismatch: 'Bloom filter does not belong to the provided tree'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class BloomFilterTreeMismatchError extends Error {
+    readonly code: number = 0x1781;
+    readonly name: string = 'BloomFilterTreeMismatch';
+    constructor() {
+        super('Bloom filter does not belong to the provided tree');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, BloomFilterTreeMismatchError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x1781, () => new BloomFilterTreeMismatchError());
+createErrorFromNameLookup.set('BloomFilterTreeMismatch', () => new BloomFilterTreeMismatchError());
+
+/**
+ * BloomFilterProbableDuplicate: 'Leaf was probably already inserted according to the bloom filter'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class BloomFilterProbableDuplicateError extends Error {
+    readonly code: number = 0x1782;
+    readonly name: string = 'BloomFilterProbableDuplicate';
+    constructor() {
+        super('Leaf was probably already inserted according to the bloom filter');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, BloomFilterProbableDuplicateError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x1782, () => new BloomFilterProbableDuplicateError());
+createErrorFromNameLookup.set('BloomFilterProbableDuplicate', () => new BloomFilterProbableDuplicateError());
+
+/**
+ * InvalidReturnData: 'Return data does not contain a tree config from this program'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidReturnDataError extends Error {
+    readonly code: number = 0x1783;
+    readonly name: string = 'InvalidReturnData';
+    constructor() {
+        super('Return data does not contain a tree config from this program');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, InvalidReturnDataError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x1783, () => new InvalidReturnDataError());
+createErrorFromNameLookup.set('InvalidReturnData', () => new InvalidReturnDataError());
+
+/**
+ * IncorrectLeafType: 'Instruction does not match the leaf type of the tree'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class IncorrectLeafTypeError extends Error {
+    readonly code: number = 0x1784;
+    readonly name: string = 'IncorrectLeafType';
+    constructor() {
+        super('Instruction does not match the leaf type of the tree');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, IncorrectLeafTypeError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x1784, () => new IncorrectLeafTypeError());
+createErrorFromNameLookup.set('IncorrectLeafType', () => new IncorrectLeafTypeError());
+
+/**
+ * EmptyLeafBatch: 'Leaf batch is empty'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class EmptyLeafBatchError extends Error {
+    readonly code: number = 0x1785;
+    readonly name: string = 'EmptyLeafBatch';
+    constructor() {
+        super('Leaf batch is empty');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, EmptyLeafBatchError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x1785, () => new EmptyLeafBatchError());
+createErrorFromNameLookup.set('EmptyLeafBatch', () => new EmptyLeafBatchError());
+
+/**
+ * CanopyRepairProofMismatch: 'Canopy repair proof does not match the range of canopy nodes'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class CanopyRepairProofMismatchError extends Error {
+    readonly code: number = 0x1786;
+    readonly name: string = 'CanopyRepairProofMismatch';
+    constructor() {
+        super('Canopy repair proof does not match the range of canopy nodes');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, CanopyRepairProofMismatchError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x1786, () => new CanopyRepairProofMismatchError());
+createErrorFromNameLookup.set('CanopyRepairProofMismatch', () => new CanopyRepairProofMismatchError());
+
+/**
+ * ChangeLogIndexOutOfBounds: 'Changelog index is out of bounds of the changelog buffer'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class ChangeLogIndexOutOfBoundsError extends Error {
+    readonly code: number = 0x1787;
+    readonly name: string = 'ChangeLogIndexOutOfBounds';
+    constructor() {
+        super('Changelog index is out of bounds of the changelog buffer');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, ChangeLogIndexOutOfBoundsError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x1787, () => new ChangeLogIndexOutOfBoundsError());
+createErrorFromNameLookup.set('ChangeLogIndexOutOfBounds', () => new ChangeLogIndexOutOfBoundsError());
+
 /**
  * Attempts to resolve a custom program error from the provided error code.
  * @category Errors
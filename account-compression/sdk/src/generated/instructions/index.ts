@@ -1,10 +1,28 @@
 export * from './append';
+export * from './appendBatch';
 export * from './appendCanopyNodes';
+export * from './appendLeafPair';
+export * from './appendSubtree';
+export * from './checkAndAppend';
+export * from './checkAndReplaceLeaf';
+export * from './closeBloomFilter';
 export * from './closeEmptyTree';
+export * from './closeTreeMetrics';
+export * from './getTreeConfig';
+export * from './initBloomFilter';
 export * from './initEmptyMerkleTree';
+export * from './initEmptyMerkleTreeWithPairLeaves';
 export * from './initPreparedTreeWithRoot';
+export * from './initTreeMetrics';
 export * from './insertOrAppend';
 export * from './prepareBatchMerkleTree';
+export * from './proveLeafSoft';
+export * from './readChangelogEntry';
+export * from './readRightmostProof';
+export * from './repairCanopy';
 export * from './replaceLeaf';
+export * from './replaceLeafPair';
+export * from './resizeCanopy';
+export * from './rotateAuthority';
 export * from './transferAuthority';
 export * from './verifyLeaf';
@@ -7,6 +7,7 @@
 
 import * as beet from '@metaplex-foundation/beet';
 
+import { AccountCompressionEventV2, accountCompressionEventV2Beet } from './AccountCompressionEventV2';
 import { ApplicationDataEvent, applicationDataEventBeet } from './ApplicationDataEvent';
 import { ChangeLogEvent, changeLogEventBeet } from './ChangeLogEvent';
 /**
@@ -21,6 +22,8 @@ import { ChangeLogEvent, changeLogEventBeet } from './ChangeLogEvent';
 export type AccountCompressionEventRecord = {
     ApplicationData: { fields: [ApplicationDataEvent] };
     ChangeLog: { fields: [ChangeLogEvent] };
+    ChangeLogBatch: { fields: [ChangeLogEvent[]] };
+    V2: { fields: [AccountCompressionEventV2] };
 };
 
 /**
@@ -42,6 +45,12 @@ export const isAccountCompressionEventChangeLog = (
 export const isAccountCompressionEventApplicationData = (
     x: AccountCompressionEvent,
 ): x is AccountCompressionEvent & { __kind: 'ApplicationData' } => x.__kind === 'ApplicationData';
+export const isAccountCompressionEventChangeLogBatch = (
+    x: AccountCompressionEvent,
+): x is AccountCompressionEvent & { __kind: 'ChangeLogBatch' } => x.__kind === 'ChangeLogBatch';
+export const isAccountCompressionEventV2 = (
+    x: AccountCompressionEvent,
+): x is AccountCompressionEvent & { __kind: 'V2' } => x.__kind === 'V2';
 
 /**
  * @category userTypes
@@ -62,4 +71,18 @@ export const accountCompressionEventBeet = beet.dataEnum<AccountCompressionEvent
             'AccountCompressionEventRecord["ApplicationData"]',
         ),
     ],
+    [
+        'ChangeLogBatch',
+        new beet.FixableBeetArgsStruct<AccountCompressionEventRecord['ChangeLogBatch']>(
+            [['fields', beet.tuple([beet.array(changeLogEventBeet)])]],
+            'AccountCompressionEventRecord["ChangeLogBatch"]',
+        ),
+    ],
+    [
+        'V2',
+        new beet.FixableBeetArgsStruct<AccountCompressionEventRecord['V2']>(
+            [['fields', beet.tuple([accountCompressionEventV2Beet])]],
+            'AccountCompressionEventRecord["V2"]',
+        ),
+    ],
 ]) as beet.FixableBeet<AccountCompressionEvent, AccountCompressionEvent>;
@@ -11,6 +11,14 @@ import {
     ConcurrentMerkleTreeHeaderDataV1,
     concurrentMerkleTreeHeaderDataV1Beet,
 } from './ConcurrentMerkleTreeHeaderDataV1';
+import {
+    ConcurrentMerkleTreeHeaderDataV2,
+    concurrentMerkleTreeHeaderDataV2Beet,
+} from './ConcurrentMerkleTreeHeaderDataV2';
+import {
+    ConcurrentMerkleTreeHeaderDataV3,
+    concurrentMerkleTreeHeaderDataV3Beet,
+} from './ConcurrentMerkleTreeHeaderDataV3';
 /**
  * This type is used to derive the {@link ConcurrentMerkleTreeHeaderData} type as well as the de/serializer.
  * However don't refer to it in your code but use the {@link ConcurrentMerkleTreeHeaderData} type instead.
@@ -22,6 +30,8 @@ import {
  */
 export type ConcurrentMerkleTreeHeaderDataRecord = {
     V1: { fields: [ConcurrentMerkleTreeHeaderDataV1] };
+    V2: { fields: [ConcurrentMerkleTreeHeaderDataV2] };
+    V3: { fields: [ConcurrentMerkleTreeHeaderDataV3] };
 };
 
 /**
@@ -40,6 +50,12 @@ export type ConcurrentMerkleTreeHeaderData = beet.DataEnumKeyAsKind<ConcurrentMe
 export const isConcurrentMerkleTreeHeaderDataV1 = (
     x: ConcurrentMerkleTreeHeaderData,
 ): x is ConcurrentMerkleTreeHeaderData & { __kind: 'V1' } => x.__kind === 'V1';
+export const isConcurrentMerkleTreeHeaderDataV2 = (
+    x: ConcurrentMerkleTreeHeaderData,
+): x is ConcurrentMerkleTreeHeaderData & { __kind: 'V2' } => x.__kind === 'V2';
+export const isConcurrentMerkleTreeHeaderDataV3 = (
+    x: ConcurrentMerkleTreeHeaderData,
+): x is ConcurrentMerkleTreeHeaderData & { __kind: 'V3' } => x.__kind === 'V3';
 
 /**
  * @category userTypes
@@ -53,4 +69,18 @@ export const concurrentMerkleTreeHeaderDataBeet = beet.dataEnum<ConcurrentMerkle
             'ConcurrentMerkleTreeHeaderDataRecord["V1"]',
         ),
     ],
+    [
+        'V2',
+        new beet.BeetArgsStruct<ConcurrentMerkleTreeHeaderDataRecord['V2']>(
+            [['fields', beet.fixedSizeTuple([concurrentMerkleTreeHeaderDataV2Beet])]],
+            'ConcurrentMerkleTreeHeaderDataRecord["V2"]',
+        ),
+    ],
+    [
+        'V3',
+        new beet.BeetArgsStruct<ConcurrentMerkleTreeHeaderDataRecord['V3']>(
+            [['fields', beet.fixedSizeTuple([concurrentMerkleTreeHeaderDataV3Beet])]],
+            'ConcurrentMerkleTreeHeaderDataRecord["V3"]',
+        ),
+    ],
 ]) as beet.FixableBeet<ConcurrentMerkleTreeHeaderData, ConcurrentMerkleTreeHeaderData>;
@@ -11,10 +11,11 @@ import * as web3 from '@solana/web3.js';
 export type ConcurrentMerkleTreeHeaderDataV1 = {
     authority: web3.PublicKey;
     creationSlot: beet.bignum;
+    hasPairLeaves: boolean;
     isBatchInitialized: boolean;
     maxBufferSize: number;
     maxDepth: number;
-    padding: number[] /* size: 5 */;
+    padding: number[] /* size: 4 */;
 };
 
 /**
@@ -28,7 +29,8 @@ export const concurrentMerkleTreeHeaderDataV1Beet = new beet.BeetArgsStruct<Conc
         ['authority', beetSolana.publicKey],
         ['creationSlot', beet.u64],
         ['isBatchInitialized', beet.bool],
-        ['padding', beet.uniformFixedSizeArray(beet.u8, 5)],
+        ['hasPairLeaves', beet.bool],
+        ['padding', beet.uniformFixedSizeArray(beet.u8, 4)],
     ],
     'ConcurrentMerkleTreeHeaderDataV1',
 );
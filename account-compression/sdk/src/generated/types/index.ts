@@ -1,10 +1,17 @@
 export * from './AccountCompressionEvent';
+export * from './AccountCompressionEventV2';
 export * from './ApplicationDataEvent';
 export * from './ApplicationDataEventV1';
+export * from './ChangeLogEntry';
 export * from './ChangeLogEvent';
 export * from './ChangeLogEventV1';
 export * from './CompressionAccountType';
 export * from './ConcurrentMerkleTreeHeader';
 export * from './ConcurrentMerkleTreeHeaderData';
 export * from './ConcurrentMerkleTreeHeaderDataV1';
+export * from './ConcurrentMerkleTreeHeaderDataV2';
+export * from './ConcurrentMerkleTreeHeaderDataV3';
 export * from './PathNode';
+export * from './RightmostProof';
+export * from './TreeConfig';
+export * from './TreeType';
@@ -1,15 +1,34 @@
 import { Connection, PublicKey, SystemProgram, TransactionInstruction } from '@solana/web3.js';
+import BN from 'bn.js';
 
-import { getConcurrentMerkleTreeAccountSize } from '../accounts';
+import { getBloomFilterAddress, getConcurrentMerkleTreeAccountSize, getTreeMetricsAddress } from '../accounts';
 import { SPL_NOOP_PROGRAM_ID, ValidDepthSizePair } from '../constants';
 import {
+    createAppendBatchInstruction,
     createAppendCanopyNodesInstruction,
     createAppendInstruction,
+    createAppendLeafPairInstruction,
+    createAppendSubtreeInstruction,
+    createCheckAndAppendInstruction,
+    createCheckAndReplaceLeafInstruction,
+    createCloseBloomFilterInstruction,
     createCloseEmptyTreeInstruction,
+    createCloseTreeMetricsInstruction,
+    createGetTreeConfigInstruction,
+    createInitBloomFilterInstruction,
     createInitEmptyMerkleTreeInstruction,
+    createInitEmptyMerkleTreeWithPairLeavesInstruction,
     createInitPreparedTreeWithRootInstruction,
+    createInitTreeMetricsInstruction,
     createPrepareBatchMerkleTreeInstruction,
+    createProveLeafSoftInstruction,
+    createReadChangelogEntryInstruction,
+    createReadRightmostProofInstruction,
+    createRepairCanopyInstruction,
     createReplaceLeafInstruction,
+    createReplaceLeafPairInstruction,
+    createResizeCanopyInstruction,
+    createRotateAuthorityInstruction,
     createTransferAuthorityInstruction,
     createVerifyLeafInstruction,
     PROGRAM_ID,
@@ -33,24 +52,67 @@ export function addProof(instruction: TransactionInstruction, nodeProof: Buffer[
     return instruction;
 }
 
+/**
+ * Helper function that adds the metrics account of the tree to a TransactionInstruction
+ * writing leaves, so that the write is counted in the metrics account.
+ * The metrics account is inserted after the accounts of the instruction
+ * (the tree, its authority, the noop program and the bloom filter if any), before the proof nodes
+ */
+export function addTreeMetrics(instruction: TransactionInstruction, merkleTree: PublicKey): TransactionInstruction {
+    const index = instruction.keys[3]?.pubkey.equals(getBloomFilterAddress(merkleTree)) ? 4 : 3;
+    instruction.keys.splice(index, 0, {
+        isSigner: false,
+        isWritable: true,
+        pubkey: getTreeMetricsAddress(merkleTree),
+    });
+    return instruction;
+}
+
 /**
  * Helper function for {@link createInitEmptyMerkleTreeInstruction}
  *
  * @param merkleTree
  * @param authority
  * @param depthSizePair
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
  * @returns
  */
 export function createInitEmptyMerkleTreeIx(
     merkleTree: PublicKey,
     authority: PublicKey,
     depthSizePair: ValidDepthSizePair,
+    noop = SPL_NOOP_PROGRAM_ID,
 ): TransactionInstruction {
     return createInitEmptyMerkleTreeInstruction(
         {
             authority: authority,
             merkleTree,
-            noop: SPL_NOOP_PROGRAM_ID,
+            noop,
+        },
+        depthSizePair,
+    );
+}
+
+/**
+ * Helper function for {@link createInitEmptyMerkleTreeWithPairLeavesInstruction}
+ *
+ * @param merkleTree
+ * @param authority
+ * @param depthSizePair
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
+ * @returns
+ */
+export function createInitEmptyMerkleTreeWithPairLeavesIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    depthSizePair: ValidDepthSizePair,
+    noop = SPL_NOOP_PROGRAM_ID,
+): TransactionInstruction {
+    return createInitEmptyMerkleTreeWithPairLeavesInstruction(
+        {
+            authority: authority,
+            merkleTree,
+            noop,
         },
         depthSizePair,
     );
@@ -61,18 +123,20 @@ export function createInitEmptyMerkleTreeIx(
  * @param merkleTree
  * @param authority
  * @param depthSizePair
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
  * @returns
  */
 export function prepareTreeIx(
     merkleTree: PublicKey,
     authority: PublicKey,
     depthSizePair: ValidDepthSizePair,
+    noop = SPL_NOOP_PROGRAM_ID,
 ): TransactionInstruction {
     return createPrepareBatchMerkleTreeInstruction(
         {
             authority: authority,
             merkleTree,
-            noop: SPL_NOOP_PROGRAM_ID,
+            noop,
         },
         depthSizePair,
     );
@@ -113,6 +177,7 @@ export function createAppendCanopyNodesIx(
  * @param rightmostLeaf
  * @param rightmostIndex
  * @param proof
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
  * @returns
  */
 export function createInitPreparedTreeWithRootIx(
@@ -122,6 +187,7 @@ export function createInitPreparedTreeWithRootIx(
     rightmostLeaf: ArrayLike<number> | Buffer,
     rightmostIndex: number,
     proof: Buffer[],
+    noop = SPL_NOOP_PROGRAM_ID,
 ): TransactionInstruction {
     return createInitPreparedTreeWithRootInstruction(
         {
@@ -134,7 +200,7 @@ export function createInitPreparedTreeWithRootIx(
             }),
             authority,
             merkleTree,
-            noop: SPL_NOOP_PROGRAM_ID,
+            noop,
         },
         {
             rightmostIndex,
@@ -144,12 +210,71 @@ export function createInitPreparedTreeWithRootIx(
     );
 }
 
+/**
+ * Helper function for {@link createRepairCanopyInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param startIndex index of the first provided node of the lowest level of the canopy
+ * @param canopyNodes nodes of the lowest level of the canopy to rewrite
+ * @param proof siblings hashing the nodes up to the root, bottom-up with the left sibling of a level first
+ * @returns
+ */
+export function createRepairCanopyIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    startIndex: number,
+    canopyNodes: Buffer[],
+    proof: Buffer[],
+): TransactionInstruction {
+    return addProof(
+        createRepairCanopyInstruction(
+            {
+                authority,
+                merkleTree,
+                noop: SPL_NOOP_PROGRAM_ID,
+            },
+            {
+                canopyNodes: canopyNodes.map(node => Array.from(node)),
+                startIndex,
+            },
+        ),
+        proof,
+    );
+}
+
+/**
+ * Helper function for {@link createResizeCanopyInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param payer
+ * @param canopyDepth number of levels of the tree to cache once the canopy is resized
+ * @returns one step of the resize, repeated in the same transaction until the canopy reaches its new size
+ */
+export function createResizeCanopyIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    payer: PublicKey,
+    canopyDepth: number,
+): TransactionInstruction {
+    return createResizeCanopyInstruction(
+        {
+            authority,
+            merkleTree,
+            payer,
+        },
+        {
+            canopyDepth,
+        },
+    );
+}
+
 /**
  * Helper function for {@link createReplaceLeafInstruction}
  * @param merkleTree
  * @param authority
  * @param proof
  * @param newLeaf
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
  * @returns
  */
 export function createReplaceIx(
@@ -157,13 +282,14 @@ export function createReplaceIx(
     authority: PublicKey,
     newLeaf: Buffer,
     proof: MerkleTreeProof,
+    noop = SPL_NOOP_PROGRAM_ID,
 ): TransactionInstruction {
     return addProof(
         createReplaceLeafInstruction(
             {
                 authority: authority,
                 merkleTree,
-                noop: SPL_NOOP_PROGRAM_ID,
+                noop,
             },
             {
                 index: proof.leafIndex,
@@ -176,23 +302,63 @@ export function createReplaceIx(
     );
 }
 
+/**
+ * Helper function for {@link createReplaceLeafPairInstruction}, the new leaf node of the
+ * tree is `hash(leafKey, leafValue)`
+ * @param merkleTree
+ * @param authority
+ * @param leafKey
+ * @param leafValue
+ * @param proof
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
+ * @returns
+ */
+export function createReplaceLeafPairIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    leafKey: Buffer,
+    leafValue: Buffer,
+    proof: MerkleTreeProof,
+    noop = SPL_NOOP_PROGRAM_ID,
+): TransactionInstruction {
+    return addProof(
+        createReplaceLeafPairInstruction(
+            {
+                authority: authority,
+                merkleTree,
+                noop,
+            },
+            {
+                index: proof.leafIndex,
+                leafKey: Array.from(leafKey),
+                leafValue: Array.from(leafValue),
+                previousLeaf: Array.from(proof.leaf),
+                root: Array.from(proof.root),
+            },
+        ),
+        proof.proof,
+    );
+}
+
 /**
  * Helper function for {@link createAppendInstruction}
  * @param merkleTree
  * @param authority
  * @param newLeaf
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
  * @returns
  */
 export function createAppendIx(
     merkleTree: PublicKey,
     authority: PublicKey,
     newLeaf: ArrayLike<number> | Buffer,
+    noop = SPL_NOOP_PROGRAM_ID,
 ): TransactionInstruction {
     return createAppendInstruction(
         {
             authority: authority,
             merkleTree,
-            noop: SPL_NOOP_PROGRAM_ID,
+            noop,
         },
         {
             leaf: Array.from(newLeaf),
@@ -200,6 +366,247 @@ export function createAppendIx(
     );
 }
 
+/**
+ * Helper function for {@link createAppendLeafPairInstruction}, the appended leaf node of
+ * the tree is `hash(leafKey, leafValue)`
+ * @param merkleTree
+ * @param authority
+ * @param leafKey
+ * @param leafValue
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
+ * @returns
+ */
+export function createAppendLeafPairIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    leafKey: ArrayLike<number> | Buffer,
+    leafValue: ArrayLike<number> | Buffer,
+    noop = SPL_NOOP_PROGRAM_ID,
+): TransactionInstruction {
+    return createAppendLeafPairInstruction(
+        {
+            authority: authority,
+            merkleTree,
+            noop,
+        },
+        {
+            leafKey: Array.from(leafKey),
+            leafValue: Array.from(leafValue),
+        },
+    );
+}
+
+/**
+ * Helper function for {@link createAppendBatchInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param newLeaves
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
+ * @returns
+ */
+export function createAppendBatchIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    newLeaves: (ArrayLike<number> | Buffer)[],
+    noop = SPL_NOOP_PROGRAM_ID,
+): TransactionInstruction {
+    return createAppendBatchInstruction(
+        {
+            authority: authority,
+            merkleTree,
+            noop,
+        },
+        {
+            leaves: newLeaves.map(leaf => Array.from(leaf)),
+        },
+    );
+}
+
+/**
+ * Helper function for {@link createAppendSubtreeInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param rightmostProof proof of the rightmost non-empty leaf within the subtree,
+ * whose root and depth are those of the subtree
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
+ * @returns
+ */
+export function createAppendSubtreeIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    rightmostProof: MerkleTreeProof,
+    noop = SPL_NOOP_PROGRAM_ID,
+): TransactionInstruction {
+    return addProof(
+        createAppendSubtreeInstruction(
+            {
+                authority: authority,
+                merkleTree,
+                noop,
+            },
+            {
+                rightmostIndex: rightmostProof.leafIndex,
+                rightmostLeaf: Array.from(rightmostProof.leaf),
+                subtreeDepth: rightmostProof.proof.length,
+                subtreeRoot: Array.from(rightmostProof.root),
+            },
+        ),
+        rightmostProof.proof,
+    );
+}
+
+/**
+ * Helper function for {@link createInitBloomFilterInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param payer
+ * @param numBits number of bits of the bloom filter, a multiple of 8
+ * @param numHashes number of bits set per leaf
+ * @returns
+ */
+export function createInitBloomFilterIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    payer: PublicKey,
+    numBits: number,
+    numHashes: number,
+): TransactionInstruction {
+    return createInitBloomFilterInstruction(
+        {
+            authority,
+            bloomFilter: getBloomFilterAddress(merkleTree),
+            merkleTree,
+            payer,
+        },
+        {
+            numBits,
+            numHashes,
+        },
+    );
+}
+
+/**
+ * Helper function for {@link createCheckAndAppendInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param newLeaf
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
+ * @returns
+ */
+export function createCheckAndAppendIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    newLeaf: ArrayLike<number> | Buffer,
+    noop = SPL_NOOP_PROGRAM_ID,
+): TransactionInstruction {
+    return createCheckAndAppendInstruction(
+        {
+            authority,
+            bloomFilter: getBloomFilterAddress(merkleTree),
+            merkleTree,
+            noop,
+        },
+        {
+            leaf: Array.from(newLeaf),
+        },
+    );
+}
+
+/**
+ * Helper function for {@link createCheckAndReplaceLeafInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param newLeaf
+ * @param proof
+ * @param noop program receiving the changelog events of the tree, SPL Noop by default
+ * @returns
+ */
+export function createCheckAndReplaceIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    newLeaf: Buffer,
+    proof: MerkleTreeProof,
+    noop = SPL_NOOP_PROGRAM_ID,
+): TransactionInstruction {
+    return addProof(
+        createCheckAndReplaceLeafInstruction(
+            {
+                authority,
+                bloomFilter: getBloomFilterAddress(merkleTree),
+                merkleTree,
+                noop,
+            },
+            {
+                index: proof.leafIndex,
+                newLeaf: Array.from(newLeaf),
+                previousLeaf: Array.from(proof.leaf),
+                root: Array.from(proof.root),
+            },
+        ),
+        proof.proof,
+    );
+}
+
+/**
+ * Helper function for {@link createCloseBloomFilterInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param recipient
+ * @returns
+ */
+export function createCloseBloomFilterIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    recipient: PublicKey,
+): TransactionInstruction {
+    return createCloseBloomFilterInstruction({
+        authority,
+        bloomFilter: getBloomFilterAddress(merkleTree),
+        merkleTree,
+        recipient,
+    });
+}
+
+/**
+ * Helper function for {@link createInitTreeMetricsInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param payer
+ * @returns
+ */
+export function createInitTreeMetricsIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    payer: PublicKey,
+): TransactionInstruction {
+    return createInitTreeMetricsInstruction({
+        authority,
+        merkleTree,
+        payer,
+        treeMetrics: getTreeMetricsAddress(merkleTree),
+    });
+}
+
+/**
+ * Helper function for {@link createCloseTreeMetricsInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param recipient
+ * @returns
+ */
+export function createCloseTreeMetricsIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    recipient: PublicKey,
+): TransactionInstruction {
+    return createCloseTreeMetricsInstruction({
+        authority,
+        merkleTree,
+        recipient,
+        treeMetrics: getTreeMetricsAddress(merkleTree),
+    });
+}
+
 /**
  * Helper function for {@link createTransferAuthorityIx}
  * @param merkleTree
@@ -223,6 +630,35 @@ export function createTransferAuthorityIx(
     );
 }
 
+/**
+ * Helper function for {@link createRotateAuthorityInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param newAuthority
+ * @param overlapSlots number of slots during which `authority` is still accepted for modifying the tree
+ * @param payer pays for the rent of the larger tree header the first time the authority of a tree is rotated
+ * @returns
+ */
+export function createRotateAuthorityIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    newAuthority: PublicKey,
+    overlapSlots: number | BN,
+    payer: PublicKey,
+): TransactionInstruction {
+    return createRotateAuthorityInstruction(
+        {
+            authority,
+            merkleTree,
+            payer,
+        },
+        {
+            newAuthority,
+            overlapSlots,
+        },
+    );
+}
+
 /**
  * Helper function for {@link createVerifyLeafInstruction}
  * @param merkleTree
@@ -245,6 +681,70 @@ export function createVerifyLeafIx(merkleTree: PublicKey, proof: MerkleTreeProof
     );
 }
 
+/**
+ * Helper function for {@link createProveLeafSoftInstruction}
+ * @param merkleTree
+ * @param proof
+ * @returns
+ */
+export function createProveLeafSoftIx(merkleTree: PublicKey, proof: MerkleTreeProof): TransactionInstruction {
+    return addProof(
+        createProveLeafSoftInstruction(
+            {
+                merkleTree,
+            },
+            {
+                index: proof.leafIndex,
+                leaf: Array.from(proof.leaf),
+                root: Array.from(proof.root),
+            },
+        ),
+        proof.proof,
+    );
+}
+
+/**
+ * Helper function for {@link createGetTreeConfigInstruction}, the config is written
+ * to return data and can be decoded with {@link treeConfigBeet}
+ * @param merkleTree
+ * @returns
+ */
+export function createGetTreeConfigIx(merkleTree: PublicKey): TransactionInstruction {
+    return createGetTreeConfigInstruction({
+        merkleTree,
+    });
+}
+
+/**
+ * Helper function for {@link createReadRightmostProofInstruction}, the proof is written
+ * to return data and can be decoded with {@link rightmostProofBeet}
+ * @param merkleTree
+ * @returns
+ */
+export function createReadRightmostProofIx(merkleTree: PublicKey): TransactionInstruction {
+    return createReadRightmostProofInstruction({
+        merkleTree,
+    });
+}
+
+/**
+ * Helper function for {@link createReadChangelogEntryInstruction}, the changelog is written
+ * to return data and can be decoded with {@link changeLogEntryBeet}
+ * @param merkleTree
+ * @param index of the changelog counting back from the most recent one
+ * @returns
+ */
+export function createReadChangelogEntryIx(merkleTree: PublicKey, index: number): TransactionInstruction {
+    return createReadChangelogEntryInstruction(
+        {
+            merkleTree,
+        },
+        {
+            index,
+        },
+    );
+}
+
 /**
  * Helper function for creating the {@link ConcurrentMerkleTreeAccount}.
  * It is best to use this method to initialize a {@link ConcurrentMerkleTreeAccount}
@@ -255,6 +755,7 @@ export function createVerifyLeafIx(merkleTree: PublicKey, proof: MerkleTreeProof
  * @param payer
  * @param depthSizePair
  * @param canopyDepth
+ * @param headerVersion 'V2' for trees initialized with a noop program other than SPL Noop
  * @returns
  */
 export async function createAllocTreeIx(
@@ -263,11 +764,13 @@ export async function createAllocTreeIx(
     payer: PublicKey,
     depthSizePair: ValidDepthSizePair,
     canopyDepth: number,
+    headerVersion = 'V1',
 ): Promise<TransactionInstruction> {
     const requiredSpace = getConcurrentMerkleTreeAccountSize(
         depthSizePair.maxDepth,
         depthSizePair.maxBufferSize,
         canopyDepth ?? 0,
+        headerVersion,
     );
     return SystemProgram.createAccount({
         fromPubkey: payer,
@@ -7,22 +7,47 @@ import { BN } from 'bn.js';
 import * as crypto from 'crypto';
 
 import {
+    addTreeMetrics,
+    BloomFilterAccount,
+    changeLogEntryBeet,
     ConcurrentMerkleTreeAccount,
+    createAllocTreeIx,
+    createAppendBatchIx,
     createAppendCanopyNodesIx,
     createAppendIx,
+    createAppendLeafPairIx,
+    createAppendSubtreeIx,
+    createCheckAndAppendIx,
+    createCheckAndReplaceIx,
+    createCloseBloomFilterIx,
     createCloseEmptyTreeInstruction,
     createCloseEmptyTreeIx,
+    createCloseTreeMetricsIx,
+    createGetTreeConfigIx,
+    createInitBloomFilterIx,
     createInitEmptyMerkleTreeIx,
+    createInitEmptyMerkleTreeWithPairLeavesIx,
     createInitPreparedTreeWithRootIx,
+    createInitTreeMetricsIx,
+    createReadChangelogEntryIx,
+    createReadRightmostProofIx,
     createReplaceIx,
+    createReplaceLeafPairIx,
+    createRotateAuthorityIx,
     createTransferAuthorityIx,
     createVerifyLeafIx,
+    getBloomFilterAddress,
+    getTreeMetricsAddress,
     prepareTreeIx,
+    rightmostProofBeet,
+    SPL_NOOP_PROGRAM_ID,
+    treeConfigBeet,
+    TreeMetricsAccount,
     ValidDepthSizePair,
 } from '../src';
 import { hash, MerkleTree } from '../src/merkle-tree';
 import { assertCMTProperties } from './accounts/concurrentMerkleTreeAccount.test';
-import { createTreeOnChain, execute, prepareTree } from './utils';
+import { createTreeOnChain, execute, prepareTree, simulateReturnData } from './utils';
 
 // eslint-disable-next-line no-empty
 describe('Account Compression', () => {
@@ -735,6 +760,441 @@ describe('Account Compression', () => {
         });
     });
 
+    describe('Appending a batch of leaves', () => {
+        const BATCH_SIZE = 4;
+
+        async function computeUnitsConsumed(txId: string): Promise<number> {
+            const tx = await connection.getTransaction(txId, { commitment: 'confirmed' });
+            return tx!.meta!.computeUnitsConsumed!;
+        }
+
+        beforeEach(async () => {
+            [cmtKeypair, offChainTree] = await createTreeOnChain(provider, payerKeypair, 1, DEPTH_SIZE_PAIR);
+            cmt = cmtKeypair.publicKey;
+        });
+        it('Appends the leaves in order', async () => {
+            const newLeaves = Array.from({ length: BATCH_SIZE }, () => crypto.randomBytes(32));
+            const appendBatchIx = createAppendBatchIx(cmt, payer, newLeaves);
+
+            await execute(provider, [appendBatchIx], [payerKeypair]);
+            newLeaves.forEach((leaf, i) => offChainTree.updateLeaf(1 + i, leaf));
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree',
+            );
+            assert(splCMT.getCurrentSeq().eq(new BN(1 + BATCH_SIZE)));
+        });
+        it('Uses less compute per leaf than single appends', async () => {
+            const singleTxId = await execute(
+                provider,
+                [createAppendIx(cmt, payer, crypto.randomBytes(32))],
+                [payerKeypair],
+            );
+            const singleComputeUnits = await computeUnitsConsumed(singleTxId);
+
+            const newLeaves = Array.from({ length: BATCH_SIZE }, () => crypto.randomBytes(32));
+            const batchTxId = await execute(provider, [createAppendBatchIx(cmt, payer, newLeaves)], [payerKeypair]);
+            const batchComputeUnitsPerLeaf = (await computeUnitsConsumed(batchTxId)) / BATCH_SIZE;
+
+            console.log(
+                `CU per leaf: ${singleComputeUnits} with append, ${batchComputeUnitsPerLeaf} with append_batch of ${BATCH_SIZE}`,
+            );
+            assert(
+                batchComputeUnitsPerLeaf < singleComputeUnits,
+                'Batch append uses less compute per leaf than single appends',
+            );
+        });
+        it('Fails to append an empty batch', async () => {
+            try {
+                await execute(provider, [createAppendBatchIx(cmt, payer, [])], [payerKeypair]);
+                assert(false, 'Appending an empty batch should have failed');
+            } catch {}
+        });
+    });
+
+    describe('Appending a subtree', () => {
+        const SUBTREE_DEPTH = 2;
+        const SUBTREE_SIZE = 2 ** SUBTREE_DEPTH;
+
+        beforeEach(async () => {
+            [cmtKeypair, offChainTree] = await createTreeOnChain(provider, payerKeypair, SUBTREE_SIZE, DEPTH_SIZE_PAIR);
+            cmt = cmtKeypair.publicKey;
+        });
+        it('Appends the subtree and continues appending right after its rightmost leaf', async () => {
+            const rightmostIndex = 2;
+            const subtreeLeaves = Array.from({ length: SUBTREE_SIZE }, (_, i) =>
+                i <= rightmostIndex ? crypto.randomBytes(32) : Buffer.alloc(32),
+            );
+            const subtree = new MerkleTree(subtreeLeaves);
+
+            await execute(
+                provider,
+                [createAppendSubtreeIx(cmt, payer, subtree.getProof(rightmostIndex))],
+                [payerKeypair],
+            );
+            subtreeLeaves.forEach((leaf, i) => offChainTree.updateLeaf(SUBTREE_SIZE + i, leaf));
+
+            let splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree',
+            );
+
+            const newLeaf = crypto.randomBytes(32);
+            await execute(provider, [createAppendIx(cmt, payer, newLeaf)], [payerKeypair]);
+            offChainTree.updateLeaf(SUBTREE_SIZE + rightmostIndex + 1, newLeaf);
+
+            splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Leaf appended after the subtree is written right after its rightmost leaf',
+            );
+        });
+        it('Fails to append a subtree at a misaligned index', async () => {
+            await execute(provider, [createAppendIx(cmt, payer, crypto.randomBytes(32))], [payerKeypair]);
+
+            const subtree = new MerkleTree(Array.from({ length: SUBTREE_SIZE }, () => crypto.randomBytes(32)));
+            try {
+                await execute(
+                    provider,
+                    [createAppendSubtreeIx(cmt, payer, subtree.getProof(SUBTREE_SIZE - 1))],
+                    [payerKeypair],
+                );
+                assert(false, 'Appending a subtree at an index which is not a multiple of its size should have failed');
+            } catch {}
+        });
+    });
+
+    describe('Having created a tree with a bloom filter', () => {
+        const NUM_BITS = 8 * 1024;
+        const NUM_HASHES = 7;
+
+        beforeEach(async () => {
+            [cmtKeypair, offChainTree] = await createTreeOnChain(provider, payerKeypair, 0, DEPTH_SIZE_PAIR);
+            cmt = cmtKeypair.publicKey;
+            await execute(provider, [createInitBloomFilterIx(cmt, payer, payer, NUM_BITS, NUM_HASHES)], [payerKeypair]);
+        });
+        it('Reads back the bloom filter header', async () => {
+            const bloomFilter = await BloomFilterAccount.fromAccountAddress(connection, getBloomFilterAddress(cmt));
+            assert(bloomFilter.getMerkleTree().equals(cmt), 'Bloom filter belongs to the tree');
+            assert(bloomFilter.getNumBits() === NUM_BITS);
+            assert(bloomFilter.getNumHashes() === NUM_HASHES);
+            assert(bloomFilter.getNumInserted().eqn(0));
+        });
+        it('Appends a new leaf and rejects it the second time', async () => {
+            const newLeaf = crypto.randomBytes(32);
+            await execute(provider, [createCheckAndAppendIx(cmt, payer, newLeaf)], [payerKeypair]);
+            offChainTree.updateLeaf(0, newLeaf);
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree',
+            );
+            const bloomFilter = await BloomFilterAccount.fromAccountAddress(connection, getBloomFilterAddress(cmt));
+            assert(bloomFilter.getNumInserted().eqn(1));
+
+            try {
+                await execute(provider, [createCheckAndAppendIx(cmt, payer, newLeaf)], [payerKeypair]);
+                assert(false, 'Appending the same leaf twice should have failed');
+            } catch {}
+        });
+        it('Replaces a leaf only with a leaf that was not inserted', async () => {
+            const firstLeaf = crypto.randomBytes(32);
+            const secondLeaf = crypto.randomBytes(32);
+            await execute(
+                provider,
+                [createCheckAndAppendIx(cmt, payer, firstLeaf), createCheckAndAppendIx(cmt, payer, secondLeaf)],
+                [payerKeypair],
+            );
+            offChainTree.updateLeaf(0, firstLeaf);
+            offChainTree.updateLeaf(1, secondLeaf);
+
+            try {
+                await execute(
+                    provider,
+                    [createCheckAndReplaceIx(cmt, payer, secondLeaf, offChainTree.getProof(0))],
+                    [payerKeypair],
+                );
+                assert(false, 'Replacing a leaf with an inserted leaf should have failed');
+            } catch {}
+
+            const newLeaf = crypto.randomBytes(32);
+            await execute(
+                provider,
+                [createCheckAndReplaceIx(cmt, payer, newLeaf, offChainTree.getProof(0))],
+                [payerKeypair],
+            );
+            offChainTree.updateLeaf(0, newLeaf);
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree',
+            );
+        });
+        it('Closes the bloom filter', async () => {
+            const bloomFilter = getBloomFilterAddress(cmt);
+            const recipient = Keypair.generate().publicKey;
+            const bloomFilterLamports = (await connection.getAccountInfo(bloomFilter))!.lamports;
+
+            await execute(provider, [createCloseBloomFilterIx(cmt, payer, recipient)], [payerKeypair]);
+
+            assert((await connection.getAccountInfo(bloomFilter)) === null, 'Bloom filter should be closed');
+            assert(
+                (await connection.getBalance(recipient)) === bloomFilterLamports,
+                'Recipient should have received the rent of the bloom filter',
+            );
+        });
+    });
+
+    describe('Having created a tree with a metrics account', () => {
+        async function getTreeMetrics(): Promise<TreeMetricsAccount> {
+            return TreeMetricsAccount.fromAccountAddress(connection, getTreeMetricsAddress(cmt));
+        }
+
+        beforeEach(async () => {
+            [cmtKeypair, offChainTree] = await createTreeOnChain(provider, payerKeypair, 0, DEPTH_SIZE_PAIR);
+            cmt = cmtKeypair.publicKey;
+            await execute(provider, [createInitTreeMetricsIx(cmt, payer, payer)], [payerKeypair]);
+        });
+        it('Reads back the empty metrics', async () => {
+            const treeMetrics = await getTreeMetrics();
+            assert(treeMetrics.getMerkleTree().equals(cmt), 'Metrics account belongs to the tree');
+            assert(treeMetrics.getNumAppends().eqn(0));
+            assert(treeMetrics.getNumReplaces().eqn(0));
+            assert(treeMetrics.getLastWriteSlot().eqn(0));
+        });
+        it('Counts the leaves appended with the metrics account', async () => {
+            const newLeaves = Array.from({ length: 3 }, () => crypto.randomBytes(32));
+            await execute(
+                provider,
+                [
+                    addTreeMetrics(createAppendIx(cmt, payer, crypto.randomBytes(32)), cmt),
+                    addTreeMetrics(createAppendBatchIx(cmt, payer, newLeaves), cmt),
+                ],
+                [payerKeypair],
+            );
+            let treeMetrics = await getTreeMetrics();
+            assert(treeMetrics.getNumAppends().eqn(4));
+            assert(treeMetrics.getLastWriteSlot().gtn(0), 'Slot of the last write is recorded');
+
+            // Only the leaves up to the rightmost leaf of the subtree are counted
+            const subtreeLeaves = [crypto.randomBytes(32), crypto.randomBytes(32), Buffer.alloc(32), Buffer.alloc(32)];
+            const subtree = new MerkleTree(subtreeLeaves);
+            await execute(
+                provider,
+                [addTreeMetrics(createAppendSubtreeIx(cmt, payer, subtree.getProof(1)), cmt)],
+                [payerKeypair],
+            );
+            treeMetrics = await getTreeMetrics();
+            assert(treeMetrics.getNumAppends().eqn(6));
+            assert(treeMetrics.getNumReplaces().eqn(0));
+        });
+        it('Counts the leaves replaced with the metrics account', async () => {
+            const leaf = crypto.randomBytes(32);
+            await execute(provider, [createAppendIx(cmt, payer, leaf)], [payerKeypair]);
+            offChainTree.updateLeaf(0, leaf);
+
+            const newLeaf = crypto.randomBytes(32);
+            await execute(
+                provider,
+                [addTreeMetrics(createReplaceIx(cmt, payer, newLeaf, offChainTree.getProof(0)), cmt)],
+                [payerKeypair],
+            );
+            offChainTree.updateLeaf(0, newLeaf);
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree',
+            );
+            const treeMetrics = await getTreeMetrics();
+            assert(treeMetrics.getNumAppends().eqn(0), 'Append without the metrics account is not counted');
+            assert(treeMetrics.getNumReplaces().eqn(1));
+        });
+        it('Counts the leaves appended with the bloom filter and the metrics account', async () => {
+            await execute(provider, [createInitBloomFilterIx(cmt, payer, payer, 8 * 1024, 7)], [payerKeypair]);
+            await execute(
+                provider,
+                [addTreeMetrics(createCheckAndAppendIx(cmt, payer, crypto.randomBytes(32)), cmt)],
+                [payerKeypair],
+            );
+
+            const treeMetrics = await getTreeMetrics();
+            assert(treeMetrics.getNumAppends().eqn(1));
+        });
+        it('Closes the metrics account', async () => {
+            const treeMetrics = getTreeMetricsAddress(cmt);
+            const recipient = Keypair.generate().publicKey;
+            const treeMetricsLamports = (await connection.getAccountInfo(treeMetrics))!.lamports;
+
+            await execute(provider, [createCloseTreeMetricsIx(cmt, payer, recipient)], [payerKeypair]);
+
+            assert((await connection.getAccountInfo(treeMetrics)) === null, 'Metrics account should be closed');
+            assert(
+                (await connection.getBalance(recipient)) === treeMetricsLamports,
+                'Recipient should have received the rent of the metrics account',
+            );
+        });
+    });
+
+    describe('Reading the tree config', () => {
+        const NUM_LEAVES = 3;
+        const CANOPY_DEPTH = 2;
+
+        beforeEach(async () => {
+            [cmtKeypair, offChainTree] = await createTreeOnChain(
+                provider,
+                payerKeypair,
+                NUM_LEAVES,
+                DEPTH_SIZE_PAIR,
+                CANOPY_DEPTH,
+            );
+            cmt = cmtKeypair.publicKey;
+        });
+        it('Returns the config of the tree', async () => {
+            const data = await simulateReturnData(provider, createGetTreeConfigIx(cmt), payer, treeConfigBeet.byteSize);
+            const [treeConfig] = treeConfigBeet.deserialize(data);
+
+            assert(treeConfig.maxDepth === MAX_DEPTH);
+            assert(treeConfig.maxBufferSize === MAX_SIZE);
+            assert(treeConfig.canopyDepth === CANOPY_DEPTH);
+            assert(treeConfig.authority.equals(payer));
+            assert(new BN(treeConfig.sequenceNumber).eqn(NUM_LEAVES));
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(new BN(treeConfig.creationSlot).eq(splCMT.getCreationSlot()));
+        });
+        it('Fails for an account which is not a tree', async () => {
+            try {
+                await simulateReturnData(provider, createGetTreeConfigIx(payer), payer, treeConfigBeet.byteSize);
+                assert(false, 'Reading the config of an account which is not a tree should have failed');
+            } catch {}
+        });
+    });
+
+    describe('Reading the rightmost proof and changelogs', () => {
+        const NUM_LEAVES = 3;
+        // Trailing zeros are truncated from return data, so it is padded to the largest size
+        const RIGHTMOST_PROOF_SIZE = 4 + 32 * MAX_DEPTH + 32 + 4;
+        const CHANGELOG_ENTRY_SIZE = 32 + 4 + 32 * MAX_DEPTH + 4 + 8;
+
+        beforeEach(async () => {
+            [cmtKeypair, offChainTree] = await createTreeOnChain(provider, payerKeypair, NUM_LEAVES, DEPTH_SIZE_PAIR);
+            cmt = cmtKeypair.publicKey;
+        });
+        it('Returns the proof of the rightmost leaf', async () => {
+            const readIx = createReadRightmostProofIx(cmt);
+            const data = await simulateReturnData(provider, readIx, payer, RIGHTMOST_PROOF_SIZE);
+            const [rightmostProof] = rightmostProofBeet.deserialize(data);
+
+            const expectedProof = offChainTree.getProof(NUM_LEAVES - 1);
+            assert(rightmostProof.index === NUM_LEAVES, 'Index is the index of the next append');
+            assert(Buffer.from(rightmostProof.leaf).equals(expectedProof.leaf));
+            assert(rightmostProof.proof.length === MAX_DEPTH);
+            rightmostProof.proof.forEach((node, i) => {
+                assert(Buffer.from(node).equals(expectedProof.proof[i]), `Proof node ${i} matches the off chain tree`);
+            });
+        });
+        it('Returns the changelogs from the most recent one', async () => {
+            let data = await simulateReturnData(
+                provider,
+                createReadChangelogEntryIx(cmt, 0),
+                payer,
+                CHANGELOG_ENTRY_SIZE,
+            );
+            let [changeLogEntry] = changeLogEntryBeet.deserialize(data);
+
+            assert(
+                Buffer.from(changeLogEntry.root).equals(offChainTree.root),
+                'Most recent root matches the off chain tree',
+            );
+            assert(changeLogEntry.index === NUM_LEAVES - 1);
+            assert(new BN(changeLogEntry.sequenceNumber).eqn(NUM_LEAVES));
+            assert(changeLogEntry.path.length === MAX_DEPTH);
+            assert(Buffer.from(changeLogEntry.path[0]).equals(offChainTree.getProof(NUM_LEAVES - 1).leaf));
+
+            data = await simulateReturnData(provider, createReadChangelogEntryIx(cmt, 1), payer, CHANGELOG_ENTRY_SIZE);
+            [changeLogEntry] = changeLogEntryBeet.deserialize(data);
+
+            assert(changeLogEntry.index === NUM_LEAVES - 2);
+            assert(new BN(changeLogEntry.sequenceNumber).eqn(NUM_LEAVES - 1));
+        });
+        it('Fails to read a changelog out of the changelog buffer', async () => {
+            try {
+                const readIx = createReadChangelogEntryIx(cmt, MAX_SIZE);
+                await simulateReturnData(provider, readIx, payer, CHANGELOG_ENTRY_SIZE);
+                assert(false, 'Reading a changelog out of the changelog buffer should have failed');
+            } catch {}
+        });
+    });
+
+    describe('Having created a tree with pair leaves', () => {
+        beforeEach(async () => {
+            cmtKeypair = Keypair.generate();
+            cmt = cmtKeypair.publicKey;
+            const allocAccountIx = await createAllocTreeIx(connection, cmt, payer, DEPTH_SIZE_PAIR, 0);
+            const initIx = createInitEmptyMerkleTreeWithPairLeavesIx(cmt, payer, DEPTH_SIZE_PAIR);
+            await execute(provider, [allocAccountIx, initIx], [payerKeypair, cmtKeypair]);
+            offChainTree = new MerkleTree(Array(2 ** MAX_DEPTH).fill(Buffer.alloc(32)));
+        });
+        it('Reads back the pair leaves flag from the header', async () => {
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(splCMT.getHasPairLeaves(), 'Tree should have pair leaves');
+        });
+        it('Appends and replaces leaf pairs', async () => {
+            const leafKey = crypto.randomBytes(32);
+            const leafValue = crypto.randomBytes(32);
+            await execute(provider, [createAppendLeafPairIx(cmt, payer, leafKey, leafValue)], [payerKeypair]);
+            offChainTree.updateLeaf(0, hash(leafKey, leafValue));
+
+            let splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Appended leaf node is the hash of the leaf pair',
+            );
+
+            const newLeafValue = crypto.randomBytes(32);
+            await execute(
+                provider,
+                [createReplaceLeafPairIx(cmt, payer, leafKey, newLeafValue, offChainTree.getProof(0))],
+                [payerKeypair],
+            );
+            offChainTree.updateLeaf(0, hash(leafKey, newLeafValue));
+
+            splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Replaced leaf node is the hash of the new leaf pair',
+            );
+        });
+        it('Fails to append a single leaf', async () => {
+            try {
+                await execute(provider, [createAppendIx(cmt, payer, crypto.randomBytes(32))], [payerKeypair]);
+                assert(false, 'Appending a single leaf to a tree with pair leaves should have failed');
+            } catch {}
+        });
+        it('Fails to append a leaf pair to a tree with single leaves', async () => {
+            [cmtKeypair, offChainTree] = await createTreeOnChain(provider, payerKeypair, 0, DEPTH_SIZE_PAIR);
+            const appendIx = createAppendLeafPairIx(
+                cmtKeypair.publicKey,
+                payer,
+                crypto.randomBytes(32),
+                crypto.randomBytes(32),
+            );
+            try {
+                await execute(provider, [appendIx], [payerKeypair]);
+                assert(false, 'Appending a leaf pair to a tree with single leaves should have failed');
+            } catch {}
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmtKeypair.publicKey);
+            assert(!splCMT.getHasPairLeaves(), 'Tree should not have pair leaves');
+        });
+    });
+
     describe('Examples transferring authority', () => {
         const authorityKeypair = Keypair.generate();
         const authority = authorityKeypair.publicKey;
@@ -780,6 +1240,139 @@ describe('Account Compression', () => {
         });
     });
 
+    describe('Examples rotating authority', () => {
+        const authorityKeypair = Keypair.generate();
+        const authority = authorityKeypair.publicKey;
+        const newAuthorityKeypair = Keypair.generate();
+        const newAuthority = newAuthorityKeypair.publicKey;
+
+        beforeEach(async () => {
+            await provider.connection.confirmTransaction(
+                await (connection as Connection).requestAirdrop(authority, 1e10),
+            );
+            [cmtKeypair, offChainTree] = await createTreeOnChain(provider, authorityKeypair, 1, DEPTH_SIZE_PAIR);
+            cmt = cmtKeypair.publicKey;
+        });
+        it('Can rotate authority and read back the V3 header', async () => {
+            const rotateAuthorityIx = createRotateAuthorityIx(cmt, authority, newAuthority, 1000, authority);
+            await execute(provider, [rotateAuthorityIx], [authorityKeypair]);
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            const previousAuthority = splCMT.getPreviousAuthority();
+
+            assert.equal(splCMT.header.header.__kind, 'V3');
+            assert(splCMT.getAuthority().equals(newAuthority), 'Authority should be the new authority');
+            assert(previousAuthority !== null, 'Previous authority should be set after a rotation');
+            assert(previousAuthority.authority.equals(authority), 'Previous authority should be the old authority');
+            assert(
+                previousAuthority.expirySlot.gt(splCMT.getCreationSlot()),
+                'Previous authority should expire after the tree creation',
+            );
+            assert(splCMT.getNoopProgram().equals(SPL_NOOP_PROGRAM_ID), 'Noop program should be unchanged');
+            assert.equal(splCMT.getMaxDepth(), MAX_DEPTH);
+            assert.equal(splCMT.getMaxBufferSize(), MAX_SIZE);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Root should be unchanged after moving the tree behind the larger header',
+            );
+        });
+        it('Accepts both authorities for modifications during the overlap', async () => {
+            const rotateAuthorityIx = createRotateAuthorityIx(cmt, authority, newAuthority, 1000, authority);
+            await execute(provider, [rotateAuthorityIx], [authorityKeypair]);
+
+            const oldAuthorityLeaf = crypto.randomBytes(32);
+            const oldAuthorityIx = createReplaceIx(cmt, authority, oldAuthorityLeaf, offChainTree.getProof(0));
+            await execute(provider, [oldAuthorityIx], [authorityKeypair]);
+            offChainTree.updateLeaf(0, oldAuthorityLeaf);
+
+            const newAuthorityLeaf = crypto.randomBytes(32);
+            const newAuthorityIx = createReplaceIx(cmt, newAuthority, newAuthorityLeaf, offChainTree.getProof(0));
+            await execute(provider, [newAuthorityIx], [newAuthorityKeypair]);
+            offChainTree.updateLeaf(0, newAuthorityLeaf);
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree',
+            );
+        });
+        it('Rejects the previous authority once the overlap is over', async () => {
+            const rotateAuthorityIx = createRotateAuthorityIx(cmt, authority, newAuthority, 0, authority);
+            await execute(provider, [rotateAuthorityIx], [authorityKeypair]);
+
+            const replaceIx = createReplaceIx(cmt, authority, crypto.randomBytes(32), offChainTree.getProof(0));
+            try {
+                await execute(provider, [replaceIx], [authorityKeypair]);
+                assert(false, 'Replacing with an expired previous authority should have failed');
+            } catch {}
+        });
+        it('Rejects the previous authority for transferring authority', async () => {
+            const rotateAuthorityIx = createRotateAuthorityIx(cmt, authority, newAuthority, 1000, authority);
+            await execute(provider, [rotateAuthorityIx], [authorityKeypair]);
+
+            const transferAuthorityIx = createTransferAuthorityIx(cmt, authority, authority);
+            try {
+                await execute(provider, [transferAuthorityIx], [authorityKeypair]);
+                assert(false, 'Only the new authority should be able to transfer authority');
+            } catch {}
+        });
+    });
+
+    describe('Having created a tree with a custom noop program', () => {
+        // Loaded at genesis with the SPL Noop binary, see Anchor.toml
+        const customNoop = new PublicKey('ArsuB7mQbzfpx6LmKfnEZDB2WpTVQmkfMHfiteZoTH6e');
+
+        beforeEach(async () => {
+            cmtKeypair = Keypair.generate();
+            cmt = cmtKeypair.publicKey;
+            offChainTree = MerkleTree.sparseMerkleTreeFromLeaves([], MAX_DEPTH);
+
+            const allocAccountIx = await createAllocTreeIx(connection, cmt, payer, DEPTH_SIZE_PAIR, 0, 'V2');
+            const initIx = createInitEmptyMerkleTreeIx(cmt, payer, DEPTH_SIZE_PAIR, customNoop);
+            await execute(provider, [allocAccountIx, initIx], [payerKeypair, cmtKeypair]);
+        });
+        it('Reads back the custom noop program from the V2 header', async () => {
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+
+            assert.equal(splCMT.header.header.__kind, 'V2');
+            assert(splCMT.getNoopProgram().equals(customNoop), 'Noop program should be the custom noop');
+            assert(splCMT.getAuthority().equals(payer), 'Authority should be the payer');
+            assert.equal(splCMT.getMaxDepth(), MAX_DEPTH);
+            assert.equal(splCMT.getMaxBufferSize(), MAX_SIZE);
+            assert(Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root), 'Root should be the empty root');
+        });
+        it('Appends leaves through the custom noop program', async () => {
+            const newLeaf = crypto.randomBytes(32);
+            await execute(provider, [createAppendIx(cmt, payer, newLeaf, customNoop)], [payerKeypair]);
+            offChainTree.updateLeaf(0, newLeaf);
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                Buffer.from(splCMT.getCurrentRoot()).equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree',
+            );
+        });
+        it('Keeps the custom noop program when rotating authority', async () => {
+            const newAuthority = Keypair.generate().publicKey;
+            await execute(provider, [createRotateAuthorityIx(cmt, payer, newAuthority, 1000, payer)], [payerKeypair]);
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert.equal(splCMT.header.header.__kind, 'V3');
+            assert(splCMT.getNoopProgram().equals(customNoop), 'Noop program should still be the custom noop');
+
+            // The previous authority can still append through the custom noop
+            const newLeaf = crypto.randomBytes(32);
+            await execute(provider, [createAppendIx(cmt, payer, newLeaf, customNoop)], [payerKeypair]);
+        });
+        it('Fails to append leaves through SPL Noop', async () => {
+            const appendIx = createAppendIx(cmt, payer, crypto.randomBytes(32), SPL_NOOP_PROGRAM_ID);
+            try {
+                await execute(provider, [appendIx], [payerKeypair]);
+                assert(false, 'Appending with another noop program than the one of the tree should have failed');
+            } catch {}
+        });
+    });
+
     describe(`Having created a tree with ${MAX_SIZE} leaves`, () => {
         beforeEach(async () => {
             [cmtKeypair, offChainTree] = await createTreeOnChain(provider, payerKeypair, MAX_SIZE, DEPTH_SIZE_PAIR);
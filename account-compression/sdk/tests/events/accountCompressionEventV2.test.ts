@@ -0,0 +1,33 @@
+import { strict as assert } from 'node:assert';
+
+import { PublicKey } from '@solana/web3.js';
+import { BN } from 'bn.js';
+
+import { deserializeAccountCompressionEventV2, TreeType } from '../../src';
+
+describe('Serde tests', () => {
+    describe('AccountCompressionEventV2 tests', () => {
+        it('Can serialize and deserialize AccountCompressionEventV2 with application data', () => {
+            const applicationId = new PublicKey('BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY');
+            const data = Buffer.from('Hello world');
+            const event = Buffer.concat([
+                Buffer.from([0x3]), // V2 Event tag
+                Buffer.from([0x1]), // schema version
+                Buffer.from([0x1]), // ConcurrentMerkleTreeWithPairLeaves tree type
+                applicationId.toBuffer(),
+                Buffer.from([0x0]), // no changelog
+                Buffer.from([0x1]), // application data present
+                Buffer.from(new BN.BN(data.length).toArray('le', 4)), // Size of application data (for Vec)
+                data, // serialized application data (for Vec)
+            ]);
+
+            const deserialized = deserializeAccountCompressionEventV2(event);
+            assert(deserialized.schemaVersion === 1);
+            assert(deserialized.treeType === TreeType.ConcurrentMerkleTreeWithPairLeaves);
+            assert(deserialized.applicationId.equals(applicationId));
+            assert(deserialized.changeLog === null);
+            const decoder = new TextDecoder();
+            assert('Hello world' === decoder.decode(deserialized.applicationData as Uint8Array));
+        });
+    });
+});
@@ -1,5 +1,14 @@
 import { AnchorProvider } from '@coral-xyz/anchor';
-import { Keypair, SendTransactionError, Signer, Transaction, TransactionInstruction } from '@solana/web3.js';
+import {
+    Keypair,
+    PublicKey,
+    SendTransactionError,
+    Signer,
+    Transaction,
+    TransactionInstruction,
+    TransactionMessage,
+    VersionedTransaction,
+} from '@solana/web3.js';
 import * as crypto from 'crypto';
 
 import {
@@ -55,6 +64,31 @@ export async function execute(
     return txid;
 }
 
+/// Simulate an instruction and return the data it wrote to return data, padded to `size` bytes
+/// since trailing zeros are truncated from return data
+export async function simulateReturnData(
+    provider: AnchorProvider,
+    instruction: TransactionInstruction,
+    payer: PublicKey,
+    size: number,
+): Promise<Buffer> {
+    const { blockhash } = await provider.connection.getLatestBlockhash();
+    const message = new TransactionMessage({
+        instructions: [instruction],
+        payerKey: payer,
+        recentBlockhash: blockhash,
+    }).compileToV0Message();
+    const { value } = await provider.connection.simulateTransaction(new VersionedTransaction(message), {
+        sigVerify: false,
+    });
+    if (value.err) {
+        console.log(value.logs);
+        throw new Error(JSON.stringify(value.err));
+    }
+    const data = value.returnData ? Buffer.from(value.returnData.data[0], 'base64') : Buffer.alloc(0);
+    return Buffer.concat([data, Buffer.alloc(size - data.length)]);
+}
+
 export async function createTreeOnChain(
     provider: AnchorProvider,
     payer: Keypair,
@@ -0,0 +1,154 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+
+use {
+    program_test::program_test_2022,
+    solana_program::{instruction::*, pubkey::Pubkey},
+    solana_program_test::*,
+    solana_sdk::{
+        signature::Signer,
+        transaction::{Transaction, TransactionError},
+    },
+    spl_associated_token_account::{
+        error::AssociatedTokenAccountError,
+        instruction::create_associated_token_account_for_program_owner,
+    },
+    spl_associated_token_account_client::address::get_associated_token_address_with_program_id,
+    spl_token_2022::{extension::StateWithExtensionsOwned, state::Account},
+};
+
+#[tokio::test]
+async fn success_create_and_recreate() {
+    let owner_program_id = Pubkey::new_unique();
+    let vault_seed = b"vault".as_ref();
+    let (wallet_address, bump_seed) =
+        Pubkey::find_program_address(&[vault_seed], &owner_program_id);
+    let token_mint_address = Pubkey::new_unique();
+    let associated_token_address = get_associated_token_address_with_program_id(
+        &wallet_address,
+        &token_mint_address,
+        &spl_token_2022::id(),
+    );
+
+    let (mut banks_client, payer, recent_blockhash) =
+        program_test_2022(token_mint_address, true).start().await;
+
+    let instruction = create_associated_token_account_for_program_owner(
+        &payer.pubkey(),
+        &wallet_address,
+        &[vault_seed, &[bump_seed]],
+        &owner_program_id,
+        &token_mint_address,
+        &spl_token_2022::id(),
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction.clone()],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let associated_account = banks_client
+        .get_account(associated_token_address)
+        .await
+        .expect("get_account")
+        .expect("associated_account not none");
+    assert_eq!(associated_account.owner, spl_token_2022::id());
+    let token_account = StateWithExtensionsOwned::<Account>::unpack(associated_account.data)
+        .unwrap()
+        .base;
+    assert_eq!(token_account.owner, wallet_address);
+    assert_eq!(token_account.mint, token_mint_address);
+
+    // Creating again succeeds, so batches can be safely retried
+    let recent_blockhash = banks_client
+        .get_new_latest_blockhash(&recent_blockhash)
+        .await
+        .unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+}
+
+#[tokio::test]
+async fn fail_wallet_not_derived_from_owner_program() {
+    let owner_program_id = Pubkey::new_unique();
+    let vault_seed = b"vault".as_ref();
+    let (_, bump_seed) = Pubkey::find_program_address(&[vault_seed], &owner_program_id);
+    let token_mint_address = Pubkey::new_unique();
+
+    let (banks_client, payer, recent_blockhash) =
+        program_test_2022(token_mint_address, true).start().await;
+
+    // An on-curve wallet address is rejected
+    let wallet_address = payer.pubkey();
+    let instruction = create_associated_token_account_for_program_owner(
+        &payer.pubkey(),
+        &wallet_address,
+        &[vault_seed, &[bump_seed]],
+        &owner_program_id,
+        &token_mint_address,
+        &spl_token_2022::id(),
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AssociatedTokenAccountError::InvalidProgramOwner as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_owner_program() {
+    let owner_program_id = Pubkey::new_unique();
+    let vault_seed = b"vault".as_ref();
+    let (wallet_address, bump_seed) =
+        Pubkey::find_program_address(&[vault_seed], &owner_program_id);
+    let token_mint_address = Pubkey::new_unique();
+
+    let (banks_client, payer, recent_blockhash) =
+        program_test_2022(token_mint_address, true).start().await;
+
+    let instruction = create_associated_token_account_for_program_owner(
+        &payer.pubkey(),
+        &wallet_address,
+        &[vault_seed, &[bump_seed]],
+        &Pubkey::new_unique(), // <-- Invalid owner program
+        &token_mint_address,
+        &spl_token_2022::id(),
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AssociatedTokenAccountError::InvalidProgramOwner as u32)
+        )
+    );
+}
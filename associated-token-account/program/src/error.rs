@@ -13,6 +13,11 @@ pub enum AssociatedTokenAccountError {
     /// Associated token account owner does not match address derivation
     #[error("Associated token account owner does not match address derivation")]
     InvalidOwner,
+
+    // 1
+    /// Wallet address is not derived from the owner program
+    #[error("Wallet address is not derived from the owner program")]
+    InvalidProgramOwner,
 }
 impl From<AssociatedTokenAccountError> for ProgramError {
     fn from(e: AssociatedTokenAccountError) -> Self {
@@ -1,7 +1,13 @@
 //! Program instructions
 
-use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 pub use spl_associated_token_account_client::instruction::*;
+use {
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+};
 
 /// Instructions supported by the AssociatedTokenAccount program
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -47,4 +53,55 @@ pub enum AssociatedTokenAccountInstruction {
     ///      account
     ///   6. `[]` SPL Token program
     RecoverNested,
+    /// Creates an associated token account for a wallet address that is a
+    /// program derived address of the given owner program, if it doesn't
+    /// already exist. Returns an error if the wallet address is not derived
+    /// from the owner program with the given seeds, or if the account exists
+    /// with a different owner.
+    ///
+    /// As for `Create`, the token program sizes the account for the extensions
+    /// required by the mint, such as `TransferHookAccount`. Creating the
+    /// account does not run a transfer hook, so no extra account metas are
+    /// resolved or needed.
+    ///
+    ///   0. `[writeable,signer]` Funding account (must be a system account)
+    ///   1. `[writeable]` Associated token account address to be created
+    ///   2. `[]` Wallet address for the new associated token account, must be
+    ///      a program derived address of `6`
+    ///   3. `[]` The token mint for the new associated token account
+    ///   4. `[]` System program
+    ///   5. `[]` SPL Token program
+    ///   6. `[]` Program the wallet address is derived from
+    CreateForProgramOwner {
+        /// Seeds of the wallet address, including the bump seed
+        wallet_seeds: Vec<Vec<u8>>,
+    },
+}
+
+/// Creates a `CreateForProgramOwner` instruction
+///
+/// `wallet_seeds` must derive `wallet_address` from `owner_program_id`,
+/// including the bump seed
+pub fn create_associated_token_account_for_program_owner(
+    funding_address: &Pubkey,
+    wallet_address: &Pubkey,
+    wallet_seeds: &[&[u8]],
+    owner_program_id: &Pubkey,
+    token_mint_address: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    let mut instruction = create_associated_token_account_idempotent(
+        funding_address,
+        wallet_address,
+        token_mint_address,
+        token_program_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(*owner_program_id, false));
+    instruction.data = borsh::to_vec(&AssociatedTokenAccountInstruction::CreateForProgramOwner {
+        wallet_seeds: wallet_seeds.iter().map(|seed| seed.to_vec()).collect(),
+    })
+    .unwrap();
+    instruction
 }
@@ -59,6 +59,9 @@ pub fn process_instruction(
         AssociatedTokenAccountInstruction::RecoverNested => {
             process_recover_nested(program_id, accounts)
         }
+        AssociatedTokenAccountInstruction::CreateForProgramOwner { wallet_seeds } => {
+            process_create_for_program_owner(program_id, accounts, wallet_seeds)
+        }
     }
 }
 
@@ -161,6 +164,36 @@ fn process_create_associated_token_account(
     )
 }
 
+/// Processes `CreateForProgramOwner` instruction
+fn process_create_for_program_owner(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    wallet_seeds: Vec<Vec<u8>>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let _funder_info = next_account_info(account_info_iter)?;
+    let _associated_token_account_info = next_account_info(account_info_iter)?;
+    let wallet_account_info = next_account_info(account_info_iter)?;
+    let _spl_token_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let _spl_token_program_info = next_account_info(account_info_iter)?;
+    let owner_program_info = next_account_info(account_info_iter)?;
+
+    // Check the wallet address is an off-curve address of the owner program
+    let wallet_seeds: Vec<&[u8]> = wallet_seeds.iter().map(|seed| seed.as_slice()).collect();
+    match Pubkey::create_program_address(&wallet_seeds, owner_program_info.key) {
+        Ok(wallet_address) if wallet_address == *wallet_account_info.key => {}
+        _ => {
+            let error = AssociatedTokenAccountError::InvalidProgramOwner;
+            msg!("{}", error);
+            return Err(error.into());
+        }
+    }
+
+    process_create_associated_token_account(program_id, accounts, CreateMode::Idempotent)
+}
+
 /// Processes `RecoverNested` instruction
 pub fn process_recover_nested(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
@@ -0,0 +1,77 @@
+//! Error types
+
+use {
+    num_derive::FromPrimitive,
+    solana_program::{
+        decode_error::DecodeError,
+        msg,
+        program_error::{PrintProgramError, ProgramError},
+    },
+    thiserror::Error,
+};
+
+/// Errors that may be returned by the Lockup VoterWeight Addin program
+#[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum LockupAddinError {
+    /// Invalid max lockup duration
+    #[error("Invalid max lockup duration")]
+    InvalidMaxLockupDuration = 1200,
+
+    /// Lockup duration exceeds the max lockup duration of the Registrar
+    #[error("Lockup duration exceeds the max lockup duration")]
+    LockupDurationTooLong, // 1201
+
+    /// Lockup can't end before the current lockup end
+    #[error("Lockup can't end before the current lockup end")]
+    LockupCannotBeShortened, // 1202
+
+    /// Tokens can't be withdrawn before the lockup ends
+    #[error("Tokens can't be withdrawn before the lockup ends")]
+    LockupNotExpired, // 1203
+
+    /// Invalid token amount
+    #[error("Invalid token amount")]
+    InvalidAmount, // 1204
+
+    /// Withdraw amount exceeds the locked amount
+    #[error("Withdraw amount exceeds the locked amount")]
+    InsufficientLockedAmount, // 1205
+
+    /// All votes must be relinquished to withdraw locked tokens
+    #[error("All votes must be relinquished to withdraw locked tokens")]
+    AllVotesMustBeRelinquishedToWithdraw, // 1206
+
+    /// Voter doesn't belong to the given Registrar
+    #[error("Voter doesn't belong to the given Registrar")]
+    InvalidRegistrarForVoter, // 1207
+
+    /// Governing token owner doesn't match the Voter
+    #[error("Governing token owner doesn't match the Voter")]
+    InvalidGoverningTokenOwnerForVoter, // 1208
+
+    /// Account address doesn't match the expected PDA
+    #[error("Account address doesn't match the expected PDA")]
+    InvalidAccountAddress, // 1209
+
+    /// Governing token owner must sign transaction
+    #[error("Governing token owner must sign transaction")]
+    GoverningTokenOwnerMustSign, // 1210
+}
+
+impl PrintProgramError for LockupAddinError {
+    fn print<E>(&self) {
+        msg!("GOVERNANCE-ADDIN-LOCKUP-ERROR: {}", &self.to_string());
+    }
+}
+
+impl From<LockupAddinError> for ProgramError {
+    fn from(e: LockupAddinError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for LockupAddinError {
+    fn type_of() -> &'static str {
+        "Governance Lockup Addin Error"
+    }
+}
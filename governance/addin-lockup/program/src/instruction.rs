@@ -0,0 +1,349 @@
+//! Program instructions
+
+use {
+    crate::state::{
+        get_max_voter_weight_record_address, get_registrar_address, get_vault_address,
+        get_voter_address, get_voter_weight_record_address,
+    },
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program, sysvar,
+    },
+    spl_governance::state::token_owner_record::get_token_owner_record_address,
+};
+
+/// Instructions supported by the Lockup VoterWeight addin program
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+#[allow(clippy::large_enum_variant)]
+pub enum LockupAddinInstruction {
+    /// Creates Registrar for the Realm and governing token mint, together
+    /// with the vault holding the locked up tokens and the
+    /// MaxVoterWeightRecord
+    ///
+    ///   0. `[writable]` Registrar account. PDA seeds: ['registrar', realm,
+    ///      governing_token_mint]
+    ///   1. `[writable]` Vault token account. PDA seeds: ['vault', registrar]
+    ///   2. `[writable]` MaxVoterWeightRecord. PDA seeds:
+    ///      ['max-voter-weight-record', registrar]
+    ///   3. `[]` Governance program id
+    ///   4. `[]` Realm account
+    ///   5. `[]` Governing token mint
+    ///   6. `[signer]` Realm authority
+    ///   7. `[signer]` Payer
+    ///   8. `[]` System program
+    ///   9. `[]` SPL Token program
+    ///   10. `[]` Sysvar Rent
+    CreateRegistrar {
+        #[allow(dead_code)]
+        /// Lockup duration giving the locked up tokens full voter weight
+        max_lockup_secs: u64,
+    },
+
+    /// Creates Voter and VoterWeightRecord for the governing token owner
+    ///
+    ///   0. `[]` Registrar account
+    ///   1. `[writable]` Voter account. PDA seeds: ['voter', registrar,
+    ///      governing_token_owner]
+    ///   2. `[writable]` VoterWeightRecord. PDA seeds: ['voter-weight-record',
+    ///      registrar, governing_token_owner]
+    ///   3. `[signer]` Governing token owner
+    ///   4. `[signer]` Payer
+    ///   5. `[]` System program
+    CreateVoter {},
+
+    /// Locks up the given amount of governing tokens until at least
+    /// `lockup_secs` from now
+    /// If the Voter's lockup ends later then the end of the lockup is kept and
+    /// the tokens are added to it
+    ///
+    ///   0. `[writable]` Registrar account
+    ///   1. `[writable]` Voter account
+    ///   2. `[writable]` Vault token account
+    ///   3. `[writable]` Source token account
+    ///   4. `[signer]` Governing token owner and source token account
+    ///      authority
+    ///   5. `[]` SPL Token program
+    Lock {
+        #[allow(dead_code)]
+        /// Amount of governing tokens to lock up
+        amount: u64,
+
+        #[allow(dead_code)]
+        /// Min lockup duration from now, at most max_lockup_secs of the
+        /// Registrar
+        lockup_secs: u64,
+    },
+
+    /// Extends the Voter's lockup to end `lockup_secs` from now
+    ///
+    ///   0. `[]` Registrar account
+    ///   1. `[writable]` Voter account
+    ///   2. `[signer]` Governing token owner
+    ExtendLockup {
+        #[allow(dead_code)]
+        /// Lockup duration from now, at most max_lockup_secs of the Registrar
+        /// and not ending before the current lockup
+        lockup_secs: u64,
+    },
+
+    /// Withdraws governing tokens once the Voter's lockup has ended
+    /// The governing token owner must have no unrelinquished votes
+    ///
+    ///   0. `[writable]` Registrar account
+    ///   1. `[writable]` Voter account
+    ///   2. `[writable]` Vault token account
+    ///   3. `[writable]` Destination token account
+    ///   4. `[signer]` Governing token owner
+    ///   5. `[]` TokenOwnerRecord of the governing token owner in the Realm,
+    ///      it doesn't have to exist
+    ///   6. `[]` SPL Token program
+    Withdraw {
+        #[allow(dead_code)]
+        /// Amount of governing tokens to withdraw
+        amount: u64,
+    },
+
+    /// Updates VoterWeightRecord with the current voter weight
+    /// The weight expires at the current slot and the instruction must be
+    /// invoked in the same transaction as the governance instruction using it
+    ///
+    ///   0. `[]` Registrar account
+    ///   1. `[]` Voter account
+    ///   2. `[writable]` VoterWeightRecord
+    UpdateVoterWeightRecord {},
+
+    /// Updates MaxVoterWeightRecord with the current max voter weight
+    /// The weight expires at the current slot and the instruction must be
+    /// invoked in the same transaction as the governance instruction using it
+    ///
+    ///   0. `[]` Registrar account
+    ///   1. `[writable]` MaxVoterWeightRecord
+    UpdateMaxVoterWeightRecord {},
+}
+
+/// Creates CreateRegistrar instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_registrar(
+    program_id: &Pubkey,
+    // Accounts
+    governance_program_id: &Pubkey,
+    realm: &Pubkey,
+    governing_token_mint: &Pubkey,
+    realm_authority: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    max_lockup_secs: u64,
+) -> Instruction {
+    let registrar_address = get_registrar_address(program_id, realm, governing_token_mint);
+
+    let accounts = vec![
+        AccountMeta::new(registrar_address, false),
+        AccountMeta::new(get_vault_address(program_id, &registrar_address), false),
+        AccountMeta::new(
+            get_max_voter_weight_record_address(program_id, &registrar_address),
+            false,
+        ),
+        AccountMeta::new_readonly(*governance_program_id, false),
+        AccountMeta::new_readonly(*realm, false),
+        AccountMeta::new_readonly(*governing_token_mint, false),
+        AccountMeta::new_readonly(*realm_authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let instruction = LockupAddinInstruction::CreateRegistrar { max_lockup_secs };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates CreateVoter instruction
+pub fn create_voter(
+    program_id: &Pubkey,
+    // Accounts
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*registrar, false),
+        AccountMeta::new(
+            get_voter_address(program_id, registrar, governing_token_owner),
+            false,
+        ),
+        AccountMeta::new(
+            get_voter_weight_record_address(program_id, registrar, governing_token_owner),
+            false,
+        ),
+        AccountMeta::new_readonly(*governing_token_owner, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = LockupAddinInstruction::CreateVoter {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates Lock instruction
+pub fn lock(
+    program_id: &Pubkey,
+    // Accounts
+    registrar: &Pubkey,
+    source_token_account: &Pubkey,
+    governing_token_owner: &Pubkey,
+    // Args
+    amount: u64,
+    lockup_secs: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*registrar, false),
+        AccountMeta::new(
+            get_voter_address(program_id, registrar, governing_token_owner),
+            false,
+        ),
+        AccountMeta::new(get_vault_address(program_id, registrar), false),
+        AccountMeta::new(*source_token_account, false),
+        AccountMeta::new_readonly(*governing_token_owner, true),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    let instruction = LockupAddinInstruction::Lock {
+        amount,
+        lockup_secs,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates ExtendLockup instruction
+pub fn extend_lockup(
+    program_id: &Pubkey,
+    // Accounts
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+    // Args
+    lockup_secs: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*registrar, false),
+        AccountMeta::new(
+            get_voter_address(program_id, registrar, governing_token_owner),
+            false,
+        ),
+        AccountMeta::new_readonly(*governing_token_owner, true),
+    ];
+
+    let instruction = LockupAddinInstruction::ExtendLockup { lockup_secs };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates Withdraw instruction
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw(
+    program_id: &Pubkey,
+    // Accounts
+    governance_program_id: &Pubkey,
+    realm: &Pubkey,
+    governing_token_mint: &Pubkey,
+    destination_token_account: &Pubkey,
+    governing_token_owner: &Pubkey,
+    // Args
+    amount: u64,
+) -> Instruction {
+    let registrar_address = get_registrar_address(program_id, realm, governing_token_mint);
+    let token_owner_record_address = get_token_owner_record_address(
+        governance_program_id,
+        realm,
+        governing_token_mint,
+        governing_token_owner,
+    );
+
+    let accounts = vec![
+        AccountMeta::new(registrar_address, false),
+        AccountMeta::new(
+            get_voter_address(program_id, &registrar_address, governing_token_owner),
+            false,
+        ),
+        AccountMeta::new(get_vault_address(program_id, &registrar_address), false),
+        AccountMeta::new(*destination_token_account, false),
+        AccountMeta::new_readonly(*governing_token_owner, true),
+        AccountMeta::new_readonly(token_owner_record_address, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    let instruction = LockupAddinInstruction::Withdraw { amount };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates UpdateVoterWeightRecord instruction
+pub fn update_voter_weight_record(
+    program_id: &Pubkey,
+    // Accounts
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*registrar, false),
+        AccountMeta::new_readonly(
+            get_voter_address(program_id, registrar, governing_token_owner),
+            false,
+        ),
+        AccountMeta::new(
+            get_voter_weight_record_address(program_id, registrar, governing_token_owner),
+            false,
+        ),
+    ];
+
+    let instruction = LockupAddinInstruction::UpdateVoterWeightRecord {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates UpdateMaxVoterWeightRecord instruction
+pub fn update_max_voter_weight_record(program_id: &Pubkey, registrar: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*registrar, false),
+        AccountMeta::new(
+            get_max_voter_weight_record_address(program_id, registrar),
+            false,
+        ),
+    ];
+
+    let instruction = LockupAddinInstruction::UpdateMaxVoterWeightRecord {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
@@ -0,0 +1,13 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![deny(missing_docs)]
+//! Governance Lockup VoterWeight Addin program
+
+pub mod entrypoint;
+pub mod error;
+pub mod instruction;
+pub mod processor;
+pub mod state;
+
+// Export current sdk types for downstream users building with a different sdk
+// version
+pub use solana_program;
@@ -0,0 +1,524 @@
+//! Program processor
+
+use {
+    crate::{
+        error::LockupAddinError,
+        instruction::LockupAddinInstruction,
+        state::{
+            assert_is_pda_address, get_max_voter_weight_record_address_seeds,
+            get_registrar_address_seeds, get_registrar_data, get_vault_address_seeds,
+            get_voter_address_seeds, get_voter_data_for_registrar_and_owner,
+            get_voter_weight_record_address_seeds, LockupAddinAccountType, Registrar, Voter,
+        },
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance::{
+        state::{
+            realm::get_realm_data_for_governing_token_mint,
+            token_owner_record::{
+                get_token_owner_record_address_seeds,
+                get_token_owner_record_data_for_realm_and_governing_mint,
+            },
+        },
+        tools::spl_token::{
+            create_spl_token_account_signed, transfer_spl_tokens, transfer_spl_tokens_signed,
+        },
+    },
+    spl_governance_addin_api::{
+        max_voter_weight::MaxVoterWeightRecord, voter_weight::VoterWeightRecord,
+    },
+    spl_governance_tools::account::{create_and_serialize_account_signed, get_account_data},
+};
+
+/// Processes an instruction
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    msg!("VERSION:{:?}", env!("CARGO_PKG_VERSION"));
+
+    let instruction = LockupAddinInstruction::try_from_slice(input)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    msg!("GOVERNANCE-ADDIN-LOCKUP-INSTRUCTION: {:?}", instruction);
+
+    match instruction {
+        LockupAddinInstruction::CreateRegistrar { max_lockup_secs } => {
+            process_create_registrar(program_id, accounts, max_lockup_secs)
+        }
+        LockupAddinInstruction::CreateVoter {} => process_create_voter(program_id, accounts),
+        LockupAddinInstruction::Lock {
+            amount,
+            lockup_secs,
+        } => process_lock(program_id, accounts, amount, lockup_secs),
+        LockupAddinInstruction::ExtendLockup { lockup_secs } => {
+            process_extend_lockup(program_id, accounts, lockup_secs)
+        }
+        LockupAddinInstruction::Withdraw { amount } => {
+            process_withdraw(program_id, accounts, amount)
+        }
+        LockupAddinInstruction::UpdateVoterWeightRecord {} => {
+            process_update_voter_weight_record(program_id, accounts)
+        }
+        LockupAddinInstruction::UpdateMaxVoterWeightRecord {} => {
+            process_update_max_voter_weight_record(program_id, accounts)
+        }
+    }
+}
+
+/// Processes CreateRegistrar instruction
+pub fn process_create_registrar(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_lockup_secs: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let registrar_info = next_account_info(account_info_iter)?; // 0
+    let vault_info = next_account_info(account_info_iter)?; // 1
+    let max_voter_weight_record_info = next_account_info(account_info_iter)?; // 2
+    let governance_program_info = next_account_info(account_info_iter)?; // 3
+    let realm_info = next_account_info(account_info_iter)?; // 4
+    let governing_token_mint_info = next_account_info(account_info_iter)?; // 5
+    let realm_authority_info = next_account_info(account_info_iter)?; // 6
+    let payer_info = next_account_info(account_info_iter)?; // 7
+    let system_info = next_account_info(account_info_iter)?; // 8
+    let spl_token_info = next_account_info(account_info_iter)?; // 9
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 10
+
+    let rent = Rent::get()?;
+
+    if max_lockup_secs == 0 || max_lockup_secs > i64::MAX as u64 {
+        return Err(LockupAddinError::InvalidMaxLockupDuration.into());
+    }
+
+    let realm_data = get_realm_data_for_governing_token_mint(
+        governance_program_info.key,
+        realm_info,
+        governing_token_mint_info.key,
+    )?;
+
+    if !realm_authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if realm_data.authority != Some(*realm_authority_info.key) {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let registrar_data = Registrar {
+        account_type: LockupAddinAccountType::Registrar,
+        governance_program_id: *governance_program_info.key,
+        realm: *realm_info.key,
+        governing_token_mint: *governing_token_mint_info.key,
+        max_lockup_secs,
+        total_locked_amount: 0,
+        reserved: [0; 64],
+    };
+
+    create_and_serialize_account_signed(
+        payer_info,
+        registrar_info,
+        &registrar_data,
+        &get_registrar_address_seeds(realm_info.key, governing_token_mint_info.key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    create_spl_token_account_signed(
+        payer_info,
+        vault_info,
+        &get_vault_address_seeds(registrar_info.key),
+        governing_token_mint_info,
+        registrar_info,
+        program_id,
+        system_info,
+        spl_token_info,
+        rent_sysvar_info,
+        &rent,
+    )?;
+
+    let max_voter_weight_record_data = MaxVoterWeightRecord {
+        account_discriminator: MaxVoterWeightRecord::ACCOUNT_DISCRIMINATOR,
+        realm: *realm_info.key,
+        governing_token_mint: *governing_token_mint_info.key,
+        max_voter_weight: 0,
+        max_voter_weight_expiry: Some(0),
+        reserved: [0; 8],
+    };
+
+    create_and_serialize_account_signed(
+        payer_info,
+        max_voter_weight_record_info,
+        &max_voter_weight_record_data,
+        &get_max_voter_weight_record_address_seeds(registrar_info.key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    Ok(())
+}
+
+/// Processes CreateVoter instruction
+pub fn process_create_voter(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let registrar_info = next_account_info(account_info_iter)?; // 0
+    let voter_info = next_account_info(account_info_iter)?; // 1
+    let voter_weight_record_info = next_account_info(account_info_iter)?; // 2
+    let governing_token_owner_info = next_account_info(account_info_iter)?; // 3
+    let payer_info = next_account_info(account_info_iter)?; // 4
+    let system_info = next_account_info(account_info_iter)?; // 5
+
+    let rent = Rent::get()?;
+
+    let registrar_data = get_registrar_data(program_id, registrar_info)?;
+
+    if !governing_token_owner_info.is_signer {
+        return Err(LockupAddinError::GoverningTokenOwnerMustSign.into());
+    }
+
+    let voter_data = Voter {
+        account_type: LockupAddinAccountType::Voter,
+        registrar: *registrar_info.key,
+        governing_token_owner: *governing_token_owner_info.key,
+        amount: 0,
+        lockup_end_ts: 0,
+        reserved: [0; 64],
+    };
+
+    create_and_serialize_account_signed(
+        payer_info,
+        voter_info,
+        &voter_data,
+        &get_voter_address_seeds(registrar_info.key, governing_token_owner_info.key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    let voter_weight_record_data = VoterWeightRecord {
+        account_discriminator: VoterWeightRecord::ACCOUNT_DISCRIMINATOR,
+        realm: registrar_data.realm,
+        governing_token_mint: registrar_data.governing_token_mint,
+        governing_token_owner: *governing_token_owner_info.key,
+        voter_weight: 0,
+        voter_weight_expiry: Some(0),
+        weight_action: None,
+        weight_action_target: None,
+        reserved: [0; 8],
+    };
+
+    create_and_serialize_account_signed(
+        payer_info,
+        voter_weight_record_info,
+        &voter_weight_record_data,
+        &get_voter_weight_record_address_seeds(registrar_info.key, governing_token_owner_info.key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    Ok(())
+}
+
+/// Returns the lockup end for a lockup of the given duration from now
+fn get_lockup_end_ts(
+    registrar_data: &Registrar,
+    lockup_secs: u64,
+    clock: &Clock,
+) -> Result<i64, ProgramError> {
+    if lockup_secs > registrar_data.max_lockup_secs {
+        return Err(LockupAddinError::LockupDurationTooLong.into());
+    }
+
+    // max_lockup_secs is validated to fit i64 when the Registrar is created
+    Ok(clock
+        .unix_timestamp
+        .checked_add(lockup_secs as i64)
+        .unwrap())
+}
+
+/// Processes Lock instruction
+pub fn process_lock(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    lockup_secs: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let registrar_info = next_account_info(account_info_iter)?; // 0
+    let voter_info = next_account_info(account_info_iter)?; // 1
+    let vault_info = next_account_info(account_info_iter)?; // 2
+    let source_token_info = next_account_info(account_info_iter)?; // 3
+    let governing_token_owner_info = next_account_info(account_info_iter)?; // 4
+    let spl_token_info = next_account_info(account_info_iter)?; // 5
+
+    let clock = Clock::get()?;
+
+    if amount == 0 {
+        return Err(LockupAddinError::InvalidAmount.into());
+    }
+
+    if !governing_token_owner_info.is_signer {
+        return Err(LockupAddinError::GoverningTokenOwnerMustSign.into());
+    }
+
+    let mut registrar_data = get_registrar_data(program_id, registrar_info)?;
+    let mut voter_data = get_voter_data_for_registrar_and_owner(
+        program_id,
+        voter_info,
+        registrar_info.key,
+        governing_token_owner_info.key,
+    )?;
+    assert_is_pda_address(
+        program_id,
+        vault_info,
+        &get_vault_address_seeds(registrar_info.key),
+    )?;
+
+    let lockup_end_ts = get_lockup_end_ts(&registrar_data, lockup_secs, &clock)?;
+
+    transfer_spl_tokens(
+        source_token_info,
+        vault_info,
+        governing_token_owner_info,
+        amount,
+        spl_token_info,
+    )?;
+
+    voter_data.amount = voter_data.amount.checked_add(amount).unwrap();
+    voter_data.lockup_end_ts = voter_data.lockup_end_ts.max(lockup_end_ts);
+    borsh::to_writer(&mut voter_info.data.borrow_mut()[..], &voter_data)?;
+
+    registrar_data.total_locked_amount = registrar_data
+        .total_locked_amount
+        .checked_add(amount)
+        .unwrap();
+    borsh::to_writer(&mut registrar_info.data.borrow_mut()[..], &registrar_data)?;
+
+    Ok(())
+}
+
+/// Processes ExtendLockup instruction
+pub fn process_extend_lockup(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lockup_secs: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let registrar_info = next_account_info(account_info_iter)?; // 0
+    let voter_info = next_account_info(account_info_iter)?; // 1
+    let governing_token_owner_info = next_account_info(account_info_iter)?; // 2
+
+    let clock = Clock::get()?;
+
+    if !governing_token_owner_info.is_signer {
+        return Err(LockupAddinError::GoverningTokenOwnerMustSign.into());
+    }
+
+    let registrar_data = get_registrar_data(program_id, registrar_info)?;
+    let mut voter_data = get_voter_data_for_registrar_and_owner(
+        program_id,
+        voter_info,
+        registrar_info.key,
+        governing_token_owner_info.key,
+    )?;
+
+    let lockup_end_ts = get_lockup_end_ts(&registrar_data, lockup_secs, &clock)?;
+
+    if lockup_end_ts < voter_data.lockup_end_ts {
+        return Err(LockupAddinError::LockupCannotBeShortened.into());
+    }
+
+    voter_data.lockup_end_ts = lockup_end_ts;
+    borsh::to_writer(&mut voter_info.data.borrow_mut()[..], &voter_data)?;
+
+    Ok(())
+}
+
+/// Processes Withdraw instruction
+pub fn process_withdraw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let registrar_info = next_account_info(account_info_iter)?; // 0
+    let voter_info = next_account_info(account_info_iter)?; // 1
+    let vault_info = next_account_info(account_info_iter)?; // 2
+    let destination_token_info = next_account_info(account_info_iter)?; // 3
+    let governing_token_owner_info = next_account_info(account_info_iter)?; // 4
+    let token_owner_record_info = next_account_info(account_info_iter)?; // 5
+    let spl_token_info = next_account_info(account_info_iter)?; // 6
+
+    let clock = Clock::get()?;
+
+    if amount == 0 {
+        return Err(LockupAddinError::InvalidAmount.into());
+    }
+
+    if !governing_token_owner_info.is_signer {
+        return Err(LockupAddinError::GoverningTokenOwnerMustSign.into());
+    }
+
+    let mut registrar_data = get_registrar_data(program_id, registrar_info)?;
+    let mut voter_data = get_voter_data_for_registrar_and_owner(
+        program_id,
+        voter_info,
+        registrar_info.key,
+        governing_token_owner_info.key,
+    )?;
+
+    if voter_data.get_remaining_lockup_secs(clock.unix_timestamp) > 0 {
+        return Err(LockupAddinError::LockupNotExpired.into());
+    }
+
+    if amount > voter_data.amount {
+        return Err(LockupAddinError::InsufficientLockedAmount.into());
+    }
+
+    // Tokens used for votes which are still counted on Proposals can't be
+    // withdrawn and locked up again from another wallet to vote twice
+    assert_is_pda_address(
+        &registrar_data.governance_program_id,
+        token_owner_record_info,
+        &get_token_owner_record_address_seeds(
+            &registrar_data.realm,
+            &registrar_data.governing_token_mint,
+            governing_token_owner_info.key,
+        ),
+    )?;
+
+    if !token_owner_record_info.data_is_empty() {
+        let token_owner_record_data = get_token_owner_record_data_for_realm_and_governing_mint(
+            &registrar_data.governance_program_id,
+            token_owner_record_info,
+            &registrar_data.realm,
+            &registrar_data.governing_token_mint,
+        )?;
+
+        if token_owner_record_data.unrelinquished_votes_count > 0 {
+            return Err(LockupAddinError::AllVotesMustBeRelinquishedToWithdraw.into());
+        }
+    }
+
+    transfer_spl_tokens_signed(
+        vault_info,
+        destination_token_info,
+        registrar_info,
+        &get_registrar_address_seeds(&registrar_data.realm, &registrar_data.governing_token_mint),
+        program_id,
+        amount,
+        spl_token_info,
+    )?;
+
+    voter_data.amount = voter_data.amount.checked_sub(amount).unwrap();
+    borsh::to_writer(&mut voter_info.data.borrow_mut()[..], &voter_data)?;
+
+    registrar_data.total_locked_amount = registrar_data
+        .total_locked_amount
+        .checked_sub(amount)
+        .unwrap();
+    borsh::to_writer(&mut registrar_info.data.borrow_mut()[..], &registrar_data)?;
+
+    Ok(())
+}
+
+/// Processes UpdateVoterWeightRecord instruction
+pub fn process_update_voter_weight_record(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let registrar_info = next_account_info(account_info_iter)?; // 0
+    let voter_info = next_account_info(account_info_iter)?; // 1
+    let voter_weight_record_info = next_account_info(account_info_iter)?; // 2
+
+    let clock = Clock::get()?;
+
+    let registrar_data = get_registrar_data(program_id, registrar_info)?;
+    let voter_data = get_account_data::<Voter>(program_id, voter_info)?;
+
+    if voter_data.registrar != *registrar_info.key {
+        return Err(LockupAddinError::InvalidRegistrarForVoter.into());
+    }
+
+    assert_is_pda_address(
+        program_id,
+        voter_weight_record_info,
+        &get_voter_weight_record_address_seeds(
+            registrar_info.key,
+            &voter_data.governing_token_owner,
+        ),
+    )?;
+
+    let mut voter_weight_record_data =
+        get_account_data::<VoterWeightRecord>(program_id, voter_weight_record_info)?;
+
+    voter_weight_record_data.voter_weight =
+        voter_data.get_voter_weight(registrar_data.max_lockup_secs, clock.unix_timestamp);
+    voter_weight_record_data.voter_weight_expiry = Some(clock.slot);
+
+    borsh::to_writer(
+        &mut voter_weight_record_info.data.borrow_mut()[..],
+        &voter_weight_record_data,
+    )?;
+
+    Ok(())
+}
+
+/// Processes UpdateMaxVoterWeightRecord instruction
+pub fn process_update_max_voter_weight_record(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let registrar_info = next_account_info(account_info_iter)?; // 0
+    let max_voter_weight_record_info = next_account_info(account_info_iter)?; // 1
+
+    let clock = Clock::get()?;
+
+    let registrar_data = get_registrar_data(program_id, registrar_info)?;
+
+    assert_is_pda_address(
+        program_id,
+        max_voter_weight_record_info,
+        &get_max_voter_weight_record_address_seeds(registrar_info.key),
+    )?;
+
+    let mut max_voter_weight_record_data =
+        get_account_data::<MaxVoterWeightRecord>(program_id, max_voter_weight_record_info)?;
+
+    max_voter_weight_record_data.max_voter_weight = registrar_data.get_max_voter_weight();
+    max_voter_weight_record_data.max_voter_weight_expiry = Some(clock.slot);
+
+    borsh::to_writer(
+        &mut max_voter_weight_record_info.data.borrow_mut()[..],
+        &max_voter_weight_record_data,
+    )?;
+
+    Ok(())
+}
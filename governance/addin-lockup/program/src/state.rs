@@ -0,0 +1,325 @@
+//! Program state
+
+use {
+    crate::error::LockupAddinError,
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, clock::UnixTimestamp, program_error::ProgramError,
+        program_pack::IsInitialized, pubkey::Pubkey,
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// Defines all Lockup Addin accounts types
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum LockupAddinAccountType {
+    /// Default uninitialized account state
+    Uninitialized,
+
+    /// Registrar of locked up governing tokens for a Realm
+    Registrar,
+
+    /// Locked up governing tokens of a voter
+    Voter,
+}
+
+/// Registrar of locked up governing tokens for a Realm and governing token
+/// mint
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct Registrar {
+    /// Account type
+    pub account_type: LockupAddinAccountType,
+
+    /// The governance program the Realm belongs to
+    pub governance_program_id: Pubkey,
+
+    /// The Realm the Registrar belongs to
+    pub realm: Pubkey,
+
+    /// Governing token mint of the locked up tokens
+    pub governing_token_mint: Pubkey,
+
+    /// Lockup duration giving the locked up tokens full voter weight
+    /// Voter weight decays linearly to zero as the remaining lockup shrinks
+    pub max_lockup_secs: u64,
+
+    /// Total amount of tokens locked up by all voters
+    pub total_locked_amount: u64,
+
+    /// Reserved space for future versions
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for Registrar {}
+
+impl IsInitialized for Registrar {
+    fn is_initialized(&self) -> bool {
+        self.account_type == LockupAddinAccountType::Registrar
+    }
+}
+
+impl Registrar {
+    /// Returns the max voter weight of the Registrar which is the weight of
+    /// all the locked up tokens if they were locked up for max lockup
+    /// duration
+    pub fn get_max_voter_weight(&self) -> u64 {
+        self.total_locked_amount
+    }
+}
+
+/// Locked up governing tokens of a governing token owner
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct Voter {
+    /// Account type
+    pub account_type: LockupAddinAccountType,
+
+    /// The Registrar the Voter belongs to
+    pub registrar: Pubkey,
+
+    /// The owner of the locked up tokens
+    pub governing_token_owner: Pubkey,
+
+    /// Amount of locked up tokens
+    pub amount: u64,
+
+    /// The time the lockup ends and the tokens can be withdrawn
+    pub lockup_end_ts: UnixTimestamp,
+
+    /// Reserved space for future versions
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for Voter {}
+
+impl IsInitialized for Voter {
+    fn is_initialized(&self) -> bool {
+        self.account_type == LockupAddinAccountType::Voter
+    }
+}
+
+impl Voter {
+    /// Returns the remaining lockup duration at the given time
+    pub fn get_remaining_lockup_secs(&self, current_unix_timestamp: UnixTimestamp) -> u64 {
+        self.lockup_end_ts
+            .saturating_sub(current_unix_timestamp)
+            .max(0) as u64
+    }
+
+    /// Returns the voter weight at the given time, which decays linearly from
+    /// the locked up amount with max lockup duration remaining to zero at the
+    /// end of the lockup
+    pub fn get_voter_weight(
+        &self,
+        max_lockup_secs: u64,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> u64 {
+        let remaining_lockup_secs = self
+            .get_remaining_lockup_secs(current_unix_timestamp)
+            .min(max_lockup_secs);
+
+        (self.amount as u128 * remaining_lockup_secs as u128 / max_lockup_secs as u128) as u64
+    }
+
+    /// Asserts the Voter belongs to the given Registrar and governing token
+    /// owner
+    pub fn assert_is_valid_voter(
+        &self,
+        registrar: &Pubkey,
+        governing_token_owner: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.registrar != *registrar {
+            return Err(LockupAddinError::InvalidRegistrarForVoter.into());
+        }
+
+        if self.governing_token_owner != *governing_token_owner {
+            return Err(LockupAddinError::InvalidGoverningTokenOwnerForVoter.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns Registrar PDA seeds
+pub fn get_registrar_address_seeds<'a>(
+    realm: &'a Pubkey,
+    governing_token_mint: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [b"registrar", realm.as_ref(), governing_token_mint.as_ref()]
+}
+
+/// Returns Registrar PDA address
+pub fn get_registrar_address(
+    program_id: &Pubkey,
+    realm: &Pubkey,
+    governing_token_mint: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_registrar_address_seeds(realm, governing_token_mint),
+        program_id,
+    )
+    .0
+}
+
+/// Returns vault PDA seeds of the Registrar, the token account holding the
+/// locked up tokens
+pub fn get_vault_address_seeds(registrar: &Pubkey) -> [&[u8]; 2] {
+    [b"vault", registrar.as_ref()]
+}
+
+/// Returns vault PDA address of the Registrar
+pub fn get_vault_address(program_id: &Pubkey, registrar: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_vault_address_seeds(registrar), program_id).0
+}
+
+/// Returns Voter PDA seeds
+pub fn get_voter_address_seeds<'a>(
+    registrar: &'a Pubkey,
+    governing_token_owner: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [b"voter", registrar.as_ref(), governing_token_owner.as_ref()]
+}
+
+/// Returns Voter PDA address
+pub fn get_voter_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_voter_address_seeds(registrar, governing_token_owner),
+        program_id,
+    )
+    .0
+}
+
+/// Returns VoterWeightRecord PDA seeds
+pub fn get_voter_weight_record_address_seeds<'a>(
+    registrar: &'a Pubkey,
+    governing_token_owner: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        b"voter-weight-record",
+        registrar.as_ref(),
+        governing_token_owner.as_ref(),
+    ]
+}
+
+/// Returns VoterWeightRecord PDA address
+pub fn get_voter_weight_record_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_voter_weight_record_address_seeds(registrar, governing_token_owner),
+        program_id,
+    )
+    .0
+}
+
+/// Returns MaxVoterWeightRecord PDA seeds
+pub fn get_max_voter_weight_record_address_seeds(registrar: &Pubkey) -> [&[u8]; 2] {
+    [b"max-voter-weight-record", registrar.as_ref()]
+}
+
+/// Returns MaxVoterWeightRecord PDA address
+pub fn get_max_voter_weight_record_address(program_id: &Pubkey, registrar: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_max_voter_weight_record_address_seeds(registrar),
+        program_id,
+    )
+    .0
+}
+
+/// Asserts the given account address is the PDA derived from the given seeds
+pub fn assert_is_pda_address(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    seeds: &[&[u8]],
+) -> Result<(), ProgramError> {
+    if Pubkey::find_program_address(seeds, program_id).0 != *account_info.key {
+        return Err(LockupAddinError::InvalidAccountAddress.into());
+    }
+
+    Ok(())
+}
+
+/// Deserializes Registrar account and checks owner program
+pub fn get_registrar_data(
+    program_id: &Pubkey,
+    registrar_info: &AccountInfo,
+) -> Result<Registrar, ProgramError> {
+    get_account_data::<Registrar>(program_id, registrar_info)
+}
+
+/// Deserializes Voter account and checks it belongs to the given Registrar
+/// and governing token owner
+pub fn get_voter_data_for_registrar_and_owner(
+    program_id: &Pubkey,
+    voter_info: &AccountInfo,
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+) -> Result<Voter, ProgramError> {
+    let voter_data = get_account_data::<Voter>(program_id, voter_info)?;
+
+    voter_data.assert_is_valid_voter(registrar, governing_token_owner)?;
+
+    Ok(voter_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_voter(amount: u64, lockup_end_ts: UnixTimestamp) -> Voter {
+        Voter {
+            account_type: LockupAddinAccountType::Voter,
+            registrar: Pubkey::new_unique(),
+            governing_token_owner: Pubkey::new_unique(),
+            amount,
+            lockup_end_ts,
+            reserved: [0; 64],
+        }
+    }
+
+    #[test]
+    fn test_voter_weight_decays_linearly() {
+        // Arrange
+        let voter = create_test_voter(1_000, 1_000);
+
+        // Act + Assert
+        assert_eq!(voter.get_voter_weight(1_000, 0), 1_000);
+        assert_eq!(voter.get_voter_weight(1_000, 250), 750);
+        assert_eq!(voter.get_voter_weight(1_000, 500), 500);
+        assert_eq!(voter.get_voter_weight(1_000, 999), 1);
+    }
+
+    #[test]
+    fn test_voter_weight_is_zero_after_lockup_end() {
+        // Arrange
+        let voter = create_test_voter(1_000, 1_000);
+
+        // Act + Assert
+        assert_eq!(voter.get_voter_weight(1_000, 1_000), 0);
+        assert_eq!(voter.get_voter_weight(1_000, 5_000), 0);
+        assert_eq!(voter.get_remaining_lockup_secs(5_000), 0);
+    }
+
+    #[test]
+    fn test_voter_weight_is_capped_at_max_lockup() {
+        // Arrange
+        let voter = create_test_voter(1_000, 10_000);
+
+        // Act + Assert
+        assert_eq!(voter.get_voter_weight(1_000, 0), 1_000);
+    }
+
+    #[test]
+    fn test_voter_weight_with_max_amount() {
+        // Arrange
+        let voter = create_test_voter(u64::MAX, i64::MAX);
+
+        // Act + Assert
+        assert_eq!(voter.get_voter_weight(i64::MAX as u64, i64::MIN), u64::MAX);
+    }
+}
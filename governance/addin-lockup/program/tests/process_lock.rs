@@ -0,0 +1,203 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+
+use {
+    program_test::LockupAddinProgramTest, solana_program_test::tokio,
+    spl_governance_addin_lockup::error::LockupAddinError,
+};
+
+#[tokio::test]
+async fn test_lock() {
+    // Arrange
+    let mut lockup_addin_test = LockupAddinProgramTest::start_new().await;
+
+    let registrar_cookie = lockup_addin_test.with_registrar(1_000_000).await;
+    let voter_cookie = lockup_addin_test.with_voter(&registrar_cookie, 100).await;
+
+    // Act
+    lockup_addin_test
+        .lock(&registrar_cookie, &voter_cookie, 100, 1_000_000)
+        .await
+        .unwrap();
+
+    // Assert
+    let clock = lockup_addin_test.bench.get_clock().await;
+
+    let voter_account = lockup_addin_test
+        .get_voter_account(&voter_cookie.address)
+        .await;
+
+    assert_eq!(100, voter_account.amount);
+    assert_eq!(
+        clock.unix_timestamp + 1_000_000,
+        voter_account.lockup_end_ts
+    );
+
+    let registrar_account = lockup_addin_test
+        .get_registrar_account(&registrar_cookie.address)
+        .await;
+
+    assert_eq!(100, registrar_account.total_locked_amount);
+}
+
+#[tokio::test]
+async fn test_lock_with_lockup_longer_than_max_error() {
+    // Arrange
+    let mut lockup_addin_test = LockupAddinProgramTest::start_new().await;
+
+    let registrar_cookie = lockup_addin_test.with_registrar(1_000_000).await;
+    let voter_cookie = lockup_addin_test.with_voter(&registrar_cookie, 100).await;
+
+    // Act
+    let err = lockup_addin_test
+        .lock(&registrar_cookie, &voter_cookie, 100, 1_000_001)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, LockupAddinError::LockupDurationTooLong.into());
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_half_lockup() {
+    // Arrange
+    let mut lockup_addin_test = LockupAddinProgramTest::start_new().await;
+
+    let registrar_cookie = lockup_addin_test.with_registrar(1_000_000).await;
+    let voter_cookie = lockup_addin_test.with_voter(&registrar_cookie, 100).await;
+
+    lockup_addin_test
+        .lock(&registrar_cookie, &voter_cookie, 100, 1_000_000)
+        .await
+        .unwrap();
+
+    let voter_account = lockup_addin_test
+        .get_voter_account(&voter_cookie.address)
+        .await;
+
+    lockup_addin_test
+        .advance_clock_past_timestamp(voter_account.lockup_end_ts - 500_000)
+        .await;
+
+    // Act
+    lockup_addin_test
+        .update_voter_weight_record(&registrar_cookie, &voter_cookie)
+        .await
+        .unwrap();
+
+    lockup_addin_test
+        .update_max_voter_weight_record(&registrar_cookie)
+        .await
+        .unwrap();
+
+    // Assert
+    let clock = lockup_addin_test.bench.get_clock().await;
+
+    let voter_weight_record = lockup_addin_test
+        .get_voter_weight_record(&registrar_cookie, &voter_cookie)
+        .await;
+
+    let expected_voter_weight = 100 * (voter_account.lockup_end_ts - clock.unix_timestamp) as u64
+        / registrar_cookie.max_lockup_secs;
+
+    assert_eq!(expected_voter_weight, voter_weight_record.voter_weight);
+    assert!(voter_weight_record.voter_weight < 50);
+    assert_eq!(Some(clock.slot), voter_weight_record.voter_weight_expiry);
+
+    let max_voter_weight_record = lockup_addin_test
+        .get_max_voter_weight_record(&registrar_cookie)
+        .await;
+
+    assert_eq!(100, max_voter_weight_record.max_voter_weight);
+}
+
+#[tokio::test]
+async fn test_extend_lockup_cannot_shorten_lockup_error() {
+    // Arrange
+    let mut lockup_addin_test = LockupAddinProgramTest::start_new().await;
+
+    let registrar_cookie = lockup_addin_test.with_registrar(1_000_000).await;
+    let voter_cookie = lockup_addin_test.with_voter(&registrar_cookie, 100).await;
+
+    lockup_addin_test
+        .lock(&registrar_cookie, &voter_cookie, 100, 1_000_000)
+        .await
+        .unwrap();
+
+    // Act
+    let err = lockup_addin_test
+        .extend_lockup(&registrar_cookie, &voter_cookie, 500_000)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, LockupAddinError::LockupCannotBeShortened.into());
+}
+
+#[tokio::test]
+async fn test_withdraw_after_lockup_end() {
+    // Arrange
+    let mut lockup_addin_test = LockupAddinProgramTest::start_new().await;
+
+    let registrar_cookie = lockup_addin_test.with_registrar(1_000).await;
+    let voter_cookie = lockup_addin_test.with_voter(&registrar_cookie, 100).await;
+
+    lockup_addin_test
+        .lock(&registrar_cookie, &voter_cookie, 100, 1_000)
+        .await
+        .unwrap();
+
+    let voter_account = lockup_addin_test
+        .get_voter_account(&voter_cookie.address)
+        .await;
+
+    lockup_addin_test
+        .advance_clock_past_timestamp(voter_account.lockup_end_ts)
+        .await;
+
+    // Act
+    lockup_addin_test
+        .withdraw(&registrar_cookie, &voter_cookie, 100)
+        .await
+        .unwrap();
+
+    // Assert
+    let voter_account = lockup_addin_test
+        .get_voter_account(&voter_cookie.address)
+        .await;
+
+    assert_eq!(0, voter_account.amount);
+
+    let registrar_account = lockup_addin_test
+        .get_registrar_account(&registrar_cookie.address)
+        .await;
+
+    assert_eq!(0, registrar_account.total_locked_amount);
+}
+
+#[tokio::test]
+async fn test_withdraw_before_lockup_end_error() {
+    // Arrange
+    let mut lockup_addin_test = LockupAddinProgramTest::start_new().await;
+
+    let registrar_cookie = lockup_addin_test.with_registrar(1_000_000).await;
+    let voter_cookie = lockup_addin_test.with_voter(&registrar_cookie, 100).await;
+
+    lockup_addin_test
+        .lock(&registrar_cookie, &voter_cookie, 100, 1_000_000)
+        .await
+        .unwrap();
+
+    // Act
+    let err = lockup_addin_test
+        .withdraw(&registrar_cookie, &voter_cookie, 100)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, LockupAddinError::LockupNotExpired.into());
+}
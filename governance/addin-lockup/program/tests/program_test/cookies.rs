@@ -0,0 +1,17 @@
+use {solana_program::pubkey::Pubkey, solana_sdk::signature::Keypair};
+
+#[derive(Debug)]
+pub struct RegistrarCookie {
+    pub address: Pubkey,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub governing_token_mint_authority: Keypair,
+    pub max_lockup_secs: u64,
+}
+
+#[derive(Debug)]
+pub struct VoterCookie {
+    pub address: Pubkey,
+    pub governing_token_owner: Keypair,
+    pub token_account: Pubkey,
+}
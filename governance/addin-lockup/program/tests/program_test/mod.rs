@@ -0,0 +1,334 @@
+use {
+    crate::program_test::cookies::{RegistrarCookie, VoterCookie},
+    solana_program::{clock::UnixTimestamp, program_error::ProgramError, pubkey::Pubkey},
+    solana_program_test::{processor, ProgramTest},
+    solana_sdk::{signature::Keypair, signer::Signer},
+    spl_governance::{
+        instruction::create_realm,
+        state::{
+            enums::MintMaxVoterWeightSource,
+            realm::{get_realm_address, GoverningTokenConfigAccountArgs},
+            realm_config::GoverningTokenType,
+        },
+    },
+    spl_governance_addin_api::{
+        max_voter_weight::MaxVoterWeightRecord, voter_weight::VoterWeightRecord,
+    },
+    spl_governance_addin_lockup::{
+        instruction::{
+            create_registrar, create_voter, extend_lockup, lock, update_max_voter_weight_record,
+            update_voter_weight_record, withdraw,
+        },
+        processor::process_instruction,
+        state::{
+            get_max_voter_weight_record_address, get_registrar_address, get_voter_address,
+            get_voter_weight_record_address, Registrar, Voter,
+        },
+    },
+    spl_governance_test_sdk::ProgramTestBench,
+    std::str::FromStr,
+};
+
+pub mod cookies;
+
+pub struct LockupAddinProgramTest {
+    pub bench: ProgramTestBench,
+    pub program_id: Pubkey,
+    pub governance_program_id: Pubkey,
+}
+
+impl LockupAddinProgramTest {
+    #[allow(dead_code)]
+    pub async fn start_new() -> Self {
+        let mut program_test = ProgramTest::default();
+
+        let program_id = Pubkey::from_str("LockupAddin11111111111111111111111111111111").unwrap();
+        program_test.add_program(
+            "spl_governance_addin_lockup",
+            program_id,
+            processor!(process_instruction),
+        );
+
+        let governance_program_id =
+            Pubkey::from_str("Governance111111111111111111111111111111111").unwrap();
+        program_test.add_program(
+            "spl_governance",
+            governance_program_id,
+            processor!(spl_governance::processor::process_instruction),
+        );
+
+        let bench = ProgramTestBench::start_new(program_test).await;
+
+        Self {
+            bench,
+            program_id,
+            governance_program_id,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_registrar(&mut self, max_lockup_secs: u64) -> RegistrarCookie {
+        let name = self.bench.get_unique_name("realm");
+
+        let realm_address = get_realm_address(&self.governance_program_id, &name);
+
+        let governing_token_mint_keypair = Keypair::new();
+        let governing_token_mint_authority = Keypair::new();
+
+        self.bench
+            .create_mint(
+                &governing_token_mint_keypair,
+                &governing_token_mint_authority.pubkey(),
+                None,
+            )
+            .await;
+
+        let realm_authority = Keypair::new();
+
+        let community_token_config_args = GoverningTokenConfigAccountArgs {
+            voter_weight_addin: Some(self.program_id),
+            max_voter_weight_addin: Some(self.program_id),
+            token_type: GoverningTokenType::default(),
+        };
+
+        let create_realm_ix = create_realm(
+            &self.governance_program_id,
+            &realm_authority.pubkey(),
+            &governing_token_mint_keypair.pubkey(),
+            &self.bench.payer.pubkey(),
+            None,
+            Some(community_token_config_args),
+            None,
+            name,
+            1,
+            MintMaxVoterWeightSource::FULL_SUPPLY_FRACTION,
+        );
+
+        let create_registrar_ix = create_registrar(
+            &self.program_id,
+            &self.governance_program_id,
+            &realm_address,
+            &governing_token_mint_keypair.pubkey(),
+            &realm_authority.pubkey(),
+            &self.bench.payer.pubkey(),
+            max_lockup_secs,
+        );
+
+        self.bench
+            .process_transaction(
+                &[create_realm_ix, create_registrar_ix],
+                Some(&[&realm_authority]),
+            )
+            .await
+            .unwrap();
+
+        RegistrarCookie {
+            address: get_registrar_address(
+                &self.program_id,
+                &realm_address,
+                &governing_token_mint_keypair.pubkey(),
+            ),
+            realm: realm_address,
+            governing_token_mint: governing_token_mint_keypair.pubkey(),
+            governing_token_mint_authority,
+            max_lockup_secs,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_voter(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        amount: u64,
+    ) -> VoterCookie {
+        let governing_token_owner = Keypair::new();
+
+        let token_account_cookie = self
+            .bench
+            .with_token_account(
+                &registrar_cookie.governing_token_mint,
+                &governing_token_owner.pubkey(),
+                &registrar_cookie.governing_token_mint_authority,
+                amount,
+            )
+            .await;
+
+        let create_voter_ix = create_voter(
+            &self.program_id,
+            &registrar_cookie.address,
+            &governing_token_owner.pubkey(),
+            &self.bench.payer.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(&[create_voter_ix], Some(&[&governing_token_owner]))
+            .await
+            .unwrap();
+
+        VoterCookie {
+            address: get_voter_address(
+                &self.program_id,
+                &registrar_cookie.address,
+                &governing_token_owner.pubkey(),
+            ),
+            governing_token_owner,
+            token_account: token_account_cookie.address,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub async fn lock(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_cookie: &VoterCookie,
+        amount: u64,
+        lockup_secs: u64,
+    ) -> Result<(), ProgramError> {
+        let lock_ix = lock(
+            &self.program_id,
+            &registrar_cookie.address,
+            &voter_cookie.token_account,
+            &voter_cookie.governing_token_owner.pubkey(),
+            amount,
+            lockup_secs,
+        );
+
+        self.bench
+            .process_transaction(&[lock_ix], Some(&[&voter_cookie.governing_token_owner]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn extend_lockup(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_cookie: &VoterCookie,
+        lockup_secs: u64,
+    ) -> Result<(), ProgramError> {
+        let extend_lockup_ix = extend_lockup(
+            &self.program_id,
+            &registrar_cookie.address,
+            &voter_cookie.governing_token_owner.pubkey(),
+            lockup_secs,
+        );
+
+        self.bench
+            .process_transaction(
+                &[extend_lockup_ix],
+                Some(&[&voter_cookie.governing_token_owner]),
+            )
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn withdraw(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_cookie: &VoterCookie,
+        amount: u64,
+    ) -> Result<(), ProgramError> {
+        let withdraw_ix = withdraw(
+            &self.program_id,
+            &self.governance_program_id,
+            &registrar_cookie.realm,
+            &registrar_cookie.governing_token_mint,
+            &voter_cookie.token_account,
+            &voter_cookie.governing_token_owner.pubkey(),
+            amount,
+        );
+
+        self.bench
+            .process_transaction(&[withdraw_ix], Some(&[&voter_cookie.governing_token_owner]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn update_voter_weight_record(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_cookie: &VoterCookie,
+    ) -> Result<(), ProgramError> {
+        let update_voter_weight_record_ix = update_voter_weight_record(
+            &self.program_id,
+            &registrar_cookie.address,
+            &voter_cookie.governing_token_owner.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(&[update_voter_weight_record_ix], None)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn update_max_voter_weight_record(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+    ) -> Result<(), ProgramError> {
+        let update_max_voter_weight_record_ix =
+            update_max_voter_weight_record(&self.program_id, &registrar_cookie.address);
+
+        self.bench
+            .process_transaction(&[update_max_voter_weight_record_ix], None)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn advance_clock_past_timestamp(&mut self, unix_timestamp: UnixTimestamp) {
+        let mut clock = self.bench.get_clock().await;
+        let mut n = 1;
+
+        while clock.unix_timestamp <= unix_timestamp {
+            // Since the exact time is not deterministic keep wrapping by arbitrary 400
+            // slots until we pass the requested timestamp
+            self.bench
+                .context
+                .warp_to_slot(clock.slot + n * 400)
+                .unwrap();
+
+            n += 1;
+            clock = self.bench.get_clock().await;
+        }
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_registrar_account(&mut self, registrar_address: &Pubkey) -> Registrar {
+        self.bench
+            .get_borsh_account::<Registrar>(registrar_address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_voter_account(&mut self, voter_address: &Pubkey) -> Voter {
+        self.bench.get_borsh_account::<Voter>(voter_address).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_voter_weight_record(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_cookie: &VoterCookie,
+    ) -> VoterWeightRecord {
+        let voter_weight_record_address = get_voter_weight_record_address(
+            &self.program_id,
+            &registrar_cookie.address,
+            &voter_cookie.governing_token_owner.pubkey(),
+        );
+
+        self.bench
+            .get_borsh_account::<VoterWeightRecord>(&voter_weight_record_address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_max_voter_weight_record(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+    ) -> MaxVoterWeightRecord {
+        let max_voter_weight_record_address =
+            get_max_voter_weight_record_address(&self.program_id, &registrar_cookie.address);
+
+        self.bench
+            .get_borsh_account::<MaxVoterWeightRecord>(&max_voter_weight_record_address)
+            .await
+    }
+}
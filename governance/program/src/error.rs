@@ -548,6 +548,306 @@ pub enum GovernanceError {
     /// TokenOwnerRecordLockAuthority not found
     #[error("TokenOwnerRecordLockAuthority not found")]
     TokenOwnerRecordLockAuthorityNotFound, // 631
+
+    /// Proposal is already linked
+    #[error("Proposal is already linked")]
+    ProposalAlreadyLinked, // 632
+
+    /// Cannot link Proposals of the same Governance
+    #[error("Cannot link Proposals of the same Governance")]
+    CannotLinkProposalsOfSameGovernance, // 633
+
+    /// Invalid Proposal for ProposalLink
+    #[error("Invalid Proposal for ProposalLink")]
+    InvalidProposalForProposalLink, // 634
+
+    /// ProposalLink is not committed
+    #[error("ProposalLink is not committed")]
+    ProposalLinkNotCommitted, // 635
+
+    /// Invalid State: Can't resolve ProposalLink
+    #[error("Invalid State: Can't resolve ProposalLink")]
+    InvalidStateCannotResolveProposalLink, // 636
+
+    /// Invalid ProposalLink config
+    #[error("Invalid ProposalLink config")]
+    InvalidProposalLinkConfig, // 637
+
+    /// Invalid ProposalTokenDeposit config
+    #[error("Invalid ProposalTokenDeposit config")]
+    InvalidProposalTokenDepositConfig, // 638
+
+    /// Invalid Proposal for ProposalTokenDeposit
+    #[error("Invalid Proposal for ProposalTokenDeposit")]
+    InvalidProposalForProposalTokenDeposit, // 639
+
+    /// Invalid destination token account for ProposalTokenDeposit
+    #[error("Invalid destination token account for ProposalTokenDeposit")]
+    InvalidDestinationForProposalTokenDeposit, // 640
+
+    /// Invalid State: Proposal voting is not completed
+    #[error("Invalid State: Proposal voting is not completed")]
+    InvalidStateVotingNotCompleted, // 641
+
+    /// Voter participation not recorded for VoteRecord
+    #[error("Voter participation not recorded for VoteRecord")]
+    VoterParticipationNotRecorded, // 642
+
+    /// Invalid State: Can't archive Proposal
+    #[error("Invalid State: Can't archive Proposal")]
+    InvalidStateCannotArchiveProposal, // 643
+
+    /// Proposal archive retention time has not elapsed
+    #[error("Proposal archive retention time has not elapsed")]
+    ProposalArchiveRetentionTimeNotElapsed, // 644
+
+    /// Invalid Proposal for ProposalArchive
+    #[error("Invalid Proposal for ProposalArchive")]
+    InvalidProposalForProposalArchive, // 645
+
+    /// Proposal is already archived
+    #[error("Proposal is already archived")]
+    ProposalAlreadyArchived, // 646
+
+    /// Cannot archive VoteRecord which is not relinquished
+    #[error("Cannot archive VoteRecord which is not relinquished")]
+    CannotArchiveUnrelinquishedVoteRecord, // 647
+
+    /// Not all VoteRecords of the Proposal are archived
+    #[error("Not all VoteRecords of the Proposal are archived")]
+    ProposalArchiveMissingVoteRecords, // 648
+
+    /// ProposalArchive is full
+    #[error("ProposalArchive is full")]
+    ProposalArchiveFull, // 649
+
+    /// ProposalTokenDeposit is not refunded
+    #[error("ProposalTokenDeposit is not refunded")]
+    ProposalTokenDepositNotRefunded, // 650
+
+    /// ProposalLink is not resolved
+    #[error("ProposalLink is not resolved")]
+    ProposalLinkNotResolved, // 651
+
+    /// Invalid NativeTreasury account
+    #[error("Invalid NativeTreasury account")]
+    InvalidNativeTreasuryAccount, // 652
+
+    /// Invalid ExecutionSimulationConfig
+    #[error("Invalid ExecutionSimulationConfig")]
+    InvalidExecutionSimulationConfig, // 653
+
+    /// Invalid ExecutionSimulationAttestation
+    #[error("Invalid ExecutionSimulationAttestation")]
+    InvalidExecutionSimulationAttestation, // 654
+
+    /// Execution simulation of the transaction failed
+    #[error("Execution simulation of the transaction failed")]
+    ExecutionSimulationFailed, // 655
+
+    /// ExecutionSimulationAttestation expired
+    #[error("ExecutionSimulationAttestation expired")]
+    ExecutionSimulationAttestationExpired, // 656
+
+    /// Simulation oracle must sign
+    #[error("Simulation oracle must sign")]
+    SimulationOracleMustSign, // 657
+
+    /// VoteIntent already exists
+    #[error("VoteIntent already exists")]
+    VoteIntentAlreadyExists, // 658
+
+    /// Invalid VoteIntent signature
+    #[error("Invalid VoteIntent signature")]
+    InvalidVoteIntentSignature, // 659
+
+    /// Invalid VoteIntent
+    #[error("Invalid VoteIntent")]
+    InvalidVoteIntent, // 660
+
+    /// Invalid FundingRound config
+    #[error("Invalid FundingRound config")]
+    InvalidFundingRoundConfig, // 661
+
+    /// FundingRound is not accepting contributions
+    #[error("FundingRound is not accepting contributions")]
+    FundingRoundNotAcceptingContributions, // 662
+
+    /// FundingRound contributions have not ended
+    #[error("FundingRound contributions have not ended")]
+    FundingRoundContributionsNotEnded, // 663
+
+    /// FundingRound already finalized
+    #[error("FundingRound already finalized")]
+    FundingRoundAlreadyFinalized, // 664
+
+    /// FundingRound not finalized
+    #[error("FundingRound not finalized")]
+    FundingRoundNotFinalized, // 665
+
+    /// FundingRound matching overflow
+    #[error("FundingRound matching overflow")]
+    FundingRoundMatchingOverflow, // 666
+
+    /// Invalid FundingRound escrow
+    #[error("Invalid FundingRound escrow")]
+    InvalidFundingRoundEscrow, // 667
+
+    /// Invalid FundingRound for FundingProject
+    #[error("Invalid FundingRound for FundingProject")]
+    InvalidFundingRoundForFundingProject, // 668
+
+    /// Invalid FundingProject recipient
+    #[error("Invalid FundingProject recipient")]
+    InvalidFundingProjectRecipient, // 669
+
+    /// Invalid FundingContribution amount
+    #[error("Invalid FundingContribution amount")]
+    InvalidFundingContributionAmount, // 670
+
+    /// FundingProject matching already claimed
+    #[error("FundingProject matching already claimed")]
+    FundingProjectMatchingAlreadyClaimed, // 671
+
+    /// Project owner must sign
+    #[error("Project owner must sign")]
+    ProjectOwnerMustSign, // 672
+
+    /// Not enough tokens to contribute to FundingProject
+    #[error("Not enough tokens to contribute to FundingProject")]
+    NotEnoughTokensToContributeToFundingProject, // 673
+
+    /// Invalid FundingContribution
+    #[error("Invalid FundingContribution")]
+    InvalidFundingContribution, // 674
+
+    /// Invalid Governance destination for FundingRound
+    #[error("Invalid Governance destination for FundingRound")]
+    InvalidGovernanceDestinationForFundingRound, // 675
+
+    /// Invalid CouncilElection config
+    #[error("Invalid CouncilElection config")]
+    InvalidCouncilElectionConfig, // 676
+
+    /// CouncilElection is not in voting state
+    #[error("CouncilElection is not in voting state")]
+    CouncilElectionNotVoting, // 677
+
+    /// CouncilElection voting has not ended yet
+    #[error("CouncilElection voting has not ended yet")]
+    CouncilElectionVotingNotEnded, // 678
+
+    /// CouncilElection already finalized
+    #[error("CouncilElection already finalized")]
+    CouncilElectionAlreadyFinalized, // 679
+
+    /// CouncilElection not finalized
+    #[error("CouncilElection not finalized")]
+    CouncilElectionNotFinalized, // 680
+
+    /// Invalid CouncilElection for CouncilCandidate
+    #[error("Invalid CouncilElection for CouncilCandidate")]
+    InvalidCouncilElectionForCouncilCandidate, // 681
+
+    /// Candidate must sign
+    #[error("Candidate must sign")]
+    CandidateMustSign, // 682
+
+    /// Not enough tokens to vote in CouncilElection
+    #[error("Not enough tokens to vote in CouncilElection")]
+    NotEnoughTokensToVoteInCouncilElection, // 683
+
+    /// All CouncilCandidates must be provided in ascending address order
+    #[error("All CouncilCandidates must be provided in ascending address order")]
+    InvalidCouncilCandidatesForFinalization, // 684
+
+    /// CouncilElection term must end after the current council term
+    #[error("CouncilElection term must end after the current council term")]
+    CouncilElectionTermEndsBeforeCurrentTerm, // 685
+
+    /// CouncilCandidate was not elected
+    #[error("CouncilCandidate was not elected")]
+    CouncilCandidateNotElected, // 686
+
+    /// CouncilSeat already claimed
+    #[error("CouncilSeat already claimed")]
+    CouncilSeatAlreadyClaimed, // 687
+
+    /// Invalid CouncilSeat
+    #[error("Invalid CouncilSeat")]
+    InvalidCouncilSeat, // 688
+
+    /// CouncilSeat is not active
+    #[error("CouncilSeat is not active")]
+    CouncilSeatNotActive, // 689
+
+    /// Realm must have council mint to run CouncilElection
+    #[error("Realm must have council mint to run CouncilElection")]
+    CouncilMintRequiredForCouncilElection, // 690
+
+    /// Invalid Realm for CouncilElection
+    #[error("Invalid Realm for CouncilElection")]
+    InvalidRealmForCouncilElection, // 691
+
+    /// CouncilElection vote already exists
+    #[error("CouncilElection vote already exists")]
+    CouncilElectionVoteAlreadyExists, // 692
+
+    /// Invalid VoteSessionKey
+    #[error("Invalid VoteSessionKey")]
+    InvalidVoteSessionKey, // 693
+
+    /// VoteSessionKey expired
+    #[error("VoteSessionKey expired")]
+    VoteSessionKeyExpired, // 694
+
+    /// Invalid VoteExtensionConfig
+    #[error("Invalid VoteExtensionConfig")]
+    InvalidVoteExtensionConfig, // 695
+
+    /// Invalid Proposal for ProposalDependency
+    #[error("Invalid Proposal for ProposalDependency")]
+    InvalidProposalForProposalDependency, // 696
+
+    /// Proposal can't depend on a Proposal in the current state
+    #[error("Proposal can't depend on a Proposal in the current state")]
+    InvalidStateCannotDependOnProposal, // 697
+
+    /// Proposal can only depend on a Proposal of the same Realm
+    #[error("Proposal can only depend on a Proposal of the same Realm")]
+    CannotDependOnProposalOfAnotherRealm, // 698
+
+    /// Proposal can't be executed before the Proposals it depends on are completed
+    #[error("Proposal can't be executed before the Proposals it depends on are completed")]
+    ProposalDependencyNotCompleted, // 699
+
+    /// All ProposalDependencies must be provided in ascending address order
+    #[error("All ProposalDependencies must be provided in ascending address order")]
+    InvalidProposalDependenciesOrder, // 700
+
+    /// Invalid AddinRegistry account address
+    #[error("Invalid AddinRegistry account address")]
+    InvalidAddinRegistryAddress, // 701
+
+    /// Invalid AddinRegistry for Realm
+    #[error("Invalid AddinRegistry for Realm")]
+    InvalidAddinRegistryForRealm, // 702
+
+    /// Addin already exists in AddinRegistry
+    #[error("Addin already exists in AddinRegistry")]
+    AddinRegistryEntryAlreadyExists, // 703
+
+    /// Addin doesn't exist in AddinRegistry
+    #[error("Addin doesn't exist in AddinRegistry")]
+    AddinRegistryEntryNotFound, // 704
+
+    /// Addin is not approved in the AddinRegistry of the Realm
+    #[error("Addin is not approved in the AddinRegistry of the Realm")]
+    AddinNotInAddinRegistry, // 705
+
+    /// Addin program version is below the min version pinned in AddinRegistry
+    #[error("Addin program version is below the min version pinned in AddinRegistry")]
+    AddinVersionBelowRegistryMinVersion, // 706
 }
 
 impl PrintProgramError for GovernanceError {
@@ -1,28 +1,52 @@
 //! Program instructions
 
 use {
-    crate::state::{
-        enums::MintMaxVoterWeightSource,
-        governance::{get_governance_address, GovernanceConfig},
-        native_treasury::get_native_treasury_address,
-        program_metadata::get_program_metadata_address,
-        proposal::{get_proposal_address, VoteType},
-        proposal_deposit::get_proposal_deposit_address,
-        proposal_transaction::{get_proposal_transaction_address, InstructionData},
-        realm::{
-            get_governing_token_holding_address, get_realm_address,
-            GoverningTokenConfigAccountArgs, GoverningTokenConfigArgs, RealmConfigArgs,
-            SetRealmAuthorityAction, SetRealmConfigItemArgs,
+    crate::{
+        state::{
+            addin_registry::get_addin_registry_address,
+            council_election::{
+                get_council_candidate_address, get_council_election_address,
+                get_council_election_vote_address, get_council_seat_address, CouncilElectionConfig,
+            },
+            enums::MintMaxVoterWeightSource,
+            execution_simulation::{
+                get_execution_simulation_attestation_address, ExecutionSimulationConfig,
+            },
+            funding_round::{
+                get_funding_contribution_address, get_funding_project_address,
+                get_funding_round_address, get_funding_round_escrow_address, FundingRoundConfig,
+            },
+            governance::{get_governance_address, GovernanceConfig},
+            native_treasury::get_native_treasury_address,
+            program_metadata::get_program_metadata_address,
+            proposal::{get_proposal_address, VoteType},
+            proposal_archive::get_proposal_archive_address,
+            proposal_dependency::get_proposal_dependency_address,
+            proposal_deposit::get_proposal_deposit_address,
+            proposal_link::get_proposal_link_address,
+            proposal_token_deposit::{
+                get_proposal_token_deposit_address, get_proposal_token_deposit_escrow_address,
+                ProposalTokenDepositConfig,
+            },
+            proposal_transaction::{get_proposal_transaction_address, InstructionData},
+            realm::{
+                get_governing_token_holding_address, get_realm_address,
+                GoverningTokenConfigAccountArgs, GoverningTokenConfigArgs, RealmConfigArgs,
+                SetRealmAuthorityAction, SetRealmConfigItemArgs,
+            },
+            realm_config::get_realm_config_address,
+            required_signatory::get_required_signatory_address,
+            signatory_record::get_signatory_record_address,
+            token_owner_record::get_token_owner_record_address,
+            vote_intent::get_vote_intent_address,
+            vote_record::{get_vote_record_address, Vote},
+            vote_session_key::get_vote_session_key_address,
         },
-        realm_config::get_realm_config_address,
-        required_signatory::get_required_signatory_address,
-        signatory_record::get_signatory_record_address,
-        token_owner_record::get_token_owner_record_address,
-        vote_record::{get_vote_record_address, Vote},
+        tools::bpf_loader_upgradeable::get_program_data_address,
     },
     borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
     solana_program::{
-        clock::UnixTimestamp,
+        clock::{Slot, UnixTimestamp},
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
         system_program, sysvar,
@@ -184,6 +208,21 @@ pub enum GovernanceInstruction {
     ///     The deposit is paid by the Payer of the transaction and can be
     ///     reclaimed using RefundProposalDeposit once the Proposal is no
     ///     longer active.
+    ///     The account must also be passed when ProposalTokenDeposit accounts
+    ///     are passed.
+    ///   11.`[writable]` Optional ProposalTokenDeposit account.
+    ///     * PDA seeds: ['proposal-token-deposit', proposal]
+    ///     Proposal token deposit is required for community Proposals when
+    ///     the Governance has ProposalTokenDepositConfig amount set.
+    ///     The deposit is paid in community tokens by the Governance Authority
+    ///     and held in escrow until it's released using
+    ///     RefundProposalTokenDeposit once the Proposal is no longer active.
+    ///   12.`[writable]` Optional ProposalTokenDeposit escrow token account.
+    ///     * PDA seeds: ['proposal-token-deposit-escrow', proposal]
+    ///   13.`[writable]` Optional community token source account owned by
+    ///     the Governance Authority
+    ///   14.`[]` Optional SPL Token program
+    ///   15.`[]` Optional Sysvar Rent
     CreateProposal {
         #[allow(dead_code)]
         /// UTF-8 encoded name of the proposal
@@ -320,7 +359,8 @@ pub enum GovernanceInstruction {
     ///     * PDA seeds: ['governance',realm, vote_governing_token_mint,
     ///       governing_token_owner]
     ///   5. `[signer]` Governance Authority (Token Owner or Governance
-    ///      Delegate)
+    ///      Delegate). The authority doesn't have to sign when the vote is
+    ///      relayed using VoteIntent signed by the authority
     ///   6. `[writable]` Proposal VoteRecord account.
     ///     * PDA seeds: ['governance',proposal,token_owner_record]
     ///   7. `[]` The Governing Token Mint which is used to cast the vote
@@ -335,8 +375,18 @@ pub enum GovernanceInstruction {
     ///   9. `[]` System program
     ///   10. `[]` RealmConfig account.
     ///     * PDA seeds: ['realm-config', realm]
-    ///   11. `[]` Optional Voter Weight Record
-    ///   12. `[]` Optional Max Voter Weight Record
+    ///   11. `[]` CouncilSeat account of the voter, required when voting with
+    ///      the council mint while the Realm has an active council term. An
+    ///      empty account can be provided by voters without a CouncilSeat
+    ///     * PDA seeds: ['council-seat', council_election, member]
+    ///   12. `[]` Optional Voter Weight Record
+    ///   13. `[]` Optional Max Voter Weight Record
+    ///   14. `[]` Optional VoteIntent account, required if Governance
+    ///      Authority is not a signer
+    ///     * PDA seeds: ['vote-intent', proposal, token_owner_record]
+    ///     Or VoteSessionKey account, required if Governance Authority is a
+    ///     session key signer
+    ///     * PDA seeds: ['vote-session-key', token_owner_record, session_key]
     CastVote {
         #[allow(dead_code)]
         /// User's vote
@@ -378,6 +428,9 @@ pub enum GovernanceInstruction {
     ///   7. `[writable]` Optional Beneficiary account which would receive
     ///      lamports when VoteRecord Account is disposed It's required only
     ///      when Proposal is still being voted on
+    ///   8. `[]` Optional VoteSessionKey account, required only when
+    ///      Governance Authority is a session key
+    ///     * PDA seeds: ['vote-session-key', token_owner_record, session_key]
     RelinquishVote,
 
     /// Executes a Transaction in the Proposal
@@ -390,7 +443,20 @@ pub enum GovernanceInstruction {
     ///   0. `[]` Governance account
     ///   1. `[writable]` Proposal account
     ///   2. `[writable]` ProposalTransaction account you wish to execute
+    ///   3. `[]` ProposalLink account, required only when the Proposal is
+    ///      linked
+    ///   4. `[]` ExecutionSimulationAttestation account, required only when
+    ///      the Governance has ExecutionSimulationConfig simulation oracle
+    ///      set (following the ProposalLink account for linked Proposals).
+    ///      PDA seeds: ['execution-simulation-attestation',
+    ///      proposal_transaction, simulation_oracle]
+    ///   5. `[]` ProposalDependency and depended on Proposal account pairs,
+    ///      one pair for each dependency of the Proposal, in ascending
+    ///      ProposalDependency address order (following the ProposalLink and
+    ///      ExecutionSimulationAttestation accounts when required)
     ///   3+ Any extra accounts that are part of the transaction, in order
+    ///      (following the ProposalLink, ExecutionSimulationAttestation and
+    ///      ProposalDependency accounts when required)
     ExecuteTransaction,
 
     /// Legacy CreateMintGovernance instruction
@@ -450,6 +516,11 @@ pub enum GovernanceInstruction {
     ///   9. `[]` Optional Max Council Voter Weight Addin Program Id
     ///   10. `[signer]` Optional Payer. Required if RealmConfig doesn't exist
     ///       and needs to be created
+    ///   11. `[]` AddinRegistry account. Required if the Realm uses its
+    ///       AddinRegistry
+    ///     * PDA seeds: ['addin-registry', realm]
+    ///   12. `[]` ProgramData account of each addin provided in 6-9, in the
+    ///       same order. Required if the Realm uses its AddinRegistry
     SetRealmConfig {
         #[allow(dead_code)]
         /// Realm config args
@@ -612,11 +683,377 @@ pub enum GovernanceInstruction {
     ///   2. `[signer]`  Realm authority
     ///   3. `[signer]` Payer
     ///   4. `[]` System
+    ///   5. `[writable]` AddinRegistry account. Required for
+    ///      AddinRegistryEntry
+    ///     * PDA seeds: ['addin-registry', realm]
     SetRealmConfigItem {
         #[allow(dead_code)]
         /// Config args
         args: SetRealmConfigItemArgs,
     },
+
+    /// Links N Proposals from different Governances
+    /// Once linked, none of the Proposals can execute its transactions until
+    /// at least threshold (M) of the Proposals succeeded and the link is
+    /// committed using ResolveProposalLink
+    /// All Proposals must be in Draft state
+    ///
+    ///   0. `[writable]` ProposalLink account. PDA seeds: ['proposal-link',
+    ///      first_proposal]
+    ///   1. `[signer]` Payer
+    ///   2. `[]` System program
+    ///
+    ///   For each linked Proposal (at least 2 and at most
+    ///   MAX_LINKED_PROPOSALS):
+    ///   3n+3. `[writable]` Proposal account
+    ///   3n+4. `[]` TokenOwnerRecord account of the Proposal owner
+    ///   3n+5. `[signer]` Governance Authority (Token Owner or Governance
+    ///      Delegate) of the Proposal
+    LinkProposals {
+        #[allow(dead_code)]
+        /// The number of linked Proposals which must succeed to commit the
+        /// link
+        threshold: u8,
+    },
+
+    /// Resolves ProposalLink once the outcome of the linked Proposals is
+    /// known
+    /// The link is committed when threshold Proposals succeeded and aborted
+    /// when too many of them were defeated, vetoed or cancelled for the
+    /// threshold to be reached
+    ///
+    ///   0. `[writable]` ProposalLink account
+    ///   1..N. `[]` The linked Proposal accounts in the order they were linked
+    ResolveProposalLink {},
+
+    /// Sets ProposalTokenDepositConfig for Governance
+    /// The config defines the amount of community tokens deposited in escrow
+    /// when a community Proposal is created and the participation floor below
+    /// which the deposit of a defeated Proposal is forfeited
+    ///
+    ///   0. `[writable, signer]` The Governance account the config is for
+    SetProposalTokenDepositConfig {
+        #[allow(dead_code)]
+        /// Proposal token deposit config
+        config: ProposalTokenDepositConfig,
+    },
+
+    /// Releases ProposalTokenDeposit once the Proposal is no longer active
+    /// The deposit is refunded to the depositor unless the Proposal was
+    /// defeated with participation below the floor captured when the deposit
+    /// was made, in which case it's forfeited to the Governance
+    /// The outcome is determined by the vote tallies recorded when the vote
+    /// was finalized
+    /// Once released the ProposalTokenDeposit and escrow accounts are closed
+    /// and their rent returned to the deposit payer
+    ///
+    ///   0. `[]` Proposal account
+    ///   1. `[writable]` ProposalTokenDeposit account.
+    ///     * PDA seeds: ['proposal-token-deposit', proposal]
+    ///   2. `[writable]` ProposalTokenDeposit escrow token account.
+    ///     * PDA seeds: ['proposal-token-deposit-escrow', proposal]
+    ///   3. `[writable]` Destination community token account owned by the
+    ///      depositor when refunded or by the Governance when forfeited
+    ///   4. `[writable]` Proposal deposit payer (beneficiary) account
+    ///   5. `[]` SPL Token program
+    RefundProposalTokenDeposit {},
+
+    /// Creates ProposalArchive for a Proposal which has been in a final state
+    /// (Completed, Cancelled, Defeated or Vetoed) for at least
+    /// PROPOSAL_ARCHIVE_RETENTION_TIME
+    /// The instruction is permissionless and starts the archival of the
+    /// Proposal which is continued with ArchiveVoteRecords and completed with
+    /// ArchiveProposal
+    ///
+    ///   0. `[]` Proposal account
+    ///   1. `[writable]` ProposalArchive account. PDA seeds:
+    ///      ['proposal-archive', proposal]
+    ///   2. `[signer]` Payer
+    ///   3. `[]` System program
+    CreateProposalArchive {},
+
+    /// Archives relinquished VoteRecords of a Proposal
+    /// The hash of each VoteRecord is appended to the ProposalArchive merkle
+    /// tree and the VoteRecord is closed with its rent transferred to the
+    /// Governance native treasury
+    /// The instruction is permissionless and can be called multiple times
+    /// until all VoteRecords of the Proposal are archived
+    ///
+    ///   0. `[writable]` ProposalArchive account
+    ///   1. `[writable]` Governance native treasury account. PDA seeds:
+    ///      ['native-treasury', governance]
+    ///   2..n. `[writable]` Relinquished VoteRecord accounts of the Proposal
+    ArchiveVoteRecords {},
+
+    /// Archives the Proposal once all its VoteRecords are archived
+    /// The hash of the Proposal is appended as the last leaf of the
+    /// ProposalArchive merkle tree and the Proposal is closed with its rent
+    /// transferred to the Governance native treasury
+    /// Any ProposalTokenDeposit must be refunded and any ProposalLink resolved
+    /// before the Proposal is archived
+    /// Note: ProposalDeposit can't be refunded once the Proposal is archived
+    ///
+    ///   0. `[writable]` Proposal account
+    ///   1. `[writable]` ProposalArchive account
+    ///   2. `[writable]` Governance native treasury account. PDA seeds:
+    ///      ['native-treasury', governance]
+    ///   3. `[]` ProposalTokenDeposit account. PDA seeds:
+    ///      ['proposal-token-deposit', proposal]
+    ///   4. `[]` Optional ProposalLink account, required if the Proposal is
+    ///      linked
+    ArchiveProposal {},
+
+    /// Sets ExecutionSimulationConfig for Governance
+    /// When the simulation oracle is set ExecuteTransaction requires a
+    /// successful ExecutionSimulationAttestation posted by the oracle no older
+    /// than max_attestation_age_slots
+    ///
+    ///   0. `[writable, signer]` The Governance account the config is for
+    SetExecutionSimulationConfig {
+        #[allow(dead_code)]
+        /// Execution simulation config
+        config: ExecutionSimulationConfig,
+    },
+
+    /// Posts or updates ExecutionSimulationAttestation with the outcome of an
+    /// off-chain simulation of a ProposalTransaction
+    /// The transaction hash is computed using get_proposal_transaction_hash()
+    /// from the ProposalTransaction instructions
+    ///
+    ///   0. `[]` ProposalTransaction account
+    ///   1. `[writable]` ExecutionSimulationAttestation account. PDA seeds:
+    ///      ['execution-simulation-attestation', proposal_transaction,
+    ///      simulation_oracle]
+    ///   2. `[signer]` Simulation oracle
+    ///   3. `[signer]` Payer
+    ///   4. `[]` System program
+    PostExecutionSimulationAttestation {
+        #[allow(dead_code)]
+        /// The hash of the simulated ProposalTransaction instructions
+        transaction_hash: [u8; 32],
+
+        #[allow(dead_code)]
+        /// Whether the simulation succeeded
+        success: bool,
+    },
+
+    /// Registers VoteIntent with a vote signed off-chain by the governing
+    /// token owner or governance delegate
+    /// The vote intent message is created using get_vote_intent_message() and
+    /// its signature must be verified by the ed25519 program instruction
+    /// which immediately precedes RegisterVoteIntent in the transaction
+    /// Once registered anybody can relay the vote using CastVote
+    ///
+    ///   0. `[]` Proposal account
+    ///   1. `[]` TokenOwnerRecord of the voter
+    ///   2. `[writable]` VoteIntent account. PDA seeds: ['vote-intent',
+    ///      proposal, token_owner_record]
+    ///   3. `[]` Instructions sysvar
+    ///   4. `[signer]` Payer
+    ///   5. `[]` System program
+    RegisterVoteIntent {
+        #[allow(dead_code)]
+        /// The intended vote
+        vote: Vote,
+    },
+
+    /// Creates quadratic FundingRound for Governance
+    /// The matching pool is funded by transferring tokens from the Governance
+    /// treasury to the FundingRound escrow token account before the round is
+    /// finalized
+    ///
+    ///   0. `[]` Realm account the Governance belongs to
+    ///   1. `[signer]` Governance account
+    ///   2. `[writable]` FundingRound account. PDA seeds: ['funding-round',
+    ///      governance, round_seed]
+    ///   3. `[writable]` FundingRound escrow token account. PDA seeds:
+    ///      ['funding-round-escrow', funding_round]
+    ///   4. `[]` Mint of the matching pool and the contributions
+    ///   5. `[signer]` Payer
+    ///   6. `[]` System program
+    ///   7. `[]` SPL Token program
+    ///   8. `[]` Sysvar Rent
+    CreateFundingRound {
+        #[allow(dead_code)]
+        /// The seed used to derive the FundingRound address
+        round_seed: Pubkey,
+
+        #[allow(dead_code)]
+        /// FundingRound config
+        config: FundingRoundConfig,
+    },
+
+    /// Registers FundingProject for FundingRound
+    /// Projects can be registered until the contributions end
+    ///
+    ///   0. `[writable]` FundingRound account
+    ///   1. `[writable]` FundingProject account. PDA seeds:
+    ///      ['funding-project', funding_round, project_owner]
+    ///   2. `[signer]` Project owner
+    ///   3. `[]` Recipient token account of the FundingRound mint receiving
+    ///      the contributions and the matching
+    ///   4. `[signer]` Payer
+    ///   5. `[]` System program
+    RegisterFundingProject {},
+
+    /// Contributes to FundingProject
+    /// Contributions are transferred directly to the project recipient and
+    /// tracked per contributor using their TokenOwnerRecord in the Realm
+    ///
+    ///   0. `[writable]` FundingRound account
+    ///   1. `[writable]` FundingProject account
+    ///   2. `[writable]` FundingContribution account. PDA seeds:
+    ///      ['funding-contribution', funding_project, token_owner_record]
+    ///   3. `[]` TokenOwnerRecord of the contributor
+    ///   4. `[signer]` Governance Authority (Token Owner or Governance
+    ///      Delegate)
+    ///   5. `[writable]` Source token account owned by the Governance
+    ///      Authority
+    ///   6. `[writable]` FundingProject recipient token account
+    ///   7. `[signer]` Payer
+    ///   8. `[]` System program
+    ///   9. `[]` SPL Token program
+    ContributeToFundingProject {
+        #[allow(dead_code)]
+        /// The amount to contribute
+        amount: u64,
+    },
+
+    /// Finalizes FundingRound once the contributions ended and captures the
+    /// matching pool held in the escrow
+    /// If no project has any matching weight the matching pool is returned to
+    /// the Governance
+    ///
+    ///   0. `[writable]` FundingRound account
+    ///   1. `[writable]` FundingRound escrow token account
+    ///   2. `[writable]` Token account owned by the Governance receiving the
+    ///      matching pool when it's not distributed
+    ///   3. `[]` SPL Token program
+    FinalizeFundingRound {},
+
+    /// Transfers the share of the matching pool to FundingProject recipient
+    /// The share is proportional to the quadratic matching weight of the
+    /// project, (sum of sqrt(contribution))^2 - sum of contributions
+    ///
+    ///   0. `[]` FundingRound account
+    ///   1. `[writable]` FundingProject account
+    ///   2. `[writable]` FundingRound escrow token account
+    ///   3. `[writable]` FundingProject recipient token account
+    ///   4. `[]` SPL Token program
+    ClaimFundingMatch {},
+
+    /// Creates CouncilElection for Realm
+    /// The elected candidates receive CouncilSeats which give them council
+    /// voter weight until the term of the election ends
+    ///
+    ///   0. `[]` Realm account
+    ///   1. `[signer]` Realm authority
+    ///   2. `[writable]` CouncilElection account. PDA seeds:
+    ///      ['council-election', realm, election_seed]
+    ///   3. `[signer]` Payer
+    ///   4. `[]` System program
+    CreateCouncilElection {
+        #[allow(dead_code)]
+        /// The seed used to derive the CouncilElection address
+        election_seed: Pubkey,
+
+        #[allow(dead_code)]
+        /// CouncilElection config
+        config: CouncilElectionConfig,
+    },
+
+    /// Registers candidate for CouncilElection
+    /// Candidates can be registered until the voting ends
+    ///
+    ///   0. `[writable]` CouncilElection account
+    ///   1. `[writable]` CouncilCandidate account. PDA seeds:
+    ///      ['council-candidate', council_election, candidate]
+    ///   2. `[signer]` Candidate
+    ///   3. `[signer]` Payer
+    ///   4. `[]` System program
+    RegisterCouncilCandidate {},
+
+    /// Casts vote for CouncilCandidate using the community tokens deposited
+    /// in the Realm
+    /// The TokenOwnerRecord is locked by the CouncilElection until the voting
+    /// ends to prevent the tokens from being withdrawn and voted again
+    ///
+    ///   0. `[]` Realm account
+    ///   1. `[]` CouncilElection account
+    ///   2. `[writable]` CouncilCandidate account
+    ///   3. `[writable]` CouncilElectionVote account. PDA seeds:
+    ///      ['council-election-vote', council_election, token_owner_record]
+    ///   4. `[writable]` TokenOwnerRecord of the voter for the community mint
+    ///   5. `[signer]` Governance Authority (Token Owner or Governance
+    ///      Delegate)
+    ///   6. `[signer]` Payer
+    ///   7. `[]` System program
+    CastCouncilElectionVote {},
+
+    /// Finalizes CouncilElection once the voting ended, elects the candidates
+    /// with the most votes and starts the new council term of the Realm
+    ///
+    ///   0. `[]` Realm account
+    ///   1. `[writable]` CouncilElection account
+    ///   2. `[writable]` RealmConfig account. PDA seeds: ['realm-config',
+    ///      realm]
+    ///   3. `[signer]` Payer
+    ///   4. `[]` System program
+    ///   5..n `[writable]` All CouncilCandidate accounts of the election in
+    ///      ascending address order
+    FinalizeCouncilElection {},
+
+    /// Creates CouncilSeat for elected CouncilCandidate
+    ///
+    ///   0. `[]` CouncilElection account
+    ///   1. `[writable]` CouncilCandidate account
+    ///   2. `[writable]` CouncilSeat account. PDA seeds: ['council-seat',
+    ///      council_election, candidate]
+    ///   3. `[signer]` Payer
+    ///   4. `[]` System program
+    ClaimCouncilSeat {},
+
+    /// Creates or updates VoteSessionKey which authorizes an ephemeral session
+    /// key to cast and relinquish votes for the TokenOwnerRecord until the
+    /// expiry slot
+    /// The session key can't withdraw governing tokens or set the governance
+    /// delegate. Setting expiry_slot in the past revokes the session key
+    ///
+    ///   0. `[]` TokenOwnerRecord account
+    ///   1. `[signer]` Governance Authority (Token Owner or Governance
+    ///      Delegate)
+    ///   2. `[writable]` VoteSessionKey account. PDA seeds:
+    ///      ['vote-session-key', token_owner_record, session_key]
+    ///   3. `[signer]` Payer
+    ///   4. `[]` System program
+    CreateVoteSessionKey {
+        #[allow(dead_code)]
+        /// The ephemeral key authorized to vote
+        session_key: Pubkey,
+
+        #[allow(dead_code)]
+        /// The last slot the session key can be used to vote
+        expiry_slot: Slot,
+    },
+
+    /// Adds a dependency of the Proposal on another Proposal of the same Realm
+    /// The Proposal can only execute its transactions once the Proposal it
+    /// depends on is Completed
+    /// The dependency can only be added while the Proposal is in Draft state and
+    /// the Proposal it depends on is no longer in Draft state
+    ///
+    ///   0. `[writable]` Proposal account
+    ///   1. `[]` TokenOwnerRecord account of the Proposal owner
+    ///   2. `[signer]` Governance Authority (Token Owner or Governance
+    ///      Delegate)
+    ///   3. `[]` Governance account of the Proposal
+    ///   4. `[]` Proposal account the Proposal depends on
+    ///   5. `[]` Governance account of the Proposal the Proposal depends on
+    ///   6. `[writable]` ProposalDependency account. PDA seeds:
+    ///      ['proposal-dependency', proposal, depends_on]
+    ///   7. `[signer]` Payer
+    ///   8. `[]` System program
+    AddProposalDependency {},
 }
 
 /// Creates CreateRealm instruction
@@ -1057,6 +1494,137 @@ pub fn cast_vote(
     }
 }
 
+/// Creates CastVote instruction relaying VoteIntent signed off-chain by the
+/// governance authority
+#[allow(clippy::too_many_arguments)]
+pub fn cast_vote_with_vote_intent(
+    program_id: &Pubkey,
+    // Accounts
+    realm: &Pubkey,
+    governance: &Pubkey,
+    proposal: &Pubkey,
+    proposal_owner_record: &Pubkey,
+    voter_token_owner_record: &Pubkey,
+    governance_authority: &Pubkey,
+    vote_governing_token_mint: &Pubkey,
+    payer: &Pubkey,
+    voter_weight_record: Option<Pubkey>,
+    max_voter_weight_record: Option<Pubkey>,
+    // Args
+    vote: Vote,
+) -> Instruction {
+    let mut instruction = cast_vote(
+        program_id,
+        realm,
+        governance,
+        proposal,
+        proposal_owner_record,
+        voter_token_owner_record,
+        governance_authority,
+        vote_governing_token_mint,
+        payer,
+        voter_weight_record,
+        max_voter_weight_record,
+        vote,
+    );
+
+    instruction.accounts[5].is_signer = false;
+
+    let vote_intent_address =
+        get_vote_intent_address(program_id, proposal, voter_token_owner_record);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(vote_intent_address, false));
+
+    instruction
+}
+
+/// Creates CastVote instruction signed by session key authorized with
+/// VoteSessionKey
+#[allow(clippy::too_many_arguments)]
+pub fn cast_vote_with_vote_session_key(
+    program_id: &Pubkey,
+    // Accounts
+    realm: &Pubkey,
+    governance: &Pubkey,
+    proposal: &Pubkey,
+    proposal_owner_record: &Pubkey,
+    voter_token_owner_record: &Pubkey,
+    session_key: &Pubkey,
+    vote_governing_token_mint: &Pubkey,
+    payer: &Pubkey,
+    voter_weight_record: Option<Pubkey>,
+    max_voter_weight_record: Option<Pubkey>,
+    // Args
+    vote: Vote,
+) -> Instruction {
+    let mut instruction = cast_vote(
+        program_id,
+        realm,
+        governance,
+        proposal,
+        proposal_owner_record,
+        voter_token_owner_record,
+        session_key,
+        vote_governing_token_mint,
+        payer,
+        voter_weight_record,
+        max_voter_weight_record,
+        vote,
+    );
+
+    let vote_session_key_address =
+        get_vote_session_key_address(program_id, voter_token_owner_record, session_key);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(vote_session_key_address, false));
+
+    instruction
+}
+
+/// Creates CastVote instruction for a voter of the council mint while the
+/// Realm has an active council term
+#[allow(clippy::too_many_arguments)]
+pub fn cast_vote_with_council_seat(
+    program_id: &Pubkey,
+    // Accounts
+    realm: &Pubkey,
+    governance: &Pubkey,
+    proposal: &Pubkey,
+    proposal_owner_record: &Pubkey,
+    voter_token_owner_record: &Pubkey,
+    governance_authority: &Pubkey,
+    vote_governing_token_mint: &Pubkey,
+    payer: &Pubkey,
+    council_seat: &Pubkey,
+    voter_weight_record: Option<Pubkey>,
+    max_voter_weight_record: Option<Pubkey>,
+    // Args
+    vote: Vote,
+) -> Instruction {
+    let mut instruction = cast_vote(
+        program_id,
+        realm,
+        governance,
+        proposal,
+        proposal_owner_record,
+        voter_token_owner_record,
+        governance_authority,
+        vote_governing_token_mint,
+        payer,
+        voter_weight_record,
+        max_voter_weight_record,
+        vote,
+    );
+
+    // CouncilSeat follows the RealmConfig account
+    instruction
+        .accounts
+        .insert(11, AccountMeta::new_readonly(*council_seat, false));
+
+    instruction
+}
+
 /// Creates FinalizeVote instruction
 pub fn finalize_vote(
     program_id: &Pubkey,
@@ -1131,6 +1699,40 @@ pub fn relinquish_vote(
     }
 }
 
+/// Creates RelinquishVote instruction signed by session key authorized with
+/// VoteSessionKey
+#[allow(clippy::too_many_arguments)]
+pub fn relinquish_vote_with_vote_session_key(
+    program_id: &Pubkey,
+    // Accounts
+    realm: &Pubkey,
+    governance: &Pubkey,
+    proposal: &Pubkey,
+    token_owner_record: &Pubkey,
+    vote_governing_token_mint: &Pubkey,
+    session_key: &Pubkey,
+    beneficiary: &Pubkey,
+) -> Instruction {
+    let mut instruction = relinquish_vote(
+        program_id,
+        realm,
+        governance,
+        proposal,
+        token_owner_record,
+        vote_governing_token_mint,
+        Some(*session_key),
+        Some(*beneficiary),
+    );
+
+    let vote_session_key_address =
+        get_vote_session_key_address(program_id, token_owner_record, session_key);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(vote_session_key_address, false));
+
+    instruction
+}
+
 /// Creates CancelProposal instruction
 pub fn cancel_proposal(
     program_id: &Pubkey,
@@ -1260,6 +1862,36 @@ pub fn execute_transaction(
     }
 }
 
+/// Creates ExecuteTransaction instruction for a linked Proposal
+pub fn execute_linked_transaction(
+    program_id: &Pubkey,
+    // Accounts
+    governance: &Pubkey,
+    proposal: &Pubkey,
+    proposal_transaction: &Pubkey,
+    proposal_link: &Pubkey,
+    instruction_program_id: &Pubkey,
+    instruction_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*governance, false),
+        AccountMeta::new(*proposal, false),
+        AccountMeta::new(*proposal_transaction, false),
+        AccountMeta::new_readonly(*proposal_link, false),
+        AccountMeta::new_readonly(*instruction_program_id, false),
+    ];
+
+    accounts.extend_from_slice(instruction_accounts);
+
+    let instruction = GovernanceInstruction::ExecuteTransaction {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
 /// Creates SetGovernanceConfig instruction
 pub fn set_governance_config(
     program_id: &Pubkey,
@@ -1353,6 +1985,23 @@ pub fn set_realm_config(
     let realm_config_address = get_realm_config_address(program_id, realm);
     accounts.push(AccountMeta::new(realm_config_address, false));
 
+    // Always pass the ProgramData accounts of the addins because they are
+    // required when the Realm uses its AddinRegistry
+    let addin_program_data_accounts: Vec<AccountMeta> =
+        [&community_token_config_args, &council_token_config_args]
+            .iter()
+            .flat_map(|token_config_args| {
+                token_config_args.iter().flat_map(|token_config_args| {
+                    [
+                        token_config_args.voter_weight_addin,
+                        token_config_args.max_voter_weight_addin,
+                    ]
+                })
+            })
+            .flatten()
+            .map(|addin| AccountMeta::new_readonly(get_program_data_address(&addin), false))
+            .collect();
+
     let community_token_config_args =
         with_governing_token_config_args(&mut accounts, community_token_config_args);
 
@@ -1361,6 +2010,10 @@ pub fn set_realm_config(
 
     accounts.push(AccountMeta::new(*payer, true));
 
+    let addin_registry_address = get_addin_registry_address(program_id, realm);
+    accounts.push(AccountMeta::new_readonly(addin_registry_address, false));
+    accounts.extend(addin_program_data_accounts);
+
     let instruction = GovernanceInstruction::SetRealmConfig {
         config_args: RealmConfigArgs {
             use_council_mint,
@@ -1748,7 +2401,7 @@ pub fn set_realm_config_item(
 ) -> Instruction {
     let realm_config_address = get_realm_config_address(program_id, realm);
 
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*realm, false),
         AccountMeta::new(realm_config_address, false),
         AccountMeta::new_readonly(*realm_authority, true),
@@ -1756,6 +2409,11 @@ pub fn set_realm_config_item(
         AccountMeta::new_readonly(system_program::id(), false),
     ];
 
+    if let SetRealmConfigItemArgs::AddinRegistryEntry { .. } = args {
+        let addin_registry_address = get_addin_registry_address(program_id, realm);
+        accounts.push(AccountMeta::new(addin_registry_address, false));
+    }
+
     let instruction = GovernanceInstruction::SetRealmConfigItem { args };
 
     Instruction {
@@ -1764,3 +2422,827 @@ pub fn set_realm_config_item(
         data: borsh::to_vec(&instruction).unwrap(),
     }
 }
+
+/// Creates LinkProposals instruction
+/// Each linked Proposal is given as (Proposal, TokenOwnerRecord of the
+/// Proposal owner, Governance Authority)
+pub fn link_proposals(
+    program_id: &Pubkey,
+    // Accounts
+    linked_proposals: &[(Pubkey, Pubkey, Pubkey)],
+    payer: &Pubkey,
+    // Args
+    threshold: u8,
+) -> Instruction {
+    let proposal_link_address = get_proposal_link_address(program_id, &linked_proposals[0].0);
+
+    let mut accounts = vec![
+        AccountMeta::new(proposal_link_address, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    for (proposal, proposal_owner_record, governance_authority) in linked_proposals {
+        accounts.push(AccountMeta::new(*proposal, false));
+        accounts.push(AccountMeta::new_readonly(*proposal_owner_record, false));
+        accounts.push(AccountMeta::new_readonly(*governance_authority, true));
+    }
+
+    let instruction = GovernanceInstruction::LinkProposals { threshold };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates ResolveProposalLink instruction
+pub fn resolve_proposal_link(
+    program_id: &Pubkey,
+    // Accounts
+    proposals: &[Pubkey],
+) -> Instruction {
+    let proposal_link_address = get_proposal_link_address(program_id, &proposals[0]);
+
+    let mut accounts = vec![AccountMeta::new(proposal_link_address, false)];
+
+    for proposal in proposals {
+        accounts.push(AccountMeta::new_readonly(*proposal, false));
+    }
+
+    let instruction = GovernanceInstruction::ResolveProposalLink {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates CreateProposal instruction with the ProposalTokenDeposit accounts
+/// required when the Governance has ProposalTokenDepositConfig amount set
+#[allow(clippy::too_many_arguments)]
+pub fn create_proposal_with_token_deposit(
+    program_id: &Pubkey,
+    // Accounts
+    governance: &Pubkey,
+    proposal_owner_record: &Pubkey,
+    governance_authority: &Pubkey,
+    payer: &Pubkey,
+    voter_weight_record: Option<Pubkey>,
+    governing_token_source: &Pubkey,
+    // Args
+    realm: &Pubkey,
+    name: String,
+    description_link: String,
+    governing_token_mint: &Pubkey,
+    vote_type: VoteType,
+    options: Vec<String>,
+    use_deny_option: bool,
+    proposal_seed: &Pubkey,
+) -> Instruction {
+    let mut instruction = create_proposal(
+        program_id,
+        governance,
+        proposal_owner_record,
+        governance_authority,
+        payer,
+        voter_weight_record,
+        realm,
+        name,
+        description_link,
+        governing_token_mint,
+        vote_type,
+        options,
+        use_deny_option,
+        proposal_seed,
+    );
+
+    let proposal_address =
+        get_proposal_address(program_id, governance, governing_token_mint, proposal_seed);
+
+    instruction.accounts.extend([
+        AccountMeta::new(
+            get_proposal_token_deposit_address(program_id, &proposal_address),
+            false,
+        ),
+        AccountMeta::new(
+            get_proposal_token_deposit_escrow_address(program_id, &proposal_address),
+            false,
+        ),
+        AccountMeta::new(*governing_token_source, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ]);
+
+    instruction
+}
+
+/// Creates SetProposalTokenDepositConfig instruction
+pub fn set_proposal_token_deposit_config(
+    program_id: &Pubkey,
+    // Accounts
+    governance: &Pubkey,
+    // Args
+    config: ProposalTokenDepositConfig,
+) -> Instruction {
+    let accounts = vec![AccountMeta::new(*governance, true)];
+
+    let instruction = GovernanceInstruction::SetProposalTokenDepositConfig { config };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates RefundProposalTokenDeposit instruction
+pub fn refund_proposal_token_deposit(
+    program_id: &Pubkey,
+    // Accounts
+    proposal: &Pubkey,
+    destination: &Pubkey,
+    proposal_deposit_payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*proposal, false),
+        AccountMeta::new(
+            get_proposal_token_deposit_address(program_id, proposal),
+            false,
+        ),
+        AccountMeta::new(
+            get_proposal_token_deposit_escrow_address(program_id, proposal),
+            false,
+        ),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new(*proposal_deposit_payer, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::RefundProposalTokenDeposit {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates CreateProposalArchive instruction
+pub fn create_proposal_archive(
+    program_id: &Pubkey,
+    // Accounts
+    proposal: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*proposal, false),
+        AccountMeta::new(get_proposal_archive_address(program_id, proposal), false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::CreateProposalArchive {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates ArchiveVoteRecords instruction
+pub fn archive_vote_records(
+    program_id: &Pubkey,
+    // Accounts
+    governance: &Pubkey,
+    proposal: &Pubkey,
+    vote_records: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(get_proposal_archive_address(program_id, proposal), false),
+        AccountMeta::new(get_native_treasury_address(program_id, governance), false),
+    ];
+
+    accounts.extend(
+        vote_records
+            .iter()
+            .map(|vote_record| AccountMeta::new(*vote_record, false)),
+    );
+
+    let instruction = GovernanceInstruction::ArchiveVoteRecords {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates ArchiveProposal instruction
+pub fn archive_proposal(
+    program_id: &Pubkey,
+    // Accounts
+    governance: &Pubkey,
+    proposal: &Pubkey,
+    proposal_link: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*proposal, false),
+        AccountMeta::new(get_proposal_archive_address(program_id, proposal), false),
+        AccountMeta::new(get_native_treasury_address(program_id, governance), false),
+        AccountMeta::new_readonly(
+            get_proposal_token_deposit_address(program_id, proposal),
+            false,
+        ),
+    ];
+
+    if let Some(proposal_link) = proposal_link {
+        accounts.push(AccountMeta::new_readonly(*proposal_link, false));
+    }
+
+    let instruction = GovernanceInstruction::ArchiveProposal {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates ExecuteTransaction instruction for a Governance which requires
+/// ExecutionSimulationAttestation
+/// Note: For linked Proposals the attestation account must follow the
+/// ProposalLink account
+pub fn execute_transaction_with_simulation_attestation(
+    program_id: &Pubkey,
+    // Accounts
+    governance: &Pubkey,
+    proposal: &Pubkey,
+    proposal_transaction: &Pubkey,
+    simulation_oracle: &Pubkey,
+    instruction_program_id: &Pubkey,
+    instruction_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut instruction = execute_transaction(
+        program_id,
+        governance,
+        proposal,
+        proposal_transaction,
+        instruction_program_id,
+        instruction_accounts,
+    );
+
+    instruction.accounts.insert(
+        3,
+        AccountMeta::new_readonly(
+            get_execution_simulation_attestation_address(
+                program_id,
+                proposal_transaction,
+                simulation_oracle,
+            ),
+            false,
+        ),
+    );
+
+    instruction
+}
+
+/// Creates ExecuteTransaction instruction for a Proposal with dependencies
+/// on other Proposals
+#[allow(clippy::too_many_arguments)]
+pub fn execute_transaction_with_dependencies(
+    program_id: &Pubkey,
+    // Accounts
+    governance: &Pubkey,
+    proposal: &Pubkey,
+    proposal_transaction: &Pubkey,
+    depends_on_proposals: &[Pubkey],
+    instruction_program_id: &Pubkey,
+    instruction_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut instruction = execute_transaction(
+        program_id,
+        governance,
+        proposal,
+        proposal_transaction,
+        instruction_program_id,
+        instruction_accounts,
+    );
+
+    let mut proposal_dependencies: Vec<(Pubkey, Pubkey)> = depends_on_proposals
+        .iter()
+        .map(|depends_on| {
+            (
+                get_proposal_dependency_address(program_id, proposal, depends_on),
+                *depends_on,
+            )
+        })
+        .collect();
+    proposal_dependencies.sort_by_key(|(proposal_dependency, _)| *proposal_dependency);
+
+    let dependency_accounts =
+        proposal_dependencies
+            .iter()
+            .flat_map(|(proposal_dependency, depends_on)| {
+                [
+                    AccountMeta::new_readonly(*proposal_dependency, false),
+                    AccountMeta::new_readonly(*depends_on, false),
+                ]
+            });
+
+    instruction.accounts.splice(3..3, dependency_accounts);
+
+    instruction
+}
+
+/// Creates SetExecutionSimulationConfig instruction
+pub fn set_execution_simulation_config(
+    program_id: &Pubkey,
+    // Accounts
+    governance: &Pubkey,
+    // Args
+    config: ExecutionSimulationConfig,
+) -> Instruction {
+    let accounts = vec![AccountMeta::new(*governance, true)];
+
+    let instruction = GovernanceInstruction::SetExecutionSimulationConfig { config };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates PostExecutionSimulationAttestation instruction
+pub fn post_execution_simulation_attestation(
+    program_id: &Pubkey,
+    // Accounts
+    proposal_transaction: &Pubkey,
+    simulation_oracle: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    transaction_hash: [u8; 32],
+    success: bool,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*proposal_transaction, false),
+        AccountMeta::new(
+            get_execution_simulation_attestation_address(
+                program_id,
+                proposal_transaction,
+                simulation_oracle,
+            ),
+            false,
+        ),
+        AccountMeta::new_readonly(*simulation_oracle, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::PostExecutionSimulationAttestation {
+        transaction_hash,
+        success,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates RegisterVoteIntent instruction
+/// Note: The instruction must be immediately preceded by the ed25519 program
+/// instruction verifying the vote intent message signature
+pub fn register_vote_intent(
+    program_id: &Pubkey,
+    // Accounts
+    proposal: &Pubkey,
+    token_owner_record: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    vote: Vote,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*proposal, false),
+        AccountMeta::new_readonly(*token_owner_record, false),
+        AccountMeta::new(
+            get_vote_intent_address(program_id, proposal, token_owner_record),
+            false,
+        ),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::RegisterVoteIntent { vote };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates CreateFundingRound instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_funding_round(
+    program_id: &Pubkey,
+    // Accounts
+    realm: &Pubkey,
+    governance: &Pubkey,
+    mint: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    round_seed: &Pubkey,
+    config: FundingRoundConfig,
+) -> Instruction {
+    let funding_round_address = get_funding_round_address(program_id, governance, round_seed);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*realm, false),
+        AccountMeta::new_readonly(*governance, true),
+        AccountMeta::new(funding_round_address, false),
+        AccountMeta::new(
+            get_funding_round_escrow_address(program_id, &funding_round_address),
+            false,
+        ),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::CreateFundingRound {
+        round_seed: *round_seed,
+        config,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates RegisterFundingProject instruction
+pub fn register_funding_project(
+    program_id: &Pubkey,
+    // Accounts
+    funding_round: &Pubkey,
+    project_owner: &Pubkey,
+    recipient: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*funding_round, false),
+        AccountMeta::new(
+            get_funding_project_address(program_id, funding_round, project_owner),
+            false,
+        ),
+        AccountMeta::new_readonly(*project_owner, true),
+        AccountMeta::new_readonly(*recipient, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::RegisterFundingProject {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates ContributeToFundingProject instruction
+#[allow(clippy::too_many_arguments)]
+pub fn contribute_to_funding_project(
+    program_id: &Pubkey,
+    // Accounts
+    funding_round: &Pubkey,
+    funding_project: &Pubkey,
+    token_owner_record: &Pubkey,
+    governance_authority: &Pubkey,
+    source: &Pubkey,
+    recipient: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    amount: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*funding_round, false),
+        AccountMeta::new(*funding_project, false),
+        AccountMeta::new(
+            get_funding_contribution_address(program_id, funding_project, token_owner_record),
+            false,
+        ),
+        AccountMeta::new_readonly(*token_owner_record, false),
+        AccountMeta::new_readonly(*governance_authority, true),
+        AccountMeta::new(*source, false),
+        AccountMeta::new(*recipient, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::ContributeToFundingProject { amount };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates FinalizeFundingRound instruction
+pub fn finalize_funding_round(
+    program_id: &Pubkey,
+    // Accounts
+    funding_round: &Pubkey,
+    governance_destination: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*funding_round, false),
+        AccountMeta::new(
+            get_funding_round_escrow_address(program_id, funding_round),
+            false,
+        ),
+        AccountMeta::new(*governance_destination, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::FinalizeFundingRound {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates ClaimFundingMatch instruction
+pub fn claim_funding_match(
+    program_id: &Pubkey,
+    // Accounts
+    funding_round: &Pubkey,
+    funding_project: &Pubkey,
+    recipient: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*funding_round, false),
+        AccountMeta::new(*funding_project, false),
+        AccountMeta::new(
+            get_funding_round_escrow_address(program_id, funding_round),
+            false,
+        ),
+        AccountMeta::new(*recipient, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::ClaimFundingMatch {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates CreateCouncilElection instruction
+pub fn create_council_election(
+    program_id: &Pubkey,
+    // Accounts
+    realm: &Pubkey,
+    realm_authority: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    election_seed: &Pubkey,
+    config: CouncilElectionConfig,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*realm, false),
+        AccountMeta::new_readonly(*realm_authority, true),
+        AccountMeta::new(
+            get_council_election_address(program_id, realm, election_seed),
+            false,
+        ),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::CreateCouncilElection {
+        election_seed: *election_seed,
+        config,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates RegisterCouncilCandidate instruction
+pub fn register_council_candidate(
+    program_id: &Pubkey,
+    // Accounts
+    council_election: &Pubkey,
+    candidate: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*council_election, false),
+        AccountMeta::new(
+            get_council_candidate_address(program_id, council_election, candidate),
+            false,
+        ),
+        AccountMeta::new_readonly(*candidate, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::RegisterCouncilCandidate {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates CastCouncilElectionVote instruction
+pub fn cast_council_election_vote(
+    program_id: &Pubkey,
+    // Accounts
+    realm: &Pubkey,
+    council_election: &Pubkey,
+    council_candidate: &Pubkey,
+    token_owner_record: &Pubkey,
+    governance_authority: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*realm, false),
+        AccountMeta::new_readonly(*council_election, false),
+        AccountMeta::new(*council_candidate, false),
+        AccountMeta::new(
+            get_council_election_vote_address(program_id, council_election, token_owner_record),
+            false,
+        ),
+        AccountMeta::new(*token_owner_record, false),
+        AccountMeta::new_readonly(*governance_authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::CastCouncilElectionVote {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates FinalizeCouncilElection instruction
+pub fn finalize_council_election(
+    program_id: &Pubkey,
+    // Accounts
+    realm: &Pubkey,
+    council_election: &Pubkey,
+    payer: &Pubkey,
+    council_candidates: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*realm, false),
+        AccountMeta::new(*council_election, false),
+        AccountMeta::new(get_realm_config_address(program_id, realm), false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let mut council_candidates = council_candidates.to_vec();
+    council_candidates.sort();
+
+    accounts.extend(
+        council_candidates
+            .iter()
+            .map(|council_candidate| AccountMeta::new(*council_candidate, false)),
+    );
+
+    let instruction = GovernanceInstruction::FinalizeCouncilElection {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates ClaimCouncilSeat instruction
+pub fn claim_council_seat(
+    program_id: &Pubkey,
+    // Accounts
+    council_election: &Pubkey,
+    candidate: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*council_election, false),
+        AccountMeta::new(
+            get_council_candidate_address(program_id, council_election, candidate),
+            false,
+        ),
+        AccountMeta::new(
+            get_council_seat_address(program_id, council_election, candidate),
+            false,
+        ),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::ClaimCouncilSeat {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates CreateVoteSessionKey instruction
+pub fn create_vote_session_key(
+    program_id: &Pubkey,
+    // Accounts
+    token_owner_record: &Pubkey,
+    governance_authority: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    session_key: &Pubkey,
+    expiry_slot: Slot,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_owner_record, false),
+        AccountMeta::new_readonly(*governance_authority, true),
+        AccountMeta::new(
+            get_vote_session_key_address(program_id, token_owner_record, session_key),
+            false,
+        ),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::CreateVoteSessionKey {
+        session_key: *session_key,
+        expiry_slot,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
+
+/// Creates AddProposalDependency instruction
+#[allow(clippy::too_many_arguments)]
+pub fn add_proposal_dependency(
+    program_id: &Pubkey,
+    // Accounts
+    proposal: &Pubkey,
+    proposal_owner_record: &Pubkey,
+    governance_authority: &Pubkey,
+    governance: &Pubkey,
+    depends_on_proposal: &Pubkey,
+    depends_on_governance: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let proposal_dependency_address =
+        get_proposal_dependency_address(program_id, proposal, depends_on_proposal);
+
+    let accounts = vec![
+        AccountMeta::new(*proposal, false),
+        AccountMeta::new_readonly(*proposal_owner_record, false),
+        AccountMeta::new_readonly(*governance_authority, true),
+        AccountMeta::new_readonly(*governance, false),
+        AccountMeta::new_readonly(*depends_on_proposal, false),
+        AccountMeta::new_readonly(*depends_on_governance, false),
+        AccountMeta::new(proposal_dependency_address, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = GovernanceInstruction::AddProposalDependency {};
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&instruction).unwrap(),
+    }
+}
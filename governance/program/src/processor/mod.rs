@@ -1,30 +1,52 @@
 //! Program processor
 
+mod process_add_proposal_dependency;
 mod process_add_required_signatory;
 mod process_add_signatory;
+mod process_archive_proposal;
+mod process_archive_vote_records;
 mod process_cancel_proposal;
+mod process_cast_council_election_vote;
 mod process_cast_vote;
+mod process_claim_council_seat;
+mod process_claim_funding_match;
 mod process_complete_proposal;
+mod process_contribute_to_funding_project;
+mod process_create_council_election;
+mod process_create_funding_round;
 mod process_create_governance;
 mod process_create_native_treasury;
 
 mod process_create_proposal;
+mod process_create_proposal_archive;
 mod process_create_realm;
 
 mod process_create_token_owner_record;
+mod process_create_vote_session_key;
 mod process_deposit_governing_tokens;
 mod process_execute_transaction;
+mod process_finalize_council_election;
+mod process_finalize_funding_round;
 mod process_finalize_vote;
 
 mod process_insert_transaction;
+mod process_link_proposals;
+mod process_post_execution_simulation_attestation;
 mod process_refund_proposal_deposit;
+mod process_refund_proposal_token_deposit;
+mod process_register_council_candidate;
+mod process_register_funding_project;
+mod process_register_vote_intent;
 mod process_relinquish_token_owner_record_locks;
 mod process_relinquish_vote;
 mod process_remove_required_signatory;
 mod process_remove_transaction;
+mod process_resolve_proposal_link;
 mod process_revoke_governing_tokens;
+mod process_set_execution_simulation_config;
 mod process_set_governance_config;
 mod process_set_governance_delegate;
+mod process_set_proposal_token_deposit_config;
 mod process_set_realm_authority;
 mod process_set_realm_config;
 mod process_set_realm_config_item;
@@ -35,28 +57,50 @@ mod process_withdraw_governing_tokens;
 
 use {
     crate::{error::GovernanceError, instruction::GovernanceInstruction},
+    process_add_proposal_dependency::*,
     process_add_required_signatory::*,
     process_add_signatory::*,
+    process_archive_proposal::*,
+    process_archive_vote_records::*,
     process_cancel_proposal::*,
+    process_cast_council_election_vote::*,
     process_cast_vote::*,
+    process_claim_council_seat::*,
+    process_claim_funding_match::*,
     process_complete_proposal::*,
+    process_contribute_to_funding_project::*,
+    process_create_council_election::*,
+    process_create_funding_round::*,
     process_create_governance::*,
     process_create_native_treasury::*,
     process_create_proposal::*,
+    process_create_proposal_archive::*,
     process_create_realm::*,
     process_create_token_owner_record::*,
+    process_create_vote_session_key::*,
     process_deposit_governing_tokens::*,
     process_execute_transaction::*,
+    process_finalize_council_election::*,
+    process_finalize_funding_round::*,
     process_finalize_vote::*,
     process_insert_transaction::*,
+    process_link_proposals::*,
+    process_post_execution_simulation_attestation::*,
     process_refund_proposal_deposit::*,
+    process_refund_proposal_token_deposit::*,
+    process_register_council_candidate::*,
+    process_register_funding_project::*,
+    process_register_vote_intent::*,
     process_relinquish_token_owner_record_locks::*,
     process_relinquish_vote::*,
     process_remove_required_signatory::*,
     process_remove_transaction::*,
+    process_resolve_proposal_link::*,
     process_revoke_governing_tokens::*,
+    process_set_execution_simulation_config::*,
     process_set_governance_config::*,
     process_set_governance_delegate::*,
+    process_set_proposal_token_deposit_config::*,
     process_set_realm_authority::*,
     process_set_realm_config::*,
     process_set_realm_config_item::*,
@@ -222,5 +266,99 @@ pub fn process_instruction(
         GovernanceInstruction::SetRealmConfigItem { args } => {
             process_set_realm_config_item(program_id, accounts, args)
         }
+
+        GovernanceInstruction::LinkProposals { threshold } => {
+            process_link_proposals(program_id, accounts, threshold)
+        }
+
+        GovernanceInstruction::ResolveProposalLink {} => {
+            process_resolve_proposal_link(program_id, accounts)
+        }
+
+        GovernanceInstruction::SetProposalTokenDepositConfig { config } => {
+            process_set_proposal_token_deposit_config(program_id, accounts, config)
+        }
+
+        GovernanceInstruction::RefundProposalTokenDeposit {} => {
+            process_refund_proposal_token_deposit(program_id, accounts)
+        }
+
+        GovernanceInstruction::CreateProposalArchive {} => {
+            process_create_proposal_archive(program_id, accounts)
+        }
+
+        GovernanceInstruction::ArchiveVoteRecords {} => {
+            process_archive_vote_records(program_id, accounts)
+        }
+
+        GovernanceInstruction::ArchiveProposal {} => process_archive_proposal(program_id, accounts),
+
+        GovernanceInstruction::SetExecutionSimulationConfig { config } => {
+            process_set_execution_simulation_config(program_id, accounts, config)
+        }
+
+        GovernanceInstruction::PostExecutionSimulationAttestation {
+            transaction_hash,
+            success,
+        } => process_post_execution_simulation_attestation(
+            program_id,
+            accounts,
+            transaction_hash,
+            success,
+        ),
+
+        GovernanceInstruction::RegisterVoteIntent { vote } => {
+            process_register_vote_intent(program_id, accounts, vote)
+        }
+
+        GovernanceInstruction::CreateFundingRound { round_seed, config } => {
+            process_create_funding_round(program_id, accounts, round_seed, config)
+        }
+
+        GovernanceInstruction::RegisterFundingProject {} => {
+            process_register_funding_project(program_id, accounts)
+        }
+
+        GovernanceInstruction::ContributeToFundingProject { amount } => {
+            process_contribute_to_funding_project(program_id, accounts, amount)
+        }
+
+        GovernanceInstruction::FinalizeFundingRound {} => {
+            process_finalize_funding_round(program_id, accounts)
+        }
+
+        GovernanceInstruction::ClaimFundingMatch {} => {
+            process_claim_funding_match(program_id, accounts)
+        }
+
+        GovernanceInstruction::CreateCouncilElection {
+            election_seed,
+            config,
+        } => process_create_council_election(program_id, accounts, election_seed, config),
+
+        GovernanceInstruction::RegisterCouncilCandidate {} => {
+            process_register_council_candidate(program_id, accounts)
+        }
+
+        GovernanceInstruction::CastCouncilElectionVote {} => {
+            process_cast_council_election_vote(program_id, accounts)
+        }
+
+        GovernanceInstruction::FinalizeCouncilElection {} => {
+            process_finalize_council_election(program_id, accounts)
+        }
+
+        GovernanceInstruction::ClaimCouncilSeat {} => {
+            process_claim_council_seat(program_id, accounts)
+        }
+
+        GovernanceInstruction::CreateVoteSessionKey {
+            session_key,
+            expiry_slot,
+        } => process_create_vote_session_key(program_id, accounts, session_key, expiry_slot),
+
+        GovernanceInstruction::AddProposalDependency {} => {
+            process_add_proposal_dependency(program_id, accounts)
+        }
     }
 }
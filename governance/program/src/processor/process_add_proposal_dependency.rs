@@ -0,0 +1,95 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::GovernanceAccountType,
+            governance::get_governance_data,
+            proposal::get_proposal_data_for_governance,
+            proposal_dependency::{get_proposal_dependency_address_seeds, ProposalDependency},
+            token_owner_record::get_token_owner_record_data_for_proposal_owner,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes AddProposalDependency instruction
+pub fn process_add_proposal_dependency(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_info = next_account_info(account_info_iter)?; // 0
+    let proposal_owner_record_info = next_account_info(account_info_iter)?; // 1
+    let governance_authority_info = next_account_info(account_info_iter)?; // 2
+    let governance_info = next_account_info(account_info_iter)?; // 3
+
+    let depends_on_proposal_info = next_account_info(account_info_iter)?; // 4
+    let depends_on_governance_info = next_account_info(account_info_iter)?; // 5
+
+    let proposal_dependency_info = next_account_info(account_info_iter)?; // 6
+
+    let payer_info = next_account_info(account_info_iter)?; // 7
+    let system_info = next_account_info(account_info_iter)?; // 8
+
+    let rent = Rent::get()?;
+
+    let governance_data = get_governance_data(program_id, governance_info)?;
+
+    let mut proposal_data =
+        get_proposal_data_for_governance(program_id, proposal_info, governance_info.key)?;
+    proposal_data.assert_can_add_dependency()?;
+
+    let depends_on_governance_data = get_governance_data(program_id, depends_on_governance_info)?;
+
+    let depends_on_proposal_data = get_proposal_data_for_governance(
+        program_id,
+        depends_on_proposal_info,
+        depends_on_governance_info.key,
+    )?;
+    depends_on_proposal_data.assert_can_be_depended_on()?;
+
+    if governance_data.realm != depends_on_governance_data.realm {
+        return Err(GovernanceError::CannotDependOnProposalOfAnotherRealm.into());
+    }
+
+    let proposal_owner_record_data = get_token_owner_record_data_for_proposal_owner(
+        program_id,
+        proposal_owner_record_info,
+        &proposal_data.token_owner_record,
+    )?;
+    proposal_owner_record_data
+        .assert_token_owner_or_delegate_is_signer(governance_authority_info)?;
+
+    let proposal_dependency_data = ProposalDependency {
+        account_type: GovernanceAccountType::ProposalDependency,
+        proposal: *proposal_info.key,
+        depends_on: *depends_on_proposal_info.key,
+        reserved: [0; 64],
+    };
+
+    create_and_serialize_account_signed::<ProposalDependency>(
+        payer_info,
+        proposal_dependency_info,
+        &proposal_dependency_data,
+        &get_proposal_dependency_address_seeds(proposal_info.key, depends_on_proposal_info.key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    proposal_data.dependencies_count = proposal_data.dependencies_count.checked_add(1).unwrap();
+    proposal_data.serialize(&mut proposal_info.data.borrow_mut()[..])?;
+
+    Ok(())
+}
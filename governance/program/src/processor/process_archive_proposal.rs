@@ -0,0 +1,85 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::ProposalLinkState, native_treasury::get_native_treasury_address,
+            proposal::get_proposal_data, proposal_archive::get_proposal_archive_data_for_proposal,
+            proposal_link::get_proposal_link_data_for_proposal,
+            proposal_token_deposit::get_proposal_token_deposit_address,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::dispose_account,
+};
+
+/// Processes ArchiveProposal instruction
+pub fn process_archive_proposal(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_info = next_account_info(account_info_iter)?; // 0
+    let proposal_archive_info = next_account_info(account_info_iter)?; // 1
+    let native_treasury_info = next_account_info(account_info_iter)?; // 2
+    let proposal_token_deposit_info = next_account_info(account_info_iter)?; // 3
+
+    let clock = Clock::get()?;
+
+    let proposal_data = get_proposal_data(program_id, proposal_info)?;
+    proposal_data.assert_can_archive(clock.unix_timestamp)?;
+
+    let mut proposal_archive_data = get_proposal_archive_data_for_proposal(
+        program_id,
+        proposal_archive_info,
+        proposal_info.key,
+    )?;
+
+    if *native_treasury_info.key
+        != get_native_treasury_address(program_id, &proposal_data.governance)
+    {
+        return Err(GovernanceError::InvalidNativeTreasuryAccount.into());
+    }
+
+    // Once the Proposal is closed its VoteRecords can't be relinquished anymore
+    // and all of them must be archived first
+    if proposal_archive_data.vote_records_count < proposal_data.voters_count {
+        return Err(GovernanceError::ProposalArchiveMissingVoteRecords.into());
+    }
+
+    // Refunding ProposalTokenDeposit requires the Proposal account
+    if *proposal_token_deposit_info.key
+        != get_proposal_token_deposit_address(program_id, proposal_info.key)
+    {
+        return Err(GovernanceError::InvalidProposalForProposalTokenDeposit.into());
+    }
+    if !proposal_token_deposit_info.data_is_empty() {
+        return Err(GovernanceError::ProposalTokenDepositNotRefunded.into());
+    }
+
+    // Resolving ProposalLink requires all linked Proposal accounts
+    if proposal_data.is_linked {
+        let proposal_link_info = next_account_info(account_info_iter)?; // 4
+        let proposal_link_data =
+            get_proposal_link_data_for_proposal(program_id, proposal_link_info, proposal_info.key)?;
+
+        if proposal_link_data.state == ProposalLinkState::Pending {
+            return Err(GovernanceError::ProposalLinkNotResolved.into());
+        }
+    }
+
+    proposal_archive_data.archive_proposal(&proposal_info.data.borrow(), clock.unix_timestamp)?;
+    borsh::to_writer(
+        &mut proposal_archive_info.data.borrow_mut()[..],
+        &proposal_archive_data,
+    )?;
+
+    dispose_account(proposal_info, native_treasury_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            native_treasury::get_native_treasury_address,
+            proposal_archive::get_proposal_archive_data, vote_record::get_vote_record_data,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+    spl_governance_tools::account::dispose_account,
+};
+
+/// Processes ArchiveVoteRecords instruction
+pub fn process_archive_vote_records(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_archive_info = next_account_info(account_info_iter)?; // 0
+    let native_treasury_info = next_account_info(account_info_iter)?; // 1
+
+    let mut proposal_archive_data = get_proposal_archive_data(program_id, proposal_archive_info)?;
+
+    if *native_treasury_info.key
+        != get_native_treasury_address(program_id, &proposal_archive_data.governance)
+    {
+        return Err(GovernanceError::InvalidNativeTreasuryAccount.into());
+    }
+
+    for vote_record_info in account_info_iter {
+        let vote_record_data = get_vote_record_data(program_id, vote_record_info)?;
+
+        if vote_record_data.proposal != proposal_archive_data.proposal {
+            return Err(GovernanceError::InvalidProposalForVoterRecord.into());
+        }
+
+        // Unrelinquished votes still count towards the voter's
+        // unrelinquished_votes_count and can only be relinquished using the
+        // Proposal account
+        if !vote_record_data.is_relinquished {
+            return Err(GovernanceError::CannotArchiveUnrelinquishedVoteRecord.into());
+        }
+
+        proposal_archive_data.archive_vote_record(&vote_record_info.data.borrow())?;
+
+        dispose_account(vote_record_info, native_treasury_info)?;
+    }
+
+    borsh::to_writer(
+        &mut proposal_archive_info.data.borrow_mut()[..],
+        &proposal_archive_data,
+    )?;
+
+    Ok(())
+}
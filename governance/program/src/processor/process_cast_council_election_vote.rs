@@ -0,0 +1,133 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            council_election::{
+                get_council_candidate_data_for_council_election, get_council_election_data,
+                get_council_election_vote_address_seeds, CouncilElectionVote,
+            },
+            enums::GovernanceAccountType,
+            realm::get_realm_data,
+            token_owner_record::{
+                get_token_owner_record_data_for_realm_and_governing_mint, TokenOwnerRecordLock,
+            },
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes CastCouncilElectionVote instruction
+pub fn process_cast_council_election_vote(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let realm_info = next_account_info(account_info_iter)?; // 0
+    let council_election_info = next_account_info(account_info_iter)?; // 1
+    let council_candidate_info = next_account_info(account_info_iter)?; // 2
+    let council_election_vote_info = next_account_info(account_info_iter)?; // 3
+    let token_owner_record_info = next_account_info(account_info_iter)?; // 4
+    let governance_authority_info = next_account_info(account_info_iter)?; // 5
+    let payer_info = next_account_info(account_info_iter)?; // 6
+    let system_info = next_account_info(account_info_iter)?; // 7
+
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+
+    if !council_election_vote_info.data_is_empty() {
+        return Err(GovernanceError::CouncilElectionVoteAlreadyExists.into());
+    }
+
+    let council_election_data = get_council_election_data(program_id, council_election_info)?;
+    council_election_data.assert_is_voting(clock.unix_timestamp)?;
+
+    if council_election_data.realm != *realm_info.key {
+        return Err(GovernanceError::InvalidRealmForCouncilElection.into());
+    }
+
+    let mut council_candidate_data = get_council_candidate_data_for_council_election(
+        program_id,
+        council_candidate_info,
+        council_election_info.key,
+    )?;
+
+    // The council is elected by the community token holders
+    let realm_data = get_realm_data(program_id, realm_info)?;
+
+    let mut token_owner_record_data = get_token_owner_record_data_for_realm_and_governing_mint(
+        program_id,
+        token_owner_record_info,
+        realm_info.key,
+        &realm_data.community_mint,
+    )?;
+
+    token_owner_record_data.assert_token_owner_or_delegate_is_signer(governance_authority_info)?;
+
+    let voter_weight = token_owner_record_data.governing_token_deposit_amount;
+
+    if voter_weight == 0 {
+        return Err(GovernanceError::NotEnoughTokensToVoteInCouncilElection.into());
+    }
+
+    council_candidate_data.votes = council_candidate_data
+        .votes
+        .checked_add(voter_weight)
+        .unwrap();
+
+    let council_election_vote_data = CouncilElectionVote {
+        account_type: GovernanceAccountType::CouncilElectionVote,
+        council_election: *council_election_info.key,
+        token_owner_record: *token_owner_record_info.key,
+        council_candidate: *council_candidate_info.key,
+        voter_weight,
+        reserved: [0; 32],
+    };
+
+    create_and_serialize_account_signed::<CouncilElectionVote>(
+        payer_info,
+        council_election_vote_info,
+        &council_election_vote_data,
+        &get_council_election_vote_address_seeds(
+            council_election_info.key,
+            token_owner_record_info.key,
+        ),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    borsh::to_writer(
+        &mut council_candidate_info.data.borrow_mut()[..],
+        &council_candidate_data,
+    )?;
+
+    // Lock the deposited tokens until the voting ends to prevent them from
+    // being withdrawn and voted again from another TokenOwnerRecord
+    token_owner_record_data.remove_expired_locks(clock.unix_timestamp);
+    token_owner_record_data.upsert_lock(TokenOwnerRecordLock {
+        lock_id: 0,
+        authority: *council_election_info.key,
+        expiry: Some(council_election_data.config.voting_end_at),
+    });
+
+    token_owner_record_data.serialize_with_resize(
+        token_owner_record_info,
+        payer_info,
+        system_info,
+        &rent,
+    )?;
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use {
     crate::{
         error::GovernanceError,
         state::{
+            council_election::get_council_seat_data_for_realm_and_member,
             enums::GovernanceAccountType,
             governance::get_governance_data_for_realm,
             proposal::get_proposal_data_for_governance_and_governing_mint,
@@ -13,7 +14,9 @@ use {
                 get_token_owner_record_data_for_proposal_owner,
                 get_token_owner_record_data_for_realm_and_governing_mint,
             },
+            vote_intent::get_vote_intent_data_for_proposal_and_token_owner_record,
             vote_record::{get_vote_kind, get_vote_record_address_seeds, Vote, VoteRecordV2},
+            vote_session_key::get_vote_session_key_data_for_token_owner_record,
         },
     },
     solana_program::{
@@ -93,8 +96,15 @@ pub fn process_cast_vote(
             &governance_data.realm,
             vote_governing_token_mint_info.key,
         )?;
-    voter_token_owner_record_data
-        .assert_token_owner_or_delegate_is_signer(governance_authority_info)?;
+
+    // When the governance authority doesn't sign the vote is relayed using
+    // VoteIntent signed off-chain by the authority, and when it signs but isn't
+    // the owner or delegate it must be a session key authorized by
+    // VoteSessionKey. Both are asserted after the optional voter weight
+    // accounts are consumed
+    let is_vote_intent_relayed = !governance_authority_info.is_signer;
+    let is_vote_session_key_used = governance_authority_info.is_signer
+        && !voter_token_owner_record_data.is_token_owner_or_delegate(governance_authority_info.key);
 
     // Update TokenOwnerRecord vote counts
     voter_token_owner_record_data.unrelinquished_votes_count = voter_token_owner_record_data
@@ -106,16 +116,54 @@ pub fn process_cast_vote(
     let realm_config_data =
         get_realm_config_data_for_realm(program_id, realm_config_info, realm_info.key)?;
 
-    let voter_weight = voter_token_owner_record_data.resolve_voter_weight(
-        account_info_iter, // voter_weight_record  *10
+    // While the Realm has an active council term the council voters provide
+    // their CouncilSeat account which is empty if they don't hold a seat
+    let council_seat_weight = if realm_config_data.get_active_council_seats_count(
         &realm_data,
-        &realm_config_data,
-        VoterWeightAction::CastVote,
-        proposal_info.key,
-    )?;
+        vote_governing_token_mint_info.key,
+        clock.unix_timestamp,
+    ) > 0
+    {
+        let council_seat_info = next_account_info(account_info_iter)?; // 11
+
+        if council_seat_info.data_is_empty() {
+            0
+        } else {
+            let council_seat_data = get_council_seat_data_for_realm_and_member(
+                program_id,
+                council_seat_info,
+                realm_info.key,
+                &voter_token_owner_record_data.governing_token_owner,
+            )?;
+
+            // Seats expire with their term and aren't revoked explicitly
+            if !council_seat_data
+                .is_active(realm_config_data.council_term.end_at, clock.unix_timestamp)
+            {
+                return Err(GovernanceError::CouncilSeatNotActive.into());
+            }
+
+            1
+        }
+    } else {
+        0
+    };
+
+    let voter_weight = voter_token_owner_record_data
+        .resolve_voter_weight(
+            account_info_iter, // voter_weight_record  *10
+            &realm_data,
+            &realm_config_data,
+            VoterWeightAction::CastVote,
+            proposal_info.key,
+        )?
+        .checked_add(council_seat_weight)
+        .unwrap();
 
     proposal_data.assert_valid_vote(&vote)?;
 
+    let previous_leading_outcome = proposal_data.get_leading_outcome();
+
     // Calculate Proposal voting weights
     match &vote {
         Vote::Approve(choices) => {
@@ -146,6 +194,14 @@ pub fn process_cast_vote(
         }
     }
 
+    // Extend the voting time if a late vote changed the leading outcome
+    proposal_data.try_extend_voting(
+        &realm_config_data.vote_extension,
+        &governance_data.config,
+        previous_leading_outcome,
+        clock.unix_timestamp,
+    );
+
     let max_voter_weight = proposal_data.resolve_max_voter_weight(
         account_info_iter, // max_voter_weight_record  11
         realm_info.key,
@@ -153,8 +209,46 @@ pub fn process_cast_vote(
         &realm_config_data,
         vote_governing_token_mint_info,
         &vote_kind,
+        clock.unix_timestamp,
     )?;
 
+    if is_vote_intent_relayed {
+        let vote_intent_info = next_account_info(account_info_iter)?; // 13
+
+        let vote_intent_data = get_vote_intent_data_for_proposal_and_token_owner_record(
+            program_id,
+            vote_intent_info,
+            proposal_info.key,
+            voter_token_owner_record_info.key,
+        )?;
+        vote_intent_data.assert_can_cast_vote(&vote, governance_authority_info.key)?;
+
+        // The signer of the intent must still be the owner or delegate
+        if !voter_token_owner_record_data.is_token_owner_or_delegate(governance_authority_info.key)
+        {
+            return Err(GovernanceError::GoverningTokenOwnerOrDelegateMustSign.into());
+        }
+    } else if is_vote_session_key_used {
+        // Without VoteSessionKey the signer is neither the owner nor the delegate
+        let vote_session_key_info = account_info_iter
+            .next()
+            .ok_or(GovernanceError::GoverningTokenOwnerOrDelegateMustSign)?; // 13
+
+        let vote_session_key_data = get_vote_session_key_data_for_token_owner_record(
+            program_id,
+            vote_session_key_info,
+            voter_token_owner_record_info.key,
+        )?;
+        vote_session_key_data.assert_can_vote(governance_authority_info.key, clock.slot)?;
+
+        // The authority of the session key must still be the owner or delegate
+        if !voter_token_owner_record_data
+            .is_token_owner_or_delegate(&vote_session_key_data.authority)
+        {
+            return Err(GovernanceError::GoverningTokenOwnerOrDelegateMustSign.into());
+        }
+    }
+
     let vote_threshold = governance_data.resolve_vote_threshold(
         &realm_data,
         vote_governing_token_mint_info.key,
@@ -193,6 +287,8 @@ pub fn process_cast_vote(
 
     let governing_token_owner = voter_token_owner_record_data.governing_token_owner;
 
+    let voter_number = proposal_data.record_vote_participation();
+
     voter_token_owner_record_data
         .serialize(&mut voter_token_owner_record_info.data.borrow_mut()[..])?;
 
@@ -206,7 +302,8 @@ pub fn process_cast_vote(
         voter_weight,
         vote,
         is_relinquished: false,
-        reserved_v2: [0; 8],
+        voter_number,
+        reserved_v2: [0; 4],
     };
 
     create_and_serialize_account_signed::<VoteRecordV2>(
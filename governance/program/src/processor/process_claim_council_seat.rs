@@ -0,0 +1,89 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            council_election::{
+                get_council_candidate_data_for_council_election, get_council_election_data,
+                get_council_seat_address_seeds, CouncilElectionState, CouncilSeat,
+            },
+            enums::GovernanceAccountType,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes ClaimCouncilSeat instruction
+pub fn process_claim_council_seat(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let council_election_info = next_account_info(account_info_iter)?; // 0
+    let council_candidate_info = next_account_info(account_info_iter)?; // 1
+    let council_seat_info = next_account_info(account_info_iter)?; // 2
+    let payer_info = next_account_info(account_info_iter)?; // 3
+    let system_info = next_account_info(account_info_iter)?; // 4
+
+    let rent = Rent::get()?;
+
+    let council_election_data = get_council_election_data(program_id, council_election_info)?;
+
+    if council_election_data.state != CouncilElectionState::Finalized {
+        return Err(GovernanceError::CouncilElectionNotFinalized.into());
+    }
+
+    let mut council_candidate_data = get_council_candidate_data_for_council_election(
+        program_id,
+        council_candidate_info,
+        council_election_info.key,
+    )?;
+
+    if !council_candidate_data.is_elected {
+        return Err(GovernanceError::CouncilCandidateNotElected.into());
+    }
+
+    if council_candidate_data.seat_claimed {
+        return Err(GovernanceError::CouncilSeatAlreadyClaimed.into());
+    }
+
+    // The seat isn't revoked when the term ends and simply stops giving the
+    // member voter weight
+    let council_seat_data = CouncilSeat {
+        account_type: GovernanceAccountType::CouncilSeat,
+        realm: council_election_data.realm,
+        council_election: *council_election_info.key,
+        member: council_candidate_data.candidate,
+        term_end_at: council_election_data.config.term_end_at,
+        reserved: [0; 32],
+    };
+
+    create_and_serialize_account_signed::<CouncilSeat>(
+        payer_info,
+        council_seat_info,
+        &council_seat_data,
+        &get_council_seat_address_seeds(
+            council_election_info.key,
+            &council_candidate_data.candidate,
+        ),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    council_candidate_data.seat_claimed = true;
+
+    borsh::to_writer(
+        &mut council_candidate_info.data.borrow_mut()[..],
+        &council_candidate_data,
+    )?;
+
+    Ok(())
+}
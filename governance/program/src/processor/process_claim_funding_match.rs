@@ -0,0 +1,78 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::funding_round::{
+            assert_is_funding_round_escrow, get_funding_project_data_for_funding_round,
+            get_funding_round_address_seeds, get_funding_round_data,
+        },
+        tools::spl_token::transfer_spl_tokens_signed,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+/// Processes ClaimFundingMatch instruction
+pub fn process_claim_funding_match(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let funding_round_info = next_account_info(account_info_iter)?; // 0
+    let funding_project_info = next_account_info(account_info_iter)?; // 1
+    let funding_round_escrow_info = next_account_info(account_info_iter)?; // 2
+    let recipient_info = next_account_info(account_info_iter)?; // 3
+    let spl_token_info = next_account_info(account_info_iter)?; // 4
+
+    let funding_round_data = get_funding_round_data(program_id, funding_round_info)?;
+
+    let mut funding_project_data = get_funding_project_data_for_funding_round(
+        program_id,
+        funding_project_info,
+        funding_round_info.key,
+    )?;
+
+    if funding_project_data.matching_claimed {
+        return Err(GovernanceError::FundingProjectMatchingAlreadyClaimed.into());
+    }
+
+    if funding_project_data.recipient != *recipient_info.key {
+        return Err(GovernanceError::InvalidFundingProjectRecipient.into());
+    }
+
+    assert_is_funding_round_escrow(
+        program_id,
+        funding_round_info.key,
+        funding_round_escrow_info,
+    )?;
+
+    let matching_amount =
+        funding_round_data.get_matching_amount(funding_project_data.get_matching_weight())?;
+
+    if matching_amount > 0 {
+        transfer_spl_tokens_signed(
+            funding_round_escrow_info,
+            recipient_info,
+            funding_round_info,
+            &get_funding_round_address_seeds(
+                &funding_round_data.governance,
+                &funding_round_data.round_seed,
+            ),
+            program_id,
+            matching_amount,
+            spl_token_info,
+        )?;
+    }
+
+    funding_project_data.matching_amount = matching_amount;
+    funding_project_data.matching_claimed = true;
+
+    borsh::to_writer(
+        &mut funding_project_info.data.borrow_mut()[..],
+        &funding_project_data,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::GovernanceAccountType,
+            funding_round::{
+                get_funding_contribution_address_seeds, get_funding_contribution_data,
+                get_funding_project_data_for_funding_round, get_funding_round_data,
+                FundingContribution,
+            },
+            token_owner_record::get_token_owner_record_data_for_realm,
+        },
+        tools::spl_token::transfer_spl_tokens,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes ContributeToFundingProject instruction
+pub fn process_contribute_to_funding_project(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let funding_round_info = next_account_info(account_info_iter)?; // 0
+    let funding_project_info = next_account_info(account_info_iter)?; // 1
+    let funding_contribution_info = next_account_info(account_info_iter)?; // 2
+    let token_owner_record_info = next_account_info(account_info_iter)?; // 3
+    let governance_authority_info = next_account_info(account_info_iter)?; // 4
+    let source_info = next_account_info(account_info_iter)?; // 5
+    let recipient_info = next_account_info(account_info_iter)?; // 6
+    let payer_info = next_account_info(account_info_iter)?; // 7
+    let system_info = next_account_info(account_info_iter)?; // 8
+    let spl_token_info = next_account_info(account_info_iter)?; // 9
+
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+
+    if amount == 0 {
+        return Err(GovernanceError::InvalidFundingContributionAmount.into());
+    }
+
+    let mut funding_round_data = get_funding_round_data(program_id, funding_round_info)?;
+    funding_round_data.assert_is_accepting_contributions(clock.unix_timestamp)?;
+
+    let mut funding_project_data = get_funding_project_data_for_funding_round(
+        program_id,
+        funding_project_info,
+        funding_round_info.key,
+    )?;
+
+    if funding_project_data.recipient != *recipient_info.key {
+        return Err(GovernanceError::InvalidFundingProjectRecipient.into());
+    }
+
+    // Contributions are tracked per member of the Realm to prevent splitting
+    // them across multiple wallets to inflate the matching
+    let token_owner_record_data = get_token_owner_record_data_for_realm(
+        program_id,
+        token_owner_record_info,
+        &funding_round_data.realm,
+    )?;
+
+    token_owner_record_data.assert_token_owner_or_delegate_is_signer(governance_authority_info)?;
+
+    if token_owner_record_data.governing_token_deposit_amount == 0 {
+        return Err(GovernanceError::NotEnoughTokensToContributeToFundingProject.into());
+    }
+
+    let previous_amount = if funding_contribution_info.data_is_empty() {
+        0
+    } else {
+        let funding_contribution_data =
+            get_funding_contribution_data(program_id, funding_contribution_info)?;
+
+        if funding_contribution_data.funding_project != *funding_project_info.key
+            || funding_contribution_data.token_owner_record != *token_owner_record_info.key
+        {
+            return Err(GovernanceError::InvalidFundingContribution.into());
+        }
+
+        funding_contribution_data.amount
+    };
+
+    let contribution_amount = previous_amount
+        .checked_add(amount)
+        .ok_or(GovernanceError::FundingRoundMatchingOverflow)?;
+
+    transfer_spl_tokens(
+        source_info,
+        recipient_info,
+        governance_authority_info,
+        amount,
+        spl_token_info,
+    )?;
+
+    // Replace the previous matching weight of the project with the weight
+    // including the contribution
+    let previous_matching_weight = funding_project_data.get_matching_weight();
+
+    funding_project_data.update_capped_contribution(
+        funding_round_data.get_capped_contribution(previous_amount),
+        funding_round_data.get_capped_contribution(contribution_amount),
+    )?;
+
+    funding_round_data.total_matching_weight = funding_round_data
+        .total_matching_weight
+        .checked_sub(previous_matching_weight)
+        .and_then(|weight| weight.checked_add(funding_project_data.get_matching_weight()))
+        .ok_or(GovernanceError::FundingRoundMatchingOverflow)?;
+
+    funding_project_data.contributions_amount = funding_project_data
+        .contributions_amount
+        .checked_add(amount)
+        .ok_or(GovernanceError::FundingRoundMatchingOverflow)?;
+
+    let funding_contribution_data = FundingContribution {
+        account_type: GovernanceAccountType::FundingContribution,
+        funding_project: *funding_project_info.key,
+        token_owner_record: *token_owner_record_info.key,
+        amount: contribution_amount,
+        reserved: [0; 32],
+    };
+
+    if funding_contribution_info.data_is_empty() {
+        create_and_serialize_account_signed::<FundingContribution>(
+            payer_info,
+            funding_contribution_info,
+            &funding_contribution_data,
+            &get_funding_contribution_address_seeds(
+                funding_project_info.key,
+                token_owner_record_info.key,
+            ),
+            program_id,
+            system_info,
+            &rent,
+            0,
+        )?;
+
+        funding_project_data.contributors_count = funding_project_data
+            .contributors_count
+            .checked_add(1)
+            .unwrap();
+    } else {
+        borsh::to_writer(
+            &mut funding_contribution_info.data.borrow_mut()[..],
+            &funding_contribution_data,
+        )?;
+    }
+
+    borsh::to_writer(
+        &mut funding_project_info.data.borrow_mut()[..],
+        &funding_project_data,
+    )?;
+
+    borsh::to_writer(
+        &mut funding_round_info.data.borrow_mut()[..],
+        &funding_round_data,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            council_election::{
+                assert_is_valid_council_election_config, get_council_election_address_seeds,
+                CouncilElection, CouncilElectionConfig, CouncilElectionState,
+            },
+            enums::GovernanceAccountType,
+            realm::get_realm_data_for_authority,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes CreateCouncilElection instruction
+pub fn process_create_council_election(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    election_seed: Pubkey,
+    config: CouncilElectionConfig,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let realm_info = next_account_info(account_info_iter)?; // 0
+    let realm_authority_info = next_account_info(account_info_iter)?; // 1
+    let council_election_info = next_account_info(account_info_iter)?; // 2
+    let payer_info = next_account_info(account_info_iter)?; // 3
+    let system_info = next_account_info(account_info_iter)?; // 4
+
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+
+    let realm_data =
+        get_realm_data_for_authority(program_id, realm_info, realm_authority_info.key)?;
+
+    if !realm_authority_info.is_signer {
+        return Err(GovernanceError::RealmAuthorityMustSign.into());
+    }
+
+    // CouncilSeats carry council voter weight and can't exist without the mint
+    if realm_data.config.council_mint.is_none() {
+        return Err(GovernanceError::CouncilMintRequiredForCouncilElection.into());
+    }
+
+    assert_is_valid_council_election_config(&config, clock.unix_timestamp)?;
+
+    let council_election_data = CouncilElection {
+        account_type: GovernanceAccountType::CouncilElection,
+        realm: *realm_info.key,
+        election_seed,
+        config,
+        state: CouncilElectionState::Voting,
+        candidates_count: 0,
+        elected_count: 0,
+        reserved: [0; 64],
+    };
+
+    create_and_serialize_account_signed::<CouncilElection>(
+        payer_info,
+        council_election_info,
+        &council_election_data,
+        &get_council_election_address_seeds(realm_info.key, &election_seed),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    Ok(())
+}
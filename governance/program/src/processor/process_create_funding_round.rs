@@ -0,0 +1,102 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::GovernanceAccountType,
+            funding_round::{
+                assert_is_valid_funding_round_config, get_funding_round_address_seeds,
+                get_funding_round_escrow_address_seeds, FundingRound, FundingRoundConfig,
+                FundingRoundState,
+            },
+            governance::get_governance_data_for_realm,
+            realm::get_realm_data,
+        },
+        tools::spl_token::{assert_is_valid_spl_token_mint, create_spl_token_account_signed},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes CreateFundingRound instruction
+pub fn process_create_funding_round(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    round_seed: Pubkey,
+    config: FundingRoundConfig,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let realm_info = next_account_info(account_info_iter)?; // 0
+    let governance_info = next_account_info(account_info_iter)?; // 1
+    let funding_round_info = next_account_info(account_info_iter)?; // 2
+    let funding_round_escrow_info = next_account_info(account_info_iter)?; // 3
+    let mint_info = next_account_info(account_info_iter)?; // 4
+    let payer_info = next_account_info(account_info_iter)?; // 5
+    let system_info = next_account_info(account_info_iter)?; // 6
+    let spl_token_info = next_account_info(account_info_iter)?; // 7
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 8
+
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+
+    // Only governance PDA via a proposal can create a round funded by its
+    // treasury
+    if !governance_info.is_signer {
+        return Err(GovernanceError::GovernancePdaMustSign.into());
+    }
+
+    get_realm_data(program_id, realm_info)?;
+    get_governance_data_for_realm(program_id, governance_info, realm_info.key)?;
+
+    assert_is_valid_funding_round_config(&config, clock.unix_timestamp)?;
+    assert_is_valid_spl_token_mint(mint_info)?;
+
+    let funding_round_data = FundingRound {
+        account_type: GovernanceAccountType::FundingRound,
+        realm: *realm_info.key,
+        governance: *governance_info.key,
+        round_seed,
+        mint: *mint_info.key,
+        config,
+        state: FundingRoundState::Active,
+        projects_count: 0,
+        total_matching_weight: 0,
+        matching_pool_amount: 0,
+        reserved: [0; 64],
+    };
+
+    create_and_serialize_account_signed::<FundingRound>(
+        payer_info,
+        funding_round_info,
+        &funding_round_data,
+        &get_funding_round_address_seeds(governance_info.key, &round_seed),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    create_spl_token_account_signed(
+        payer_info,
+        funding_round_escrow_info,
+        &get_funding_round_escrow_address_seeds(funding_round_info.key),
+        mint_info,
+        funding_round_info,
+        program_id,
+        system_info,
+        spl_token_info,
+        rent_sysvar_info,
+        &rent,
+    )?;
+
+    Ok(())
+}
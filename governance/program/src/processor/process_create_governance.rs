@@ -4,13 +4,15 @@ use {
     crate::{
         state::{
             enums::GovernanceAccountType,
+            execution_simulation::ExecutionSimulationConfig,
             governance::{
                 assert_valid_create_governance_args, get_governance_address_seeds,
                 GovernanceConfig, GovernanceV2,
             },
+            proposal_token_deposit::ProposalTokenDepositConfig,
             realm::get_realm_data,
         },
-        tools::structs::Reserved119,
+        tools::structs::Reserved70,
     },
     solana_program::{
         account_info::{next_account_info, AccountInfo},
@@ -61,7 +63,9 @@ pub fn process_create_governance(
         governance_seed: *governance_seed_info.key,
         config,
         reserved1: 0,
-        reserved_v2: Reserved119::default(),
+        reserved_v2: Reserved70::default(),
+        execution_simulation_config: ExecutionSimulationConfig::default(),
+        proposal_token_deposit_config: ProposalTokenDepositConfig::default(),
         required_signatories_count: 0,
         active_proposal_count: 0,
     };
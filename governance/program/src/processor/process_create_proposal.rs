@@ -11,11 +11,16 @@ use {
                 ProposalOption, ProposalV2, VoteType,
             },
             proposal_deposit::{get_proposal_deposit_address_seeds, ProposalDeposit},
+            proposal_token_deposit::{
+                get_proposal_token_deposit_address_seeds,
+                get_proposal_token_deposit_escrow_address_seeds, ProposalTokenDeposit,
+            },
             realm::get_realm_data_for_governing_token_mint,
             realm_config::get_realm_config_data_for_realm,
             token_owner_record::get_token_owner_record_data_for_realm,
             vote_record::VoteKind,
         },
+        tools::spl_token::{create_spl_token_account_signed, transfer_spl_tokens},
     },
     solana_program::{
         account_info::{next_account_info, AccountInfo},
@@ -164,8 +169,13 @@ pub fn process_create_proposal(
         max_voting_time: None,
         vote_threshold: None,
 
-        reserved: [0; 64],
-        reserved1: 0,
+        votes_count: 0,
+        voters_count: 0,
+        voting_extensions_count: 0,
+        voting_extended_time: 0,
+        dependencies_count: 0,
+        reserved: [0; 50],
+        is_linked: false,
     };
 
     create_and_serialize_account_signed::<ProposalV2>(
@@ -188,26 +198,90 @@ pub fn process_create_proposal(
         .checked_add(1)
         .unwrap();
 
-    // Take Proposal deposit if needed
     let proposal_deposit_amount = governance_data.get_proposal_deposit_amount();
-    if proposal_deposit_amount > 0 {
+    let proposal_token_deposit_amount = governance_data
+        .get_proposal_token_deposit_amount(&realm_data, governing_token_mint_info.key);
+
+    // Note: ProposalDeposit account precedes ProposalTokenDeposit accounts and
+    // must be passed when the token deposit is required
+    if proposal_deposit_amount > 0 || proposal_token_deposit_amount > 0 {
         let proposal_deposit_info = next_account_info(account_info_iter)?; // *10
-        let proposal_deposit_data = ProposalDeposit {
-            account_type: GovernanceAccountType::ProposalDeposit,
+
+        // Take Proposal deposit if needed
+        if proposal_deposit_amount > 0 {
+            let proposal_deposit_data = ProposalDeposit {
+                account_type: GovernanceAccountType::ProposalDeposit,
+                proposal: *proposal_info.key,
+                deposit_payer: *payer_info.key,
+                reserved: [0; 64],
+            };
+
+            create_and_serialize_account_signed::<ProposalDeposit>(
+                payer_info,
+                proposal_deposit_info,
+                &proposal_deposit_data,
+                &get_proposal_deposit_address_seeds(proposal_info.key, payer_info.key),
+                program_id,
+                system_info,
+                &rent,
+                proposal_deposit_amount,
+            )?;
+        }
+    }
+
+    // Take Proposal token deposit if needed
+    if proposal_token_deposit_amount > 0 {
+        let proposal_token_deposit_info = next_account_info(account_info_iter)?; // *11
+        let proposal_token_deposit_escrow_info = next_account_info(account_info_iter)?; // *12
+        let governing_token_source_info = next_account_info(account_info_iter)?; // *13
+        let spl_token_info = next_account_info(account_info_iter)?; // *14
+        let rent_sysvar_info = next_account_info(account_info_iter)?; // *15
+
+        let proposal_token_deposit_data = ProposalTokenDeposit {
+            account_type: GovernanceAccountType::ProposalTokenDeposit,
             proposal: *proposal_info.key,
+            depositor: *governance_authority_info.key,
             deposit_payer: *payer_info.key,
+            amount: proposal_token_deposit_amount,
+            participation_floor_percentage: governance_data
+                .proposal_token_deposit_config
+                .participation_floor_percentage,
             reserved: [0; 64],
         };
 
-        create_and_serialize_account_signed::<ProposalDeposit>(
+        create_and_serialize_account_signed::<ProposalTokenDeposit>(
+            payer_info,
+            proposal_token_deposit_info,
+            &proposal_token_deposit_data,
+            &get_proposal_token_deposit_address_seeds(proposal_info.key),
+            program_id,
+            system_info,
+            &rent,
+            0,
+        )?;
+
+        // The escrow token account is owned by the ProposalTokenDeposit PDA
+        create_spl_token_account_signed(
             payer_info,
-            proposal_deposit_info,
-            &proposal_deposit_data,
-            &get_proposal_deposit_address_seeds(proposal_info.key, payer_info.key),
+            proposal_token_deposit_escrow_info,
+            &get_proposal_token_deposit_escrow_address_seeds(proposal_info.key),
+            governing_token_mint_info,
+            proposal_token_deposit_info,
             program_id,
             system_info,
+            spl_token_info,
+            rent_sysvar_info,
             &rent,
-            proposal_deposit_amount,
+        )?;
+
+        // The deposit is taken from the token account of the Governance Authority
+        // which signed the transaction
+        transfer_spl_tokens(
+            governing_token_source_info,
+            proposal_token_deposit_escrow_info,
+            governance_authority_info,
+            proposal_token_deposit_amount,
+            spl_token_info,
         )?;
     }
 
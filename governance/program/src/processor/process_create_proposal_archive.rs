@@ -0,0 +1,56 @@
+//! Program state processor
+
+use {
+    crate::state::{
+        proposal::get_proposal_data,
+        proposal_archive::{get_proposal_archive_address_seeds, ProposalArchive},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes CreateProposalArchive instruction
+pub fn process_create_proposal_archive(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_info = next_account_info(account_info_iter)?; // 0
+    let proposal_archive_info = next_account_info(account_info_iter)?; // 1
+
+    let payer_info = next_account_info(account_info_iter)?; // 2
+    let system_info = next_account_info(account_info_iter)?; // 3
+
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+
+    let proposal_data = get_proposal_data(program_id, proposal_info)?;
+    proposal_data.assert_can_archive(clock.unix_timestamp)?;
+
+    let proposal_archive_data = ProposalArchive::new(
+        proposal_data.governance,
+        *proposal_info.key,
+        proposal_data.state,
+    );
+
+    create_and_serialize_account_signed::<ProposalArchive>(
+        payer_info,
+        proposal_archive_info,
+        &proposal_archive_data,
+        &get_proposal_archive_address_seeds(proposal_info.key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    Ok(())
+}
@@ -10,10 +10,11 @@ use {
                 get_realm_address_seeds, RealmConfig, RealmConfigArgs, RealmV2,
             },
             realm_config::{
-                get_realm_config_address_seeds, resolve_governing_token_config, RealmConfigAccount,
+                get_realm_config_address_seeds, resolve_governing_token_config, CouncilTerm,
+                RealmConfigAccount, VoteExtensionConfig,
             },
         },
-        tools::{spl_token::create_spl_token_account_signed, structs::Reserved110},
+        tools::{spl_token::create_spl_token_account_signed, structs::Reserved91},
     },
     solana_program::{
         account_info::{next_account_info, AccountInfo},
@@ -110,7 +111,10 @@ pub fn process_create_realm(
         realm: *realm_info.key,
         community_token_config,
         council_token_config,
-        reserved: Reserved110::default(),
+        council_term: CouncilTerm::default(),
+        vote_extension: VoteExtensionConfig::default(),
+        use_addin_registry: false,
+        reserved: Reserved91::default(),
     };
 
     create_and_serialize_account_signed::<RealmConfigAccount>(
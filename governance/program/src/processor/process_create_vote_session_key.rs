@@ -0,0 +1,90 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::GovernanceAccountType,
+            token_owner_record::get_token_owner_record_data,
+            vote_session_key::{
+                get_vote_session_key_address_seeds,
+                get_vote_session_key_data_for_token_owner_record, VoteSessionKey,
+            },
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Slot,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes CreateVoteSessionKey instruction
+pub fn process_create_vote_session_key(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    session_key: Pubkey,
+    expiry_slot: Slot,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let token_owner_record_info = next_account_info(account_info_iter)?; // 0
+    let governance_authority_info = next_account_info(account_info_iter)?; // 1
+    let vote_session_key_info = next_account_info(account_info_iter)?; // 2
+    let payer_info = next_account_info(account_info_iter)?; // 3
+    let system_info = next_account_info(account_info_iter)?; // 4
+
+    let token_owner_record_data = get_token_owner_record_data(program_id, token_owner_record_info)?;
+    token_owner_record_data.assert_token_owner_or_delegate_is_signer(governance_authority_info)?;
+
+    // An existing session key is updated in place which allows to extend or
+    // revoke it by setting expiry_slot in the past
+    if !vote_session_key_info.data_is_empty() {
+        let mut vote_session_key_data = get_vote_session_key_data_for_token_owner_record(
+            program_id,
+            vote_session_key_info,
+            token_owner_record_info.key,
+        )?;
+
+        if vote_session_key_data.session_key != session_key {
+            return Err(GovernanceError::InvalidVoteSessionKey.into());
+        }
+
+        vote_session_key_data.authority = *governance_authority_info.key;
+        vote_session_key_data.expiry_slot = expiry_slot;
+        borsh::to_writer(
+            &mut vote_session_key_info.data.borrow_mut()[..],
+            &vote_session_key_data,
+        )?;
+
+        return Ok(());
+    }
+
+    let rent = Rent::get()?;
+
+    let vote_session_key_data = VoteSessionKey {
+        account_type: GovernanceAccountType::VoteSessionKey,
+        token_owner_record: *token_owner_record_info.key,
+        authority: *governance_authority_info.key,
+        session_key,
+        expiry_slot,
+        reserved: [0; 32],
+    };
+
+    create_and_serialize_account_signed::<VoteSessionKey>(
+        payer_info,
+        vote_session_key_info,
+        &vote_session_key_data,
+        &get_vote_session_key_address_seeds(token_owner_record_info.key, &session_key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    Ok(())
+}
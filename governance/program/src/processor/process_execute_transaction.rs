@@ -1,12 +1,18 @@
 //! Program state processor
 
 use {
-    crate::state::{
-        enums::{ProposalState, TransactionExecutionStatus},
-        governance::get_governance_data,
-        native_treasury::get_native_treasury_address_seeds,
-        proposal::{get_proposal_data_for_governance, OptionVoteResult},
-        proposal_transaction::get_proposal_transaction_data_for_proposal,
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::{ProposalState, TransactionExecutionStatus},
+            execution_simulation::get_execution_simulation_attestation_data_for_proposal_transaction,
+            governance::get_governance_data,
+            native_treasury::get_native_treasury_address_seeds,
+            proposal::{get_proposal_data, get_proposal_data_for_governance, OptionVoteResult},
+            proposal_dependency::get_proposal_dependency_data_for_proposal,
+            proposal_link::get_proposal_link_data_for_proposal,
+            proposal_transaction::get_proposal_transaction_data_for_proposal,
+        },
     },
     solana_program::{
         account_info::{next_account_info, AccountInfo},
@@ -46,6 +52,64 @@ pub fn process_execute_transaction(program_id: &Pubkey, accounts: &[AccountInfo]
         clock.unix_timestamp,
     )?;
 
+    // Linked Proposal can only be executed once threshold linked Proposals
+    // succeeded and the link was committed
+    if proposal_data.is_linked {
+        let proposal_link_info = next_account_info(account_info_iter)?; // 3
+        get_proposal_link_data_for_proposal(program_id, proposal_link_info, proposal_info.key)?
+            .assert_is_committed()?;
+    }
+
+    // The transaction can only be executed once the simulation oracle attested
+    // its successful simulation within the configured number of slots
+    let execution_simulation_config = &governance_data.execution_simulation_config;
+    if execution_simulation_config.is_attestation_required() {
+        let execution_simulation_attestation_info = next_account_info(account_info_iter)?;
+        get_execution_simulation_attestation_data_for_proposal_transaction(
+            program_id,
+            execution_simulation_attestation_info,
+            proposal_transaction_info.key,
+            &execution_simulation_config.simulation_oracle,
+        )?
+        .assert_can_execute_transaction(
+            execution_simulation_config,
+            &proposal_transaction_data.instructions,
+            clock.slot,
+        )?;
+    }
+
+    // The transaction can only be executed once all the Proposals the Proposal
+    // depends on were completed
+    // The dependencies must be provided in ascending address order which
+    // guarantees each of them is provided exactly once
+    let mut last_proposal_dependency: Option<&Pubkey> = None;
+    for _ in 0..proposal_data.dependencies_count {
+        let proposal_dependency_info = next_account_info(account_info_iter)?;
+        let depends_on_proposal_info = next_account_info(account_info_iter)?;
+
+        if let Some(last_proposal_dependency) = last_proposal_dependency {
+            if proposal_dependency_info.key <= last_proposal_dependency {
+                return Err(GovernanceError::InvalidProposalDependenciesOrder.into());
+            }
+        }
+        last_proposal_dependency = Some(proposal_dependency_info.key);
+
+        let proposal_dependency_data = get_proposal_dependency_data_for_proposal(
+            program_id,
+            proposal_dependency_info,
+            proposal_info.key,
+        )?;
+
+        if proposal_dependency_data.depends_on != *depends_on_proposal_info.key {
+            return Err(GovernanceError::InvalidProposalForProposalDependency.into());
+        }
+
+        let depends_on_proposal_data = get_proposal_data(program_id, depends_on_proposal_info)?;
+        if depends_on_proposal_data.state != ProposalState::Completed {
+            return Err(GovernanceError::ProposalDependencyNotCompleted.into());
+        }
+    }
+
     // Execute instruction with Governance PDA as signer
     let instructions = proposal_transaction_data
         .instructions
@@ -0,0 +1,128 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            council_election::{
+                get_council_candidate_data_for_council_election, get_council_election_data,
+                CouncilElectionState,
+            },
+            realm::get_realm_data,
+            realm_config::{get_realm_config_data_for_realm, CouncilTerm},
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+/// Processes FinalizeCouncilElection instruction
+pub fn process_finalize_council_election(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let realm_info = next_account_info(account_info_iter)?; // 0
+    let council_election_info = next_account_info(account_info_iter)?; // 1
+    let realm_config_info = next_account_info(account_info_iter)?; // 2
+    let payer_info = next_account_info(account_info_iter)?; // 3
+    let system_info = next_account_info(account_info_iter)?; // 4
+
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+
+    let mut council_election_data = get_council_election_data(program_id, council_election_info)?;
+    council_election_data.assert_can_finalize(clock.unix_timestamp)?;
+
+    if council_election_data.realm != *realm_info.key {
+        return Err(GovernanceError::InvalidRealmForCouncilElection.into());
+    }
+
+    get_realm_data(program_id, realm_info)?;
+
+    let mut realm_config_data =
+        get_realm_config_data_for_realm(program_id, realm_config_info, realm_info.key)?;
+
+    // A council which ends earlier than the current one would shorten its term
+    if council_election_data.config.term_end_at <= realm_config_data.council_term.end_at {
+        return Err(GovernanceError::CouncilElectionTermEndsBeforeCurrentTerm.into());
+    }
+
+    // All candidates must be provided to rank them and the strictly ascending
+    // address order guarantees none of them is provided twice
+    let council_candidate_infos = account_info_iter.as_slice();
+
+    if council_candidate_infos.len() != council_election_data.candidates_count as usize
+        || council_candidate_infos
+            .windows(2)
+            .any(|pair| pair[0].key >= pair[1].key)
+    {
+        return Err(GovernanceError::InvalidCouncilCandidatesForFinalization.into());
+    }
+
+    let council_candidates = council_candidate_infos
+        .iter()
+        .map(|council_candidate_info| {
+            get_council_candidate_data_for_council_election(
+                program_id,
+                council_candidate_info,
+                council_election_info.key,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let seats_count = council_election_data.config.seats_count as usize;
+
+    for (council_candidate_info, council_candidate_data) in council_candidate_infos
+        .iter()
+        .zip(council_candidates.iter())
+    {
+        let rank = council_candidates
+            .iter()
+            .filter(|other| other.ranks_above(council_candidate_data))
+            .count();
+
+        if rank < seats_count {
+            let mut council_candidate_data = council_candidate_data.clone();
+            council_candidate_data.is_elected = true;
+
+            borsh::to_writer(
+                &mut council_candidate_info.data.borrow_mut()[..],
+                &council_candidate_data,
+            )?;
+
+            council_election_data.elected_count =
+                council_election_data.elected_count.checked_add(1).unwrap();
+        }
+    }
+
+    // The elected seats replace the seats of the current council term
+    realm_config_data.council_term = CouncilTerm {
+        seats_count: council_election_data.elected_count,
+        end_at: council_election_data.config.term_end_at,
+    };
+
+    realm_config_data.serialize(
+        program_id,
+        realm_config_info,
+        payer_info,
+        system_info,
+        &rent,
+    )?;
+
+    council_election_data.state = CouncilElectionState::Finalized;
+
+    borsh::to_writer(
+        &mut council_election_info.data.borrow_mut()[..],
+        &council_election_data,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::funding_round::{
+            assert_is_funding_round_escrow, get_funding_round_address_seeds,
+            get_funding_round_data, FundingRoundState,
+        },
+        tools::spl_token::{get_spl_token_amount, get_spl_token_owner, transfer_spl_tokens_signed},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+/// Processes FinalizeFundingRound instruction
+pub fn process_finalize_funding_round(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let funding_round_info = next_account_info(account_info_iter)?; // 0
+    let funding_round_escrow_info = next_account_info(account_info_iter)?; // 1
+    let governance_destination_info = next_account_info(account_info_iter)?; // 2
+    let spl_token_info = next_account_info(account_info_iter)?; // 3
+
+    let clock = Clock::get()?;
+
+    let mut funding_round_data = get_funding_round_data(program_id, funding_round_info)?;
+    funding_round_data.assert_can_finalize(clock.unix_timestamp)?;
+
+    assert_is_funding_round_escrow(
+        program_id,
+        funding_round_info.key,
+        funding_round_escrow_info,
+    )?;
+
+    let escrow_amount = get_spl_token_amount(funding_round_escrow_info)?;
+
+    // Without any matching weight there is nothing to distribute the pool to
+    // and it's returned to the Governance
+    if funding_round_data.total_matching_weight == 0 && escrow_amount > 0 {
+        if get_spl_token_owner(governance_destination_info)? != funding_round_data.governance {
+            return Err(GovernanceError::InvalidGovernanceDestinationForFundingRound.into());
+        }
+
+        transfer_spl_tokens_signed(
+            funding_round_escrow_info,
+            governance_destination_info,
+            funding_round_info,
+            &get_funding_round_address_seeds(
+                &funding_round_data.governance,
+                &funding_round_data.round_seed,
+            ),
+            program_id,
+            escrow_amount,
+            spl_token_info,
+        )?;
+    } else {
+        funding_round_data.matching_pool_amount = escrow_amount;
+    }
+
+    funding_round_data.state = FundingRoundState::Finalized;
+
+    borsh::to_writer(
+        &mut funding_round_info.data.borrow_mut()[..],
+        &funding_round_data,
+    )?;
+
+    Ok(())
+}
@@ -56,6 +56,7 @@ pub fn process_finalize_vote(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
         &realm_config_data,
         governing_token_mint_info,
         &VoteKind::Electorate,
+        clock.unix_timestamp,
     )?;
 
     let vote_threshold = governance_data.resolve_vote_threshold(
@@ -0,0 +1,97 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::{GovernanceAccountType, ProposalLinkState},
+            proposal::get_proposal_data,
+            proposal_link::{get_proposal_link_address_seeds, ProposalLink},
+            token_owner_record::get_token_owner_record_data_for_proposal_owner,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes LinkProposals instruction
+pub fn process_link_proposals(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_link_info = next_account_info(account_info_iter)?; // 0
+    let payer_info = next_account_info(account_info_iter)?; // 1
+    let system_info = next_account_info(account_info_iter)?; // 2
+
+    let rent = Rent::get()?;
+
+    // Each linked Proposal is followed by the TokenOwnerRecord and the
+    // Governance Authority of its owner
+    let linked_proposal_infos = account_info_iter.as_slice();
+    if linked_proposal_infos.len() % 3 != 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let proposal_link_data = ProposalLink {
+        account_type: GovernanceAccountType::ProposalLink,
+        proposals: linked_proposal_infos
+            .chunks(3)
+            .map(|infos| *infos[0].key)
+            .collect(),
+        threshold,
+        state: ProposalLinkState::Pending,
+        resolved_at: None,
+        reserved: [0; 64],
+    };
+    proposal_link_data.assert_is_valid_link()?;
+
+    let mut governances: Vec<Pubkey> = vec![];
+
+    for infos in linked_proposal_infos.chunks(3) {
+        let (proposal_info, proposal_owner_record_info, governance_authority_info) =
+            (&infos[0], &infos[1], &infos[2]);
+
+        let mut proposal_data = get_proposal_data(program_id, proposal_info)?;
+        proposal_data.assert_can_link()?;
+
+        if governances.contains(&proposal_data.governance) {
+            return Err(GovernanceError::CannotLinkProposalsOfSameGovernance.into());
+        }
+        governances.push(proposal_data.governance);
+
+        // All Proposal owners must consent to the link
+        let proposal_owner_record_data = get_token_owner_record_data_for_proposal_owner(
+            program_id,
+            proposal_owner_record_info,
+            &proposal_data.token_owner_record,
+        )?;
+        proposal_owner_record_data
+            .assert_token_owner_or_delegate_is_signer(governance_authority_info)?;
+
+        proposal_data.is_linked = true;
+        proposal_data.serialize(&mut proposal_info.data.borrow_mut()[..])?;
+    }
+
+    create_and_serialize_account_signed::<ProposalLink>(
+        payer_info,
+        proposal_link_info,
+        &proposal_link_data,
+        &get_proposal_link_address_seeds(&proposal_link_data.proposals[0]),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::GovernanceAccountType,
+            execution_simulation::{
+                get_execution_simulation_attestation_address_seeds,
+                get_execution_simulation_attestation_data_for_proposal_transaction,
+                ExecutionSimulationAttestation,
+            },
+            proposal_transaction::get_proposal_transaction_data,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes PostExecutionSimulationAttestation instruction
+pub fn process_post_execution_simulation_attestation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    transaction_hash: [u8; 32],
+    success: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_transaction_info = next_account_info(account_info_iter)?; // 0
+    let execution_simulation_attestation_info = next_account_info(account_info_iter)?; // 1
+    let simulation_oracle_info = next_account_info(account_info_iter)?; // 2
+
+    if !simulation_oracle_info.is_signer {
+        return Err(GovernanceError::SimulationOracleMustSign.into());
+    }
+
+    let clock = Clock::get()?;
+
+    // Assert the attested account is a ProposalTransaction
+    get_proposal_transaction_data(program_id, proposal_transaction_info)?;
+
+    if execution_simulation_attestation_info.data_is_empty() {
+        let payer_info = next_account_info(account_info_iter)?; // 3
+        let system_info = next_account_info(account_info_iter)?; // 4
+
+        let rent = Rent::get()?;
+
+        let execution_simulation_attestation_data = ExecutionSimulationAttestation {
+            account_type: GovernanceAccountType::ExecutionSimulationAttestation,
+            proposal_transaction: *proposal_transaction_info.key,
+            simulation_oracle: *simulation_oracle_info.key,
+            transaction_hash,
+            success,
+            slot: clock.slot,
+            reserved: [0; 32],
+        };
+
+        create_and_serialize_account_signed::<ExecutionSimulationAttestation>(
+            payer_info,
+            execution_simulation_attestation_info,
+            &execution_simulation_attestation_data,
+            &get_execution_simulation_attestation_address_seeds(
+                proposal_transaction_info.key,
+                simulation_oracle_info.key,
+            ),
+            program_id,
+            system_info,
+            &rent,
+            0,
+        )?;
+    } else {
+        // The oracle can re-post the attestation, for example when the simulation
+        // outcome changed or the previous attestation expired
+        let mut execution_simulation_attestation_data =
+            get_execution_simulation_attestation_data_for_proposal_transaction(
+                program_id,
+                execution_simulation_attestation_info,
+                proposal_transaction_info.key,
+                simulation_oracle_info.key,
+            )?;
+
+        execution_simulation_attestation_data.transaction_hash = transaction_hash;
+        execution_simulation_attestation_data.success = success;
+        execution_simulation_attestation_data.slot = clock.slot;
+
+        borsh::to_writer(
+            &mut execution_simulation_attestation_info.data.borrow_mut()[..],
+            &execution_simulation_attestation_data,
+        )?;
+    }
+
+    Ok(())
+}
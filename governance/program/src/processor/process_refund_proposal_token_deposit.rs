@@ -0,0 +1,97 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            proposal::get_proposal_data,
+            proposal_token_deposit::{
+                get_proposal_token_deposit_address_seeds,
+                get_proposal_token_deposit_data_for_proposal,
+            },
+        },
+        tools::spl_token::{
+            close_spl_token_account_signed, get_spl_token_amount, get_spl_token_owner,
+            transfer_spl_tokens_signed,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+    spl_governance_tools::account::dispose_account,
+};
+
+/// Processes RefundProposalTokenDeposit instruction
+pub fn process_refund_proposal_token_deposit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_info = next_account_info(account_info_iter)?; // 0
+
+    let proposal_token_deposit_info = next_account_info(account_info_iter)?; // 1
+    let proposal_token_deposit_escrow_info = next_account_info(account_info_iter)?; // 2
+    let destination_info = next_account_info(account_info_iter)?; // 3
+    let proposal_deposit_payer_info = next_account_info(account_info_iter)?; // 4
+    let spl_token_info = next_account_info(account_info_iter)?; // 5
+
+    let proposal_data = get_proposal_data(program_id, proposal_info)?;
+
+    proposal_data.assert_can_refund_proposal_deposit()?;
+
+    let proposal_token_deposit_data = get_proposal_token_deposit_data_for_proposal(
+        program_id,
+        proposal_token_deposit_info,
+        proposal_info.key,
+    )?;
+
+    if proposal_token_deposit_data.deposit_payer != *proposal_deposit_payer_info.key {
+        return Err(GovernanceError::InvalidDepositPayerForProposalDeposit.into());
+    }
+
+    // The deposit of a Proposal defeated below the participation floor is
+    // forfeited to the Governance, otherwise it's refunded to the depositor
+    let destination_owner = if proposal_data.is_defeated_below_participation_floor(
+        proposal_token_deposit_data.participation_floor_percentage,
+    ) {
+        proposal_data.governance
+    } else {
+        proposal_token_deposit_data.depositor
+    };
+
+    if get_spl_token_owner(destination_info)? != destination_owner {
+        return Err(GovernanceError::InvalidDestinationForProposalTokenDeposit.into());
+    }
+
+    // Release the whole escrow balance to ensure the escrow account can be closed
+    let escrow_amount = get_spl_token_amount(proposal_token_deposit_escrow_info)?;
+
+    let proposal_token_deposit_address_seeds =
+        get_proposal_token_deposit_address_seeds(proposal_info.key);
+
+    transfer_spl_tokens_signed(
+        proposal_token_deposit_escrow_info,
+        destination_info,
+        proposal_token_deposit_info,
+        &proposal_token_deposit_address_seeds,
+        program_id,
+        escrow_amount,
+        spl_token_info,
+    )?;
+
+    close_spl_token_account_signed(
+        proposal_token_deposit_escrow_info,
+        proposal_deposit_payer_info,
+        proposal_token_deposit_info,
+        &proposal_token_deposit_address_seeds,
+        program_id,
+        spl_token_info,
+    )?;
+
+    dispose_account(proposal_token_deposit_info, proposal_deposit_payer_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            council_election::{
+                get_council_candidate_address_seeds, get_council_election_data, CouncilCandidate,
+            },
+            enums::GovernanceAccountType,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes RegisterCouncilCandidate instruction
+pub fn process_register_council_candidate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let council_election_info = next_account_info(account_info_iter)?; // 0
+    let council_candidate_info = next_account_info(account_info_iter)?; // 1
+    let candidate_info = next_account_info(account_info_iter)?; // 2
+    let payer_info = next_account_info(account_info_iter)?; // 3
+    let system_info = next_account_info(account_info_iter)?; // 4
+
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+
+    if !candidate_info.is_signer {
+        return Err(GovernanceError::CandidateMustSign.into());
+    }
+
+    let mut council_election_data = get_council_election_data(program_id, council_election_info)?;
+    council_election_data.assert_is_voting(clock.unix_timestamp)?;
+
+    let council_candidate_data = CouncilCandidate {
+        account_type: GovernanceAccountType::CouncilCandidate,
+        council_election: *council_election_info.key,
+        candidate: *candidate_info.key,
+        candidate_index: council_election_data.candidates_count,
+        votes: 0,
+        is_elected: false,
+        seat_claimed: false,
+        reserved: [0; 32],
+    };
+
+    create_and_serialize_account_signed::<CouncilCandidate>(
+        payer_info,
+        council_candidate_info,
+        &council_candidate_data,
+        &get_council_candidate_address_seeds(council_election_info.key, candidate_info.key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    council_election_data.candidates_count = council_election_data
+        .candidates_count
+        .checked_add(1)
+        .unwrap();
+    borsh::to_writer(
+        &mut council_election_info.data.borrow_mut()[..],
+        &council_election_data,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::GovernanceAccountType,
+            funding_round::{
+                get_funding_project_address_seeds, get_funding_round_data, FundingProject,
+            },
+        },
+        tools::spl_token::get_spl_token_mint,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes RegisterFundingProject instruction
+pub fn process_register_funding_project(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let funding_round_info = next_account_info(account_info_iter)?; // 0
+    let funding_project_info = next_account_info(account_info_iter)?; // 1
+    let project_owner_info = next_account_info(account_info_iter)?; // 2
+    let recipient_info = next_account_info(account_info_iter)?; // 3
+    let payer_info = next_account_info(account_info_iter)?; // 4
+    let system_info = next_account_info(account_info_iter)?; // 5
+
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+
+    if !project_owner_info.is_signer {
+        return Err(GovernanceError::ProjectOwnerMustSign.into());
+    }
+
+    let mut funding_round_data = get_funding_round_data(program_id, funding_round_info)?;
+    funding_round_data.assert_is_accepting_contributions(clock.unix_timestamp)?;
+
+    if get_spl_token_mint(recipient_info)? != funding_round_data.mint {
+        return Err(GovernanceError::InvalidFundingProjectRecipient.into());
+    }
+
+    let funding_project_data = FundingProject {
+        account_type: GovernanceAccountType::FundingProject,
+        funding_round: *funding_round_info.key,
+        project_owner: *project_owner_info.key,
+        recipient: *recipient_info.key,
+        contributions_amount: 0,
+        capped_contributions_amount: 0,
+        contributors_count: 0,
+        contributions_sqrt_sum: 0,
+        matching_amount: 0,
+        matching_claimed: false,
+        reserved: [0; 32],
+    };
+
+    create_and_serialize_account_signed::<FundingProject>(
+        payer_info,
+        funding_project_info,
+        &funding_project_data,
+        &get_funding_project_address_seeds(funding_round_info.key, project_owner_info.key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    funding_round_data.projects_count = funding_round_data.projects_count.checked_add(1).unwrap();
+    borsh::to_writer(
+        &mut funding_round_info.data.borrow_mut()[..],
+        &funding_round_data,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::GovernanceAccountType,
+            proposal::get_proposal_data,
+            token_owner_record::get_token_owner_record_data,
+            vote_intent::{
+                get_ed25519_signature, get_vote_hash, get_vote_intent_address_seeds,
+                get_vote_intent_message, VoteIntent,
+            },
+            vote_record::Vote,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::{
+            instructions::{load_current_index_checked, load_instruction_at_checked},
+            Sysvar,
+        },
+    },
+    spl_governance_tools::account::create_and_serialize_account_signed,
+};
+
+/// Processes RegisterVoteIntent instruction
+pub fn process_register_vote_intent(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    vote: Vote,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_info = next_account_info(account_info_iter)?; // 0
+    let token_owner_record_info = next_account_info(account_info_iter)?; // 1
+    let vote_intent_info = next_account_info(account_info_iter)?; // 2
+    let instructions_info = next_account_info(account_info_iter)?; // 3
+    let payer_info = next_account_info(account_info_iter)?; // 4
+    let system_info = next_account_info(account_info_iter)?; // 5
+
+    let rent = Rent::get()?;
+
+    // Once registered the intent can't be replaced with an older signed intent
+    if !vote_intent_info.data_is_empty() {
+        return Err(GovernanceError::VoteIntentAlreadyExists.into());
+    }
+
+    // Assert the vote intent is for a Proposal
+    get_proposal_data(program_id, proposal_info)?;
+
+    let token_owner_record_data = get_token_owner_record_data(program_id, token_owner_record_info)?;
+
+    // The signature must be verified by the ed25519 instruction preceding the
+    // current one
+    let current_index = load_current_index_checked(instructions_info)?;
+    let ed25519_index = current_index
+        .checked_sub(1)
+        .ok_or(GovernanceError::InvalidVoteIntentSignature)?;
+    let ed25519_instruction =
+        load_instruction_at_checked(ed25519_index as usize, instructions_info)?;
+    let ed25519_signature = get_ed25519_signature(&ed25519_instruction)?;
+
+    if ed25519_signature.message
+        != get_vote_intent_message(
+            program_id,
+            proposal_info.key,
+            token_owner_record_info.key,
+            &vote,
+        )
+    {
+        return Err(GovernanceError::InvalidVoteIntentSignature.into());
+    }
+
+    if ed25519_signature.public_key != token_owner_record_data.governing_token_owner
+        && Some(ed25519_signature.public_key) != token_owner_record_data.governance_delegate
+    {
+        return Err(GovernanceError::GoverningTokenOwnerOrDelegateMustSign.into());
+    }
+
+    let vote_intent_data = VoteIntent {
+        account_type: GovernanceAccountType::VoteIntent,
+        proposal: *proposal_info.key,
+        token_owner_record: *token_owner_record_info.key,
+        governance_authority: ed25519_signature.public_key,
+        vote_hash: get_vote_hash(&vote).to_bytes(),
+        signature: ed25519_signature.signature,
+        reserved: [0; 32],
+    };
+
+    create_and_serialize_account_signed::<VoteIntent>(
+        payer_info,
+        vote_intent_info,
+        &vote_intent_data,
+        &get_vote_intent_address_seeds(proposal_info.key, token_owner_record_info.key),
+        program_id,
+        system_info,
+        &rent,
+        0,
+    )?;
+
+    Ok(())
+}
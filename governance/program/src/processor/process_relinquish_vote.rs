@@ -10,6 +10,7 @@ use {
             realm::get_realm_data_for_governing_token_mint,
             token_owner_record::get_token_owner_record_data_for_realm_and_governing_mint,
             vote_record::{get_vote_record_data_for_proposal_and_token_owner_record, Vote},
+            vote_session_key::get_vote_session_key_data_for_token_owner_record,
         },
     },
     solana_program::{
@@ -80,8 +81,31 @@ pub fn process_relinquish_vote(program_id: &Pubkey, accounts: &[AccountInfo]) ->
         // Note: It's only required to sign by governing_authority if relinquishing the
         // vote results in vote change If the Proposal is already decided then
         // anybody can prune active votes for token owner
-        token_owner_record_data
-            .assert_token_owner_or_delegate_is_signer(governance_authority_info)?;
+        //
+        // The governance authority can also be a session key authorized by
+        // VoteSessionKey
+        if governance_authority_info.is_signer
+            && !token_owner_record_data.is_token_owner_or_delegate(governance_authority_info.key)
+        {
+            let vote_session_key_info = account_info_iter
+                .next()
+                .ok_or(GovernanceError::GoverningTokenOwnerOrDelegateMustSign)?; // 7
+
+            let vote_session_key_data = get_vote_session_key_data_for_token_owner_record(
+                program_id,
+                vote_session_key_info,
+                token_owner_record_info.key,
+            )?;
+            vote_session_key_data.assert_can_vote(governance_authority_info.key, clock.slot)?;
+
+            if !token_owner_record_data.is_token_owner_or_delegate(&vote_session_key_data.authority)
+            {
+                return Err(GovernanceError::GoverningTokenOwnerOrDelegateMustSign.into());
+            }
+        } else {
+            token_owner_record_data
+                .assert_token_owner_or_delegate_is_signer(governance_authority_info)?;
+        }
 
         match vote_record_data.vote {
             Vote::Approve(choices) => {
@@ -112,6 +136,8 @@ pub fn process_relinquish_vote(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             }
         }
 
+        proposal_data.relinquish_vote_participation(vote_record_data.voter_number);
+
         proposal_data.serialize(&mut proposal_info.data.borrow_mut()[..])?;
 
         dispose_account(vote_record_info, beneficiary_info)?;
@@ -0,0 +1,74 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            enums::{ProposalLinkState, ProposalState},
+            proposal::get_proposal_data,
+            proposal_link::get_proposal_link_data,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+/// Processes ResolveProposalLink instruction
+pub fn process_resolve_proposal_link(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_link_info = next_account_info(account_info_iter)?; // 0
+
+    let clock = Clock::get()?;
+
+    let mut proposal_link_data = get_proposal_link_data(program_id, proposal_link_info)?;
+
+    if proposal_link_data.state != ProposalLinkState::Pending {
+        return Err(GovernanceError::InvalidStateCannotResolveProposalLink.into());
+    }
+
+    // All linked Proposals must be provided in the order they were linked
+    let mut outcomes = vec![];
+    for proposal in proposal_link_data.proposals.iter() {
+        let proposal_info = next_account_info(account_info_iter)?; // 1..N
+        if proposal_info.key != proposal {
+            return Err(GovernanceError::InvalidProposalForProposalLink.into());
+        }
+
+        let proposal_data = get_proposal_data(program_id, proposal_info)?;
+        outcomes.push(get_link_vote_outcome(&proposal_data.state));
+    }
+
+    proposal_link_data.state = proposal_link_data
+        .resolve_state(&outcomes)
+        .ok_or(GovernanceError::InvalidStateCannotResolveProposalLink)?;
+    proposal_link_data.resolved_at = Some(clock.unix_timestamp);
+
+    borsh::to_writer(
+        &mut proposal_link_info.data.borrow_mut()[..],
+        &proposal_link_data,
+    )?;
+
+    Ok(())
+}
+
+/// Returns whether the Proposal succeeded, was rejected or None if its
+/// outcome is not known yet
+fn get_link_vote_outcome(proposal_state: &ProposalState) -> Option<bool> {
+    match proposal_state {
+        ProposalState::Succeeded
+        | ProposalState::Executing
+        | ProposalState::ExecutingWithErrors
+        | ProposalState::Completed => Some(true),
+        ProposalState::Defeated | ProposalState::Vetoed | ProposalState::Cancelled => Some(false),
+        ProposalState::Draft | ProposalState::SigningOff | ProposalState::Voting => None,
+    }
+}
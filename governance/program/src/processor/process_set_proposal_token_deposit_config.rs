@@ -0,0 +1,52 @@
+//! Program state processor
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{
+            governance::{get_governance_data, is_governance_v2_account_type},
+            proposal_token_deposit::{
+                assert_is_valid_proposal_token_deposit_config, ProposalTokenDepositConfig,
+            },
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+    spl_governance_tools::error::GovernanceToolsError,
+};
+
+/// Processes SetProposalTokenDepositConfig instruction
+pub fn process_set_proposal_token_deposit_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    config: ProposalTokenDepositConfig,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let governance_info = next_account_info(account_info_iter)?; // 0
+
+    // Only governance PDA via a proposal can authorize change to its own config
+    if !governance_info.is_signer {
+        return Err(GovernanceError::GovernancePdaMustSign.into());
+    };
+
+    assert_is_valid_proposal_token_deposit_config(&config)?;
+
+    let mut governance_data = get_governance_data(program_id, governance_info)?;
+
+    // The config is stored in the extended GovernanceV2 layout
+    // Note: GovernanceV1 accounts are migrated to GovernanceV2 when a Proposal is
+    // created and hence any Governance executing this instruction is already V2
+    if !is_governance_v2_account_type(&governance_data.account_type) {
+        return Err(GovernanceToolsError::InvalidAccountType.into());
+    }
+
+    governance_data.proposal_token_deposit_config = config;
+
+    governance_data.serialize(&mut governance_info.data.borrow_mut()[..])?;
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use {
     crate::{
         error::GovernanceError,
         state::{
+            addin_registry::get_addin_registry_data_for_realm,
             realm::{
                 assert_valid_realm_config_args, get_realm_data_for_authority, RealmConfigArgs,
             },
@@ -94,10 +95,30 @@ pub fn process_set_realm_config(
         Some(realm_config_data.council_token_config.clone()),
     )?;
 
+    let payer_info = next_account_info(account_info_iter)?; // 10
+
+    if realm_config_data.use_addin_registry {
+        let addin_registry_info = next_account_info(account_info_iter)?; // 11
+        let addin_registry_data =
+            get_addin_registry_data_for_realm(program_id, addin_registry_info, realm_info.key)?;
+
+        // 12+
+        for addin in [
+            community_token_config.voter_weight_addin,
+            community_token_config.max_voter_weight_addin,
+            council_token_config.voter_weight_addin,
+            council_token_config.max_voter_weight_addin,
+        ]
+        .iter()
+        .flatten()
+        {
+            let addin_program_data_info = next_account_info(account_info_iter)?;
+            addin_registry_data.assert_addin_is_approved(addin, addin_program_data_info)?;
+        }
+    }
+
     realm_config_data.community_token_config = community_token_config;
     realm_config_data.council_token_config = council_token_config;
-
-    let payer_info = next_account_info(account_info_iter)?; // 10
     let rent = Rent::get()?;
 
     realm_config_data.serialize(
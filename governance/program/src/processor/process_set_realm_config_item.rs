@@ -4,6 +4,7 @@ use {
     crate::{
         error::GovernanceError,
         state::{
+            addin_registry::{get_addin_registry_data_for_realm, AddinRegistryEntry},
             realm::{get_realm_data_for_authority, SetRealmConfigItemArgs},
             realm_config::get_realm_config_data_for_realm,
         },
@@ -76,6 +77,57 @@ pub fn process_set_realm_config_item(
                 }
             }
         }
+        SetRealmConfigItemArgs::VoteExtension { config } => {
+            config.assert_is_valid()?;
+
+            realm_config_data.vote_extension = config;
+        }
+        SetRealmConfigItemArgs::AddinRegistryEntry {
+            action,
+            program_id: addin,
+            min_version,
+        } => {
+            let addin_registry_info = next_account_info(account_info_iter)?; // 5
+
+            let mut addin_registry_data =
+                get_addin_registry_data_for_realm(program_id, addin_registry_info, realm_info.key)?;
+
+            match action {
+                SetConfigItemActionType::Add => {
+                    if addin_registry_data.get_entry(&addin).is_some() {
+                        return Err(GovernanceError::AddinRegistryEntryAlreadyExists.into());
+                    }
+
+                    addin_registry_data.entries.push(AddinRegistryEntry {
+                        program_id: addin,
+                        min_version,
+                    });
+                }
+                SetConfigItemActionType::Remove => {
+                    if let Some(entry_index) = addin_registry_data
+                        .entries
+                        .iter()
+                        .position(|entry| entry.program_id == addin)
+                    {
+                        addin_registry_data.entries.remove(entry_index);
+                    } else {
+                        return Err(GovernanceError::AddinRegistryEntryNotFound.into());
+                    }
+                }
+            }
+
+            addin_registry_data.serialize(
+                program_id,
+                addin_registry_info,
+                payer_info,
+                system_info,
+                &rent,
+            )?;
+
+            // Once used the registry can't be disabled and removing all its entries
+            // rejects all addins
+            realm_config_data.use_addin_registry = true;
+        }
     }
 
     realm_config_data.serialize(
@@ -0,0 +1,310 @@
+//! AddinRegistry account
+
+use {
+    crate::{
+        error::GovernanceError, state::enums::GovernanceAccountType,
+        tools::bpf_loader_upgradeable::get_program_data_address,
+    },
+    bincode::deserialize,
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::Slot,
+        program_error::ProgramError,
+        program_pack::IsInitialized,
+        pubkey::Pubkey,
+        rent::Rent,
+    },
+    spl_governance_tools::account::{
+        create_and_serialize_account_signed, extend_account_size, get_account_data, AccountMaxSize,
+    },
+};
+
+/// Addin program approved by the Realm in its AddinRegistry
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct AddinRegistryEntry {
+    /// The approved addin program
+    pub program_id: Pubkey,
+
+    /// The min version of the addin program
+    /// The version of a program deployed with the upgradeable loader is the
+    /// slot it was last deployed at, as recorded in its ProgramData account
+    /// 0 accepts any version, including programs which are not upgradeable
+    pub min_version: Slot,
+}
+
+/// AddinRegistry account
+/// The account pins the voter weight and max voter weight addins which can be
+/// configured for the Realm
+/// Once the registry is used by the Realm every addin set with SetRealmConfig
+/// must have an entry in the registry and be deployed at the entry min version
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct AddinRegistry {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The Realm the registry belongs to
+    pub realm: Pubkey,
+
+    /// Reserved
+    pub reserved: [u8; 64],
+
+    /// The approved addins
+    pub entries: Vec<AddinRegistryEntry>,
+}
+
+impl AccountMaxSize for AddinRegistry {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 64 + 4 + self.entries.len() * 40)
+    }
+}
+
+impl IsInitialized for AddinRegistry {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::AddinRegistry
+    }
+}
+
+impl AddinRegistry {
+    /// Returns the registry entry of the given addin program
+    pub fn get_entry(&self, program_id: &Pubkey) -> Option<&AddinRegistryEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.program_id == *program_id)
+    }
+
+    /// Asserts the given addin program has an entry in the registry and is
+    /// deployed at the entry min version
+    pub fn assert_addin_is_approved(
+        &self,
+        addin: &Pubkey,
+        addin_program_data_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        let entry = self
+            .get_entry(addin)
+            .ok_or(GovernanceError::AddinNotInAddinRegistry)?;
+
+        if get_program_data_address(addin) != *addin_program_data_info.key {
+            return Err(GovernanceError::InvalidProgramDataAccountAddress.into());
+        }
+
+        if entry.min_version == 0 {
+            return Ok(());
+        }
+
+        if addin_program_data_info.owner != &bpf_loader_upgradeable::id() {
+            return Err(GovernanceError::AddinVersionBelowRegistryMinVersion.into());
+        }
+
+        let version = if let UpgradeableLoaderState::ProgramData {
+            slot,
+            upgrade_authority_address: _,
+        } = deserialize(&addin_program_data_info.data.borrow())
+            .map_err(|_| GovernanceError::InvalidProgramDataAccountData)?
+        {
+            slot
+        } else {
+            return Err(GovernanceError::InvalidProgramDataAccountData.into());
+        };
+
+        if version < entry.min_version {
+            return Err(GovernanceError::AddinVersionBelowRegistryMinVersion.into());
+        }
+
+        Ok(())
+    }
+
+    /// Serializes AddinRegistry and resizes it if required
+    /// If the account doesn't exist then it's created
+    pub fn serialize<'a>(
+        self,
+        program_id: &Pubkey,
+        addin_registry_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_info: &AccountInfo<'a>,
+        rent: &Rent,
+    ) -> Result<(), ProgramError> {
+        if addin_registry_info.data_is_empty() {
+            create_and_serialize_account_signed::<AddinRegistry>(
+                payer_info,
+                addin_registry_info,
+                &self,
+                &get_addin_registry_address_seeds(&self.realm),
+                program_id,
+                system_info,
+                rent,
+                0,
+            )?;
+        } else {
+            let addin_registry_max_size = self.get_max_size().unwrap();
+            if addin_registry_info.data_len() < addin_registry_max_size {
+                extend_account_size(
+                    addin_registry_info,
+                    payer_info,
+                    addin_registry_max_size,
+                    rent,
+                    system_info,
+                )?;
+            }
+
+            borsh::to_writer(&mut addin_registry_info.data.borrow_mut()[..], &self)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns AddinRegistry PDA seeds
+pub fn get_addin_registry_address_seeds(realm: &Pubkey) -> [&[u8]; 2] {
+    [b"addin-registry", realm.as_ref()]
+}
+
+/// Returns AddinRegistry PDA address
+pub fn get_addin_registry_address(program_id: &Pubkey, realm: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_addin_registry_address_seeds(realm), program_id).0
+}
+
+/// Deserializes AddinRegistry account and checks owner program
+pub fn get_addin_registry_data(
+    program_id: &Pubkey,
+    addin_registry_info: &AccountInfo,
+) -> Result<AddinRegistry, ProgramError> {
+    get_account_data::<AddinRegistry>(program_id, addin_registry_info)
+}
+
+/// If the account exists then deserializes it into AddinRegistry struct and
+/// checks the owner program and the Realm it belongs to
+/// If the account doesn't exist then it checks its address is derived from the
+/// given owner program and Realm and returns an empty AddinRegistry
+pub fn get_addin_registry_data_for_realm(
+    program_id: &Pubkey,
+    addin_registry_info: &AccountInfo,
+    realm: &Pubkey,
+) -> Result<AddinRegistry, ProgramError> {
+    if addin_registry_info.data_is_empty() {
+        if get_addin_registry_address(program_id, realm) != *addin_registry_info.key {
+            return Err(GovernanceError::InvalidAddinRegistryAddress.into());
+        }
+
+        return Ok(AddinRegistry {
+            account_type: GovernanceAccountType::AddinRegistry,
+            realm: *realm,
+            reserved: [0; 64],
+            entries: vec![],
+        });
+    }
+
+    let addin_registry_data = get_addin_registry_data(program_id, addin_registry_info)?;
+
+    if addin_registry_data.realm != *realm {
+        return Err(GovernanceError::InvalidAddinRegistryForRealm.into());
+    }
+
+    Ok(addin_registry_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_addin_registry() -> AddinRegistry {
+        AddinRegistry {
+            account_type: GovernanceAccountType::AddinRegistry,
+            realm: Pubkey::new_unique(),
+            reserved: [0; 64],
+            entries: vec![
+                AddinRegistryEntry {
+                    program_id: Pubkey::new_unique(),
+                    min_version: 0,
+                },
+                AddinRegistryEntry {
+                    program_id: Pubkey::new_unique(),
+                    min_version: 100,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_max_size() {
+        let addin_registry = create_test_addin_registry();
+
+        let size = borsh::to_vec(&addin_registry).unwrap().len();
+
+        assert_eq!(addin_registry.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_assert_addin_is_approved() {
+        // Arrange
+        let addin_registry = create_test_addin_registry();
+        let any_version_addin = addin_registry.entries[0].program_id;
+        let min_version_addin = addin_registry.entries[1].program_id;
+
+        let program_data_for = |addin: &Pubkey, slot: Slot| {
+            (
+                get_program_data_address(addin),
+                bincode::serialize(&UpgradeableLoaderState::ProgramData {
+                    slot,
+                    upgrade_authority_address: None,
+                })
+                .unwrap(),
+            )
+        };
+
+        let owner = Pubkey::default();
+        let mut lamports = 0;
+        let (any_version_key, mut any_version_data) = program_data_for(&any_version_addin, 0);
+        let any_version_info = AccountInfo::new(
+            &any_version_key,
+            false,
+            false,
+            &mut lamports,
+            &mut any_version_data,
+            &owner,
+            false,
+            0,
+        );
+
+        // Act + Assert
+        assert_eq!(
+            addin_registry.assert_addin_is_approved(&any_version_addin, &any_version_info),
+            Ok(())
+        );
+        assert_eq!(
+            addin_registry.assert_addin_is_approved(&Pubkey::new_unique(), &any_version_info),
+            Err(GovernanceError::AddinNotInAddinRegistry.into())
+        );
+        assert_eq!(
+            addin_registry.assert_addin_is_approved(&min_version_addin, &any_version_info),
+            Err(GovernanceError::InvalidProgramDataAccountAddress.into())
+        );
+
+        for (slot, expected_result) in [
+            (
+                99,
+                Err(GovernanceError::AddinVersionBelowRegistryMinVersion.into()),
+            ),
+            (100, Ok(())),
+        ] {
+            let mut lamports = 0;
+            let (key, mut data) = program_data_for(&min_version_addin, slot);
+            let program_data_info = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &bpf_loader_upgradeable::ID,
+                false,
+                0,
+            );
+
+            assert_eq!(
+                addin_registry.assert_addin_is_approved(&min_version_addin, &program_data_info),
+                expected_result
+            );
+        }
+    }
+}
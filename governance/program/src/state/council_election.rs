@@ -0,0 +1,520 @@
+//! Council election accounts
+
+use {
+    crate::{error::GovernanceError, state::enums::GovernanceAccountType},
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, clock::UnixTimestamp, program_error::ProgramError,
+        program_pack::IsInitialized, pubkey::Pubkey,
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// CouncilElection configuration
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct CouncilElectionConfig {
+    /// Unix timestamp after which candidates can't be registered and votes
+    /// are no longer accepted
+    pub voting_end_at: UnixTimestamp,
+
+    /// The number of council seats to elect
+    pub seats_count: u8,
+
+    /// Unix timestamp at which the term of the elected council ends and its
+    /// CouncilSeats expire
+    pub term_end_at: UnixTimestamp,
+}
+
+/// Asserts the given CouncilElectionConfig is valid
+pub fn assert_is_valid_council_election_config(
+    config: &CouncilElectionConfig,
+    current_unix_timestamp: UnixTimestamp,
+) -> Result<(), ProgramError> {
+    if config.seats_count == 0
+        || config.voting_end_at <= current_unix_timestamp
+        || config.term_end_at <= config.voting_end_at
+    {
+        return Err(GovernanceError::InvalidCouncilElectionConfig.into());
+    }
+
+    Ok(())
+}
+
+/// The state of a CouncilElection
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum CouncilElectionState {
+    /// Candidates can be registered and votes cast until voting_end_at
+    Voting,
+
+    /// The winners have been elected and can claim their CouncilSeats
+    Finalized,
+}
+
+/// CouncilElection account
+/// The account runs an election of the council of a Realm
+/// Candidates are voted for by the community token holders and the winners
+/// receive term-limited CouncilSeats instead of council tokens
+/// Each CouncilSeat gives its member council voter weight of 1 until the
+/// term ends and the seats expire without any further action
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct CouncilElection {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The Realm the council is elected for
+    pub realm: Pubkey,
+
+    /// The seed used to derive the CouncilElection address
+    pub election_seed: Pubkey,
+
+    /// CouncilElection configuration
+    pub config: CouncilElectionConfig,
+
+    /// The state of the election
+    pub state: CouncilElectionState,
+
+    /// The number of registered candidates
+    pub candidates_count: u16,
+
+    /// The number of elected candidates
+    pub elected_count: u8,
+
+    /// Reserved
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for CouncilElection {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 17 + 1 + 2 + 1 + 64)
+    }
+}
+
+impl IsInitialized for CouncilElection {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::CouncilElection
+    }
+}
+
+impl CouncilElection {
+    /// Asserts candidates can be registered and votes cast
+    pub fn assert_is_voting(
+        &self,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        if self.state != CouncilElectionState::Voting
+            || current_unix_timestamp >= self.config.voting_end_at
+        {
+            return Err(GovernanceError::CouncilElectionNotVoting.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts the election can be finalized
+    pub fn assert_can_finalize(
+        &self,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        if self.state != CouncilElectionState::Voting {
+            return Err(GovernanceError::CouncilElectionAlreadyFinalized.into());
+        }
+
+        if current_unix_timestamp < self.config.voting_end_at {
+            return Err(GovernanceError::CouncilElectionVotingNotEnded.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns CouncilElection PDA seeds
+pub fn get_council_election_address_seeds<'a>(
+    realm: &'a Pubkey,
+    election_seed: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [b"council-election", realm.as_ref(), election_seed.as_ref()]
+}
+
+/// Returns CouncilElection PDA address
+pub fn get_council_election_address(
+    program_id: &Pubkey,
+    realm: &Pubkey,
+    election_seed: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_council_election_address_seeds(realm, election_seed),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes CouncilElection account and checks owner program and account
+/// type
+pub fn get_council_election_data(
+    program_id: &Pubkey,
+    council_election_info: &AccountInfo,
+) -> Result<CouncilElection, ProgramError> {
+    get_account_data::<CouncilElection>(program_id, council_election_info)
+}
+
+/// CouncilCandidate account
+/// The account tracks the votes received by a candidate registered for a
+/// CouncilElection
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct CouncilCandidate {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The CouncilElection the candidate is registered for
+    pub council_election: Pubkey,
+
+    /// The candidate who receives the CouncilSeat when elected
+    pub candidate: Pubkey,
+
+    /// The registration order of the candidate, used to break ties
+    pub candidate_index: u16,
+
+    /// The total voter weight of the votes cast for the candidate
+    pub votes: u64,
+
+    /// Whether the candidate was elected
+    pub is_elected: bool,
+
+    /// Whether the elected candidate claimed the CouncilSeat
+    pub seat_claimed: bool,
+
+    /// Reserved
+    pub reserved: [u8; 32],
+}
+
+impl AccountMaxSize for CouncilCandidate {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 2 + 8 + 1 + 1 + 32)
+    }
+}
+
+impl IsInitialized for CouncilCandidate {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::CouncilCandidate
+    }
+}
+
+impl CouncilCandidate {
+    /// Returns true if the candidate ranks higher than the other candidate
+    /// Candidates are ranked by votes and ties are won by the candidate who
+    /// registered first
+    pub fn ranks_above(&self, other: &CouncilCandidate) -> bool {
+        self.votes > other.votes
+            || (self.votes == other.votes && self.candidate_index < other.candidate_index)
+    }
+}
+
+/// Returns CouncilCandidate PDA seeds
+pub fn get_council_candidate_address_seeds<'a>(
+    council_election: &'a Pubkey,
+    candidate: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        b"council-candidate",
+        council_election.as_ref(),
+        candidate.as_ref(),
+    ]
+}
+
+/// Returns CouncilCandidate PDA address
+pub fn get_council_candidate_address(
+    program_id: &Pubkey,
+    council_election: &Pubkey,
+    candidate: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_council_candidate_address_seeds(council_election, candidate),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes CouncilCandidate account and checks owner program and account
+/// type
+pub fn get_council_candidate_data(
+    program_id: &Pubkey,
+    council_candidate_info: &AccountInfo,
+) -> Result<CouncilCandidate, ProgramError> {
+    get_account_data::<CouncilCandidate>(program_id, council_candidate_info)
+}
+
+/// Deserializes CouncilCandidate account
+/// 1) Checks owner program and account type
+/// 2) Asserts it's registered for the given CouncilElection
+pub fn get_council_candidate_data_for_council_election(
+    program_id: &Pubkey,
+    council_candidate_info: &AccountInfo,
+    council_election: &Pubkey,
+) -> Result<CouncilCandidate, ProgramError> {
+    let council_candidate_data = get_council_candidate_data(program_id, council_candidate_info)?;
+
+    if council_candidate_data.council_election != *council_election {
+        return Err(GovernanceError::InvalidCouncilElectionForCouncilCandidate.into());
+    }
+
+    Ok(council_candidate_data)
+}
+
+/// CouncilElectionVote account
+/// The account records the vote cast in a CouncilElection by a community
+/// token holder identified by their TokenOwnerRecord
+/// Each TokenOwnerRecord can vote for a single candidate
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct CouncilElectionVote {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The CouncilElection the vote was cast in
+    pub council_election: Pubkey,
+
+    /// The TokenOwnerRecord of the voter
+    pub token_owner_record: Pubkey,
+
+    /// The CouncilCandidate voted for
+    pub council_candidate: Pubkey,
+
+    /// The voter weight of the vote
+    pub voter_weight: u64,
+
+    /// Reserved
+    pub reserved: [u8; 32],
+}
+
+impl AccountMaxSize for CouncilElectionVote {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 32 + 8 + 32)
+    }
+}
+
+impl IsInitialized for CouncilElectionVote {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::CouncilElectionVote
+    }
+}
+
+/// Returns CouncilElectionVote PDA seeds
+pub fn get_council_election_vote_address_seeds<'a>(
+    council_election: &'a Pubkey,
+    token_owner_record: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        b"council-election-vote",
+        council_election.as_ref(),
+        token_owner_record.as_ref(),
+    ]
+}
+
+/// Returns CouncilElectionVote PDA address
+pub fn get_council_election_vote_address(
+    program_id: &Pubkey,
+    council_election: &Pubkey,
+    token_owner_record: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_council_election_vote_address_seeds(council_election, token_owner_record),
+        program_id,
+    )
+    .0
+}
+
+/// CouncilSeat account
+/// The account is the council membership of an elected candidate
+/// It gives its member council voter weight of 1 while the term of the seat
+/// is the current council term of the Realm and it hasn't ended yet
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct CouncilSeat {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The Realm the seat belongs to
+    pub realm: Pubkey,
+
+    /// The CouncilElection the seat was won in
+    pub council_election: Pubkey,
+
+    /// The council member holding the seat
+    pub member: Pubkey,
+
+    /// Unix timestamp at which the seat expires
+    pub term_end_at: UnixTimestamp,
+
+    /// Reserved
+    pub reserved: [u8; 32],
+}
+
+impl AccountMaxSize for CouncilSeat {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 32 + 8 + 32)
+    }
+}
+
+impl IsInitialized for CouncilSeat {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::CouncilSeat
+    }
+}
+
+impl CouncilSeat {
+    /// Returns true if the seat is part of the given council term and the
+    /// term hasn't ended yet
+    pub fn is_active(
+        &self,
+        council_term_end_at: UnixTimestamp,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> bool {
+        self.term_end_at == council_term_end_at && current_unix_timestamp < self.term_end_at
+    }
+}
+
+/// Returns CouncilSeat PDA seeds
+pub fn get_council_seat_address_seeds<'a>(
+    council_election: &'a Pubkey,
+    member: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [b"council-seat", council_election.as_ref(), member.as_ref()]
+}
+
+/// Returns CouncilSeat PDA address
+pub fn get_council_seat_address(
+    program_id: &Pubkey,
+    council_election: &Pubkey,
+    member: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_council_seat_address_seeds(council_election, member),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes CouncilSeat account
+/// 1) Checks owner program and account type
+/// 2) Asserts it belongs to the given Realm and member
+pub fn get_council_seat_data_for_realm_and_member(
+    program_id: &Pubkey,
+    council_seat_info: &AccountInfo,
+    realm: &Pubkey,
+    member: &Pubkey,
+) -> Result<CouncilSeat, ProgramError> {
+    let council_seat_data = get_account_data::<CouncilSeat>(program_id, council_seat_info)?;
+
+    if council_seat_data.realm != *realm || council_seat_data.member != *member {
+        return Err(GovernanceError::InvalidCouncilSeat.into());
+    }
+
+    Ok(council_seat_data)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn create_test_council_candidate(candidate_index: u16, votes: u64) -> CouncilCandidate {
+        CouncilCandidate {
+            account_type: GovernanceAccountType::CouncilCandidate,
+            council_election: Pubkey::new_unique(),
+            candidate: Pubkey::new_unique(),
+            candidate_index,
+            votes,
+            is_elected: false,
+            seat_claimed: false,
+            reserved: [0; 32],
+        }
+    }
+
+    #[test]
+    fn test_max_size() {
+        // Arrange
+        let council_election_data = CouncilElection {
+            account_type: GovernanceAccountType::CouncilElection,
+            realm: Pubkey::new_unique(),
+            election_seed: Pubkey::new_unique(),
+            config: CouncilElectionConfig {
+                voting_end_at: 100,
+                seats_count: 5,
+                term_end_at: 200,
+            },
+            state: CouncilElectionState::Voting,
+            candidates_count: 10,
+            elected_count: 0,
+            reserved: [0; 64],
+        };
+        let council_candidate_data = create_test_council_candidate(1, 10);
+        let council_election_vote_data = CouncilElectionVote {
+            account_type: GovernanceAccountType::CouncilElectionVote,
+            council_election: Pubkey::new_unique(),
+            token_owner_record: Pubkey::new_unique(),
+            council_candidate: Pubkey::new_unique(),
+            voter_weight: 10,
+            reserved: [0; 32],
+        };
+        let council_seat_data = CouncilSeat {
+            account_type: GovernanceAccountType::CouncilSeat,
+            realm: Pubkey::new_unique(),
+            council_election: Pubkey::new_unique(),
+            member: Pubkey::new_unique(),
+            term_end_at: 200,
+            reserved: [0; 32],
+        };
+
+        // Act
+        let council_election_size = borsh::to_vec(&council_election_data).unwrap().len();
+        let council_candidate_size = borsh::to_vec(&council_candidate_data).unwrap().len();
+        let council_election_vote_size = borsh::to_vec(&council_election_vote_data).unwrap().len();
+        let council_seat_size = borsh::to_vec(&council_seat_data).unwrap().len();
+
+        // Assert
+        assert_eq!(
+            council_election_data.get_max_size(),
+            Some(council_election_size)
+        );
+        assert_eq!(
+            council_candidate_data.get_max_size(),
+            Some(council_candidate_size)
+        );
+        assert_eq!(
+            council_election_vote_data.get_max_size(),
+            Some(council_election_vote_size)
+        );
+        assert_eq!(council_seat_data.get_max_size(), Some(council_seat_size));
+    }
+
+    #[test]
+    fn test_candidate_ranking_breaks_ties_by_registration_order() {
+        // Arrange
+        let first_candidate = create_test_council_candidate(0, 10);
+        let second_candidate = create_test_council_candidate(1, 10);
+        let third_candidate = create_test_council_candidate(2, 20);
+
+        // Act + Assert
+        assert!(first_candidate.ranks_above(&second_candidate));
+        assert!(!second_candidate.ranks_above(&first_candidate));
+        assert!(third_candidate.ranks_above(&first_candidate));
+    }
+
+    #[test]
+    fn test_council_seat_expires_at_term_end() {
+        // Arrange
+        let council_seat_data = CouncilSeat {
+            account_type: GovernanceAccountType::CouncilSeat,
+            realm: Pubkey::new_unique(),
+            council_election: Pubkey::new_unique(),
+            member: Pubkey::new_unique(),
+            term_end_at: 200,
+            reserved: [0; 32],
+        };
+
+        // Act + Assert
+        assert!(council_seat_data.is_active(200, 199));
+        assert!(!council_seat_data.is_active(200, 200));
+
+        // Seats of a replaced council term are no longer active
+        assert!(!council_seat_data.is_active(300, 199));
+    }
+}
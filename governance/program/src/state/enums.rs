@@ -102,6 +102,55 @@ pub enum GovernanceAccountType {
 
     /// Required signatory account
     RequiredSignatory,
+
+    /// Proposal link account coordinating execution of Proposals from
+    /// different Governances
+    ProposalLink,
+
+    /// Proposal token deposit account holding community tokens in escrow
+    ProposalTokenDeposit,
+
+    /// Proposal archive account attesting to an archived Proposal and its
+    /// VoteRecords
+    ProposalArchive,
+
+    /// Execution simulation attestation account posted by a simulation oracle
+    /// for a ProposalTransaction
+    ExecutionSimulationAttestation,
+
+    /// Vote intent account signed off-chain by a voter and relayed by CastVote
+    VoteIntent,
+
+    /// Quadratic funding round account of a Governance
+    FundingRound,
+
+    /// Project registered for a FundingRound
+    FundingProject,
+
+    /// Contribution made to a FundingProject by a member of the Realm
+    FundingContribution,
+
+    /// Council election account of a Realm
+    CouncilElection,
+
+    /// Candidate registered for a CouncilElection
+    CouncilCandidate,
+
+    /// Vote cast in a CouncilElection by a community token holder
+    CouncilElectionVote,
+
+    /// Term-limited council membership won in a CouncilElection
+    CouncilSeat,
+
+    /// Ephemeral key authorized to vote on behalf of a TokenOwnerRecord
+    VoteSessionKey,
+
+    /// Proposal dependency account recording that a Proposal can only be
+    /// executed after another Proposal of the same Realm completed
+    ProposalDependency,
+
+    /// Addin registry account pinning the addins approved by a Realm
+    AddinRegistry,
 }
 
 /// What state a Proposal is in
@@ -204,6 +253,21 @@ pub enum VoteTipping {
     Disabled,
 }
 
+/// What state a ProposalLink is in
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum ProposalLinkState {
+    /// Pending - The linked Proposals haven't been decided yet
+    Pending,
+
+    /// Committed - At least threshold linked Proposals succeeded and the
+    /// succeeded Proposals can be executed
+    Committed,
+
+    /// Aborted - Too many of the linked Proposals didn't succeed for the
+    /// threshold to be reached and none of them can be executed
+    Aborted,
+}
+
 /// The status of instruction execution
 #[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum TransactionExecutionStatus {
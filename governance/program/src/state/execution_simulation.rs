@@ -0,0 +1,266 @@
+//! Execution simulation attestation account
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{enums::GovernanceAccountType, proposal_transaction::InstructionData},
+    },
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo,
+        clock::Slot,
+        hash::{hashv, Hash},
+        program_error::ProgramError,
+        program_pack::IsInitialized,
+        pubkey::Pubkey,
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// Execution simulation configuration of a Governance
+/// When the simulation oracle is set then ExecuteTransaction requires a
+/// successful ExecutionSimulationAttestation posted by the oracle for the
+/// executed ProposalTransaction
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ExecutionSimulationConfig {
+    /// The key which posts ExecutionSimulationAttestation accounts
+    /// Pubkey::default() means no attestation is required
+    pub simulation_oracle: Pubkey,
+
+    /// The maximum number of slots between the attestation and the execution
+    /// of the ProposalTransaction
+    pub max_attestation_age_slots: u64,
+}
+
+impl ExecutionSimulationConfig {
+    /// Returns true if ExecuteTransaction requires ExecutionSimulationAttestation
+    pub fn is_attestation_required(&self) -> bool {
+        self.simulation_oracle != Pubkey::default()
+    }
+}
+
+/// Asserts the given ExecutionSimulationConfig is valid
+pub fn assert_is_valid_execution_simulation_config(
+    config: &ExecutionSimulationConfig,
+) -> Result<(), ProgramError> {
+    // Zero age would only accept attestations posted in the executing slot
+    if config.is_attestation_required() && config.max_attestation_age_slots == 0 {
+        return Err(GovernanceError::InvalidExecutionSimulationConfig.into());
+    }
+
+    Ok(())
+}
+
+/// Returns the hash of ProposalTransaction instructions attested by
+/// ExecutionSimulationAttestation
+pub fn get_proposal_transaction_hash(instructions: &[InstructionData]) -> Hash {
+    hashv(&[&borsh::to_vec(instructions).unwrap()])
+}
+
+/// Execution simulation attestation account
+/// The account is posted by a simulation oracle and records the outcome of an
+/// off-chain simulation of a ProposalTransaction
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ExecutionSimulationAttestation {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The ProposalTransaction the attestation is for
+    pub proposal_transaction: Pubkey,
+
+    /// The simulation oracle which posted the attestation
+    pub simulation_oracle: Pubkey,
+
+    /// The hash of the simulated ProposalTransaction instructions
+    pub transaction_hash: [u8; 32],
+
+    /// Whether the simulation of the ProposalTransaction succeeded
+    pub success: bool,
+
+    /// The slot the attestation was posted at
+    pub slot: Slot,
+
+    /// Reserved
+    pub reserved: [u8; 32],
+}
+
+impl AccountMaxSize for ExecutionSimulationAttestation {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 32 + 1 + 8 + 32)
+    }
+}
+
+impl IsInitialized for ExecutionSimulationAttestation {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::ExecutionSimulationAttestation
+    }
+}
+
+impl ExecutionSimulationAttestation {
+    /// Asserts the attestation allows the execution of ProposalTransaction
+    /// with the given instructions at the given slot
+    pub fn assert_can_execute_transaction(
+        &self,
+        config: &ExecutionSimulationConfig,
+        instructions: &[InstructionData],
+        slot: Slot,
+    ) -> Result<(), ProgramError> {
+        if self.transaction_hash != get_proposal_transaction_hash(instructions).to_bytes() {
+            return Err(GovernanceError::InvalidExecutionSimulationAttestation.into());
+        }
+
+        if !self.success {
+            return Err(GovernanceError::ExecutionSimulationFailed.into());
+        }
+
+        if slot.saturating_sub(self.slot) > config.max_attestation_age_slots {
+            return Err(GovernanceError::ExecutionSimulationAttestationExpired.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns ExecutionSimulationAttestation PDA seeds
+pub fn get_execution_simulation_attestation_address_seeds<'a>(
+    proposal_transaction: &'a Pubkey,
+    simulation_oracle: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        b"execution-simulation-attestation",
+        proposal_transaction.as_ref(),
+        simulation_oracle.as_ref(),
+    ]
+}
+
+/// Returns ExecutionSimulationAttestation PDA address
+pub fn get_execution_simulation_attestation_address(
+    program_id: &Pubkey,
+    proposal_transaction: &Pubkey,
+    simulation_oracle: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_execution_simulation_attestation_address_seeds(
+            proposal_transaction,
+            simulation_oracle,
+        ),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes ExecutionSimulationAttestation account and checks owner
+/// program and account type
+pub fn get_execution_simulation_attestation_data(
+    program_id: &Pubkey,
+    execution_simulation_attestation_info: &AccountInfo,
+) -> Result<ExecutionSimulationAttestation, ProgramError> {
+    get_account_data::<ExecutionSimulationAttestation>(
+        program_id,
+        execution_simulation_attestation_info,
+    )
+}
+
+/// Deserializes ExecutionSimulationAttestation account and asserts it was
+/// posted by the given simulation oracle for the given ProposalTransaction
+pub fn get_execution_simulation_attestation_data_for_proposal_transaction(
+    program_id: &Pubkey,
+    execution_simulation_attestation_info: &AccountInfo,
+    proposal_transaction: &Pubkey,
+    simulation_oracle: &Pubkey,
+) -> Result<ExecutionSimulationAttestation, ProgramError> {
+    let attestation_data = get_execution_simulation_attestation_data(
+        program_id,
+        execution_simulation_attestation_info,
+    )?;
+
+    if attestation_data.proposal_transaction != *proposal_transaction
+        || attestation_data.simulation_oracle != *simulation_oracle
+    {
+        return Err(GovernanceError::InvalidExecutionSimulationAttestation.into());
+    }
+
+    Ok(attestation_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_attestation(instructions: &[InstructionData]) -> ExecutionSimulationAttestation {
+        ExecutionSimulationAttestation {
+            account_type: GovernanceAccountType::ExecutionSimulationAttestation,
+            proposal_transaction: Pubkey::new_unique(),
+            simulation_oracle: Pubkey::new_unique(),
+            transaction_hash: get_proposal_transaction_hash(instructions).to_bytes(),
+            success: true,
+            slot: 100,
+            reserved: [0; 32],
+        }
+    }
+
+    fn create_test_instructions() -> Vec<InstructionData> {
+        vec![InstructionData {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![],
+            data: vec![1, 2, 3],
+        }]
+    }
+
+    #[test]
+    fn test_max_size() {
+        let attestation = create_test_attestation(&create_test_instructions());
+
+        let size = borsh::to_vec(&attestation).unwrap().len();
+
+        assert_eq!(attestation.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_assert_can_execute_transaction() {
+        let instructions = create_test_instructions();
+        let attestation = create_test_attestation(&instructions);
+        let config = ExecutionSimulationConfig {
+            simulation_oracle: attestation.simulation_oracle,
+            max_attestation_age_slots: 10,
+        };
+
+        assert_eq!(
+            attestation.assert_can_execute_transaction(&config, &instructions, 110),
+            Ok(())
+        );
+        assert_eq!(
+            attestation.assert_can_execute_transaction(&config, &instructions, 111),
+            Err(GovernanceError::ExecutionSimulationAttestationExpired.into())
+        );
+        assert_eq!(
+            attestation.assert_can_execute_transaction(&config, &create_test_instructions(), 100),
+            Err(GovernanceError::InvalidExecutionSimulationAttestation.into())
+        );
+
+        let failed_attestation = ExecutionSimulationAttestation {
+            success: false,
+            ..attestation
+        };
+
+        assert_eq!(
+            failed_attestation.assert_can_execute_transaction(&config, &instructions, 100),
+            Err(GovernanceError::ExecutionSimulationFailed.into())
+        );
+    }
+
+    #[test]
+    fn test_assert_is_valid_execution_simulation_config() {
+        assert_eq!(
+            assert_is_valid_execution_simulation_config(&ExecutionSimulationConfig::default()),
+            Ok(())
+        );
+        assert_eq!(
+            assert_is_valid_execution_simulation_config(&ExecutionSimulationConfig {
+                simulation_oracle: Pubkey::new_unique(),
+                max_attestation_age_slots: 0,
+            }),
+            Err(GovernanceError::InvalidExecutionSimulationConfig.into())
+        );
+    }
+}
@@ -0,0 +1,593 @@
+//! Quadratic funding round accounts
+
+use {
+    crate::{error::GovernanceError, state::enums::GovernanceAccountType},
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, clock::UnixTimestamp, program_error::ProgramError,
+        program_pack::IsInitialized, pubkey::Pubkey,
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// FundingRound configuration
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct FundingRoundConfig {
+    /// Unix timestamp after which projects can't be registered and
+    /// contributions are no longer accepted
+    pub contributions_end_at: UnixTimestamp,
+
+    /// The maximum amount contributed by a single contributor to a project
+    /// which is counted towards the matching
+    /// Contributions above the cap are still transferred to the project
+    /// 0 means contributions are not capped
+    pub max_contribution_per_contributor: u64,
+}
+
+/// Asserts the given FundingRoundConfig is valid
+pub fn assert_is_valid_funding_round_config(
+    config: &FundingRoundConfig,
+    current_unix_timestamp: UnixTimestamp,
+) -> Result<(), ProgramError> {
+    if config.contributions_end_at <= current_unix_timestamp {
+        return Err(GovernanceError::InvalidFundingRoundConfig.into());
+    }
+
+    Ok(())
+}
+
+/// The state of a FundingRound
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum FundingRoundState {
+    /// Projects can be registered and contributions made until
+    /// contributions_end_at
+    Active,
+
+    /// The matching pool has been captured and matching can be claimed by the
+    /// projects
+    Finalized,
+}
+
+/// FundingRound account
+/// The account runs a quadratic funding round for a Governance where
+/// contributions made by the members of the Realm to the registered projects
+/// are matched from a pool funded by the Governance treasury
+/// The matching pool is held in the FundingRound escrow token account and
+/// distributed in proportion to the quadratic matching weight of each project
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct FundingRound {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The Realm the contributors must be members of
+    pub realm: Pubkey,
+
+    /// The Governance which created the round and funds the matching pool
+    pub governance: Pubkey,
+
+    /// The seed used to derive the FundingRound address
+    pub round_seed: Pubkey,
+
+    /// The mint of the matching pool and the contributions
+    pub mint: Pubkey,
+
+    /// FundingRound configuration
+    pub config: FundingRoundConfig,
+
+    /// The state of the round
+    pub state: FundingRoundState,
+
+    /// The number of registered projects
+    pub projects_count: u32,
+
+    /// The sum of the matching weights of all the projects
+    pub total_matching_weight: u128,
+
+    /// The amount held in the escrow when the round was finalized
+    pub matching_pool_amount: u64,
+
+    /// Reserved
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for FundingRound {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 32 + 32 + 16 + 1 + 4 + 16 + 8 + 64)
+    }
+}
+
+impl IsInitialized for FundingRound {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::FundingRound
+    }
+}
+
+impl FundingRound {
+    /// Asserts projects can be registered and contributions made
+    pub fn assert_is_accepting_contributions(
+        &self,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        if self.state != FundingRoundState::Active
+            || current_unix_timestamp >= self.config.contributions_end_at
+        {
+            return Err(GovernanceError::FundingRoundNotAcceptingContributions.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts the round can be finalized
+    pub fn assert_can_finalize(
+        &self,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        if self.state != FundingRoundState::Active {
+            return Err(GovernanceError::FundingRoundAlreadyFinalized.into());
+        }
+
+        if current_unix_timestamp < self.config.contributions_end_at {
+            return Err(GovernanceError::FundingRoundContributionsNotEnded.into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the amount of a contribution which is counted towards the
+    /// matching
+    pub fn get_capped_contribution(&self, amount: u64) -> u64 {
+        if self.config.max_contribution_per_contributor == 0 {
+            amount
+        } else {
+            amount.min(self.config.max_contribution_per_contributor)
+        }
+    }
+
+    /// Returns the share of the matching pool for the given matching weight
+    pub fn get_matching_amount(&self, matching_weight: u128) -> Result<u64, ProgramError> {
+        if self.state != FundingRoundState::Finalized {
+            return Err(GovernanceError::FundingRoundNotFinalized.into());
+        }
+
+        if self.total_matching_weight == 0 {
+            return Ok(0);
+        }
+
+        // The pool is at most u64::MAX and the ratio at most 1 hence the result
+        // fits into u64
+        let matching_amount = (self.matching_pool_amount as u128)
+            .checked_mul(matching_weight)
+            .ok_or(GovernanceError::FundingRoundMatchingOverflow)?
+            .checked_div(self.total_matching_weight)
+            .unwrap();
+
+        Ok(matching_amount as u64)
+    }
+}
+
+/// Returns FundingRound PDA seeds
+pub fn get_funding_round_address_seeds<'a>(
+    governance: &'a Pubkey,
+    round_seed: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [b"funding-round", governance.as_ref(), round_seed.as_ref()]
+}
+
+/// Returns FundingRound PDA address
+pub fn get_funding_round_address(
+    program_id: &Pubkey,
+    governance: &Pubkey,
+    round_seed: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_funding_round_address_seeds(governance, round_seed),
+        program_id,
+    )
+    .0
+}
+
+/// Returns FundingRound escrow token account PDA seeds
+/// Note: The escrow token account holding the matching pool is owned by the
+/// FundingRound PDA
+pub fn get_funding_round_escrow_address_seeds(funding_round: &Pubkey) -> [&[u8]; 2] {
+    [b"funding-round-escrow", funding_round.as_ref()]
+}
+
+/// Returns FundingRound escrow token account PDA address
+pub fn get_funding_round_escrow_address(program_id: &Pubkey, funding_round: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_funding_round_escrow_address_seeds(funding_round),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes FundingRound account and checks owner program and account
+/// type
+pub fn get_funding_round_data(
+    program_id: &Pubkey,
+    funding_round_info: &AccountInfo,
+) -> Result<FundingRound, ProgramError> {
+    get_account_data::<FundingRound>(program_id, funding_round_info)
+}
+
+/// Asserts the given account is the escrow token account of the FundingRound
+pub fn assert_is_funding_round_escrow(
+    program_id: &Pubkey,
+    funding_round: &Pubkey,
+    funding_round_escrow_info: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if get_funding_round_escrow_address(program_id, funding_round) != *funding_round_escrow_info.key
+    {
+        return Err(GovernanceError::InvalidFundingRoundEscrow.into());
+    }
+
+    Ok(())
+}
+
+/// FundingProject account
+/// The account tracks the contributions made to a project registered for a
+/// FundingRound
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct FundingProject {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The FundingRound the project is registered for
+    pub funding_round: Pubkey,
+
+    /// The owner who registered the project
+    pub project_owner: Pubkey,
+
+    /// The token account receiving the contributions and the matching
+    pub recipient: Pubkey,
+
+    /// The total amount contributed to the project
+    pub contributions_amount: u64,
+
+    /// The total amount contributed to the project counted towards the
+    /// matching, with the contributions capped per contributor
+    pub capped_contributions_amount: u64,
+
+    /// The number of unique contributors
+    pub contributors_count: u32,
+
+    /// The sum of the square roots of the capped contributions of each
+    /// contributor
+    pub contributions_sqrt_sum: u64,
+
+    /// The amount of the matching pool transferred to the recipient
+    pub matching_amount: u64,
+
+    /// Whether the matching was claimed
+    pub matching_claimed: bool,
+
+    /// Reserved
+    pub reserved: [u8; 32],
+}
+
+impl AccountMaxSize for FundingProject {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 32 + 8 + 8 + 4 + 8 + 8 + 1 + 32)
+    }
+}
+
+impl IsInitialized for FundingProject {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::FundingProject
+    }
+}
+
+impl FundingProject {
+    /// Returns the quadratic funding matching weight of the project
+    /// (sum of sqrt(contribution))^2 - sum of contributions
+    pub fn get_matching_weight(&self) -> u128 {
+        let sqrt_sum = self.contributions_sqrt_sum as u128;
+
+        sqrt_sum
+            .saturating_mul(sqrt_sum)
+            .saturating_sub(self.capped_contributions_amount as u128)
+    }
+
+    /// Replaces the previous capped contribution of a contributor with the new
+    /// one
+    pub fn update_capped_contribution(
+        &mut self,
+        previous_capped_amount: u64,
+        capped_amount: u64,
+    ) -> Result<(), ProgramError> {
+        self.contributions_sqrt_sum = self
+            .contributions_sqrt_sum
+            .checked_sub(integer_sqrt(previous_capped_amount))
+            .and_then(|sqrt_sum| sqrt_sum.checked_add(integer_sqrt(capped_amount)))
+            .ok_or(GovernanceError::FundingRoundMatchingOverflow)?;
+
+        self.capped_contributions_amount = self
+            .capped_contributions_amount
+            .checked_sub(previous_capped_amount)
+            .and_then(|amount| amount.checked_add(capped_amount))
+            .ok_or(GovernanceError::FundingRoundMatchingOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// Returns FundingProject PDA seeds
+pub fn get_funding_project_address_seeds<'a>(
+    funding_round: &'a Pubkey,
+    project_owner: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        b"funding-project",
+        funding_round.as_ref(),
+        project_owner.as_ref(),
+    ]
+}
+
+/// Returns FundingProject PDA address
+pub fn get_funding_project_address(
+    program_id: &Pubkey,
+    funding_round: &Pubkey,
+    project_owner: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_funding_project_address_seeds(funding_round, project_owner),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes FundingProject account and checks owner program and account
+/// type
+pub fn get_funding_project_data(
+    program_id: &Pubkey,
+    funding_project_info: &AccountInfo,
+) -> Result<FundingProject, ProgramError> {
+    get_account_data::<FundingProject>(program_id, funding_project_info)
+}
+
+/// Deserializes FundingProject account
+/// 1) Checks owner program and account type
+/// 2) Asserts it's registered for the given FundingRound
+pub fn get_funding_project_data_for_funding_round(
+    program_id: &Pubkey,
+    funding_project_info: &AccountInfo,
+    funding_round: &Pubkey,
+) -> Result<FundingProject, ProgramError> {
+    let funding_project_data = get_funding_project_data(program_id, funding_project_info)?;
+
+    if funding_project_data.funding_round != *funding_round {
+        return Err(GovernanceError::InvalidFundingRoundForFundingProject.into());
+    }
+
+    Ok(funding_project_data)
+}
+
+/// FundingContribution account
+/// The account records the total contributed to a project by a member of the
+/// Realm identified by their TokenOwnerRecord
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct FundingContribution {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The FundingProject the contribution was made to
+    pub funding_project: Pubkey,
+
+    /// The TokenOwnerRecord of the contributor
+    pub token_owner_record: Pubkey,
+
+    /// The total amount contributed
+    pub amount: u64,
+
+    /// Reserved
+    pub reserved: [u8; 32],
+}
+
+impl AccountMaxSize for FundingContribution {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 8 + 32)
+    }
+}
+
+impl IsInitialized for FundingContribution {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::FundingContribution
+    }
+}
+
+/// Returns FundingContribution PDA seeds
+pub fn get_funding_contribution_address_seeds<'a>(
+    funding_project: &'a Pubkey,
+    token_owner_record: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        b"funding-contribution",
+        funding_project.as_ref(),
+        token_owner_record.as_ref(),
+    ]
+}
+
+/// Returns FundingContribution PDA address
+pub fn get_funding_contribution_address(
+    program_id: &Pubkey,
+    funding_project: &Pubkey,
+    token_owner_record: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_funding_contribution_address_seeds(funding_project, token_owner_record),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes FundingContribution account and checks owner program and
+/// account type
+pub fn get_funding_contribution_data(
+    program_id: &Pubkey,
+    funding_contribution_info: &AccountInfo,
+) -> Result<FundingContribution, ProgramError> {
+    get_account_data::<FundingContribution>(program_id, funding_contribution_info)
+}
+
+/// Returns the integer square root of the given value rounded down
+fn integer_sqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+
+    // Newton's method starting from an initial guess not below the integer root
+    let mut x = value;
+    let mut y = value / 2;
+
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn create_test_funding_round() -> FundingRound {
+        FundingRound {
+            account_type: GovernanceAccountType::FundingRound,
+            realm: Pubkey::new_unique(),
+            governance: Pubkey::new_unique(),
+            round_seed: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            config: FundingRoundConfig {
+                contributions_end_at: 100,
+                max_contribution_per_contributor: 10_000,
+            },
+            state: FundingRoundState::Active,
+            projects_count: 2,
+            total_matching_weight: 0,
+            matching_pool_amount: 0,
+            reserved: [0; 64],
+        }
+    }
+
+    fn create_test_funding_project() -> FundingProject {
+        FundingProject {
+            account_type: GovernanceAccountType::FundingProject,
+            funding_round: Pubkey::new_unique(),
+            project_owner: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            contributions_amount: 0,
+            capped_contributions_amount: 0,
+            contributors_count: 0,
+            contributions_sqrt_sum: 0,
+            matching_amount: 0,
+            matching_claimed: false,
+            reserved: [0; 32],
+        }
+    }
+
+    #[test]
+    fn test_max_size() {
+        // Arrange
+        let funding_round_data = create_test_funding_round();
+        let funding_project_data = create_test_funding_project();
+        let funding_contribution_data = FundingContribution {
+            account_type: GovernanceAccountType::FundingContribution,
+            funding_project: Pubkey::new_unique(),
+            token_owner_record: Pubkey::new_unique(),
+            amount: 10,
+            reserved: [0; 32],
+        };
+
+        // Act
+        let funding_round_size = borsh::to_vec(&funding_round_data).unwrap().len();
+        let funding_project_size = borsh::to_vec(&funding_project_data).unwrap().len();
+        let funding_contribution_size = borsh::to_vec(&funding_contribution_data).unwrap().len();
+
+        // Assert
+        assert_eq!(funding_round_data.get_max_size(), Some(funding_round_size));
+        assert_eq!(
+            funding_project_data.get_max_size(),
+            Some(funding_project_size)
+        );
+        assert_eq!(
+            funding_contribution_data.get_max_size(),
+            Some(funding_contribution_size)
+        );
+    }
+
+    #[test]
+    fn test_integer_sqrt() {
+        for value in 0..10_000u64 {
+            let sqrt = integer_sqrt(value);
+            assert!(sqrt * sqrt <= value);
+            assert!((sqrt + 1) * (sqrt + 1) > value);
+        }
+
+        assert_eq!(integer_sqrt(u64::MAX), u32::MAX as u64);
+    }
+
+    #[test]
+    fn test_matching_favours_many_small_contributions() {
+        // Arrange
+        let mut funding_round = create_test_funding_round();
+
+        // 4 contributors of 100 each
+        let mut project_a = create_test_funding_project();
+        for _ in 0..4 {
+            project_a.update_capped_contribution(0, 100).unwrap();
+        }
+
+        // 1 contributor of 400
+        let mut project_b = create_test_funding_project();
+        project_b.update_capped_contribution(0, 400).unwrap();
+
+        // Act
+        funding_round.total_matching_weight =
+            project_a.get_matching_weight() + project_b.get_matching_weight();
+        funding_round.matching_pool_amount = 1_000;
+        funding_round.state = FundingRoundState::Finalized;
+
+        // Assert
+        // (4 * 10)^2 - 400 = 1200
+        assert_eq!(project_a.get_matching_weight(), 1_200);
+        // (20)^2 - 400 = 0
+        assert_eq!(project_b.get_matching_weight(), 0);
+        assert_eq!(
+            funding_round
+                .get_matching_amount(project_a.get_matching_weight())
+                .unwrap(),
+            1_000
+        );
+        assert_eq!(
+            funding_round
+                .get_matching_amount(project_b.get_matching_weight())
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_update_capped_contribution() {
+        // Arrange
+        let funding_round = create_test_funding_round();
+        let mut project = create_test_funding_project();
+
+        // Act
+        project
+            .update_capped_contribution(0, funding_round.get_capped_contribution(2_500))
+            .unwrap();
+        project
+            .update_capped_contribution(
+                funding_round.get_capped_contribution(2_500),
+                funding_round.get_capped_contribution(40_000),
+            )
+            .unwrap();
+
+        // Assert
+        assert_eq!(project.capped_contributions_amount, 10_000);
+        assert_eq!(project.contributions_sqrt_sum, 100);
+    }
+}
@@ -4,11 +4,13 @@ use {
         error::GovernanceError,
         state::{
             enums::{GovernanceAccountType, VoteThreshold, VoteTipping},
+            execution_simulation::ExecutionSimulationConfig,
             legacy::{is_governance_v1_account_type, GovernanceV1},
+            proposal_token_deposit::ProposalTokenDepositConfig,
             realm::{assert_is_valid_realm, RealmV2},
             vote_record::VoteKind,
         },
-        tools::structs::Reserved119,
+        tools::structs::Reserved70,
     },
     borsh::{io::Write, BorshDeserialize, BorshSchema, BorshSerialize},
     solana_program::{
@@ -110,7 +112,19 @@ pub struct GovernanceV2 {
     /// Note 1: V1 accounts must be resized before using this space
     /// Note 2: The reserved space should be used from the end to also allow the
     /// config to grow if needed
-    pub reserved_v2: Reserved119,
+    pub reserved_v2: Reserved70,
+
+    /// Execution simulation config
+    /// Note: The config is stored outside of GovernanceConfig to preserve the
+    /// GovernanceV1 account layout and it's set using
+    /// SetExecutionSimulationConfig instruction
+    pub execution_simulation_config: ExecutionSimulationConfig,
+
+    /// Proposal token deposit config
+    /// Note: The config is stored outside of GovernanceConfig to preserve the
+    /// GovernanceV1 account layout and it's set using
+    /// SetProposalTokenDepositConfig instruction
+    pub proposal_token_deposit_config: ProposalTokenDepositConfig,
 
     /// The number of required signatories for proposals in the Governance
     pub required_signatories_count: u8,
@@ -159,7 +173,22 @@ pub fn is_governance_v2_account_type(account_type: &GovernanceAccountType) -> bo
         | GovernanceAccountType::VoteRecordV2
         | GovernanceAccountType::ProgramMetadata
         | GovernanceAccountType::ProposalDeposit
-        | GovernanceAccountType::RequiredSignatory => false,
+        | GovernanceAccountType::RequiredSignatory
+        | GovernanceAccountType::ProposalLink
+        | GovernanceAccountType::ProposalTokenDeposit
+        | GovernanceAccountType::ProposalArchive
+        | GovernanceAccountType::ExecutionSimulationAttestation
+        | GovernanceAccountType::VoteIntent
+        | GovernanceAccountType::FundingRound
+        | GovernanceAccountType::FundingProject
+        | GovernanceAccountType::FundingContribution
+        | GovernanceAccountType::CouncilElection
+        | GovernanceAccountType::CouncilCandidate
+        | GovernanceAccountType::CouncilElectionVote
+        | GovernanceAccountType::CouncilSeat
+        | GovernanceAccountType::VoteSessionKey
+        | GovernanceAccountType::ProposalDependency
+        | GovernanceAccountType::AddinRegistry => false,
     }
 }
 
@@ -195,7 +224,22 @@ pub fn try_get_governance_v2_type_for_v1(
         | GovernanceAccountType::VoteRecordV2
         | GovernanceAccountType::ProgramMetadata
         | GovernanceAccountType::ProposalDeposit
-        | GovernanceAccountType::RequiredSignatory => None,
+        | GovernanceAccountType::RequiredSignatory
+        | GovernanceAccountType::ProposalLink
+        | GovernanceAccountType::ProposalTokenDeposit
+        | GovernanceAccountType::ProposalArchive
+        | GovernanceAccountType::ExecutionSimulationAttestation
+        | GovernanceAccountType::VoteIntent
+        | GovernanceAccountType::FundingRound
+        | GovernanceAccountType::FundingProject
+        | GovernanceAccountType::FundingContribution
+        | GovernanceAccountType::CouncilElection
+        | GovernanceAccountType::CouncilCandidate
+        | GovernanceAccountType::CouncilElectionVote
+        | GovernanceAccountType::CouncilSeat
+        | GovernanceAccountType::VoteSessionKey
+        | GovernanceAccountType::ProposalDependency
+        | GovernanceAccountType::AddinRegistry => None,
     }
 }
 
@@ -244,7 +288,22 @@ impl GovernanceV2 {
             | GovernanceAccountType::RealmV2
             | GovernanceAccountType::TokenOwnerRecordV2
             | GovernanceAccountType::SignatoryRecordV2
-            | GovernanceAccountType::RequiredSignatory => {
+            | GovernanceAccountType::RequiredSignatory
+            | GovernanceAccountType::ProposalLink
+            | GovernanceAccountType::ProposalTokenDeposit
+            | GovernanceAccountType::ProposalArchive
+            | GovernanceAccountType::ExecutionSimulationAttestation
+            | GovernanceAccountType::VoteIntent
+            | GovernanceAccountType::FundingRound
+            | GovernanceAccountType::FundingProject
+            | GovernanceAccountType::FundingContribution
+            | GovernanceAccountType::CouncilElection
+            | GovernanceAccountType::CouncilCandidate
+            | GovernanceAccountType::CouncilElectionVote
+            | GovernanceAccountType::CouncilSeat
+            | GovernanceAccountType::VoteSessionKey
+            | GovernanceAccountType::ProposalDependency
+            | GovernanceAccountType::AddinRegistry => {
                 return Err(GovernanceToolsError::InvalidAccountType.into())
             }
         };
@@ -262,7 +321,10 @@ impl GovernanceV2 {
 
             // If reserved_v2 is used it must be individually assessed for GovernanceV1
             // account backward compatibility impact
-            if self.reserved_v2 != Reserved119::default() {
+            if self.reserved_v2 != Reserved70::default()
+                || self.execution_simulation_config != ExecutionSimulationConfig::default()
+                || self.proposal_token_deposit_config != ProposalTokenDepositConfig::default()
+            {
                 panic!("Extended data not supported by GovernanceV1")
             }
 
@@ -398,6 +460,22 @@ impl GovernanceV2 {
             .saturating_sub(self.config.deposit_exempt_proposal_count as u64)
             .saturating_mul(SECURITY_DEPOSIT_BASE_LAMPORTS)
     }
+
+    /// Returns the amount of community tokens which must be deposited to
+    /// create a Proposal for the given governing_token_mint
+    ///
+    /// Note: The token deposit is only required for community Proposals
+    pub fn get_proposal_token_deposit_amount(
+        &self,
+        realm_data: &RealmV2,
+        governing_token_mint: &Pubkey,
+    ) -> u64 {
+        if realm_data.community_mint == *governing_token_mint {
+            self.proposal_token_deposit_config.amount
+        } else {
+            0
+        }
+    }
 }
 
 /// Deserializes Governance account and checks owner program
@@ -417,7 +495,9 @@ pub fn get_governance_data(
             governance_seed: governance_data_v1.governance_seed,
             reserved1: 0,
             config: governance_data_v1.config,
-            reserved_v2: Reserved119::default(),
+            reserved_v2: Reserved70::default(),
+            execution_simulation_config: ExecutionSimulationConfig::default(),
+            proposal_token_deposit_config: ProposalTokenDepositConfig::default(),
             required_signatories_count: 0,
             // GovernanceV1 layout doesn't support active_proposal_count
             // For any legacy GovernanceV1 account it's not preserved until the account layout is
@@ -699,7 +779,9 @@ mod test {
             governance_seed: Pubkey::new_unique(),
             reserved1: 0,
             config: create_test_governance_config(),
-            reserved_v2: Reserved119::default(),
+            reserved_v2: Reserved70::default(),
+            execution_simulation_config: ExecutionSimulationConfig::default(),
+            proposal_token_deposit_config: ProposalTokenDepositConfig::default(),
             active_proposal_count: 10,
             required_signatories_count: 0,
         }
@@ -158,7 +158,22 @@ pub fn is_governance_v1_account_type(account_type: &GovernanceAccountType) -> bo
         | GovernanceAccountType::VoteRecordV2
         | GovernanceAccountType::ProgramMetadata
         | GovernanceAccountType::ProposalDeposit
-        | GovernanceAccountType::RequiredSignatory => false,
+        | GovernanceAccountType::RequiredSignatory
+        | GovernanceAccountType::ProposalLink
+        | GovernanceAccountType::ProposalTokenDeposit
+        | GovernanceAccountType::ProposalArchive
+        | GovernanceAccountType::ExecutionSimulationAttestation
+        | GovernanceAccountType::VoteIntent
+        | GovernanceAccountType::FundingRound
+        | GovernanceAccountType::FundingProject
+        | GovernanceAccountType::FundingContribution
+        | GovernanceAccountType::CouncilElection
+        | GovernanceAccountType::CouncilCandidate
+        | GovernanceAccountType::CouncilElectionVote
+        | GovernanceAccountType::CouncilSeat
+        | GovernanceAccountType::VoteSessionKey
+        | GovernanceAccountType::ProposalDependency
+        | GovernanceAccountType::AddinRegistry => false,
     }
 }
 
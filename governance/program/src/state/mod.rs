@@ -1,16 +1,26 @@
 //! Program accounts
 
+pub mod addin_registry;
+pub mod council_election;
 pub mod enums;
+pub mod execution_simulation;
+pub mod funding_round;
 pub mod governance;
 pub mod legacy;
 pub mod native_treasury;
 pub mod program_metadata;
 pub mod proposal;
+pub mod proposal_archive;
+pub mod proposal_dependency;
 pub mod proposal_deposit;
+pub mod proposal_link;
+pub mod proposal_token_deposit;
 pub mod proposal_transaction;
 pub mod realm;
 pub mod realm_config;
 pub mod required_signatory;
 pub mod signatory_record;
 pub mod token_owner_record;
+pub mod vote_intent;
 pub mod vote_record;
+pub mod vote_session_key;
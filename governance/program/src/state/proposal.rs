@@ -14,9 +14,10 @@ use {
             },
             governance::GovernanceConfig,
             legacy::ProposalV1,
+            proposal_archive::PROPOSAL_ARCHIVE_RETENTION_TIME,
             proposal_transaction::ProposalTransactionV2,
             realm::RealmV2,
-            realm_config::RealmConfigAccount,
+            realm_config::{RealmConfigAccount, VoteExtensionConfig},
             vote_record::{Vote, VoteKind},
         },
         tools::spl_token::get_spl_token_mint_supply,
@@ -30,7 +31,10 @@ use {
         program_pack::IsInitialized,
         pubkey::Pubkey,
     },
-    spl_governance_tools::account::{get_account_data, get_account_type, AccountMaxSize},
+    spl_governance_tools::{
+        account::{get_account_data, get_account_type, AccountMaxSize},
+        error::GovernanceToolsError,
+    },
     std::{cmp::Ordering, slice::Iter},
 };
 
@@ -175,9 +179,12 @@ pub struct ProposalV2 {
     /// proposal to make them binding as opposed to survey only proposals
     pub deny_vote_weight: Option<u64>,
 
-    /// Reserved space for future versions
-    /// This field is a leftover from unused veto_vote_weight: Option<u64>
-    pub reserved1: u8,
+    /// Indicates whether the Proposal is linked with Proposals from other
+    /// Governances through a ProposalLink account
+    /// Linked Proposals can only execute transactions once the ProposalLink
+    /// is committed
+    /// Note: This field reuses the space of the leftover reserved1 byte
+    pub is_linked: bool,
 
     /// The total weight of  votes
     /// Note: Abstain is not supported in the current version
@@ -236,8 +243,35 @@ pub struct ProposalV2 {
     /// (only higher value possible)
     pub vote_threshold: Option<VoteThreshold>,
 
+    /// The number of votes cast on the Proposal
+    /// VoteRecords are assigned voter numbers in the order they are cast, from
+    /// 1 to votes_count, and votes relinquished while voting keep their number
+    /// Note: This field reuses the space of the reserved field
+    pub votes_count: u32,
+
+    /// The number of voters whose votes count towards the Proposal outcome
+    /// It excludes votes relinquished while the Proposal is being voted on
+    /// and is final once voting is completed
+    /// Note: This field reuses the space of the reserved field
+    pub voters_count: u32,
+
+    /// The number of times the voting time was extended because a late vote
+    /// changed the leading outcome
+    /// Note: This field reuses the space of the reserved field
+    pub voting_extensions_count: u8,
+
+    /// The total time (in seconds) by which the voting time was extended
+    /// Note: This field reuses the space of the reserved field
+    pub voting_extended_time: u32,
+
+    /// The number of Proposals which must be completed before the Proposal
+    /// can execute its transactions, each recorded in a ProposalDependency
+    /// account
+    /// Note: This field reuses the space of the reserved field
+    pub dependencies_count: u8,
+
     /// Reserved space for future versions
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 50],
 
     /// Proposal name
     pub name: String,
@@ -367,13 +401,46 @@ impl ProposalV2 {
         }
     }
 
+    /// Checks if the Proposal was defeated with participation below the given
+    /// floor, as a percentage of the max vote weight, in which case its token
+    /// deposit is forfeited
+    ///
+    /// Note: Participation is evaluated using the vote weights and the max vote
+    /// weight captured when the vote was finalized
+    pub fn is_defeated_below_participation_floor(
+        &self,
+        participation_floor_percentage: u8,
+    ) -> bool {
+        if self.state != ProposalState::Defeated {
+            return false;
+        }
+
+        let max_vote_weight = match self.max_vote_weight {
+            Some(max_vote_weight) => max_vote_weight as u128,
+            None => return false,
+        };
+
+        let participation = self
+            .options
+            .iter()
+            .map(|o| o.vote_weight as u128)
+            .sum::<u128>()
+            + self.deny_vote_weight.unwrap_or(0) as u128
+            + self.abstain_vote_weight.unwrap_or(0) as u128;
+
+        participation * 100 < max_vote_weight * participation_floor_percentage as u128
+    }
+
     /// Expected base vote end time determined by the configured
-    /// base_voting_time and actual voting start time
+    /// base_voting_time, the time the voting was extended by and actual voting
+    /// start time
     pub fn voting_base_time_end(&self, config: &GovernanceConfig) -> UnixTimestamp {
         self.voting_at
             .unwrap()
             .checked_add(config.voting_base_time as i64)
             .unwrap()
+            .checked_add(self.voting_extended_time as i64)
+            .unwrap()
     }
 
     /// Checks whether the base voting time has ended for the proposal
@@ -602,7 +669,8 @@ impl ProposalV2 {
     }
 
     /// Resolves max voter weight using either 1) voting governing_token_mint
-    /// supply or 2) max voter weight if configured for the token mint
+    /// supply and the active council seats or 2) max voter weight if
+    /// configured for the token mint
     #[allow(clippy::too_many_arguments)]
     pub fn resolve_max_voter_weight(
         &mut self,
@@ -612,6 +680,7 @@ impl ProposalV2 {
         realm_config_data: &RealmConfigAccount,
         vote_governing_token_mint_info: &AccountInfo,
         vote_kind: &VoteKind,
+        current_unix_timestamp: UnixTimestamp,
     ) -> Result<u64, ProgramError> {
         // if the Realm is configured to use max voter weight for the given voting
         // governing_token_mint then use the externally provided max_voter_weight
@@ -641,8 +710,15 @@ impl ProposalV2 {
             ));
         }
 
+        // Each active CouncilSeat carries the voter weight of a single council token
         let vote_governing_token_mint_supply =
-            get_spl_token_mint_supply(vote_governing_token_mint_info)?;
+            get_spl_token_mint_supply(vote_governing_token_mint_info)?
+                .checked_add(realm_config_data.get_active_council_seats_count(
+                    realm_data,
+                    vote_governing_token_mint_info.key,
+                    current_unix_timestamp,
+                ))
+                .unwrap();
 
         let max_voter_weight = self.get_max_voter_weight_from_mint_supply(
             realm_data,
@@ -835,6 +911,81 @@ impl ProposalV2 {
         Ok(())
     }
 
+    /// Checks if the Proposal can be linked with Proposals from other
+    /// Governances
+    pub fn assert_can_link(&self) -> Result<(), ProgramError> {
+        if self.account_type != GovernanceAccountType::ProposalV2 {
+            return Err(GovernanceToolsError::InvalidAccountType.into());
+        }
+
+        if self.is_linked {
+            return Err(GovernanceError::ProposalAlreadyLinked.into());
+        }
+
+        // Linking changes the execution semantics of the Proposal and hence it
+        // must be established before the Proposal is signed off and voted on
+        self.assert_is_draft_state()
+    }
+
+    /// Checks if a dependency on another Proposal can be added to the Proposal
+    pub fn assert_can_add_dependency(&self) -> Result<(), ProgramError> {
+        if self.account_type != GovernanceAccountType::ProposalV2 {
+            return Err(GovernanceToolsError::InvalidAccountType.into());
+        }
+
+        // Dependencies change the execution semantics of the Proposal and hence
+        // they must be declared before the Proposal is signed off and voted on
+        self.assert_is_draft_state()
+    }
+
+    /// Checks if the Proposal can be depended on by another Proposal
+    /// Draft Proposals can't be depended on which also prevents dependency
+    /// cycles because a Proposal can only gain dependencies while in Draft
+    pub fn assert_can_be_depended_on(&self) -> Result<(), ProgramError> {
+        match self.state {
+            ProposalState::SigningOff
+            | ProposalState::Voting
+            | ProposalState::Succeeded
+            | ProposalState::Executing
+            | ProposalState::ExecutingWithErrors
+            | ProposalState::Completed => Ok(()),
+            ProposalState::Draft
+            | ProposalState::Cancelled
+            | ProposalState::Defeated
+            | ProposalState::Vetoed => {
+                Err(GovernanceError::InvalidStateCannotDependOnProposal.into())
+            }
+        }
+    }
+
+    /// Checks if Proposal can be archived
+    /// The Proposal must be in a final state for at least the archive retention
+    /// time
+    pub fn assert_can_archive(
+        &self,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        self.assert_is_final_state()
+            .map_err(|_| GovernanceError::InvalidStateCannotArchiveProposal)?;
+
+        // Proposals voted on before vote participation was recorded can't tell
+        // whether all their VoteRecords were archived
+        if self.voting_at.is_some() && self.votes_count == 0 {
+            return Err(GovernanceError::InvalidStateCannotArchiveProposal.into());
+        }
+
+        let final_state_at = self
+            .closed_at
+            .or(self.voting_completed_at)
+            .ok_or(GovernanceError::InvalidStateCannotArchiveProposal)?;
+
+        if current_unix_timestamp < final_state_at + PROPOSAL_ARCHIVE_RETENTION_TIME {
+            return Err(GovernanceError::ProposalArchiveRetentionTimeNotElapsed.into());
+        }
+
+        Ok(())
+    }
+
     /// Checks if Instructions can be executed for the Proposal in the given
     /// state
     pub fn assert_can_execute_transaction(
@@ -986,6 +1137,103 @@ impl ProposalV2 {
         Ok(())
     }
 
+    /// Records a vote cast on the Proposal and returns the voter number for its
+    /// VoteRecord
+    /// Note: Participation is not recorded for ProposalV1 accounts and 0 is
+    /// returned
+    pub fn record_vote_participation(&mut self) -> u32 {
+        if self.account_type != GovernanceAccountType::ProposalV2 {
+            return 0;
+        }
+
+        self.votes_count = self.votes_count.checked_add(1).unwrap();
+        self.voters_count = self.voters_count.checked_add(1).unwrap();
+
+        self.votes_count
+    }
+
+    /// Removes the participation of a vote relinquished while the Proposal is
+    /// being voted on
+    pub fn relinquish_vote_participation(&mut self, voter_number: u32) {
+        // Votes cast before participation was recorded were never counted
+        if voter_number == 0 {
+            return;
+        }
+
+        self.voters_count = self.voters_count.checked_sub(1).unwrap();
+    }
+
+    /// Returns the index of the option with the highest vote weight or
+    /// options.len() if the Deny vote weight is the highest
+    /// Returns None if there is no single leading outcome
+    pub fn get_leading_outcome(&self) -> Option<usize> {
+        let mut leading_outcome = None;
+        let mut leading_weight = 0;
+        let mut is_tied = true;
+
+        let outcome_weights = self
+            .options
+            .iter()
+            .map(|option| option.vote_weight)
+            .chain(self.deny_vote_weight);
+
+        for (outcome, weight) in outcome_weights.enumerate() {
+            match weight.cmp(&leading_weight) {
+                Ordering::Greater => {
+                    leading_outcome = Some(outcome);
+                    leading_weight = weight;
+                    is_tied = false;
+                }
+                Ordering::Equal => is_tied = true,
+                Ordering::Less => {}
+            }
+        }
+
+        if is_tied {
+            None
+        } else {
+            leading_outcome
+        }
+    }
+
+    /// Extends the voting time of the Proposal if the vote cast within the
+    /// extension window changed the leading outcome and the max number of
+    /// extensions hasn't been reached yet
+    /// Returns true if the voting time was extended
+    /// Note: The voting time of ProposalV1 accounts is never extended
+    pub fn try_extend_voting(
+        &mut self,
+        vote_extension: &VoteExtensionConfig,
+        config: &GovernanceConfig,
+        previous_leading_outcome: Option<usize>,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> bool {
+        if self.account_type != GovernanceAccountType::ProposalV2
+            || !vote_extension.is_enabled()
+            || self.voting_extensions_count >= vote_extension.max_extensions_count
+        {
+            return false;
+        }
+
+        let window_start = self
+            .voting_max_time_end(config)
+            .saturating_sub(vote_extension.window_time as i64);
+
+        if current_unix_timestamp < window_start
+            || self.get_leading_outcome() == previous_leading_outcome
+        {
+            return false;
+        }
+
+        self.voting_extensions_count = self.voting_extensions_count.checked_add(1).unwrap();
+        self.voting_extended_time = self
+            .voting_extended_time
+            .checked_add(vote_extension.extension_time)
+            .unwrap();
+
+        true
+    }
+
     /// Serializes account into the target buffer
     pub fn serialize<W: Write>(self, writer: W) -> Result<(), ProgramError> {
         if self.account_type == GovernanceAccountType::ProposalV2 {
@@ -1014,6 +1262,14 @@ impl ProposalV2 {
                 panic!("ProposalV1 doesn't support multiple options")
             }
 
+            if self.is_linked {
+                panic!("ProposalV1 doesn't support linked proposals")
+            }
+
+            if self.dependencies_count > 0 {
+                panic!("ProposalV1 doesn't support proposal dependencies")
+            }
+
             let proposal_data_v1 = ProposalV1 {
                 account_type: self.account_type,
                 governance: self.governance,
@@ -1133,8 +1389,13 @@ pub fn get_proposal_data(
             vote_threshold: proposal_data_v1.vote_threshold,
             name: proposal_data_v1.name,
             description_link: proposal_data_v1.description_link,
-            reserved: [0; 64],
-            reserved1: 0,
+            votes_count: 0,
+            voters_count: 0,
+            voting_extensions_count: 0,
+            voting_extended_time: 0,
+            dependencies_count: 0,
+            reserved: [0; 50],
+            is_linked: false,
         });
     }
 
@@ -1292,8 +1553,13 @@ mod test {
             max_voting_time: Some(0),
             vote_threshold: Some(VoteThreshold::YesVotePercentage(100)),
 
-            reserved: [0; 64],
-            reserved1: 0,
+            votes_count: 0,
+            voters_count: 0,
+            voting_extensions_count: 0,
+            voting_extended_time: 0,
+            dependencies_count: 0,
+            reserved: [0; 50],
+            is_linked: false,
         }
     }
 
@@ -3246,4 +3512,187 @@ mod test {
 
         assert_eq!(proposal_v1_source, proposal_v1_target)
     }
+
+    #[test]
+    fn test_is_defeated_below_participation_floor() {
+        // Arrange
+        let mut proposal = create_test_proposal();
+        proposal.state = ProposalState::Defeated;
+        proposal.max_vote_weight = Some(100);
+        proposal.options[0].vote_weight = 5;
+        proposal.deny_vote_weight = Some(4);
+        proposal.abstain_vote_weight = None;
+
+        // Act + Assert
+        assert!(proposal.is_defeated_below_participation_floor(10));
+        assert!(!proposal.is_defeated_below_participation_floor(9));
+        assert!(!proposal.is_defeated_below_participation_floor(0));
+
+        proposal.state = ProposalState::Succeeded;
+        assert!(!proposal.is_defeated_below_participation_floor(10));
+    }
+
+    #[test]
+    fn test_record_vote_participation() {
+        // Arrange
+        let mut proposal = create_test_proposal();
+        proposal.account_type = GovernanceAccountType::ProposalV2;
+
+        // Act
+        let voter_number1 = proposal.record_vote_participation();
+        let voter_number2 = proposal.record_vote_participation();
+        proposal.relinquish_vote_participation(voter_number1);
+
+        // Assert
+        assert_eq!(1, voter_number1);
+        assert_eq!(2, voter_number2);
+        assert_eq!(2, proposal.votes_count);
+        assert_eq!(1, proposal.voters_count);
+
+        // Votes cast before participation was recorded are ignored
+        proposal.relinquish_vote_participation(0);
+        assert_eq!(1, proposal.voters_count);
+    }
+
+    #[test]
+    fn test_record_vote_participation_for_proposal_v1() {
+        // Arrange
+        let mut proposal = create_test_proposal();
+        proposal.account_type = GovernanceAccountType::ProposalV1;
+
+        // Act
+        let voter_number = proposal.record_vote_participation();
+
+        // Assert
+        assert_eq!(0, voter_number);
+        assert_eq!(0, proposal.votes_count);
+        assert_eq!(0, proposal.voters_count);
+    }
+
+    fn create_test_vote_extension_config() -> VoteExtensionConfig {
+        VoteExtensionConfig {
+            window_time: 2,
+            extension_time: 3,
+            max_extensions_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_get_leading_outcome() {
+        // Arrange
+        let mut proposal = create_test_proposal();
+
+        // Act + Assert
+        assert_eq!(None, proposal.get_leading_outcome());
+
+        proposal.options[0].vote_weight = 10;
+        assert_eq!(Some(0), proposal.get_leading_outcome());
+
+        proposal.deny_vote_weight = Some(20);
+        assert_eq!(Some(1), proposal.get_leading_outcome());
+
+        proposal.options[0].vote_weight = 20;
+        assert_eq!(None, proposal.get_leading_outcome());
+    }
+
+    #[test]
+    fn test_try_extend_voting_when_leading_outcome_changed_within_window() {
+        // Arrange
+        let mut proposal = create_test_proposal();
+        proposal.account_type = GovernanceAccountType::ProposalV2;
+        proposal.state = ProposalState::Voting;
+        let governance_config = create_test_governance_config();
+        let vote_extension = create_test_vote_extension_config();
+
+        let previous_leading_outcome = proposal.get_leading_outcome();
+        proposal.options[0].vote_weight = 10;
+
+        let current_timestamp = proposal.voting_max_time_end(&governance_config) - 1;
+
+        // Act
+        let extended = proposal.try_extend_voting(
+            &vote_extension,
+            &governance_config,
+            previous_leading_outcome,
+            current_timestamp,
+        );
+
+        // Assert
+        assert!(extended);
+        assert_eq!(1, proposal.voting_extensions_count);
+        assert_eq!(3, proposal.voting_extended_time);
+        assert_eq!(
+            proposal.voting_at.unwrap() + governance_config.voting_base_time as i64 + 3,
+            proposal.voting_base_time_end(&governance_config)
+        );
+
+        // The max number of extensions has been reached
+        let previous_leading_outcome = proposal.get_leading_outcome();
+        proposal.deny_vote_weight = Some(20);
+
+        let extended = proposal.try_extend_voting(
+            &vote_extension,
+            &governance_config,
+            previous_leading_outcome,
+            proposal.voting_max_time_end(&governance_config) - 1,
+        );
+
+        assert!(!extended);
+        assert_eq!(1, proposal.voting_extensions_count);
+    }
+
+    #[test]
+    fn test_try_extend_voting_outside_window() {
+        // Arrange
+        let mut proposal = create_test_proposal();
+        proposal.account_type = GovernanceAccountType::ProposalV2;
+        proposal.state = ProposalState::Voting;
+        let governance_config = create_test_governance_config();
+        let vote_extension = create_test_vote_extension_config();
+
+        let previous_leading_outcome = proposal.get_leading_outcome();
+        proposal.options[0].vote_weight = 10;
+
+        let current_timestamp = proposal.voting_max_time_end(&governance_config) - 3;
+
+        // Act
+        let extended = proposal.try_extend_voting(
+            &vote_extension,
+            &governance_config,
+            previous_leading_outcome,
+            current_timestamp,
+        );
+
+        // Assert
+        assert!(!extended);
+        assert_eq!(0, proposal.voting_extended_time);
+    }
+
+    #[test]
+    fn test_try_extend_voting_with_unchanged_leading_outcome() {
+        // Arrange
+        let mut proposal = create_test_proposal();
+        proposal.account_type = GovernanceAccountType::ProposalV2;
+        proposal.state = ProposalState::Voting;
+        proposal.options[0].vote_weight = 10;
+        let governance_config = create_test_governance_config();
+        let vote_extension = create_test_vote_extension_config();
+
+        let previous_leading_outcome = proposal.get_leading_outcome();
+        proposal.options[0].vote_weight = 20;
+
+        let current_timestamp = proposal.voting_max_time_end(&governance_config) - 1;
+
+        // Act
+        let extended = proposal.try_extend_voting(
+            &vote_extension,
+            &governance_config,
+            previous_leading_outcome,
+            current_timestamp,
+        );
+
+        // Assert
+        assert!(!extended);
+        assert_eq!(0, proposal.voting_extensions_count);
+    }
 }
@@ -0,0 +1,304 @@
+//! ProposalArchive account
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::enums::{GovernanceAccountType, ProposalState},
+    },
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, clock::UnixTimestamp, keccak, program_error::ProgramError,
+        program_pack::IsInitialized, pubkey::Pubkey,
+    },
+    spl_concurrent_merkle_tree::{
+        hash::hash_to_parent,
+        node::{Node, EMPTY},
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// The max depth of the ProposalArchive merkle tree
+/// It allows to archive a Proposal with up to 2^20 - 1 VoteRecords
+pub const PROPOSAL_ARCHIVE_MAX_DEPTH: usize = 20;
+
+/// The time in seconds a Proposal must remain in its final state before it can
+/// be archived
+pub const PROPOSAL_ARCHIVE_RETENTION_TIME: UnixTimestamp = 90 * 24 * 60 * 60; // 90 days
+
+/// ProposalArchive account
+/// The account attests to a Proposal and its VoteRecords which were closed
+/// once archived. It stores the root of a merkle tree whose leaves are keccak
+/// hashes of the archived accounts data: the VoteRecords in the order they
+/// were archived followed by the Proposal
+/// The tree uses the same hashing as spl-account-compression trees of
+/// PROPOSAL_ARCHIVE_MAX_DEPTH and the archived accounts can be proven against
+/// the root using the standard concurrent merkle tree proofs
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ProposalArchive {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The Governance the archived Proposal belonged to
+    pub governance: Pubkey,
+
+    /// The archived Proposal
+    pub proposal: Pubkey,
+
+    /// The final state of the archived Proposal
+    pub proposal_state: ProposalState,
+
+    /// The number of archived VoteRecords
+    pub vote_records_count: u32,
+
+    /// When the Proposal was archived and closed
+    /// None while VoteRecords are still being archived
+    pub archived_at: Option<UnixTimestamp>,
+
+    /// The root of the archive merkle tree
+    pub root: [u8; 32],
+
+    /// The left siblings on the path of the next leaf to the root used to
+    /// append leaves to the merkle tree
+    pub rightmost_path: [[u8; 32]; PROPOSAL_ARCHIVE_MAX_DEPTH],
+
+    /// Reserved
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for ProposalArchive {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 1 + 4 + 9 + 32 + 32 * PROPOSAL_ARCHIVE_MAX_DEPTH + 64)
+    }
+}
+
+impl IsInitialized for ProposalArchive {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::ProposalArchive
+    }
+}
+
+impl ProposalArchive {
+    /// Creates an empty ProposalArchive for the given Proposal
+    pub fn new(governance: Pubkey, proposal: Pubkey, proposal_state: ProposalState) -> Self {
+        let mut root = EMPTY;
+        for _ in 0..PROPOSAL_ARCHIVE_MAX_DEPTH {
+            let sibling = root;
+            hash_to_parent(&mut root, &sibling, true);
+        }
+
+        Self {
+            account_type: GovernanceAccountType::ProposalArchive,
+            governance,
+            proposal,
+            proposal_state,
+            vote_records_count: 0,
+            archived_at: None,
+            root,
+            rightmost_path: [EMPTY; PROPOSAL_ARCHIVE_MAX_DEPTH],
+            reserved: [0; 64],
+        }
+    }
+
+    /// Checks the Proposal hasn't been archived yet
+    pub fn assert_is_not_archived(&self) -> Result<(), ProgramError> {
+        if self.archived_at.is_some() {
+            return Err(GovernanceError::ProposalAlreadyArchived.into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of leaves in the archive merkle tree
+    pub fn leaves_count(&self) -> u32 {
+        self.vote_records_count + u32::from(self.archived_at.is_some())
+    }
+
+    /// Archives the given VoteRecord account data
+    pub fn archive_vote_record(&mut self, vote_record_data: &[u8]) -> Result<(), ProgramError> {
+        self.assert_is_not_archived()?;
+
+        // The last leaf of the tree is reserved for the Proposal
+        if self.vote_records_count >= (1 << PROPOSAL_ARCHIVE_MAX_DEPTH) - 1 {
+            return Err(GovernanceError::ProposalArchiveFull.into());
+        }
+
+        self.append_leaf(vote_record_data);
+        self.vote_records_count = self.vote_records_count.checked_add(1).unwrap();
+
+        Ok(())
+    }
+
+    /// Archives the given Proposal account data as the last leaf of the tree
+    pub fn archive_proposal(
+        &mut self,
+        proposal_data: &[u8],
+        archived_at: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        self.assert_is_not_archived()?;
+
+        self.append_leaf(proposal_data);
+        self.archived_at = Some(archived_at);
+
+        Ok(())
+    }
+
+    /// Appends the hash of the given account data to the archive merkle tree
+    /// and updates its root
+    fn append_leaf(&mut self, account_data: &[u8]) {
+        let index = self.leaves_count();
+
+        let mut node: Node = get_archive_leaf(account_data);
+        let mut empty_node = EMPTY;
+
+        for (level, sibling) in self.rightmost_path.iter_mut().enumerate() {
+            if index >> level & 1 == 0 {
+                *sibling = node;
+                hash_to_parent(&mut node, &empty_node, true);
+            } else {
+                hash_to_parent(&mut node, sibling, false);
+            }
+            let empty_sibling = empty_node;
+            hash_to_parent(&mut empty_node, &empty_sibling, true);
+        }
+
+        self.root = node;
+    }
+}
+
+/// Returns the archive merkle tree leaf for the given account data
+pub fn get_archive_leaf(account_data: &[u8]) -> Node {
+    keccak::hash(account_data).to_bytes()
+}
+
+/// Returns ProposalArchive PDA seeds
+pub fn get_proposal_archive_address_seeds(proposal: &Pubkey) -> [&[u8]; 2] {
+    [b"proposal-archive", proposal.as_ref()]
+}
+
+/// Returns ProposalArchive PDA address
+pub fn get_proposal_archive_address(program_id: &Pubkey, proposal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_proposal_archive_address_seeds(proposal), program_id).0
+}
+
+/// Deserializes ProposalArchive account and checks owner program and account
+/// type
+pub fn get_proposal_archive_data(
+    program_id: &Pubkey,
+    proposal_archive_info: &AccountInfo,
+) -> Result<ProposalArchive, ProgramError> {
+    get_account_data::<ProposalArchive>(program_id, proposal_archive_info)
+}
+
+/// Deserializes ProposalArchive account and asserts it belongs to the given
+/// Proposal
+pub fn get_proposal_archive_data_for_proposal(
+    program_id: &Pubkey,
+    proposal_archive_info: &AccountInfo,
+    proposal: &Pubkey,
+) -> Result<ProposalArchive, ProgramError> {
+    let proposal_archive_data = get_proposal_archive_data(program_id, proposal_archive_info)?;
+
+    if proposal_archive_data.proposal != *proposal {
+        return Err(GovernanceError::InvalidProposalForProposalArchive.into());
+    }
+
+    Ok(proposal_archive_data)
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, spl_concurrent_merkle_tree::concurrent_merkle_tree::ConcurrentMerkleTree};
+
+    fn create_test_proposal_archive() -> ProposalArchive {
+        ProposalArchive::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            ProposalState::Completed,
+        )
+    }
+
+    #[test]
+    fn test_max_size() {
+        // Arrange
+        let mut proposal_archive_data = create_test_proposal_archive();
+        proposal_archive_data.archived_at = Some(10);
+
+        // Act
+        let size = borsh::to_vec(&proposal_archive_data).unwrap().len();
+
+        // Assert
+        assert_eq!(proposal_archive_data.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_root_matches_concurrent_merkle_tree() {
+        // Arrange
+        let mut proposal_archive_data = create_test_proposal_archive();
+
+        let mut merkle_tree =
+            Box::new(ConcurrentMerkleTree::<PROPOSAL_ARCHIVE_MAX_DEPTH, 8>::new());
+        merkle_tree.initialize().unwrap();
+
+        assert_eq!(proposal_archive_data.root, merkle_tree.get_root());
+
+        // Act
+        for i in 0..5u8 {
+            let vote_record_data = [i; 10];
+            proposal_archive_data
+                .archive_vote_record(&vote_record_data)
+                .unwrap();
+            merkle_tree
+                .append(get_archive_leaf(&vote_record_data))
+                .unwrap();
+
+            assert_eq!(proposal_archive_data.root, merkle_tree.get_root());
+        }
+
+        let proposal_data = [10; 20];
+        proposal_archive_data
+            .archive_proposal(&proposal_data, 100)
+            .unwrap();
+        merkle_tree
+            .append(get_archive_leaf(&proposal_data))
+            .unwrap();
+
+        // Assert
+        assert_eq!(proposal_archive_data.root, merkle_tree.get_root());
+        assert_eq!(proposal_archive_data.vote_records_count, 5);
+        assert_eq!(proposal_archive_data.leaves_count(), 6);
+    }
+
+    #[test]
+    fn test_archive_vote_record_with_full_archive_error() {
+        // Arrange
+        let mut proposal_archive_data = create_test_proposal_archive();
+        proposal_archive_data.vote_records_count = (1 << PROPOSAL_ARCHIVE_MAX_DEPTH) - 1;
+
+        // Act
+        let err = proposal_archive_data
+            .archive_vote_record(&[0; 10])
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, GovernanceError::ProposalArchiveFull.into());
+    }
+
+    #[test]
+    fn test_archive_vote_record_with_archived_proposal_error() {
+        // Arrange
+        let mut proposal_archive_data = create_test_proposal_archive();
+        proposal_archive_data
+            .archive_proposal(&[0; 10], 100)
+            .unwrap();
+
+        // Act
+        let err = proposal_archive_data
+            .archive_vote_record(&[0; 10])
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, GovernanceError::ProposalAlreadyArchived.into());
+    }
+}
@@ -0,0 +1,112 @@
+//! ProposalDependency account
+
+use {
+    crate::{error::GovernanceError, state::enums::GovernanceAccountType},
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
+        pubkey::Pubkey,
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// ProposalDependency account
+/// The account records that a Proposal can only execute its transactions
+/// once another Proposal of the same Realm was executed and completed
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ProposalDependency {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The dependent Proposal
+    pub proposal: Pubkey,
+
+    /// The Proposal which must be completed before the dependent Proposal
+    /// can execute its transactions
+    pub depends_on: Pubkey,
+
+    /// Reserved
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for ProposalDependency {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 64)
+    }
+}
+
+impl IsInitialized for ProposalDependency {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::ProposalDependency
+    }
+}
+
+/// Returns ProposalDependency PDA seeds
+pub fn get_proposal_dependency_address_seeds<'a>(
+    proposal: &'a Pubkey,
+    depends_on: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        b"proposal-dependency",
+        proposal.as_ref(),
+        depends_on.as_ref(),
+    ]
+}
+
+/// Returns ProposalDependency PDA address
+pub fn get_proposal_dependency_address(
+    program_id: &Pubkey,
+    proposal: &Pubkey,
+    depends_on: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_proposal_dependency_address_seeds(proposal, depends_on),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes ProposalDependency account and checks owner program and
+/// account type
+pub fn get_proposal_dependency_data(
+    program_id: &Pubkey,
+    proposal_dependency_info: &AccountInfo,
+) -> Result<ProposalDependency, ProgramError> {
+    get_account_data::<ProposalDependency>(program_id, proposal_dependency_info)
+}
+
+/// Deserializes ProposalDependency account and asserts it belongs to the
+/// given Proposal
+pub fn get_proposal_dependency_data_for_proposal(
+    program_id: &Pubkey,
+    proposal_dependency_info: &AccountInfo,
+    proposal: &Pubkey,
+) -> Result<ProposalDependency, ProgramError> {
+    let proposal_dependency_data =
+        get_proposal_dependency_data(program_id, proposal_dependency_info)?;
+
+    if proposal_dependency_data.proposal != *proposal {
+        return Err(GovernanceError::InvalidProposalForProposalDependency.into());
+    }
+
+    Ok(proposal_dependency_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_max_size() {
+        let proposal_dependency = ProposalDependency {
+            account_type: GovernanceAccountType::ProposalDependency,
+            proposal: Pubkey::new_unique(),
+            depends_on: Pubkey::new_unique(),
+            reserved: [0; 64],
+        };
+
+        let size = borsh::to_vec(&proposal_dependency).unwrap().len();
+
+        assert_eq!(proposal_dependency.get_max_size(), Some(size));
+    }
+}
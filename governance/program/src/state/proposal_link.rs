@@ -0,0 +1,205 @@
+//! ProposalLink account
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::enums::{GovernanceAccountType, ProposalLinkState},
+    },
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, clock::UnixTimestamp, program_error::ProgramError,
+        program_pack::IsInitialized, pubkey::Pubkey,
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// Max number of Proposals linked by a single ProposalLink
+pub const MAX_LINKED_PROPOSALS: usize = 10;
+
+/// ProposalLink account
+/// The account links N Proposals from different Governances and acts as the
+/// coordinator of a two-phase commit: none of the Proposals can execute its
+/// transactions until at least threshold (M) of them succeeded and the link is
+/// committed
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ProposalLink {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The linked Proposals
+    /// The first Proposal is used to derive the ProposalLink address
+    pub proposals: Vec<Pubkey>,
+
+    /// The number of linked Proposals which must succeed to commit the link
+    pub threshold: u8,
+
+    /// Current ProposalLink state
+    pub state: ProposalLinkState,
+
+    /// When the ProposalLink was committed or aborted
+    pub resolved_at: Option<UnixTimestamp>,
+
+    /// Reserved
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for ProposalLink {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 4 + self.proposals.len() * 32 + 1 + 1 + 9 + 64)
+    }
+}
+
+impl IsInitialized for ProposalLink {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::ProposalLink
+    }
+}
+
+impl ProposalLink {
+    /// Checks the number of linked Proposals and the threshold are valid
+    pub fn assert_is_valid_link(&self) -> Result<(), ProgramError> {
+        if self.proposals.len() < 2
+            || self.proposals.len() > MAX_LINKED_PROPOSALS
+            || self.threshold == 0
+            || self.threshold as usize > self.proposals.len()
+        {
+            return Err(GovernanceError::InvalidProposalLinkConfig.into());
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the link state from the outcomes of the linked Proposals
+    /// The link is committed once threshold Proposals succeeded and aborted
+    /// once too many of them failed for the threshold to be reached
+    /// Returns None if the outcome is not known yet
+    pub fn resolve_state(&self, outcomes: &[Option<bool>]) -> Option<ProposalLinkState> {
+        let succeeded = outcomes.iter().filter(|o| **o == Some(true)).count();
+        let failed = outcomes.iter().filter(|o| **o == Some(false)).count();
+
+        if succeeded >= self.threshold as usize {
+            Some(ProposalLinkState::Committed)
+        } else if failed > self.proposals.len() - self.threshold as usize {
+            Some(ProposalLinkState::Aborted)
+        } else {
+            None
+        }
+    }
+
+    /// Checks the ProposalLink is committed and linked Proposals can be
+    /// executed
+    pub fn assert_is_committed(&self) -> Result<(), ProgramError> {
+        if self.state != ProposalLinkState::Committed {
+            return Err(GovernanceError::ProposalLinkNotCommitted.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns ProposalLink PDA seeds
+/// A Proposal can only be linked once and hence the first linked Proposal
+/// identifies the link
+pub fn get_proposal_link_address_seeds(first_proposal: &Pubkey) -> [&[u8]; 2] {
+    [b"proposal-link", first_proposal.as_ref()]
+}
+
+/// Returns ProposalLink PDA address
+pub fn get_proposal_link_address(program_id: &Pubkey, first_proposal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_proposal_link_address_seeds(first_proposal), program_id).0
+}
+
+/// Deserializes ProposalLink account and checks owner program and account
+/// type
+pub fn get_proposal_link_data(
+    program_id: &Pubkey,
+    proposal_link_info: &AccountInfo,
+) -> Result<ProposalLink, ProgramError> {
+    get_account_data::<ProposalLink>(program_id, proposal_link_info)
+}
+
+/// Deserializes ProposalLink account and asserts the given Proposal is one of
+/// the linked Proposals
+pub fn get_proposal_link_data_for_proposal(
+    program_id: &Pubkey,
+    proposal_link_info: &AccountInfo,
+    proposal: &Pubkey,
+) -> Result<ProposalLink, ProgramError> {
+    let proposal_link_data = get_proposal_link_data(program_id, proposal_link_info)?;
+
+    if !proposal_link_data.proposals.contains(proposal) {
+        return Err(GovernanceError::InvalidProposalForProposalLink.into());
+    }
+
+    Ok(proposal_link_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_proposal_link(proposals: usize, threshold: u8) -> ProposalLink {
+        ProposalLink {
+            account_type: GovernanceAccountType::ProposalLink,
+            proposals: (0..proposals).map(|_| Pubkey::new_unique()).collect(),
+            threshold,
+            state: ProposalLinkState::Committed,
+            resolved_at: Some(10),
+            reserved: [0; 64],
+        }
+    }
+
+    #[test]
+    fn test_max_size() {
+        let proposal_link = create_test_proposal_link(MAX_LINKED_PROPOSALS, 2);
+
+        let size = borsh::to_vec(&proposal_link).unwrap().len();
+
+        assert_eq!(proposal_link.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_assert_is_valid_link() {
+        assert!(create_test_proposal_link(2, 2)
+            .assert_is_valid_link()
+            .is_ok());
+        assert!(create_test_proposal_link(3, 1)
+            .assert_is_valid_link()
+            .is_ok());
+
+        for (proposals, threshold) in [(1, 1), (3, 0), (3, 4), (MAX_LINKED_PROPOSALS + 1, 2)] {
+            assert_eq!(
+                create_test_proposal_link(proposals, threshold).assert_is_valid_link(),
+                Err(GovernanceError::InvalidProposalLinkConfig.into())
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_state() {
+        // 2 of 3
+        let proposal_link = create_test_proposal_link(3, 2);
+
+        assert_eq!(
+            proposal_link.resolve_state(&[Some(true), None, Some(true)]),
+            Some(ProposalLinkState::Committed)
+        );
+        assert_eq!(
+            proposal_link.resolve_state(&[Some(true), Some(false), None]),
+            None
+        );
+        assert_eq!(
+            proposal_link.resolve_state(&[Some(false), Some(true), Some(false)]),
+            Some(ProposalLinkState::Aborted)
+        );
+
+        // 2 of 2
+        let proposal_link = create_test_proposal_link(2, 2);
+
+        assert_eq!(
+            proposal_link.resolve_state(&[None, Some(false)]),
+            Some(ProposalLinkState::Aborted)
+        );
+        assert_eq!(proposal_link.resolve_state(&[Some(true), None]), None);
+    }
+}
@@ -0,0 +1,174 @@
+//! Proposal token deposit account
+
+use {
+    crate::{error::GovernanceError, state::enums::GovernanceAccountType},
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
+        pubkey::Pubkey,
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// Proposal token deposit configuration of a Governance
+/// When the amount is set then creating a community Proposal requires a
+/// deposit of community tokens which is held in escrow until the Proposal is
+/// no longer active
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ProposalTokenDepositConfig {
+    /// The amount of community tokens deposited when a Proposal is created
+    /// 0 means no deposit is required
+    pub amount: u64,
+
+    /// The minimum participation, as a percentage of the max vote weight, a
+    /// defeated Proposal must reach for the deposit to be refunded
+    /// Deposits of Proposals defeated below the floor are forfeited to the
+    /// Governance
+    pub participation_floor_percentage: u8,
+}
+
+/// Asserts the given ProposalTokenDepositConfig is valid
+pub fn assert_is_valid_proposal_token_deposit_config(
+    config: &ProposalTokenDepositConfig,
+) -> Result<(), ProgramError> {
+    if config.participation_floor_percentage > 100 {
+        return Err(GovernanceError::InvalidProposalTokenDepositConfig.into());
+    }
+
+    Ok(())
+}
+
+/// Proposal token deposit account
+/// The account records the community tokens held in escrow for a Proposal
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ProposalTokenDeposit {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The Proposal the deposit belongs to
+    pub proposal: Pubkey,
+
+    /// The owner of the token account the deposit was transferred from
+    pub depositor: Pubkey,
+
+    /// The account which paid for the deposit and escrow accounts rent
+    pub deposit_payer: Pubkey,
+
+    /// The amount of community tokens held in escrow
+    pub amount: u64,
+
+    /// The participation floor at the time the deposit was made
+    pub participation_floor_percentage: u8,
+
+    /// Reserved
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for ProposalTokenDeposit {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 32 + 8 + 1 + 64)
+    }
+}
+
+impl IsInitialized for ProposalTokenDeposit {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::ProposalTokenDeposit
+    }
+}
+
+/// Returns ProposalTokenDeposit PDA seeds
+pub fn get_proposal_token_deposit_address_seeds(proposal: &Pubkey) -> [&[u8]; 2] {
+    [b"proposal-token-deposit", proposal.as_ref()]
+}
+
+/// Returns ProposalTokenDeposit PDA address
+pub fn get_proposal_token_deposit_address(program_id: &Pubkey, proposal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_proposal_token_deposit_address_seeds(proposal),
+        program_id,
+    )
+    .0
+}
+
+/// Returns ProposalTokenDeposit escrow token account PDA seeds
+/// Note: The escrow token account is owned by the ProposalTokenDeposit PDA
+pub fn get_proposal_token_deposit_escrow_address_seeds(proposal: &Pubkey) -> [&[u8]; 2] {
+    [b"proposal-token-deposit-escrow", proposal.as_ref()]
+}
+
+/// Returns ProposalTokenDeposit escrow token account PDA address
+pub fn get_proposal_token_deposit_escrow_address(program_id: &Pubkey, proposal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_proposal_token_deposit_escrow_address_seeds(proposal),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes ProposalTokenDeposit account and checks owner program and
+/// account type
+pub fn get_proposal_token_deposit_data(
+    program_id: &Pubkey,
+    proposal_token_deposit_info: &AccountInfo,
+) -> Result<ProposalTokenDeposit, ProgramError> {
+    get_account_data::<ProposalTokenDeposit>(program_id, proposal_token_deposit_info)
+}
+
+/// Deserializes ProposalTokenDeposit account
+/// 1) Checks owner program and account type
+/// 2) Asserts it belongs to the given Proposal
+pub fn get_proposal_token_deposit_data_for_proposal(
+    program_id: &Pubkey,
+    proposal_token_deposit_info: &AccountInfo,
+    proposal: &Pubkey,
+) -> Result<ProposalTokenDeposit, ProgramError> {
+    let proposal_token_deposit_data =
+        get_proposal_token_deposit_data(program_id, proposal_token_deposit_info)?;
+
+    if proposal_token_deposit_data.proposal != *proposal {
+        return Err(GovernanceError::InvalidProposalForProposalTokenDeposit.into());
+    }
+
+    Ok(proposal_token_deposit_data)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_max_size() {
+        // Arrange
+        let proposal_token_deposit_data = ProposalTokenDeposit {
+            account_type: GovernanceAccountType::ProposalTokenDeposit,
+            proposal: Pubkey::new_unique(),
+            depositor: Pubkey::new_unique(),
+            deposit_payer: Pubkey::new_unique(),
+            amount: 10,
+            participation_floor_percentage: 5,
+            reserved: [0; 64],
+        };
+
+        // Act
+        let size = borsh::to_vec(&proposal_token_deposit_data).unwrap().len();
+
+        // Assert
+        assert_eq!(proposal_token_deposit_data.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_config_size() {
+        // Arrange
+        let config = ProposalTokenDepositConfig {
+            amount: 10,
+            participation_floor_percentage: 5,
+        };
+
+        // Act
+        let size = borsh::to_vec(&config).unwrap().len();
+
+        // Assert
+        assert_eq!(size, 9);
+    }
+}
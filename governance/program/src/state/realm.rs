@@ -6,7 +6,9 @@ use {
         state::{
             enums::{GovernanceAccountType, MintMaxVoterWeightSource},
             legacy::RealmV1,
-            realm_config::{get_realm_config_data_for_realm, GoverningTokenType},
+            realm_config::{
+                get_realm_config_data_for_realm, GoverningTokenType, VoteExtensionConfig,
+            },
             token_owner_record::get_token_owner_record_data_for_realm,
             vote_record::VoteKind,
         },
@@ -16,6 +18,7 @@ use {
     borsh::{io::Write, BorshDeserialize, BorshSchema, BorshSerialize},
     solana_program::{
         account_info::{next_account_info, AccountInfo},
+        clock::Slot,
         program_error::ProgramError,
         program_pack::IsInitialized,
         pubkey::Pubkey,
@@ -28,7 +31,8 @@ use {
 };
 
 /// SetRealmConfigItem instruction arguments to set a single Realm config item
-/// Note: In the current version only TokenOwnerRecordLockAuthority is supported
+/// Note: In the current version only TokenOwnerRecordLockAuthority,
+/// VoteExtension and AddinRegistryEntry are supported
 /// Eventually all Realm config items should be supported for single config item
 /// change
 #[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -45,6 +49,30 @@ pub enum SetRealmConfigItemArgs {
         #[allow(dead_code)]
         authority: Pubkey,
     },
+
+    /// Set the voting time extension applied when a late vote changes the
+    /// leading outcome of a Proposal
+    VoteExtension {
+        /// The voting time extension config
+        #[allow(dead_code)]
+        config: VoteExtensionConfig,
+    },
+
+    /// Set an entry of the AddinRegistry of the Realm
+    /// Adding the first entry makes the Realm use the registry to validate the
+    /// addins set with SetRealmConfig
+    AddinRegistryEntry {
+        /// Action indicating whether to add or remove the addin
+        #[allow(dead_code)]
+        action: SetConfigItemActionType,
+        /// The addin program
+        #[allow(dead_code)]
+        program_id: Pubkey,
+        /// The min version of the addin program, ignored when the addin is
+        /// removed
+        #[allow(dead_code)]
+        min_version: Slot,
+    },
 }
 
 /// Realm Config instruction args
@@ -221,7 +249,22 @@ pub fn is_realm_account_type(account_type: &GovernanceAccountType) -> bool {
         | GovernanceAccountType::VoteRecordV2
         | GovernanceAccountType::ProgramMetadata
         | GovernanceAccountType::ProposalDeposit
-        | GovernanceAccountType::RequiredSignatory => false,
+        | GovernanceAccountType::RequiredSignatory
+        | GovernanceAccountType::ProposalLink
+        | GovernanceAccountType::ProposalTokenDeposit
+        | GovernanceAccountType::ProposalArchive
+        | GovernanceAccountType::ExecutionSimulationAttestation
+        | GovernanceAccountType::VoteIntent
+        | GovernanceAccountType::FundingRound
+        | GovernanceAccountType::FundingProject
+        | GovernanceAccountType::FundingContribution
+        | GovernanceAccountType::CouncilElection
+        | GovernanceAccountType::CouncilCandidate
+        | GovernanceAccountType::CouncilElectionVote
+        | GovernanceAccountType::CouncilSeat
+        | GovernanceAccountType::VoteSessionKey
+        | GovernanceAccountType::ProposalDependency
+        | GovernanceAccountType::AddinRegistry => false,
     }
 }
 
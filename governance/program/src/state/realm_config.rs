@@ -6,11 +6,12 @@ use {
             enums::GovernanceAccountType,
             realm::{GoverningTokenConfigArgs, RealmConfigArgs, RealmV2},
         },
-        tools::structs::Reserved110,
+        tools::structs::Reserved91,
     },
     borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
     solana_program::{
         account_info::{next_account_info, AccountInfo},
+        clock::UnixTimestamp,
         program_error::ProgramError,
         program_pack::IsInitialized,
         pubkey::Pubkey,
@@ -92,6 +93,49 @@ pub struct GoverningTokenConfig {
     pub lock_authorities: Vec<Pubkey>,
 }
 
+/// The term of the council elected in a CouncilElection
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct CouncilTerm {
+    /// The number of council seats of the term
+    /// Each seat adds 1 to the council max voter weight until the term ends
+    pub seats_count: u8,
+
+    /// Unix timestamp at which the term ends and its CouncilSeats expire
+    pub end_at: UnixTimestamp,
+}
+
+/// Configuration of the voting time extension applied when a late vote changes
+/// the leading outcome of a Proposal
+/// The extension is disabled when window_time is 0
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct VoteExtensionConfig {
+    /// Time window (in seconds) before the end of the voting time in which a
+    /// vote changing the leading outcome extends the voting time
+    pub window_time: u32,
+
+    /// Time (in seconds) by which the voting time is extended
+    pub extension_time: u32,
+
+    /// The max number of times the voting time of a Proposal can be extended
+    pub max_extensions_count: u8,
+}
+
+impl VoteExtensionConfig {
+    /// Returns true if the voting time extension is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.window_time > 0
+    }
+
+    /// Asserts the config is valid
+    pub fn assert_is_valid(&self) -> Result<(), ProgramError> {
+        if self.is_enabled() && (self.extension_time == 0 || self.max_extensions_count == 0) {
+            return Err(GovernanceError::InvalidVoteExtensionConfig.into());
+        }
+
+        Ok(())
+    }
+}
+
 /// RealmConfig account
 /// The account is an optional extension to RealmConfig stored on Realm account
 #[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -108,8 +152,20 @@ pub struct RealmConfigAccount {
     /// Council token config
     pub council_token_config: GoverningTokenConfig,
 
+    /// The current term of the council elected in a CouncilElection
+    pub council_term: CouncilTerm,
+
+    /// The voting time extension applied to the Proposals of the Realm
+    /// Note: This field reuses the space of the reserved field
+    pub vote_extension: VoteExtensionConfig,
+
+    /// Indicates whether the addins set with SetRealmConfig must be approved
+    /// in the AddinRegistry of the Realm
+    /// Note: This field reuses the space of the reserved field
+    pub use_addin_registry: bool,
+
     /// Reserved
-    pub reserved: Reserved110,
+    pub reserved: Reserved91,
 }
 
 impl AccountMaxSize for RealmConfigAccount {
@@ -117,7 +173,10 @@ impl AccountMaxSize for RealmConfigAccount {
         Some(
             1 + 32
                 + 75 * 2
-                + 110
+                + 9
+                + 9
+                + 1
+                + 91
                 + self.community_token_config.lock_authorities.len() * 32
                 + self.council_token_config.lock_authorities.len() * 32,
         )
@@ -148,6 +207,24 @@ impl RealmConfigAccount {
         Ok(token_config)
     }
 
+    /// Returns the number of active seats of the current council term for the
+    /// given governing_token_mint
+    /// The seats only count towards the voter weight of the council mint
+    pub fn get_active_council_seats_count(
+        &self,
+        realm_data: &RealmV2,
+        governing_token_mint: &Pubkey,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> u64 {
+        if Some(*governing_token_mint) == realm_data.config.council_mint
+            && current_unix_timestamp < self.council_term.end_at
+        {
+            self.council_term.seats_count as u64
+        } else {
+            0
+        }
+    }
+
     /// Returns mutable GoverningTokenConfig for the given governing_token_mint
     pub fn get_token_config_mut(
         &mut self,
@@ -319,7 +396,10 @@ pub fn get_realm_config_data_for_realm(
             realm: *realm,
             community_token_config: GoverningTokenConfig::default(),
             council_token_config: GoverningTokenConfig::default(),
-            reserved: Reserved110::default(),
+            council_term: CouncilTerm::default(),
+            vote_extension: VoteExtensionConfig::default(),
+            use_addin_registry: false,
+            reserved: Reserved91::default(),
         }
     } else {
         let realm_config_data = get_realm_config_data(program_id, realm_config_info)?;
@@ -406,7 +486,10 @@ mod test {
                 reserved: [0; 4],
                 lock_authorities: vec![],
             },
-            reserved: Reserved110::default(),
+            council_term: CouncilTerm::default(),
+            vote_extension: VoteExtensionConfig::default(),
+            use_addin_registry: false,
+            reserved: Reserved91::default(),
         };
 
         let size = borsh::to_vec(&realm_config).unwrap().len();
@@ -433,7 +516,10 @@ mod test {
                 reserved: [0; 4],
                 lock_authorities: vec![Pubkey::new_unique(), Pubkey::new_unique()],
             },
-            reserved: Reserved110::default(),
+            council_term: CouncilTerm::default(),
+            vote_extension: VoteExtensionConfig::default(),
+            use_addin_registry: false,
+            reserved: Reserved91::default(),
         };
 
         let size = borsh::to_vec(&realm_config).unwrap().len();
@@ -156,21 +156,22 @@ impl TokenOwnerRecordV2 {
         &self,
         governance_authority_info: &AccountInfo,
     ) -> Result<(), ProgramError> {
-        if governance_authority_info.is_signer {
-            if &self.governing_token_owner == governance_authority_info.key {
-                return Ok(());
-            }
-
-            if let Some(governance_delegate) = self.governance_delegate {
-                if &governance_delegate == governance_authority_info.key {
-                    return Ok(());
-                }
-            };
+        if governance_authority_info.is_signer
+            && self.is_token_owner_or_delegate(governance_authority_info.key)
+        {
+            return Ok(());
         }
 
         Err(GovernanceError::GoverningTokenOwnerOrDelegateMustSign.into())
     }
 
+    /// Checks whether the provided Governance Authority is the governing token
+    /// owner or the governance delegate
+    pub fn is_token_owner_or_delegate(&self, governance_authority: &Pubkey) -> bool {
+        self.governing_token_owner == *governance_authority
+            || self.governance_delegate == Some(*governance_authority)
+    }
+
     /// Asserts TokenOwner has enough tokens to be allowed to create proposal
     /// and doesn't have any outstanding proposals
     pub fn assert_can_create_proposal(
@@ -0,0 +1,364 @@
+//! Vote intent account
+
+use {
+    crate::{
+        error::GovernanceError,
+        state::{enums::GovernanceAccountType, vote_record::Vote},
+    },
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo,
+        ed25519_program,
+        hash::{hashv, Hash},
+        instruction::Instruction,
+        program_error::ProgramError,
+        program_pack::IsInitialized,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// Domain prefix of the messages signed off-chain to express a vote intent
+pub const VOTE_INTENT_MESSAGE_PREFIX: &[u8] = b"spl-governance:vote-intent";
+
+/// Size of ed25519 signature
+const SIGNATURE_BYTES: usize = 64;
+
+/// Size of ed25519 instruction header (num_signatures: u8, padding: u8)
+const ED25519_HEADER_BYTES: usize = 2;
+
+/// Size of ed25519 instruction signature offsets (7 x u16)
+const ED25519_SIGNATURE_OFFSETS_BYTES: usize = 14;
+
+/// Vote intent account
+/// The account stores a vote signed off-chain by the governing token owner or
+/// its delegate, which allows anybody, for example a vote aggregator, to relay
+/// the vote using CastVote without the voter's signature on the transaction
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct VoteIntent {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The Proposal the vote is intended for
+    pub proposal: Pubkey,
+
+    /// The TokenOwnerRecord of the voter
+    pub token_owner_record: Pubkey,
+
+    /// The governing token owner or delegate who signed the vote intent
+    pub governance_authority: Pubkey,
+
+    /// The hash of the intended Vote
+    pub vote_hash: [u8; 32],
+
+    /// The ed25519 signature of the vote intent message
+    pub signature: [u8; 64],
+
+    /// Reserved
+    pub reserved: [u8; 32],
+}
+
+impl AccountMaxSize for VoteIntent {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 32 + 32 + 64 + 32)
+    }
+}
+
+impl IsInitialized for VoteIntent {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::VoteIntent
+    }
+}
+
+impl VoteIntent {
+    /// Asserts the vote intent can be relayed by CastVote with the given vote
+    /// and governance authority
+    pub fn assert_can_cast_vote(
+        &self,
+        vote: &Vote,
+        governance_authority: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.vote_hash != get_vote_hash(vote).to_bytes()
+            || self.governance_authority != *governance_authority
+        {
+            return Err(GovernanceError::InvalidVoteIntent.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the hash of the Vote stored in VoteIntent
+pub fn get_vote_hash(vote: &Vote) -> Hash {
+    hashv(&[&borsh::to_vec(vote).unwrap()])
+}
+
+/// Returns the message the governing token owner or delegate signs off-chain
+/// to express the vote intent
+pub fn get_vote_intent_message(
+    program_id: &Pubkey,
+    proposal: &Pubkey,
+    token_owner_record: &Pubkey,
+    vote: &Vote,
+) -> Vec<u8> {
+    [
+        VOTE_INTENT_MESSAGE_PREFIX,
+        program_id.as_ref(),
+        proposal.as_ref(),
+        token_owner_record.as_ref(),
+        &borsh::to_vec(vote).unwrap(),
+    ]
+    .concat()
+}
+
+/// Signature verified by the ed25519 program
+#[derive(Debug, PartialEq, Eq)]
+pub struct Ed25519Signature {
+    /// The public key of the signer
+    pub public_key: Pubkey,
+
+    /// The signature
+    pub signature: [u8; 64],
+
+    /// The signed message
+    pub message: Vec<u8>,
+}
+
+/// Returns the single signature verified by the given ed25519 program
+/// instruction
+/// The signature, public key and message must be stored in the ed25519
+/// instruction data itself
+pub fn get_ed25519_signature(instruction: &Instruction) -> Result<Ed25519Signature, ProgramError> {
+    if instruction.program_id != ed25519_program::id() {
+        return Err(GovernanceError::InvalidVoteIntentSignature.into());
+    }
+
+    let data = &instruction.data;
+
+    if data.len() < ED25519_HEADER_BYTES + ED25519_SIGNATURE_OFFSETS_BYTES || data[0] != 1 {
+        return Err(GovernanceError::InvalidVoteIntentSignature.into());
+    }
+
+    let offsets = &data
+        [ED25519_HEADER_BYTES..ED25519_HEADER_BYTES + ED25519_SIGNATURE_OFFSETS_BYTES]
+        .chunks_exact(2)
+        .map(|offset| u16::from_le_bytes([offset[0], offset[1]]))
+        .collect::<Vec<u16>>();
+
+    let (signature_offset, signature_instruction_index) = (offsets[0] as usize, offsets[1]);
+    let (public_key_offset, public_key_instruction_index) = (offsets[2] as usize, offsets[3]);
+    let (message_offset, message_size, message_instruction_index) =
+        (offsets[4] as usize, offsets[5] as usize, offsets[6]);
+
+    // u16::MAX instruction index refers to the ed25519 instruction itself
+    if signature_instruction_index != u16::MAX
+        || public_key_instruction_index != u16::MAX
+        || message_instruction_index != u16::MAX
+    {
+        return Err(GovernanceError::InvalidVoteIntentSignature.into());
+    }
+
+    let get_slice = |offset: usize, size: usize| {
+        data.get(offset..offset.saturating_add(size))
+            .ok_or(GovernanceError::InvalidVoteIntentSignature)
+    };
+
+    Ok(Ed25519Signature {
+        public_key: Pubkey::try_from(get_slice(public_key_offset, PUBKEY_BYTES)?).unwrap(),
+        signature: get_slice(signature_offset, SIGNATURE_BYTES)?
+            .try_into()
+            .unwrap(),
+        message: get_slice(message_offset, message_size)?.to_vec(),
+    })
+}
+
+/// Returns VoteIntent PDA seeds
+pub fn get_vote_intent_address_seeds<'a>(
+    proposal: &'a Pubkey,
+    token_owner_record: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        b"vote-intent",
+        proposal.as_ref(),
+        token_owner_record.as_ref(),
+    ]
+}
+
+/// Returns VoteIntent PDA address
+pub fn get_vote_intent_address(
+    program_id: &Pubkey,
+    proposal: &Pubkey,
+    token_owner_record: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_vote_intent_address_seeds(proposal, token_owner_record),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes VoteIntent account and checks owner program and account type
+pub fn get_vote_intent_data(
+    program_id: &Pubkey,
+    vote_intent_info: &AccountInfo,
+) -> Result<VoteIntent, ProgramError> {
+    get_account_data::<VoteIntent>(program_id, vote_intent_info)
+}
+
+/// Deserializes VoteIntent account and asserts it's for the given Proposal and
+/// TokenOwnerRecord
+pub fn get_vote_intent_data_for_proposal_and_token_owner_record(
+    program_id: &Pubkey,
+    vote_intent_info: &AccountInfo,
+    proposal: &Pubkey,
+    token_owner_record: &Pubkey,
+) -> Result<VoteIntent, ProgramError> {
+    let vote_intent_data = get_vote_intent_data(program_id, vote_intent_info)?;
+
+    if vote_intent_data.proposal != *proposal
+        || vote_intent_data.token_owner_record != *token_owner_record
+    {
+        return Err(GovernanceError::InvalidVoteIntent.into());
+    }
+
+    Ok(vote_intent_data)
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::state::vote_record::VoteChoice};
+
+    fn create_test_vote() -> Vote {
+        Vote::Approve(vec![VoteChoice {
+            rank: 0,
+            weight_percentage: 100,
+        }])
+    }
+
+    fn create_test_vote_intent() -> VoteIntent {
+        VoteIntent {
+            account_type: GovernanceAccountType::VoteIntent,
+            proposal: Pubkey::new_unique(),
+            token_owner_record: Pubkey::new_unique(),
+            governance_authority: Pubkey::new_unique(),
+            vote_hash: get_vote_hash(&create_test_vote()).to_bytes(),
+            signature: [1; 64],
+            reserved: [0; 32],
+        }
+    }
+
+    fn create_test_ed25519_instruction(
+        public_key: &Pubkey,
+        signature: &[u8; 64],
+        message: &[u8],
+    ) -> Instruction {
+        let public_key_offset = ED25519_HEADER_BYTES + ED25519_SIGNATURE_OFFSETS_BYTES;
+        let signature_offset = public_key_offset + PUBKEY_BYTES;
+        let message_offset = signature_offset + SIGNATURE_BYTES;
+
+        let mut data = vec![1, 0];
+        for offset in [
+            signature_offset as u16,
+            u16::MAX,
+            public_key_offset as u16,
+            u16::MAX,
+            message_offset as u16,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(public_key.as_ref());
+        data.extend_from_slice(signature);
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::id(),
+            accounts: vec![],
+            data,
+        }
+    }
+
+    #[test]
+    fn test_max_size() {
+        let vote_intent = create_test_vote_intent();
+
+        let size = borsh::to_vec(&vote_intent).unwrap().len();
+
+        assert_eq!(vote_intent.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_assert_can_cast_vote() {
+        let vote_intent = create_test_vote_intent();
+
+        assert_eq!(
+            vote_intent
+                .assert_can_cast_vote(&create_test_vote(), &vote_intent.governance_authority),
+            Ok(())
+        );
+        assert_eq!(
+            vote_intent.assert_can_cast_vote(&Vote::Deny, &vote_intent.governance_authority),
+            Err(GovernanceError::InvalidVoteIntent.into())
+        );
+        assert_eq!(
+            vote_intent.assert_can_cast_vote(&create_test_vote(), &Pubkey::new_unique()),
+            Err(GovernanceError::InvalidVoteIntent.into())
+        );
+    }
+
+    #[test]
+    fn test_get_ed25519_signature() {
+        let public_key = Pubkey::new_unique();
+        let message = get_vote_intent_message(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &create_test_vote(),
+        );
+
+        let instruction = create_test_ed25519_instruction(&public_key, &[7; 64], &message);
+
+        assert_eq!(
+            get_ed25519_signature(&instruction),
+            Ok(Ed25519Signature {
+                public_key,
+                signature: [7; 64],
+                message,
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_ed25519_signature_with_invalid_instruction_error() {
+        let instruction = create_test_ed25519_instruction(&Pubkey::new_unique(), &[7; 64], b"vote");
+
+        // Wrong program
+        let mut wrong_program_instruction = instruction.clone();
+        wrong_program_instruction.program_id = Pubkey::new_unique();
+
+        // Multiple signatures
+        let mut multiple_signatures_instruction = instruction.clone();
+        multiple_signatures_instruction.data[0] = 2;
+
+        // Message stored in another instruction
+        let mut external_message_instruction = instruction.clone();
+        external_message_instruction.data[14..16].copy_from_slice(&0u16.to_le_bytes());
+
+        // Truncated data
+        let mut truncated_instruction = instruction;
+        truncated_instruction.data.pop();
+
+        for instruction in [
+            wrong_program_instruction,
+            multiple_signatures_instruction,
+            external_message_instruction,
+            truncated_instruction,
+        ] {
+            assert_eq!(
+                get_ed25519_signature(&instruction),
+                Err(GovernanceError::InvalidVoteIntentSignature.into())
+            );
+        }
+    }
+}
@@ -6,7 +6,7 @@ use {
         state::{
             enums::GovernanceAccountType,
             legacy::{VoteRecordV1, VoteWeightV1},
-            proposal::ProposalV2,
+            proposal::{get_proposal_data, ProposalV2},
             realm::RealmV2,
             token_owner_record::TokenOwnerRecordV2,
         },
@@ -114,9 +114,14 @@ pub struct VoteRecordV2 {
     /// Voter's vote
     pub vote: Vote,
 
+    /// The order in which the vote was cast on the Proposal, starting from 1
+    /// 0 means the vote was cast before voter participation was recorded
+    /// Note: This field reuses the space of the reserved_v2 field
+    pub voter_number: u32,
+
     /// Reserved space for versions v2 and onwards
     /// Note: V1 accounts must be resized before using this space
-    pub reserved_v2: [u8; 8],
+    pub reserved_v2: [u8; 4],
 }
 
 impl AccountMaxSize for VoteRecordV2 {}
@@ -146,7 +151,7 @@ impl VoteRecordV2 {
 
             // If reserved_v2 is used it must be individually asses for v1 backward
             // compatibility impact
-            if self.reserved_v2 != [0; 8] {
+            if self.voter_number != 0 || self.reserved_v2 != [0; 4] {
                 panic!("Extended data not supported by VoteRecordV1")
             }
 
@@ -202,7 +207,8 @@ pub fn get_vote_record_data(
             is_relinquished: vote_record_data_v1.is_relinquished,
             voter_weight,
             vote,
-            reserved_v2: [0; 8],
+            voter_number: 0,
+            reserved_v2: [0; 4],
         });
     }
 
@@ -246,6 +252,72 @@ pub fn get_vote_record_data_for_proposal_and_token_owner_record(
     Ok(vote_record_data)
 }
 
+/// Participation of a voter in a Proposal once its voting is completed
+/// It can be consumed by external programs, for example to reward voters,
+/// without indexing all VoteRecords of the Proposal
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoterParticipation {
+    /// Proposal account
+    pub proposal: Pubkey,
+
+    /// The Governing Token Owner who cast the vote
+    pub governing_token_owner: Pubkey,
+
+    /// The voter number of the VoteRecord in the range 1..=votes_count
+    /// It can be used as an index into a bitmap of votes_count bits to track
+    /// the voters who have already been processed
+    pub voter_number: u32,
+
+    /// The weight of the vote
+    pub voter_weight: u64,
+
+    /// The kind of the vote
+    pub vote_kind: VoteKind,
+
+    /// The number of votes cast on the Proposal
+    pub votes_count: u32,
+
+    /// The number of voters whose votes counted towards the Proposal outcome
+    pub voters_count: u32,
+}
+
+/// Returns the participation of the voter of the given VoteRecord in the given
+/// Proposal
+/// 1) Asserts the Proposal voting is completed
+/// 2) Asserts the VoteRecord belongs to the Proposal and its participation was
+///    recorded
+pub fn get_voter_participation(
+    program_id: &Pubkey,
+    proposal_info: &AccountInfo,
+    vote_record_info: &AccountInfo,
+) -> Result<VoterParticipation, ProgramError> {
+    let proposal_data = get_proposal_data(program_id, proposal_info)?;
+
+    if proposal_data.voting_completed_at.is_none() {
+        return Err(GovernanceError::InvalidStateVotingNotCompleted.into());
+    }
+
+    let vote_record_data = get_vote_record_data(program_id, vote_record_info)?;
+
+    if vote_record_data.proposal != *proposal_info.key {
+        return Err(GovernanceError::InvalidProposalForVoterRecord.into());
+    }
+
+    if vote_record_data.voter_number == 0 {
+        return Err(GovernanceError::VoterParticipationNotRecorded.into());
+    }
+
+    Ok(VoterParticipation {
+        proposal: *proposal_info.key,
+        governing_token_owner: vote_record_data.governing_token_owner,
+        voter_number: vote_record_data.voter_number,
+        voter_weight: vote_record_data.voter_weight,
+        vote_kind: get_vote_kind(&vote_record_data.vote),
+        votes_count: proposal_data.votes_count,
+        voters_count: proposal_data.voters_count,
+    })
+}
+
 /// Returns VoteRecord PDA seeds
 pub fn get_vote_record_address_seeds<'a>(
     proposal: &'a Pubkey,
@@ -0,0 +1,158 @@
+//! Vote session key account
+
+use {
+    crate::{error::GovernanceError, state::enums::GovernanceAccountType},
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, clock::Slot, program_error::ProgramError,
+        program_pack::IsInitialized, pubkey::Pubkey,
+    },
+    spl_governance_tools::account::{get_account_data, AccountMaxSize},
+};
+
+/// Vote session key account
+/// The account authorizes an ephemeral session key to cast and relinquish
+/// votes on behalf of the governing token owner or its delegate until the
+/// expiry slot
+/// The session key can't be used to withdraw governing tokens or to change
+/// the governance delegate
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct VoteSessionKey {
+    /// Governance account type
+    pub account_type: GovernanceAccountType,
+
+    /// The TokenOwnerRecord the session key votes for
+    pub token_owner_record: Pubkey,
+
+    /// The governing token owner or delegate who authorized the session key
+    pub authority: Pubkey,
+
+    /// The ephemeral session key
+    pub session_key: Pubkey,
+
+    /// The slot after which the session key can no longer be used
+    pub expiry_slot: Slot,
+
+    /// Reserved
+    pub reserved: [u8; 32],
+}
+
+impl AccountMaxSize for VoteSessionKey {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 32 + 8 + 32)
+    }
+}
+
+impl IsInitialized for VoteSessionKey {
+    fn is_initialized(&self) -> bool {
+        self.account_type == GovernanceAccountType::VoteSessionKey
+    }
+}
+
+impl VoteSessionKey {
+    /// Asserts the session key can be used to vote at the given slot
+    pub fn assert_can_vote(&self, session_key: &Pubkey, slot: Slot) -> Result<(), ProgramError> {
+        if self.session_key != *session_key {
+            return Err(GovernanceError::InvalidVoteSessionKey.into());
+        }
+
+        if slot > self.expiry_slot {
+            return Err(GovernanceError::VoteSessionKeyExpired.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns VoteSessionKey PDA seeds
+pub fn get_vote_session_key_address_seeds<'a>(
+    token_owner_record: &'a Pubkey,
+    session_key: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        b"vote-session-key",
+        token_owner_record.as_ref(),
+        session_key.as_ref(),
+    ]
+}
+
+/// Returns VoteSessionKey PDA address
+pub fn get_vote_session_key_address(
+    program_id: &Pubkey,
+    token_owner_record: &Pubkey,
+    session_key: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_vote_session_key_address_seeds(token_owner_record, session_key),
+        program_id,
+    )
+    .0
+}
+
+/// Deserializes VoteSessionKey account and checks owner program and account
+/// type
+pub fn get_vote_session_key_data(
+    program_id: &Pubkey,
+    vote_session_key_info: &AccountInfo,
+) -> Result<VoteSessionKey, ProgramError> {
+    get_account_data::<VoteSessionKey>(program_id, vote_session_key_info)
+}
+
+/// Deserializes VoteSessionKey account and asserts it's for the given
+/// TokenOwnerRecord
+pub fn get_vote_session_key_data_for_token_owner_record(
+    program_id: &Pubkey,
+    vote_session_key_info: &AccountInfo,
+    token_owner_record: &Pubkey,
+) -> Result<VoteSessionKey, ProgramError> {
+    let vote_session_key_data = get_vote_session_key_data(program_id, vote_session_key_info)?;
+
+    if vote_session_key_data.token_owner_record != *token_owner_record {
+        return Err(GovernanceError::InvalidVoteSessionKey.into());
+    }
+
+    Ok(vote_session_key_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_vote_session_key() -> VoteSessionKey {
+        VoteSessionKey {
+            account_type: GovernanceAccountType::VoteSessionKey,
+            token_owner_record: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            session_key: Pubkey::new_unique(),
+            expiry_slot: 100,
+            reserved: [0; 32],
+        }
+    }
+
+    #[test]
+    fn test_max_size() {
+        let vote_session_key = create_test_vote_session_key();
+
+        let size = borsh::to_vec(&vote_session_key).unwrap().len();
+
+        assert_eq!(vote_session_key.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_assert_can_vote() {
+        let vote_session_key = create_test_vote_session_key();
+
+        assert_eq!(
+            vote_session_key.assert_can_vote(&vote_session_key.session_key, 100),
+            Ok(())
+        );
+        assert_eq!(
+            vote_session_key.assert_can_vote(&vote_session_key.session_key, 101),
+            Err(GovernanceError::VoteSessionKeyExpired.into())
+        );
+        assert_eq!(
+            vote_session_key.assert_can_vote(&Pubkey::new_unique(), 100),
+            Err(GovernanceError::InvalidVoteSessionKey.into())
+        );
+    }
+}
@@ -254,6 +254,54 @@ pub fn burn_spl_tokens_signed<'a>(
     Ok(())
 }
 
+/// Closes SPL Token account owned by the provided PDA authority with seeds and
+/// transfers its lamports to the destination account
+pub fn close_spl_token_account_signed<'a>(
+    token_account_info: &AccountInfo<'a>,
+    destination_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    authority_seeds: &[&[u8]],
+    program_id: &Pubkey,
+    spl_token_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let (authority_address, bump_seed) = Pubkey::find_program_address(authority_seeds, program_id);
+
+    if authority_address != *authority_info.key {
+        msg!(
+            "Close SPL Token Account with Authority PDA: {:?} was requested while PDA: {:?} was expected",
+            authority_info.key,
+            authority_address
+        );
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let close_account_ix = spl_token::instruction::close_account(
+        &spl_token::id(),
+        token_account_info.key,
+        destination_info.key,
+        authority_info.key,
+        &[],
+    )
+    .unwrap();
+
+    let mut signers_seeds = authority_seeds.to_vec();
+    let bump = &[bump_seed];
+    signers_seeds.push(bump);
+
+    invoke_signed(
+        &close_account_ix,
+        &[
+            spl_token_info.clone(),
+            token_account_info.clone(),
+            destination_info.clone(),
+            authority_info.clone(),
+        ],
+        &[&signers_seeds[..]],
+    )?;
+
+    Ok(())
+}
+
 /// Asserts the given account_info represents a valid SPL Token account which is
 /// initialized and belongs to spl_token program
 pub fn assert_is_valid_spl_token_account(account_info: &AccountInfo) -> Result<(), ProgramError> {
@@ -344,6 +392,17 @@ pub fn get_spl_token_owner(token_account_info: &AccountInfo) -> Result<Pubkey, P
     Ok(Pubkey::new_from_array(*owner_data))
 }
 
+/// Computationally cheap method to get amount from a token account
+/// It reads amount without deserializing full account data
+pub fn get_spl_token_amount(token_account_info: &AccountInfo) -> Result<u64, ProgramError> {
+    assert_is_valid_spl_token_account(token_account_info)?;
+
+    // TokeAccount layout:   mint(32), owner(32), amount(8)
+    let data = token_account_info.try_borrow_data()?;
+    let amount_data = array_ref![data, 64, 8];
+    Ok(u64::from_le_bytes(*amount_data))
+}
+
 /// Computationally cheap method to just get supply from a mint without
 /// unpacking the whole object
 pub fn get_spl_token_mint_supply(mint_info: &AccountInfo) -> Result<u64, ProgramError> {
@@ -23,23 +23,38 @@ impl Default for Reserved110 {
     }
 }
 
-/// Reserved 119 bytes
+/// Reserved 91 bytes
 #[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
-pub struct Reserved119 {
+pub struct Reserved91 {
     /// Reserved 64 bytes
     pub reserved64: [u8; 64],
-    /// Reserved 32 bytes
-    pub reserved32: [u8; 32],
-    /// Reserved 19 bytes
-    pub reserved23: [u8; 23],
+    /// Reserved 27 bytes
+    pub reserved27: [u8; 27],
 }
 
-impl Default for Reserved119 {
+impl Default for Reserved91 {
     fn default() -> Self {
         Self {
             reserved64: [0; 64],
-            reserved32: [0; 32],
-            reserved23: [0; 23],
+            reserved27: [0; 27],
+        }
+    }
+}
+
+/// Reserved 70 bytes
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct Reserved70 {
+    /// Reserved 64 bytes
+    pub reserved64: [u8; 64],
+    /// Reserved 6 bytes
+    pub reserved6: [u8; 6],
+}
+
+impl Default for Reserved70 {
+    fn default() -> Self {
+        Self {
+            reserved64: [0; 64],
+            reserved6: [0; 6],
         }
     }
 }
@@ -0,0 +1,297 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+
+use {
+    program_test::*,
+    solana_program_test::tokio,
+    spl_governance::{error::GovernanceError, state::enums::ProposalState},
+};
+
+#[tokio::test]
+async fn test_add_proposal_dependency() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let depends_on_proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let proposal_dependency_cookie = governance_test
+        .with_proposal_dependency(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &depends_on_proposal_cookie,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_dependency_account = governance_test
+        .get_proposal_dependency_account(&proposal_dependency_cookie.address)
+        .await;
+
+    assert_eq!(
+        proposal_dependency_cookie.account,
+        proposal_dependency_account
+    );
+
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(1, proposal_account.dependencies_count);
+}
+
+#[tokio::test]
+async fn test_add_dependency_on_draft_proposal_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let depends_on_proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .with_proposal_dependency(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &depends_on_proposal_cookie,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::InvalidStateCannotDependOnProposal.into()
+    );
+}
+
+#[tokio::test]
+async fn test_add_dependency_on_proposal_of_another_realm_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+    let realm_cookie2 = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let token_owner_record_cookie2 = governance_test
+        .with_community_token_deposit(&realm_cookie2)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie2 = governance_test
+        .with_governance(&realm_cookie2, &token_owner_record_cookie2)
+        .await
+        .unwrap();
+
+    let depends_on_proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie2, &mut governance_cookie2)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .with_proposal_dependency(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &depends_on_proposal_cookie,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::CannotDependOnProposalOfAnotherRealm.into()
+    );
+}
+
+#[tokio::test]
+async fn test_execute_proposal_with_dependency() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let governed_mint_cookie = governance_test.with_governed_mint(&governance_cookie).await;
+
+    let mut depends_on_proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let depends_on_proposal_transaction_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_cookie,
+            &mut depends_on_proposal_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal_by_owner(&depends_on_proposal_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let proposal_transaction_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_cookie,
+            &mut proposal_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .with_proposal_dependency(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &depends_on_proposal_cookie,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal_by_owner(&proposal_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(
+            &depends_on_proposal_cookie,
+            &token_owner_record_cookie,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::Yes)
+        .await
+        .unwrap();
+
+    // Advance timestamp past hold_up_time
+    governance_test
+        .advance_clock_by_min_timespan(
+            governance_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+    // Act
+    let err = governance_test
+        .execute_proposal_transaction_with_dependencies(
+            &proposal_cookie,
+            &proposal_transaction_cookie,
+            &[depends_on_proposal_cookie.address],
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::ProposalDependencyNotCompleted.into());
+
+    // Act
+    governance_test
+        .execute_proposal_transaction(
+            &depends_on_proposal_cookie,
+            &depends_on_proposal_transaction_cookie,
+        )
+        .await
+        .unwrap();
+
+    governance_test.advance_clock().await;
+
+    governance_test
+        .execute_proposal_transaction_with_dependencies(
+            &proposal_cookie,
+            &proposal_transaction_cookie,
+            &[depends_on_proposal_cookie.address],
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(ProposalState::Completed, proposal_account.state);
+}
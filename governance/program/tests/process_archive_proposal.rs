@@ -0,0 +1,325 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+
+use {
+    program_test::*,
+    solana_program_test::tokio,
+    spl_concurrent_merkle_tree::{hash::recompute, node::empty_node},
+    spl_governance::{
+        error::GovernanceError,
+        state::{
+            enums::{GovernanceAccountType, ProposalState},
+            proposal_archive::{get_archive_leaf, PROPOSAL_ARCHIVE_MAX_DEPTH},
+        },
+    },
+};
+
+#[tokio::test]
+async fn test_archive_proposal() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let native_treasury_cookie = governance_test
+        .with_native_treasury(&governance_cookie)
+        .await;
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let vote_record_cookie = governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::No)
+        .await
+        .unwrap();
+
+    governance_test
+        .relinquish_vote(&proposal_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .advance_clock_past_proposal_archive_retention_time()
+        .await;
+
+    let proposal_account = governance_test
+        .bench
+        .get_account(&proposal_cookie.address)
+        .await
+        .unwrap();
+
+    let vote_record_account = governance_test
+        .bench
+        .get_account(&vote_record_cookie.address)
+        .await
+        .unwrap();
+
+    let treasury_lamports = governance_test
+        .bench
+        .get_account(&native_treasury_cookie.address)
+        .await
+        .unwrap()
+        .lamports;
+
+    // Act
+    let proposal_archive_address = governance_test
+        .with_proposal_archive(&proposal_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .archive_vote_records(&proposal_cookie, &[&vote_record_cookie])
+        .await
+        .unwrap();
+
+    governance_test
+        .archive_proposal(&proposal_cookie)
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_archive_account = governance_test
+        .get_proposal_archive_account(&proposal_archive_address)
+        .await;
+
+    assert_eq!(
+        GovernanceAccountType::ProposalArchive,
+        proposal_archive_account.account_type
+    );
+    assert_eq!(
+        governance_cookie.address,
+        proposal_archive_account.governance
+    );
+    assert_eq!(proposal_cookie.address, proposal_archive_account.proposal);
+    assert_eq!(
+        ProposalState::Defeated,
+        proposal_archive_account.proposal_state
+    );
+    assert_eq!(1, proposal_archive_account.vote_records_count);
+    assert!(proposal_archive_account.archived_at.is_some());
+
+    // The archived accounts can be proven against the archive root
+    let vote_record_leaf = get_archive_leaf(&vote_record_account.data);
+    let proposal_leaf = get_archive_leaf(&proposal_account.data);
+
+    let mut proof = vec![proposal_leaf];
+    proof.extend((1..PROPOSAL_ARCHIVE_MAX_DEPTH as u32).map(empty_node));
+
+    assert_eq!(
+        proposal_archive_account.root,
+        recompute(vote_record_leaf, &proof, 0)
+    );
+
+    assert!(governance_test
+        .bench
+        .get_account(&proposal_cookie.address)
+        .await
+        .is_none());
+
+    assert!(governance_test
+        .bench
+        .get_account(&vote_record_cookie.address)
+        .await
+        .is_none());
+
+    let treasury_account = governance_test
+        .bench
+        .get_account(&native_treasury_cookie.address)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        treasury_lamports + proposal_account.lamports + vote_record_account.lamports,
+        treasury_account.lamports
+    );
+}
+
+#[tokio::test]
+async fn test_create_proposal_archive_with_retention_time_not_elapsed_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::No)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .with_proposal_archive(&proposal_cookie)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::ProposalArchiveRetentionTimeNotElapsed.into()
+    );
+}
+
+#[tokio::test]
+async fn test_create_proposal_archive_for_voting_proposal_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .with_proposal_archive(&proposal_cookie)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::InvalidStateCannotArchiveProposal.into()
+    );
+}
+
+#[tokio::test]
+async fn test_archive_vote_records_with_unrelinquished_vote_record_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let vote_record_cookie = governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::No)
+        .await
+        .unwrap();
+
+    governance_test
+        .advance_clock_past_proposal_archive_retention_time()
+        .await;
+
+    governance_test
+        .with_proposal_archive(&proposal_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .archive_vote_records(&proposal_cookie, &[&vote_record_cookie])
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::CannotArchiveUnrelinquishedVoteRecord.into()
+    );
+}
+
+#[tokio::test]
+async fn test_archive_proposal_with_missing_vote_records_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::No)
+        .await
+        .unwrap();
+
+    governance_test
+        .advance_clock_past_proposal_archive_retention_time()
+        .await;
+
+    governance_test
+        .with_proposal_archive(&proposal_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .archive_proposal(&proposal_cookie)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::ProposalArchiveMissingVoteRecords.into()
+    );
+}
@@ -7,13 +7,18 @@ use {
     program_test::*,
     solana_program::pubkey::Pubkey,
     solana_program_test::tokio,
+    solana_sdk::signature::{Keypair, Signer},
     spl_governance::{
         error::GovernanceError,
+        instruction::set_governance_delegate,
         state::{
             enums::{MintMaxVoterWeightSource, ProposalState, VoteThreshold, VoteTipping},
-            vote_record::Vote,
+            realm::SetRealmConfigItemArgs,
+            realm_config::VoteExtensionConfig,
+            vote_record::{get_vote_record_address, Vote, VoteChoice},
         },
     },
+    spl_governance_tools::error::GovernanceToolsError,
 };
 
 #[tokio::test]
@@ -1507,3 +1512,461 @@ async fn test_cast_approve_vote_with_cannot_vote_in_cool_off_time_error() {
 
     assert_eq!(err, GovernanceError::VoteNotAllowedInCoolOffTime.into());
 }
+
+#[tokio::test]
+async fn test_cast_vote_with_vote_intent() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let vote = Vote::Approve(vec![VoteChoice {
+        rank: 0,
+        weight_percentage: 100,
+    }]);
+
+    let vote_intent_address = governance_test
+        .with_vote_intent(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &token_owner_record_cookie.token_owner,
+            vote.clone(),
+        )
+        .await
+        .unwrap();
+
+    // Act
+    governance_test
+        .relay_vote_with_vote_intent(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &token_owner_record_cookie.token_owner.pubkey(),
+            vote.clone(),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let vote_intent_account = governance_test
+        .get_vote_intent_account(&vote_intent_address)
+        .await;
+
+    assert_eq!(
+        vote_intent_account.governance_authority,
+        token_owner_record_cookie.token_owner.pubkey()
+    );
+
+    let vote_record_account = governance_test
+        .get_vote_record_account(&get_vote_record_address(
+            &governance_test.program_id,
+            &proposal_cookie.address,
+            &token_owner_record_cookie.address,
+        ))
+        .await;
+
+    assert_eq!(vote_record_account.vote, vote);
+
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(proposal_account.state, ProposalState::Succeeded);
+}
+
+#[tokio::test]
+async fn test_register_vote_intent_with_invalid_signer_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .with_vote_intent(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &Keypair::new(),
+            Vote::Deny,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::GoverningTokenOwnerOrDelegateMustSign.into()
+    );
+}
+
+#[tokio::test]
+async fn test_cast_vote_with_vote_intent_for_different_vote_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_vote_intent(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &token_owner_record_cookie.token_owner,
+            Vote::Deny,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .relay_vote_with_vote_intent(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &token_owner_record_cookie.token_owner.pubkey(),
+            Vote::Approve(vec![VoteChoice {
+                rank: 0,
+                weight_percentage: 100,
+            }]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::InvalidVoteIntent.into());
+}
+
+#[tokio::test]
+async fn test_cast_vote_without_signature_and_vote_intent_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .relay_vote_with_vote_intent(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &token_owner_record_cookie.token_owner.pubkey(),
+            Vote::Deny,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceToolsError::AccountDoesNotExist.into());
+}
+
+#[tokio::test]
+async fn test_cast_vote_with_vote_session_key() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let clock = governance_test.bench.get_clock().await;
+
+    let vote_session_key_cookie = governance_test
+        .with_vote_session_key(&token_owner_record_cookie, clock.slot + 100)
+        .await
+        .unwrap();
+
+    let vote = Vote::Approve(vec![VoteChoice {
+        rank: 0,
+        weight_percentage: 100,
+    }]);
+
+    // Act
+    governance_test
+        .cast_vote_with_vote_session_key(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &vote_session_key_cookie,
+            vote.clone(),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let vote_session_key_account = governance_test
+        .get_vote_session_key_account(&vote_session_key_cookie.address)
+        .await;
+
+    assert_eq!(
+        vote_session_key_account.authority,
+        token_owner_record_cookie.token_owner.pubkey()
+    );
+    assert_eq!(vote_session_key_account.expiry_slot, clock.slot + 100);
+
+    let vote_record_account = governance_test
+        .get_vote_record_account(&get_vote_record_address(
+            &governance_test.program_id,
+            &proposal_cookie.address,
+            &token_owner_record_cookie.address,
+        ))
+        .await;
+
+    assert_eq!(vote_record_account.vote, vote);
+    assert_eq!(
+        vote_record_account.governing_token_owner,
+        token_owner_record_cookie.token_owner.pubkey()
+    );
+
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(proposal_account.state, ProposalState::Succeeded);
+}
+
+#[tokio::test]
+async fn test_cast_vote_with_expired_vote_session_key_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let clock = governance_test.bench.get_clock().await;
+
+    let vote_session_key_cookie = governance_test
+        .with_vote_session_key(&token_owner_record_cookie, clock.slot)
+        .await
+        .unwrap();
+
+    governance_test.advance_clock().await;
+
+    // Act
+    let err = governance_test
+        .cast_vote_with_vote_session_key(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &vote_session_key_cookie,
+            Vote::Deny,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::VoteSessionKeyExpired.into());
+}
+
+#[tokio::test]
+async fn test_set_governance_delegate_with_vote_session_key_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let clock = governance_test.bench.get_clock().await;
+
+    let vote_session_key_cookie = governance_test
+        .with_vote_session_key(&token_owner_record_cookie, clock.slot + 100)
+        .await
+        .unwrap();
+
+    let set_governance_delegate_ix = set_governance_delegate(
+        &governance_test.program_id,
+        &vote_session_key_cookie.session_key.pubkey(),
+        &realm_cookie.address,
+        &realm_cookie.account.community_mint,
+        &token_owner_record_cookie.token_owner.pubkey(),
+        &Some(vote_session_key_cookie.session_key.pubkey()),
+    );
+
+    // Act
+    let err = governance_test
+        .bench
+        .process_transaction(
+            &[set_governance_delegate_ix],
+            Some(&[&vote_session_key_cookie.session_key]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::GoverningTokenOwnerOrDelegateMustSign.into()
+    );
+}
+
+#[tokio::test]
+async fn test_cast_late_vote_changing_leading_outcome_extends_voting() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    // Extend voting by 100 seconds once if the leading outcome changes at any
+    // time of the voting
+    let args = SetRealmConfigItemArgs::VoteExtension {
+        config: VoteExtensionConfig {
+            window_time: 10,
+            extension_time: 100,
+            max_extensions_count: 1,
+        },
+    };
+
+    governance_test
+        .set_realm_config_item(&realm_cookie, args)
+        .await
+        .unwrap();
+
+    let mut governance_config = governance_test.get_default_governance_config();
+    governance_config.community_vote_tipping = VoteTipping::Disabled;
+
+    let token_owner_record_cookie1 = governance_test
+        .with_community_token_deposit_amount(&realm_cookie, 100)
+        .await
+        .unwrap();
+
+    let token_owner_record_cookie2 = governance_test
+        .with_community_token_deposit_amount(&realm_cookie, 200)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance_using_config(
+            &realm_cookie,
+            &token_owner_record_cookie1,
+            &governance_config,
+        )
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie1, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    governance_test
+        .with_cast_yes_no_vote(
+            &proposal_cookie,
+            &token_owner_record_cookie1,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(1, proposal_account.voting_extensions_count);
+    assert_eq!(100, proposal_account.voting_extended_time);
+
+    // Act: the max number of extensions has been reached
+    governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie2, YesNoVote::No)
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(1, proposal_account.voting_extensions_count);
+    assert_eq!(100, proposal_account.voting_extended_time);
+    assert_eq!(ProposalState::Voting, proposal_account.state);
+}
@@ -0,0 +1,403 @@
+#![cfg(feature = "test-sbf")]
+
+use solana_program_test::*;
+
+mod program_test;
+
+use {
+    program_test::*,
+    spl_governance::{
+        error::GovernanceError,
+        state::{enums::GovernanceAccountType, funding_round::FundingRoundState},
+    },
+};
+
+#[tokio::test]
+async fn test_claim_funding_match() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie1 = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie1)
+        .await
+        .unwrap();
+
+    let funding_round_cookie = governance_test
+        .with_funding_round(
+            &realm_cookie,
+            &mut governance_cookie,
+            &token_owner_record_cookie1,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let token_owner_record_cookie2 = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let funding_project_cookie1 = governance_test
+        .with_funding_project(&funding_round_cookie)
+        .await
+        .unwrap();
+
+    let funding_project_cookie2 = governance_test
+        .with_funding_project(&funding_round_cookie)
+        .await
+        .unwrap();
+
+    // Project 1 is supported by two contributors and project 2 by a single
+    // contributor with the same total amount
+    governance_test
+        .contribute_to_funding_project(
+            &funding_round_cookie,
+            &funding_project_cookie1,
+            &token_owner_record_cookie1,
+            100,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .contribute_to_funding_project(
+            &funding_round_cookie,
+            &funding_project_cookie1,
+            &token_owner_record_cookie2,
+            100,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .contribute_to_funding_project(
+            &funding_round_cookie,
+            &funding_project_cookie2,
+            &token_owner_record_cookie1,
+            200,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .fund_funding_round(&funding_round_cookie, 1_000)
+        .await;
+
+    governance_test
+        .advance_clock_past_timestamp(funding_round_cookie.config.contributions_end_at)
+        .await;
+
+    governance_test
+        .finalize_funding_round(&funding_round_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    governance_test
+        .claim_funding_match(&funding_round_cookie, &funding_project_cookie1)
+        .await
+        .unwrap();
+
+    governance_test
+        .claim_funding_match(&funding_round_cookie, &funding_project_cookie2)
+        .await
+        .unwrap();
+
+    // Assert
+    let funding_round_account = governance_test
+        .get_funding_round_account(&funding_round_cookie.address)
+        .await;
+
+    assert_eq!(
+        GovernanceAccountType::FundingRound,
+        funding_round_account.account_type
+    );
+    assert_eq!(FundingRoundState::Finalized, funding_round_account.state);
+    assert_eq!(2, funding_round_account.projects_count);
+    assert_eq!(1_000, funding_round_account.matching_pool_amount);
+
+    let funding_project_account1 = governance_test
+        .get_funding_project_account(&funding_project_cookie1.address)
+        .await;
+
+    assert_eq!(2, funding_project_account1.contributors_count);
+    assert_eq!(200, funding_project_account1.contributions_amount);
+    assert_eq!(1_000, funding_project_account1.matching_amount);
+    assert!(funding_project_account1.matching_claimed);
+
+    let funding_project_account2 = governance_test
+        .get_funding_project_account(&funding_project_cookie2.address)
+        .await;
+
+    assert_eq!(1, funding_project_account2.contributors_count);
+    assert_eq!(0, funding_project_account2.matching_amount);
+    assert!(funding_project_account2.matching_claimed);
+
+    let recipient_account1 = governance_test
+        .get_token_account(&funding_project_cookie1.recipient)
+        .await;
+
+    assert_eq!(1_200, recipient_account1.amount);
+
+    let recipient_account2 = governance_test
+        .get_token_account(&funding_project_cookie2.recipient)
+        .await;
+
+    assert_eq!(200, recipient_account2.amount);
+}
+
+#[tokio::test]
+async fn test_claim_funding_match_with_contribution_cap() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie1 = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie1)
+        .await
+        .unwrap();
+
+    let funding_round_cookie = governance_test
+        .with_funding_round(
+            &realm_cookie,
+            &mut governance_cookie,
+            &token_owner_record_cookie1,
+            100,
+        )
+        .await
+        .unwrap();
+
+    let token_owner_record_cookie2 = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let funding_project_cookie1 = governance_test
+        .with_funding_project(&funding_round_cookie)
+        .await
+        .unwrap();
+
+    let funding_project_cookie2 = governance_test
+        .with_funding_project(&funding_round_cookie)
+        .await
+        .unwrap();
+
+    // Only 100 of the 400 contributed to project 1 by the first contributor
+    // count towards the matching
+    governance_test
+        .contribute_to_funding_project(
+            &funding_round_cookie,
+            &funding_project_cookie1,
+            &token_owner_record_cookie1,
+            400,
+        )
+        .await
+        .unwrap();
+
+    for funding_project_cookie in [&funding_project_cookie1, &funding_project_cookie2] {
+        governance_test
+            .contribute_to_funding_project(
+                &funding_round_cookie,
+                funding_project_cookie,
+                &token_owner_record_cookie2,
+                100,
+            )
+            .await
+            .unwrap();
+    }
+
+    governance_test
+        .contribute_to_funding_project(
+            &funding_round_cookie,
+            &funding_project_cookie2,
+            &token_owner_record_cookie1,
+            100,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .fund_funding_round(&funding_round_cookie, 1_000)
+        .await;
+
+    governance_test
+        .advance_clock_past_timestamp(funding_round_cookie.config.contributions_end_at)
+        .await;
+
+    governance_test
+        .finalize_funding_round(&funding_round_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    governance_test
+        .claim_funding_match(&funding_round_cookie, &funding_project_cookie1)
+        .await
+        .unwrap();
+
+    governance_test
+        .claim_funding_match(&funding_round_cookie, &funding_project_cookie2)
+        .await
+        .unwrap();
+
+    // Assert
+    let funding_project_account1 = governance_test
+        .get_funding_project_account(&funding_project_cookie1.address)
+        .await;
+
+    assert_eq!(500, funding_project_account1.contributions_amount);
+    assert_eq!(200, funding_project_account1.capped_contributions_amount);
+    assert_eq!(500, funding_project_account1.matching_amount);
+
+    let funding_project_account2 = governance_test
+        .get_funding_project_account(&funding_project_cookie2.address)
+        .await;
+
+    assert_eq!(500, funding_project_account2.matching_amount);
+
+    let err = governance_test
+        .claim_funding_match(&funding_round_cookie, &funding_project_cookie1)
+        .await
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        GovernanceError::FundingProjectMatchingAlreadyClaimed.into()
+    );
+}
+
+#[tokio::test]
+async fn test_contribute_to_funding_project_after_contributions_ended_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let funding_round_cookie = governance_test
+        .with_funding_round(
+            &realm_cookie,
+            &mut governance_cookie,
+            &token_owner_record_cookie,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let funding_project_cookie = governance_test
+        .with_funding_project(&funding_round_cookie)
+        .await
+        .unwrap();
+
+    // The round can't be finalized while contributions are accepted
+    let finalize_err = governance_test
+        .finalize_funding_round(&funding_round_cookie)
+        .await
+        .err()
+        .unwrap();
+
+    governance_test
+        .advance_clock_past_timestamp(funding_round_cookie.config.contributions_end_at)
+        .await;
+
+    // Act
+    let err = governance_test
+        .contribute_to_funding_project(
+            &funding_round_cookie,
+            &funding_project_cookie,
+            &token_owner_record_cookie,
+            100,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        finalize_err,
+        GovernanceError::FundingRoundContributionsNotEnded.into()
+    );
+    assert_eq!(
+        err,
+        GovernanceError::FundingRoundNotAcceptingContributions.into()
+    );
+}
+
+#[tokio::test]
+async fn test_finalize_funding_round_without_contributions_returns_matching_pool() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let funding_round_cookie = governance_test
+        .with_funding_round(
+            &realm_cookie,
+            &mut governance_cookie,
+            &token_owner_record_cookie,
+            0,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .fund_funding_round(&funding_round_cookie, 1_000)
+        .await;
+
+    governance_test
+        .advance_clock_past_timestamp(funding_round_cookie.config.contributions_end_at)
+        .await;
+
+    // Act
+    let governance_destination = governance_test
+        .finalize_funding_round(&funding_round_cookie)
+        .await
+        .unwrap();
+
+    // Assert
+    let funding_round_account = governance_test
+        .get_funding_round_account(&funding_round_cookie.address)
+        .await;
+
+    assert_eq!(FundingRoundState::Finalized, funding_round_account.state);
+    assert_eq!(0, funding_round_account.matching_pool_amount);
+
+    let governance_destination_account = governance_test
+        .get_token_account(&governance_destination)
+        .await;
+
+    assert_eq!(1_000, governance_destination_account.amount);
+}
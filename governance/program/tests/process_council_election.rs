@@ -0,0 +1,363 @@
+#![cfg(feature = "test-sbf")]
+
+use solana_program_test::*;
+
+mod program_test;
+
+use {
+    program_test::*,
+    solana_sdk::signature::Signer,
+    spl_governance::{
+        error::GovernanceError,
+        state::{
+            council_election::CouncilElectionState,
+            enums::{ProposalState, VoteThreshold},
+            vote_record::{Vote, VoteChoice},
+        },
+    },
+};
+
+#[tokio::test]
+async fn test_finalize_council_election() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let council_election_cookie = governance_test
+        .with_council_election(&realm_cookie, 2)
+        .await
+        .unwrap();
+
+    let council_candidate_cookie1 = governance_test
+        .with_council_candidate(&council_election_cookie)
+        .await
+        .unwrap();
+
+    let council_candidate_cookie2 = governance_test
+        .with_council_candidate(&council_election_cookie)
+        .await
+        .unwrap();
+
+    let council_candidate_cookie3 = governance_test
+        .with_council_candidate(&council_election_cookie)
+        .await
+        .unwrap();
+
+    // Candidate 3 wins outright and candidates 1 and 2 tie for the second seat
+    // which goes to the candidate registered first
+    for council_candidate_cookie in [
+        &council_candidate_cookie1,
+        &council_candidate_cookie2,
+        &council_candidate_cookie3,
+        &council_candidate_cookie3,
+    ] {
+        let token_owner_record_cookie = governance_test
+            .with_community_token_deposit(&realm_cookie)
+            .await
+            .unwrap();
+
+        governance_test
+            .cast_council_election_vote(
+                &council_election_cookie,
+                council_candidate_cookie,
+                &token_owner_record_cookie,
+            )
+            .await
+            .unwrap();
+    }
+
+    governance_test
+        .advance_clock_past_timestamp(council_election_cookie.config.voting_end_at)
+        .await;
+
+    // Act
+    governance_test
+        .finalize_council_election(
+            &council_election_cookie,
+            &[
+                &council_candidate_cookie1,
+                &council_candidate_cookie2,
+                &council_candidate_cookie3,
+            ],
+        )
+        .await
+        .unwrap();
+
+    let council_seat_address = governance_test
+        .claim_council_seat(&council_election_cookie, &council_candidate_cookie1)
+        .await
+        .unwrap();
+
+    // Assert
+    let council_election_account = governance_test
+        .get_council_election_account(&council_election_cookie.address)
+        .await;
+
+    assert_eq!(
+        CouncilElectionState::Finalized,
+        council_election_account.state
+    );
+    assert_eq!(3, council_election_account.candidates_count);
+    assert_eq!(2, council_election_account.elected_count);
+
+    let council_candidate_account1 = governance_test
+        .get_council_candidate_account(&council_candidate_cookie1.address)
+        .await;
+
+    assert_eq!(100, council_candidate_account1.votes);
+    assert!(council_candidate_account1.is_elected);
+    assert!(council_candidate_account1.seat_claimed);
+
+    let council_candidate_account2 = governance_test
+        .get_council_candidate_account(&council_candidate_cookie2.address)
+        .await;
+
+    assert!(!council_candidate_account2.is_elected);
+
+    let council_candidate_account3 = governance_test
+        .get_council_candidate_account(&council_candidate_cookie3.address)
+        .await;
+
+    assert_eq!(200, council_candidate_account3.votes);
+    assert!(council_candidate_account3.is_elected);
+    assert!(!council_candidate_account3.seat_claimed);
+
+    let council_seat_account = governance_test
+        .get_council_seat_account(&council_seat_address)
+        .await;
+
+    assert_eq!(
+        council_candidate_cookie1.candidate.pubkey(),
+        council_seat_account.member
+    );
+    assert_eq!(
+        council_election_cookie.config.term_end_at,
+        council_seat_account.term_end_at
+    );
+
+    let realm_config_account = governance_test
+        .get_realm_config_account(&realm_cookie.realm_config.address)
+        .await;
+
+    assert_eq!(2, realm_config_account.council_term.seats_count);
+    assert_eq!(
+        council_election_cookie.config.term_end_at,
+        realm_config_account.council_term.end_at
+    );
+
+    let err = governance_test
+        .claim_council_seat(&council_election_cookie, &council_candidate_cookie2)
+        .await
+        .err()
+        .unwrap();
+
+    assert_eq!(err, GovernanceError::CouncilCandidateNotElected.into());
+}
+
+#[tokio::test]
+async fn test_cast_council_election_vote_locks_token_owner_record() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let council_election_cookie = governance_test
+        .with_council_election(&realm_cookie, 1)
+        .await
+        .unwrap();
+
+    let council_candidate_cookie1 = governance_test
+        .with_council_candidate(&council_election_cookie)
+        .await
+        .unwrap();
+
+    let council_candidate_cookie2 = governance_test
+        .with_council_candidate(&council_election_cookie)
+        .await
+        .unwrap();
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .cast_council_election_vote(
+            &council_election_cookie,
+            &council_candidate_cookie1,
+            &token_owner_record_cookie,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let vote_err = governance_test
+        .cast_council_election_vote(
+            &council_election_cookie,
+            &council_candidate_cookie2,
+            &token_owner_record_cookie,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    let withdraw_err = governance_test
+        .withdraw_community_tokens(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        vote_err,
+        GovernanceError::CouncilElectionVoteAlreadyExists.into()
+    );
+    assert_eq!(withdraw_err, GovernanceError::TokenOwnerRecordLocked.into());
+
+    // The tokens can be withdrawn once the voting ended
+    governance_test
+        .advance_clock_past_timestamp(council_election_cookie.config.voting_end_at)
+        .await;
+
+    governance_test
+        .withdraw_community_tokens(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_finalize_council_election_with_missing_candidate_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let council_election_cookie = governance_test
+        .with_council_election(&realm_cookie, 1)
+        .await
+        .unwrap();
+
+    let council_candidate_cookie1 = governance_test
+        .with_council_candidate(&council_election_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_council_candidate(&council_election_cookie)
+        .await
+        .unwrap();
+
+    // The election can't be finalized while the voting is in progress
+    let voting_err = governance_test
+        .finalize_council_election(&council_election_cookie, &[&council_candidate_cookie1])
+        .await
+        .err()
+        .unwrap();
+
+    governance_test
+        .advance_clock_past_timestamp(council_election_cookie.config.voting_end_at)
+        .await;
+
+    // Act
+    let err = governance_test
+        .finalize_council_election(&council_election_cookie, &[&council_candidate_cookie1])
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        voting_err,
+        GovernanceError::CouncilElectionVotingNotEnded.into()
+    );
+    assert_eq!(
+        err,
+        GovernanceError::InvalidCouncilCandidatesForFinalization.into()
+    );
+}
+
+#[tokio::test]
+async fn test_cast_vote_with_council_seat() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let council_election_cookie = governance_test
+        .with_council_election(&realm_cookie, 1)
+        .await
+        .unwrap();
+
+    let council_candidate_cookie = governance_test
+        .with_council_candidate(&council_election_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .advance_clock_past_timestamp(council_election_cookie.config.voting_end_at)
+        .await;
+
+    governance_test
+        .finalize_council_election(&council_election_cookie, &[&council_candidate_cookie])
+        .await
+        .unwrap();
+
+    let council_seat_address = governance_test
+        .claim_council_seat(&council_election_cookie, &council_candidate_cookie)
+        .await
+        .unwrap();
+
+    let proposal_owner_record_cookie = governance_test
+        .with_council_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_config = governance_test.get_default_governance_config();
+    governance_config.community_vote_threshold = VoteThreshold::Disabled;
+
+    let mut governance_cookie = governance_test
+        .with_governance_using_config(
+            &realm_cookie,
+            &proposal_owner_record_cookie,
+            &governance_config,
+        )
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&proposal_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // The council member holds a seat and no council tokens
+    let token_owner_record_cookie = governance_test
+        .with_token_owner_record_for_owner(
+            &realm_cookie,
+            &realm_cookie.account.config.council_mint.unwrap(),
+            council_candidate_cookie.candidate,
+        )
+        .await;
+
+    // Act
+    governance_test
+        .with_cast_vote_with_council_seat(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &council_seat_address,
+            Vote::Approve(vec![VoteChoice {
+                rank: 0,
+                weight_percentage: 100,
+            }]),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(ProposalState::Voting, proposal_account.state);
+    assert_eq!(1, proposal_account.options[0].vote_weight);
+}
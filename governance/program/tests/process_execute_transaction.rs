@@ -10,6 +10,7 @@ use {
         sysvar::clock,
     },
     solana_program_test::tokio,
+    solana_sdk::signature::{Keypair, Signer},
     spl_governance::{
         error::GovernanceError,
         state::enums::{ProposalState, TransactionExecutionStatus},
@@ -777,3 +778,413 @@ async fn test_execute_transaction_with_create_proposal_and_execute_in_single_slo
         GovernanceError::CannotExecuteTransactionWithinHoldUpTime.into()
     );
 }
+
+const MAX_ATTESTATION_AGE_SLOTS: u64 = 10;
+
+#[tokio::test]
+async fn test_execute_transaction_with_simulation_attestation() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let simulation_oracle = Keypair::new();
+
+    governance_test
+        .with_execution_simulation_config(
+            &mut governance_cookie,
+            &simulation_oracle.pubkey(),
+            MAX_ATTESTATION_AGE_SLOTS,
+        )
+        .await;
+
+    let governed_mint_cookie = governance_test.with_governed_mint(&governance_cookie).await;
+
+    let mut proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let signatory_record_cookie = governance_test
+        .with_signatory(
+            &proposal_cookie,
+            &governance_cookie,
+            &token_owner_record_cookie,
+        )
+        .await
+        .unwrap();
+
+    let proposal_transaction_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_cookie,
+            &mut proposal_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal(&proposal_cookie, &signatory_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::Yes)
+        .await
+        .unwrap();
+
+    // Advance timestamp past hold_up_time
+    governance_test
+        .advance_clock_by_min_timespan(
+            governance_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+    governance_test
+        .post_execution_simulation_attestation(
+            &proposal_transaction_cookie,
+            &simulation_oracle,
+            true,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    governance_test
+        .execute_proposal_transaction_with_simulation_attestation(
+            &proposal_cookie,
+            &proposal_transaction_cookie,
+            &simulation_oracle.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_transaction_account = governance_test
+        .get_proposal_transaction_account(&proposal_transaction_cookie.address)
+        .await;
+
+    assert_eq!(
+        TransactionExecutionStatus::Success,
+        proposal_transaction_account.execution_status
+    );
+
+    let instruction_token_account = governance_test
+        .get_token_account(&proposal_transaction_cookie.account.instructions[0].accounts[1].pubkey)
+        .await;
+
+    assert_eq!(10, instruction_token_account.amount);
+}
+
+#[tokio::test]
+async fn test_execute_transaction_with_failed_simulation_attestation_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let simulation_oracle = Keypair::new();
+
+    governance_test
+        .with_execution_simulation_config(
+            &mut governance_cookie,
+            &simulation_oracle.pubkey(),
+            MAX_ATTESTATION_AGE_SLOTS,
+        )
+        .await;
+
+    let governed_mint_cookie = governance_test.with_governed_mint(&governance_cookie).await;
+
+    let mut proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let signatory_record_cookie = governance_test
+        .with_signatory(
+            &proposal_cookie,
+            &governance_cookie,
+            &token_owner_record_cookie,
+        )
+        .await
+        .unwrap();
+
+    let proposal_transaction_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_cookie,
+            &mut proposal_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal(&proposal_cookie, &signatory_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::Yes)
+        .await
+        .unwrap();
+
+    // Advance timestamp past hold_up_time
+    governance_test
+        .advance_clock_by_min_timespan(
+            governance_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+    governance_test
+        .post_execution_simulation_attestation(
+            &proposal_transaction_cookie,
+            &simulation_oracle,
+            false,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .execute_proposal_transaction_with_simulation_attestation(
+            &proposal_cookie,
+            &proposal_transaction_cookie,
+            &simulation_oracle.pubkey(),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::ExecutionSimulationFailed.into());
+}
+
+#[tokio::test]
+async fn test_execute_transaction_with_expired_simulation_attestation_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let simulation_oracle = Keypair::new();
+
+    governance_test
+        .with_execution_simulation_config(
+            &mut governance_cookie,
+            &simulation_oracle.pubkey(),
+            MAX_ATTESTATION_AGE_SLOTS,
+        )
+        .await;
+
+    let governed_mint_cookie = governance_test.with_governed_mint(&governance_cookie).await;
+
+    let mut proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let signatory_record_cookie = governance_test
+        .with_signatory(
+            &proposal_cookie,
+            &governance_cookie,
+            &token_owner_record_cookie,
+        )
+        .await
+        .unwrap();
+
+    let proposal_transaction_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_cookie,
+            &mut proposal_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal(&proposal_cookie, &signatory_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::Yes)
+        .await
+        .unwrap();
+
+    // Advance timestamp past hold_up_time
+    governance_test
+        .advance_clock_by_min_timespan(
+            governance_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+    governance_test
+        .post_execution_simulation_attestation(
+            &proposal_transaction_cookie,
+            &simulation_oracle,
+            true,
+        )
+        .await
+        .unwrap();
+
+    let clock = governance_test.bench.get_clock().await;
+
+    governance_test
+        .bench
+        .context
+        .warp_to_slot(clock.slot + MAX_ATTESTATION_AGE_SLOTS + 1)
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .execute_proposal_transaction_with_simulation_attestation(
+            &proposal_cookie,
+            &proposal_transaction_cookie,
+            &simulation_oracle.pubkey(),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::ExecutionSimulationAttestationExpired.into()
+    );
+}
+
+#[tokio::test]
+async fn test_execute_transaction_with_simulation_attestation_from_other_oracle_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let simulation_oracle = Keypair::new();
+
+    governance_test
+        .with_execution_simulation_config(
+            &mut governance_cookie,
+            &simulation_oracle.pubkey(),
+            MAX_ATTESTATION_AGE_SLOTS,
+        )
+        .await;
+
+    let governed_mint_cookie = governance_test.with_governed_mint(&governance_cookie).await;
+
+    let mut proposal_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let signatory_record_cookie = governance_test
+        .with_signatory(
+            &proposal_cookie,
+            &governance_cookie,
+            &token_owner_record_cookie,
+        )
+        .await
+        .unwrap();
+
+    let proposal_transaction_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_cookie,
+            &mut proposal_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal(&proposal_cookie, &signatory_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::Yes)
+        .await
+        .unwrap();
+
+    // Advance timestamp past hold_up_time
+    governance_test
+        .advance_clock_by_min_timespan(
+            governance_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+    let other_simulation_oracle = Keypair::new();
+
+    governance_test
+        .post_execution_simulation_attestation(
+            &proposal_transaction_cookie,
+            &other_simulation_oracle,
+            true,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .execute_proposal_transaction_with_simulation_attestation(
+            &proposal_cookie,
+            &proposal_transaction_cookie,
+            &other_simulation_oracle.pubkey(),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::InvalidExecutionSimulationAttestation.into()
+    );
+}
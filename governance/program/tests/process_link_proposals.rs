@@ -0,0 +1,743 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+
+use {
+    program_test::*,
+    solana_program_test::tokio,
+    spl_governance::{
+        error::GovernanceError,
+        state::enums::{ProposalLinkState, ProposalState},
+    },
+};
+
+#[tokio::test]
+async fn test_link_proposals() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_a_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_b_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_a_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_a_cookie)
+        .await
+        .unwrap();
+
+    let proposal_b_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_b_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let proposal_link_cookie = governance_test
+        .with_proposal_link(
+            &[
+                (&proposal_a_cookie, &token_owner_record_cookie),
+                (&proposal_b_cookie, &token_owner_record_cookie),
+            ],
+            2,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_link_account = governance_test
+        .get_proposal_link_account(&proposal_link_cookie.address)
+        .await;
+
+    assert_eq!(proposal_link_cookie.account, proposal_link_account);
+
+    let proposal_a_account = governance_test
+        .get_proposal_account(&proposal_a_cookie.address)
+        .await;
+
+    assert!(proposal_a_account.is_linked);
+
+    let proposal_b_account = governance_test
+        .get_proposal_account(&proposal_b_cookie.address)
+        .await;
+
+    assert!(proposal_b_account.is_linked);
+}
+
+#[tokio::test]
+async fn test_link_proposals_of_same_governance_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_a_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let proposal_b_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .with_proposal_link(
+            &[
+                (&proposal_a_cookie, &token_owner_record_cookie),
+                (&proposal_b_cookie, &token_owner_record_cookie),
+            ],
+            2,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::CannotLinkProposalsOfSameGovernance.into()
+    );
+}
+
+#[tokio::test]
+async fn test_link_already_linked_proposal_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_a_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_b_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_a_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_a_cookie)
+        .await
+        .unwrap();
+
+    let proposal_b_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_b_cookie)
+        .await
+        .unwrap();
+
+    let proposal_c_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_b_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_proposal_link(
+            &[
+                (&proposal_a_cookie, &token_owner_record_cookie),
+                (&proposal_b_cookie, &token_owner_record_cookie),
+            ],
+            2,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .with_proposal_link(
+            &[
+                (&proposal_a_cookie, &token_owner_record_cookie),
+                (&proposal_c_cookie, &token_owner_record_cookie),
+            ],
+            2,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::ProposalAlreadyLinked.into());
+}
+
+#[tokio::test]
+async fn test_execute_linked_proposals() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_a_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_b_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let governed_mint_a_cookie = governance_test
+        .with_governed_mint(&governance_a_cookie)
+        .await;
+
+    let governed_mint_b_cookie = governance_test
+        .with_governed_mint(&governance_b_cookie)
+        .await;
+
+    let mut proposal_a_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_a_cookie)
+        .await
+        .unwrap();
+
+    let mut proposal_b_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_b_cookie)
+        .await
+        .unwrap();
+
+    let proposal_transaction_a_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_a_cookie,
+            &mut proposal_a_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let proposal_transaction_b_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_b_cookie,
+            &mut proposal_b_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let proposal_link_cookie = governance_test
+        .with_proposal_link(
+            &[
+                (&proposal_a_cookie, &token_owner_record_cookie),
+                (&proposal_b_cookie, &token_owner_record_cookie),
+            ],
+            2,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal_by_owner(&proposal_a_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal_by_owner(&proposal_b_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(
+            &proposal_a_cookie,
+            &token_owner_record_cookie,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(
+            &proposal_b_cookie,
+            &token_owner_record_cookie,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    // Advance timestamp past hold_up_time
+    governance_test
+        .advance_clock_by_min_timespan(
+            governance_a_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+    // Act
+    governance_test
+        .resolve_proposal_link(&proposal_link_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .execute_linked_proposal_transaction(
+            &proposal_a_cookie,
+            &proposal_transaction_a_cookie,
+            &proposal_link_cookie,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .execute_linked_proposal_transaction(
+            &proposal_b_cookie,
+            &proposal_transaction_b_cookie,
+            &proposal_link_cookie,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_link_account = governance_test
+        .get_proposal_link_account(&proposal_link_cookie.address)
+        .await;
+
+    assert_eq!(ProposalLinkState::Committed, proposal_link_account.state);
+
+    let proposal_a_account = governance_test
+        .get_proposal_account(&proposal_a_cookie.address)
+        .await;
+
+    assert_eq!(ProposalState::Completed, proposal_a_account.state);
+
+    let proposal_b_account = governance_test
+        .get_proposal_account(&proposal_b_cookie.address)
+        .await;
+
+    assert_eq!(ProposalState::Completed, proposal_b_account.state);
+}
+
+#[tokio::test]
+async fn test_execute_linked_proposal_with_link_not_committed_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_a_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_b_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let governed_mint_a_cookie = governance_test
+        .with_governed_mint(&governance_a_cookie)
+        .await;
+
+    let mut proposal_a_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_a_cookie)
+        .await
+        .unwrap();
+
+    let proposal_b_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_b_cookie)
+        .await
+        .unwrap();
+
+    let proposal_transaction_a_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_a_cookie,
+            &mut proposal_a_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let proposal_link_cookie = governance_test
+        .with_proposal_link(
+            &[
+                (&proposal_a_cookie, &token_owner_record_cookie),
+                (&proposal_b_cookie, &token_owner_record_cookie),
+            ],
+            2,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal_by_owner(&proposal_a_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(
+            &proposal_a_cookie,
+            &token_owner_record_cookie,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    // Advance timestamp past hold_up_time
+    governance_test
+        .advance_clock_by_min_timespan(
+            governance_a_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+    // Proposal B is still in Draft and the link can't be resolved yet
+    let resolve_err = governance_test
+        .resolve_proposal_link(&proposal_link_cookie)
+        .await
+        .err()
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .execute_linked_proposal_transaction(
+            &proposal_a_cookie,
+            &proposal_transaction_a_cookie,
+            &proposal_link_cookie,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        resolve_err,
+        GovernanceError::InvalidStateCannotResolveProposalLink.into()
+    );
+    assert_eq!(err, GovernanceError::ProposalLinkNotCommitted.into());
+}
+
+#[tokio::test]
+async fn test_resolve_proposal_link_with_cancelled_proposal() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_a_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_b_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let governed_mint_a_cookie = governance_test
+        .with_governed_mint(&governance_a_cookie)
+        .await;
+
+    let mut proposal_a_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_a_cookie)
+        .await
+        .unwrap();
+
+    let proposal_b_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_b_cookie)
+        .await
+        .unwrap();
+
+    let proposal_transaction_a_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_a_cookie,
+            &mut proposal_a_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let proposal_link_cookie = governance_test
+        .with_proposal_link(
+            &[
+                (&proposal_a_cookie, &token_owner_record_cookie),
+                (&proposal_b_cookie, &token_owner_record_cookie),
+            ],
+            2,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal_by_owner(&proposal_a_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(
+            &proposal_a_cookie,
+            &token_owner_record_cookie,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .cancel_proposal(&proposal_b_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    // Advance timestamp past hold_up_time
+    governance_test
+        .advance_clock_by_min_timespan(
+            governance_a_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+    // Act
+    governance_test
+        .resolve_proposal_link(&proposal_link_cookie)
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_link_account = governance_test
+        .get_proposal_link_account(&proposal_link_cookie.address)
+        .await;
+
+    assert_eq!(ProposalLinkState::Aborted, proposal_link_account.state);
+
+    let err = governance_test
+        .execute_linked_proposal_transaction(
+            &proposal_a_cookie,
+            &proposal_transaction_a_cookie,
+            &proposal_link_cookie,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    assert_eq!(err, GovernanceError::ProposalLinkNotCommitted.into());
+}
+
+#[tokio::test]
+async fn test_link_proposals_with_invalid_threshold_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_a_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_b_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_a_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_a_cookie)
+        .await
+        .unwrap();
+
+    let proposal_b_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_b_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .with_proposal_link(
+            &[
+                (&proposal_a_cookie, &token_owner_record_cookie),
+                (&proposal_b_cookie, &token_owner_record_cookie),
+            ],
+            3,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::InvalidProposalLinkConfig.into());
+}
+
+#[tokio::test]
+async fn test_execute_proposal_of_committed_m_of_n_link() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_a_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_b_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_c_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let governed_mint_a_cookie = governance_test
+        .with_governed_mint(&governance_a_cookie)
+        .await;
+
+    let mut proposal_a_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_a_cookie)
+        .await
+        .unwrap();
+
+    let proposal_b_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_b_cookie)
+        .await
+        .unwrap();
+
+    let proposal_c_cookie = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_c_cookie)
+        .await
+        .unwrap();
+
+    let proposal_transaction_a_cookie = governance_test
+        .with_mint_tokens_transaction(
+            &governed_mint_a_cookie,
+            &mut proposal_a_cookie,
+            &token_owner_record_cookie,
+            0,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // 2 of 3 linked Proposals must succeed
+    let proposal_link_cookie = governance_test
+        .with_proposal_link(
+            &[
+                (&proposal_a_cookie, &token_owner_record_cookie),
+                (&proposal_b_cookie, &token_owner_record_cookie),
+                (&proposal_c_cookie, &token_owner_record_cookie),
+            ],
+            2,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal_by_owner(&proposal_a_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal_by_owner(&proposal_b_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(
+            &proposal_a_cookie,
+            &token_owner_record_cookie,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(
+            &proposal_b_cookie,
+            &token_owner_record_cookie,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .cancel_proposal(&proposal_c_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    // Advance timestamp past hold_up_time
+    governance_test
+        .advance_clock_by_min_timespan(
+            governance_a_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+    // Act
+    governance_test
+        .resolve_proposal_link(&proposal_link_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .execute_linked_proposal_transaction(
+            &proposal_a_cookie,
+            &proposal_transaction_a_cookie,
+            &proposal_link_cookie,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_link_account = governance_test
+        .get_proposal_link_account(&proposal_link_cookie.address)
+        .await;
+
+    assert_eq!(ProposalLinkState::Committed, proposal_link_account.state);
+
+    let proposal_a_account = governance_test
+        .get_proposal_account(&proposal_a_cookie.address)
+        .await;
+
+    assert_eq!(ProposalState::Completed, proposal_a_account.state);
+}
@@ -0,0 +1,324 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    solana_program::program_error::ProgramError,
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+mod program_test;
+
+use {
+    program_test::*,
+    spl_governance::{
+        error::GovernanceError,
+        state::{
+            enums::{GovernanceAccountType, ProposalState},
+            proposal_token_deposit::{
+                get_proposal_token_deposit_address, get_proposal_token_deposit_escrow_address,
+                ProposalTokenDepositConfig,
+            },
+        },
+    },
+};
+
+#[tokio::test]
+async fn test_create_proposal_with_token_deposit() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_proposal_token_deposit_config(
+            &mut governance_cookie,
+            ProposalTokenDepositConfig {
+                amount: 50,
+                participation_floor_percentage: 10,
+            },
+        )
+        .await;
+
+    // Act
+    let proposal_cookie = governance_test
+        .with_proposal_using_token_deposit(
+            &realm_cookie,
+            &token_owner_record_cookie,
+            &mut governance_cookie,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_token_deposit_address =
+        get_proposal_token_deposit_address(&governance_test.program_id, &proposal_cookie.address);
+
+    let proposal_token_deposit_account = governance_test
+        .get_proposal_token_deposit_account(&proposal_token_deposit_address)
+        .await;
+
+    assert_eq!(
+        GovernanceAccountType::ProposalTokenDeposit,
+        proposal_token_deposit_account.account_type
+    );
+    assert_eq!(
+        proposal_cookie.address,
+        proposal_token_deposit_account.proposal
+    );
+    assert_eq!(
+        token_owner_record_cookie.token_owner.pubkey(),
+        proposal_token_deposit_account.depositor
+    );
+    assert_eq!(50, proposal_token_deposit_account.amount);
+    assert_eq!(
+        10,
+        proposal_token_deposit_account.participation_floor_percentage
+    );
+
+    let escrow_account = governance_test
+        .get_token_account(&get_proposal_token_deposit_escrow_address(
+            &governance_test.program_id,
+            &proposal_cookie.address,
+        ))
+        .await;
+
+    assert_eq!(50, escrow_account.amount);
+    assert_eq!(proposal_token_deposit_address, escrow_account.owner);
+
+    let token_source_account = governance_test
+        .get_token_account(&token_owner_record_cookie.token_source)
+        .await;
+
+    assert_eq!(0, token_source_account.amount);
+}
+
+#[tokio::test]
+async fn test_create_proposal_with_token_deposit_with_missing_accounts_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_proposal_token_deposit_config(
+            &mut governance_cookie,
+            ProposalTokenDepositConfig {
+                amount: 50,
+                participation_floor_percentage: 10,
+            },
+        )
+        .await;
+
+    // Act
+    let err = governance_test
+        .with_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, ProgramError::NotEnoughAccountKeys);
+}
+
+#[tokio::test]
+async fn test_refund_proposal_token_deposit() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_proposal_token_deposit_config(
+            &mut governance_cookie,
+            ProposalTokenDepositConfig {
+                amount: 50,
+                participation_floor_percentage: 10,
+            },
+        )
+        .await;
+
+    let proposal_cookie = governance_test
+        .with_proposal_using_token_deposit(
+            &realm_cookie,
+            &token_owner_record_cookie,
+            &mut governance_cookie,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .cancel_proposal(&proposal_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    governance_test
+        .refund_proposal_token_deposit(&proposal_cookie, &token_owner_record_cookie.token_source)
+        .await
+        .unwrap();
+
+    // Assert
+    let token_source_account = governance_test
+        .get_token_account(&token_owner_record_cookie.token_source)
+        .await;
+
+    assert_eq!(50, token_source_account.amount);
+
+    let proposal_token_deposit_account_info = governance_test
+        .bench
+        .get_account(&get_proposal_token_deposit_address(
+            &governance_test.program_id,
+            &proposal_cookie.address,
+        ))
+        .await;
+
+    assert_eq!(None, proposal_token_deposit_account_info);
+
+    let escrow_account_info = governance_test
+        .bench
+        .get_account(&get_proposal_token_deposit_escrow_address(
+            &governance_test.program_id,
+            &proposal_cookie.address,
+        ))
+        .await;
+
+    assert_eq!(None, escrow_account_info);
+}
+
+#[tokio::test]
+async fn test_refund_proposal_token_deposit_defeated_below_participation_floor() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_proposal_token_deposit_config(
+            &mut governance_cookie,
+            ProposalTokenDepositConfig {
+                amount: 50,
+                participation_floor_percentage: 10,
+            },
+        )
+        .await;
+
+    let proposal_cookie = governance_test
+        .with_proposal_using_token_deposit(
+            &realm_cookie,
+            &token_owner_record_cookie,
+            &mut governance_cookie,
+        )
+        .await
+        .unwrap();
+
+    let signatory_record_cookie = governance_test
+        .with_signatory(
+            &proposal_cookie,
+            &governance_cookie,
+            &token_owner_record_cookie,
+        )
+        .await
+        .unwrap();
+
+    governance_test
+        .sign_off_proposal(&proposal_cookie, &signatory_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    // Advance clock past max_voting_time without any votes cast
+    governance_test
+        .advance_clock_past_timestamp(
+            governance_cookie.account.config.voting_base_time as i64
+                + proposal_account.voting_at.unwrap(),
+        )
+        .await;
+
+    governance_test
+        .finalize_vote(&realm_cookie, &proposal_cookie, None)
+        .await
+        .unwrap();
+
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(ProposalState::Defeated, proposal_account.state);
+
+    let governance_token_account = Keypair::new();
+    governance_test
+        .bench
+        .create_empty_token_account(
+            &governance_token_account,
+            &realm_cookie.account.community_mint,
+            &governance_cookie.address,
+        )
+        .await;
+
+    // Act
+    let err = governance_test
+        .refund_proposal_token_deposit(&proposal_cookie, &token_owner_record_cookie.token_source)
+        .await
+        .err()
+        .unwrap();
+
+    governance_test
+        .refund_proposal_token_deposit(&proposal_cookie, &governance_token_account.pubkey())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::InvalidDestinationForProposalTokenDeposit.into()
+    );
+
+    let governance_token_account = governance_test
+        .get_token_account(&governance_token_account.pubkey())
+        .await;
+
+    assert_eq!(50, governance_token_account.amount);
+}
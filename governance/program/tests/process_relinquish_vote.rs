@@ -625,3 +625,141 @@ async fn test_change_yes_vote_to_no_within_cool_off_time() {
     assert_eq!(100, proposal_account.deny_vote_weight.unwrap());
     assert_eq!(ProposalState::Voting, proposal_account.state);
 }
+
+#[tokio::test]
+async fn test_relinquish_active_vote_with_vote_session_key() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    // Total 300 tokens
+    governance_test
+        .mint_community_tokens(&realm_cookie, 200)
+        .await;
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let clock = governance_test.bench.get_clock().await;
+
+    let vote_session_key_cookie = governance_test
+        .with_vote_session_key(&token_owner_record_cookie, clock.slot + 100)
+        .await
+        .unwrap();
+
+    governance_test
+        .cast_vote_with_vote_session_key(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &vote_session_key_cookie,
+            Vote::Deny,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    governance_test
+        .relinquish_vote_with_vote_session_key(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &vote_session_key_cookie,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(0, proposal_account.deny_vote_weight.unwrap());
+    assert_eq!(ProposalState::Voting, proposal_account.state);
+
+    let token_owner_record = governance_test
+        .get_token_owner_record_account(&token_owner_record_cookie.address)
+        .await;
+
+    assert_eq!(0, token_owner_record.unrelinquished_votes_count);
+}
+
+#[tokio::test]
+async fn test_relinquish_vote_with_revoked_vote_session_key_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    // Total 300 tokens
+    governance_test
+        .mint_community_tokens(&realm_cookie, 200)
+        .await;
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let clock = governance_test.bench.get_clock().await;
+
+    let vote_session_key_cookie = governance_test
+        .with_vote_session_key(&token_owner_record_cookie, clock.slot + 100)
+        .await
+        .unwrap();
+
+    governance_test
+        .cast_vote_with_vote_session_key(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &vote_session_key_cookie,
+            Vote::Deny,
+        )
+        .await
+        .unwrap();
+
+    // Revoke the session key by moving its expiry to the past
+    governance_test
+        .set_vote_session_key_expiry(
+            &token_owner_record_cookie,
+            &vote_session_key_cookie.session_key.pubkey(),
+            0,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .relinquish_vote_with_vote_session_key(
+            &proposal_cookie,
+            &token_owner_record_cookie,
+            &vote_session_key_cookie,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::VoteSessionKeyExpired.into());
+}
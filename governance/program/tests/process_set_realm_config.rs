@@ -412,3 +412,115 @@ async fn test_set_realm_config_with_token_owner_record_lock_authorities() {
         realm_config_account.council_token_config.lock_authorities
     );
 }
+
+#[tokio::test]
+async fn test_set_realm_config_with_addin_approved_in_addin_registry() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let mut realm_cookie = governance_test.with_realm().await;
+
+    let voter_weight_addin = Pubkey::new_unique();
+
+    governance_test
+        .with_addin_registry_entry(&realm_cookie, &voter_weight_addin, 0)
+        .await
+        .unwrap();
+
+    let realm_setup_args = RealmSetupArgs {
+        community_token_config_args: GoverningTokenConfigAccountArgs {
+            voter_weight_addin: Some(voter_weight_addin),
+            max_voter_weight_addin: None,
+            token_type: GoverningTokenType::Liquid,
+        },
+        ..Default::default()
+    };
+
+    // Act
+    governance_test
+        .set_realm_config(&mut realm_cookie, &realm_setup_args)
+        .await
+        .unwrap();
+
+    // Assert
+    let realm_config_account = governance_test
+        .get_realm_config_account(&realm_cookie.realm_config.address)
+        .await;
+
+    assert_eq!(
+        realm_config_account
+            .community_token_config
+            .voter_weight_addin,
+        Some(voter_weight_addin)
+    );
+}
+
+#[tokio::test]
+async fn test_set_realm_config_with_addin_not_in_addin_registry_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let mut realm_cookie = governance_test.with_realm().await;
+
+    governance_test
+        .with_addin_registry_entry(&realm_cookie, &Pubkey::new_unique(), 0)
+        .await
+        .unwrap();
+
+    let realm_setup_args = RealmSetupArgs {
+        community_token_config_args: GoverningTokenConfigAccountArgs {
+            voter_weight_addin: Some(Pubkey::new_unique()),
+            max_voter_weight_addin: None,
+            token_type: GoverningTokenType::Liquid,
+        },
+        ..Default::default()
+    };
+
+    // Act
+    let err = governance_test
+        .set_realm_config(&mut realm_cookie, &realm_setup_args)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::AddinNotInAddinRegistry.into());
+}
+
+#[tokio::test]
+async fn test_set_realm_config_with_addin_below_addin_registry_min_version_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let mut realm_cookie = governance_test.with_realm().await;
+
+    let max_voter_weight_addin = Pubkey::new_unique();
+
+    governance_test
+        .with_addin_registry_entry(&realm_cookie, &max_voter_weight_addin, 1)
+        .await
+        .unwrap();
+
+    // The addin is not deployed with the upgradeable loader and has no version
+    let realm_setup_args = RealmSetupArgs {
+        community_token_config_args: GoverningTokenConfigAccountArgs {
+            voter_weight_addin: None,
+            max_voter_weight_addin: Some(max_voter_weight_addin),
+            token_type: GoverningTokenType::Liquid,
+        },
+        ..Default::default()
+    };
+
+    // Act
+    let err = governance_test
+        .set_realm_config(&mut realm_cookie, &realm_setup_args)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        err,
+        GovernanceError::AddinVersionBelowRegistryMinVersion.into()
+    );
+}
@@ -10,11 +10,14 @@ use {
     spl_governance::{
         error::GovernanceError,
         state::{
+            addin_registry::AddinRegistryEntry,
             enums::GovernanceAccountType,
             realm::SetRealmConfigItemArgs,
-            realm_config::{GoverningTokenConfig, RealmConfigAccount},
+            realm_config::{
+                CouncilTerm, GoverningTokenConfig, RealmConfigAccount, VoteExtensionConfig,
+            },
         },
-        tools::structs::{Reserved110, SetConfigItemActionType},
+        tools::structs::{Reserved91, SetConfigItemActionType},
     },
     spl_governance_tools::account::AccountMaxSize,
 };
@@ -336,7 +339,10 @@ async fn test_set_realm_config_item_with_extended_account_size() {
         realm: realm_cookie.address,
         community_token_config: GoverningTokenConfig::default(),
         council_token_config: GoverningTokenConfig::default(),
-        reserved: Reserved110::default(),
+        council_term: CouncilTerm::default(),
+        vote_extension: VoteExtensionConfig::default(),
+        use_addin_registry: false,
+        reserved: Reserved91::default(),
     };
 
     assert_eq!(
@@ -384,3 +390,200 @@ async fn test_remove_token_owner_record_lock_authority_with_authority_not_found_
         GovernanceError::TokenOwnerRecordLockAuthorityNotFound.into()
     );
 }
+
+#[tokio::test]
+async fn test_set_vote_extension() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let vote_extension = VoteExtensionConfig {
+        window_time: 3600,
+        extension_time: 7200,
+        max_extensions_count: 2,
+    };
+
+    // Act
+    let args = SetRealmConfigItemArgs::VoteExtension {
+        config: vote_extension.clone(),
+    };
+
+    governance_test
+        .set_realm_config_item(&realm_cookie, args)
+        .await
+        .unwrap();
+
+    // Assert
+    let realm_config_account = governance_test
+        .get_realm_config_account(&realm_cookie.realm_config.address)
+        .await;
+
+    assert_eq!(vote_extension, realm_config_account.vote_extension);
+}
+
+#[tokio::test]
+async fn test_set_vote_extension_with_invalid_config_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    // Act
+    let args = SetRealmConfigItemArgs::VoteExtension {
+        config: VoteExtensionConfig {
+            window_time: 3600,
+            extension_time: 0,
+            max_extensions_count: 2,
+        },
+    };
+
+    let err = governance_test
+        .set_realm_config_item(&realm_cookie, args)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::InvalidVoteExtensionConfig.into());
+}
+
+#[tokio::test]
+async fn test_add_addin_registry_entry() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let addin = Pubkey::new_unique();
+
+    // Act
+    governance_test
+        .with_addin_registry_entry(&realm_cookie, &addin, 10)
+        .await
+        .unwrap();
+
+    // Assert
+    let addin_registry = governance_test
+        .get_addin_registry_account(&realm_cookie)
+        .await;
+
+    assert_eq!(
+        addin_registry.account_type,
+        GovernanceAccountType::AddinRegistry
+    );
+    assert_eq!(addin_registry.realm, realm_cookie.address);
+    assert_eq!(
+        addin_registry.entries,
+        vec![AddinRegistryEntry {
+            program_id: addin,
+            min_version: 10,
+        }]
+    );
+
+    let realm_config_account = governance_test
+        .get_realm_config_account(&realm_cookie.realm_config.address)
+        .await;
+
+    assert!(realm_config_account.use_addin_registry);
+}
+
+#[tokio::test]
+async fn test_add_addin_registry_entry_with_entry_already_exists_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let addin = Pubkey::new_unique();
+
+    governance_test
+        .with_addin_registry_entry(&realm_cookie, &addin, 0)
+        .await
+        .unwrap();
+
+    // Act
+    let err = governance_test
+        .with_addin_registry_entry(&realm_cookie, &addin, 10)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::AddinRegistryEntryAlreadyExists.into());
+}
+
+#[tokio::test]
+async fn test_remove_addin_registry_entry() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let addin1 = Pubkey::new_unique();
+    let addin2 = Pubkey::new_unique();
+
+    governance_test
+        .with_addin_registry_entry(&realm_cookie, &addin1, 0)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_addin_registry_entry(&realm_cookie, &addin2, 0)
+        .await
+        .unwrap();
+
+    // Act
+    let args = SetRealmConfigItemArgs::AddinRegistryEntry {
+        action: SetConfigItemActionType::Remove,
+        program_id: addin1,
+        min_version: 0,
+    };
+
+    governance_test
+        .set_realm_config_item(&realm_cookie, args)
+        .await
+        .unwrap();
+
+    // Assert
+    let addin_registry = governance_test
+        .get_addin_registry_account(&realm_cookie)
+        .await;
+
+    assert_eq!(
+        addin_registry.entries,
+        vec![AddinRegistryEntry {
+            program_id: addin2,
+            min_version: 0,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_remove_addin_registry_entry_with_entry_not_found_error() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    governance_test
+        .with_addin_registry_entry(&realm_cookie, &Pubkey::new_unique(), 0)
+        .await
+        .unwrap();
+
+    // Act
+    let args = SetRealmConfigItemArgs::AddinRegistryEntry {
+        action: SetConfigItemActionType::Remove,
+        program_id: Pubkey::new_unique(),
+        min_version: 0,
+    };
+
+    let err = governance_test
+        .set_realm_config_item(&realm_cookie, args)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_eq!(err, GovernanceError::AddinRegistryEntryNotFound.into());
+}
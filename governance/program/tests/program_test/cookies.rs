@@ -3,9 +3,11 @@ use {
     solana_program::{clock::UnixTimestamp, instruction::Instruction, pubkey::Pubkey},
     solana_sdk::signature::Keypair,
     spl_governance::state::{
+        council_election::CouncilElectionConfig, funding_round::FundingRoundConfig,
         governance::GovernanceV2, native_treasury::NativeTreasury,
-        program_metadata::ProgramMetadata, proposal::ProposalV2, proposal_deposit::ProposalDeposit,
-        proposal_transaction::ProposalTransactionV2, realm::RealmV2,
+        program_metadata::ProgramMetadata, proposal::ProposalV2,
+        proposal_dependency::ProposalDependency, proposal_deposit::ProposalDeposit,
+        proposal_link::ProposalLink, proposal_transaction::ProposalTransactionV2, realm::RealmV2,
         realm_config::RealmConfigAccount, signatory_record::SignatoryRecordV2,
         token_owner_record::TokenOwnerRecordV2, vote_record::VoteRecordV2,
     },
@@ -141,6 +143,18 @@ pub struct ProposalDepositCookie {
     pub account: ProposalDeposit,
 }
 
+#[derive(Debug)]
+pub struct ProposalDependencyCookie {
+    pub address: Pubkey,
+    pub account: ProposalDependency,
+}
+
+#[derive(Debug)]
+pub struct ProposalLinkCookie {
+    pub address: Pubkey,
+    pub account: ProposalLink,
+}
+
 #[derive(Debug)]
 pub struct SignatoryRecordCookie {
     pub address: Pubkey,
@@ -196,3 +210,39 @@ pub struct TokenOwnerRecordLockCookie {
 pub struct TokenOwnerRecordLockAuthorityCookie {
     pub authority: Keypair,
 }
+
+#[derive(Debug)]
+pub struct FundingRoundCookie {
+    pub address: Pubkey,
+    pub escrow: Pubkey,
+    pub governance: Pubkey,
+    pub mint: Pubkey,
+    pub mint_authority: Keypair,
+    pub config: FundingRoundConfig,
+}
+
+#[derive(Debug)]
+pub struct FundingProjectCookie {
+    pub address: Pubkey,
+    pub project_owner: Keypair,
+    pub recipient: Pubkey,
+}
+
+#[derive(Debug)]
+pub struct CouncilElectionCookie {
+    pub address: Pubkey,
+    pub realm: Pubkey,
+    pub config: CouncilElectionConfig,
+}
+
+#[derive(Debug)]
+pub struct CouncilCandidateCookie {
+    pub address: Pubkey,
+    pub candidate: Keypair,
+}
+
+#[derive(Debug)]
+pub struct VoteSessionKeyCookie {
+    pub address: Pubkey,
+    pub session_key: Keypair,
+}
@@ -10,27 +10,51 @@ use {
         program_error::ProgramError,
         program_pack::{IsInitialized, Pack},
         pubkey::Pubkey,
-        system_instruction,
+        system_instruction, sysvar,
     },
     solana_program_test::*,
     solana_sdk::signature::{Keypair, Signer},
     spl_governance::{
         instruction::{
-            add_required_signatory, add_signatory, cancel_proposal, cast_vote, complete_proposal,
-            create_governance, create_native_treasury, create_proposal, create_realm,
-            create_token_owner_record, deposit_governing_tokens, execute_transaction,
-            finalize_vote, insert_transaction, refund_proposal_deposit,
-            relinquish_token_owner_record_locks, relinquish_vote, remove_required_signatory,
-            remove_transaction, revoke_governing_tokens, set_governance_config,
+            add_proposal_dependency, add_required_signatory, add_signatory, archive_proposal,
+            archive_vote_records, cancel_proposal, cast_council_election_vote, cast_vote,
+            cast_vote_with_council_seat, cast_vote_with_vote_intent,
+            cast_vote_with_vote_session_key, claim_council_seat, claim_funding_match,
+            complete_proposal, contribute_to_funding_project, create_council_election,
+            create_funding_round, create_governance, create_native_treasury, create_proposal,
+            create_proposal_archive, create_realm, create_token_owner_record,
+            create_vote_session_key, deposit_governing_tokens, execute_linked_transaction,
+            execute_transaction, execute_transaction_with_dependencies,
+            execute_transaction_with_simulation_attestation, finalize_council_election,
+            finalize_funding_round, finalize_vote, insert_transaction, link_proposals,
+            post_execution_simulation_attestation, refund_proposal_deposit,
+            refund_proposal_token_deposit, register_council_candidate, register_funding_project,
+            register_vote_intent, relinquish_token_owner_record_locks, relinquish_vote,
+            relinquish_vote_with_vote_session_key, remove_required_signatory, remove_transaction,
+            resolve_proposal_link, revoke_governing_tokens, set_governance_config,
             set_governance_delegate, set_realm_authority, set_realm_config, set_realm_config_item,
             set_token_owner_record_lock, sign_off_proposal, upgrade_program_metadata,
             withdraw_governing_tokens, AddSignatoryAuthority,
         },
         processor::process_instruction,
         state::{
+            addin_registry::{get_addin_registry_address, AddinRegistry},
+            council_election::{
+                get_council_candidate_address, get_council_election_address,
+                get_council_seat_address, CouncilCandidate, CouncilElection, CouncilElectionConfig,
+                CouncilSeat,
+            },
             enums::{
                 GovernanceAccountType, InstructionExecutionFlags, MintMaxVoterWeightSource,
-                ProposalState, TransactionExecutionStatus, VoteThreshold,
+                ProposalLinkState, ProposalState, TransactionExecutionStatus, VoteThreshold,
+            },
+            execution_simulation::{
+                get_execution_simulation_attestation_address, get_proposal_transaction_hash,
+                ExecutionSimulationConfig,
+            },
+            funding_round::{
+                get_funding_project_address, get_funding_round_address,
+                get_funding_round_escrow_address, FundingProject, FundingRound, FundingRoundConfig,
             },
             governance::{
                 get_governance_address, GovernanceConfig, GovernanceV2,
@@ -41,7 +65,16 @@ use {
             proposal::{
                 get_proposal_address, OptionVoteResult, ProposalOption, ProposalV2, VoteType,
             },
+            proposal_archive::{
+                get_proposal_archive_address, ProposalArchive, PROPOSAL_ARCHIVE_RETENTION_TIME,
+            },
+            proposal_dependency::{get_proposal_dependency_address, ProposalDependency},
             proposal_deposit::{get_proposal_deposit_address, ProposalDeposit},
+            proposal_link::{get_proposal_link_address, ProposalLink},
+            proposal_token_deposit::{
+                get_proposal_token_deposit_address, get_proposal_token_deposit_escrow_address,
+                ProposalTokenDeposit, ProposalTokenDepositConfig,
+            },
             proposal_transaction::{
                 get_proposal_transaction_address, InstructionData, ProposalTransactionV2,
             },
@@ -50,18 +83,23 @@ use {
                 GoverningTokenConfigAccountArgs, RealmConfig, RealmV2, SetRealmAuthorityAction,
                 SetRealmConfigItemArgs,
             },
-            realm_config::{get_realm_config_address, GoverningTokenConfig, RealmConfigAccount},
+            realm_config::{
+                get_realm_config_address, CouncilTerm, GoverningTokenConfig, RealmConfigAccount,
+                VoteExtensionConfig,
+            },
             required_signatory::RequiredSignatory,
             signatory_record::{get_signatory_record_address, SignatoryRecordV2},
             token_owner_record::{
                 get_token_owner_record_address, TokenOwnerRecordV2,
                 TOKEN_OWNER_RECORD_LAYOUT_VERSION,
             },
+            vote_intent::{get_vote_intent_address, get_vote_intent_message, VoteIntent},
             vote_record::{get_vote_record_address, Vote, VoteChoice, VoteRecordV2},
+            vote_session_key::{get_vote_session_key_address, VoteSessionKey},
         },
         tools::{
             bpf_loader_upgradeable::get_program_data_address,
-            structs::{Reserved110, Reserved119, SetConfigItemActionType},
+            structs::{Reserved70, Reserved91, SetConfigItemActionType},
         },
     },
     spl_governance_addin_api::{
@@ -82,11 +120,12 @@ use {
     crate::{
         args::{PluginSetupArgs, RealmSetupArgs},
         cookies::{
-            GovernanceCookie, GovernedMintCookie, GovernedProgramCookie,
+            CouncilCandidateCookie, CouncilElectionCookie, FundingProjectCookie,
+            FundingRoundCookie, GovernanceCookie, GovernedMintCookie, GovernedProgramCookie,
             GovernedTokenAccountCookie, MaxVoterWeightRecordCookie, NativeTreasuryCookie,
-            ProgramMetadataCookie, ProposalCookie, ProposalDepositCookie,
-            ProposalTransactionCookie, RealmCookie, TokenOwnerRecordCookie,
-            TokenOwnerRecordLockCookie, VoteRecordCookie,
+            ProgramMetadataCookie, ProposalCookie, ProposalDependencyCookie, ProposalDepositCookie,
+            ProposalLinkCookie, ProposalTransactionCookie, RealmCookie, TokenOwnerRecordCookie,
+            TokenOwnerRecordLockCookie, VoteRecordCookie, VoteSessionKeyCookie,
         },
         program_test::cookies::{
             RealmConfigCookie, SignatoryRecordCookie, VoterWeightRecordCookie,
@@ -100,6 +139,15 @@ use {
     },
 };
 
+/// The time contributions are accepted for FundingRounds created by the tests
+pub const FUNDING_ROUND_CONTRIBUTIONS_TIME: UnixTimestamp = 3600;
+
+/// The time votes are accepted for CouncilElections created by the tests
+pub const COUNCIL_ELECTION_VOTING_TIME: UnixTimestamp = 3600;
+
+/// The term of the councils elected in CouncilElections created by the tests
+pub const COUNCIL_TERM_TIME: UnixTimestamp = 86400;
+
 /// Yes/No Vote
 pub enum YesNoVote {
     /// Yes vote
@@ -372,7 +420,10 @@ impl GovernanceProgramTest {
             account: RealmConfigAccount {
                 account_type: GovernanceAccountType::RealmConfig,
                 realm: realm_address,
-                reserved: Reserved110::default(),
+                council_term: CouncilTerm::default(),
+                vote_extension: VoteExtensionConfig::default(),
+                use_addin_registry: false,
+                reserved: Reserved91::default(),
                 community_token_config: GoverningTokenConfig {
                     voter_weight_addin: realm_setup_args
                         .community_token_config_args
@@ -485,7 +536,10 @@ impl GovernanceProgramTest {
                 account_type: GovernanceAccountType::RealmConfig,
                 realm: realm_address,
                 council_token_config: GoverningTokenConfig::default(),
-                reserved: Reserved110::default(),
+                council_term: CouncilTerm::default(),
+                vote_extension: VoteExtensionConfig::default(),
+                use_addin_registry: false,
+                reserved: Reserved91::default(),
                 community_token_config: GoverningTokenConfig::default(),
             },
         };
@@ -550,8 +604,17 @@ impl GovernanceProgramTest {
         realm_cookie: &RealmCookie,
         governing_token_mint: &Pubkey,
     ) -> TokenOwnerRecordCookie {
-        let token_owner = Keypair::new();
+        self.with_token_owner_record_for_owner(realm_cookie, governing_token_mint, Keypair::new())
+            .await
+    }
 
+    #[allow(dead_code)]
+    pub async fn with_token_owner_record_for_owner(
+        &mut self,
+        realm_cookie: &RealmCookie,
+        governing_token_mint: &Pubkey,
+        token_owner: Keypair,
+    ) -> TokenOwnerRecordCookie {
         let create_token_owner_record_ix = create_token_owner_record(
             &self.program_id,
             &realm_cookie.address,
@@ -1188,7 +1251,10 @@ impl GovernanceProgramTest {
             account: RealmConfigAccount {
                 account_type: GovernanceAccountType::RealmConfig,
                 realm: realm_cookie.address,
-                reserved: Reserved110::default(),
+                council_term: CouncilTerm::default(),
+                vote_extension: VoteExtensionConfig::default(),
+                use_addin_registry: false,
+                reserved: Reserved91::default(),
                 community_token_config: GoverningTokenConfig {
                     voter_weight_addin: realm_setup_args
                         .community_token_config_args
@@ -1487,7 +1553,9 @@ impl GovernanceProgramTest {
             governance_seed,
             config: governance_config.clone(),
             reserved1: 0,
-            reserved_v2: Reserved119::default(),
+            reserved_v2: Reserved70::default(),
+            execution_simulation_config: ExecutionSimulationConfig::default(),
+            proposal_token_deposit_config: ProposalTokenDepositConfig::default(),
             required_signatories_count: 0,
             active_proposal_count: 0,
         };
@@ -1788,9 +1856,14 @@ impl GovernanceProgramTest {
             max_voting_time: None,
             vote_threshold: None,
 
-            reserved: [0; 64],
+            votes_count: 0,
+            voters_count: 0,
+            voting_extensions_count: 0,
+            voting_extended_time: 0,
+            dependencies_count: 0,
+            reserved: [0; 50],
 
-            reserved1: 0,
+            is_linked: false,
         };
 
         let proposal_address = get_proposal_address(
@@ -1996,6 +2069,152 @@ impl GovernanceProgramTest {
         Ok(())
     }
 
+    #[allow(dead_code)]
+    pub async fn with_proposal_token_deposit_config(
+        &mut self,
+        governance_cookie: &mut GovernanceCookie,
+        config: ProposalTokenDepositConfig,
+    ) {
+        governance_cookie.account.proposal_token_deposit_config = config;
+
+        self.set_account(&governance_cookie.address, &governance_cookie.account);
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_proposal_using_token_deposit(
+        &mut self,
+        realm_cookie: &RealmCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        governance_cookie: &mut GovernanceCookie,
+    ) -> Result<ProposalCookie, ProgramError> {
+        let amount = governance_cookie
+            .account
+            .proposal_token_deposit_config
+            .amount;
+
+        self.bench
+            .mint_tokens(
+                &realm_cookie.account.community_mint,
+                &realm_cookie.community_mint_authority,
+                &token_owner_record_cookie.token_source,
+                amount,
+            )
+            .await;
+
+        let program_id = self.program_id;
+        let token_source = token_owner_record_cookie.token_source;
+
+        self.with_proposal_using_instruction(token_owner_record_cookie, governance_cookie, |i| {
+            let proposal_address = i.accounts[1].pubkey;
+
+            i.accounts.extend([
+                AccountMeta::new(
+                    get_proposal_token_deposit_address(&program_id, &proposal_address),
+                    false,
+                ),
+                AccountMeta::new(
+                    get_proposal_token_deposit_escrow_address(&program_id, &proposal_address),
+                    false,
+                ),
+                AccountMeta::new(token_source, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ]);
+        })
+        .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn refund_proposal_token_deposit(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        destination: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let refund_proposal_token_deposit_ix = refund_proposal_token_deposit(
+            &self.program_id,
+            &proposal_cookie.address,
+            destination,
+            &self.bench.payer.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(&[refund_proposal_token_deposit_ix], None)
+            .await?;
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_proposal_archive(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+    ) -> Result<Pubkey, ProgramError> {
+        let create_proposal_archive_ix = create_proposal_archive(
+            &self.program_id,
+            &proposal_cookie.address,
+            &self.bench.payer.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(&[create_proposal_archive_ix], None)
+            .await?;
+
+        Ok(get_proposal_archive_address(
+            &self.program_id,
+            &proposal_cookie.address,
+        ))
+    }
+
+    #[allow(dead_code)]
+    pub async fn archive_vote_records(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        vote_record_cookies: &[&VoteRecordCookie],
+    ) -> Result<(), ProgramError> {
+        let vote_records: Vec<Pubkey> = vote_record_cookies
+            .iter()
+            .map(|vote_record_cookie| vote_record_cookie.address)
+            .collect();
+
+        let archive_vote_records_ix = archive_vote_records(
+            &self.program_id,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &vote_records,
+        );
+
+        self.bench
+            .process_transaction(&[archive_vote_records_ix], None)
+            .await?;
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn archive_proposal(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+    ) -> Result<(), ProgramError> {
+        let archive_proposal_ix = archive_proposal(
+            &self.program_id,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            None,
+        );
+
+        self.bench
+            .process_transaction(&[archive_proposal_ix], None)
+            .await?;
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn advance_clock_past_proposal_archive_retention_time(&mut self) {
+        self.advance_clock_by_min_timespan(PROPOSAL_ARCHIVE_RETENTION_TIME as u64)
+            .await;
+    }
+
     #[allow(dead_code)]
     pub async fn finalize_vote(
         &mut self,
@@ -2173,6 +2392,8 @@ impl GovernanceProgramTest {
             .account
             .governing_token_deposit_amount;
 
+        let proposal_account = self.get_proposal_account(&proposal_cookie.address).await;
+
         let account = VoteRecordV2 {
             account_type: GovernanceAccountType::VoteRecordV2,
             proposal: proposal_cookie.address,
@@ -2180,7 +2401,8 @@ impl GovernanceProgramTest {
             vote,
             voter_weight: vote_amount,
             is_relinquished: false,
-            reserved_v2: [0; 8],
+            voter_number: proposal_account.votes_count,
+            reserved_v2: [0; 4],
         };
 
         let vote_record_cookie = VoteRecordCookie {
@@ -2725,90 +2947,982 @@ impl GovernanceProgramTest {
     }
 
     #[allow(dead_code)]
-    pub async fn get_token_owner_record_account(&mut self, address: &Pubkey) -> TokenOwnerRecordV2 {
-        self.bench
-            .get_borsh_account::<TokenOwnerRecordV2>(address)
-            .await
-    }
+    pub async fn execute_linked_proposal_transaction(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        proposal_transaction_cookie: &ProposalTransactionCookie,
+        proposal_link_cookie: &ProposalLinkCookie,
+    ) -> Result<(), ProgramError> {
+        let execute_proposal_transaction_ix = execute_linked_transaction(
+            &self.program_id,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &proposal_transaction_cookie.address,
+            &proposal_link_cookie.address,
+            &proposal_transaction_cookie.instruction.program_id,
+            &proposal_transaction_cookie.instruction.accounts,
+        );
 
-    #[allow(dead_code)]
-    pub async fn get_program_metadata_account(&mut self, address: &Pubkey) -> ProgramMetadata {
         self.bench
-            .get_borsh_account::<ProgramMetadata>(address)
+            .process_transaction(&[execute_proposal_transaction_ix], None)
             .await
     }
 
     #[allow(dead_code)]
-    pub async fn get_native_treasury_account(&mut self, address: &Pubkey) -> NativeTreasury {
-        self.bench
-            .get_borsh_account::<NativeTreasury>(address)
-            .await
+    pub async fn with_execution_simulation_config(
+        &mut self,
+        governance_cookie: &mut GovernanceCookie,
+        simulation_oracle: &Pubkey,
+        max_attestation_age_slots: u64,
+    ) {
+        governance_cookie.account.execution_simulation_config = ExecutionSimulationConfig {
+            simulation_oracle: *simulation_oracle,
+            max_attestation_age_slots,
+        };
+
+        self.set_account(&governance_cookie.address, &governance_cookie.account);
     }
 
     #[allow(dead_code)]
-    pub async fn get_proposal_deposit_account(&mut self, address: &Pubkey) -> ProposalDeposit {
+    pub async fn post_execution_simulation_attestation(
+        &mut self,
+        proposal_transaction_cookie: &ProposalTransactionCookie,
+        simulation_oracle: &Keypair,
+        success: bool,
+    ) -> Result<Pubkey, ProgramError> {
+        let transaction_hash =
+            get_proposal_transaction_hash(&proposal_transaction_cookie.account.instructions);
+
+        let post_execution_simulation_attestation_ix = post_execution_simulation_attestation(
+            &self.program_id,
+            &proposal_transaction_cookie.address,
+            &simulation_oracle.pubkey(),
+            &self.bench.payer.pubkey(),
+            transaction_hash.to_bytes(),
+            success,
+        );
+
         self.bench
-            .get_borsh_account::<ProposalDeposit>(address)
-            .await
-    }
+            .process_transaction(
+                &[post_execution_simulation_attestation_ix],
+                Some(&[simulation_oracle]),
+            )
+            .await?;
 
-    #[allow(dead_code)]
-    pub async fn get_realm_account(&mut self, realm_address: &Pubkey) -> RealmV2 {
-        self.bench.get_borsh_account::<RealmV2>(realm_address).await
+        Ok(get_execution_simulation_attestation_address(
+            &self.program_id,
+            &proposal_transaction_cookie.address,
+            &simulation_oracle.pubkey(),
+        ))
     }
 
     #[allow(dead_code)]
-    pub async fn get_realm_config_account(
+    pub async fn with_vote_intent(
         &mut self,
-        realm_config_address: &Pubkey,
-    ) -> RealmConfigAccount {
-        self.bench
-            .get_borsh_account::<RealmConfigAccount>(realm_config_address)
-            .await
-    }
+        proposal_cookie: &ProposalCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        governance_authority: &Keypair,
+        vote: Vote,
+    ) -> Result<Pubkey, ProgramError> {
+        let message = get_vote_intent_message(
+            &self.program_id,
+            &proposal_cookie.address,
+            &token_owner_record_cookie.address,
+            &vote,
+        );
 
-    #[allow(dead_code)]
-    pub fn remove_realm_config_account(&mut self, realm_config_address: &Pubkey) {
-        self.bench.remove_account(realm_config_address);
-    }
+        let ed25519_ix = create_ed25519_instruction(governance_authority, &message);
 
-    #[allow(dead_code)]
-    pub async fn get_governance_account(&mut self, governance_address: &Pubkey) -> GovernanceV2 {
+        let register_vote_intent_ix = register_vote_intent(
+            &self.program_id,
+            &proposal_cookie.address,
+            &token_owner_record_cookie.address,
+            &self.bench.payer.pubkey(),
+            vote,
+        );
+
+        // Only the payer signs the transaction
         self.bench
-            .get_borsh_account::<GovernanceV2>(governance_address)
-            .await
+            .process_transaction(&[ed25519_ix, register_vote_intent_ix], None)
+            .await?;
+
+        Ok(get_vote_intent_address(
+            &self.program_id,
+            &proposal_cookie.address,
+            &token_owner_record_cookie.address,
+        ))
     }
 
     #[allow(dead_code)]
-    pub async fn get_proposal_account(&mut self, proposal_address: &Pubkey) -> ProposalV2 {
-        self.bench
-            .get_borsh_account::<ProposalV2>(proposal_address)
-            .await
+    pub async fn relay_vote_with_vote_intent(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        governance_authority: &Pubkey,
+        vote: Vote,
+    ) -> Result<(), ProgramError> {
+        let cast_vote_ix = cast_vote_with_vote_intent(
+            &self.program_id,
+            &token_owner_record_cookie.account.realm,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &proposal_cookie.account.token_owner_record,
+            &token_owner_record_cookie.address,
+            governance_authority,
+            &token_owner_record_cookie.account.governing_token_mint,
+            &self.bench.payer.pubkey(),
+            None,
+            None,
+            vote,
+        );
+
+        self.bench.process_transaction(&[cast_vote_ix], None).await
     }
 
     #[allow(dead_code)]
-    pub async fn get_vote_record_account(&mut self, vote_record_address: &Pubkey) -> VoteRecordV2 {
-        self.bench
-            .get_borsh_account::<VoteRecordV2>(vote_record_address)
-            .await
+    pub async fn with_vote_session_key(
+        &mut self,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        expiry_slot: Slot,
+    ) -> Result<VoteSessionKeyCookie, ProgramError> {
+        let session_key = Keypair::new();
+
+        self.set_vote_session_key_expiry(
+            token_owner_record_cookie,
+            &session_key.pubkey(),
+            expiry_slot,
+        )
+        .await?;
+
+        Ok(VoteSessionKeyCookie {
+            address: get_vote_session_key_address(
+                &self.program_id,
+                &token_owner_record_cookie.address,
+                &session_key.pubkey(),
+            ),
+            session_key,
+        })
     }
 
     #[allow(dead_code)]
-    pub async fn get_proposal_transaction_account(
+    pub async fn set_vote_session_key_expiry(
         &mut self,
-        proposal_transaction_address: &Pubkey,
-    ) -> ProposalTransactionV2 {
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        session_key: &Pubkey,
+        expiry_slot: Slot,
+    ) -> Result<(), ProgramError> {
+        let create_vote_session_key_ix = create_vote_session_key(
+            &self.program_id,
+            &token_owner_record_cookie.address,
+            &token_owner_record_cookie.token_owner.pubkey(),
+            &self.bench.payer.pubkey(),
+            session_key,
+            expiry_slot,
+        );
+
         self.bench
-            .get_borsh_account::<ProposalTransactionV2>(proposal_transaction_address)
+            .process_transaction(
+                &[create_vote_session_key_ix],
+                Some(&[&token_owner_record_cookie.token_owner]),
+            )
             .await
     }
 
     #[allow(dead_code)]
-    pub async fn get_required_signatory_account(
+    pub async fn cast_vote_with_vote_session_key(
         &mut self,
-        required_signatory_address: &Pubkey,
-    ) -> RequiredSignatory {
-        self.bench
+        proposal_cookie: &ProposalCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        vote_session_key_cookie: &VoteSessionKeyCookie,
+        vote: Vote,
+    ) -> Result<(), ProgramError> {
+        let cast_vote_ix = cast_vote_with_vote_session_key(
+            &self.program_id,
+            &token_owner_record_cookie.account.realm,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &proposal_cookie.account.token_owner_record,
+            &token_owner_record_cookie.address,
+            &vote_session_key_cookie.session_key.pubkey(),
+            &token_owner_record_cookie.account.governing_token_mint,
+            &self.bench.payer.pubkey(),
+            None,
+            None,
+            vote,
+        );
+
+        self.bench
+            .process_transaction(
+                &[cast_vote_ix],
+                Some(&[&vote_session_key_cookie.session_key]),
+            )
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn relinquish_vote_with_vote_session_key(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        vote_session_key_cookie: &VoteSessionKeyCookie,
+    ) -> Result<(), ProgramError> {
+        let relinquish_vote_ix = relinquish_vote_with_vote_session_key(
+            &self.program_id,
+            &token_owner_record_cookie.account.realm,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &token_owner_record_cookie.address,
+            &token_owner_record_cookie.account.governing_token_mint,
+            &vote_session_key_cookie.session_key.pubkey(),
+            &self.bench.payer.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(
+                &[relinquish_vote_ix],
+                Some(&[&vote_session_key_cookie.session_key]),
+            )
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_funding_round(
+        &mut self,
+        realm_cookie: &RealmCookie,
+        governance_cookie: &mut GovernanceCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        max_contribution_per_contributor: u64,
+    ) -> Result<FundingRoundCookie, ProgramError> {
+        let mint_keypair = Keypair::new();
+        let mint_authority = Keypair::new();
+
+        self.bench
+            .create_mint(&mint_keypair, &mint_authority.pubkey(), None)
+            .await;
+
+        // The round is created by a Proposal and paid for by the native treasury
+        let native_treasury_cookie = self.with_native_treasury(governance_cookie).await;
+
+        let clock = self.bench.get_clock().await;
+        let config = FundingRoundConfig {
+            contributions_end_at: clock.unix_timestamp + FUNDING_ROUND_CONTRIBUTIONS_TIME,
+            max_contribution_per_contributor,
+        };
+
+        let round_seed = Pubkey::new_unique();
+
+        let mut create_funding_round_ix = create_funding_round(
+            &self.program_id,
+            &realm_cookie.address,
+            &governance_cookie.address,
+            &mint_keypair.pubkey(),
+            &native_treasury_cookie.address,
+            &round_seed,
+            config.clone(),
+        );
+
+        let mut proposal_cookie = self
+            .with_proposal(token_owner_record_cookie, governance_cookie)
+            .await?;
+
+        let signatory_record_cookie = self
+            .with_signatory(
+                &proposal_cookie,
+                governance_cookie,
+                token_owner_record_cookie,
+            )
+            .await?;
+
+        let proposal_transaction_cookie = self
+            .with_proposal_transaction(
+                &mut proposal_cookie,
+                token_owner_record_cookie,
+                0,
+                None,
+                &mut create_funding_round_ix,
+            )
+            .await?;
+
+        self.sign_off_proposal(&proposal_cookie, &signatory_record_cookie)
+            .await?;
+
+        self.with_cast_yes_no_vote(&proposal_cookie, token_owner_record_cookie, YesNoVote::Yes)
+            .await?;
+
+        self.advance_clock_by_min_timespan(
+            governance_cookie.account.config.transactions_hold_up_time as u64,
+        )
+        .await;
+
+        self.execute_proposal_transaction(&proposal_cookie, &proposal_transaction_cookie)
+            .await?;
+
+        let funding_round_address =
+            get_funding_round_address(&self.program_id, &governance_cookie.address, &round_seed);
+
+        Ok(FundingRoundCookie {
+            address: funding_round_address,
+            escrow: get_funding_round_escrow_address(&self.program_id, &funding_round_address),
+            governance: governance_cookie.address,
+            mint: mint_keypair.pubkey(),
+            mint_authority,
+            config,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn fund_funding_round(
+        &mut self,
+        funding_round_cookie: &FundingRoundCookie,
+        amount: u64,
+    ) {
+        self.bench
+            .mint_tokens(
+                &funding_round_cookie.mint,
+                &funding_round_cookie.mint_authority,
+                &funding_round_cookie.escrow,
+                amount,
+            )
+            .await;
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_funding_project(
+        &mut self,
+        funding_round_cookie: &FundingRoundCookie,
+    ) -> Result<FundingProjectCookie, ProgramError> {
+        let project_owner = Keypair::new();
+        let recipient_keypair = Keypair::new();
+
+        self.bench
+            .create_empty_token_account(
+                &recipient_keypair,
+                &funding_round_cookie.mint,
+                &project_owner.pubkey(),
+            )
+            .await;
+
+        let register_funding_project_ix = register_funding_project(
+            &self.program_id,
+            &funding_round_cookie.address,
+            &project_owner.pubkey(),
+            &recipient_keypair.pubkey(),
+            &self.bench.payer.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(&[register_funding_project_ix], Some(&[&project_owner]))
+            .await?;
+
+        Ok(FundingProjectCookie {
+            address: get_funding_project_address(
+                &self.program_id,
+                &funding_round_cookie.address,
+                &project_owner.pubkey(),
+            ),
+            project_owner,
+            recipient: recipient_keypair.pubkey(),
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn contribute_to_funding_project(
+        &mut self,
+        funding_round_cookie: &FundingRoundCookie,
+        funding_project_cookie: &FundingProjectCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        amount: u64,
+    ) -> Result<(), ProgramError> {
+        let governance_authority = token_owner_record_cookie.get_governance_authority();
+        let source_keypair = Keypair::new();
+
+        self.bench
+            .create_empty_token_account(
+                &source_keypair,
+                &funding_round_cookie.mint,
+                &governance_authority.pubkey(),
+            )
+            .await;
+
+        self.bench
+            .mint_tokens(
+                &funding_round_cookie.mint,
+                &funding_round_cookie.mint_authority,
+                &source_keypair.pubkey(),
+                amount,
+            )
+            .await;
+
+        let contribute_to_funding_project_ix = contribute_to_funding_project(
+            &self.program_id,
+            &funding_round_cookie.address,
+            &funding_project_cookie.address,
+            &token_owner_record_cookie.address,
+            &governance_authority.pubkey(),
+            &source_keypair.pubkey(),
+            &funding_project_cookie.recipient,
+            &self.bench.payer.pubkey(),
+            amount,
+        );
+
+        self.bench
+            .process_transaction(
+                &[contribute_to_funding_project_ix],
+                Some(&[governance_authority]),
+            )
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn finalize_funding_round(
+        &mut self,
+        funding_round_cookie: &FundingRoundCookie,
+    ) -> Result<Pubkey, ProgramError> {
+        let governance_destination_keypair = Keypair::new();
+
+        self.bench
+            .create_empty_token_account(
+                &governance_destination_keypair,
+                &funding_round_cookie.mint,
+                &funding_round_cookie.governance,
+            )
+            .await;
+
+        let finalize_funding_round_ix = finalize_funding_round(
+            &self.program_id,
+            &funding_round_cookie.address,
+            &governance_destination_keypair.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(&[finalize_funding_round_ix], None)
+            .await?;
+
+        Ok(governance_destination_keypair.pubkey())
+    }
+
+    #[allow(dead_code)]
+    pub async fn claim_funding_match(
+        &mut self,
+        funding_round_cookie: &FundingRoundCookie,
+        funding_project_cookie: &FundingProjectCookie,
+    ) -> Result<(), ProgramError> {
+        let claim_funding_match_ix = claim_funding_match(
+            &self.program_id,
+            &funding_round_cookie.address,
+            &funding_project_cookie.address,
+            &funding_project_cookie.recipient,
+        );
+
+        self.bench
+            .process_transaction(&[claim_funding_match_ix], None)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_council_election(
+        &mut self,
+        realm_cookie: &RealmCookie,
+        seats_count: u8,
+    ) -> Result<CouncilElectionCookie, ProgramError> {
+        let clock = self.bench.get_clock().await;
+        let voting_end_at = clock.unix_timestamp + COUNCIL_ELECTION_VOTING_TIME;
+
+        let config = CouncilElectionConfig {
+            voting_end_at,
+            seats_count,
+            term_end_at: voting_end_at + COUNCIL_TERM_TIME,
+        };
+
+        let election_seed = Pubkey::new_unique();
+        let realm_authority = realm_cookie.realm_authority.as_ref().unwrap();
+
+        let create_council_election_ix = create_council_election(
+            &self.program_id,
+            &realm_cookie.address,
+            &realm_authority.pubkey(),
+            &self.bench.payer.pubkey(),
+            &election_seed,
+            config.clone(),
+        );
+
+        self.bench
+            .process_transaction(&[create_council_election_ix], Some(&[realm_authority]))
+            .await?;
+
+        Ok(CouncilElectionCookie {
+            address: get_council_election_address(
+                &self.program_id,
+                &realm_cookie.address,
+                &election_seed,
+            ),
+            realm: realm_cookie.address,
+            config,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_council_candidate(
+        &mut self,
+        council_election_cookie: &CouncilElectionCookie,
+    ) -> Result<CouncilCandidateCookie, ProgramError> {
+        let candidate = Keypair::new();
+
+        let register_council_candidate_ix = register_council_candidate(
+            &self.program_id,
+            &council_election_cookie.address,
+            &candidate.pubkey(),
+            &self.bench.payer.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(&[register_council_candidate_ix], Some(&[&candidate]))
+            .await?;
+
+        Ok(CouncilCandidateCookie {
+            address: get_council_candidate_address(
+                &self.program_id,
+                &council_election_cookie.address,
+                &candidate.pubkey(),
+            ),
+            candidate,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn cast_council_election_vote(
+        &mut self,
+        council_election_cookie: &CouncilElectionCookie,
+        council_candidate_cookie: &CouncilCandidateCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+    ) -> Result<(), ProgramError> {
+        let governance_authority = token_owner_record_cookie.get_governance_authority();
+
+        let cast_council_election_vote_ix = cast_council_election_vote(
+            &self.program_id,
+            &council_election_cookie.realm,
+            &council_election_cookie.address,
+            &council_candidate_cookie.address,
+            &token_owner_record_cookie.address,
+            &governance_authority.pubkey(),
+            &self.bench.payer.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(
+                &[cast_council_election_vote_ix],
+                Some(&[governance_authority]),
+            )
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn finalize_council_election(
+        &mut self,
+        council_election_cookie: &CouncilElectionCookie,
+        council_candidate_cookies: &[&CouncilCandidateCookie],
+    ) -> Result<(), ProgramError> {
+        let council_candidates = council_candidate_cookies
+            .iter()
+            .map(|council_candidate_cookie| council_candidate_cookie.address)
+            .collect::<Vec<_>>();
+
+        let finalize_council_election_ix = finalize_council_election(
+            &self.program_id,
+            &council_election_cookie.realm,
+            &council_election_cookie.address,
+            &self.bench.payer.pubkey(),
+            &council_candidates,
+        );
+
+        self.bench
+            .process_transaction(&[finalize_council_election_ix], None)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn claim_council_seat(
+        &mut self,
+        council_election_cookie: &CouncilElectionCookie,
+        council_candidate_cookie: &CouncilCandidateCookie,
+    ) -> Result<Pubkey, ProgramError> {
+        let claim_council_seat_ix = claim_council_seat(
+            &self.program_id,
+            &council_election_cookie.address,
+            &council_candidate_cookie.candidate.pubkey(),
+            &self.bench.payer.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(&[claim_council_seat_ix], None)
+            .await?;
+
+        Ok(get_council_seat_address(
+            &self.program_id,
+            &council_election_cookie.address,
+            &council_candidate_cookie.candidate.pubkey(),
+        ))
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_cast_vote_with_council_seat(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        council_seat: &Pubkey,
+        vote: Vote,
+    ) -> Result<(), ProgramError> {
+        let cast_vote_ix = cast_vote_with_council_seat(
+            &self.program_id,
+            &token_owner_record_cookie.account.realm,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &proposal_cookie.account.token_owner_record,
+            &token_owner_record_cookie.address,
+            &token_owner_record_cookie.token_owner.pubkey(),
+            &token_owner_record_cookie.account.governing_token_mint,
+            &self.bench.payer.pubkey(),
+            council_seat,
+            None,
+            None,
+            vote,
+        );
+
+        self.bench
+            .process_transaction(
+                &[cast_vote_ix],
+                Some(&[&token_owner_record_cookie.token_owner]),
+            )
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn execute_proposal_transaction_with_simulation_attestation(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        proposal_transaction_cookie: &ProposalTransactionCookie,
+        simulation_oracle: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let execute_proposal_transaction_ix = execute_transaction_with_simulation_attestation(
+            &self.program_id,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &proposal_transaction_cookie.address,
+            simulation_oracle,
+            &proposal_transaction_cookie.instruction.program_id,
+            &proposal_transaction_cookie.instruction.accounts,
+        );
+
+        self.bench
+            .process_transaction(&[execute_proposal_transaction_ix], None)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn execute_proposal_transaction_with_dependencies(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        proposal_transaction_cookie: &ProposalTransactionCookie,
+        depends_on_proposals: &[Pubkey],
+    ) -> Result<(), ProgramError> {
+        let execute_proposal_transaction_ix = execute_transaction_with_dependencies(
+            &self.program_id,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &proposal_transaction_cookie.address,
+            depends_on_proposals,
+            &proposal_transaction_cookie.instruction.program_id,
+            &proposal_transaction_cookie.instruction.accounts,
+        );
+
+        self.bench
+            .process_transaction(&[execute_proposal_transaction_ix], None)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_proposal_dependency(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        depends_on_proposal_cookie: &ProposalCookie,
+    ) -> Result<ProposalDependencyCookie, ProgramError> {
+        let governance_authority = token_owner_record_cookie.get_governance_authority();
+
+        let add_proposal_dependency_ix = add_proposal_dependency(
+            &self.program_id,
+            &proposal_cookie.address,
+            &proposal_cookie.account.token_owner_record,
+            &governance_authority.pubkey(),
+            &proposal_cookie.account.governance,
+            &depends_on_proposal_cookie.address,
+            &depends_on_proposal_cookie.account.governance,
+            &self.bench.payer.pubkey(),
+        );
+
+        self.bench
+            .process_transaction(&[add_proposal_dependency_ix], Some(&[governance_authority]))
+            .await?;
+
+        let account = ProposalDependency {
+            account_type: GovernanceAccountType::ProposalDependency,
+            proposal: proposal_cookie.address,
+            depends_on: depends_on_proposal_cookie.address,
+            reserved: [0; 64],
+        };
+
+        Ok(ProposalDependencyCookie {
+            address: get_proposal_dependency_address(
+                &self.program_id,
+                &proposal_cookie.address,
+                &depends_on_proposal_cookie.address,
+            ),
+            account,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_proposal_link(
+        &mut self,
+        linked_proposals: &[(&ProposalCookie, &TokenOwnerRecordCookie)],
+        threshold: u8,
+    ) -> Result<ProposalLinkCookie, ProgramError> {
+        let governance_authorities: Vec<&Keypair> = linked_proposals
+            .iter()
+            .map(|(_, token_owner_record_cookie)| {
+                token_owner_record_cookie.get_governance_authority()
+            })
+            .collect();
+
+        let link_proposals_ix = link_proposals(
+            &self.program_id,
+            &linked_proposals
+                .iter()
+                .zip(governance_authorities.iter())
+                .map(|((proposal_cookie, _), governance_authority)| {
+                    (
+                        proposal_cookie.address,
+                        proposal_cookie.account.token_owner_record,
+                        governance_authority.pubkey(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            &self.bench.payer.pubkey(),
+            threshold,
+        );
+
+        self.bench
+            .process_transaction(&[link_proposals_ix], Some(&governance_authorities))
+            .await?;
+
+        let proposals: Vec<Pubkey> = linked_proposals
+            .iter()
+            .map(|(proposal_cookie, _)| proposal_cookie.address)
+            .collect();
+
+        let account = ProposalLink {
+            account_type: GovernanceAccountType::ProposalLink,
+            proposals: proposals.clone(),
+            threshold,
+            state: ProposalLinkState::Pending,
+            resolved_at: None,
+            reserved: [0; 64],
+        };
+
+        Ok(ProposalLinkCookie {
+            address: get_proposal_link_address(&self.program_id, &proposals[0]),
+            account,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn resolve_proposal_link(
+        &mut self,
+        proposal_link_cookie: &ProposalLinkCookie,
+    ) -> Result<(), ProgramError> {
+        let resolve_proposal_link_ix =
+            resolve_proposal_link(&self.program_id, &proposal_link_cookie.account.proposals);
+
+        self.bench
+            .process_transaction(&[resolve_proposal_link_ix], None)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_token_owner_record_account(&mut self, address: &Pubkey) -> TokenOwnerRecordV2 {
+        self.bench
+            .get_borsh_account::<TokenOwnerRecordV2>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_program_metadata_account(&mut self, address: &Pubkey) -> ProgramMetadata {
+        self.bench
+            .get_borsh_account::<ProgramMetadata>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_native_treasury_account(&mut self, address: &Pubkey) -> NativeTreasury {
+        self.bench
+            .get_borsh_account::<NativeTreasury>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_proposal_link_account(&mut self, address: &Pubkey) -> ProposalLink {
+        self.bench.get_borsh_account::<ProposalLink>(address).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_proposal_dependency_account(
+        &mut self,
+        address: &Pubkey,
+    ) -> ProposalDependency {
+        self.bench
+            .get_borsh_account::<ProposalDependency>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_proposal_deposit_account(&mut self, address: &Pubkey) -> ProposalDeposit {
+        self.bench
+            .get_borsh_account::<ProposalDeposit>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_proposal_archive_account(&mut self, address: &Pubkey) -> ProposalArchive {
+        self.bench
+            .get_borsh_account::<ProposalArchive>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_proposal_token_deposit_account(
+        &mut self,
+        address: &Pubkey,
+    ) -> ProposalTokenDeposit {
+        self.bench
+            .get_borsh_account::<ProposalTokenDeposit>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_realm_account(&mut self, realm_address: &Pubkey) -> RealmV2 {
+        self.bench.get_borsh_account::<RealmV2>(realm_address).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_realm_config_account(
+        &mut self,
+        realm_config_address: &Pubkey,
+    ) -> RealmConfigAccount {
+        self.bench
+            .get_borsh_account::<RealmConfigAccount>(realm_config_address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_addin_registry_account(
+        &mut self,
+        realm_cookie: &RealmCookie,
+    ) -> AddinRegistry {
+        self.bench
+            .get_borsh_account::<AddinRegistry>(&get_addin_registry_address(
+                &self.program_id,
+                &realm_cookie.address,
+            ))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub fn remove_realm_config_account(&mut self, realm_config_address: &Pubkey) {
+        self.bench.remove_account(realm_config_address);
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_governance_account(&mut self, governance_address: &Pubkey) -> GovernanceV2 {
+        self.bench
+            .get_borsh_account::<GovernanceV2>(governance_address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_proposal_account(&mut self, proposal_address: &Pubkey) -> ProposalV2 {
+        self.bench
+            .get_borsh_account::<ProposalV2>(proposal_address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_vote_record_account(&mut self, vote_record_address: &Pubkey) -> VoteRecordV2 {
+        self.bench
+            .get_borsh_account::<VoteRecordV2>(vote_record_address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_vote_intent_account(&mut self, vote_intent_address: &Pubkey) -> VoteIntent {
+        self.bench
+            .get_borsh_account::<VoteIntent>(vote_intent_address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_vote_session_key_account(&mut self, address: &Pubkey) -> VoteSessionKey {
+        self.bench
+            .get_borsh_account::<VoteSessionKey>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_funding_round_account(&mut self, address: &Pubkey) -> FundingRound {
+        self.bench.get_borsh_account::<FundingRound>(address).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_funding_project_account(&mut self, address: &Pubkey) -> FundingProject {
+        self.bench
+            .get_borsh_account::<FundingProject>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_council_election_account(&mut self, address: &Pubkey) -> CouncilElection {
+        self.bench
+            .get_borsh_account::<CouncilElection>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_council_candidate_account(&mut self, address: &Pubkey) -> CouncilCandidate {
+        self.bench
+            .get_borsh_account::<CouncilCandidate>(address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_council_seat_account(&mut self, address: &Pubkey) -> CouncilSeat {
+        self.bench.get_borsh_account::<CouncilSeat>(address).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_proposal_transaction_account(
+        &mut self,
+        proposal_transaction_address: &Pubkey,
+    ) -> ProposalTransactionV2 {
+        self.bench
+            .get_borsh_account::<ProposalTransactionV2>(proposal_transaction_address)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_required_signatory_account(
+        &mut self,
+        required_signatory_address: &Pubkey,
+    ) -> RequiredSignatory {
+        self.bench
             .get_borsh_account::<RequiredSignatory>(required_signatory_address)
             .await
     }
@@ -3200,6 +4314,22 @@ impl GovernanceProgramTest {
             .await
     }
 
+    #[allow(dead_code)]
+    pub async fn with_addin_registry_entry(
+        &mut self,
+        realm_cookie: &RealmCookie,
+        addin: &Pubkey,
+        min_version: Slot,
+    ) -> Result<(), ProgramError> {
+        let args = SetRealmConfigItemArgs::AddinRegistryEntry {
+            action: SetConfigItemActionType::Add,
+            program_id: *addin,
+            min_version,
+        };
+
+        self.set_realm_config_item(realm_cookie, args).await
+    }
+
     #[allow(dead_code)]
     pub async fn with_community_token_owner_record_lock_authority(
         &mut self,
@@ -3242,3 +4372,37 @@ impl GovernanceProgramTest {
         })
     }
 }
+
+/// Creates ed25519 program instruction verifying the signature of the given
+/// message, with the signature, public key and message stored in the
+/// instruction data
+#[allow(dead_code)]
+pub fn create_ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+    let signature = signer.sign_message(message);
+
+    let public_key_offset: u16 = 2 + 14;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1, 0];
+    for offset in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: solana_program::ed25519_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
@@ -0,0 +1,191 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+
+use {program_test::*, solana_program_test::tokio, spl_governance::state::enums::ProposalState};
+
+#[tokio::test]
+async fn test_cast_vote_records_voter_participation() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie1 = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let token_owner_record_cookie2 = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie1)
+        .await
+        .unwrap();
+
+    // Total 600 tokens
+    governance_test
+        .mint_community_tokens(&realm_cookie, 400)
+        .await;
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie1, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let vote_record_cookie1 = governance_test
+        .with_cast_yes_no_vote(
+            &proposal_cookie,
+            &token_owner_record_cookie1,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    let vote_record_cookie2 = governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie2, YesNoVote::No)
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(ProposalState::Voting, proposal_account.state);
+    assert_eq!(2, proposal_account.votes_count);
+    assert_eq!(2, proposal_account.voters_count);
+
+    let vote_record_account1 = governance_test
+        .get_vote_record_account(&vote_record_cookie1.address)
+        .await;
+
+    assert_eq!(1, vote_record_account1.voter_number);
+
+    let vote_record_account2 = governance_test
+        .get_vote_record_account(&vote_record_cookie2.address)
+        .await;
+
+    assert_eq!(2, vote_record_account2.voter_number);
+}
+
+#[tokio::test]
+async fn test_relinquish_active_vote_removes_voter_participation() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie1 = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let token_owner_record_cookie2 = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie1)
+        .await
+        .unwrap();
+
+    // Total 600 tokens
+    governance_test
+        .mint_community_tokens(&realm_cookie, 400)
+        .await;
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie1, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    governance_test
+        .with_cast_yes_no_vote(
+            &proposal_cookie,
+            &token_owner_record_cookie1,
+            YesNoVote::Yes,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    governance_test
+        .relinquish_vote(&proposal_cookie, &token_owner_record_cookie1)
+        .await
+        .unwrap();
+
+    let vote_record_cookie2 = governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie2, YesNoVote::No)
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(2, proposal_account.votes_count);
+    assert_eq!(1, proposal_account.voters_count);
+
+    let vote_record_account2 = governance_test
+        .get_vote_record_account(&vote_record_cookie2.address)
+        .await;
+
+    assert_eq!(2, vote_record_account2.voter_number);
+}
+
+#[tokio::test]
+async fn test_relinquish_voted_proposal_keeps_voter_participation() {
+    // Arrange
+    let mut governance_test = GovernanceProgramTest::start_new().await;
+
+    let realm_cookie = governance_test.with_realm().await;
+
+    let token_owner_record_cookie = governance_test
+        .with_community_token_deposit(&realm_cookie)
+        .await
+        .unwrap();
+
+    let mut governance_cookie = governance_test
+        .with_governance(&realm_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    let proposal_cookie = governance_test
+        .with_signed_off_proposal(&token_owner_record_cookie, &mut governance_cookie)
+        .await
+        .unwrap();
+
+    let vote_record_cookie = governance_test
+        .with_cast_yes_no_vote(&proposal_cookie, &token_owner_record_cookie, YesNoVote::Yes)
+        .await
+        .unwrap();
+
+    // Act
+    governance_test
+        .relinquish_vote(&proposal_cookie, &token_owner_record_cookie)
+        .await
+        .unwrap();
+
+    // Assert
+    let proposal_account = governance_test
+        .get_proposal_account(&proposal_cookie.address)
+        .await;
+
+    assert_eq!(ProposalState::Succeeded, proposal_account.state);
+    assert_eq!(1, proposal_account.votes_count);
+    assert_eq!(1, proposal_account.voters_count);
+
+    let vote_record_account = governance_test
+        .get_vote_record_account(&vote_record_cookie.address)
+        .await;
+
+    assert!(vote_record_account.is_relinquished);
+    assert_eq!(1, vote_record_account.voter_number);
+}
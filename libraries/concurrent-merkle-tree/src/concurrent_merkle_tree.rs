@@ -122,6 +122,20 @@ pub struct FillEmptyOrAppendArgs {
     pub index: u32,
 }
 
+/// Arguments structure for appending a subtree root to the tree.
+///
+/// The subtree occupies `power(2, subtree_depth)` leaf slots starting at the
+/// current rightmost index of the tree. `proof_vec` is the proof of the
+/// subtree's rightmost leaf within the subtree (`subtree_depth` nodes) and
+/// `index` is the position of that leaf within the subtree.
+pub struct AppendSubtreeArgs {
+    pub subtree_root: Node,
+    pub subtree_depth: u32,
+    pub rightmost_leaf: Node,
+    pub proof_vec: Vec<Node>,
+    pub index: u32,
+}
+
 /// Arguments structure for proving a leaf in the tree.
 pub struct ProveLeafArgs {
     pub current_root: Node,
@@ -341,6 +355,120 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize>
         Ok(node)
     }
 
+    /// Appends the root of a subtree of depth `subtree_depth`, occupying the
+    /// next `power(2, subtree_depth)` leaf slots of the tree.
+    ///
+    /// The current rightmost index of the tree must be a multiple of the
+    /// subtree's leaf capacity. The provided proof of the subtree's rightmost
+    /// leaf must recompute to the subtree root, and every node to the right
+    /// of that leaf must be empty, so that subsequent appends continue right
+    /// after it.
+    pub fn append_subtree(
+        &mut self,
+        args: &AppendSubtreeArgs,
+    ) -> Result<Node, ConcurrentMerkleTreeError> {
+        check_bounds(MAX_DEPTH, MAX_BUFFER_SIZE);
+        if !self.is_initialized() {
+            return Err(ConcurrentMerkleTreeError::TreeNotInitialized);
+        }
+        if args.subtree_root == EMPTY {
+            return Err(ConcurrentMerkleTreeError::CannotAppendEmptyNode);
+        }
+        let subtree_depth = args.subtree_depth as usize;
+        if subtree_depth > MAX_DEPTH || args.proof_vec.len() != subtree_depth {
+            return Err(ConcurrentMerkleTreeError::InvalidSubtreeDepth);
+        }
+        let subtree_size = 1 << subtree_depth;
+        if args.index >= subtree_size {
+            return Err(ConcurrentMerkleTreeError::LeafIndexOutOfBounds);
+        }
+        let start_index = self.rightmost_proof.index;
+        if start_index + subtree_size > 1 << MAX_DEPTH {
+            return Err(ConcurrentMerkleTreeError::TreeFull);
+        }
+        if start_index & (subtree_size - 1) != 0 {
+            solana_logging!(
+                "Subtree of depth {} cannot start at index {}",
+                subtree_depth,
+                start_index
+            );
+            return Err(ConcurrentMerkleTreeError::SubtreeMisaligned);
+        }
+        if start_index == 0 && self.get_root() != empty_node(MAX_DEPTH as u32) {
+            return Err(ConcurrentMerkleTreeError::TreeAlreadyInitialized);
+        }
+
+        let mut change_list = [EMPTY; MAX_DEPTH];
+        let mut proof = [EMPTY; MAX_DEPTH];
+        let mut node = args.rightmost_leaf;
+        let empty_node_cache = [Node::default(); MAX_DEPTH];
+
+        // Recompute the subtree root from its rightmost leaf
+        for i in 0..subtree_depth {
+            let sibling = args.proof_vec[i];
+            let is_left = (args.index >> i) & 1 == 0;
+            if is_left && sibling != empty_node_cached::<MAX_DEPTH>(i as u32, &empty_node_cache) {
+                solana_logging!("Subtree has non-empty nodes to the right of its rightmost leaf");
+                return Err(ConcurrentMerkleTreeError::InvalidProof);
+            }
+            change_list[i] = node;
+            proof[i] = sibling;
+            hash_to_parent(&mut node, &sibling, is_left);
+        }
+        if node != args.subtree_root {
+            solana_logging!("Subtree proof failed to verify");
+            return Err(ConcurrentMerkleTreeError::InvalidProof);
+        }
+
+        // Compute the node where the subtree intersects the main tree
+        let intersection = start_index.trailing_zeros() as usize;
+        let mut intersection_node = self.rightmost_proof.leaf;
+        if start_index != 0 {
+            for i in 0..intersection {
+                hash_to_parent(
+                    &mut intersection_node,
+                    &self.rightmost_proof.proof[i],
+                    ((start_index - 1) >> i) & 1 == 0,
+                );
+            }
+        }
+
+        for i in subtree_depth..MAX_DEPTH {
+            change_list[i] = node;
+            match i {
+                i if i < intersection => {
+                    let sibling = empty_node_cached::<MAX_DEPTH>(i as u32, &empty_node_cache);
+                    hash_to_parent(&mut node, &sibling, true);
+                    proof[i] = sibling;
+                }
+                i if i == intersection => {
+                    hash_to_parent(&mut node, &intersection_node, false);
+                    proof[i] = intersection_node;
+                }
+                _ => {
+                    hash_to_parent(
+                        &mut node,
+                        &self.rightmost_proof.proof[i],
+                        ((start_index - 1) >> i) & 1 == 0,
+                    );
+                    proof[i] = self.rightmost_proof.proof[i];
+                }
+            }
+        }
+
+        let leaf_index = start_index + args.index;
+        self.update_internal_counters();
+        self.change_logs[self.active_index as usize] =
+            ChangeLog::<MAX_DEPTH>::new(node, change_list, leaf_index);
+        self.rightmost_proof = Path {
+            proof,
+            index: leaf_index + 1,
+            leaf: args.rightmost_leaf,
+            _padding: 0,
+        };
+        Ok(node)
+    }
+
     /// Convenience function for `set_leaf`
     ///
     /// This method will `set_leaf` if the leaf at `index` is an empty node,
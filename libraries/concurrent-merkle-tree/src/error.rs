@@ -39,4 +39,13 @@ pub enum ConcurrentMerkleTreeError {
     /// Tree has at least 1 non-EMPTY leaf
     #[error("Tree is not empty")]
     TreeNonEmpty,
+
+    /// The subtree depth exceeds the tree depth or doesn't match the proof
+    /// length
+    #[error("Subtree depth is larger than the tree depth or doesn't match the proof length")]
+    InvalidSubtreeDepth,
+
+    /// The subtree doesn't start at a multiple of its leaf capacity
+    #[error("Rightmost index of the tree is not aligned to the subtree size")]
+    SubtreeMisaligned,
 }
@@ -3,8 +3,8 @@ use {
     rand::{self, thread_rng, Rng},
     spl_concurrent_merkle_tree::{
         concurrent_merkle_tree::{
-            ConcurrentMerkleTree, FillEmptyOrAppendArgs, InitializeWithRootArgs, ProveLeafArgs,
-            SetLeafArgs,
+            AppendSubtreeArgs, ConcurrentMerkleTree, FillEmptyOrAppendArgs, InitializeWithRootArgs,
+            ProveLeafArgs, SetLeafArgs,
         },
         error::ConcurrentMerkleTreeError,
         node::{Node, EMPTY},
@@ -574,3 +574,117 @@ async fn test_prove_tree_empty_batched() {
     // Check that the last leaf was successfully removed
     cmt.prove_tree_is_empty().unwrap();
 }
+
+/// Builds the arguments to append a subtree of `subtree_depth` holding
+/// `leaves`, followed by empty leaves
+fn subtree_args(subtree_depth: u32, leaves: &[Node]) -> AppendSubtreeArgs {
+    let mut subtree_leaves = vec![EMPTY; 1 << subtree_depth];
+    subtree_leaves[..leaves.len()].copy_from_slice(leaves);
+    let subtree = MerkleTree::new(subtree_leaves.as_slice());
+    let index = leaves.len() - 1;
+    AppendSubtreeArgs {
+        subtree_root: subtree.get_root(),
+        subtree_depth,
+        rightmost_leaf: leaves[index],
+        proof_vec: subtree.get_proof_of_leaf(index),
+        index: index as u32,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_append_subtree() {
+    let (mut cmt, mut tree) = setup();
+    let mut rng = thread_rng();
+    cmt.initialize().unwrap();
+
+    // Full subtree into the empty tree
+    let leaves: Vec<Node> = (0..4).map(|_| rng.gen::<Node>()).collect();
+    cmt.append_subtree(&subtree_args(2, &leaves)).unwrap();
+    for (i, leaf) in leaves.iter().enumerate() {
+        tree.add_leaf(*leaf, i);
+    }
+    assert_eq!(cmt.get_change_log().root, tree.get_root());
+    assert_eq!(cmt.rightmost_proof.index, 4);
+
+    // Misaligned subtree
+    let leaves: Vec<Node> = (0..8).map(|_| rng.gen::<Node>()).collect();
+    assert_eq!(
+        cmt.append_subtree(&subtree_args(3, &leaves)).unwrap_err(),
+        ConcurrentMerkleTreeError::SubtreeMisaligned
+    );
+
+    // Partially filled subtree, following appends land right after it
+    for i in 4..8 {
+        let leaf = rng.gen::<Node>();
+        cmt.append(leaf).unwrap();
+        tree.add_leaf(leaf, i);
+    }
+    let leaves: Vec<Node> = (0..5).map(|_| rng.gen::<Node>()).collect();
+    cmt.append_subtree(&subtree_args(3, &leaves)).unwrap();
+    for (i, leaf) in leaves.iter().enumerate() {
+        tree.add_leaf(*leaf, 8 + i);
+    }
+    assert_eq!(cmt.get_change_log().root, tree.get_root());
+    assert_eq!(cmt.rightmost_proof.index, 13);
+
+    let leaf = rng.gen::<Node>();
+    cmt.append(leaf).unwrap();
+    tree.add_leaf(leaf, 13);
+    assert_eq!(cmt.get_change_log().root, tree.get_root());
+
+    // Leaves of the subtree can be proven and replaced
+    for index in [8, 12] {
+        cmt.prove_leaf(&ProveLeafArgs {
+            current_root: tree.get_root(),
+            leaf: tree.get_leaf(index),
+            proof_vec: tree.get_proof_of_leaf(index),
+            index: index as u32,
+        })
+        .unwrap();
+    }
+    let new_leaf = rng.gen::<Node>();
+    cmt.set_leaf(&SetLeafArgs {
+        current_root: tree.get_root(),
+        previous_leaf: tree.get_leaf(9),
+        new_leaf,
+        proof_vec: tree.get_proof_of_leaf(9),
+        index: 9,
+    })
+    .unwrap();
+    tree.add_leaf(new_leaf, 9);
+    assert_eq!(cmt.get_change_log().root, tree.get_root());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_append_subtree_invalid_proof() {
+    let (mut cmt, _) = setup();
+    let mut rng = thread_rng();
+    cmt.initialize().unwrap();
+
+    // Wrong subtree root
+    let leaves: Vec<Node> = (0..4).map(|_| rng.gen::<Node>()).collect();
+    let mut args = subtree_args(2, &leaves);
+    args.subtree_root = rng.gen::<Node>();
+    assert_eq!(
+        cmt.append_subtree(&args).unwrap_err(),
+        ConcurrentMerkleTreeError::InvalidProof
+    );
+
+    // Claimed rightmost leaf has non-empty leaves to its right
+    let mut args = subtree_args(2, &leaves);
+    args.rightmost_leaf = leaves[1];
+    args.proof_vec = MerkleTree::new(leaves.as_slice()).get_proof_of_leaf(1);
+    args.index = 1;
+    assert_eq!(
+        cmt.append_subtree(&args).unwrap_err(),
+        ConcurrentMerkleTreeError::InvalidProof
+    );
+
+    // Subtree deeper than the tree
+    let mut args = subtree_args(2, &leaves);
+    args.subtree_depth = DEPTH as u32 + 1;
+    assert_eq!(
+        cmt.append_subtree(&args).unwrap_err(),
+        ConcurrentMerkleTreeError::InvalidSubtreeDepth
+    );
+}
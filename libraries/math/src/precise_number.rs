@@ -277,12 +277,15 @@ impl PreciseNumber {
 
     /// Get the power of a number, where the exponent is expressed as a fraction
     /// (numerator / denominator)
-    /// NOTE: this function is private because its accurate range and precision
-    /// have not been estbalished.
-    #[allow(dead_code)]
-    fn checked_pow_fraction(&self, exponent: &Self) -> Option<Self> {
-        assert!(self.value >= Self::min_pow_base());
-        assert!(self.value <= Self::max_pow_base());
+    ///
+    /// The fractional part of the exponent is approximated with a Taylor
+    /// Series around 1, so the base must be between 0 and 2, exclusive of 0.
+    /// The approximation converges quickly for bases close to 1, so callers
+    /// requiring precision should keep the base within [0.5, 1.5].
+    pub fn checked_pow_fraction(&self, exponent: &Self) -> Option<Self> {
+        if self.value < Self::min_pow_base() || self.value > Self::max_pow_base() {
+            return None;
+        }
         let whole_exponent = exponent.floor()?;
         let precise_whole = self.checked_pow(whole_exponent.to_imprecise()?)?;
         let (remainder_exponent, negative) = exponent.unsigned_sub(&whole_exponent);
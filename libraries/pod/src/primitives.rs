@@ -6,6 +6,10 @@ use bytemuck_derive::{Pod, Zeroable};
 use serde::{Deserialize, Serialize};
 
 /// The standard `bool` is not a `Pod`, define a replacement that is
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
 #[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde-traits", serde(from = "bool", into = "bool"))]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
@@ -68,6 +72,10 @@ macro_rules! impl_int_conversion {
 }
 
 /// `u16` type that can be used in `Pod`s
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
 #[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde-traits", serde(from = "u16", into = "u16"))]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
@@ -1,4 +1,5 @@
 use {
+    crate::state::get_class_validator_key,
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
         instruction::{AccountMeta, Instruction},
@@ -112,6 +113,49 @@ pub enum NameRegistryInstruction {
         /// the new space is less than the current space.
         space: u32,
     },
+
+    /// Set the program validating the record data of a class.
+    ///
+    /// The class validator account is created on first use, its address is a
+    /// program-derived address with seeds `[CLASS_VALIDATOR_SEED, class]`.
+    ///
+    /// Accounts expected by this instruction:
+    ///   0. `[]` System program
+    ///   1. `[writeable, signer]` Payer account
+    ///   2. `[writeable]` Class validator account
+    ///   3. `[signer]` Account class
+    SetClassValidator {
+        /// Program invoked by `UpdateValidated` for records of the class.
+        /// `Pubkey::default()` disables the validated updates.
+        validator_program_id: Pubkey,
+    },
+
+    /// Update the data in a name record of a class, checked by the validator
+    /// program of the class.
+    ///
+    /// The data is written like with `Update`, then the validator program is
+    /// invoked with the updated record, see `validate_record_data`. `Update`
+    /// leaves the data unchecked, so a class relying on its validator only
+    /// updates its records through this instruction.
+    ///
+    /// Accounts expected by this instruction:
+    ///   0. `[writeable]` Name record to be updated
+    ///   1. `[signer]` Account class
+    ///   2. `[]` Class validator account
+    ///   3. `[]` Validator program
+    UpdateValidated { offset: u32, data: Vec<u8> },
+}
+
+/// Instruction data passed to a class validator program on `UpdateValidated`.
+///
+/// The validator is invoked after the data was written, with the updated name
+/// record as its only (read-only) account, and must fail to reject the update.
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct ValidateRecordData {
+    /// Offset of the written data, after the `NameRecordHeader`
+    pub offset: u32,
+    /// Data written to the name record
+    pub data: Vec<u8>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -179,6 +223,47 @@ pub fn update(
     })
 }
 
+pub fn update_validated(
+    name_service_program_id: Pubkey,
+    offset: u32,
+    data: Vec<u8>,
+    name_account_key: Pubkey,
+    name_class: Pubkey,
+    validator_program_id: Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let instruction_data = NameRegistryInstruction::UpdateValidated { offset, data };
+    let data = borsh::to_vec(&instruction_data).unwrap();
+    let (class_validator_key, _) = get_class_validator_key(&name_service_program_id, &name_class);
+    let accounts = vec![
+        AccountMeta::new(name_account_key, false),
+        AccountMeta::new_readonly(name_class, true),
+        AccountMeta::new_readonly(class_validator_key, false),
+        AccountMeta::new_readonly(validator_program_id, false),
+    ];
+
+    Ok(Instruction {
+        program_id: name_service_program_id,
+        accounts,
+        data,
+    })
+}
+
+pub fn validate_record_data(
+    validator_program_id: Pubkey,
+    name_account_key: Pubkey,
+    offset: u32,
+    data: Vec<u8>,
+) -> Result<Instruction, ProgramError> {
+    let data = borsh::to_vec(&ValidateRecordData { offset, data }).unwrap();
+    let accounts = vec![AccountMeta::new_readonly(name_account_key, false)];
+
+    Ok(Instruction {
+        program_id: validator_program_id,
+        accounts,
+        data,
+    })
+}
+
 pub fn transfer(
     name_service_program_id: Pubkey,
     new_owner: Pubkey,
@@ -247,3 +332,28 @@ pub fn realloc(
         data,
     })
 }
+
+pub fn set_class_validator(
+    name_service_program_id: Pubkey,
+    payer_key: Pubkey,
+    name_class: Pubkey,
+    validator_program_id: Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let instruction_data = NameRegistryInstruction::SetClassValidator {
+        validator_program_id,
+    };
+    let data = borsh::to_vec(&instruction_data).unwrap();
+    let (class_validator_key, _) = get_class_validator_key(&name_service_program_id, &name_class);
+    let accounts = vec![
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(payer_key, true),
+        AccountMeta::new(class_validator_key, false),
+        AccountMeta::new_readonly(name_class, true),
+    ];
+
+    Ok(Instruction {
+        program_id: name_service_program_id,
+        accounts,
+        data,
+    })
+}
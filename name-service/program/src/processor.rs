@@ -1,7 +1,10 @@
 use {
     crate::{
-        instruction::NameRegistryInstruction,
-        state::{get_seeds_and_key, write_data, NameRecordHeader},
+        instruction::{validate_record_data, NameRegistryInstruction},
+        state::{
+            get_class_validator_key, get_seeds_and_key, write_data, ClassValidator,
+            NameRecordHeader, CLASS_VALIDATOR_SEED,
+        },
     },
     borsh::BorshDeserialize,
     solana_program::{
@@ -170,6 +173,65 @@ impl Processor {
         Ok(())
     }
 
+    pub fn process_update_validated(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        offset: u32,
+        data: Vec<u8>,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+
+        let name_account = next_account_info(accounts_iter)?;
+        let name_class = next_account_info(accounts_iter)?;
+        let class_validator_account = next_account_info(accounts_iter)?;
+        let validator_program = next_account_info(accounts_iter)?;
+
+        let name_record_header = NameRecordHeader::unpack_from_slice(&name_account.data.borrow())?;
+
+        // Verifications
+        if name_record_header.class == Pubkey::default() {
+            msg!("The name record has no class to validate its data.");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let (class_validator_key, _) =
+            get_class_validator_key(program_id, &name_record_header.class);
+        if *class_validator_account.key != class_validator_key {
+            msg!("The given class validator account is incorrect.");
+            return Err(ProgramError::InvalidArgument);
+        }
+        if class_validator_account.owner != program_id {
+            msg!("The class validator account is not owned by the program.");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let class_validator =
+            ClassValidator::unpack_from_slice(&class_validator_account.data.borrow())?;
+        if class_validator.validator_program_id == Pubkey::default() {
+            msg!("The name class has no validator program.");
+            return Err(ProgramError::InvalidArgument);
+        }
+        if *validator_program.key != class_validator.validator_program_id {
+            msg!("The given validator program is incorrect.");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Processor::process_update(
+            &[name_account.clone(), name_class.clone()],
+            offset,
+            data.clone(),
+        )?;
+
+        // The validator checks the updated record, failing reverts the write
+        invoke(
+            &validate_record_data(
+                class_validator.validator_program_id,
+                *name_account.key,
+                offset,
+                data,
+            )?,
+            &[name_account.clone(), validator_program.clone()],
+        )
+    }
+
     pub fn process_transfer(accounts: &[AccountInfo], new_owner: Pubkey) -> ProgramResult {
         let accounts_iter = &mut accounts.iter();
 
@@ -293,6 +355,74 @@ impl Processor {
         Ok(())
     }
 
+    fn process_set_class_validator(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        validator_program_id: Pubkey,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let system_program = next_account_info(accounts_iter)?;
+        let payer_account = next_account_info(accounts_iter)?;
+        let class_validator_account = next_account_info(accounts_iter)?;
+        let name_class = next_account_info(accounts_iter)?;
+
+        // Verifications
+        if !name_class.is_signer || *name_class.key == Pubkey::default() {
+            msg!("The given name class is incorrect or not a signer.");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let (class_validator_key, bump) = get_class_validator_key(program_id, name_class.key);
+        if class_validator_key != *class_validator_account.key {
+            msg!("The given class validator account is incorrect.");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if class_validator_account.data_len() == 0 {
+            let signer_seeds: &[&[u8]] = &[CLASS_VALIDATOR_SEED, name_class.key.as_ref(), &[bump]];
+            let required_lamports = Rent::get()?
+                .minimum_balance(ClassValidator::LEN)
+                .saturating_sub(class_validator_account.lamports());
+            if required_lamports > 0 {
+                invoke(
+                    &system_instruction::transfer(
+                        payer_account.key,
+                        class_validator_account.key,
+                        required_lamports,
+                    ),
+                    &[
+                        payer_account.clone(),
+                        class_validator_account.clone(),
+                        system_program.clone(),
+                    ],
+                )?;
+            }
+            invoke_signed(
+                &system_instruction::allocate(
+                    class_validator_account.key,
+                    ClassValidator::LEN as u64,
+                ),
+                &[class_validator_account.clone(), system_program.clone()],
+                &[signer_seeds],
+            )?;
+            invoke_signed(
+                &system_instruction::assign(class_validator_account.key, program_id),
+                &[class_validator_account.clone(), system_program.clone()],
+                &[signer_seeds],
+            )?;
+        } else if class_validator_account.owner != program_id {
+            msg!("The class validator account is not owned by the program.");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let class_validator = ClassValidator {
+            class: *name_class.key,
+            validator_program_id,
+        };
+        class_validator.pack_into_slice(&mut class_validator_account.data.borrow_mut());
+
+        Ok(())
+    }
+
     pub fn process_instruction(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
@@ -328,6 +458,16 @@ impl Processor {
                 msg!("Instruction: Realloc Name Record");
                 Processor::process_realloc(accounts, space)?;
             }
+            NameRegistryInstruction::SetClassValidator {
+                validator_program_id,
+            } => {
+                msg!("Instruction: Set Class Validator");
+                Processor::process_set_class_validator(program_id, accounts, validator_program_id)?;
+            }
+            NameRegistryInstruction::UpdateValidated { offset, data } => {
+                msg!("Instruction: Update Validated Data");
+                Processor::process_update_validated(program_id, accounts, offset, data)?;
+            }
         }
         Ok(())
     }
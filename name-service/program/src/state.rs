@@ -55,6 +55,47 @@ impl IsInitialized for NameRecordHeader {
     }
 }
 
+/// Per-class configuration of the program validating record data.
+///
+/// Stored in a program-derived address with seeds `[CLASS_VALIDATOR_SEED,
+/// class]`. When `validator_program_id` is not `Pubkey::default()`, records of
+/// the class can be updated with `UpdateValidated`, which checks the data by
+/// invoking the validator program, see `instruction::validate_record_data`.
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct ClassValidator {
+    // The class this configuration applies to
+    pub class: Pubkey,
+
+    // The program validating record data of the class, or `Pubkey::default()`
+    // if validated updates are disabled
+    pub validator_program_id: Pubkey,
+}
+
+impl Sealed for ClassValidator {}
+
+impl Pack for ClassValidator {
+    const LEN: usize = 64;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut p = src;
+        ClassValidator::deserialize(&mut p).map_err(|_| {
+            msg!("Failed to deserialize class validator");
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for ClassValidator {
+    fn is_initialized(&self) -> bool {
+        self.class != Pubkey::default()
+    }
+}
+
 pub fn write_data(account: &AccountInfo, input: &[u8], offset: usize) {
     let mut account_data = account.data.borrow_mut();
     account_data[offset..offset.saturating_add(input.len())].copy_from_slice(input);
@@ -64,6 +105,8 @@ pub fn write_data(account: &AccountInfo, input: &[u8], offset: usize) {
 
 pub const HASH_PREFIX: &str = "SPL Name Service";
 
+pub const CLASS_VALIDATOR_SEED: &[u8] = b"class_validator";
+
 ////////////////////////////////////////////////////////////
 
 pub fn get_seeds_and_key(
@@ -95,3 +138,7 @@ pub fn get_seeds_and_key(
 
     (name_account_key, seeds_vec)
 }
+
+pub fn get_class_validator_key(program_id: &Pubkey, class: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLASS_VALIDATOR_SEED, &class.to_bytes()], program_id)
+}
@@ -1,6 +1,10 @@
 #![cfg(feature = "test-sbf")]
 use {
-    solana_program::{hash::hashv, instruction::Instruction, program_pack::Pack, pubkey::Pubkey},
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::AccountInfo, entrypoint::ProgramResult, hash::hashv,
+        instruction::Instruction, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
+    },
     solana_program_test::{
         processor, tokio, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext,
     },
@@ -10,7 +14,10 @@ use {
         transport::TransportError,
     },
     spl_name_service::{
-        instruction::{create, delete, realloc, transfer, update, NameRegistryInstruction},
+        instruction::{
+            create, delete, realloc, set_class_validator, transfer, update, update_validated,
+            NameRegistryInstruction, ValidateRecordData,
+        },
         processor::Processor,
         state::{get_seeds_and_key, NameRecordHeader, HASH_PREFIX},
     },
@@ -241,7 +248,149 @@ async fn test_name_service() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_class_validator() {
+    let program_id = Pubkey::from_str("XCWuBvfNamesXCWuBvfkegQfZyiNwAJb9Ss623VQ5DA").unwrap();
+    let validator_program_id = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::new(
+        "spl_name_service",
+        program_id,
+        processor!(Processor::process_instruction),
+    );
+    program_test.add_program(
+        "ipfs_record_validator",
+        validator_program_id,
+        processor!(process_ipfs_record_validator),
+    );
+
+    let mut ctx = program_test.start_with_context().await;
+
+    let ipfs_class = Keypair::new();
+    let owner = Keypair::new();
+
+    let hashed_name: Vec<u8> = hashv(&[(HASH_PREFIX.to_owned() + "bonfida").as_bytes()])
+        .as_ref()
+        .to_vec();
+    let (name_account_key, _) = get_seeds_and_key(
+        &program_id,
+        hashed_name.clone(),
+        Some(&ipfs_class.pubkey()),
+        None,
+    );
+
+    let space = 100usize;
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let create_name_instruction = create(
+        program_id,
+        NameRegistryInstruction::Create {
+            hashed_name,
+            lamports: rent.minimum_balance(space.saturating_add(NameRecordHeader::LEN)),
+            space: space as u32,
+        },
+        name_account_key,
+        ctx.payer.pubkey(),
+        owner.pubkey(),
+        Some(ipfs_class.pubkey()),
+        None,
+        None,
+    )
+    .unwrap();
+    let set_class_validator_instruction = set_class_validator(
+        program_id,
+        ctx.payer.pubkey(),
+        ipfs_class.pubkey(),
+        validator_program_id,
+    )
+    .unwrap();
+    sign_send_instruction(&mut ctx, create_name_instruction, vec![&ipfs_class])
+        .await
+        .unwrap();
+    sign_send_instruction(&mut ctx, set_class_validator_instruction, vec![&ipfs_class])
+        .await
+        .unwrap();
+
+    let update_instruction = |data: &[u8], validator_program_id: Pubkey| {
+        update_validated(
+            program_id,
+            0,
+            data.to_vec(),
+            name_account_key,
+            ipfs_class.pubkey(),
+            validator_program_id,
+        )
+        .unwrap()
+    };
+
+    // the validator rejects malformed data
+    sign_send_instruction(
+        &mut ctx,
+        update_instruction(b"https://bonfida.org", validator_program_id),
+        vec![&ipfs_class],
+    )
+    .await
+    .unwrap_err();
+
+    // only the validator of the class can be passed
+    sign_send_instruction(
+        &mut ctx,
+        update_instruction(b"https://bonfida.org", Pubkey::new_unique()),
+        vec![&ipfs_class],
+    )
+    .await
+    .unwrap_err();
+
+    // plain updates leave the data unchecked and keep their accounts
+    let unchecked_update_instruction = update(
+        program_id,
+        0,
+        b"https://bonfida.org".to_vec(),
+        name_account_key,
+        ipfs_class.pubkey(),
+        None,
+    )
+    .unwrap();
+    sign_send_instruction(&mut ctx, unchecked_update_instruction, vec![&ipfs_class])
+        .await
+        .unwrap();
+
+    let data = b"ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    sign_send_instruction(
+        &mut ctx,
+        update_instruction(data, validator_program_id),
+        vec![&ipfs_class],
+    )
+    .await
+    .unwrap();
+
+    let name_account = ctx
+        .banks_client
+        .get_account(name_account_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &name_account.data[NameRecordHeader::LEN..NameRecordHeader::LEN + data.len()],
+        data
+    );
+}
+
 // Utils
+fn process_ipfs_record_validator(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = ValidateRecordData::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let name_account = &accounts[0];
+    let record = &name_account.data.borrow()[NameRecordHeader::LEN..];
+    if args.offset != 0 || !record.starts_with(b"ipfs://") {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(())
+}
+
 pub async fn sign_send_instruction(
     ctx: &mut ProgramTestContext,
     instruction: Instruction,
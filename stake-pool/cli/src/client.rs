@@ -13,9 +13,15 @@ use {
     },
     solana_sdk::{compute_budget::ComputeBudgetInstruction, transaction::Transaction},
     spl_stake_pool::{
+        find_dao_treasury_program_address, find_pending_fees_program_address,
         find_withdraw_authority_program_address,
-        state::{StakePool, ValidatorList},
+        state::{DaoTreasury, PendingFees, StakePool, ValidatorList},
     },
+    spl_token_2022::{
+        extension::{transfer_hook, StateWithExtensions},
+        state::Mint,
+    },
+    spl_transfer_hook_interface::offchain::add_extra_account_metas_for_execute,
     std::collections::HashSet,
 };
 
@@ -41,6 +47,42 @@ pub fn get_validator_list(
     Ok(validator_list)
 }
 
+pub fn get_pending_fees(
+    rpc_client: &RpcClient,
+    stake_pool_address: &Pubkey,
+) -> Result<Option<PendingFees>, Error> {
+    let (pending_fees_address, _) =
+        find_pending_fees_program_address(&spl_stake_pool::id(), stake_pool_address);
+    let account = rpc_client
+        .get_account_with_commitment(&pending_fees_address, rpc_client.commitment())?
+        .value;
+    account
+        .map(|account| {
+            try_from_slice_unchecked::<PendingFees>(account.data.as_slice()).map_err(|err| {
+                format!("Invalid pending fees {}: {}", pending_fees_address, err).into()
+            })
+        })
+        .transpose()
+}
+
+pub fn get_dao_treasury(
+    rpc_client: &RpcClient,
+    stake_pool_address: &Pubkey,
+) -> Result<Option<DaoTreasury>, Error> {
+    let (dao_treasury_address, _) =
+        find_dao_treasury_program_address(&spl_stake_pool::id(), stake_pool_address);
+    let account = rpc_client
+        .get_account_with_commitment(&dao_treasury_address, rpc_client.commitment())?
+        .value;
+    account
+        .map(|account| {
+            try_from_slice_unchecked::<DaoTreasury>(account.data.as_slice()).map_err(|err| {
+                format!("Invalid DAO treasury {}: {}", dao_treasury_address, err).into()
+            })
+        })
+        .transpose()
+}
+
 pub fn get_token_account(
     rpc_client: &RpcClient,
     token_account_address: &Pubkey,
@@ -72,6 +114,44 @@ pub fn get_token_mint(
     Ok(token_mint)
 }
 
+/// Adds the extra accounts required by the transfer hook program of the pool
+/// mint, if any, to a withdrawal instruction, which transfers the withdrawal
+/// fee from `source` to the manager fee account
+pub fn add_transfer_hook_accounts(
+    rpc_client: &RpcClient,
+    instruction: &mut Instruction,
+    stake_pool: &StakePool,
+    source: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) -> Result<(), Error> {
+    if stake_pool.token_program_id != spl_token_2022::id() {
+        return Ok(());
+    }
+    let mint_data = rpc_client.get_account_data(&stake_pool.pool_mint)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)
+        .map_err(|err| format!("Invalid token mint {}: {}", stake_pool.pool_mint, err))?;
+    if let Some(program_id) = transfer_hook::get_program_id(&mint) {
+        futures::executor::block_on(add_extra_account_metas_for_execute(
+            instruction,
+            &program_id,
+            source,
+            &stake_pool.pool_mint,
+            &stake_pool.manager_fee_account,
+            authority,
+            amount,
+            |address| {
+                let account_data = rpc_client
+                    .get_account_with_commitment(&address, rpc_client.commitment())
+                    .map(|response| response.value.map(|account| account.data));
+                async move { account_data.map_err(Into::into) }
+            },
+        ))
+        .map_err(|err| format!("Could not resolve the transfer hook accounts: {}", err))?;
+    }
+    Ok(())
+}
+
 pub(crate) fn get_stake_state(
     rpc_client: &RpcClient,
     stake_address: &Pubkey,
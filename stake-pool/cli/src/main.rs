@@ -44,7 +44,9 @@ use {
         transaction::Transaction,
     },
     spl_associated_token_account::instruction::create_associated_token_account,
-    spl_associated_token_account_client::address::get_associated_token_address,
+    spl_associated_token_account_client::address::{
+        get_associated_token_address, get_associated_token_address_with_program_id,
+    },
     spl_stake_pool::{
         self, find_stake_program_address, find_transient_stake_program_address,
         find_withdraw_authority_program_address,
@@ -446,6 +448,7 @@ fn setup_pool_fee_account(
         config,
         mint_pubkey,
         &config.manager.pubkey(),
+        &spl_token::id(),
         &mut instructions,
         total_rent_free_balances,
     );
@@ -467,7 +470,8 @@ fn setup_and_initialize_validator_list_with_stake_pool(
     reserve_keypair: &Keypair,
     mint_keypair: &Keypair,
     pool_fee_account: &Pubkey,
-    deposit_authority: Option<Keypair>,
+    stake_deposit_authority: Option<Keypair>,
+    sol_deposit_authority: Option<Pubkey>,
     epoch_fee: Fee,
     withdrawal_fee: Fee,
     deposit_fee: Fee,
@@ -550,7 +554,7 @@ fn setup_and_initialize_validator_list_with_stake_pool(
         &mint_keypair.pubkey(),
         pool_fee_account,
         &spl_token::id(),
-        deposit_authority.as_ref().map(|x| x.pubkey()),
+        stake_deposit_authority.as_ref().map(|x| x.pubkey()),
         epoch_fee,
         withdrawal_fee,
         deposit_fee,
@@ -559,11 +563,30 @@ fn setup_and_initialize_validator_list_with_stake_pool(
     ));
     signers.push(stake_pool_keypair);
 
-    if let Some(ref deposit_auth) = deposit_authority {
-        signers.push(deposit_auth);
+    // Initialize applies the stake deposit authority to SOL deposits too, so
+    // reset the SOL deposit authority if it's meant to be different
+    let initial_sol_deposit_authority = stake_deposit_authority.as_ref().map(|x| x.pubkey());
+    if sol_deposit_authority != initial_sol_deposit_authority {
+        instructions.push(spl_stake_pool::instruction::set_funding_authority(
+            &spl_stake_pool::id(),
+            &stake_pool_keypair.pubkey(),
+            &config.manager.pubkey(),
+            sol_deposit_authority.as_ref(),
+            FundingType::SolDeposit,
+        ));
+    }
+
+    if let Some(ref stake_deposit_auth) = stake_deposit_authority {
+        signers.push(stake_deposit_auth);
         println!(
-            "Deposits will be restricted to {} only, this can be changed using the set-funding-authority command.",
-            deposit_auth.pubkey()
+            "Stake deposits will be restricted to {} only, this can be changed using the set-funding-authority command.",
+            stake_deposit_auth.pubkey()
+        );
+    }
+    if let Some(sol_deposit_auth) = sol_deposit_authority {
+        println!(
+            "SOL deposits will be restricted to {} only, this can be changed using the set-funding-authority command.",
+            sol_deposit_auth
         );
     }
 
@@ -583,7 +606,8 @@ fn setup_and_initialize_validator_list_with_stake_pool(
 #[allow(clippy::too_many_arguments)]
 fn command_create_pool(
     config: &Config,
-    deposit_authority: Option<Keypair>,
+    stake_deposit_authority: Option<Keypair>,
+    sol_deposit_authority: Option<Pubkey>,
     epoch_fee: Fee,
     withdrawal_fee: Fee,
     deposit_fee: Fee,
@@ -669,7 +693,8 @@ fn command_create_pool(
         &reserve_keypair,
         &mint_keypair,
         &pool_fee_account,
-        deposit_authority,
+        stake_deposit_authority,
+        sol_deposit_authority,
         epoch_fee,
         withdrawal_fee,
         deposit_fee,
@@ -954,11 +979,12 @@ fn add_associated_token_account(
     config: &Config,
     mint: &Pubkey,
     owner: &Pubkey,
+    token_program_id: &Pubkey,
     instructions: &mut Vec<Instruction>,
     rent_free_balances: &mut u64,
 ) -> Pubkey {
     // Account for tokens not specified, creating one
-    let account = get_associated_token_address(owner, mint);
+    let account = get_associated_token_address_with_program_id(owner, mint, token_program_id);
     if get_token_account(&config.rpc_client, &account, mint).is_err() {
         println!("Creating associated token account {} to receive stake pool tokens of mint {}, owned by {}", account, mint, owner);
 
@@ -971,7 +997,7 @@ fn add_associated_token_account(
             &config.fee_payer.pubkey(),
             owner,
             mint,
-            &spl_token::id(),
+            token_program_id,
         ));
 
         *rent_free_balances += min_account_balance;
@@ -1040,6 +1066,7 @@ fn command_deposit_stake(
             config,
             &stake_pool.pool_mint,
             &config.token_owner.pubkey(),
+            &stake_pool.token_program_id,
             &mut instructions,
             &mut total_rent_free_balances,
         ));
@@ -1075,7 +1102,7 @@ fn command_deposit_stake(
                 &stake_pool.manager_fee_account,
                 &referrer_token_account,
                 &stake_pool.pool_mint,
-                &spl_token::id(),
+                &stake_pool.token_program_id,
             )
         } else {
             spl_stake_pool::instruction::deposit_stake(
@@ -1091,7 +1118,7 @@ fn command_deposit_stake(
                 &stake_pool.manager_fee_account,
                 &referrer_token_account,
                 &stake_pool.pool_mint,
-                &spl_token::id(),
+                &stake_pool.token_program_id,
             )
         };
 
@@ -1131,6 +1158,7 @@ fn command_deposit_all_stake(
             config,
             &stake_pool.pool_mint,
             &config.token_owner.pubkey(),
+            &stake_pool.token_program_id,
             &mut create_token_account_instructions,
             &mut total_rent_free_balances,
         ));
@@ -1213,7 +1241,7 @@ fn command_deposit_all_stake(
                 &stake_pool.manager_fee_account,
                 &referrer_token_account,
                 &stake_pool.pool_mint,
-                &spl_token::id(),
+                &stake_pool.token_program_id,
             )
         } else {
             spl_stake_pool::instruction::deposit_stake(
@@ -1229,7 +1257,7 @@ fn command_deposit_all_stake(
                 &stake_pool.manager_fee_account,
                 &referrer_token_account,
                 &stake_pool.pool_mint,
-                &spl_token::id(),
+                &stake_pool.token_program_id,
             )
         };
 
@@ -1293,6 +1321,7 @@ fn command_deposit_sol(
             config,
             &stake_pool.pool_mint,
             &config.token_owner.pubkey(),
+            &stake_pool.token_program_id,
             &mut instructions,
             &mut total_rent_free_balances,
         ));
@@ -1327,7 +1356,7 @@ fn command_deposit_sol(
             &stake_pool.manager_fee_account,
             &referrer_token_account,
             &stake_pool.pool_mint,
-            &spl_token::id(),
+            &stake_pool.token_program_id,
             amount,
         )
     } else {
@@ -1341,7 +1370,7 @@ fn command_deposit_sol(
             &stake_pool.manager_fee_account,
             &referrer_token_account,
             &stake_pool.pool_mint,
-            &spl_token::id(),
+            &stake_pool.token_program_id,
             amount,
         )
     };
@@ -1409,12 +1438,16 @@ fn command_list(config: &Config, stake_pool_address: &Pubkey) -> CommandResult {
         .collect();
     let total_pool_tokens =
         spl_token::amount_to_ui_amount(stake_pool.pool_token_supply, pool_mint.decimals);
+    let pending_fees = get_pending_fees(&config.rpc_client, stake_pool_address)?;
     let mut cli_stake_pool = CliStakePool::from((
         *stake_pool_address,
         stake_pool,
         validator_list,
         pool_withdraw_authority,
     ));
+    if let Some(pending_fees) = pending_fees {
+        cli_stake_pool.set_pending_fees(pending_fees);
+    }
     let update_required = last_update_epoch != epoch_info.epoch;
     let cli_stake_pool_details = CliStakePoolDetails {
         reserve_stake_account_address,
@@ -1457,7 +1490,7 @@ fn command_update(
 
     let validator_list = get_validator_list(&config.rpc_client, &stake_pool.validator_list)?;
 
-    let (mut update_list_instructions, final_instructions) = if stale_only {
+    let (mut update_list_instructions, mut final_instructions) = if stale_only {
         spl_stake_pool::instruction::update_stale_stake_pool(
             &spl_stake_pool::id(),
             &stake_pool,
@@ -1476,6 +1509,27 @@ fn command_update(
         )
     };
 
+    // the pending deposit and referral fees only count down if their account
+    // is passed to the balance update
+    if get_pending_fees(&config.rpc_client, stake_pool_address)?.is_some() {
+        let dao_treasury_pool_account = get_dao_treasury(&config.rpc_client, stake_pool_address)?
+            .map(|dao_treasury| dao_treasury.treasury_pool_account);
+        let (withdraw_authority, _) =
+            find_withdraw_authority_program_address(&spl_stake_pool::id(), stake_pool_address);
+        final_instructions[0] =
+            spl_stake_pool::instruction::update_stake_pool_balance_with_pending_fees(
+                &spl_stake_pool::id(),
+                stake_pool_address,
+                &withdraw_authority,
+                &stake_pool.validator_list,
+                &stake_pool.reserve_stake,
+                &stake_pool.manager_fee_account,
+                &stake_pool.pool_mint,
+                &stake_pool.token_program_id,
+                dao_treasury_pool_account.as_ref(),
+            );
+    }
+
     let update_list_instructions_len = update_list_instructions.len();
     if update_list_instructions_len > 0 {
         let last_instruction = update_list_instructions.split_off(update_list_instructions_len - 1);
@@ -1679,10 +1733,12 @@ fn command_withdraw_stake(
     let pool_withdraw_authority =
         find_withdraw_authority_program_address(&spl_stake_pool::id(), stake_pool_address).0;
 
-    let pool_token_account = pool_token_account.unwrap_or(get_associated_token_address(
-        &config.token_owner.pubkey(),
-        &stake_pool.pool_mint,
-    ));
+    let pool_token_account =
+        pool_token_account.unwrap_or(get_associated_token_address_with_program_id(
+            &config.token_owner.pubkey(),
+            &stake_pool.pool_mint,
+            &stake_pool.token_program_id,
+        ));
     let token_account = get_token_account(
         &config.rpc_client,
         &pool_token_account,
@@ -1835,8 +1891,8 @@ fn command_withdraw_stake(
 
     instructions.push(
         // Approve spending token
-        spl_token::instruction::approve(
-            &spl_token::id(),
+        spl_token_2022::instruction::approve(
+            &stake_pool.token_program_id,
             &pool_token_account,
             &user_transfer_authority.pubkey(),
             &config.token_owner.pubkey(),
@@ -1885,7 +1941,7 @@ fn command_withdraw_stake(
                 stake_receiver_param.unwrap()
             };
 
-        instructions.push(spl_stake_pool::instruction::withdraw_stake(
+        let mut withdraw_instruction = spl_stake_pool::instruction::withdraw_stake(
             &spl_stake_pool::id(),
             stake_pool_address,
             &stake_pool.validator_list,
@@ -1897,9 +1953,18 @@ fn command_withdraw_stake(
             &pool_token_account,
             &stake_pool.manager_fee_account,
             &stake_pool.pool_mint,
-            &spl_token::id(),
+            &stake_pool.token_program_id,
             withdraw_account.pool_amount,
-        ));
+        );
+        add_transfer_hook_accounts(
+            &config.rpc_client,
+            &mut withdraw_instruction,
+            &stake_pool,
+            &pool_token_account,
+            &user_transfer_authority.pubkey(),
+            withdraw_account.pool_amount,
+        )?;
+        instructions.push(withdraw_instruction);
     }
 
     // Merging the stake with account provided by user
@@ -1942,10 +2007,12 @@ fn command_withdraw_sol(
     let pool_mint = get_token_mint(&config.rpc_client, &stake_pool.pool_mint)?;
     let pool_amount = spl_token::ui_amount_to_amount(pool_amount, pool_mint.decimals);
 
-    let pool_token_account = pool_token_account.unwrap_or(get_associated_token_address(
-        &config.token_owner.pubkey(),
-        &stake_pool.pool_mint,
-    ));
+    let pool_token_account =
+        pool_token_account.unwrap_or(get_associated_token_address_with_program_id(
+            &config.token_owner.pubkey(),
+            &stake_pool.pool_mint,
+            &stake_pool.token_program_id,
+        ));
     let token_account = get_token_account(
         &config.rpc_client,
         &pool_token_account,
@@ -1972,8 +2039,8 @@ fn command_withdraw_sol(
 
     let mut instructions = vec![
         // Approve spending token
-        spl_token::instruction::approve(
-            &spl_token::id(),
+        spl_token_2022::instruction::approve(
+            &stake_pool.token_program_id,
             &pool_token_account,
             &user_transfer_authority.pubkey(),
             &config.token_owner.pubkey(),
@@ -1985,7 +2052,9 @@ fn command_withdraw_sol(
     let pool_withdraw_authority =
         find_withdraw_authority_program_address(&spl_stake_pool::id(), stake_pool_address).0;
 
-    let withdraw_instruction = if let Some(withdraw_authority) = config.funding_authority.as_ref() {
+    let mut withdraw_instruction = if let Some(withdraw_authority) =
+        config.funding_authority.as_ref()
+    {
         let expected_sol_withdraw_authority =
             stake_pool.sol_withdraw_authority.ok_or_else(|| {
                 "SOL withdraw authority specified in arguments but stake pool has none".to_string()
@@ -2011,7 +2080,7 @@ fn command_withdraw_sol(
             sol_receiver,
             &stake_pool.manager_fee_account,
             &stake_pool.pool_mint,
-            &spl_token::id(),
+            &stake_pool.token_program_id,
             pool_amount,
         )
     } else {
@@ -2025,10 +2094,18 @@ fn command_withdraw_sol(
             sol_receiver,
             &stake_pool.manager_fee_account,
             &stake_pool.pool_mint,
-            &spl_token::id(),
+            &stake_pool.token_program_id,
             pool_amount,
         )
     };
+    add_transfer_hook_accounts(
+        &config.rpc_client,
+        &mut withdraw_instruction,
+        &stake_pool,
+        &pool_token_account,
+        &user_transfer_authority.pubkey(),
+        pool_amount,
+    )?;
 
     instructions.push(withdraw_instruction);
 
@@ -2151,16 +2228,23 @@ fn command_set_fee(
     }
     let mut signers = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
     unique_signers!(signers);
-    let transaction = checked_transaction_with_signers(
-        config,
-        &[spl_stake_pool::instruction::set_fee(
+    let instruction = if new_fee.can_only_change_next_epoch() {
+        spl_stake_pool::instruction::set_fee(
             &spl_stake_pool::id(),
             stake_pool_address,
             &config.manager.pubkey(),
             new_fee,
-        )],
-        &signers,
-    )?;
+        )
+    } else {
+        spl_stake_pool::instruction::set_fee_with_pending_fees(
+            &spl_stake_pool::id(),
+            stake_pool_address,
+            &config.manager.pubkey(),
+            &config.fee_payer.pubkey(),
+            new_fee,
+        )
+    };
+    let transaction = checked_transaction_with_signers(config, &[instruction], &signers)?;
     send_transaction(config, transaction)?;
     Ok(())
 }
@@ -2376,7 +2460,27 @@ fn main() {
                     .validator(is_valid_signer)
                     .value_name("DEPOSIT_AUTHORITY_KEYPAIR")
                     .takes_value(true)
-                    .help("Deposit authority required to sign all deposits into the stake pool"),
+                    .help("Deposit authority required to sign all stake and SOL deposits into the stake pool"),
+            )
+            .arg(
+                Arg::with_name("stake_deposit_authority")
+                    .long("stake-deposit-authority")
+                    .validator(is_valid_signer)
+                    .value_name("STAKE_DEPOSIT_AUTHORITY_KEYPAIR")
+                    .takes_value(true)
+                    .conflicts_with("deposit_authority")
+                    .help("Deposit authority required to sign stake deposits into the stake pool. \
+                           SOL deposits remain permissionless unless --sol-deposit-authority is also provided."),
+            )
+            .arg(
+                Arg::with_name("sol_deposit_authority")
+                    .long("sol-deposit-authority")
+                    .validator(is_pubkey)
+                    .value_name("SOL_DEPOSIT_AUTHORITY_ADDRESS")
+                    .takes_value(true)
+                    .conflicts_with("deposit_authority")
+                    .help("Deposit authority required to sign SOL deposits into the stake pool. \
+                           Stake deposits remain permissionless unless --stake-deposit-authority is also provided."),
             )
             .arg(
                 Arg::with_name("pool_keypair")
@@ -3177,7 +3281,16 @@ fn main() {
 
     let _ = match matches.subcommand() {
         ("create-pool", Some(arg_matches)) => {
-            let deposit_authority = keypair_of(arg_matches, "deposit_authority");
+            let (stake_deposit_authority, sol_deposit_authority) =
+                if let Some(deposit_authority) = keypair_of(arg_matches, "deposit_authority") {
+                    let deposit_authority_address = deposit_authority.pubkey();
+                    (Some(deposit_authority), Some(deposit_authority_address))
+                } else {
+                    (
+                        keypair_of(arg_matches, "stake_deposit_authority"),
+                        pubkey_of(arg_matches, "sol_deposit_authority"),
+                    )
+                };
             let e_numerator = value_t_or_exit!(arg_matches, "epoch_fee_numerator", u64);
             let e_denominator = value_t_or_exit!(arg_matches, "epoch_fee_denominator", u64);
             let w_numerator = value_t!(arg_matches, "withdrawal_fee_numerator", u64);
@@ -3193,7 +3306,8 @@ fn main() {
             let unsafe_fees = arg_matches.is_present("unsafe_fees");
             command_create_pool(
                 &config,
-                deposit_authority,
+                stake_deposit_authority,
+                sol_deposit_authority,
                 Fee {
                     numerator: e_numerator,
                     denominator: e_denominator,
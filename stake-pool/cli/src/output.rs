@@ -3,7 +3,7 @@ use {
     solana_cli_output::{QuietDisplay, VerboseDisplay},
     solana_sdk::{native_token::Sol, pubkey::Pubkey, stake::state::Lockup},
     spl_stake_pool::state::{
-        Fee, PodStakeStatus, StakePool, StakeStatus, ValidatorList, ValidatorStakeInfo,
+        Fee, PendingFees, PodStakeStatus, StakePool, StakeStatus, ValidatorList, ValidatorStakeInfo,
     },
     std::fmt::{Display, Formatter, Result, Write},
 };
@@ -71,9 +71,26 @@ pub(crate) struct CliStakePool {
     pub next_sol_withdrawal_fee: Option<CliStakePoolFee>,
     pub last_epoch_pool_token_supply: u64,
     pub last_epoch_total_lamports: u64,
+    pub next_stake_deposit_fee: Option<CliStakePoolFee>,
+    pub next_stake_referral_fee: Option<u8>,
+    pub next_sol_deposit_fee: Option<CliStakePoolFee>,
+    pub next_sol_referral_fee: Option<u8>,
     pub details: Option<CliStakePoolDetails>,
 }
 
+impl CliStakePool {
+    /// Fills in the deposit and referral fee increases kept in the pending
+    /// fees account of the pool
+    pub fn set_pending_fees(&mut self, pending_fees: PendingFees) {
+        self.next_stake_deposit_fee =
+            Option::<Fee>::from(pending_fees.next_stake_deposit_fee).map(CliStakePoolFee::from);
+        self.next_stake_referral_fee = pending_fees.next_stake_referral_fee.into();
+        self.next_sol_deposit_fee =
+            Option::<Fee>::from(pending_fees.next_sol_deposit_fee).map(CliStakePoolFee::from);
+        self.next_sol_referral_fee = pending_fees.next_sol_referral_fee.into();
+    }
+}
+
 impl QuietDisplay for CliStakePool {}
 impl VerboseDisplay for CliStakePool {
     fn write_str(&self, w: &mut dyn Write) -> Result {
@@ -152,21 +169,49 @@ impl VerboseDisplay for CliStakePool {
             "Stake Deposit Fee: {} of deposit amount",
             &self.stake_deposit_fee
         )?;
+        if let Some(next_stake_deposit_fee) = &self.next_stake_deposit_fee {
+            writeln!(
+                w,
+                "Next Stake Deposit Fee: {} of deposit amount",
+                next_stake_deposit_fee
+            )?;
+        }
         writeln!(
             w,
             "SOL Deposit Fee: {} of deposit amount",
             &self.sol_deposit_fee
         )?;
+        if let Some(next_sol_deposit_fee) = &self.next_sol_deposit_fee {
+            writeln!(
+                w,
+                "Next SOL Deposit Fee: {} of deposit amount",
+                next_sol_deposit_fee
+            )?;
+        }
         writeln!(
             w,
             "Stake Deposit Referral Fee: {}% of Stake Deposit Fee",
             &self.stake_referral_fee
         )?;
+        if let Some(next_stake_referral_fee) = &self.next_stake_referral_fee {
+            writeln!(
+                w,
+                "Next Stake Deposit Referral Fee: {}% of Stake Deposit Fee",
+                next_stake_referral_fee
+            )?;
+        }
         writeln!(
             w,
             "SOL Deposit Referral Fee: {}% of SOL Deposit Fee",
             &self.sol_referral_fee
         )?;
+        if let Some(next_sol_referral_fee) = &self.next_sol_referral_fee {
+            writeln!(
+                w,
+                "Next SOL Deposit Referral Fee: {}% of SOL Deposit Fee",
+                next_sol_referral_fee
+            )?;
+        }
         writeln!(w)?;
 
         match &self.details {
@@ -364,7 +409,9 @@ pub(crate) struct CliStakePoolValidator {
     pub transient_stake_lamports: u64,
     pub last_update_epoch: u64,
     pub transient_seed_suffix: u64,
-    pub unused: u32,
+    pub commission: u8,
+    pub commission_flagged: bool,
+    pub commission_flag_epoch: u16,
     pub validator_seed_suffix: u32,
     pub status: CliStakePoolValidatorStakeStatus,
     pub vote_account_address: String,
@@ -377,7 +424,9 @@ impl From<ValidatorStakeInfo> for CliStakePoolValidator {
             transient_stake_lamports: v.transient_stake_lamports.into(),
             last_update_epoch: v.last_update_epoch.into(),
             transient_seed_suffix: v.transient_seed_suffix.into(),
-            unused: v.unused.into(),
+            commission: v.commission,
+            commission_flagged: v.is_commission_flagged(),
+            commission_flag_epoch: v.commission_flag_epoch.into(),
             validator_seed_suffix: v.validator_seed_suffix.into(),
             status: CliStakePoolValidatorStakeStatus::from(v.status),
             vote_account_address: v.vote_account_address.to_string(),
@@ -499,6 +548,10 @@ impl From<(Pubkey, StakePool, ValidatorList, Pubkey)> for CliStakePool {
                 .map(CliStakePoolFee::from),
             last_epoch_pool_token_supply: stake_pool.last_epoch_pool_token_supply,
             last_epoch_total_lamports: stake_pool.last_epoch_total_lamports,
+            next_stake_deposit_fee: None,
+            next_stake_referral_fee: None,
+            next_sol_deposit_fee: None,
+            next_sol_referral_fee: None,
             details: None,
         }
     }
@@ -164,6 +164,38 @@ pub enum StakePoolError {
     /// Missing required sysvar account
     #[error("Missing required sysvar account")]
     MissingRequiredSysvar,
+    /// Provided exchange rate history account does not match the one derived
+    /// for the stake pool
+    #[error("InvalidExchangeRateHistory")]
+    InvalidExchangeRateHistory,
+    /// The stake pool has already been updated for the current epoch
+    #[error("StakePoolAlreadyUpdated")]
+    StakePoolAlreadyUpdated,
+    /// The transient stake account can be handled by the regular update
+    /// process
+    #[error("TransientStakeNotStuck")]
+    TransientStakeNotStuck,
+    /// Provided DAO treasury account does not match the one derived for the
+    /// stake pool, or its treasury pool token account
+    #[error("InvalidDaoTreasury")]
+    InvalidDaoTreasury,
+    /// Provided pending fees account does not match the one derived for the
+    /// stake pool, or is missing for a delayed fee increase
+    #[error("InvalidPendingFees")]
+    InvalidPendingFees,
+    /// Provided validator commission cap account does not match the one
+    /// derived for the stake pool, or is not set
+    #[error("InvalidValidatorCommissionCap")]
+    InvalidValidatorCommissionCap,
+    /// Provided vote account is not a valid vote account
+    #[error("InvalidVoteAccount")]
+    InvalidVoteAccount,
+    /// The validator commission did not rise above the cap of the stake pool
+    #[error("ValidatorCommissionWithinCap")]
+    ValidatorCommissionWithinCap,
+    /// The grace period of the flagged validator commission is not over yet
+    #[error("ValidatorCommissionGracePeriodActive")]
+    ValidatorCommissionGracePeriodActive,
 }
 impl From<StakePoolError> for ProgramError {
     fn from(e: StakePoolError) -> Self {
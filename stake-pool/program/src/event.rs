@@ -0,0 +1,227 @@
+//! Events logged by the stake pool program
+//!
+//! Every deposit and withdrawal logs a single borsh-serialized
+//! `StakePoolEvent` through `sol_log_data`, which shows up in the transaction
+//! logs as `Program data: <base64>`. The event records the amounts and fees of
+//! the operation as well as the pool's total lamports and pool token supply
+//! once it has been applied, giving the resulting exchange rate.
+//!
+//! `UpdateStakePoolBalance` logs the share of the epoch rewards minted to the
+//! DAO treasury, if the pool has one.
+//!
+//! `SetFee` logs the fees that only take effect after two epoch boundaries,
+//! so that depositors can react before they apply.
+
+use {
+    crate::state::{DaoTreasury, FeeType, StakePool},
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{log::sol_log_data, pubkey::Pubkey},
+};
+
+/// Deposit of stake or SOL into the pool
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct DepositEvent {
+    /// Stake pool account
+    pub stake_pool: Pubkey,
+    /// Pool token account receiving the user's pool tokens
+    pub destination_pool_account: Pubkey,
+    /// Pool token account receiving the referral fee
+    pub referrer_pool_account: Pubkey,
+    /// Lamports added to the pool
+    pub lamports: u64,
+    /// Pool tokens minted to the user
+    pub pool_tokens_user: u64,
+    /// Pool tokens minted to the manager as deposit fee
+    pub pool_tokens_manager_fee: u64,
+    /// Pool tokens minted to the referrer as referral fee
+    pub pool_tokens_referral_fee: u64,
+    /// Total lamports of the pool after the deposit
+    pub total_lamports: u64,
+    /// Pool token supply after the deposit
+    pub pool_token_supply: u64,
+}
+
+/// Withdrawal of stake or SOL from the pool
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct WithdrawEvent {
+    /// Stake pool account
+    pub stake_pool: Pubkey,
+    /// Pool token account the pool tokens are taken from
+    pub source_pool_account: Pubkey,
+    /// Stake account or system account receiving the lamports
+    pub destination: Pubkey,
+    /// Pool tokens taken from the user, including the fee
+    pub pool_tokens: u64,
+    /// Pool tokens transferred to the manager as withdrawal fee
+    pub pool_tokens_manager_fee: u64,
+    /// Lamports removed from the pool
+    pub lamports: u64,
+    /// Total lamports of the pool after the withdrawal
+    pub total_lamports: u64,
+    /// Pool token supply after the withdrawal
+    pub pool_token_supply: u64,
+}
+
+/// Share of the epoch rewards minted to the DAO treasury
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct DaoTreasuryRewardEvent {
+    /// Stake pool account
+    pub stake_pool: Pubkey,
+    /// Pool token account of the DAO treasury
+    pub treasury_pool_account: Pubkey,
+    /// Rewards earned by the pool since the last update
+    pub reward_lamports: u64,
+    /// Pool tokens minted to the DAO treasury
+    pub pool_tokens: u64,
+    /// Total pool tokens minted to the DAO treasury so far
+    pub total_pool_tokens: u64,
+    /// Total lamports of the pool after the update
+    pub total_lamports: u64,
+    /// Pool token supply after the update
+    pub pool_token_supply: u64,
+}
+
+/// Fee change waiting for two epoch boundaries before taking effect
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct PendingFeeEvent {
+    /// Stake pool account
+    pub stake_pool: Pubkey,
+    /// Type of fee and value it will be set to
+    pub fee: FeeType,
+    /// First epoch the fee applies to, once the pool is updated
+    pub effective_epoch: u64,
+}
+
+/// Events logged by the stake pool program
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum StakePoolEvent {
+    /// Logged by `DepositStake` and `DepositStakeWithSlippage`
+    DepositStake(DepositEvent),
+    /// Logged by `DepositSol` and `DepositSolWithSlippage`
+    DepositSol(DepositEvent),
+    /// Logged by `WithdrawStake` and `WithdrawStakeWithSlippage`
+    WithdrawStake(WithdrawEvent),
+    /// Logged by `WithdrawSol` and `WithdrawSolWithSlippage`
+    WithdrawSol(WithdrawEvent),
+    /// Logged by `UpdateStakePoolBalance`
+    DaoTreasuryReward(DaoTreasuryRewardEvent),
+    /// Logged by `SetFee` when the new fee is delayed
+    PendingFee(PendingFeeEvent),
+}
+
+impl StakePoolEvent {
+    /// Logs the event as program data
+    pub fn log(&self) {
+        sol_log_data(&[&borsh::to_vec(self).unwrap()]);
+    }
+}
+
+/// Logs a deposit event, built outside of the processor's stack frame
+#[allow(clippy::too_many_arguments)]
+#[inline(never)]
+pub(crate) fn log_deposit(
+    event_type: fn(DepositEvent) -> StakePoolEvent,
+    stake_pool_address: &Pubkey,
+    stake_pool: &StakePool,
+    destination_pool_account: &Pubkey,
+    referrer_pool_account: &Pubkey,
+    lamports: u64,
+    pool_tokens_user: u64,
+    pool_tokens_manager_fee: u64,
+    pool_tokens_referral_fee: u64,
+) {
+    event_type(DepositEvent {
+        stake_pool: *stake_pool_address,
+        destination_pool_account: *destination_pool_account,
+        referrer_pool_account: *referrer_pool_account,
+        lamports,
+        pool_tokens_user,
+        pool_tokens_manager_fee,
+        pool_tokens_referral_fee,
+        total_lamports: stake_pool.total_lamports,
+        pool_token_supply: stake_pool.pool_token_supply,
+    })
+    .log();
+}
+
+/// Logs a withdrawal event, built outside of the processor's stack frame
+#[allow(clippy::too_many_arguments)]
+#[inline(never)]
+pub(crate) fn log_withdraw(
+    event_type: fn(WithdrawEvent) -> StakePoolEvent,
+    stake_pool_address: &Pubkey,
+    stake_pool: &StakePool,
+    source_pool_account: &Pubkey,
+    destination: &Pubkey,
+    pool_tokens: u64,
+    pool_tokens_manager_fee: u64,
+    lamports: u64,
+) {
+    event_type(WithdrawEvent {
+        stake_pool: *stake_pool_address,
+        source_pool_account: *source_pool_account,
+        destination: *destination,
+        pool_tokens,
+        pool_tokens_manager_fee,
+        lamports,
+        total_lamports: stake_pool.total_lamports,
+        pool_token_supply: stake_pool.pool_token_supply,
+    })
+    .log();
+}
+
+/// Logs the share of the rewards minted to the DAO treasury, built outside of
+/// the processor's stack frame
+#[inline(never)]
+pub(crate) fn log_dao_treasury_reward(
+    stake_pool_address: &Pubkey,
+    stake_pool: &StakePool,
+    dao_treasury: &DaoTreasury,
+    reward_lamports: u64,
+    pool_tokens: u64,
+) {
+    StakePoolEvent::DaoTreasuryReward(DaoTreasuryRewardEvent {
+        stake_pool: *stake_pool_address,
+        treasury_pool_account: dao_treasury.treasury_pool_account,
+        reward_lamports,
+        pool_tokens,
+        total_pool_tokens: dao_treasury.total_pool_tokens,
+        total_lamports: stake_pool.total_lamports,
+        pool_token_supply: stake_pool.pool_token_supply,
+    })
+    .log();
+}
+
+/// Logs a fee change waiting to take effect
+#[inline(never)]
+pub(crate) fn log_pending_fee(stake_pool_address: &Pubkey, fee: FeeType, effective_epoch: u64) {
+    StakePoolEvent::PendingFee(PendingFeeEvent {
+        stake_pool: *stake_pool_address,
+        fee,
+        effective_epoch,
+    })
+    .log();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_round_trip() {
+        let event = StakePoolEvent::WithdrawSol(WithdrawEvent {
+            stake_pool: Pubkey::new_unique(),
+            source_pool_account: Pubkey::new_unique(),
+            destination: Pubkey::new_unique(),
+            pool_tokens: 1_000,
+            pool_tokens_manager_fee: 10,
+            lamports: 1_980,
+            total_lamports: 1_000_000,
+            pool_token_supply: 500_000,
+        });
+
+        let data = borsh::to_vec(&event).unwrap();
+        assert_eq!(data[0], 3);
+        assert_eq!(StakePoolEvent::try_from_slice(&data).unwrap(), event);
+    }
+}
@@ -7,8 +7,10 @@
 
 use {
     crate::{
-        find_deposit_authority_program_address, find_ephemeral_stake_program_address,
-        find_stake_program_address, find_transient_stake_program_address,
+        find_dao_treasury_program_address, find_deposit_authority_program_address,
+        find_ephemeral_stake_program_address, find_exchange_rate_history_program_address,
+        find_pending_fees_program_address, find_stake_program_address,
+        find_transient_stake_program_address, find_validator_commission_cap_program_address,
         find_withdraw_authority_program_address,
         inline_mpl_token_metadata::{self, pda::find_metadata_account},
         state::{Fee, FeeType, StakePool, ValidatorList, ValidatorStakeInfo},
@@ -70,7 +72,8 @@ pub enum StakePoolInstruction {
     ///   9. `[]` (Optional) Deposit authority that must sign all deposits.
     ///      Defaults to the program address generated using
     ///      `find_deposit_authority_program_address`, making deposits
-    ///      permissionless.
+    ///      permissionless. The stake and SOL deposit authorities can then be
+    ///      changed independently with `SetFundingAuthority`.
     Initialize {
         /// Fee assessed as percentage of perceived rewards
         fee: Fee,
@@ -92,7 +95,8 @@ pub enum StakePoolInstruction {
     ///     crate::MINIMUM_ACTIVE_STAKE,
     ///     solana_program::stake::tools::get_minimum_delegation()
     ///   )`.
-    ///   It is funded from the stake pool reserve.
+    ///   It is funded from the stake pool reserve. The current commission of
+    ///   the validator is recorded for `FlagValidatorCommission`.
     ///
     ///   0. `[w]` Stake pool
     ///   1. `[s]` Staker
@@ -265,6 +269,16 @@ pub enum StakePoolInstruction {
     ///   4. `[w]` Account to receive pool fee tokens
     ///   5. `[w]` Pool mint account
     ///   6. `[]` Pool token program
+    ///   7. `[w]` (Optional) Exchange rate history account, updated with the
+    ///      pool exchange rate on the first update of the epoch if it has been
+    ///      created
+    ///   8. `[w]` (Optional) DAO treasury account, receives its share of the
+    ///      rewards if it has been set
+    ///   9. `[w]` (Optional) DAO treasury pool token account, required if the
+    ///      DAO treasury has been set
+    ///   10. `[w]` (Optional) Pending fees account, counted down on the first
+    ///       update of the epoch if it has been created, applying the deposit
+    ///       and referral fee increases that are ready
     UpdateStakePoolBalance,
 
     ///   Cleans up validator stake account entries marked as `ReadyForRemoval`
@@ -332,6 +346,8 @@ pub enum StakePoolInstruction {
     ///  10. `[]` Sysvar clock account (required)
     ///  11. `[]` Pool token program id
     ///  12. `[]` Stake program id,
+    ///  13. ..13+N `[]` (Optional) Extra accounts required by the transfer
+    ///      hook program of the pool mint, to transfer the withdrawal fee
     ///
     ///  userdata: amount of pool tokens to withdraw
     WithdrawStake(u64),
@@ -346,8 +362,21 @@ pub enum StakePoolInstruction {
 
     ///  (Manager only) Update fee
     ///
+    ///  Epoch and withdrawal fees, as well as deposit and referral fee
+    ///  increases once the pool went through an epoch with pool tokens
+    ///  outstanding, take effect after two epoch boundaries and can only be
+    ///  increased by a bounded step.
+    ///
+    ///  Delayed deposit and referral fee increases are kept in the pending fees
+    ///  account, which `UpdateStakePoolBalance` applies them from.
+    ///
     ///  0. `[w]` StakePool
     ///  1. `[s]` Manager
+    ///  2. `[w]` (Optional) Pending fees account, derived from the stake pool.
+    ///     Required for delayed deposit and referral fee increases, other
+    ///     deposit and referral fee changes cancel its pending increase.
+    ///  3. `[s, w]` (Optional) Payer for creation of the pending fees account
+    ///  4. `[]` (Optional) System program id
     SetFee {
         /// Type of fee to update and value to update it to
         fee: FeeType,
@@ -402,6 +431,9 @@ pub enum StakePoolInstruction {
     ///  10. `[]` Stake program account
     ///  11. `[]` Token program id
     ///  12. `[s]` (Optional) Stake pool sol withdraw authority
+    ///  13. ..13+N `[]` (Optional) Extra accounts required by the transfer
+    ///      hook program of the pool mint, to transfer the withdrawal fee.
+    ///      These start at index 12 if no sol withdraw authority is set.
     WithdrawSol(u64),
 
     /// Create token metadata for the stake-pool token in the
@@ -681,6 +713,8 @@ pub enum StakePoolInstruction {
     ///  10. `[]` Sysvar clock account (required)
     ///  11. `[]` Pool token program id
     ///  12. `[]` Stake program id,
+    ///  13. ..13+N `[]` (Optional) Extra accounts required by the transfer
+    ///      hook program of the pool mint, to transfer the withdrawal fee
     ///
     ///  userdata: amount of pool tokens to withdraw
     WithdrawStakeWithSlippage {
@@ -731,12 +765,106 @@ pub enum StakePoolInstruction {
     ///  10. `[]` Stake program account
     ///  11. `[]` Token program id
     ///  12. `[s]` (Optional) Stake pool sol withdraw authority
+    ///  13. ..13+N `[]` (Optional) Extra accounts required by the transfer
+    ///      hook program of the pool mint, to transfer the withdrawal fee.
+    ///      These start at index 12 if no sol withdraw authority is set.
     WithdrawSolWithSlippage {
         /// Pool tokens to burn in exchange for lamports
         pool_tokens_in: u64,
         /// Minimum amount of lamports that must be received
         minimum_lamports_out: u64,
     },
+
+    ///   Create the exchange rate history account for the stake pool, which
+    ///   records the pool exchange rate over the last epochs during
+    ///   `UpdateStakePoolBalance`
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[w]` Exchange rate history account, derived from the stake pool
+    ///   2. `[s, w]` Payer for creation of the exchange rate history account
+    ///   3. `[]` System program id
+    CreateExchangeRateHistory,
+
+    ///   (Permissionless) Recovers a transient stake account which cannot be
+    ///   merged by `UpdateValidatorListBalance`
+    ///
+    ///   A delegated transient stake account is stuck if its validator stake
+    ///   account is no longer active in the pool, or if its delegation is
+    ///   below the current minimum delegation. It is deactivated, and merged
+    ///   into the reserve during the next update. An inactive transient stake
+    ///   account is merged into the reserve right away.
+    ///
+    ///   Only allowed while the stake pool has not been updated for the
+    ///   current epoch.
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[w]` Validator list
+    ///   3. `[w]` Reserve stake account
+    ///   4. `[]` Canonical stake account of the validator
+    ///   5. `[w]` Transient stake account to recover
+    ///   6. `[]` Validator vote account
+    ///   7. `[]` Sysvar clock
+    ///   8. `[]` Sysvar stake history
+    ///   9. `[]` Stake program
+    RecoverTransientStake,
+
+    ///   (Manager only) Sets the DAO treasury of the stake pool, creating its
+    ///   account if needed. Each `UpdateStakePoolBalance` mints `reward_fee`
+    ///   of the rewards earned by the pool, after the epoch fee, to the
+    ///   treasury pool token account. Principal is never skimmed.
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[w]` DAO treasury account, derived from the stake pool
+    ///   3. `[]` Pool token account receiving the treasury share of the
+    ///      rewards
+    ///   4. `[s, w]` Payer for creation of the DAO treasury account
+    ///   5. `[]` System program id
+    SetDaoTreasury {
+        /// Share of the rewards minted to the treasury, zero pauses skimming
+        reward_fee: Fee,
+    },
+
+    ///   (Manager only) Sets the validator commission cap of the stake pool,
+    ///   creating its account if needed. Validators whose commission rises
+    ///   above the cap can then be flagged with `FlagValidatorCommission`.
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[w]` Validator commission cap account, derived from the stake
+    ///      pool
+    ///   3. `[s, w]` Payer for creation of the validator commission cap
+    ///      account
+    ///   4. `[]` System program id
+    SetValidatorCommissionCap {
+        /// Maximum commission of the validators, as a percentage
+        max_commission: u8,
+        /// Number of epochs a flagged validator has to lower its commission
+        /// before being removed from the pool
+        grace_epochs: u16,
+    },
+
+    ///   (Permissionless) Flags a validator whose commission rose above the
+    ///   validator commission cap of the pool, and higher than its commission
+    ///   when it was added to the pool.
+    ///
+    ///   The first call flags the validator. Once the grace period of the cap
+    ///   is over, a call with the commission still above the cap removes the
+    ///   validator from the pool, as `RemoveValidatorFromPool` would. A call
+    ///   with the commission back under the cap clears the flag.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Validator commission cap account, derived from the stake
+    ///      pool
+    ///   2. `[]` Stake pool withdraw authority
+    ///   3. `[w]` Validator list
+    ///   4. `[w]` Canonical stake account of the validator
+    ///   5. `[w]` Transient stake account of the validator
+    ///   6. `[]` Validator vote account
+    ///   7. `[]` Sysvar clock
+    ///   8. `[]` Stake program
+    FlagValidatorCommission,
 }
 
 /// Creates an 'initialize' instruction.
@@ -1624,6 +1752,10 @@ pub fn update_stake_pool_balance(
         AccountMeta::new(*manager_fee_account, false),
         AccountMeta::new(*stake_pool_mint, false),
         AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(
+            find_exchange_rate_history_program_address(program_id, stake_pool).0,
+            false,
+        ),
     ];
     Instruction {
         program_id: *program_id,
@@ -1632,6 +1764,75 @@ pub fn update_stake_pool_balance(
     }
 }
 
+/// Creates `UpdateStakePoolBalance` instruction (pool balance from the stake
+/// account list balances), minting the DAO treasury share of the rewards to
+/// the treasury pool token account
+pub fn update_stake_pool_balance_with_dao_treasury(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    withdraw_authority: &Pubkey,
+    validator_list_storage: &Pubkey,
+    reserve_stake: &Pubkey,
+    manager_fee_account: &Pubkey,
+    stake_pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    dao_treasury_pool_account: &Pubkey,
+) -> Instruction {
+    let mut instruction = update_stake_pool_balance(
+        program_id,
+        stake_pool,
+        withdraw_authority,
+        validator_list_storage,
+        reserve_stake,
+        manager_fee_account,
+        stake_pool_mint,
+        token_program_id,
+    );
+    instruction.accounts.extend([
+        AccountMeta::new(
+            find_dao_treasury_program_address(program_id, stake_pool).0,
+            false,
+        ),
+        AccountMeta::new(*dao_treasury_pool_account, false),
+    ]);
+    instruction
+}
+
+/// Creates `UpdateStakePoolBalance` instruction (pool balance from the stake
+/// account list balances), applying the pending deposit and referral fee
+/// increases that are ready
+pub fn update_stake_pool_balance_with_pending_fees(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    withdraw_authority: &Pubkey,
+    validator_list_storage: &Pubkey,
+    reserve_stake: &Pubkey,
+    manager_fee_account: &Pubkey,
+    stake_pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    dao_treasury_pool_account: Option<&Pubkey>,
+) -> Instruction {
+    let (dao_treasury, _) = find_dao_treasury_program_address(program_id, stake_pool);
+    // the DAO treasury pool token account is only read once the DAO treasury
+    // has been set, so the treasury itself stands in for it otherwise
+    let mut instruction = update_stake_pool_balance_with_dao_treasury(
+        program_id,
+        stake_pool,
+        withdraw_authority,
+        validator_list_storage,
+        reserve_stake,
+        manager_fee_account,
+        stake_pool_mint,
+        token_program_id,
+        dao_treasury_pool_account.unwrap_or(&dao_treasury),
+    );
+    instruction.accounts.push(AccountMeta::new(
+        find_pending_fees_program_address(program_id, stake_pool).0,
+        false,
+    ));
+    instruction
+}
+
 /// Creates `CleanupRemovedValidatorEntries` instruction (removes entries from
 /// the validator list)
 pub fn cleanup_removed_validator_entries(
@@ -2540,6 +2741,27 @@ pub fn set_fee(
     }
 }
 
+/// Creates a 'set fee' instruction for a deposit or referral fee, passing the
+/// pending fees account that keeps delayed increases, created on first use.
+pub fn set_fee_with_pending_fees(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    payer: &Pubkey,
+    fee: FeeType,
+) -> Instruction {
+    let mut instruction = set_fee(program_id, stake_pool, manager, fee);
+    instruction.accounts.extend([
+        AccountMeta::new(
+            find_pending_fees_program_address(program_id, stake_pool).0,
+            false,
+        ),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]);
+    instruction
+}
+
 /// Creates a 'set staker' instruction.
 pub fn set_staker(
     program_id: &Pubkey,
@@ -2646,3 +2868,178 @@ pub fn create_token_metadata(
             .unwrap(),
     }
 }
+
+/// Creates an instruction to create the exchange rate history account for the
+/// stake pool
+pub fn create_exchange_rate_history(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let (exchange_rate_history, _) =
+        find_exchange_rate_history_program_address(program_id, stake_pool);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new(exchange_rate_history, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::CreateExchangeRateHistory).unwrap(),
+    }
+}
+
+/// Creates an instruction to set the DAO treasury of the stake pool
+pub fn set_dao_treasury(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    treasury_pool_account: &Pubkey,
+    payer: &Pubkey,
+    reward_fee: Fee,
+) -> Instruction {
+    let (dao_treasury, _) = find_dao_treasury_program_address(program_id, stake_pool);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new(dao_treasury, false),
+        AccountMeta::new_readonly(*treasury_pool_account, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetDaoTreasury { reward_fee }).unwrap(),
+    }
+}
+
+/// Creates `RecoverTransientStake` instruction (recover a stuck transient stake
+/// account of a validator)
+pub fn recover_transient_stake(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list: &Pubkey,
+    reserve_stake: &Pubkey,
+    vote_account_address: &Pubkey,
+    validator_stake_seed: Option<NonZeroU32>,
+    transient_stake_seed: u64,
+) -> Instruction {
+    let (stake_pool_withdraw_authority, _) =
+        find_withdraw_authority_program_address(program_id, stake_pool);
+    let (validator_stake_account, _) = find_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool,
+        validator_stake_seed,
+    );
+    let (transient_stake_account, _) = find_transient_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool,
+        transient_stake_seed,
+    );
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new_readonly(validator_stake_account, false),
+        AccountMeta::new(transient_stake_account, false),
+        AccountMeta::new_readonly(*vote_account_address, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::RecoverTransientStake).unwrap(),
+    }
+}
+
+/// Creates an instruction to set the validator commission cap of the stake
+/// pool
+pub fn set_validator_commission_cap(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    payer: &Pubkey,
+    max_commission: u8,
+    grace_epochs: u16,
+) -> Instruction {
+    let (validator_commission_cap, _) =
+        find_validator_commission_cap_program_address(program_id, stake_pool);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new(validator_commission_cap, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetValidatorCommissionCap {
+            max_commission,
+            grace_epochs,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates `FlagValidatorCommission` instruction (flag, and eventually remove,
+/// a validator whose commission rose above the cap of the pool)
+pub fn flag_validator_commission(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list: &Pubkey,
+    vote_account_address: &Pubkey,
+    validator_stake_seed: Option<NonZeroU32>,
+    transient_stake_seed: u64,
+) -> Instruction {
+    let (validator_commission_cap, _) =
+        find_validator_commission_cap_program_address(program_id, stake_pool);
+    let (stake_pool_withdraw_authority, _) =
+        find_withdraw_authority_program_address(program_id, stake_pool);
+    let (validator_stake_account, _) = find_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool,
+        validator_stake_seed,
+    );
+    let (transient_stake_account, _) = find_transient_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool,
+        transient_stake_seed,
+    );
+
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(validator_commission_cap, false),
+        AccountMeta::new_readonly(stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(validator_stake_account, false),
+        AccountMeta::new(transient_stake_account, false),
+        AccountMeta::new_readonly(*vote_account_address, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::FlagValidatorCommission).unwrap(),
+    }
+}
@@ -4,6 +4,7 @@
 
 pub mod big_vec;
 pub mod error;
+pub mod event;
 pub mod inline_mpl_token_metadata;
 pub mod instruction;
 pub mod processor;
@@ -33,6 +34,18 @@ const TRANSIENT_STAKE_SEED_PREFIX: &[u8] = b"transient";
 /// Seed for ephemeral stake account
 const EPHEMERAL_STAKE_SEED_PREFIX: &[u8] = b"ephemeral";
 
+/// Seed for exchange rate history account
+const EXCHANGE_RATE_HISTORY_SEED_PREFIX: &[u8] = b"exchange-rate-history";
+
+/// Seed for DAO treasury account
+const DAO_TREASURY_SEED_PREFIX: &[u8] = b"dao-treasury";
+
+/// Seed for pending fees account
+const PENDING_FEES_SEED_PREFIX: &[u8] = b"pending-fees";
+
+/// Seed for validator commission cap account
+const VALIDATOR_COMMISSION_CAP_SEED_PREFIX: &[u8] = b"validator-commission-cap";
+
 /// Minimum amount of staked lamports required in a validator stake account to
 /// allow for merges without a mismatch on credits observed
 pub const MINIMUM_ACTIVE_STAKE: u64 = 1_000_000;
@@ -57,6 +70,28 @@ pub const WITHDRAWAL_BASELINE_FEE: Fee = Fee {
     denominator: 1000,
 };
 
+/// Maximum factor by which an epoch or deposit fee can be increased per change,
+/// giving stakers time to react to a series of increases.
+/// If current fee is 0, EPOCH_BASELINE_FEE or DEPOSIT_BASELINE_FEE is used as
+/// the baseline
+pub const MAX_FEE_INCREASE: Fee = Fee {
+    numerator: 5,
+    denominator: 4,
+};
+/// Drop-in baseline fee when evaluating epoch fee increases when fee is 0
+pub const EPOCH_BASELINE_FEE: Fee = Fee {
+    numerator: 1,
+    denominator: 100,
+};
+/// Drop-in baseline fee when evaluating deposit fee increases when fee is 0
+pub const DEPOSIT_BASELINE_FEE: Fee = Fee {
+    numerator: 1,
+    denominator: 1000,
+};
+/// Maximum number of percentage points by which a referral fee can be
+/// increased per change
+pub const MAX_REFERRAL_FEE_INCREASE: u8 = 10;
+
 /// The maximum number of transient stake accounts respecting
 /// transaction account limits.
 pub const MAX_TRANSIENT_STAKE_ACCOUNTS: usize = 10;
@@ -157,6 +192,56 @@ pub fn find_ephemeral_stake_program_address(
     )
 }
 
+/// Generates the exchange rate history program address for the stake pool
+pub fn find_exchange_rate_history_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            EXCHANGE_RATE_HISTORY_SEED_PREFIX,
+            stake_pool_address.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Generates the DAO treasury program address for the stake pool
+pub fn find_dao_treasury_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DAO_TREASURY_SEED_PREFIX, stake_pool_address.as_ref()],
+        program_id,
+    )
+}
+
+/// Generates the pending fees program address for the stake pool
+pub fn find_pending_fees_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PENDING_FEES_SEED_PREFIX, stake_pool_address.as_ref()],
+        program_id,
+    )
+}
+
+/// Generates the validator commission cap program address for the stake pool
+pub fn find_validator_commission_cap_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VALIDATOR_COMMISSION_CAP_SEED_PREFIX,
+            stake_pool_address.as_ref(),
+        ],
+        program_id,
+    )
+}
+
 solana_program::declare_id!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
 
 #[cfg(test)]
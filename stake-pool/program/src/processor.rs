@@ -3,6 +3,7 @@
 use {
     crate::{
         error::StakePoolError,
+        event::{self, StakePoolEvent},
         find_deposit_authority_program_address,
         inline_mpl_token_metadata::{
             self,
@@ -13,12 +14,14 @@ use {
         instruction::{FundingType, PreferredValidatorType, StakePoolInstruction},
         minimum_delegation, minimum_reserve_lamports, minimum_stake_lamports,
         state::{
-            is_extension_supported_for_mint, AccountType, Fee, FeeType, FutureEpoch, StakePool,
-            StakeStatus, StakeWithdrawSource, ValidatorList, ValidatorListHeader,
+            is_extension_supported_for_mint, AccountType, DaoTreasury, ExchangeRateEntry,
+            ExchangeRateHistory, Fee, FeeType, FutureEpoch, PendingFees, StakePool, StakeStatus,
+            StakeWithdrawSource, ValidatorCommissionCap, ValidatorList, ValidatorListHeader,
             ValidatorStakeInfo,
         },
-        AUTHORITY_DEPOSIT, AUTHORITY_WITHDRAW, EPHEMERAL_STAKE_SEED_PREFIX,
-        TRANSIENT_STAKE_SEED_PREFIX,
+        AUTHORITY_DEPOSIT, AUTHORITY_WITHDRAW, DAO_TREASURY_SEED_PREFIX,
+        EPHEMERAL_STAKE_SEED_PREFIX, EXCHANGE_RATE_HISTORY_SEED_PREFIX, PENDING_FEES_SEED_PREFIX,
+        TRANSIENT_STAKE_SEED_PREFIX, VALIDATOR_COMMISSION_CAP_SEED_PREFIX,
     },
     borsh::BorshDeserialize,
     num_traits::FromPrimitive,
@@ -31,10 +34,11 @@ use {
         msg,
         program::{invoke, invoke_signed},
         program_error::{PrintProgramError, ProgramError},
-        pubkey::Pubkey,
+        pubkey::{Pubkey, PUBKEY_BYTES},
         rent::Rent,
         stake, system_instruction, system_program,
         sysvar::Sysvar,
+        vote,
     },
     spl_token_2022::{
         check_spl_token_program_account,
@@ -57,6 +61,35 @@ fn get_stake_state(
     }
 }
 
+/// Offset of the commission in the data of a vote account, for the vote state
+/// versions written by the vote program since v1.14
+const VOTE_STATE_COMMISSION_OFFSET: usize = 4 + PUBKEY_BYTES + PUBKEY_BYTES;
+
+/// Read the commission of a validator from its vote account
+fn get_vote_commission(vote_account_info: &AccountInfo) -> Result<u8, ProgramError> {
+    if *vote_account_info.owner != vote::program::id() {
+        msg!(
+            "Expected vote account {} to be owned by the vote program",
+            vote_account_info.key
+        );
+        return Err(StakePoolError::InvalidVoteAccount.into());
+    }
+    let data = vote_account_info.try_borrow_data()?;
+    let version = data
+        .get(..4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(StakePoolError::InvalidVoteAccount)?;
+    // `VoteStateVersions::V1_14_11` and `VoteStateVersions::Current` both start
+    // with the node pubkey, authorized withdrawer and commission
+    match version {
+        1 | 2 => data
+            .get(VOTE_STATE_COMMISSION_OFFSET)
+            .copied()
+            .ok_or_else(|| StakePoolError::InvalidVoteAccount.into()),
+        _ => Err(StakePoolError::InvalidVoteAccount.into()),
+    }
+}
+
 /// Check validity of vote address for a particular stake account
 fn check_validator_stake_address(
     program_id: &Pubkey,
@@ -120,6 +153,66 @@ fn check_ephemeral_stake_address(
     }
 }
 
+/// Check address validity for the exchange rate history account
+fn check_exchange_rate_history_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    exchange_rate_history_address: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (expected_address, bump_seed) =
+        crate::find_exchange_rate_history_program_address(program_id, stake_pool_address);
+    if expected_address != *exchange_rate_history_address {
+        Err(StakePoolError::InvalidExchangeRateHistory.into())
+    } else {
+        Ok(bump_seed)
+    }
+}
+
+/// Check address validity for the DAO treasury account
+fn check_dao_treasury_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    dao_treasury_address: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (expected_address, bump_seed) =
+        crate::find_dao_treasury_program_address(program_id, stake_pool_address);
+    if expected_address != *dao_treasury_address {
+        Err(StakePoolError::InvalidDaoTreasury.into())
+    } else {
+        Ok(bump_seed)
+    }
+}
+
+/// Check address validity for the pending fees account
+fn check_pending_fees_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    pending_fees_address: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (expected_address, bump_seed) =
+        crate::find_pending_fees_program_address(program_id, stake_pool_address);
+    if expected_address != *pending_fees_address {
+        Err(StakePoolError::InvalidPendingFees.into())
+    } else {
+        Ok(bump_seed)
+    }
+}
+
+/// Check address validity for the validator commission cap account
+fn check_validator_commission_cap_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    validator_commission_cap_address: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (expected_address, bump_seed) =
+        crate::find_validator_commission_cap_program_address(program_id, stake_pool_address);
+    if expected_address != *validator_commission_cap_address {
+        Err(StakePoolError::InvalidValidatorCommissionCap.into())
+    } else {
+        Ok(bump_seed)
+    }
+}
+
 /// Check mpl metadata account address for the pool mint
 fn check_mpl_metadata_account_address(
     metadata_address: &Pubkey,
@@ -612,6 +705,9 @@ impl Processor {
     }
 
     /// Issue a spl_token `Transfer` instruction.
+    ///
+    /// `additional_accounts` must contain the extra accounts required by the
+    /// transfer hook program of the mint, if any.
     #[allow(clippy::too_many_arguments)]
     fn token_transfer<'a>(
         token_program: AccountInfo<'a>,
@@ -619,20 +715,21 @@ impl Processor {
         mint: AccountInfo<'a>,
         destination: AccountInfo<'a>,
         authority: AccountInfo<'a>,
+        additional_accounts: &[AccountInfo<'a>],
         amount: u64,
         decimals: u8,
     ) -> Result<(), ProgramError> {
-        let ix = spl_token_2022::instruction::transfer_checked(
+        spl_token_2022::onchain::invoke_transfer_checked(
             token_program.key,
-            source.key,
-            mint.key,
-            destination.key,
-            authority.key,
-            &[],
+            source,
+            mint,
+            destination,
+            authority,
+            additional_accounts,
             amount,
             decimals,
-        )?;
-        invoke(&ix, &[source, mint, destination, authority])
+            &[],
+        )
     }
 
     fn sol_transfer<'a>(
@@ -1022,6 +1119,7 @@ impl Processor {
             AUTHORITY_WITHDRAW,
             stake_pool.stake_withdraw_bump_seed,
         )?;
+        let commission = get_vote_commission(validator_vote_info)?;
 
         validator_list.push(ValidatorStakeInfo {
             status: StakeStatus::Active.into(),
@@ -1030,7 +1128,9 @@ impl Processor {
             transient_stake_lamports: 0.into(),
             last_update_epoch: clock.epoch.into(),
             transient_seed_suffix: 0.into(),
-            unused: 0.into(),
+            commission,
+            commission_flagged: false.into(),
+            commission_flag_epoch: 0.into(),
             validator_seed_suffix: raw_validator_seed.into(),
         })?;
 
@@ -1109,6 +1209,34 @@ impl Processor {
             NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
         )?;
 
+        Self::remove_validator(
+            program_id,
+            stake_pool_info,
+            &mut stake_pool,
+            withdraw_authority_info,
+            validator_stake_info,
+            stake_account_info,
+            &stake,
+            transient_stake_account_info,
+            clock_info,
+        )
+    }
+
+    /// Deactivates the stake of a validator and marks it for removal, clearing
+    /// it as preferred validator if needed
+    #[allow(clippy::too_many_arguments)]
+    fn remove_validator<'a>(
+        program_id: &Pubkey,
+        stake_pool_info: &AccountInfo<'a>,
+        stake_pool: &mut StakePool,
+        withdraw_authority_info: &AccountInfo<'a>,
+        validator_stake_info: &mut ValidatorStakeInfo,
+        stake_account_info: &AccountInfo<'a>,
+        stake: &stake::state::Stake,
+        transient_stake_account_info: &AccountInfo<'a>,
+        clock_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let vote_account_address = stake.delegation.voter_pubkey;
         if validator_stake_info.status != StakeStatus::Active.into() {
             msg!("Validator is already marked for removal");
             return Err(StakePoolError::ValidatorNotFound.into());
@@ -1170,7 +1298,7 @@ impl Processor {
         if stake_pool.preferred_withdraw_validator_vote_address == Some(vote_account_address) {
             stake_pool.preferred_withdraw_validator_vote_address = None;
         }
-        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], stake_pool)?;
 
         Ok(())
     }
@@ -2134,6 +2262,158 @@ impl Processor {
         Ok(())
     }
 
+    /// Processes `RecoverTransientStake` instruction.
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_recover_transient_stake(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let validator_stake_info = next_account_info(account_info_iter)?;
+        let transient_stake_info = next_account_info(account_info_iter)?;
+        let validator_vote_account_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
+        let stake_history_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_validator_list(validator_list_info)?;
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+        stake_pool.check_reserve_stake(reserve_stake_info)?;
+        check_stake_program(stake_program_info.key)?;
+
+        // Recovery is permissionless, so only allow it while the pool is being
+        // updated, before the new pool balance is computed for the epoch
+        if stake_pool.last_update_epoch >= clock.epoch {
+            msg!("Stake pool already updated for epoch {}", clock.epoch);
+            return Err(StakePoolError::StakePoolAlreadyUpdated.into());
+        }
+
+        check_account_owner(validator_list_info, program_id)?;
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (header, mut validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        if !header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        let vote_account_address = validator_vote_account_info.key;
+        let validator_stake_record = validator_list
+            .find_mut::<ValidatorStakeInfo, _>(|x| {
+                ValidatorStakeInfo::memcmp_pubkey(x, vote_account_address)
+            })
+            .ok_or_else(|| {
+                msg!(
+                    "Vote account {} not found in stake pool",
+                    vote_account_address
+                );
+                StakePoolError::ValidatorNotFound
+            })?;
+        check_validator_stake_address(
+            program_id,
+            stake_pool_info.key,
+            validator_stake_info.key,
+            vote_account_address,
+            NonZeroU32::new(validator_stake_record.validator_seed_suffix.into()),
+        )?;
+        check_transient_stake_address(
+            program_id,
+            stake_pool_info.key,
+            transient_stake_info.key,
+            vote_account_address,
+            validator_stake_record.transient_seed_suffix.into(),
+        )?;
+
+        let transient_stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
+            &transient_stake_info.data.borrow(),
+        )
+        .ok();
+        let (meta, transient_stake) = match transient_stake_state {
+            Some(stake::state::StakeStateV2::Initialized(meta)) => (meta, None),
+            Some(stake::state::StakeStateV2::Stake(meta, stake, _)) => (meta, Some(stake)),
+            _ => {
+                msg!("Transient stake account is not in use");
+                return Err(StakePoolError::TransientStakeNotStuck.into());
+            }
+        };
+        if !stake_is_usable_by_pool(&meta, withdraw_authority_info.key, &stake_pool.lockup) {
+            return Err(StakePoolError::WrongStakeStake.into());
+        }
+
+        match transient_stake {
+            Some(stake) if !stake_is_inactive_without_history(&stake, clock.epoch) => {
+                if stake.delegation.deactivation_epoch != Epoch::MAX {
+                    msg!("Transient stake already deactivating, it will be merged into the reserve during the next update");
+                    return Err(StakePoolError::TransientStakeNotStuck.into());
+                }
+
+                // The transient stake can only be merged into an active
+                // validator stake account which is still part of the pool
+                let validator_stake_is_active = validator_stake_record.status
+                    == StakeStatus::Active.into()
+                    && matches!(
+                        get_stake_state(validator_stake_info),
+                        Ok((_, validator_stake)) if validator_stake.delegation.deactivation_epoch == Epoch::MAX
+                    );
+                let stake_minimum_delegation = stake::tools::get_minimum_delegation()?;
+                let current_minimum_delegation = minimum_delegation(stake_minimum_delegation);
+                if validator_stake_is_active && stake.delegation.stake >= current_minimum_delegation
+                {
+                    msg!("Transient stake can be merged into the validator stake account");
+                    return Err(StakePoolError::TransientStakeNotStuck.into());
+                }
+
+                // Deactivate the transient stake, the next update merges it
+                // into the reserve
+                msg!(
+                    "Deactivating stuck transient stake of {} lamports delegated to {}",
+                    stake.delegation.stake,
+                    vote_account_address
+                );
+                Self::stake_deactivate(
+                    transient_stake_info.clone(),
+                    clock_info.clone(),
+                    withdraw_authority_info.clone(),
+                    stake_pool_info.key,
+                    AUTHORITY_WITHDRAW,
+                    stake_pool.stake_withdraw_bump_seed,
+                )?;
+            }
+            _ => {
+                // The transient stake is inactive, merge it into the reserve
+                // right away
+                Self::stake_merge(
+                    stake_pool_info.key,
+                    transient_stake_info.clone(),
+                    withdraw_authority_info.clone(),
+                    AUTHORITY_WITHDRAW,
+                    stake_pool.stake_withdraw_bump_seed,
+                    reserve_stake_info.clone(),
+                    clock_info.clone(),
+                    stake_history_info.clone(),
+                )?;
+                validator_stake_record.status.remove_transient_stake()?;
+                // The lamports are now accounted for in the reserve
+                validator_stake_record.transient_stake_lamports = 0u64.into();
+            }
+        }
+
+        Ok(())
+    }
+
     /// Processes `UpdateStakePoolBalance` instruction.
     #[inline(always)] // needed to optimize number of validators
     fn process_update_stake_pool_balance(
@@ -2148,6 +2428,10 @@ impl Processor {
         let manager_fee_info = next_account_info(account_info_iter)?;
         let pool_mint_info = next_account_info(account_info_iter)?;
         let token_program_info = next_account_info(account_info_iter)?;
+        let maybe_exchange_rate_history_info = next_account_info(account_info_iter).ok();
+        let maybe_dao_treasury_info = next_account_info(account_info_iter).ok();
+        let maybe_dao_treasury_pool_info = next_account_info(account_info_iter).ok();
+        let maybe_pending_fees_info = next_account_info(account_info_iter).ok();
         let clock = Clock::get()?;
 
         check_account_owner(stake_pool_info, program_id)?;
@@ -2227,21 +2511,42 @@ impl Processor {
             )?;
         }
 
-        if stake_pool.last_update_epoch < clock.epoch {
-            if let Some(fee) = stake_pool.next_epoch_fee.get() {
-                stake_pool.epoch_fee = *fee;
-            }
-            stake_pool.next_epoch_fee.update_epoch();
-
-            if let Some(fee) = stake_pool.next_stake_withdrawal_fee.get() {
-                stake_pool.stake_withdrawal_fee = *fee;
+        // The DAO treasury is opt-in, only skim rewards into it once it has
+        // been set
+        let dao_treasury_reward = match maybe_dao_treasury_info {
+            Some(dao_treasury_info) if !dao_treasury_info.data_is_empty() => {
+                Self::skim_dao_treasury_reward(
+                    program_id,
+                    stake_pool_info,
+                    &stake_pool,
+                    dao_treasury_info,
+                    maybe_dao_treasury_pool_info,
+                    pool_mint_info,
+                    withdraw_info,
+                    token_program_info,
+                    reward_lamports,
+                    fee,
+                    clock.epoch,
+                )?
             }
-            stake_pool.next_stake_withdrawal_fee.update_epoch();
+            _ => None,
+        };
 
-            if let Some(fee) = stake_pool.next_sol_withdrawal_fee.get() {
-                stake_pool.sol_withdrawal_fee = *fee;
+        if stake_pool.last_update_epoch < clock.epoch {
+            stake_pool.update_fees_for_epoch();
+
+            // The pending fees are opt-in, only apply them once the account
+            // has been created
+            if let Some(pending_fees_info) = maybe_pending_fees_info {
+                if !pending_fees_info.data_is_empty() {
+                    Self::apply_pending_fees(
+                        program_id,
+                        stake_pool_info,
+                        &mut stake_pool,
+                        pending_fees_info,
+                    )?;
+                }
             }
-            stake_pool.next_sol_withdrawal_fee.update_epoch();
 
             stake_pool.last_update_epoch = clock.epoch;
             stake_pool.last_epoch_total_lamports = previous_lamports;
@@ -2255,6 +2560,557 @@ impl Processor {
 
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
+        if let Some((dao_treasury, pool_tokens)) = dao_treasury_reward {
+            event::log_dao_treasury_reward(
+                stake_pool_info.key,
+                &stake_pool,
+                &dao_treasury,
+                reward_lamports,
+                pool_tokens,
+            );
+        }
+
+        // The history is opt-in, only record into it once it has been created
+        if let Some(exchange_rate_history_info) = maybe_exchange_rate_history_info {
+            if !exchange_rate_history_info.data_is_empty() {
+                check_account_owner(exchange_rate_history_info, program_id)?;
+                let mut exchange_rate_history = try_from_slice_unchecked::<ExchangeRateHistory>(
+                    &exchange_rate_history_info.data.borrow(),
+                )?;
+                if !exchange_rate_history.is_valid()
+                    || exchange_rate_history.stake_pool != *stake_pool_info.key
+                {
+                    return Err(StakePoolError::InvalidExchangeRateHistory.into());
+                }
+                if exchange_rate_history.record(ExchangeRateEntry {
+                    epoch: clock.epoch,
+                    total_lamports: stake_pool.total_lamports,
+                    pool_token_supply: stake_pool.pool_token_supply,
+                }) {
+                    borsh::to_writer(
+                        &mut exchange_rate_history_info.data.borrow_mut()[..],
+                        &exchange_rate_history,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the pending deposit and referral fees that are ready to the
+    /// stake pool, and counts down the others
+    #[inline(never)] // needed to avoid stack size violation
+    fn apply_pending_fees(
+        program_id: &Pubkey,
+        stake_pool_info: &AccountInfo,
+        stake_pool: &mut StakePool,
+        pending_fees_info: &AccountInfo,
+    ) -> ProgramResult {
+        check_pending_fees_address(program_id, stake_pool_info.key, pending_fees_info.key)?;
+        check_account_owner(pending_fees_info, program_id)?;
+        let mut pending_fees =
+            try_from_slice_unchecked::<PendingFees>(&pending_fees_info.data.borrow())?;
+        if !pending_fees.is_valid() {
+            return Err(StakePoolError::InvalidPendingFees.into());
+        }
+        pending_fees.update_fees_for_epoch(stake_pool);
+        borsh::to_writer(&mut pending_fees_info.data.borrow_mut()[..], &pending_fees)?;
+        Ok(())
+    }
+
+    /// Mints the DAO treasury share of the rewards to the treasury pool token
+    /// account, returning the updated treasury and the pool tokens minted
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)] // needed to avoid stack size violation
+    fn skim_dao_treasury_reward<'a>(
+        program_id: &Pubkey,
+        stake_pool_info: &AccountInfo<'a>,
+        stake_pool: &StakePool,
+        dao_treasury_info: &AccountInfo<'a>,
+        maybe_dao_treasury_pool_info: Option<&AccountInfo<'a>>,
+        pool_mint_info: &AccountInfo<'a>,
+        withdraw_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        reward_lamports: u64,
+        epoch_fee_pool_tokens: u64,
+        epoch: Epoch,
+    ) -> Result<Option<(DaoTreasury, u64)>, ProgramError> {
+        check_account_owner(dao_treasury_info, program_id)?;
+        let mut dao_treasury =
+            try_from_slice_unchecked::<DaoTreasury>(&dao_treasury_info.data.borrow())?;
+        if !dao_treasury.is_valid() || dao_treasury.stake_pool != *stake_pool_info.key {
+            return Err(StakePoolError::InvalidDaoTreasury.into());
+        }
+        let dao_treasury_pool_info =
+            maybe_dao_treasury_pool_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if dao_treasury.treasury_pool_account != *dao_treasury_pool_info.key {
+            return Err(StakePoolError::InvalidDaoTreasury.into());
+        }
+
+        // If the treasury pool account is invalid, it doesn't receive the
+        // rewards, same as the manager fee
+        if stake_pool
+            .check_pool_fee_account_info(dao_treasury_pool_info, "DAO treasury pool account")
+            .is_err()
+        {
+            return Ok(None);
+        }
+        let pool_tokens = stake_pool
+            .calc_dao_treasury_fee_amount(
+                &dao_treasury.reward_fee,
+                reward_lamports,
+                epoch_fee_pool_tokens,
+            )
+            .ok_or(StakePoolError::CalculationFailure)?;
+        if pool_tokens == 0 {
+            return Ok(None);
+        }
+
+        Self::token_mint_to(
+            stake_pool_info.key,
+            token_program_info.clone(),
+            pool_mint_info.clone(),
+            dao_treasury_pool_info.clone(),
+            withdraw_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            pool_tokens,
+        )?;
+
+        dao_treasury.total_pool_tokens = dao_treasury
+            .total_pool_tokens
+            .checked_add(pool_tokens)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        dao_treasury.last_skim_epoch = epoch;
+        borsh::to_writer(&mut dao_treasury_info.data.borrow_mut()[..], &dao_treasury)?;
+
+        Ok(Some((dao_treasury, pool_tokens)))
+    }
+
+    /// Processes the `SetDaoTreasury` instruction
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_dao_treasury(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        reward_fee: Fee,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let dao_treasury_info = next_account_info(account_info_iter)?;
+        let treasury_pool_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        check_system_program(system_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_manager(manager_info)?;
+        stake_pool.check_pool_fee_account_info(treasury_pool_info, "DAO treasury pool account")?;
+
+        if reward_fee.numerator > reward_fee.denominator {
+            msg!("Fee greater than 100%: {:?}", reward_fee);
+            return Err(StakePoolError::FeeTooHigh.into());
+        }
+
+        let bump_seed =
+            check_dao_treasury_address(program_id, stake_pool_info.key, dao_treasury_info.key)?;
+
+        let dao_treasury = if dao_treasury_info.data_is_empty() {
+            if !payer_info.is_signer {
+                msg!("Payer did not sign DAO treasury creation");
+                return Err(StakePoolError::SignatureMissing.into());
+            }
+
+            // The account may have been pre-funded, only top it up to
+            // rent-exemption
+            let rent = Rent::get()?;
+            let required_lamports = rent
+                .minimum_balance(DaoTreasury::LEN)
+                .saturating_sub(dao_treasury_info.lamports());
+            if required_lamports > 0 {
+                invoke(
+                    &system_instruction::transfer(
+                        payer_info.key,
+                        dao_treasury_info.key,
+                        required_lamports,
+                    ),
+                    &[
+                        payer_info.clone(),
+                        dao_treasury_info.clone(),
+                        system_program_info.clone(),
+                    ],
+                )?;
+            }
+
+            let dao_treasury_signer_seeds: &[&[_]] = &[
+                DAO_TREASURY_SEED_PREFIX,
+                stake_pool_info.key.as_ref(),
+                &[bump_seed],
+            ];
+            invoke_signed(
+                &system_instruction::allocate(dao_treasury_info.key, DaoTreasury::LEN as u64),
+                &[dao_treasury_info.clone()],
+                &[dao_treasury_signer_seeds],
+            )?;
+            invoke_signed(
+                &system_instruction::assign(dao_treasury_info.key, program_id),
+                &[dao_treasury_info.clone()],
+                &[dao_treasury_signer_seeds],
+            )?;
+
+            DaoTreasury::new(*stake_pool_info.key, *treasury_pool_info.key, reward_fee)
+        } else {
+            check_account_owner(dao_treasury_info, program_id)?;
+            let mut dao_treasury =
+                try_from_slice_unchecked::<DaoTreasury>(&dao_treasury_info.data.borrow())?;
+            if !dao_treasury.is_valid() {
+                return Err(StakePoolError::InvalidDaoTreasury.into());
+            }
+            dao_treasury.treasury_pool_account = *treasury_pool_info.key;
+            dao_treasury.reward_fee = reward_fee;
+            dao_treasury
+        };
+
+        borsh::to_writer(&mut dao_treasury_info.data.borrow_mut()[..], &dao_treasury)?;
+
+        Ok(())
+    }
+
+    /// Processes the `SetValidatorCommissionCap` instruction
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_validator_commission_cap(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_commission: u8,
+        grace_epochs: u16,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let commission_cap_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        check_system_program(system_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_manager(manager_info)?;
+
+        if max_commission > 100 {
+            msg!(
+                "Validator commission cap greater than 100%: {}",
+                max_commission
+            );
+            return Err(StakePoolError::FeeTooHigh.into());
+        }
+
+        let bump_seed = check_validator_commission_cap_address(
+            program_id,
+            stake_pool_info.key,
+            commission_cap_info.key,
+        )?;
+
+        let commission_cap = if commission_cap_info.data_is_empty() {
+            if !payer_info.is_signer {
+                msg!("Payer did not sign validator commission cap creation");
+                return Err(StakePoolError::SignatureMissing.into());
+            }
+
+            // The account may have been pre-funded, only top it up to
+            // rent-exemption
+            let rent = Rent::get()?;
+            let required_lamports = rent
+                .minimum_balance(ValidatorCommissionCap::LEN)
+                .saturating_sub(commission_cap_info.lamports());
+            if required_lamports > 0 {
+                invoke(
+                    &system_instruction::transfer(
+                        payer_info.key,
+                        commission_cap_info.key,
+                        required_lamports,
+                    ),
+                    &[
+                        payer_info.clone(),
+                        commission_cap_info.clone(),
+                        system_program_info.clone(),
+                    ],
+                )?;
+            }
+
+            let commission_cap_signer_seeds: &[&[_]] = &[
+                VALIDATOR_COMMISSION_CAP_SEED_PREFIX,
+                stake_pool_info.key.as_ref(),
+                &[bump_seed],
+            ];
+            invoke_signed(
+                &system_instruction::allocate(
+                    commission_cap_info.key,
+                    ValidatorCommissionCap::LEN as u64,
+                ),
+                &[commission_cap_info.clone()],
+                &[commission_cap_signer_seeds],
+            )?;
+            invoke_signed(
+                &system_instruction::assign(commission_cap_info.key, program_id),
+                &[commission_cap_info.clone()],
+                &[commission_cap_signer_seeds],
+            )?;
+
+            ValidatorCommissionCap::new(*stake_pool_info.key, max_commission, grace_epochs)
+        } else {
+            check_account_owner(commission_cap_info, program_id)?;
+            let mut commission_cap = try_from_slice_unchecked::<ValidatorCommissionCap>(
+                &commission_cap_info.data.borrow(),
+            )?;
+            if !commission_cap.is_valid() {
+                return Err(StakePoolError::InvalidValidatorCommissionCap.into());
+            }
+            commission_cap.max_commission = max_commission;
+            commission_cap.grace_epochs = grace_epochs;
+            commission_cap
+        };
+
+        borsh::to_writer(
+            &mut commission_cap_info.data.borrow_mut()[..],
+            &commission_cap,
+        )?;
+
+        Ok(())
+    }
+
+    /// Processes the `FlagValidatorCommission` instruction
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_flag_validator_commission(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let commission_cap_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let transient_stake_account_info = next_account_info(account_info_iter)?;
+        let validator_vote_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+
+        check_stake_program(stake_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+        stake_pool.check_validator_list(validator_list_info)?;
+
+        if stake_pool.last_update_epoch < clock.epoch {
+            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
+        }
+
+        check_validator_commission_cap_address(
+            program_id,
+            stake_pool_info.key,
+            commission_cap_info.key,
+        )?;
+        if commission_cap_info.data_is_empty() {
+            msg!("Stake pool has no validator commission cap");
+            return Err(StakePoolError::InvalidValidatorCommissionCap.into());
+        }
+        check_account_owner(commission_cap_info, program_id)?;
+        let commission_cap =
+            try_from_slice_unchecked::<ValidatorCommissionCap>(&commission_cap_info.data.borrow())?;
+        if !commission_cap.is_valid() {
+            return Err(StakePoolError::InvalidValidatorCommissionCap.into());
+        }
+
+        let commission = get_vote_commission(validator_vote_info)?;
+
+        check_account_owner(validator_list_info, program_id)?;
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (header, mut validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        if !header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        let vote_account_address = validator_vote_info.key;
+        let validator_stake_info = validator_list
+            .find_mut::<ValidatorStakeInfo, _>(|x| {
+                ValidatorStakeInfo::memcmp_pubkey(x, vote_account_address)
+            })
+            .ok_or_else(|| {
+                msg!(
+                    "Vote account {} not found in stake pool",
+                    vote_account_address
+                );
+                StakePoolError::ValidatorNotFound
+            })?;
+        check_validator_stake_address(
+            program_id,
+            stake_pool_info.key,
+            stake_account_info.key,
+            vote_account_address,
+            NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
+        )?;
+        if validator_stake_info.status != StakeStatus::Active.into() {
+            msg!("Validator is already marked for removal");
+            return Err(StakePoolError::ValidatorNotFound.into());
+        }
+
+        if !commission_cap.is_exceeded_by(commission, validator_stake_info.commission) {
+            if validator_stake_info.is_commission_flagged() {
+                msg!(
+                    "Commission {}% of validator {} is back within the cap, clearing flag",
+                    commission,
+                    vote_account_address
+                );
+                validator_stake_info.clear_commission_flag();
+                return Ok(());
+            }
+            msg!(
+                "Commission {}% of validator {} is within the cap of {}%",
+                commission,
+                vote_account_address,
+                commission_cap.max_commission
+            );
+            return Err(StakePoolError::ValidatorCommissionWithinCap.into());
+        }
+
+        let newly_flagged = !validator_stake_info.is_commission_flagged();
+        if newly_flagged {
+            msg!(
+                "Flagging validator {} with commission {}% above the cap of {}%",
+                vote_account_address,
+                commission,
+                commission_cap.max_commission
+            );
+            validator_stake_info.flag_commission(clock.epoch);
+        }
+
+        let elapsed_epochs = validator_stake_info.commission_flag_elapsed_epochs(clock.epoch);
+        if elapsed_epochs < commission_cap.grace_epochs {
+            if newly_flagged {
+                return Ok(());
+            }
+            msg!(
+                "Validator {} can lower its commission for {} more epochs",
+                vote_account_address,
+                commission_cap.grace_epochs.saturating_sub(elapsed_epochs)
+            );
+            return Err(StakePoolError::ValidatorCommissionGracePeriodActive.into());
+        }
+
+        msg!(
+            "Removing validator {} with commission {}% above the cap of {}%",
+            vote_account_address,
+            commission,
+            commission_cap.max_commission
+        );
+        let (_, stake) = get_stake_state(stake_account_info)?;
+        Self::remove_validator(
+            program_id,
+            stake_pool_info,
+            &mut stake_pool,
+            withdraw_authority_info,
+            validator_stake_info,
+            stake_account_info,
+            &stake,
+            transient_stake_account_info,
+            clock_info,
+        )
+    }
+
+    /// Processes the `CreateExchangeRateHistory` instruction
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_create_exchange_rate_history(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let exchange_rate_history_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        if !payer_info.is_signer {
+            msg!("Payer did not sign exchange rate history creation");
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+
+        check_system_program(system_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        let bump_seed = check_exchange_rate_history_address(
+            program_id,
+            stake_pool_info.key,
+            exchange_rate_history_info.key,
+        )?;
+        if !exchange_rate_history_info.data_is_empty() {
+            return Err(StakePoolError::AlreadyInUse.into());
+        }
+
+        // The account may have been pre-funded, only top it up to rent-exemption
+        let rent = Rent::get()?;
+        let required_lamports = rent
+            .minimum_balance(ExchangeRateHistory::LEN)
+            .saturating_sub(exchange_rate_history_info.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    payer_info.key,
+                    exchange_rate_history_info.key,
+                    required_lamports,
+                ),
+                &[
+                    payer_info.clone(),
+                    exchange_rate_history_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        let exchange_rate_history_signer_seeds: &[&[_]] = &[
+            EXCHANGE_RATE_HISTORY_SEED_PREFIX,
+            stake_pool_info.key.as_ref(),
+            &[bump_seed],
+        ];
+        invoke_signed(
+            &system_instruction::allocate(
+                exchange_rate_history_info.key,
+                ExchangeRateHistory::LEN as u64,
+            ),
+            &[exchange_rate_history_info.clone()],
+            &[exchange_rate_history_signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(exchange_rate_history_info.key, program_id),
+            &[exchange_rate_history_info.clone()],
+            &[exchange_rate_history_signer_seeds],
+        )?;
+
+        borsh::to_writer(
+            &mut exchange_rate_history_info.data.borrow_mut()[..],
+            &ExchangeRateHistory::new(*stake_pool_info.key),
+        )?;
+
         Ok(())
     }
 
@@ -2548,6 +3404,18 @@ impl Processor {
             .ok_or(StakePoolError::CalculationFailure)?;
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
+        event::log_deposit(
+            StakePoolEvent::DepositStake,
+            stake_pool_info.key,
+            &stake_pool,
+            dest_user_pool_info.key,
+            referrer_fee_info.key,
+            total_deposit_lamports,
+            pool_tokens_user,
+            pool_tokens_manager_deposit_fee,
+            pool_tokens_referral_fee,
+        );
+
         validator_stake_info.active_stake_lamports = validator_stake_account_info.lamports().into();
 
         Ok(())
@@ -2698,6 +3566,18 @@ impl Processor {
             .ok_or(StakePoolError::CalculationFailure)?;
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
+        event::log_deposit(
+            StakePoolEvent::DepositSol,
+            stake_pool_info.key,
+            &stake_pool,
+            dest_user_pool_info.key,
+            referrer_fee_info.key,
+            deposit_lamports,
+            pool_tokens_user,
+            pool_tokens_manager_deposit_fee,
+            pool_tokens_referral_fee,
+        );
+
         Ok(())
     }
 
@@ -2724,6 +3604,7 @@ impl Processor {
         let clock = &Clock::from_account_info(clock_info)?;
         let token_program_info = next_account_info(account_info_iter)?;
         let stake_program_info = next_account_info(account_info_iter)?;
+        let transfer_hook_accounts = account_info_iter.as_slice();
 
         check_stake_program(stake_program_info.key)?;
         check_account_owner(stake_pool_info, program_id)?;
@@ -2964,6 +3845,7 @@ impl Processor {
                 pool_mint_info.clone(),
                 manager_fee_info.clone(),
                 user_transfer_authority_info.clone(),
+                transfer_hook_accounts,
                 pool_tokens_fee,
                 decimals,
             )?;
@@ -2979,6 +3861,17 @@ impl Processor {
             .ok_or(StakePoolError::CalculationFailure)?;
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
+        event::log_withdraw(
+            StakePoolEvent::WithdrawStake,
+            stake_pool_info.key,
+            &stake_pool,
+            burn_from_pool_info.key,
+            stake_split_to.key,
+            pool_tokens,
+            pool_tokens_fee,
+            withdraw_lamports,
+        );
+
         if let Some((validator_list_item, withdraw_source)) = validator_list_item_info {
             match withdraw_source {
                 StakeWithdrawSource::Active => {
@@ -3037,6 +3930,7 @@ impl Processor {
         let stake_history_info = next_account_info(account_info_iter)?;
         let stake_program_info = next_account_info(account_info_iter)?;
         let token_program_info = next_account_info(account_info_iter)?;
+        let remaining_accounts = account_info_iter.as_slice();
         let sol_withdraw_authority_info = next_account_info(account_info_iter);
 
         check_account_owner(stake_pool_info, program_id)?;
@@ -3045,6 +3939,13 @@ impl Processor {
             return Err(StakePoolError::InvalidState.into());
         }
 
+        // transfer hook accounts follow the sol withdraw authority, if one is set
+        let transfer_hook_accounts = if stake_pool.sol_withdraw_authority.is_some() {
+            account_info_iter.as_slice()
+        } else {
+            remaining_accounts
+        };
+
         stake_pool.check_authority_withdraw(
             withdraw_authority_info.key,
             program_id,
@@ -3134,6 +4035,7 @@ impl Processor {
                 pool_mint_info.clone(),
                 manager_fee_info.clone(),
                 user_transfer_authority_info.clone(),
+                transfer_hook_accounts,
                 pool_tokens_fee,
                 decimals,
             )?;
@@ -3161,6 +4063,17 @@ impl Processor {
             .ok_or(StakePoolError::CalculationFailure)?;
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
+        event::log_withdraw(
+            StakePoolEvent::WithdrawSol,
+            stake_pool_info.key,
+            &stake_pool,
+            burn_from_pool_info.key,
+            destination_lamports_info.key,
+            pool_tokens,
+            pool_tokens_fee,
+            withdraw_lamports,
+        );
+
         Ok(())
     }
 
@@ -3358,6 +4271,7 @@ impl Processor {
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
         let manager_info = next_account_info(account_info_iter)?;
+        let maybe_pending_fees_info = next_account_info(account_info_iter).ok();
         let clock = Clock::get()?;
 
         check_account_owner(stake_pool_info, program_id)?;
@@ -3367,16 +4281,109 @@ impl Processor {
         }
         stake_pool.check_manager(manager_info)?;
 
-        if fee.can_only_change_next_epoch() && stake_pool.last_update_epoch < clock.epoch {
+        let delayed = stake_pool.is_fee_change_delayed(&fee);
+        if delayed && stake_pool.last_update_epoch < clock.epoch {
             return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
         }
 
         fee.check_too_high()?;
-        stake_pool.update_fee(&fee)?;
+        let mut pending_fees = match maybe_pending_fees_info {
+            Some(pending_fees_info) if !fee.can_only_change_next_epoch() => {
+                Self::get_or_create_pending_fees(
+                    program_id,
+                    stake_pool_info,
+                    pending_fees_info,
+                    account_info_iter,
+                    delayed,
+                )?
+            }
+            _ => None,
+        };
+        stake_pool.update_fee(&fee, pending_fees.as_mut())?;
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        if let Some((pending_fees_info, pending_fees)) = maybe_pending_fees_info.zip(pending_fees) {
+            borsh::to_writer(&mut pending_fees_info.data.borrow_mut()[..], &pending_fees)?;
+        }
+
+        if delayed {
+            // the value counts down on the next two epoch updates of the pool
+            event::log_pending_fee(stake_pool_info.key, fee, clock.epoch.saturating_add(2));
+        }
         Ok(())
     }
 
+    /// Loads the pending fees of the stake pool, creating the account if
+    /// needed and `create` is set
+    fn get_or_create_pending_fees<'a, 'b: 'a>(
+        program_id: &Pubkey,
+        stake_pool_info: &AccountInfo<'b>,
+        pending_fees_info: &AccountInfo<'b>,
+        account_info_iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+        create: bool,
+    ) -> Result<Option<PendingFees>, ProgramError> {
+        let bump_seed =
+            check_pending_fees_address(program_id, stake_pool_info.key, pending_fees_info.key)?;
+        if !pending_fees_info.data_is_empty() {
+            check_account_owner(pending_fees_info, program_id)?;
+            let pending_fees =
+                try_from_slice_unchecked::<PendingFees>(&pending_fees_info.data.borrow())?;
+            if !pending_fees.is_valid() {
+                return Err(StakePoolError::InvalidPendingFees.into());
+            }
+            return Ok(Some(pending_fees));
+        }
+        if !create {
+            return Ok(None);
+        }
+
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        check_system_program(system_program_info.key)?;
+        if !payer_info.is_signer {
+            msg!("Payer did not sign pending fees creation");
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+
+        // The account may have been pre-funded, only top it up to
+        // rent-exemption
+        let rent = Rent::get()?;
+        let required_lamports = rent
+            .minimum_balance(PendingFees::LEN)
+            .saturating_sub(pending_fees_info.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    payer_info.key,
+                    pending_fees_info.key,
+                    required_lamports,
+                ),
+                &[
+                    payer_info.clone(),
+                    pending_fees_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        let pending_fees_signer_seeds: &[&[_]] = &[
+            PENDING_FEES_SEED_PREFIX,
+            stake_pool_info.key.as_ref(),
+            &[bump_seed],
+        ];
+        invoke_signed(
+            &system_instruction::allocate(pending_fees_info.key, PendingFees::LEN as u64),
+            &[pending_fees_info.clone()],
+            &[pending_fees_signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(pending_fees_info.key, program_id),
+            &[pending_fees_info.clone()],
+            &[pending_fees_signer_seeds],
+        )?;
+
+        Ok(Some(PendingFees::new(*stake_pool_info.key)))
+    }
+
     /// Processes [SetStaker](enum.Instruction.html).
     #[inline(never)] // needed to avoid stack size violation
     fn process_set_staker(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
@@ -3655,6 +4662,34 @@ impl Processor {
                     Some(minimum_lamports_out),
                 )
             }
+            StakePoolInstruction::CreateExchangeRateHistory => {
+                msg!("Instruction: CreateExchangeRateHistory");
+                Self::process_create_exchange_rate_history(program_id, accounts)
+            }
+            StakePoolInstruction::RecoverTransientStake => {
+                msg!("Instruction: RecoverTransientStake");
+                Self::process_recover_transient_stake(program_id, accounts)
+            }
+            StakePoolInstruction::SetDaoTreasury { reward_fee } => {
+                msg!("Instruction: SetDaoTreasury");
+                Self::process_set_dao_treasury(program_id, accounts, reward_fee)
+            }
+            StakePoolInstruction::SetValidatorCommissionCap {
+                max_commission,
+                grace_epochs,
+            } => {
+                msg!("Instruction: SetValidatorCommissionCap");
+                Self::process_set_validator_commission_cap(
+                    program_id,
+                    accounts,
+                    max_commission,
+                    grace_epochs,
+                )
+            }
+            StakePoolInstruction::FlagValidatorCommission => {
+                msg!("Instruction: FlagValidatorCommission");
+                Self::process_flag_validator_commission(program_id, accounts)
+            }
         }
     }
 }
@@ -3710,6 +4745,15 @@ impl PrintProgramError for StakePoolError {
             StakePoolError::IncorrectMintDecimals => msg!("Error: Provided mint does not have 9 decimals to match SOL"),
             StakePoolError::ReserveDepleted => msg!("Error: Pool reserve does not have enough lamports to fund rent-exempt reserve in split destination. Deposit more SOL in reserve, or pre-fund split destination with the rent-exempt reserve for a stake account."),
             StakePoolError::MissingRequiredSysvar => msg!("Missing required sysvar account"),
+            StakePoolError::InvalidExchangeRateHistory => msg!("Error: Provided exchange rate history account does not match the one derived for the stake pool"),
+            StakePoolError::StakePoolAlreadyUpdated => msg!("Error: The stake pool has already been updated for the current epoch"),
+            StakePoolError::TransientStakeNotStuck => msg!("Error: The transient stake account can be handled by the regular update process"),
+            StakePoolError::InvalidDaoTreasury => msg!("Error: Provided DAO treasury account does not match the one derived for the stake pool, or its treasury pool token account"),
+            StakePoolError::InvalidPendingFees => msg!("Error: Provided pending fees account does not match the one derived for the stake pool, or is missing for a delayed fee increase"),
+            StakePoolError::InvalidValidatorCommissionCap => msg!("Error: Provided validator commission cap account does not match the one derived for the stake pool, or is not set"),
+            StakePoolError::InvalidVoteAccount => msg!("Error: Provided vote account is not a valid vote account"),
+            StakePoolError::ValidatorCommissionWithinCap => msg!("Error: The validator commission did not rise above the cap of the stake pool"),
+            StakePoolError::ValidatorCommissionGracePeriodActive => msg!("Error: The grace period of the flagged validator commission is not over yet"),
         }
     }
 }
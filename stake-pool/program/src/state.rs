@@ -2,7 +2,8 @@
 
 use {
     crate::{
-        big_vec::BigVec, error::StakePoolError, MAX_WITHDRAWAL_FEE_INCREASE,
+        big_vec::BigVec, error::StakePoolError, DEPOSIT_BASELINE_FEE, EPOCH_BASELINE_FEE,
+        MAX_FEE_INCREASE, MAX_REFERRAL_FEE_INCREASE, MAX_WITHDRAWAL_FEE_INCREASE,
         WITHDRAWAL_BASELINE_FEE,
     },
     borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
@@ -19,7 +20,7 @@ use {
         pubkey::{Pubkey, PUBKEY_BYTES},
         stake::state::Lockup,
     },
-    spl_pod::primitives::{PodU32, PodU64},
+    spl_pod::primitives::{PodBool, PodU16, PodU32, PodU64},
     spl_token_2022::{
         extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
         state::{Account, AccountState, Mint},
@@ -37,6 +38,14 @@ pub enum AccountType {
     StakePool,
     /// Validator stake list
     ValidatorList,
+    /// Exchange rate history
+    ExchangeRateHistory,
+    /// DAO treasury
+    DaoTreasury,
+    /// Pending fees
+    PendingFees,
+    /// Validator commission cap
+    ValidatorCommissionCap,
 }
 
 /// Initialized program details.
@@ -258,6 +267,39 @@ impl StakePool {
         }
     }
 
+    /// calculate pool tokens to be minted to the DAO treasury for its share of
+    /// the epoch rewards, after `epoch_fee_pool_tokens` have been minted to
+    /// the manager. The treasury share is capped to the rewards left over
+    /// after the epoch fee, so that it never comes out of the principal.
+    pub fn calc_dao_treasury_fee_amount(
+        &self,
+        reward_fee: &Fee,
+        reward_lamports: u64,
+        epoch_fee_pool_tokens: u64,
+    ) -> Option<u64> {
+        if reward_lamports == 0 {
+            return Some(0);
+        }
+        let total_lamports = (self.total_lamports as u128).checked_add(reward_lamports as u128)?;
+        let remaining_reward_lamports =
+            (reward_lamports as u128).saturating_sub(self.epoch_fee.apply(reward_lamports)?);
+        let fee_lamports = reward_fee
+            .apply(reward_lamports)?
+            .min(remaining_reward_lamports);
+        let pool_token_supply =
+            (self.pool_token_supply as u128).checked_add(epoch_fee_pool_tokens as u128)?;
+        if fee_lamports == 0 || pool_token_supply == 0 || total_lamports == fee_lamports {
+            Some(0)
+        } else {
+            u64::try_from(
+                pool_token_supply
+                    .checked_mul(fee_lamports)?
+                    .checked_div(total_lamports.checked_sub(fee_lamports)?)?,
+            )
+            .ok()
+        }
+    }
+
     /// Get the current value of pool tokens, rounded up
     #[inline]
     pub fn get_lamports_per_pool_token(&self) -> Option<u64> {
@@ -298,13 +340,23 @@ impl StakePool {
         &self,
         manager_fee_info: &AccountInfo,
     ) -> Result<(), ProgramError> {
-        let account_data = manager_fee_info.try_borrow_data()?;
+        self.check_pool_fee_account_info(manager_fee_info, "Manager fee account")
+    }
+
+    /// Check that a pool token account receiving fees, such as the DAO
+    /// treasury pool token account, is valid
+    pub(crate) fn check_pool_fee_account_info(
+        &self,
+        fee_account_info: &AccountInfo,
+        name: &str,
+    ) -> Result<(), ProgramError> {
+        let account_data = fee_account_info.try_borrow_data()?;
         let token_account = StateWithExtensions::<Account>::unpack(&account_data)?;
-        if manager_fee_info.owner != &self.token_program_id
+        if fee_account_info.owner != &self.token_program_id
             || token_account.base.state != AccountState::Initialized
             || token_account.base.mint != self.pool_mint
         {
-            msg!("Manager fee account is not owned by token program, is not initialized, or does not match stake pool's mint");
+            msg!("{} is not owned by token program, is not initialized, or does not match stake pool's mint", name);
             return Err(StakePoolError::InvalidFeeAccount.into());
         }
         let extensions = token_account.get_extension_types()?;
@@ -477,12 +529,66 @@ impl StakePool {
         self.account_type == AccountType::Uninitialized
     }
 
-    /// Updates one of the StakePool's fees.
-    pub fn update_fee(&mut self, fee: &FeeType) -> Result<(), StakePoolError> {
+    /// Returns if the given fee only takes effect after two epoch boundaries.
+    ///
+    /// Epoch and withdrawal fees are always delayed, deposit and referral fees
+    /// only when they increase once the pool went through an epoch with pool
+    /// tokens outstanding, leaving the manager free to configure a new pool.
+    pub fn is_fee_change_delayed(&self, fee: &FeeType) -> bool {
+        if fee.can_only_change_next_epoch() {
+            return true;
+        }
+        if self.last_epoch_pool_token_supply == 0 {
+            return false;
+        }
         match fee {
-            FeeType::SolReferral(new_fee) => self.sol_referral_fee = *new_fee,
-            FeeType::StakeReferral(new_fee) => self.stake_referral_fee = *new_fee,
-            FeeType::Epoch(new_fee) => self.next_epoch_fee = FutureEpoch::new(*new_fee),
+            FeeType::SolReferral(new_fee) => *new_fee > self.sol_referral_fee,
+            FeeType::StakeReferral(new_fee) => *new_fee > self.stake_referral_fee,
+            FeeType::SolDeposit(new_fee) => new_fee.is_higher_than(&self.sol_deposit_fee),
+            FeeType::StakeDeposit(new_fee) => new_fee.is_higher_than(&self.stake_deposit_fee),
+            FeeType::Epoch(_) | FeeType::StakeWithdrawal(_) | FeeType::SolWithdrawal(_) => true,
+        }
+    }
+
+    /// Updates one of the StakePool's fees. Delayed deposit and referral fee
+    /// increases are recorded in the pending fees of the pool, and cleared by
+    /// immediate changes.
+    pub fn update_fee(
+        &mut self,
+        fee: &FeeType,
+        mut pending_fees: Option<&mut PendingFees>,
+    ) -> Result<(), StakePoolError> {
+        let delayed = self.is_fee_change_delayed(fee);
+        if !delayed {
+            if let Some(pending_fees) = pending_fees.as_deref_mut() {
+                pending_fees.clear(fee);
+            }
+        }
+        match fee {
+            FeeType::SolReferral(new_fee) => {
+                if delayed {
+                    check_referral_increase(*new_fee, self.sol_referral_fee)?;
+                    pending_fees
+                        .ok_or(StakePoolError::InvalidPendingFees)?
+                        .next_sol_referral_fee = FutureEpoch::new(*new_fee);
+                } else {
+                    self.sol_referral_fee = *new_fee;
+                }
+            }
+            FeeType::StakeReferral(new_fee) => {
+                if delayed {
+                    check_referral_increase(*new_fee, self.stake_referral_fee)?;
+                    pending_fees
+                        .ok_or(StakePoolError::InvalidPendingFees)?
+                        .next_stake_referral_fee = FutureEpoch::new(*new_fee);
+                } else {
+                    self.stake_referral_fee = *new_fee;
+                }
+            }
+            FeeType::Epoch(new_fee) => {
+                new_fee.check_increase(&self.epoch_fee, &EPOCH_BASELINE_FEE, &MAX_FEE_INCREASE)?;
+                self.next_epoch_fee = FutureEpoch::new(*new_fee)
+            }
             FeeType::StakeWithdrawal(new_fee) => {
                 new_fee.check_withdrawal(&self.stake_withdrawal_fee)?;
                 self.next_stake_withdrawal_fee = FutureEpoch::new(*new_fee)
@@ -491,16 +597,74 @@ impl StakePool {
                 new_fee.check_withdrawal(&self.sol_withdrawal_fee)?;
                 self.next_sol_withdrawal_fee = FutureEpoch::new(*new_fee)
             }
-            FeeType::SolDeposit(new_fee) => self.sol_deposit_fee = *new_fee,
-            FeeType::StakeDeposit(new_fee) => self.stake_deposit_fee = *new_fee,
+            FeeType::SolDeposit(new_fee) => {
+                if delayed {
+                    new_fee.check_increase(
+                        &self.sol_deposit_fee,
+                        &DEPOSIT_BASELINE_FEE,
+                        &MAX_FEE_INCREASE,
+                    )?;
+                    pending_fees
+                        .ok_or(StakePoolError::InvalidPendingFees)?
+                        .next_sol_deposit_fee = FutureEpoch::new(*new_fee);
+                } else {
+                    self.sol_deposit_fee = *new_fee;
+                }
+            }
+            FeeType::StakeDeposit(new_fee) => {
+                if delayed {
+                    new_fee.check_increase(
+                        &self.stake_deposit_fee,
+                        &DEPOSIT_BASELINE_FEE,
+                        &MAX_FEE_INCREASE,
+                    )?;
+                    pending_fees
+                        .ok_or(StakePoolError::InvalidPendingFees)?
+                        .next_stake_deposit_fee = FutureEpoch::new(*new_fee);
+                } else {
+                    self.stake_deposit_fee = *new_fee;
+                }
+            }
         };
         Ok(())
     }
+
+    /// Applies the fees that are ready, to be done once per epoch on the
+    /// first update of the pool
+    pub fn update_fees_for_epoch(&mut self) {
+        if let Some(fee) = self.next_epoch_fee.get() {
+            self.epoch_fee = *fee;
+        }
+        self.next_epoch_fee.update_epoch();
+
+        if let Some(fee) = self.next_stake_withdrawal_fee.get() {
+            self.stake_withdrawal_fee = *fee;
+        }
+        self.next_stake_withdrawal_fee.update_epoch();
+
+        if let Some(fee) = self.next_sol_withdrawal_fee.get() {
+            self.sol_withdrawal_fee = *fee;
+        }
+        self.next_sol_withdrawal_fee.update_epoch();
+    }
+}
+
+/// Checks that a referral fee increase stays within MAX_REFERRAL_FEE_INCREASE
+/// percentage points
+fn check_referral_increase(new_fee: u8, old_fee: u8) -> Result<(), StakePoolError> {
+    if new_fee.saturating_sub(old_fee) > MAX_REFERRAL_FEE_INCREASE {
+        msg!(
+            "Referral fee increase exceeds maximum allowed, proposed increase {}%",
+            new_fee.saturating_sub(old_fee)
+        );
+        return Err(StakePoolError::FeeIncreaseTooHigh);
+    }
+    Ok(())
 }
 
 /// Checks if the given extension is supported for the stake pool mint
 pub fn is_extension_supported_for_mint(extension_type: &ExtensionType) -> bool {
-    const SUPPORTED_EXTENSIONS: [ExtensionType; 8] = [
+    const SUPPORTED_EXTENSIONS: [ExtensionType; 9] = [
         ExtensionType::Uninitialized,
         ExtensionType::TransferFeeConfig,
         ExtensionType::ConfidentialTransferMint,
@@ -509,6 +673,7 @@ pub fn is_extension_supported_for_mint(extension_type: &ExtensionType) -> bool {
         ExtensionType::InterestBearingConfig,
         ExtensionType::MetadataPointer,
         ExtensionType::TokenMetadata,
+        ExtensionType::TransferHook, // extra accounts are passed on withdrawal
     ];
     if !SUPPORTED_EXTENSIONS.contains(extension_type) {
         msg!(
@@ -526,11 +691,12 @@ pub fn is_extension_supported_for_fee_account(extension_type: &ExtensionType) ->
     // Note: this does not include the `ConfidentialTransferAccount` extension
     // because it is possible to block non-confidential transfers with the
     // extension enabled.
-    const SUPPORTED_EXTENSIONS: [ExtensionType; 4] = [
+    const SUPPORTED_EXTENSIONS: [ExtensionType; 5] = [
         ExtensionType::Uninitialized,
         ExtensionType::TransferFeeAmount,
         ExtensionType::ImmutableOwner,
         ExtensionType::CpiGuard,
+        ExtensionType::TransferHookAccount,
     ];
     if !SUPPORTED_EXTENSIONS.contains(extension_type) {
         msg!("Fee account cannot have the {:?} extension", extension_type);
@@ -709,8 +875,17 @@ pub struct ValidatorStakeInfo {
     /// account address
     pub transient_seed_suffix: PodU64,
 
-    /// Unused space, initially meant to specify the end of seed suffixes
-    pub unused: PodU32,
+    /// Commission of the validator when it was added to the pool, zero for
+    /// validators added before it was recorded
+    pub commission: u8,
+
+    /// Whether the commission of the validator was flagged above the
+    /// validator commission cap of the pool
+    pub commission_flagged: PodBool,
+
+    /// Epoch in which the commission was flagged, truncated to 16 bits since
+    /// only the epochs elapsed during the grace period are relevant
+    pub commission_flag_epoch: PodU16,
 
     /// Validator account seed suffix
     pub validator_seed_suffix: PodU32, // really `Option<NonZeroU32>` so 0 is `None`
@@ -758,6 +933,29 @@ impl ValidatorStakeInfo {
     pub fn is_not_removed(data: &[u8]) -> bool {
         FromPrimitive::from_u8(data[40]) != Some(StakeStatus::ReadyForRemoval)
     }
+
+    /// Check if the commission of the validator is flagged above the cap
+    pub fn is_commission_flagged(&self) -> bool {
+        bool::from(self.commission_flagged)
+    }
+
+    /// Flag the commission of the validator in the given epoch
+    pub fn flag_commission(&mut self, epoch: u64) {
+        self.commission_flagged = true.into();
+        self.commission_flag_epoch = (epoch as u16).into();
+    }
+
+    /// Clear the commission flag of the validator
+    pub fn clear_commission_flag(&mut self) {
+        self.commission_flagged = false.into();
+        self.commission_flag_epoch = 0.into();
+    }
+
+    /// Number of epochs elapsed since the commission was flagged, counted
+    /// modulo 2^16 like the flag epoch
+    pub fn commission_flag_elapsed_epochs(&self, epoch: u64) -> u16 {
+        (epoch as u16).wrapping_sub(self.commission_flag_epoch.into())
+    }
 }
 
 impl Sealed for ValidatorStakeInfo {}
@@ -862,6 +1060,278 @@ impl ValidatorListHeader {
     }
 }
 
+/// Number of epochs kept in the exchange rate history
+pub const EXCHANGE_RATE_HISTORY_LEN: usize = 64;
+
+/// Pool exchange rate at the time of the first `UpdateStakePoolBalance` in an
+/// epoch
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ExchangeRateEntry {
+    /// Epoch of the update
+    pub epoch: u64,
+    /// Total lamports managed by the pool after the update
+    pub total_lamports: u64,
+    /// Pool token supply after the update
+    pub pool_token_supply: u64,
+}
+
+/// Ring buffer of the pool exchange rate over the last
+/// `EXCHANGE_RATE_HISTORY_LEN` epochs, stored in a PDA alongside the stake
+/// pool so that clients can compute the APY without relying on an indexer
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ExchangeRateHistory {
+    /// Account type, must be ExchangeRateHistory currently
+    pub account_type: AccountType,
+    /// Stake pool the history belongs to
+    pub stake_pool: Pubkey,
+    /// Number of entries recorded so far, saturating at
+    /// `EXCHANGE_RATE_HISTORY_LEN`
+    pub len: u16,
+    /// Index of the slot to write the next entry into
+    pub next_index: u16,
+    /// Entries, in ring buffer order
+    pub entries: [ExchangeRateEntry; EXCHANGE_RATE_HISTORY_LEN],
+}
+
+impl ExchangeRateHistory {
+    /// Serialized size of the account
+    pub const LEN: usize = 1 + PUBKEY_BYTES + 2 + 2 + EXCHANGE_RATE_HISTORY_LEN * 24;
+
+    /// Create an empty history for the given stake pool
+    pub fn new(stake_pool: Pubkey) -> Self {
+        Self {
+            account_type: AccountType::ExchangeRateHistory,
+            stake_pool,
+            len: 0,
+            next_index: 0,
+            entries: [ExchangeRateEntry::default(); EXCHANGE_RATE_HISTORY_LEN],
+        }
+    }
+
+    /// Check if the history is actually initialized as an exchange rate
+    /// history
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::ExchangeRateHistory
+    }
+
+    /// Index of the slot `offset` positions after `index`, wrapping around
+    fn wrapping_index(index: usize, offset: usize) -> usize {
+        index.saturating_add(offset) % EXCHANGE_RATE_HISTORY_LEN
+    }
+
+    /// Most recently recorded entry, if any
+    pub fn latest(&self) -> Option<&ExchangeRateEntry> {
+        if self.len == 0 {
+            None
+        } else {
+            let index = Self::wrapping_index(
+                self.next_index as usize,
+                EXCHANGE_RATE_HISTORY_LEN.saturating_sub(1),
+            );
+            self.entries.get(index)
+        }
+    }
+
+    /// Record an entry, overwriting the oldest one once the buffer is full.
+    /// Only the first entry of an epoch is kept, returns whether the entry was
+    /// recorded.
+    pub fn record(&mut self, entry: ExchangeRateEntry) -> bool {
+        if self
+            .latest()
+            .map(|latest| latest.epoch >= entry.epoch)
+            .unwrap_or(false)
+        {
+            return false;
+        }
+        let index = Self::wrapping_index(self.next_index as usize, 0);
+        self.entries[index] = entry;
+        self.next_index = Self::wrapping_index(index, 1) as u16;
+        self.len = self
+            .len
+            .saturating_add(1)
+            .min(EXCHANGE_RATE_HISTORY_LEN as u16);
+        true
+    }
+
+    /// Recorded entries, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &ExchangeRateEntry> {
+        let len = self.len as usize;
+        let start = Self::wrapping_index(
+            self.next_index as usize,
+            EXCHANGE_RATE_HISTORY_LEN.saturating_sub(len),
+        );
+        (0..len).map(move |i| &self.entries[Self::wrapping_index(start, i)])
+    }
+}
+
+/// DAO treasury of the stake pool, stored in a PDA alongside the stake pool.
+/// `UpdateStakePoolBalance` mints a share of the epoch rewards to the treasury
+/// pool token account, leaving the principal of the pool untouched.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct DaoTreasury {
+    /// Account type, must be DaoTreasury currently
+    pub account_type: AccountType,
+    /// Stake pool the treasury belongs to
+    pub stake_pool: Pubkey,
+    /// Pool token account receiving the treasury share of the rewards
+    pub treasury_pool_account: Pubkey,
+    /// Share of the epoch rewards minted to the treasury as pool tokens, a
+    /// zero fee pauses the skimming
+    pub reward_fee: Fee,
+    /// Total pool tokens minted to the treasury
+    pub total_pool_tokens: u64,
+    /// Last epoch pool tokens were minted to the treasury
+    pub last_skim_epoch: u64,
+}
+
+impl DaoTreasury {
+    /// Serialized size of the account
+    pub const LEN: usize = 1 + PUBKEY_BYTES + PUBKEY_BYTES + 16 + 8 + 8;
+
+    /// Create a DAO treasury for the given stake pool
+    pub fn new(stake_pool: Pubkey, treasury_pool_account: Pubkey, reward_fee: Fee) -> Self {
+        Self {
+            account_type: AccountType::DaoTreasury,
+            stake_pool,
+            treasury_pool_account,
+            reward_fee,
+            total_pool_tokens: 0,
+            last_skim_epoch: 0,
+        }
+    }
+
+    /// Check if the account is actually initialized as a DAO treasury
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::DaoTreasury
+    }
+}
+
+/// Pending deposit and referral fee increases of the stake pool, stored in a
+/// PDA alongside the stake pool so that the stake pool account keeps its size.
+/// `UpdateStakePoolBalance` applies them after two epoch boundaries, like the
+/// epoch and withdrawal fees kept in the stake pool.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct PendingFees {
+    /// Account type, must be PendingFees currently
+    pub account_type: AccountType,
+    /// Stake pool the pending fees belong to
+    pub stake_pool: Pubkey,
+    /// Future stake deposit fee
+    pub next_stake_deposit_fee: FutureEpoch<Fee>,
+    /// Future stake referral fee
+    pub next_stake_referral_fee: FutureEpoch<u8>,
+    /// Future SOL deposit fee
+    pub next_sol_deposit_fee: FutureEpoch<Fee>,
+    /// Future SOL referral fee
+    pub next_sol_referral_fee: FutureEpoch<u8>,
+}
+
+impl PendingFees {
+    /// Serialized size of the account, with every fee pending
+    pub const LEN: usize = 1 + PUBKEY_BYTES + 2 * (1 + 16) + 2 * (1 + 1);
+
+    /// Create empty pending fees for the given stake pool
+    pub fn new(stake_pool: Pubkey) -> Self {
+        Self {
+            account_type: AccountType::PendingFees,
+            stake_pool,
+            next_stake_deposit_fee: FutureEpoch::None,
+            next_stake_referral_fee: FutureEpoch::None,
+            next_sol_deposit_fee: FutureEpoch::None,
+            next_sol_referral_fee: FutureEpoch::None,
+        }
+    }
+
+    /// Check if the account is actually initialized as pending fees
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::PendingFees
+    }
+
+    /// Cancels the pending increase of the given fee
+    pub fn clear(&mut self, fee: &FeeType) {
+        match fee {
+            FeeType::SolReferral(_) => self.next_sol_referral_fee = FutureEpoch::None,
+            FeeType::StakeReferral(_) => self.next_stake_referral_fee = FutureEpoch::None,
+            FeeType::SolDeposit(_) => self.next_sol_deposit_fee = FutureEpoch::None,
+            FeeType::StakeDeposit(_) => self.next_stake_deposit_fee = FutureEpoch::None,
+            FeeType::Epoch(_) | FeeType::StakeWithdrawal(_) | FeeType::SolWithdrawal(_) => {}
+        }
+    }
+
+    /// Applies the fees that are ready to the stake pool, to be done once per
+    /// epoch on the first update of the pool
+    pub fn update_fees_for_epoch(&mut self, stake_pool: &mut StakePool) {
+        if let Some(fee) = self.next_stake_deposit_fee.get() {
+            stake_pool.stake_deposit_fee = *fee;
+        }
+        self.next_stake_deposit_fee.update_epoch();
+
+        if let Some(fee) = self.next_stake_referral_fee.get() {
+            stake_pool.stake_referral_fee = *fee;
+        }
+        self.next_stake_referral_fee.update_epoch();
+
+        if let Some(fee) = self.next_sol_deposit_fee.get() {
+            stake_pool.sol_deposit_fee = *fee;
+        }
+        self.next_sol_deposit_fee.update_epoch();
+
+        if let Some(fee) = self.next_sol_referral_fee.get() {
+            stake_pool.sol_referral_fee = *fee;
+        }
+        self.next_sol_referral_fee.update_epoch();
+    }
+}
+
+/// Validator commission cap of the stake pool, stored in a PDA alongside the
+/// stake pool. Validators whose commission rises above the cap can be flagged
+/// by anyone with `FlagValidatorCommission`, and are removed from the pool
+/// once the grace period is over.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ValidatorCommissionCap {
+    /// Account type, must be ValidatorCommissionCap currently
+    pub account_type: AccountType,
+    /// Stake pool the cap belongs to
+    pub stake_pool: Pubkey,
+    /// Maximum commission of the validators of the pool, as a percentage
+    pub max_commission: u8,
+    /// Number of epochs a validator can stay above the cap after being
+    /// flagged, so it has time to lower its commission again
+    pub grace_epochs: u16,
+}
+
+impl ValidatorCommissionCap {
+    /// Serialized size of the account
+    pub const LEN: usize = 1 + PUBKEY_BYTES + 1 + 2;
+
+    /// Create a validator commission cap for the given stake pool
+    pub fn new(stake_pool: Pubkey, max_commission: u8, grace_epochs: u16) -> Self {
+        Self {
+            account_type: AccountType::ValidatorCommissionCap,
+            stake_pool,
+            max_commission,
+            grace_epochs,
+        }
+    }
+
+    /// Check if the account is actually initialized as a validator commission
+    /// cap
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::ValidatorCommissionCap
+    }
+
+    /// Check if a validator commission rose above the cap, compared to the
+    /// commission of the validator when it was added to the pool
+    pub fn is_exceeded_by(&self, commission: u8, commission_at_add: u8) -> bool {
+        commission > self.max_commission && commission > commission_at_add
+    }
+}
+
 /// Wrapper type that "counts down" epochs, which is Borsh-compatible with the
 /// native `Option`
 #[repr(C)]
@@ -954,28 +1424,40 @@ impl Fee {
     /// this fn checks if those are met, returning an error if not.
     /// Does nothing and returns Ok if fee type is not withdrawal
     pub fn check_withdrawal(&self, old_withdrawal_fee: &Fee) -> Result<(), StakePoolError> {
-        // If the previous withdrawal fee was 0, we allow the fee to be set to a
-        // maximum of (WITHDRAWAL_BASELINE_FEE * MAX_WITHDRAWAL_FEE_INCREASE)
-        let (old_num, old_denom) =
-            if old_withdrawal_fee.denominator == 0 || old_withdrawal_fee.numerator == 0 {
-                (
-                    WITHDRAWAL_BASELINE_FEE.numerator,
-                    WITHDRAWAL_BASELINE_FEE.denominator,
-                )
-            } else {
-                (old_withdrawal_fee.numerator, old_withdrawal_fee.denominator)
-            };
+        self.check_increase(
+            old_withdrawal_fee,
+            &WITHDRAWAL_BASELINE_FEE,
+            &MAX_WITHDRAWAL_FEE_INCREASE,
+        )
+    }
 
-        // Check that new_fee / old_fee <= MAX_WITHDRAWAL_FEE_INCREASE
+    /// Checks that the fee is at most `max_increase` times the old fee,
+    /// returning an error if not.
+    /// If the old fee is 0, `baseline_fee` is used in its place
+    pub fn check_increase(
+        &self,
+        old_fee: &Fee,
+        baseline_fee: &Fee,
+        max_increase: &Fee,
+    ) -> Result<(), StakePoolError> {
+        // If the previous fee was 0, we allow the fee to be set to a
+        // maximum of (baseline_fee * max_increase)
+        let (old_num, old_denom) = if old_fee.denominator == 0 || old_fee.numerator == 0 {
+            (baseline_fee.numerator, baseline_fee.denominator)
+        } else {
+            (old_fee.numerator, old_fee.denominator)
+        };
+
+        // Check that new_fee / old_fee <= max_increase
         // Program fails if provided numerator or denominator is too large, resulting in
         // overflow
         if (old_num as u128)
             .checked_mul(self.denominator as u128)
-            .map(|x| x.checked_mul(MAX_WITHDRAWAL_FEE_INCREASE.numerator as u128))
+            .map(|x| x.checked_mul(max_increase.numerator as u128))
             .ok_or(StakePoolError::CalculationFailure)?
             < (self.numerator as u128)
                 .checked_mul(old_denom as u128)
-                .map(|x| x.checked_mul(MAX_WITHDRAWAL_FEE_INCREASE.denominator as u128))
+                .map(|x| x.checked_mul(max_increase.denominator as u128))
                 .ok_or(StakePoolError::CalculationFailure)?
         {
             msg!(
@@ -987,6 +1469,23 @@ impl Fee {
         }
         Ok(())
     }
+
+    /// Returns if the fee takes a larger proportion than `other`, a fee with
+    /// a denominator of 0 being considered as 0
+    pub fn is_higher_than(&self, other: &Fee) -> bool {
+        let (num, denom) = if self.denominator == 0 {
+            (0, 1)
+        } else {
+            (self.numerator, self.denominator)
+        };
+        let (other_num, other_denom) = if other.denominator == 0 {
+            (0, 1)
+        } else {
+            (other.numerator, other.denominator)
+        };
+        (num as u128).saturating_mul(other_denom as u128)
+            > (other_num as u128).saturating_mul(denom as u128)
+    }
 }
 
 impl fmt::Display for Fee {
@@ -1085,7 +1584,9 @@ mod test {
                     transient_stake_lamports: u64::from_le_bytes([128; 8]).into(),
                     last_update_epoch: u64::from_le_bytes([64; 8]).into(),
                     transient_seed_suffix: 0.into(),
-                    unused: 0.into(),
+                    commission: 0,
+                    commission_flagged: false.into(),
+                    commission_flag_epoch: 0.into(),
                     validator_seed_suffix: 0.into(),
                 },
                 ValidatorStakeInfo {
@@ -1095,7 +1596,9 @@ mod test {
                     transient_stake_lamports: 222222222.into(),
                     last_update_epoch: 11223445566.into(),
                     transient_seed_suffix: 0.into(),
-                    unused: 0.into(),
+                    commission: 0,
+                    commission_flagged: false.into(),
+                    commission_flag_epoch: 0.into(),
                     validator_seed_suffix: 0.into(),
                 },
                 ValidatorStakeInfo {
@@ -1105,7 +1608,9 @@ mod test {
                     transient_stake_lamports: 0.into(),
                     last_update_epoch: 999999999999999.into(),
                     transient_seed_suffix: 0.into(),
-                    unused: 0.into(),
+                    commission: 0,
+                    commission_flagged: false.into(),
+                    commission_flag_epoch: 0.into(),
                     validator_seed_suffix: 0.into(),
                 },
             ],
@@ -1274,6 +1779,271 @@ mod test {
                                                         // truncation
     }
 
+    #[test]
+    fn dao_treasury_fee_calculation() {
+        // 10% epoch fee and 20% treasury share of 10 SOL in rewards should be
+        // 1 SOL and 2 SOL in fees
+        let mut stake_pool = StakePool {
+            total_lamports: 100 * LAMPORTS_PER_SOL,
+            pool_token_supply: 100 * LAMPORTS_PER_SOL,
+            epoch_fee: Fee {
+                numerator: 1,
+                denominator: 10,
+            },
+            ..StakePool::default()
+        };
+        let reward_fee = Fee {
+            numerator: 2,
+            denominator: 10,
+        };
+        let reward_lamports = 10 * LAMPORTS_PER_SOL;
+        let pool_token_fee = stake_pool.calc_epoch_fee_amount(reward_lamports).unwrap();
+        let pool_token_treasury_fee = stake_pool
+            .calc_dao_treasury_fee_amount(&reward_fee, reward_lamports, pool_token_fee)
+            .unwrap();
+
+        stake_pool.total_lamports += reward_lamports;
+        stake_pool.pool_token_supply += pool_token_fee + pool_token_treasury_fee;
+
+        let treasury_fee_lamports = stake_pool
+            .calc_lamports_withdraw_amount(pool_token_treasury_fee)
+            .unwrap();
+        // off by a couple of lamports due to truncation
+        assert!(2 * LAMPORTS_PER_SOL - treasury_fee_lamports <= 2);
+
+        // the treasury share is capped to the rewards left after the epoch fee
+        let stake_pool = StakePool {
+            total_lamports: 100 * LAMPORTS_PER_SOL,
+            pool_token_supply: 100 * LAMPORTS_PER_SOL,
+            epoch_fee: Fee {
+                numerator: 1,
+                denominator: 1,
+            },
+            ..StakePool::default()
+        };
+        let pool_token_fee = stake_pool.calc_epoch_fee_amount(reward_lamports).unwrap();
+        assert_eq!(
+            stake_pool
+                .calc_dao_treasury_fee_amount(&reward_fee, reward_lamports, pool_token_fee)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn deposit_fee_increase_delayed() {
+        let deposit_fee = Fee {
+            numerator: 1,
+            denominator: 100,
+        };
+        let new_deposit_fee = Fee {
+            numerator: 5,
+            denominator: 400,
+        };
+        let mut stake_pool = StakePool {
+            pool_token_supply: LAMPORTS_PER_SOL,
+            sol_deposit_fee: deposit_fee,
+            sol_referral_fee: 20,
+            ..StakePool::default()
+        };
+        let mut pending_fees = PendingFees::new(Pubkey::new_unique());
+
+        // a new pool can be configured freely
+        let fee = FeeType::SolDeposit(Fee {
+            numerator: 1,
+            denominator: 2,
+        });
+        assert!(!stake_pool.is_fee_change_delayed(&fee));
+        stake_pool.update_fee(&fee, None).unwrap();
+        stake_pool
+            .update_fee(&FeeType::SolDeposit(deposit_fee), None)
+            .unwrap();
+
+        stake_pool.last_epoch_pool_token_supply = LAMPORTS_PER_SOL;
+        assert_eq!(
+            stake_pool.update_fee(&fee, Some(&mut pending_fees)),
+            Err(StakePoolError::FeeIncreaseTooHigh)
+        );
+        assert_eq!(
+            stake_pool.update_fee(&FeeType::SolReferral(31), Some(&mut pending_fees)),
+            Err(StakePoolError::FeeIncreaseTooHigh)
+        );
+
+        // delayed increases need the pending fees
+        assert_eq!(
+            stake_pool.update_fee(&FeeType::SolDeposit(new_deposit_fee), None),
+            Err(StakePoolError::InvalidPendingFees)
+        );
+
+        stake_pool
+            .update_fee(
+                &FeeType::SolDeposit(new_deposit_fee),
+                Some(&mut pending_fees),
+            )
+            .unwrap();
+        stake_pool
+            .update_fee(&FeeType::SolReferral(30), Some(&mut pending_fees))
+            .unwrap();
+        assert_eq!(stake_pool.sol_deposit_fee, deposit_fee);
+        assert_eq!(
+            pending_fees.next_sol_deposit_fee,
+            FutureEpoch::Two(new_deposit_fee)
+        );
+        assert_eq!(stake_pool.sol_referral_fee, 20);
+        assert_eq!(pending_fees.next_sol_referral_fee, FutureEpoch::Two(30));
+
+        pending_fees.update_fees_for_epoch(&mut stake_pool);
+        assert_eq!(stake_pool.sol_deposit_fee, deposit_fee);
+        pending_fees.update_fees_for_epoch(&mut stake_pool);
+        assert_eq!(stake_pool.sol_deposit_fee, new_deposit_fee);
+        assert_eq!(pending_fees.next_sol_deposit_fee, FutureEpoch::None);
+        assert_eq!(stake_pool.sol_referral_fee, 30);
+
+        // decreases apply immediately and cancel a pending increase
+        stake_pool
+            .update_fee(&FeeType::SolReferral(40), Some(&mut pending_fees))
+            .unwrap();
+        stake_pool
+            .update_fee(&FeeType::SolReferral(10), Some(&mut pending_fees))
+            .unwrap();
+        assert_eq!(stake_pool.sol_referral_fee, 10);
+        assert_eq!(pending_fees.next_sol_referral_fee, FutureEpoch::None);
+    }
+
+    #[test]
+    fn fee_comparison() {
+        let zero = Fee {
+            numerator: 1,
+            denominator: 0,
+        };
+        let fee = Fee {
+            numerator: 1,
+            denominator: 100,
+        };
+        assert!(fee.is_higher_than(&zero));
+        assert!(!zero.is_higher_than(&fee));
+        assert!(!fee.is_higher_than(&Fee {
+            numerator: 10,
+            denominator: 1000,
+        }));
+    }
+
+    #[test]
+    fn dao_treasury_len() {
+        let dao_treasury = DaoTreasury::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Fee {
+                numerator: 1,
+                denominator: 10,
+            },
+        );
+        assert_eq!(get_packed_len::<DaoTreasury>(), DaoTreasury::LEN);
+        assert_eq!(
+            borsh::to_vec(&dao_treasury).unwrap().len(),
+            DaoTreasury::LEN
+        );
+    }
+
+    #[test]
+    fn pending_fees_len() {
+        let fee = Fee {
+            numerator: 1,
+            denominator: 100,
+        };
+        let pending_fees = PendingFees {
+            next_stake_deposit_fee: FutureEpoch::Two(fee),
+            next_stake_referral_fee: FutureEpoch::Two(1),
+            next_sol_deposit_fee: FutureEpoch::Two(fee),
+            next_sol_referral_fee: FutureEpoch::Two(1),
+            ..PendingFees::new(Pubkey::new_unique())
+        };
+        assert_eq!(
+            borsh::to_vec(&pending_fees).unwrap().len(),
+            PendingFees::LEN
+        );
+    }
+
+    #[test]
+    fn stake_pool_fits_existing_accounts() {
+        // size of the accounts of the pools created before the pending fees,
+        // which can't be reallocated by the program
+        const EXISTING_STAKE_POOL_LEN: usize = 611;
+        assert_eq!(get_packed_len::<StakePool>(), EXISTING_STAKE_POOL_LEN);
+
+        let fee = Fee {
+            numerator: 1,
+            denominator: 100,
+        };
+        let stake_pool = StakePool {
+            account_type: AccountType::StakePool,
+            preferred_deposit_validator_vote_address: Some(Pubkey::new_unique()),
+            preferred_withdraw_validator_vote_address: Some(Pubkey::new_unique()),
+            sol_deposit_authority: Some(Pubkey::new_unique()),
+            sol_withdraw_authority: Some(Pubkey::new_unique()),
+            next_epoch_fee: FutureEpoch::Two(fee),
+            next_stake_withdrawal_fee: FutureEpoch::Two(fee),
+            next_sol_withdrawal_fee: FutureEpoch::Two(fee),
+            sol_deposit_fee: fee,
+            pool_token_supply: LAMPORTS_PER_SOL,
+            last_epoch_pool_token_supply: LAMPORTS_PER_SOL,
+            ..StakePool::default()
+        };
+        let mut data = vec![0; EXISTING_STAKE_POOL_LEN];
+        borsh::to_writer(&mut data[..], &stake_pool).unwrap();
+
+        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&data).unwrap();
+        let mut pending_fees = PendingFees::new(Pubkey::new_unique());
+        let new_fee = Fee {
+            numerator: 5,
+            denominator: 400,
+        };
+        stake_pool
+            .update_fee(&FeeType::SolDeposit(new_fee), Some(&mut pending_fees))
+            .unwrap();
+        borsh::to_writer(&mut data[..], &stake_pool).unwrap();
+        let stake_pool = try_from_slice_unchecked::<StakePool>(&data).unwrap();
+        assert_eq!(stake_pool.sol_deposit_fee, fee);
+        assert_eq!(pending_fees.next_sol_deposit_fee, FutureEpoch::Two(new_fee));
+    }
+
+    #[test]
+    fn validator_commission_cap_len() {
+        let commission_cap = ValidatorCommissionCap::new(Pubkey::new_unique(), 10, 3);
+        assert_eq!(
+            get_packed_len::<ValidatorCommissionCap>(),
+            ValidatorCommissionCap::LEN
+        );
+        assert_eq!(
+            borsh::to_vec(&commission_cap).unwrap().len(),
+            ValidatorCommissionCap::LEN
+        );
+    }
+
+    #[test]
+    fn validator_commission_flag() {
+        let commission_cap = ValidatorCommissionCap::new(Pubkey::new_unique(), 10, 3);
+        assert!(!commission_cap.is_exceeded_by(10, 0));
+        assert!(commission_cap.is_exceeded_by(11, 0));
+        // validators added above the cap are only flagged if they increase
+        // their commission further
+        assert!(!commission_cap.is_exceeded_by(20, 20));
+        assert!(commission_cap.is_exceeded_by(21, 20));
+
+        let mut validator = ValidatorStakeInfo::default();
+        assert!(!validator.is_commission_flagged());
+        validator.flag_commission(u16::MAX as u64);
+        assert!(validator.is_commission_flagged());
+        assert_eq!(validator.commission_flag_elapsed_epochs(u16::MAX as u64), 0);
+        // the elapsed epochs are still counted after the flag epoch wraps
+        assert_eq!(
+            validator.commission_flag_elapsed_epochs(u16::MAX as u64 + 3),
+            3
+        );
+        validator.clear_commission_flag();
+        assert!(!validator.is_commission_flagged());
+    }
+
     #[test]
     fn zero_withdraw_calculation() {
         let epoch_fee = Fee {
@@ -1462,4 +2232,32 @@ mod test {
         let withdraw_result = stake_pool.calc_lamports_withdraw_amount(1).unwrap();
         assert_eq!(stake_pool.total_lamports, withdraw_result);
     }
+
+    #[test]
+    fn exchange_rate_history_wraps_around() {
+        let mut history = ExchangeRateHistory::new(Pubkey::new_unique());
+        assert_eq!(
+            get_packed_len::<ExchangeRateHistory>(),
+            ExchangeRateHistory::LEN
+        );
+        assert!(history.latest().is_none());
+
+        let total_entries = EXCHANGE_RATE_HISTORY_LEN as u64 + 3;
+        for epoch in 0..total_entries {
+            let entry = ExchangeRateEntry {
+                epoch,
+                total_lamports: epoch * 10,
+                pool_token_supply: epoch * 5,
+            };
+            assert!(history.record(entry));
+            // only the first entry of the epoch is kept
+            assert!(!history.record(entry));
+        }
+
+        assert_eq!(history.len as usize, EXCHANGE_RATE_HISTORY_LEN);
+        assert_eq!(history.latest().unwrap().epoch, total_entries - 1);
+        let epochs: Vec<u64> = history.iter().map(|entry| entry.epoch).collect();
+        let expected: Vec<u64> = (3..total_entries).collect();
+        assert_eq!(epochs, expected);
+    }
 }
@@ -0,0 +1,206 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{borsh1::try_from_slice_unchecked, instruction::InstructionError},
+    solana_program_test::*,
+    solana_sdk::{
+        native_token::LAMPORTS_PER_SOL,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_dao_treasury_program_address, id,
+        state::{DaoTreasury, Fee, StakePool},
+        MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+const REWARD_FEE: Fee = Fee {
+    numerator: 1,
+    denominator: 10,
+};
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts, Keypair) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &stake_pool_accounts.pool_fee_account.pubkey(),
+            100 * LAMPORTS_PER_SOL,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let treasury_pool_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &treasury_pool_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &Keypair::new(),
+        &[],
+    )
+    .await
+    .unwrap();
+
+    (context, stake_pool_accounts, treasury_pool_account)
+}
+
+async fn get_dao_treasury(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+) -> DaoTreasury {
+    let (address, _) =
+        find_dao_treasury_program_address(&id(), &stake_pool_accounts.stake_pool.pubkey());
+    let account = get_account(&mut context.banks_client, &address).await;
+    try_from_slice_unchecked::<DaoTreasury>(account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn success_set() {
+    let (mut context, stake_pool_accounts, treasury_pool_account) = setup().await;
+
+    let error = stake_pool_accounts
+        .set_dao_treasury(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &stake_pool_accounts.manager,
+            &treasury_pool_account.pubkey(),
+            REWARD_FEE,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let dao_treasury = get_dao_treasury(&mut context, &stake_pool_accounts).await;
+    assert_eq!(
+        dao_treasury,
+        DaoTreasury::new(
+            stake_pool_accounts.stake_pool.pubkey(),
+            treasury_pool_account.pubkey(),
+            REWARD_FEE,
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_set_wrong_manager() {
+    let (mut context, stake_pool_accounts, treasury_pool_account) = setup().await;
+
+    let error = stake_pool_accounts
+        .set_dao_treasury(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &Keypair::new(),
+            &treasury_pool_account.pubkey(),
+            REWARD_FEE,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongManager as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_skim_rewards() {
+    let (mut context, stake_pool_accounts, treasury_pool_account) = setup().await;
+
+    let error = stake_pool_accounts
+        .set_dao_treasury(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &stake_pool_accounts.manager,
+            &treasury_pool_account.pubkey(),
+            REWARD_FEE,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // rewards land in the reserve
+    let reward_lamports = 10 * LAMPORTS_PER_SOL;
+    transfer(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        reward_lamports,
+    )
+    .await;
+
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .update_validator_list_balance(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            0,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let error = stake_pool_accounts
+        .update_stake_pool_balance_with_dao_treasury(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            &treasury_pool_account.pubkey(),
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let treasury_pool_tokens =
+        get_token_balance(&mut context.banks_client, &treasury_pool_account.pubkey()).await;
+    assert!(treasury_pool_tokens > 0);
+
+    let dao_treasury = get_dao_treasury(&mut context, &stake_pool_accounts).await;
+    assert_eq!(dao_treasury.total_pool_tokens, treasury_pool_tokens);
+
+    // the treasury share is worth at most 10% of the rewards
+    let stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
+    assert_eq!(dao_treasury.last_skim_epoch, stake_pool.last_update_epoch);
+    let treasury_lamports = stake_pool
+        .calc_lamports_withdraw_amount(treasury_pool_tokens)
+        .unwrap();
+    assert!(treasury_lamports <= reward_lamports / 10);
+    assert!(reward_lamports / 10 - treasury_lamports <= 2);
+}
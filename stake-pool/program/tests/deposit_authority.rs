@@ -10,9 +10,15 @@ use {
     solana_sdk::{
         borsh1::try_from_slice_unchecked,
         signature::{Keypair, Signer},
-        transaction::TransactionError,
+        transaction::{Transaction, TransactionError},
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        id,
+        instruction::{self, FundingType},
+        state::StakePool,
+        MINIMUM_RESERVE_LAMPORTS,
     },
-    spl_stake_pool::{error::StakePoolError, state::StakePool, MINIMUM_RESERVE_LAMPORTS},
 };
 
 #[tokio::test]
@@ -220,3 +226,71 @@ async fn fail_deposit_without_authority_signature() {
         _ => panic!("Wrong error occurs while try to make a deposit with wrong stake program ID"),
     }
 }
+
+#[tokio::test]
+async fn success_deposit_sol_with_stake_deposit_authority_only() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let stake_deposit_authority = Keypair::new();
+    let stake_pool_accounts =
+        StakePoolAccounts::new_with_deposit_authority(stake_deposit_authority);
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    // Keep stake deposits restricted while opening SOL deposits to everyone
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::set_funding_authority(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            None,
+            FundingType::SolDeposit,
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &stake_pool_accounts.manager], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let stake_pool_account =
+        get_account(&mut banks_client, &stake_pool_accounts.stake_pool.pubkey()).await;
+    let stake_pool =
+        try_from_slice_unchecked::<StakePool>(stake_pool_account.data.as_slice()).unwrap();
+    assert_eq!(
+        stake_pool.stake_deposit_authority,
+        stake_pool_accounts.stake_deposit_authority
+    );
+    assert_eq!(stake_pool.sol_deposit_authority, None);
+
+    let user = Keypair::new();
+    let user_pool_account = Keypair::new();
+    create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &user_pool_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &user_pool_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+}
@@ -0,0 +1,176 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{borsh1::try_from_slice_unchecked, instruction::InstructionError},
+    solana_program_test::*,
+    solana_sdk::{signature::Signer, transaction::TransactionError},
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_exchange_rate_history_program_address, id,
+        state::{ExchangeRateEntry, ExchangeRateHistory, StakePool},
+        MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+    (context, stake_pool_accounts)
+}
+
+async fn get_exchange_rate_history(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+) -> ExchangeRateHistory {
+    let (address, _) =
+        find_exchange_rate_history_program_address(&id(), &stake_pool_accounts.stake_pool.pubkey());
+    let account = get_account(&mut context.banks_client, &address).await;
+    try_from_slice_unchecked::<ExchangeRateHistory>(account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn success_create() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let error = stake_pool_accounts
+        .create_exchange_rate_history(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let exchange_rate_history = get_exchange_rate_history(&mut context, &stake_pool_accounts).await;
+    assert_eq!(
+        exchange_rate_history,
+        ExchangeRateHistory::new(stake_pool_accounts.stake_pool.pubkey())
+    );
+}
+
+#[tokio::test]
+async fn fail_create_twice() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let error = stake_pool_accounts
+        .create_exchange_rate_history(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .create_exchange_rate_history(&mut context.banks_client, &context.payer, &last_blockhash)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::AlreadyInUse as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_record_once_per_epoch() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let error = stake_pool_accounts
+        .create_exchange_rate_history(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+
+    let stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
+    let first_entry = ExchangeRateEntry {
+        epoch: stake_pool.last_update_epoch,
+        total_lamports: stake_pool.total_lamports,
+        pool_token_supply: stake_pool.pool_token_supply,
+    };
+    let exchange_rate_history = get_exchange_rate_history(&mut context, &stake_pool_accounts).await;
+    assert_eq!(
+        exchange_rate_history.iter().collect::<Vec<_>>(),
+        vec![&first_entry]
+    );
+
+    // another update in the same epoch doesn't record a new entry
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .update_stake_pool_balance(&mut context.banks_client, &context.payer, &last_blockhash)
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let exchange_rate_history = get_exchange_rate_history(&mut context, &stake_pool_accounts).await;
+    assert_eq!(exchange_rate_history.len, 1);
+
+    // next epoch records a new entry
+    let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
+    context.warp_to_slot(slot + slots_per_epoch).unwrap();
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&last_blockhash)
+        .await
+        .unwrap();
+    stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+    let exchange_rate_history = get_exchange_rate_history(&mut context, &stake_pool_accounts).await;
+    let entries = exchange_rate_history.iter().collect::<Vec<_>>();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0], &first_entry);
+    assert_eq!(entries[1].epoch, first_entry.epoch + 1);
+}
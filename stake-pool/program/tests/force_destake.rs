@@ -67,7 +67,9 @@ async fn setup(
         transient_stake_lamports: 0.into(),
         last_update_epoch: 0.into(),
         transient_seed_suffix: 0.into(),
-        unused: 0.into(),
+        commission: 0,
+        commission_flagged: false.into(),
+        commission_flag_epoch: 0.into(),
         validator_seed_suffix: raw_validator_seed.into(),
     });
 
@@ -49,6 +49,7 @@ pub const DEFAULT_VALIDATOR_STAKE_SEED: Option<NonZeroU32> = NonZeroU32::new(1_0
 pub const DEFAULT_TRANSIENT_STAKE_SEED: u64 = 42;
 pub const STAKE_ACCOUNT_RENT_EXEMPTION: u64 = 2_282_880;
 const ACCOUNT_RENT_EXEMPTION: u64 = 1_000_000_000; // go with something big to be safe
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
 
 pub fn program_test() -> ProgramTest {
     let mut program_test = ProgramTest::new("spl_stake_pool", id(), processor!(Processor::process));
@@ -74,6 +75,16 @@ pub fn program_test_with_metadata_program() -> ProgramTest {
     program_test
 }
 
+pub fn program_test_with_transfer_hook_program() -> ProgramTest {
+    let mut program_test = program_test();
+    program_test.add_program(
+        "spl_transfer_hook_example",
+        TRANSFER_HOOK_PROGRAM_ID,
+        processor!(spl_transfer_hook_example::processor::process),
+    );
+    program_test
+}
+
 pub async fn get_account(banks_client: &mut BanksClient, pubkey: &Pubkey) -> SolanaAccount {
     banks_client
         .get_account(*pubkey)
@@ -136,6 +147,12 @@ pub async fn create_mint(
             ),
             ExtensionType::NonTransferable =>
                 spl_token_2022::instruction::initialize_non_transferable_mint(program_id, &mint_pubkey),
+            ExtensionType::TransferHook => spl_token_2022::extension::transfer_hook::instruction::initialize(
+                program_id,
+                &mint_pubkey,
+                Some(*manager),
+                Some(TRANSFER_HOOK_PROGRAM_ID),
+            ),
             _ => unimplemented!(),
         };
         instructions.push(instruction.unwrap());
@@ -249,7 +266,8 @@ pub async fn create_token_account(
             ExtensionType::TransferFeeAmount
             | ExtensionType::MemoTransfer
             | ExtensionType::CpiGuard
-            | ExtensionType::NonTransferableAccount => (),
+            | ExtensionType::NonTransferableAccount
+            | ExtensionType::TransferHookAccount => (),
             _ => unimplemented!(),
         };
     }
@@ -293,7 +311,8 @@ pub async fn create_token_account(
             }
             ExtensionType::ImmutableOwner
             | ExtensionType::TransferFeeAmount
-            | ExtensionType::NonTransferableAccount => (),
+            | ExtensionType::NonTransferableAccount
+            | ExtensionType::TransferHookAccount => (),
             _ => unimplemented!(),
         }
     }
@@ -865,6 +884,7 @@ pub struct StakePoolAccounts {
     pub pool_mint: Keypair,
     pub pool_fee_account: Keypair,
     pub pool_decimals: u8,
+    pub pool_mint_extensions: Vec<ExtensionType>,
     pub manager: Keypair,
     pub staker: Keypair,
     pub withdraw_authority: Pubkey,
@@ -939,7 +959,7 @@ impl StakePoolAccounts {
             &self.pool_mint,
             &self.withdraw_authority,
             self.pool_decimals,
-            &[],
+            &self.pool_mint_extensions,
         )
         .await?;
         create_token_account(
@@ -950,7 +970,7 @@ impl StakePoolAccounts {
             &self.pool_fee_account,
             &self.pool_mint.pubkey(),
             &self.manager,
-            &[],
+            &ExtensionType::get_required_init_account_extensions(&self.pool_mint_extensions),
         )
         .await?;
         create_independent_stake_account(
@@ -1475,6 +1495,177 @@ impl StakePoolAccounts {
             .err()
     }
 
+    pub async fn update_stake_pool_balance_with_dao_treasury(
+        &self,
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: &Hash,
+        dao_treasury_pool_account: &Pubkey,
+    ) -> Option<TransportError> {
+        let mut instructions = vec![instruction::update_stake_pool_balance_with_dao_treasury(
+            &id(),
+            &self.stake_pool.pubkey(),
+            &self.withdraw_authority,
+            &self.validator_list.pubkey(),
+            &self.reserve_stake.pubkey(),
+            &self.pool_fee_account.pubkey(),
+            &self.pool_mint.pubkey(),
+            &self.token_program_id,
+            dao_treasury_pool_account,
+        )];
+        self.maybe_add_compute_budget_instruction(&mut instructions);
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            *recent_blockhash,
+        );
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.into())
+            .err()
+    }
+
+    pub async fn set_dao_treasury(
+        &self,
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: &Hash,
+        manager: &Keypair,
+        treasury_pool_account: &Pubkey,
+        reward_fee: state::Fee,
+    ) -> Option<TransportError> {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction::set_dao_treasury(
+                &id(),
+                &self.stake_pool.pubkey(),
+                &manager.pubkey(),
+                treasury_pool_account,
+                &payer.pubkey(),
+                reward_fee,
+            )],
+            Some(&payer.pubkey()),
+            &[payer, manager],
+            *recent_blockhash,
+        );
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.into())
+            .err()
+    }
+
+    pub async fn create_exchange_rate_history(
+        &self,
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: &Hash,
+    ) -> Option<TransportError> {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction::create_exchange_rate_history(
+                &id(),
+                &self.stake_pool.pubkey(),
+                &payer.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            *recent_blockhash,
+        );
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.into())
+            .err()
+    }
+
+    pub async fn recover_transient_stake(
+        &self,
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: &Hash,
+        vote_account_address: &Pubkey,
+        validator_stake_seed: Option<NonZeroU32>,
+        transient_stake_seed: u64,
+    ) -> Option<TransportError> {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction::recover_transient_stake(
+                &id(),
+                &self.stake_pool.pubkey(),
+                &self.validator_list.pubkey(),
+                &self.reserve_stake.pubkey(),
+                vote_account_address,
+                validator_stake_seed,
+                transient_stake_seed,
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            *recent_blockhash,
+        );
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.into())
+            .err()
+    }
+
+    pub async fn set_validator_commission_cap(
+        &self,
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: &Hash,
+        max_commission: u8,
+        grace_epochs: u16,
+    ) -> Option<TransportError> {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction::set_validator_commission_cap(
+                &id(),
+                &self.stake_pool.pubkey(),
+                &self.manager.pubkey(),
+                &payer.pubkey(),
+                max_commission,
+                grace_epochs,
+            )],
+            Some(&payer.pubkey()),
+            &[payer, &self.manager],
+            *recent_blockhash,
+        );
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.into())
+            .err()
+    }
+
+    pub async fn flag_validator_commission(
+        &self,
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: &Hash,
+        vote_account_address: &Pubkey,
+        validator_stake_seed: Option<NonZeroU32>,
+        transient_stake_seed: u64,
+    ) -> Option<TransportError> {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction::flag_validator_commission(
+                &id(),
+                &self.stake_pool.pubkey(),
+                &self.validator_list.pubkey(),
+                vote_account_address,
+                validator_stake_seed,
+                transient_stake_seed,
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            *recent_blockhash,
+        );
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.into())
+            .err()
+    }
+
     pub async fn cleanup_removed_validator_entries(
         &self,
         banks_client: &mut BanksClient,
@@ -1530,7 +1721,7 @@ impl StakePoolAccounts {
             );
         }
         instructions.extend([
-            instruction::update_stake_pool_balance(
+            instruction::update_stake_pool_balance_with_pending_fees(
                 &id(),
                 &self.stake_pool.pubkey(),
                 &self.withdraw_authority,
@@ -1539,6 +1730,7 @@ impl StakePoolAccounts {
                 &self.pool_fee_account.pubkey(),
                 &self.pool_mint.pubkey(),
                 &self.token_program_id,
+                None,
             ),
             instruction::cleanup_removed_validator_entries(
                 &id(),
@@ -2044,6 +2236,7 @@ impl Default for StakePoolAccounts {
             pool_mint,
             pool_fee_account,
             pool_decimals: native_mint::DECIMALS,
+            pool_mint_extensions: vec![],
             manager,
             staker,
             withdraw_authority,
@@ -2100,7 +2293,9 @@ pub async fn simple_add_validator_to_pool(
         &pool_token_account,
         &stake_pool_accounts.pool_mint.pubkey(),
         payer,
-        &[],
+        &ExtensionType::get_required_init_account_extensions(
+            &stake_pool_accounts.pool_mint_extensions,
+        ),
     )
     .await
     .unwrap();
@@ -2286,7 +2481,9 @@ pub async fn simple_deposit_stake(
         &pool_account,
         &stake_pool_accounts.pool_mint.pubkey(),
         &authority,
-        &[],
+        &ExtensionType::get_required_init_account_extensions(
+            &stake_pool_accounts.pool_mint_extensions,
+        ),
     )
     .await
     .unwrap();
@@ -2451,7 +2648,9 @@ pub fn add_validator_stake_account(
         transient_stake_lamports: 0.into(),
         last_update_epoch: FIRST_NORMAL_EPOCH.into(),
         transient_seed_suffix: 0.into(),
-        unused: 0.into(),
+        commission: 0,
+        commission_flagged: false.into(),
+        commission_flag_epoch: 0.into(),
         validator_seed_suffix: raw_suffix.into(),
     });
 
@@ -2594,8 +2793,28 @@ pub async fn setup_for_withdraw(
     Keypair,
     u64,
 ) {
-    let mut context = program_test().start_with_context().await;
-    let stake_pool_accounts = StakePoolAccounts::new_with_token_program(token_program_id);
+    setup_for_withdraw_with_accounts(
+        program_test(),
+        StakePoolAccounts::new_with_token_program(token_program_id),
+        reserve_lamports,
+    )
+    .await
+}
+
+pub async fn setup_for_withdraw_with_accounts(
+    program_test: ProgramTest,
+    stake_pool_accounts: StakePoolAccounts,
+    reserve_lamports: u64,
+) -> (
+    ProgramTestContext,
+    StakePoolAccounts,
+    ValidatorStakeAccount,
+    DepositStakeAccount,
+    Keypair,
+    Keypair,
+    u64,
+) {
+    let mut context = program_test.start_with_context().await;
     stake_pool_accounts
         .initialize_stake_pool(
             &mut context.banks_client,
@@ -513,7 +513,10 @@ async fn success_with_supported_extensions() {
     let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
     let stake_pool_accounts = StakePoolAccounts::new_with_token_program(spl_token_2022::id());
 
-    let mint_extensions = vec![ExtensionType::TransferFeeConfig];
+    let mint_extensions = vec![
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::TransferHook,
+    ];
     create_required_accounts(
         &mut banks_client,
         &payer,
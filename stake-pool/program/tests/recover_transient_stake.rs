@@ -0,0 +1,335 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked,
+        clock::Clock,
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        stake::{
+            self,
+            stake_flags::StakeFlags,
+            state::{Authorized, Delegation, Lockup, Meta, Stake, StakeStateV2},
+        },
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        account::{Account, WritableAccount},
+        clock::Epoch,
+        signature::Signer,
+        transaction::TransactionError,
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_stake_program_address, find_transient_stake_program_address, id,
+        state::{StakeStatus, ValidatorStakeInfo},
+    },
+    std::num::NonZeroU32,
+};
+
+const TRANSIENT_STAKE_SEED: u64 = 0;
+
+fn stake_account(state: &StakeStateV2) -> Account {
+    let mut data = vec![0; std::mem::size_of::<StakeStateV2>()];
+    bincode::serialize_into(&mut data[..], state).unwrap();
+    Account::create(
+        TEST_STAKE_AMOUNT + STAKE_ACCOUNT_RENT_EXEMPTION,
+        data,
+        stake::program::id(),
+        false,
+        Epoch::default(),
+    )
+}
+
+fn pool_meta(stake_pool_accounts: &StakePoolAccounts) -> Meta {
+    Meta {
+        rent_exempt_reserve: STAKE_ACCOUNT_RENT_EXEMPTION,
+        authorized: Authorized {
+            staker: stake_pool_accounts.withdraw_authority,
+            withdrawer: stake_pool_accounts.withdraw_authority,
+        },
+        lockup: Lockup::default(),
+    }
+}
+
+fn active_stake(voter_pubkey: &Pubkey) -> Stake {
+    Stake {
+        delegation: Delegation {
+            voter_pubkey: *voter_pubkey,
+            stake: TEST_STAKE_AMOUNT,
+            activation_epoch: 0,
+            deactivation_epoch: Epoch::MAX,
+            ..Delegation::default()
+        },
+        credits_observed: 1,
+    }
+}
+
+async fn setup(
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake: &StakeStateV2,
+    voter_pubkey: &Pubkey,
+) -> (ProgramTestContext, Option<NonZeroU32>) {
+    let mut program_test = program_test();
+
+    let stake_pool_pubkey = stake_pool_accounts.stake_pool.pubkey();
+    let (mut stake_pool, mut validator_list) = stake_pool_accounts.state();
+
+    let _ = add_vote_account_with_pubkey(voter_pubkey, &mut program_test);
+
+    let raw_validator_seed = 42;
+    let validator_seed = NonZeroU32::new(raw_validator_seed);
+    let (stake_address, _) =
+        find_stake_program_address(&id(), voter_pubkey, &stake_pool_pubkey, validator_seed);
+    program_test.add_account(stake_address, stake_account(validator_stake));
+
+    // active transient stake, which can only be merged into an active
+    // validator stake account
+    let (transient_stake_address, _) = find_transient_stake_program_address(
+        &id(),
+        voter_pubkey,
+        &stake_pool_pubkey,
+        TRANSIENT_STAKE_SEED,
+    );
+    program_test.add_account(
+        transient_stake_address,
+        stake_account(&StakeStateV2::Stake(
+            pool_meta(stake_pool_accounts),
+            active_stake(voter_pubkey),
+            StakeFlags::empty(),
+        )),
+    );
+
+    let stake_lamports = TEST_STAKE_AMOUNT + STAKE_ACCOUNT_RENT_EXEMPTION;
+    validator_list.validators.push(ValidatorStakeInfo {
+        status: StakeStatus::Active.into(),
+        vote_account_address: *voter_pubkey,
+        active_stake_lamports: stake_lamports.into(),
+        transient_stake_lamports: stake_lamports.into(),
+        last_update_epoch: 0.into(),
+        transient_seed_suffix: TRANSIENT_STAKE_SEED.into(),
+        commission: 0,
+        commission_flagged: false.into(),
+        commission_flag_epoch: 0.into(),
+        validator_seed_suffix: raw_validator_seed.into(),
+    });
+
+    stake_pool.total_lamports += 2 * stake_lamports;
+    stake_pool.pool_token_supply += 2 * stake_lamports;
+
+    add_reserve_stake_account(
+        &mut program_test,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        TEST_STAKE_AMOUNT,
+    );
+    add_stake_pool_account(
+        &mut program_test,
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool,
+    );
+    add_validator_list_account(
+        &mut program_test,
+        &stake_pool_accounts.validator_list.pubkey(),
+        &validator_list,
+        stake_pool_accounts.max_validators,
+    );
+
+    add_mint_account(
+        &mut program_test,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        stake_pool.pool_token_supply,
+    );
+    add_token_account(
+        &mut program_test,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.manager.pubkey(),
+    );
+
+    let mut context = program_test.start_with_context().await;
+
+    // move forward to the next epoch, so the pool needs an update
+    let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
+    context.warp_to_slot(first_normal_slot + 1).unwrap();
+
+    (context, validator_seed)
+}
+
+#[tokio::test]
+async fn success_with_destaked_validator() {
+    let stake_pool_accounts = StakePoolAccounts::default();
+    let voter_pubkey = Pubkey::new_unique();
+    let (mut context, validator_seed) = setup(
+        &stake_pool_accounts,
+        &StakeStateV2::Initialized(pool_meta(&stake_pool_accounts)),
+        &voter_pubkey,
+    )
+    .await;
+    let transient_stake_address = find_transient_stake_program_address(
+        &id(),
+        &voter_pubkey,
+        &stake_pool_accounts.stake_pool.pubkey(),
+        TRANSIENT_STAKE_SEED,
+    )
+    .0;
+
+    let error = stake_pool_accounts
+        .recover_transient_stake(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &voter_pubkey,
+            validator_seed,
+            TRANSIENT_STAKE_SEED,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // transient stake is deactivated
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let transient_stake_account =
+        get_account(&mut context.banks_client, &transient_stake_address).await;
+    let transient_stake_state =
+        try_from_slice_unchecked::<StakeStateV2>(&transient_stake_account.data).unwrap();
+    assert_eq!(
+        transient_stake_state
+            .delegation()
+            .unwrap()
+            .deactivation_epoch,
+        clock.epoch
+    );
+
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // move forward to after deactivation, the next update merges the
+    // transient stake into the reserve
+    let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
+    context.warp_to_slot(clock.slot + slots_per_epoch).unwrap();
+    let pre_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let post_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(
+        post_reserve_lamports,
+        pre_reserve_lamports + transient_stake_account.lamports
+    );
+    assert!(context
+        .banks_client
+        .get_account(transient_stake_address)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn fail_not_stuck() {
+    let stake_pool_accounts = StakePoolAccounts::default();
+    let voter_pubkey = Pubkey::new_unique();
+    let (mut context, validator_seed) = setup(
+        &stake_pool_accounts,
+        &StakeStateV2::Stake(
+            pool_meta(&stake_pool_accounts),
+            active_stake(&voter_pubkey),
+            StakeFlags::empty(),
+        ),
+        &voter_pubkey,
+    )
+    .await;
+
+    let error = stake_pool_accounts
+        .recover_transient_stake(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &voter_pubkey,
+            validator_seed,
+            TRANSIENT_STAKE_SEED,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::TransientStakeNotStuck as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_after_update() {
+    let stake_pool_accounts = StakePoolAccounts::default();
+    let voter_pubkey = Pubkey::new_unique();
+    let (mut context, validator_seed) = setup(
+        &stake_pool_accounts,
+        &StakeStateV2::Initialized(pool_meta(&stake_pool_accounts)),
+        &voter_pubkey,
+    )
+    .await;
+
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = stake_pool_accounts
+        .recover_transient_stake(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &voter_pubkey,
+            validator_seed,
+            TRANSIENT_STAKE_SEED,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::StakePoolAlreadyUpdated as u32)
+        )
+    );
+}
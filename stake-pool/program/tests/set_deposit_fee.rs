@@ -13,8 +13,8 @@ use {
         transaction::{Transaction, TransactionError},
     },
     spl_stake_pool::{
-        error, id, instruction,
-        state::{Fee, FeeType, StakePool},
+        error, find_pending_fees_program_address, id, instruction,
+        state::{Fee, FeeType, FutureEpoch, PendingFees, StakePool},
         MINIMUM_RESERVE_LAMPORTS,
     },
 };
@@ -277,3 +277,283 @@ async fn fail_sol_high_deposit_fee() {
         _ => panic!("Wrong error occurs when setting fee too high"),
     }
 }
+
+async fn setup_with_deposits(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+) -> u64 {
+    let user = Keypair::new();
+    let pool_token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &pool_token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // go through an epoch with pool tokens outstanding, restricting increases
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            false,
+        )
+        .await;
+    slot
+}
+
+async fn get_pending_fees(
+    banks_client: &mut BanksClient,
+    stake_pool_accounts: &StakePoolAccounts,
+) -> PendingFees {
+    let (pending_fees_address, _) =
+        find_pending_fees_program_address(&id(), &stake_pool_accounts.stake_pool.pubkey());
+    let pending_fees = get_account(banks_client, &pending_fees_address).await;
+    try_from_slice_unchecked::<PendingFees>(pending_fees.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn success_sol_increase_with_deposits() {
+    let (mut context, stake_pool_accounts, _) = setup(None).await;
+    let slot = setup_with_deposits(&mut context, &stake_pool_accounts).await;
+
+    let stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
+    let old_deposit_fee = stake_pool.sol_deposit_fee;
+    let new_deposit_fee = Fee {
+        numerator: old_deposit_fee.numerator * 5,
+        denominator: old_deposit_fee.denominator * 4,
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_fee_with_pending_fees(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            &context.payer.pubkey(),
+            FeeType::SolDeposit(new_deposit_fee),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
+    assert_eq!(stake_pool.sol_deposit_fee, old_deposit_fee);
+    let pending_fees = get_pending_fees(&mut context.banks_client, &stake_pool_accounts).await;
+    assert_eq!(
+        pending_fees.next_sol_deposit_fee,
+        FutureEpoch::Two(new_deposit_fee)
+    );
+
+    let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    context.warp_to_slot(slot + slots_per_epoch).unwrap();
+    stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+
+    let stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
+    assert_eq!(stake_pool.sol_deposit_fee, old_deposit_fee);
+    let pending_fees = get_pending_fees(&mut context.banks_client, &stake_pool_accounts).await;
+    assert_eq!(
+        pending_fees.next_sol_deposit_fee,
+        FutureEpoch::One(new_deposit_fee)
+    );
+
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    context.warp_to_slot(slot + 2 * slots_per_epoch).unwrap();
+    stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+
+    let stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
+    assert_eq!(stake_pool.sol_deposit_fee, new_deposit_fee);
+    let pending_fees = get_pending_fees(&mut context.banks_client, &stake_pool_accounts).await;
+    assert_eq!(pending_fees.next_sol_deposit_fee, FutureEpoch::None);
+}
+
+#[tokio::test]
+async fn success_stake_decrease_with_deposits() {
+    let (mut context, stake_pool_accounts, _) = setup(None).await;
+    setup_with_deposits(&mut context, &stake_pool_accounts).await;
+
+    // decreases take effect immediately
+    let new_deposit_fee = Fee {
+        numerator: 0,
+        denominator: 0,
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_fee(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            FeeType::StakeDeposit(new_deposit_fee),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
+    assert_eq!(stake_pool.stake_deposit_fee, new_deposit_fee);
+    let (pending_fees_address, _) =
+        find_pending_fees_program_address(&id(), &stake_pool_accounts.stake_pool.pubkey());
+    assert!(context
+        .banks_client
+        .get_account(pending_fees_address)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn fail_stake_increase_without_pending_fees() {
+    let (mut context, stake_pool_accounts, _) = setup(None).await;
+    setup_with_deposits(&mut context, &stake_pool_accounts).await;
+
+    let stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
+    let old_deposit_fee = stake_pool.stake_deposit_fee;
+    let new_deposit_fee = Fee {
+        numerator: old_deposit_fee.numerator * 5,
+        denominator: old_deposit_fee.denominator * 4,
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_fee(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            FeeType::StakeDeposit(new_deposit_fee),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::StakePoolError::InvalidPendingFees as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_stake_increase_too_high_with_deposits() {
+    let (mut context, stake_pool_accounts, new_deposit_fee) = setup(None).await;
+    setup_with_deposits(&mut context, &stake_pool_accounts).await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_fee(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            FeeType::StakeDeposit(new_deposit_fee),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .unwrap();
+
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(error_index)) => {
+            let program_error = error::StakePoolError::FeeIncreaseTooHigh as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs when increasing fee too much"),
+    }
+}
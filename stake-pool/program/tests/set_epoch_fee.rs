@@ -32,8 +32,8 @@ async fn setup() -> (ProgramTestContext, StakePoolAccounts, Fee) {
         .await
         .unwrap();
     let new_fee = Fee {
-        numerator: 10,
-        denominator: 10,
+        numerator: 5,
+        denominator: 400,
     };
 
     (context, stake_pool_accounts, new_fee)
@@ -194,6 +194,42 @@ async fn fail_high_fee() {
     }
 }
 
+#[tokio::test]
+async fn fail_increase_too_high() {
+    let (context, stake_pool_accounts, _new_fee) = setup().await;
+
+    let new_fee = Fee {
+        numerator: 10,
+        denominator: 10,
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_fee(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            FeeType::Epoch(new_fee),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .unwrap();
+
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(error_index)) => {
+            let program_error = error::StakePoolError::FeeIncreaseTooHigh as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs when increasing fee too much"),
+    }
+}
+
 #[tokio::test]
 async fn fail_not_updated() {
     let mut context = program_test().start_with_context().await;
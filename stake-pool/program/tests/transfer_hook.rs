@@ -0,0 +1,271 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{instruction::Instruction, pubkey::Pubkey},
+    solana_program_test::*,
+    solana_sdk::{
+        account::Account,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+    spl_stake_pool::{id, instruction, MINIMUM_RESERVE_LAMPORTS},
+    spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed},
+    spl_token_2022::extension::ExtensionType,
+    spl_transfer_hook_interface::{
+        get_extra_account_metas_address, offchain::add_extra_account_metas_for_execute,
+    },
+};
+
+fn setup_program_test(stake_pool_accounts: &StakePoolAccounts) -> ProgramTest {
+    let mut program_test = program_test_with_transfer_hook_program();
+    let extra_account_metas = vec![
+        ExtraAccountMeta::new_with_pubkey(&Pubkey::new_unique(), false, false).unwrap(),
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::AccountKey { index: 0 }, // source
+                Seed::AccountKey { index: 2 }, // destination
+            ],
+            false,
+            false,
+        )
+        .unwrap(),
+    ];
+    program_test.add_account(
+        get_extra_account_metas_address(
+            &stake_pool_accounts.pool_mint.pubkey(),
+            &TRANSFER_HOOK_PROGRAM_ID,
+        ),
+        Account {
+            lamports: 1_000_000_000,
+            data: spl_transfer_hook_example::state::example_data(&extra_account_metas).unwrap(),
+            owner: TRANSFER_HOOK_PROGRAM_ID,
+            ..Account::default()
+        },
+    );
+    program_test
+}
+
+fn stake_pool_accounts_with_transfer_hook() -> StakePoolAccounts {
+    StakePoolAccounts {
+        token_program_id: spl_token_2022::id(),
+        pool_mint_extensions: vec![ExtensionType::TransferHook],
+        ..Default::default()
+    }
+}
+
+async fn add_transfer_hook_accounts(
+    banks_client: &mut BanksClient,
+    stake_pool_accounts: &StakePoolAccounts,
+    instruction: &mut Instruction,
+    source: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) {
+    add_extra_account_metas_for_execute(
+        instruction,
+        &TRANSFER_HOOK_PROGRAM_ID,
+        source,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        authority,
+        amount,
+        |address| {
+            let banks_client = banks_client.clone();
+            async move {
+                banks_client
+                    .get_account(address)
+                    .await
+                    .map(|account| account.map(|account| account.data))
+                    .map_err(Into::into)
+            }
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn success_withdraw_sol() {
+    let stake_pool_accounts = stake_pool_accounts_with_transfer_hook();
+    let mut context = setup_program_test(&stake_pool_accounts)
+        .start_with_context()
+        .await;
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    // deposits only mint, so the transfer hook isn't involved
+    let user = Keypair::new();
+    let pool_token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &pool_token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[ExtensionType::TransferHookAccount],
+    )
+    .await
+    .unwrap();
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let pool_tokens =
+        get_token_balance(&mut context.banks_client, &pool_token_account.pubkey()).await;
+
+    // the withdrawal fee transfer fails without the transfer hook accounts
+    let mut instruction = instruction::withdraw_sol(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &user.pubkey(),
+        &pool_token_account.pubkey(),
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &user.pubkey(),
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        pool_tokens,
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction.clone()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &user],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err();
+
+    let pre_fee_tokens = get_token_balance(
+        &mut context.banks_client,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+    )
+    .await;
+    add_transfer_hook_accounts(
+        &mut context.banks_client,
+        &stake_pool_accounts,
+        &mut instruction,
+        &pool_token_account.pubkey(),
+        &user.pubkey(),
+        pool_tokens,
+    )
+    .await;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &user],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let fee_tokens = get_token_balance(
+        &mut context.banks_client,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+    )
+    .await;
+    assert_eq!(
+        fee_tokens - pre_fee_tokens,
+        stake_pool_accounts.calculate_withdrawal_fee(pool_tokens)
+    );
+    let user_tokens =
+        get_token_balance(&mut context.banks_client, &pool_token_account.pubkey()).await;
+    assert_eq!(user_tokens, 0);
+}
+
+#[tokio::test]
+async fn success_withdraw_stake() {
+    let stake_pool_accounts = stake_pool_accounts_with_transfer_hook();
+    let program_test = setup_program_test(&stake_pool_accounts);
+    let (
+        mut context,
+        stake_pool_accounts,
+        validator_stake_account,
+        deposit_info,
+        user_transfer_authority,
+        user_stake_recipient,
+        tokens_to_withdraw,
+    ) = setup_for_withdraw_with_accounts(
+        program_test,
+        stake_pool_accounts,
+        MINIMUM_RESERVE_LAMPORTS,
+    )
+    .await;
+
+    let mut instruction = instruction::withdraw_stake(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.validator_list.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &validator_stake_account.stake_account,
+        &user_stake_recipient.pubkey(),
+        &Keypair::new().pubkey(),
+        &user_transfer_authority.pubkey(),
+        &deposit_info.pool_account.pubkey(),
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        tokens_to_withdraw,
+    );
+    let pre_fee_tokens = get_token_balance(
+        &mut context.banks_client,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+    )
+    .await;
+    add_transfer_hook_accounts(
+        &mut context.banks_client,
+        &stake_pool_accounts,
+        &mut instruction,
+        &deposit_info.pool_account.pubkey(),
+        &user_transfer_authority.pubkey(),
+        tokens_to_withdraw,
+    )
+    .await;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &user_transfer_authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let fee_tokens = get_token_balance(
+        &mut context.banks_client,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+    )
+    .await;
+    assert_eq!(
+        fee_tokens - pre_fee_tokens,
+        stake_pool_accounts.calculate_withdrawal_fee(tokens_to_withdraw)
+    );
+}
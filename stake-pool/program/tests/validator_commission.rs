@@ -0,0 +1,322 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{borsh1::try_from_slice_unchecked, instruction::InstructionError},
+    solana_program_test::*,
+    solana_sdk::{
+        account::AccountSharedData, pubkey::Pubkey, signature::Signer,
+        transaction::TransactionError,
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_validator_commission_cap_program_address, id,
+        state::{StakeStatus, ValidatorCommissionCap, ValidatorStakeInfo},
+        MINIMUM_RESERVE_LAMPORTS,
+    },
+    std::num::NonZeroU32,
+};
+
+const MAX_COMMISSION: u8 = 10;
+const GRACE_EPOCHS: u16 = 2;
+
+/// Offset of the commission in the data of a current vote account
+const VOTE_COMMISSION_OFFSET: usize = 4 + 32 + 32;
+
+async fn setup(
+    initial_commission: u8,
+) -> (ProgramTestContext, StakePoolAccounts, ValidatorStakeAccount) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            10_000_000_000 + MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let validator_stake = ValidatorStakeAccount::new(
+        &stake_pool_accounts.stake_pool.pubkey(),
+        NonZeroU32::new(u32::MAX),
+        u64::MAX,
+    );
+    create_vote(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &validator_stake.validator,
+        &validator_stake.vote,
+    )
+    .await;
+    set_vote_commission(
+        &mut context,
+        &validator_stake.vote.pubkey(),
+        initial_commission,
+    )
+    .await;
+
+    let error = stake_pool_accounts
+        .add_validator_to_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &validator_stake.stake_account,
+            &validator_stake.vote.pubkey(),
+            validator_stake.validator_stake_seed,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    (context, stake_pool_accounts, validator_stake)
+}
+
+async fn set_vote_commission(context: &mut ProgramTestContext, vote: &Pubkey, commission: u8) {
+    let mut vote_account = get_account(&mut context.banks_client, vote).await;
+    vote_account.data[VOTE_COMMISSION_OFFSET] = commission;
+    context.set_account(vote, &AccountSharedData::from(vote_account));
+}
+
+async fn get_validator_stake_info(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake: &ValidatorStakeAccount,
+) -> ValidatorStakeInfo {
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    *validator_list.find(&validator_stake.vote.pubkey()).unwrap()
+}
+
+async fn flag_validator_commission(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake: &ValidatorStakeAccount,
+) -> Option<TransactionError> {
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let last_blockhash = context.last_blockhash;
+    stake_pool_accounts
+        .flag_validator_commission(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            &validator_stake.vote.pubkey(),
+            validator_stake.validator_stake_seed,
+            validator_stake.transient_stake_seed,
+        )
+        .await
+        .map(|e| e.unwrap())
+}
+
+fn assert_custom_error(error: Option<TransactionError>, expected: StakePoolError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(expected as u32))
+    );
+}
+
+#[tokio::test]
+async fn success_set_validator_commission_cap() {
+    let (mut context, stake_pool_accounts, _) = setup(0).await;
+
+    let error = stake_pool_accounts
+        .set_validator_commission_cap(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MAX_COMMISSION,
+            GRACE_EPOCHS,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let commission_cap_address = find_validator_commission_cap_program_address(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .0;
+    let commission_cap = get_account(&mut context.banks_client, &commission_cap_address).await;
+    let commission_cap =
+        try_from_slice_unchecked::<ValidatorCommissionCap>(&commission_cap.data).unwrap();
+    assert_eq!(
+        commission_cap,
+        ValidatorCommissionCap::new(
+            stake_pool_accounts.stake_pool.pubkey(),
+            MAX_COMMISSION,
+            GRACE_EPOCHS
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_set_validator_commission_cap_above_100() {
+    let (mut context, stake_pool_accounts, _) = setup(0).await;
+
+    let error = stake_pool_accounts
+        .set_validator_commission_cap(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            101,
+            GRACE_EPOCHS,
+        )
+        .await
+        .map(|e| e.unwrap());
+    assert_custom_error(error, StakePoolError::FeeTooHigh);
+}
+
+#[tokio::test]
+async fn success_flag_and_remove_after_grace_period() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup(0).await;
+    let error = stake_pool_accounts
+        .set_validator_commission_cap(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MAX_COMMISSION,
+            GRACE_EPOCHS,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    set_vote_commission(&mut context, &validator_stake.vote.pubkey(), 100).await;
+    let error =
+        flag_validator_commission(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(error.is_none(), "{:?}", error);
+    let validator_stake_info =
+        get_validator_stake_info(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(validator_stake_info.is_commission_flagged());
+    assert_eq!(validator_stake_info.status, StakeStatus::Active.into());
+
+    // still within the grace period
+    let error =
+        flag_validator_commission(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert_custom_error(error, StakePoolError::ValidatorCommissionGracePeriodActive);
+
+    let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
+    let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
+    context
+        .warp_to_slot(first_normal_slot + GRACE_EPOCHS as u64 * slots_per_epoch + 1)
+        .unwrap();
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let last_blockhash = context.last_blockhash;
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error =
+        flag_validator_commission(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(error.is_none(), "{:?}", error);
+    let validator_stake_info =
+        get_validator_stake_info(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert_eq!(
+        validator_stake_info.status,
+        StakeStatus::DeactivatingValidator.into()
+    );
+}
+
+#[tokio::test]
+async fn success_clear_flag() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup(0).await;
+    let error = stake_pool_accounts
+        .set_validator_commission_cap(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MAX_COMMISSION,
+            GRACE_EPOCHS,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    set_vote_commission(&mut context, &validator_stake.vote.pubkey(), 100).await;
+    let error =
+        flag_validator_commission(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    set_vote_commission(&mut context, &validator_stake.vote.pubkey(), MAX_COMMISSION).await;
+    let error =
+        flag_validator_commission(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(error.is_none(), "{:?}", error);
+    let validator_stake_info =
+        get_validator_stake_info(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(!validator_stake_info.is_commission_flagged());
+    assert_eq!(validator_stake_info.status, StakeStatus::Active.into());
+}
+
+#[tokio::test]
+async fn fail_within_cap() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup(0).await;
+    let error = stake_pool_accounts
+        .set_validator_commission_cap(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MAX_COMMISSION,
+            GRACE_EPOCHS,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    set_vote_commission(&mut context, &validator_stake.vote.pubkey(), MAX_COMMISSION).await;
+    let error =
+        flag_validator_commission(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert_custom_error(error, StakePoolError::ValidatorCommissionWithinCap);
+}
+
+#[tokio::test]
+async fn fail_commission_not_increased_since_add() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup(50).await;
+    let validator_stake_info =
+        get_validator_stake_info(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert_eq!(validator_stake_info.commission, 50);
+
+    let error = stake_pool_accounts
+        .set_validator_commission_cap(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MAX_COMMISSION,
+            GRACE_EPOCHS,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error =
+        flag_validator_commission(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert_custom_error(error, StakePoolError::ValidatorCommissionWithinCap);
+
+    set_vote_commission(&mut context, &validator_stake.vote.pubkey(), 51).await;
+    let error =
+        flag_validator_commission(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn fail_without_commission_cap() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup(0).await;
+
+    set_vote_commission(&mut context, &validator_stake.vote.pubkey(), 100).await;
+    let error =
+        flag_validator_commission(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert_custom_error(error, StakePoolError::InvalidValidatorCommissionCap);
+}
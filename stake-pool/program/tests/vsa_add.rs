@@ -115,7 +115,9 @@ async fn success() {
                 active_stake_lamports: (stake_rent + current_minimum_delegation).into(),
                 transient_stake_lamports: 0.into(),
                 transient_seed_suffix: 0.into(),
-                unused: 0.into(),
+                commission: 0,
+                commission_flagged: false.into(),
+                commission_flag_epoch: 0.into(),
                 validator_seed_suffix: validator_stake
                     .validator_stake_seed
                     .map(|s| s.get())
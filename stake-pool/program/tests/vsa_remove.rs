@@ -544,7 +544,9 @@ async fn success_with_deactivating_transient_stake() {
             active_stake_lamports: (stake_rent + current_minimum_delegation).into(),
             transient_stake_lamports: (TEST_STAKE_AMOUNT + stake_rent * 2).into(),
             transient_seed_suffix: validator_stake.transient_stake_seed.into(),
-            unused: 0.into(),
+            commission: 0,
+            commission_flagged: false.into(),
+            commission_flag_epoch: 0.into(),
             validator_seed_suffix: validator_stake
                 .validator_stake_seed
                 .map(|s| s.get())
@@ -2,7 +2,7 @@
 
 from enum import IntEnum
 from typing import List, NamedTuple, Optional
-from construct import Bytes, Container, Struct, Switch, Int8ul, Int32ul, Int64ul, Pass  # type: ignore
+from construct import Bytes, Container, Struct, Switch, Int8ul, Int16ul, Int32ul, Int64ul, Pass  # type: ignore
 
 from solders.pubkey import Pubkey
 from stake.state import Lockup, LOCKUP_LAYOUT
@@ -133,8 +133,14 @@ class ValidatorStakeInfo(NamedTuple):
     transient_seed_suffix: int
     """Transient account seed suffix."""
 
-    unused: int
-    """Unused space, initially meant to specify the range of transient stake account suffixes."""
+    commission: int
+    """Commission of the validator when it was added to the pool."""
+
+    commission_flagged: bool
+    """Whether the commission of the validator was flagged above the validator commission cap of the pool."""
+
+    commission_flag_epoch: int
+    """Epoch in which the commission was flagged, truncated to 16 bits."""
 
     validator_seed_suffix: int
     """Validator account seed suffix."""
@@ -152,7 +158,9 @@ class ValidatorStakeInfo(NamedTuple):
             transient_stake_lamports=container['transient_stake_lamports'],
             last_update_epoch=container['last_update_epoch'],
             transient_seed_suffix=container['transient_seed_suffix'],
-            unused=container['unused'],
+            commission=container['commission'],
+            commission_flagged=bool(container['commission_flagged']),
+            commission_flag_epoch=container['commission_flag_epoch'],
             validator_seed_suffix=container['validator_seed_suffix'],
             status=container['status'],
             vote_account_address=Pubkey(container['vote_account_address']),
@@ -307,7 +315,9 @@ VALIDATOR_INFO_LAYOUT = Struct(
     "transient_stake_lamports" / Int64ul,
     "last_update_epoch" / Int64ul,
     "transient_seed_suffix" / Int64ul,
-    "unused" / Int32ul,
+    "commission" / Int8ul,
+    "commission_flagged" / Int8ul,
+    "commission_flag_epoch" / Int16ul,
     "validator_seed_suffix" / Int32ul,
     "status" / Int8ul,
     "vote_account_address" / PUBLIC_KEY_LAYOUT,
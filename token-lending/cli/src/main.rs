@@ -401,6 +401,10 @@ fn main() {
                         flash_loan_fee_wad,
                         host_fee_percentage,
                     },
+                    max_oracle_age_slots: 0,
+                    max_oracle_confidence_bps: 0,
+                    deposit_value_limit: 0,
+                    borrow_value_limit: 0,
                 },
                 source_liquidity_pubkey,
                 source_liquidity_owner_keypair,
@@ -168,6 +168,60 @@ pub enum LendingError {
     /// Lending instruction exceeds desired slippage limit
     #[error("Amount smaller than desired slippage limit")]
     ExceededSlippage,
+    /// Host fee receiver is not registered in the host fee registry
+    #[error("Host fee receiver is not registered")]
+    HostFeeReceiverNotRegistered,
+    /// Host fee registry has reached its maximum number of hosts
+    #[error("Host fee registry is full")]
+    HostFeeRegistryFull,
+    /// Obligation is too large to be liquidated with the fixed liquidation
+    /// bonus
+    #[error("Obligation must be liquidated through a liquidation auction")]
+    LiquidationAuctionRequired,
+    /// Obligation is too small to be liquidated through an auction
+    #[error("Obligation cannot be liquidated through a liquidation auction")]
+    LiquidationAuctionNotRequired,
+
+    // 50
+    /// Liquidation auction is still running
+    #[error("Liquidation auction is still active")]
+    LiquidationAuctionActive,
+    /// Obligation health account does not match the obligation or is stale
+    #[error("Obligation health account is invalid")]
+    ObligationHealthInvalid,
+    /// Obligation has not been unhealthy for the liquidation grace period
+    #[error("Liquidation grace period has not elapsed")]
+    LiquidationGracePeriodActive,
+    /// Margin account is stale and must be refreshed in the current slot
+    #[error("Margin account needs to be refreshed")]
+    MarginAccountStale,
+    /// Obligation is not part of the margin account or cannot be added to it
+    #[error("Margin account obligation is invalid")]
+    MarginObligationInvalid,
+    /// Margin account has reached its maximum number of obligations
+    #[error("Margin account is full")]
+    MarginAccountFull,
+    /// Obligation is part of a margin account and must be used through it
+    #[error("Obligation must be used through its margin account")]
+    MarginAccountRequired,
+    /// Removing the obligation would leave borrows undercollateralized
+    #[error("Margin account obligation cannot be removed while undercollateralized")]
+    MarginObligationUndercollateralized,
+    /// Oracle price confidence interval exceeds the reserve threshold
+    #[error("Oracle price confidence interval is too wide")]
+    OracleConfidenceTooWide,
+    /// Protocol liquidity withdrawal has not been announced
+    #[error("Protocol liquidity withdrawal has not been announced")]
+    ProtocolLiquidityWithdrawalNotAnnounced,
+    /// Protocol liquidity withdrawal timelock has not elapsed
+    #[error("Protocol liquidity is locked")]
+    ProtocolLiquidityLocked,
+    /// Reserve supply value exceeds the deposit value limit
+    #[error("Reserve deposit value limit exceeded")]
+    ReserveDepositLimitExceeded,
+    /// Reserve borrowed value exceeds the borrow value limit
+    #[error("Reserve borrow value limit exceeded")]
+    ReserveBorrowLimitExceeded,
 }
 
 impl From<LendingError> for ProgramError {
@@ -0,0 +1,89 @@
+//! Events logged by the lending program
+//!
+//! Events are logged through `sol_log_data` and show up in the transaction
+//! logs as `Program data: <base64> <base64> ...`, one field per base64
+//! string. The first field is the event name.
+
+use {
+    crate::{math::Decimal, state::Obligation},
+    solana_program::{clock::Slot, log::sol_log_data, program_error::ProgramError, pubkey::Pubkey},
+};
+
+/// Name of the event logged when an obligation falls below the health
+/// warning threshold
+pub const OBLIGATION_AT_RISK_EVENT: &[u8] = b"ObligationAtRisk";
+
+/// Obligation fell below the health warning threshold during a refresh
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObligationAtRiskEvent {
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Lending market of the obligation
+    pub lending_market: Pubkey,
+    /// Owner of the obligation
+    pub owner: Pubkey,
+    /// Market value of borrows after the refresh
+    pub borrowed_value: Decimal,
+    /// Borrow value at which the obligation can be liquidated
+    pub unhealthy_borrow_value: Decimal,
+}
+
+impl ObligationAtRiskEvent {
+    /// Create the event for a refreshed obligation
+    pub fn new(obligation_pubkey: Pubkey, obligation: &Obligation) -> Self {
+        Self {
+            obligation: obligation_pubkey,
+            lending_market: obligation.lending_market,
+            owner: obligation.owner,
+            borrowed_value: obligation.borrowed_value,
+            unhealthy_borrow_value: obligation.unhealthy_borrow_value,
+        }
+    }
+
+    /// Log the event, decimal values are logged as little-endian scaled
+    /// u128 values
+    pub fn log(&self) -> Result<(), ProgramError> {
+        let borrowed_value = self.borrowed_value.to_scaled_val()?.to_le_bytes();
+        let unhealthy_borrow_value = self.unhealthy_borrow_value.to_scaled_val()?.to_le_bytes();
+        sol_log_data(&[
+            OBLIGATION_AT_RISK_EVENT,
+            self.obligation.as_ref(),
+            self.lending_market.as_ref(),
+            self.owner.as_ref(),
+            &borrowed_value,
+            &unhealthy_borrow_value,
+        ]);
+        Ok(())
+    }
+}
+
+/// Name of the event logged when a withdrawal of protocol-owned liquidity is
+/// announced
+pub const PROTOCOL_LIQUIDITY_WITHDRAWAL_ANNOUNCED_EVENT: &[u8] =
+    b"ProtocolLiquidityWithdrawalAnnounced";
+
+/// Lending market owner announced a withdrawal of protocol-owned liquidity
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProtocolLiquidityWithdrawalAnnouncedEvent {
+    /// Protocol liquidity account
+    pub protocol_liquidity: Pubkey,
+    /// Reserve the liquidity is deposited in
+    pub reserve: Pubkey,
+    /// Amount of collateral tokens to withdraw
+    pub collateral_amount: u64,
+    /// Slot from which the withdrawal can be made
+    pub unlock_slot: Slot,
+}
+
+impl ProtocolLiquidityWithdrawalAnnouncedEvent {
+    /// Log the event, integer values are logged as little-endian bytes
+    pub fn log(&self) {
+        sol_log_data(&[
+            PROTOCOL_LIQUIDITY_WITHDRAWAL_ANNOUNCED_EVENT,
+            self.protocol_liquidity.as_ref(),
+            self.reserve.as_ref(),
+            &self.collateral_amount.to_le_bytes(),
+            &self.unlock_slot.to_le_bytes(),
+        ]);
+    }
+}
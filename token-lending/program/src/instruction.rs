@@ -3,14 +3,14 @@
 use {
     crate::{
         error::LendingError,
-        state::{ReserveConfig, ReserveFees},
+        state::{LiquidationAuctionConfig, ReserveConfig, ReserveFees},
     },
     solana_program::{
         instruction::{AccountMeta, Instruction},
         msg,
         program_error::ProgramError,
         pubkey::{Pubkey, PUBKEY_BYTES},
-        sysvar,
+        system_program, sysvar,
     },
     std::{convert::TryInto, mem::size_of},
 };
@@ -69,7 +69,7 @@ pub enum LendingInstruction {
     ///      oracle account.
     ///   10. `[]` Lending market account.
     ///   11. `[]` Derived lending market authority.
-    ///   12. `[signer]` Lending market owner.
+    ///   12. `[signer]` Lending market ops admin, the owner if none is set.
     ///   13. `[signer]` User transfer authority ($authority).
     ///   14. `[]` Clock sysvar.
     ///   15. `[]` Rent sysvar.
@@ -90,6 +90,8 @@ pub enum LendingInstruction {
     ///   1. `[]` Reserve liquidity oracle account. Must be the Pyth price
     ///      account specified at InitReserve.
     ///   2. `[]` Clock sysvar.
+    ///   3. `[writable]` (Optional) Reserve rate history account, records the
+    ///      rates after accruing interest.
     RefreshReserve,
 
     // 4
@@ -162,6 +164,11 @@ pub enum LendingInstruction {
     ///      order.
     ///   3. .. `[]` Liquidity borrow reserve accounts - refreshed, all, in
     ///      order.
+    ///   4. `[writable]` (optional) Obligation health account, records the
+    ///      obligation health for the liquidation grace period.
+    ///
+    /// Logs an `ObligationAtRisk` event when the obligation falls below the
+    /// health warning threshold.
     RefreshObligation,
 
     // 8
@@ -229,6 +236,9 @@ pub enum LendingInstruction {
     ///   8. `[]` Clock sysvar.
     ///   9. `[]` Token program id.
     ///   10. `[optional, writable]` Host fee receiver account.
+    ///   11. `[optional]` Host fee registry account. Required with a host fee
+    ///       receiver if the lending market has a host fee registry, the host
+    ///       fee receiver must be registered and is paid its registered share.
     BorrowObligationLiquidity {
         /// Amount of liquidity to borrow - u64::MAX for 100% of borrowing power
         liquidity_amount: u64,
@@ -279,6 +289,9 @@ pub enum LendingInstruction {
     ///   9. `[signer]` User transfer authority ($authority).
     ///   10. `[]` Clock sysvar.
     ///   11. `[]` Token program id.
+    ///   12. `[]` Obligation health account - refreshed with the obligation.
+    ///       Required only if the lending market has a liquidation grace
+    ///       period.
     LiquidateObligation {
         /// Amount of liquidity to repay - u64::MAX for up to 100% of borrowed
         /// amount
@@ -337,13 +350,456 @@ pub enum LendingInstruction {
     ///
     ///   0. `[writable]` Reserve account
     ///   1. `[]` Lending market account
-    ///   2. `[signer]` Lending market owner
+    ///   2. `[signer]` Lending market risk admin, the owner if none is set
     ModifyReserveConfig {
         /// Reserve configuration updated values
         new_config: ReserveConfig,
+        /// Whether the config was encoded without the oracle thresholds and
+        /// value limits, which then keep their current values
+        legacy_config: bool,
+    },
+
+    // 15
+    /// Sets the maximum share of borrow and flash loan fees paid to host fee
+    /// receivers in a lending market.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Lending market account.
+    ///   1. `[signer]` Lending market owner.
+    SetHostFeePercentageCap {
+        /// Maximum host fee percentage, at most `MAX_HOST_FEE_PERCENTAGE`
+        host_fee_percentage_cap: u8,
+    },
+
+    // 16
+    /// Initializes a new host fee registry for a lending market. A lending
+    /// market has at most one host fee registry.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Host fee registry account - uninitialized.
+    ///   1. `[writable]` Lending market account.
+    ///   2. `[signer]` Lending market owner.
+    ///   3. `[]` Rent sysvar.
+    InitHostFeeRegistry,
+
+    // 17
+    /// Registers, updates or removes the share of borrow fees paid to a host
+    /// fee receiver. A percentage of zero removes the host from the registry.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Host fee registry account.
+    ///   1. `[]` Lending market account.
+    ///   2. `[signer]` Lending market owner.
+    ///   3. `[]` Host fee receiver account.
+    SetHostFeeShare {
+        /// Amount of fee going to the host fee receiver, as a percentage
+        host_fee_percentage: u8,
+    },
+
+    // 18
+    /// Sets or clears the liquidation auction configuration of a lending
+    /// market. Obligations with a borrowed value of at least the configured
+    /// minimum can only be liquidated through a liquidation auction.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Lending market account.
+    ///   1. `[signer]` Lending market risk admin, the owner if none is set.
+    SetLiquidationAuctionConfig {
+        /// Liquidation auction configuration, `None` disables auctions
+        config: Option<LiquidationAuctionConfig>,
+    },
+
+    // 19
+    /// Starts a liquidation auction for an unhealthy obligation whose
+    /// borrowed value requires it. The liquidation bonus grows from zero to
+    /// the market's maximum auction bonus over the auction window, and stays
+    /// at the maximum until the obligation is liquidated.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Liquidation auction account - uninitialized.
+    ///   1. `[]` Obligation account - refreshed.
+    ///   2. `[]` Lending market account.
+    ///   3. `[]` Clock sysvar.
+    ///   4. `[]` Rent sysvar.
+    StartLiquidationAuction,
+
+    // 20
+    /// Repay borrowed liquidity to a reserve to receive collateral at a
+    /// discount from an obligation in a running liquidation auction. The
+    /// discount is the auction's current liquidation bonus.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0-11. Same as `LiquidateObligation`.
+    ///   12. `[]` Liquidation auction account.
+    ///   13. `[]` Obligation health account - refreshed with the obligation.
+    ///       Required only if the lending market has a liquidation grace
+    ///       period.
+    FillLiquidationAuction {
+        /// Amount of liquidity to repay - u64::MAX for up to 100% of borrowed
+        /// amount
+        liquidity_amount: u64,
+    },
+
+    // 21
+    /// Deposit native SOL into a wrapped SOL reserve in exchange for
+    /// collateral. The SOL is wrapped in a temporary token account which is
+    /// closed back to the user transfer authority within the instruction.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` Native mint.
+    ///   1. `[]` System program id.
+    ///   2. Accounts 2 to 11 are the same as `DepositReserveLiquidity`, with
+    ///      the source liquidity token account replaced by the writable
+    ///      temporary wrapped SOL account derived from [b"native-sol",
+    ///      $authority], and $authority writable as it pays the SOL.
+    DepositReserveLiquidityNative {
+        /// Amount of SOL to deposit in exchange for collateral tokens
+        liquidity_amount: u64,
+    },
+
+    // 22
+    /// Redeem collateral from a wrapped SOL reserve in exchange for native
+    /// SOL, unwrapped through a temporary token account.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` Native mint.
+    ///   1. `[]` System program id.
+    ///   2. Accounts 2 to 11 are the same as `RedeemReserveCollateral`, with
+    ///      the destination liquidity token account replaced by the writable
+    ///      temporary wrapped SOL account derived from [b"native-sol",
+    ///      $authority], and $authority writable as it receives the SOL.
+    RedeemReserveCollateralNative {
+        /// Amount of collateral tokens to redeem in exchange for SOL
+        collateral_amount: u64,
+    },
+
+    // 23
+    /// Borrow native SOL from a wrapped SOL reserve, unwrapped through a
+    /// temporary token account. Requires a refreshed obligation and reserve.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` Native mint.
+    ///   1. `[]` System program id.
+    ///   2. Accounts 2 to 13 are the same as `BorrowObligationLiquidity`, with
+    ///      the destination liquidity token account replaced by the writable
+    ///      temporary wrapped SOL account derived from [b"native-sol",
+    ///      obligation owner], and the obligation owner writable as it receives
+    ///      the SOL.
+    BorrowObligationLiquidityNative {
+        /// Amount of SOL to borrow - u64::MAX for 100% of borrowing power
+        liquidity_amount: u64,
+        /// Minimum amount of SOL to receive, if borrowing 100% of borrowing
+        /// power
+        slippage_limit: u64,
+    },
+
+    // 24
+    /// Repay borrowed liquidity to a wrapped SOL reserve with native SOL.
+    /// Requires a refreshed obligation and reserve. SOL left over after
+    /// repaying is returned to the user transfer authority.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` Native mint.
+    ///   1. `[]` System program id.
+    ///   2. Accounts 2 to 9 are the same as `RepayObligationLiquidity`, with
+    ///      the source liquidity token account replaced by the writable
+    ///      temporary wrapped SOL account derived from [b"native-sol",
+    ///      $authority], and $authority writable as it pays the SOL.
+    RepayObligationLiquidityNative {
+        /// Amount of SOL to repay, the borrowed amount is repaid in full if it
+        /// is lower - u64::MAX is not supported
+        liquidity_amount: u64,
+    },
+
+    // 25
+    /// Sets or clears the risk admin and ops admin of a lending market. The
+    /// risk admin can modify reserve configs and the liquidation auction
+    /// config, the ops admin can add reserves and update reserve oracles.
+    /// Powers of an admin that is not set stay with the owner.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Lending market account.
+    ///   1. `[signer]` Lending market owner.
+    SetLendingMarketAdmins {
+        /// The new risk admin, `None` hands risk powers back to the owner
+        risk_admin: Option<Pubkey>,
+        /// The new ops admin, `None` hands ops powers back to the owner
+        ops_admin: Option<Pubkey>,
+    },
+
+    // 26
+    /// Replaces the oracle of a reserve. The new Pyth price account must be
+    /// quoted in the lending market quote currency. The reserve is marked
+    /// stale and must be refreshed before it is used again.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Reserve account.
+    ///   1. `[]` Lending market account.
+    ///   2. `[signer]` Lending market ops admin, the owner if none is set.
+    ///   3. `[]` Pyth product account.
+    ///   4. `[]` Pyth price account. This will be used as the reserve
+    ///      liquidity oracle account.
+    ///   5. `[]` Clock sysvar.
+    SetReserveOracle,
+
+    // 27
+    /// Sets the liquidation grace period of a lending market. Obligations
+    /// can only be liquidated once their obligation health account recorded
+    /// them as unhealthy for at least the grace period.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Lending market account.
+    ///   1. `[signer]` Lending market risk admin, the owner if none is set.
+    SetLiquidationGracePeriod {
+        /// Number of slots an obligation must stay unhealthy before it can be
+        /// liquidated, zero disables the grace period
+        liquidation_grace_slots: u64,
+    },
+
+    // 28
+    /// Creates the obligation health account of an obligation, which
+    /// refreshes use to record how long the obligation has been unhealthy.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Obligation health account - uninitialized, derived
+    ///      from [b"obligation-health", $obligation].
+    ///   1. `[]` Obligation account ($obligation).
+    ///   2. `[writable, signer]` Payer of the account rent.
+    ///   3. `[]` System program id.
+    ///   4. `[]` Clock sysvar.
+    InitObligationHealth,
+
+    // 29
+    /// Initializes a new margin account, which nets the health of several
+    /// obligations of its owner for borrows and withdrawals.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Margin account - uninitialized.
+    ///   1. `[]` Lending market account.
+    ///   2. `[signer]` Margin account owner.
+    ///   3. `[]` Clock sysvar.
+    ///   4. `[]` Rent sysvar.
+    InitMarginAccount,
+
+    // 30
+    /// Adds an obligation to a margin account. The obligation is handed over
+    /// to the lending market authority, so it can only be used through the
+    /// margin account until it is removed. The margin account is marked stale.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Margin account.
+    ///   1. `[writable]` Obligation account, owned by the margin account
+    ///      owner.
+    ///   2. `[]` Lending market account.
+    ///   3. `[signer]` Margin account owner.
+    AddMarginObligation,
+
+    // 31
+    /// Removes an obligation from a margin account and hands it back to the
+    /// margin account owner. Both the obligation and the rest of the margin
+    /// account must stay within their allowed borrow value. Requires a
+    /// refreshed margin account and obligation.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Margin account - refreshed.
+    ///   1. `[writable]` Obligation account - refreshed.
+    ///   2. `[]` Lending market account.
+    ///   3. `[signer]` Margin account owner.
+    ///   4. `[]` Clock sysvar.
+    RemoveMarginObligation,
+
+    // 32
+    /// Refresh a margin account's aggregated market values. Requires all
+    /// obligations of the margin account, refreshed and in order.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Margin account.
+    ///   1. `[]` Clock sysvar.
+    ///   2. .. `[]` Obligation accounts - refreshed, all, in order.
+    RefreshMarginAccount,
+
+    // 33
+    /// Deposit collateral to an obligation of a margin account. Requires a
+    /// refreshed reserve. The margin account is marked stale.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Margin account.
+    ///   1. Accounts 1 to 9 are the same as `DepositObligationCollateral`, with
+    ///      the margin account owner as the obligation owner.
+    DepositObligationCollateralWithMargin {
+        /// Amount of collateral tokens to deposit
+        collateral_amount: u64,
+    },
+
+    // 34
+    /// Withdraw collateral from an obligation of a margin account, up to the
+    /// allowed borrow value of the margin account. Requires a refreshed
+    /// margin account, obligation and reserve. The margin account is marked
+    /// stale.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Margin account - refreshed.
+    ///   1. Accounts 1 to 9 are the same as `WithdrawObligationCollateral`,
+    ///      with the margin account owner as the obligation owner.
+    WithdrawObligationCollateralWithMargin {
+        /// Amount of collateral tokens to withdraw - u64::MAX for up to 100% of
+        /// deposited amount
+        collateral_amount: u64,
+    },
+
+    // 35
+    /// Borrow liquidity into an obligation of a margin account, against the
+    /// collateral of all of its obligations. Requires a refreshed margin
+    /// account, obligation and reserve. The margin account is marked stale.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Margin account - refreshed.
+    ///   1. Accounts 1 to 12 are the same as `BorrowObligationLiquidity`, with
+    ///      the margin account owner as the obligation owner.
+    BorrowObligationLiquidityWithMargin {
+        /// Amount of liquidity to borrow - u64::MAX for 100% of borrowing power
+        liquidity_amount: u64,
+        /// Minimum amount of liquidity to receive, if borrowing 100% of
+        /// borrowing power
+        slippage_limit: u64,
+    },
+
+    // 36
+    /// Repay borrowed liquidity of an obligation of an unhealthy margin
+    /// account to receive collateral at a discount from another, or the
+    /// same, obligation of the margin account. Requires a refreshed margin
+    /// account, obligations and reserves. The margin account is marked stale.
+    /// Liquidation auctions and the liquidation grace period do not apply to
+    /// margin accounts.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Margin account - refreshed.
+    ///   1. `[writable]` Withdraw obligation account - refreshed. Obligation
+    ///      the collateral is withdrawn from.
+    ///   2. Accounts 2 to 13 are the same as `LiquidateObligation`, with the
+    ///      obligation being the repay obligation the liquidity is repaid to.
+    LiquidateMarginObligation {
+        /// Amount of liquidity to repay - u64::MAX for up to 100% of borrowed
+        /// amount
+        liquidity_amount: u64,
+    },
+
+    // 37
+    /// Initializes the protocol-owned liquidity of a reserve. Collateral
+    /// minted for protocol-owned liquidity is locked in a collateral supply
+    /// owned by the lending market authority, and can only be withdrawn after
+    /// an announced withdrawal's timelock elapsed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Protocol liquidity account - uninitialized.
+    ///   1. `[writable]` Protocol collateral supply SPL Token account -
+    ///      uninitialized.
+    ///   2. `[]` Reserve account.
+    ///   3. `[]` Reserve collateral SPL Token mint.
+    ///   4. `[]` Lending market account.
+    ///   5. `[]` Derived lending market authority.
+    ///   6. `[signer]` Lending market owner.
+    ///   7. `[]` Rent sysvar.
+    ///   8. `[]` Token program id.
+    InitProtocolLiquidity {
+        /// Number of slots between a withdrawal announcement and the
+        /// withdrawal, cannot be changed once initialized
+        withdrawal_timelock_slots: u64,
+    },
+
+    // 38
+    /// Deposit protocol-owned liquidity into a reserve, locking the minted
+    /// collateral in the protocol collateral supply. Requires a refreshed
+    /// reserve.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Protocol liquidity account.
+    ///   1. `[signer]` Lending market owner.
+    ///   2. Accounts 2 to 11 are the same as `DepositReserveLiquidity`, with
+    ///      the protocol collateral supply as the destination collateral.
+    DepositProtocolLiquidity {
+        /// Amount of liquidity to deposit in exchange for locked collateral
+        /// tokens
+        liquidity_amount: u64,
+    },
+
+    // 39
+    /// Announce a withdrawal of protocol-owned liquidity, replacing any
+    /// pending announcement. The withdrawal unlocks once the timelock of the
+    /// protocol liquidity elapsed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Protocol liquidity account.
+    ///   1. `[]` Lending market account.
+    ///   2. `[signer]` Lending market owner.
+    ///   3. `[]` Clock sysvar.
+    AnnounceProtocolLiquidityWithdrawal {
+        /// Amount of locked collateral tokens to withdraw
+        collateral_amount: u64,
     },
+
+    // 40
+    /// Redeem the locked collateral of an unlocked withdrawal announcement
+    /// for liquidity. Requires a refreshed reserve.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Protocol liquidity account.
+    ///   1. `[writable]` Protocol collateral supply SPL Token account.
+    ///   2. `[writable]` Destination liquidity token account.
+    ///   3. `[writable]` Reserve account.
+    ///   4. `[writable]` Reserve collateral SPL Token mint.
+    ///   5. `[writable]` Reserve liquidity supply SPL Token account.
+    ///   6. `[]` Lending market account.
+    ///   7. `[]` Derived lending market authority.
+    ///   8. `[signer]` Lending market owner.
+    ///   9. `[]` Clock sysvar.
+    ///   10. `[]` Token program id.
+    WithdrawProtocolLiquidity,
+
+    // 41
+    /// Creates the rate history account of a reserve, which refreshes use to
+    /// record the latest rates of the reserve for external integrations.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Reserve rate history account - uninitialized, derived
+    ///      from [b"rate-history", $reserve].
+    ///   1. `[]` Reserve account ($reserve).
+    ///   2. `[writable, signer]` Payer of the account rent.
+    ///   3. `[]` System program id.
+    InitReserveRateHistory,
 }
 
+/// Encoded length of the oracle thresholds and value limits at the end of a
+/// reserve config, which legacy clients omit
+const RESERVE_CONFIG_EXTENSION_LEN: usize = 8 + 2 + 8 + 8;
+
 impl LendingInstruction {
     /// Unpacks a byte buffer into a
     /// [LendingInstruction](enum.LendingInstruction.html).
@@ -366,7 +822,7 @@ impl LendingInstruction {
             }
             2 => {
                 let (liquidity_amount, rest) = Self::unpack_u64(rest)?;
-                let config = Self::unpack_reserve_config(rest)?;
+                let (config, _legacy_config) = Self::unpack_reserve_config(rest)?;
                 Self::InitReserve {
                     liquidity_amount,
                     config,
@@ -412,9 +868,127 @@ impl LendingInstruction {
                 Self::FlashLoan { amount }
             }
             14 => {
-                let new_config = Self::unpack_reserve_config(rest)?;
-                Self::ModifyReserveConfig { new_config }
+                let (new_config, legacy_config) = Self::unpack_reserve_config(rest)?;
+                Self::ModifyReserveConfig {
+                    new_config,
+                    legacy_config,
+                }
+            }
+            15 => {
+                let (host_fee_percentage_cap, _rest) = Self::unpack_u8(rest)?;
+                Self::SetHostFeePercentageCap {
+                    host_fee_percentage_cap,
+                }
+            }
+            16 => Self::InitHostFeeRegistry,
+            17 => {
+                let (host_fee_percentage, _rest) = Self::unpack_u8(rest)?;
+                Self::SetHostFeeShare {
+                    host_fee_percentage,
+                }
+            }
+            18 => {
+                let (has_config, rest) = Self::unpack_u8(rest)?;
+                let config = match has_config {
+                    0 => None,
+                    1 => {
+                        let (min_borrowed_value, rest) = Self::unpack_u64(rest)?;
+                        let (duration_slots, rest) = Self::unpack_u64(rest)?;
+                        let (max_bonus, _rest) = Self::unpack_u8(rest)?;
+                        Some(LiquidationAuctionConfig {
+                            min_borrowed_value,
+                            duration_slots,
+                            max_bonus,
+                        })
+                    }
+                    _ => {
+                        msg!("Liquidation auction config flag cannot be unpacked");
+                        return Err(LendingError::InstructionUnpackError.into());
+                    }
+                };
+                Self::SetLiquidationAuctionConfig { config }
+            }
+            19 => Self::StartLiquidationAuction,
+            20 => {
+                let (liquidity_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::FillLiquidationAuction { liquidity_amount }
+            }
+            21 => {
+                let (liquidity_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::DepositReserveLiquidityNative { liquidity_amount }
+            }
+            22 => {
+                let (collateral_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::RedeemReserveCollateralNative { collateral_amount }
+            }
+            23 => {
+                let (liquidity_amount, rest) = Self::unpack_u64(rest)?;
+                let (slippage_limit, _rest) = Self::unpack_u64(rest)?;
+                Self::BorrowObligationLiquidityNative {
+                    liquidity_amount,
+                    slippage_limit,
+                }
+            }
+            24 => {
+                let (liquidity_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::RepayObligationLiquidityNative { liquidity_amount }
+            }
+            25 => {
+                let (risk_admin, rest) = Self::unpack_optional_pubkey(rest)?;
+                let (ops_admin, _rest) = Self::unpack_optional_pubkey(rest)?;
+                Self::SetLendingMarketAdmins {
+                    risk_admin,
+                    ops_admin,
+                }
             }
+            26 => Self::SetReserveOracle,
+            27 => {
+                let (liquidation_grace_slots, _rest) = Self::unpack_u64(rest)?;
+                Self::SetLiquidationGracePeriod {
+                    liquidation_grace_slots,
+                }
+            }
+            28 => Self::InitObligationHealth,
+            29 => Self::InitMarginAccount,
+            30 => Self::AddMarginObligation,
+            31 => Self::RemoveMarginObligation,
+            32 => Self::RefreshMarginAccount,
+            33 => {
+                let (collateral_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::DepositObligationCollateralWithMargin { collateral_amount }
+            }
+            34 => {
+                let (collateral_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::WithdrawObligationCollateralWithMargin { collateral_amount }
+            }
+            35 => {
+                let (liquidity_amount, rest) = Self::unpack_u64(rest)?;
+                let (slippage_limit, _rest) = Self::unpack_u64(rest)?;
+                Self::BorrowObligationLiquidityWithMargin {
+                    liquidity_amount,
+                    slippage_limit,
+                }
+            }
+            36 => {
+                let (liquidity_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::LiquidateMarginObligation { liquidity_amount }
+            }
+            37 => {
+                let (withdrawal_timelock_slots, _rest) = Self::unpack_u64(rest)?;
+                Self::InitProtocolLiquidity {
+                    withdrawal_timelock_slots,
+                }
+            }
+            38 => {
+                let (liquidity_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::DepositProtocolLiquidity { liquidity_amount }
+            }
+            39 => {
+                let (collateral_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::AnnounceProtocolLiquidityWithdrawal { collateral_amount }
+            }
+            40 => Self::WithdrawProtocolLiquidity,
+            41 => Self::InitReserveRateHistory,
             _ => {
                 msg!("Instruction cannot be unpacked");
                 return Err(LendingError::InstructionUnpackError.into());
@@ -436,6 +1010,20 @@ impl LendingInstruction {
         Ok((value, rest))
     }
 
+    fn unpack_u16(input: &[u8]) -> Result<(u16, &[u8]), ProgramError> {
+        if input.len() < 2 {
+            msg!("u16 cannot be unpacked");
+            return Err(LendingError::InstructionUnpackError.into());
+        }
+        let (bytes, rest) = input.split_at(2);
+        let value = bytes
+            .get(..2)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u16::from_le_bytes)
+            .ok_or(LendingError::InstructionUnpackError)?;
+        Ok((value, rest))
+    }
+
     fn unpack_u8(input: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
         if input.is_empty() {
             msg!("u8 cannot be unpacked");
@@ -474,7 +1062,25 @@ impl LendingInstruction {
         Ok((pk, rest))
     }
 
-    fn unpack_reserve_config(input: &[u8]) -> Result<ReserveConfig, ProgramError> {
+    fn unpack_optional_pubkey(input: &[u8]) -> Result<(Option<Pubkey>, &[u8]), ProgramError> {
+        let (has_pubkey, rest) = Self::unpack_u8(input)?;
+        match has_pubkey {
+            0 => Ok((None, rest)),
+            1 => {
+                let (pubkey, rest) = Self::unpack_pubkey(rest)?;
+                Ok((Some(pubkey), rest))
+            }
+            _ => {
+                msg!("Pubkey option flag cannot be unpacked");
+                Err(LendingError::InstructionUnpackError.into())
+            }
+        }
+    }
+
+    /// Unpacks a reserve config, either in full or in the legacy encoding
+    /// which ends at the host fee percentage. The legacy encoding leaves the
+    /// oracle thresholds and value limits at 0 and is flagged in the result.
+    fn unpack_reserve_config(input: &[u8]) -> Result<(ReserveConfig, bool), ProgramError> {
         let (optimal_utilization_rate, rest) = Self::unpack_u8(input)?;
         let (loan_to_value_ratio, rest) = Self::unpack_u8(rest)?;
         let (liquidation_bonus, rest) = Self::unpack_u8(rest)?;
@@ -484,9 +1090,33 @@ impl LendingInstruction {
         let (max_borrow_rate, rest) = Self::unpack_u8(rest)?;
         let (borrow_fee_wad, rest) = Self::unpack_u64(rest)?;
         let (flash_loan_fee_wad, rest) = Self::unpack_u64(rest)?;
-        let (host_fee_percentage, _rest) = Self::unpack_u8(rest)?;
+        let (host_fee_percentage, rest) = Self::unpack_u8(rest)?;
+        let legacy_config = rest.is_empty();
+        let (
+            max_oracle_age_slots,
+            max_oracle_confidence_bps,
+            deposit_value_limit,
+            borrow_value_limit,
+        ) = if legacy_config {
+            (0, 0, 0, 0)
+        } else {
+            let (max_oracle_age_slots, rest) = Self::unpack_u64(rest)?;
+            let (max_oracle_confidence_bps, rest) = Self::unpack_u16(rest)?;
+            let (deposit_value_limit, rest) = Self::unpack_u64(rest)?;
+            let (borrow_value_limit, rest) = Self::unpack_u64(rest)?;
+            if !rest.is_empty() {
+                msg!("Reserve config cannot be unpacked");
+                return Err(LendingError::InstructionUnpackError.into());
+            }
+            (
+                max_oracle_age_slots,
+                max_oracle_confidence_bps,
+                deposit_value_limit,
+                borrow_value_limit,
+            )
+        };
 
-        Ok(ReserveConfig {
+        let config = ReserveConfig {
             optimal_utilization_rate,
             loan_to_value_ratio,
             liquidation_bonus,
@@ -499,7 +1129,12 @@ impl LendingInstruction {
                 flash_loan_fee_wad,
                 host_fee_percentage,
             },
-        })
+            max_oracle_age_slots,
+            max_oracle_confidence_bps,
+            deposit_value_limit,
+            borrow_value_limit,
+        };
+        Ok((config, legacy_config))
     }
 
     /// Packs a [LendingInstruction](enum.LendingInstruction.html) into a byte
@@ -572,32 +1207,179 @@ impl LendingInstruction {
                 buf.push(13);
                 buf.extend_from_slice(&amount.to_le_bytes());
             }
-            Self::ModifyReserveConfig { new_config } => {
+            Self::ModifyReserveConfig {
+                new_config,
+                legacy_config,
+            } => {
                 buf.push(14);
                 Self::extend_buffer_from_reserve_config(&mut buf, &new_config);
+                if legacy_config {
+                    buf.truncate(buf.len() - RESERVE_CONFIG_EXTENSION_LEN);
+                }
             }
-        }
-        buf
-    }
-
-    // Helper function to pack a ReserveConfig into a Vec<u8> buffer
-    fn extend_buffer_from_reserve_config(buf: &mut Vec<u8>, config: &ReserveConfig) {
-        buf.extend_from_slice(&config.optimal_utilization_rate.to_le_bytes());
-        buf.extend_from_slice(&config.loan_to_value_ratio.to_le_bytes());
-        buf.extend_from_slice(&config.liquidation_bonus.to_le_bytes());
-        buf.extend_from_slice(&config.liquidation_threshold.to_le_bytes());
-        buf.extend_from_slice(&config.min_borrow_rate.to_le_bytes());
-        buf.extend_from_slice(&config.optimal_borrow_rate.to_le_bytes());
-        buf.extend_from_slice(&config.max_borrow_rate.to_le_bytes());
-        buf.extend_from_slice(&config.fees.borrow_fee_wad.to_le_bytes());
-        buf.extend_from_slice(&config.fees.flash_loan_fee_wad.to_le_bytes());
-        buf.extend_from_slice(&config.fees.host_fee_percentage.to_le_bytes());
-    }
-}
-
-/// Creates an 'InitLendingMarket' instruction.
-pub fn init_lending_market(
-    program_id: Pubkey,
+            Self::SetHostFeePercentageCap {
+                host_fee_percentage_cap,
+            } => {
+                buf.push(15);
+                buf.extend_from_slice(&host_fee_percentage_cap.to_le_bytes());
+            }
+            Self::InitHostFeeRegistry => {
+                buf.push(16);
+            }
+            Self::SetHostFeeShare {
+                host_fee_percentage,
+            } => {
+                buf.push(17);
+                buf.extend_from_slice(&host_fee_percentage.to_le_bytes());
+            }
+            Self::SetLiquidationAuctionConfig { config } => {
+                buf.push(18);
+                match config {
+                    Some(config) => {
+                        buf.push(1);
+                        buf.extend_from_slice(&config.min_borrowed_value.to_le_bytes());
+                        buf.extend_from_slice(&config.duration_slots.to_le_bytes());
+                        buf.extend_from_slice(&config.max_bonus.to_le_bytes());
+                    }
+                    None => buf.push(0),
+                }
+            }
+            Self::StartLiquidationAuction => {
+                buf.push(19);
+            }
+            Self::FillLiquidationAuction { liquidity_amount } => {
+                buf.push(20);
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
+            }
+            Self::DepositReserveLiquidityNative { liquidity_amount } => {
+                buf.push(21);
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
+            }
+            Self::RedeemReserveCollateralNative { collateral_amount } => {
+                buf.push(22);
+                buf.extend_from_slice(&collateral_amount.to_le_bytes());
+            }
+            Self::BorrowObligationLiquidityNative {
+                liquidity_amount,
+                slippage_limit,
+            } => {
+                buf.push(23);
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
+                buf.extend_from_slice(&slippage_limit.to_le_bytes());
+            }
+            Self::RepayObligationLiquidityNative { liquidity_amount } => {
+                buf.push(24);
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
+            }
+            Self::SetLendingMarketAdmins {
+                risk_admin,
+                ops_admin,
+            } => {
+                buf.push(25);
+                Self::extend_buffer_from_optional_pubkey(&mut buf, &risk_admin);
+                Self::extend_buffer_from_optional_pubkey(&mut buf, &ops_admin);
+            }
+            Self::SetReserveOracle => {
+                buf.push(26);
+            }
+            Self::SetLiquidationGracePeriod {
+                liquidation_grace_slots,
+            } => {
+                buf.push(27);
+                buf.extend_from_slice(&liquidation_grace_slots.to_le_bytes());
+            }
+            Self::InitObligationHealth => {
+                buf.push(28);
+            }
+            Self::InitMarginAccount => {
+                buf.push(29);
+            }
+            Self::AddMarginObligation => {
+                buf.push(30);
+            }
+            Self::RemoveMarginObligation => {
+                buf.push(31);
+            }
+            Self::RefreshMarginAccount => {
+                buf.push(32);
+            }
+            Self::DepositObligationCollateralWithMargin { collateral_amount } => {
+                buf.push(33);
+                buf.extend_from_slice(&collateral_amount.to_le_bytes());
+            }
+            Self::WithdrawObligationCollateralWithMargin { collateral_amount } => {
+                buf.push(34);
+                buf.extend_from_slice(&collateral_amount.to_le_bytes());
+            }
+            Self::BorrowObligationLiquidityWithMargin {
+                liquidity_amount,
+                slippage_limit,
+            } => {
+                buf.push(35);
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
+                buf.extend_from_slice(&slippage_limit.to_le_bytes());
+            }
+            Self::LiquidateMarginObligation { liquidity_amount } => {
+                buf.push(36);
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
+            }
+            Self::InitProtocolLiquidity {
+                withdrawal_timelock_slots,
+            } => {
+                buf.push(37);
+                buf.extend_from_slice(&withdrawal_timelock_slots.to_le_bytes());
+            }
+            Self::DepositProtocolLiquidity { liquidity_amount } => {
+                buf.push(38);
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
+            }
+            Self::AnnounceProtocolLiquidityWithdrawal { collateral_amount } => {
+                buf.push(39);
+                buf.extend_from_slice(&collateral_amount.to_le_bytes());
+            }
+            Self::WithdrawProtocolLiquidity => {
+                buf.push(40);
+            }
+            Self::InitReserveRateHistory => {
+                buf.push(41);
+            }
+        }
+        buf
+    }
+
+    // Helper function to pack an optional Pubkey into a Vec<u8> buffer
+    fn extend_buffer_from_optional_pubkey(buf: &mut Vec<u8>, pubkey: &Option<Pubkey>) {
+        match pubkey {
+            Some(pubkey) => {
+                buf.push(1);
+                buf.extend_from_slice(pubkey.as_ref());
+            }
+            None => buf.push(0),
+        }
+    }
+
+    // Helper function to pack a ReserveConfig into a Vec<u8> buffer
+    fn extend_buffer_from_reserve_config(buf: &mut Vec<u8>, config: &ReserveConfig) {
+        buf.extend_from_slice(&config.optimal_utilization_rate.to_le_bytes());
+        buf.extend_from_slice(&config.loan_to_value_ratio.to_le_bytes());
+        buf.extend_from_slice(&config.liquidation_bonus.to_le_bytes());
+        buf.extend_from_slice(&config.liquidation_threshold.to_le_bytes());
+        buf.extend_from_slice(&config.min_borrow_rate.to_le_bytes());
+        buf.extend_from_slice(&config.optimal_borrow_rate.to_le_bytes());
+        buf.extend_from_slice(&config.max_borrow_rate.to_le_bytes());
+        buf.extend_from_slice(&config.fees.borrow_fee_wad.to_le_bytes());
+        buf.extend_from_slice(&config.fees.flash_loan_fee_wad.to_le_bytes());
+        buf.extend_from_slice(&config.fees.host_fee_percentage.to_le_bytes());
+        buf.extend_from_slice(&config.max_oracle_age_slots.to_le_bytes());
+        buf.extend_from_slice(&config.max_oracle_confidence_bps.to_le_bytes());
+        buf.extend_from_slice(&config.deposit_value_limit.to_le_bytes());
+        buf.extend_from_slice(&config.borrow_value_limit.to_le_bytes());
+    }
+}
+
+/// Creates an 'InitLendingMarket' instruction.
+pub fn init_lending_market(
+    program_id: Pubkey,
     owner: Pubkey,
     quote_currency: [u8; 32],
     lending_market_pubkey: Pubkey,
@@ -932,6 +1714,42 @@ pub fn borrow_obligation_liquidity(
     }
 }
 
+/// Creates a 'BorrowObligationLiquidity' instruction paying the host fee
+/// receiver its share registered in a host fee registry.
+#[allow(clippy::too_many_arguments)]
+pub fn borrow_obligation_liquidity_with_host_fee_registry(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    slippage_limit: Option<u64>,
+    source_liquidity_pubkey: Pubkey,
+    destination_liquidity_pubkey: Pubkey,
+    borrow_reserve_pubkey: Pubkey,
+    borrow_reserve_liquidity_fee_receiver_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    obligation_owner_pubkey: Pubkey,
+    host_fee_receiver_pubkey: Pubkey,
+    host_fee_registry_pubkey: Pubkey,
+) -> Instruction {
+    let mut instruction = borrow_obligation_liquidity(
+        program_id,
+        liquidity_amount,
+        slippage_limit,
+        source_liquidity_pubkey,
+        destination_liquidity_pubkey,
+        borrow_reserve_pubkey,
+        borrow_reserve_liquidity_fee_receiver_pubkey,
+        obligation_pubkey,
+        lending_market_pubkey,
+        obligation_owner_pubkey,
+        Some(host_fee_receiver_pubkey),
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(host_fee_registry_pubkey, false));
+    instruction
+}
+
 /// Creates a `RepayObligationLiquidity` instruction
 #[allow(clippy::too_many_arguments)]
 pub fn repay_obligation_liquidity(
@@ -1053,452 +1871,1851 @@ pub fn modify_reserve_config(
     Instruction {
         program_id,
         accounts,
-        data: LendingInstruction::ModifyReserveConfig { new_config: config }.pack(),
+        data: LendingInstruction::ModifyReserveConfig {
+            new_config: config,
+            legacy_config: false,
+        }
+        .pack(),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Creates a 'SetHostFeePercentageCap' instruction.
+pub fn set_host_fee_percentage_cap(
+    program_id: Pubkey,
+    host_fee_percentage_cap: u8,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner_pubkey, true),
+        ],
+        data: LendingInstruction::SetHostFeePercentageCap {
+            host_fee_percentage_cap,
+        }
+        .pack(),
+    }
+}
 
-    #[test]
-    fn test_init_lending_market() {
-        let program_id = Pubkey::new_unique();
-        let owner = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let oracle_program_id = Pubkey::new_unique();
-        let currency = "USD";
-        let mut quote_currency = [0u8; 32];
-        quote_currency[0..currency.len()].clone_from_slice(currency.as_bytes());
-        let instruction = init_lending_market(
-            program_id,
-            owner,
-            quote_currency,
-            lending_market_pubkey,
-            oracle_program_id,
-        );
-        assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 4);
-        assert_eq!(
-            instruction.data,
-            LendingInstruction::InitLendingMarket {
-                owner,
-                quote_currency,
-            }
-            .pack()
-        );
+/// Creates an 'InitHostFeeRegistry' instruction.
+pub fn init_host_fee_registry(
+    program_id: Pubkey,
+    host_fee_registry_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(host_fee_registry_pubkey, false),
+            AccountMeta::new(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner_pubkey, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: LendingInstruction::InitHostFeeRegistry.pack(),
     }
+}
 
-    #[test]
-    fn test_set_lending_market_owner() {
-        let program_id = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let lending_market_owner = Pubkey::new_unique();
-        let new_owner = Pubkey::new_unique();
-        let instruction = set_lending_market_owner(
-            program_id,
-            lending_market_pubkey,
-            lending_market_owner,
-            new_owner,
-        );
-        assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 2);
-        assert_eq!(
-            instruction.data,
-            LendingInstruction::SetLendingMarketOwner { new_owner }.pack()
-        );
+/// Creates a 'SetHostFeeShare' instruction.
+pub fn set_host_fee_share(
+    program_id: Pubkey,
+    host_fee_percentage: u8,
+    host_fee_registry_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+    host_fee_receiver_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(host_fee_registry_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner_pubkey, true),
+            AccountMeta::new_readonly(host_fee_receiver_pubkey, false),
+        ],
+        data: LendingInstruction::SetHostFeeShare {
+            host_fee_percentage,
+        }
+        .pack(),
     }
+}
 
-    #[test]
-    fn test_init_reserve() {
-        let program_id = Pubkey::new_unique();
-        let liquidity_amount = u64::MAX;
-        let config = ReserveConfig {
-            optimal_utilization_rate: 50,
-            loan_to_value_ratio: 1,
-            liquidation_bonus: 10,
-            liquidation_threshold: 5,
-            min_borrow_rate: 2,
-            optimal_borrow_rate: 4,
-            max_borrow_rate: 10,
-            fees: ReserveFees {
-                borrow_fee_wad: 1,
-                flash_loan_fee_wad: 3,
-                host_fee_percentage: 1,
-            },
-        };
-        let source_liquidity_pubkey = Pubkey::new_unique();
-        let destination_collateral_pubkey = Pubkey::new_unique();
-        let reserve_pubkey = Pubkey::new_unique();
-        let reserve_liquidity_mint_pubkey = Pubkey::new_unique();
-        let reserve_liquidity_supply_pubkey = Pubkey::new_unique();
-        let reserve_liquidity_fee_receiver_pubkey = Pubkey::new_unique();
-        let reserve_collateral_mint_pubkey = Pubkey::new_unique();
-        let reserve_collateral_supply_pubkey = Pubkey::new_unique();
-        let pyth_product_pubkey = Pubkey::new_unique();
-        let pyth_price_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let lending_market_owner_pubkey = Pubkey::new_unique();
-        let user_transfer_authority_pubkey = Pubkey::new_unique();
-        let instruction = init_reserve(
-            program_id,
-            liquidity_amount,
-            config,
-            source_liquidity_pubkey,
-            destination_collateral_pubkey,
-            reserve_pubkey,
-            reserve_liquidity_mint_pubkey,
-            reserve_liquidity_supply_pubkey,
-            reserve_liquidity_fee_receiver_pubkey,
-            reserve_collateral_mint_pubkey,
-            reserve_collateral_supply_pubkey,
-            pyth_product_pubkey,
-            pyth_price_pubkey,
-            lending_market_pubkey,
-            lending_market_owner_pubkey,
-            user_transfer_authority_pubkey,
-        );
-        assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 17);
-        assert_eq!(
-            instruction.data,
-            LendingInstruction::InitReserve {
-                liquidity_amount,
-                config,
-            }
-            .pack()
-        );
+/// Creates a 'SetLiquidationAuctionConfig' instruction.
+pub fn set_liquidation_auction_config(
+    program_id: Pubkey,
+    config: Option<LiquidationAuctionConfig>,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner_pubkey, true),
+        ],
+        data: LendingInstruction::SetLiquidationAuctionConfig { config }.pack(),
     }
+}
 
-    #[test]
-    fn test_refresh_reserve() {
-        let program_id = Pubkey::new_unique();
-        let reserve_pubkey = Pubkey::new_unique();
+/// Creates a 'StartLiquidationAuction' instruction.
+pub fn start_liquidation_auction(
+    program_id: Pubkey,
+    liquidation_auction_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(liquidation_auction_pubkey, false),
+            AccountMeta::new_readonly(obligation_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: LendingInstruction::StartLiquidationAuction.pack(),
+    }
+}
+
+/// Creates a `FillLiquidationAuction` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn fill_liquidation_auction(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    source_liquidity_pubkey: Pubkey,
+    destination_collateral_pubkey: Pubkey,
+    repay_reserve_pubkey: Pubkey,
+    repay_reserve_liquidity_supply_pubkey: Pubkey,
+    withdraw_reserve_pubkey: Pubkey,
+    withdraw_reserve_collateral_supply_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+    liquidation_auction_pubkey: Pubkey,
+) -> Instruction {
+    let mut instruction = liquidate_obligation(
+        program_id,
+        liquidity_amount,
+        source_liquidity_pubkey,
+        destination_collateral_pubkey,
+        repay_reserve_pubkey,
+        repay_reserve_liquidity_supply_pubkey,
+        withdraw_reserve_pubkey,
+        withdraw_reserve_collateral_supply_pubkey,
+        obligation_pubkey,
+        lending_market_pubkey,
+        user_transfer_authority_pubkey,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(liquidation_auction_pubkey, false));
+    instruction.data = LendingInstruction::FillLiquidationAuction { liquidity_amount }.pack();
+    instruction
+}
+
+/// Seed of the temporary wrapped SOL account used by native SOL instructions
+pub const NATIVE_SOL_SEED: &[u8] = b"native-sol";
+
+/// Finds the temporary wrapped SOL account of a user for native SOL
+/// instructions
+pub fn find_native_sol_address(program_id: &Pubkey, user_pubkey: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NATIVE_SOL_SEED, user_pubkey.as_ref()], program_id)
+}
+
+// Helper function to turn a token instruction into its native SOL counterpart
+fn native_sol_instruction(
+    mut instruction: Instruction,
+    user_pubkey: Pubkey,
+    data: Vec<u8>,
+) -> Instruction {
+    for account in instruction.accounts.iter_mut() {
+        if account.pubkey == user_pubkey {
+            account.is_writable = true;
+        }
+    }
+    instruction.accounts.splice(
+        0..0,
+        [
+            AccountMeta::new_readonly(spl_token::native_mint::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+    instruction.data = data;
+    instruction
+}
+
+/// Creates a 'DepositReserveLiquidityNative' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_reserve_liquidity_native(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    destination_collateral_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    reserve_liquidity_supply_pubkey: Pubkey,
+    reserve_collateral_mint_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+) -> Instruction {
+    let (native_sol_pubkey, _bump_seed) =
+        find_native_sol_address(&program_id, &user_transfer_authority_pubkey);
+    native_sol_instruction(
+        deposit_reserve_liquidity(
+            program_id,
+            liquidity_amount,
+            native_sol_pubkey,
+            destination_collateral_pubkey,
+            reserve_pubkey,
+            reserve_liquidity_supply_pubkey,
+            reserve_collateral_mint_pubkey,
+            lending_market_pubkey,
+            user_transfer_authority_pubkey,
+        ),
+        user_transfer_authority_pubkey,
+        LendingInstruction::DepositReserveLiquidityNative { liquidity_amount }.pack(),
+    )
+}
+
+/// Creates a 'RedeemReserveCollateralNative' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn redeem_reserve_collateral_native(
+    program_id: Pubkey,
+    collateral_amount: u64,
+    source_collateral_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    reserve_collateral_mint_pubkey: Pubkey,
+    reserve_liquidity_supply_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+) -> Instruction {
+    let (native_sol_pubkey, _bump_seed) =
+        find_native_sol_address(&program_id, &user_transfer_authority_pubkey);
+    native_sol_instruction(
+        redeem_reserve_collateral(
+            program_id,
+            collateral_amount,
+            source_collateral_pubkey,
+            native_sol_pubkey,
+            reserve_pubkey,
+            reserve_collateral_mint_pubkey,
+            reserve_liquidity_supply_pubkey,
+            lending_market_pubkey,
+            user_transfer_authority_pubkey,
+        ),
+        user_transfer_authority_pubkey,
+        LendingInstruction::RedeemReserveCollateralNative { collateral_amount }.pack(),
+    )
+}
+
+/// Creates a 'BorrowObligationLiquidityNative' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn borrow_obligation_liquidity_native(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    slippage_limit: Option<u64>,
+    source_liquidity_pubkey: Pubkey,
+    borrow_reserve_pubkey: Pubkey,
+    borrow_reserve_liquidity_fee_receiver_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    obligation_owner_pubkey: Pubkey,
+    host_fee_receiver_pubkey: Option<Pubkey>,
+) -> Instruction {
+    let (native_sol_pubkey, _bump_seed) =
+        find_native_sol_address(&program_id, &obligation_owner_pubkey);
+    native_sol_instruction(
+        borrow_obligation_liquidity(
+            program_id,
+            liquidity_amount,
+            slippage_limit,
+            source_liquidity_pubkey,
+            native_sol_pubkey,
+            borrow_reserve_pubkey,
+            borrow_reserve_liquidity_fee_receiver_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            obligation_owner_pubkey,
+            host_fee_receiver_pubkey,
+        ),
+        obligation_owner_pubkey,
+        LendingInstruction::BorrowObligationLiquidityNative {
+            liquidity_amount,
+            slippage_limit: slippage_limit.unwrap_or(0),
+        }
+        .pack(),
+    )
+}
+
+/// Creates a 'RepayObligationLiquidityNative' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn repay_obligation_liquidity_native(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    destination_liquidity_pubkey: Pubkey,
+    repay_reserve_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+) -> Instruction {
+    let (native_sol_pubkey, _bump_seed) =
+        find_native_sol_address(&program_id, &user_transfer_authority_pubkey);
+    native_sol_instruction(
+        repay_obligation_liquidity(
+            program_id,
+            liquidity_amount,
+            native_sol_pubkey,
+            destination_liquidity_pubkey,
+            repay_reserve_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            user_transfer_authority_pubkey,
+        ),
+        user_transfer_authority_pubkey,
+        LendingInstruction::RepayObligationLiquidityNative { liquidity_amount }.pack(),
+    )
+}
+
+/// Creates a 'SetLendingMarketAdmins' instruction.
+pub fn set_lending_market_admins(
+    program_id: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+    risk_admin: Option<Pubkey>,
+    ops_admin: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner_pubkey, true),
+        ],
+        data: LendingInstruction::SetLendingMarketAdmins {
+            risk_admin,
+            ops_admin,
+        }
+        .pack(),
+    }
+}
+
+/// Creates a 'SetReserveOracle' instruction.
+pub fn set_reserve_oracle(
+    program_id: Pubkey,
+    reserve_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_ops_admin_pubkey: Pubkey,
+    pyth_product_pubkey: Pubkey,
+    pyth_price_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(reserve_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_ops_admin_pubkey, true),
+            AccountMeta::new_readonly(pyth_product_pubkey, false),
+            AccountMeta::new_readonly(pyth_price_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: LendingInstruction::SetReserveOracle.pack(),
+    }
+}
+
+/// Creates a 'SetLiquidationGracePeriod' instruction.
+pub fn set_liquidation_grace_period(
+    program_id: Pubkey,
+    liquidation_grace_slots: u64,
+    lending_market_pubkey: Pubkey,
+    lending_market_risk_admin_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_risk_admin_pubkey, true),
+        ],
+        data: LendingInstruction::SetLiquidationGracePeriod {
+            liquidation_grace_slots,
+        }
+        .pack(),
+    }
+}
+
+/// Seed of the obligation health account of an obligation
+pub const OBLIGATION_HEALTH_SEED: &[u8] = b"obligation-health";
+
+/// Finds the obligation health account of an obligation
+pub fn find_obligation_health_address(
+    program_id: &Pubkey,
+    obligation_pubkey: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OBLIGATION_HEALTH_SEED, obligation_pubkey.as_ref()],
+        program_id,
+    )
+}
+
+/// Creates an 'InitObligationHealth' instruction.
+pub fn init_obligation_health(
+    program_id: Pubkey,
+    obligation_pubkey: Pubkey,
+    payer_pubkey: Pubkey,
+) -> Instruction {
+    let (obligation_health_pubkey, _bump_seed) =
+        find_obligation_health_address(&program_id, &obligation_pubkey);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(obligation_health_pubkey, false),
+            AccountMeta::new_readonly(obligation_pubkey, false),
+            AccountMeta::new(payer_pubkey, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: LendingInstruction::InitObligationHealth.pack(),
+    }
+}
+
+/// Appends the obligation health account of an obligation to a
+/// 'RefreshObligation', 'LiquidateObligation' or 'FillLiquidationAuction'
+/// instruction.
+pub fn with_obligation_health(
+    mut instruction: Instruction,
+    obligation_pubkey: Pubkey,
+) -> Instruction {
+    let (obligation_health_pubkey, _bump_seed) =
+        find_obligation_health_address(&instruction.program_id, &obligation_pubkey);
+    if instruction.data == LendingInstruction::RefreshObligation.pack() {
+        instruction
+            .accounts
+            .push(AccountMeta::new(obligation_health_pubkey, false));
+    } else {
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(obligation_health_pubkey, false));
+    }
+    instruction
+}
+
+/// Creates an 'InitMarginAccount' instruction.
+pub fn init_margin_account(
+    program_id: Pubkey,
+    margin_account_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    margin_account_owner_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(margin_account_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(margin_account_owner_pubkey, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: LendingInstruction::InitMarginAccount.pack(),
+    }
+}
+
+/// Creates an 'AddMarginObligation' instruction.
+pub fn add_margin_obligation(
+    program_id: Pubkey,
+    margin_account_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    margin_account_owner_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(margin_account_pubkey, false),
+            AccountMeta::new(obligation_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(margin_account_owner_pubkey, true),
+        ],
+        data: LendingInstruction::AddMarginObligation.pack(),
+    }
+}
+
+/// Creates a 'RemoveMarginObligation' instruction.
+pub fn remove_margin_obligation(
+    program_id: Pubkey,
+    margin_account_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    margin_account_owner_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(margin_account_pubkey, false),
+            AccountMeta::new(obligation_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(margin_account_owner_pubkey, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: LendingInstruction::RemoveMarginObligation.pack(),
+    }
+}
+
+/// Creates a 'RefreshMarginAccount' instruction.
+pub fn refresh_margin_account(
+    program_id: Pubkey,
+    margin_account_pubkey: Pubkey,
+    obligation_pubkeys: Vec<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(margin_account_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    accounts.extend(
+        obligation_pubkeys
+            .into_iter()
+            .map(|pubkey| AccountMeta::new_readonly(pubkey, false)),
+    );
+    Instruction {
+        program_id,
+        accounts,
+        data: LendingInstruction::RefreshMarginAccount.pack(),
+    }
+}
+
+// Helper function to turn an obligation instruction into its margin account
+// counterpart
+fn margin_account_instruction(
+    mut instruction: Instruction,
+    margin_accounts: Vec<AccountMeta>,
+    data: Vec<u8>,
+) -> Instruction {
+    instruction.accounts.splice(0..0, margin_accounts);
+    instruction.data = data;
+    instruction
+}
+
+/// Creates a 'DepositObligationCollateralWithMargin' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_obligation_collateral_with_margin(
+    program_id: Pubkey,
+    collateral_amount: u64,
+    margin_account_pubkey: Pubkey,
+    source_collateral_pubkey: Pubkey,
+    destination_collateral_pubkey: Pubkey,
+    deposit_reserve_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    margin_account_owner_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+) -> Instruction {
+    margin_account_instruction(
+        deposit_obligation_collateral(
+            program_id,
+            collateral_amount,
+            source_collateral_pubkey,
+            destination_collateral_pubkey,
+            deposit_reserve_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            margin_account_owner_pubkey,
+            user_transfer_authority_pubkey,
+        ),
+        vec![AccountMeta::new(margin_account_pubkey, false)],
+        LendingInstruction::DepositObligationCollateralWithMargin { collateral_amount }.pack(),
+    )
+}
+
+/// Creates a 'WithdrawObligationCollateralWithMargin' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_obligation_collateral_with_margin(
+    program_id: Pubkey,
+    collateral_amount: u64,
+    margin_account_pubkey: Pubkey,
+    source_collateral_pubkey: Pubkey,
+    destination_collateral_pubkey: Pubkey,
+    withdraw_reserve_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    margin_account_owner_pubkey: Pubkey,
+) -> Instruction {
+    margin_account_instruction(
+        withdraw_obligation_collateral(
+            program_id,
+            collateral_amount,
+            source_collateral_pubkey,
+            destination_collateral_pubkey,
+            withdraw_reserve_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            margin_account_owner_pubkey,
+        ),
+        vec![AccountMeta::new(margin_account_pubkey, false)],
+        LendingInstruction::WithdrawObligationCollateralWithMargin { collateral_amount }.pack(),
+    )
+}
+
+/// Creates a 'BorrowObligationLiquidityWithMargin' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn borrow_obligation_liquidity_with_margin(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    slippage_limit: Option<u64>,
+    margin_account_pubkey: Pubkey,
+    source_liquidity_pubkey: Pubkey,
+    destination_liquidity_pubkey: Pubkey,
+    borrow_reserve_pubkey: Pubkey,
+    borrow_reserve_liquidity_fee_receiver_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    margin_account_owner_pubkey: Pubkey,
+    host_fee_receiver_pubkey: Option<Pubkey>,
+) -> Instruction {
+    margin_account_instruction(
+        borrow_obligation_liquidity(
+            program_id,
+            liquidity_amount,
+            slippage_limit,
+            source_liquidity_pubkey,
+            destination_liquidity_pubkey,
+            borrow_reserve_pubkey,
+            borrow_reserve_liquidity_fee_receiver_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            margin_account_owner_pubkey,
+            host_fee_receiver_pubkey,
+        ),
+        vec![AccountMeta::new(margin_account_pubkey, false)],
+        LendingInstruction::BorrowObligationLiquidityWithMargin {
+            liquidity_amount,
+            slippage_limit: slippage_limit.unwrap_or(0),
+        }
+        .pack(),
+    )
+}
+
+/// Creates a 'LiquidateMarginObligation' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn liquidate_margin_obligation(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    margin_account_pubkey: Pubkey,
+    withdraw_obligation_pubkey: Pubkey,
+    source_liquidity_pubkey: Pubkey,
+    destination_collateral_pubkey: Pubkey,
+    repay_reserve_pubkey: Pubkey,
+    repay_reserve_liquidity_supply_pubkey: Pubkey,
+    withdraw_reserve_pubkey: Pubkey,
+    withdraw_reserve_collateral_supply_pubkey: Pubkey,
+    repay_obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+) -> Instruction {
+    margin_account_instruction(
+        liquidate_obligation(
+            program_id,
+            liquidity_amount,
+            source_liquidity_pubkey,
+            destination_collateral_pubkey,
+            repay_reserve_pubkey,
+            repay_reserve_liquidity_supply_pubkey,
+            withdraw_reserve_pubkey,
+            withdraw_reserve_collateral_supply_pubkey,
+            repay_obligation_pubkey,
+            lending_market_pubkey,
+            user_transfer_authority_pubkey,
+        ),
+        vec![
+            AccountMeta::new(margin_account_pubkey, false),
+            AccountMeta::new(withdraw_obligation_pubkey, false),
+        ],
+        LendingInstruction::LiquidateMarginObligation { liquidity_amount }.pack(),
+    )
+}
+
+/// Creates an 'InitProtocolLiquidity' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn init_protocol_liquidity(
+    program_id: Pubkey,
+    withdrawal_timelock_slots: u64,
+    protocol_liquidity_pubkey: Pubkey,
+    protocol_collateral_supply_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    reserve_collateral_mint_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+) -> Instruction {
+    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
+        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
+        &program_id,
+    );
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(protocol_liquidity_pubkey, false),
+            AccountMeta::new(protocol_collateral_supply_pubkey, false),
+            AccountMeta::new_readonly(reserve_pubkey, false),
+            AccountMeta::new_readonly(reserve_collateral_mint_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_authority_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner_pubkey, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: LendingInstruction::InitProtocolLiquidity {
+            withdrawal_timelock_slots,
+        }
+        .pack(),
+    }
+}
+
+/// Creates a 'DepositProtocolLiquidity' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_protocol_liquidity(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    protocol_liquidity_pubkey: Pubkey,
+    source_liquidity_pubkey: Pubkey,
+    protocol_collateral_supply_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    reserve_liquidity_supply_pubkey: Pubkey,
+    reserve_collateral_mint_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+) -> Instruction {
+    let mut instruction = deposit_reserve_liquidity(
+        program_id,
+        liquidity_amount,
+        source_liquidity_pubkey,
+        protocol_collateral_supply_pubkey,
+        reserve_pubkey,
+        reserve_liquidity_supply_pubkey,
+        reserve_collateral_mint_pubkey,
+        lending_market_pubkey,
+        user_transfer_authority_pubkey,
+    );
+    instruction.accounts.splice(
+        0..0,
+        vec![
+            AccountMeta::new(protocol_liquidity_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner_pubkey, true),
+        ],
+    );
+    instruction.data = LendingInstruction::DepositProtocolLiquidity { liquidity_amount }.pack();
+    instruction
+}
+
+/// Creates an 'AnnounceProtocolLiquidityWithdrawal' instruction.
+pub fn announce_protocol_liquidity_withdrawal(
+    program_id: Pubkey,
+    collateral_amount: u64,
+    protocol_liquidity_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(protocol_liquidity_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner_pubkey, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: LendingInstruction::AnnounceProtocolLiquidityWithdrawal { collateral_amount }.pack(),
+    }
+}
+
+/// Creates a 'WithdrawProtocolLiquidity' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_protocol_liquidity(
+    program_id: Pubkey,
+    protocol_liquidity_pubkey: Pubkey,
+    protocol_collateral_supply_pubkey: Pubkey,
+    destination_liquidity_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    reserve_collateral_mint_pubkey: Pubkey,
+    reserve_liquidity_supply_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+) -> Instruction {
+    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
+        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
+        &program_id,
+    );
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(protocol_liquidity_pubkey, false),
+            AccountMeta::new(protocol_collateral_supply_pubkey, false),
+            AccountMeta::new(destination_liquidity_pubkey, false),
+            AccountMeta::new(reserve_pubkey, false),
+            AccountMeta::new(reserve_collateral_mint_pubkey, false),
+            AccountMeta::new(reserve_liquidity_supply_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_authority_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner_pubkey, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: LendingInstruction::WithdrawProtocolLiquidity.pack(),
+    }
+}
+
+/// Seed of the rate history account of a reserve
+pub const RESERVE_RATE_HISTORY_SEED: &[u8] = b"rate-history";
+
+/// Finds the rate history account of a reserve
+pub fn find_reserve_rate_history_address(
+    program_id: &Pubkey,
+    reserve_pubkey: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RESERVE_RATE_HISTORY_SEED, reserve_pubkey.as_ref()],
+        program_id,
+    )
+}
+
+/// Creates an 'InitReserveRateHistory' instruction.
+pub fn init_reserve_rate_history(
+    program_id: Pubkey,
+    reserve_pubkey: Pubkey,
+    payer_pubkey: Pubkey,
+) -> Instruction {
+    let (reserve_rate_history_pubkey, _bump_seed) =
+        find_reserve_rate_history_address(&program_id, &reserve_pubkey);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(reserve_rate_history_pubkey, false),
+            AccountMeta::new_readonly(reserve_pubkey, false),
+            AccountMeta::new(payer_pubkey, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: LendingInstruction::InitReserveRateHistory.pack(),
+    }
+}
+
+/// Appends the rate history account of a reserve to a 'RefreshReserve'
+/// instruction.
+pub fn with_reserve_rate_history(
+    mut instruction: Instruction,
+    reserve_pubkey: Pubkey,
+) -> Instruction {
+    let (reserve_rate_history_pubkey, _bump_seed) =
+        find_reserve_rate_history_address(&instruction.program_id, &reserve_pubkey);
+    instruction
+        .accounts
+        .push(AccountMeta::new(reserve_rate_history_pubkey, false));
+    instruction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_lending_market() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let oracle_program_id = Pubkey::new_unique();
+        let currency = "USD";
+        let mut quote_currency = [0u8; 32];
+        quote_currency[0..currency.len()].clone_from_slice(currency.as_bytes());
+        let instruction = init_lending_market(
+            program_id,
+            owner,
+            quote_currency,
+            lending_market_pubkey,
+            oracle_program_id,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::InitLendingMarket {
+                owner,
+                quote_currency,
+            }
+            .pack()
+        );
+    }
+
+    #[test]
+    fn test_set_lending_market_owner() {
+        let program_id = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner = Pubkey::new_unique();
+        let new_owner = Pubkey::new_unique();
+        let instruction = set_lending_market_owner(
+            program_id,
+            lending_market_pubkey,
+            lending_market_owner,
+            new_owner,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 2);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::SetLendingMarketOwner { new_owner }.pack()
+        );
+    }
+
+    #[test]
+    fn test_init_reserve() {
+        let program_id = Pubkey::new_unique();
+        let liquidity_amount = u64::MAX;
+        let config = ReserveConfig {
+            optimal_utilization_rate: 50,
+            loan_to_value_ratio: 1,
+            liquidation_bonus: 10,
+            liquidation_threshold: 5,
+            min_borrow_rate: 2,
+            optimal_borrow_rate: 4,
+            max_borrow_rate: 10,
+            fees: ReserveFees {
+                borrow_fee_wad: 1,
+                flash_loan_fee_wad: 3,
+                host_fee_percentage: 1,
+            },
+            max_oracle_age_slots: 0,
+            max_oracle_confidence_bps: 0,
+            deposit_value_limit: 0,
+            borrow_value_limit: 0,
+        };
+        let source_liquidity_pubkey = Pubkey::new_unique();
+        let destination_collateral_pubkey = Pubkey::new_unique();
+        let reserve_pubkey = Pubkey::new_unique();
+        let reserve_liquidity_mint_pubkey = Pubkey::new_unique();
+        let reserve_liquidity_supply_pubkey = Pubkey::new_unique();
+        let reserve_liquidity_fee_receiver_pubkey = Pubkey::new_unique();
+        let reserve_collateral_mint_pubkey = Pubkey::new_unique();
+        let reserve_collateral_supply_pubkey = Pubkey::new_unique();
+        let pyth_product_pubkey = Pubkey::new_unique();
+        let pyth_price_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner_pubkey = Pubkey::new_unique();
+        let user_transfer_authority_pubkey = Pubkey::new_unique();
+        let instruction = init_reserve(
+            program_id,
+            liquidity_amount,
+            config,
+            source_liquidity_pubkey,
+            destination_collateral_pubkey,
+            reserve_pubkey,
+            reserve_liquidity_mint_pubkey,
+            reserve_liquidity_supply_pubkey,
+            reserve_liquidity_fee_receiver_pubkey,
+            reserve_collateral_mint_pubkey,
+            reserve_collateral_supply_pubkey,
+            pyth_product_pubkey,
+            pyth_price_pubkey,
+            lending_market_pubkey,
+            lending_market_owner_pubkey,
+            user_transfer_authority_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 17);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::InitReserve {
+                liquidity_amount,
+                config,
+            }
+            .pack()
+        );
+    }
+
+    #[test]
+    fn test_refresh_reserve() {
+        let program_id = Pubkey::new_unique();
+        let reserve_pubkey = Pubkey::new_unique();
         let reserve_liquidity_oracle_pubkey = Pubkey::new_unique();
         let instruction =
             refresh_reserve(program_id, reserve_pubkey, reserve_liquidity_oracle_pubkey);
         assert_eq!(instruction.program_id, program_id);
         assert_eq!(instruction.accounts.len(), 3);
-        assert_eq!(instruction.data, LendingInstruction::RefreshReserve.pack());
+        assert_eq!(instruction.data, LendingInstruction::RefreshReserve.pack());
+    }
+
+    #[test]
+    fn test_deposit_reserve_liquidity() {
+        let program_id = Pubkey::new_unique();
+        let liquidity_amount = u64::MAX;
+        let source_liquidity_pubkey = Pubkey::new_unique();
+        let destination_collateral_pubkey = Pubkey::new_unique();
+        let reserve_pubkey = Pubkey::new_unique();
+        let reserve_liquidity_supply_pubkey = Pubkey::new_unique();
+        let reserve_collateral_mint_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let user_transfer_authority_pubkey = Pubkey::new_unique();
+        let instruction = deposit_reserve_liquidity(
+            program_id,
+            liquidity_amount,
+            source_liquidity_pubkey,
+            destination_collateral_pubkey,
+            reserve_pubkey,
+            reserve_liquidity_supply_pubkey,
+            reserve_collateral_mint_pubkey,
+            lending_market_pubkey,
+            user_transfer_authority_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 10);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::DepositReserveLiquidity { liquidity_amount }.pack()
+        );
+    }
+
+    #[test]
+    fn test_redeem_reserve_collateral() {
+        let program_id = Pubkey::new_unique();
+        let collateral_amount = u64::MAX;
+        let source_collateral_pubkey = Pubkey::new_unique();
+        let destination_liquidity_pubkey = Pubkey::new_unique();
+        let reserve_pubkey = Pubkey::new_unique();
+        let reserve_collateral_mint_pubkey = Pubkey::new_unique();
+        let reserve_liquidity_supply_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let user_transfer_authority_pubkey = Pubkey::new_unique();
+        let instruction = redeem_reserve_collateral(
+            program_id,
+            collateral_amount,
+            source_collateral_pubkey,
+            destination_liquidity_pubkey,
+            reserve_pubkey,
+            reserve_collateral_mint_pubkey,
+            reserve_liquidity_supply_pubkey,
+            lending_market_pubkey,
+            user_transfer_authority_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 10);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::RedeemReserveCollateral { collateral_amount }.pack()
+        );
+    }
+
+    #[test]
+    fn test_init_obligation() {
+        let program_id = Pubkey::new_unique();
+        let obligation_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let obligation_owner_pubkey = Pubkey::new_unique();
+        let instruction = init_obligation(
+            program_id,
+            obligation_pubkey,
+            lending_market_pubkey,
+            obligation_owner_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 6);
+        assert_eq!(instruction.data, LendingInstruction::InitObligation.pack());
+    }
+
+    #[test]
+    fn test_refresh_obligation() {
+        let program_id = Pubkey::new_unique();
+        let obligation_pubkey = Pubkey::new_unique();
+        let reserve_pubkeys = vec![Pubkey::new_unique()];
+        let instruction = refresh_obligation(program_id, obligation_pubkey, reserve_pubkeys);
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 3);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::RefreshObligation.pack()
+        );
+    }
+
+    #[test]
+    fn test_deposit_obligation_collateral() {
+        let program_id = Pubkey::new_unique();
+        let collateral_amount = u64::MAX;
+        let source_collateral_pubkey = Pubkey::new_unique();
+        let destination_collateral_pubkey = Pubkey::new_unique();
+        let deposit_reserve_pubkey = Pubkey::new_unique();
+        let obligation_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let obligation_owner_pubkey = Pubkey::new_unique();
+        let user_transfer_authority_pubkey = Pubkey::new_unique();
+        let instruction = deposit_obligation_collateral(
+            program_id,
+            collateral_amount,
+            source_collateral_pubkey,
+            destination_collateral_pubkey,
+            deposit_reserve_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            obligation_owner_pubkey,
+            user_transfer_authority_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 9);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::DepositObligationCollateral { collateral_amount }.pack()
+        );
+    }
+
+    #[test]
+    fn test_withdraw_obligation_collateral() {
+        let program_id = Pubkey::new_unique();
+        let collateral_amount = u64::MAX;
+        let source_collateral_pubkey = Pubkey::new_unique();
+        let destination_collateral_pubkey = Pubkey::new_unique();
+        let withdraw_reserve_pubkey = Pubkey::new_unique();
+        let obligation_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let obligation_owner_pubkey = Pubkey::new_unique();
+        let instruction = withdraw_obligation_collateral(
+            program_id,
+            collateral_amount,
+            source_collateral_pubkey,
+            destination_collateral_pubkey,
+            withdraw_reserve_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            obligation_owner_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 9);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::WithdrawObligationCollateral { collateral_amount }.pack()
+        );
+    }
+
+    #[test]
+    fn test_borrow_obligation_liquidity() {
+        let program_id = Pubkey::new_unique();
+        let liquidity_amount = u64::MAX;
+        let source_liquidity_pubkey = Pubkey::new_unique();
+        let destination_liquidity_pubkey = Pubkey::new_unique();
+        let borrow_reserve_pubkey = Pubkey::new_unique();
+        let borrow_reserve_liquidity_fee_receiver_pubkey = Pubkey::new_unique();
+        let obligation_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let obligation_owner_pubkey = Pubkey::new_unique();
+        let host_fee_receiver_pubkey = Some(Pubkey::new_unique());
+        let instruction = borrow_obligation_liquidity(
+            program_id,
+            liquidity_amount,
+            None,
+            source_liquidity_pubkey,
+            destination_liquidity_pubkey,
+            borrow_reserve_pubkey,
+            borrow_reserve_liquidity_fee_receiver_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            obligation_owner_pubkey,
+            host_fee_receiver_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 11);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::BorrowObligationLiquidity {
+                liquidity_amount,
+                slippage_limit: 0
+            }
+            .pack()
+        );
+    }
+
+    #[test]
+    fn test_repay_obligation_liquidity() {
+        let program_id = Pubkey::new_unique();
+        let liquidity_amount = u64::MAX;
+        let source_liquidity_pubkey = Pubkey::new_unique();
+        let destination_liquidity_pubkey = Pubkey::new_unique();
+        let repay_reserve_pubkey = Pubkey::new_unique();
+        let obligation_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let user_transfer_authority_pubkey = Pubkey::new_unique();
+        let instruction = repay_obligation_liquidity(
+            program_id,
+            liquidity_amount,
+            source_liquidity_pubkey,
+            destination_liquidity_pubkey,
+            repay_reserve_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            user_transfer_authority_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 8);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::RepayObligationLiquidity { liquidity_amount }.pack()
+        );
+    }
+
+    #[test]
+    fn test_liquidate_obligation() {
+        let program_id = Pubkey::new_unique();
+        let liquidity_amount = u64::MAX;
+        let source_liquidity_pubkey = Pubkey::new_unique();
+        let destination_collateral_pubkey = Pubkey::new_unique();
+        let repay_reserve_pubkey = Pubkey::new_unique();
+        let repay_reserve_liquidity_supply_pubkey = Pubkey::new_unique();
+        let withdraw_reserve_pubkey = Pubkey::new_unique();
+        let withdraw_reserve_collateral_supply_pubkey = Pubkey::new_unique();
+        let obligation_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let user_transfer_authority_pubkey = Pubkey::new_unique();
+        let instruction = liquidate_obligation(
+            program_id,
+            liquidity_amount,
+            source_liquidity_pubkey,
+            destination_collateral_pubkey,
+            repay_reserve_pubkey,
+            repay_reserve_liquidity_supply_pubkey,
+            withdraw_reserve_pubkey,
+            withdraw_reserve_collateral_supply_pubkey,
+            obligation_pubkey,
+            lending_market_pubkey,
+            user_transfer_authority_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 12);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::LiquidateObligation { liquidity_amount }.pack()
+        );
+    }
+
+    #[test]
+    fn test_flash_loan() {
+        let program_id = Pubkey::new_unique();
+        let amount = u64::MAX;
+        let source_liquidity_pubkey = Pubkey::new_unique();
+        let destination_liquidity_pubkey = Pubkey::new_unique();
+        let repay_reserve_pubkey = Pubkey::new_unique();
+        let reserve_liquidity_fee_receiver_pubkey = Pubkey::new_unique();
+        let host_fee_receiver_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let flash_loan_receiver_program_id = Pubkey::new_unique();
+        let account_meta = AccountMeta {
+            pubkey: Pubkey::new_unique(),
+            is_signer: true,
+            is_writable: false,
+        };
+        let flash_loan_receiver_program_accounts = vec![account_meta];
+        let instruction = flash_loan(
+            program_id,
+            amount,
+            source_liquidity_pubkey,
+            destination_liquidity_pubkey,
+            repay_reserve_pubkey,
+            reserve_liquidity_fee_receiver_pubkey,
+            host_fee_receiver_pubkey,
+            lending_market_pubkey,
+            flash_loan_receiver_program_id,
+            flash_loan_receiver_program_accounts,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 10);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::FlashLoan { amount }.pack()
+        );
+    }
+
+    #[test]
+    fn test_modify_reserve_config() {
+        let program_id = Pubkey::new_unique();
+        let config = ReserveConfig {
+            optimal_utilization_rate: 60,
+            loan_to_value_ratio: 1,
+            liquidation_bonus: 10,
+            liquidation_threshold: 5,
+            min_borrow_rate: 2,
+            optimal_borrow_rate: 4,
+            max_borrow_rate: 10,
+            fees: ReserveFees {
+                borrow_fee_wad: 1,
+                flash_loan_fee_wad: 3,
+                host_fee_percentage: 1,
+            },
+            max_oracle_age_slots: 0,
+            max_oracle_confidence_bps: 0,
+            deposit_value_limit: 0,
+            borrow_value_limit: 0,
+        };
+        let reserve_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner_pubkey = Pubkey::new_unique();
+        let instruction = modify_reserve_config(
+            program_id,
+            config,
+            reserve_pubkey,
+            lending_market_pubkey,
+            lending_market_owner_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 3);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::ModifyReserveConfig {
+                new_config: config,
+                legacy_config: false,
+            }
+            .pack()
+        );
+    }
+
+    #[test]
+    fn test_unpack_legacy_reserve_config() {
+        let config = ReserveConfig {
+            optimal_utilization_rate: 60,
+            loan_to_value_ratio: 1,
+            liquidation_bonus: 10,
+            liquidation_threshold: 5,
+            min_borrow_rate: 2,
+            optimal_borrow_rate: 4,
+            max_borrow_rate: 10,
+            fees: ReserveFees {
+                borrow_fee_wad: 1,
+                flash_loan_fee_wad: 3,
+                host_fee_percentage: 1,
+            },
+            max_oracle_age_slots: 0,
+            max_oracle_confidence_bps: 0,
+            deposit_value_limit: 0,
+            borrow_value_limit: 0,
+        };
+        let data = LendingInstruction::ModifyReserveConfig {
+            new_config: config,
+            legacy_config: true,
+        }
+        .pack();
+        assert_eq!(
+            LendingInstruction::unpack(&data).unwrap(),
+            LendingInstruction::ModifyReserveConfig {
+                new_config: config,
+                legacy_config: true,
+            }
+        );
+
+        // truncated or extended configs are rejected
+        let full_data = LendingInstruction::ModifyReserveConfig {
+            new_config: config,
+            legacy_config: false,
+        }
+        .pack();
+        assert_eq!(full_data.len(), data.len() + RESERVE_CONFIG_EXTENSION_LEN);
+        assert!(LendingInstruction::unpack(&full_data[..full_data.len() - 1]).is_err());
+        assert!(LendingInstruction::unpack(&full_data[..data.len() + 8]).is_err());
+        assert!(LendingInstruction::unpack(&[full_data.as_slice(), &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_set_host_fee_percentage_cap() {
+        let program_id = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner_pubkey = Pubkey::new_unique();
+        let host_fee_percentage_cap = 10;
+        let instruction = set_host_fee_percentage_cap(
+            program_id,
+            host_fee_percentage_cap,
+            lending_market_pubkey,
+            lending_market_owner_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 2);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::SetHostFeePercentageCap {
+                host_fee_percentage_cap
+            }
+            .pack()
+        );
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::SetHostFeePercentageCap {
+                host_fee_percentage_cap
+            }
+        );
+    }
+
+    #[test]
+    fn test_init_host_fee_registry() {
+        let program_id = Pubkey::new_unique();
+        let host_fee_registry_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner_pubkey = Pubkey::new_unique();
+        let instruction = init_host_fee_registry(
+            program_id,
+            host_fee_registry_pubkey,
+            lending_market_pubkey,
+            lending_market_owner_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(
+            instruction.data,
+            LendingInstruction::InitHostFeeRegistry.pack()
+        );
+    }
+
+    #[test]
+    fn test_set_host_fee_share() {
+        let program_id = Pubkey::new_unique();
+        let host_fee_registry_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner_pubkey = Pubkey::new_unique();
+        let host_fee_receiver_pubkey = Pubkey::new_unique();
+        let host_fee_percentage = 15;
+        let instruction = set_host_fee_share(
+            program_id,
+            host_fee_percentage,
+            host_fee_registry_pubkey,
+            lending_market_pubkey,
+            lending_market_owner_pubkey,
+            host_fee_receiver_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::SetHostFeeShare {
+                host_fee_percentage
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_liquidation_auction_config() {
+        let program_id = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner_pubkey = Pubkey::new_unique();
+        for config in [
+            Some(LiquidationAuctionConfig {
+                min_borrowed_value: 1_000_000,
+                duration_slots: 150,
+                max_bonus: 15,
+            }),
+            None,
+        ] {
+            let instruction = set_liquidation_auction_config(
+                program_id,
+                config,
+                lending_market_pubkey,
+                lending_market_owner_pubkey,
+            );
+            assert_eq!(instruction.program_id, program_id);
+            assert_eq!(instruction.accounts.len(), 2);
+            assert_eq!(
+                LendingInstruction::unpack(&instruction.data).unwrap(),
+                LendingInstruction::SetLiquidationAuctionConfig { config }
+            );
+        }
+    }
+
+    #[test]
+    fn test_start_liquidation_auction() {
+        let program_id = Pubkey::new_unique();
+        let instruction = start_liquidation_auction(
+            program_id,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 5);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::StartLiquidationAuction
+        );
+    }
+
+    #[test]
+    fn test_fill_liquidation_auction() {
+        let program_id = Pubkey::new_unique();
+        let liquidity_amount = 1000;
+        let liquidation_auction_pubkey = Pubkey::new_unique();
+        let instruction = fill_liquidation_auction(
+            program_id,
+            liquidity_amount,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            liquidation_auction_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 13);
+        assert_eq!(instruction.accounts[12].pubkey, liquidation_auction_pubkey);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::FillLiquidationAuction { liquidity_amount }
+        );
+    }
+
+    #[test]
+    fn test_deposit_reserve_liquidity_native() {
+        let program_id = Pubkey::new_unique();
+        let liquidity_amount = 1000;
+        let user_transfer_authority_pubkey = Pubkey::new_unique();
+        let instruction = deposit_reserve_liquidity_native(
+            program_id,
+            liquidity_amount,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            user_transfer_authority_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 12);
+        assert_eq!(instruction.accounts[0].pubkey, spl_token::native_mint::id());
+        assert_eq!(instruction.accounts[1].pubkey, system_program::id());
+        assert_eq!(
+            instruction.accounts[2].pubkey,
+            find_native_sol_address(&program_id, &user_transfer_authority_pubkey).0
+        );
+        assert_eq!(
+            instruction.accounts[9].pubkey,
+            user_transfer_authority_pubkey
+        );
+        assert!(instruction.accounts[9].is_signer);
+        assert!(instruction.accounts[9].is_writable);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::DepositReserveLiquidityNative { liquidity_amount }
+        );
+    }
+
+    #[test]
+    fn test_borrow_obligation_liquidity_native() {
+        let program_id = Pubkey::new_unique();
+        let liquidity_amount = 1000;
+        let obligation_owner_pubkey = Pubkey::new_unique();
+        let instruction = borrow_obligation_liquidity_native(
+            program_id,
+            liquidity_amount,
+            Some(900),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            obligation_owner_pubkey,
+            None,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 12);
+        assert_eq!(
+            instruction.accounts[3].pubkey,
+            find_native_sol_address(&program_id, &obligation_owner_pubkey).0
+        );
+        assert!(instruction.accounts[9].is_writable);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::BorrowObligationLiquidityNative {
+                liquidity_amount,
+                slippage_limit: 900,
+            }
+        );
     }
 
     #[test]
-    fn test_deposit_reserve_liquidity() {
+    fn test_set_lending_market_admins() {
         let program_id = Pubkey::new_unique();
-        let liquidity_amount = u64::MAX;
-        let source_liquidity_pubkey = Pubkey::new_unique();
-        let destination_collateral_pubkey = Pubkey::new_unique();
-        let reserve_pubkey = Pubkey::new_unique();
-        let reserve_liquidity_supply_pubkey = Pubkey::new_unique();
-        let reserve_collateral_mint_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let user_transfer_authority_pubkey = Pubkey::new_unique();
-        let instruction = deposit_reserve_liquidity(
+        let risk_admin = Some(Pubkey::new_unique());
+        let instruction = set_lending_market_admins(
             program_id,
-            liquidity_amount,
-            source_liquidity_pubkey,
-            destination_collateral_pubkey,
-            reserve_pubkey,
-            reserve_liquidity_supply_pubkey,
-            reserve_collateral_mint_pubkey,
-            lending_market_pubkey,
-            user_transfer_authority_pubkey,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            risk_admin,
+            None,
         );
         assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 10);
+        assert_eq!(instruction.accounts.len(), 2);
+        assert!(instruction.accounts[1].is_signer);
         assert_eq!(
-            instruction.data,
-            LendingInstruction::DepositReserveLiquidity { liquidity_amount }.pack()
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::SetLendingMarketAdmins {
+                risk_admin,
+                ops_admin: None,
+            }
         );
     }
 
     #[test]
-    fn test_redeem_reserve_collateral() {
+    fn test_set_reserve_oracle() {
         let program_id = Pubkey::new_unique();
-        let collateral_amount = u64::MAX;
-        let source_collateral_pubkey = Pubkey::new_unique();
-        let destination_liquidity_pubkey = Pubkey::new_unique();
-        let reserve_pubkey = Pubkey::new_unique();
-        let reserve_collateral_mint_pubkey = Pubkey::new_unique();
-        let reserve_liquidity_supply_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let user_transfer_authority_pubkey = Pubkey::new_unique();
-        let instruction = redeem_reserve_collateral(
+        let pyth_price_pubkey = Pubkey::new_unique();
+        let instruction = set_reserve_oracle(
             program_id,
-            collateral_amount,
-            source_collateral_pubkey,
-            destination_liquidity_pubkey,
-            reserve_pubkey,
-            reserve_collateral_mint_pubkey,
-            reserve_liquidity_supply_pubkey,
-            lending_market_pubkey,
-            user_transfer_authority_pubkey,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            pyth_price_pubkey,
         );
         assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 10);
+        assert_eq!(instruction.accounts.len(), 6);
+        assert_eq!(instruction.accounts[4].pubkey, pyth_price_pubkey);
         assert_eq!(
-            instruction.data,
-            LendingInstruction::RedeemReserveCollateral { collateral_amount }.pack()
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::SetReserveOracle
         );
     }
 
     #[test]
-    fn test_init_obligation() {
+    fn test_set_liquidation_grace_period() {
         let program_id = Pubkey::new_unique();
-        let obligation_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let obligation_owner_pubkey = Pubkey::new_unique();
-        let instruction = init_obligation(
+        let instruction = set_liquidation_grace_period(
             program_id,
-            obligation_pubkey,
-            lending_market_pubkey,
-            obligation_owner_pubkey,
+            150,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
         );
         assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 6);
-        assert_eq!(instruction.data, LendingInstruction::InitObligation.pack());
+        assert_eq!(instruction.accounts.len(), 2);
+        assert!(instruction.accounts[0].is_writable);
+        assert!(instruction.accounts[1].is_signer);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::SetLiquidationGracePeriod {
+                liquidation_grace_slots: 150,
+            }
+        );
     }
 
     #[test]
-    fn test_refresh_obligation() {
+    fn test_init_obligation_health() {
         let program_id = Pubkey::new_unique();
         let obligation_pubkey = Pubkey::new_unique();
-        let reserve_pubkeys = vec![Pubkey::new_unique()];
-        let instruction = refresh_obligation(program_id, obligation_pubkey, reserve_pubkeys);
+        let instruction =
+            init_obligation_health(program_id, obligation_pubkey, Pubkey::new_unique());
         assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 3);
+        assert_eq!(instruction.accounts.len(), 5);
         assert_eq!(
-            instruction.data,
-            LendingInstruction::RefreshObligation.pack()
+            instruction.accounts[0].pubkey,
+            find_obligation_health_address(&program_id, &obligation_pubkey).0
+        );
+        assert!(instruction.accounts[2].is_signer);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::InitObligationHealth
         );
     }
 
     #[test]
-    fn test_deposit_obligation_collateral() {
+    fn test_with_obligation_health() {
         let program_id = Pubkey::new_unique();
-        let collateral_amount = u64::MAX;
-        let source_collateral_pubkey = Pubkey::new_unique();
-        let destination_collateral_pubkey = Pubkey::new_unique();
-        let deposit_reserve_pubkey = Pubkey::new_unique();
         let obligation_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let obligation_owner_pubkey = Pubkey::new_unique();
-        let user_transfer_authority_pubkey = Pubkey::new_unique();
-        let instruction = deposit_obligation_collateral(
-            program_id,
-            collateral_amount,
-            source_collateral_pubkey,
-            destination_collateral_pubkey,
-            deposit_reserve_pubkey,
+        let obligation_health_pubkey =
+            find_obligation_health_address(&program_id, &obligation_pubkey).0;
+
+        let instruction = with_obligation_health(
+            refresh_obligation(program_id, obligation_pubkey, vec![Pubkey::new_unique()]),
             obligation_pubkey,
-            lending_market_pubkey,
-            obligation_owner_pubkey,
-            user_transfer_authority_pubkey,
         );
-        assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 9);
-        assert_eq!(
-            instruction.data,
-            LendingInstruction::DepositObligationCollateral { collateral_amount }.pack()
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(instruction.accounts[3].pubkey, obligation_health_pubkey);
+        assert!(instruction.accounts[3].is_writable);
+
+        let instruction = with_obligation_health(
+            liquidate_obligation(
+                program_id,
+                1,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                obligation_pubkey,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ),
+            obligation_pubkey,
         );
+        assert_eq!(instruction.accounts.len(), 13);
+        assert_eq!(instruction.accounts[12].pubkey, obligation_health_pubkey);
+        assert!(!instruction.accounts[12].is_writable);
     }
 
     #[test]
-    fn test_withdraw_obligation_collateral() {
+    fn test_init_margin_account() {
         let program_id = Pubkey::new_unique();
-        let collateral_amount = u64::MAX;
-        let source_collateral_pubkey = Pubkey::new_unique();
-        let destination_collateral_pubkey = Pubkey::new_unique();
-        let withdraw_reserve_pubkey = Pubkey::new_unique();
-        let obligation_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let obligation_owner_pubkey = Pubkey::new_unique();
-        let instruction = withdraw_obligation_collateral(
+        let instruction = init_margin_account(
             program_id,
-            collateral_amount,
-            source_collateral_pubkey,
-            destination_collateral_pubkey,
-            withdraw_reserve_pubkey,
-            obligation_pubkey,
-            lending_market_pubkey,
-            obligation_owner_pubkey,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
         );
         assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 9);
+        assert_eq!(instruction.accounts.len(), 5);
+        assert!(instruction.accounts[0].is_writable);
+        assert!(instruction.accounts[2].is_signer);
         assert_eq!(
-            instruction.data,
-            LendingInstruction::WithdrawObligationCollateral { collateral_amount }.pack()
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::InitMarginAccount
         );
     }
 
     #[test]
-    fn test_borrow_obligation_liquidity() {
+    fn test_borrow_obligation_liquidity_with_margin() {
         let program_id = Pubkey::new_unique();
-        let liquidity_amount = u64::MAX;
-        let source_liquidity_pubkey = Pubkey::new_unique();
-        let destination_liquidity_pubkey = Pubkey::new_unique();
-        let borrow_reserve_pubkey = Pubkey::new_unique();
-        let borrow_reserve_liquidity_fee_receiver_pubkey = Pubkey::new_unique();
-        let obligation_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let obligation_owner_pubkey = Pubkey::new_unique();
-        let host_fee_receiver_pubkey = Some(Pubkey::new_unique());
-        let instruction = borrow_obligation_liquidity(
+        let liquidity_amount = 1000;
+        let margin_account_pubkey = Pubkey::new_unique();
+        let instruction = borrow_obligation_liquidity_with_margin(
             program_id,
             liquidity_amount,
+            Some(900),
+            margin_account_pubkey,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
             None,
-            source_liquidity_pubkey,
-            destination_liquidity_pubkey,
-            borrow_reserve_pubkey,
-            borrow_reserve_liquidity_fee_receiver_pubkey,
-            obligation_pubkey,
-            lending_market_pubkey,
-            obligation_owner_pubkey,
-            host_fee_receiver_pubkey,
         );
         assert_eq!(instruction.program_id, program_id);
         assert_eq!(instruction.accounts.len(), 11);
+        assert_eq!(instruction.accounts[0].pubkey, margin_account_pubkey);
+        assert!(instruction.accounts[0].is_writable);
+        assert!(instruction.accounts[8].is_signer);
         assert_eq!(
-            instruction.data,
-            LendingInstruction::BorrowObligationLiquidity {
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::BorrowObligationLiquidityWithMargin {
                 liquidity_amount,
-                slippage_limit: 0
+                slippage_limit: 900,
             }
-            .pack()
         );
     }
 
     #[test]
-    fn test_repay_obligation_liquidity() {
+    fn test_liquidate_margin_obligation() {
         let program_id = Pubkey::new_unique();
-        let liquidity_amount = u64::MAX;
-        let source_liquidity_pubkey = Pubkey::new_unique();
-        let destination_liquidity_pubkey = Pubkey::new_unique();
-        let repay_reserve_pubkey = Pubkey::new_unique();
-        let obligation_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let user_transfer_authority_pubkey = Pubkey::new_unique();
-        let instruction = repay_obligation_liquidity(
+        let withdraw_obligation_pubkey = Pubkey::new_unique();
+        let instruction = liquidate_margin_obligation(
             program_id,
-            liquidity_amount,
-            source_liquidity_pubkey,
-            destination_liquidity_pubkey,
-            repay_reserve_pubkey,
-            obligation_pubkey,
-            lending_market_pubkey,
-            user_transfer_authority_pubkey,
+            1,
+            Pubkey::new_unique(),
+            withdraw_obligation_pubkey,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
         );
         assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 8);
+        assert_eq!(instruction.accounts.len(), 14);
+        assert_eq!(instruction.accounts[1].pubkey, withdraw_obligation_pubkey);
+        assert!(instruction.accounts[1].is_writable);
         assert_eq!(
-            instruction.data,
-            LendingInstruction::RepayObligationLiquidity { liquidity_amount }.pack()
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::LiquidateMarginObligation {
+                liquidity_amount: 1
+            }
         );
     }
 
     #[test]
-    fn test_liquidate_obligation() {
+    fn test_deposit_protocol_liquidity() {
         let program_id = Pubkey::new_unique();
-        let liquidity_amount = u64::MAX;
-        let source_liquidity_pubkey = Pubkey::new_unique();
-        let destination_collateral_pubkey = Pubkey::new_unique();
-        let repay_reserve_pubkey = Pubkey::new_unique();
-        let repay_reserve_liquidity_supply_pubkey = Pubkey::new_unique();
-        let withdraw_reserve_pubkey = Pubkey::new_unique();
-        let withdraw_reserve_collateral_supply_pubkey = Pubkey::new_unique();
-        let obligation_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let user_transfer_authority_pubkey = Pubkey::new_unique();
-        let instruction = liquidate_obligation(
+        let protocol_liquidity_pubkey = Pubkey::new_unique();
+        let protocol_collateral_supply_pubkey = Pubkey::new_unique();
+        let instruction = deposit_protocol_liquidity(
             program_id,
-            liquidity_amount,
-            source_liquidity_pubkey,
-            destination_collateral_pubkey,
-            repay_reserve_pubkey,
-            repay_reserve_liquidity_supply_pubkey,
-            withdraw_reserve_pubkey,
-            withdraw_reserve_collateral_supply_pubkey,
-            obligation_pubkey,
-            lending_market_pubkey,
-            user_transfer_authority_pubkey,
+            1,
+            protocol_liquidity_pubkey,
+            Pubkey::new_unique(),
+            protocol_collateral_supply_pubkey,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
         );
         assert_eq!(instruction.program_id, program_id);
         assert_eq!(instruction.accounts.len(), 12);
+        assert_eq!(instruction.accounts[0].pubkey, protocol_liquidity_pubkey);
+        assert!(instruction.accounts[1].is_signer);
         assert_eq!(
-            instruction.data,
-            LendingInstruction::LiquidateObligation { liquidity_amount }.pack()
+            instruction.accounts[3].pubkey,
+            protocol_collateral_supply_pubkey
+        );
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::DepositProtocolLiquidity {
+                liquidity_amount: 1
+            }
         );
     }
 
     #[test]
-    fn test_flash_loan() {
+    fn test_announce_protocol_liquidity_withdrawal() {
         let program_id = Pubkey::new_unique();
-        let amount = u64::MAX;
-        let source_liquidity_pubkey = Pubkey::new_unique();
-        let destination_liquidity_pubkey = Pubkey::new_unique();
-        let repay_reserve_pubkey = Pubkey::new_unique();
-        let reserve_liquidity_fee_receiver_pubkey = Pubkey::new_unique();
-        let host_fee_receiver_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let flash_loan_receiver_program_id = Pubkey::new_unique();
-        let account_meta = AccountMeta {
-            pubkey: Pubkey::new_unique(),
-            is_signer: true,
-            is_writable: false,
-        };
-        let flash_loan_receiver_program_accounts = vec![account_meta];
-        let instruction = flash_loan(
+        let instruction = announce_protocol_liquidity_withdrawal(
             program_id,
-            amount,
-            source_liquidity_pubkey,
-            destination_liquidity_pubkey,
-            repay_reserve_pubkey,
-            reserve_liquidity_fee_receiver_pubkey,
-            host_fee_receiver_pubkey,
-            lending_market_pubkey,
-            flash_loan_receiver_program_id,
-            flash_loan_receiver_program_accounts,
+            100,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
         );
         assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 10);
+        assert_eq!(instruction.accounts.len(), 4);
+        assert!(instruction.accounts[0].is_writable);
+        assert!(instruction.accounts[2].is_signer);
         assert_eq!(
-            instruction.data,
-            LendingInstruction::FlashLoan { amount }.pack()
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::AnnounceProtocolLiquidityWithdrawal {
+                collateral_amount: 100
+            }
         );
     }
 
     #[test]
-    fn test_modify_reserve_config() {
+    fn test_init_reserve_rate_history() {
         let program_id = Pubkey::new_unique();
-        let config = ReserveConfig {
-            optimal_utilization_rate: 60,
-            loan_to_value_ratio: 1,
-            liquidation_bonus: 10,
-            liquidation_threshold: 5,
-            min_borrow_rate: 2,
-            optimal_borrow_rate: 4,
-            max_borrow_rate: 10,
-            fees: ReserveFees {
-                borrow_fee_wad: 1,
-                flash_loan_fee_wad: 3,
-                host_fee_percentage: 1,
-            },
-        };
         let reserve_pubkey = Pubkey::new_unique();
-        let lending_market_pubkey = Pubkey::new_unique();
-        let lending_market_owner_pubkey = Pubkey::new_unique();
-        let instruction = modify_reserve_config(
-            program_id,
-            config,
+        let instruction =
+            init_reserve_rate_history(program_id, reserve_pubkey, Pubkey::new_unique());
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(
+            instruction.accounts[0].pubkey,
+            find_reserve_rate_history_address(&program_id, &reserve_pubkey).0
+        );
+        assert!(instruction.accounts[2].is_signer);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::InitReserveRateHistory
+        );
+    }
+
+    #[test]
+    fn test_with_reserve_rate_history() {
+        let program_id = Pubkey::new_unique();
+        let reserve_pubkey = Pubkey::new_unique();
+        let instruction = with_reserve_rate_history(
+            refresh_reserve(program_id, reserve_pubkey, Pubkey::new_unique()),
             reserve_pubkey,
-            lending_market_pubkey,
-            lending_market_owner_pubkey,
         );
-        assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 3);
+        assert_eq!(instruction.accounts.len(), 4);
         assert_eq!(
-            instruction.data,
-            LendingInstruction::ModifyReserveConfig { new_config: config }.pack()
+            instruction.accounts[3].pubkey,
+            find_reserve_rate_history_address(&program_id, &reserve_pubkey).0
         );
+        assert!(instruction.accounts[3].is_writable);
     }
 }
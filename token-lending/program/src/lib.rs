@@ -5,6 +5,7 @@
 
 pub mod entrypoint;
 pub mod error;
+pub mod event;
 pub mod instruction;
 pub mod math;
 pub mod processor;
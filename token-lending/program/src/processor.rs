@@ -3,14 +3,22 @@
 use {
     crate::{
         error::LendingError,
-        instruction::LendingInstruction,
-        math::{Decimal, Rate, TryAdd, TryDiv, TryMul},
+        event::{ObligationAtRiskEvent, ProtocolLiquidityWithdrawalAnnouncedEvent},
+        instruction::{
+            LendingInstruction, NATIVE_SOL_SEED, OBLIGATION_HEALTH_SEED, RESERVE_RATE_HISTORY_SEED,
+        },
+        math::{Decimal, Rate, TryAdd, TryDiv, TryMul, TrySub},
         pyth,
         state::{
             CalculateBorrowResult, CalculateLiquidationResult, CalculateRepayResult,
-            InitLendingMarketParams, InitObligationParams, InitReserveParams, LendingMarket,
-            NewReserveCollateralParams, NewReserveLiquidityParams, Obligation, Reserve,
-            ReserveCollateral, ReserveConfig, ReserveLiquidity,
+            HostFeeRegistry, InitHostFeeRegistryParams, InitLendingMarketParams,
+            InitLiquidationAuctionParams, InitMarginAccountParams, InitObligationHealthParams,
+            InitObligationParams, InitProtocolLiquidityParams, InitReserveParams,
+            InitReserveRateHistoryParams, LendingMarket, LiquidationAuction,
+            LiquidationAuctionConfig, MarginAccount, NewReserveCollateralParams,
+            NewReserveLiquidityParams, Obligation, ObligationHealth, ProtocolLiquidity, Reserve,
+            ReserveCollateral, ReserveConfig, ReserveFees, ReserveLiquidity, ReserveRateHistory,
+            MAX_HOST_FEE_PERCENTAGE, MAX_LIQUIDATION_AUCTION_BONUS,
         },
     },
     num_traits::FromPrimitive,
@@ -24,9 +32,11 @@ use {
         program_error::{PrintProgramError, ProgramError},
         program_pack::{IsInitialized, Pack},
         pubkey::Pubkey,
+        system_instruction, system_program,
         sysvar::{clock::Clock, rent::Rent, Sysvar},
     },
     spl_token::{
+        native_mint,
         solana_program::instruction::AccountMeta,
         state::{Account, Mint},
     },
@@ -81,11 +91,11 @@ pub fn process_instruction(
         }
         LendingInstruction::DepositObligationCollateral { collateral_amount } => {
             msg!("Instruction: Deposit Obligation Collateral");
-            process_deposit_obligation_collateral(program_id, collateral_amount, accounts)
+            process_deposit_obligation_collateral(program_id, collateral_amount, None, accounts)
         }
         LendingInstruction::WithdrawObligationCollateral { collateral_amount } => {
             msg!("Instruction: Withdraw Obligation Collateral");
-            process_withdraw_obligation_collateral(program_id, collateral_amount, accounts)
+            process_withdraw_obligation_collateral(program_id, collateral_amount, None, accounts)
         }
         LendingInstruction::BorrowObligationLiquidity {
             liquidity_amount,
@@ -96,6 +106,7 @@ pub fn process_instruction(
                 program_id,
                 liquidity_amount,
                 slippage_limit,
+                None,
                 accounts,
             )
         }
@@ -105,15 +116,214 @@ pub fn process_instruction(
         }
         LendingInstruction::LiquidateObligation { liquidity_amount } => {
             msg!("Instruction: Liquidate Obligation");
-            process_liquidate_obligation(program_id, liquidity_amount, accounts)
+            process_liquidate_obligation(program_id, liquidity_amount, false, None, accounts)
         }
         LendingInstruction::FlashLoan { amount } => {
             msg!("Instruction: Flash Loan");
             process_flash_loan(program_id, amount, accounts)
         }
-        LendingInstruction::ModifyReserveConfig { new_config } => {
+        LendingInstruction::ModifyReserveConfig {
+            new_config,
+            legacy_config,
+        } => {
             msg!("Instruction: Modify Reserve Config");
-            process_modify_reserve_config(program_id, new_config, accounts)
+            process_modify_reserve_config(program_id, new_config, legacy_config, accounts)
+        }
+        LendingInstruction::SetHostFeePercentageCap {
+            host_fee_percentage_cap,
+        } => {
+            msg!("Instruction: Set Host Fee Percentage Cap");
+            process_set_host_fee_percentage_cap(program_id, host_fee_percentage_cap, accounts)
+        }
+        LendingInstruction::InitHostFeeRegistry => {
+            msg!("Instruction: Init Host Fee Registry");
+            process_init_host_fee_registry(program_id, accounts)
+        }
+        LendingInstruction::SetHostFeeShare {
+            host_fee_percentage,
+        } => {
+            msg!("Instruction: Set Host Fee Share");
+            process_set_host_fee_share(program_id, host_fee_percentage, accounts)
+        }
+        LendingInstruction::SetLiquidationAuctionConfig { config } => {
+            msg!("Instruction: Set Liquidation Auction Config");
+            process_set_liquidation_auction_config(program_id, config, accounts)
+        }
+        LendingInstruction::StartLiquidationAuction => {
+            msg!("Instruction: Start Liquidation Auction");
+            process_start_liquidation_auction(program_id, accounts)
+        }
+        LendingInstruction::FillLiquidationAuction { liquidity_amount } => {
+            msg!("Instruction: Fill Liquidation Auction");
+            process_liquidate_obligation(program_id, liquidity_amount, true, None, accounts)
+        }
+        LendingInstruction::DepositReserveLiquidityNative { liquidity_amount } => {
+            msg!("Instruction: Deposit Reserve Liquidity Native");
+            process_native_sol(
+                program_id,
+                liquidity_amount,
+                0,
+                7,
+                9,
+                accounts,
+                |accounts| {
+                    process_deposit_reserve_liquidity(program_id, liquidity_amount, accounts)
+                },
+            )
+        }
+        LendingInstruction::RedeemReserveCollateralNative { collateral_amount } => {
+            msg!("Instruction: Redeem Reserve Collateral Native");
+            process_native_sol(program_id, 0, 1, 7, 9, accounts, |accounts| {
+                process_redeem_reserve_collateral(program_id, collateral_amount, accounts)
+            })
+        }
+        LendingInstruction::BorrowObligationLiquidityNative {
+            liquidity_amount,
+            slippage_limit,
+        } => {
+            msg!("Instruction: Borrow Obligation Liquidity Native");
+            process_native_sol(program_id, 0, 1, 7, 9, accounts, |accounts| {
+                process_borrow_obligation_liquidity(
+                    program_id,
+                    liquidity_amount,
+                    slippage_limit,
+                    None,
+                    accounts,
+                )
+            })
+        }
+        LendingInstruction::SetLendingMarketAdmins {
+            risk_admin,
+            ops_admin,
+        } => {
+            msg!("Instruction: Set Lending Market Admins");
+            process_set_lending_market_admins(program_id, risk_admin, ops_admin, accounts)
+        }
+        LendingInstruction::SetReserveOracle => {
+            msg!("Instruction: Set Reserve Oracle");
+            process_set_reserve_oracle(program_id, accounts)
+        }
+        LendingInstruction::RepayObligationLiquidityNative { liquidity_amount } => {
+            msg!("Instruction: Repay Obligation Liquidity Native");
+            if liquidity_amount == u64::MAX {
+                msg!("Liquidity amount to repay with native SOL must be specified");
+                return Err(LendingError::InvalidAmount.into());
+            }
+            process_native_sol(
+                program_id,
+                liquidity_amount,
+                0,
+                5,
+                7,
+                accounts,
+                |accounts| {
+                    process_repay_obligation_liquidity(program_id, liquidity_amount, accounts)
+                },
+            )
+        }
+        LendingInstruction::SetLiquidationGracePeriod {
+            liquidation_grace_slots,
+        } => {
+            msg!("Instruction: Set Liquidation Grace Period");
+            process_set_liquidation_grace_period(program_id, liquidation_grace_slots, accounts)
+        }
+        LendingInstruction::InitObligationHealth => {
+            msg!("Instruction: Init Obligation Health");
+            process_init_obligation_health(program_id, accounts)
+        }
+        LendingInstruction::InitMarginAccount => {
+            msg!("Instruction: Init Margin Account");
+            process_init_margin_account(program_id, accounts)
+        }
+        LendingInstruction::AddMarginObligation => {
+            msg!("Instruction: Add Margin Obligation");
+            process_add_margin_obligation(program_id, accounts)
+        }
+        LendingInstruction::RemoveMarginObligation => {
+            msg!("Instruction: Remove Margin Obligation");
+            process_remove_margin_obligation(program_id, accounts)
+        }
+        LendingInstruction::RefreshMarginAccount => {
+            msg!("Instruction: Refresh Margin Account");
+            process_refresh_margin_account(program_id, accounts)
+        }
+        LendingInstruction::DepositObligationCollateralWithMargin { collateral_amount } => {
+            msg!("Instruction: Deposit Obligation Collateral With Margin");
+            let (margin_account_info, accounts) = accounts
+                .split_first()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            process_deposit_obligation_collateral(
+                program_id,
+                collateral_amount,
+                Some(margin_account_info),
+                accounts,
+            )
+        }
+        LendingInstruction::WithdrawObligationCollateralWithMargin { collateral_amount } => {
+            msg!("Instruction: Withdraw Obligation Collateral With Margin");
+            let (margin_account_info, accounts) = accounts
+                .split_first()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            process_withdraw_obligation_collateral(
+                program_id,
+                collateral_amount,
+                Some(margin_account_info),
+                accounts,
+            )
+        }
+        LendingInstruction::BorrowObligationLiquidityWithMargin {
+            liquidity_amount,
+            slippage_limit,
+        } => {
+            msg!("Instruction: Borrow Obligation Liquidity With Margin");
+            let (margin_account_info, accounts) = accounts
+                .split_first()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            process_borrow_obligation_liquidity(
+                program_id,
+                liquidity_amount,
+                slippage_limit,
+                Some(margin_account_info),
+                accounts,
+            )
+        }
+        LendingInstruction::LiquidateMarginObligation { liquidity_amount } => {
+            msg!("Instruction: Liquidate Margin Obligation");
+            let (margin_account_info, accounts) = accounts
+                .split_first()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let (withdraw_obligation_info, accounts) = accounts
+                .split_first()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            process_liquidate_obligation(
+                program_id,
+                liquidity_amount,
+                false,
+                Some((margin_account_info, withdraw_obligation_info)),
+                accounts,
+            )
+        }
+        LendingInstruction::InitProtocolLiquidity {
+            withdrawal_timelock_slots,
+        } => {
+            msg!("Instruction: Init Protocol Liquidity");
+            process_init_protocol_liquidity(program_id, withdrawal_timelock_slots, accounts)
+        }
+        LendingInstruction::DepositProtocolLiquidity { liquidity_amount } => {
+            msg!("Instruction: Deposit Protocol Liquidity");
+            process_deposit_protocol_liquidity(program_id, liquidity_amount, accounts)
+        }
+        LendingInstruction::AnnounceProtocolLiquidityWithdrawal { collateral_amount } => {
+            msg!("Instruction: Announce Protocol Liquidity Withdrawal");
+            process_announce_protocol_liquidity_withdrawal(program_id, collateral_amount, accounts)
+        }
+        LendingInstruction::WithdrawProtocolLiquidity => {
+            msg!("Instruction: Withdraw Protocol Liquidity");
+            process_withdraw_protocol_liquidity(program_id, accounts)
+        }
+        LendingInstruction::InitReserveRateHistory => {
+            msg!("Instruction: Init Reserve Rate History");
+            process_init_reserve_rate_history(program_id, accounts)
         }
     }
 }
@@ -179,6 +389,37 @@ fn process_set_lending_market_owner(
     Ok(())
 }
 
+fn process_set_lending_market_admins(
+    program_id: &Pubkey,
+    risk_admin: Option<Pubkey>,
+    ops_admin: Option<Pubkey>,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+
+    let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    lending_market.risk_admin = risk_admin;
+    lending_market.ops_admin = ops_admin;
+    LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
 fn process_init_reserve(
     program_id: &Pubkey,
     liquidity_amount: u64,
@@ -205,7 +446,7 @@ fn process_init_reserve(
     let pyth_price_info = next_account_info(account_info_iter)?;
     let lending_market_info = next_account_info(account_info_iter)?;
     let lending_market_authority_info = next_account_info(account_info_iter)?;
-    let lending_market_owner_info = next_account_info(account_info_iter)?;
+    let lending_market_ops_admin_info = next_account_info(account_info_iter)?;
     let user_transfer_authority_info = next_account_info(account_info_iter)?;
     let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
     let rent_info = next_account_info(account_info_iter)?;
@@ -233,57 +474,11 @@ fn process_init_reserve(
         msg!("Lending market token program does not match the token program provided");
         return Err(LendingError::InvalidTokenProgram.into());
     }
-    if &lending_market.owner != lending_market_owner_info.key {
-        msg!("Lending market owner does not match the lending market owner provided");
-        return Err(LendingError::InvalidMarketOwner.into());
-    }
-    if !lending_market_owner_info.is_signer {
-        msg!("Lending market owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
-
-    if &lending_market.oracle_program_id != pyth_product_info.owner {
-        msg!("Pyth product account provided is not owned by the lending market oracle program");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-    if &lending_market.oracle_program_id != pyth_price_info.owner {
-        msg!("Pyth price account provided is not owned by the lending market oracle program");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-
-    let pyth_product_data = pyth_product_info.try_borrow_data()?;
-    let pyth_product = pyth::load::<pyth::Product>(&pyth_product_data)
-        .map_err(|_| ProgramError::InvalidAccountData)?;
-    if pyth_product.magic != pyth::MAGIC {
-        msg!("Pyth product account provided is not a valid Pyth account");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-    if pyth_product.ver != pyth::VERSION_2 {
-        msg!("Pyth product account provided has a different version than expected");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-    if pyth_product.atype != pyth::AccountType::Product as u32 {
-        msg!("Pyth product account provided is not a valid Pyth product account");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-
-    let pyth_price_pubkey_bytes: &[u8; 32] = pyth_price_info
-        .key
-        .as_ref()
-        .try_into()
-        .map_err(|_| LendingError::InvalidAccountInput)?;
-    if &pyth_product.px_acc.val != pyth_price_pubkey_bytes {
-        msg!("Pyth product price account does not match the Pyth price provided");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
+    assert_ops_authority(&lending_market, lending_market_ops_admin_info)?;
 
-    let quote_currency = get_pyth_product_quote_currency(pyth_product)?;
-    if lending_market.quote_currency != quote_currency {
-        msg!("Lending market quote currency does not match the oracle quote currency");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
+    validate_pyth_oracle(&lending_market, pyth_product_info, pyth_price_info)?;
 
-    let market_price = get_pyth_price(pyth_price_info, clock)?;
+    let market_price = get_pyth_price(pyth_price_info, clock, &config)?;
 
     let authority_signer_seeds = &[
         lending_market_info.key.as_ref(),
@@ -402,10 +597,22 @@ fn process_refresh_reserve(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
         return Err(LendingError::InvalidAccountInput.into());
     }
 
-    reserve.liquidity.market_price = get_pyth_price(reserve_liquidity_oracle_info, clock)?;
+    reserve.liquidity.market_price =
+        get_pyth_price(reserve_liquidity_oracle_info, clock, &reserve.config)?;
 
     reserve.accrue_interest(clock.slot)?;
     reserve.last_update.update_slot(clock.slot);
+
+    if let Some(reserve_rate_history_info) = account_info_iter.next() {
+        let mut reserve_rate_history =
+            unpack_reserve_rate_history(program_id, reserve_rate_history_info, reserve_info.key)?;
+        reserve_rate_history.record(reserve.rate_snapshot(clock.slot)?);
+        ReserveRateHistory::pack(
+            reserve_rate_history,
+            &mut reserve_rate_history_info.data.borrow_mut(),
+        )?;
+    }
+
     Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
 
     Ok(())
@@ -489,6 +696,7 @@ fn process_deposit_reserve_liquidity(
     }
 
     let collateral_amount = reserve.deposit_liquidity(liquidity_amount)?;
+    reserve.validate_deposit_value_limit()?;
     reserve.last_update.mark_stale();
     Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
 
@@ -764,16 +972,33 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
         borrowed_value = borrowed_value.try_add(market_value)?;
     }
 
+    let obligation_health_info = account_info_iter.next();
     if account_info_iter.peek().is_some() {
         msg!("Too many obligation deposit or borrow reserves provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
 
+    let was_at_risk = obligation.is_at_risk()?;
+
     obligation.deposited_value = deposited_value;
     obligation.borrowed_value = borrowed_value;
     obligation.allowed_borrow_value = allowed_borrow_value;
     obligation.unhealthy_borrow_value = unhealthy_borrow_value;
 
+    if !was_at_risk && obligation.is_at_risk()? {
+        ObligationAtRiskEvent::new(*obligation_info.key, &obligation).log()?;
+    }
+
+    if let Some(obligation_health_info) = obligation_health_info {
+        let mut obligation_health =
+            unpack_obligation_health(program_id, obligation_health_info, obligation_info.key)?;
+        obligation_health.update(obligation.is_unhealthy(), clock.slot);
+        ObligationHealth::pack(
+            obligation_health,
+            &mut obligation_health_info.data.borrow_mut(),
+        )?;
+    }
+
     obligation.last_update.update_slot(clock.slot);
     Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
 
@@ -784,6 +1009,7 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
 fn process_deposit_obligation_collateral(
     program_id: &Pubkey,
     collateral_amount: u64,
+    margin_account_info: Option<&AccountInfo>,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
     if collateral_amount == 0 {
@@ -849,14 +1075,13 @@ fn process_deposit_obligation_collateral(
         msg!("Obligation lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if &obligation.owner != obligation_owner_info.key {
-        msg!("Obligation owner does not match the obligation owner provided");
-        return Err(LendingError::InvalidObligationOwner.into());
-    }
-    if !obligation_owner_info.is_signer {
-        msg!("Obligation owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    let margin_account = assert_obligation_owner(
+        program_id,
+        &obligation,
+        obligation_info.key,
+        obligation_owner_info,
+        margin_account_info,
+    )?;
 
     obligation
         .find_or_add_collateral_to_deposits(*deposit_reserve_info.key)?
@@ -864,6 +1089,8 @@ fn process_deposit_obligation_collateral(
     obligation.last_update.mark_stale();
     Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
 
+    mark_margin_account_stale(margin_account, margin_account_info)?;
+
     spl_token_transfer(TokenTransferParams {
         source: source_collateral_info.clone(),
         destination: destination_collateral_info.clone(),
@@ -880,6 +1107,7 @@ fn process_deposit_obligation_collateral(
 fn process_withdraw_obligation_collateral(
     program_id: &Pubkey,
     collateral_amount: u64,
+    margin_account_info: Option<&AccountInfo>,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
     if collateral_amount == 0 {
@@ -939,14 +1167,13 @@ fn process_withdraw_obligation_collateral(
         msg!("Obligation lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if &obligation.owner != obligation_owner_info.key {
-        msg!("Obligation owner does not match the obligation owner provided");
-        return Err(LendingError::InvalidObligationOwner.into());
-    }
-    if !obligation_owner_info.is_signer {
-        msg!("Obligation owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    let margin_account = assert_obligation_owner(
+        program_id,
+        &obligation,
+        obligation_info.key,
+        obligation_owner_info,
+        margin_account_info,
+    )?;
     if obligation.last_update.is_stale(clock.slot)? {
         msg!("Obligation is stale and must be refreshed in the current slot");
         return Err(LendingError::ObligationStale.into());
@@ -972,19 +1199,36 @@ fn process_withdraw_obligation_collateral(
         return Err(LendingError::InvalidMarketAuthority.into());
     }
 
-    let withdraw_amount = if obligation.borrows.is_empty() {
+    let (borrows_empty, deposited_value) = match &margin_account {
+        Some(margin_account) => {
+            if margin_account.last_update.is_stale(clock.slot)? {
+                msg!("Margin account is stale and must be refreshed in the current slot");
+                return Err(LendingError::MarginAccountStale.into());
+            }
+            (
+                margin_account.borrowed_value == Decimal::zero(),
+                margin_account.deposited_value,
+            )
+        }
+        None => (obligation.borrows.is_empty(), obligation.deposited_value),
+    };
+
+    let withdraw_amount = if borrows_empty {
         if collateral_amount == u64::MAX {
             collateral.deposited_amount
         } else {
             collateral.deposited_amount.min(collateral_amount)
         }
-    } else if obligation.deposited_value == Decimal::zero() {
+    } else if deposited_value == Decimal::zero() {
         msg!("Obligation deposited value is zero");
         return Err(LendingError::ObligationDepositsZero.into());
     } else {
-        let max_withdraw_value = obligation.max_withdraw_value(Rate::from_percent(
-            withdraw_reserve.config.loan_to_value_ratio,
-        ))?;
+        let withdraw_collateral_ltv =
+            Rate::from_percent(withdraw_reserve.config.loan_to_value_ratio);
+        let max_withdraw_value = match &margin_account {
+            Some(margin_account) => margin_account.max_withdraw_value(withdraw_collateral_ltv)?,
+            None => obligation.max_withdraw_value(withdraw_collateral_ltv)?,
+        };
         if max_withdraw_value == Decimal::zero() {
             msg!("Maximum withdraw value is zero");
             return Err(LendingError::WithdrawTooLarge.into());
@@ -1019,6 +1263,8 @@ fn process_withdraw_obligation_collateral(
     obligation.last_update.mark_stale();
     Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
 
+    mark_margin_account_stale(margin_account, margin_account_info)?;
+
     spl_token_transfer(TokenTransferParams {
         source: source_collateral_info.clone(),
         destination: destination_collateral_info.clone(),
@@ -1036,6 +1282,7 @@ fn process_borrow_obligation_liquidity(
     program_id: &Pubkey,
     liquidity_amount: u64,
     slippage_limit: u64,
+    margin_account_info: Option<&AccountInfo>,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
     if liquidity_amount == 0 {
@@ -1054,6 +1301,8 @@ fn process_borrow_obligation_liquidity(
     let obligation_owner_info = next_account_info(account_info_iter)?;
     let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
     let token_program_id = next_account_info(account_info_iter)?;
+    let host_fee_receiver_info = next_account_info(account_info_iter).ok();
+    let host_fee_registry_info = next_account_info(account_info_iter).ok();
 
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
     if lending_market_info.owner != program_id {
@@ -1102,25 +1351,38 @@ fn process_borrow_obligation_liquidity(
         msg!("Obligation lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if &obligation.owner != obligation_owner_info.key {
-        msg!("Obligation owner does not match the obligation owner provided");
-        return Err(LendingError::InvalidObligationOwner.into());
-    }
-    if !obligation_owner_info.is_signer {
-        msg!("Obligation owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    let margin_account = assert_obligation_owner(
+        program_id,
+        &obligation,
+        obligation_info.key,
+        obligation_owner_info,
+        margin_account_info,
+    )?;
     if obligation.last_update.is_stale(clock.slot)? {
         msg!("Obligation is stale and must be refreshed in the current slot");
         return Err(LendingError::ObligationStale.into());
     }
-    if obligation.deposits.is_empty() {
-        msg!("Obligation has no deposits to borrow against");
-        return Err(LendingError::ObligationDepositsEmpty.into());
-    }
-    if obligation.deposited_value == Decimal::zero() {
-        msg!("Obligation deposits have zero value");
-        return Err(LendingError::ObligationDepositsZero.into());
+    match &margin_account {
+        Some(margin_account) => {
+            if margin_account.last_update.is_stale(clock.slot)? {
+                msg!("Margin account is stale and must be refreshed in the current slot");
+                return Err(LendingError::MarginAccountStale.into());
+            }
+            if margin_account.deposited_value == Decimal::zero() {
+                msg!("Margin account deposits have zero value");
+                return Err(LendingError::ObligationDepositsZero.into());
+            }
+        }
+        None => {
+            if obligation.deposits.is_empty() {
+                msg!("Obligation has no deposits to borrow against");
+                return Err(LendingError::ObligationDepositsEmpty.into());
+            }
+            if obligation.deposited_value == Decimal::zero() {
+                msg!("Obligation deposits have zero value");
+                return Err(LendingError::ObligationDepositsZero.into());
+            }
+        }
     }
 
     let authority_signer_seeds = &[
@@ -1136,18 +1398,57 @@ fn process_borrow_obligation_liquidity(
         return Err(LendingError::InvalidMarketAuthority.into());
     }
 
-    let remaining_borrow_value = obligation.remaining_borrow_value()?;
+    let remaining_borrow_value = match &margin_account {
+        Some(margin_account) => margin_account.remaining_borrow_value()?,
+        None => obligation.remaining_borrow_value()?,
+    };
     if remaining_borrow_value == Decimal::zero() {
         msg!("Remaining borrow value is zero");
         return Err(LendingError::BorrowTooLarge.into());
     }
 
+    // Once the lending market has a host fee registry, only registered host
+    // fee receivers are paid
+    let host_fee_percentage = match (host_fee_receiver_info, lending_market.host_fee_registry) {
+        (Some(host_fee_receiver_info), Some(host_fee_registry_pubkey)) => {
+            let host_fee_registry_info = host_fee_registry_info.ok_or_else(|| {
+                msg!("Host fee registry of the lending market must be provided with a host fee receiver");
+                LendingError::InvalidAccountInput
+            })?;
+            if host_fee_registry_info.key != &host_fee_registry_pubkey {
+                msg!("Host fee registry provided does not match the lending market host fee registry");
+                return Err(LendingError::InvalidAccountInput.into());
+            }
+            if host_fee_registry_info.owner != program_id {
+                msg!("Host fee registry provided is not owned by the lending program");
+                return Err(LendingError::InvalidAccountOwner.into());
+            }
+            let host_fee_registry = HostFeeRegistry::unpack(&host_fee_registry_info.data.borrow())?;
+            if &host_fee_registry.lending_market != lending_market_info.key {
+                msg!("Host fee registry lending market does not match the lending market provided");
+                return Err(LendingError::InvalidAccountInput.into());
+            }
+            host_fee_registry
+                .find_host_fee_share(host_fee_receiver_info.key)
+                .ok_or_else(|| {
+                    msg!("Host fee receiver provided is not registered in the host fee registry");
+                    LendingError::HostFeeReceiverNotRegistered
+                })?
+                .host_fee_percentage
+        }
+        _ => borrow_reserve.config.fees.host_fee_percentage,
+    };
+
     let CalculateBorrowResult {
         borrow_amount,
         receive_amount,
         borrow_fee,
         host_fee,
-    } = borrow_reserve.calculate_borrow(liquidity_amount, remaining_borrow_value)?;
+    } = borrow_reserve.calculate_borrow_with_host_fee_percentage(
+        liquidity_amount,
+        remaining_borrow_value,
+        lending_market.cap_host_fee_percentage(host_fee_percentage),
+    )?;
 
     if receive_amount == 0 {
         msg!("Borrow amount is too small to receive liquidity after fees");
@@ -1160,6 +1461,7 @@ fn process_borrow_obligation_liquidity(
     }
 
     borrow_reserve.liquidity.borrow(borrow_amount)?;
+    borrow_reserve.validate_borrow_value_limit()?;
     borrow_reserve.last_update.mark_stale();
     Reserve::pack(borrow_reserve, &mut borrow_reserve_info.data.borrow_mut())?;
 
@@ -1169,8 +1471,10 @@ fn process_borrow_obligation_liquidity(
     obligation.last_update.mark_stale();
     Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
 
+    mark_margin_account_stale(margin_account, margin_account_info)?;
+
     let mut owner_fee = borrow_fee;
-    if let Ok(host_fee_receiver_info) = next_account_info(account_info_iter) {
+    if let Some(host_fee_receiver_info) = host_fee_receiver_info {
         if host_fee > 0 {
             owner_fee = owner_fee
                 .checked_sub(host_fee)
@@ -1317,6 +1621,8 @@ fn process_repay_obligation_liquidity(
 fn process_liquidate_obligation(
     program_id: &Pubkey,
     liquidity_amount: u64,
+    is_auction: bool,
+    margin_accounts: Option<(&AccountInfo, &AccountInfo)>,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
     if liquidity_amount == 0 {
@@ -1337,6 +1643,11 @@ fn process_liquidate_obligation(
     let user_transfer_authority_info = next_account_info(account_info_iter)?;
     let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
     let token_program_id = next_account_info(account_info_iter)?;
+    let liquidation_auction_info = if is_auction {
+        Some(next_account_info(account_info_iter)?)
+    } else {
+        None
+    };
 
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
     if lending_market_info.owner != program_id {
@@ -1415,17 +1726,116 @@ fn process_liquidate_obligation(
         msg!("Obligation is stale and must be refreshed in the current slot");
         return Err(LendingError::ObligationStale.into());
     }
-    if obligation.deposited_value == Decimal::zero() {
-        msg!("Obligation deposited value is zero");
-        return Err(LendingError::ObligationDepositsZero.into());
-    }
-    if obligation.borrowed_value == Decimal::zero() {
-        msg!("Obligation borrowed value is zero");
-        return Err(LendingError::ObligationBorrowsZero.into());
-    }
-    if obligation.borrowed_value < obligation.unhealthy_borrow_value {
-        msg!("Obligation is healthy and cannot be liquidated");
-        return Err(LendingError::ObligationHealthy.into());
+
+    // the collateral of a margin account obligation can be withdrawn from
+    // another obligation of the margin account
+    let (margin_account, withdraw_obligation) = match margin_accounts {
+        Some((margin_account_info, withdraw_obligation_info)) => {
+            let margin =
+                unpack_margin_account(program_id, margin_account_info, lending_market_info.key)?;
+            if margin.last_update.is_stale(clock.slot)? {
+                msg!("Margin account is stale and must be refreshed in the current slot");
+                return Err(LendingError::MarginAccountStale.into());
+            }
+            if margin.find_obligation(obligation_info.key).is_none()
+                || margin
+                    .find_obligation(withdraw_obligation_info.key)
+                    .is_none()
+            {
+                msg!("Obligations provided must be part of the margin account provided");
+                return Err(LendingError::MarginObligationInvalid.into());
+            }
+            if margin.deposited_value == Decimal::zero() {
+                msg!("Margin account deposited value is zero");
+                return Err(LendingError::ObligationDepositsZero.into());
+            }
+            if margin.borrowed_value == Decimal::zero() {
+                msg!("Margin account borrowed value is zero");
+                return Err(LendingError::ObligationBorrowsZero.into());
+            }
+            if !margin.is_unhealthy() {
+                msg!("Margin account is healthy and cannot be liquidated");
+                return Err(LendingError::ObligationHealthy.into());
+            }
+
+            let withdraw_obligation = if withdraw_obligation_info.key != obligation_info.key {
+                if withdraw_obligation_info.owner != program_id {
+                    msg!("Withdraw obligation provided is not owned by the lending program");
+                    return Err(LendingError::InvalidAccountOwner.into());
+                }
+                let obligation = Obligation::unpack(&withdraw_obligation_info.data.borrow())?;
+                if obligation.last_update.is_stale(clock.slot)? {
+                    msg!("Withdraw obligation is stale and must be refreshed in the current slot");
+                    return Err(LendingError::ObligationStale.into());
+                }
+                Some((withdraw_obligation_info, obligation))
+            } else {
+                None
+            };
+            (Some((margin_account_info, margin)), withdraw_obligation)
+        }
+        None => {
+            if obligation.deposited_value == Decimal::zero() {
+                msg!("Obligation deposited value is zero");
+                return Err(LendingError::ObligationDepositsZero.into());
+            }
+            if obligation.borrowed_value == Decimal::zero() {
+                msg!("Obligation borrowed value is zero");
+                return Err(LendingError::ObligationBorrowsZero.into());
+            }
+            if obligation.borrowed_value < obligation.unhealthy_borrow_value {
+                msg!("Obligation is healthy and cannot be liquidated");
+                return Err(LendingError::ObligationHealthy.into());
+            }
+            (None, None)
+        }
+    };
+
+    let liquidation_bonus = match liquidation_auction_info {
+        Some(liquidation_auction_info) => {
+            let liquidation_auction =
+                LiquidationAuction::unpack(&liquidation_auction_info.data.borrow())?;
+            if liquidation_auction_info.owner != program_id {
+                msg!("Liquidation auction provided is not owned by the lending program");
+                return Err(LendingError::InvalidAccountOwner.into());
+            }
+            if &liquidation_auction.lending_market != lending_market_info.key {
+                msg!(
+                    "Liquidation auction lending market does not match the lending market provided"
+                );
+                return Err(LendingError::InvalidAccountInput.into());
+            }
+            if &liquidation_auction.obligation != obligation_info.key {
+                msg!("Liquidation auction obligation does not match the obligation provided");
+                return Err(LendingError::InvalidAccountInput.into());
+            }
+            liquidation_auction.current_bonus(clock.slot)?
+        }
+        None => {
+            if margin_account.is_none()
+                && lending_market.requires_liquidation_auction(obligation.borrowed_value)
+            {
+                msg!("Obligation borrowed value requires a liquidation auction");
+                return Err(LendingError::LiquidationAuctionRequired.into());
+            }
+            Rate::from_percent(withdraw_reserve.config.liquidation_bonus)
+        }
+    };
+
+    if margin_account.is_none() && lending_market.liquidation_grace_slots > 0 {
+        let obligation_health_info = next_account_info(account_info_iter)?;
+        let obligation_health =
+            unpack_obligation_health(program_id, obligation_health_info, obligation_info.key)?;
+        if obligation_health.last_update_slot != clock.slot {
+            msg!("Obligation health is stale and must be refreshed with the obligation in the current slot");
+            return Err(LendingError::ObligationHealthInvalid.into());
+        }
+        if !obligation_health
+            .is_grace_period_elapsed(lending_market.liquidation_grace_slots, clock.slot)
+        {
+            msg!("Obligation has not been unhealthy for the liquidation grace period");
+            return Err(LendingError::LiquidationGracePeriodActive.into());
+        }
     }
 
     let (liquidity, liquidity_index) =
@@ -1435,8 +1845,10 @@ fn process_liquidate_obligation(
         return Err(LendingError::ObligationLiquidityEmpty.into());
     }
 
-    let (collateral, collateral_index) =
-        obligation.find_collateral_in_deposits(*withdraw_reserve_info.key)?;
+    let (collateral, collateral_index) = withdraw_obligation
+        .as_ref()
+        .map_or(&obligation, |(_, withdraw_obligation)| withdraw_obligation)
+        .find_collateral_in_deposits(*withdraw_reserve_info.key)?;
     if collateral.market_value == Decimal::zero() {
         msg!("Obligation deposit value is zero");
         return Err(LendingError::ObligationCollateralEmpty.into());
@@ -1454,16 +1866,21 @@ fn process_liquidate_obligation(
         );
         return Err(LendingError::InvalidMarketAuthority.into());
     }
+    if margin_account.is_none() && obligation.owner == lending_market_authority_pubkey {
+        msg!("Obligation is part of a margin account and must be liquidated through it");
+        return Err(LendingError::MarginAccountRequired.into());
+    }
 
     let CalculateLiquidationResult {
         settle_amount,
         repay_amount,
         withdraw_amount,
-    } = withdraw_reserve.calculate_liquidation(
+    } = withdraw_reserve.calculate_liquidation_with_bonus(
         liquidity_amount,
         &obligation,
         liquidity,
         collateral,
+        liquidation_bonus,
     )?;
 
     if repay_amount == 0 {
@@ -1480,10 +1897,25 @@ fn process_liquidate_obligation(
     Reserve::pack(repay_reserve, &mut repay_reserve_info.data.borrow_mut())?;
 
     obligation.repay(settle_amount, liquidity_index)?;
-    obligation.withdraw(withdraw_amount, collateral_index)?;
+    match withdraw_obligation {
+        Some((withdraw_obligation_info, mut withdraw_obligation)) => {
+            withdraw_obligation.withdraw(withdraw_amount, collateral_index)?;
+            withdraw_obligation.last_update.mark_stale();
+            Obligation::pack(
+                withdraw_obligation,
+                &mut withdraw_obligation_info.data.borrow_mut(),
+            )?;
+        }
+        None => obligation.withdraw(withdraw_amount, collateral_index)?,
+    }
     obligation.last_update.mark_stale();
     Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
 
+    if let Some((margin_account_info, mut margin_account)) = margin_account {
+        margin_account.last_update.mark_stale();
+        MarginAccount::pack(margin_account, &mut margin_account_info.data.borrow_mut())?;
+    }
+
     spl_token_transfer(TokenTransferParams {
         source: source_liquidity_info.clone(),
         destination: repay_reserve_liquidity_supply_info.clone(),
@@ -1581,10 +2013,12 @@ fn process_flash_loan(
     };
 
     let flash_loan_amount_decimal = Decimal::from(flash_loan_amount);
-    let (origination_fee, host_fee) = reserve
-        .config
-        .fees
-        .calculate_flash_loan_fees(flash_loan_amount_decimal)?;
+    let fees = ReserveFees {
+        host_fee_percentage: lending_market
+            .cap_host_fee_percentage(reserve.config.fees.host_fee_percentage),
+        ..reserve.config.fees
+    };
+    let (origination_fee, host_fee) = fees.calculate_flash_loan_fees(flash_loan_amount_decimal)?;
 
     let balance_before_flash_loan = Account::unpack(&source_liquidity_info.data.borrow())?.amount;
     let expected_balance_after_flash_loan = balance_before_flash_loan
@@ -1687,15 +2121,14 @@ fn process_flash_loan(
 
 fn process_modify_reserve_config(
     program_id: &Pubkey,
-    new_config: ReserveConfig,
+    mut new_config: ReserveConfig,
+    legacy_config: bool,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
-    new_config.validate()?;
-
     let account_info_iter = &mut accounts.iter().peekable();
     let reserve_info = next_account_info(account_info_iter)?;
     let lending_market_info = next_account_info(account_info_iter)?;
-    let lending_market_owner_info = next_account_info(account_info_iter)?;
+    let lending_market_risk_admin_info = next_account_info(account_info_iter)?;
 
     if reserve_info.owner != program_id {
         msg!("Reserve provided is not owned by the lending program");
@@ -1707,29 +2140,1336 @@ fn process_modify_reserve_config(
         msg!("Lending market provided is not owned by the lending program");
         return Err(LendingError::InvalidAccountOwner.into());
     }
-    if &lending_market.owner != lending_market_owner_info.key {
-        msg!("Lending market owner does not match the lending market owner provided");
-        return Err(LendingError::InvalidMarketOwner.into());
-    }
-    if !lending_market_owner_info.is_signer {
-        msg!("Lending market owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_risk_authority(&lending_market, lending_market_risk_admin_info)?;
 
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow_mut())?;
     // Validate that the reserve account corresponds to the correct lending market,
-    // after validating above that the lending market and lending market owner
-    // correspond, to prevent one compromised lending market owner from changing
+    // after validating above that the lending market and lending market risk admin
+    // correspond, to prevent one compromised lending market admin from changing
     // configs on other lending markets
     if reserve.lending_market != *lending_market_info.key {
         msg!("Reserve account does not match the lending market");
         return Err(LendingError::InvalidAccountInput.into());
     }
 
-    reserve.config = new_config;
+    // Configs sent by older clients do not include the oracle thresholds and
+    // value limits, so keep their current values instead of disabling them
+    if legacy_config {
+        new_config.max_oracle_age_slots = reserve.config.max_oracle_age_slots;
+        new_config.max_oracle_confidence_bps = reserve.config.max_oracle_confidence_bps;
+        new_config.deposit_value_limit = reserve.config.deposit_value_limit;
+        new_config.borrow_value_limit = reserve.config.borrow_value_limit;
+    }
+    new_config.validate()?;
+    reserve.config = new_config;
+
+    Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_set_reserve_oracle(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reserve_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_ops_admin_info = next_account_info(account_info_iter)?;
+    let pyth_product_info = next_account_info(account_info_iter)?;
+    let pyth_price_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    if reserve_info.owner != program_id {
+        msg!("Reserve provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    assert_ops_authority(&lending_market, lending_market_ops_admin_info)?;
+
+    let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
+    if reserve.lending_market != *lending_market_info.key {
+        msg!("Reserve account does not match the lending market");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    validate_pyth_oracle(&lending_market, pyth_product_info, pyth_price_info)?;
+
+    reserve.liquidity.oracle_pubkey = *pyth_price_info.key;
+    reserve.liquidity.market_price = get_pyth_price(pyth_price_info, clock, &reserve.config)?;
+    reserve.last_update.mark_stale();
+    Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_set_host_fee_percentage_cap(
+    program_id: &Pubkey,
+    host_fee_percentage_cap: u8,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    if host_fee_percentage_cap > MAX_HOST_FEE_PERCENTAGE {
+        msg!(
+            "Host fee percentage cap must be in range [0, {}]",
+            MAX_HOST_FEE_PERCENTAGE
+        );
+        return Err(LendingError::InvalidConfig.into());
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+
+    let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    lending_market.host_fee_percentage_cap = Some(host_fee_percentage_cap);
+    LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_init_host_fee_registry(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let host_fee_registry_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+    let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
+
+    assert_rent_exempt(rent, host_fee_registry_info)?;
+    let mut host_fee_registry = assert_uninitialized::<HostFeeRegistry>(host_fee_registry_info)?;
+    if host_fee_registry_info.owner != program_id {
+        msg!("Host fee registry provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+    if lending_market.host_fee_registry.is_some() {
+        msg!("Lending market already has a host fee registry");
+        return Err(LendingError::AlreadyInitialized.into());
+    }
+
+    host_fee_registry.init(InitHostFeeRegistryParams {
+        lending_market: *lending_market_info.key,
+    });
+    HostFeeRegistry::pack(
+        host_fee_registry,
+        &mut host_fee_registry_info.data.borrow_mut(),
+    )?;
+
+    lending_market.host_fee_registry = Some(*host_fee_registry_info.key);
+    LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_set_host_fee_share(
+    program_id: &Pubkey,
+    host_fee_percentage: u8,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let host_fee_registry_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+    let host_fee_receiver_info = next_account_info(account_info_iter)?;
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let mut host_fee_registry = HostFeeRegistry::unpack(&host_fee_registry_info.data.borrow())?;
+    if host_fee_registry_info.owner != program_id {
+        msg!("Host fee registry provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &host_fee_registry.lending_market != lending_market_info.key {
+        msg!("Host fee registry lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    host_fee_registry.set_host_fee_share(*host_fee_receiver_info.key, host_fee_percentage)?;
+    HostFeeRegistry::pack(
+        host_fee_registry,
+        &mut host_fee_registry_info.data.borrow_mut(),
+    )?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_set_liquidation_auction_config(
+    program_id: &Pubkey,
+    config: Option<LiquidationAuctionConfig>,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    if let Some(config) = &config {
+        if config.duration_slots == 0 {
+            msg!("Liquidation auction duration must be greater than zero");
+            return Err(LendingError::InvalidConfig.into());
+        }
+        if config.max_bonus > MAX_LIQUIDATION_AUCTION_BONUS {
+            msg!(
+                "Liquidation auction max bonus must be in range [0, {}]",
+                MAX_LIQUIDATION_AUCTION_BONUS
+            );
+            return Err(LendingError::InvalidConfig.into());
+        }
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_risk_admin_info = next_account_info(account_info_iter)?;
+
+    let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    assert_risk_authority(&lending_market, lending_market_risk_admin_info)?;
+
+    lending_market.liquidation_auction_config = config;
+    LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_start_liquidation_auction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let liquidation_auction_info = next_account_info(account_info_iter)?;
+    let obligation_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+    let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
+
+    assert_rent_exempt(rent, liquidation_auction_info)?;
+    let mut liquidation_auction =
+        LiquidationAuction::unpack_unchecked(&liquidation_auction_info.data.borrow())?;
+    if liquidation_auction_info.owner != program_id {
+        msg!("Liquidation auction provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if liquidation_auction.is_initialized() {
+        msg!("Liquidation auction has already been started");
+        return Err(LendingError::LiquidationAuctionActive.into());
+    }
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    let config = match lending_market.liquidation_auction_config {
+        Some(config) => config,
+        None => {
+            msg!("Lending market does not have liquidation auctions enabled");
+            return Err(LendingError::LiquidationAuctionNotRequired.into());
+        }
+    };
+
+    let obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    if obligation_info.owner != program_id {
+        msg!("Obligation provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &obligation.lending_market != lending_market_info.key {
+        msg!("Obligation lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if obligation.last_update.is_stale(clock.slot)? {
+        msg!("Obligation is stale and must be refreshed in the current slot");
+        return Err(LendingError::ObligationStale.into());
+    }
+    if obligation.borrowed_value < obligation.unhealthy_borrow_value {
+        msg!("Obligation is healthy and cannot be liquidated");
+        return Err(LendingError::ObligationHealthy.into());
+    }
+    if !lending_market.requires_liquidation_auction(obligation.borrowed_value) {
+        msg!("Obligation borrowed value is below the liquidation auction minimum");
+        return Err(LendingError::LiquidationAuctionNotRequired.into());
+    }
+    let lending_market_authority_pubkey = Pubkey::create_program_address(
+        &[
+            lending_market_info.key.as_ref(),
+            &[lending_market.bump_seed],
+        ],
+        program_id,
+    )?;
+    if obligation.owner == lending_market_authority_pubkey {
+        msg!("Obligation is part of a margin account and must be liquidated through it");
+        return Err(LendingError::MarginAccountRequired.into());
+    }
+
+    liquidation_auction.init(InitLiquidationAuctionParams {
+        current_slot: clock.slot,
+        lending_market: *lending_market_info.key,
+        obligation: *obligation_info.key,
+        config,
+    });
+    LiquidationAuction::pack(
+        liquidation_auction,
+        &mut liquidation_auction_info.data.borrow_mut(),
+    )?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_set_liquidation_grace_period(
+    program_id: &Pubkey,
+    liquidation_grace_slots: u64,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_risk_admin_info = next_account_info(account_info_iter)?;
+
+    let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    assert_risk_authority(&lending_market, lending_market_risk_admin_info)?;
+
+    lending_market.liquidation_grace_slots = liquidation_grace_slots;
+    LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_init_obligation_health(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let obligation_health_info = next_account_info(account_info_iter)?;
+    let obligation_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    if system_program_info.key != &system_program::id() {
+        msg!("System program provided is not the system program");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if !payer_info.is_signer {
+        msg!("Payer provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    if obligation_info.owner != program_id {
+        msg!("Obligation provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    let (obligation_health_pubkey, bump_seed) = Pubkey::find_program_address(
+        &[OBLIGATION_HEALTH_SEED, obligation_info.key.as_ref()],
+        program_id,
+    );
+    if &obligation_health_pubkey != obligation_health_info.key {
+        msg!("Derived obligation health account does not match the obligation health account provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if obligation_health_info.owner == program_id {
+        msg!("Obligation health account is already initialized");
+        return Err(LendingError::AlreadyInitialized.into());
+    }
+    let obligation_health_signer_seeds: &[&[u8]] = &[
+        OBLIGATION_HEALTH_SEED,
+        obligation_info.key.as_ref(),
+        &[bump_seed],
+    ];
+
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(ObligationHealth::LEN);
+    let system_account_infos = [
+        payer_info.clone(),
+        obligation_health_info.clone(),
+        system_program_info.clone(),
+    ];
+    if obligation_health_info.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer_info.key,
+                obligation_health_info.key,
+                lamports,
+                ObligationHealth::LEN as u64,
+                program_id,
+            ),
+            &system_account_infos,
+            &[obligation_health_signer_seeds],
+        )?;
+    } else {
+        let top_up = lamports.saturating_sub(obligation_health_info.lamports());
+        if top_up > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, obligation_health_info.key, top_up),
+                &system_account_infos,
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(obligation_health_info.key, ObligationHealth::LEN as u64),
+            &system_account_infos,
+            &[obligation_health_signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(obligation_health_info.key, program_id),
+            &system_account_infos,
+            &[obligation_health_signer_seeds],
+        )?;
+    }
+
+    let obligation_health = ObligationHealth::new(InitObligationHealthParams {
+        current_slot: clock.slot,
+        lending_market: obligation.lending_market,
+        obligation: *obligation_info.key,
+    });
+    ObligationHealth::pack(
+        obligation_health,
+        &mut obligation_health_info.data.borrow_mut(),
+    )?;
+
+    Ok(())
+}
+
+/// Wraps `wrap_amount` lamports of the user into a temporary wrapped SOL
+/// account, runs `process` with the token instruction accounts and closes the
+/// temporary account back to the user.
+///
+/// The indices are positions within the token instruction accounts, which
+/// follow the native mint and the system program.
+#[inline(never)] // avoid stack frame limit
+fn process_init_margin_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let margin_account_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let margin_account_owner_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+    let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
+
+    assert_rent_exempt(rent, margin_account_info)?;
+    let mut margin_account = assert_uninitialized::<MarginAccount>(margin_account_info)?;
+    if margin_account_info.owner != program_id {
+        msg!("Margin account provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    if !margin_account_owner_info.is_signer {
+        msg!("Margin account owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    margin_account.init(InitMarginAccountParams {
+        current_slot: clock.slot,
+        lending_market: *lending_market_info.key,
+        owner: *margin_account_owner_info.key,
+    });
+    MarginAccount::pack(margin_account, &mut margin_account_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_add_margin_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let margin_account_info = next_account_info(account_info_iter)?;
+    let obligation_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let margin_account_owner_info = next_account_info(account_info_iter)?;
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    let mut margin_account =
+        unpack_margin_account(program_id, margin_account_info, lending_market_info.key)?;
+    if &margin_account.owner != margin_account_owner_info.key {
+        msg!("Margin account owner does not match the margin account owner provided");
+        return Err(LendingError::InvalidObligationOwner.into());
+    }
+    if !margin_account_owner_info.is_signer {
+        msg!("Margin account owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    if obligation_info.owner != program_id {
+        msg!("Obligation provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &obligation.lending_market != lending_market_info.key {
+        msg!("Obligation lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &obligation.owner != margin_account_owner_info.key {
+        msg!("Obligation owner does not match the margin account owner provided");
+        return Err(LendingError::InvalidObligationOwner.into());
+    }
+
+    // the obligation is handed over to the lending market authority, which
+    // nobody can sign for, so it can only be used through the margin account
+    obligation.owner = Pubkey::create_program_address(
+        &[
+            lending_market_info.key.as_ref(),
+            &[lending_market.bump_seed],
+        ],
+        program_id,
+    )?;
+    Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
+
+    margin_account.add_obligation(*obligation_info.key)?;
+    margin_account.last_update.mark_stale();
+    MarginAccount::pack(margin_account, &mut margin_account_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_remove_margin_obligation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let margin_account_info = next_account_info(account_info_iter)?;
+    let obligation_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let margin_account_owner_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    let mut margin_account =
+        unpack_margin_account(program_id, margin_account_info, lending_market_info.key)?;
+    if &margin_account.owner != margin_account_owner_info.key {
+        msg!("Margin account owner does not match the margin account owner provided");
+        return Err(LendingError::InvalidObligationOwner.into());
+    }
+    if !margin_account_owner_info.is_signer {
+        msg!("Margin account owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+    if margin_account.last_update.is_stale(clock.slot)? {
+        msg!("Margin account is stale and must be refreshed in the current slot");
+        return Err(LendingError::MarginAccountStale.into());
+    }
+
+    let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    if obligation_info.owner != program_id {
+        msg!("Obligation provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if obligation.last_update.is_stale(clock.slot)? {
+        msg!("Obligation is stale and must be refreshed in the current slot");
+        return Err(LendingError::ObligationStale.into());
+    }
+
+    margin_account.remove_obligation(obligation_info.key)?;
+
+    // both the removed obligation and the rest of the margin account must
+    // cover their own borrows
+    if obligation.borrowed_value > obligation.allowed_borrow_value {
+        msg!("Obligation borrows cannot exceed its allowed borrow value once removed");
+        return Err(LendingError::MarginObligationUndercollateralized.into());
+    }
+    let remaining_borrowed_value = margin_account
+        .borrowed_value
+        .try_sub(obligation.borrowed_value)?;
+    let remaining_allowed_borrow_value = margin_account
+        .allowed_borrow_value
+        .try_sub(obligation.allowed_borrow_value)?;
+    if remaining_borrowed_value > remaining_allowed_borrow_value {
+        msg!("Margin account borrows cannot exceed its allowed borrow value once the obligation is removed");
+        return Err(LendingError::MarginObligationUndercollateralized.into());
+    }
+
+    obligation.owner = margin_account.owner;
+    Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
+
+    margin_account.last_update.mark_stale();
+    MarginAccount::pack(margin_account, &mut margin_account_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_refresh_margin_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let margin_account_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    let mut margin_account = MarginAccount::unpack(&margin_account_info.data.borrow())?;
+    if margin_account_info.owner != program_id {
+        msg!("Margin account provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    let mut deposited_value = Decimal::zero();
+    let mut borrowed_value = Decimal::zero();
+    let mut allowed_borrow_value = Decimal::zero();
+    let mut unhealthy_borrow_value = Decimal::zero();
+
+    for (index, obligation_pubkey) in margin_account.obligations.iter().enumerate() {
+        let obligation_info = next_account_info(account_info_iter)?;
+        if obligation_info.owner != program_id {
+            msg!(
+                "Obligation {} provided is not owned by the lending program",
+                index
+            );
+            return Err(LendingError::InvalidAccountOwner.into());
+        }
+        if obligation_pubkey != obligation_info.key {
+            msg!(
+                "Obligation {} of the margin account does not match the obligation provided",
+                index
+            );
+            return Err(LendingError::InvalidAccountInput.into());
+        }
+
+        let obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+        if obligation.last_update.is_stale(clock.slot)? {
+            msg!(
+                "Obligation {} provided is stale and must be refreshed in the current slot",
+                index
+            );
+            return Err(LendingError::ObligationStale.into());
+        }
+
+        deposited_value = deposited_value.try_add(obligation.deposited_value)?;
+        borrowed_value = borrowed_value.try_add(obligation.borrowed_value)?;
+        allowed_borrow_value = allowed_borrow_value.try_add(obligation.allowed_borrow_value)?;
+        unhealthy_borrow_value =
+            unhealthy_borrow_value.try_add(obligation.unhealthy_borrow_value)?;
+    }
+
+    if account_info_iter.next().is_some() {
+        msg!("Too many margin account obligations provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    margin_account.deposited_value = deposited_value;
+    margin_account.borrowed_value = borrowed_value;
+    margin_account.allowed_borrow_value = allowed_borrow_value;
+    margin_account.unhealthy_borrow_value = unhealthy_borrow_value;
+    margin_account.last_update.update_slot(clock.slot);
+    MarginAccount::pack(margin_account, &mut margin_account_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_init_protocol_liquidity(
+    program_id: &Pubkey,
+    withdrawal_timelock_slots: u64,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let protocol_liquidity_info = next_account_info(account_info_iter)?;
+    let protocol_collateral_supply_info = next_account_info(account_info_iter)?;
+    let reserve_info = next_account_info(account_info_iter)?;
+    let reserve_collateral_mint_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_authority_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let rent = &Rent::from_account_info(rent_info)?;
+    let token_program_id = next_account_info(account_info_iter)?;
+
+    assert_rent_exempt(rent, protocol_liquidity_info)?;
+    let mut protocol_liquidity =
+        assert_uninitialized::<ProtocolLiquidity>(protocol_liquidity_info)?;
+    if protocol_liquidity_info.owner != program_id {
+        msg!("Protocol liquidity provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.token_program_id != token_program_id.key {
+        msg!("Lending market token program does not match the token program provided");
+        return Err(LendingError::InvalidTokenProgram.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let reserve = Reserve::unpack(&reserve_info.data.borrow())?;
+    if reserve_info.owner != program_id {
+        msg!("Reserve provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &reserve.lending_market != lending_market_info.key {
+        msg!("Reserve lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &reserve.collateral.mint_pubkey != reserve_collateral_mint_info.key {
+        msg!("Reserve collateral mint does not match the reserve collateral mint provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    let authority_signer_seeds = &[
+        lending_market_info.key.as_ref(),
+        &[lending_market.bump_seed],
+    ];
+    let lending_market_authority_pubkey =
+        Pubkey::create_program_address(authority_signer_seeds, program_id)?;
+    if &lending_market_authority_pubkey != lending_market_authority_info.key {
+        msg!(
+            "Derived lending market authority does not match the lending market authority provided"
+        );
+        return Err(LendingError::InvalidMarketAuthority.into());
+    }
+
+    // the collateral supply is owned by the lending market authority, so the
+    // locked collateral can only leave it through WithdrawProtocolLiquidity
+    spl_token_init_account(TokenInitializeAccountParams {
+        account: protocol_collateral_supply_info.clone(),
+        mint: reserve_collateral_mint_info.clone(),
+        owner: lending_market_authority_info.clone(),
+        rent: rent_info.clone(),
+        token_program: token_program_id.clone(),
+    })?;
+
+    protocol_liquidity.init(InitProtocolLiquidityParams {
+        lending_market: *lending_market_info.key,
+        reserve: *reserve_info.key,
+        collateral_supply: *protocol_collateral_supply_info.key,
+        withdrawal_timelock_slots,
+    });
+    ProtocolLiquidity::pack(
+        protocol_liquidity,
+        &mut protocol_liquidity_info.data.borrow_mut(),
+    )?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_deposit_protocol_liquidity(
+    program_id: &Pubkey,
+    liquidity_amount: u64,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let (protocol_liquidity_info, accounts) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let (lending_market_owner_info, accounts) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let account_info_iter = &mut accounts.iter();
+    let _source_liquidity_info = next_account_info(account_info_iter)?;
+    let protocol_collateral_supply_info = next_account_info(account_info_iter)?;
+    let reserve_info = next_account_info(account_info_iter)?;
+    let _reserve_liquidity_supply_info = next_account_info(account_info_iter)?;
+    let _reserve_collateral_mint_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+
+    let mut protocol_liquidity = ProtocolLiquidity::unpack(&protocol_liquidity_info.data.borrow())?;
+    if protocol_liquidity_info.owner != program_id {
+        msg!("Protocol liquidity provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &protocol_liquidity.lending_market != lending_market_info.key {
+        msg!("Protocol liquidity lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &protocol_liquidity.reserve != reserve_info.key {
+        msg!("Protocol liquidity reserve does not match the reserve provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &protocol_liquidity.collateral_supply != protocol_collateral_supply_info.key {
+        msg!("Protocol collateral supply does not match the destination collateral provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let collateral_balance_before_deposit =
+        Account::unpack(&protocol_collateral_supply_info.data.borrow())?.amount;
+
+    process_deposit_reserve_liquidity(program_id, liquidity_amount, accounts)?;
+
+    let collateral_balance_after_deposit =
+        Account::unpack(&protocol_collateral_supply_info.data.borrow())?.amount;
+    protocol_liquidity.deposit(
+        collateral_balance_after_deposit
+            .checked_sub(collateral_balance_before_deposit)
+            .ok_or(LendingError::MathOverflow)?,
+    )?;
+    ProtocolLiquidity::pack(
+        protocol_liquidity,
+        &mut protocol_liquidity_info.data.borrow_mut(),
+    )?;
+
+    Ok(())
+}
+
+fn process_announce_protocol_liquidity_withdrawal(
+    program_id: &Pubkey,
+    collateral_amount: u64,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let protocol_liquidity_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let mut protocol_liquidity = ProtocolLiquidity::unpack(&protocol_liquidity_info.data.borrow())?;
+    if protocol_liquidity_info.owner != program_id {
+        msg!("Protocol liquidity provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &protocol_liquidity.lending_market != lending_market_info.key {
+        msg!("Protocol liquidity lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    let unlock_slot = protocol_liquidity.announce_withdrawal(collateral_amount, clock.slot)?;
+    ProtocolLiquidityWithdrawalAnnouncedEvent {
+        protocol_liquidity: *protocol_liquidity_info.key,
+        reserve: protocol_liquidity.reserve,
+        collateral_amount,
+        unlock_slot,
+    }
+    .log();
+    ProtocolLiquidity::pack(
+        protocol_liquidity,
+        &mut protocol_liquidity_info.data.borrow_mut(),
+    )?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_withdraw_protocol_liquidity(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let protocol_liquidity_info = next_account_info(account_info_iter)?;
+    let protocol_collateral_supply_info = next_account_info(account_info_iter)?;
+    let destination_liquidity_info = next_account_info(account_info_iter)?;
+    let reserve_info = next_account_info(account_info_iter)?;
+    let reserve_collateral_mint_info = next_account_info(account_info_iter)?;
+    let reserve_liquidity_supply_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_authority_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+    let token_program_id = next_account_info(account_info_iter)?;
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.token_program_id != token_program_id.key {
+        msg!("Lending market token program does not match the token program provided");
+        return Err(LendingError::InvalidTokenProgram.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let mut protocol_liquidity = ProtocolLiquidity::unpack(&protocol_liquidity_info.data.borrow())?;
+    if protocol_liquidity_info.owner != program_id {
+        msg!("Protocol liquidity provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &protocol_liquidity.lending_market != lending_market_info.key {
+        msg!("Protocol liquidity lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &protocol_liquidity.reserve != reserve_info.key {
+        msg!("Protocol liquidity reserve does not match the reserve provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &protocol_liquidity.collateral_supply != protocol_collateral_supply_info.key {
+        msg!("Protocol collateral supply does not match the protocol collateral supply provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
+    if reserve_info.owner != program_id {
+        msg!("Reserve provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &reserve.collateral.mint_pubkey != reserve_collateral_mint_info.key {
+        msg!("Reserve collateral mint does not match the reserve collateral mint provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &reserve.liquidity.supply_pubkey != reserve_liquidity_supply_info.key {
+        msg!("Reserve liquidity supply does not match the reserve liquidity supply provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &reserve.liquidity.supply_pubkey == destination_liquidity_info.key {
+        msg!("Reserve liquidity supply cannot be used as the destination liquidity provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if reserve.last_update.is_stale(clock.slot)? {
+        msg!("Reserve is stale and must be refreshed in the current slot");
+        return Err(LendingError::ReserveStale.into());
+    }
+
+    let authority_signer_seeds = &[
+        lending_market_info.key.as_ref(),
+        &[lending_market.bump_seed],
+    ];
+    let lending_market_authority_pubkey =
+        Pubkey::create_program_address(authority_signer_seeds, program_id)?;
+    if &lending_market_authority_pubkey != lending_market_authority_info.key {
+        msg!(
+            "Derived lending market authority does not match the lending market authority provided"
+        );
+        return Err(LendingError::InvalidMarketAuthority.into());
+    }
+
+    let collateral_amount = protocol_liquidity.withdraw(clock.slot)?;
+    ProtocolLiquidity::pack(
+        protocol_liquidity,
+        &mut protocol_liquidity_info.data.borrow_mut(),
+    )?;
 
+    let liquidity_amount = reserve.redeem_collateral(collateral_amount)?;
+    reserve.last_update.mark_stale();
     Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
 
+    spl_token_burn(TokenBurnParams {
+        mint: reserve_collateral_mint_info.clone(),
+        source: protocol_collateral_supply_info.clone(),
+        amount: collateral_amount,
+        authority: lending_market_authority_info.clone(),
+        authority_signer_seeds,
+        token_program: token_program_id.clone(),
+    })?;
+
+    spl_token_transfer(TokenTransferParams {
+        source: reserve_liquidity_supply_info.clone(),
+        destination: destination_liquidity_info.clone(),
+        amount: liquidity_amount,
+        authority: lending_market_authority_info.clone(),
+        authority_signer_seeds,
+        token_program: token_program_id.clone(),
+    })?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_init_reserve_rate_history(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reserve_rate_history_info = next_account_info(account_info_iter)?;
+    let reserve_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if system_program_info.key != &system_program::id() {
+        msg!("System program provided is not the system program");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if !payer_info.is_signer {
+        msg!("Payer provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let reserve = Reserve::unpack(&reserve_info.data.borrow())?;
+    if reserve_info.owner != program_id {
+        msg!("Reserve provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    let (reserve_rate_history_pubkey, bump_seed) = Pubkey::find_program_address(
+        &[RESERVE_RATE_HISTORY_SEED, reserve_info.key.as_ref()],
+        program_id,
+    );
+    if &reserve_rate_history_pubkey != reserve_rate_history_info.key {
+        msg!("Derived reserve rate history account does not match the reserve rate history account provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if reserve_rate_history_info.owner == program_id {
+        msg!("Reserve rate history account is already initialized");
+        return Err(LendingError::AlreadyInitialized.into());
+    }
+    let reserve_rate_history_signer_seeds: &[&[u8]] = &[
+        RESERVE_RATE_HISTORY_SEED,
+        reserve_info.key.as_ref(),
+        &[bump_seed],
+    ];
+
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(ReserveRateHistory::LEN);
+    let system_account_infos = [
+        payer_info.clone(),
+        reserve_rate_history_info.clone(),
+        system_program_info.clone(),
+    ];
+    if reserve_rate_history_info.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer_info.key,
+                reserve_rate_history_info.key,
+                lamports,
+                ReserveRateHistory::LEN as u64,
+                program_id,
+            ),
+            &system_account_infos,
+            &[reserve_rate_history_signer_seeds],
+        )?;
+    } else {
+        let top_up = lamports.saturating_sub(reserve_rate_history_info.lamports());
+        if top_up > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    payer_info.key,
+                    reserve_rate_history_info.key,
+                    top_up,
+                ),
+                &system_account_infos,
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(
+                reserve_rate_history_info.key,
+                ReserveRateHistory::LEN as u64,
+            ),
+            &system_account_infos,
+            &[reserve_rate_history_signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(reserve_rate_history_info.key, program_id),
+            &system_account_infos,
+            &[reserve_rate_history_signer_seeds],
+        )?;
+    }
+
+    let reserve_rate_history = ReserveRateHistory::new(InitReserveRateHistoryParams {
+        lending_market: reserve.lending_market,
+        reserve: *reserve_info.key,
+    });
+    ReserveRateHistory::pack(
+        reserve_rate_history,
+        &mut reserve_rate_history_info.data.borrow_mut(),
+    )?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_native_sol<'a>(
+    program_id: &Pubkey,
+    wrap_amount: u64,
+    native_sol_index: usize,
+    user_index: usize,
+    token_program_index: usize,
+    accounts: &[AccountInfo<'a>],
+    process: impl FnOnce(&[AccountInfo<'a>]) -> ProgramResult,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let native_mint_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let token_accounts = account_info_iter.as_slice();
+    let native_sol_info = token_accounts
+        .get(native_sol_index)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let user_info = token_accounts
+        .get(user_index)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let token_program_id = token_accounts
+        .get(token_program_index)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if native_mint_info.key != &native_mint::id() {
+        msg!("Native mint provided is not the wrapped SOL mint");
+        return Err(LendingError::InvalidTokenMint.into());
+    }
+    if system_program_info.key != &system_program::id() {
+        msg!("System program provided is not the system program");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if token_program_id.key != &spl_token::id() {
+        msg!("Token program provided does not own the wrapped SOL mint");
+        return Err(LendingError::InvalidTokenProgram.into());
+    }
+    if !user_info.is_signer {
+        msg!("User transfer authority provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let (native_sol_pubkey, bump_seed) =
+        Pubkey::find_program_address(&[NATIVE_SOL_SEED, user_info.key.as_ref()], program_id);
+    if &native_sol_pubkey != native_sol_info.key {
+        msg!(
+            "Derived native SOL account {} does not match the native SOL account provided {}",
+            &native_sol_pubkey.to_string(),
+            &native_sol_info.key.to_string(),
+        );
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    let native_sol_signer_seeds: &[&[u8]] =
+        &[NATIVE_SOL_SEED, user_info.key.as_ref(), &[bump_seed]];
+
+    let rent = Rent::get()?;
+    let lamports = rent
+        .minimum_balance(Account::LEN)
+        .checked_add(wrap_amount)
+        .ok_or(LendingError::MathOverflow)?;
+    let system_account_infos = [
+        user_info.clone(),
+        native_sol_info.clone(),
+        system_program_info.clone(),
+    ];
+    if native_sol_info.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                user_info.key,
+                native_sol_info.key,
+                lamports,
+                Account::LEN as u64,
+                token_program_id.key,
+            ),
+            &system_account_infos,
+            &[native_sol_signer_seeds],
+        )?;
+    } else {
+        // lamports sent to the address ahead of time are wrapped as well and
+        // returned to the user when the account is closed
+        let top_up = lamports.saturating_sub(native_sol_info.lamports());
+        if top_up > 0 {
+            invoke(
+                &system_instruction::transfer(user_info.key, native_sol_info.key, top_up),
+                &system_account_infos,
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(native_sol_info.key, Account::LEN as u64),
+            &system_account_infos,
+            &[native_sol_signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(native_sol_info.key, token_program_id.key),
+            &system_account_infos,
+            &[native_sol_signer_seeds],
+        )?;
+    }
+
+    invoke(
+        &spl_token::instruction::initialize_account3(
+            token_program_id.key,
+            native_sol_info.key,
+            native_mint_info.key,
+            user_info.key,
+        )?,
+        &[
+            native_sol_info.clone(),
+            native_mint_info.clone(),
+            token_program_id.clone(),
+        ],
+    )
+    .map_err(|_| LendingError::TokenInitializeAccountFailed)?;
+
+    process(token_accounts)?;
+
+    invoke(
+        &spl_token::instruction::close_account(
+            token_program_id.key,
+            native_sol_info.key,
+            user_info.key,
+            user_info.key,
+            &[],
+        )?,
+        &[
+            native_sol_info.clone(),
+            user_info.clone(),
+            token_program_id.clone(),
+        ],
+    )
+}
+
+fn unpack_obligation_health(
+    program_id: &Pubkey,
+    obligation_health_info: &AccountInfo,
+    obligation_pubkey: &Pubkey,
+) -> Result<ObligationHealth, ProgramError> {
+    let obligation_health = ObligationHealth::unpack(&obligation_health_info.data.borrow())?;
+    if obligation_health_info.owner != program_id {
+        msg!("Obligation health provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &obligation_health.obligation != obligation_pubkey {
+        msg!("Obligation health obligation does not match the obligation provided");
+        return Err(LendingError::ObligationHealthInvalid.into());
+    }
+    Ok(obligation_health)
+}
+
+fn unpack_reserve_rate_history(
+    program_id: &Pubkey,
+    reserve_rate_history_info: &AccountInfo,
+    reserve_pubkey: &Pubkey,
+) -> Result<ReserveRateHistory, ProgramError> {
+    let reserve_rate_history =
+        ReserveRateHistory::unpack(&reserve_rate_history_info.data.borrow())?;
+    if reserve_rate_history_info.owner != program_id {
+        msg!("Reserve rate history provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &reserve_rate_history.reserve != reserve_pubkey {
+        msg!("Reserve rate history reserve does not match the reserve provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    Ok(reserve_rate_history)
+}
+
+fn unpack_margin_account(
+    program_id: &Pubkey,
+    margin_account_info: &AccountInfo,
+    lending_market_pubkey: &Pubkey,
+) -> Result<MarginAccount, ProgramError> {
+    let margin_account = MarginAccount::unpack(&margin_account_info.data.borrow())?;
+    if margin_account_info.owner != program_id {
+        msg!("Margin account provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &margin_account.lending_market != lending_market_pubkey {
+        msg!("Margin account lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    Ok(margin_account)
+}
+
+/// Asserts the obligation owner signed, either as the obligation owner or as
+/// the owner of the margin account the obligation is part of, and returns
+/// the margin account if one was provided
+fn assert_obligation_owner(
+    program_id: &Pubkey,
+    obligation: &Obligation,
+    obligation_pubkey: &Pubkey,
+    obligation_owner_info: &AccountInfo,
+    margin_account_info: Option<&AccountInfo>,
+) -> Result<Option<MarginAccount>, ProgramError> {
+    let margin_account = match margin_account_info {
+        Some(margin_account_info) => {
+            let margin_account =
+                unpack_margin_account(program_id, margin_account_info, &obligation.lending_market)?;
+            if margin_account.find_obligation(obligation_pubkey).is_none() {
+                msg!("Obligation provided is not part of the margin account provided");
+                return Err(LendingError::MarginObligationInvalid.into());
+            }
+            if &margin_account.owner != obligation_owner_info.key {
+                msg!("Margin account owner does not match the obligation owner provided");
+                return Err(LendingError::InvalidObligationOwner.into());
+            }
+            Some(margin_account)
+        }
+        None => {
+            if &obligation.owner != obligation_owner_info.key {
+                msg!("Obligation owner does not match the obligation owner provided");
+                return Err(LendingError::InvalidObligationOwner.into());
+            }
+            None
+        }
+    };
+    if !obligation_owner_info.is_signer {
+        msg!("Obligation owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+    Ok(margin_account)
+}
+
+fn mark_margin_account_stale(
+    margin_account: Option<MarginAccount>,
+    margin_account_info: Option<&AccountInfo>,
+) -> ProgramResult {
+    if let (Some(mut margin_account), Some(margin_account_info)) =
+        (margin_account, margin_account_info)
+    {
+        margin_account.last_update.mark_stale();
+        MarginAccount::pack(margin_account, &mut margin_account_info.data.borrow_mut())?;
+    }
     Ok(())
 }
 
@@ -1758,6 +3498,85 @@ fn unpack_mint(data: &[u8]) -> Result<Mint, LendingError> {
     Mint::unpack(data).map_err(|_| LendingError::InvalidTokenMint)
 }
 
+fn assert_risk_authority(
+    lending_market: &LendingMarket,
+    lending_market_risk_admin_info: &AccountInfo,
+) -> ProgramResult {
+    if &lending_market.risk_authority() != lending_market_risk_admin_info.key {
+        msg!("Lending market risk admin does not match the lending market risk admin provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_risk_admin_info.is_signer {
+        msg!("Lending market risk admin provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+    Ok(())
+}
+
+fn assert_ops_authority(
+    lending_market: &LendingMarket,
+    lending_market_ops_admin_info: &AccountInfo,
+) -> ProgramResult {
+    if &lending_market.ops_authority() != lending_market_ops_admin_info.key {
+        msg!("Lending market ops admin does not match the lending market ops admin provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_ops_admin_info.is_signer {
+        msg!("Lending market ops admin provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+    Ok(())
+}
+
+fn validate_pyth_oracle(
+    lending_market: &LendingMarket,
+    pyth_product_info: &AccountInfo,
+    pyth_price_info: &AccountInfo,
+) -> ProgramResult {
+    if &lending_market.oracle_program_id != pyth_product_info.owner {
+        msg!("Pyth product account provided is not owned by the lending market oracle program");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+    if &lending_market.oracle_program_id != pyth_price_info.owner {
+        msg!("Pyth price account provided is not owned by the lending market oracle program");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+
+    let pyth_product_data = pyth_product_info.try_borrow_data()?;
+    let pyth_product = pyth::load::<pyth::Product>(&pyth_product_data)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if pyth_product.magic != pyth::MAGIC {
+        msg!("Pyth product account provided is not a valid Pyth account");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+    if pyth_product.ver != pyth::VERSION_2 {
+        msg!("Pyth product account provided has a different version than expected");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+    if pyth_product.atype != pyth::AccountType::Product as u32 {
+        msg!("Pyth product account provided is not a valid Pyth product account");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+
+    let pyth_price_pubkey_bytes: &[u8; 32] = pyth_price_info
+        .key
+        .as_ref()
+        .try_into()
+        .map_err(|_| LendingError::InvalidAccountInput)?;
+    if &pyth_product.px_acc.val != pyth_price_pubkey_bytes {
+        msg!("Pyth product price account does not match the Pyth price provided");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+
+    let quote_currency = get_pyth_product_quote_currency(pyth_product)?;
+    if lending_market.quote_currency != quote_currency {
+        msg!("Lending market quote currency does not match the oracle quote currency");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+
+    Ok(())
+}
+
 fn get_pyth_product_quote_currency(pyth_product: &pyth::Product) -> Result<[u8; 32], ProgramError> {
     const LEN: usize = 14;
     const KEY: &[u8; LEN] = b"quote_currency";
@@ -1800,9 +3619,11 @@ fn get_pyth_product_quote_currency(pyth_product: &pyth::Product) -> Result<[u8;
     Err(LendingError::InvalidOracleConfig.into())
 }
 
-fn get_pyth_price(pyth_price_info: &AccountInfo, clock: &Clock) -> Result<Decimal, ProgramError> {
-    const STALE_AFTER_SLOTS_ELAPSED: u64 = 5;
-
+fn get_pyth_price(
+    pyth_price_info: &AccountInfo,
+    clock: &Clock,
+    config: &ReserveConfig,
+) -> Result<Decimal, ProgramError> {
     let pyth_price_data = pyth_price_info.try_borrow_data()?;
     let pyth_price = pyth::load::<pyth::Price>(&pyth_price_data)
         .map_err(|_| ProgramError::InvalidAccountData)?;
@@ -1821,7 +3642,7 @@ fn get_pyth_price(pyth_price_info: &AccountInfo, clock: &Clock) -> Result<Decima
         .slot
         .checked_sub(pyth_price.valid_slot)
         .ok_or(LendingError::MathOverflow)?;
-    if slots_elapsed >= STALE_AFTER_SLOTS_ELAPSED {
+    if slots_elapsed >= config.max_oracle_age_slots() {
         msg!("Oracle price is stale");
         return Err(LendingError::InvalidOracleConfig.into());
     }
@@ -1830,6 +3651,7 @@ fn get_pyth_price(pyth_price_info: &AccountInfo, clock: &Clock) -> Result<Decima
         msg!("Oracle price cannot be negative");
         LendingError::InvalidOracleConfig
     })?;
+    config.validate_oracle_confidence(price, pyth_price.agg.conf)?;
 
     let market_price = if pyth_price.expo >= 0 {
         let exponent = pyth_price
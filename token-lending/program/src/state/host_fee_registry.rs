@@ -0,0 +1,269 @@
+use {
+    super::*,
+    crate::error::LendingError,
+    arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
+    solana_program::{
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        program_pack::{IsInitialized, Pack, Sealed},
+        pubkey::{Pubkey, PUBKEY_BYTES},
+    },
+    std::convert::TryFrom,
+};
+
+/// Max number of hosts that can be registered for a lending market
+pub const MAX_HOST_FEE_REGISTRY_ENTRIES: usize = 10;
+
+/// Registry of host fee receivers and their individual share of borrow fees
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HostFeeRegistry {
+    /// Version of the struct
+    pub version: u8,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Registered hosts, unique by host fee receiver address
+    pub hosts: Vec<HostFeeShare>,
+}
+
+impl HostFeeRegistry {
+    /// Create a new host fee registry
+    pub fn new(params: InitHostFeeRegistryParams) -> Self {
+        let mut host_fee_registry = Self::default();
+        Self::init(&mut host_fee_registry, params);
+        host_fee_registry
+    }
+
+    /// Initialize a host fee registry
+    pub fn init(&mut self, params: InitHostFeeRegistryParams) {
+        self.version = PROGRAM_VERSION;
+        self.lending_market = params.lending_market;
+        self.hosts = vec![];
+    }
+
+    /// Find the share of a registered host fee receiver
+    pub fn find_host_fee_share(&self, host_fee_receiver: &Pubkey) -> Option<&HostFeeShare> {
+        self.hosts
+            .iter()
+            .find(|host| &host.host_fee_receiver == host_fee_receiver)
+    }
+
+    /// Add or update the share of a host fee receiver, removing it from the
+    /// registry if the share is zero
+    pub fn set_host_fee_share(
+        &mut self,
+        host_fee_receiver: Pubkey,
+        host_fee_percentage: u8,
+    ) -> ProgramResult {
+        if host_fee_percentage > MAX_HOST_FEE_PERCENTAGE {
+            msg!(
+                "Host fee percentage must be in range [0, {}]",
+                MAX_HOST_FEE_PERCENTAGE
+            );
+            return Err(LendingError::InvalidConfig.into());
+        }
+
+        let host_index = self
+            .hosts
+            .iter()
+            .position(|host| host.host_fee_receiver == host_fee_receiver);
+        match (host_index, host_fee_percentage) {
+            (Some(host_index), 0) => {
+                self.hosts.remove(host_index);
+            }
+            (Some(host_index), _) => {
+                self.hosts[host_index].host_fee_percentage = host_fee_percentage;
+            }
+            (None, 0) => {
+                msg!("Host fee receiver is not registered");
+                return Err(LendingError::HostFeeReceiverNotRegistered.into());
+            }
+            (None, _) => {
+                if self.hosts.len() >= MAX_HOST_FEE_REGISTRY_ENTRIES {
+                    msg!(
+                        "Host fee registry cannot have more than {} hosts",
+                        MAX_HOST_FEE_REGISTRY_ENTRIES
+                    );
+                    return Err(LendingError::HostFeeRegistryFull.into());
+                }
+                self.hosts.push(HostFeeShare {
+                    host_fee_receiver,
+                    host_fee_percentage,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Initialize a host fee registry
+pub struct InitHostFeeRegistryParams {
+    /// Lending market address
+    pub lending_market: Pubkey,
+}
+
+/// Share of borrow fees paid to a registered host
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HostFeeShare {
+    /// Host fee receiver token account
+    pub host_fee_receiver: Pubkey,
+    /// Amount of fee going to the host fee receiver, as a percentage
+    pub host_fee_percentage: u8,
+}
+
+impl Sealed for HostFeeRegistry {}
+impl IsInitialized for HostFeeRegistry {
+    fn is_initialized(&self) -> bool {
+        self.version != UNINITIALIZED_VERSION
+    }
+}
+
+const HOST_FEE_SHARE_LEN: usize = 33; // 32 + 1
+const HOST_FEE_REGISTRY_LEN: usize = 428; // 1 + 32 + 1 + (33 * 10) + 64
+impl Pack for HostFeeRegistry {
+    const LEN: usize = HOST_FEE_REGISTRY_LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let output = array_mut_ref![dst, 0, HOST_FEE_REGISTRY_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (version, lending_market, hosts_len, data_flat, _padding) = mut_array_refs![
+            output,
+            1,
+            PUBKEY_BYTES,
+            1,
+            HOST_FEE_SHARE_LEN * MAX_HOST_FEE_REGISTRY_ENTRIES,
+            64
+        ];
+
+        *version = self.version.to_le_bytes();
+        lending_market.copy_from_slice(self.lending_market.as_ref());
+        *hosts_len = u8::try_from(self.hosts.len()).unwrap().to_le_bytes();
+
+        let mut offset = 0;
+        for host in &self.hosts {
+            let host_flat = array_mut_ref![data_flat, offset, HOST_FEE_SHARE_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (host_fee_receiver, host_fee_percentage) =
+                mut_array_refs![host_flat, PUBKEY_BYTES, 1];
+            host_fee_receiver.copy_from_slice(host.host_fee_receiver.as_ref());
+            *host_fee_percentage = host.host_fee_percentage.to_le_bytes();
+            offset += HOST_FEE_SHARE_LEN;
+        }
+    }
+
+    /// Unpacks a byte buffer into a
+    /// [HostFeeRegistry](struct.HostFeeRegistry.html).
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![src, 0, HOST_FEE_REGISTRY_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (version, lending_market, hosts_len, data_flat, _padding) = array_refs![
+            input,
+            1,
+            PUBKEY_BYTES,
+            1,
+            HOST_FEE_SHARE_LEN * MAX_HOST_FEE_REGISTRY_ENTRIES,
+            64
+        ];
+
+        let version = u8::from_le_bytes(*version);
+        if version > PROGRAM_VERSION {
+            msg!("Host fee registry version does not match lending program version");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let hosts_len = u8::from_le_bytes(*hosts_len) as usize;
+        if hosts_len > MAX_HOST_FEE_REGISTRY_ENTRIES {
+            msg!("Host fee registry has too many hosts");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut hosts = Vec::with_capacity(hosts_len);
+        let mut offset = 0;
+        for _ in 0..hosts_len {
+            let host_flat = array_ref![data_flat, offset, HOST_FEE_SHARE_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (host_fee_receiver, host_fee_percentage) = array_refs![host_flat, PUBKEY_BYTES, 1];
+            hosts.push(HostFeeShare {
+                host_fee_receiver: Pubkey::new_from_array(*host_fee_receiver),
+                host_fee_percentage: u8::from_le_bytes(*host_fee_percentage),
+            });
+            offset += HOST_FEE_SHARE_LEN;
+        }
+
+        Ok(Self {
+            version,
+            lending_market: Pubkey::new_from_array(*lending_market),
+            hosts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pack_and_unpack_host_fee_registry() {
+        let mut host_fee_registry = HostFeeRegistry::new(InitHostFeeRegistryParams {
+            lending_market: Pubkey::new_unique(),
+        });
+        let host_fee_receiver = Pubkey::new_unique();
+        host_fee_registry
+            .set_host_fee_share(host_fee_receiver, 20)
+            .unwrap();
+        host_fee_registry
+            .set_host_fee_share(Pubkey::new_unique(), 10)
+            .unwrap();
+
+        let mut packed = [0u8; HostFeeRegistry::LEN];
+        HostFeeRegistry::pack(host_fee_registry.clone(), &mut packed).unwrap();
+        let unpacked = HostFeeRegistry::unpack(&packed).unwrap();
+        assert_eq!(unpacked, host_fee_registry);
+        assert_eq!(
+            unpacked
+                .find_host_fee_share(&host_fee_receiver)
+                .unwrap()
+                .host_fee_percentage,
+            20
+        );
+    }
+
+    #[test]
+    fn set_host_fee_share_limits() {
+        let mut host_fee_registry = HostFeeRegistry::new(InitHostFeeRegistryParams {
+            lending_market: Pubkey::new_unique(),
+        });
+        assert_eq!(
+            host_fee_registry.set_host_fee_share(Pubkey::new_unique(), MAX_HOST_FEE_PERCENTAGE + 1),
+            Err(LendingError::InvalidConfig.into())
+        );
+        assert_eq!(
+            host_fee_registry.set_host_fee_share(Pubkey::new_unique(), 0),
+            Err(LendingError::HostFeeReceiverNotRegistered.into())
+        );
+
+        let host_fee_receivers: Vec<Pubkey> = (0..MAX_HOST_FEE_REGISTRY_ENTRIES)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for host_fee_receiver in &host_fee_receivers {
+            host_fee_registry
+                .set_host_fee_share(*host_fee_receiver, 1)
+                .unwrap();
+        }
+        assert_eq!(
+            host_fee_registry.set_host_fee_share(Pubkey::new_unique(), 1),
+            Err(LendingError::HostFeeRegistryFull.into())
+        );
+
+        host_fee_registry
+            .set_host_fee_share(host_fee_receivers[0], 0)
+            .unwrap();
+        assert!(host_fee_registry
+            .find_host_fee_share(&host_fee_receivers[0])
+            .is_none());
+        assert_eq!(
+            host_fee_registry.hosts.len(),
+            MAX_HOST_FEE_REGISTRY_ENTRIES - 1
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use {
     super::*,
+    crate::math::Decimal,
     arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
     solana_program::{
         msg,
@@ -9,6 +10,14 @@ use {
     },
 };
 
+/// Hard cap on the share of borrow and flash loan fees paid to a host fee
+/// receiver, as a percentage
+pub const MAX_HOST_FEE_PERCENTAGE: u8 = 50;
+
+/// Hard cap on the liquidation bonus paid at the end of a liquidation
+/// auction, as a percentage
+pub const MAX_LIQUIDATION_AUCTION_BONUS: u8 = 100;
+
 /// Lending market state
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct LendingMarket {
@@ -27,6 +36,28 @@ pub struct LendingMarket {
     pub token_program_id: Pubkey,
     /// Oracle (Pyth) program id
     pub oracle_program_id: Pubkey,
+    /// Maximum share of fees paid to a host fee receiver, as a percentage.
+    /// Set by the owner up to `MAX_HOST_FEE_PERCENTAGE`, `None` leaves the
+    /// reserve host fee percentage uncapped.
+    pub host_fee_percentage_cap: Option<u8>,
+    /// Host fee registry of the lending market, set when it is initialized.
+    /// Borrows paying a host fee receiver must then provide the registry, and
+    /// only pay registered receivers.
+    pub host_fee_registry: Option<Pubkey>,
+    /// Obligations with a borrowed value of at least
+    /// `min_borrowed_value` must be liquidated through a liquidation auction
+    /// instead of the fixed liquidation bonus. `None` disables auctions.
+    pub liquidation_auction_config: Option<LiquidationAuctionConfig>,
+    /// Risk admin which can update reserve configs and the liquidation
+    /// auction config. `None` leaves these powers with the owner.
+    pub risk_admin: Option<Pubkey>,
+    /// Operations admin which can add new reserves and update reserve
+    /// oracles. `None` leaves these powers with the owner.
+    pub ops_admin: Option<Pubkey>,
+    /// Number of slots an obligation must stay unhealthy, as recorded by its
+    /// obligation health account, before it can be liquidated. Zero disables
+    /// the grace period.
+    pub liquidation_grace_slots: u64,
 }
 
 impl LendingMarket {
@@ -45,7 +76,53 @@ impl LendingMarket {
         self.quote_currency = params.quote_currency;
         self.token_program_id = params.token_program_id;
         self.oracle_program_id = params.oracle_program_id;
+        self.host_fee_percentage_cap = None;
+        self.host_fee_registry = None;
+        self.liquidation_auction_config = None;
+        self.risk_admin = None;
+        self.ops_admin = None;
+        self.liquidation_grace_slots = 0;
+    }
+
+    /// Authority allowed to update reserve risk parameters
+    pub fn risk_authority(&self) -> Pubkey {
+        self.risk_admin.unwrap_or(self.owner)
+    }
+
+    /// Authority allowed to add reserves and update reserve oracles
+    pub fn ops_authority(&self) -> Pubkey {
+        self.ops_admin.unwrap_or(self.owner)
     }
+
+    /// Apply the market host fee percentage cap to a host fee percentage
+    pub fn cap_host_fee_percentage(&self, host_fee_percentage: u8) -> u8 {
+        match self.host_fee_percentage_cap {
+            Some(host_fee_percentage_cap) => host_fee_percentage.min(host_fee_percentage_cap),
+            None => host_fee_percentage,
+        }
+    }
+
+    /// Check if an obligation with the given borrowed value must be
+    /// liquidated through a liquidation auction
+    pub fn requires_liquidation_auction(&self, borrowed_value: Decimal) -> bool {
+        match &self.liquidation_auction_config {
+            Some(config) => borrowed_value >= Decimal::from(config.min_borrowed_value),
+            None => false,
+        }
+    }
+}
+
+/// Lending market liquidation auction configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LiquidationAuctionConfig {
+    /// Minimum borrowed value of an obligation, in quote currency, for it to
+    /// be liquidated through an auction
+    pub min_borrowed_value: u64,
+    /// Number of slots over which the liquidation bonus grows from zero to
+    /// `max_bonus`
+    pub duration_slots: u64,
+    /// Liquidation bonus paid at the end of the auction, as a percentage
+    pub max_bonus: u8,
 }
 
 /// Initialize a lending market
@@ -72,7 +149,7 @@ impl IsInitialized for LendingMarket {
     }
 }
 
-const LENDING_MARKET_LEN: usize = 258; // 1 + 1 + 32 + 32 + 32 + 32 + 128
+const LENDING_MARKET_LEN: usize = 258; // 1 + 1 + 32 + 32 + 32 + 32 + 1 + 1 + 1 + 32 + 1 + 8 + 8 + 1 + 1 + 32 + 1 + 32 + 8 + 1
 impl Pack for LendingMarket {
     const LEN: usize = LENDING_MARKET_LEN;
 
@@ -86,6 +163,19 @@ impl Pack for LendingMarket {
             quote_currency,
             token_program_id,
             oracle_program_id,
+            host_fee_percentage_cap_flag,
+            host_fee_percentage_cap,
+            host_fee_registry_flag,
+            host_fee_registry,
+            liquidation_auction_config_flag,
+            min_borrowed_value,
+            duration_slots,
+            max_bonus,
+            risk_admin_flag,
+            risk_admin,
+            ops_admin_flag,
+            ops_admin,
+            liquidation_grace_slots,
             _padding,
         ) = mut_array_refs![
             output,
@@ -95,7 +185,20 @@ impl Pack for LendingMarket {
             32,
             PUBKEY_BYTES,
             PUBKEY_BYTES,
-            128
+            1,
+            1,
+            1,
+            PUBKEY_BYTES,
+            1,
+            8,
+            8,
+            1,
+            1,
+            PUBKEY_BYTES,
+            1,
+            PUBKEY_BYTES,
+            8,
+            1
         ];
 
         *version = self.version.to_le_bytes();
@@ -104,6 +207,26 @@ impl Pack for LendingMarket {
         quote_currency.copy_from_slice(self.quote_currency.as_ref());
         token_program_id.copy_from_slice(self.token_program_id.as_ref());
         oracle_program_id.copy_from_slice(self.oracle_program_id.as_ref());
+        pack_bool(
+            self.host_fee_percentage_cap.is_some(),
+            host_fee_percentage_cap_flag,
+        );
+        *host_fee_percentage_cap = self.host_fee_percentage_cap.unwrap_or(0).to_le_bytes();
+        pack_bool(self.host_fee_registry.is_some(), host_fee_registry_flag);
+        host_fee_registry.copy_from_slice(self.host_fee_registry.unwrap_or_default().as_ref());
+        pack_bool(
+            self.liquidation_auction_config.is_some(),
+            liquidation_auction_config_flag,
+        );
+        let config = self.liquidation_auction_config.unwrap_or_default();
+        *min_borrowed_value = config.min_borrowed_value.to_le_bytes();
+        *duration_slots = config.duration_slots.to_le_bytes();
+        *max_bonus = config.max_bonus.to_le_bytes();
+        pack_bool(self.risk_admin.is_some(), risk_admin_flag);
+        risk_admin.copy_from_slice(self.risk_admin.unwrap_or_default().as_ref());
+        pack_bool(self.ops_admin.is_some(), ops_admin_flag);
+        ops_admin.copy_from_slice(self.ops_admin.unwrap_or_default().as_ref());
+        *liquidation_grace_slots = self.liquidation_grace_slots.to_le_bytes();
     }
 
     /// Unpacks a byte buffer into a
@@ -118,6 +241,19 @@ impl Pack for LendingMarket {
             quote_currency,
             token_program_id,
             oracle_program_id,
+            host_fee_percentage_cap_flag,
+            host_fee_percentage_cap,
+            host_fee_registry_flag,
+            host_fee_registry,
+            liquidation_auction_config_flag,
+            min_borrowed_value,
+            duration_slots,
+            max_bonus,
+            risk_admin_flag,
+            risk_admin,
+            ops_admin_flag,
+            ops_admin,
+            liquidation_grace_slots,
             _padding,
         ) = array_refs![
             input,
@@ -127,7 +263,20 @@ impl Pack for LendingMarket {
             32,
             PUBKEY_BYTES,
             PUBKEY_BYTES,
-            128
+            1,
+            1,
+            1,
+            PUBKEY_BYTES,
+            1,
+            8,
+            8,
+            1,
+            1,
+            PUBKEY_BYTES,
+            1,
+            PUBKEY_BYTES,
+            8,
+            1
         ];
 
         let version = u8::from_le_bytes(*version);
@@ -143,6 +292,70 @@ impl Pack for LendingMarket {
             quote_currency: *quote_currency,
             token_program_id: Pubkey::new_from_array(*token_program_id),
             oracle_program_id: Pubkey::new_from_array(*oracle_program_id),
+            host_fee_percentage_cap: if unpack_bool(host_fee_percentage_cap_flag)? {
+                Some(u8::from_le_bytes(*host_fee_percentage_cap))
+            } else {
+                None
+            },
+            host_fee_registry: if unpack_bool(host_fee_registry_flag)? {
+                Some(Pubkey::new_from_array(*host_fee_registry))
+            } else {
+                None
+            },
+            liquidation_auction_config: if unpack_bool(liquidation_auction_config_flag)? {
+                Some(LiquidationAuctionConfig {
+                    min_borrowed_value: u64::from_le_bytes(*min_borrowed_value),
+                    duration_slots: u64::from_le_bytes(*duration_slots),
+                    max_bonus: u8::from_le_bytes(*max_bonus),
+                })
+            } else {
+                None
+            },
+            risk_admin: if unpack_bool(risk_admin_flag)? {
+                Some(Pubkey::new_from_array(*risk_admin))
+            } else {
+                None
+            },
+            ops_admin: if unpack_bool(ops_admin_flag)? {
+                Some(Pubkey::new_from_array(*ops_admin))
+            } else {
+                None
+            },
+            liquidation_grace_slots: u64::from_le_bytes(*liquidation_grace_slots),
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn admins_default_to_owner() {
+        let owner = Pubkey::new_unique();
+        let mut lending_market = LendingMarket::new(InitLendingMarketParams {
+            bump_seed: 255,
+            owner,
+            quote_currency: [0; 32],
+            token_program_id: Pubkey::new_unique(),
+            oracle_program_id: Pubkey::new_unique(),
+        });
+
+        // markets created before the admin roles have zeroed padding
+        let mut data = [0u8; LENDING_MARKET_LEN];
+        LendingMarket::pack(lending_market.clone(), &mut data).unwrap();
+        let unpacked = LendingMarket::unpack(&data).unwrap();
+        assert_eq!(unpacked.risk_admin, None);
+        assert_eq!(unpacked.ops_admin, None);
+        assert_eq!(unpacked.risk_authority(), owner);
+        assert_eq!(unpacked.ops_authority(), owner);
+
+        let risk_admin = Pubkey::new_unique();
+        lending_market.risk_admin = Some(risk_admin);
+        LendingMarket::pack(lending_market.clone(), &mut data).unwrap();
+        let unpacked = LendingMarket::unpack(&data).unwrap();
+        assert_eq!(unpacked, lending_market);
+        assert_eq!(unpacked.risk_authority(), risk_admin);
+        assert_eq!(unpacked.ops_authority(), owner);
+    }
+}
@@ -0,0 +1,182 @@
+use {
+    super::*,
+    crate::math::{Rate, TryDiv, TryMul},
+    arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
+    solana_program::{
+        clock::Slot,
+        msg,
+        program_error::ProgramError,
+        program_pack::{IsInitialized, Pack, Sealed},
+        pubkey::{Pubkey, PUBKEY_BYTES},
+    },
+};
+
+/// Dutch liquidation auction for an unhealthy obligation. The liquidation
+/// bonus grows linearly from zero to `max_bonus` over the auction window and
+/// then stays at `max_bonus`. Liquidators can fill portions of the obligation
+/// at any time once the auction has started.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LiquidationAuction {
+    /// Version of the struct
+    pub version: u8,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Obligation being liquidated
+    pub obligation: Pubkey,
+    /// Slot when the auction started
+    pub start_slot: Slot,
+    /// Number of slots over which the liquidation bonus grows
+    pub duration_slots: u64,
+    /// Liquidation bonus paid at the end of the auction, as a percentage
+    pub max_bonus: u8,
+}
+
+impl LiquidationAuction {
+    /// Create a new liquidation auction
+    pub fn new(params: InitLiquidationAuctionParams) -> Self {
+        let mut liquidation_auction = Self::default();
+        Self::init(&mut liquidation_auction, params);
+        liquidation_auction
+    }
+
+    /// Initialize a liquidation auction
+    pub fn init(&mut self, params: InitLiquidationAuctionParams) {
+        self.version = PROGRAM_VERSION;
+        self.lending_market = params.lending_market;
+        self.obligation = params.obligation;
+        self.start_slot = params.current_slot;
+        self.duration_slots = params.config.duration_slots;
+        self.max_bonus = params.config.max_bonus;
+    }
+
+    /// Calculate the liquidation bonus paid at the given slot, capped at
+    /// `max_bonus` once the auction window has passed
+    pub fn current_bonus(&self, slot: Slot) -> Result<Rate, ProgramError> {
+        if self.duration_slots == 0 {
+            return Ok(Rate::from_percent(self.max_bonus));
+        }
+        let elapsed_slots = slot
+            .saturating_sub(self.start_slot)
+            .min(self.duration_slots);
+        Rate::from_percent(self.max_bonus)
+            .try_mul(elapsed_slots)?
+            .try_div(self.duration_slots)
+    }
+}
+
+/// Initialize a liquidation auction
+pub struct InitLiquidationAuctionParams {
+    /// Last slot when the auction was started
+    pub current_slot: Slot,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Obligation being liquidated
+    pub obligation: Pubkey,
+    /// Lending market auction configuration
+    pub config: LiquidationAuctionConfig,
+}
+
+impl Sealed for LiquidationAuction {}
+impl IsInitialized for LiquidationAuction {
+    fn is_initialized(&self) -> bool {
+        self.version != UNINITIALIZED_VERSION
+    }
+}
+
+const LIQUIDATION_AUCTION_LEN: usize = 146; // 1 + 32 + 32 + 8 + 8 + 1 + 64
+impl Pack for LiquidationAuction {
+    const LEN: usize = LIQUIDATION_AUCTION_LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let output = array_mut_ref![dst, 0, LIQUIDATION_AUCTION_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (version, lending_market, obligation, start_slot, duration_slots, max_bonus, _padding) =
+            mut_array_refs![output, 1, PUBKEY_BYTES, PUBKEY_BYTES, 8, 8, 1, 64];
+
+        *version = self.version.to_le_bytes();
+        lending_market.copy_from_slice(self.lending_market.as_ref());
+        obligation.copy_from_slice(self.obligation.as_ref());
+        *start_slot = self.start_slot.to_le_bytes();
+        *duration_slots = self.duration_slots.to_le_bytes();
+        *max_bonus = self.max_bonus.to_le_bytes();
+    }
+
+    /// Unpacks a byte buffer into a
+    /// [LiquidationAuction](struct.LiquidationAuction.html).
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![src, 0, LIQUIDATION_AUCTION_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (version, lending_market, obligation, start_slot, duration_slots, max_bonus, _padding) =
+            array_refs![input, 1, PUBKEY_BYTES, PUBKEY_BYTES, 8, 8, 1, 64];
+
+        let version = u8::from_le_bytes(*version);
+        if version > PROGRAM_VERSION {
+            msg!("Liquidation auction version does not match lending program version");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            version,
+            lending_market: Pubkey::new_from_array(*lending_market),
+            obligation: Pubkey::new_from_array(*obligation),
+            start_slot: u64::from_le_bytes(*start_slot),
+            duration_slots: u64::from_le_bytes(*duration_slots),
+            max_bonus: u8::from_le_bytes(*max_bonus),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pack_and_unpack_liquidation_auction() {
+        let liquidation_auction = LiquidationAuction::new(InitLiquidationAuctionParams {
+            current_slot: 42,
+            lending_market: Pubkey::new_unique(),
+            obligation: Pubkey::new_unique(),
+            config: LiquidationAuctionConfig {
+                min_borrowed_value: 1_000_000,
+                duration_slots: 100,
+                max_bonus: 20,
+            },
+        });
+
+        let mut packed = [0u8; LiquidationAuction::LEN];
+        LiquidationAuction::pack(liquidation_auction.clone(), &mut packed).unwrap();
+        let unpacked = LiquidationAuction::unpack(&packed).unwrap();
+        assert_eq!(unpacked, liquidation_auction);
+    }
+
+    #[test]
+    fn liquidation_auction_bonus_grows_until_max() {
+        let liquidation_auction = LiquidationAuction::new(InitLiquidationAuctionParams {
+            current_slot: 100,
+            lending_market: Pubkey::new_unique(),
+            obligation: Pubkey::new_unique(),
+            config: LiquidationAuctionConfig {
+                min_borrowed_value: 0,
+                duration_slots: 100,
+                max_bonus: 20,
+            },
+        });
+
+        assert_eq!(
+            liquidation_auction.current_bonus(100).unwrap(),
+            Rate::zero()
+        );
+        assert_eq!(
+            liquidation_auction.current_bonus(150).unwrap(),
+            Rate::from_percent(10)
+        );
+        assert_eq!(
+            liquidation_auction.current_bonus(200).unwrap(),
+            Rate::from_percent(20)
+        );
+        assert_eq!(
+            liquidation_auction.current_bonus(1_000).unwrap(),
+            Rate::from_percent(20)
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use {
+    super::*,
+    crate::{
+        error::LendingError,
+        math::{Decimal, Rate, TryDiv, TrySub},
+    },
+    arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
+    solana_program::{
+        clock::Slot,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        program_pack::{IsInitialized, Pack, Sealed},
+        pubkey::{Pubkey, PUBKEY_BYTES},
+    },
+    std::convert::TryFrom,
+};
+
+/// Max number of obligations a margin account can aggregate
+pub const MAX_MARGIN_ACCOUNT_OBLIGATIONS: usize = 5;
+
+/// Margin account netting the health of several obligations of the same
+/// owner. Obligations added to a margin account are owned by the lending
+/// market authority until removed, so they can only be borrowed against,
+/// withdrawn from and liquidated through the margin account.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarginAccount {
+    /// Version of the struct
+    pub version: u8,
+    /// Last update to the aggregated market values
+    pub last_update: LastUpdate,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Owner authority which can borrow against the margin account
+    pub owner: Pubkey,
+    /// Obligations aggregated by the margin account, in refresh order
+    pub obligations: Vec<Pubkey>,
+    /// Market value of deposits across all obligations
+    pub deposited_value: Decimal,
+    /// Market value of borrows across all obligations
+    pub borrowed_value: Decimal,
+    /// The maximum borrow value across all obligations
+    pub allowed_borrow_value: Decimal,
+    /// The dangerous borrow value across all obligations
+    pub unhealthy_borrow_value: Decimal,
+}
+
+impl MarginAccount {
+    /// Create a new margin account
+    pub fn new(params: InitMarginAccountParams) -> Self {
+        let mut margin_account = Self::default();
+        Self::init(&mut margin_account, params);
+        margin_account
+    }
+
+    /// Initialize a margin account
+    pub fn init(&mut self, params: InitMarginAccountParams) {
+        self.version = PROGRAM_VERSION;
+        self.last_update = LastUpdate::new(params.current_slot);
+        self.lending_market = params.lending_market;
+        self.owner = params.owner;
+        self.obligations = vec![];
+    }
+
+    /// Calculate the maximum liquidity value that can be borrowed
+    pub fn remaining_borrow_value(&self) -> Result<Decimal, ProgramError> {
+        self.allowed_borrow_value.try_sub(self.borrowed_value)
+    }
+
+    /// Calculate the maximum collateral value that can be withdrawn
+    pub fn max_withdraw_value(
+        &self,
+        withdraw_collateral_ltv: Rate,
+    ) -> Result<Decimal, ProgramError> {
+        if self.allowed_borrow_value <= self.borrowed_value {
+            return Ok(Decimal::zero());
+        }
+        if withdraw_collateral_ltv == Rate::zero() {
+            return Ok(self.deposited_value);
+        }
+        self.allowed_borrow_value
+            .try_sub(self.borrowed_value)?
+            .try_div(withdraw_collateral_ltv)
+    }
+
+    /// Check if the obligations of the margin account can be liquidated
+    pub fn is_unhealthy(&self) -> bool {
+        self.borrowed_value > Decimal::zero() && self.borrowed_value >= self.unhealthy_borrow_value
+    }
+
+    /// Find the index of an obligation in the margin account
+    pub fn find_obligation(&self, obligation: &Pubkey) -> Option<usize> {
+        self.obligations.iter().position(|key| key == obligation)
+    }
+
+    /// Add an obligation to the margin account
+    pub fn add_obligation(&mut self, obligation: Pubkey) -> ProgramResult {
+        if self.find_obligation(&obligation).is_some() {
+            msg!("Obligation is already part of the margin account");
+            return Err(LendingError::MarginObligationInvalid.into());
+        }
+        if self.obligations.len() >= MAX_MARGIN_ACCOUNT_OBLIGATIONS {
+            msg!(
+                "Margin account cannot have more than {} obligations",
+                MAX_MARGIN_ACCOUNT_OBLIGATIONS
+            );
+            return Err(LendingError::MarginAccountFull.into());
+        }
+        self.obligations.push(obligation);
+        Ok(())
+    }
+
+    /// Remove an obligation from the margin account
+    pub fn remove_obligation(&mut self, obligation: &Pubkey) -> ProgramResult {
+        let index = self.find_obligation(obligation).ok_or_else(|| {
+            msg!("Obligation is not part of the margin account");
+            LendingError::MarginObligationInvalid
+        })?;
+        self.obligations.remove(index);
+        Ok(())
+    }
+}
+
+/// Initialize a margin account
+pub struct InitMarginAccountParams {
+    /// Last update to the aggregated market values
+    pub current_slot: Slot,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Owner authority which can borrow against the margin account
+    pub owner: Pubkey,
+}
+
+impl Sealed for MarginAccount {}
+impl IsInitialized for MarginAccount {
+    fn is_initialized(&self) -> bool {
+        self.version != UNINITIALIZED_VERSION
+    }
+}
+
+const MARGIN_ACCOUNT_LEN: usize = 363; // 1 + 8 + 1 + 32 + 32 + 16 + 16 + 16 + 16 + 1 + (32 * 5) + 64
+impl Pack for MarginAccount {
+    const LEN: usize = MARGIN_ACCOUNT_LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let output = array_mut_ref![dst, 0, MARGIN_ACCOUNT_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            version,
+            last_update_slot,
+            last_update_stale,
+            lending_market,
+            owner,
+            deposited_value,
+            borrowed_value,
+            allowed_borrow_value,
+            unhealthy_borrow_value,
+            obligations_len,
+            obligations_flat,
+            _padding,
+        ) = mut_array_refs![
+            output,
+            1,
+            8,
+            1,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            16,
+            16,
+            16,
+            16,
+            1,
+            PUBKEY_BYTES * MAX_MARGIN_ACCOUNT_OBLIGATIONS,
+            64
+        ];
+
+        *version = self.version.to_le_bytes();
+        *last_update_slot = self.last_update.slot.to_le_bytes();
+        pack_bool(self.last_update.stale, last_update_stale);
+        lending_market.copy_from_slice(self.lending_market.as_ref());
+        owner.copy_from_slice(self.owner.as_ref());
+        pack_decimal(self.deposited_value, deposited_value);
+        pack_decimal(self.borrowed_value, borrowed_value);
+        pack_decimal(self.allowed_borrow_value, allowed_borrow_value);
+        pack_decimal(self.unhealthy_borrow_value, unhealthy_borrow_value);
+        *obligations_len = u8::try_from(self.obligations.len()).unwrap().to_le_bytes();
+
+        for (obligation, obligation_flat) in self
+            .obligations
+            .iter()
+            .zip(obligations_flat.chunks_exact_mut(PUBKEY_BYTES))
+        {
+            obligation_flat.copy_from_slice(obligation.as_ref());
+        }
+    }
+
+    /// Unpacks a byte buffer into a
+    /// [MarginAccount](struct.MarginAccount.html).
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![src, 0, MARGIN_ACCOUNT_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            version,
+            last_update_slot,
+            last_update_stale,
+            lending_market,
+            owner,
+            deposited_value,
+            borrowed_value,
+            allowed_borrow_value,
+            unhealthy_borrow_value,
+            obligations_len,
+            obligations_flat,
+            _padding,
+        ) = array_refs![
+            input,
+            1,
+            8,
+            1,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            16,
+            16,
+            16,
+            16,
+            1,
+            PUBKEY_BYTES * MAX_MARGIN_ACCOUNT_OBLIGATIONS,
+            64
+        ];
+
+        let version = u8::from_le_bytes(*version);
+        if version > PROGRAM_VERSION {
+            msg!("Margin account version does not match lending program version");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let obligations_len = u8::from_le_bytes(*obligations_len) as usize;
+        if obligations_len > MAX_MARGIN_ACCOUNT_OBLIGATIONS {
+            msg!("Margin account obligations length exceeds the maximum");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let obligations = obligations_flat
+            .chunks_exact(PUBKEY_BYTES)
+            .take(obligations_len)
+            .map(|obligation| Pubkey::new_from_array(*array_ref![obligation, 0, PUBKEY_BYTES]))
+            .collect();
+
+        Ok(Self {
+            version,
+            last_update: LastUpdate {
+                slot: u64::from_le_bytes(*last_update_slot),
+                stale: unpack_bool(last_update_stale)?,
+            },
+            lending_market: Pubkey::new_from_array(*lending_market),
+            owner: Pubkey::new_from_array(*owner),
+            obligations,
+            deposited_value: unpack_decimal(deposited_value),
+            borrowed_value: unpack_decimal(borrowed_value),
+            allowed_borrow_value: unpack_decimal(allowed_borrow_value),
+            unhealthy_borrow_value: unpack_decimal(unhealthy_borrow_value),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pack_and_unpack_margin_account() {
+        let mut margin_account = MarginAccount::new(InitMarginAccountParams {
+            current_slot: 42,
+            lending_market: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+        });
+        margin_account.add_obligation(Pubkey::new_unique()).unwrap();
+        margin_account.add_obligation(Pubkey::new_unique()).unwrap();
+        margin_account.deposited_value = Decimal::from(100u64);
+        margin_account.borrowed_value = Decimal::from(40u64);
+        margin_account.allowed_borrow_value = Decimal::from(50u64);
+        margin_account.unhealthy_borrow_value = Decimal::from(55u64);
+
+        let mut packed = [0u8; MarginAccount::LEN];
+        MarginAccount::pack(margin_account.clone(), &mut packed).unwrap();
+        let unpacked = MarginAccount::unpack(&packed).unwrap();
+        assert_eq!(unpacked, margin_account);
+    }
+
+    #[test]
+    fn margin_account_obligations_are_unique_and_bounded() {
+        let mut margin_account = MarginAccount::new(InitMarginAccountParams {
+            current_slot: 0,
+            lending_market: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+        });
+        let obligation = Pubkey::new_unique();
+        margin_account.add_obligation(obligation).unwrap();
+        assert_eq!(
+            margin_account.add_obligation(obligation),
+            Err(LendingError::MarginObligationInvalid.into())
+        );
+
+        for _ in 1..MAX_MARGIN_ACCOUNT_OBLIGATIONS {
+            margin_account.add_obligation(Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(
+            margin_account.add_obligation(Pubkey::new_unique()),
+            Err(LendingError::MarginAccountFull.into())
+        );
+
+        margin_account.remove_obligation(&obligation).unwrap();
+        assert_eq!(margin_account.find_obligation(&obligation), None);
+        assert_eq!(
+            margin_account.remove_obligation(&obligation),
+            Err(LendingError::MarginObligationInvalid.into())
+        );
+    }
+
+    #[test]
+    fn margin_account_health_nets_obligations() {
+        let margin_account = MarginAccount {
+            deposited_value: Decimal::from(200u64),
+            borrowed_value: Decimal::from(90u64),
+            allowed_borrow_value: Decimal::from(100u64),
+            unhealthy_borrow_value: Decimal::from(120u64),
+            ..MarginAccount::default()
+        };
+        assert!(!margin_account.is_unhealthy());
+        assert_eq!(
+            margin_account.remaining_borrow_value().unwrap(),
+            Decimal::from(10u64)
+        );
+        assert_eq!(
+            margin_account
+                .max_withdraw_value(Rate::from_percent(50))
+                .unwrap(),
+            Decimal::from(20u64)
+        );
+
+        let unhealthy_margin_account = MarginAccount {
+            borrowed_value: Decimal::from(120u64),
+            ..margin_account
+        };
+        assert!(unhealthy_margin_account.is_unhealthy());
+        assert_eq!(
+            unhealthy_margin_account
+                .max_withdraw_value(Rate::from_percent(50))
+                .unwrap(),
+            Decimal::zero()
+        );
+    }
+}
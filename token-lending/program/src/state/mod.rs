@@ -1,9 +1,15 @@
 //! State types
 
+mod host_fee_registry;
 mod last_update;
 mod lending_market;
+mod liquidation_auction;
+mod margin_account;
 mod obligation;
+mod obligation_health;
+mod protocol_liquidity;
 mod reserve;
+mod reserve_rate_history;
 
 use {
     crate::math::{Decimal, WAD},
@@ -13,7 +19,11 @@ use {
         program_error::ProgramError,
     },
 };
-pub use {last_update::*, lending_market::*, obligation::*, reserve::*};
+pub use {
+    host_fee_registry::*, last_update::*, lending_market::*, liquidation_auction::*,
+    margin_account::*, obligation::*, obligation_health::*, protocol_liquidity::*, reserve::*,
+    reserve_rate_history::*,
+};
 
 /// Collateral tokens are initially valued at a ratio of 5:1
 /// (collateral:liquidity)
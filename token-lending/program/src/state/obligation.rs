@@ -23,6 +23,10 @@ use {
 /// obligation
 pub const MAX_OBLIGATION_RESERVES: usize = 10;
 
+/// Obligations with an unhealthy borrow value below this percentage of their
+/// borrowed value are at risk of liquidation and logged as such on refresh
+pub const OBLIGATION_HEALTH_WARNING_PERCENTAGE: u8 = 110;
+
 /// Lending market obligation state
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Obligation {
@@ -110,6 +114,22 @@ impl Obligation {
             .try_div(withdraw_collateral_ltv)
     }
 
+    /// Check if the obligation can be liquidated
+    pub fn is_unhealthy(&self) -> bool {
+        self.borrowed_value > Decimal::zero() && self.borrowed_value >= self.unhealthy_borrow_value
+    }
+
+    /// Check if the obligation is close to being liquidated
+    pub fn is_at_risk(&self) -> Result<bool, ProgramError> {
+        if self.borrowed_value == Decimal::zero() {
+            return Ok(false);
+        }
+        let warning_borrow_value = self
+            .borrowed_value
+            .try_mul(Rate::from_percent(OBLIGATION_HEALTH_WARNING_PERCENTAGE))?;
+        Ok(self.unhealthy_borrow_value < warning_borrow_value)
+    }
+
     /// Calculate the maximum liquidity value that can be borrowed
     pub fn remaining_borrow_value(&self) -> Result<Decimal, ProgramError> {
         self.allowed_borrow_value.try_sub(self.borrowed_value)
@@ -543,6 +563,27 @@ mod test {
         );
     }
 
+    #[test]
+    fn obligation_health_thresholds() {
+        let obligation = |borrowed_value: u64, unhealthy_borrow_value: u64| Obligation {
+            borrowed_value: Decimal::from(borrowed_value),
+            unhealthy_borrow_value: Decimal::from(unhealthy_borrow_value),
+            ..Obligation::default()
+        };
+
+        assert!(!obligation(0, 0).is_unhealthy());
+        assert!(!obligation(0, 0).is_at_risk().unwrap());
+
+        assert!(!obligation(100, 111).is_unhealthy());
+        assert!(!obligation(100, 111).is_at_risk().unwrap());
+
+        assert!(!obligation(100, 109).is_unhealthy());
+        assert!(obligation(100, 109).is_at_risk().unwrap());
+
+        assert!(obligation(100, 100).is_unhealthy());
+        assert!(obligation(100, 100).is_at_risk().unwrap());
+    }
+
     // Creates rates (r1, r2) where 0 < r1 <= r2 <= 100*r1
     prop_compose! {
         fn cumulative_rates()(rate in 1..=u128::MAX)(
@@ -0,0 +1,189 @@
+use {
+    super::*,
+    arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
+    solana_program::{
+        clock::Slot,
+        msg,
+        program_error::ProgramError,
+        program_pack::{IsInitialized, Pack, Sealed},
+        pubkey::{Pubkey, PUBKEY_BYTES},
+    },
+};
+
+/// Health history of an obligation, recorded by refreshes which include the
+/// account. Liquidations in a lending market with a liquidation grace period
+/// require the obligation to have stayed unhealthy for the whole period.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ObligationHealth {
+    /// Version of the struct
+    pub version: u8,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Obligation the health history is recorded for
+    pub obligation: Pubkey,
+    /// Slot of the last refresh which recorded the obligation health
+    pub last_update_slot: Slot,
+    /// Slot of the first refresh in the current run of unhealthy refreshes.
+    /// `None` if the obligation was healthy at the last recorded refresh.
+    pub unhealthy_since_slot: Option<Slot>,
+}
+
+impl ObligationHealth {
+    /// Create a new obligation health account
+    pub fn new(params: InitObligationHealthParams) -> Self {
+        let mut obligation_health = Self::default();
+        Self::init(&mut obligation_health, params);
+        obligation_health
+    }
+
+    /// Initialize an obligation health account
+    pub fn init(&mut self, params: InitObligationHealthParams) {
+        self.version = PROGRAM_VERSION;
+        self.lending_market = params.lending_market;
+        self.obligation = params.obligation;
+        self.last_update_slot = params.current_slot;
+        self.unhealthy_since_slot = None;
+    }
+
+    /// Record the obligation health at the given slot
+    pub fn update(&mut self, is_unhealthy: bool, slot: Slot) {
+        self.unhealthy_since_slot = if is_unhealthy {
+            Some(self.unhealthy_since_slot.unwrap_or(slot))
+        } else {
+            None
+        };
+        self.last_update_slot = slot;
+    }
+
+    /// Check if the obligation has been unhealthy for at least
+    /// `grace_slots` slots at the given slot
+    pub fn is_grace_period_elapsed(&self, grace_slots: u64, slot: Slot) -> bool {
+        match self.unhealthy_since_slot {
+            Some(unhealthy_since_slot) => slot.saturating_sub(unhealthy_since_slot) >= grace_slots,
+            None => false,
+        }
+    }
+}
+
+/// Initialize an obligation health account
+pub struct InitObligationHealthParams {
+    /// Last slot when the account was initialized
+    pub current_slot: Slot,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Obligation the health history is recorded for
+    pub obligation: Pubkey,
+}
+
+impl Sealed for ObligationHealth {}
+impl IsInitialized for ObligationHealth {
+    fn is_initialized(&self) -> bool {
+        self.version != UNINITIALIZED_VERSION
+    }
+}
+
+const OBLIGATION_HEALTH_LEN: usize = 146; // 1 + 32 + 32 + 8 + 1 + 8 + 64
+impl Pack for ObligationHealth {
+    const LEN: usize = OBLIGATION_HEALTH_LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let output = array_mut_ref![dst, 0, OBLIGATION_HEALTH_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            version,
+            lending_market,
+            obligation,
+            last_update_slot,
+            unhealthy_since_slot_flag,
+            unhealthy_since_slot,
+            _padding,
+        ) = mut_array_refs![output, 1, PUBKEY_BYTES, PUBKEY_BYTES, 8, 1, 8, 64];
+
+        *version = self.version.to_le_bytes();
+        lending_market.copy_from_slice(self.lending_market.as_ref());
+        obligation.copy_from_slice(self.obligation.as_ref());
+        *last_update_slot = self.last_update_slot.to_le_bytes();
+        pack_bool(
+            self.unhealthy_since_slot.is_some(),
+            unhealthy_since_slot_flag,
+        );
+        *unhealthy_since_slot = self.unhealthy_since_slot.unwrap_or(0).to_le_bytes();
+    }
+
+    /// Unpacks a byte buffer into an
+    /// [ObligationHealth](struct.ObligationHealth.html).
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![src, 0, OBLIGATION_HEALTH_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            version,
+            lending_market,
+            obligation,
+            last_update_slot,
+            unhealthy_since_slot_flag,
+            unhealthy_since_slot,
+            _padding,
+        ) = array_refs![input, 1, PUBKEY_BYTES, PUBKEY_BYTES, 8, 1, 8, 64];
+
+        let version = u8::from_le_bytes(*version);
+        if version > PROGRAM_VERSION {
+            msg!("Obligation health version does not match lending program version");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            version,
+            lending_market: Pubkey::new_from_array(*lending_market),
+            obligation: Pubkey::new_from_array(*obligation),
+            last_update_slot: u64::from_le_bytes(*last_update_slot),
+            unhealthy_since_slot: if unpack_bool(unhealthy_since_slot_flag)? {
+                Some(u64::from_le_bytes(*unhealthy_since_slot))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pack_and_unpack_obligation_health() {
+        let mut obligation_health = ObligationHealth::new(InitObligationHealthParams {
+            current_slot: 42,
+            lending_market: Pubkey::new_unique(),
+            obligation: Pubkey::new_unique(),
+        });
+        obligation_health.update(true, 50);
+
+        let mut packed = [0u8; ObligationHealth::LEN];
+        ObligationHealth::pack(obligation_health.clone(), &mut packed).unwrap();
+        let unpacked = ObligationHealth::unpack(&packed).unwrap();
+        assert_eq!(unpacked, obligation_health);
+    }
+
+    #[test]
+    fn obligation_health_grace_period_requires_consecutive_unhealthy_refreshes() {
+        let mut obligation_health = ObligationHealth::new(InitObligationHealthParams {
+            current_slot: 100,
+            lending_market: Pubkey::new_unique(),
+            obligation: Pubkey::new_unique(),
+        });
+        assert!(!obligation_health.is_grace_period_elapsed(0, 100));
+
+        obligation_health.update(true, 100);
+        obligation_health.update(true, 105);
+        assert_eq!(obligation_health.unhealthy_since_slot, Some(100));
+        assert!(!obligation_health.is_grace_period_elapsed(10, 109));
+        assert!(obligation_health.is_grace_period_elapsed(10, 110));
+
+        // a healthy refresh restarts the grace period
+        obligation_health.update(false, 111);
+        assert_eq!(obligation_health.unhealthy_since_slot, None);
+        obligation_health.update(true, 112);
+        assert!(!obligation_health.is_grace_period_elapsed(10, 121));
+        assert!(obligation_health.is_grace_period_elapsed(10, 122));
+    }
+}
@@ -0,0 +1,295 @@
+use {
+    super::*,
+    crate::error::LendingError,
+    arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
+    solana_program::{
+        clock::Slot,
+        msg,
+        program_error::ProgramError,
+        program_pack::{IsInitialized, Pack, Sealed},
+        pubkey::{Pubkey, PUBKEY_BYTES},
+    },
+};
+
+/// Protocol-owned liquidity of a reserve, seeded by the lending market owner.
+/// The collateral minted for the liquidity is locked in a collateral supply
+/// owned by the lending market authority and can only be withdrawn once a
+/// withdrawal was announced and its timelock elapsed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProtocolLiquidity {
+    /// Version of the struct
+    pub version: u8,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Reserve address
+    pub reserve: Pubkey,
+    /// Collateral supply address holding the locked collateral tokens
+    pub collateral_supply: Pubkey,
+    /// Amount of locked collateral tokens
+    pub collateral_amount: u64,
+    /// Number of slots between a withdrawal announcement and the withdrawal
+    pub withdrawal_timelock_slots: u64,
+    /// Pending withdrawal announcement
+    pub withdrawal: Option<ProtocolLiquidityWithdrawal>,
+}
+
+impl ProtocolLiquidity {
+    /// Create new protocol-owned liquidity
+    pub fn new(params: InitProtocolLiquidityParams) -> Self {
+        let mut protocol_liquidity = Self::default();
+        Self::init(&mut protocol_liquidity, params);
+        protocol_liquidity
+    }
+
+    /// Initialize protocol-owned liquidity
+    pub fn init(&mut self, params: InitProtocolLiquidityParams) {
+        self.version = PROGRAM_VERSION;
+        self.lending_market = params.lending_market;
+        self.reserve = params.reserve;
+        self.collateral_supply = params.collateral_supply;
+        self.collateral_amount = 0;
+        self.withdrawal_timelock_slots = params.withdrawal_timelock_slots;
+        self.withdrawal = None;
+    }
+
+    /// Lock collateral tokens minted for deposited liquidity
+    pub fn deposit(&mut self, collateral_amount: u64) -> Result<(), ProgramError> {
+        self.collateral_amount = self
+            .collateral_amount
+            .checked_add(collateral_amount)
+            .ok_or(LendingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Announce a withdrawal of locked collateral tokens, replacing any
+    /// pending announcement, and return the slot it unlocks at
+    pub fn announce_withdrawal(
+        &mut self,
+        collateral_amount: u64,
+        slot: Slot,
+    ) -> Result<Slot, ProgramError> {
+        if collateral_amount == 0 || collateral_amount > self.collateral_amount {
+            msg!("Withdrawal amount must be in range [1, locked collateral amount]");
+            return Err(LendingError::InvalidAmount.into());
+        }
+
+        let unlock_slot = slot
+            .checked_add(self.withdrawal_timelock_slots)
+            .ok_or(LendingError::MathOverflow)?;
+        self.withdrawal = Some(ProtocolLiquidityWithdrawal {
+            collateral_amount,
+            unlock_slot,
+        });
+        Ok(unlock_slot)
+    }
+
+    /// Release the collateral tokens of an unlocked withdrawal announcement
+    pub fn withdraw(&mut self, slot: Slot) -> Result<u64, ProgramError> {
+        let withdrawal = self.withdrawal.ok_or_else(|| {
+            msg!("Protocol liquidity withdrawal must be announced");
+            LendingError::ProtocolLiquidityWithdrawalNotAnnounced
+        })?;
+        if slot < withdrawal.unlock_slot {
+            msg!(
+                "Protocol liquidity withdrawal is locked until slot {}",
+                withdrawal.unlock_slot
+            );
+            return Err(LendingError::ProtocolLiquidityLocked.into());
+        }
+
+        self.collateral_amount = self
+            .collateral_amount
+            .checked_sub(withdrawal.collateral_amount)
+            .ok_or(LendingError::MathOverflow)?;
+        self.withdrawal = None;
+        Ok(withdrawal.collateral_amount)
+    }
+}
+
+/// Initialize protocol-owned liquidity
+pub struct InitProtocolLiquidityParams {
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Reserve address
+    pub reserve: Pubkey,
+    /// Collateral supply address
+    pub collateral_supply: Pubkey,
+    /// Number of slots between a withdrawal announcement and the withdrawal
+    pub withdrawal_timelock_slots: u64,
+}
+
+/// Announced withdrawal of protocol-owned liquidity
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProtocolLiquidityWithdrawal {
+    /// Amount of collateral tokens to withdraw
+    pub collateral_amount: u64,
+    /// Slot from which the withdrawal can be made
+    pub unlock_slot: Slot,
+}
+
+impl Sealed for ProtocolLiquidity {}
+impl IsInitialized for ProtocolLiquidity {
+    fn is_initialized(&self) -> bool {
+        self.version != UNINITIALIZED_VERSION
+    }
+}
+
+const PROTOCOL_LIQUIDITY_LEN: usize = 194; // 1 + 32 + 32 + 32 + 8 + 8 + 1 + 8 + 8 + 64
+impl Pack for ProtocolLiquidity {
+    const LEN: usize = PROTOCOL_LIQUIDITY_LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let output = array_mut_ref![dst, 0, PROTOCOL_LIQUIDITY_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            version,
+            lending_market,
+            reserve,
+            collateral_supply,
+            collateral_amount,
+            withdrawal_timelock_slots,
+            withdrawal_announced,
+            withdrawal_collateral_amount,
+            withdrawal_unlock_slot,
+            _padding,
+        ) = mut_array_refs![
+            output,
+            1,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            8,
+            8,
+            1,
+            8,
+            8,
+            64
+        ];
+
+        *version = self.version.to_le_bytes();
+        lending_market.copy_from_slice(self.lending_market.as_ref());
+        reserve.copy_from_slice(self.reserve.as_ref());
+        collateral_supply.copy_from_slice(self.collateral_supply.as_ref());
+        *collateral_amount = self.collateral_amount.to_le_bytes();
+        *withdrawal_timelock_slots = self.withdrawal_timelock_slots.to_le_bytes();
+
+        let withdrawal = self.withdrawal.unwrap_or_default();
+        pack_bool(self.withdrawal.is_some(), withdrawal_announced);
+        *withdrawal_collateral_amount = withdrawal.collateral_amount.to_le_bytes();
+        *withdrawal_unlock_slot = withdrawal.unlock_slot.to_le_bytes();
+    }
+
+    /// Unpacks a byte buffer into a
+    /// [ProtocolLiquidity](struct.ProtocolLiquidity.html).
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![src, 0, PROTOCOL_LIQUIDITY_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            version,
+            lending_market,
+            reserve,
+            collateral_supply,
+            collateral_amount,
+            withdrawal_timelock_slots,
+            withdrawal_announced,
+            withdrawal_collateral_amount,
+            withdrawal_unlock_slot,
+            _padding,
+        ) = array_refs![
+            input,
+            1,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            8,
+            8,
+            1,
+            8,
+            8,
+            64
+        ];
+
+        let version = u8::from_le_bytes(*version);
+        if version > PROGRAM_VERSION {
+            msg!("Protocol liquidity version does not match lending program version");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let withdrawal = if unpack_bool(withdrawal_announced)? {
+            Some(ProtocolLiquidityWithdrawal {
+                collateral_amount: u64::from_le_bytes(*withdrawal_collateral_amount),
+                unlock_slot: u64::from_le_bytes(*withdrawal_unlock_slot),
+            })
+        } else {
+            None
+        };
+
+        Ok(Self {
+            version,
+            lending_market: Pubkey::new_from_array(*lending_market),
+            reserve: Pubkey::new_from_array(*reserve),
+            collateral_supply: Pubkey::new_from_array(*collateral_supply),
+            collateral_amount: u64::from_le_bytes(*collateral_amount),
+            withdrawal_timelock_slots: u64::from_le_bytes(*withdrawal_timelock_slots),
+            withdrawal,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn new_protocol_liquidity() -> ProtocolLiquidity {
+        ProtocolLiquidity::new(InitProtocolLiquidityParams {
+            lending_market: Pubkey::new_unique(),
+            reserve: Pubkey::new_unique(),
+            collateral_supply: Pubkey::new_unique(),
+            withdrawal_timelock_slots: 100,
+        })
+    }
+
+    #[test]
+    fn pack_and_unpack_protocol_liquidity() {
+        let mut protocol_liquidity = new_protocol_liquidity();
+        protocol_liquidity.deposit(1_000).unwrap();
+
+        let mut packed = [0u8; ProtocolLiquidity::LEN];
+        ProtocolLiquidity::pack(protocol_liquidity.clone(), &mut packed).unwrap();
+        assert_eq!(
+            ProtocolLiquidity::unpack(&packed).unwrap(),
+            protocol_liquidity
+        );
+
+        protocol_liquidity.announce_withdrawal(400, 10).unwrap();
+        ProtocolLiquidity::pack(protocol_liquidity.clone(), &mut packed).unwrap();
+        assert_eq!(
+            ProtocolLiquidity::unpack(&packed).unwrap(),
+            protocol_liquidity
+        );
+    }
+
+    #[test]
+    fn withdraw_after_timelock() {
+        let mut protocol_liquidity = new_protocol_liquidity();
+        protocol_liquidity.deposit(1_000).unwrap();
+
+        assert_eq!(
+            protocol_liquidity.withdraw(10),
+            Err(LendingError::ProtocolLiquidityWithdrawalNotAnnounced.into())
+        );
+        assert_eq!(
+            protocol_liquidity.announce_withdrawal(1_001, 10),
+            Err(LendingError::InvalidAmount.into())
+        );
+
+        assert_eq!(protocol_liquidity.announce_withdrawal(400, 10), Ok(110));
+        assert_eq!(
+            protocol_liquidity.withdraw(109),
+            Err(LendingError::ProtocolLiquidityLocked.into())
+        );
+        assert_eq!(protocol_liquidity.withdraw(110), Ok(400));
+        assert_eq!(protocol_liquidity.collateral_amount, 600);
+        assert_eq!(protocol_liquidity.withdrawal, None);
+    }
+}
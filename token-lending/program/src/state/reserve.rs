@@ -25,6 +25,13 @@ pub const LIQUIDATION_CLOSE_FACTOR: u8 = 50;
 /// Obligation borrow amount that is small enough to close out
 pub const LIQUIDATION_CLOSE_AMOUNT: u64 = 2;
 
+/// Max number of slots since the last oracle price update for reserves
+/// without a configured max oracle age
+pub const DEFAULT_MAX_ORACLE_AGE_SLOTS: u64 = 5;
+
+/// Number of basis points in 100%
+pub const BPS_SCALER: u64 = 10_000;
+
 /// Lending market reserve state
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Reserve {
@@ -121,12 +128,57 @@ impl Reserve {
         }
     }
 
+    /// Check the market value of the total supply, including active loans, is
+    /// within the deposit value limit. Uses the market price of the last
+    /// refresh.
+    pub fn validate_deposit_value_limit(&self) -> ProgramResult {
+        if self.config.deposit_value_limit == 0 {
+            return Ok(());
+        }
+        let supply_value = self
+            .liquidity
+            .market_value(self.liquidity.total_supply()?)?;
+        if supply_value > Decimal::from(self.config.deposit_value_limit) {
+            msg!("Reserve supply value cannot exceed the deposit value limit");
+            return Err(LendingError::ReserveDepositLimitExceeded.into());
+        }
+        Ok(())
+    }
+
+    /// Check the market value of the total borrows is within the borrow value
+    /// limit. Uses the market price of the last refresh.
+    pub fn validate_borrow_value_limit(&self) -> ProgramResult {
+        if self.config.borrow_value_limit == 0 {
+            return Ok(());
+        }
+        let borrowed_value = self
+            .liquidity
+            .market_value(self.liquidity.borrowed_amount_wads)?;
+        if borrowed_value > Decimal::from(self.config.borrow_value_limit) {
+            msg!("Reserve borrowed value cannot exceed the borrow value limit");
+            return Err(LendingError::ReserveBorrowLimitExceeded.into());
+        }
+        Ok(())
+    }
+
     /// Collateral exchange rate
     pub fn collateral_exchange_rate(&self) -> Result<CollateralExchangeRate, ProgramError> {
         let total_liquidity = self.liquidity.total_supply()?;
         self.collateral.exchange_rate(total_liquidity)
     }
 
+    /// Snapshot of the current borrow, supply and utilization rates
+    pub fn rate_snapshot(&self, slot: Slot) -> Result<RateSnapshot, ProgramError> {
+        let utilization_rate = self.liquidity.utilization_rate()?;
+        let borrow_rate = self.current_borrow_rate()?;
+        Ok(RateSnapshot {
+            slot,
+            borrow_rate,
+            supply_rate: borrow_rate.try_mul(utilization_rate)?,
+            utilization_rate,
+        })
+    }
+
     /// Update borrow rate and accrue interest
     pub fn accrue_interest(&mut self, current_slot: Slot) -> ProgramResult {
         let slots_elapsed = self.last_update.slots_elapsed(current_slot)?;
@@ -144,6 +196,25 @@ impl Reserve {
         amount_to_borrow: u64,
         max_borrow_value: Decimal,
     ) -> Result<CalculateBorrowResult, ProgramError> {
+        self.calculate_borrow_with_host_fee_percentage(
+            amount_to_borrow,
+            max_borrow_value,
+            self.config.fees.host_fee_percentage,
+        )
+    }
+
+    /// Borrow liquidity up to a maximum market value, paying the given share
+    /// of the borrow fee to the host instead of the reserve host fee percentage
+    pub fn calculate_borrow_with_host_fee_percentage(
+        &self,
+        amount_to_borrow: u64,
+        max_borrow_value: Decimal,
+        host_fee_percentage: u8,
+    ) -> Result<CalculateBorrowResult, ProgramError> {
+        let fees = ReserveFees {
+            host_fee_percentage,
+            ..self.config.fees
+        };
         // @TODO: add lookup table https://git.io/JOCYq
         let decimals = 10u64
             .checked_pow(self.liquidity.mint_decimals as u32)
@@ -153,10 +224,8 @@ impl Reserve {
                 .try_mul(decimals)?
                 .try_div(self.liquidity.market_price)?
                 .min(self.liquidity.available_amount.into());
-            let (borrow_fee, host_fee) = self
-                .config
-                .fees
-                .calculate_borrow_fees(borrow_amount, FeeCalculation::Inclusive)?;
+            let (borrow_fee, host_fee) =
+                fees.calculate_borrow_fees(borrow_amount, FeeCalculation::Inclusive)?;
             let receive_amount = borrow_amount
                 .try_floor_u64()?
                 .checked_sub(borrow_fee)
@@ -171,10 +240,8 @@ impl Reserve {
         } else {
             let receive_amount = amount_to_borrow;
             let borrow_amount = Decimal::from(receive_amount);
-            let (borrow_fee, host_fee) = self
-                .config
-                .fees
-                .calculate_borrow_fees(borrow_amount, FeeCalculation::Exclusive)?;
+            let (borrow_fee, host_fee) =
+                fees.calculate_borrow_fees(borrow_amount, FeeCalculation::Exclusive)?;
 
             let borrow_amount = borrow_amount.try_add(borrow_fee.into())?;
             let borrow_value = borrow_amount
@@ -221,7 +288,26 @@ impl Reserve {
         liquidity: &ObligationLiquidity,
         collateral: &ObligationCollateral,
     ) -> Result<CalculateLiquidationResult, ProgramError> {
-        let bonus_rate = Rate::from_percent(self.config.liquidation_bonus).try_add(Rate::one())?;
+        self.calculate_liquidation_with_bonus(
+            amount_to_liquidate,
+            obligation,
+            liquidity,
+            collateral,
+            Rate::from_percent(self.config.liquidation_bonus),
+        )
+    }
+
+    /// Liquidate some or all of an unhealthy obligation, paying the given
+    /// liquidation bonus instead of the reserve's configured bonus
+    pub fn calculate_liquidation_with_bonus(
+        &self,
+        amount_to_liquidate: u64,
+        obligation: &Obligation,
+        liquidity: &ObligationLiquidity,
+        collateral: &ObligationCollateral,
+        liquidation_bonus: Rate,
+    ) -> Result<CalculateLiquidationResult, ProgramError> {
+        let bonus_rate = liquidation_bonus.try_add(Rate::one())?;
 
         let max_amount = if amount_to_liquidate == u64::MAX {
             liquidity.borrowed_amount_wads
@@ -392,6 +478,17 @@ impl ReserveLiquidity {
         Decimal::from(self.available_amount).try_add(self.borrowed_amount_wads)
     }
 
+    /// Calculate the market value of a liquidity amount, in the lending market
+    /// quote currency
+    pub fn market_value(&self, liquidity_amount: Decimal) -> Result<Decimal, ProgramError> {
+        let decimals = 10u64
+            .checked_pow(self.mint_decimals as u32)
+            .ok_or(LendingError::MathOverflow)?;
+        liquidity_amount
+            .try_mul(self.market_price)?
+            .try_div(decimals)
+    }
+
     /// Add liquidity to available amount
     pub fn deposit(&mut self, liquidity_amount: u64) -> ProgramResult {
         self.available_amount = self
@@ -613,9 +710,52 @@ pub struct ReserveConfig {
     pub max_borrow_rate: u8,
     /// Program owner fees assessed, separate from gains due to interest accrual
     pub fees: ReserveFees,
+    /// Max number of slots since the last oracle price update before the price
+    /// is rejected as stale, 0 to use the default
+    pub max_oracle_age_slots: u64,
+    /// Max oracle price confidence interval relative to the price, in basis
+    /// points, 0 to accept any confidence interval
+    pub max_oracle_confidence_bps: u16,
+    /// Max market value of the total reserve supply after a deposit, in whole
+    /// units of the lending market quote currency (usually USD), 0 for no
+    /// limit
+    pub deposit_value_limit: u64,
+    /// Max market value of the total reserve borrows after a borrow, in whole
+    /// units of the lending market quote currency (usually USD), 0 for no
+    /// limit
+    pub borrow_value_limit: u64,
 }
 
 impl ReserveConfig {
+    /// Max number of slots an oracle price can be old, falling back to
+    /// DEFAULT_MAX_ORACLE_AGE_SLOTS when not configured
+    pub fn max_oracle_age_slots(&self) -> u64 {
+        if self.max_oracle_age_slots == 0 {
+            DEFAULT_MAX_ORACLE_AGE_SLOTS
+        } else {
+            self.max_oracle_age_slots
+        }
+    }
+
+    /// Check the oracle price confidence interval is within the configured
+    /// ratio of the price, both expressed with the same exponent
+    pub fn validate_oracle_confidence(&self, price: u64, confidence: u64) -> ProgramResult {
+        if self.max_oracle_confidence_bps == 0 {
+            return Ok(());
+        }
+        let max_confidence = (price as u128)
+            .checked_mul(self.max_oracle_confidence_bps as u128)
+            .ok_or(LendingError::MathOverflow)?;
+        let confidence = (confidence as u128)
+            .checked_mul(BPS_SCALER as u128)
+            .ok_or(LendingError::MathOverflow)?;
+        if confidence > max_confidence {
+            msg!("Oracle price confidence interval is too wide");
+            return Err(LendingError::OracleConfidenceTooWide.into());
+        }
+        Ok(())
+    }
+
     /// Validate the reserve configs, when initializing or modifying the reserve
     /// configs
     pub fn validate(&self) -> ProgramResult {
@@ -657,6 +797,10 @@ impl ReserveConfig {
             msg!("Host fee percentage must be in range [0, 100]");
             return Err(LendingError::InvalidConfig.into());
         }
+        if self.max_oracle_confidence_bps as u64 > BPS_SCALER {
+            msg!("Max oracle confidence must be in range [0, 10_000]");
+            return Err(LendingError::InvalidConfig.into());
+        }
 
         Ok(())
     }
@@ -771,7 +915,7 @@ impl IsInitialized for Reserve {
 }
 
 const RESERVE_LEN: usize = 571; // 1 + 8 + 1 + 32 + 32 + 1 + 32 + 32 + 32 + 8 + 16 + 16 + 16 + 32 + 8 + 32 + 1 +
-                                // 1 + 1 + 1 + 1 + 1 + 1 + 8 + 8 + 1 + 248
+                                // 1 + 1 + 1 + 1 + 1 + 1 + 8 + 8 + 1 + 8 + 2 + 8 + 8 + 222
 impl Pack for Reserve {
     const LEN: usize = RESERVE_LEN;
 
@@ -806,6 +950,10 @@ impl Pack for Reserve {
             config_fees_borrow_fee_wad,
             config_fees_flash_loan_fee_wad,
             config_fees_host_fee_percentage,
+            config_max_oracle_age_slots,
+            config_max_oracle_confidence_bps,
+            config_deposit_value_limit,
+            config_borrow_value_limit,
             _padding,
         ) = mut_array_refs![
             output,
@@ -835,7 +983,11 @@ impl Pack for Reserve {
             8,
             8,
             1,
-            248
+            8,
+            2,
+            8,
+            8,
+            222
         ];
 
         // reserve
@@ -877,6 +1029,10 @@ impl Pack for Reserve {
         *config_fees_borrow_fee_wad = self.config.fees.borrow_fee_wad.to_le_bytes();
         *config_fees_flash_loan_fee_wad = self.config.fees.flash_loan_fee_wad.to_le_bytes();
         *config_fees_host_fee_percentage = self.config.fees.host_fee_percentage.to_le_bytes();
+        *config_max_oracle_age_slots = self.config.max_oracle_age_slots.to_le_bytes();
+        *config_max_oracle_confidence_bps = self.config.max_oracle_confidence_bps.to_le_bytes();
+        *config_deposit_value_limit = self.config.deposit_value_limit.to_le_bytes();
+        *config_borrow_value_limit = self.config.borrow_value_limit.to_le_bytes();
     }
 
     /// Unpacks a byte buffer into a [ReserveInfo](struct.ReserveInfo.html).
@@ -910,6 +1066,10 @@ impl Pack for Reserve {
             config_fees_borrow_fee_wad,
             config_fees_flash_loan_fee_wad,
             config_fees_host_fee_percentage,
+            config_max_oracle_age_slots,
+            config_max_oracle_confidence_bps,
+            config_deposit_value_limit,
+            config_borrow_value_limit,
             _padding,
         ) = array_refs![
             input,
@@ -939,7 +1099,11 @@ impl Pack for Reserve {
             8,
             8,
             1,
-            248
+            8,
+            2,
+            8,
+            8,
+            222
         ];
 
         let version = u8::from_le_bytes(*version);
@@ -984,6 +1148,10 @@ impl Pack for Reserve {
                     flash_loan_fee_wad: u64::from_le_bytes(*config_fees_flash_loan_fee_wad),
                     host_fee_percentage: u8::from_le_bytes(*config_fees_host_fee_percentage),
                 },
+                max_oracle_age_slots: u64::from_le_bytes(*config_max_oracle_age_slots),
+                max_oracle_confidence_bps: u16::from_le_bytes(*config_max_oracle_confidence_bps),
+                deposit_value_limit: u64::from_le_bytes(*config_deposit_value_limit),
+                borrow_value_limit: u64::from_le_bytes(*config_borrow_value_limit),
             },
         })
     }
@@ -1367,4 +1535,88 @@ mod test {
         assert_eq!(total_fee, 10); // 1% of 1000
         assert_eq!(host_fee, 0); // 0 host fee
     }
+
+    #[test]
+    fn oracle_age_default() {
+        let mut config = ReserveConfig::default();
+        assert_eq!(config.max_oracle_age_slots(), DEFAULT_MAX_ORACLE_AGE_SLOTS);
+
+        config.max_oracle_age_slots = 20;
+        assert_eq!(config.max_oracle_age_slots(), 20);
+    }
+
+    #[test]
+    fn oracle_confidence_validation() {
+        let mut config = ReserveConfig::default();
+
+        // any confidence interval accepted when not configured
+        assert!(config.validate_oracle_confidence(100, 100).is_ok());
+
+        config.max_oracle_confidence_bps = 100; // 1%
+        assert!(config.validate_oracle_confidence(10_000, 100).is_ok());
+        assert_eq!(
+            config.validate_oracle_confidence(10_000, 101).unwrap_err(),
+            LendingError::OracleConfidenceTooWide.into()
+        );
+        assert!(config
+            .validate_oracle_confidence(u64::MAX, u64::MAX / 100)
+            .is_ok());
+
+        let mut config = ReserveConfig {
+            loan_to_value_ratio: 50,
+            liquidation_threshold: 55,
+            max_oracle_confidence_bps: BPS_SCALER as u16,
+            ..ReserveConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.max_oracle_confidence_bps += 1;
+        assert_eq!(
+            config.validate().unwrap_err(),
+            LendingError::InvalidConfig.into()
+        );
+    }
+
+    #[test]
+    fn deposit_value_limit() {
+        let mut reserve = Reserve::default();
+        reserve.liquidity.mint_decimals = 6;
+        reserve.liquidity.market_price = Decimal::from(2u64);
+        reserve.liquidity.available_amount = 500_000_000;
+        reserve.liquidity.borrowed_amount_wads = Decimal::from(250_000_000u64);
+
+        // any supply value accepted when not configured
+        assert!(reserve.validate_deposit_value_limit().is_ok());
+
+        reserve.config.deposit_value_limit = 1_500;
+        assert!(reserve.validate_deposit_value_limit().is_ok());
+
+        // same token amounts are over the limit after the price moves
+        reserve.liquidity.market_price = Decimal::from(3u64);
+        assert_eq!(
+            reserve.validate_deposit_value_limit().unwrap_err(),
+            LendingError::ReserveDepositLimitExceeded.into()
+        );
+    }
+
+    #[test]
+    fn borrow_value_limit() {
+        let mut reserve = Reserve::default();
+        reserve.liquidity.mint_decimals = 6;
+        reserve.liquidity.market_price = Decimal::from(2u64);
+        reserve.liquidity.available_amount = 500_000_000;
+        reserve.liquidity.borrowed_amount_wads = Decimal::from(250_000_000u64);
+
+        // any borrowed value accepted when not configured
+        assert!(reserve.validate_borrow_value_limit().is_ok());
+
+        reserve.config.borrow_value_limit = 500;
+        assert!(reserve.validate_borrow_value_limit().is_ok());
+
+        reserve.liquidity.borrowed_amount_wads = Decimal::from(250_000_001u64);
+        assert_eq!(
+            reserve.validate_borrow_value_limit().unwrap_err(),
+            LendingError::ReserveBorrowLimitExceeded.into()
+        );
+    }
 }
@@ -0,0 +1,257 @@
+use {
+    super::*,
+    crate::math::Rate,
+    arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
+    solana_program::{
+        clock::Slot,
+        msg,
+        program_error::ProgramError,
+        program_pack::{IsInitialized, Pack, Sealed},
+        pubkey::{Pubkey, PUBKEY_BYTES},
+    },
+    std::convert::TryFrom,
+};
+
+/// Number of rate snapshots kept by a reserve rate history
+pub const MAX_RATE_SNAPSHOTS: usize = 32;
+
+/// Rates of a reserve at the slot it accrued interest
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RateSnapshot {
+    /// Slot the rates were recorded at
+    pub slot: Slot,
+    /// Borrow APY
+    pub borrow_rate: Rate,
+    /// Supply APY, the borrow APY paid on the borrowed share of the supply
+    pub supply_rate: Rate,
+    /// Ratio of borrowed liquidity to the total liquidity supply
+    pub utilization_rate: Rate,
+}
+
+/// Ring buffer of the latest rate snapshots of a reserve, recorded by
+/// refreshes which include the account. Integrations can read the rates at
+/// fixed offsets without unpacking the whole reserve: the snapshot `i` starts
+/// at `RATE_SNAPSHOTS_OFFSET + i * RATE_SNAPSHOT_LEN` and the latest one is at
+/// index `(snapshots_count - 1) % MAX_RATE_SNAPSHOTS`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReserveRateHistory {
+    /// Version of the struct
+    pub version: u8,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Reserve the rates are recorded for
+    pub reserve: Pubkey,
+    /// Total number of snapshots recorded since the account was initialized
+    pub snapshots_count: u64,
+    /// Recorded snapshots, indexed by `snapshots_count % MAX_RATE_SNAPSHOTS`
+    pub snapshots: [RateSnapshot; MAX_RATE_SNAPSHOTS],
+}
+
+impl ReserveRateHistory {
+    /// Create a new reserve rate history
+    pub fn new(params: InitReserveRateHistoryParams) -> Self {
+        let mut rate_history = Self::default();
+        Self::init(&mut rate_history, params);
+        rate_history
+    }
+
+    /// Initialize a reserve rate history
+    pub fn init(&mut self, params: InitReserveRateHistoryParams) {
+        self.version = PROGRAM_VERSION;
+        self.lending_market = params.lending_market;
+        self.reserve = params.reserve;
+        self.snapshots_count = 0;
+        self.snapshots = [RateSnapshot::default(); MAX_RATE_SNAPSHOTS];
+    }
+
+    /// Latest recorded snapshot
+    pub fn latest(&self) -> Option<&RateSnapshot> {
+        let index = self.snapshots_count.checked_sub(1)? as usize % MAX_RATE_SNAPSHOTS;
+        Some(&self.snapshots[index])
+    }
+
+    /// Record a snapshot, replacing the latest one if it was recorded in the
+    /// same slot and the oldest one once the history is full
+    pub fn record(&mut self, snapshot: RateSnapshot) {
+        let index = match self.latest() {
+            Some(latest) if latest.slot == snapshot.slot => {
+                (self.snapshots_count - 1) as usize % MAX_RATE_SNAPSHOTS
+            }
+            _ => {
+                let index = self.snapshots_count as usize % MAX_RATE_SNAPSHOTS;
+                self.snapshots_count += 1;
+                index
+            }
+        };
+        self.snapshots[index] = snapshot;
+    }
+}
+
+/// Initialize a reserve rate history
+pub struct InitReserveRateHistoryParams {
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Reserve the rates are recorded for
+    pub reserve: Pubkey,
+}
+
+impl Sealed for ReserveRateHistory {}
+impl IsInitialized for ReserveRateHistory {
+    fn is_initialized(&self) -> bool {
+        self.version != UNINITIALIZED_VERSION
+    }
+}
+
+/// Length of a packed rate snapshot
+pub const RATE_SNAPSHOT_LEN: usize = 56; // 8 + 16 + 16 + 16
+/// Offset of the first rate snapshot in a packed reserve rate history
+pub const RATE_SNAPSHOTS_OFFSET: usize = 73; // 1 + 32 + 32 + 8
+const RATE_SNAPSHOTS_LEN: usize = RATE_SNAPSHOT_LEN * MAX_RATE_SNAPSHOTS;
+const RESERVE_RATE_HISTORY_LEN: usize = 1929; // 1 + 32 + 32 + 8 + (56 * 32) + 64
+impl Pack for ReserveRateHistory {
+    const LEN: usize = RESERVE_RATE_HISTORY_LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let output = array_mut_ref![dst, 0, RESERVE_RATE_HISTORY_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (version, lending_market, reserve, snapshots_count, snapshots, _padding) = mut_array_refs![
+            output,
+            1,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            8,
+            RATE_SNAPSHOTS_LEN,
+            64
+        ];
+
+        *version = self.version.to_le_bytes();
+        lending_market.copy_from_slice(self.lending_market.as_ref());
+        reserve.copy_from_slice(self.reserve.as_ref());
+        *snapshots_count = self.snapshots_count.to_le_bytes();
+
+        for (snapshot, dst) in self
+            .snapshots
+            .iter()
+            .zip(snapshots.chunks_exact_mut(RATE_SNAPSHOT_LEN))
+        {
+            let dst = array_mut_ref![dst, 0, RATE_SNAPSHOT_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (slot, borrow_rate, supply_rate, utilization_rate) =
+                mut_array_refs![dst, 8, 16, 16, 16];
+            *slot = snapshot.slot.to_le_bytes();
+            pack_decimal(snapshot.borrow_rate.into(), borrow_rate);
+            pack_decimal(snapshot.supply_rate.into(), supply_rate);
+            pack_decimal(snapshot.utilization_rate.into(), utilization_rate);
+        }
+    }
+
+    /// Unpacks a byte buffer into a
+    /// [ReserveRateHistory](struct.ReserveRateHistory.html).
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![src, 0, RESERVE_RATE_HISTORY_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (version, lending_market, reserve, snapshots_count, snapshots_src, _padding) = array_refs![
+            input,
+            1,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            8,
+            RATE_SNAPSHOTS_LEN,
+            64
+        ];
+
+        let version = u8::from_le_bytes(*version);
+        if version > PROGRAM_VERSION {
+            msg!("Reserve rate history version does not match lending program version");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut snapshots = [RateSnapshot::default(); MAX_RATE_SNAPSHOTS];
+        for (snapshot, src) in snapshots
+            .iter_mut()
+            .zip(snapshots_src.chunks_exact(RATE_SNAPSHOT_LEN))
+        {
+            let src = array_ref![src, 0, RATE_SNAPSHOT_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (slot, borrow_rate, supply_rate, utilization_rate) =
+                array_refs![src, 8, 16, 16, 16];
+            *snapshot = RateSnapshot {
+                slot: u64::from_le_bytes(*slot),
+                borrow_rate: Rate::try_from(unpack_decimal(borrow_rate))?,
+                supply_rate: Rate::try_from(unpack_decimal(supply_rate))?,
+                utilization_rate: Rate::try_from(unpack_decimal(utilization_rate))?,
+            };
+        }
+
+        Ok(Self {
+            version,
+            lending_market: Pubkey::new_from_array(*lending_market),
+            reserve: Pubkey::new_from_array(*reserve),
+            snapshots_count: u64::from_le_bytes(*snapshots_count),
+            snapshots,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn snapshot(slot: Slot, percent: u8) -> RateSnapshot {
+        RateSnapshot {
+            slot,
+            borrow_rate: Rate::from_percent(percent),
+            supply_rate: Rate::from_percent(percent / 2),
+            utilization_rate: Rate::from_percent(50),
+        }
+    }
+
+    #[test]
+    fn pack_and_unpack_reserve_rate_history() {
+        let mut rate_history = ReserveRateHistory::new(InitReserveRateHistoryParams {
+            lending_market: Pubkey::new_unique(),
+            reserve: Pubkey::new_unique(),
+        });
+        rate_history.record(snapshot(42, 10));
+
+        let mut packed = [0u8; ReserveRateHistory::LEN];
+        ReserveRateHistory::pack(rate_history.clone(), &mut packed).unwrap();
+        let unpacked = ReserveRateHistory::unpack(&packed).unwrap();
+        assert_eq!(unpacked, rate_history);
+
+        // the latest snapshot can be read at its fixed offset
+        let offset = RATE_SNAPSHOTS_OFFSET;
+        assert_eq!(u64::from_le_bytes(*array_ref![packed, offset, 8]), 42);
+        assert_eq!(
+            u128::from_le_bytes(*array_ref![packed, offset + 8, 16]),
+            Rate::from_percent(10).to_scaled_val()
+        );
+    }
+
+    #[test]
+    fn reserve_rate_history_wraps_around() {
+        let mut rate_history = ReserveRateHistory::new(InitReserveRateHistoryParams {
+            lending_market: Pubkey::new_unique(),
+            reserve: Pubkey::new_unique(),
+        });
+        assert_eq!(rate_history.latest(), None);
+
+        rate_history.record(snapshot(1, 10));
+        rate_history.record(snapshot(1, 20));
+        assert_eq!(rate_history.snapshots_count, 1);
+        assert_eq!(rate_history.latest(), Some(&snapshot(1, 20)));
+
+        for slot in 2..=(MAX_RATE_SNAPSHOTS as u64 + 1) {
+            rate_history.record(snapshot(slot, 30));
+        }
+        assert_eq!(rate_history.snapshots_count, MAX_RATE_SNAPSHOTS as u64 + 1);
+        assert_eq!(
+            rate_history.snapshots[0].slot,
+            MAX_RATE_SNAPSHOTS as u64 + 1
+        );
+        assert_eq!(
+            rate_history.latest(),
+            Some(&snapshot(MAX_RATE_SNAPSHOTS as u64 + 1, 30))
+        );
+    }
+}
@@ -18,13 +18,15 @@ use {
     },
     spl_token_lending::{
         instruction::{
-            borrow_obligation_liquidity, deposit_reserve_liquidity, init_lending_market,
-            init_obligation, init_reserve, liquidate_obligation, refresh_reserve,
+            borrow_obligation_liquidity, deposit_reserve_liquidity, init_host_fee_registry,
+            init_lending_market, init_margin_account, init_obligation, init_reserve,
+            liquidate_obligation, refresh_reserve,
         },
         math::{Decimal, Rate, TryAdd, TryMul},
         pyth,
         state::{
-            InitLendingMarketParams, InitObligationParams, InitReserveParams, LendingMarket,
+            HostFeeRegistry, HostFeeShare, InitHostFeeRegistryParams, InitLendingMarketParams,
+            InitObligationParams, InitReserveParams, LendingMarket, MarginAccount,
             NewReserveCollateralParams, NewReserveLiquidityParams, Obligation,
             ObligationCollateral, ObligationLiquidity, Reserve, ReserveCollateral, ReserveConfig,
             ReserveFees, ReserveLiquidity, INITIAL_COLLATERAL_RATIO, PROGRAM_VERSION,
@@ -54,6 +56,10 @@ pub const TEST_RESERVE_CONFIG: ReserveConfig = ReserveConfig {
         flash_loan_fee_wad: 3_000_000_000_000_000,
         host_fee_percentage: 20,
     },
+    max_oracle_age_slots: 0,
+    max_oracle_confidence_bps: 0,
+    deposit_value_limit: 0,
+    borrow_value_limit: 0,
 };
 
 pub const SOL_PYTH_PRODUCT: &str = "3Mnn2fX6rQyUsyELYms1sBJyChWofzSNRoqYzvgMVz5E";
@@ -121,6 +127,47 @@ pub fn add_lending_market(test: &mut ProgramTest) -> TestLendingMarket {
     }
 }
 
+pub fn add_host_fee_registry(
+    test: &mut ProgramTest,
+    lending_market: &TestLendingMarket,
+    hosts: Vec<HostFeeShare>,
+) -> TestHostFeeRegistry {
+    let host_fee_registry_pubkey = Pubkey::new_unique();
+    let mut host_fee_registry = HostFeeRegistry::new(InitHostFeeRegistryParams {
+        lending_market: lending_market.pubkey,
+    });
+    host_fee_registry.hosts = hosts;
+
+    test.add_packable_account(
+        host_fee_registry_pubkey,
+        u32::MAX as u64,
+        &host_fee_registry,
+        &spl_token_lending::id(),
+    );
+
+    let (_, bump_seed) =
+        Pubkey::find_program_address(&[lending_market.pubkey.as_ref()], &spl_token_lending::id());
+    let mut market = LendingMarket::new(InitLendingMarketParams {
+        bump_seed,
+        owner: lending_market.owner.pubkey(),
+        quote_currency: lending_market.quote_currency,
+        token_program_id: spl_token::id(),
+        oracle_program_id: lending_market.oracle_program_id,
+    });
+    market.host_fee_registry = Some(host_fee_registry_pubkey);
+    test.add_packable_account(
+        lending_market.pubkey,
+        u32::MAX as u64,
+        &market,
+        &spl_token_lending::id(),
+    );
+
+    TestHostFeeRegistry {
+        pubkey: host_fee_registry_pubkey,
+        lending_market: lending_market.pubkey,
+    }
+}
+
 #[derive(Default)]
 pub struct AddObligationArgs<'a> {
     pub deposits: &'a [(&'a TestReserve, u64)],
@@ -979,6 +1026,139 @@ impl TestObligation {
     }
 }
 
+#[derive(Debug)]
+pub struct TestMarginAccount {
+    pub pubkey: Pubkey,
+    pub lending_market: Pubkey,
+    pub owner: Pubkey,
+}
+
+impl TestMarginAccount {
+    pub async fn init(
+        banks_client: &mut BanksClient,
+        lending_market: &TestLendingMarket,
+        user_accounts_owner: &Keypair,
+        payer: &Keypair,
+    ) -> Result<Self, TransactionError> {
+        let margin_account_keypair = Keypair::new();
+        let margin_account = TestMarginAccount {
+            pubkey: margin_account_keypair.pubkey(),
+            lending_market: lending_market.pubkey,
+            owner: user_accounts_owner.pubkey(),
+        };
+
+        let rent = banks_client.get_rent().await.unwrap();
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_account(
+                    &payer.pubkey(),
+                    &margin_account_keypair.pubkey(),
+                    rent.minimum_balance(MarginAccount::LEN),
+                    MarginAccount::LEN as u64,
+                    &spl_token_lending::id(),
+                ),
+                init_margin_account(
+                    spl_token_lending::id(),
+                    margin_account.pubkey,
+                    lending_market.pubkey,
+                    user_accounts_owner.pubkey(),
+                ),
+            ],
+            Some(&payer.pubkey()),
+        );
+
+        let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        transaction.sign(
+            &vec![payer, &margin_account_keypair, user_accounts_owner],
+            recent_blockhash,
+        );
+
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.unwrap())?;
+
+        Ok(margin_account)
+    }
+
+    pub async fn get_state(&self, banks_client: &mut BanksClient) -> MarginAccount {
+        let margin_account: Account = banks_client
+            .get_account(self.pubkey)
+            .await
+            .unwrap()
+            .unwrap();
+        MarginAccount::unpack(&margin_account.data[..]).unwrap()
+    }
+}
+
+#[derive(Debug)]
+pub struct TestHostFeeRegistry {
+    pub pubkey: Pubkey,
+    pub lending_market: Pubkey,
+}
+
+impl TestHostFeeRegistry {
+    pub async fn init(
+        banks_client: &mut BanksClient,
+        lending_market: &TestLendingMarket,
+        payer: &Keypair,
+    ) -> Result<Self, TransactionError> {
+        let host_fee_registry_keypair = Keypair::new();
+        let host_fee_registry = TestHostFeeRegistry {
+            pubkey: host_fee_registry_keypair.pubkey(),
+            lending_market: lending_market.pubkey,
+        };
+
+        let rent = banks_client.get_rent().await.unwrap();
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_account(
+                    &payer.pubkey(),
+                    &host_fee_registry_keypair.pubkey(),
+                    rent.minimum_balance(HostFeeRegistry::LEN),
+                    HostFeeRegistry::LEN as u64,
+                    &spl_token_lending::id(),
+                ),
+                init_host_fee_registry(
+                    spl_token_lending::id(),
+                    host_fee_registry.pubkey,
+                    lending_market.pubkey,
+                    lending_market.owner.pubkey(),
+                ),
+            ],
+            Some(&payer.pubkey()),
+        );
+
+        let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        transaction.sign(
+            &vec![payer, &host_fee_registry_keypair, &lending_market.owner],
+            recent_blockhash,
+        );
+
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.unwrap())?;
+
+        Ok(host_fee_registry)
+    }
+
+    pub async fn get_state(&self, banks_client: &mut BanksClient) -> HostFeeRegistry {
+        let host_fee_registry_account: Account = banks_client
+            .get_account(self.pubkey)
+            .await
+            .unwrap()
+            .unwrap();
+        HostFeeRegistry::unpack(&host_fee_registry_account.data[..]).unwrap()
+    }
+
+    pub async fn validate_state(&self, banks_client: &mut BanksClient) {
+        let host_fee_registry = self.get_state(banks_client).await;
+        assert_eq!(host_fee_registry.version, PROGRAM_VERSION);
+        assert_eq!(host_fee_registry.lending_market, self.lending_market);
+    }
+}
+
 #[derive(Debug)]
 pub struct TestObligationCollateral {
     pub obligation_pubkey: Pubkey,
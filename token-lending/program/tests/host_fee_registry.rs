@@ -0,0 +1,417 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program_test::*,
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_token_lending::{
+        error::LendingError,
+        instruction::{
+            borrow_obligation_liquidity, borrow_obligation_liquidity_with_host_fee_registry,
+            refresh_obligation, set_host_fee_percentage_cap, set_host_fee_share,
+        },
+        processor::process_instruction,
+        state::{HostFeeShare, INITIAL_COLLATERAL_RATIO, MAX_HOST_FEE_PERCENTAGE},
+    },
+};
+
+#[tokio::test]
+async fn test_set_host_fee_share() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let lending_market = add_lending_market(&mut test);
+    let (mut banks_client, payer, _recent_blockhash) = test.start().await;
+
+    let host_fee_registry = TestHostFeeRegistry::init(&mut banks_client, &lending_market, &payer)
+        .await
+        .unwrap();
+    host_fee_registry.validate_state(&mut banks_client).await;
+    assert!(host_fee_registry
+        .get_state(&mut banks_client)
+        .await
+        .hosts
+        .is_empty());
+    assert_eq!(
+        lending_market
+            .get_state(&mut banks_client)
+            .await
+            .host_fee_registry,
+        Some(host_fee_registry.pubkey)
+    );
+
+    // a lending market has at most one host fee registry
+    assert_eq!(
+        TestHostFeeRegistry::init(&mut banks_client, &lending_market, &payer)
+            .await
+            .unwrap_err(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(LendingError::AlreadyInitialized as u32)
+        )
+    );
+
+    let host_a = Pubkey::new_unique();
+    let host_b = Pubkey::new_unique();
+    for (host_fee_receiver, host_fee_percentage) in [(host_a, 30), (host_b, 10), (host_a, 40)] {
+        let mut transaction = Transaction::new_with_payer(
+            &[set_host_fee_share(
+                spl_token_lending::id(),
+                host_fee_percentage,
+                host_fee_registry.pubkey,
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+                host_fee_receiver,
+            )],
+            Some(&payer.pubkey()),
+        );
+        let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.unwrap())
+            .unwrap();
+    }
+
+    // registering an existing host updates its share
+    assert_eq!(
+        host_fee_registry.get_state(&mut banks_client).await.hosts,
+        vec![
+            HostFeeShare {
+                host_fee_receiver: host_a,
+                host_fee_percentage: 40,
+            },
+            HostFeeShare {
+                host_fee_receiver: host_b,
+                host_fee_percentage: 10,
+            },
+        ]
+    );
+
+    // a share of zero removes the host
+    let mut transaction = Transaction::new_with_payer(
+        &[set_host_fee_share(
+            spl_token_lending::id(),
+            0,
+            host_fee_registry.pubkey,
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+            host_a,
+        )],
+        Some(&payer.pubkey()),
+    );
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+        .unwrap();
+
+    assert_eq!(
+        host_fee_registry.get_state(&mut banks_client).await.hosts,
+        vec![HostFeeShare {
+            host_fee_receiver: host_b,
+            host_fee_percentage: 10,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_set_host_fee_share_errors() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let lending_market = add_lending_market(&mut test);
+    let host_fee_registry = add_host_fee_registry(&mut test, &lending_market, vec![]);
+    let (banks_client, payer, recent_blockhash) = test.start().await;
+
+    let invalid_owner = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[set_host_fee_share(
+            spl_token_lending::id(),
+            10,
+            host_fee_registry.pubkey,
+            lending_market.pubkey,
+            invalid_owner.pubkey(),
+            Pubkey::new_unique(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &invalid_owner], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::InvalidMarketOwner as u32)
+        )
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[set_host_fee_share(
+            spl_token_lending::id(),
+            MAX_HOST_FEE_PERCENTAGE + 1,
+            host_fee_registry.pubkey,
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+            Pubkey::new_unique(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::InvalidConfig as u32)
+        )
+    );
+
+    // removing a host which is not registered
+    let mut transaction = Transaction::new_with_payer(
+        &[set_host_fee_share(
+            spl_token_lending::id(),
+            0,
+            host_fee_registry.pubkey,
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+            Pubkey::new_unique(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::HostFeeReceiverNotRegistered as u32)
+        )
+    );
+}
+
+async fn borrow_with_host_fee_registry(
+    host_fee_percentage: u8,
+    host_fee_percentage_cap: Option<u8>,
+    register_host: bool,
+    provide_registry: bool,
+) -> Result<(u64, u64), TransactionError> {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    const USDC_TOTAL_BORROW_FRACTIONAL: u64 = 1_000 * FRACTIONAL_TO_USDC;
+    const FEE_AMOUNT: u64 = 100;
+
+    const SOL_DEPOSIT_AMOUNT_LAMPORTS: u64 = 100 * LAMPORTS_TO_SOL * INITIAL_COLLATERAL_RATIO;
+    const USDC_BORROW_AMOUNT_FRACTIONAL: u64 = USDC_TOTAL_BORROW_FRACTIONAL - FEE_AMOUNT;
+    const SOL_RESERVE_COLLATERAL_LAMPORTS: u64 = 2 * SOL_DEPOSIT_AMOUNT_LAMPORTS;
+    const USDC_RESERVE_LIQUIDITY_FRACTIONAL: u64 = 2 * USDC_TOTAL_BORROW_FRACTIONAL;
+
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market(&mut test);
+
+    let sol_oracle = add_sol_oracle(&mut test);
+    let sol_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            collateral_amount: SOL_RESERVE_COLLATERAL_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            config: TEST_RESERVE_CONFIG,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let usdc_mint = add_usdc_mint(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
+    let usdc_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            liquidity_amount: USDC_RESERVE_LIQUIDITY_FRACTIONAL,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            config: TEST_RESERVE_CONFIG,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let hosts = if register_host {
+        vec![HostFeeShare {
+            host_fee_receiver: usdc_test_reserve.liquidity_host_pubkey,
+            host_fee_percentage,
+        }]
+    } else {
+        vec![]
+    };
+    let host_fee_registry = add_host_fee_registry(&mut test, &lending_market, hosts);
+
+    let test_obligation = add_obligation(
+        &mut test,
+        &lending_market,
+        &user_accounts_owner,
+        AddObligationArgs {
+            deposits: &[(&sol_test_reserve, SOL_DEPOSIT_AMOUNT_LAMPORTS)],
+            ..AddObligationArgs::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let mut instructions = vec![];
+    let mut signers = vec![&payer, &user_accounts_owner];
+    if let Some(host_fee_percentage_cap) = host_fee_percentage_cap {
+        signers.push(&lending_market.owner);
+        instructions.push(set_host_fee_percentage_cap(
+            spl_token_lending::id(),
+            host_fee_percentage_cap,
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+        ));
+    }
+    instructions.push(refresh_obligation(
+        spl_token_lending::id(),
+        test_obligation.pubkey,
+        vec![sol_test_reserve.pubkey],
+    ));
+    if provide_registry {
+        instructions.push(borrow_obligation_liquidity_with_host_fee_registry(
+            spl_token_lending::id(),
+            USDC_BORROW_AMOUNT_FRACTIONAL,
+            None,
+            usdc_test_reserve.liquidity_supply_pubkey,
+            usdc_test_reserve.user_liquidity_pubkey,
+            usdc_test_reserve.pubkey,
+            usdc_test_reserve.liquidity_fee_receiver_pubkey,
+            test_obligation.pubkey,
+            lending_market.pubkey,
+            test_obligation.owner,
+            usdc_test_reserve.liquidity_host_pubkey,
+            host_fee_registry.pubkey,
+        ));
+    } else {
+        instructions.push(borrow_obligation_liquidity(
+            spl_token_lending::id(),
+            USDC_BORROW_AMOUNT_FRACTIONAL,
+            None,
+            usdc_test_reserve.liquidity_supply_pubkey,
+            usdc_test_reserve.user_liquidity_pubkey,
+            usdc_test_reserve.pubkey,
+            usdc_test_reserve.liquidity_fee_receiver_pubkey,
+            test_obligation.pubkey,
+            lending_market.pubkey,
+            test_obligation.owner,
+            Some(usdc_test_reserve.liquidity_host_pubkey),
+        ));
+    }
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    transaction.sign(&signers, recent_blockhash);
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())?;
+
+    let borrow_amount =
+        get_token_balance(&mut banks_client, usdc_test_reserve.user_liquidity_pubkey).await;
+    assert_eq!(borrow_amount, USDC_BORROW_AMOUNT_FRACTIONAL);
+
+    let fee_balance = get_token_balance(
+        &mut banks_client,
+        usdc_test_reserve.liquidity_fee_receiver_pubkey,
+    )
+    .await;
+    let host_fee_balance =
+        get_token_balance(&mut banks_client, usdc_test_reserve.liquidity_host_pubkey).await;
+    assert_eq!(fee_balance + host_fee_balance, FEE_AMOUNT);
+    Ok((fee_balance, host_fee_balance))
+}
+
+#[tokio::test]
+async fn test_borrow_registered_host_fee_share() {
+    // the registered share replaces the reserve host fee percentage of 20%
+    assert_eq!(
+        borrow_with_host_fee_registry(50, None, true, true)
+            .await
+            .unwrap(),
+        (50, 50)
+    );
+    assert_eq!(
+        borrow_with_host_fee_registry(10, None, true, true)
+            .await
+            .unwrap(),
+        (90, 10)
+    );
+}
+
+#[tokio::test]
+async fn test_borrow_capped_host_fee_share() {
+    assert_eq!(
+        borrow_with_host_fee_registry(50, Some(25), true, true)
+            .await
+            .unwrap(),
+        (75, 25)
+    );
+}
+
+#[tokio::test]
+async fn test_borrow_unregistered_host() {
+    assert_eq!(
+        borrow_with_host_fee_registry(50, None, false, true)
+            .await
+            .unwrap_err(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(LendingError::HostFeeReceiverNotRegistered as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_borrow_host_without_registry() {
+    // a registered host still needs the market's registry to be provided
+    assert_eq!(
+        borrow_with_host_fee_registry(50, None, true, false)
+            .await
+            .unwrap_err(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(LendingError::InvalidAccountInput as u32)
+        )
+    );
+}
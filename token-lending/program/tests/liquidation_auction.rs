@@ -0,0 +1,498 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    solana_program_test::*,
+    solana_sdk::{
+        account::Account,
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_token::instruction::approve,
+    spl_token_lending::{
+        error::LendingError,
+        instruction::{
+            fill_liquidation_auction, liquidate_obligation, refresh_obligation, refresh_reserve,
+            set_liquidation_auction_config, start_liquidation_auction,
+        },
+        processor::process_instruction,
+        state::{LiquidationAuction, LiquidationAuctionConfig, INITIAL_COLLATERAL_RATIO},
+    },
+};
+
+// 100 SOL collateral
+const SOL_DEPOSIT_AMOUNT_LAMPORTS: u64 = 100 * LAMPORTS_TO_SOL * INITIAL_COLLATERAL_RATIO;
+// 100 SOL * 80% LTV -> 80 SOL * 20 USDC -> 1600 USDC borrow
+const USDC_BORROW_AMOUNT_FRACTIONAL: u64 = 1_600 * FRACTIONAL_TO_USDC;
+// 1600 USDC * 50% -> 800 USDC liquidation
+const USDC_LIQUIDATION_AMOUNT_FRACTIONAL: u64 = USDC_BORROW_AMOUNT_FRACTIONAL / 2;
+
+const SOL_RESERVE_COLLATERAL_LAMPORTS: u64 = 2 * SOL_DEPOSIT_AMOUNT_LAMPORTS;
+const USDC_RESERVE_LIQUIDITY_FRACTIONAL: u64 = 2 * USDC_BORROW_AMOUNT_FRACTIONAL;
+
+const LIQUIDATION_AUCTION_CONFIG: LiquidationAuctionConfig = LiquidationAuctionConfig {
+    min_borrowed_value: 1_000,
+    duration_slots: 100,
+    max_bonus: 20,
+};
+
+struct TestAuction {
+    lending_market: TestLendingMarket,
+    sol_oracle: TestOracle,
+    usdc_oracle: TestOracle,
+    sol_test_reserve: TestReserve,
+    usdc_test_reserve: TestReserve,
+    test_obligation: TestObligation,
+    user_accounts_owner: Keypair,
+    liquidation_auction_pubkey: Pubkey,
+}
+
+fn setup(test: &mut ProgramTest) -> TestAuction {
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market(test);
+
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.loan_to_value_ratio = 50;
+    reserve_config.liquidation_threshold = 80;
+    reserve_config.liquidation_bonus = 10;
+    // the oracle prices must stay valid past the auction window
+    reserve_config.max_oracle_age_slots = 1_000;
+
+    let sol_oracle = add_sol_oracle(test);
+    let sol_test_reserve = add_reserve(
+        test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            collateral_amount: SOL_RESERVE_COLLATERAL_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let usdc_mint = add_usdc_mint(test);
+    let usdc_oracle = add_usdc_oracle(test);
+    let usdc_test_reserve = add_reserve(
+        test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            borrow_amount: USDC_BORROW_AMOUNT_FRACTIONAL,
+            user_liquidity_amount: USDC_BORROW_AMOUNT_FRACTIONAL,
+            liquidity_amount: USDC_RESERVE_LIQUIDITY_FRACTIONAL,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let test_obligation = add_obligation(
+        test,
+        &lending_market,
+        &user_accounts_owner,
+        AddObligationArgs {
+            deposits: &[(&sol_test_reserve, SOL_DEPOSIT_AMOUNT_LAMPORTS)],
+            borrows: &[(&usdc_test_reserve, USDC_BORROW_AMOUNT_FRACTIONAL)],
+            ..AddObligationArgs::default()
+        },
+    );
+
+    let liquidation_auction_pubkey = Pubkey::new_unique();
+    test.add_account(
+        liquidation_auction_pubkey,
+        Account::new(
+            u32::MAX as u64,
+            LiquidationAuction::LEN,
+            &spl_token_lending::id(),
+        ),
+    );
+
+    TestAuction {
+        lending_market,
+        sol_oracle,
+        usdc_oracle,
+        sol_test_reserve,
+        usdc_test_reserve,
+        test_obligation,
+        user_accounts_owner,
+        liquidation_auction_pubkey,
+    }
+}
+
+#[tokio::test]
+async fn test_success() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let TestAuction {
+        lending_market,
+        sol_test_reserve,
+        usdc_test_reserve,
+        test_obligation,
+        user_accounts_owner,
+        liquidation_auction_pubkey,
+        ..
+    } = setup(&mut test);
+    let user_transfer_authority = Keypair::new();
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let initial_user_collateral_balance =
+        get_token_balance(&mut banks_client, sol_test_reserve.user_collateral_pubkey).await;
+
+    // filled in the slot the auction starts, so no bonus is paid:
+    // 800 USDC / 20 USDC per SOL -> 40 SOL
+    const SOL_LIQUIDATION_AMOUNT_LAMPORTS: u64 = 40 * LAMPORTS_TO_SOL * INITIAL_COLLATERAL_RATIO;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            set_liquidation_auction_config(
+                spl_token_lending::id(),
+                Some(LIQUIDATION_AUCTION_CONFIG),
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+            ),
+            approve(
+                &spl_token::id(),
+                &usdc_test_reserve.user_liquidity_pubkey,
+                &user_transfer_authority.pubkey(),
+                &user_accounts_owner.pubkey(),
+                &[],
+                USDC_LIQUIDATION_AMOUNT_FRACTIONAL,
+            )
+            .unwrap(),
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                vec![sol_test_reserve.pubkey, usdc_test_reserve.pubkey],
+            ),
+            start_liquidation_auction(
+                spl_token_lending::id(),
+                liquidation_auction_pubkey,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+            ),
+            fill_liquidation_auction(
+                spl_token_lending::id(),
+                USDC_LIQUIDATION_AMOUNT_FRACTIONAL,
+                usdc_test_reserve.user_liquidity_pubkey,
+                sol_test_reserve.user_collateral_pubkey,
+                usdc_test_reserve.pubkey,
+                usdc_test_reserve.liquidity_supply_pubkey,
+                sol_test_reserve.pubkey,
+                sol_test_reserve.collateral_supply_pubkey,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+                user_transfer_authority.pubkey(),
+                liquidation_auction_pubkey,
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(
+        &[
+            &payer,
+            &lending_market.owner,
+            &user_accounts_owner,
+            &user_transfer_authority,
+        ],
+        recent_blockhash,
+    );
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    let lending_market_state = lending_market.get_state(&mut banks_client).await;
+    assert_eq!(
+        lending_market_state.liquidation_auction_config,
+        Some(LIQUIDATION_AUCTION_CONFIG)
+    );
+
+    let liquidation_auction = LiquidationAuction::unpack(
+        &banks_client
+            .get_account(liquidation_auction_pubkey)
+            .await
+            .unwrap()
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    assert_eq!(liquidation_auction.obligation, test_obligation.pubkey);
+    assert_eq!(
+        liquidation_auction.max_bonus,
+        LIQUIDATION_AUCTION_CONFIG.max_bonus
+    );
+
+    let user_collateral_balance =
+        get_token_balance(&mut banks_client, sol_test_reserve.user_collateral_pubkey).await;
+    assert_eq!(
+        user_collateral_balance,
+        initial_user_collateral_balance + SOL_LIQUIDATION_AMOUNT_LAMPORTS
+    );
+}
+
+#[tokio::test]
+async fn test_liquidate_requires_auction() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let TestAuction {
+        lending_market,
+        sol_test_reserve,
+        usdc_test_reserve,
+        test_obligation,
+        user_accounts_owner,
+        ..
+    } = setup(&mut test);
+    let user_transfer_authority = Keypair::new();
+
+    let (banks_client, payer, recent_blockhash) = test.start().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            set_liquidation_auction_config(
+                spl_token_lending::id(),
+                Some(LIQUIDATION_AUCTION_CONFIG),
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+            ),
+            approve(
+                &spl_token::id(),
+                &usdc_test_reserve.user_liquidity_pubkey,
+                &user_transfer_authority.pubkey(),
+                &user_accounts_owner.pubkey(),
+                &[],
+                USDC_LIQUIDATION_AMOUNT_FRACTIONAL,
+            )
+            .unwrap(),
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                vec![sol_test_reserve.pubkey, usdc_test_reserve.pubkey],
+            ),
+            liquidate_obligation(
+                spl_token_lending::id(),
+                USDC_LIQUIDATION_AMOUNT_FRACTIONAL,
+                usdc_test_reserve.user_liquidity_pubkey,
+                sol_test_reserve.user_collateral_pubkey,
+                usdc_test_reserve.pubkey,
+                usdc_test_reserve.liquidity_supply_pubkey,
+                sol_test_reserve.pubkey,
+                sol_test_reserve.collateral_supply_pubkey,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+                user_transfer_authority.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(
+        &[
+            &payer,
+            &lending_market.owner,
+            &user_accounts_owner,
+            &user_transfer_authority,
+        ],
+        recent_blockhash,
+    );
+
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            3,
+            InstructionError::Custom(LendingError::LiquidationAuctionRequired as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_fill_after_auction_window() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let TestAuction {
+        lending_market,
+        sol_oracle,
+        usdc_oracle,
+        sol_test_reserve,
+        usdc_test_reserve,
+        test_obligation,
+        user_accounts_owner,
+        liquidation_auction_pubkey,
+    } = setup(&mut test);
+    let user_transfer_authority = Keypair::new();
+
+    let mut test_context = test.start_with_context().await;
+    let payer = test_context.payer.insecure_clone();
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            set_liquidation_auction_config(
+                spl_token_lending::id(),
+                Some(LIQUIDATION_AUCTION_CONFIG),
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+            ),
+            approve(
+                &spl_token::id(),
+                &usdc_test_reserve.user_liquidity_pubkey,
+                &user_transfer_authority.pubkey(),
+                &user_accounts_owner.pubkey(),
+                &[],
+                USDC_LIQUIDATION_AMOUNT_FRACTIONAL,
+            )
+            .unwrap(),
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                vec![sol_test_reserve.pubkey, usdc_test_reserve.pubkey],
+            ),
+            start_liquidation_auction(
+                spl_token_lending::id(),
+                liquidation_auction_pubkey,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[&payer, &lending_market.owner, &user_accounts_owner],
+        test_context.last_blockhash,
+    );
+    assert!(test_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .is_ok());
+
+    let liquidation_auction = LiquidationAuction::unpack(
+        &test_context
+            .banks_client
+            .get_account(liquidation_auction_pubkey)
+            .await
+            .unwrap()
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+
+    // the auction keeps paying the maximum bonus after its window
+    test_context
+        .warp_to_slot(
+            liquidation_auction.start_slot + 2 * LIQUIDATION_AUCTION_CONFIG.duration_slots,
+        )
+        .unwrap();
+    let recent_blockhash = test_context.get_new_latest_blockhash().await.unwrap();
+
+    let initial_user_collateral_balance = get_token_balance(
+        &mut test_context.banks_client,
+        sol_test_reserve.user_collateral_pubkey,
+    )
+    .await;
+
+    // 800 USDC / 20 USDC per SOL -> 40 SOL + 20% bonus -> 48 SOL
+    const SOL_LIQUIDATION_AMOUNT_LAMPORTS: u64 = 48 * LAMPORTS_TO_SOL * INITIAL_COLLATERAL_RATIO;
+
+    let refresh = || {
+        vec![
+            refresh_reserve(
+                spl_token_lending::id(),
+                usdc_test_reserve.pubkey,
+                usdc_oracle.price_pubkey,
+            ),
+            refresh_reserve(
+                spl_token_lending::id(),
+                sol_test_reserve.pubkey,
+                sol_oracle.price_pubkey,
+            ),
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                vec![sol_test_reserve.pubkey, usdc_test_reserve.pubkey],
+            ),
+        ]
+    };
+
+    // a started auction cannot be restarted
+    let mut instructions = refresh();
+    instructions.push(start_liquidation_auction(
+        spl_token_lending::id(),
+        liquidation_auction_pubkey,
+        test_obligation.pubkey,
+        lending_market.pubkey,
+    ));
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    transaction.sign(&[&payer], recent_blockhash);
+    assert_eq!(
+        test_context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            3,
+            InstructionError::Custom(LendingError::LiquidationAuctionActive as u32)
+        )
+    );
+
+    let mut instructions = refresh();
+    instructions.push(fill_liquidation_auction(
+        spl_token_lending::id(),
+        USDC_LIQUIDATION_AMOUNT_FRACTIONAL,
+        usdc_test_reserve.user_liquidity_pubkey,
+        sol_test_reserve.user_collateral_pubkey,
+        usdc_test_reserve.pubkey,
+        usdc_test_reserve.liquidity_supply_pubkey,
+        sol_test_reserve.pubkey,
+        sol_test_reserve.collateral_supply_pubkey,
+        test_obligation.pubkey,
+        lending_market.pubkey,
+        user_transfer_authority.pubkey(),
+        liquidation_auction_pubkey,
+    ));
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    transaction.sign(&[&payer, &user_transfer_authority], recent_blockhash);
+    assert!(test_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .is_ok());
+
+    let user_collateral_balance = get_token_balance(
+        &mut test_context.banks_client,
+        sol_test_reserve.user_collateral_pubkey,
+    )
+    .await;
+    // accrued interest on the borrow makes the withdraw amount round down
+    assert_eq!(
+        user_collateral_balance,
+        initial_user_collateral_balance + SOL_LIQUIDATION_AMOUNT_LAMPORTS - 1
+    );
+}
@@ -0,0 +1,251 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::program_pack::Pack,
+    solana_program_test::*,
+    solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_token::instruction::approve,
+    spl_token_lending::{
+        error::LendingError,
+        instruction::{
+            find_obligation_health_address, init_obligation_health, liquidate_obligation,
+            refresh_obligation, refresh_reserve, set_liquidation_grace_period,
+            with_obligation_health,
+        },
+        processor::process_instruction,
+        state::{ObligationHealth, INITIAL_COLLATERAL_RATIO},
+    },
+};
+
+// 100 SOL collateral
+const SOL_DEPOSIT_AMOUNT_LAMPORTS: u64 = 100 * LAMPORTS_TO_SOL * INITIAL_COLLATERAL_RATIO;
+// 100 SOL * 80% LTV -> 80 SOL * 20 USDC -> 1600 USDC borrow
+const USDC_BORROW_AMOUNT_FRACTIONAL: u64 = 1_600 * FRACTIONAL_TO_USDC;
+// 1600 USDC * 50% -> 800 USDC liquidation
+const USDC_LIQUIDATION_AMOUNT_FRACTIONAL: u64 = USDC_BORROW_AMOUNT_FRACTIONAL / 2;
+
+const SOL_RESERVE_COLLATERAL_LAMPORTS: u64 = 2 * SOL_DEPOSIT_AMOUNT_LAMPORTS;
+const USDC_RESERVE_LIQUIDITY_FRACTIONAL: u64 = 2 * USDC_BORROW_AMOUNT_FRACTIONAL;
+
+// oracle prices go stale after 5 slots, so the grace period has to be short
+const LIQUIDATION_GRACE_SLOTS: u64 = 2;
+
+#[tokio::test]
+async fn test_liquidate_after_grace_period() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let user_accounts_owner = Keypair::new();
+    let user_transfer_authority = Keypair::new();
+    let lending_market = add_lending_market(&mut test);
+
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.loan_to_value_ratio = 50;
+    reserve_config.liquidation_threshold = 80;
+    reserve_config.liquidation_bonus = 10;
+
+    let sol_oracle = add_sol_oracle(&mut test);
+    let sol_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            collateral_amount: SOL_RESERVE_COLLATERAL_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let usdc_mint = add_usdc_mint(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
+    let usdc_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            borrow_amount: USDC_BORROW_AMOUNT_FRACTIONAL,
+            user_liquidity_amount: USDC_BORROW_AMOUNT_FRACTIONAL,
+            liquidity_amount: USDC_RESERVE_LIQUIDITY_FRACTIONAL,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let test_obligation = add_obligation(
+        &mut test,
+        &lending_market,
+        &user_accounts_owner,
+        AddObligationArgs {
+            deposits: &[(&sol_test_reserve, SOL_DEPOSIT_AMOUNT_LAMPORTS)],
+            borrows: &[(&usdc_test_reserve, USDC_BORROW_AMOUNT_FRACTIONAL)],
+            ..AddObligationArgs::default()
+        },
+    );
+
+    let mut test_context = test.start_with_context().await;
+    let payer = test_context.payer.insecure_clone();
+    let (obligation_health_pubkey, _bump_seed) =
+        find_obligation_health_address(&spl_token_lending::id(), &test_obligation.pubkey);
+
+    let refresh = || {
+        with_obligation_health(
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                vec![sol_test_reserve.pubkey, usdc_test_reserve.pubkey],
+            ),
+            test_obligation.pubkey,
+        )
+    };
+    let liquidate = || {
+        with_obligation_health(
+            liquidate_obligation(
+                spl_token_lending::id(),
+                USDC_LIQUIDATION_AMOUNT_FRACTIONAL,
+                usdc_test_reserve.user_liquidity_pubkey,
+                sol_test_reserve.user_collateral_pubkey,
+                usdc_test_reserve.pubkey,
+                usdc_test_reserve.liquidity_supply_pubkey,
+                sol_test_reserve.pubkey,
+                sol_test_reserve.collateral_supply_pubkey,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+                user_transfer_authority.pubkey(),
+            ),
+            test_obligation.pubkey,
+        )
+    };
+
+    // first unhealthy refresh starts the grace period
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            set_liquidation_grace_period(
+                spl_token_lending::id(),
+                LIQUIDATION_GRACE_SLOTS,
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+            ),
+            init_obligation_health(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                payer.pubkey(),
+            ),
+            approve(
+                &spl_token::id(),
+                &usdc_test_reserve.user_liquidity_pubkey,
+                &user_transfer_authority.pubkey(),
+                &user_accounts_owner.pubkey(),
+                &[],
+                USDC_LIQUIDATION_AMOUNT_FRACTIONAL,
+            )
+            .unwrap(),
+            refresh(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[&payer, &lending_market.owner, &user_accounts_owner],
+        test_context.last_blockhash,
+    );
+    assert!(test_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .is_ok());
+
+    let obligation_health = ObligationHealth::unpack(
+        &test_context
+            .banks_client
+            .get_account(obligation_health_pubkey)
+            .await
+            .unwrap()
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    let unhealthy_since_slot = obligation_health.unhealthy_since_slot.unwrap();
+    assert_eq!(obligation_health.obligation, test_obligation.pubkey);
+
+    // liquidation within the grace period fails
+    let mut transaction =
+        Transaction::new_with_payer(&[refresh(), liquidate()], Some(&payer.pubkey()));
+    transaction.sign(
+        &[&payer, &user_transfer_authority],
+        test_context.last_blockhash,
+    );
+    assert_eq!(
+        test_context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(LendingError::LiquidationGracePeriodActive as u32)
+        )
+    );
+
+    // liquidation succeeds once the obligation stayed unhealthy for the grace
+    // period
+    test_context
+        .warp_to_slot(unhealthy_since_slot + LIQUIDATION_GRACE_SLOTS)
+        .unwrap();
+    let recent_blockhash = test_context.get_new_latest_blockhash().await.unwrap();
+
+    let initial_user_collateral_balance = get_token_balance(
+        &mut test_context.banks_client,
+        sol_test_reserve.user_collateral_pubkey,
+    )
+    .await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_reserve(
+                spl_token_lending::id(),
+                usdc_test_reserve.pubkey,
+                usdc_oracle.price_pubkey,
+            ),
+            refresh_reserve(
+                spl_token_lending::id(),
+                sol_test_reserve.pubkey,
+                sol_oracle.price_pubkey,
+            ),
+            refresh(),
+            liquidate(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &user_transfer_authority], recent_blockhash);
+    assert!(test_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .is_ok());
+
+    let user_collateral_balance = get_token_balance(
+        &mut test_context.banks_client,
+        sol_test_reserve.user_collateral_pubkey,
+    )
+    .await;
+    assert!(user_collateral_balance > initial_user_collateral_balance);
+}
@@ -0,0 +1,328 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program_test::*,
+    solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_token_lending::{
+        error::LendingError,
+        instruction::{
+            add_margin_obligation, borrow_obligation_liquidity_with_margin, refresh_margin_account,
+            refresh_obligation, remove_margin_obligation, withdraw_obligation_collateral,
+        },
+        processor::process_instruction,
+        state::INITIAL_COLLATERAL_RATIO,
+    },
+};
+
+// 100 SOL collateral in one obligation
+const SOL_DEPOSIT_AMOUNT_LAMPORTS: u64 = 100 * LAMPORTS_TO_SOL * INITIAL_COLLATERAL_RATIO;
+// borrowed into another obligation of the margin account
+const USDC_BORROW_AMOUNT_FRACTIONAL: u64 = 100 * FRACTIONAL_TO_USDC;
+
+const SOL_RESERVE_COLLATERAL_LAMPORTS: u64 = 2 * SOL_DEPOSIT_AMOUNT_LAMPORTS;
+const USDC_RESERVE_LIQUIDITY_FRACTIONAL: u64 = 2 * USDC_BORROW_AMOUNT_FRACTIONAL;
+
+struct MarginTest {
+    lending_market: TestLendingMarket,
+    sol_test_reserve: TestReserve,
+    usdc_test_reserve: TestReserve,
+    collateral_obligation: TestObligation,
+    borrow_obligation: TestObligation,
+}
+
+fn setup(test: &mut ProgramTest, user_accounts_owner: &Keypair, borrowed: bool) -> MarginTest {
+    let lending_market = add_lending_market(test);
+
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.loan_to_value_ratio = 50;
+
+    let sol_oracle = add_sol_oracle(test);
+    let sol_test_reserve = add_reserve(
+        test,
+        &lending_market,
+        &sol_oracle,
+        user_accounts_owner,
+        AddReserveArgs {
+            collateral_amount: SOL_RESERVE_COLLATERAL_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let usdc_mint = add_usdc_mint(test);
+    let usdc_oracle = add_usdc_oracle(test);
+    let usdc_test_reserve = add_reserve(
+        test,
+        &lending_market,
+        &usdc_oracle,
+        user_accounts_owner,
+        AddReserveArgs {
+            liquidity_amount: USDC_RESERVE_LIQUIDITY_FRACTIONAL,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            borrow_amount: if borrowed {
+                USDC_BORROW_AMOUNT_FRACTIONAL
+            } else {
+                0
+            },
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let collateral_obligation = add_obligation(
+        test,
+        &lending_market,
+        user_accounts_owner,
+        AddObligationArgs {
+            deposits: &[(&sol_test_reserve, SOL_DEPOSIT_AMOUNT_LAMPORTS)],
+            ..AddObligationArgs::default()
+        },
+    );
+    let usdc_borrows = [(&usdc_test_reserve, USDC_BORROW_AMOUNT_FRACTIONAL)];
+    let borrow_obligation = add_obligation(
+        test,
+        &lending_market,
+        user_accounts_owner,
+        AddObligationArgs {
+            borrows: if borrowed { &usdc_borrows } else { &[] },
+            ..AddObligationArgs::default()
+        },
+    );
+
+    MarginTest {
+        lending_market,
+        sol_test_reserve,
+        usdc_test_reserve,
+        collateral_obligation,
+        borrow_obligation,
+    }
+}
+
+#[tokio::test]
+async fn test_borrow_against_margin_account() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let user_accounts_owner = Keypair::new();
+    let MarginTest {
+        lending_market,
+        sol_test_reserve,
+        usdc_test_reserve,
+        collateral_obligation,
+        borrow_obligation,
+    } = setup(&mut test, &user_accounts_owner, false);
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let test_margin_account = TestMarginAccount::init(
+        &mut banks_client,
+        &lending_market,
+        &user_accounts_owner,
+        &payer,
+    )
+    .await
+    .unwrap();
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            add_margin_obligation(
+                spl_token_lending::id(),
+                test_margin_account.pubkey,
+                collateral_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+            ),
+            add_margin_obligation(
+                spl_token_lending::id(),
+                test_margin_account.pubkey,
+                borrow_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+            ),
+            refresh_obligation(
+                spl_token_lending::id(),
+                collateral_obligation.pubkey,
+                vec![sol_test_reserve.pubkey],
+            ),
+            refresh_obligation(spl_token_lending::id(), borrow_obligation.pubkey, vec![]),
+            refresh_margin_account(
+                spl_token_lending::id(),
+                test_margin_account.pubkey,
+                vec![collateral_obligation.pubkey, borrow_obligation.pubkey],
+            ),
+            borrow_obligation_liquidity_with_margin(
+                spl_token_lending::id(),
+                USDC_BORROW_AMOUNT_FRACTIONAL,
+                None,
+                test_margin_account.pubkey,
+                usdc_test_reserve.liquidity_supply_pubkey,
+                usdc_test_reserve.user_liquidity_pubkey,
+                usdc_test_reserve.pubkey,
+                usdc_test_reserve.liquidity_fee_receiver_pubkey,
+                borrow_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+                None,
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    let borrow_amount =
+        get_token_balance(&mut banks_client, usdc_test_reserve.user_liquidity_pubkey).await;
+    assert_eq!(borrow_amount, USDC_BORROW_AMOUNT_FRACTIONAL);
+
+    let obligation = borrow_obligation.get_state(&mut banks_client).await;
+    assert!(obligation.deposits.is_empty());
+    assert_eq!(obligation.borrows.len(), 1);
+    assert_eq!(obligation.owner, lending_market.authority);
+
+    let obligation = collateral_obligation.get_state(&mut banks_client).await;
+    assert_eq!(obligation.owner, lending_market.authority);
+
+    let margin_account = test_margin_account.get_state(&mut banks_client).await;
+    assert_eq!(margin_account.owner, user_accounts_owner.pubkey());
+    assert_eq!(
+        margin_account.obligations,
+        vec![collateral_obligation.pubkey, borrow_obligation.pubkey]
+    );
+    assert!(margin_account.last_update.stale);
+}
+
+#[tokio::test]
+async fn test_remove_margin_obligation_undercollateralized() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let user_accounts_owner = Keypair::new();
+    let MarginTest {
+        lending_market,
+        sol_test_reserve,
+        usdc_test_reserve,
+        collateral_obligation,
+        borrow_obligation,
+    } = setup(&mut test, &user_accounts_owner, true);
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let test_margin_account = TestMarginAccount::init(
+        &mut banks_client,
+        &lending_market,
+        &user_accounts_owner,
+        &payer,
+    )
+    .await
+    .unwrap();
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            add_margin_obligation(
+                spl_token_lending::id(),
+                test_margin_account.pubkey,
+                collateral_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+            ),
+            add_margin_obligation(
+                spl_token_lending::id(),
+                test_margin_account.pubkey,
+                borrow_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    // the borrows of the other obligation are backed by this one
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_obligation(
+                spl_token_lending::id(),
+                collateral_obligation.pubkey,
+                vec![sol_test_reserve.pubkey],
+            ),
+            refresh_obligation(
+                spl_token_lending::id(),
+                borrow_obligation.pubkey,
+                vec![usdc_test_reserve.pubkey],
+            ),
+            refresh_margin_account(
+                spl_token_lending::id(),
+                test_margin_account.pubkey,
+                vec![collateral_obligation.pubkey, borrow_obligation.pubkey],
+            ),
+            remove_margin_obligation(
+                spl_token_lending::id(),
+                test_margin_account.pubkey,
+                collateral_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            3,
+            InstructionError::Custom(LendingError::MarginObligationUndercollateralized as u32)
+        )
+    );
+
+    // obligations of a margin account can no longer be used directly
+    let mut transaction = Transaction::new_with_payer(
+        &[withdraw_obligation_collateral(
+            spl_token_lending::id(),
+            SOL_DEPOSIT_AMOUNT_LAMPORTS,
+            sol_test_reserve.collateral_supply_pubkey,
+            sol_test_reserve.user_collateral_pubkey,
+            sol_test_reserve.pubkey,
+            collateral_obligation.pubkey,
+            lending_market.pubkey,
+            user_accounts_owner.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::InvalidObligationOwner as u32)
+        )
+    );
+}
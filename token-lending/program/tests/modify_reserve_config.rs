@@ -14,7 +14,7 @@ use {
     },
     spl_token_lending::{
         error::LendingError,
-        instruction::modify_reserve_config,
+        instruction::{modify_reserve_config, LendingInstruction},
         processor::process_instruction,
         state::{
             InitLendingMarketParams, LendingMarket, ReserveConfig, ReserveFees,
@@ -74,6 +74,10 @@ async fn modify_reserve_config_success() {
             flash_loan_fee_wad: 3_000_000_000_000_000,
             host_fee_percentage: 20,
         },
+        max_oracle_age_slots: 10,
+        max_oracle_confidence_bps: 100,
+        deposit_value_limit: 1_000_000,
+        borrow_value_limit: 500_000,
     };
 
     let mut transaction = Transaction::new_with_payer(
@@ -97,6 +101,47 @@ async fn modify_reserve_config_success() {
 
     let reserve_info = sol_test_reserve.get_state(&mut banks_client).await;
     assert_eq!(reserve_info.config, new_config);
+
+    // a config from an older client keeps the oracle thresholds and limits
+    let legacy_config = ReserveConfig {
+        loan_to_value_ratio: 40,
+        max_oracle_age_slots: 0,
+        max_oracle_confidence_bps: 0,
+        deposit_value_limit: 0,
+        borrow_value_limit: 0,
+        ..new_config
+    };
+    let mut instruction = modify_reserve_config(
+        spl_token_lending::id(),
+        legacy_config,
+        sol_test_reserve.pubkey,
+        lending_market.pubkey,
+        lending_market.owner.pubkey(),
+    );
+    instruction.data = LendingInstruction::ModifyReserveConfig {
+        new_config: legacy_config,
+        legacy_config: true,
+    }
+    .pack();
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+        .unwrap();
+
+    let reserve_info = sol_test_reserve.get_state(&mut banks_client).await;
+    assert_eq!(
+        reserve_info.config,
+        ReserveConfig {
+            loan_to_value_ratio: 40,
+            ..new_config
+        }
+    );
 }
 
 #[tokio::test]
@@ -155,6 +200,10 @@ async fn wrong_signer_of_lending_market_cannot_change_reserve_config() {
             flash_loan_fee_wad: 3_000_000_000_000_000,
             host_fee_percentage: 20,
         },
+        max_oracle_age_slots: 0,
+        max_oracle_confidence_bps: 0,
+        deposit_value_limit: 0,
+        borrow_value_limit: 0,
     };
 
     let mut instruction = modify_reserve_config(
@@ -269,6 +318,10 @@ async fn owner_of_different_lending_market_cannot_change_reserve_config() {
             flash_loan_fee_wad: 3_000_000_000_000_000,
             host_fee_percentage: 20,
         },
+        max_oracle_age_slots: 0,
+        max_oracle_confidence_bps: 0,
+        deposit_value_limit: 0,
+        borrow_value_limit: 0,
     };
 
     let mut transaction = Transaction::new_with_payer(
@@ -359,6 +412,10 @@ async fn correct_owner_providing_wrong_lending_market_fails() {
             flash_loan_fee_wad: 3_000_000_000_000_000,
             host_fee_percentage: 20,
         },
+        max_oracle_age_slots: 0,
+        max_oracle_confidence_bps: 0,
+        deposit_value_limit: 0,
+        borrow_value_limit: 0,
     };
 
     let mut transaction = Transaction::new_with_payer(
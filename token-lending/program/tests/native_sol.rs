@@ -0,0 +1,443 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program_test::*,
+    solana_sdk::{
+        account::Account,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_instruction, system_program,
+        transaction::Transaction,
+    },
+    spl_token_lending::{
+        instruction::{
+            borrow_obligation_liquidity_native, deposit_reserve_liquidity_native,
+            find_native_sol_address, redeem_reserve_collateral_native, refresh_obligation,
+            refresh_reserve, repay_obligation_liquidity_native,
+        },
+        math::Decimal,
+        processor::process_instruction,
+        state::INITIAL_COLLATERAL_RATIO,
+    },
+};
+
+const USER_LAMPORTS: u64 = 100 * LAMPORTS_TO_SOL;
+
+fn add_user(test: &mut ProgramTest) -> Keypair {
+    let user = Keypair::new();
+    test.add_account(
+        user.pubkey(),
+        Account::new(USER_LAMPORTS, 0, &system_program::id()),
+    );
+    user
+}
+
+async fn get_balance(banks_client: &mut BanksClient, pubkey: Pubkey) -> u64 {
+    banks_client.get_balance(pubkey).await.unwrap()
+}
+
+async fn assert_native_sol_closed(banks_client: &mut BanksClient, user: &Pubkey) {
+    let (native_sol_pubkey, _) = find_native_sol_address(&spl_token_lending::id(), user);
+    assert_eq!(
+        banks_client.get_account(native_sol_pubkey).await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_deposit_and_redeem_native() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    const SOL_RESERVE_LIQUIDITY_LAMPORTS: u64 = 10 * LAMPORTS_TO_SOL;
+    const SOL_DEPOSIT_AMOUNT_LAMPORTS: u64 = LAMPORTS_TO_SOL;
+    const COLLATERAL_AMOUNT: u64 = SOL_DEPOSIT_AMOUNT_LAMPORTS * INITIAL_COLLATERAL_RATIO;
+
+    let user_accounts_owner = add_user(&mut test);
+    let lending_market = add_lending_market(&mut test);
+
+    let sol_oracle = add_sol_oracle(&mut test);
+    let sol_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            liquidity_amount: SOL_RESERVE_LIQUIDITY_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            config: TEST_RESERVE_CONFIG,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let initial_collateral_balance =
+        get_token_balance(&mut banks_client, sol_test_reserve.user_collateral_pubkey).await;
+    let initial_liquidity_supply_balance =
+        get_token_balance(&mut banks_client, sol_test_reserve.liquidity_supply_pubkey).await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[deposit_reserve_liquidity_native(
+            spl_token_lending::id(),
+            SOL_DEPOSIT_AMOUNT_LAMPORTS,
+            sol_test_reserve.user_collateral_pubkey,
+            sol_test_reserve.pubkey,
+            sol_test_reserve.liquidity_supply_pubkey,
+            sol_test_reserve.collateral_mint_pubkey,
+            lending_market.pubkey,
+            user_accounts_owner.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    // the temporary account rent is refunded, only the deposit is spent
+    assert_eq!(
+        get_balance(&mut banks_client, user_accounts_owner.pubkey()).await,
+        USER_LAMPORTS - SOL_DEPOSIT_AMOUNT_LAMPORTS
+    );
+    assert_eq!(
+        get_token_balance(&mut banks_client, sol_test_reserve.user_collateral_pubkey).await,
+        initial_collateral_balance + COLLATERAL_AMOUNT
+    );
+    assert_eq!(
+        get_token_balance(&mut banks_client, sol_test_reserve.liquidity_supply_pubkey).await,
+        initial_liquidity_supply_balance + SOL_DEPOSIT_AMOUNT_LAMPORTS
+    );
+    assert_native_sol_closed(&mut banks_client, &user_accounts_owner.pubkey()).await;
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_reserve(
+                spl_token_lending::id(),
+                sol_test_reserve.pubkey,
+                sol_test_reserve.liquidity_oracle_pubkey,
+            ),
+            redeem_reserve_collateral_native(
+                spl_token_lending::id(),
+                COLLATERAL_AMOUNT,
+                sol_test_reserve.user_collateral_pubkey,
+                sol_test_reserve.pubkey,
+                sol_test_reserve.collateral_mint_pubkey,
+                sol_test_reserve.liquidity_supply_pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    assert_eq!(
+        get_balance(&mut banks_client, user_accounts_owner.pubkey()).await,
+        USER_LAMPORTS
+    );
+    assert_eq!(
+        get_token_balance(&mut banks_client, sol_test_reserve.user_collateral_pubkey).await,
+        initial_collateral_balance
+    );
+    assert_eq!(
+        get_token_balance(&mut banks_client, sol_test_reserve.liquidity_supply_pubkey).await,
+        initial_liquidity_supply_balance
+    );
+    assert_native_sol_closed(&mut banks_client, &user_accounts_owner.pubkey()).await;
+}
+
+#[tokio::test]
+async fn test_deposit_native_with_prefunded_account() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    const SOL_RESERVE_LIQUIDITY_LAMPORTS: u64 = 10 * LAMPORTS_TO_SOL;
+    const SOL_DEPOSIT_AMOUNT_LAMPORTS: u64 = LAMPORTS_TO_SOL;
+    const PREFUNDED_LAMPORTS: u64 = LAMPORTS_TO_SOL / 2;
+
+    let user_accounts_owner = add_user(&mut test);
+    let lending_market = add_lending_market(&mut test);
+
+    let sol_oracle = add_sol_oracle(&mut test);
+    let sol_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            liquidity_amount: SOL_RESERVE_LIQUIDITY_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            config: TEST_RESERVE_CONFIG,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let initial_collateral_balance =
+        get_token_balance(&mut banks_client, sol_test_reserve.user_collateral_pubkey).await;
+
+    // anyone can send lamports to the temporary account address ahead of time,
+    // which must not block native SOL instructions
+    let (native_sol_pubkey, _) =
+        find_native_sol_address(&spl_token_lending::id(), &user_accounts_owner.pubkey());
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::transfer(&payer.pubkey(), &native_sol_pubkey, PREFUNDED_LAMPORTS),
+            deposit_reserve_liquidity_native(
+                spl_token_lending::id(),
+                SOL_DEPOSIT_AMOUNT_LAMPORTS,
+                sol_test_reserve.user_collateral_pubkey,
+                sol_test_reserve.pubkey,
+                sol_test_reserve.liquidity_supply_pubkey,
+                sol_test_reserve.collateral_mint_pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    // the prefunded lamports go to the user along with the rent
+    assert_eq!(
+        get_balance(&mut banks_client, user_accounts_owner.pubkey()).await,
+        USER_LAMPORTS - SOL_DEPOSIT_AMOUNT_LAMPORTS + PREFUNDED_LAMPORTS
+    );
+    assert_eq!(
+        get_token_balance(&mut banks_client, sol_test_reserve.user_collateral_pubkey).await,
+        initial_collateral_balance + SOL_DEPOSIT_AMOUNT_LAMPORTS * INITIAL_COLLATERAL_RATIO
+    );
+    assert_native_sol_closed(&mut banks_client, &user_accounts_owner.pubkey()).await;
+}
+
+#[tokio::test]
+async fn test_borrow_native() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    const USDC_DEPOSIT_AMOUNT_FRACTIONAL: u64 =
+        2_000 * FRACTIONAL_TO_USDC * INITIAL_COLLATERAL_RATIO;
+    const SOL_BORROW_AMOUNT_LAMPORTS: u64 = LAMPORTS_TO_SOL;
+    const USDC_RESERVE_COLLATERAL_FRACTIONAL: u64 = 2 * USDC_DEPOSIT_AMOUNT_FRACTIONAL;
+    const SOL_RESERVE_LIQUIDITY_LAMPORTS: u64 = 10 * SOL_BORROW_AMOUNT_LAMPORTS;
+
+    let user_accounts_owner = add_user(&mut test);
+    let lending_market = add_lending_market(&mut test);
+
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.loan_to_value_ratio = 50;
+
+    let usdc_mint = add_usdc_mint(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
+    let usdc_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            collateral_amount: USDC_RESERVE_COLLATERAL_FRACTIONAL,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let sol_oracle = add_sol_oracle(&mut test);
+    let sol_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            liquidity_amount: SOL_RESERVE_LIQUIDITY_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let test_obligation = add_obligation(
+        &mut test,
+        &lending_market,
+        &user_accounts_owner,
+        AddObligationArgs {
+            deposits: &[(&usdc_test_reserve, USDC_DEPOSIT_AMOUNT_FRACTIONAL)],
+            ..AddObligationArgs::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                vec![usdc_test_reserve.pubkey],
+            ),
+            borrow_obligation_liquidity_native(
+                spl_token_lending::id(),
+                SOL_BORROW_AMOUNT_LAMPORTS,
+                None,
+                sol_test_reserve.liquidity_supply_pubkey,
+                sol_test_reserve.pubkey,
+                sol_test_reserve.liquidity_fee_receiver_pubkey,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+                test_obligation.owner,
+                None,
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    assert_eq!(
+        get_balance(&mut banks_client, user_accounts_owner.pubkey()).await,
+        USER_LAMPORTS + SOL_BORROW_AMOUNT_LAMPORTS
+    );
+    let obligation = test_obligation.get_state(&mut banks_client).await;
+    assert_eq!(obligation.borrows.len(), 1);
+    assert_eq!(
+        obligation.borrows[0].borrow_reserve,
+        sol_test_reserve.pubkey
+    );
+    assert!(
+        obligation.borrows[0].borrowed_amount_wads >= Decimal::from(SOL_BORROW_AMOUNT_LAMPORTS)
+    );
+    assert_native_sol_closed(&mut banks_client, &user_accounts_owner.pubkey()).await;
+}
+
+#[tokio::test]
+async fn test_repay_native_refunds_leftover() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    const USDC_DEPOSIT_AMOUNT_FRACTIONAL: u64 =
+        2_000 * FRACTIONAL_TO_USDC * INITIAL_COLLATERAL_RATIO;
+    const SOL_BORROW_AMOUNT_LAMPORTS: u64 = LAMPORTS_TO_SOL;
+    const SOL_REPAY_AMOUNT_LAMPORTS: u64 = 3 * SOL_BORROW_AMOUNT_LAMPORTS;
+    const USDC_RESERVE_COLLATERAL_FRACTIONAL: u64 = 2 * USDC_DEPOSIT_AMOUNT_FRACTIONAL;
+    const SOL_RESERVE_LIQUIDITY_LAMPORTS: u64 = 10 * SOL_BORROW_AMOUNT_LAMPORTS;
+
+    let user_accounts_owner = add_user(&mut test);
+    let user_transfer_authority = add_user(&mut test);
+    let lending_market = add_lending_market(&mut test);
+
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.loan_to_value_ratio = 50;
+
+    let usdc_mint = add_usdc_mint(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
+    let usdc_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            collateral_amount: USDC_RESERVE_COLLATERAL_FRACTIONAL,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let sol_oracle = add_sol_oracle(&mut test);
+    let sol_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            borrow_amount: SOL_BORROW_AMOUNT_LAMPORTS,
+            liquidity_amount: SOL_RESERVE_LIQUIDITY_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let test_obligation = add_obligation(
+        &mut test,
+        &lending_market,
+        &user_accounts_owner,
+        AddObligationArgs {
+            deposits: &[(&usdc_test_reserve, USDC_DEPOSIT_AMOUNT_FRACTIONAL)],
+            borrows: &[(&sol_test_reserve, SOL_BORROW_AMOUNT_LAMPORTS)],
+            ..AddObligationArgs::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let initial_liquidity_supply_balance =
+        get_token_balance(&mut banks_client, sol_test_reserve.liquidity_supply_pubkey).await;
+
+    // repaying more than is borrowed only spends the borrowed amount, the rest
+    // of the wrapped SOL is returned when the temporary account is closed
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                vec![usdc_test_reserve.pubkey, sol_test_reserve.pubkey],
+            ),
+            repay_obligation_liquidity_native(
+                spl_token_lending::id(),
+                SOL_REPAY_AMOUNT_LAMPORTS,
+                sol_test_reserve.liquidity_supply_pubkey,
+                sol_test_reserve.pubkey,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+                user_transfer_authority.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &user_transfer_authority], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    assert_eq!(
+        get_balance(&mut banks_client, user_transfer_authority.pubkey()).await,
+        USER_LAMPORTS - SOL_BORROW_AMOUNT_LAMPORTS
+    );
+    assert_eq!(
+        get_token_balance(&mut banks_client, sol_test_reserve.liquidity_supply_pubkey).await,
+        initial_liquidity_supply_balance + SOL_BORROW_AMOUNT_LAMPORTS
+    );
+    let obligation = test_obligation.get_state(&mut banks_client).await;
+    assert_eq!(obligation.borrows.len(), 0);
+    assert_native_sol_closed(&mut banks_client, &user_transfer_authority.pubkey()).await;
+}
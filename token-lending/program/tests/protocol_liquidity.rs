@@ -0,0 +1,283 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::program_pack::Pack,
+    solana_program_test::*,
+    solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        system_instruction::create_account,
+        transaction::{Transaction, TransactionError},
+    },
+    spl_token::state::Account as Token,
+    spl_token_lending::{
+        error::LendingError,
+        instruction::{
+            announce_protocol_liquidity_withdrawal, deposit_protocol_liquidity,
+            init_protocol_liquidity, refresh_reserve, withdraw_protocol_liquidity,
+        },
+        processor::process_instruction,
+        state::{ProtocolLiquidity, INITIAL_COLLATERAL_RATIO},
+    },
+};
+
+const WITHDRAWAL_TIMELOCK_SLOTS: u64 = 2;
+
+#[tokio::test]
+async fn test_timelocked_withdrawal() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    const USDC_DEPOSIT_AMOUNT_FRACTIONAL: u64 = 100 * FRACTIONAL_TO_USDC;
+    const COLLATERAL_AMOUNT: u64 = USDC_DEPOSIT_AMOUNT_FRACTIONAL * INITIAL_COLLATERAL_RATIO;
+
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market(&mut test);
+
+    let usdc_mint = add_usdc_mint(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
+    let usdc_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            user_liquidity_amount: USDC_DEPOSIT_AMOUNT_FRACTIONAL,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            config: TEST_RESERVE_CONFIG,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let mut test_context = test.start_with_context().await;
+    let payer = test_context.payer.insecure_clone();
+    let rent = test_context.banks_client.get_rent().await.unwrap();
+
+    let protocol_liquidity_keypair = Keypair::new();
+    let protocol_collateral_supply_keypair = Keypair::new();
+    let protocol_liquidity_pubkey = protocol_liquidity_keypair.pubkey();
+    let protocol_collateral_supply_pubkey = protocol_collateral_supply_keypair.pubkey();
+
+    let withdraw = || {
+        withdraw_protocol_liquidity(
+            spl_token_lending::id(),
+            protocol_liquidity_pubkey,
+            protocol_collateral_supply_pubkey,
+            usdc_test_reserve.user_liquidity_pubkey,
+            usdc_test_reserve.pubkey,
+            usdc_test_reserve.collateral_mint_pubkey,
+            usdc_test_reserve.liquidity_supply_pubkey,
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+        )
+    };
+
+    // seed the reserve with protocol-owned liquidity
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            create_account(
+                &payer.pubkey(),
+                &protocol_liquidity_pubkey,
+                rent.minimum_balance(ProtocolLiquidity::LEN),
+                ProtocolLiquidity::LEN as u64,
+                &spl_token_lending::id(),
+            ),
+            create_account(
+                &payer.pubkey(),
+                &protocol_collateral_supply_pubkey,
+                rent.minimum_balance(Token::LEN),
+                Token::LEN as u64,
+                &spl_token::id(),
+            ),
+            init_protocol_liquidity(
+                spl_token_lending::id(),
+                WITHDRAWAL_TIMELOCK_SLOTS,
+                protocol_liquidity_pubkey,
+                protocol_collateral_supply_pubkey,
+                usdc_test_reserve.pubkey,
+                usdc_test_reserve.collateral_mint_pubkey,
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+            ),
+            deposit_protocol_liquidity(
+                spl_token_lending::id(),
+                USDC_DEPOSIT_AMOUNT_FRACTIONAL,
+                protocol_liquidity_pubkey,
+                usdc_test_reserve.user_liquidity_pubkey,
+                protocol_collateral_supply_pubkey,
+                usdc_test_reserve.pubkey,
+                usdc_test_reserve.liquidity_supply_pubkey,
+                usdc_test_reserve.collateral_mint_pubkey,
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+                user_accounts_owner.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[
+            &payer,
+            &protocol_liquidity_keypair,
+            &protocol_collateral_supply_keypair,
+            &lending_market.owner,
+            &user_accounts_owner,
+        ],
+        test_context.last_blockhash,
+    );
+    assert!(test_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .is_ok());
+
+    let collateral_balance = get_token_balance(
+        &mut test_context.banks_client,
+        protocol_collateral_supply_pubkey,
+    )
+    .await;
+    assert_eq!(collateral_balance, COLLATERAL_AMOUNT);
+
+    // withdrawal without an announcement fails
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_reserve(
+                spl_token_lending::id(),
+                usdc_test_reserve.pubkey,
+                usdc_oracle.price_pubkey,
+            ),
+            withdraw(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[&payer, &lending_market.owner],
+        test_context.last_blockhash,
+    );
+    assert_eq!(
+        test_context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(LendingError::ProtocolLiquidityWithdrawalNotAnnounced as u32)
+        )
+    );
+
+    // withdrawal within the timelock fails
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_reserve(
+                spl_token_lending::id(),
+                usdc_test_reserve.pubkey,
+                usdc_oracle.price_pubkey,
+            ),
+            announce_protocol_liquidity_withdrawal(
+                spl_token_lending::id(),
+                COLLATERAL_AMOUNT,
+                protocol_liquidity_pubkey,
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+            ),
+            withdraw(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[&payer, &lending_market.owner],
+        test_context.last_blockhash,
+    );
+    assert_eq!(
+        test_context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            2,
+            InstructionError::Custom(LendingError::ProtocolLiquidityLocked as u32)
+        )
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[announce_protocol_liquidity_withdrawal(
+            spl_token_lending::id(),
+            COLLATERAL_AMOUNT,
+            protocol_liquidity_pubkey,
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[&payer, &lending_market.owner],
+        test_context.last_blockhash,
+    );
+    assert!(test_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .is_ok());
+
+    let protocol_liquidity = ProtocolLiquidity::unpack(
+        &test_context
+            .banks_client
+            .get_account(protocol_liquidity_pubkey)
+            .await
+            .unwrap()
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    let withdrawal = protocol_liquidity.withdrawal.unwrap();
+    assert_eq!(withdrawal.collateral_amount, COLLATERAL_AMOUNT);
+
+    // withdrawal succeeds once the timelock elapsed
+    test_context.warp_to_slot(withdrawal.unlock_slot).unwrap();
+    let recent_blockhash = test_context.get_new_latest_blockhash().await.unwrap();
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_reserve(
+                spl_token_lending::id(),
+                usdc_test_reserve.pubkey,
+                usdc_oracle.price_pubkey,
+            ),
+            withdraw(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+    assert!(test_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .is_ok());
+
+    let collateral_balance = get_token_balance(
+        &mut test_context.banks_client,
+        protocol_collateral_supply_pubkey,
+    )
+    .await;
+    assert_eq!(collateral_balance, 0);
+
+    let user_liquidity_balance = get_token_balance(
+        &mut test_context.banks_client,
+        usdc_test_reserve.user_liquidity_pubkey,
+    )
+    .await;
+    assert_eq!(user_liquidity_balance, USDC_DEPOSIT_AMOUNT_FRACTIONAL);
+}
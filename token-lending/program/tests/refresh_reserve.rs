@@ -5,16 +5,20 @@ mod helpers;
 
 use {
     helpers::*,
+    solana_program::program_pack::Pack,
     solana_program_test::*,
     solana_sdk::{
         signature::{Keypair, Signer},
         transaction::Transaction,
     },
     spl_token_lending::{
-        instruction::refresh_reserve,
+        instruction::{
+            find_reserve_rate_history_address, init_reserve_rate_history, refresh_reserve,
+            with_reserve_rate_history,
+        },
         math::{Decimal, Rate, TryAdd, TryDiv, TryMul},
         processor::process_instruction,
-        state::SLOTS_PER_YEAR,
+        state::{ReserveRateHistory, SLOTS_PER_YEAR},
     },
 };
 
@@ -140,3 +144,102 @@ async fn test_success() {
         usdc_test_reserve.market_price
     );
 }
+
+#[tokio::test]
+async fn test_rate_history() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    const USDC_RESERVE_LIQUIDITY_FRACTIONAL: u64 = 100 * FRACTIONAL_TO_USDC;
+    const BORROW_AMOUNT: u64 = 100;
+
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market(&mut test);
+
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.loan_to_value_ratio = 80;
+
+    // Configure reserve to a fixed borrow rate of 1%
+    const BORROW_RATE: u8 = 1;
+    reserve_config.min_borrow_rate = BORROW_RATE;
+    reserve_config.optimal_borrow_rate = BORROW_RATE;
+    reserve_config.optimal_utilization_rate = 100;
+
+    let usdc_mint = add_usdc_mint(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
+    let usdc_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            borrow_amount: BORROW_AMOUNT,
+            liquidity_amount: USDC_RESERVE_LIQUIDITY_FRACTIONAL,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            config: reserve_config,
+            slots_elapsed: 1, // elapsed from 1; clock.slot = 2
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let mut test_context = test.start_with_context().await;
+    test_context.warp_to_slot(3).unwrap(); // clock.slot = 3
+
+    let ProgramTestContext {
+        mut banks_client,
+        payer,
+        last_blockhash: recent_blockhash,
+        ..
+    } = test_context;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            init_reserve_rate_history(
+                spl_token_lending::id(),
+                usdc_test_reserve.pubkey,
+                payer.pubkey(),
+            ),
+            with_reserve_rate_history(
+                refresh_reserve(
+                    spl_token_lending::id(),
+                    usdc_test_reserve.pubkey,
+                    usdc_oracle.price_pubkey,
+                ),
+                usdc_test_reserve.pubkey,
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    let usdc_reserve = usdc_test_reserve.get_state(&mut banks_client).await;
+    let reserve_rate_history_pubkey =
+        find_reserve_rate_history_address(&spl_token_lending::id(), &usdc_test_reserve.pubkey).0;
+    let reserve_rate_history_account = banks_client
+        .get_account(reserve_rate_history_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let reserve_rate_history =
+        ReserveRateHistory::unpack(&reserve_rate_history_account.data).unwrap();
+
+    assert_eq!(reserve_rate_history.reserve, usdc_test_reserve.pubkey);
+    assert_eq!(reserve_rate_history.snapshots_count, 1);
+
+    let snapshot = reserve_rate_history.latest().unwrap();
+    assert_eq!(snapshot, &usdc_reserve.rate_snapshot(3).unwrap());
+    assert_eq!(snapshot.borrow_rate, Rate::from_percent(BORROW_RATE));
+    assert_eq!(
+        snapshot.supply_rate,
+        snapshot
+            .borrow_rate
+            .try_mul(snapshot.utilization_rate)
+            .unwrap()
+    );
+}
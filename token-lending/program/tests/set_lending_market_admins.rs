@@ -0,0 +1,197 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program_test::*,
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_token_lending::{
+        error::LendingError,
+        instruction::{modify_reserve_config, set_lending_market_admins},
+        processor::process_instruction,
+    },
+};
+
+#[tokio::test]
+async fn test_success() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let lending_market = add_lending_market(&mut test);
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let lending_market_info = lending_market.get_state(&mut banks_client).await;
+    assert_eq!(lending_market_info.risk_admin, None);
+    assert_eq!(lending_market_info.ops_admin, None);
+    assert_eq!(
+        lending_market_info.risk_authority(),
+        lending_market.owner.pubkey()
+    );
+
+    let risk_admin = Pubkey::new_unique();
+    let ops_admin = Pubkey::new_unique();
+    let mut transaction = Transaction::new_with_payer(
+        &[set_lending_market_admins(
+            spl_token_lending::id(),
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+            Some(risk_admin),
+            Some(ops_admin),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+        .unwrap();
+
+    let lending_market_info = lending_market.get_state(&mut banks_client).await;
+    assert_eq!(lending_market_info.risk_admin, Some(risk_admin));
+    assert_eq!(lending_market_info.ops_admin, Some(ops_admin));
+    assert_eq!(lending_market_info.owner, lending_market.owner.pubkey());
+}
+
+#[tokio::test]
+async fn test_invalid_owner() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let lending_market = add_lending_market(&mut test);
+    let (banks_client, payer, recent_blockhash) = test.start().await;
+
+    let invalid_owner = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[set_lending_market_admins(
+            spl_token_lending::id(),
+            lending_market.pubkey,
+            invalid_owner.pubkey(),
+            Some(invalid_owner.pubkey()),
+            Some(invalid_owner.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer, &invalid_owner], recent_blockhash);
+
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::InvalidMarketOwner as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_risk_admin_replaces_owner() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market(&mut test);
+    let sol_oracle = add_sol_oracle(&mut test);
+    let sol_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            liquidity_amount: LAMPORTS_TO_SOL,
+            liquidity_mint_decimals: 9,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            config: TEST_RESERVE_CONFIG,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let risk_admin = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[set_lending_market_admins(
+            spl_token_lending::id(),
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+            Some(risk_admin.pubkey()),
+            None,
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+        .unwrap();
+
+    let mut new_config = TEST_RESERVE_CONFIG;
+    new_config.loan_to_value_ratio -= 1;
+
+    // the owner no longer holds risk powers
+    let mut transaction = Transaction::new_with_payer(
+        &[modify_reserve_config(
+            spl_token_lending::id(),
+            new_config,
+            sol_test_reserve.pubkey,
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::InvalidMarketOwner as u32)
+        )
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[modify_reserve_config(
+            spl_token_lending::id(),
+            new_config,
+            sol_test_reserve.pubkey,
+            lending_market.pubkey,
+            risk_admin.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &risk_admin], recent_blockhash);
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+        .unwrap();
+
+    let sol_reserve = sol_test_reserve.get_state(&mut banks_client).await;
+    assert_eq!(sol_reserve.config, new_config);
+}
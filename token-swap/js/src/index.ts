@@ -120,6 +120,7 @@ export const CurveType = Object.freeze({
   ConstantProduct: 0, // Constant product curve, Uniswap-style
   ConstantPrice: 1, // Constant price curve, always X amount of A token for 1 B token, where X is defined at init
   Offset: 2, // Offset curve, like Uniswap, but with an additional offset on the token B side
+  LiquidityBootstrapping: 3, // Weighted curve, like Balancer, with token weights shifting linearly over time
 });
 
 /**
@@ -549,7 +550,7 @@ export class TokenSwap {
     );
 
     const keys = [
-      {pubkey: tokenSwap, isSigner: false, isWritable: false},
+      {pubkey: tokenSwap, isSigner: false, isWritable: true},
       {pubkey: authority, isSigner: false, isWritable: false},
       {pubkey: userTransferAuthority, isSigner: true, isWritable: false},
       {pubkey: userSource, isSigner: false, isWritable: true},
@@ -11,6 +11,7 @@ use {
             constant_price::ConstantPriceCurve,
             constant_product::ConstantProductCurve,
             fees::Fees,
+            liquidity_bootstrapping::LiquidityBootstrappingCurve,
             offset::OffsetCurve,
         },
         error::SwapError,
@@ -20,6 +21,7 @@ use {
         },
     },
     spl_token_swap_fuzz::{
+        invariants::{check_invariants, Operation, SwapSnapshot},
         native_account_data::NativeAccountData,
         native_token::{get_token_balance, transfer},
         native_token_swap::NativeTokenSwap,
@@ -194,13 +196,26 @@ fn run_fuzz(fuzz_data: FuzzData) {
         INITIAL_SWAP_TOKEN_B_AMOUNT + get_total_token_b_amount(&fuzz_data.instructions);
 
     for fuzz_instruction in fuzz_data.instructions {
+        let before = SwapSnapshot::new(&token_swap);
+        let operation = get_operation(&fuzz_instruction);
         run_fuzz_instruction(
-            fuzz_instruction,
+            fuzz_instruction.clone(),
             &mut token_swap,
             &mut token_a_accounts,
             &mut token_b_accounts,
             &mut pool_accounts,
         );
+        let after = SwapSnapshot::new(&token_swap);
+
+        // abort at the violating instruction, rather than only checking the
+        // totals at the end of the run
+        if let Err(violation) = check_invariants(&swap_curve, operation, &before, &after) {
+            println!("Invariant violated: {}", violation);
+            println!("Instruction: {:?}", fuzz_instruction);
+            println!("Before: {:?}", before);
+            println!("After: {:?}", after);
+            panic!("{}", violation);
+        }
     }
 
     // Omit fees intentionally, because fees in the form of pool tokens can
@@ -396,6 +411,20 @@ fn run_fuzz_instruction(
         .ok();
 }
 
+fn get_operation(fuzz_instruction: &FuzzInstruction) -> Operation {
+    match fuzz_instruction {
+        FuzzInstruction::Swap { .. } => Operation::Swap,
+        FuzzInstruction::DepositAllTokenTypes { .. } => Operation::DepositAllTokenTypes,
+        FuzzInstruction::WithdrawAllTokenTypes { .. } => Operation::WithdrawAllTokenTypes,
+        FuzzInstruction::DepositSingleTokenTypeExactAmountIn { .. } => {
+            Operation::DepositSingleTokenType
+        }
+        FuzzInstruction::WithdrawSingleTokenTypeExactAmountOut { .. } => {
+            Operation::WithdrawSingleTokenType
+        }
+    }
+}
+
 fn get_total_token_a_amount(fuzz_instructions: &[FuzzInstruction]) -> u64 {
     let mut token_a_ids = HashSet::new();
     for fuzz_instruction in fuzz_instructions.iter() {
@@ -471,6 +500,13 @@ fn get_swap_curve(curve_type: CurveType) -> SwapCurve {
             CurveType::Offset => Arc::new(OffsetCurve {
                 token_b_offset: 100_000_000_000,
             }),
+            CurveType::LiquidityBootstrapping => Arc::new(LiquidityBootstrappingCurve {
+                start_weight_a: 8_000,
+                end_weight_a: 2_000,
+                start_timestamp: 0,
+                end_timestamp: 1_000_000,
+                unix_timestamp: 0,
+            }),
         },
     }
 }
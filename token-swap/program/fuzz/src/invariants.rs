@@ -0,0 +1,137 @@
+//! Invariants checked after every operation of a fuzzing run
+
+use {
+    crate::{
+        native_token::{get_mint_supply, get_token_balance},
+        native_token_swap::NativeTokenSwap,
+    },
+    spl_math::precise_number::PreciseNumber,
+    spl_token_swap::curve::base::SwapCurve,
+    std::fmt,
+};
+
+/// Balances of the swap, taken before and after every operation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapSnapshot {
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub pool_token_supply: u64,
+    pub pool_fee_amount: u64,
+}
+
+impl SwapSnapshot {
+    pub fn new(token_swap: &NativeTokenSwap) -> Self {
+        Self {
+            token_a_amount: get_token_balance(&token_swap.token_a_account),
+            token_b_amount: get_token_balance(&token_swap.token_b_account),
+            pool_token_supply: get_mint_supply(&token_swap.pool_mint_account),
+            pool_fee_amount: get_token_balance(&token_swap.pool_fee_account),
+        }
+    }
+
+    /// Value of a pool token held by a liquidity provider. Fee tokens are
+    /// omitted, like in the final check of the fuzz target, since rounding up
+    /// small fees can dilute the value of all pool tokens.
+    fn pool_token_value(&self, swap_curve: &SwapCurve) -> Option<PreciseNumber> {
+        let lp_pool_token_supply = self.pool_token_supply - self.pool_fee_amount;
+        if lp_pool_token_supply == 0 {
+            return None;
+        }
+        swap_curve
+            .calculator
+            .normalized_value(self.token_a_amount as u128, self.token_b_amount as u128)?
+            .checked_div(&PreciseNumber::new(lp_pool_token_supply as u128)?)
+    }
+}
+
+/// Kind of the fuzzed operation, some invariants only apply to deposits and
+/// withdrawals of both tokens
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Swap,
+    DepositAllTokenTypes,
+    WithdrawAllTokenTypes,
+    DepositSingleTokenType,
+    WithdrawSingleTokenType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// The value of a liquidity provider pool token decreased
+    PoolTokenValueDecreased,
+    /// The fee account lost pool tokens
+    FeeAccountDecreased,
+    /// A deposit minted more pool tokens than its share of the reserves
+    DepositOverMinted,
+    /// A withdrawal paid out more than the share of the burned pool tokens
+    WithdrawalOverPaid,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::PoolTokenValueDecreased => write!(f, "pool token value decreased"),
+            Self::FeeAccountDecreased => write!(f, "fee account balance decreased"),
+            Self::DepositOverMinted => write!(f, "deposit minted more than its share"),
+            Self::WithdrawalOverPaid => write!(f, "withdrawal paid out more than its share"),
+        }
+    }
+}
+
+/// Checks the invariants between the snapshots taken around an operation
+pub fn check_invariants(
+    swap_curve: &SwapCurve,
+    operation: Operation,
+    before: &SwapSnapshot,
+    after: &SwapSnapshot,
+) -> Result<(), InvariantViolation> {
+    if after.pool_fee_amount < before.pool_fee_amount {
+        return Err(InvariantViolation::FeeAccountDecreased);
+    }
+
+    if let (Some(value_before), Some(value_after)) = (
+        before.pool_token_value(swap_curve),
+        after.pool_token_value(swap_curve),
+    ) {
+        if !value_before.less_than_or_equal(&value_after) {
+            return Err(InvariantViolation::PoolTokenValueDecreased);
+        }
+    }
+
+    let supply_before = before.pool_token_supply as u128;
+    let reserves = [
+        (before.token_a_amount, after.token_a_amount),
+        (before.token_b_amount, after.token_b_amount),
+    ];
+    match operation {
+        // minted / supply <= deposited / reserve, for both tokens
+        Operation::DepositAllTokenTypes => {
+            let minted = after
+                .pool_token_supply
+                .saturating_sub(before.pool_token_supply) as u128;
+            for (reserve_before, reserve_after) in reserves {
+                let deposited = reserve_after.saturating_sub(reserve_before) as u128;
+                if minted * reserve_before as u128 > deposited * supply_before {
+                    return Err(InvariantViolation::DepositOverMinted);
+                }
+            }
+        }
+        // withdrawn / reserve <= burned / supply, for both tokens
+        Operation::WithdrawAllTokenTypes => {
+            let burned = before
+                .pool_token_supply
+                .saturating_sub(after.pool_token_supply) as u128;
+            for (reserve_before, reserve_after) in reserves {
+                let withdrawn = reserve_before.saturating_sub(reserve_after) as u128;
+                if withdrawn * supply_before > burned * reserve_before as u128 {
+                    return Err(InvariantViolation::WithdrawalOverPaid);
+                }
+            }
+        }
+        Operation::Swap
+        | Operation::DepositSingleTokenType
+        | Operation::WithdrawSingleTokenType => {}
+    }
+
+    Ok(())
+}
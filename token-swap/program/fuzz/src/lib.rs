@@ -1,4 +1,5 @@
 #![allow(clippy::arithmetic_side_effects)]
+pub mod invariants;
 pub mod native_account_data;
 pub mod native_processor;
 pub mod native_token;
@@ -1,8 +1,8 @@
 use {
     crate::native_account_data::NativeAccountData,
     solana_program::{
-        account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
-        program_error::ProgramError, program_stubs, pubkey::Pubkey,
+        account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, entrypoint::SUCCESS,
+        instruction::Instruction, program_error::ProgramError, program_stubs, pubkey::Pubkey,
     },
 };
 
@@ -43,6 +43,13 @@ impl program_stubs::SyscallStubs for TestSyscallStubs {
             &instruction.data,
         )
     }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe {
+            *(var_addr as *mut _ as *mut Clock) = Clock::default();
+        }
+        SUCCESS
+    }
 }
 
 fn test_syscall_stubs() {
@@ -53,3 +53,8 @@ pub fn transfer(
     TokenAccount::pack(from, &mut from_account.data[..]).unwrap();
     TokenAccount::pack(to, &mut to_account.data[..]).unwrap();
 }
+
+pub fn get_mint_supply(account_data: &NativeAccountData) -> u64 {
+    let mint = Mint::unpack(&account_data.data).unwrap();
+    mint.supply
+}
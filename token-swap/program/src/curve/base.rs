@@ -8,10 +8,12 @@ use {
         constant_price::ConstantPriceCurve,
         constant_product::ConstantProductCurve,
         fees::Fees,
+        liquidity_bootstrapping::LiquidityBootstrappingCurve,
         offset::OffsetCurve,
     },
     arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
     solana_program::{
+        clock::UnixTimestamp,
         program_error::ProgramError,
         program_pack::{Pack, Sealed},
     },
@@ -34,6 +36,9 @@ pub enum CurveType {
     ConstantPrice,
     /// Offset curve, like Uniswap, but the token B side has a faked offset
     Offset,
+    /// Liquidity bootstrapping curve, like Balancer, with token weights
+    /// shifting linearly over a configured window
+    LiquidityBootstrapping,
 }
 
 /// Encodes all results of swapping from a source token to a destination token
@@ -166,6 +171,18 @@ impl SwapCurve {
             RoundDirection::Ceiling,
         )
     }
+
+    /// Get the curve with its time-varying parameters evaluated at the given
+    /// time.  Curves with constant parameters are returned unchanged.
+    pub fn at_unix_timestamp(&self, unix_timestamp: UnixTimestamp) -> Self {
+        Self {
+            curve_type: self.curve_type,
+            calculator: self
+                .calculator
+                .at_unix_timestamp(unix_timestamp)
+                .unwrap_or_else(|| self.calculator.clone()),
+        }
+    }
 }
 
 /// Default implementation for SwapCurve cannot be derived because of
@@ -229,6 +246,9 @@ impl Pack for SwapCurve {
                     Arc::new(ConstantPriceCurve::unpack_from_slice(calculator)?)
                 }
                 CurveType::Offset => Arc::new(OffsetCurve::unpack_from_slice(calculator)?),
+                CurveType::LiquidityBootstrapping => {
+                    Arc::new(LiquidityBootstrappingCurve::unpack_from_slice(calculator)?)
+                }
             },
         })
     }
@@ -258,6 +278,7 @@ impl TryFrom<u8> for CurveType {
             0 => Ok(CurveType::ConstantProduct),
             1 => Ok(CurveType::ConstantPrice),
             2 => Ok(CurveType::Offset),
+            3 => Ok(CurveType::LiquidityBootstrapping),
             _ => Err(ProgramError::InvalidAccountData),
         }
     }
@@ -2,7 +2,12 @@
 
 #[cfg(feature = "fuzz")]
 use arbitrary::Arbitrary;
-use {crate::error::SwapError, spl_math::precise_number::PreciseNumber, std::fmt::Debug};
+use {
+    crate::error::SwapError,
+    solana_program::clock::UnixTimestamp,
+    spl_math::precise_number::PreciseNumber,
+    std::{fmt::Debug, sync::Arc},
+};
 
 /// Initial amount of pool tokens for swap contract, hard-coded to something
 /// "sensible" given a maximum of u128.
@@ -173,6 +178,17 @@ pub trait CurveCalculator: Debug + DynPack {
         true
     }
 
+    /// Get a calculator with the time-varying parameters of the curve, such as
+    /// the weights of a liquidity bootstrapping curve, evaluated at the given
+    /// time.  Curves with constant parameters return `None` and can be used
+    /// as-is.
+    fn at_unix_timestamp(
+        &self,
+        _unix_timestamp: UnixTimestamp,
+    ) -> Option<Arc<dyn CurveCalculator + Sync + Send>> {
+        None
+    }
+
     /// Calculates the total normalized value of the curve given the liquidity
     /// parameters.
     ///
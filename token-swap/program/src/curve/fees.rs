@@ -163,6 +163,18 @@ impl Fees {
         )
     }
 
+    /// Fees charged to registered keepers on flash swaps: the trade fee
+    /// still accrues to liquidity providers, but no owner or host fee is taken
+    pub fn keeper_fees(&self) -> Fees {
+        Fees {
+            owner_trade_fee_numerator: 0,
+            owner_trade_fee_denominator: 0,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+            ..self.clone()
+        }
+    }
+
     /// Validate that the fees are reasonable
     pub fn validate(&self) -> Result<(), SwapError> {
         validate_fraction(self.trade_fee_numerator, self.trade_fee_denominator)?;
@@ -279,4 +291,25 @@ mod tests {
         let unpacked = Fees::unpack_from_slice(&packed).unwrap();
         assert_eq!(fees, unpacked);
     }
+
+    #[test]
+    fn keeper_fees() {
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 4,
+            owner_trade_fee_numerator: 2,
+            owner_trade_fee_denominator: 5,
+            owner_withdraw_fee_numerator: 4,
+            owner_withdraw_fee_denominator: 10,
+            host_fee_numerator: 7,
+            host_fee_denominator: 100,
+        };
+        let keeper_fees = fees.keeper_fees();
+        assert_eq!(keeper_fees.trading_fee(100), Some(25));
+        assert_eq!(keeper_fees.owner_trading_fee(100), Some(0));
+        assert_eq!(keeper_fees.host_fee(100), Some(0));
+        assert_eq!(keeper_fees.owner_withdraw_fee(100), Some(40));
+        assert_eq!(keeper_fees.pre_trading_fee_amount(75), Some(100));
+        keeper_fees.validate().unwrap();
+    }
 }
@@ -0,0 +1,602 @@
+//! The Balancer-style weighted invariant calculator with weights shifting over
+//! time, used for liquidity bootstrapping pools
+
+use {
+    crate::{
+        curve::{
+            calculator::{
+                map_zero_to_none, CurveCalculator, DynPack, RoundDirection, SwapWithoutFeesResult,
+                TradeDirection, TradingTokenResult,
+            },
+            constant_product::pool_tokens_to_trading_tokens,
+        },
+        error::SwapError,
+    },
+    arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
+    solana_program::{
+        clock::UnixTimestamp,
+        program_error::ProgramError,
+        program_pack::{IsInitialized, Pack, Sealed},
+    },
+    spl_math::precise_number::PreciseNumber,
+    std::{convert::TryFrom, sync::Arc},
+};
+
+/// Denominator of the token weights, which are expressed in basis points
+pub const WEIGHT_DENOMINATOR: u64 = 10_000;
+
+/// Minimum weight of either token at any point of the schedule, 1%
+pub const MIN_WEIGHT: u64 = 100;
+
+/// Maximum share of the pool balance of a token that can be swapped in,
+/// deposited, or withdrawn in a single operation, expressed as a divisor.
+/// Keeping the operation below half the balance keeps the base of the weighted
+/// power calculations within [0.5, 1.5], where the approximation is precise.
+pub const MAX_BALANCE_RATIO_DIVISOR: u128 = 2;
+
+/// Liquidity bootstrapping curve, a weighted constant product curve where the
+/// weight of token A shifts linearly from `start_weight_a` to `end_weight_a`
+/// between `start_timestamp` and `end_timestamp`, and token B is weighted with
+/// the remainder.
+///
+/// Starting with a high weight on the token being sold and ending with a low
+/// one makes the price decline over the sale unless buyers push it up, which
+/// discourages front-running the launch.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LiquidityBootstrappingCurve {
+    /// Weight of token A at the start of the schedule, in basis points
+    pub start_weight_a: u64,
+    /// Weight of token A at the end of the schedule, in basis points
+    pub end_weight_a: u64,
+    /// Time at which the weights start shifting
+    pub start_timestamp: UnixTimestamp,
+    /// Time at which the weights reach their final value
+    pub end_timestamp: UnixTimestamp,
+    /// Time at which the weights are evaluated.  This is not stored in the
+    /// pool, it is provided by the program from the clock through
+    /// `CurveCalculator::at_unix_timestamp` before any calculation.
+    pub unix_timestamp: UnixTimestamp,
+}
+
+impl LiquidityBootstrappingCurve {
+    /// Weight of token A at the time of the curve, in basis points
+    pub fn weight_a(&self) -> Option<u64> {
+        if self.unix_timestamp <= self.start_timestamp {
+            return Some(self.start_weight_a);
+        }
+        if self.unix_timestamp >= self.end_timestamp {
+            return Some(self.end_weight_a);
+        }
+        let elapsed =
+            u128::try_from(self.unix_timestamp.checked_sub(self.start_timestamp)?).ok()?;
+        let duration =
+            u128::try_from(self.end_timestamp.checked_sub(self.start_timestamp)?).ok()?;
+        let start_weight_a = u128::from(self.start_weight_a);
+        let end_weight_a = u128::from(self.end_weight_a);
+        let weight_a = if end_weight_a >= start_weight_a {
+            start_weight_a.checked_add(
+                end_weight_a
+                    .checked_sub(start_weight_a)?
+                    .checked_mul(elapsed)?
+                    .checked_div(duration)?,
+            )?
+        } else {
+            start_weight_a.checked_sub(
+                start_weight_a
+                    .checked_sub(end_weight_a)?
+                    .checked_mul(elapsed)?
+                    .checked_div(duration)?,
+            )?
+        };
+        u64::try_from(weight_a).ok()
+    }
+
+    /// Weights of the source and destination tokens of a trade at the time of
+    /// the curve, in basis points
+    fn weights(&self, trade_direction: TradeDirection) -> Option<(u64, u64)> {
+        let weight_a = self.weight_a()?;
+        let weight_b = WEIGHT_DENOMINATOR.checked_sub(weight_a)?;
+        match trade_direction {
+            TradeDirection::AtoB => Some((weight_a, weight_b)),
+            TradeDirection::BtoA => Some((weight_b, weight_a)),
+        }
+    }
+}
+
+/// The weighted swap calculation, using the Balancer formula found at
+/// <https://balancer.finance/whitepaper/#out-given-in>:
+///
+/// destination_amount_swapped = swap_destination_amount *
+///     (1 - (swap_source_amount / (swap_source_amount + source_amount)) ^
+///     (source_weight / destination_weight))
+///
+/// The source amount may be at most half of the source token balance.
+pub fn swap(
+    source_amount: u128,
+    swap_source_amount: u128,
+    swap_destination_amount: u128,
+    source_weight: u64,
+    destination_weight: u64,
+) -> Option<SwapWithoutFeesResult> {
+    if source_amount > swap_source_amount.checked_div(MAX_BALANCE_RATIO_DIVISOR)? {
+        return None;
+    }
+    let new_swap_source_amount =
+        PreciseNumber::new(swap_source_amount.checked_add(source_amount)?)?;
+    let base = PreciseNumber::new(swap_source_amount)?.checked_div(&new_swap_source_amount)?;
+    let exponent = PreciseNumber::new(u128::from(source_weight))?
+        .checked_div(&PreciseNumber::new(u128::from(destination_weight))?)?;
+    let ratio = PreciseNumber::new(1)?.checked_sub(&base.checked_pow_fraction(&exponent)?)?;
+    let destination_amount_swapped = PreciseNumber::new(swap_destination_amount)?
+        .checked_mul(&ratio)?
+        .floor()?
+        .to_imprecise()?;
+
+    Some(SwapWithoutFeesResult {
+        source_amount_swapped: source_amount,
+        destination_amount_swapped: map_zero_to_none(destination_amount_swapped)?,
+    })
+}
+
+/// Get the amount of pool tokens for the deposited amount of token A or B.
+///
+/// Uses the Balancer formula found at
+/// <https://balancer.finance/whitepaper/#single-asset-deposit>:
+///
+/// pool_tokens = pool_supply * ((1 + source_amount / swap_source_amount) ^
+///     source_weight - 1)
+pub fn deposit_single_token_type(
+    source_amount: u128,
+    swap_source_amount: u128,
+    pool_supply: u128,
+    source_weight: u64,
+    round_direction: RoundDirection,
+) -> Option<u128> {
+    if source_amount > swap_source_amount.checked_div(MAX_BALANCE_RATIO_DIVISOR)? {
+        return None;
+    }
+    let one = PreciseNumber::new(1)?;
+    let ratio =
+        PreciseNumber::new(source_amount)?.checked_div(&PreciseNumber::new(swap_source_amount)?)?;
+    let base = one.checked_add(&ratio)?;
+    let exponent = normalized_weight(source_weight)?;
+    let root = base.checked_pow_fraction(&exponent)?.checked_sub(&one)?;
+    let pool_tokens = PreciseNumber::new(pool_supply)?.checked_mul(&root)?;
+    match round_direction {
+        RoundDirection::Floor => pool_tokens.floor()?.to_imprecise(),
+        RoundDirection::Ceiling => pool_tokens.ceiling()?.to_imprecise(),
+    }
+}
+
+/// Get the amount of pool tokens for the withdrawn amount of token A or B.
+///
+/// Uses the Balancer formula found at
+/// <https://balancer.finance/whitepaper/#single-asset-withdrawal>:
+///
+/// pool_tokens = pool_supply * (1 - (1 - source_amount / swap_source_amount) ^
+///     source_weight)
+pub fn withdraw_single_token_type_exact_out(
+    source_amount: u128,
+    swap_source_amount: u128,
+    pool_supply: u128,
+    source_weight: u64,
+    round_direction: RoundDirection,
+) -> Option<u128> {
+    if source_amount > swap_source_amount.checked_div(MAX_BALANCE_RATIO_DIVISOR)? {
+        return None;
+    }
+    let one = PreciseNumber::new(1)?;
+    let ratio =
+        PreciseNumber::new(source_amount)?.checked_div(&PreciseNumber::new(swap_source_amount)?)?;
+    let base = one.checked_sub(&ratio)?;
+    let exponent = normalized_weight(source_weight)?;
+    let root = one.checked_sub(&base.checked_pow_fraction(&exponent)?)?;
+    let pool_tokens = PreciseNumber::new(pool_supply)?.checked_mul(&root)?;
+    match round_direction {
+        RoundDirection::Floor => pool_tokens.floor()?.to_imprecise(),
+        RoundDirection::Ceiling => pool_tokens.ceiling()?.to_imprecise(),
+    }
+}
+
+/// Calculates the total normalized value of the curve given the liquidity
+/// parameters.
+///
+/// The weighted implementation for this function gives the weighted geometric
+/// mean of the balances, `swap_token_a_amount ^ weight_a *
+/// swap_token_b_amount ^ weight_b`, which has the dimension of `tokens ^ 1`
+/// since the weights sum up to 1.
+pub fn normalized_value(
+    swap_token_a_amount: u128,
+    swap_token_b_amount: u128,
+    weight_a: u64,
+) -> Option<PreciseNumber> {
+    if swap_token_a_amount == 0 || swap_token_b_amount == 0 {
+        return PreciseNumber::new(0);
+    }
+    // a ^ w_a * b ^ w_b = larger * (smaller / larger) ^ w_smaller, which keeps
+    // the base of the power below 1
+    let weight_b = WEIGHT_DENOMINATOR.checked_sub(weight_a)?;
+    let (smaller, larger, smaller_weight) = if swap_token_a_amount <= swap_token_b_amount {
+        (swap_token_a_amount, swap_token_b_amount, weight_a)
+    } else {
+        (swap_token_b_amount, swap_token_a_amount, weight_b)
+    };
+    let larger = PreciseNumber::new(larger)?;
+    let mut base = PreciseNumber::new(smaller)?.checked_div(&larger)?;
+    if base == PreciseNumber::new(0)? {
+        return None;
+    }
+    let mut exponent = normalized_weight(smaller_weight)?;
+    // the approximation converges slowly for bases close to 0, so take square
+    // roots of the base until it gets close enough to 1, doubling the exponent
+    // every time: x ^ n = sqrt(x) ^ (2 * n)
+    let half = PreciseNumber::new(1)?.checked_div(&PreciseNumber::new(2)?)?;
+    let two = PreciseNumber::new(2)?;
+    while base.less_than(&half) {
+        base = base.sqrt()?;
+        exponent = exponent.checked_mul(&two)?;
+    }
+    larger.checked_mul(&base.checked_pow_fraction(&exponent)?)
+}
+
+/// Weight in basis points as a precise fraction of 1
+fn normalized_weight(weight: u64) -> Option<PreciseNumber> {
+    PreciseNumber::new(u128::from(weight))?
+        .checked_div(&PreciseNumber::new(u128::from(WEIGHT_DENOMINATOR))?)
+}
+
+impl CurveCalculator for LiquidityBootstrappingCurve {
+    /// Weighted swap using the weights at the time of the curve
+    fn swap_without_fees(
+        &self,
+        source_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<SwapWithoutFeesResult> {
+        let (source_weight, destination_weight) = self.weights(trade_direction)?;
+        swap(
+            source_amount,
+            swap_source_amount,
+            swap_destination_amount,
+            source_weight,
+            destination_weight,
+        )
+    }
+
+    /// Pool tokens always represent a proportional share of both balances,
+    /// regardless of the weights
+    fn pool_tokens_to_trading_tokens(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        swap_token_a_amount: u128,
+        swap_token_b_amount: u128,
+        round_direction: RoundDirection,
+    ) -> Option<TradingTokenResult> {
+        pool_tokens_to_trading_tokens(
+            pool_tokens,
+            pool_token_supply,
+            swap_token_a_amount,
+            swap_token_b_amount,
+            round_direction,
+        )
+    }
+
+    fn deposit_single_token_type(
+        &self,
+        source_amount: u128,
+        swap_token_a_amount: u128,
+        swap_token_b_amount: u128,
+        pool_supply: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<u128> {
+        let (source_weight, _) = self.weights(trade_direction)?;
+        let swap_source_amount = match trade_direction {
+            TradeDirection::AtoB => swap_token_a_amount,
+            TradeDirection::BtoA => swap_token_b_amount,
+        };
+        deposit_single_token_type(
+            source_amount,
+            swap_source_amount,
+            pool_supply,
+            source_weight,
+            RoundDirection::Floor,
+        )
+    }
+
+    fn withdraw_single_token_type_exact_out(
+        &self,
+        source_amount: u128,
+        swap_token_a_amount: u128,
+        swap_token_b_amount: u128,
+        pool_supply: u128,
+        trade_direction: TradeDirection,
+        round_direction: RoundDirection,
+    ) -> Option<u128> {
+        let (source_weight, _) = self.weights(trade_direction)?;
+        let swap_source_amount = match trade_direction {
+            TradeDirection::AtoB => swap_token_a_amount,
+            TradeDirection::BtoA => swap_token_b_amount,
+        };
+        withdraw_single_token_type_exact_out(
+            source_amount,
+            swap_source_amount,
+            pool_supply,
+            source_weight,
+            round_direction,
+        )
+    }
+
+    fn validate(&self) -> Result<(), SwapError> {
+        let valid_weights = MIN_WEIGHT..=WEIGHT_DENOMINATOR - MIN_WEIGHT;
+        if !valid_weights.contains(&self.start_weight_a)
+            || !valid_weights.contains(&self.end_weight_a)
+            || self.start_timestamp >= self.end_timestamp
+        {
+            Err(SwapError::InvalidCurve)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn at_unix_timestamp(
+        &self,
+        unix_timestamp: UnixTimestamp,
+    ) -> Option<Arc<dyn CurveCalculator + Sync + Send>> {
+        Some(Arc::new(Self {
+            unix_timestamp,
+            ..self.clone()
+        }))
+    }
+
+    fn normalized_value(
+        &self,
+        swap_token_a_amount: u128,
+        swap_token_b_amount: u128,
+    ) -> Option<PreciseNumber> {
+        normalized_value(swap_token_a_amount, swap_token_b_amount, self.weight_a()?)
+    }
+}
+
+/// IsInitialized is required to use `Pack::pack` and `Pack::unpack`
+impl IsInitialized for LiquidityBootstrappingCurve {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+}
+impl Sealed for LiquidityBootstrappingCurve {}
+impl Pack for LiquidityBootstrappingCurve {
+    const LEN: usize = 32;
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        (self as &dyn DynPack).pack_into_slice(output);
+    }
+
+    fn unpack_from_slice(input: &[u8]) -> Result<LiquidityBootstrappingCurve, ProgramError> {
+        let input = array_ref![input, 0, 32];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (start_weight_a, end_weight_a, start_timestamp, end_timestamp) =
+            array_refs![input, 8, 8, 8, 8];
+        Ok(Self {
+            start_weight_a: u64::from_le_bytes(*start_weight_a),
+            end_weight_a: u64::from_le_bytes(*end_weight_a),
+            start_timestamp: i64::from_le_bytes(*start_timestamp),
+            end_timestamp: i64::from_le_bytes(*end_timestamp),
+            unix_timestamp: 0,
+        })
+    }
+}
+
+impl DynPack for LiquidityBootstrappingCurve {
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 32];
+        let (start_weight_a, end_weight_a, start_timestamp, end_timestamp) =
+            mut_array_refs![output, 8, 8, 8, 8];
+        *start_weight_a = self.start_weight_a.to_le_bytes();
+        *end_weight_a = self.end_weight_a.to_le_bytes();
+        *start_timestamp = self.start_timestamp.to_le_bytes();
+        *end_timestamp = self.end_timestamp.to_le_bytes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::curve::constant_product};
+
+    fn test_curve() -> LiquidityBootstrappingCurve {
+        LiquidityBootstrappingCurve {
+            start_weight_a: 9_000,
+            end_weight_a: 5_000,
+            start_timestamp: 1_000,
+            end_timestamp: 2_000,
+            unix_timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn pack_curve() {
+        let curve = test_curve();
+
+        let mut packed = [0u8; LiquidityBootstrappingCurve::LEN];
+        Pack::pack_into_slice(&curve, &mut packed[..]);
+        let unpacked = LiquidityBootstrappingCurve::unpack(&packed).unwrap();
+        assert_eq!(curve, unpacked);
+
+        let mut packed = vec![];
+        packed.extend_from_slice(&curve.start_weight_a.to_le_bytes());
+        packed.extend_from_slice(&curve.end_weight_a.to_le_bytes());
+        packed.extend_from_slice(&curve.start_timestamp.to_le_bytes());
+        packed.extend_from_slice(&curve.end_timestamp.to_le_bytes());
+        let unpacked = LiquidityBootstrappingCurve::unpack(&packed).unwrap();
+        assert_eq!(curve, unpacked);
+    }
+
+    #[test]
+    fn weights_shift_over_time() {
+        let curve = test_curve();
+        for (unix_timestamp, expected_weight_a) in [
+            (0, 9_000),
+            (1_000, 9_000),
+            (1_250, 8_000),
+            (1_500, 7_000),
+            (2_000, 5_000),
+            (i64::MAX, 5_000),
+        ] {
+            let curve = LiquidityBootstrappingCurve {
+                unix_timestamp,
+                ..curve.clone()
+            };
+            assert_eq!(curve.weight_a(), Some(expected_weight_a));
+            assert_eq!(
+                curve.weights(TradeDirection::BtoA),
+                Some((WEIGHT_DENOMINATOR - expected_weight_a, expected_weight_a))
+            );
+        }
+
+        // increasing schedules are supported too
+        let curve = LiquidityBootstrappingCurve {
+            start_weight_a: 2_000,
+            end_weight_a: 6_000,
+            unix_timestamp: 1_750,
+            ..curve
+        };
+        assert_eq!(curve.weight_a(), Some(5_000));
+    }
+
+    #[test]
+    fn swap_equal_weights_matches_constant_product() {
+        let swap_source_amount: u128 = 1_000_000;
+        let swap_destination_amount: u128 = 50_000_000;
+        let source_amount: u128 = 100_000;
+        let curve = LiquidityBootstrappingCurve {
+            unix_timestamp: 2_000,
+            ..test_curve()
+        };
+        let result = curve
+            .swap_without_fees(
+                source_amount,
+                swap_source_amount,
+                swap_destination_amount,
+                TradeDirection::AtoB,
+            )
+            .unwrap();
+        let expected =
+            constant_product::swap(source_amount, swap_source_amount, swap_destination_amount)
+                .unwrap();
+        assert_eq!(result.source_amount_swapped, source_amount);
+        assert!(
+            result
+                .destination_amount_swapped
+                .abs_diff(expected.destination_amount_swapped)
+                <= 1
+        );
+    }
+
+    #[test]
+    fn swap_price_declines_over_time() {
+        // token A is sold with a weight shifting from 90% to 50%, so the same
+        // amount of token B buys more token A as time passes
+        let swap_token_a_amount: u128 = 9_000_000;
+        let swap_token_b_amount: u128 = 1_000_000;
+        let source_amount: u128 = 10_000;
+        let mut last_amount_out = 0;
+        for unix_timestamp in [1_000, 1_500, 2_000] {
+            let curve = LiquidityBootstrappingCurve {
+                unix_timestamp,
+                ..test_curve()
+            };
+            let result = curve
+                .swap_without_fees(
+                    source_amount,
+                    swap_token_b_amount,
+                    swap_token_a_amount,
+                    TradeDirection::BtoA,
+                )
+                .unwrap();
+            assert!(result.destination_amount_swapped > last_amount_out);
+            last_amount_out = result.destination_amount_swapped;
+        }
+    }
+
+    #[test]
+    fn swap_too_large_fails() {
+        let curve = test_curve();
+        let swap_source_amount: u128 = 1_000_000;
+        let swap_destination_amount: u128 = 1_000_000;
+        assert!(curve
+            .swap_without_fees(
+                swap_source_amount / 2,
+                swap_source_amount,
+                swap_destination_amount,
+                TradeDirection::AtoB,
+            )
+            .is_some());
+        assert!(curve
+            .swap_without_fees(
+                swap_source_amount / 2 + 1,
+                swap_source_amount,
+                swap_destination_amount,
+                TradeDirection::AtoB,
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn validate_curve() {
+        assert_eq!(test_curve().validate(), Ok(()));
+        for curve in [
+            LiquidityBootstrappingCurve {
+                start_weight_a: MIN_WEIGHT - 1,
+                ..test_curve()
+            },
+            LiquidityBootstrappingCurve {
+                end_weight_a: WEIGHT_DENOMINATOR - MIN_WEIGHT + 1,
+                ..test_curve()
+            },
+            LiquidityBootstrappingCurve {
+                end_timestamp: 1_000,
+                ..test_curve()
+            },
+        ] {
+            assert_eq!(curve.validate(), Err(SwapError::InvalidCurve));
+        }
+    }
+
+    #[test]
+    fn single_token_deposit_and_withdraw() {
+        let curve = LiquidityBootstrappingCurve {
+            unix_timestamp: 1_500,
+            ..test_curve()
+        };
+        let swap_token_a_amount: u128 = 7_000_000;
+        let swap_token_b_amount: u128 = 3_000_000;
+        let pool_supply: u128 = 1_000_000_000;
+        let source_amount: u128 = 70_000;
+
+        // depositing 1% of the token A balance at a 70% weight mints about
+        // 0.7% of the pool supply
+        let deposit_pool_tokens = curve
+            .deposit_single_token_type(
+                source_amount,
+                swap_token_a_amount,
+                swap_token_b_amount,
+                pool_supply,
+                TradeDirection::AtoB,
+            )
+            .unwrap();
+        assert!(deposit_pool_tokens > 6_900_000 && deposit_pool_tokens < 7_000_000);
+
+        // withdrawing the same amount costs slightly more pool tokens
+        let withdraw_pool_tokens = curve
+            .withdraw_single_token_type_exact_out(
+                source_amount,
+                swap_token_a_amount,
+                swap_token_b_amount,
+                pool_supply,
+                TradeDirection::AtoB,
+                RoundDirection::Ceiling,
+            )
+            .unwrap();
+        assert!(withdraw_pool_tokens > 7_000_000 && withdraw_pool_tokens < 7_100_000);
+    }
+}
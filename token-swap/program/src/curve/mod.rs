@@ -5,4 +5,5 @@ pub mod calculator;
 pub mod constant_price;
 pub mod constant_product;
 pub mod fees;
+pub mod liquidity_bootstrapping;
 pub mod offset;
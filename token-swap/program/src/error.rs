@@ -117,6 +117,51 @@ pub enum SwapError {
     /// The pool fee account is invalid.
     #[error("The pool fee account is invalid")]
     InvalidFeeAccount,
+    /// The provided position mint is invalid
+    #[error("The provided position mint is invalid")]
+    InvalidPositionMint,
+    /// The provided position account does not match the swap or position mint
+    #[error("The provided position account does not match the swap or position mint")]
+    IncorrectPosition,
+    /// The keeper is not registered with the swap
+    #[error("The keeper is not registered with the swap")]
+    KeeperNotRegistered,
+    /// The flash swap is not followed by a matching repayment
+    #[error("The flash swap is not followed by a matching repayment")]
+    InvalidFlashSwap,
+    /// The swap account has no room for a protocol fee
+    #[error("The swap account has no room for a protocol fee")]
+    ProtocolFeeUnavailable,
+    /// The protocol fee share or timelock is invalid
+    #[error("The protocol fee share or timelock is invalid")]
+    InvalidProtocolFee,
+    /// The provided account is not the protocol fee recipient
+    #[error("The provided account is not the protocol fee recipient")]
+    IncorrectProtocolFeeRecipient,
+    /// No protocol fee change is pending
+    #[error("No protocol fee change is pending")]
+    NoPendingProtocolFee,
+    /// The protocol fee change is still timelocked
+    #[error("The protocol fee change is still timelocked")]
+    ProtocolFeeTimelocked,
+    /// The withdraw fee schedule tiers are invalid
+    #[error("The withdraw fee schedule tiers are invalid")]
+    InvalidWithdrawFeeSchedule,
+    /// The provided withdraw fee schedule does not match the swap
+    #[error("The provided withdraw fee schedule does not match the swap")]
+    IncorrectWithdrawFeeSchedule,
+    /// The provided LP tenure account does not match the swap or pool account
+    #[error("The provided LP tenure account does not match the swap or pool account")]
+    IncorrectLpTenure,
+    /// The user transfer authority is the swap authority
+    #[error("The user transfer authority cannot be the swap authority")]
+    InvalidUserTransferAuthority,
+    /// The provided position vault is not the one derived for the swap
+    #[error("The provided position vault is not the one derived for the swap")]
+    IncorrectPositionVault,
+    /// The provided position group is not the one derived for the swap
+    #[error("The provided position group is not the one derived for the swap")]
+    IncorrectPositionGroup,
 }
 impl From<SwapError> for ProgramError {
     fn from(e: SwapError) -> Self {
@@ -200,6 +245,55 @@ impl PrintProgramError for SwapError {
             SwapError::InvalidFeeAccount => {
                 msg!("Error: The pool fee account is invalid")
             }
+            SwapError::InvalidPositionMint => {
+                msg!("Error: The provided position mint is invalid")
+            }
+            SwapError::IncorrectPosition => {
+                msg!(
+                    "Error: The provided position account does not match the swap or position mint"
+                )
+            }
+            SwapError::KeeperNotRegistered => {
+                msg!("Error: The keeper is not registered with the swap")
+            }
+            SwapError::InvalidFlashSwap => {
+                msg!("Error: The flash swap is not followed by a matching repayment")
+            }
+            SwapError::ProtocolFeeUnavailable => {
+                msg!("Error: The swap account has no room for a protocol fee")
+            }
+            SwapError::InvalidProtocolFee => {
+                msg!("Error: The protocol fee share or timelock is invalid")
+            }
+            SwapError::IncorrectProtocolFeeRecipient => {
+                msg!("Error: The provided account is not the protocol fee recipient")
+            }
+            SwapError::NoPendingProtocolFee => {
+                msg!("Error: No protocol fee change is pending")
+            }
+            SwapError::ProtocolFeeTimelocked => {
+                msg!("Error: The protocol fee change is still timelocked")
+            }
+            SwapError::InvalidWithdrawFeeSchedule => {
+                msg!("Error: The withdraw fee schedule tiers are invalid")
+            }
+            SwapError::IncorrectWithdrawFeeSchedule => {
+                msg!("Error: The provided withdraw fee schedule does not match the swap")
+            }
+            SwapError::IncorrectLpTenure => {
+                msg!(
+                    "Error: The provided LP tenure account does not match the swap or pool account"
+                )
+            }
+            SwapError::InvalidUserTransferAuthority => {
+                msg!("Error: The user transfer authority cannot be the swap authority")
+            }
+            SwapError::IncorrectPositionVault => {
+                msg!("Error: The provided position vault is not the one derived for the swap")
+            }
+            SwapError::IncorrectPositionGroup => {
+                msg!("Error: The provided position group is not the one derived for the swap")
+            }
         }
     }
 }
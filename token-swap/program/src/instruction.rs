@@ -8,12 +8,15 @@ use {
     crate::{
         curve::{base::SwapCurve, fees::Fees},
         error::SwapError,
+        processor::Processor,
+        state::{WithdrawFeeTier, MAX_WITHDRAW_FEE_TIERS},
     },
     solana_program::{
         instruction::{AccountMeta, Instruction},
         program_error::ProgramError,
         program_pack::Pack,
         pubkey::Pubkey,
+        system_program, sysvar,
     },
     std::{convert::TryInto, mem::size_of},
 };
@@ -94,6 +97,76 @@ pub struct WithdrawSingleTokenTypeExactAmountOut {
     pub maximum_pool_token_amount: u64,
 }
 
+/// IssuePosition instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct IssuePosition {
+    /// Amount of pool tokens to lock into the position
+    pub pool_token_amount: u64,
+}
+
+/// FlashSwap instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlashSwap {
+    /// SOURCE amount the keeper repays later in the transaction
+    pub amount_in: u64,
+    /// DESTINATION amount to borrow, must not exceed the output of the curve
+    /// for `amount_in` at keeper fees
+    pub amount_out: u64,
+}
+
+/// FlashSwapRepay instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlashSwapRepay {
+    /// SOURCE amount to transfer into the swap, must match the preceding
+    /// flash swap
+    pub amount_in: u64,
+}
+
+/// InitializeProtocolFee instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitializeProtocolFee {
+    /// Number of slots a proposed protocol fee change must wait before it can
+    /// be executed, must be non zero
+    pub timelock_slots: u64,
+}
+
+/// ProposeProtocolFee instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProposeProtocolFee {
+    /// Share of the owner trade fee taken by the protocol, in basis points
+    pub fee_bps: u16,
+}
+
+/// SetWithdrawFeeSchedule instruction data
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetWithdrawFeeSchedule {
+    /// Fee tiers sorted by strictly increasing tenure and decreasing fee, at
+    /// most `MAX_WITHDRAW_FEE_TIERS`
+    pub tiers: Vec<WithdrawFeeTier>,
+}
+
+/// ZapIn instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZapIn {
+    /// SOURCE amount to split between the swap and the deposit
+    pub source_token_amount: u64,
+    /// Minimum amount of pool tokens to receive, prevents excessive slippage
+    pub minimum_pool_token_amount: u64,
+}
+
 /// Instructions supported by the token swap program.
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -116,7 +189,7 @@ pub enum SwapInstruction {
 
     ///   Swap the tokens in the pool.
     ///
-    ///   0. `[]` Token-swap
+    ///   0. `[writable]` Token-swap, only written to if it has a protocol fee
     ///   1. `[]` swap authority
     ///   2. `[]` user transfer authority
     ///   3. `[writable]` token_(A|B) SOURCE Account, amount is transferable by
@@ -157,6 +230,9 @@ pub enum SwapInstruction {
     ///   11. `[]` Token A program id
     ///   12. `[]` Token B program id
     ///   13. `[]` Pool Token program id
+    ///   14. `[optional, writable]` LP tenure account tracking the deposit
+    ///       into the pool account. Must be owned by the swap program, and
+    ///       uninitialized or tracking the same pool account.
     DepositAllTokenTypes(DepositAllTokenTypes),
 
     ///   Withdraw both types of tokens from the pool at the current ratio,
@@ -179,6 +255,10 @@ pub enum SwapInstruction {
     ///   12. `[]` Pool Token program id
     ///   13. `[]` Token A program id
     ///   14. `[]` Token B program id
+    ///   15. `[optional]` Withdraw fee schedule of the swap
+    ///   16. `[optional, writable]` LP tenure account of the SOURCE Pool
+    ///       account, scaling down the withdraw fee with the schedule.
+    ///       Required if the withdraw fee schedule is provided.
     WithdrawAllTokenTypes(WithdrawAllTokenTypes),
 
     ///   Deposit one type of tokens into the pool. The output is a "pool"
@@ -198,6 +278,9 @@ pub enum SwapInstruction {
     ///   8. `[]` Token (A|B) SOURCE mint
     ///   9. `[]` Token (A|B) SOURCE program id
     ///   10. `[]` Pool Token program id
+    ///   11. `[optional, writable]` LP tenure account tracking the deposit
+    ///       into the pool account. Must be owned by the swap program, and
+    ///       uninitialized or tracking the same pool account.
     DepositSingleTokenTypeExactAmountIn(DepositSingleTokenTypeExactAmountIn),
 
     ///   Withdraw one token type from the pool at the current ratio given the
@@ -216,7 +299,194 @@ pub enum SwapInstruction {
     ///   9. `[]` Token (A|B) DESTINATION mint
     ///   10. `[]` Pool Token program id
     ///   11. `[]` Token (A|B) DESTINATION program id
+    ///   12. `[optional]` Withdraw fee schedule of the swap
+    ///   13. `[optional, writable]` LP tenure account of the SOURCE Pool
+    ///       account, scaling down the withdraw fee with the schedule.
+    ///       Required if the withdraw fee schedule is provided.
     WithdrawSingleTokenTypeExactAmountOut(WithdrawSingleTokenTypeExactAmountOut),
+
+    ///   Lock pool tokens into an LP position represented by a Token-2022
+    ///   NFT. The position records the pool token amount along with the
+    ///   amounts of token A and B it was worth at issuance.
+    ///
+    ///   0. `[]` Token-swap
+    ///   1. `[]` swap authority
+    ///   2. `[signer]` user transfer authority
+    ///   3. `[]` Pool mint account
+    ///   4. `[writable]` SOURCE Pool account, amount is transferable by user
+    ///      transfer authority.
+    ///   5. `[writable]` Position vault to lock the pool tokens in, the PDA
+    ///      derived from the swap with `Processor::position_vault_id`. Created
+    ///      on first use.
+    ///   6. `[writable]` Position group, the Token-2022 group mint of the
+    ///      swap's positions, the PDA derived from the swap with
+    ///      `Processor::position_group_id`. Created on first use.
+    ///   7. `[]` token_a Swap Account
+    ///   8. `[]` token_b Swap Account
+    ///   9. `[writable]` Position account to initialize. Must be uninitialized
+    ///      and owned by the swap program.
+    ///   10. `[writable]` Position mint. Must be an empty Token-2022 mint with
+    ///       zero decimals, swap authority as the mint authority, no freeze
+    ///       authority, and metadata and group member pointers to itself. The
+    ///       Token-2022 metadata of the position and its membership of the
+    ///       position group are initialized in the mint.
+    ///   11. `[writable]` Position token account to receive the NFT
+    ///   12. `[writable, signer]` Payer funding the position vault, the
+    ///       position group and the position metadata
+    ///   13. `[]` System program
+    ///   14. `[]` Pool Token program id
+    ///   15. `[]` Position Token program id
+    IssuePosition(IssuePosition),
+
+    ///   Redeem an LP position, burning its NFT and releasing the locked pool
+    ///   tokens.
+    ///
+    ///   0. `[]` Token-swap
+    ///   1. `[]` swap authority
+    ///   2. `[signer]` Position token account owner or delegate
+    ///   3. `[writable]` Position account
+    ///   4. `[writable]` Position mint
+    ///   5. `[writable]` Position token account holding the NFT
+    ///   6. `[writable]` Position vault of the swap
+    ///   7. `[writable]` Pool account to receive the pool tokens
+    ///   8. `[]` Pool mint account
+    ///   9. `[]` Pool Token program id
+    ///   10. `[]` Position Token program id
+    RedeemPosition,
+
+    ///   Register a keeper allowed to flash swap against the pool, signed by
+    ///   the owner of the pool fee account.
+    ///
+    ///   0. `[]` Token-swap
+    ///   1. `[]` Pool fee account
+    ///   2. `[signer]` Pool fee account owner
+    ///   3. `[writable]` Keeper registration account to initialize. Must be
+    ///      uninitialized and owned by the swap program.
+    ///   4. `[]` Keeper
+    RegisterKeeper,
+
+    ///   Deregister a keeper, signed by the owner of the pool fee account.
+    ///
+    ///   0. `[]` Token-swap
+    ///   1. `[]` Pool fee account
+    ///   2. `[signer]` Pool fee account owner
+    ///   3. `[writable]` Keeper registration account
+    DeregisterKeeper,
+
+    ///   Borrow tokens from one side of the pool as a registered keeper. The
+    ///   next token-swap instruction in the transaction must be a
+    ///   `FlashSwapRepay` for the same swap, reserve and `amount_in`. The
+    ///   output is priced on the reserves at the time of the borrow, charging
+    ///   only the trade fee. Keepers typically use this to move the pool
+    ///   toward an external price; the price itself is not checked here.
+    ///
+    ///   0. `[]` Token-swap
+    ///   1. `[]` swap authority
+    ///   2. `[signer]` Keeper
+    ///   3. `[]` Keeper registration account
+    ///   4. `[]` token_(A|B) Base Account to repay INTO. Must be the SOURCE
+    ///      token.
+    ///   5. `[writable]` token_(A|B) Base Account to borrow FROM. Must be the
+    ///      DESTINATION token.
+    ///   6. `[writable]` token_(A|B) DESTINATION Account assigned to KEEPER
+    ///   7. `[]` Token (A|B) SOURCE mint
+    ///   8. `[]` Token (A|B) DESTINATION mint
+    ///   9. `[]` Token (A|B) DESTINATION program id
+    ///   10. `[]` Instructions sysvar
+    FlashSwap(FlashSwap),
+
+    ///   Repay a flash swap borrowed earlier in the transaction.
+    ///
+    ///   0. `[]` Token-swap
+    ///   1. `[]` swap authority
+    ///   2. `[signer]` user transfer authority
+    ///   3. `[writable]` token_(A|B) SOURCE Account, amount is transferable by
+    ///      user transfer authority,
+    ///   4. `[writable]` token_(A|B) Base Account to repay INTO.
+    ///   5. `[]` Token (A|B) SOURCE mint
+    ///   6. `[]` Token (A|B) SOURCE program id
+    FlashSwapRepay(FlashSwapRepay),
+
+    ///   Set up the protocol fee switch of a swap, signed by the owner of the
+    ///   pool fee account. The swap account must have been created with room
+    ///   for the protocol fee. The protocol fee starts at zero; the authority
+    ///   turns it on through `ProposeProtocolFee` and `ExecuteProtocolFee`.
+    ///
+    ///   0. `[writable]` Token-swap
+    ///   1. `[]` Pool fee account
+    ///   2. `[signer]` Pool fee account owner
+    ///   3. `[]` Protocol fee authority
+    InitializeProtocolFee(InitializeProtocolFee),
+
+    ///   Propose a new protocol fee authority, recipient and share, replacing
+    ///   any pending proposal. The proposal can be executed once the
+    ///   timelock has passed.
+    ///
+    ///   0. `[writable]` Token-swap
+    ///   1. `[signer]` Protocol fee authority
+    ///   2. `[]` Proposed protocol fee authority
+    ///   3. `[]` Proposed recipient, a pool token account
+    ProposeProtocolFee(ProposeProtocolFee),
+
+    ///   Apply the pending protocol fee proposal once its timelock has passed.
+    ///   Anyone can execute it. Pool tokens owed but not yet collected go to
+    ///   the new recipient.
+    ///
+    ///   0. `[writable]` Token-swap
+    ExecuteProtocolFee,
+
+    ///   Mint the pool tokens owed as protocol fee to the recipient. Anyone
+    ///   can collect them.
+    ///
+    ///   0. `[writable]` Token-swap
+    ///   1. `[]` swap authority
+    ///   2. `[writable]` Pool mint account, swap authority is the owner
+    ///   3. `[writable]` Protocol fee recipient
+    ///   4. `[]` Pool Token program id
+    CollectProtocolFee,
+
+    ///   Set the withdraw fee schedule of a swap, signed by the owner of the
+    ///   pool fee account. The owner withdraw fee of pool tokens withdrawn
+    ///   along with the LP tenure account of their pool account is scaled
+    ///   down with their tenure.
+    ///
+    ///   0. `[]` Token-swap
+    ///   1. `[]` Pool fee account
+    ///   2. `[signer]` Pool fee account owner
+    ///   3. `[writable]` Withdraw fee schedule account. Must be owned by the
+    ///      swap program, and uninitialized or the schedule of the swap.
+    SetWithdrawFeeSchedule(SetWithdrawFeeSchedule),
+
+    ///   Deposit a single token type into the pool as a balanced deposit.
+    ///   The part of the SOURCE amount which brings the rest to the pool
+    ///   ratio is swapped through the pool into the DESTINATION account,
+    ///   then both tokens are deposited for pool tokens. Amounts left over
+    ///   by rounding stay in the user accounts.
+    ///
+    ///   0. `[writable]` Token-swap, only written to if it has a protocol fee
+    ///   1. `[]` swap authority
+    ///   2. `[signer]` user transfer authority
+    ///   3. `[writable]` token_(A|B) SOURCE Account, amount is transferable by
+    ///      user transfer authority,
+    ///   4. `[writable]` token_(A|B) Base Account to swap INTO.  Must be the
+    ///      SOURCE token.
+    ///   5. `[writable]` token_(A|B) Base Account to swap FROM.  Must be the
+    ///      DESTINATION token.
+    ///   6. `[writable]` token_(A|B) DESTINATION Account receiving the swapped
+    ///      amount, transferable by user transfer authority.
+    ///   7. `[writable]` Pool MINT account, swap authority is the owner.
+    ///   8. `[writable]` Fee account, to receive trading fees
+    ///   9. `[writable]` Pool Account to deposit the generated tokens, user is
+    ///      the owner.
+    ///   10. `[]` Token (A|B) SOURCE mint
+    ///   11. `[]` Token (A|B) DESTINATION mint
+    ///   12. `[]` Token (A|B) SOURCE program id
+    ///   13. `[]` Token (A|B) DESTINATION program id
+    ///   14. `[]` Pool Token program id
+    ///   15. `[optional, writable]` LP tenure account tracking the deposit
+    ///       into the pool account. Must be owned by the swap program, and
+    ///       uninitialized or tracking the same pool account.
+    ZapIn(ZapIn),
 }
 
 impl SwapInstruction {
@@ -279,6 +549,61 @@ impl SwapInstruction {
                     maximum_pool_token_amount,
                 })
             }
+            6 => {
+                let (pool_token_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::IssuePosition(IssuePosition { pool_token_amount })
+            }
+            7 => Self::RedeemPosition,
+            8 => Self::RegisterKeeper,
+            9 => Self::DeregisterKeeper,
+            10 => {
+                let (amount_in, rest) = Self::unpack_u64(rest)?;
+                let (amount_out, _rest) = Self::unpack_u64(rest)?;
+                Self::FlashSwap(FlashSwap {
+                    amount_in,
+                    amount_out,
+                })
+            }
+            11 => {
+                let (amount_in, _rest) = Self::unpack_u64(rest)?;
+                Self::FlashSwapRepay(FlashSwapRepay { amount_in })
+            }
+            12 => {
+                let (timelock_slots, _rest) = Self::unpack_u64(rest)?;
+                Self::InitializeProtocolFee(InitializeProtocolFee { timelock_slots })
+            }
+            13 => {
+                let (fee_bps, _rest) = Self::unpack_u16(rest)?;
+                Self::ProposeProtocolFee(ProposeProtocolFee { fee_bps })
+            }
+            14 => Self::ExecuteProtocolFee,
+            15 => Self::CollectProtocolFee,
+            16 => {
+                let (&tiers_count, mut rest) =
+                    rest.split_first().ok_or(SwapError::InvalidInstruction)?;
+                if usize::from(tiers_count) > MAX_WITHDRAW_FEE_TIERS {
+                    return Err(SwapError::InvalidInstruction.into());
+                }
+                let mut tiers = Vec::with_capacity(usize::from(tiers_count));
+                for _ in 0..tiers_count {
+                    let (min_tenure, next) = Self::unpack_u64(rest)?;
+                    let (fee_bps, next) = Self::unpack_u16(next)?;
+                    tiers.push(WithdrawFeeTier {
+                        min_tenure,
+                        fee_bps,
+                    });
+                    rest = next;
+                }
+                Self::SetWithdrawFeeSchedule(SetWithdrawFeeSchedule { tiers })
+            }
+            17 => {
+                let (source_token_amount, rest) = Self::unpack_u64(rest)?;
+                let (minimum_pool_token_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::ZapIn(ZapIn {
+                    source_token_amount,
+                    minimum_pool_token_amount,
+                })
+            }
             _ => return Err(SwapError::InvalidInstruction.into()),
         })
     }
@@ -297,6 +622,20 @@ impl SwapInstruction {
         }
     }
 
+    fn unpack_u16(input: &[u8]) -> Result<(u16, &[u8]), ProgramError> {
+        if input.len() >= 2 {
+            let (amount, rest) = input.split_at(2);
+            let amount = amount
+                .get(..2)
+                .and_then(|slice| slice.try_into().ok())
+                .map(u16::from_le_bytes)
+                .ok_or(SwapError::InvalidInstruction)?;
+            Ok((amount, rest))
+        } else {
+            Err(SwapError::InvalidInstruction.into())
+        }
+    }
+
     /// Packs a [SwapInstruction](enum.SwapInstruction.html) into a byte buffer.
     pub fn pack(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
@@ -356,6 +695,61 @@ impl SwapInstruction {
                 buf.extend_from_slice(&destination_token_amount.to_le_bytes());
                 buf.extend_from_slice(&maximum_pool_token_amount.to_le_bytes());
             }
+            Self::IssuePosition(IssuePosition { pool_token_amount }) => {
+                buf.push(6);
+                buf.extend_from_slice(&pool_token_amount.to_le_bytes());
+            }
+            Self::RedeemPosition => {
+                buf.push(7);
+            }
+            Self::RegisterKeeper => {
+                buf.push(8);
+            }
+            Self::DeregisterKeeper => {
+                buf.push(9);
+            }
+            Self::FlashSwap(FlashSwap {
+                amount_in,
+                amount_out,
+            }) => {
+                buf.push(10);
+                buf.extend_from_slice(&amount_in.to_le_bytes());
+                buf.extend_from_slice(&amount_out.to_le_bytes());
+            }
+            Self::FlashSwapRepay(FlashSwapRepay { amount_in }) => {
+                buf.push(11);
+                buf.extend_from_slice(&amount_in.to_le_bytes());
+            }
+            Self::InitializeProtocolFee(InitializeProtocolFee { timelock_slots }) => {
+                buf.push(12);
+                buf.extend_from_slice(&timelock_slots.to_le_bytes());
+            }
+            Self::ProposeProtocolFee(ProposeProtocolFee { fee_bps }) => {
+                buf.push(13);
+                buf.extend_from_slice(&fee_bps.to_le_bytes());
+            }
+            Self::ExecuteProtocolFee => {
+                buf.push(14);
+            }
+            Self::CollectProtocolFee => {
+                buf.push(15);
+            }
+            Self::SetWithdrawFeeSchedule(SetWithdrawFeeSchedule { tiers }) => {
+                buf.push(16);
+                buf.push(tiers.len() as u8);
+                for tier in tiers {
+                    buf.extend_from_slice(&tier.min_tenure.to_le_bytes());
+                    buf.extend_from_slice(&tier.fee_bps.to_le_bytes());
+                }
+            }
+            Self::ZapIn(ZapIn {
+                source_token_amount,
+                minimum_pool_token_amount,
+            }) => {
+                buf.push(17);
+                buf.extend_from_slice(&source_token_amount.to_le_bytes());
+                buf.extend_from_slice(&minimum_pool_token_amount.to_le_bytes());
+            }
         }
         buf
     }
@@ -591,7 +985,7 @@ pub fn swap(
     let data = SwapInstruction::Swap(instruction).pack();
 
     let mut accounts = vec![
-        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new(*swap_pubkey, false),
         AccountMeta::new_readonly(*authority_pubkey, false),
         AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
         AccountMeta::new(*source_pubkey, false),
@@ -617,6 +1011,375 @@ pub fn swap(
     })
 }
 
+/// Creates an 'issue_position' instruction.
+pub fn issue_position(
+    program_id: &Pubkey,
+    pool_token_program_id: &Pubkey,
+    position_token_program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    user_transfer_authority_pubkey: &Pubkey,
+    pool_mint_pubkey: &Pubkey,
+    source_pubkey: &Pubkey,
+    swap_token_a_pubkey: &Pubkey,
+    swap_token_b_pubkey: &Pubkey,
+    position_pubkey: &Pubkey,
+    position_mint_pubkey: &Pubkey,
+    position_token_pubkey: &Pubkey,
+    payer_pubkey: &Pubkey,
+    instruction: IssuePosition,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::IssuePosition(instruction).pack();
+    let (position_vault_pubkey, _) = Processor::position_vault_id(program_id, swap_pubkey);
+    let (position_group_pubkey, _) = Processor::position_group_id(program_id, swap_pubkey);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
+        AccountMeta::new_readonly(*pool_mint_pubkey, false),
+        AccountMeta::new(*source_pubkey, false),
+        AccountMeta::new(position_vault_pubkey, false),
+        AccountMeta::new(position_group_pubkey, false),
+        AccountMeta::new_readonly(*swap_token_a_pubkey, false),
+        AccountMeta::new_readonly(*swap_token_b_pubkey, false),
+        AccountMeta::new(*position_pubkey, false),
+        AccountMeta::new(*position_mint_pubkey, false),
+        AccountMeta::new(*position_token_pubkey, false),
+        AccountMeta::new(*payer_pubkey, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*pool_token_program_id, false),
+        AccountMeta::new_readonly(*position_token_program_id, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'redeem_position' instruction.
+pub fn redeem_position(
+    program_id: &Pubkey,
+    pool_token_program_id: &Pubkey,
+    position_token_program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    position_owner_pubkey: &Pubkey,
+    position_pubkey: &Pubkey,
+    position_mint_pubkey: &Pubkey,
+    position_token_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    pool_mint_pubkey: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::RedeemPosition.pack();
+    let (position_vault_pubkey, _) = Processor::position_vault_id(program_id, swap_pubkey);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new_readonly(*position_owner_pubkey, true),
+        AccountMeta::new(*position_pubkey, false),
+        AccountMeta::new(*position_mint_pubkey, false),
+        AccountMeta::new(*position_token_pubkey, false),
+        AccountMeta::new(position_vault_pubkey, false),
+        AccountMeta::new(*destination_pubkey, false),
+        AccountMeta::new_readonly(*pool_mint_pubkey, false),
+        AccountMeta::new_readonly(*pool_token_program_id, false),
+        AccountMeta::new_readonly(*position_token_program_id, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'register_keeper' instruction.
+pub fn register_keeper(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    pool_fee_pubkey: &Pubkey,
+    pool_fee_owner_pubkey: &Pubkey,
+    keeper_registration_pubkey: &Pubkey,
+    keeper_pubkey: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::RegisterKeeper.pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new_readonly(*pool_fee_pubkey, false),
+        AccountMeta::new_readonly(*pool_fee_owner_pubkey, true),
+        AccountMeta::new(*keeper_registration_pubkey, false),
+        AccountMeta::new_readonly(*keeper_pubkey, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'deregister_keeper' instruction.
+pub fn deregister_keeper(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    pool_fee_pubkey: &Pubkey,
+    pool_fee_owner_pubkey: &Pubkey,
+    keeper_registration_pubkey: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::DeregisterKeeper.pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new_readonly(*pool_fee_pubkey, false),
+        AccountMeta::new_readonly(*pool_fee_owner_pubkey, true),
+        AccountMeta::new(*keeper_registration_pubkey, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'flash_swap' instruction.
+pub fn flash_swap(
+    program_id: &Pubkey,
+    destination_token_program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    keeper_pubkey: &Pubkey,
+    keeper_registration_pubkey: &Pubkey,
+    swap_source_pubkey: &Pubkey,
+    swap_destination_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    source_mint_pubkey: &Pubkey,
+    destination_mint_pubkey: &Pubkey,
+    instruction: FlashSwap,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::FlashSwap(instruction).pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new_readonly(*keeper_pubkey, true),
+        AccountMeta::new_readonly(*keeper_registration_pubkey, false),
+        AccountMeta::new_readonly(*swap_source_pubkey, false),
+        AccountMeta::new(*swap_destination_pubkey, false),
+        AccountMeta::new(*destination_pubkey, false),
+        AccountMeta::new_readonly(*source_mint_pubkey, false),
+        AccountMeta::new_readonly(*destination_mint_pubkey, false),
+        AccountMeta::new_readonly(*destination_token_program_id, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'flash_swap_repay' instruction.
+pub fn flash_swap_repay(
+    program_id: &Pubkey,
+    source_token_program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    user_transfer_authority_pubkey: &Pubkey,
+    source_pubkey: &Pubkey,
+    swap_source_pubkey: &Pubkey,
+    source_mint_pubkey: &Pubkey,
+    instruction: FlashSwapRepay,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::FlashSwapRepay(instruction).pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
+        AccountMeta::new(*source_pubkey, false),
+        AccountMeta::new(*swap_source_pubkey, false),
+        AccountMeta::new_readonly(*source_mint_pubkey, false),
+        AccountMeta::new_readonly(*source_token_program_id, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize_protocol_fee' instruction.
+pub fn initialize_protocol_fee(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    pool_fee_pubkey: &Pubkey,
+    pool_fee_owner_pubkey: &Pubkey,
+    protocol_fee_authority_pubkey: &Pubkey,
+    instruction: InitializeProtocolFee,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::InitializeProtocolFee(instruction).pack();
+
+    let accounts = vec![
+        AccountMeta::new(*swap_pubkey, false),
+        AccountMeta::new_readonly(*pool_fee_pubkey, false),
+        AccountMeta::new_readonly(*pool_fee_owner_pubkey, true),
+        AccountMeta::new_readonly(*protocol_fee_authority_pubkey, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'propose_protocol_fee' instruction.
+pub fn propose_protocol_fee(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    protocol_fee_authority_pubkey: &Pubkey,
+    new_protocol_fee_authority_pubkey: &Pubkey,
+    recipient_pubkey: &Pubkey,
+    instruction: ProposeProtocolFee,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::ProposeProtocolFee(instruction).pack();
+
+    let accounts = vec![
+        AccountMeta::new(*swap_pubkey, false),
+        AccountMeta::new_readonly(*protocol_fee_authority_pubkey, true),
+        AccountMeta::new_readonly(*new_protocol_fee_authority_pubkey, false),
+        AccountMeta::new_readonly(*recipient_pubkey, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'execute_protocol_fee' instruction.
+pub fn execute_protocol_fee(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::ExecuteProtocolFee.pack();
+
+    let accounts = vec![AccountMeta::new(*swap_pubkey, false)];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'collect_protocol_fee' instruction.
+pub fn collect_protocol_fee(
+    program_id: &Pubkey,
+    pool_token_program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    pool_mint_pubkey: &Pubkey,
+    recipient_pubkey: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::CollectProtocolFee.pack();
+
+    let accounts = vec![
+        AccountMeta::new(*swap_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new(*pool_mint_pubkey, false),
+        AccountMeta::new(*recipient_pubkey, false),
+        AccountMeta::new_readonly(*pool_token_program_id, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'set_withdraw_fee_schedule' instruction.
+pub fn set_withdraw_fee_schedule(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    pool_fee_pubkey: &Pubkey,
+    pool_fee_owner_pubkey: &Pubkey,
+    withdraw_fee_schedule_pubkey: &Pubkey,
+    instruction: SetWithdrawFeeSchedule,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::SetWithdrawFeeSchedule(instruction).pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new_readonly(*pool_fee_pubkey, false),
+        AccountMeta::new_readonly(*pool_fee_owner_pubkey, true),
+        AccountMeta::new(*withdraw_fee_schedule_pubkey, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'zap_in' instruction.
+pub fn zap_in(
+    program_id: &Pubkey,
+    source_token_program_id: &Pubkey,
+    destination_token_program_id: &Pubkey,
+    pool_token_program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    user_transfer_authority_pubkey: &Pubkey,
+    source_pubkey: &Pubkey,
+    swap_source_pubkey: &Pubkey,
+    swap_destination_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    pool_mint_pubkey: &Pubkey,
+    pool_fee_pubkey: &Pubkey,
+    pool_destination_pubkey: &Pubkey,
+    source_mint_pubkey: &Pubkey,
+    destination_mint_pubkey: &Pubkey,
+    instruction: ZapIn,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::ZapIn(instruction).pack();
+
+    let accounts = vec![
+        AccountMeta::new(*swap_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
+        AccountMeta::new(*source_pubkey, false),
+        AccountMeta::new(*swap_source_pubkey, false),
+        AccountMeta::new(*swap_destination_pubkey, false),
+        AccountMeta::new(*destination_pubkey, false),
+        AccountMeta::new(*pool_mint_pubkey, false),
+        AccountMeta::new(*pool_fee_pubkey, false),
+        AccountMeta::new(*pool_destination_pubkey, false),
+        AccountMeta::new_readonly(*source_mint_pubkey, false),
+        AccountMeta::new_readonly(*destination_mint_pubkey, false),
+        AccountMeta::new_readonly(*source_token_program_id, false),
+        AccountMeta::new_readonly(*destination_token_program_id, false),
+        AccountMeta::new_readonly(*pool_token_program_id, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
 /// Unpacks a reference from a bytes buffer.
 /// TODO actually pack / unpack instead of relying on normal memory layout.
 pub fn unpack<T>(input: &[u8]) -> Result<&T, ProgramError> {
@@ -776,4 +1539,137 @@ mod tests {
         let unpacked = SwapInstruction::unpack(&expect).unwrap();
         assert_eq!(unpacked, check);
     }
+
+    #[test]
+    fn pack_issue_position() {
+        let pool_token_amount: u64 = 1_000_000;
+        let check = SwapInstruction::IssuePosition(IssuePosition { pool_token_amount });
+        let packed = check.pack();
+        let mut expect = vec![6];
+        expect.extend_from_slice(&pool_token_amount.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_redeem_position() {
+        let check = SwapInstruction::RedeemPosition;
+        let packed = check.pack();
+        let expect = vec![7];
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_register_keeper() {
+        let check = SwapInstruction::RegisterKeeper;
+        let packed = check.pack();
+        let expect = vec![8];
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_flash_swap() {
+        let amount_in: u64 = 2;
+        let amount_out: u64 = 10;
+        let check = SwapInstruction::FlashSwap(FlashSwap {
+            amount_in,
+            amount_out,
+        });
+        let packed = check.pack();
+        let mut expect = vec![10];
+        expect.extend_from_slice(&amount_in.to_le_bytes());
+        expect.extend_from_slice(&amount_out.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_flash_swap_repay() {
+        let amount_in: u64 = 2;
+        let check = SwapInstruction::FlashSwapRepay(FlashSwapRepay { amount_in });
+        let packed = check.pack();
+        let mut expect = vec![11];
+        expect.extend_from_slice(&amount_in.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_initialize_protocol_fee() {
+        let timelock_slots: u64 = 216_000;
+        let check =
+            SwapInstruction::InitializeProtocolFee(InitializeProtocolFee { timelock_slots });
+        let packed = check.pack();
+        let mut expect = vec![12];
+        expect.extend_from_slice(&timelock_slots.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_propose_protocol_fee() {
+        let fee_bps: u16 = 1_500;
+        let check = SwapInstruction::ProposeProtocolFee(ProposeProtocolFee { fee_bps });
+        let packed = check.pack();
+        let mut expect = vec![13];
+        expect.extend_from_slice(&fee_bps.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_set_withdraw_fee_schedule() {
+        let tiers = vec![
+            WithdrawFeeTier {
+                min_tenure: 86_400,
+                fee_bps: 5_000,
+            },
+            WithdrawFeeTier {
+                min_tenure: 2_592_000,
+                fee_bps: 0,
+            },
+        ];
+        let check = SwapInstruction::SetWithdrawFeeSchedule(SetWithdrawFeeSchedule { tiers });
+        let packed = check.pack();
+        let mut expect = vec![16, 2];
+        expect.extend_from_slice(&86_400u64.to_le_bytes());
+        expect.extend_from_slice(&5_000u16.to_le_bytes());
+        expect.extend_from_slice(&2_592_000u64.to_le_bytes());
+        expect.extend_from_slice(&0u16.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let too_many_tiers = vec![16, MAX_WITHDRAW_FEE_TIERS as u8 + 1];
+        assert_eq!(
+            SwapInstruction::unpack(&too_many_tiers),
+            Err(SwapError::InvalidInstruction.into())
+        );
+    }
+
+    #[test]
+    fn pack_zap_in() {
+        let source_token_amount: u64 = 10_000;
+        let minimum_pool_token_amount: u64 = 4_500;
+        let check = SwapInstruction::ZapIn(ZapIn {
+            source_token_amount,
+            minimum_pool_token_amount,
+        });
+        let packed = check.pack();
+        let mut expect = vec![17];
+        expect.extend_from_slice(&source_token_amount.to_le_bytes());
+        expect.extend_from_slice(&minimum_pool_token_amount.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
 }
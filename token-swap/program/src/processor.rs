@@ -4,16 +4,22 @@ use {
     crate::{
         constraints::{SwapConstraints, SWAP_CONSTRAINTS},
         curve::{
-            base::SwapCurve,
-            calculator::{RoundDirection, TradeDirection},
+            base::{CurveType, SwapCurve},
+            calculator::{CurveCalculator, RoundDirection, TradeDirection},
             fees::Fees,
         },
         error::SwapError,
         instruction::{
-            DepositAllTokenTypes, DepositSingleTokenTypeExactAmountIn, Initialize, Swap,
-            SwapInstruction, WithdrawAllTokenTypes, WithdrawSingleTokenTypeExactAmountOut,
+            DepositAllTokenTypes, DepositSingleTokenTypeExactAmountIn, FlashSwap, FlashSwapRepay,
+            Initialize, InitializeProtocolFee, IssuePosition, ProposeProtocolFee,
+            SetWithdrawFeeSchedule, Swap, SwapInstruction, WithdrawAllTokenTypes,
+            WithdrawSingleTokenTypeExactAmountOut, ZapIn,
+        },
+        state::{
+            KeeperRegistration, LpPosition, LpTenure, ProtocolFee, SwapState, SwapV1, SwapVersion,
+            WithdrawFeeSchedule, WithdrawFeeTier, MAX_PROTOCOL_FEE_BPS, POSITION_GROUP_SEED,
+            POSITION_METADATA_NAME, POSITION_METADATA_SYMBOL, POSITION_VAULT_SEED,
         },
-        state::{SwapState, SwapV1, SwapVersion},
     },
     num_traits::FromPrimitive,
     solana_program::{
@@ -23,21 +29,30 @@ use {
         entrypoint::ProgramResult,
         instruction::Instruction,
         msg,
-        program::invoke_signed,
+        program::{invoke, invoke_signed},
         program_error::{PrintProgramError, ProgramError},
         program_option::COption,
+        program_pack::Pack,
         pubkey::Pubkey,
-        sysvar::Sysvar,
+        system_instruction,
+        sysvar::{
+            instructions::{load_current_index_checked, load_instruction_at_checked},
+            rent::Rent,
+            Sysvar,
+        },
     },
     spl_token_2022::{
         check_spl_token_program_account,
         error::TokenError,
         extension::{
+            group_member_pointer::GroupMemberPointer, metadata_pointer::MetadataPointer,
             mint_close_authority::MintCloseAuthority, transfer_fee::TransferFeeConfig,
-            BaseStateWithExtensions, StateWithExtensions,
+            BaseStateWithExtensions, ExtensionType, StateWithExtensions,
         },
         state::{Account, Mint},
     },
+    spl_token_group_interface::state::{TokenGroup, TokenGroupMember},
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
     std::{convert::TryInto, error::Error},
 };
 
@@ -89,6 +104,20 @@ impl Processor {
         }
     }
 
+    /// Evaluates the swap curve of the pool at the current time, only reading
+    /// the clock for curves with time-varying parameters.
+    pub fn current_swap_curve(swap_curve: &SwapCurve) -> Result<SwapCurve, ProgramError> {
+        match swap_curve.curve_type {
+            CurveType::LiquidityBootstrapping => {
+                Ok(swap_curve.at_unix_timestamp(Clock::get()?.unix_timestamp))
+            }
+            _ => Ok(SwapCurve {
+                curve_type: swap_curve.curve_type,
+                calculator: swap_curve.calculator.clone(),
+            }),
+        }
+    }
+
     /// Calculates the authority id by generating a program address.
     pub fn authority_id(
         program_id: &Pubkey,
@@ -406,6 +435,7 @@ impl Processor {
             return Err(ProgramError::IncorrectProgramId);
         }
         let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        let swap_curve = Self::current_swap_curve(token_swap.swap_curve())?;
 
         if *authority_info.key
             != Self::authority_id(program_id, swap_info.key, token_swap.bump_seed())?
@@ -473,8 +503,7 @@ impl Processor {
         } else {
             TradeDirection::BtoA
         };
-        let result = token_swap
-            .swap_curve()
+        let result = swap_curve
             .swap(
                 u128::from(actual_amount_in),
                 u128::from(source_account.amount),
@@ -556,8 +585,7 @@ impl Processor {
         )?;
 
         if result.owner_fee > 0 {
-            let mut pool_token_amount = token_swap
-                .swap_curve()
+            let mut pool_token_amount = swap_curve
                 .calculator
                 .withdraw_single_token_type_exact_out(
                     result.owner_fee,
@@ -596,6 +624,21 @@ impl Processor {
                     )?;
                 }
             }
+            if let Some(mut protocol_fee) = Self::unpack_protocol_fee(swap_info)? {
+                let protocol_fee_amount = protocol_fee
+                    .protocol_fee(pool_token_amount)
+                    .ok_or(SwapError::FeeCalculationFailure)?;
+                if protocol_fee_amount > 0 {
+                    pool_token_amount = pool_token_amount
+                        .checked_sub(protocol_fee_amount)
+                        .ok_or(SwapError::FeeCalculationFailure)?;
+                    protocol_fee.pool_tokens_owed = protocol_fee
+                        .pool_tokens_owed
+                        .checked_add(to_u64(protocol_fee_amount)?)
+                        .ok_or(SwapError::FeeCalculationFailure)?;
+                    Self::pack_protocol_fee(protocol_fee, swap_info)?;
+                }
+            }
             if token_swap
                 .check_pool_fee_info(pool_fee_account_info)
                 .is_ok()
@@ -650,6 +693,7 @@ impl Processor {
         let token_a_program_info = next_account_info(account_info_iter)?;
         let token_b_program_info = next_account_info(account_info_iter)?;
         let pool_token_program_info = next_account_info(account_info_iter)?;
+        let lp_tenure_info = next_account_info(account_info_iter).ok();
 
         let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
         let calculator = &token_swap.swap_curve().calculator;
@@ -728,6 +772,16 @@ impl Processor {
             token_b_amount,
             Self::unpack_mint(token_b_mint_info, token_swap.token_program_id())?.decimals,
         )?;
+        if let Some(lp_tenure_info) = lp_tenure_info {
+            Self::record_lp_tenure(
+                program_id,
+                token_swap.as_ref(),
+                swap_info,
+                dest_info,
+                lp_tenure_info,
+                pool_token_amount,
+            )?;
+        }
         Self::token_mint_to(
             swap_info.key,
             pool_token_program_info.clone(),
@@ -765,6 +819,8 @@ impl Processor {
         let pool_token_program_info = next_account_info(account_info_iter)?;
         let token_a_program_info = next_account_info(account_info_iter)?;
         let token_b_program_info = next_account_info(account_info_iter)?;
+        let withdraw_fee_schedule_info = next_account_info(account_info_iter).ok();
+        let lp_tenure_info = next_account_info(account_info_iter).ok();
 
         let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
         Self::check_accounts(
@@ -787,20 +843,35 @@ impl Processor {
 
         let calculator = &token_swap.swap_curve().calculator;
 
+        let lp_tenure = Self::withdraw_lp_tenure(
+            program_id,
+            token_swap.as_ref(),
+            swap_info,
+            source_info,
+            withdraw_fee_schedule_info,
+            lp_tenure_info,
+        )?;
         let withdraw_fee = match token_swap.check_pool_fee_info(pool_fee_account_info) {
             Ok(_) => {
                 if *pool_fee_account_info.key == *source_info.key {
                     // withdrawing from the fee account, don't assess withdraw fee
                     0
                 } else {
-                    token_swap
-                        .fees()
-                        .owner_withdraw_fee(u128::from(pool_token_amount))
-                        .ok_or(SwapError::FeeCalculationFailure)?
+                    Self::owner_withdraw_fee(
+                        program_id,
+                        token_swap.as_ref(),
+                        swap_info,
+                        withdraw_fee_schedule_info.zip(lp_tenure.as_ref()),
+                        u128::from(pool_token_amount),
+                    )?
                 }
             }
             Err(_) => 0,
         };
+        if let Some((lp_tenure_info, mut lp_tenure)) = lp_tenure_info.zip(lp_tenure) {
+            lp_tenure.withdraw(pool_token_amount);
+            LpTenure::pack(lp_tenure, &mut lp_tenure_info.data.borrow_mut())?;
+        }
         let pool_token_amount = u128::from(pool_token_amount)
             .checked_sub(withdraw_fee)
             .ok_or(SwapError::CalculationFailure)?;
@@ -902,9 +973,11 @@ impl Processor {
         let source_token_mint_info = next_account_info(account_info_iter)?;
         let source_token_program_info = next_account_info(account_info_iter)?;
         let pool_token_program_info = next_account_info(account_info_iter)?;
+        let lp_tenure_info = next_account_info(account_info_iter).ok();
 
         let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
-        let calculator = &token_swap.swap_curve().calculator;
+        let swap_curve = Self::current_swap_curve(token_swap.swap_curve())?;
+        let calculator = &swap_curve.calculator;
         if !calculator.allows_deposits() {
             return Err(SwapError::UnsupportedCurveOperation.into());
         }
@@ -945,8 +1018,7 @@ impl Processor {
         let pool_mint = Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?;
         let pool_mint_supply = u128::from(pool_mint.supply);
         let pool_token_amount = if pool_mint_supply > 0 {
-            token_swap
-                .swap_curve()
+            swap_curve
                 .deposit_single_token_type(
                     u128::from(source_token_amount),
                     u128::from(swap_token_a.amount),
@@ -998,6 +1070,16 @@ impl Processor {
                 )?;
             }
         }
+        if let Some(lp_tenure_info) = lp_tenure_info {
+            Self::record_lp_tenure(
+                program_id,
+                token_swap.as_ref(),
+                swap_info,
+                destination_info,
+                lp_tenure_info,
+                pool_token_amount,
+            )?;
+        }
         Self::token_mint_to(
             swap_info.key,
             pool_token_program_info.clone(),
@@ -1011,6 +1093,323 @@ impl Processor {
         Ok(())
     }
 
+    /// Source amount of a zap in to swap, the largest one leaving at least
+    /// as much source as the swap output requires for a deposit at the ratio
+    /// of the pool after the swap
+    fn zap_in_swap_amount(
+        swap_curve: &SwapCurve,
+        fees: &Fees,
+        source_token_amount: u64,
+        swap_source_amount: u64,
+        swap_destination_amount: u64,
+        pool_mint_supply: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<u64> {
+        let (mut low, mut high) = (0, source_token_amount);
+        while low < high {
+            let amount_in = high - (high - low) / 2;
+            let leaves_enough_source = match swap_curve.swap(
+                u128::from(amount_in),
+                u128::from(swap_source_amount),
+                u128::from(swap_destination_amount),
+                trade_direction,
+                fees,
+            ) {
+                Some(result) => {
+                    let (swap_token_a_amount, swap_token_b_amount) = match trade_direction {
+                        TradeDirection::AtoB => (
+                            result.new_swap_source_amount,
+                            result.new_swap_destination_amount,
+                        ),
+                        TradeDirection::BtoA => (
+                            result.new_swap_destination_amount,
+                            result.new_swap_source_amount,
+                        ),
+                    };
+                    let ratio = swap_curve.calculator.pool_tokens_to_trading_tokens(
+                        pool_mint_supply,
+                        pool_mint_supply,
+                        swap_token_a_amount,
+                        swap_token_b_amount,
+                        RoundDirection::Floor,
+                    )?;
+                    let (source_ratio, destination_ratio) = match trade_direction {
+                        TradeDirection::AtoB => (ratio.token_a_amount, ratio.token_b_amount),
+                        TradeDirection::BtoA => (ratio.token_b_amount, ratio.token_a_amount),
+                    };
+                    u128::from(source_token_amount)
+                        .checked_sub(result.source_amount_swapped)?
+                        .checked_mul(destination_ratio)?
+                        >= result
+                            .destination_amount_swapped
+                            .checked_mul(source_ratio)?
+                }
+                None => true,
+            };
+            if leaves_enough_source {
+                low = amount_in;
+            } else {
+                high = amount_in - 1;
+            }
+        }
+        Some(low)
+    }
+
+    /// Largest amount of pool tokens a deposit of at most the given token
+    /// amounts can mint
+    fn zap_in_pool_token_amount(
+        calculator: &dyn CurveCalculator,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        swap_token_a_amount: u64,
+        swap_token_b_amount: u64,
+        pool_mint_supply: u128,
+    ) -> Option<u64> {
+        let ratio = calculator.pool_tokens_to_trading_tokens(
+            pool_mint_supply,
+            pool_mint_supply,
+            u128::from(swap_token_a_amount),
+            u128::from(swap_token_b_amount),
+            RoundDirection::Floor,
+        )?;
+        let (mut low, mut high) = (
+            0,
+            to_u64(std::cmp::min(
+                u128::from(token_a_amount)
+                    .checked_mul(pool_mint_supply)?
+                    .checked_div(ratio.token_a_amount)?,
+                u128::from(token_b_amount)
+                    .checked_mul(pool_mint_supply)?
+                    .checked_div(ratio.token_b_amount)?,
+            ))
+            .ok()?,
+        );
+        while low < high {
+            let pool_token_amount = high - (high - low) / 2;
+            let fits = calculator
+                .pool_tokens_to_trading_tokens(
+                    u128::from(pool_token_amount),
+                    pool_mint_supply,
+                    u128::from(swap_token_a_amount),
+                    u128::from(swap_token_b_amount),
+                    RoundDirection::Ceiling,
+                )
+                .map_or(false, |results| {
+                    results.token_a_amount <= u128::from(token_a_amount)
+                        && results.token_b_amount <= u128::from(token_b_amount)
+                });
+            if fits {
+                low = pool_token_amount;
+            } else {
+                high = pool_token_amount - 1;
+            }
+        }
+        Some(low)
+    }
+
+    /// Processes a [ZapIn](enum.Instruction.html).
+    pub fn process_zap_in(
+        program_id: &Pubkey,
+        source_token_amount: u64,
+        minimum_pool_token_amount: u64,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let user_transfer_authority_info = next_account_info(account_info_iter)?;
+        let source_info = next_account_info(account_info_iter)?;
+        let swap_source_info = next_account_info(account_info_iter)?;
+        let swap_destination_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let pool_fee_account_info = next_account_info(account_info_iter)?;
+        let pool_destination_info = next_account_info(account_info_iter)?;
+        let source_token_mint_info = next_account_info(account_info_iter)?;
+        let destination_token_mint_info = next_account_info(account_info_iter)?;
+        let source_token_program_info = next_account_info(account_info_iter)?;
+        let destination_token_program_info = next_account_info(account_info_iter)?;
+        let pool_token_program_info = next_account_info(account_info_iter)?;
+        let lp_tenure_info = next_account_info(account_info_iter).ok();
+
+        if swap_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        if *authority_info.key
+            != Self::authority_id(program_id, swap_info.key, token_swap.bump_seed())?
+        {
+            return Err(SwapError::InvalidProgramAddress.into());
+        }
+        if !token_swap.swap_curve().calculator.allows_deposits() {
+            return Err(SwapError::UnsupportedCurveOperation.into());
+        }
+        let trade_direction = if *swap_source_info.key == *token_swap.token_a_account()
+            && *swap_destination_info.key == *token_swap.token_b_account()
+        {
+            TradeDirection::AtoB
+        } else if *swap_source_info.key == *token_swap.token_b_account()
+            && *swap_destination_info.key == *token_swap.token_a_account()
+        {
+            TradeDirection::BtoA
+        } else {
+            return Err(SwapError::IncorrectSwapAccount.into());
+        };
+
+        // Swap the part of the source which balances the rest
+        let swap_curve = Self::current_swap_curve(token_swap.swap_curve())?;
+        let swap_source =
+            Self::unpack_token_account(swap_source_info, token_swap.token_program_id())?;
+        let swap_destination =
+            Self::unpack_token_account(swap_destination_info, token_swap.token_program_id())?;
+        let pool_mint = Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?;
+        let amount_in = Self::zap_in_swap_amount(
+            &swap_curve,
+            token_swap.fees(),
+            source_token_amount,
+            swap_source.amount,
+            swap_destination.amount,
+            u128::from(pool_mint.supply),
+            trade_direction,
+        )
+        .ok_or(SwapError::CalculationFailure)?;
+        if amount_in == 0 {
+            return Err(SwapError::ZeroTradingTokens.into());
+        }
+
+        let source_amount_before =
+            Self::unpack_token_account(source_info, source_token_program_info.key)?.amount;
+        let destination_amount_before =
+            Self::unpack_token_account(destination_info, destination_token_program_info.key)?
+                .amount;
+        Self::process_swap(
+            program_id,
+            amount_in,
+            0,
+            &[
+                swap_info.clone(),
+                authority_info.clone(),
+                user_transfer_authority_info.clone(),
+                source_info.clone(),
+                swap_source_info.clone(),
+                swap_destination_info.clone(),
+                destination_info.clone(),
+                pool_mint_info.clone(),
+                pool_fee_account_info.clone(),
+                source_token_mint_info.clone(),
+                destination_token_mint_info.clone(),
+                source_token_program_info.clone(),
+                destination_token_program_info.clone(),
+                pool_token_program_info.clone(),
+            ],
+        )?;
+        let source_amount = source_amount_before
+            .checked_sub(
+                Self::unpack_token_account(source_info, source_token_program_info.key)?.amount,
+            )
+            .and_then(|amount_swapped| source_token_amount.checked_sub(amount_swapped))
+            .ok_or(SwapError::CalculationFailure)?;
+        let destination_amount =
+            Self::unpack_token_account(destination_info, destination_token_program_info.key)?
+                .amount
+                .checked_sub(destination_amount_before)
+                .ok_or(SwapError::CalculationFailure)?;
+
+        // Deposit the rest of the source along with the swapped amount
+        let swap_source =
+            Self::unpack_token_account(swap_source_info, token_swap.token_program_id())?;
+        let swap_destination =
+            Self::unpack_token_account(swap_destination_info, token_swap.token_program_id())?;
+        let pool_mint = Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?;
+        let (token_a_amount, token_b_amount, swap_token_a_amount, swap_token_b_amount) =
+            match trade_direction {
+                TradeDirection::AtoB => (
+                    source_amount,
+                    destination_amount,
+                    swap_source.amount,
+                    swap_destination.amount,
+                ),
+                TradeDirection::BtoA => (
+                    destination_amount,
+                    source_amount,
+                    swap_destination.amount,
+                    swap_source.amount,
+                ),
+            };
+        let pool_token_amount = Self::zap_in_pool_token_amount(
+            token_swap.swap_curve().calculator.as_ref(),
+            token_a_amount,
+            token_b_amount,
+            swap_token_a_amount,
+            swap_token_b_amount,
+            u128::from(pool_mint.supply),
+        )
+        .ok_or(SwapError::CalculationFailure)?;
+        if pool_token_amount < minimum_pool_token_amount {
+            return Err(SwapError::ExceededSlippage.into());
+        }
+
+        let (
+            (source_a_info, token_a_info, token_a_mint_info, token_a_program_info),
+            (source_b_info, token_b_info, token_b_mint_info, token_b_program_info),
+        ) = match trade_direction {
+            TradeDirection::AtoB => (
+                (
+                    source_info,
+                    swap_source_info,
+                    source_token_mint_info,
+                    source_token_program_info,
+                ),
+                (
+                    destination_info,
+                    swap_destination_info,
+                    destination_token_mint_info,
+                    destination_token_program_info,
+                ),
+            ),
+            TradeDirection::BtoA => (
+                (
+                    destination_info,
+                    swap_destination_info,
+                    destination_token_mint_info,
+                    destination_token_program_info,
+                ),
+                (
+                    source_info,
+                    swap_source_info,
+                    source_token_mint_info,
+                    source_token_program_info,
+                ),
+            ),
+        };
+        let mut deposit_accounts = vec![
+            swap_info.clone(),
+            authority_info.clone(),
+            user_transfer_authority_info.clone(),
+            source_a_info.clone(),
+            source_b_info.clone(),
+            token_a_info.clone(),
+            token_b_info.clone(),
+            pool_mint_info.clone(),
+            pool_destination_info.clone(),
+            token_a_mint_info.clone(),
+            token_b_mint_info.clone(),
+            token_a_program_info.clone(),
+            token_b_program_info.clone(),
+            pool_token_program_info.clone(),
+        ];
+        if let Some(lp_tenure_info) = lp_tenure_info {
+            deposit_accounts.push(lp_tenure_info.clone());
+        }
+        Self::process_deposit_all_token_types(
+            program_id,
+            pool_token_amount,
+            token_a_amount,
+            token_b_amount,
+            &deposit_accounts,
+        )
+    }
+
     /// Processes a
     /// [WithdrawSingleTokenTypeExactAmountOut](enum.Instruction.html).
     pub fn process_withdraw_single_token_type_exact_amount_out(
@@ -1032,8 +1431,11 @@ impl Processor {
         let destination_token_mint_info = next_account_info(account_info_iter)?;
         let pool_token_program_info = next_account_info(account_info_iter)?;
         let destination_token_program_info = next_account_info(account_info_iter)?;
+        let withdraw_fee_schedule_info = next_account_info(account_info_iter).ok();
+        let lp_tenure_info = next_account_info(account_info_iter).ok();
 
         let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        let swap_curve = Self::current_swap_curve(token_swap.swap_curve())?;
         let destination_account =
             Self::unpack_token_account(destination_info, token_swap.token_program_id())?;
         let swap_token_a =
@@ -1072,8 +1474,7 @@ impl Processor {
         let swap_token_a_amount = u128::from(swap_token_a.amount);
         let swap_token_b_amount = u128::from(swap_token_b.amount);
 
-        let burn_pool_token_amount = token_swap
-            .swap_curve()
+        let burn_pool_token_amount = swap_curve
             .withdraw_single_token_type_exact_out(
                 u128::from(destination_token_amount),
                 swap_token_a_amount,
@@ -1084,16 +1485,27 @@ impl Processor {
             )
             .ok_or(SwapError::ZeroTradingTokens)?;
 
+        let lp_tenure = Self::withdraw_lp_tenure(
+            program_id,
+            token_swap.as_ref(),
+            swap_info,
+            source_info,
+            withdraw_fee_schedule_info,
+            lp_tenure_info,
+        )?;
         let withdraw_fee = match token_swap.check_pool_fee_info(pool_fee_account_info) {
             Ok(_) => {
                 if *pool_fee_account_info.key == *source_info.key {
                     // withdrawing from the fee account, don't assess withdraw fee
                     0
                 } else {
-                    token_swap
-                        .fees()
-                        .owner_withdraw_fee(burn_pool_token_amount)
-                        .ok_or(SwapError::FeeCalculationFailure)?
+                    Self::owner_withdraw_fee(
+                        program_id,
+                        token_swap.as_ref(),
+                        swap_info,
+                        withdraw_fee_schedule_info.zip(lp_tenure.as_ref()),
+                        burn_pool_token_amount,
+                    )?
                 }
             }
             Err(_) => 0,
@@ -1101,6 +1513,10 @@ impl Processor {
         let pool_token_amount = burn_pool_token_amount
             .checked_add(withdraw_fee)
             .ok_or(SwapError::CalculationFailure)?;
+        if let Some((lp_tenure_info, mut lp_tenure)) = lp_tenure_info.zip(lp_tenure) {
+            lp_tenure.withdraw(to_u64(pool_token_amount)?);
+            LpTenure::pack(lp_tenure, &mut lp_tenure_info.data.borrow_mut())?;
+        }
 
         if to_u64(pool_token_amount)? > maximum_pool_token_amount {
             return Err(SwapError::ExceededSlippage.into());
@@ -1166,58 +1582,1400 @@ impl Processor {
         Ok(())
     }
 
-    /// Processes an [Instruction](enum.Instruction.html).
-    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
-        Self::process_with_constraints(program_id, accounts, input, &SWAP_CONSTRAINTS)
+    /// Unpacks the LP tenure of a pool account, initializing it on first use,
+    /// and syncs it with the balance of the pool account
+    fn sync_lp_tenure(
+        program_id: &Pubkey,
+        token_swap: &dyn SwapState,
+        swap_info: &AccountInfo,
+        pool_account_info: &AccountInfo,
+        lp_tenure_info: &AccountInfo,
+    ) -> Result<LpTenure, ProgramError> {
+        if lp_tenure_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let mut lp_tenure = LpTenure::unpack_unchecked(&lp_tenure_info.data.borrow())?;
+        if !lp_tenure.is_initialized {
+            lp_tenure = LpTenure {
+                is_initialized: true,
+                swap: *swap_info.key,
+                pool_token_account: *pool_account_info.key,
+                ..LpTenure::default()
+            };
+        } else if lp_tenure.swap != *swap_info.key
+            || lp_tenure.pool_token_account != *pool_account_info.key
+        {
+            return Err(SwapError::IncorrectLpTenure.into());
+        }
+
+        let pool_account =
+            Self::unpack_token_account(pool_account_info, token_swap.token_program_id())?;
+        lp_tenure
+            .sync(pool_account.amount, Clock::get()?.unix_timestamp)
+            .ok_or(SwapError::CalculationFailure)?;
+        Ok(lp_tenure)
     }
 
-    /// Processes an instruction given extra constraint
-    pub fn process_with_constraints(
+    /// Tracks the tenure of pool tokens deposited into a pool account
+    fn record_lp_tenure(
         program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        input: &[u8],
-        swap_constraints: &Option<SwapConstraints>,
+        token_swap: &dyn SwapState,
+        swap_info: &AccountInfo,
+        pool_account_info: &AccountInfo,
+        lp_tenure_info: &AccountInfo,
+        pool_token_amount: u64,
     ) -> ProgramResult {
-        let instruction = SwapInstruction::unpack(input)?;
-        match instruction {
-            SwapInstruction::Initialize(Initialize { fees, swap_curve }) => {
-                msg!("Instruction: Init");
-                Self::process_initialize(program_id, fees, swap_curve, accounts, swap_constraints)
-            }
-            SwapInstruction::Swap(Swap {
-                amount_in,
-                minimum_amount_out,
-            }) => {
-                msg!("Instruction: Swap");
-                Self::process_swap(program_id, amount_in, minimum_amount_out, accounts)
-            }
-            SwapInstruction::DepositAllTokenTypes(DepositAllTokenTypes {
-                pool_token_amount,
-                maximum_token_a_amount,
-                maximum_token_b_amount,
-            }) => {
-                msg!("Instruction: DepositAllTokenTypes");
-                Self::process_deposit_all_token_types(
-                    program_id,
-                    pool_token_amount,
-                    maximum_token_a_amount,
-                    maximum_token_b_amount,
-                    accounts,
-                )
-            }
-            SwapInstruction::WithdrawAllTokenTypes(WithdrawAllTokenTypes {
+        let mut lp_tenure = Self::sync_lp_tenure(
+            program_id,
+            token_swap,
+            swap_info,
+            pool_account_info,
+            lp_tenure_info,
+        )?;
+        lp_tenure
+            .deposit(pool_token_amount, Clock::get()?.unix_timestamp)
+            .ok_or(SwapError::CalculationFailure)?;
+        LpTenure::pack(lp_tenure, &mut lp_tenure_info.data.borrow_mut())
+    }
+
+    /// Unpacks and syncs the LP tenure of the pool account withdrawn from, if
+    /// provided. The LP tenure is required along with the withdraw fee
+    /// schedule, which scales down the withdraw fee with it.
+    fn withdraw_lp_tenure(
+        program_id: &Pubkey,
+        token_swap: &dyn SwapState,
+        swap_info: &AccountInfo,
+        pool_account_info: &AccountInfo,
+        withdraw_fee_schedule_info: Option<&AccountInfo>,
+        lp_tenure_info: Option<&AccountInfo>,
+    ) -> Result<Option<LpTenure>, ProgramError> {
+        match lp_tenure_info {
+            Some(lp_tenure_info) => Self::sync_lp_tenure(
+                program_id,
+                token_swap,
+                swap_info,
+                pool_account_info,
+                lp_tenure_info,
+            )
+            .map(Some),
+            None if withdraw_fee_schedule_info.is_some() => Err(ProgramError::NotEnoughAccountKeys),
+            None => Ok(None),
+        }
+    }
+
+    /// Owner withdraw fee of pool tokens withdrawn from a pool account. If the
+    /// withdraw fee schedule of the swap is provided along with the LP tenure
+    /// of the pool account, the fee is scaled down for the pool tokens tracked
+    /// by the LP tenure.
+    fn owner_withdraw_fee(
+        program_id: &Pubkey,
+        token_swap: &dyn SwapState,
+        swap_info: &AccountInfo,
+        tenure: Option<(&AccountInfo, &LpTenure)>,
+        pool_token_amount: u128,
+    ) -> Result<u128, ProgramError> {
+        let owner_withdraw_fee = token_swap
+            .fees()
+            .owner_withdraw_fee(pool_token_amount)
+            .ok_or(SwapError::FeeCalculationFailure)?;
+        let (withdraw_fee_schedule_info, lp_tenure) = match tenure {
+            Some(tenure) => tenure,
+            None => return Ok(owner_withdraw_fee),
+        };
+
+        if withdraw_fee_schedule_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let withdraw_fee_schedule =
+            WithdrawFeeSchedule::unpack(&withdraw_fee_schedule_info.data.borrow())?;
+        if withdraw_fee_schedule.swap != *swap_info.key {
+            return Err(SwapError::IncorrectWithdrawFeeSchedule.into());
+        }
+
+        let tenured_pool_token_amount =
+            pool_token_amount.min(u128::from(lp_tenure.pool_token_amount));
+        withdraw_fee_schedule
+            .withdraw_fee(
+                owner_withdraw_fee,
                 pool_token_amount,
-                minimum_token_a_amount,
-                minimum_token_b_amount,
-            }) => {
-                msg!("Instruction: WithdrawAllTokenTypes");
-                Self::process_withdraw_all_token_types(
-                    program_id,
-                    pool_token_amount,
-                    minimum_token_a_amount,
-                    minimum_token_b_amount,
-                    accounts,
-                )
+                tenured_pool_token_amount,
+                lp_tenure.tenure(Clock::get()?.unix_timestamp),
+            )
+            .ok_or(SwapError::FeeCalculationFailure.into())
+    }
+
+    /// Checks that the user transfer authority is not the swap authority,
+    /// which the program signs token transfers and burns for
+    fn check_user_transfer_authority(
+        authority_info: &AccountInfo,
+        user_transfer_authority_info: &AccountInfo,
+    ) -> ProgramResult {
+        if authority_info.key == user_transfer_authority_info.key {
+            return Err(SwapError::InvalidUserTransferAuthority.into());
+        }
+        Ok(())
+    }
+
+    /// Derives the address of the position vault of a swap, the pool token
+    /// account holding the pool tokens locked in positions. The vault is its
+    /// own owner, so only issue and redeem, which sign with its seeds, can move
+    /// tokens out of it.
+    pub fn position_vault_id(program_id: &Pubkey, swap: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[&swap.to_bytes()[..32], POSITION_VAULT_SEED], program_id)
+    }
+
+    /// Creates the position vault of a swap on first use, or checks the
+    /// existing one
+    #[allow(clippy::too_many_arguments)]
+    fn create_or_check_position_vault<'a>(
+        swap: &Pubkey,
+        position_vault_info: &AccountInfo<'a>,
+        pool_mint_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        pool_token_program_info: &AccountInfo<'a>,
+        vault_bump_seed: u8,
+    ) -> ProgramResult {
+        if position_vault_info.owner == pool_token_program_info.key {
+            let position_vault =
+                Self::unpack_token_account(position_vault_info, pool_token_program_info.key)?;
+            if position_vault.owner != *position_vault_info.key
+                || position_vault.mint != *pool_mint_info.key
+            {
+                return Err(SwapError::IncorrectPositionVault.into());
+            }
+            return Ok(());
+        }
+
+        let space = {
+            let pool_mint_data = pool_mint_info.data.borrow();
+            let pool_mint = StateWithExtensions::<Mint>::unpack(&pool_mint_data)?;
+            let account_extensions = ExtensionType::get_required_init_account_extensions(
+                &pool_mint.get_extension_types()?,
+            );
+            ExtensionType::try_calculate_account_len::<Account>(&account_extensions)?
+        };
+
+        // the vault address may hold lamports already, so fund it up to rent
+        // exemption before allocating it rather than creating it
+        let required_lamports = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(position_vault_info.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    payer_info.key,
+                    position_vault_info.key,
+                    required_lamports,
+                ),
+                &[
+                    payer_info.clone(),
+                    position_vault_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        let swap_bytes = swap.to_bytes();
+        let vault_signature_seeds = [&swap_bytes[..32], POSITION_VAULT_SEED, &[vault_bump_seed]];
+        let signers = &[&vault_signature_seeds[..]];
+        invoke_signed(
+            &system_instruction::allocate(position_vault_info.key, space as u64),
+            &[position_vault_info.clone(), system_program_info.clone()],
+            signers,
+        )?;
+        invoke_signed(
+            &system_instruction::assign(position_vault_info.key, pool_token_program_info.key),
+            &[position_vault_info.clone(), system_program_info.clone()],
+            signers,
+        )?;
+
+        invoke(
+            &spl_token_2022::instruction::initialize_account3(
+                pool_token_program_info.key,
+                position_vault_info.key,
+                pool_mint_info.key,
+                position_vault_info.key,
+            )?,
+            &[
+                position_vault_info.clone(),
+                pool_mint_info.clone(),
+                pool_token_program_info.clone(),
+            ],
+        )
+    }
+
+    /// Derives the address of the position group of a swap, the Token-2022
+    /// group mint that every position mint of the swap is a member of
+    pub fn position_group_id(program_id: &Pubkey, swap: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[&swap.to_bytes()[..32], POSITION_GROUP_SEED], program_id)
+    }
+
+    /// Creates the position group of a swap on first use, or checks the
+    /// existing one. The group mint has no supply, and the swap authority is
+    /// both its mint and update authority.
+    #[allow(clippy::too_many_arguments)]
+    fn create_or_check_position_group<'a>(
+        swap: &Pubkey,
+        authority_info: &AccountInfo<'a>,
+        position_group_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        position_token_program_info: &AccountInfo<'a>,
+        group_bump_seed: u8,
+        bump_seed: u8,
+    ) -> ProgramResult {
+        if position_group_info.owner == position_token_program_info.key {
+            let position_group_data = position_group_info.data.borrow();
+            let position_group = StateWithExtensions::<Mint>::unpack(&position_group_data)?;
+            let update_authority: Option<Pubkey> = position_group
+                .get_extension::<TokenGroup>()
+                .map_err(|_| SwapError::IncorrectPositionGroup)?
+                .update_authority
+                .into();
+            if update_authority != Some(*authority_info.key) {
+                return Err(SwapError::IncorrectPositionGroup.into());
+            }
+            return Ok(());
+        }
+
+        // the group extension is allocated by the token program when the
+        // group is initialized, so fund the mint for it up front
+        let space =
+            ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::GroupPointer])?;
+        let required_lamports = Rent::get()?
+            .minimum_balance(ExtensionType::try_calculate_account_len::<Mint>(&[
+                ExtensionType::GroupPointer,
+                ExtensionType::TokenGroup,
+            ])?)
+            .saturating_sub(position_group_info.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    payer_info.key,
+                    position_group_info.key,
+                    required_lamports,
+                ),
+                &[
+                    payer_info.clone(),
+                    position_group_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        let swap_bytes = swap.to_bytes();
+        let group_signature_seeds = [&swap_bytes[..32], POSITION_GROUP_SEED, &[group_bump_seed]];
+        let signers = &[&group_signature_seeds[..]];
+        invoke_signed(
+            &system_instruction::allocate(position_group_info.key, space as u64),
+            &[position_group_info.clone(), system_program_info.clone()],
+            signers,
+        )?;
+        invoke_signed(
+            &system_instruction::assign(position_group_info.key, position_token_program_info.key),
+            &[position_group_info.clone(), system_program_info.clone()],
+            signers,
+        )?;
+
+        invoke(
+            &spl_token_2022::extension::group_pointer::instruction::initialize(
+                position_token_program_info.key,
+                position_group_info.key,
+                None,
+                Some(*position_group_info.key),
+            )?,
+            &[
+                position_group_info.clone(),
+                position_token_program_info.clone(),
+            ],
+        )?;
+        invoke(
+            &spl_token_2022::instruction::initialize_mint2(
+                position_token_program_info.key,
+                position_group_info.key,
+                authority_info.key,
+                None,
+                0,
+            )?,
+            &[
+                position_group_info.clone(),
+                position_token_program_info.clone(),
+            ],
+        )?;
+
+        let authority_signature_seeds = [&swap_bytes[..32], &[bump_seed]];
+        let signers = &[&authority_signature_seeds[..]];
+        invoke_signed_wrapper::<TokenError>(
+            &spl_token_group_interface::instruction::initialize_group(
+                position_token_program_info.key,
+                position_group_info.key,
+                position_group_info.key,
+                authority_info.key,
+                Some(*authority_info.key),
+                u64::MAX,
+            ),
+            &[
+                position_group_info.clone(),
+                authority_info.clone(),
+                position_token_program_info.clone(),
+            ],
+            signers,
+        )
+    }
+
+    /// Adds a position mint to the position group of its swap
+    #[allow(clippy::too_many_arguments)]
+    fn initialize_position_group_member<'a>(
+        swap: &Pubkey,
+        authority_info: &AccountInfo<'a>,
+        position_group_info: &AccountInfo<'a>,
+        position_mint_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        position_token_program_info: &AccountInfo<'a>,
+        bump_seed: u8,
+    ) -> ProgramResult {
+        // the member extension is stored in the mint as well
+        let new_position_mint_len = {
+            let position_mint_data = position_mint_info.data.borrow();
+            StateWithExtensions::<Mint>::unpack(&position_mint_data)?
+                .try_get_new_account_len::<TokenGroupMember>()?
+        };
+        let required_lamports = Rent::get()?
+            .minimum_balance(new_position_mint_len)
+            .saturating_sub(position_mint_info.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    payer_info.key,
+                    position_mint_info.key,
+                    required_lamports,
+                ),
+                &[
+                    payer_info.clone(),
+                    position_mint_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        let swap_bytes = swap.to_bytes();
+        let authority_signature_seeds = [&swap_bytes[..32], &[bump_seed]];
+        let signers = &[&authority_signature_seeds[..]];
+        invoke_signed_wrapper::<TokenError>(
+            &spl_token_group_interface::instruction::initialize_member(
+                position_token_program_info.key,
+                position_mint_info.key,
+                position_mint_info.key,
+                authority_info.key,
+                position_group_info.key,
+                authority_info.key,
+            ),
+            &[
+                position_mint_info.clone(),
+                authority_info.clone(),
+                position_group_info.clone(),
+                position_token_program_info.clone(),
+            ],
+            signers,
+        )
+    }
+
+    /// Initializes the Token-2022 metadata of a position mint, recording the
+    /// entry data of the position as additional metadata fields
+    #[allow(clippy::too_many_arguments)]
+    fn initialize_position_metadata<'a>(
+        swap: &Pubkey,
+        authority_info: &AccountInfo<'a>,
+        position_info: &AccountInfo<'a>,
+        position_mint_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        position_token_program_info: &AccountInfo<'a>,
+        position: &LpPosition,
+        bump_seed: u8,
+    ) -> ProgramResult {
+        let token_metadata = TokenMetadata {
+            update_authority: Some(*authority_info.key).try_into()?,
+            mint: *position_mint_info.key,
+            name: POSITION_METADATA_NAME.to_string(),
+            symbol: POSITION_METADATA_SYMBOL.to_string(),
+            uri: String::new(),
+            additional_metadata: position.metadata_fields(position_info.key),
+        };
+
+        // the metadata is stored in the mint, so top it up to stay rent exempt
+        // with the metadata of the position
+        let new_position_mint_len = {
+            let position_mint_data = position_mint_info.data.borrow();
+            StateWithExtensions::<Mint>::unpack(&position_mint_data)?
+                .try_get_new_account_len_for_variable_len_extension(&token_metadata)?
+        };
+        let required_lamports = Rent::get()?
+            .minimum_balance(new_position_mint_len)
+            .saturating_sub(position_mint_info.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    payer_info.key,
+                    position_mint_info.key,
+                    required_lamports,
+                ),
+                &[
+                    payer_info.clone(),
+                    position_mint_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        let swap_bytes = swap.to_bytes();
+        let authority_signature_seeds = [&swap_bytes[..32], &[bump_seed]];
+        let signers = &[&authority_signature_seeds[..]];
+        invoke_signed_wrapper::<TokenError>(
+            &spl_token_metadata_interface::instruction::initialize(
+                position_token_program_info.key,
+                position_mint_info.key,
+                authority_info.key,
+                position_mint_info.key,
+                authority_info.key,
+                token_metadata.name,
+                token_metadata.symbol,
+                token_metadata.uri,
+            ),
+            &[
+                position_mint_info.clone(),
+                authority_info.clone(),
+                position_token_program_info.clone(),
+            ],
+            signers,
+        )?;
+        for (key, value) in token_metadata.additional_metadata {
+            invoke_signed_wrapper::<TokenError>(
+                &spl_token_metadata_interface::instruction::update_field(
+                    position_token_program_info.key,
+                    position_mint_info.key,
+                    authority_info.key,
+                    Field::Key(key),
+                    value,
+                ),
+                &[
+                    position_mint_info.clone(),
+                    authority_info.clone(),
+                    position_token_program_info.clone(),
+                ],
+                signers,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Processes an [IssuePosition](enum.Instruction.html).
+    pub fn process_issue_position(
+        program_id: &Pubkey,
+        pool_token_amount: u64,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let user_transfer_authority_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let source_info = next_account_info(account_info_iter)?;
+        let position_vault_info = next_account_info(account_info_iter)?;
+        let position_group_info = next_account_info(account_info_iter)?;
+        let token_a_info = next_account_info(account_info_iter)?;
+        let token_b_info = next_account_info(account_info_iter)?;
+        let position_info = next_account_info(account_info_iter)?;
+        let position_mint_info = next_account_info(account_info_iter)?;
+        let position_token_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let pool_token_program_info = next_account_info(account_info_iter)?;
+        let position_token_program_info = next_account_info(account_info_iter)?;
+
+        if pool_token_amount == 0 {
+            return Err(SwapError::InvalidInput.into());
+        }
+
+        let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        Self::check_accounts(
+            token_swap.as_ref(),
+            program_id,
+            swap_info,
+            authority_info,
+            token_a_info,
+            token_b_info,
+            pool_mint_info,
+            pool_token_program_info,
+            None,
+            None,
+            None,
+        )?;
+        Self::check_user_transfer_authority(authority_info, user_transfer_authority_info)?;
+        let (position_vault_key, vault_bump_seed) =
+            Self::position_vault_id(program_id, swap_info.key);
+        if *position_vault_info.key != position_vault_key {
+            return Err(SwapError::IncorrectPositionVault.into());
+        }
+        if source_info.key == position_vault_info.key {
+            return Err(SwapError::InvalidInput.into());
+        }
+        let (position_group_key, group_bump_seed) =
+            Self::position_group_id(program_id, swap_info.key);
+        if *position_group_info.key != position_group_key {
+            return Err(SwapError::IncorrectPositionGroup.into());
+        }
+
+        if position_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if LpPosition::unpack_unchecked(&position_info.data.borrow())?.is_initialized {
+            return Err(SwapError::AlreadyInUse.into());
+        }
+
+        if *position_token_program_info.key != spl_token_2022::id() {
+            return Err(SwapError::IncorrectTokenProgramId.into());
+        }
+        {
+            let position_mint_data = position_mint_info.data.borrow();
+            let position_mint = Self::unpack_mint_with_extensions(
+                &position_mint_data,
+                position_mint_info.owner,
+                position_token_program_info.key,
+            )?;
+            if position_mint.base.mint_authority != COption::Some(*authority_info.key) {
+                return Err(SwapError::InvalidOwner.into());
+            }
+            if position_mint.base.freeze_authority.is_some() {
+                return Err(SwapError::InvalidFreezeAuthority.into());
+            }
+            if position_mint.base.supply != 0 || position_mint.base.decimals != 0 {
+                return Err(SwapError::InvalidPositionMint.into());
+            }
+            let metadata_address: Option<Pubkey> = position_mint
+                .get_extension::<MetadataPointer>()
+                .map_err(|_| SwapError::InvalidPositionMint)?
+                .metadata_address
+                .into();
+            if metadata_address != Some(*position_mint_info.key) {
+                return Err(SwapError::InvalidPositionMint.into());
+            }
+            let member_address: Option<Pubkey> = position_mint
+                .get_extension::<GroupMemberPointer>()
+                .map_err(|_| SwapError::InvalidPositionMint)?
+                .member_address
+                .into();
+            if member_address != Some(*position_mint_info.key) {
+                return Err(SwapError::InvalidPositionMint.into());
+            }
+        }
+
+        let token_a = Self::unpack_token_account(token_a_info, token_swap.token_program_id())?;
+        let token_b = Self::unpack_token_account(token_b_info, token_swap.token_program_id())?;
+        let pool_mint = Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?;
+        let results = token_swap
+            .swap_curve()
+            .calculator
+            .pool_tokens_to_trading_tokens(
+                u128::from(pool_token_amount),
+                u128::from(pool_mint.supply),
+                u128::from(token_a.amount),
+                u128::from(token_b.amount),
+                RoundDirection::Floor,
+            )
+            .ok_or(SwapError::ZeroTradingTokens)?;
+
+        Self::create_or_check_position_vault(
+            swap_info.key,
+            position_vault_info,
+            pool_mint_info,
+            payer_info,
+            system_program_info,
+            pool_token_program_info,
+            vault_bump_seed,
+        )?;
+        Self::token_transfer(
+            swap_info.key,
+            pool_token_program_info.clone(),
+            source_info.clone(),
+            pool_mint_info.clone(),
+            position_vault_info.clone(),
+            user_transfer_authority_info.clone(),
+            token_swap.bump_seed(),
+            pool_token_amount,
+            pool_mint.decimals,
+        )?;
+        Self::token_mint_to(
+            swap_info.key,
+            position_token_program_info.clone(),
+            position_mint_info.clone(),
+            position_token_info.clone(),
+            authority_info.clone(),
+            token_swap.bump_seed(),
+            1,
+        )?;
+
+        let position = LpPosition {
+            is_initialized: true,
+            swap: *swap_info.key,
+            position_mint: *position_mint_info.key,
+            pool_token_amount,
+            entry_token_a_amount: to_u64(results.token_a_amount)?,
+            entry_token_b_amount: to_u64(results.token_b_amount)?,
+            entry_pool_token_supply: pool_mint.supply,
+        };
+        Self::initialize_position_metadata(
+            swap_info.key,
+            authority_info,
+            position_info,
+            position_mint_info,
+            payer_info,
+            system_program_info,
+            position_token_program_info,
+            &position,
+            token_swap.bump_seed(),
+        )?;
+        Self::create_or_check_position_group(
+            swap_info.key,
+            authority_info,
+            position_group_info,
+            payer_info,
+            system_program_info,
+            position_token_program_info,
+            group_bump_seed,
+            token_swap.bump_seed(),
+        )?;
+        Self::initialize_position_group_member(
+            swap_info.key,
+            authority_info,
+            position_group_info,
+            position_mint_info,
+            payer_info,
+            system_program_info,
+            position_token_program_info,
+            token_swap.bump_seed(),
+        )?;
+        LpPosition::pack(position, &mut position_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Processes a [RedeemPosition](enum.Instruction.html).
+    pub fn process_redeem_position(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let position_owner_info = next_account_info(account_info_iter)?;
+        let position_info = next_account_info(account_info_iter)?;
+        let position_mint_info = next_account_info(account_info_iter)?;
+        let position_token_info = next_account_info(account_info_iter)?;
+        let position_vault_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let pool_token_program_info = next_account_info(account_info_iter)?;
+        let position_token_program_info = next_account_info(account_info_iter)?;
+
+        let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        if swap_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if *authority_info.key
+            != Self::authority_id(program_id, swap_info.key, token_swap.bump_seed())?
+        {
+            return Err(SwapError::InvalidProgramAddress.into());
+        }
+        Self::check_user_transfer_authority(authority_info, position_owner_info)?;
+        if *pool_mint_info.key != *token_swap.pool_mint() {
+            return Err(SwapError::IncorrectPoolMint.into());
+        }
+        if *pool_token_program_info.key != *token_swap.token_program_id() {
+            return Err(SwapError::IncorrectTokenProgramId.into());
+        }
+        if *position_token_program_info.key != spl_token_2022::id() {
+            return Err(SwapError::IncorrectTokenProgramId.into());
+        }
+        let (position_vault_key, vault_bump_seed) =
+            Self::position_vault_id(program_id, swap_info.key);
+        if *position_vault_info.key != position_vault_key {
+            return Err(SwapError::IncorrectPositionVault.into());
+        }
+        if position_owner_info.key == position_vault_info.key {
+            return Err(SwapError::InvalidUserTransferAuthority.into());
+        }
+
+        if position_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let position = LpPosition::unpack(&position_info.data.borrow())?;
+        if position.swap != *swap_info.key || position.position_mint != *position_mint_info.key {
+            return Err(SwapError::IncorrectPosition.into());
+        }
+
+        // the burn is signed by the position owner, proving it holds the NFT
+        invoke(
+            &spl_token_2022::instruction::burn(
+                position_token_program_info.key,
+                position_token_info.key,
+                position_mint_info.key,
+                position_owner_info.key,
+                &[],
+                1,
+            )?,
+            &[
+                position_token_info.clone(),
+                position_mint_info.clone(),
+                position_owner_info.clone(),
+                position_token_program_info.clone(),
+            ],
+        )?;
+
+        let swap_bytes = swap_info.key.to_bytes();
+        let vault_signature_seeds = [&swap_bytes[..32], POSITION_VAULT_SEED, &[vault_bump_seed]];
+        let signers = &[&vault_signature_seeds[..]];
+        invoke_signed_wrapper::<TokenError>(
+            &spl_token_2022::instruction::transfer_checked(
+                pool_token_program_info.key,
+                position_vault_info.key,
+                pool_mint_info.key,
+                destination_info.key,
+                position_vault_info.key,
+                &[],
+                position.pool_token_amount,
+                Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?.decimals,
+            )?,
+            &[
+                position_vault_info.clone(),
+                pool_mint_info.clone(),
+                destination_info.clone(),
+                pool_token_program_info.clone(),
+            ],
+            signers,
+        )?;
+
+        LpPosition::pack(LpPosition::default(), &mut position_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Checks that the signer owns the pool fee account of the swap
+    fn check_pool_fee_owner(
+        program_id: &Pubkey,
+        swap_info: &AccountInfo,
+        pool_fee_account_info: &AccountInfo,
+        pool_fee_owner_info: &AccountInfo,
+    ) -> ProgramResult {
+        if swap_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        if *pool_fee_account_info.key != *token_swap.pool_fee_account() {
+            return Err(SwapError::IncorrectFeeAccount.into());
+        }
+        let pool_fee_account =
+            Self::unpack_token_account(pool_fee_account_info, token_swap.token_program_id())?;
+        if pool_fee_account.owner != *pool_fee_owner_info.key {
+            return Err(SwapError::InvalidOwner.into());
+        }
+        if !pool_fee_owner_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Ok(())
+    }
+
+    /// Processes a [RegisterKeeper](enum.Instruction.html).
+    pub fn process_register_keeper(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let pool_fee_account_info = next_account_info(account_info_iter)?;
+        let pool_fee_owner_info = next_account_info(account_info_iter)?;
+        let keeper_registration_info = next_account_info(account_info_iter)?;
+        let keeper_info = next_account_info(account_info_iter)?;
+
+        Self::check_pool_fee_owner(
+            program_id,
+            swap_info,
+            pool_fee_account_info,
+            pool_fee_owner_info,
+        )?;
+
+        if keeper_registration_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if KeeperRegistration::unpack_unchecked(&keeper_registration_info.data.borrow())?
+            .is_initialized
+        {
+            return Err(SwapError::AlreadyInUse.into());
+        }
+
+        let registration = KeeperRegistration {
+            is_initialized: true,
+            swap: *swap_info.key,
+            keeper: *keeper_info.key,
+        };
+        KeeperRegistration::pack(
+            registration,
+            &mut keeper_registration_info.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Processes a [DeregisterKeeper](enum.Instruction.html).
+    pub fn process_deregister_keeper(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let pool_fee_account_info = next_account_info(account_info_iter)?;
+        let pool_fee_owner_info = next_account_info(account_info_iter)?;
+        let keeper_registration_info = next_account_info(account_info_iter)?;
+
+        Self::check_pool_fee_owner(
+            program_id,
+            swap_info,
+            pool_fee_account_info,
+            pool_fee_owner_info,
+        )?;
+
+        if keeper_registration_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let registration = KeeperRegistration::unpack(&keeper_registration_info.data.borrow())?;
+        if registration.swap != *swap_info.key {
+            return Err(SwapError::KeeperNotRegistered.into());
+        }
+
+        KeeperRegistration::pack(
+            KeeperRegistration::default(),
+            &mut keeper_registration_info.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Unpacks the protocol fee stored after the swap state, if the swap
+    /// account has an initialized one
+    fn unpack_protocol_fee(swap_info: &AccountInfo) -> Result<Option<ProtocolFee>, ProgramError> {
+        match swap_info
+            .data
+            .borrow()
+            .get(ProtocolFee::OFFSET..ProtocolFee::SWAP_ACCOUNT_LEN)
+        {
+            Some(data) => {
+                let protocol_fee = ProtocolFee::unpack_unchecked(data)?;
+                Ok(protocol_fee.is_initialized.then_some(protocol_fee))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Packs the protocol fee after the swap state
+    fn pack_protocol_fee(protocol_fee: ProtocolFee, swap_info: &AccountInfo) -> ProgramResult {
+        let mut data = swap_info.data.borrow_mut();
+        let data = data
+            .get_mut(ProtocolFee::OFFSET..ProtocolFee::SWAP_ACCOUNT_LEN)
+            .ok_or(SwapError::ProtocolFeeUnavailable)?;
+        ProtocolFee::pack(protocol_fee, data)
+    }
+
+    /// Processes an [InitializeProtocolFee](enum.Instruction.html).
+    pub fn process_initialize_protocol_fee(
+        program_id: &Pubkey,
+        timelock_slots: u64,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let pool_fee_account_info = next_account_info(account_info_iter)?;
+        let pool_fee_owner_info = next_account_info(account_info_iter)?;
+        let protocol_fee_authority_info = next_account_info(account_info_iter)?;
+
+        Self::check_pool_fee_owner(
+            program_id,
+            swap_info,
+            pool_fee_account_info,
+            pool_fee_owner_info,
+        )?;
+
+        if timelock_slots == 0 {
+            return Err(SwapError::InvalidProtocolFee.into());
+        }
+        if swap_info.data_len() < ProtocolFee::SWAP_ACCOUNT_LEN {
+            return Err(SwapError::ProtocolFeeUnavailable.into());
+        }
+        if Self::unpack_protocol_fee(swap_info)?.is_some() {
+            return Err(SwapError::AlreadyInUse.into());
+        }
+
+        let protocol_fee = ProtocolFee {
+            is_initialized: true,
+            authority: *protocol_fee_authority_info.key,
+            timelock_slots,
+            ..ProtocolFee::default()
+        };
+        Self::pack_protocol_fee(protocol_fee, swap_info)
+    }
+
+    /// Processes a [ProposeProtocolFee](enum.Instruction.html).
+    pub fn process_propose_protocol_fee(
+        program_id: &Pubkey,
+        fee_bps: u16,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let protocol_fee_authority_info = next_account_info(account_info_iter)?;
+        let new_protocol_fee_authority_info = next_account_info(account_info_iter)?;
+        let recipient_info = next_account_info(account_info_iter)?;
+
+        if swap_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        let mut protocol_fee =
+            Self::unpack_protocol_fee(swap_info)?.ok_or(ProgramError::UninitializedAccount)?;
+
+        if protocol_fee.authority != *protocol_fee_authority_info.key {
+            return Err(SwapError::InvalidOwner.into());
+        }
+        if !protocol_fee_authority_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if fee_bps > MAX_PROTOCOL_FEE_BPS {
+            return Err(SwapError::InvalidProtocolFee.into());
+        }
+        let recipient = Self::unpack_token_account(recipient_info, token_swap.token_program_id())?;
+        if recipient.mint != *token_swap.pool_mint() {
+            return Err(SwapError::IncorrectPoolMint.into());
+        }
+
+        protocol_fee.pending_authority = *new_protocol_fee_authority_info.key;
+        protocol_fee.pending_recipient = *recipient_info.key;
+        protocol_fee.pending_fee_bps = fee_bps;
+        protocol_fee.pending_execute_slot = Clock::get()?
+            .slot
+            .checked_add(protocol_fee.timelock_slots)
+            .ok_or(SwapError::CalculationFailure)?;
+        Self::pack_protocol_fee(protocol_fee, swap_info)
+    }
+
+    /// Processes an [ExecuteProtocolFee](enum.Instruction.html).
+    pub fn process_execute_protocol_fee(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+
+        if swap_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let mut protocol_fee =
+            Self::unpack_protocol_fee(swap_info)?.ok_or(ProgramError::UninitializedAccount)?;
+
+        if protocol_fee.pending_execute_slot == 0 {
+            return Err(SwapError::NoPendingProtocolFee.into());
+        }
+        if Clock::get()?.slot < protocol_fee.pending_execute_slot {
+            return Err(SwapError::ProtocolFeeTimelocked.into());
+        }
+
+        protocol_fee.authority = protocol_fee.pending_authority;
+        protocol_fee.recipient = protocol_fee.pending_recipient;
+        protocol_fee.fee_bps = protocol_fee.pending_fee_bps;
+        protocol_fee.pending_authority = Pubkey::default();
+        protocol_fee.pending_recipient = Pubkey::default();
+        protocol_fee.pending_fee_bps = 0;
+        protocol_fee.pending_execute_slot = 0;
+        Self::pack_protocol_fee(protocol_fee, swap_info)
+    }
+
+    /// Processes a [CollectProtocolFee](enum.Instruction.html).
+    pub fn process_collect_protocol_fee(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let recipient_info = next_account_info(account_info_iter)?;
+        let pool_token_program_info = next_account_info(account_info_iter)?;
+
+        if swap_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        if *authority_info.key
+            != Self::authority_id(program_id, swap_info.key, token_swap.bump_seed())?
+        {
+            return Err(SwapError::InvalidProgramAddress.into());
+        }
+        if *pool_mint_info.key != *token_swap.pool_mint() {
+            return Err(SwapError::IncorrectPoolMint.into());
+        }
+        let mut protocol_fee =
+            Self::unpack_protocol_fee(swap_info)?.ok_or(ProgramError::UninitializedAccount)?;
+        if *recipient_info.key != protocol_fee.recipient {
+            return Err(SwapError::IncorrectProtocolFeeRecipient.into());
+        }
+
+        let pool_tokens_owed = protocol_fee.pool_tokens_owed;
+        if pool_tokens_owed > 0 {
+            protocol_fee.pool_tokens_owed = 0;
+            Self::pack_protocol_fee(protocol_fee, swap_info)?;
+            Self::token_mint_to(
+                swap_info.key,
+                pool_token_program_info.clone(),
+                pool_mint_info.clone(),
+                recipient_info.clone(),
+                authority_info.clone(),
+                token_swap.bump_seed(),
+                pool_tokens_owed,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Processes a [SetWithdrawFeeSchedule](enum.Instruction.html).
+    pub fn process_set_withdraw_fee_schedule(
+        program_id: &Pubkey,
+        tiers: &[WithdrawFeeTier],
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let pool_fee_account_info = next_account_info(account_info_iter)?;
+        let pool_fee_owner_info = next_account_info(account_info_iter)?;
+        let withdraw_fee_schedule_info = next_account_info(account_info_iter)?;
+
+        Self::check_pool_fee_owner(
+            program_id,
+            swap_info,
+            pool_fee_account_info,
+            pool_fee_owner_info,
+        )?;
+
+        if withdraw_fee_schedule_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let withdraw_fee_schedule =
+            WithdrawFeeSchedule::unpack_unchecked(&withdraw_fee_schedule_info.data.borrow())?;
+        if withdraw_fee_schedule.is_initialized && withdraw_fee_schedule.swap != *swap_info.key {
+            return Err(SwapError::IncorrectWithdrawFeeSchedule.into());
+        }
+        WithdrawFeeSchedule::validate(tiers)?;
+
+        let mut withdraw_fee_schedule = WithdrawFeeSchedule {
+            is_initialized: true,
+            swap: *swap_info.key,
+            tiers_count: tiers.len() as u8,
+            ..WithdrawFeeSchedule::default()
+        };
+        withdraw_fee_schedule.tiers[..tiers.len()].copy_from_slice(tiers);
+        WithdrawFeeSchedule::pack(
+            withdraw_fee_schedule,
+            &mut withdraw_fee_schedule_info.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Checks that the flash swap is called directly by the transaction and
+    /// that the next token-swap instruction repays it
+    fn check_flash_swap_repay(
+        program_id: &Pubkey,
+        swap: &Pubkey,
+        swap_source: &Pubkey,
+        amount_in: u64,
+        instructions_sysvar_info: &AccountInfo,
+    ) -> ProgramResult {
+        let current_index = load_current_index_checked(instructions_sysvar_info)?;
+        let current_instruction =
+            load_instruction_at_checked(current_index as usize, instructions_sysvar_info)?;
+        if current_instruction.program_id != *program_id {
+            return Err(SwapError::InvalidFlashSwap.into());
+        }
+
+        let mut index = current_index as usize;
+        loop {
+            index = index.checked_add(1).ok_or(SwapError::InvalidFlashSwap)?;
+            let instruction = load_instruction_at_checked(index, instructions_sysvar_info)
+                .map_err(|_| SwapError::InvalidFlashSwap)?;
+            if instruction.program_id != *program_id {
+                continue;
+            }
+            let repay_amount_in = match SwapInstruction::unpack(&instruction.data) {
+                Ok(SwapInstruction::FlashSwapRepay(FlashSwapRepay { amount_in })) => amount_in,
+                _ => return Err(SwapError::InvalidFlashSwap.into()),
+            };
+            if repay_amount_in != amount_in
+                || instruction.accounts.first().map(|meta| meta.pubkey) != Some(*swap)
+                || instruction.accounts.get(4).map(|meta| meta.pubkey) != Some(*swap_source)
+            {
+                return Err(SwapError::InvalidFlashSwap.into());
+            }
+            return Ok(());
+        }
+    }
+
+    /// Processes a [FlashSwap](enum.Instruction.html).
+    pub fn process_flash_swap(
+        program_id: &Pubkey,
+        amount_in: u64,
+        amount_out: u64,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let keeper_info = next_account_info(account_info_iter)?;
+        let keeper_registration_info = next_account_info(account_info_iter)?;
+        let swap_source_info = next_account_info(account_info_iter)?;
+        let swap_destination_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let source_token_mint_info = next_account_info(account_info_iter)?;
+        let destination_token_mint_info = next_account_info(account_info_iter)?;
+        let destination_token_program_info = next_account_info(account_info_iter)?;
+        let instructions_sysvar_info = next_account_info(account_info_iter)?;
+
+        if swap_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        let swap_curve = Self::current_swap_curve(token_swap.swap_curve())?;
+
+        if *authority_info.key
+            != Self::authority_id(program_id, swap_info.key, token_swap.bump_seed())?
+        {
+            return Err(SwapError::InvalidProgramAddress.into());
+        }
+        if !(*swap_source_info.key == *token_swap.token_a_account()
+            || *swap_source_info.key == *token_swap.token_b_account())
+        {
+            return Err(SwapError::IncorrectSwapAccount.into());
+        }
+        if !(*swap_destination_info.key == *token_swap.token_a_account()
+            || *swap_destination_info.key == *token_swap.token_b_account())
+        {
+            return Err(SwapError::IncorrectSwapAccount.into());
+        }
+        if *swap_source_info.key == *swap_destination_info.key {
+            return Err(SwapError::InvalidInput.into());
+        }
+        if swap_destination_info.key == destination_info.key {
+            return Err(SwapError::InvalidInput.into());
+        }
+
+        if !keeper_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if keeper_registration_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let registration = KeeperRegistration::unpack(&keeper_registration_info.data.borrow())
+            .map_err(|_| SwapError::KeeperNotRegistered)?;
+        if registration.swap != *swap_info.key || registration.keeper != *keeper_info.key {
+            return Err(SwapError::KeeperNotRegistered.into());
+        }
+
+        Self::check_flash_swap_repay(
+            program_id,
+            swap_info.key,
+            swap_source_info.key,
+            amount_in,
+            instructions_sysvar_info,
+        )?;
+
+        let source_account =
+            Self::unpack_token_account(swap_source_info, token_swap.token_program_id())?;
+        let dest_account =
+            Self::unpack_token_account(swap_destination_info, token_swap.token_program_id())?;
+        if source_account.mint != *source_token_mint_info.key {
+            return Err(SwapError::IncorrectSwapAccount.into());
+        }
+
+        // Take transfer fees into account for the amount repaid later
+        let actual_amount_in = {
+            let source_mint_data = source_token_mint_info.data.borrow();
+            let source_mint = Self::unpack_mint_with_extensions(
+                &source_mint_data,
+                source_token_mint_info.owner,
+                token_swap.token_program_id(),
+            )?;
+
+            if let Ok(transfer_fee_config) = source_mint.get_extension::<TransferFeeConfig>() {
+                amount_in.saturating_sub(
+                    transfer_fee_config
+                        .calculate_epoch_fee(Clock::get()?.epoch, amount_in)
+                        .ok_or(SwapError::FeeCalculationFailure)?,
+                )
+            } else {
+                amount_in
+            }
+        };
+
+        // Price the borrow on the current reserves at keeper fees
+        let trade_direction = if *swap_source_info.key == *token_swap.token_a_account() {
+            TradeDirection::AtoB
+        } else {
+            TradeDirection::BtoA
+        };
+        let result = swap_curve
+            .swap(
+                u128::from(actual_amount_in),
+                u128::from(source_account.amount),
+                u128::from(dest_account.amount),
+                trade_direction,
+                &token_swap.fees().keeper_fees(),
+            )
+            .ok_or(SwapError::ZeroTradingTokens)?;
+        if amount_out > to_u64(result.destination_amount_swapped)? {
+            return Err(SwapError::ExceededSlippage.into());
+        }
+
+        let destination_mint_decimals = {
+            let destination_mint_data = destination_token_mint_info.data.borrow();
+            Self::unpack_mint_with_extensions(
+                &destination_mint_data,
+                destination_token_mint_info.owner,
+                token_swap.token_program_id(),
+            )?
+            .base
+            .decimals
+        };
+
+        Self::token_transfer(
+            swap_info.key,
+            destination_token_program_info.clone(),
+            swap_destination_info.clone(),
+            destination_token_mint_info.clone(),
+            destination_info.clone(),
+            authority_info.clone(),
+            token_swap.bump_seed(),
+            amount_out,
+            destination_mint_decimals,
+        )?;
+
+        Ok(())
+    }
+
+    /// Processes a [FlashSwapRepay](enum.Instruction.html).
+    pub fn process_flash_swap_repay(
+        program_id: &Pubkey,
+        amount_in: u64,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let user_transfer_authority_info = next_account_info(account_info_iter)?;
+        let source_info = next_account_info(account_info_iter)?;
+        let swap_source_info = next_account_info(account_info_iter)?;
+        let source_token_mint_info = next_account_info(account_info_iter)?;
+        let source_token_program_info = next_account_info(account_info_iter)?;
+
+        if swap_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+
+        if *authority_info.key
+            != Self::authority_id(program_id, swap_info.key, token_swap.bump_seed())?
+        {
+            return Err(SwapError::InvalidProgramAddress.into());
+        }
+        if !(*swap_source_info.key == *token_swap.token_a_account()
+            || *swap_source_info.key == *token_swap.token_b_account())
+        {
+            return Err(SwapError::IncorrectSwapAccount.into());
+        }
+        if swap_source_info.key == source_info.key {
+            return Err(SwapError::InvalidInput.into());
+        }
+
+        let source_mint_decimals = {
+            let source_mint_data = source_token_mint_info.data.borrow();
+            Self::unpack_mint_with_extensions(
+                &source_mint_data,
+                source_token_mint_info.owner,
+                token_swap.token_program_id(),
+            )?
+            .base
+            .decimals
+        };
+
+        Self::token_transfer(
+            swap_info.key,
+            source_token_program_info.clone(),
+            source_info.clone(),
+            source_token_mint_info.clone(),
+            swap_source_info.clone(),
+            user_transfer_authority_info.clone(),
+            token_swap.bump_seed(),
+            amount_in,
+            source_mint_decimals,
+        )?;
+
+        Ok(())
+    }
+
+    /// Processes an [Instruction](enum.Instruction.html).
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+        Self::process_with_constraints(program_id, accounts, input, &SWAP_CONSTRAINTS)
+    }
+
+    /// Processes an instruction given extra constraint
+    pub fn process_with_constraints(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        input: &[u8],
+        swap_constraints: &Option<SwapConstraints>,
+    ) -> ProgramResult {
+        let instruction = SwapInstruction::unpack(input)?;
+        match instruction {
+            SwapInstruction::Initialize(Initialize { fees, swap_curve }) => {
+                msg!("Instruction: Init");
+                Self::process_initialize(program_id, fees, swap_curve, accounts, swap_constraints)
+            }
+            SwapInstruction::Swap(Swap {
+                amount_in,
+                minimum_amount_out,
+            }) => {
+                msg!("Instruction: Swap");
+                Self::process_swap(program_id, amount_in, minimum_amount_out, accounts)
+            }
+            SwapInstruction::DepositAllTokenTypes(DepositAllTokenTypes {
+                pool_token_amount,
+                maximum_token_a_amount,
+                maximum_token_b_amount,
+            }) => {
+                msg!("Instruction: DepositAllTokenTypes");
+                Self::process_deposit_all_token_types(
+                    program_id,
+                    pool_token_amount,
+                    maximum_token_a_amount,
+                    maximum_token_b_amount,
+                    accounts,
+                )
+            }
+            SwapInstruction::WithdrawAllTokenTypes(WithdrawAllTokenTypes {
+                pool_token_amount,
+                minimum_token_a_amount,
+                minimum_token_b_amount,
+            }) => {
+                msg!("Instruction: WithdrawAllTokenTypes");
+                Self::process_withdraw_all_token_types(
+                    program_id,
+                    pool_token_amount,
+                    minimum_token_a_amount,
+                    minimum_token_b_amount,
+                    accounts,
+                )
             }
             SwapInstruction::DepositSingleTokenTypeExactAmountIn(
                 DepositSingleTokenTypeExactAmountIn {
@@ -1247,6 +3005,65 @@ impl Processor {
                     accounts,
                 )
             }
+            SwapInstruction::IssuePosition(IssuePosition { pool_token_amount }) => {
+                msg!("Instruction: IssuePosition");
+                Self::process_issue_position(program_id, pool_token_amount, accounts)
+            }
+            SwapInstruction::RedeemPosition => {
+                msg!("Instruction: RedeemPosition");
+                Self::process_redeem_position(program_id, accounts)
+            }
+            SwapInstruction::RegisterKeeper => {
+                msg!("Instruction: RegisterKeeper");
+                Self::process_register_keeper(program_id, accounts)
+            }
+            SwapInstruction::DeregisterKeeper => {
+                msg!("Instruction: DeregisterKeeper");
+                Self::process_deregister_keeper(program_id, accounts)
+            }
+            SwapInstruction::FlashSwap(FlashSwap {
+                amount_in,
+                amount_out,
+            }) => {
+                msg!("Instruction: FlashSwap");
+                Self::process_flash_swap(program_id, amount_in, amount_out, accounts)
+            }
+            SwapInstruction::FlashSwapRepay(FlashSwapRepay { amount_in }) => {
+                msg!("Instruction: FlashSwapRepay");
+                Self::process_flash_swap_repay(program_id, amount_in, accounts)
+            }
+            SwapInstruction::InitializeProtocolFee(InitializeProtocolFee { timelock_slots }) => {
+                msg!("Instruction: InitializeProtocolFee");
+                Self::process_initialize_protocol_fee(program_id, timelock_slots, accounts)
+            }
+            SwapInstruction::ProposeProtocolFee(ProposeProtocolFee { fee_bps }) => {
+                msg!("Instruction: ProposeProtocolFee");
+                Self::process_propose_protocol_fee(program_id, fee_bps, accounts)
+            }
+            SwapInstruction::ExecuteProtocolFee => {
+                msg!("Instruction: ExecuteProtocolFee");
+                Self::process_execute_protocol_fee(program_id, accounts)
+            }
+            SwapInstruction::CollectProtocolFee => {
+                msg!("Instruction: CollectProtocolFee");
+                Self::process_collect_protocol_fee(program_id, accounts)
+            }
+            SwapInstruction::SetWithdrawFeeSchedule(SetWithdrawFeeSchedule { tiers }) => {
+                msg!("Instruction: SetWithdrawFeeSchedule");
+                Self::process_set_withdraw_fee_schedule(program_id, &tiers, accounts)
+            }
+            SwapInstruction::ZapIn(ZapIn {
+                source_token_amount,
+                minimum_pool_token_amount,
+            }) => {
+                msg!("Instruction: ZapIn");
+                Self::process_zap_in(
+                    program_id,
+                    source_token_amount,
+                    minimum_pool_token_amount,
+                    accounts,
+                )
+            }
         }
     }
 }
@@ -1278,16 +3095,24 @@ mod tests {
                 calculator::{CurveCalculator, INITIAL_SWAP_POOL_AMOUNT},
                 constant_price::ConstantPriceCurve,
                 constant_product::ConstantProductCurve,
+                liquidity_bootstrapping::LiquidityBootstrappingCurve,
                 offset::OffsetCurve,
             },
             instruction::{
-                deposit_all_token_types, deposit_single_token_type_exact_amount_in, initialize,
-                swap, withdraw_all_token_types, withdraw_single_token_type_exact_amount_out,
+                collect_protocol_fee, deposit_all_token_types,
+                deposit_single_token_type_exact_amount_in, execute_protocol_fee, initialize,
+                initialize_protocol_fee, issue_position, propose_protocol_fee,
+                set_withdraw_fee_schedule, swap, withdraw_all_token_types,
+                withdraw_single_token_type_exact_amount_out, zap_in,
             },
         },
         solana_program::{
-            clock::Clock, entrypoint::SUCCESS, instruction::Instruction, program_pack::Pack,
-            program_stubs, rent::Rent,
+            clock::{Clock, UnixTimestamp},
+            entrypoint::SUCCESS,
+            instruction::{AccountMeta, Instruction},
+            program_pack::Pack,
+            program_stubs,
+            rent::Rent,
         },
         solana_sdk::account::{
             create_account_for_test, create_is_signer_account_infos, Account as SolanaAccount,
@@ -1366,12 +3191,23 @@ mod tests {
 
         fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
             unsafe {
-                *(var_addr as *mut _ as *mut Clock) = Clock::default();
+                *(var_addr as *mut _ as *mut Clock) = Clock {
+                    slot: TEST_CLOCK_SLOT.with(|slot| slot.get()),
+                    unix_timestamp: TEST_CLOCK_UNIX_TIMESTAMP.with(|timestamp| timestamp.get()),
+                    ..Clock::default()
+                };
             }
             SUCCESS
         }
     }
 
+    thread_local! {
+        // Slot returned by the clock sysvar stub for the current test
+        static TEST_CLOCK_SLOT: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+        // Unix timestamp returned by the clock sysvar stub for the current test
+        static TEST_CLOCK_UNIX_TIMESTAMP: std::cell::Cell<i64> = const { std::cell::Cell::new(0) };
+    }
+
     fn test_syscall_stubs() {
         use std::sync::Once;
         static ONCE: Once = Once::new();
@@ -1929,46 +3765,146 @@ mod tests {
             let source_token_program_id = deposit_token_account.owner;
             do_process_instruction(
                 approve(
-                    &source_token_program_id,
-                    deposit_account_key,
-                    &user_transfer_authority_key,
-                    depositor_key,
+                    &source_token_program_id,
+                    deposit_account_key,
+                    &user_transfer_authority_key,
+                    depositor_key,
+                    &[],
+                    source_token_amount,
+                )
+                .unwrap(),
+                vec![
+                    deposit_token_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
+            )
+            .unwrap();
+
+            let source_mint_key =
+                StateWithExtensions::<Account>::unpack(&deposit_token_account.data)
+                    .unwrap()
+                    .base
+                    .mint;
+            let swap_source_key = self.get_swap_key(&source_mint_key);
+            let (source_mint_key, mut source_mint_account) = self.get_token_mint(swap_source_key);
+
+            let pool_token_program_id = deposit_pool_account.owner;
+            do_process_instruction(
+                deposit_single_token_type_exact_amount_in(
+                    &SWAP_PROGRAM_ID,
+                    &source_token_program_id,
+                    &pool_token_program_id,
+                    &self.swap_key,
+                    &self.authority_key,
+                    &user_transfer_authority_key,
+                    deposit_account_key,
+                    &self.token_a_key,
+                    &self.token_b_key,
+                    &self.pool_mint_key,
+                    deposit_pool_key,
+                    &source_mint_key,
+                    DepositSingleTokenTypeExactAmountIn {
+                        source_token_amount,
+                        minimum_pool_token_amount,
+                    },
+                )
+                .unwrap(),
+                vec![
+                    &mut self.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    deposit_token_account,
+                    &mut self.token_a_account,
+                    &mut self.token_b_account,
+                    &mut self.pool_mint_account,
+                    deposit_pool_account,
+                    &mut source_mint_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
+            )
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn zap_in(
+            &mut self,
+            user_key: &Pubkey,
+            user_source_key: &Pubkey,
+            user_source_account: &mut SolanaAccount,
+            swap_source_key: &Pubkey,
+            swap_destination_key: &Pubkey,
+            user_destination_key: &Pubkey,
+            user_destination_account: &mut SolanaAccount,
+            user_pool_key: &Pubkey,
+            user_pool_account: &mut SolanaAccount,
+            source_token_amount: u64,
+            minimum_pool_token_amount: u64,
+        ) -> ProgramResult {
+            let user_transfer_key = Pubkey::new_unique();
+            let source_token_program_id = self.get_token_program_id(swap_source_key);
+            let destination_token_program_id = self.get_token_program_id(swap_destination_key);
+            // approve moving the source and the swapped amount
+            do_process_instruction(
+                approve(
+                    source_token_program_id,
+                    user_source_key,
+                    &user_transfer_key,
+                    user_key,
+                    &[],
+                    source_token_amount,
+                )
+                .unwrap(),
+                vec![
+                    user_source_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
+            )
+            .unwrap();
+            do_process_instruction(
+                approve(
+                    destination_token_program_id,
+                    user_destination_key,
+                    &user_transfer_key,
+                    user_key,
                     &[],
-                    source_token_amount,
+                    u64::MAX,
                 )
                 .unwrap(),
                 vec![
-                    deposit_token_account,
+                    user_destination_account,
                     &mut SolanaAccount::default(),
                     &mut SolanaAccount::default(),
                 ],
             )
             .unwrap();
 
-            let source_mint_key =
-                StateWithExtensions::<Account>::unpack(&deposit_token_account.data)
-                    .unwrap()
-                    .base
-                    .mint;
-            let swap_source_key = self.get_swap_key(&source_mint_key);
             let (source_mint_key, mut source_mint_account) = self.get_token_mint(swap_source_key);
+            let (destination_mint_key, mut destination_mint_account) =
+                self.get_token_mint(swap_destination_key);
+            let mut swap_source_account = self.get_token_account(swap_source_key).clone();
+            let mut swap_destination_account = self.get_token_account(swap_destination_key).clone();
 
-            let pool_token_program_id = deposit_pool_account.owner;
             do_process_instruction(
-                deposit_single_token_type_exact_amount_in(
+                zap_in(
                     &SWAP_PROGRAM_ID,
-                    &source_token_program_id,
-                    &pool_token_program_id,
+                    source_token_program_id,
+                    destination_token_program_id,
+                    &self.pool_token_program_id,
                     &self.swap_key,
                     &self.authority_key,
-                    &user_transfer_authority_key,
-                    deposit_account_key,
-                    &self.token_a_key,
-                    &self.token_b_key,
+                    &user_transfer_key,
+                    user_source_key,
+                    swap_source_key,
+                    swap_destination_key,
+                    user_destination_key,
                     &self.pool_mint_key,
-                    deposit_pool_key,
+                    &self.pool_fee_key,
+                    user_pool_key,
                     &source_mint_key,
-                    DepositSingleTokenTypeExactAmountIn {
+                    &destination_mint_key,
+                    ZapIn {
                         source_token_amount,
                         minimum_pool_token_amount,
                     },
@@ -1978,16 +3914,25 @@ mod tests {
                     &mut self.swap_account,
                     &mut SolanaAccount::default(),
                     &mut SolanaAccount::default(),
-                    deposit_token_account,
-                    &mut self.token_a_account,
-                    &mut self.token_b_account,
+                    user_source_account,
+                    &mut swap_source_account,
+                    &mut swap_destination_account,
+                    user_destination_account,
                     &mut self.pool_mint_account,
-                    deposit_pool_account,
+                    &mut self.pool_fee_account,
+                    user_pool_account,
                     &mut source_mint_account,
+                    &mut destination_mint_account,
+                    &mut SolanaAccount::default(),
                     &mut SolanaAccount::default(),
                     &mut SolanaAccount::default(),
                 ],
-            )
+            )?;
+
+            self.set_token_account(swap_source_key, swap_source_account);
+            self.set_token_account(swap_destination_key, swap_destination_account);
+
+            Ok(())
         }
 
         #[allow(clippy::too_many_arguments)]
@@ -7333,7 +9278,271 @@ mod tests {
     #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
     #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
     #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
-    fn test_overdraw_offset_curve(
+    fn test_overdraw_offset_curve(
+        pool_token_program_id: Pubkey,
+        token_a_program_id: Pubkey,
+        token_b_program_id: Pubkey,
+    ) {
+        let trade_fee_numerator = 1;
+        let trade_fee_denominator = 10;
+        let owner_trade_fee_numerator = 1;
+        let owner_trade_fee_denominator = 30;
+        let owner_withdraw_fee_numerator = 1;
+        let owner_withdraw_fee_denominator = 30;
+        let host_fee_numerator = 10;
+        let host_fee_denominator = 100;
+
+        let token_a_amount = 1_000_000_000;
+        let token_b_amount = 0;
+        let fees = Fees {
+            trade_fee_numerator,
+            trade_fee_denominator,
+            owner_trade_fee_numerator,
+            owner_trade_fee_denominator,
+            owner_withdraw_fee_numerator,
+            owner_withdraw_fee_denominator,
+            host_fee_numerator,
+            host_fee_denominator,
+        };
+
+        let token_b_offset = 2_000_000;
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::Offset,
+            calculator: Arc::new(OffsetCurve { token_b_offset }),
+        };
+        let user_key = Pubkey::new_unique();
+        let swapper_key = Pubkey::new_unique();
+
+        let mut accounts = SwapAccountInfo::new(
+            &user_key,
+            fees,
+            SwapTransferFees::default(),
+            swap_curve,
+            token_a_amount,
+            token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
+
+        accounts.initialize_swap().unwrap();
+
+        let swap_token_a_key = accounts.token_a_key;
+        let swap_token_b_key = accounts.token_b_key;
+        let initial_a = 500_000;
+        let initial_b = 1_000;
+
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            _pool_key,
+            _pool_account,
+        ) = accounts.setup_token_accounts(&user_key, &swapper_key, initial_a, initial_b, 0);
+
+        // swap a to b way, fails, there's no liquidity
+        let a_to_b_amount = initial_a;
+        let minimum_token_b_amount = 0;
+
+        assert_eq!(
+            Err(SwapError::ZeroTradingTokens.into()),
+            accounts.swap(
+                &swapper_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                a_to_b_amount,
+                minimum_token_b_amount,
+            )
+        );
+
+        // swap b to a, succeeds at offset price
+        let b_to_a_amount = initial_b;
+        let minimum_token_a_amount = 0;
+        accounts
+            .swap(
+                &swapper_key,
+                &token_b_key,
+                &mut token_b_account,
+                &swap_token_b_key,
+                &swap_token_a_key,
+                &token_a_key,
+                &mut token_a_account,
+                b_to_a_amount,
+                minimum_token_a_amount,
+            )
+            .unwrap();
+
+        // try a to b again, succeeds due to new liquidity
+        accounts
+            .swap(
+                &swapper_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                a_to_b_amount,
+                minimum_token_b_amount,
+            )
+            .unwrap();
+
+        // try a to b again, fails due to no more liquidity
+        assert_eq!(
+            Err(SwapError::ZeroTradingTokens.into()),
+            accounts.swap(
+                &swapper_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                a_to_b_amount,
+                minimum_token_b_amount,
+            )
+        );
+
+        // Try to deposit, fails because deposits are not allowed for offset
+        // curve swaps
+        {
+            let initial_a = 100;
+            let initial_b = 100;
+            let pool_amount = 100;
+            let (
+                token_a_key,
+                mut token_a_account,
+                token_b_key,
+                mut token_b_account,
+                pool_key,
+                mut pool_account,
+            ) = accounts.setup_token_accounts(&user_key, &swapper_key, initial_a, initial_b, 0);
+            assert_eq!(
+                Err(SwapError::UnsupportedCurveOperation.into()),
+                accounts.deposit_all_token_types(
+                    &swapper_key,
+                    &token_a_key,
+                    &mut token_a_account,
+                    &token_b_key,
+                    &mut token_b_account,
+                    &pool_key,
+                    &mut pool_account,
+                    pool_amount,
+                    initial_a,
+                    initial_b,
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_liquidity_bootstrapping_curve() {
+        let token_a_amount = 9_000_000;
+        let token_b_amount = 1_000_000;
+        // token A is sold with a weight going from 90% down to 50%
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::LiquidityBootstrapping,
+            calculator: Arc::new(LiquidityBootstrappingCurve {
+                start_weight_a: 9_000,
+                end_weight_a: 5_000,
+                start_timestamp: 1_000,
+                end_timestamp: 2_000,
+                unix_timestamp: 0,
+            }),
+        };
+        let user_key = Pubkey::new_unique();
+        let swapper_key = Pubkey::new_unique();
+
+        let mut accounts = SwapAccountInfo::new(
+            &user_key,
+            Fees::default(),
+            SwapTransferFees::default(),
+            swap_curve,
+            token_a_amount,
+            token_b_amount,
+            &spl_token::id(),
+            &spl_token::id(),
+            &spl_token::id(),
+        );
+
+        accounts.initialize_swap().unwrap();
+
+        let swap_token_a_key = accounts.token_a_key;
+        let swap_token_b_key = accounts.token_b_key;
+        let b_to_a_amount = 10_000;
+
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            _pool_key,
+            _pool_account,
+        ) = accounts.setup_token_accounts(&user_key, &swapper_key, 0, 2 * b_to_a_amount, 0);
+
+        // the same amount of token B buys more token A at the end of the window
+        let mut token_a_balance = 0;
+        let mut last_token_a_bought = 0;
+        for unix_timestamp in [1_000, 2_000] {
+            TEST_CLOCK_UNIX_TIMESTAMP.with(|timestamp| timestamp.set(unix_timestamp));
+            let result = accounts.swap(
+                &swapper_key,
+                &token_b_key,
+                &mut token_b_account,
+                &swap_token_b_key,
+                &swap_token_a_key,
+                &token_a_key,
+                &mut token_a_account,
+                b_to_a_amount,
+                0,
+            );
+            TEST_CLOCK_UNIX_TIMESTAMP.with(|timestamp| timestamp.set(0));
+            result.unwrap();
+
+            let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data)
+                .unwrap()
+                .base;
+            let token_a_bought = token_a.amount - token_a_balance;
+            assert!(token_a_bought > last_token_a_bought);
+            token_a_balance = token_a.amount;
+            last_token_a_bought = token_a_bought;
+        }
+
+        // swaps of more than half the pool balance are rejected
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            _pool_key,
+            _pool_account,
+        ) = accounts.setup_token_accounts(&user_key, &swapper_key, 0, token_b_amount, 0);
+        assert_eq!(
+            Err(SwapError::ZeroTradingTokens.into()),
+            accounts.swap(
+                &swapper_key,
+                &token_b_key,
+                &mut token_b_account,
+                &swap_token_b_key,
+                &swap_token_a_key,
+                &token_a_key,
+                &mut token_a_account,
+                token_b_amount,
+                0,
+            )
+        );
+    }
+
+    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
+    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
+    fn test_withdraw_all_offset_curve(
         pool_token_program_id: Pubkey,
         token_a_program_id: Pubkey,
         token_b_program_id: Pubkey,
@@ -7342,13 +9551,13 @@ mod tests {
         let trade_fee_denominator = 10;
         let owner_trade_fee_numerator = 1;
         let owner_trade_fee_denominator = 30;
-        let owner_withdraw_fee_numerator = 1;
+        let owner_withdraw_fee_numerator = 0;
         let owner_withdraw_fee_denominator = 30;
         let host_fee_numerator = 10;
         let host_fee_denominator = 100;
 
         let token_a_amount = 1_000_000_000;
-        let token_b_amount = 0;
+        let token_b_amount = 10;
         let fees = Fees {
             trade_fee_numerator,
             trade_fee_denominator,
@@ -7365,8 +9574,9 @@ mod tests {
             curve_type: CurveType::Offset,
             calculator: Arc::new(OffsetCurve { token_b_offset }),
         };
+        let total_pool = swap_curve.calculator.new_pool_supply();
         let user_key = Pubkey::new_unique();
-        let swapper_key = Pubkey::new_unique();
+        let withdrawer_key = Pubkey::new_unique();
 
         let mut accounts = SwapAccountInfo::new(
             &user_key,
@@ -7382,10 +9592,105 @@ mod tests {
 
         accounts.initialize_swap().unwrap();
 
-        let swap_token_a_key = accounts.token_a_key;
-        let swap_token_b_key = accounts.token_b_key;
-        let initial_a = 500_000;
-        let initial_b = 1_000;
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            _pool_key,
+            _pool_account,
+        ) = accounts.setup_token_accounts(&user_key, &withdrawer_key, 0, 0, 0);
+
+        let pool_key = accounts.pool_token_key;
+        let mut pool_account = accounts.pool_token_account.clone();
+
+        // WithdrawAllTokenTypes takes all tokens for A and B.
+        // The curve's calculation for token B will say to transfer
+        // `token_b_offset + token_b_amount`, but only `token_b_amount` will be
+        // moved.
+        accounts
+            .withdraw_all_token_types(
+                &user_key,
+                &pool_key,
+                &mut pool_account,
+                &token_a_key,
+                &mut token_a_account,
+                &token_b_key,
+                &mut token_b_account,
+                total_pool.try_into().unwrap(),
+                0,
+                0,
+            )
+            .unwrap();
+
+        let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
+        assert_eq!(token_a.base.amount, token_a_amount);
+        let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
+        assert_eq!(token_b.base.amount, token_b_amount);
+        let swap_token_a =
+            StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
+        assert_eq!(swap_token_a.base.amount, 0);
+        let swap_token_b =
+            StateWithExtensions::<Account>::unpack(&accounts.token_b_account.data).unwrap();
+        assert_eq!(swap_token_b.base.amount, 0);
+    }
+
+    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
+    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
+    fn test_withdraw_all_constant_price_curve(
+        pool_token_program_id: Pubkey,
+        token_a_program_id: Pubkey,
+        token_b_program_id: Pubkey,
+    ) {
+        let trade_fee_numerator = 1;
+        let trade_fee_denominator = 10;
+        let owner_trade_fee_numerator = 1;
+        let owner_trade_fee_denominator = 30;
+        let owner_withdraw_fee_numerator = 0;
+        let owner_withdraw_fee_denominator = 30;
+        let host_fee_numerator = 10;
+        let host_fee_denominator = 100;
+
+        // initialize "unbalanced", so that withdrawing all will have some issues
+        // A: 1_000_000_000
+        // B: 2_000_000_000 (1_000 * 2_000_000)
+        let swap_token_a_amount = 1_000_000_000;
+        let swap_token_b_amount = 1_000;
+        let token_b_price = 2_000_000;
+        let fees = Fees {
+            trade_fee_numerator,
+            trade_fee_denominator,
+            owner_trade_fee_numerator,
+            owner_trade_fee_denominator,
+            owner_withdraw_fee_numerator,
+            owner_withdraw_fee_denominator,
+            host_fee_numerator,
+            host_fee_denominator,
+        };
+
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantPrice,
+            calculator: Arc::new(ConstantPriceCurve { token_b_price }),
+        };
+        let total_pool = swap_curve.calculator.new_pool_supply();
+        let user_key = Pubkey::new_unique();
+        let withdrawer_key = Pubkey::new_unique();
+
+        let mut accounts = SwapAccountInfo::new(
+            &user_key,
+            fees,
+            SwapTransferFees::default(),
+            swap_curve,
+            swap_token_a_amount,
+            swap_token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
+
+        accounts.initialize_swap().unwrap();
 
         let (
             token_a_key,
@@ -7394,112 +9699,130 @@ mod tests {
             mut token_b_account,
             _pool_key,
             _pool_account,
-        ) = accounts.setup_token_accounts(&user_key, &swapper_key, initial_a, initial_b, 0);
+        ) = accounts.setup_token_accounts(&user_key, &withdrawer_key, 0, 0, 0);
 
-        // swap a to b way, fails, there's no liquidity
-        let a_to_b_amount = initial_a;
-        let minimum_token_b_amount = 0;
+        let pool_key = accounts.pool_token_key;
+        let mut pool_account = accounts.pool_token_account.clone();
 
+        // WithdrawAllTokenTypes will not take all token A and B, since their
+        // ratio is unbalanced.  It will try to take 1_500_000_000 worth of
+        // each token, which means 1_500_000_000 token A, and 750 token B.
+        // With no slippage, this will leave 250 token B in the pool.
         assert_eq!(
-            Err(SwapError::ZeroTradingTokens.into()),
-            accounts.swap(
-                &swapper_key,
+            Err(SwapError::ExceededSlippage.into()),
+            accounts.withdraw_all_token_types(
+                &user_key,
+                &pool_key,
+                &mut pool_account,
                 &token_a_key,
                 &mut token_a_account,
-                &swap_token_a_key,
-                &swap_token_b_key,
                 &token_b_key,
                 &mut token_b_account,
-                a_to_b_amount,
-                minimum_token_b_amount,
+                total_pool.try_into().unwrap(),
+                swap_token_a_amount,
+                swap_token_b_amount,
             )
         );
 
-        // swap b to a, succeeds at offset price
-        let b_to_a_amount = initial_b;
-        let minimum_token_a_amount = 0;
         accounts
-            .swap(
-                &swapper_key,
-                &token_b_key,
-                &mut token_b_account,
-                &swap_token_b_key,
-                &swap_token_a_key,
+            .withdraw_all_token_types(
+                &user_key,
+                &pool_key,
+                &mut pool_account,
                 &token_a_key,
                 &mut token_a_account,
-                b_to_a_amount,
-                minimum_token_a_amount,
+                &token_b_key,
+                &mut token_b_account,
+                total_pool.try_into().unwrap(),
+                0,
+                0,
             )
             .unwrap();
 
-        // try a to b again, succeeds due to new liquidity
-        accounts
-            .swap(
-                &swapper_key,
+        let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
+        assert_eq!(token_a.base.amount, swap_token_a_amount);
+        let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
+        assert_eq!(token_b.base.amount, 750);
+        let swap_token_a =
+            StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
+        assert_eq!(swap_token_a.base.amount, 0);
+        let swap_token_b =
+            StateWithExtensions::<Account>::unpack(&accounts.token_b_account.data).unwrap();
+        assert_eq!(swap_token_b.base.amount, 250);
+
+        // deposit now, not enough to cover the tokens already in there
+        let token_b_amount = 10;
+        let token_a_amount = token_b_amount * token_b_price;
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            pool_key,
+            mut pool_account,
+        ) = accounts.setup_token_accounts(
+            &user_key,
+            &withdrawer_key,
+            token_a_amount,
+            token_b_amount,
+            0,
+        );
+
+        assert_eq!(
+            Err(SwapError::ExceededSlippage.into()),
+            accounts.deposit_all_token_types(
+                &withdrawer_key,
                 &token_a_key,
                 &mut token_a_account,
-                &swap_token_a_key,
-                &swap_token_b_key,
                 &token_b_key,
                 &mut token_b_account,
-                a_to_b_amount,
-                minimum_token_b_amount,
+                &pool_key,
+                &mut pool_account,
+                1, // doesn't matter
+                token_a_amount,
+                token_b_amount,
             )
-            .unwrap();
+        );
+
+        // deposit enough tokens, success!
+        let token_b_amount = 125;
+        let token_a_amount = token_b_amount * token_b_price;
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            pool_key,
+            mut pool_account,
+        ) = accounts.setup_token_accounts(
+            &user_key,
+            &withdrawer_key,
+            token_a_amount,
+            token_b_amount,
+            0,
+        );
 
-        // try a to b again, fails due to no more liquidity
-        assert_eq!(
-            Err(SwapError::ZeroTradingTokens.into()),
-            accounts.swap(
-                &swapper_key,
+        accounts
+            .deposit_all_token_types(
+                &withdrawer_key,
                 &token_a_key,
                 &mut token_a_account,
-                &swap_token_a_key,
-                &swap_token_b_key,
                 &token_b_key,
                 &mut token_b_account,
-                a_to_b_amount,
-                minimum_token_b_amount,
+                &pool_key,
+                &mut pool_account,
+                1, // doesn't matter
+                token_a_amount,
+                token_b_amount,
             )
-        );
-
-        // Try to deposit, fails because deposits are not allowed for offset
-        // curve swaps
-        {
-            let initial_a = 100;
-            let initial_b = 100;
-            let pool_amount = 100;
-            let (
-                token_a_key,
-                mut token_a_account,
-                token_b_key,
-                mut token_b_account,
-                pool_key,
-                mut pool_account,
-            ) = accounts.setup_token_accounts(&user_key, &swapper_key, initial_a, initial_b, 0);
-            assert_eq!(
-                Err(SwapError::UnsupportedCurveOperation.into()),
-                accounts.deposit_all_token_types(
-                    &swapper_key,
-                    &token_a_key,
-                    &mut token_a_account,
-                    &token_b_key,
-                    &mut token_b_account,
-                    &pool_key,
-                    &mut pool_account,
-                    pool_amount,
-                    initial_a,
-                    initial_b,
-                )
-            );
-        }
+            .unwrap();
     }
 
     #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
     #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
     #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
     #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
-    fn test_withdraw_all_offset_curve(
+    fn test_deposits_allowed_single_token(
         pool_token_program_id: Pubkey,
         token_a_program_id: Pubkey,
         token_b_program_id: Pubkey,
@@ -7513,8 +9836,8 @@ mod tests {
         let host_fee_numerator = 10;
         let host_fee_denominator = 100;
 
-        let token_a_amount = 1_000_000_000;
-        let token_b_amount = 10;
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 0;
         let fees = Fees {
             trade_fee_numerator,
             trade_fee_denominator,
@@ -7531,9 +9854,84 @@ mod tests {
             curve_type: CurveType::Offset,
             calculator: Arc::new(OffsetCurve { token_b_offset }),
         };
-        let total_pool = swap_curve.calculator.new_pool_supply();
+        let creator_key = Pubkey::new_unique();
+        let depositor_key = Pubkey::new_unique();
+
+        let mut accounts = SwapAccountInfo::new(
+            &creator_key,
+            fees,
+            SwapTransferFees::default(),
+            swap_curve,
+            token_a_amount,
+            token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
+
+        accounts.initialize_swap().unwrap();
+
+        let initial_a = 1_000_000;
+        let initial_b = 2_000_000;
+        let (
+            _depositor_token_a_key,
+            _depositor_token_a_account,
+            depositor_token_b_key,
+            mut depositor_token_b_account,
+            depositor_pool_key,
+            mut depositor_pool_account,
+        ) = accounts.setup_token_accounts(&creator_key, &depositor_key, initial_a, initial_b, 0);
+
+        assert_eq!(
+            Err(SwapError::UnsupportedCurveOperation.into()),
+            accounts.deposit_single_token_type_exact_amount_in(
+                &depositor_key,
+                &depositor_token_b_key,
+                &mut depositor_token_b_account,
+                &depositor_pool_key,
+                &mut depositor_pool_account,
+                initial_b,
+                0,
+            )
+        );
+    }
+
+    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
+    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
+    fn test_withdraw_with_invalid_fee_account(
+        pool_token_program_id: Pubkey,
+        token_a_program_id: Pubkey,
+        token_b_program_id: Pubkey,
+    ) {
         let user_key = Pubkey::new_unique();
+
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 2,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 10,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 5,
+            host_fee_numerator: 7,
+            host_fee_denominator: 100,
+        };
+
+        let token_a_amount = 1000;
+        let token_b_amount = 2000;
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        };
+
         let withdrawer_key = Pubkey::new_unique();
+        let initial_a = token_a_amount / 10;
+        let initial_b = token_b_amount / 10;
+        let initial_pool = swap_curve.calculator.new_pool_supply() / 10;
+        let withdraw_amount = initial_pool / 4;
+        let minimum_token_a_amount = initial_a / 40;
+        let minimum_token_b_amount = initial_b / 40;
 
         let mut accounts = SwapAccountInfo::new(
             &user_key,
@@ -7554,94 +9952,150 @@ mod tests {
             mut token_a_account,
             token_b_key,
             mut token_b_account,
-            _pool_key,
-            _pool_account,
-        ) = accounts.setup_token_accounts(&user_key, &withdrawer_key, 0, 0, 0);
+            pool_key,
+            mut pool_account,
+        ) = accounts.setup_token_accounts(
+            &user_key,
+            &withdrawer_key,
+            initial_a,
+            initial_b,
+            initial_pool.try_into().unwrap(),
+        );
 
-        let pool_key = accounts.pool_token_key;
-        let mut pool_account = accounts.pool_token_account.clone();
+        let destination_key = Pubkey::new_unique();
+        let mut destination = SolanaAccount::new(
+            account_minimum_balance(),
+            Account::get_packed_len(),
+            &withdrawer_key,
+        );
 
-        // WithdrawAllTokenTypes takes all tokens for A and B.
-        // The curve's calculation for token B will say to transfer
-        // `token_b_offset + token_b_amount`, but only `token_b_amount` will be
-        // moved.
-        accounts
-            .withdraw_all_token_types(
+        do_process_instruction(
+            close_account(
+                &pool_token_program_id,
+                &accounts.pool_fee_key,
+                &destination_key,
                 &user_key,
+                &[],
+            )
+            .unwrap(),
+            vec![
+                &mut accounts.pool_fee_account,
+                &mut destination,
+                &mut SolanaAccount::default(),
+            ],
+        )
+        .unwrap();
+
+        let user_transfer_authority_key = Pubkey::new_unique();
+        let pool_token_amount = withdraw_amount.try_into().unwrap();
+
+        do_process_instruction(
+            approve(
+                &pool_token_program_id,
                 &pool_key,
+                &user_transfer_authority_key,
+                &withdrawer_key,
+                &[],
+                pool_token_amount,
+            )
+            .unwrap(),
+            vec![
                 &mut pool_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+            ],
+        )
+        .unwrap();
+
+        do_process_instruction(
+            withdraw_all_token_types(
+                &SWAP_PROGRAM_ID,
+                &pool_token_program_id,
+                &token_a_program_id,
+                &token_b_program_id,
+                &accounts.swap_key,
+                &accounts.authority_key,
+                &user_transfer_authority_key,
+                &accounts.pool_mint_key,
+                &accounts.pool_fee_key,
+                &pool_key,
+                &accounts.token_a_key,
+                &accounts.token_b_key,
                 &token_a_key,
-                &mut token_a_account,
                 &token_b_key,
-                &mut token_b_account,
-                total_pool.try_into().unwrap(),
-                0,
-                0,
+                &accounts.token_a_mint_key,
+                &accounts.token_b_mint_key,
+                WithdrawAllTokenTypes {
+                    pool_token_amount,
+                    minimum_token_a_amount,
+                    minimum_token_b_amount,
+                },
             )
-            .unwrap();
-
-        let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
-        assert_eq!(token_a.base.amount, token_a_amount);
-        let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
-        assert_eq!(token_b.base.amount, token_b_amount);
-        let swap_token_a =
-            StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
-        assert_eq!(swap_token_a.base.amount, 0);
-        let swap_token_b =
-            StateWithExtensions::<Account>::unpack(&accounts.token_b_account.data).unwrap();
-        assert_eq!(swap_token_b.base.amount, 0);
+            .unwrap(),
+            vec![
+                &mut accounts.swap_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                &mut accounts.pool_mint_account,
+                &mut pool_account,
+                &mut accounts.token_a_account,
+                &mut accounts.token_b_account,
+                &mut token_a_account,
+                &mut token_b_account,
+                &mut accounts.pool_fee_account,
+                &mut accounts.token_a_mint_account,
+                &mut accounts.token_b_mint_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+            ],
+        )
+        .unwrap();
     }
 
     #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
     #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
     #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
     #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
-    fn test_withdraw_all_constant_price_curve(
+    fn test_withdraw_one_exact_out_with_invalid_fee_account(
         pool_token_program_id: Pubkey,
         token_a_program_id: Pubkey,
         token_b_program_id: Pubkey,
     ) {
-        let trade_fee_numerator = 1;
-        let trade_fee_denominator = 10;
-        let owner_trade_fee_numerator = 1;
-        let owner_trade_fee_denominator = 30;
-        let owner_withdraw_fee_numerator = 0;
-        let owner_withdraw_fee_denominator = 30;
-        let host_fee_numerator = 10;
-        let host_fee_denominator = 100;
+        let user_key = Pubkey::new_unique();
 
-        // initialize "unbalanced", so that withdrawing all will have some issues
-        // A: 1_000_000_000
-        // B: 2_000_000_000 (1_000 * 2_000_000)
-        let swap_token_a_amount = 1_000_000_000;
-        let swap_token_b_amount = 1_000;
-        let token_b_price = 2_000_000;
         let fees = Fees {
-            trade_fee_numerator,
-            trade_fee_denominator,
-            owner_trade_fee_numerator,
-            owner_trade_fee_denominator,
-            owner_withdraw_fee_numerator,
-            owner_withdraw_fee_denominator,
-            host_fee_numerator,
-            host_fee_denominator,
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 2,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 10,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 5,
+            host_fee_numerator: 7,
+            host_fee_denominator: 100,
         };
 
+        let token_a_amount = 1000;
+        let token_b_amount = 2000;
         let swap_curve = SwapCurve {
-            curve_type: CurveType::ConstantPrice,
-            calculator: Arc::new(ConstantPriceCurve { token_b_price }),
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
         };
-        let total_pool = swap_curve.calculator.new_pool_supply();
-        let user_key = Pubkey::new_unique();
+
         let withdrawer_key = Pubkey::new_unique();
+        let initial_a = token_a_amount / 10;
+        let initial_b = token_b_amount / 10;
+        let initial_pool = swap_curve.calculator.new_pool_supply() / 10;
+        let maximum_pool_token_amount = to_u64(initial_pool / 4).unwrap();
+        let destination_a_amount = initial_a / 40;
 
         let mut accounts = SwapAccountInfo::new(
             &user_key,
             fees,
             SwapTransferFees::default(),
             swap_curve,
-            swap_token_a_amount,
-            swap_token_b_amount,
+            token_a_amount,
+            token_b_amount,
             &pool_token_program_id,
             &token_a_program_id,
             &token_b_program_id,
@@ -7652,64 +10106,180 @@ mod tests {
         let (
             token_a_key,
             mut token_a_account,
-            token_b_key,
-            mut token_b_account,
-            _pool_key,
-            _pool_account,
-        ) = accounts.setup_token_accounts(&user_key, &withdrawer_key, 0, 0, 0);
+            _token_b_key,
+            _token_b_account,
+            pool_key,
+            mut pool_account,
+        ) = accounts.setup_token_accounts(
+            &user_key,
+            &withdrawer_key,
+            initial_a,
+            initial_b,
+            initial_pool.try_into().unwrap(),
+        );
 
-        let pool_key = accounts.pool_token_key;
-        let mut pool_account = accounts.pool_token_account.clone();
+        let destination_key = Pubkey::new_unique();
+        let mut destination = SolanaAccount::new(
+            account_minimum_balance(),
+            Account::get_packed_len(),
+            &withdrawer_key,
+        );
 
-        // WithdrawAllTokenTypes will not take all token A and B, since their
-        // ratio is unbalanced.  It will try to take 1_500_000_000 worth of
-        // each token, which means 1_500_000_000 token A, and 750 token B.
-        // With no slippage, this will leave 250 token B in the pool.
-        assert_eq!(
-            Err(SwapError::ExceededSlippage.into()),
-            accounts.withdraw_all_token_types(
+        do_process_instruction(
+            close_account(
+                &pool_token_program_id,
+                &accounts.pool_fee_key,
+                &destination_key,
                 &user_key,
-                &pool_key,
-                &mut pool_account,
-                &token_a_key,
-                &mut token_a_account,
-                &token_b_key,
-                &mut token_b_account,
-                total_pool.try_into().unwrap(),
-                swap_token_a_amount,
-                swap_token_b_amount,
+                &[],
             )
-        );
+            .unwrap(),
+            vec![
+                &mut accounts.pool_fee_account,
+                &mut destination,
+                &mut SolanaAccount::default(),
+            ],
+        )
+        .unwrap();
 
-        accounts
-            .withdraw_all_token_types(
-                &user_key,
+        let user_transfer_authority_key = Pubkey::new_unique();
+
+        do_process_instruction(
+            approve(
+                &pool_token_program_id,
                 &pool_key,
+                &user_transfer_authority_key,
+                &withdrawer_key,
+                &[],
+                maximum_pool_token_amount,
+            )
+            .unwrap(),
+            vec![
                 &mut pool_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+            ],
+        )
+        .unwrap();
+
+        do_process_instruction(
+            withdraw_single_token_type_exact_amount_out(
+                &SWAP_PROGRAM_ID,
+                &pool_token_program_id,
+                &token_a_program_id,
+                &accounts.swap_key,
+                &accounts.authority_key,
+                &user_transfer_authority_key,
+                &accounts.pool_mint_key,
+                &accounts.pool_fee_key,
+                &pool_key,
+                &accounts.token_a_key,
+                &accounts.token_b_key,
                 &token_a_key,
+                &accounts.token_a_mint_key,
+                WithdrawSingleTokenTypeExactAmountOut {
+                    destination_token_amount: destination_a_amount,
+                    maximum_pool_token_amount,
+                },
+            )
+            .unwrap(),
+            vec![
+                &mut accounts.swap_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                &mut accounts.pool_mint_account,
+                &mut pool_account,
+                &mut accounts.token_a_account,
+                &mut accounts.token_b_account,
                 &mut token_a_account,
-                &token_b_key,
-                &mut token_b_account,
-                total_pool.try_into().unwrap(),
-                0,
-                0,
+                &mut accounts.pool_fee_account,
+                &mut accounts.token_a_mint_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+            ],
+        )
+        .unwrap();
+    }
+
+    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
+    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
+    fn test_valid_swap_with_invalid_fee_account(
+        pool_token_program_id: Pubkey,
+        token_a_program_id: Pubkey,
+        token_b_program_id: Pubkey,
+    ) {
+        let owner_key = &Pubkey::new_unique();
+
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 5_000_000;
+
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 10,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 30,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 30,
+            host_fee_numerator: 10,
+            host_fee_denominator: 100,
+        };
+
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        };
+
+        let owner_key_str = owner_key.to_string();
+        let constraints = Some(SwapConstraints {
+            owner_key: Some(owner_key_str.as_ref()),
+            valid_curve_types: &[CurveType::ConstantProduct],
+            fees: &fees,
+        });
+        let mut accounts = SwapAccountInfo::new(
+            owner_key,
+            fees.clone(),
+            SwapTransferFees::default(),
+            swap_curve,
+            token_a_amount,
+            token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
+
+        do_process_instruction_with_fee_constraints(
+            initialize(
+                &SWAP_PROGRAM_ID,
+                &pool_token_program_id,
+                &accounts.swap_key,
+                &accounts.authority_key,
+                &accounts.token_a_key,
+                &accounts.token_b_key,
+                &accounts.pool_mint_key,
+                &accounts.pool_fee_key,
+                &accounts.pool_token_key,
+                accounts.fees.clone(),
+                accounts.swap_curve.clone(),
             )
-            .unwrap();
+            .unwrap(),
+            vec![
+                &mut accounts.swap_account,
+                &mut SolanaAccount::default(),
+                &mut accounts.token_a_account,
+                &mut accounts.token_b_account,
+                &mut accounts.pool_mint_account,
+                &mut accounts.pool_fee_account,
+                &mut accounts.pool_token_account,
+                &mut SolanaAccount::default(),
+            ],
+            &constraints,
+        )
+        .unwrap();
 
-        let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
-        assert_eq!(token_a.base.amount, swap_token_a_amount);
-        let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
-        assert_eq!(token_b.base.amount, 750);
-        let swap_token_a =
-            StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
-        assert_eq!(swap_token_a.base.amount, 0);
-        let swap_token_b =
-            StateWithExtensions::<Account>::unpack(&accounts.token_b_account.data).unwrap();
-        assert_eq!(swap_token_b.base.amount, 250);
+        let authority_key = accounts.authority_key;
 
-        // deposit now, not enough to cover the tokens already in there
-        let token_b_amount = 10;
-        let token_a_amount = token_b_amount * token_b_price;
         let (
             token_a_key,
             mut token_a_account,
@@ -7718,83 +10288,100 @@ mod tests {
             pool_key,
             mut pool_account,
         ) = accounts.setup_token_accounts(
-            &user_key,
-            &withdrawer_key,
+            owner_key,
+            &authority_key,
             token_a_amount,
             token_b_amount,
             0,
         );
 
-        assert_eq!(
-            Err(SwapError::ExceededSlippage.into()),
-            accounts.deposit_all_token_types(
-                &withdrawer_key,
-                &token_a_key,
-                &mut token_a_account,
-                &token_b_key,
-                &mut token_b_account,
-                &pool_key,
-                &mut pool_account,
-                1, // doesn't matter
-                token_a_amount,
-                token_b_amount,
-            )
+        let destination_key = Pubkey::new_unique();
+        let mut destination = SolanaAccount::new(
+            account_minimum_balance(),
+            Account::get_packed_len(),
+            owner_key,
         );
 
-        // deposit enough tokens, success!
-        let token_b_amount = 125;
-        let token_a_amount = token_b_amount * token_b_price;
-        let (
-            token_a_key,
-            mut token_a_account,
-            token_b_key,
-            mut token_b_account,
-            pool_key,
-            mut pool_account,
-        ) = accounts.setup_token_accounts(
-            &user_key,
-            &withdrawer_key,
-            token_a_amount,
-            token_b_amount,
-            0,
-        );
+        do_process_instruction(
+            close_account(
+                &pool_token_program_id,
+                &accounts.pool_fee_key,
+                &destination_key,
+                owner_key,
+                &[],
+            )
+            .unwrap(),
+            vec![
+                &mut accounts.pool_fee_account,
+                &mut destination,
+                &mut SolanaAccount::default(),
+            ],
+        )
+        .unwrap();
 
-        accounts
-            .deposit_all_token_types(
-                &withdrawer_key,
+        do_process_instruction_with_fee_constraints(
+            swap(
+                &SWAP_PROGRAM_ID,
+                &token_a_program_id,
+                &token_b_program_id,
+                &pool_token_program_id,
+                &accounts.swap_key,
+                &accounts.authority_key,
+                &accounts.authority_key,
                 &token_a_key,
-                &mut token_a_account,
+                &accounts.token_a_key,
+                &accounts.token_b_key,
                 &token_b_key,
+                &accounts.pool_mint_key,
+                &accounts.pool_fee_key,
+                &accounts.token_a_mint_key,
+                &accounts.token_b_mint_key,
+                Some(&pool_key),
+                Swap {
+                    amount_in: token_a_amount / 2,
+                    minimum_amount_out: 0,
+                },
+            )
+            .unwrap(),
+            vec![
+                &mut accounts.swap_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                &mut token_a_account,
+                &mut accounts.token_a_account,
+                &mut accounts.token_b_account,
                 &mut token_b_account,
-                &pool_key,
+                &mut accounts.pool_mint_account,
+                &mut accounts.pool_fee_account,
+                &mut accounts.token_a_mint_account,
+                &mut accounts.token_b_mint_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
                 &mut pool_account,
-                1, // doesn't matter
-                token_a_amount,
-                token_b_amount,
-            )
-            .unwrap();
+            ],
+            &constraints,
+        )
+        .unwrap();
     }
 
-    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
     #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
     #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
     #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
-    fn test_deposits_allowed_single_token(
+    fn test_swap_curve_with_transfer_fees(
         pool_token_program_id: Pubkey,
         token_a_program_id: Pubkey,
         token_b_program_id: Pubkey,
     ) {
+        // All fees
         let trade_fee_numerator = 1;
         let trade_fee_denominator = 10;
         let owner_trade_fee_numerator = 1;
         let owner_trade_fee_denominator = 30;
-        let owner_withdraw_fee_numerator = 0;
+        let owner_withdraw_fee_numerator = 1;
         let owner_withdraw_fee_denominator = 30;
-        let host_fee_numerator = 10;
+        let host_fee_numerator = 20;
         let host_fee_denominator = 100;
-
-        let token_a_amount = 1_000_000;
-        let token_b_amount = 0;
         let fees = Fees {
             trade_fee_numerator,
             trade_fee_denominator,
@@ -7806,397 +10393,976 @@ mod tests {
             host_fee_denominator,
         };
 
-        let token_b_offset = 2_000_000;
+        let token_a_amount = 10_000_000_000;
+        let token_b_amount = 50_000_000_000;
+
+        check_valid_swap_curve(
+            fees,
+            SwapTransferFees {
+                pool_token: TransferFee::default(),
+                token_a: TransferFee {
+                    epoch: 0.into(),
+                    transfer_fee_basis_points: 100.into(),
+                    maximum_fee: 1_000_000_000.into(),
+                },
+                token_b: TransferFee::default(),
+            },
+            CurveType::ConstantProduct,
+            Arc::new(ConstantProductCurve {}),
+            token_a_amount,
+            token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
+    }
+
+    #[test_case(spl_token::id(); "token")]
+    #[test_case(spl_token_2022::id(); "token-2022")]
+    fn test_issue_position_account_checks(pool_token_program_id: Pubkey) {
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 10,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 30,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 30,
+            host_fee_numerator: 10,
+            host_fee_denominator: 100,
+        };
         let swap_curve = SwapCurve {
-            curve_type: CurveType::Offset,
-            calculator: Arc::new(OffsetCurve { token_b_offset }),
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
         };
-        let creator_key = Pubkey::new_unique();
-        let depositor_key = Pubkey::new_unique();
+        let total_pool = u64::try_from(swap_curve.calculator.new_pool_supply()).unwrap();
+        let token_a_amount = 1_000;
+        let token_b_amount = 9_000;
+        let user_key = Pubkey::new_unique();
 
         let mut accounts = SwapAccountInfo::new(
-            &creator_key,
+            &user_key,
             fees,
             SwapTransferFees::default(),
             swap_curve,
             token_a_amount,
             token_b_amount,
             &pool_token_program_id,
-            &token_a_program_id,
-            &token_b_program_id,
+            &pool_token_program_id,
+            &pool_token_program_id,
         );
-
         accounts.initialize_swap().unwrap();
 
-        let initial_a = 1_000_000;
-        let initial_b = 2_000_000;
-        let (
-            _depositor_token_a_key,
-            _depositor_token_a_account,
-            depositor_token_b_key,
-            mut depositor_token_b_account,
-            depositor_pool_key,
-            mut depositor_pool_account,
-        ) = accounts.setup_token_accounts(&creator_key, &depositor_key, initial_a, initial_b, 0);
+        let authority_key = accounts.authority_key;
+        let position_token_program_id = spl_token_2022::id();
+        let position_key = Pubkey::new_unique();
+        let position_mint_key = Pubkey::new_unique();
+        let position_token_key = Pubkey::new_unique();
+        let pool_token_amount = total_pool / 4;
+
+        // the swap authority signs for every token movement of the program,
+        // so it can never stand in for the user
+        let issue = issue_position(
+            &SWAP_PROGRAM_ID,
+            &pool_token_program_id,
+            &position_token_program_id,
+            &accounts.swap_key,
+            &authority_key,
+            &authority_key,
+            &accounts.pool_mint_key,
+            &accounts.pool_token_key,
+            &accounts.token_a_key,
+            &accounts.token_b_key,
+            &position_key,
+            &position_mint_key,
+            &position_token_key,
+            &user_key,
+            IssuePosition { pool_token_amount },
+        )
+        .unwrap();
+        assert_eq!(
+            Err(SwapError::InvalidUserTransferAuthority.into()),
+            do_process_instruction(
+                issue,
+                vec![
+                    &mut accounts.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut accounts.pool_mint_account,
+                    &mut accounts.pool_token_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut accounts.token_a_account,
+                    &mut accounts.token_b_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
+            )
+        );
 
+        // the locked pool tokens may only go to the derived vault
+        let mut issue = issue_position(
+            &SWAP_PROGRAM_ID,
+            &pool_token_program_id,
+            &position_token_program_id,
+            &accounts.swap_key,
+            &authority_key,
+            &user_key,
+            &accounts.pool_mint_key,
+            &accounts.pool_token_key,
+            &accounts.token_a_key,
+            &accounts.token_b_key,
+            &position_key,
+            &position_mint_key,
+            &position_token_key,
+            &user_key,
+            IssuePosition { pool_token_amount },
+        )
+        .unwrap();
+        let mut vault_issue = issue.clone();
+        vault_issue.accounts[5].pubkey = Pubkey::new_unique();
         assert_eq!(
-            Err(SwapError::UnsupportedCurveOperation.into()),
-            accounts.deposit_single_token_type_exact_amount_in(
-                &depositor_key,
-                &depositor_token_b_key,
-                &mut depositor_token_b_account,
-                &depositor_pool_key,
-                &mut depositor_pool_account,
-                initial_b,
-                0,
+            Err(SwapError::IncorrectPositionVault.into()),
+            do_process_instruction(
+                vault_issue,
+                vec![
+                    &mut accounts.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut accounts.pool_mint_account,
+                    &mut accounts.pool_token_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut accounts.token_a_account,
+                    &mut accounts.token_b_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
+            )
+        );
+
+        // nor may the position mint join any group but the derived one
+        issue.accounts[6].pubkey = Pubkey::new_unique();
+        assert_eq!(
+            Err(SwapError::IncorrectPositionGroup.into()),
+            do_process_instruction(
+                issue,
+                vec![
+                    &mut accounts.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut accounts.pool_mint_account,
+                    &mut accounts.pool_token_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut accounts.token_a_account,
+                    &mut accounts.token_b_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
             )
         );
     }
 
-    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
-    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
-    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
-    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
-    fn test_withdraw_with_invalid_fee_account(
-        pool_token_program_id: Pubkey,
-        token_a_program_id: Pubkey,
-        token_b_program_id: Pubkey,
-    ) {
-        let user_key = Pubkey::new_unique();
-
+    #[test_case(spl_token::id(); "token")]
+    #[test_case(spl_token_2022::id(); "token-2022")]
+    fn test_protocol_fee(pool_token_program_id: Pubkey) {
         let fees = Fees {
             trade_fee_numerator: 1,
-            trade_fee_denominator: 2,
+            trade_fee_denominator: 10,
             owner_trade_fee_numerator: 1,
-            owner_trade_fee_denominator: 10,
-            owner_withdraw_fee_numerator: 1,
-            owner_withdraw_fee_denominator: 5,
-            host_fee_numerator: 7,
+            owner_trade_fee_denominator: 30,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 30,
+            host_fee_numerator: 0,
             host_fee_denominator: 100,
         };
-
-        let token_a_amount = 1000;
-        let token_b_amount = 2000;
         let swap_curve = SwapCurve {
             curve_type: CurveType::ConstantProduct,
             calculator: Arc::new(ConstantProductCurve {}),
         };
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 9_000_000;
+        let user_key = Pubkey::new_unique();
+        let protocol_fee_authority_key = Pubkey::new_unique();
+        let timelock_slots = 100;
 
-        let withdrawer_key = Pubkey::new_unique();
-        let initial_a = token_a_amount / 10;
-        let initial_b = token_b_amount / 10;
-        let initial_pool = swap_curve.calculator.new_pool_supply() / 10;
-        let withdraw_amount = initial_pool / 4;
-        let minimum_token_a_amount = initial_a / 40;
-        let minimum_token_b_amount = initial_b / 40;
-
+        // swap account without room for the protocol fee
         let mut accounts = SwapAccountInfo::new(
             &user_key,
-            fees,
+            fees.clone(),
             SwapTransferFees::default(),
-            swap_curve,
+            swap_curve.clone(),
             token_a_amount,
             token_b_amount,
             &pool_token_program_id,
-            &token_a_program_id,
-            &token_b_program_id,
+            &pool_token_program_id,
+            &pool_token_program_id,
         );
-
         accounts.initialize_swap().unwrap();
+        assert_eq!(
+            Err(SwapError::ProtocolFeeUnavailable.into()),
+            do_process_instruction(
+                initialize_protocol_fee(
+                    &SWAP_PROGRAM_ID,
+                    &accounts.swap_key,
+                    &accounts.pool_fee_key,
+                    &user_key,
+                    &protocol_fee_authority_key,
+                    InitializeProtocolFee { timelock_slots },
+                )
+                .unwrap(),
+                vec![
+                    &mut accounts.swap_account,
+                    &mut accounts.pool_fee_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
+            )
+        );
 
-        let (
-            token_a_key,
-            mut token_a_account,
-            token_b_key,
-            mut token_b_account,
-            pool_key,
-            mut pool_account,
-        ) = accounts.setup_token_accounts(
+        let mut accounts = SwapAccountInfo::new(
             &user_key,
-            &withdrawer_key,
-            initial_a,
-            initial_b,
-            initial_pool.try_into().unwrap(),
+            fees,
+            SwapTransferFees::default(),
+            swap_curve,
+            token_a_amount,
+            token_b_amount,
+            &pool_token_program_id,
+            &pool_token_program_id,
+            &pool_token_program_id,
         );
+        accounts.swap_account =
+            SolanaAccount::new(0, ProtocolFee::SWAP_ACCOUNT_LEN, &SWAP_PROGRAM_ID);
+        accounts.initialize_swap().unwrap();
 
-        let destination_key = Pubkey::new_unique();
-        let mut destination = SolanaAccount::new(
-            account_minimum_balance(),
-            Account::get_packed_len(),
-            &withdrawer_key,
+        // wrong pool fee account owner
+        assert_eq!(
+            Err(SwapError::InvalidOwner.into()),
+            do_process_instruction(
+                initialize_protocol_fee(
+                    &SWAP_PROGRAM_ID,
+                    &accounts.swap_key,
+                    &accounts.pool_fee_key,
+                    &protocol_fee_authority_key,
+                    &protocol_fee_authority_key,
+                    InitializeProtocolFee { timelock_slots },
+                )
+                .unwrap(),
+                vec![
+                    &mut accounts.swap_account,
+                    &mut accounts.pool_fee_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
+            )
         );
 
         do_process_instruction(
-            close_account(
-                &pool_token_program_id,
+            initialize_protocol_fee(
+                &SWAP_PROGRAM_ID,
+                &accounts.swap_key,
                 &accounts.pool_fee_key,
-                &destination_key,
                 &user_key,
-                &[],
+                &protocol_fee_authority_key,
+                InitializeProtocolFee { timelock_slots },
             )
             .unwrap(),
             vec![
+                &mut accounts.swap_account,
                 &mut accounts.pool_fee_account,
-                &mut destination,
+                &mut SolanaAccount::default(),
                 &mut SolanaAccount::default(),
             ],
         )
         .unwrap();
 
-        let user_transfer_authority_key = Pubkey::new_unique();
-        let pool_token_amount = withdraw_amount.try_into().unwrap();
+        let protocol_fee =
+            ProtocolFee::unpack(&accounts.swap_account.data[ProtocolFee::OFFSET..]).unwrap();
+        assert_eq!(protocol_fee.authority, protocol_fee_authority_key);
+        assert_eq!(protocol_fee.timelock_slots, timelock_slots);
+        assert_eq!(protocol_fee.fee_bps, 0);
+
+        let recipient_owner_key = Pubkey::new_unique();
+        let (recipient_key, mut recipient_account) = mint_token(
+            &pool_token_program_id,
+            &accounts.pool_mint_key,
+            &mut accounts.pool_mint_account,
+            &accounts.authority_key,
+            &recipient_owner_key,
+            0,
+        );
+
+        // only the protocol fee authority can propose
+        assert_eq!(
+            Err(SwapError::InvalidOwner.into()),
+            do_process_instruction(
+                propose_protocol_fee(
+                    &SWAP_PROGRAM_ID,
+                    &accounts.swap_key,
+                    &user_key,
+                    &protocol_fee_authority_key,
+                    &recipient_key,
+                    ProposeProtocolFee { fee_bps: 5_000 },
+                )
+                .unwrap(),
+                vec![
+                    &mut accounts.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut recipient_account,
+                ],
+            )
+        );
+
+        // share over 100%
+        assert_eq!(
+            Err(SwapError::InvalidProtocolFee.into()),
+            do_process_instruction(
+                propose_protocol_fee(
+                    &SWAP_PROGRAM_ID,
+                    &accounts.swap_key,
+                    &protocol_fee_authority_key,
+                    &protocol_fee_authority_key,
+                    &recipient_key,
+                    ProposeProtocolFee {
+                        fee_bps: MAX_PROTOCOL_FEE_BPS + 1
+                    },
+                )
+                .unwrap(),
+                vec![
+                    &mut accounts.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut recipient_account,
+                ],
+            )
+        );
+
+        // nothing to execute yet
+        assert_eq!(
+            Err(SwapError::NoPendingProtocolFee.into()),
+            do_process_instruction(
+                execute_protocol_fee(&SWAP_PROGRAM_ID, &accounts.swap_key).unwrap(),
+                vec![&mut accounts.swap_account],
+            )
+        );
 
         do_process_instruction(
-            approve(
-                &pool_token_program_id,
-                &pool_key,
-                &user_transfer_authority_key,
-                &withdrawer_key,
-                &[],
-                pool_token_amount,
+            propose_protocol_fee(
+                &SWAP_PROGRAM_ID,
+                &accounts.swap_key,
+                &protocol_fee_authority_key,
+                &protocol_fee_authority_key,
+                &recipient_key,
+                ProposeProtocolFee { fee_bps: 5_000 },
             )
             .unwrap(),
             vec![
-                &mut pool_account,
+                &mut accounts.swap_account,
                 &mut SolanaAccount::default(),
                 &mut SolanaAccount::default(),
+                &mut recipient_account,
             ],
         )
         .unwrap();
 
+        // still timelocked
+        assert_eq!(
+            Err(SwapError::ProtocolFeeTimelocked.into()),
+            do_process_instruction(
+                execute_protocol_fee(&SWAP_PROGRAM_ID, &accounts.swap_key).unwrap(),
+                vec![&mut accounts.swap_account],
+            )
+        );
+
+        TEST_CLOCK_SLOT.with(|slot| slot.set(timelock_slots));
+        let result = do_process_instruction(
+            execute_protocol_fee(&SWAP_PROGRAM_ID, &accounts.swap_key).unwrap(),
+            vec![&mut accounts.swap_account],
+        );
+        TEST_CLOCK_SLOT.with(|slot| slot.set(0));
+        result.unwrap();
+
+        let protocol_fee =
+            ProtocolFee::unpack(&accounts.swap_account.data[ProtocolFee::OFFSET..]).unwrap();
+        assert_eq!(protocol_fee.recipient, recipient_key);
+        assert_eq!(protocol_fee.fee_bps, 5_000);
+        assert_eq!(protocol_fee.pending_execute_slot, 0);
+
+        // swap, half of the owner fee is owed to the protocol
+        let initial_a = token_a_amount / 5;
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            _pool_key,
+            _pool_account,
+        ) = accounts.setup_token_accounts(&user_key, &user_key, initial_a, 0, 0);
+        let swap_token_a_key = accounts.token_a_key;
+        let swap_token_b_key = accounts.token_b_key;
+        accounts
+            .swap(
+                &user_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                initial_a,
+                0,
+            )
+            .unwrap();
+
+        let protocol_fee =
+            ProtocolFee::unpack(&accounts.swap_account.data[ProtocolFee::OFFSET..]).unwrap();
+        let pool_fee =
+            StateWithExtensions::<Account>::unpack(&accounts.pool_fee_account.data).unwrap();
+        let owner_fee = pool_fee.base.amount + protocol_fee.pool_tokens_owed;
+        assert!(protocol_fee.pool_tokens_owed > 0);
+        assert_eq!(protocol_fee.pool_tokens_owed, owner_fee / 2);
+
+        // only the recipient can collect
+        assert_eq!(
+            Err(SwapError::IncorrectProtocolFeeRecipient.into()),
+            do_process_instruction(
+                collect_protocol_fee(
+                    &SWAP_PROGRAM_ID,
+                    &pool_token_program_id,
+                    &accounts.swap_key,
+                    &accounts.authority_key,
+                    &accounts.pool_mint_key,
+                    &accounts.pool_fee_key,
+                )
+                .unwrap(),
+                vec![
+                    &mut accounts.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut accounts.pool_mint_account,
+                    &mut accounts.pool_fee_account,
+                    &mut SolanaAccount::default(),
+                ],
+            )
+        );
+
+        let pool_tokens_owed = protocol_fee.pool_tokens_owed;
         do_process_instruction(
-            withdraw_all_token_types(
+            collect_protocol_fee(
                 &SWAP_PROGRAM_ID,
                 &pool_token_program_id,
-                &token_a_program_id,
-                &token_b_program_id,
                 &accounts.swap_key,
                 &accounts.authority_key,
-                &user_transfer_authority_key,
                 &accounts.pool_mint_key,
-                &accounts.pool_fee_key,
-                &pool_key,
-                &accounts.token_a_key,
-                &accounts.token_b_key,
-                &token_a_key,
-                &token_b_key,
-                &accounts.token_a_mint_key,
-                &accounts.token_b_mint_key,
-                WithdrawAllTokenTypes {
-                    pool_token_amount,
-                    minimum_token_a_amount,
-                    minimum_token_b_amount,
-                },
+                &recipient_key,
             )
             .unwrap(),
             vec![
-                &mut accounts.swap_account,
-                &mut SolanaAccount::default(),
-                &mut SolanaAccount::default(),
-                &mut accounts.pool_mint_account,
-                &mut pool_account,
-                &mut accounts.token_a_account,
-                &mut accounts.token_b_account,
-                &mut token_a_account,
-                &mut token_b_account,
-                &mut accounts.pool_fee_account,
-                &mut accounts.token_a_mint_account,
-                &mut accounts.token_b_mint_account,
-                &mut SolanaAccount::default(),
+                &mut accounts.swap_account,
                 &mut SolanaAccount::default(),
+                &mut accounts.pool_mint_account,
+                &mut recipient_account,
                 &mut SolanaAccount::default(),
             ],
         )
         .unwrap();
-    }
 
-    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
-    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
-    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
-    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
-    fn test_withdraw_one_exact_out_with_invalid_fee_account(
-        pool_token_program_id: Pubkey,
-        token_a_program_id: Pubkey,
-        token_b_program_id: Pubkey,
-    ) {
-        let user_key = Pubkey::new_unique();
+        let protocol_fee =
+            ProtocolFee::unpack(&accounts.swap_account.data[ProtocolFee::OFFSET..]).unwrap();
+        assert_eq!(protocol_fee.pool_tokens_owed, 0);
+        let recipient = StateWithExtensions::<Account>::unpack(&recipient_account.data).unwrap();
+        assert_eq!(recipient.base.amount, pool_tokens_owed);
+    }
 
+    #[test]
+    fn test_withdraw_fee_schedule() {
         let fees = Fees {
-            trade_fee_numerator: 1,
-            trade_fee_denominator: 2,
-            owner_trade_fee_numerator: 1,
-            owner_trade_fee_denominator: 10,
+            trade_fee_numerator: 0,
+            trade_fee_denominator: 10,
+            owner_trade_fee_numerator: 0,
+            owner_trade_fee_denominator: 30,
             owner_withdraw_fee_numerator: 1,
-            owner_withdraw_fee_denominator: 5,
-            host_fee_numerator: 7,
+            owner_withdraw_fee_denominator: 10,
+            host_fee_numerator: 0,
             host_fee_denominator: 100,
         };
-
-        let token_a_amount = 1000;
-        let token_b_amount = 2000;
         let swap_curve = SwapCurve {
             curve_type: CurveType::ConstantProduct,
             calculator: Arc::new(ConstantProductCurve {}),
         };
-
-        let withdrawer_key = Pubkey::new_unique();
-        let initial_a = token_a_amount / 10;
-        let initial_b = token_b_amount / 10;
-        let initial_pool = swap_curve.calculator.new_pool_supply() / 10;
-        let maximum_pool_token_amount = to_u64(initial_pool / 4).unwrap();
-        let destination_a_amount = initial_a / 40;
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 9_000_000;
+        let user_key = Pubkey::new_unique();
+        let depositor_key = Pubkey::new_unique();
 
         let mut accounts = SwapAccountInfo::new(
             &user_key,
-            fees,
+            fees.clone(),
             SwapTransferFees::default(),
             swap_curve,
             token_a_amount,
             token_b_amount,
-            &pool_token_program_id,
-            &token_a_program_id,
-            &token_b_program_id,
+            &spl_token::id(),
+            &spl_token::id(),
+            &spl_token::id(),
         );
-
         accounts.initialize_swap().unwrap();
 
-        let (
-            token_a_key,
-            mut token_a_account,
-            _token_b_key,
-            _token_b_account,
-            pool_key,
-            mut pool_account,
-        ) = accounts.setup_token_accounts(
-            &user_key,
-            &withdrawer_key,
-            initial_a,
-            initial_b,
-            initial_pool.try_into().unwrap(),
+        let tiers = vec![
+            WithdrawFeeTier {
+                min_tenure: 100,
+                fee_bps: 5_000,
+            },
+            WithdrawFeeTier {
+                min_tenure: 1_000,
+                fee_bps: 0,
+            },
+        ];
+        let withdraw_fee_schedule_key = Pubkey::new_unique();
+        let mut withdraw_fee_schedule_account =
+            SolanaAccount::new(0, WithdrawFeeSchedule::LEN, &SWAP_PROGRAM_ID);
+
+        // wrong pool fee account owner
+        assert_eq!(
+            Err(SwapError::InvalidOwner.into()),
+            do_process_instruction(
+                set_withdraw_fee_schedule(
+                    &SWAP_PROGRAM_ID,
+                    &accounts.swap_key,
+                    &accounts.pool_fee_key,
+                    &depositor_key,
+                    &withdraw_fee_schedule_key,
+                    SetWithdrawFeeSchedule {
+                        tiers: tiers.clone(),
+                    },
+                )
+                .unwrap(),
+                vec![
+                    &mut accounts.swap_account,
+                    &mut accounts.pool_fee_account,
+                    &mut SolanaAccount::default(),
+                    &mut withdraw_fee_schedule_account,
+                ],
+            )
         );
 
-        let destination_key = Pubkey::new_unique();
-        let mut destination = SolanaAccount::new(
-            account_minimum_balance(),
-            Account::get_packed_len(),
-            &withdrawer_key,
+        // fee increasing with tenure
+        assert_eq!(
+            Err(SwapError::InvalidWithdrawFeeSchedule.into()),
+            do_process_instruction(
+                set_withdraw_fee_schedule(
+                    &SWAP_PROGRAM_ID,
+                    &accounts.swap_key,
+                    &accounts.pool_fee_key,
+                    &user_key,
+                    &withdraw_fee_schedule_key,
+                    SetWithdrawFeeSchedule {
+                        tiers: tiers.iter().rev().copied().collect(),
+                    },
+                )
+                .unwrap(),
+                vec![
+                    &mut accounts.swap_account,
+                    &mut accounts.pool_fee_account,
+                    &mut SolanaAccount::default(),
+                    &mut withdraw_fee_schedule_account,
+                ],
+            )
         );
 
         do_process_instruction(
-            close_account(
-                &pool_token_program_id,
+            set_withdraw_fee_schedule(
+                &SWAP_PROGRAM_ID,
+                &accounts.swap_key,
                 &accounts.pool_fee_key,
-                &destination_key,
                 &user_key,
-                &[],
+                &withdraw_fee_schedule_key,
+                SetWithdrawFeeSchedule {
+                    tiers: tiers.clone(),
+                },
             )
             .unwrap(),
             vec![
+                &mut accounts.swap_account,
                 &mut accounts.pool_fee_account,
-                &mut destination,
                 &mut SolanaAccount::default(),
+                &mut withdraw_fee_schedule_account,
             ],
         )
         .unwrap();
 
-        let user_transfer_authority_key = Pubkey::new_unique();
+        let withdraw_fee_schedule =
+            WithdrawFeeSchedule::unpack(&withdraw_fee_schedule_account.data).unwrap();
+        assert_eq!(withdraw_fee_schedule.swap, accounts.swap_key);
+        assert_eq!(withdraw_fee_schedule.tiers(), tiers.as_slice());
 
-        do_process_instruction(
-            approve(
-                &pool_token_program_id,
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            pool_key,
+            mut pool_account,
+        ) = accounts.setup_token_accounts(
+            &user_key,
+            &depositor_key,
+            token_a_amount,
+            token_b_amount,
+            0,
+        );
+        let lp_tenure_key = Pubkey::new_unique();
+        let mut lp_tenure_account = SolanaAccount::new(0, LpTenure::LEN, &SWAP_PROGRAM_ID);
+        let pool_token_amount = to_u64(INITIAL_SWAP_POOL_AMOUNT / 10).unwrap();
+
+        // deposit tracking the tenure
+        TEST_CLOCK_UNIX_TIMESTAMP.with(|timestamp| timestamp.set(1_000));
+        let swap_key = accounts.swap_key;
+        let authority_key = accounts.authority_key;
+        let swap_token_a_key = accounts.token_a_key;
+        let swap_token_b_key = accounts.token_b_key;
+        let pool_mint_key = accounts.pool_mint_key;
+        let token_a_mint_key = accounts.token_a_mint_key;
+        let token_b_mint_key = accounts.token_b_mint_key;
+        let pool_fee_key = accounts.pool_fee_key;
+        let deposit_instruction = |pool_key: &Pubkey| {
+            let mut instruction = deposit_all_token_types(
+                &SWAP_PROGRAM_ID,
+                &spl_token::id(),
+                &spl_token::id(),
+                &spl_token::id(),
+                &swap_key,
+                &authority_key,
+                &depositor_key,
+                &token_a_key,
+                &token_b_key,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &pool_mint_key,
+                pool_key,
+                &token_a_mint_key,
+                &token_b_mint_key,
+                DepositAllTokenTypes {
+                    pool_token_amount,
+                    maximum_token_a_amount: token_a_amount,
+                    maximum_token_b_amount: token_b_amount,
+                },
+            )
+            .unwrap();
+            instruction
+                .accounts
+                .push(AccountMeta::new(lp_tenure_key, false));
+            instruction
+        };
+        let withdraw_instruction = |withdraw_amount: u64| {
+            let mut instruction = withdraw_all_token_types(
+                &SWAP_PROGRAM_ID,
+                &spl_token::id(),
+                &spl_token::id(),
+                &spl_token::id(),
+                &swap_key,
+                &authority_key,
+                &depositor_key,
+                &pool_mint_key,
+                &pool_fee_key,
                 &pool_key,
-                &user_transfer_authority_key,
-                &withdrawer_key,
-                &[],
-                maximum_pool_token_amount,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_a_key,
+                &token_b_key,
+                &token_a_mint_key,
+                &token_b_mint_key,
+                WithdrawAllTokenTypes {
+                    pool_token_amount: withdraw_amount,
+                    minimum_token_a_amount: 0,
+                    minimum_token_b_amount: 0,
+                },
             )
-            .unwrap(),
+            .unwrap();
+            instruction.accounts.extend([
+                AccountMeta::new_readonly(withdraw_fee_schedule_key, false),
+                AccountMeta::new(lp_tenure_key, false),
+            ]);
+            instruction
+        };
+        let deposit = deposit_instruction(&pool_key);
+        let result = do_process_instruction(
+            deposit.clone(),
             vec![
+                &mut accounts.swap_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                &mut token_a_account,
+                &mut token_b_account,
+                &mut accounts.token_a_account,
+                &mut accounts.token_b_account,
+                &mut accounts.pool_mint_account,
                 &mut pool_account,
+                &mut accounts.token_a_mint_account,
+                &mut accounts.token_b_mint_account,
                 &mut SolanaAccount::default(),
                 &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                &mut lp_tenure_account,
             ],
-        )
-        .unwrap();
-
-        do_process_instruction(
-            withdraw_single_token_type_exact_amount_out(
-                &SWAP_PROGRAM_ID,
-                &pool_token_program_id,
-                &token_a_program_id,
-                &accounts.swap_key,
-                &accounts.authority_key,
-                &user_transfer_authority_key,
-                &accounts.pool_mint_key,
-                &accounts.pool_fee_key,
-                &pool_key,
-                &accounts.token_a_key,
-                &accounts.token_b_key,
-                &token_a_key,
-                &accounts.token_a_mint_key,
-                WithdrawSingleTokenTypeExactAmountOut {
-                    destination_token_amount: destination_a_amount,
-                    maximum_pool_token_amount,
-                },
+        );
+        TEST_CLOCK_UNIX_TIMESTAMP.with(|timestamp| timestamp.set(0));
+        result.unwrap();
+
+        let lp_tenure = LpTenure::unpack(&lp_tenure_account.data).unwrap();
+        assert_eq!(lp_tenure.swap, accounts.swap_key);
+        assert_eq!(lp_tenure.pool_token_account, pool_key);
+        assert_eq!(lp_tenure.pool_token_amount, pool_token_amount);
+        assert_eq!(lp_tenure.deposit_timestamp, 1_000);
+
+        // the LP tenure account is required with the withdraw fee schedule
+        let mut instruction = withdraw_instruction(pool_token_amount / 2);
+        instruction.accounts.truncate(16);
+        assert_eq!(
+            Err(ProgramError::NotEnoughAccountKeys),
+            do_process_instruction(
+                instruction,
+                vec![
+                    &mut accounts.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut accounts.pool_mint_account,
+                    &mut pool_account,
+                    &mut accounts.token_a_account,
+                    &mut accounts.token_b_account,
+                    &mut token_a_account,
+                    &mut token_b_account,
+                    &mut accounts.pool_fee_account,
+                    &mut accounts.token_a_mint_account,
+                    &mut accounts.token_b_mint_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut withdraw_fee_schedule_account.clone(),
+                ],
             )
-            .unwrap(),
+        );
+
+        // withdraw half of the deposit in the first tier, then the rest in the
+        // fee holiday
+        let withdraw_and_collect_fee =
+            |accounts: &mut SwapAccountInfo,
+             pool_account: &mut SolanaAccount,
+             token_a_account: &mut SolanaAccount,
+             token_b_account: &mut SolanaAccount,
+             lp_tenure_account: &mut SolanaAccount,
+             timestamp: UnixTimestamp,
+             withdraw_amount: u64| {
+                let pool_fee_amount =
+                    StateWithExtensions::<Account>::unpack(&accounts.pool_fee_account.data)
+                        .unwrap()
+                        .base
+                        .amount;
+                TEST_CLOCK_UNIX_TIMESTAMP.with(|t| t.set(timestamp));
+                let result = do_process_instruction(
+                    withdraw_instruction(withdraw_amount),
+                    vec![
+                        &mut accounts.swap_account,
+                        &mut SolanaAccount::default(),
+                        &mut SolanaAccount::default(),
+                        &mut accounts.pool_mint_account,
+                        pool_account,
+                        &mut accounts.token_a_account,
+                        &mut accounts.token_b_account,
+                        token_a_account,
+                        token_b_account,
+                        &mut accounts.pool_fee_account,
+                        &mut accounts.token_a_mint_account,
+                        &mut accounts.token_b_mint_account,
+                        &mut SolanaAccount::default(),
+                        &mut SolanaAccount::default(),
+                        &mut SolanaAccount::default(),
+                        &mut withdraw_fee_schedule_account.clone(),
+                        lp_tenure_account,
+                    ],
+                );
+                TEST_CLOCK_UNIX_TIMESTAMP.with(|t| t.set(0));
+                result.unwrap();
+                let pool_fee =
+                    StateWithExtensions::<Account>::unpack(&accounts.pool_fee_account.data)
+                        .unwrap()
+                        .base;
+                u128::from(pool_fee.amount - pool_fee_amount)
+            };
+        for (timestamp, fee_bps) in [(1_500, 5_000u16), (2_000, 0)] {
+            let withdraw_amount = pool_token_amount / 2;
+            let fee = withdraw_and_collect_fee(
+                &mut accounts,
+                &mut pool_account,
+                &mut token_a_account,
+                &mut token_b_account,
+                &mut lp_tenure_account,
+                timestamp,
+                withdraw_amount,
+            );
+            let owner_withdraw_fee = fees
+                .owner_withdraw_fee(u128::from(withdraw_amount))
+                .unwrap();
+            assert_eq!(fee, owner_withdraw_fee * u128::from(fee_bps) / 10_000);
+        }
+
+        let lp_tenure = LpTenure::unpack(&lp_tenure_account.data).unwrap();
+        assert_eq!(lp_tenure.pool_token_amount, 0);
+
+        // pool tokens moved out of the pool account outside of the swap reset
+        // the tenure of the whole balance, so tokens moved back in don't
+        // inherit it
+        TEST_CLOCK_UNIX_TIMESTAMP.with(|timestamp| timestamp.set(3_000));
+        let result = do_process_instruction(
+            deposit,
             vec![
                 &mut accounts.swap_account,
                 &mut SolanaAccount::default(),
                 &mut SolanaAccount::default(),
+                &mut token_a_account,
+                &mut token_b_account,
+                &mut accounts.token_a_account,
+                &mut accounts.token_b_account,
                 &mut accounts.pool_mint_account,
                 &mut pool_account,
-                &mut accounts.token_a_account,
-                &mut accounts.token_b_account,
-                &mut token_a_account,
-                &mut accounts.pool_fee_account,
-                &mut accounts.token_a_mint_account,
+                &mut accounts.token_a_mint_account,
+                &mut accounts.token_b_mint_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                &mut lp_tenure_account,
+            ],
+        );
+        TEST_CLOCK_UNIX_TIMESTAMP.with(|timestamp| timestamp.set(0));
+        result.unwrap();
+
+        let (other_pool_key, mut other_pool_account) = mint_token(
+            &spl_token::id(),
+            &accounts.pool_mint_key,
+            &mut accounts.pool_mint_account,
+            &accounts.authority_key,
+            &depositor_key,
+            0,
+        );
+        let transfer = |source: &Pubkey, destination: &Pubkey, amount: u64| {
+            spl_token::instruction::transfer(
+                &spl_token::id(),
+                source,
+                destination,
+                &depositor_key,
+                &[],
+                amount,
+            )
+            .unwrap()
+        };
+        do_process_instruction(
+            transfer(&pool_key, &other_pool_key, pool_token_amount),
+            vec![
+                &mut pool_account,
+                &mut other_pool_account,
                 &mut SolanaAccount::default(),
+            ],
+        )
+        .unwrap();
+        do_process_instruction(
+            transfer(&other_pool_key, &pool_key, pool_token_amount / 2),
+            vec![
+                &mut other_pool_account,
+                &mut pool_account,
                 &mut SolanaAccount::default(),
             ],
         )
         .unwrap();
+
+        let withdraw_amount = pool_token_amount / 2;
+        let fee = withdraw_and_collect_fee(
+            &mut accounts,
+            &mut pool_account,
+            &mut token_a_account,
+            &mut token_b_account,
+            &mut lp_tenure_account,
+            5_000,
+            withdraw_amount,
+        );
+        assert_eq!(
+            fee,
+            fees.owner_withdraw_fee(u128::from(withdraw_amount))
+                .unwrap()
+        );
+        let lp_tenure = LpTenure::unpack(&lp_tenure_account.data).unwrap();
+        assert_eq!(lp_tenure.pool_token_amount, 0);
+        assert_eq!(lp_tenure.deposit_timestamp, 5_000);
+
+        // the tenure only tracks deposits into its pool account
+        assert_eq!(
+            Err(SwapError::IncorrectLpTenure.into()),
+            do_process_instruction(
+                deposit_instruction(&other_pool_key),
+                vec![
+                    &mut accounts.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut token_a_account,
+                    &mut token_b_account,
+                    &mut accounts.token_a_account,
+                    &mut accounts.token_b_account,
+                    &mut accounts.pool_mint_account,
+                    &mut other_pool_account,
+                    &mut accounts.token_a_mint_account,
+                    &mut accounts.token_b_mint_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut lp_tenure_account,
+                ],
+            )
+        );
     }
 
     #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
     #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
     #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
-    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
-    fn test_valid_swap_with_invalid_fee_account(
+    fn test_zap_in(
         pool_token_program_id: Pubkey,
         token_a_program_id: Pubkey,
         token_b_program_id: Pubkey,
     ) {
-        let owner_key = &Pubkey::new_unique();
-
-        let token_a_amount = 1_000_000;
-        let token_b_amount = 5_000_000;
-
         let fees = Fees {
-            trade_fee_numerator: 1,
-            trade_fee_denominator: 10,
-            owner_trade_fee_numerator: 1,
-            owner_trade_fee_denominator: 30,
-            owner_withdraw_fee_numerator: 1,
-            owner_withdraw_fee_denominator: 30,
-            host_fee_numerator: 10,
-            host_fee_denominator: 100,
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator: 5,
+            owner_trade_fee_denominator: 10_000,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
         };
-
         let swap_curve = SwapCurve {
             curve_type: CurveType::ConstantProduct,
             calculator: Arc::new(ConstantProductCurve {}),
         };
+        let token_a_amount = 1_000_000_000;
+        let token_b_amount = 4_000_000_000;
+        let user_key = Pubkey::new_unique();
+        let depositor_key = Pubkey::new_unique();
 
-        let owner_key_str = owner_key.to_string();
-        let constraints = Some(SwapConstraints {
-            owner_key: Some(owner_key_str.as_ref()),
-            valid_curve_types: &[CurveType::ConstantProduct],
-            fees: &fees,
-        });
         let mut accounts = SwapAccountInfo::new(
-            owner_key,
-            fees.clone(),
+            &user_key,
+            fees,
             SwapTransferFees::default(),
             swap_curve,
             token_a_amount,
@@ -8205,38 +11371,9 @@ mod tests {
             &token_a_program_id,
             &token_b_program_id,
         );
+        accounts.initialize_swap().unwrap();
 
-        do_process_instruction_with_fee_constraints(
-            initialize(
-                &SWAP_PROGRAM_ID,
-                &pool_token_program_id,
-                &accounts.swap_key,
-                &accounts.authority_key,
-                &accounts.token_a_key,
-                &accounts.token_b_key,
-                &accounts.pool_mint_key,
-                &accounts.pool_fee_key,
-                &accounts.pool_token_key,
-                accounts.fees.clone(),
-                accounts.swap_curve.clone(),
-            )
-            .unwrap(),
-            vec![
-                &mut accounts.swap_account,
-                &mut SolanaAccount::default(),
-                &mut accounts.token_a_account,
-                &mut accounts.token_b_account,
-                &mut accounts.pool_mint_account,
-                &mut accounts.pool_fee_account,
-                &mut accounts.pool_token_account,
-                &mut SolanaAccount::default(),
-            ],
-            &constraints,
-        )
-        .unwrap();
-
-        let authority_key = accounts.authority_key;
-
+        let source_token_amount = 100_000_000;
         let (
             token_a_key,
             mut token_a_account,
@@ -8244,133 +11381,87 @@ mod tests {
             mut token_b_account,
             pool_key,
             mut pool_account,
-        ) = accounts.setup_token_accounts(
-            owner_key,
-            &authority_key,
-            token_a_amount,
-            token_b_amount,
-            0,
-        );
-
-        let destination_key = Pubkey::new_unique();
-        let mut destination = SolanaAccount::new(
-            account_minimum_balance(),
-            Account::get_packed_len(),
-            owner_key,
-        );
+        ) = accounts.setup_token_accounts(&user_key, &depositor_key, source_token_amount, 0, 0);
+        let swap_token_a_key = accounts.token_a_key;
+        let swap_token_b_key = accounts.token_b_key;
 
-        do_process_instruction(
-            close_account(
-                &pool_token_program_id,
-                &accounts.pool_fee_key,
-                &destination_key,
-                owner_key,
-                &[],
+        // slippage bound not met
+        assert_eq!(
+            Err(SwapError::ExceededSlippage.into()),
+            accounts.zap_in(
+                &depositor_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                &pool_key,
+                &mut pool_account,
+                source_token_amount,
+                u64::MAX,
             )
-            .unwrap(),
-            vec![
-                &mut accounts.pool_fee_account,
-                &mut destination,
-                &mut SolanaAccount::default(),
-            ],
-        )
-        .unwrap();
+        );
 
-        do_process_instruction_with_fee_constraints(
-            swap(
-                &SWAP_PROGRAM_ID,
-                &token_a_program_id,
-                &token_b_program_id,
-                &pool_token_program_id,
-                &accounts.swap_key,
-                &accounts.authority_key,
-                &accounts.authority_key,
+        // mismatched swap accounts
+        assert_eq!(
+            Err(SwapError::IncorrectSwapAccount.into()),
+            accounts.zap_in(
+                &depositor_key,
                 &token_a_key,
-                &accounts.token_a_key,
-                &accounts.token_b_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_a_key,
                 &token_b_key,
-                &accounts.pool_mint_key,
-                &accounts.pool_fee_key,
-                &accounts.token_a_mint_key,
-                &accounts.token_b_mint_key,
-                Some(&pool_key),
-                Swap {
-                    amount_in: token_a_amount / 2,
-                    minimum_amount_out: 0,
-                },
+                &mut token_b_account,
+                &pool_key,
+                &mut pool_account,
+                source_token_amount,
+                0,
             )
-            .unwrap(),
-            vec![
-                &mut accounts.swap_account,
-                &mut SolanaAccount::default(),
-                &mut SolanaAccount::default(),
+        );
+
+        let pool_mint_supply =
+            StateWithExtensions::<Mint>::unpack(&accounts.pool_mint_account.data)
+                .unwrap()
+                .base
+                .supply;
+        accounts
+            .zap_in(
+                &depositor_key,
+                &token_a_key,
                 &mut token_a_account,
-                &mut accounts.token_a_account,
-                &mut accounts.token_b_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
                 &mut token_b_account,
-                &mut accounts.pool_mint_account,
-                &mut accounts.pool_fee_account,
-                &mut accounts.token_a_mint_account,
-                &mut accounts.token_b_mint_account,
-                &mut SolanaAccount::default(),
-                &mut SolanaAccount::default(),
-                &mut SolanaAccount::default(),
+                &pool_key,
                 &mut pool_account,
-            ],
-            &constraints,
-        )
-        .unwrap();
-    }
-
-    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
-    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
-    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
-    fn test_swap_curve_with_transfer_fees(
-        pool_token_program_id: Pubkey,
-        token_a_program_id: Pubkey,
-        token_b_program_id: Pubkey,
-    ) {
-        // All fees
-        let trade_fee_numerator = 1;
-        let trade_fee_denominator = 10;
-        let owner_trade_fee_numerator = 1;
-        let owner_trade_fee_denominator = 30;
-        let owner_withdraw_fee_numerator = 1;
-        let owner_withdraw_fee_denominator = 30;
-        let host_fee_numerator = 20;
-        let host_fee_denominator = 100;
-        let fees = Fees {
-            trade_fee_numerator,
-            trade_fee_denominator,
-            owner_trade_fee_numerator,
-            owner_trade_fee_denominator,
-            owner_withdraw_fee_numerator,
-            owner_withdraw_fee_denominator,
-            host_fee_numerator,
-            host_fee_denominator,
-        };
-
-        let token_a_amount = 10_000_000_000;
-        let token_b_amount = 50_000_000_000;
+                source_token_amount,
+                0,
+            )
+            .unwrap();
 
-        check_valid_swap_curve(
-            fees,
-            SwapTransferFees {
-                pool_token: TransferFee::default(),
-                token_a: TransferFee {
-                    epoch: 0.into(),
-                    transfer_fee_basis_points: 100.into(),
-                    maximum_fee: 1_000_000_000.into(),
-                },
-                token_b: TransferFee::default(),
-            },
-            CurveType::ConstantProduct,
-            Arc::new(ConstantProductCurve {}),
-            token_a_amount,
-            token_b_amount,
-            &pool_token_program_id,
-            &token_a_program_id,
-            &token_b_program_id,
-        );
+        // the pool tokens are worth about half of the source, the other half
+        // pays for the swap, and only dust is left over
+        let pool_tokens = StateWithExtensions::<Account>::unpack(&pool_account.data)
+            .unwrap()
+            .base
+            .amount;
+        let expected_pool_tokens = u128::from(pool_mint_supply)
+            * u128::from(source_token_amount / 2)
+            / u128::from(token_a_amount);
+        assert!(u128::from(pool_tokens) > expected_pool_tokens * 95 / 100);
+        assert!(u128::from(pool_tokens) < expected_pool_tokens);
+        let token_a_left = StateWithExtensions::<Account>::unpack(&token_a_account.data)
+            .unwrap()
+            .base
+            .amount;
+        let token_b_left = StateWithExtensions::<Account>::unpack(&token_b_account.data)
+            .unwrap()
+            .base
+            .amount;
+        assert!(token_a_left < source_token_amount / 10_000);
+        assert!(token_b_left < source_token_amount / 10_000 * 4);
     }
 }
@@ -9,6 +9,7 @@ use {
     enum_dispatch::enum_dispatch,
     solana_program::{
         account_info::AccountInfo,
+        clock::UnixTimestamp,
         msg,
         program_error::ProgramError,
         program_pack::{IsInitialized, Pack, Sealed},
@@ -18,7 +19,7 @@ use {
         extension::StateWithExtensions,
         state::{Account, AccountState},
     },
-    std::sync::Arc,
+    std::{cmp::Ordering, convert::TryInto, sync::Arc},
 };
 
 /// Trait representing access to program state across all versions
@@ -73,19 +74,28 @@ impl SwapVersion {
         match src {
             Self::SwapV1(swap_info) => {
                 dst[0] = 1;
-                SwapV1::pack(swap_info, &mut dst[1..])
+                let dst = dst
+                    .get_mut(1..Self::LATEST_LEN)
+                    .ok_or(ProgramError::InvalidAccountData)?;
+                SwapV1::pack(swap_info, dst)
             }
         }
     }
 
     /// Unpack the swap account based on its version, returning the result as a
-    /// SwapState trait object
+    /// SwapState trait object. Any data after the swap state, such as a
+    /// [ProtocolFee](struct.ProtocolFee.html), is ignored.
     pub fn unpack(input: &[u8]) -> Result<Arc<dyn SwapState>, ProgramError> {
         let (&version, rest) = input
             .split_first()
             .ok_or(ProgramError::InvalidAccountData)?;
         match version {
-            1 => Ok(Arc::new(SwapV1::unpack(rest)?)),
+            1 => {
+                let rest = rest
+                    .get(..SwapV1::LEN)
+                    .ok_or(ProgramError::InvalidAccountData)?;
+                Ok(Arc::new(SwapV1::unpack(rest)?))
+            }
             _ => Err(ProgramError::UninitializedAccount),
         }
     }
@@ -281,6 +291,581 @@ impl Pack for SwapV1 {
     }
 }
 
+/// Seed of the position vault of a swap, see
+/// [Processor::position_vault_id](crate::processor::Processor::position_vault_id)
+pub const POSITION_VAULT_SEED: &[u8] = b"position_vault";
+
+/// Seed of the position group of a swap, see
+/// [Processor::position_group_id](crate::processor::Processor::position_group_id)
+pub const POSITION_GROUP_SEED: &[u8] = b"position_group";
+
+/// Name in the Token-2022 metadata of position NFTs
+pub const POSITION_METADATA_NAME: &str = "Token Swap LP Position";
+
+/// Symbol in the Token-2022 metadata of position NFTs
+pub const POSITION_METADATA_SYMBOL: &str = "SWAP-LP";
+
+/// LP position held as a Token-2022 NFT instead of fungible pool tokens.
+/// The pool tokens backing the position are locked in the position vault of
+/// the swap until the position is redeemed. The entry data of the position is
+/// also written to the Token-2022 metadata of the NFT, so wallets and indexers
+/// can read it.
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+pub struct LpPosition {
+    /// Initialized state.
+    pub is_initialized: bool,
+    /// Token-swap the position belongs to
+    pub swap: Pubkey,
+    /// Mint of the position NFT, supply is 1 while the position is open
+    pub position_mint: Pubkey,
+    /// Amount of pool tokens locked in the position
+    pub pool_token_amount: u64,
+    /// Amount of token A the pool tokens were worth when the position was
+    /// issued
+    pub entry_token_a_amount: u64,
+    /// Amount of token B the pool tokens were worth when the position was
+    /// issued
+    pub entry_token_b_amount: u64,
+    /// Pool token supply when the position was issued
+    pub entry_pool_token_supply: u64,
+}
+
+impl LpPosition {
+    /// Additional metadata fields of the position NFT
+    pub fn metadata_fields(&self, position: &Pubkey) -> Vec<(String, String)> {
+        vec![
+            ("swap".to_string(), self.swap.to_string()),
+            ("position".to_string(), position.to_string()),
+            (
+                "pool_token_amount".to_string(),
+                self.pool_token_amount.to_string(),
+            ),
+            (
+                "entry_token_a_amount".to_string(),
+                self.entry_token_a_amount.to_string(),
+            ),
+            (
+                "entry_token_b_amount".to_string(),
+                self.entry_token_b_amount.to_string(),
+            ),
+            (
+                "entry_pool_token_supply".to_string(),
+                self.entry_pool_token_supply.to_string(),
+            ),
+        ]
+    }
+}
+
+impl Sealed for LpPosition {}
+impl IsInitialized for LpPosition {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for LpPosition {
+    const LEN: usize = 97;
+
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 97];
+        let (
+            is_initialized,
+            swap,
+            position_mint,
+            pool_token_amount,
+            entry_token_a_amount,
+            entry_token_b_amount,
+            entry_pool_token_supply,
+        ) = mut_array_refs![output, 1, 32, 32, 8, 8, 8, 8];
+        is_initialized[0] = self.is_initialized as u8;
+        swap.copy_from_slice(self.swap.as_ref());
+        position_mint.copy_from_slice(self.position_mint.as_ref());
+        *pool_token_amount = self.pool_token_amount.to_le_bytes();
+        *entry_token_a_amount = self.entry_token_a_amount.to_le_bytes();
+        *entry_token_b_amount = self.entry_token_b_amount.to_le_bytes();
+        *entry_pool_token_supply = self.entry_pool_token_supply.to_le_bytes();
+    }
+
+    /// Unpacks a byte buffer into a [LpPosition](struct.LpPosition.html).
+    fn unpack_from_slice(input: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![input, 0, 97];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            is_initialized,
+            swap,
+            position_mint,
+            pool_token_amount,
+            entry_token_a_amount,
+            entry_token_b_amount,
+            entry_pool_token_supply,
+        ) = array_refs![input, 1, 32, 32, 8, 8, 8, 8];
+        Ok(Self {
+            is_initialized: match is_initialized {
+                [0] => false,
+                [1] => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            swap: Pubkey::new_from_array(*swap),
+            position_mint: Pubkey::new_from_array(*position_mint),
+            pool_token_amount: u64::from_le_bytes(*pool_token_amount),
+            entry_token_a_amount: u64::from_le_bytes(*entry_token_a_amount),
+            entry_token_b_amount: u64::from_le_bytes(*entry_token_b_amount),
+            entry_pool_token_supply: u64::from_le_bytes(*entry_pool_token_supply),
+        })
+    }
+}
+
+/// Registration allowing a keeper to flash swap against a token-swap with
+/// reduced fees, created by the owner of the pool fee account
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+pub struct KeeperRegistration {
+    /// Initialized state.
+    pub is_initialized: bool,
+    /// Token-swap the keeper is registered with
+    pub swap: Pubkey,
+    /// Keeper allowed to flash swap
+    pub keeper: Pubkey,
+}
+
+impl Sealed for KeeperRegistration {}
+impl IsInitialized for KeeperRegistration {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for KeeperRegistration {
+    const LEN: usize = 65;
+
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 65];
+        let (is_initialized, swap, keeper) = mut_array_refs![output, 1, 32, 32];
+        is_initialized[0] = self.is_initialized as u8;
+        swap.copy_from_slice(self.swap.as_ref());
+        keeper.copy_from_slice(self.keeper.as_ref());
+    }
+
+    /// Unpacks a byte buffer into a
+    /// [KeeperRegistration](struct.KeeperRegistration.html).
+    fn unpack_from_slice(input: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![input, 0, 65];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (is_initialized, swap, keeper) = array_refs![input, 1, 32, 32];
+        Ok(Self {
+            is_initialized: match is_initialized {
+                [0] => false,
+                [1] => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            swap: Pubkey::new_from_array(*swap),
+            keeper: Pubkey::new_from_array(*keeper),
+        })
+    }
+}
+
+/// Maximum share of the owner trade fee the protocol fee can take, in basis
+/// points
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 10_000;
+
+/// Protocol fee switch, stored right after the swap state in swap accounts
+/// created with room for it.
+///
+/// The protocol takes `fee_bps` of the pool tokens minted as owner trade fee
+/// on every swap. These pool tokens accrue in `pool_tokens_owed` and are
+/// minted to the recipient when collected. Any change to the authority,
+/// recipient or share is proposed by the authority and only takes effect
+/// `timelock_slots` after the proposal.
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+pub struct ProtocolFee {
+    /// Initialized state.
+    pub is_initialized: bool,
+    /// Authority allowed to propose protocol fee changes
+    pub authority: Pubkey,
+    /// Pool token account receiving the protocol fee
+    pub recipient: Pubkey,
+    /// Share of the owner trade fee taken by the protocol, in basis points
+    pub fee_bps: u16,
+    /// Number of slots a proposed change must wait before execution
+    pub timelock_slots: u64,
+    /// Proposed authority
+    pub pending_authority: Pubkey,
+    /// Proposed recipient
+    pub pending_recipient: Pubkey,
+    /// Proposed share of the owner trade fee, in basis points
+    pub pending_fee_bps: u16,
+    /// Slot from which the proposed change can be executed, 0 if no change is
+    /// pending
+    pub pending_execute_slot: u64,
+    /// Pool tokens owed to the recipient and not yet collected
+    pub pool_tokens_owed: u64,
+}
+
+impl ProtocolFee {
+    /// Offset of the protocol fee in the swap account data
+    pub const OFFSET: usize = SwapVersion::LATEST_LEN;
+    /// Size of a swap account with room for a protocol fee
+    pub const SWAP_ACCOUNT_LEN: usize = Self::OFFSET + <Self as Pack>::LEN;
+
+    /// Protocol fee part of the pool tokens minted as owner trade fee
+    pub fn protocol_fee(&self, owner_fee_pool_tokens: u128) -> Option<u128> {
+        owner_fee_pool_tokens
+            .checked_mul(u128::from(self.fee_bps))?
+            .checked_div(u128::from(MAX_PROTOCOL_FEE_BPS))
+    }
+}
+
+impl Sealed for ProtocolFee {}
+impl IsInitialized for ProtocolFee {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for ProtocolFee {
+    const LEN: usize = 157;
+
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 157];
+        let (
+            is_initialized,
+            authority,
+            recipient,
+            fee_bps,
+            timelock_slots,
+            pending_authority,
+            pending_recipient,
+            pending_fee_bps,
+            pending_execute_slot,
+            pool_tokens_owed,
+        ) = mut_array_refs![output, 1, 32, 32, 2, 8, 32, 32, 2, 8, 8];
+        is_initialized[0] = self.is_initialized as u8;
+        authority.copy_from_slice(self.authority.as_ref());
+        recipient.copy_from_slice(self.recipient.as_ref());
+        *fee_bps = self.fee_bps.to_le_bytes();
+        *timelock_slots = self.timelock_slots.to_le_bytes();
+        pending_authority.copy_from_slice(self.pending_authority.as_ref());
+        pending_recipient.copy_from_slice(self.pending_recipient.as_ref());
+        *pending_fee_bps = self.pending_fee_bps.to_le_bytes();
+        *pending_execute_slot = self.pending_execute_slot.to_le_bytes();
+        *pool_tokens_owed = self.pool_tokens_owed.to_le_bytes();
+    }
+
+    /// Unpacks a byte buffer into a [ProtocolFee](struct.ProtocolFee.html).
+    fn unpack_from_slice(input: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![input, 0, 157];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            is_initialized,
+            authority,
+            recipient,
+            fee_bps,
+            timelock_slots,
+            pending_authority,
+            pending_recipient,
+            pending_fee_bps,
+            pending_execute_slot,
+            pool_tokens_owed,
+        ) = array_refs![input, 1, 32, 32, 2, 8, 32, 32, 2, 8, 8];
+        Ok(Self {
+            is_initialized: match is_initialized {
+                [0] => false,
+                [1] => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            authority: Pubkey::new_from_array(*authority),
+            recipient: Pubkey::new_from_array(*recipient),
+            fee_bps: u16::from_le_bytes(*fee_bps),
+            timelock_slots: u64::from_le_bytes(*timelock_slots),
+            pending_authority: Pubkey::new_from_array(*pending_authority),
+            pending_recipient: Pubkey::new_from_array(*pending_recipient),
+            pending_fee_bps: u16::from_le_bytes(*pending_fee_bps),
+            pending_execute_slot: u64::from_le_bytes(*pending_execute_slot),
+            pool_tokens_owed: u64::from_le_bytes(*pool_tokens_owed),
+        })
+    }
+}
+
+/// Maximum number of tiers in a withdraw fee schedule
+pub const MAX_WITHDRAW_FEE_TIERS: usize = 4;
+
+/// Share of the owner withdraw fee charged without tenure, in basis points
+pub const MAX_WITHDRAW_FEE_BPS: u16 = 10_000;
+
+/// Tier of a [WithdrawFeeSchedule](struct.WithdrawFeeSchedule.html)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WithdrawFeeTier {
+    /// Minimum tenure, in seconds, of the withdrawn pool tokens for the tier
+    /// to apply
+    pub min_tenure: u64,
+    /// Share of the owner withdraw fee charged in the tier, in basis points.
+    /// Zero makes the tier a fee holiday.
+    pub fee_bps: u16,
+}
+
+impl WithdrawFeeTier {
+    /// Packed length of a tier
+    pub const LEN: usize = 10;
+
+    /// Packs the tier into a byte array
+    pub fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 10];
+        let (min_tenure, fee_bps) = mut_array_refs![output, 8, 2];
+        *min_tenure = self.min_tenure.to_le_bytes();
+        *fee_bps = self.fee_bps.to_le_bytes();
+    }
+
+    /// Unpacks a tier from a byte array
+    pub fn unpack_from_slice(input: &[u8]) -> Self {
+        let input = array_ref![input, 0, 10];
+        let (min_tenure, fee_bps) = array_refs![input, 8, 2];
+        Self {
+            min_tenure: u64::from_le_bytes(*min_tenure),
+            fee_bps: u16::from_le_bytes(*fee_bps),
+        }
+    }
+}
+
+/// Schedule scaling down the owner withdraw fee with the tenure of the
+/// withdrawn pool tokens, created by the owner of the pool fee account.
+///
+/// The tiers are sorted by increasing tenure and decreasing fee. Pool tokens
+/// held for less than the first tier pay the full owner withdraw fee.
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+pub struct WithdrawFeeSchedule {
+    /// Initialized state.
+    pub is_initialized: bool,
+    /// Token-swap the schedule applies to
+    pub swap: Pubkey,
+    /// Number of tiers in use
+    pub tiers_count: u8,
+    /// Fee tiers, only the first `tiers_count` are used
+    pub tiers: [WithdrawFeeTier; MAX_WITHDRAW_FEE_TIERS],
+}
+
+impl WithdrawFeeSchedule {
+    /// Tiers in use
+    pub fn tiers(&self) -> &[WithdrawFeeTier] {
+        let tiers_count = usize::from(self.tiers_count).min(MAX_WITHDRAW_FEE_TIERS);
+        &self.tiers[..tiers_count]
+    }
+
+    /// Checks the tiers are sorted by strictly increasing tenure and
+    /// decreasing fee
+    pub fn validate(tiers: &[WithdrawFeeTier]) -> Result<(), SwapError> {
+        if tiers.len() > MAX_WITHDRAW_FEE_TIERS {
+            return Err(SwapError::InvalidWithdrawFeeSchedule);
+        }
+        let mut previous = WithdrawFeeTier {
+            min_tenure: 0,
+            fee_bps: MAX_WITHDRAW_FEE_BPS,
+        };
+        for (i, tier) in tiers.iter().enumerate() {
+            if (i > 0 && tier.min_tenure <= previous.min_tenure) || tier.fee_bps > previous.fee_bps
+            {
+                return Err(SwapError::InvalidWithdrawFeeSchedule);
+            }
+            previous = *tier;
+        }
+        Ok(())
+    }
+
+    /// Share of the owner withdraw fee charged for the given tenure, in basis
+    /// points
+    pub fn fee_bps(&self, tenure: u64) -> u16 {
+        self.tiers()
+            .iter()
+            .rev()
+            .find(|tier| tier.min_tenure <= tenure)
+            .map_or(MAX_WITHDRAW_FEE_BPS, |tier| tier.fee_bps)
+    }
+
+    /// Owner withdraw fee once scaled down for the tenured part of the
+    /// withdrawn pool tokens
+    pub fn withdraw_fee(
+        &self,
+        owner_withdraw_fee: u128,
+        pool_token_amount: u128,
+        tenured_pool_token_amount: u128,
+        tenure: u64,
+    ) -> Option<u128> {
+        if pool_token_amount == 0 {
+            return Some(owner_withdraw_fee);
+        }
+        let discount_bps = MAX_WITHDRAW_FEE_BPS.checked_sub(self.fee_bps(tenure))?;
+        let discount = owner_withdraw_fee
+            .checked_mul(tenured_pool_token_amount)?
+            .checked_mul(u128::from(discount_bps))?
+            .checked_div(pool_token_amount.checked_mul(u128::from(MAX_WITHDRAW_FEE_BPS))?)?;
+        owner_withdraw_fee.checked_sub(discount)
+    }
+}
+
+impl Sealed for WithdrawFeeSchedule {}
+impl IsInitialized for WithdrawFeeSchedule {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for WithdrawFeeSchedule {
+    const LEN: usize = 74;
+
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 74];
+        let (is_initialized, swap, tiers_count, tiers) = mut_array_refs![output, 1, 32, 1, 40];
+        is_initialized[0] = self.is_initialized as u8;
+        swap.copy_from_slice(self.swap.as_ref());
+        tiers_count[0] = self.tiers_count;
+        for (tier, output) in self
+            .tiers
+            .iter()
+            .zip(tiers.chunks_exact_mut(WithdrawFeeTier::LEN))
+        {
+            tier.pack_into_slice(output);
+        }
+    }
+
+    /// Unpacks a byte buffer into a
+    /// [WithdrawFeeSchedule](struct.WithdrawFeeSchedule.html).
+    fn unpack_from_slice(input: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![input, 0, 74];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (is_initialized, swap, tiers_count, tiers_data) = array_refs![input, 1, 32, 1, 40];
+        let mut tiers = [WithdrawFeeTier::default(); MAX_WITHDRAW_FEE_TIERS];
+        for (tier, input) in tiers
+            .iter_mut()
+            .zip(tiers_data.chunks_exact(WithdrawFeeTier::LEN))
+        {
+            *tier = WithdrawFeeTier::unpack_from_slice(input);
+        }
+        Ok(Self {
+            is_initialized: match is_initialized {
+                [0] => false,
+                [1] => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            swap: Pubkey::new_from_array(*swap),
+            tiers_count: tiers_count[0],
+            tiers,
+        })
+    }
+}
+
+/// Tenure of the pool tokens deposited into a pool token account, used to
+/// scale down the owner withdraw fee with a
+/// [WithdrawFeeSchedule](struct.WithdrawFeeSchedule.html).
+///
+/// The tracked amount is reconciled with the balance of the account whenever
+/// the swap touches it. Pool tokens transferred in from elsewhere are tracked
+/// from that point, and transferring pool tokens out resets the tenure of the
+/// whole balance, so transferred pool tokens never carry tenure over.
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+pub struct LpTenure {
+    /// Initialized state.
+    pub is_initialized: bool,
+    /// Token-swap the tenure is tracked for
+    pub swap: Pubkey,
+    /// Pool token account the deposited pool tokens are held in
+    pub pool_token_account: Pubkey,
+    /// Tracked pool tokens, the balance of the pool account when last synced
+    pub pool_token_amount: u64,
+    /// Deposit time of the tracked pool tokens, averaged over deposits
+    /// weighted by amount
+    pub deposit_timestamp: UnixTimestamp,
+}
+
+impl LpTenure {
+    /// Reconciles the tracked pool tokens with `balance`, the pool token
+    /// balance of the account at `timestamp`
+    pub fn sync(&mut self, balance: u64, timestamp: UnixTimestamp) -> Option<()> {
+        match balance.cmp(&self.pool_token_amount) {
+            Ordering::Less => {
+                self.pool_token_amount = balance;
+                self.deposit_timestamp = timestamp;
+                Some(())
+            }
+            Ordering::Greater => {
+                self.deposit(balance.checked_sub(self.pool_token_amount)?, timestamp)
+            }
+            Ordering::Equal => Some(()),
+        }
+    }
+
+    /// Tracks pool tokens deposited at `timestamp`
+    pub fn deposit(&mut self, pool_token_amount: u64, timestamp: UnixTimestamp) -> Option<()> {
+        let total_amount = self.pool_token_amount.checked_add(pool_token_amount)?;
+        if total_amount > 0 {
+            let weighted_timestamp = i128::from(self.deposit_timestamp)
+                .checked_mul(i128::from(self.pool_token_amount))?
+                .checked_add(i128::from(timestamp).checked_mul(i128::from(pool_token_amount))?)?
+                .checked_div(i128::from(total_amount))?;
+            self.deposit_timestamp = weighted_timestamp.try_into().ok()?;
+        }
+        self.pool_token_amount = total_amount;
+        Some(())
+    }
+
+    /// Stops tracking withdrawn pool tokens
+    pub fn withdraw(&mut self, pool_token_amount: u64) {
+        self.pool_token_amount = self.pool_token_amount.saturating_sub(pool_token_amount);
+    }
+
+    /// Tenure, in seconds, of the tracked pool tokens at `timestamp`
+    pub fn tenure(&self, timestamp: UnixTimestamp) -> u64 {
+        timestamp
+            .saturating_sub(self.deposit_timestamp)
+            .try_into()
+            .unwrap_or(0)
+    }
+}
+
+impl Sealed for LpTenure {}
+impl IsInitialized for LpTenure {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for LpTenure {
+    const LEN: usize = 81;
+
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 81];
+        let (is_initialized, swap, pool_token_account, pool_token_amount, deposit_timestamp) =
+            mut_array_refs![output, 1, 32, 32, 8, 8];
+        is_initialized[0] = self.is_initialized as u8;
+        swap.copy_from_slice(self.swap.as_ref());
+        pool_token_account.copy_from_slice(self.pool_token_account.as_ref());
+        *pool_token_amount = self.pool_token_amount.to_le_bytes();
+        *deposit_timestamp = self.deposit_timestamp.to_le_bytes();
+    }
+
+    /// Unpacks a byte buffer into a [LpTenure](struct.LpTenure.html).
+    fn unpack_from_slice(input: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![input, 0, 81];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (is_initialized, swap, pool_token_account, pool_token_amount, deposit_timestamp) =
+            array_refs![input, 1, 32, 32, 8, 8];
+        Ok(Self {
+            is_initialized: match is_initialized {
+                [0] => false,
+                [1] => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            swap: Pubkey::new_from_array(*swap),
+            pool_token_account: Pubkey::new_from_array(*pool_token_account),
+            pool_token_amount: u64::from_le_bytes(*pool_token_amount),
+            deposit_timestamp: UnixTimestamp::from_le_bytes(*deposit_timestamp),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, crate::curve::offset::OffsetCurve, std::convert::TryInto};
@@ -406,4 +991,277 @@ mod tests {
         let err = SwapV1::unpack(&packed).unwrap_err();
         assert_eq!(err, ProgramError::UninitializedAccount);
     }
+
+    #[test]
+    fn lp_position_pack() {
+        let position = LpPosition {
+            is_initialized: true,
+            swap: Pubkey::new_from_array([8u8; 32]),
+            position_mint: Pubkey::new_from_array([9u8; 32]),
+            pool_token_amount: 1_000,
+            entry_token_a_amount: 200,
+            entry_token_b_amount: 300,
+            entry_pool_token_supply: 10_000,
+        };
+
+        let mut packed = [0u8; LpPosition::LEN];
+        LpPosition::pack(position, &mut packed).unwrap();
+        let unpacked = LpPosition::unpack(&packed).unwrap();
+        assert_eq!(unpacked.pool_token_amount, 1_000);
+        assert_eq!(unpacked.entry_pool_token_supply, 10_000);
+
+        let mut expect = vec![1u8];
+        expect.extend_from_slice(&[8u8; 32]);
+        expect.extend_from_slice(&[9u8; 32]);
+        expect.extend_from_slice(&1_000u64.to_le_bytes());
+        expect.extend_from_slice(&200u64.to_le_bytes());
+        expect.extend_from_slice(&300u64.to_le_bytes());
+        expect.extend_from_slice(&10_000u64.to_le_bytes());
+        assert_eq!(packed.to_vec(), expect);
+
+        let packed = [0u8; LpPosition::LEN];
+        let err = LpPosition::unpack(&packed).unwrap_err();
+        assert_eq!(err, ProgramError::UninitializedAccount);
+    }
+
+    #[test]
+    fn keeper_registration_pack() {
+        let registration = KeeperRegistration {
+            is_initialized: true,
+            swap: Pubkey::new_from_array([8u8; 32]),
+            keeper: Pubkey::new_from_array([9u8; 32]),
+        };
+
+        let mut packed = [0u8; KeeperRegistration::LEN];
+        KeeperRegistration::pack(registration, &mut packed).unwrap();
+        let unpacked = KeeperRegistration::unpack(&packed).unwrap();
+        assert_eq!(unpacked.keeper, Pubkey::new_from_array([9u8; 32]));
+
+        let mut expect = vec![1u8];
+        expect.extend_from_slice(&[8u8; 32]);
+        expect.extend_from_slice(&[9u8; 32]);
+        assert_eq!(packed.to_vec(), expect);
+
+        let packed = [0u8; KeeperRegistration::LEN];
+        let err = KeeperRegistration::unpack(&packed).unwrap_err();
+        assert_eq!(err, ProgramError::UninitializedAccount);
+    }
+
+    #[test]
+    fn protocol_fee_pack() {
+        let protocol_fee = ProtocolFee {
+            is_initialized: true,
+            authority: Pubkey::new_from_array([8u8; 32]),
+            recipient: Pubkey::new_from_array([9u8; 32]),
+            fee_bps: 2_500,
+            timelock_slots: 1_000,
+            pending_authority: Pubkey::new_from_array([10u8; 32]),
+            pending_recipient: Pubkey::new_from_array([11u8; 32]),
+            pending_fee_bps: 5_000,
+            pending_execute_slot: 1_200,
+            pool_tokens_owed: 42,
+        };
+
+        let mut packed = [0u8; ProtocolFee::LEN];
+        ProtocolFee::pack_into_slice(&protocol_fee, &mut packed);
+        let unpacked = ProtocolFee::unpack(&packed).unwrap();
+        assert_eq!(unpacked, protocol_fee);
+        assert_eq!(unpacked.protocol_fee(1_000), Some(250));
+
+        let mut expect = vec![1u8];
+        expect.extend_from_slice(&[8u8; 32]);
+        expect.extend_from_slice(&[9u8; 32]);
+        expect.extend_from_slice(&2_500u16.to_le_bytes());
+        expect.extend_from_slice(&1_000u64.to_le_bytes());
+        expect.extend_from_slice(&[10u8; 32]);
+        expect.extend_from_slice(&[11u8; 32]);
+        expect.extend_from_slice(&5_000u16.to_le_bytes());
+        expect.extend_from_slice(&1_200u64.to_le_bytes());
+        expect.extend_from_slice(&42u64.to_le_bytes());
+        assert_eq!(packed.to_vec(), expect);
+    }
+
+    #[test]
+    fn withdraw_fee_schedule_pack() {
+        let mut tiers = [WithdrawFeeTier::default(); MAX_WITHDRAW_FEE_TIERS];
+        tiers[0] = WithdrawFeeTier {
+            min_tenure: 86_400,
+            fee_bps: 5_000,
+        };
+        tiers[1] = WithdrawFeeTier {
+            min_tenure: 2_592_000,
+            fee_bps: 0,
+        };
+        let schedule = WithdrawFeeSchedule {
+            is_initialized: true,
+            swap: Pubkey::new_from_array([8u8; 32]),
+            tiers_count: 2,
+            tiers,
+        };
+
+        let mut packed = [0u8; WithdrawFeeSchedule::LEN];
+        WithdrawFeeSchedule::pack_into_slice(&schedule, &mut packed);
+        let unpacked = WithdrawFeeSchedule::unpack(&packed).unwrap();
+        assert_eq!(unpacked, schedule);
+
+        let mut expect = vec![1u8];
+        expect.extend_from_slice(&[8u8; 32]);
+        expect.push(2);
+        expect.extend_from_slice(&86_400u64.to_le_bytes());
+        expect.extend_from_slice(&5_000u16.to_le_bytes());
+        expect.extend_from_slice(&2_592_000u64.to_le_bytes());
+        expect.extend_from_slice(&0u16.to_le_bytes());
+        expect.extend_from_slice(&[0u8; 20]);
+        assert_eq!(packed.to_vec(), expect);
+    }
+
+    #[test]
+    fn withdraw_fee_schedule_tiers() {
+        let mut tiers = [WithdrawFeeTier::default(); MAX_WITHDRAW_FEE_TIERS];
+        tiers[0] = WithdrawFeeTier {
+            min_tenure: 100,
+            fee_bps: 5_000,
+        };
+        tiers[1] = WithdrawFeeTier {
+            min_tenure: 1_000,
+            fee_bps: 0,
+        };
+        let schedule = WithdrawFeeSchedule {
+            is_initialized: true,
+            swap: Pubkey::new_from_array([8u8; 32]),
+            tiers_count: 2,
+            tiers,
+        };
+        assert_eq!(WithdrawFeeSchedule::validate(schedule.tiers()), Ok(()));
+
+        assert_eq!(schedule.fee_bps(99), MAX_WITHDRAW_FEE_BPS);
+        assert_eq!(schedule.fee_bps(100), 5_000);
+        assert_eq!(schedule.fee_bps(999), 5_000);
+        assert_eq!(schedule.fee_bps(1_000), 0);
+
+        // only half of the withdrawn pool tokens are tenured
+        assert_eq!(schedule.withdraw_fee(100, 1_000, 500, 99), Some(100));
+        assert_eq!(schedule.withdraw_fee(100, 1_000, 500, 100), Some(75));
+        assert_eq!(schedule.withdraw_fee(100, 1_000, 500, 1_000), Some(50));
+        assert_eq!(schedule.withdraw_fee(100, 1_000, 1_000, 1_000), Some(0));
+
+        let increasing_fee = [tiers[1], tiers[0]];
+        assert_eq!(
+            WithdrawFeeSchedule::validate(&increasing_fee),
+            Err(SwapError::InvalidWithdrawFeeSchedule)
+        );
+        let repeated_tenure = [tiers[0], tiers[0]];
+        assert_eq!(
+            WithdrawFeeSchedule::validate(&repeated_tenure),
+            Err(SwapError::InvalidWithdrawFeeSchedule)
+        );
+        let above_full_fee = [WithdrawFeeTier {
+            min_tenure: 100,
+            fee_bps: MAX_WITHDRAW_FEE_BPS + 1,
+        }];
+        assert_eq!(
+            WithdrawFeeSchedule::validate(&above_full_fee),
+            Err(SwapError::InvalidWithdrawFeeSchedule)
+        );
+    }
+
+    #[test]
+    fn lp_tenure_pack() {
+        let tenure = LpTenure {
+            is_initialized: true,
+            swap: Pubkey::new_from_array([8u8; 32]),
+            pool_token_account: Pubkey::new_from_array([9u8; 32]),
+            pool_token_amount: 1_000,
+            deposit_timestamp: 1_700_000_000,
+        };
+
+        let mut packed = [0u8; LpTenure::LEN];
+        LpTenure::pack_into_slice(&tenure, &mut packed);
+        let unpacked = LpTenure::unpack(&packed).unwrap();
+        assert_eq!(unpacked, tenure);
+
+        let mut expect = vec![1u8];
+        expect.extend_from_slice(&[8u8; 32]);
+        expect.extend_from_slice(&[9u8; 32]);
+        expect.extend_from_slice(&1_000u64.to_le_bytes());
+        expect.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        assert_eq!(packed.to_vec(), expect);
+    }
+
+    #[test]
+    fn lp_tenure_deposit() {
+        let mut tenure = LpTenure {
+            is_initialized: true,
+            ..LpTenure::default()
+        };
+        tenure.deposit(1_000, 100).unwrap();
+        assert_eq!(tenure.pool_token_amount, 1_000);
+        assert_eq!(tenure.deposit_timestamp, 100);
+
+        // weighted by amount
+        tenure.deposit(3_000, 200).unwrap();
+        assert_eq!(tenure.pool_token_amount, 4_000);
+        assert_eq!(tenure.deposit_timestamp, 175);
+        assert_eq!(tenure.tenure(275), 100);
+
+        tenure.withdraw(1_000);
+        assert_eq!(tenure.pool_token_amount, 3_000);
+        assert_eq!(tenure.deposit_timestamp, 175);
+    }
+
+    #[test]
+    fn lp_tenure_sync() {
+        let mut tenure = LpTenure {
+            is_initialized: true,
+            pool_token_amount: 1_000,
+            deposit_timestamp: 100,
+            ..LpTenure::default()
+        };
+        tenure.sync(1_000, 200).unwrap();
+        assert_eq!(tenure.pool_token_amount, 1_000);
+        assert_eq!(tenure.deposit_timestamp, 100);
+
+        // pool tokens transferred in are tracked from now
+        tenure.sync(2_000, 300).unwrap();
+        assert_eq!(tenure.pool_token_amount, 2_000);
+        assert_eq!(tenure.deposit_timestamp, 200);
+
+        // pool tokens transferred out reset the tenure
+        tenure.sync(500, 400).unwrap();
+        assert_eq!(tenure.pool_token_amount, 500);
+        assert_eq!(tenure.deposit_timestamp, 400);
+        assert_eq!(tenure.tenure(400), 0);
+    }
+
+    #[test]
+    fn swap_version_unpack_ignores_protocol_fee() {
+        let swap_info = SwapVersion::SwapV1(SwapV1 {
+            is_initialized: true,
+            bump_seed: TEST_BUMP_SEED,
+            token_program_id: TEST_TOKEN_PROGRAM_ID,
+            token_a: TEST_TOKEN_A,
+            token_b: TEST_TOKEN_B,
+            pool_mint: TEST_POOL_MINT,
+            token_a_mint: TEST_TOKEN_A_MINT,
+            token_b_mint: TEST_TOKEN_B_MINT,
+            pool_fee_account: TEST_POOL_FEE_ACCOUNT,
+            fees: TEST_FEES,
+            swap_curve: SwapCurve {
+                curve_type: TEST_CURVE_TYPE.try_into().unwrap(),
+                calculator: Arc::new(TEST_CURVE),
+            },
+        });
+
+        let mut packed = [0u8; ProtocolFee::SWAP_ACCOUNT_LEN];
+        SwapVersion::pack(swap_info, &mut packed).unwrap();
+        let protocol_fee = ProtocolFee {
+            is_initialized: true,
+            ..ProtocolFee::default()
+        };
+        ProtocolFee::pack(protocol_fee, &mut packed[ProtocolFee::OFFSET..]).unwrap();
+
+        let unpacked = SwapVersion::unpack(&packed).unwrap();
+        assert!(unpacked.is_initialized());
+        assert_eq!(*unpacked.pool_mint(), TEST_POOL_MINT);
+    }
 }
@@ -0,0 +1,566 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    solana_program_test::{processor, tokio, ProgramTest, ProgramTestContext},
+    solana_sdk::{
+        instruction::InstructionError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_instruction,
+        transaction::{Transaction, TransactionError},
+    },
+    spl_token_2022::{
+        extension::{
+            group_member_pointer, metadata_pointer, BaseStateWithExtensions, ExtensionType,
+            StateWithExtensions,
+        },
+        state::{Account, Mint},
+    },
+    spl_token_group_interface::state::{TokenGroup, TokenGroupMember},
+    spl_token_metadata_interface::state::TokenMetadata,
+    spl_token_swap::{
+        curve::{base::SwapCurve, constant_product::ConstantProductCurve, fees::Fees},
+        error::SwapError,
+        id,
+        instruction::{
+            initialize, issue_position, redeem_position, withdraw_all_token_types, IssuePosition,
+            WithdrawAllTokenTypes,
+        },
+        processor::Processor,
+        state::{LpPosition, SwapVersion, POSITION_METADATA_NAME},
+    },
+    std::sync::Arc,
+};
+
+const TOKEN_A_AMOUNT: u64 = 1_000_000;
+const TOKEN_B_AMOUNT: u64 = 9_000_000;
+
+fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new("spl_token_swap", id(), processor!(Processor::process));
+    program_test.prefer_bpf(false);
+    program_test.add_program(
+        "spl_token_2022",
+        spl_token_2022::id(),
+        processor!(spl_token_2022::processor::Processor::process),
+    );
+    program_test
+}
+
+async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[solana_sdk::instruction::Instruction],
+    signers: &[&Keypair],
+) -> Result<(), TransactionError> {
+    let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+}
+
+async fn create_mint(
+    context: &mut ProgramTestContext,
+    mint: &Keypair,
+    mint_authority: &Pubkey,
+    extension_types: &[ExtensionType],
+    extension_instructions: Vec<solana_sdk::instruction::Instruction>,
+    decimals: u8,
+) {
+    let space = ExtensionType::try_calculate_account_len::<Mint>(extension_types).unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let mut instructions = vec![system_instruction::create_account(
+        &context.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(space),
+        space as u64,
+        &spl_token_2022::id(),
+    )];
+    instructions.extend(extension_instructions);
+    instructions.push(
+        spl_token_2022::instruction::initialize_mint(
+            &spl_token_2022::id(),
+            &mint.pubkey(),
+            mint_authority,
+            None,
+            decimals,
+        )
+        .unwrap(),
+    );
+    process(context, &instructions, &[mint]).await.unwrap();
+}
+
+async fn create_token_account(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    process(
+        context,
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(Account::LEN),
+                Account::LEN as u64,
+                &spl_token_2022::id(),
+            ),
+            spl_token_2022::instruction::initialize_account3(
+                &spl_token_2022::id(),
+                &account.pubkey(),
+                mint,
+                owner,
+            )
+            .unwrap(),
+        ],
+        &[&account],
+    )
+    .await
+    .unwrap();
+    account.pubkey()
+}
+
+async fn token_amount(context: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(*account)
+        .await
+        .unwrap()
+        .unwrap();
+    StateWithExtensions::<Account>::unpack(&account.data)
+        .unwrap()
+        .base
+        .amount
+}
+
+struct SwapSetup {
+    swap: Pubkey,
+    authority: Pubkey,
+    pool_mint: Pubkey,
+    pool_fee: Pubkey,
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+    token_a: Pubkey,
+    token_b: Pubkey,
+    user: Keypair,
+    user_pool: Pubkey,
+}
+
+async fn setup_swap(context: &mut ProgramTestContext) -> SwapSetup {
+    let payer = context.payer.pubkey();
+    let swap = Keypair::new();
+    let (authority, _) = Pubkey::find_program_address(&[&swap.pubkey().to_bytes()], &id());
+    let user = Keypair::new();
+
+    let token_a_mint = Keypair::new();
+    let token_b_mint = Keypair::new();
+    let pool_mint = Keypair::new();
+    create_mint(context, &token_a_mint, &payer, &[], vec![], 6).await;
+    create_mint(context, &token_b_mint, &payer, &[], vec![], 6).await;
+    create_mint(context, &pool_mint, &authority, &[], vec![], 2).await;
+
+    let token_a = create_token_account(context, &token_a_mint.pubkey(), &authority).await;
+    let token_b = create_token_account(context, &token_b_mint.pubkey(), &authority).await;
+    let pool_fee = create_token_account(context, &pool_mint.pubkey(), &payer).await;
+    let user_pool = create_token_account(context, &pool_mint.pubkey(), &user.pubkey()).await;
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    process(
+        context,
+        &[
+            spl_token_2022::instruction::mint_to(
+                &spl_token_2022::id(),
+                &token_a_mint.pubkey(),
+                &token_a,
+                &payer,
+                &[],
+                TOKEN_A_AMOUNT,
+            )
+            .unwrap(),
+            spl_token_2022::instruction::mint_to(
+                &spl_token_2022::id(),
+                &token_b_mint.pubkey(),
+                &token_b,
+                &payer,
+                &[],
+                TOKEN_B_AMOUNT,
+            )
+            .unwrap(),
+            system_instruction::create_account(
+                &payer,
+                &swap.pubkey(),
+                rent.minimum_balance(SwapVersion::LATEST_LEN),
+                SwapVersion::LATEST_LEN as u64,
+                &id(),
+            ),
+            initialize(
+                &id(),
+                &spl_token_2022::id(),
+                &swap.pubkey(),
+                &authority,
+                &token_a,
+                &token_b,
+                &pool_mint.pubkey(),
+                &pool_fee,
+                &user_pool,
+                Fees::default(),
+                SwapCurve {
+                    curve_type: spl_token_swap::curve::base::CurveType::ConstantProduct,
+                    calculator: Arc::new(ConstantProductCurve {}),
+                },
+            )
+            .unwrap(),
+        ],
+        &[&swap],
+    )
+    .await
+    .unwrap();
+
+    SwapSetup {
+        swap: swap.pubkey(),
+        authority,
+        pool_mint: pool_mint.pubkey(),
+        pool_fee,
+        token_a_mint: token_a_mint.pubkey(),
+        token_b_mint: token_b_mint.pubkey(),
+        token_a,
+        token_b,
+        user,
+        user_pool,
+    }
+}
+
+struct PositionSetup {
+    position: Pubkey,
+    position_mint: Pubkey,
+    position_token: Pubkey,
+}
+
+async fn issue(
+    context: &mut ProgramTestContext,
+    setup: &SwapSetup,
+    pool_token_amount: u64,
+) -> PositionSetup {
+    let position = Keypair::new();
+    let position_mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    process(
+        context,
+        &[system_instruction::create_account(
+            &context.payer.pubkey(),
+            &position.pubkey(),
+            rent.minimum_balance(LpPosition::LEN),
+            LpPosition::LEN as u64,
+            &id(),
+        )],
+        &[&position],
+    )
+    .await
+    .unwrap();
+    create_mint(
+        context,
+        &position_mint,
+        &setup.authority,
+        &[
+            ExtensionType::MetadataPointer,
+            ExtensionType::GroupMemberPointer,
+        ],
+        vec![
+            metadata_pointer::instruction::initialize(
+                &spl_token_2022::id(),
+                &position_mint.pubkey(),
+                None,
+                Some(position_mint.pubkey()),
+            )
+            .unwrap(),
+            group_member_pointer::instruction::initialize(
+                &spl_token_2022::id(),
+                &position_mint.pubkey(),
+                None,
+                Some(position_mint.pubkey()),
+            )
+            .unwrap(),
+        ],
+        0,
+    )
+    .await;
+    let position_token =
+        create_token_account(context, &position_mint.pubkey(), &setup.user.pubkey()).await;
+
+    let payer = context.payer.pubkey();
+    process(
+        context,
+        &[issue_position(
+            &id(),
+            &spl_token_2022::id(),
+            &spl_token_2022::id(),
+            &setup.swap,
+            &setup.authority,
+            &setup.user.pubkey(),
+            &setup.pool_mint,
+            &setup.user_pool,
+            &setup.token_a,
+            &setup.token_b,
+            &position.pubkey(),
+            &position_mint.pubkey(),
+            &position_token,
+            &payer,
+            IssuePosition { pool_token_amount },
+        )
+        .unwrap()],
+        &[&setup.user],
+    )
+    .await
+    .unwrap();
+
+    PositionSetup {
+        position: position.pubkey(),
+        position_mint: position_mint.pubkey(),
+        position_token,
+    }
+}
+
+#[tokio::test]
+async fn test_issue_and_redeem_position() {
+    let mut context = program_test().start_with_context().await;
+    let setup = setup_swap(&mut context).await;
+    let total_pool = token_amount(&mut context, &setup.user_pool).await;
+    let pool_token_amount = total_pool / 4;
+
+    let position = issue(&mut context, &setup, pool_token_amount).await;
+
+    let (position_vault, _) = Processor::position_vault_id(&id(), &setup.swap);
+    assert_eq!(
+        token_amount(&mut context, &position_vault).await,
+        pool_token_amount
+    );
+    assert_eq!(
+        token_amount(&mut context, &position.position_token).await,
+        1
+    );
+
+    let position_account = context
+        .banks_client
+        .get_account(position.position)
+        .await
+        .unwrap()
+        .unwrap();
+    let lp_position = LpPosition::unpack(&position_account.data).unwrap();
+    assert_eq!(lp_position.pool_token_amount, pool_token_amount);
+    assert_eq!(lp_position.entry_token_a_amount, TOKEN_A_AMOUNT / 4);
+    assert_eq!(lp_position.entry_token_b_amount, TOKEN_B_AMOUNT / 4);
+
+    // the entry data is readable from the Token-2022 metadata of the NFT
+    let position_mint_account = context
+        .banks_client
+        .get_account(position.position_mint)
+        .await
+        .unwrap()
+        .unwrap();
+    let position_mint = StateWithExtensions::<Mint>::unpack(&position_mint_account.data).unwrap();
+    let token_metadata = position_mint
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
+    assert_eq!(token_metadata.name, POSITION_METADATA_NAME);
+    assert_eq!(
+        token_metadata.additional_metadata,
+        lp_position.metadata_fields(&position.position)
+    );
+
+    // and the NFT is a member of the position group of the swap
+    let (position_group, _) = Processor::position_group_id(&id(), &setup.swap);
+    let group_member = position_mint.get_extension::<TokenGroupMember>().unwrap();
+    assert_eq!(group_member.group, position_group);
+    assert_eq!(u64::from(group_member.member_number), 1);
+    let position_group_account = context
+        .banks_client
+        .get_account(position_group)
+        .await
+        .unwrap()
+        .unwrap();
+    let position_group_mint =
+        StateWithExtensions::<Mint>::unpack(&position_group_account.data).unwrap();
+    let token_group = position_group_mint.get_extension::<TokenGroup>().unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(token_group.update_authority),
+        Some(setup.authority)
+    );
+    assert_eq!(u64::from(token_group.size), 1);
+    assert_eq!(position_group_mint.base.supply, 0);
+
+    process(
+        &mut context,
+        &[redeem_position(
+            &id(),
+            &spl_token_2022::id(),
+            &spl_token_2022::id(),
+            &setup.swap,
+            &setup.authority,
+            &setup.user.pubkey(),
+            &position.position,
+            &position.position_mint,
+            &position.position_token,
+            &setup.user_pool,
+            &setup.pool_mint,
+        )
+        .unwrap()],
+        &[&setup.user],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_amount(&mut context, &setup.user_pool).await,
+        total_pool
+    );
+    assert_eq!(token_amount(&mut context, &position_vault).await, 0);
+    assert_eq!(
+        token_amount(&mut context, &position.position_token).await,
+        0
+    );
+
+    // later positions join the existing group
+    let position = issue(&mut context, &setup, pool_token_amount).await;
+    let position_mint_account = context
+        .banks_client
+        .get_account(position.position_mint)
+        .await
+        .unwrap()
+        .unwrap();
+    let position_mint = StateWithExtensions::<Mint>::unpack(&position_mint_account.data).unwrap();
+    let group_member = position_mint.get_extension::<TokenGroupMember>().unwrap();
+    assert_eq!(group_member.group, position_group);
+    assert_eq!(u64::from(group_member.member_number), 2);
+}
+
+#[tokio::test]
+async fn test_withdraw_from_position_vault_fails() {
+    let mut context = program_test().start_with_context().await;
+    let setup = setup_swap(&mut context).await;
+    let total_pool = token_amount(&mut context, &setup.user_pool).await;
+    issue(&mut context, &setup, total_pool / 4).await;
+
+    let attacker = Keypair::new();
+    let attacker_token_a =
+        create_token_account(&mut context, &setup.token_a_mint, &attacker.pubkey()).await;
+    let attacker_token_b =
+        create_token_account(&mut context, &setup.token_b_mint, &attacker.pubkey()).await;
+    let (position_vault, _) = Processor::position_vault_id(&id(), &setup.swap);
+
+    // the vault doesn't delegate to the swap authority, so the burn signed by
+    // the swap authority is rejected
+    let withdraw = |user_transfer_authority: &Pubkey| {
+        withdraw_all_token_types(
+            &id(),
+            &spl_token_2022::id(),
+            &spl_token_2022::id(),
+            &spl_token_2022::id(),
+            &setup.swap,
+            &setup.authority,
+            user_transfer_authority,
+            &setup.pool_mint,
+            &setup.pool_fee,
+            &position_vault,
+            &setup.token_a,
+            &setup.token_b,
+            &attacker_token_a,
+            &attacker_token_b,
+            &setup.token_a_mint,
+            &setup.token_b_mint,
+            WithdrawAllTokenTypes {
+                pool_token_amount: total_pool / 4,
+                minimum_token_a_amount: 0,
+                minimum_token_b_amount: 0,
+            },
+        )
+        .unwrap()
+    };
+    let mut swap_authority_withdraw = withdraw(&setup.authority);
+    swap_authority_withdraw.accounts[2].is_signer = false;
+    assert_eq!(
+        process(&mut context, &[swap_authority_withdraw], &[]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(spl_token_2022::error::TokenError::OwnerMismatch as u32)
+        ))
+    );
+
+    // the position vault is its own owner, and only issue and redeem sign
+    // with its seeds, so the burn escalates a signer privilege
+    let mut vault_withdraw = withdraw(&position_vault);
+    vault_withdraw.accounts[2].is_signer = false;
+    assert!(process(&mut context, &[vault_withdraw], &[]).await.is_err());
+
+    assert_eq!(
+        token_amount(&mut context, &position_vault).await,
+        total_pool / 4
+    );
+    assert_eq!(token_amount(&mut context, &attacker_token_a).await, 0);
+    assert_eq!(token_amount(&mut context, &attacker_token_b).await, 0);
+}
+
+#[tokio::test]
+async fn test_redeem_position_by_non_holder_fails() {
+    let mut context = program_test().start_with_context().await;
+    let setup = setup_swap(&mut context).await;
+    let total_pool = token_amount(&mut context, &setup.user_pool).await;
+    let position = issue(&mut context, &setup, total_pool / 4).await;
+
+    let attacker = Keypair::new();
+    let attacker_pool =
+        create_token_account(&mut context, &setup.pool_mint, &attacker.pubkey()).await;
+    let redeem = |position_owner: &Pubkey| {
+        redeem_position(
+            &id(),
+            &spl_token_2022::id(),
+            &spl_token_2022::id(),
+            &setup.swap,
+            &setup.authority,
+            position_owner,
+            &position.position,
+            &position.position_mint,
+            &position.position_token,
+            &attacker_pool,
+            &setup.pool_mint,
+        )
+        .unwrap()
+    };
+
+    // the attacker doesn't own the position token account
+    assert_eq!(
+        process(&mut context, &[redeem(&attacker.pubkey())], &[&attacker]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(spl_token_2022::error::TokenError::OwnerMismatch as u32)
+        ))
+    );
+
+    // nor can the swap authority, which the program signs for, stand in for
+    // the owner
+    let mut swap_authority_redeem = redeem(&setup.authority);
+    swap_authority_redeem.accounts[2].is_signer = false;
+    assert_eq!(
+        process(&mut context, &[swap_authority_redeem], &[]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(SwapError::InvalidUserTransferAuthority as u32)
+        ))
+    );
+
+    assert_eq!(token_amount(&mut context, &attacker_pool).await, 0);
+    assert_eq!(
+        token_amount(&mut context, &position.position_token).await,
+        1
+    );
+}
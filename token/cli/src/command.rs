@@ -42,6 +42,7 @@ use {
                     ApplyPendingBalanceAccountInfo, TransferAccountInfo, WithdrawAccountInfo,
                 },
                 ConfidentialTransferAccount, ConfidentialTransferMint,
+                ConfidentialTransferRevealMode,
             },
             confidential_transfer_fee::ConfidentialTransferFeeConfig,
             cpi_guard::CpiGuard,
@@ -1612,6 +1613,15 @@ async fn command_transfer(
                 .unwrap();
             let transfer_account_info = TransferAccountInfo::new(extension);
 
+            // the transfer amount must be revealed if either account is in reveal mode
+            let recipient_state = token.get_account_info(&recipient_token_account).await?;
+            let reveal_transfer_amount = state
+                .get_extension::<ConfidentialTransferRevealMode>()
+                .is_ok()
+                || recipient_state
+                    .get_extension::<ConfidentialTransferRevealMode>()
+                    .is_ok();
+
             let TransferProofData {
                 equality_proof_data,
                 ciphertext_validity_proof_data_with_ciphertext,
@@ -1684,6 +1694,7 @@ async fn command_transfer(
                     Some(&ciphertext_validity_proof_account_with_ciphertext),
                     Some(&range_proof_context_proof_account),
                     transfer_balance,
+                    reveal_transfer_amount,
                     Some(transfer_account_info),
                     &args.sender_elgamal_keypair,
                     &args.sender_aes_key,
@@ -2,7 +2,7 @@ use {
     crate::client::{
         ProgramClient, ProgramClientError, SendTransaction, SimulateTransaction, SimulationResult,
     },
-    bytemuck::{bytes_of, Pod},
+    bytemuck::{bytes_of, try_from_bytes, Pod},
     futures::future::join_all,
     futures_util::TryFutureExt,
     solana_program_test::tokio::time,
@@ -33,17 +33,21 @@ use {
             confidential_transfer::{
                 self,
                 account_info::{
-                    ApplyPendingBalanceAccountInfo, EmptyAccountAccountInfo, TransferAccountInfo,
-                    WithdrawAccountInfo,
+                    ApplyPendingBalanceAccountInfo, DiscloseBalanceAccountInfo,
+                    EmptyAccountAccountInfo, TransferAccountInfo, WithdrawAccountInfo,
                 },
-                ConfidentialTransferAccount, DecryptableBalance,
+                get_balance_disclosure_address, BalanceDisclosure, ConfidentialTransferAccount,
+                DecryptableBalance,
             },
             confidential_transfer_fee::{
                 self, account_info::WithheldTokensInfo, ConfidentialTransferFeeAmount,
                 ConfidentialTransferFeeConfig,
             },
-            cpi_guard, default_account_state, group_member_pointer, group_pointer,
-            interest_bearing_mint, memo_transfer, metadata_pointer, pausable, scaled_ui_amount,
+            cpi_guard, default_account_state,
+            freeze_delegates::{self, FreezeDelegateScope},
+            group_member_pointer, group_pointer, interest_bearing_mint, memo_transfer,
+            metadata_pointer, pausable, scaled_ui_amount,
+            token_metadata::field_authorities::{self, TokenMetadataFieldAuthorities},
             transfer_fee, transfer_hook, BaseStateWithExtensions, Extension, ExtensionType,
             StateWithExtensionsOwned,
         },
@@ -196,6 +200,11 @@ pub enum ExtensionInitializationParams {
     PausableConfig {
         authority: Pubkey,
     },
+    FreezeDelegates,
+    TransferHookBudget {
+        max_extra_accounts: u16,
+        max_compute_units: u32,
+    },
 }
 impl ExtensionInitializationParams {
     /// Get the extension type associated with the init params
@@ -217,6 +226,8 @@ impl ExtensionInitializationParams {
             Self::GroupMemberPointer { .. } => ExtensionType::GroupMemberPointer,
             Self::ScaledUiAmountConfig { .. } => ExtensionType::ScaledUiAmount,
             Self::PausableConfig { .. } => ExtensionType::Pausable,
+            Self::FreezeDelegates => ExtensionType::FreezeDelegates,
+            Self::TransferHookBudget { .. } => ExtensionType::TransferHookBudget,
         }
     }
     /// Generate an appropriate initialization instruction for the given mint
@@ -338,6 +349,18 @@ impl ExtensionInitializationParams {
             Self::PausableConfig { authority } => {
                 pausable::instruction::initialize(token_program_id, mint, &authority)
             }
+            Self::FreezeDelegates => {
+                freeze_delegates::instruction::initialize(token_program_id, mint)
+            }
+            Self::TransferHookBudget {
+                max_extra_accounts,
+                max_compute_units,
+            } => transfer_hook::instruction::initialize_budget(
+                token_program_id,
+                mint,
+                max_extra_accounts,
+                max_compute_units,
+            ),
         }
     }
 }
@@ -1597,6 +1620,31 @@ where
         .await
     }
 
+    /// Exempt an account from transfer fees, or remove its exemption
+    pub async fn set_transfer_fee_exemption<S: Signers>(
+        &self,
+        account: &Pubkey,
+        authority: &Pubkey,
+        exempt: bool,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[transfer_fee::instruction::set_transfer_fee_exemption(
+                &self.program_id,
+                account,
+                &self.pubkey,
+                authority,
+                &multisig_signers,
+                exempt,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Set default account state on mint
     pub async fn set_default_account_state<S: Signers>(
         &self,
@@ -1716,6 +1764,33 @@ where
         .await
     }
 
+    /// Reallocate a token account to be large enough for a set of
+    /// `ExtensionType`s, refunding any lamports above its rent-exempt reserve
+    /// to the payer
+    pub async fn reallocate_idempotent<S: Signers>(
+        &self,
+        account: &Pubkey,
+        authority: &Pubkey,
+        extension_types: &[ExtensionType],
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[instruction::reallocate_idempotent(
+                &self.program_id,
+                account,
+                &self.payer.pubkey(),
+                authority,
+                &multisig_signers,
+                extension_types,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Require memos on transfers into this account
     pub async fn enable_required_transfer_memos<S: Signers>(
         &self,
@@ -1802,6 +1877,54 @@ where
         .await
     }
 
+    /// Register a freeze delegate on the mint, or update its scope
+    pub async fn set_freeze_delegate<S: Signers>(
+        &self,
+        freeze_authority: &Pubkey,
+        delegate: &Pubkey,
+        scope: FreezeDelegateScope,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(freeze_authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[freeze_delegates::instruction::set_delegate(
+                &self.program_id,
+                self.get_address(),
+                freeze_authority,
+                &multisig_signers,
+                delegate,
+                scope,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Remove a freeze delegate from the mint
+    pub async fn remove_freeze_delegate<S: Signers>(
+        &self,
+        freeze_authority: &Pubkey,
+        delegate: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(freeze_authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[freeze_delegates::instruction::remove_delegate(
+                &self.program_id,
+                self.get_address(),
+                freeze_authority,
+                &multisig_signers,
+                delegate,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Prevent unsafe usage of token account through CPI
     pub async fn enable_cpi_guard<S: Signers>(
         &self,
@@ -1917,6 +2040,31 @@ where
         .await
     }
 
+    /// Update transfer hook budget
+    pub async fn update_transfer_hook_budget<S: Signers>(
+        &self,
+        authority: &Pubkey,
+        max_extra_accounts: u16,
+        max_compute_units: u32,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[transfer_hook::instruction::update_budget(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                max_extra_accounts,
+                max_compute_units,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Update metadata pointer address
     pub async fn update_metadata_address<S: Signers>(
         &self,
@@ -2170,6 +2318,155 @@ where
         .await
     }
 
+    /// Disclose the available balance of a confidential token account in its
+    /// balance disclosure receipt account
+    #[allow(clippy::too_many_arguments)]
+    pub async fn confidential_transfer_disclose_balance<S: Signers>(
+        &self,
+        account: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+        proof_account: Option<&ProofAccount>,
+        account_info: Option<DiscloseBalanceAccountInfo>,
+        elgamal_keypair: &ElGamalKeypair,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        let account_info = if let Some(account_info) = account_info {
+            account_info
+        } else {
+            let account = self.get_account_info(account).await?;
+            let confidential_transfer_account =
+                account.get_extension::<ConfidentialTransferAccount>()?;
+            DiscloseBalanceAccountInfo::new(confidential_transfer_account)
+        };
+
+        let proof_data = if proof_account.is_some() {
+            None
+        } else {
+            Some(
+                account_info
+                    .generate_proof_data(elgamal_keypair, amount)
+                    .map_err(|_| TokenError::ProofGeneration)?,
+            )
+        };
+
+        // cannot panic as long as either `proof_data` or `proof_account` is `Some(..)`,
+        // which is guaranteed by the previous check
+        let proof_location = Self::confidential_transfer_create_proof_location(
+            proof_data.as_ref(),
+            proof_account,
+            1,
+        )
+        .unwrap();
+
+        self.process_ixs(
+            &confidential_transfer::instruction::disclose_balance(
+                &self.program_id,
+                account,
+                &self.payer.pubkey(),
+                amount,
+                authority,
+                &multisig_signers,
+                proof_location,
+            )?,
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Enable reveal mode on a confidential token account
+    pub async fn confidential_transfer_enable_reveal_mode<S: Signers>(
+        &self,
+        account: &Pubkey,
+        authority: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[confidential_transfer::instruction::enable_reveal_mode(
+                &self.program_id,
+                account,
+                authority,
+                &multisig_signers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Retrieve the balance disclosure receipt of a confidential token account
+    pub async fn get_balance_disclosure(&self, account: &Pubkey) -> TokenResult<BalanceDisclosure> {
+        let receipt_address = get_balance_disclosure_address(account, &self.program_id);
+        let receipt_account = self.get_account(receipt_address).await?;
+        if receipt_account.owner != self.program_id {
+            return Err(TokenError::AccountInvalidOwner);
+        }
+        Ok(*BalanceDisclosure::unpack(&receipt_account.data)?)
+    }
+
+    /// Reveal the available balance of a confidential token account in reveal
+    /// mode
+    #[allow(clippy::too_many_arguments)]
+    pub async fn confidential_transfer_reveal_available_balance<S: Signers>(
+        &self,
+        account: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+        proof_account: Option<&ProofAccount>,
+        account_info: Option<DiscloseBalanceAccountInfo>,
+        elgamal_keypair: &ElGamalKeypair,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        let account_info = if let Some(account_info) = account_info {
+            account_info
+        } else {
+            let account = self.get_account_info(account).await?;
+            let confidential_transfer_account =
+                account.get_extension::<ConfidentialTransferAccount>()?;
+            DiscloseBalanceAccountInfo::new(confidential_transfer_account)
+        };
+
+        let proof_data = if proof_account.is_some() {
+            None
+        } else {
+            Some(
+                account_info
+                    .generate_proof_data(elgamal_keypair, amount)
+                    .map_err(|_| TokenError::ProofGeneration)?,
+            )
+        };
+
+        // cannot panic as long as either `proof_data` or `proof_account` is `Some(..)`,
+        // which is guaranteed by the previous check
+        let proof_location = Self::confidential_transfer_create_proof_location(
+            proof_data.as_ref(),
+            proof_account,
+            1,
+        )
+        .unwrap();
+
+        self.process_ixs(
+            &confidential_transfer::instruction::reveal_available_balance(
+                &self.program_id,
+                account,
+                amount,
+                authority,
+                &multisig_signers,
+                proof_location,
+            )?,
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Deposit SPL Tokens into the pending balance of a confidential token
     /// account
     pub async fn confidential_transfer_deposit<S: Signers>(
@@ -2297,6 +2594,7 @@ where
         ciphertext_validity_proof_account_with_ciphertext: Option<&ProofAccountWithCiphertext>,
         range_proof_account: Option<&ProofAccount>,
         transfer_amount: u64,
+        reveal_transfer_amount: bool,
         account_info: Option<TransferAccountInfo>,
         source_elgamal_keypair: &ElGamalKeypair,
         source_aes_key: &AeKey,
@@ -2399,32 +2697,104 @@ where
         )
         .unwrap();
 
+        // the transfer amount must be revealed to the token program if the source
+        // or destination account is in reveal mode
+        let revealed_transfer_amount_proof_data = if reveal_transfer_amount {
+            let ciphertext_validity_proof_context =
+                if let Some(proof_data) = ciphertext_validity_proof_data.as_ref() {
+                    proof_data.context
+                } else {
+                    // unwrap is safe as long as either `proof_data` or `proof_account` is
+                    // `Some(..)`, which is guaranteed by the previous check
+                    self.confidential_transfer_get_ciphertext_validity_proof_context(
+                        &ciphertext_validity_proof_account_with_ciphertext
+                            .unwrap()
+                            .proof_account,
+                    )
+                    .await?
+                };
+            Some(
+                TransferAccountInfo::generate_revealed_transfer_amount_proof_data(
+                    transfer_amount,
+                    source_elgamal_keypair,
+                    &ciphertext_validity_proof_context,
+                )
+                .map_err(|_| TokenError::ProofGeneration)?,
+            )
+        } else {
+            None
+        };
+        // the proof instruction follows the other proof instructions of the transfer
+        let revealed_transfer_amount_instruction_offset = [
+            matches!(
+                equality_proof_location,
+                ProofLocation::InstructionOffset(..)
+            ),
+            matches!(
+                ciphertext_validity_proof_location,
+                ProofLocation::InstructionOffset(..)
+            ),
+            matches!(range_proof_location, ProofLocation::InstructionOffset(..)),
+        ]
+        .into_iter()
+        .filter(|is_instruction_offset| *is_instruction_offset)
+        .count() as i8
+            + 1;
+        let revealed_transfer_amount = Self::confidential_transfer_create_proof_location(
+            revealed_transfer_amount_proof_data.as_ref(),
+            None,
+            revealed_transfer_amount_instruction_offset,
+        )
+        .map(|proof_location| (transfer_amount, proof_location));
+
         let new_decryptable_available_balance = account_info
             .new_decryptable_available_balance(transfer_amount, source_aes_key)
             .map_err(|_| TokenError::AccountDecryption)?
             .into();
 
-        let mut instructions = confidential_transfer::instruction::transfer(
-            &self.program_id,
-            source_account,
-            self.get_address(),
-            destination_account,
-            &new_decryptable_available_balance,
-            &transfer_amount_auditor_ciphertext_lo,
-            &transfer_amount_auditor_ciphertext_hi,
-            source_authority,
-            &multisig_signers,
-            equality_proof_location,
-            ciphertext_validity_proof_location,
-            range_proof_location,
-        )?;
+        let mut instructions = if let Some(revealed_transfer_amount) = revealed_transfer_amount {
+            confidential_transfer::instruction::transfer_with_revealed_amount(
+                &self.program_id,
+                source_account,
+                self.get_address(),
+                destination_account,
+                &new_decryptable_available_balance,
+                &transfer_amount_auditor_ciphertext_lo,
+                &transfer_amount_auditor_ciphertext_hi,
+                source_authority,
+                &multisig_signers,
+                equality_proof_location,
+                ciphertext_validity_proof_location,
+                range_proof_location,
+                revealed_transfer_amount,
+            )?
+        } else {
+            confidential_transfer::instruction::transfer(
+                &self.program_id,
+                source_account,
+                self.get_address(),
+                destination_account,
+                &new_decryptable_available_balance,
+                &transfer_amount_auditor_ciphertext_lo,
+                &transfer_amount_auditor_ciphertext_hi,
+                source_authority,
+                &multisig_signers,
+                equality_proof_location,
+                ciphertext_validity_proof_location,
+                range_proof_location,
+            )?
+        };
         offchain::add_extra_account_metas(
             &mut instructions[0],
             source_account,
             self.get_address(),
             destination_account,
             source_authority,
-            u64::MAX,
+            if reveal_transfer_amount {
+                transfer_amount
+            } else {
+                u64::MAX
+            },
             |address| {
                 self.client
                     .get_account(address)
@@ -2653,6 +3023,7 @@ where
         fee_ciphertext_validity_proof_account: Option<&ProofAccount>,
         range_proof_account: Option<&ProofAccount>,
         transfer_amount: u64,
+        reveal_transfer_amount: bool,
         account_info: Option<TransferAccountInfo>,
         source_elgamal_keypair: &ElGamalKeypair,
         source_aes_key: &AeKey,
@@ -2794,34 +3165,116 @@ where
         )
         .unwrap();
 
+        // the transfer amount must be revealed to the token program if the source
+        // or destination account is in reveal mode
+        let revealed_transfer_amount_proof_data = if reveal_transfer_amount {
+            let ciphertext_validity_proof_context =
+                if let Some(proof_data) = transfer_amount_ciphertext_validity_proof_data.as_ref() {
+                    proof_data.context
+                } else {
+                    // unwrap is safe as long as either `proof_data` or `proof_account` is
+                    // `Some(..)`, which is guaranteed by the previous check
+                    self.confidential_transfer_get_ciphertext_validity_proof_context(
+                        &transfer_amount_ciphertext_validity_proof_account_with_ciphertext
+                            .unwrap()
+                            .proof_account,
+                    )
+                    .await?
+                };
+            Some(
+                TransferAccountInfo::generate_revealed_transfer_amount_proof_data(
+                    transfer_amount,
+                    source_elgamal_keypair,
+                    &ciphertext_validity_proof_context,
+                )
+                .map_err(|_| TokenError::ProofGeneration)?,
+            )
+        } else {
+            None
+        };
+        // the proof instruction follows the other proof instructions of the transfer
+        let revealed_transfer_amount_instruction_offset = [
+            matches!(
+                equality_proof_location,
+                ProofLocation::InstructionOffset(..)
+            ),
+            matches!(
+                transfer_amount_ciphertext_validity_proof_location,
+                ProofLocation::InstructionOffset(..)
+            ),
+            matches!(
+                fee_sigma_proof_location,
+                ProofLocation::InstructionOffset(..)
+            ),
+            matches!(
+                fee_ciphertext_validity_proof_location,
+                ProofLocation::InstructionOffset(..)
+            ),
+            matches!(range_proof_location, ProofLocation::InstructionOffset(..)),
+        ]
+        .into_iter()
+        .filter(|is_instruction_offset| *is_instruction_offset)
+        .count() as i8
+            + 1;
+        let revealed_transfer_amount = Self::confidential_transfer_create_proof_location(
+            revealed_transfer_amount_proof_data.as_ref(),
+            None,
+            revealed_transfer_amount_instruction_offset,
+        )
+        .map(|proof_location| (transfer_amount, proof_location));
+
         let new_decryptable_available_balance = account_info
             .new_decryptable_available_balance(transfer_amount, source_aes_key)
             .map_err(|_| TokenError::AccountDecryption)?
             .into();
 
-        let mut instructions = confidential_transfer::instruction::transfer_with_fee(
-            &self.program_id,
-            source_account,
-            self.get_address(),
-            destination_account,
-            &new_decryptable_available_balance,
-            &transfer_amount_auditor_ciphertext_lo,
-            &transfer_amount_auditor_ciphertext_hi,
-            source_authority,
-            &multisig_signers,
-            equality_proof_location,
-            transfer_amount_ciphertext_validity_proof_location,
-            fee_sigma_proof_location,
-            fee_ciphertext_validity_proof_location,
-            range_proof_location,
-        )?;
+        let mut instructions = if let Some(revealed_transfer_amount) = revealed_transfer_amount {
+            confidential_transfer::instruction::transfer_with_fee_with_revealed_amount(
+                &self.program_id,
+                source_account,
+                self.get_address(),
+                destination_account,
+                &new_decryptable_available_balance,
+                &transfer_amount_auditor_ciphertext_lo,
+                &transfer_amount_auditor_ciphertext_hi,
+                source_authority,
+                &multisig_signers,
+                equality_proof_location,
+                transfer_amount_ciphertext_validity_proof_location,
+                fee_sigma_proof_location,
+                fee_ciphertext_validity_proof_location,
+                range_proof_location,
+                revealed_transfer_amount,
+            )?
+        } else {
+            confidential_transfer::instruction::transfer_with_fee(
+                &self.program_id,
+                source_account,
+                self.get_address(),
+                destination_account,
+                &new_decryptable_available_balance,
+                &transfer_amount_auditor_ciphertext_lo,
+                &transfer_amount_auditor_ciphertext_hi,
+                source_authority,
+                &multisig_signers,
+                equality_proof_location,
+                transfer_amount_ciphertext_validity_proof_location,
+                fee_sigma_proof_location,
+                fee_ciphertext_validity_proof_location,
+                range_proof_location,
+            )?
+        };
         offchain::add_extra_account_metas(
             &mut instructions[0],
             source_account,
             self.get_address(),
             destination_account,
             source_authority,
-            u64::MAX,
+            if reveal_transfer_amount {
+                transfer_amount
+            } else {
+                u64::MAX
+            },
             |address| {
                 self.client
                     .get_account(address)
@@ -3180,6 +3633,39 @@ where
         .await
     }
 
+    // Reads the context of a transfer amount ciphertext validity proof from a
+    // context state account or from the proof data in a record account.
+    async fn confidential_transfer_get_ciphertext_validity_proof_context(
+        &self,
+        proof_account: &ProofAccount,
+    ) -> TokenResult<BatchedGroupedCiphertext3HandlesValidityProofContext> {
+        match proof_account {
+            ProofAccount::ContextAccount(context_state_account) => {
+                let account = self.get_account(*context_state_account).await?;
+                let context_state = try_from_bytes::<
+                    ProofContextState<BatchedGroupedCiphertext3HandlesValidityProofContext>,
+                >(&account.data)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+                Ok(context_state.proof_context)
+            }
+            ProofAccount::RecordAccount(record_account, data_offset) => {
+                let account = self.get_account(*record_account).await?;
+                let start = RecordData::WRITABLE_START_INDEX.saturating_add(*data_offset as usize);
+                let end = start
+                    .saturating_add(size_of::<BatchedGroupedCiphertext3HandlesValidityProofData>());
+                let proof_data = account
+                    .data
+                    .get(start..end)
+                    .and_then(|data| {
+                        try_from_bytes::<BatchedGroupedCiphertext3HandlesValidityProofData>(data)
+                            .ok()
+                    })
+                    .ok_or(ProgramError::InvalidAccountData)?;
+                Ok(proof_data.context)
+            }
+        }
+    }
+
     // Creates `ProofLocation` from proof data and `ProofAccount`. If both
     // `proof_data` and `proof_account` are `None`, then the result is `None`.
     fn confidential_transfer_create_proof_location<'a, ZK: ZkProofData<U>, U: Pod>(
@@ -3394,6 +3880,64 @@ where
         self.process_ixs(&instructions, signing_keypairs).await
     }
 
+    async fn get_additional_rent_for_field_authority(
+        &self,
+        field: Field,
+        authority: Option<Pubkey>,
+    ) -> TokenResult<u64> {
+        let account = self.get_account(self.pubkey).await?;
+        let account_lamports = account.lamports;
+        let mint_state = self.unpack_mint_info(account)?;
+        let mut field_authorities = mint_state
+            .get_variable_len_extension::<TokenMetadataFieldAuthorities>()
+            .unwrap_or_default();
+        field_authorities.set(field, authority);
+        let new_account_len =
+            mint_state.try_get_new_account_len_for_variable_len_extension(&field_authorities)?;
+        let new_rent_exempt_minimum = self
+            .client
+            .get_minimum_balance_for_rent_exemption(new_account_len)
+            .await
+            .map_err(TokenError::Client)?;
+        Ok(new_rent_exempt_minimum.saturating_sub(account_lamports))
+    }
+
+    /// Set or revoke the authority of a single token-metadata field on a mint.
+    /// Includes a transfer for any additional rent-exempt SOL required.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn token_metadata_set_field_authority_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Pubkey,
+        update_authority: &Pubkey,
+        field: Field,
+        authority: Option<Pubkey>,
+        transfer_lamports: Option<u64>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let additional_lamports = if let Some(transfer_lamports) = transfer_lamports {
+            transfer_lamports
+        } else {
+            self.get_additional_rent_for_field_authority(field.clone(), authority)
+                .await?
+        };
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                &self.pubkey,
+                additional_lamports,
+            ));
+        }
+        instructions.push(field_authorities::set_field_authority(
+            &self.program_id,
+            &self.pubkey,
+            update_authority,
+            field,
+            authority,
+        ));
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
     /// Update the token-metadata authority in a mint
     pub async fn token_metadata_update_authority<S: Signers>(
         &self,
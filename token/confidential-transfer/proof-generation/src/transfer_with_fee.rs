@@ -42,19 +42,36 @@ pub struct TransferWithFeeProofData {
     pub range_proof_data: BatchedRangeProofU256Data,
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn transfer_with_fee_split_proof_data(
-    current_available_balance: &ElGamalCiphertext,
-    current_decryptable_available_balance: &AeCiphertext,
+/// The ciphertexts and openings of a confidential transfer with fee, from
+/// which the proofs of the transfer are generated
+pub struct TransferWithFeeCiphertexts {
+    pub transfer_amount_lo: u64,
+    pub transfer_amount_hi: u64,
+    pub transfer_amount_ciphertext_lo: TransferAmountCiphertext,
+    pub transfer_amount_ciphertext_hi: TransferAmountCiphertext,
+    pub transfer_amount_opening_lo: PedersenOpening,
+    pub transfer_amount_opening_hi: PedersenOpening,
+    pub fee_amount: u64,
+    pub delta_fee: u64,
+    pub fee_amount_lo: u64,
+    pub fee_amount_hi: u64,
+    pub fee_ciphertext_lo: FeeCiphertext,
+    pub fee_ciphertext_hi: FeeCiphertext,
+    pub fee_opening_lo: PedersenOpening,
+    pub fee_opening_hi: PedersenOpening,
+}
+
+/// Splits the transfer amount and the fee charged for it into the low and high
+/// bit components and encrypts them
+pub fn transfer_with_fee_proof_data(
     transfer_amount: u64,
-    source_elgamal_keypair: &ElGamalKeypair,
-    aes_key: &AeKey,
+    source_elgamal_pubkey: &ElGamalPubkey,
     destination_elgamal_pubkey: &ElGamalPubkey,
     auditor_elgamal_pubkey: Option<&ElGamalPubkey>,
     withdraw_withheld_authority_elgamal_pubkey: &ElGamalPubkey,
     fee_rate_basis_points: u16,
     maximum_fee: u64,
-) -> Result<TransferWithFeeProofData, TokenProofGenerationError> {
+) -> Result<TransferWithFeeCiphertexts, TokenProofGenerationError> {
     let default_auditor_pubkey = ElGamalPubkey::default();
     let auditor_elgamal_pubkey = auditor_elgamal_pubkey.unwrap_or(&default_auditor_pubkey);
 
@@ -64,21 +81,100 @@ pub fn transfer_with_fee_split_proof_data(
             .ok_or(TokenProofGenerationError::IllegalAmountBitLength)?;
 
     // Encrypt the `lo` and `hi` transfer amounts
-    let (transfer_amount_grouped_ciphertext_lo, transfer_amount_opening_lo) =
-        TransferAmountCiphertext::new(
-            transfer_amount_lo,
-            source_elgamal_keypair.pubkey(),
-            destination_elgamal_pubkey,
-            auditor_elgamal_pubkey,
-        );
+    let (transfer_amount_ciphertext_lo, transfer_amount_opening_lo) = TransferAmountCiphertext::new(
+        transfer_amount_lo,
+        source_elgamal_pubkey,
+        destination_elgamal_pubkey,
+        auditor_elgamal_pubkey,
+    );
 
-    let (transfer_amount_grouped_ciphertext_hi, transfer_amount_opening_hi) =
-        TransferAmountCiphertext::new(
-            transfer_amount_hi,
-            source_elgamal_keypair.pubkey(),
-            destination_elgamal_pubkey,
-            auditor_elgamal_pubkey,
-        );
+    let (transfer_amount_ciphertext_hi, transfer_amount_opening_hi) = TransferAmountCiphertext::new(
+        transfer_amount_hi,
+        source_elgamal_pubkey,
+        destination_elgamal_pubkey,
+        auditor_elgamal_pubkey,
+    );
+
+    // calculate fee
+    let (raw_fee_amount, delta_fee) = calculate_fee(transfer_amount, fee_rate_basis_points)
+        .ok_or(TokenProofGenerationError::FeeCalculation)?;
+
+    // if raw fee is greater than the maximum fee, then use the maximum fee for the
+    // fee amount
+    let fee_amount = std::cmp::min(maximum_fee, raw_fee_amount);
+
+    // split and encrypt fee
+    let (fee_amount_lo, fee_amount_hi) = try_split_u64(fee_amount, FEE_AMOUNT_LO_BITS)
+        .ok_or(TokenProofGenerationError::IllegalAmountBitLength)?;
+    let (fee_ciphertext_lo, fee_opening_lo) = FeeCiphertext::new(
+        fee_amount_lo,
+        destination_elgamal_pubkey,
+        withdraw_withheld_authority_elgamal_pubkey,
+    );
+    let (fee_ciphertext_hi, fee_opening_hi) = FeeCiphertext::new(
+        fee_amount_hi,
+        destination_elgamal_pubkey,
+        withdraw_withheld_authority_elgamal_pubkey,
+    );
+
+    Ok(TransferWithFeeCiphertexts {
+        transfer_amount_lo,
+        transfer_amount_hi,
+        transfer_amount_ciphertext_lo,
+        transfer_amount_ciphertext_hi,
+        transfer_amount_opening_lo,
+        transfer_amount_opening_hi,
+        fee_amount,
+        delta_fee,
+        fee_amount_lo,
+        fee_amount_hi,
+        fee_ciphertext_lo,
+        fee_ciphertext_hi,
+        fee_opening_lo,
+        fee_opening_hi,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_with_fee_split_proof_data(
+    current_available_balance: &ElGamalCiphertext,
+    current_decryptable_available_balance: &AeCiphertext,
+    transfer_amount: u64,
+    source_elgamal_keypair: &ElGamalKeypair,
+    aes_key: &AeKey,
+    destination_elgamal_pubkey: &ElGamalPubkey,
+    auditor_elgamal_pubkey: Option<&ElGamalPubkey>,
+    withdraw_withheld_authority_elgamal_pubkey: &ElGamalPubkey,
+    fee_rate_basis_points: u16,
+    maximum_fee: u64,
+) -> Result<TransferWithFeeProofData, TokenProofGenerationError> {
+    let default_auditor_pubkey = ElGamalPubkey::default();
+    let auditor_elgamal_pubkey = auditor_elgamal_pubkey.unwrap_or(&default_auditor_pubkey);
+
+    let TransferWithFeeCiphertexts {
+        transfer_amount_lo,
+        transfer_amount_hi,
+        transfer_amount_ciphertext_lo: transfer_amount_grouped_ciphertext_lo,
+        transfer_amount_ciphertext_hi: transfer_amount_grouped_ciphertext_hi,
+        transfer_amount_opening_lo,
+        transfer_amount_opening_hi,
+        fee_amount,
+        delta_fee,
+        fee_amount_lo,
+        fee_amount_hi,
+        fee_ciphertext_lo,
+        fee_ciphertext_hi,
+        fee_opening_lo,
+        fee_opening_hi,
+    } = transfer_with_fee_proof_data(
+        transfer_amount,
+        source_elgamal_keypair.pubkey(),
+        destination_elgamal_pubkey,
+        Some(auditor_elgamal_pubkey),
+        withdraw_withheld_authority_elgamal_pubkey,
+        fee_rate_basis_points,
+        maximum_fee,
+    )?;
 
     // Decrypt the current available balance at the source
     let current_decrypted_available_balance = current_decryptable_available_balance
@@ -158,30 +254,6 @@ pub fn transfer_with_fee_split_proof_data(
             ciphertext_hi: transfer_amount_auditor_ciphertext_hi,
         };
 
-    // calculate fee
-    let transfer_fee_basis_points = fee_rate_basis_points;
-    let transfer_fee_maximum_fee = maximum_fee;
-    let (raw_fee_amount, delta_fee) = calculate_fee(transfer_amount, transfer_fee_basis_points)
-        .ok_or(TokenProofGenerationError::FeeCalculation)?;
-
-    // if raw fee is greater than the maximum fee, then use the maximum fee for the
-    // fee amount
-    let fee_amount = std::cmp::min(transfer_fee_maximum_fee, raw_fee_amount);
-
-    // split and encrypt fee
-    let (fee_amount_lo, fee_amount_hi) = try_split_u64(fee_amount, FEE_AMOUNT_LO_BITS)
-        .ok_or(TokenProofGenerationError::IllegalAmountBitLength)?;
-    let (fee_ciphertext_lo, fee_opening_lo) = FeeCiphertext::new(
-        fee_amount_lo,
-        destination_elgamal_pubkey,
-        withdraw_withheld_authority_elgamal_pubkey,
-    );
-    let (fee_ciphertext_hi, fee_opening_hi) = FeeCiphertext::new(
-        fee_amount_hi,
-        destination_elgamal_pubkey,
-        withdraw_withheld_authority_elgamal_pubkey,
-    );
-
     // create combined commitments and openings to be used to generate proofs
     let combined_transfer_amount_commitment = try_combine_lo_hi_commitments(
         transfer_amount_grouped_ciphertext_lo.get_commitment(),
@@ -214,7 +286,7 @@ pub fn transfer_with_fee_split_proof_data(
             &combined_transfer_amount_opening,
         ),
         (&combined_fee_commitment, &combined_fee_opening),
-        transfer_fee_basis_points,
+        fee_rate_basis_points,
     );
 
     // generate fee sigma proof
@@ -227,7 +299,7 @@ pub fn transfer_with_fee_split_proof_data(
         delta_fee,
         &claimed_commitment,
         &claimed_opening,
-        transfer_fee_maximum_fee,
+        maximum_fee,
     )
     .map_err(TokenProofGenerationError::from)?;
 
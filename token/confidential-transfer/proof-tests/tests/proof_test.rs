@@ -1,6 +1,9 @@
 use {
     solana_zk_sdk::{
-        encryption::{auth_encryption::AeKey, elgamal::ElGamalKeypair},
+        encryption::{
+            auth_encryption::AeKey,
+            elgamal::{ElGamalCiphertext, ElGamalKeypair},
+        },
         zk_elgamal_proof_program::proof_data::ZkProofData,
     },
     spl_token_confidential_transfer_proof_extraction::{
@@ -11,7 +14,10 @@ use {
         burn::{burn_split_proof_data, BurnProofData},
         mint::{mint_split_proof_data, MintProofData},
         transfer::{transfer_split_proof_data, TransferProofData},
-        transfer_with_fee::{transfer_with_fee_split_proof_data, TransferWithFeeProofData},
+        transfer_with_fee::{
+            transfer_with_fee_proof_data, transfer_with_fee_split_proof_data,
+            TransferWithFeeCiphertexts, TransferWithFeeProofData,
+        },
         withdraw::{withdraw_proof_data, WithdrawProofData},
     },
 };
@@ -158,6 +164,107 @@ fn test_transfer_with_fee_proof_validity(
     .unwrap();
 }
 
+#[test]
+fn test_transfer_with_fee_ciphertexts_correctness() {
+    test_transfer_with_fee_ciphertexts(0, 0, 0);
+    test_transfer_with_fee_ciphertexts(100, 5, 10);
+    test_transfer_with_fee_ciphertexts(100, 5, 1);
+    test_transfer_with_fee_ciphertexts(65536, 5, 10);
+    test_transfer_with_fee_ciphertexts(1_000_000, 100, 5_000);
+}
+
+fn test_transfer_with_fee_ciphertexts(
+    transfer_amount: u64,
+    fee_rate_basis_points: u16,
+    maximum_fee: u64,
+) {
+    let source_keypair = ElGamalKeypair::new_rand();
+    let destination_keypair = ElGamalKeypair::new_rand();
+    let withdraw_withheld_authority_keypair = ElGamalKeypair::new_rand();
+
+    let TransferWithFeeCiphertexts {
+        transfer_amount_lo,
+        transfer_amount_hi,
+        transfer_amount_ciphertext_lo,
+        transfer_amount_ciphertext_hi,
+        fee_amount,
+        fee_amount_lo,
+        fee_amount_hi,
+        fee_ciphertext_lo,
+        fee_ciphertext_hi,
+        ..
+    } = transfer_with_fee_proof_data(
+        transfer_amount,
+        source_keypair.pubkey(),
+        destination_keypair.pubkey(),
+        None,
+        withdraw_withheld_authority_keypair.pubkey(),
+        fee_rate_basis_points,
+        maximum_fee,
+    )
+    .unwrap();
+
+    let expected_fee = std::cmp::min(
+        (transfer_amount * fee_rate_basis_points as u64).div_ceil(10_000),
+        maximum_fee,
+    );
+    assert_eq!(fee_amount, expected_fee);
+    assert_eq!(
+        transfer_amount_lo + (transfer_amount_hi << 16),
+        transfer_amount
+    );
+    assert_eq!(fee_amount_lo + (fee_amount_hi << 16), fee_amount);
+
+    // the destination can decrypt both the transfer amount and the fee
+    for (ciphertext, amount) in [
+        (
+            ElGamalCiphertext {
+                commitment: *transfer_amount_ciphertext_lo.get_commitment(),
+                handle: *transfer_amount_ciphertext_lo.get_destination_handle(),
+            },
+            transfer_amount_lo,
+        ),
+        (
+            ElGamalCiphertext {
+                commitment: *transfer_amount_ciphertext_hi.get_commitment(),
+                handle: *transfer_amount_ciphertext_hi.get_destination_handle(),
+            },
+            transfer_amount_hi,
+        ),
+        (
+            ElGamalCiphertext {
+                commitment: *fee_ciphertext_lo.get_commitment(),
+                handle: *fee_ciphertext_lo.get_destination_handle(),
+            },
+            fee_amount_lo,
+        ),
+        (
+            ElGamalCiphertext {
+                commitment: *fee_ciphertext_hi.get_commitment(),
+                handle: *fee_ciphertext_hi.get_destination_handle(),
+            },
+            fee_amount_hi,
+        ),
+    ] {
+        assert_eq!(
+            destination_keypair.secret().decrypt_u32(&ciphertext),
+            Some(amount)
+        );
+    }
+
+    // the withdraw withheld authority can decrypt the fee
+    let fee_ciphertext_lo = ElGamalCiphertext {
+        commitment: *fee_ciphertext_lo.get_commitment(),
+        handle: *fee_ciphertext_lo.get_withdraw_withheld_authority_handle(),
+    };
+    assert_eq!(
+        withdraw_withheld_authority_keypair
+            .secret()
+            .decrypt_u32(&fee_ciphertext_lo),
+        Some(fee_amount_lo)
+    );
+}
+
 #[test]
 fn test_withdraw_proof_correctness() {
     test_withdraw_validity(0, 0);
@@ -25,7 +25,8 @@ use {
             confidential_transfer::{
                 self,
                 account_info::{EmptyAccountAccountInfo, TransferAccountInfo, WithdrawAccountInfo},
-                ConfidentialTransferAccount, MAXIMUM_DEPOSIT_TRANSFER_AMOUNT,
+                ConfidentialTransferAccount, ConfidentialTransferRevealMode,
+                MAXIMUM_DEPOSIT_TRANSFER_AMOUNT,
             },
             BaseStateWithExtensions, ExtensionType,
         },
@@ -1287,6 +1288,343 @@ async fn confidential_transfer_withdraw_with_option(option: ConfidentialTransfer
         .await;
 }
 
+#[cfg(feature = "zk-ops")]
+#[tokio::test]
+async fn confidential_transfer_disclose_balance() {
+    let authority = Keypair::new();
+    let auto_approve_new_accounts = true;
+    let auditor_elgamal_keypair = ElGamalKeypair::new_rand();
+    let auditor_elgamal_pubkey = (*auditor_elgamal_keypair.pubkey()).into();
+
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint {
+                authority: Some(authority.pubkey()),
+                auto_approve_new_accounts,
+                auditor_elgamal_pubkey: Some(auditor_elgamal_pubkey),
+            },
+        ])
+        .await
+        .unwrap();
+
+    let TokenContext {
+        token,
+        alice,
+        mint_authority,
+        decimals,
+        ..
+    } = context.token_context.unwrap();
+    let alice_meta = ConfidentialTokenAccountMeta::new_with_tokens(
+        &token,
+        &alice,
+        None,
+        false,
+        false,
+        &mint_authority,
+        42,
+        decimals,
+    )
+    .await;
+
+    token
+        .confidential_transfer_disclose_balance(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            42,
+            None,
+            None,
+            &alice_meta.elgamal_keypair,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    let receipt = token
+        .get_balance_disclosure(&alice_meta.token_account)
+        .await
+        .unwrap();
+    assert_eq!(receipt.token_account, alice_meta.token_account);
+    assert_eq!(u64::from(receipt.amount), 42);
+
+    // a wrong amount fails proof verification and leaves the receipt untouched
+    let err = token
+        .confidential_transfer_disclose_balance(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            41,
+            None,
+            None,
+            &alice_meta.elgamal_keypair,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(1, InstructionError::InvalidInstructionData)
+        )))
+    );
+
+    let receipt = token
+        .get_balance_disclosure(&alice_meta.token_account)
+        .await
+        .unwrap();
+    assert_eq!(u64::from(receipt.amount), 42);
+}
+
+#[cfg(feature = "zk-ops")]
+#[tokio::test]
+async fn confidential_transfer_reveal_mode() {
+    let authority = Keypair::new();
+    let auto_approve_new_accounts = true;
+    let auditor_elgamal_keypair = ElGamalKeypair::new_rand();
+    let auditor_elgamal_pubkey = (*auditor_elgamal_keypair.pubkey()).into();
+
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint {
+                authority: Some(authority.pubkey()),
+                auto_approve_new_accounts,
+                auditor_elgamal_pubkey: Some(auditor_elgamal_pubkey),
+            },
+        ])
+        .await
+        .unwrap();
+
+    let TokenContext {
+        token,
+        alice,
+        bob,
+        mint_authority,
+        decimals,
+        ..
+    } = context.token_context.unwrap();
+    let alice_meta = ConfidentialTokenAccountMeta::new_with_tokens(
+        &token,
+        &alice,
+        None,
+        false,
+        false,
+        &mint_authority,
+        42,
+        decimals,
+    )
+    .await;
+
+    token
+        .reallocate(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            &[ExtensionType::ConfidentialTransferRevealMode],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    token
+        .confidential_transfer_enable_reveal_mode(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    let state = token
+        .get_account_info(&alice_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferRevealMode>()
+        .unwrap();
+    assert!(bool::from(extension.pending_balance_revealed));
+    assert!(!bool::from(extension.available_balance_revealed));
+
+    // the available balance must be revealed before it can be debited
+    let err = token
+        .confidential_transfer_withdraw(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            None,
+            None,
+            42,
+            decimals,
+            None,
+            &alice_meta.elgamal_keypair,
+            &alice_meta.aes_key,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::ConfidentialTransferBalanceNotRevealed as u32)
+            )
+        )))
+    );
+
+    token
+        .confidential_transfer_reveal_available_balance(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            42,
+            None,
+            None,
+            &alice_meta.elgamal_keypair,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    // public deposits and withdrawals keep the balances revealed
+    token
+        .confidential_transfer_withdraw(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            None,
+            None,
+            10,
+            decimals,
+            None,
+            &alice_meta.elgamal_keypair,
+            &alice_meta.aes_key,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    token
+        .confidential_transfer_deposit(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            10,
+            decimals,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    let state = token
+        .get_account_info(&alice_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferRevealMode>()
+        .unwrap();
+    assert!(bool::from(extension.pending_balance_revealed));
+    assert!(bool::from(extension.available_balance_revealed));
+    assert_eq!(u64::from(extension.revealed_pending_balance), 10);
+    assert_eq!(u64::from(extension.revealed_available_balance), 32);
+
+    let bob_meta = ConfidentialTokenAccountMeta::new(&token, &bob, None, false, false).await;
+    token
+        .reallocate(
+            &bob_meta.token_account,
+            &bob.pubkey(),
+            &[ExtensionType::ConfidentialTransferRevealMode],
+            &[&bob],
+        )
+        .await
+        .unwrap();
+    token
+        .confidential_transfer_enable_reveal_mode(&bob_meta.token_account, &bob.pubkey(), &[&bob])
+        .await
+        .unwrap();
+
+    // confidential transfers between accounts in reveal mode must reveal the amount
+    let err = token
+        .confidential_transfer_transfer(
+            &alice_meta.token_account,
+            &bob_meta.token_account,
+            &alice.pubkey(),
+            None,
+            None,
+            None,
+            5,
+            false,
+            None,
+            &alice_meta.elgamal_keypair,
+            &alice_meta.aes_key,
+            bob_meta.elgamal_keypair.pubkey(),
+            Some(auditor_elgamal_keypair.pubkey()),
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::ConfidentialTransferAmountNotRevealed as u32)
+            )
+        )))
+    );
+
+    token
+        .confidential_transfer_transfer(
+            &alice_meta.token_account,
+            &bob_meta.token_account,
+            &alice.pubkey(),
+            None,
+            None,
+            None,
+            5,
+            true,
+            None,
+            &alice_meta.elgamal_keypair,
+            &alice_meta.aes_key,
+            bob_meta.elgamal_keypair.pubkey(),
+            Some(auditor_elgamal_keypair.pubkey()),
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    let state = token
+        .get_account_info(&alice_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferRevealMode>()
+        .unwrap();
+    assert!(bool::from(extension.available_balance_revealed));
+    assert_eq!(u64::from(extension.revealed_available_balance), 27);
+
+    let state = token
+        .get_account_info(&bob_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferRevealMode>()
+        .unwrap();
+    assert!(bool::from(extension.pending_balance_revealed));
+    assert_eq!(u64::from(extension.revealed_pending_balance), 5);
+
+    // enabling reveal mode twice fails
+    let err = token
+        .confidential_transfer_enable_reveal_mode(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::ExtensionAlreadyInitialized as u32)
+            )
+        )))
+    );
+}
+
 #[allow(clippy::too_many_arguments)]
 #[cfg(feature = "zk-ops")]
 async fn confidential_transfer_with_option<S: Signers>(
@@ -1320,6 +1658,7 @@ async fn confidential_transfer_with_option<S: Signers>(
                     None,
                     None,
                     transfer_amount,
+                    false,
                     None,
                     source_elgamal_keypair,
                     source_aes_key,
@@ -1429,6 +1768,7 @@ async fn confidential_transfer_with_option<S: Signers>(
                     Some(&ciphertext_validity_proof_account_with_ciphertext),
                     Some(&range_proof_account),
                     transfer_amount,
+                    false,
                     None,
                     source_elgamal_keypair,
                     source_aes_key,
@@ -1564,6 +1904,7 @@ async fn confidential_transfer_with_option<S: Signers>(
                     Some(&ciphertext_validity_proof_account_with_ciphertext),
                     Some(&range_proof_context_proof_account),
                     transfer_amount,
+                    false,
                     None,
                     source_elgamal_keypair,
                     source_aes_key,
@@ -2138,6 +2479,7 @@ async fn confidential_transfer_with_fee_with_option<S: Signers>(
                     None,
                     None,
                     transfer_amount,
+                    false,
                     None,
                     source_elgamal_keypair,
                     source_aes_key,
@@ -2292,6 +2634,7 @@ async fn confidential_transfer_with_fee_with_option<S: Signers>(
                     Some(&fee_ciphertext_validity_proof_account),
                     Some(&range_proof_account),
                     transfer_amount,
+                    false,
                     None,
                     source_elgamal_keypair,
                     source_aes_key,
@@ -2491,6 +2834,7 @@ async fn confidential_transfer_with_fee_with_option<S: Signers>(
                     Some(&fee_ciphertext_validity_proof_context_proof_account),
                     Some(&range_proof_context_proof_account),
                     transfer_amount,
+                    false,
                     None,
                     source_elgamal_keypair,
                     source_aes_key,
@@ -18,7 +18,8 @@ use {
         error::TokenError,
         extension::{
             confidential_transfer::{
-                ConfidentialTransferAccount, ConfidentialTransferMint, DecryptableBalance,
+                ConfidentialTransferAccount, ConfidentialTransferMint,
+                ConfidentialTransferRevealMode, DecryptableBalance,
             },
             confidential_transfer_fee::{
                 account_info::WithheldTokensInfo, ConfidentialTransferFeeAmount,
@@ -689,6 +690,7 @@ async fn confidential_transfer_withdraw_withheld_tokens_from_mint_with_option(
             None,
             None,
             100,
+            false,
             None,
             &alice_meta.elgamal_keypair,
             &alice_meta.aes_key,
@@ -1015,6 +1017,7 @@ async fn confidential_transfer_withdraw_withheld_tokens_from_accounts_with_optio
             None,
             None,
             100,
+            false,
             None,
             &alice_meta.elgamal_keypair,
             &alice_meta.aes_key,
@@ -1154,6 +1157,7 @@ async fn confidential_transfer_harvest_withheld_tokens_to_mint() {
             None,
             None,
             100,
+            false,
             None,
             &alice_meta.elgamal_keypair,
             &alice_meta.aes_key,
@@ -1222,3 +1226,142 @@ async fn confidential_transfer_harvest_withheld_tokens_to_mint() {
 
     check_withheld_amount_in_mint(&token, &withdraw_withheld_authority_elgamal_keypair, fee).await;
 }
+
+#[cfg(feature = "zk-ops")]
+#[tokio::test]
+async fn confidential_transfer_transfer_with_fee_reveal_mode() {
+    let transfer_fee_authority = Keypair::new();
+    let withdraw_withheld_authority = Keypair::new();
+
+    let confidential_transfer_authority = Keypair::new();
+    let auto_approve_new_accounts = true;
+    let auditor_elgamal_keypair = ElGamalKeypair::new_rand();
+    let auditor_elgamal_pubkey = (*auditor_elgamal_keypair.pubkey()).into();
+
+    let confidential_transfer_fee_authority = Keypair::new();
+    let withdraw_withheld_authority_elgamal_keypair = ElGamalKeypair::new_rand();
+    let withdraw_withheld_authority_elgamal_pubkey =
+        (*withdraw_withheld_authority_elgamal_keypair.pubkey()).into();
+
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::TransferFeeConfig {
+                transfer_fee_config_authority: Some(transfer_fee_authority.pubkey()),
+                withdraw_withheld_authority: Some(withdraw_withheld_authority.pubkey()),
+                transfer_fee_basis_points: TEST_FEE_BASIS_POINTS,
+                maximum_fee: TEST_MAXIMUM_FEE,
+            },
+            ExtensionInitializationParams::ConfidentialTransferMint {
+                authority: Some(confidential_transfer_authority.pubkey()),
+                auto_approve_new_accounts,
+                auditor_elgamal_pubkey: Some(auditor_elgamal_pubkey),
+            },
+            ExtensionInitializationParams::ConfidentialTransferFeeConfig {
+                authority: Some(confidential_transfer_fee_authority.pubkey()),
+                withdraw_withheld_authority_elgamal_pubkey,
+            },
+        ])
+        .await
+        .unwrap();
+
+    let TokenContext {
+        token,
+        alice,
+        bob,
+        mint_authority,
+        decimals,
+        ..
+    } = context.token_context.unwrap();
+
+    let alice_meta =
+        ConfidentialTokenAccountMeta::new(&token, &alice, &mint_authority, 100, decimals).await;
+    let bob_meta =
+        ConfidentialTokenAccountMeta::new(&token, &bob, &mint_authority, 0, decimals).await;
+
+    for (meta, owner) in [(&alice_meta, &alice), (&bob_meta, &bob)] {
+        token
+            .reallocate(
+                &meta.token_account,
+                &owner.pubkey(),
+                &[ExtensionType::ConfidentialTransferRevealMode],
+                &[owner],
+            )
+            .await
+            .unwrap();
+        token
+            .confidential_transfer_enable_reveal_mode(
+                &meta.token_account,
+                &owner.pubkey(),
+                &[owner],
+            )
+            .await
+            .unwrap();
+    }
+    token
+        .confidential_transfer_reveal_available_balance(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            100,
+            None,
+            None,
+            &alice_meta.elgamal_keypair,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    let transfer_fee_parameters = TransferFee {
+        epoch: 0.into(),
+        maximum_fee: TEST_MAXIMUM_FEE.into(),
+        transfer_fee_basis_points: TEST_FEE_BASIS_POINTS.into(),
+    };
+
+    token
+        .confidential_transfer_transfer_with_fee(
+            &alice_meta.token_account,
+            &bob_meta.token_account,
+            &alice.pubkey(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            100,
+            true,
+            None,
+            &alice_meta.elgamal_keypair,
+            &alice_meta.aes_key,
+            bob_meta.elgamal_keypair.pubkey(),
+            Some(auditor_elgamal_keypair.pubkey()),
+            withdraw_withheld_authority_elgamal_keypair.pubkey(),
+            transfer_fee_parameters.transfer_fee_basis_points.into(),
+            transfer_fee_parameters.maximum_fee.into(),
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    // the source is debited the transfer amount and the destination is credited
+    // the transfer amount minus the fee
+    let state = token
+        .get_account_info(&alice_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferRevealMode>()
+        .unwrap();
+    assert!(bool::from(extension.available_balance_revealed));
+    assert_eq!(u64::from(extension.revealed_available_balance), 0);
+
+    let fee = transfer_fee_parameters.calculate_fee(100).unwrap();
+    let state = token
+        .get_account_info(&bob_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferRevealMode>()
+        .unwrap();
+    assert!(bool::from(extension.pending_balance_revealed));
+    assert_eq!(u64::from(extension.revealed_pending_balance), 100 - fee);
+}
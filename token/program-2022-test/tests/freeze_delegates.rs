@@ -0,0 +1,220 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        extension::{
+            freeze_delegates::{FreezeDelegateScope, FreezeDelegates, MAX_FREEZE_DELEGATES},
+            BaseStateWithExtensions,
+        },
+        state::AccountState,
+    },
+    spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
+};
+
+fn client_error(token_error: TokenError) -> TokenClientError {
+    TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(0, InstructionError::Custom(token_error as u32)),
+    )))
+}
+
+#[tokio::test]
+async fn success_initialize() {
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_freezing_mint(vec![ExtensionInitializationParams::FreezeDelegates])
+        .await
+        .unwrap();
+    let TokenContext { token, .. } = context.token_context.unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<FreezeDelegates>().unwrap();
+    assert_eq!(*extension, FreezeDelegates::default());
+}
+
+#[tokio::test]
+async fn set_and_remove_delegate() {
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_freezing_mint(vec![ExtensionInitializationParams::FreezeDelegates])
+        .await
+        .unwrap();
+    let TokenContext {
+        freeze_authority,
+        token,
+        ..
+    } = context.token_context.unwrap();
+    let freeze_authority = freeze_authority.unwrap();
+
+    // only the freeze authority can manage delegates
+    let delegate = Pubkey::new_unique();
+    let wrong_authority = Keypair::new();
+    let err = token
+        .set_freeze_delegate(
+            &wrong_authority.pubkey(),
+            &delegate,
+            FreezeDelegateScope::FreezeOnly,
+            &[&wrong_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::OwnerMismatch));
+
+    let delegates: Vec<Pubkey> = (0..MAX_FREEZE_DELEGATES)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    for delegate in &delegates {
+        token
+            .set_freeze_delegate(
+                &freeze_authority.pubkey(),
+                delegate,
+                FreezeDelegateScope::FreezeOnly,
+                &[&freeze_authority],
+            )
+            .await
+            .unwrap();
+    }
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<FreezeDelegates>().unwrap();
+    for delegate in &delegates {
+        assert!(extension.get_delegate(delegate).is_some());
+    }
+
+    // all slots taken
+    let err = token
+        .set_freeze_delegate(
+            &freeze_authority.pubkey(),
+            &delegate,
+            FreezeDelegateScope::FreezeOnly,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::FreezeDelegateLimitReached));
+
+    token
+        .remove_freeze_delegate(
+            &freeze_authority.pubkey(),
+            &delegates[0],
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<FreezeDelegates>().unwrap();
+    assert!(extension.get_delegate(&delegates[0]).is_none());
+
+    let err = token
+        .remove_freeze_delegate(
+            &freeze_authority.pubkey(),
+            &delegates[0],
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::FreezeDelegateNotFound));
+}
+
+#[tokio::test]
+async fn freeze_and_thaw_with_delegate() {
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_freezing_mint(vec![ExtensionInitializationParams::FreezeDelegates])
+        .await
+        .unwrap();
+    let TokenContext {
+        freeze_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.unwrap();
+    let freeze_authority = freeze_authority.unwrap();
+
+    let account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&account, &alice.pubkey())
+        .await
+        .unwrap();
+    let account = account.pubkey();
+
+    let freeze_only_delegate = Keypair::new();
+    token
+        .set_freeze_delegate(
+            &freeze_authority.pubkey(),
+            &freeze_only_delegate.pubkey(),
+            FreezeDelegateScope::FreezeOnly,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+    let freeze_and_thaw_delegate = Keypair::new();
+    token
+        .set_freeze_delegate(
+            &freeze_authority.pubkey(),
+            &freeze_and_thaw_delegate.pubkey(),
+            FreezeDelegateScope::FreezeAndThaw,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+
+    // freeze-only delegate can freeze, but not thaw
+    token
+        .freeze(
+            &account,
+            &freeze_only_delegate.pubkey(),
+            &[&freeze_only_delegate],
+        )
+        .await
+        .unwrap();
+    let state = token.get_account_info(&account).await.unwrap();
+    assert_eq!(state.base.state, AccountState::Frozen);
+
+    let err = token
+        .thaw(
+            &account,
+            &freeze_only_delegate.pubkey(),
+            &[&freeze_only_delegate],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::FreezeDelegateScopeExceeded));
+
+    // freeze-and-thaw delegate can thaw
+    token
+        .thaw(
+            &account,
+            &freeze_and_thaw_delegate.pubkey(),
+            &[&freeze_and_thaw_delegate],
+        )
+        .await
+        .unwrap();
+    let state = token.get_account_info(&account).await.unwrap();
+    assert_eq!(state.base.state, AccountState::Initialized);
+
+    // removed delegate can't freeze anymore
+    token
+        .remove_freeze_delegate(
+            &freeze_authority.pubkey(),
+            &freeze_only_delegate.pubkey(),
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+    let err = token
+        .freeze(
+            &account,
+            &freeze_only_delegate.pubkey(),
+            &[&freeze_only_delegate],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::OwnerMismatch));
+}
@@ -199,6 +199,100 @@ async fn reallocate_without_current_extension_knowledge() {
     );
 }
 
+#[tokio::test]
+async fn reallocate_idempotent_refunds_excess_lamports() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        token,
+        alice,
+        mint_authority,
+        ..
+    } = context.token_context.unwrap();
+    let context = context.context.clone();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    // overfund the account
+    let excess_lamports = 1_000_000_000;
+    {
+        let context = context.lock().await;
+        let instructions = vec![system_instruction::transfer(
+            &context.payer.pubkey(),
+            &alice_account,
+            excess_lamports,
+        )];
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+    }
+
+    // reallocate grows the account and keeps only the new rent-exempt reserve
+    token
+        .reallocate_idempotent(
+            &alice_account,
+            &alice.pubkey(),
+            &[ExtensionType::ImmutableOwner],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let account = token.get_account(alice_account).await.unwrap();
+    let expected_len =
+        ExtensionType::try_calculate_account_len::<Account>(&[ExtensionType::ImmutableOwner])
+            .unwrap();
+    let rent = {
+        let context = context.lock().await;
+        context.banks_client.get_rent().await.unwrap()
+    };
+    assert_eq!(account.data.len(), expected_len);
+    assert_eq!(account.lamports, rent.minimum_balance(expected_len));
+
+    // repeating the instruction is a noop
+    token.get_new_latest_blockhash().await.unwrap();
+    token
+        .reallocate_idempotent(
+            &alice_account,
+            &alice.pubkey(),
+            &[ExtensionType::ImmutableOwner],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let account = token.get_account(alice_account).await.unwrap();
+    assert_eq!(account.data.len(), expected_len);
+    assert_eq!(account.lamports, rent.minimum_balance(expected_len));
+
+    // the refund still requires the owner
+    let error = token
+        .reallocate_idempotent(
+            &alice_account,
+            &mint_authority.pubkey(),
+            &[ExtensionType::ImmutableOwner],
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+}
+
 #[test_case(&[ExtensionType::CpiGuard], 1_000_000_000, true ; "transfer more than new rent and sync")]
 #[test_case(&[ExtensionType::CpiGuard], 1_000_000_000, false ; "transfer more than new rent")]
 #[test_case(&[ExtensionType::CpiGuard], 1, true ; "transfer less than new rent and sync")]
@@ -9,7 +9,17 @@ use {
         instruction::InstructionError, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
         transaction::TransactionError, transport::TransportError,
     },
-    spl_token_2022::{extension::BaseStateWithExtensions, processor::Processor},
+    spl_token_2022::{
+        error::TokenError,
+        extension::{
+            token_metadata::{
+                display_hints::{DisplayHints, ROUNDING_MODE_KEY, SIGNIFICANT_DIGITS_KEY},
+                field_authorities::TokenMetadataFieldAuthorities,
+            },
+            BaseStateWithExtensions,
+        },
+        processor::Processor,
+    },
     spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
     spl_token_metadata_interface::{
         error::TokenMetadataError,
@@ -204,3 +214,244 @@ async fn fail_authority_checks() {
         )))
     );
 }
+
+#[tokio::test]
+async fn display_hints() {
+    let authority = Keypair::new();
+    let mint_keypair = Keypair::new();
+    let mut test_context = setup(mint_keypair, &authority.pubkey()).await;
+    let payer_pubkey = test_context.context.lock().await.payer.pubkey();
+    let token_context = test_context.token_context.take().unwrap();
+
+    let update_authority = Keypair::new();
+    token_context
+        .token
+        .token_metadata_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            &token_context.mint_authority.pubkey(),
+            "MySuperCoolToken".to_string(),
+            "MINE".to_string(),
+            "my.super.cool.token".to_string(),
+            &[&token_context.mint_authority],
+        )
+        .await
+        .unwrap();
+
+    for (key, value) in [
+        (SIGNIFICANT_DIGITS_KEY, "4"),
+        (ROUNDING_MODE_KEY, "half_up"),
+    ] {
+        token_context
+            .token
+            .token_metadata_update_field_with_rent_transfer(
+                &payer_pubkey,
+                &update_authority.pubkey(),
+                Field::Key(key.to_string()),
+                value.to_string(),
+                None,
+                &[&update_authority],
+            )
+            .await
+            .unwrap();
+    }
+
+    let mint_info = token_context.token.get_mint_info().await.unwrap();
+    let fetched_metadata = mint_info
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
+    let hints = DisplayHints::try_from_metadata(&fetched_metadata).unwrap();
+    assert_eq!(hints.format_ui_amount(1_234_567_890, 9), "1.235");
+
+    // invalid values and unknown keys within the namespace are rejected
+    for (key, value) in [
+        (SIGNIFICANT_DIGITS_KEY, "0"),
+        (ROUNDING_MODE_KEY, "nearest"),
+        ("display.unknown", "1"),
+    ] {
+        let error = token_context
+            .token
+            .token_metadata_update_field(
+                &update_authority.pubkey(),
+                Field::Key(key.to_string()),
+                value.to_string(),
+                &[&update_authority],
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error,
+            TokenClientError::Client(Box::new(TransportError::TransactionError(
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(TokenError::InvalidDisplayHint as u32)
+                )
+            )))
+        );
+    }
+}
+
+#[tokio::test]
+async fn field_authorities() {
+    let authority = Keypair::new();
+    let mint_keypair = Keypair::new();
+    let mut test_context = setup(mint_keypair, &authority.pubkey()).await;
+    let payer_pubkey = test_context.context.lock().await.payer.pubkey();
+    let token_context = test_context.token_context.take().unwrap();
+
+    let update_authority = Keypair::new();
+    token_context
+        .token
+        .token_metadata_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            &token_context.mint_authority.pubkey(),
+            "MySuperCoolToken".to_string(),
+            "MINE".to_string(),
+            "my.super.cool.token".to_string(),
+            &[&token_context.mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // delegate the uri and the "level" field
+    let artist = Keypair::new();
+    let game_server = Keypair::new();
+    for (field, authority) in [
+        (Field::Uri, artist.pubkey()),
+        (Field::Key("level".to_string()), game_server.pubkey()),
+    ] {
+        token_context
+            .token
+            .token_metadata_set_field_authority_with_rent_transfer(
+                &payer_pubkey,
+                &update_authority.pubkey(),
+                field,
+                Some(authority),
+                None,
+                &[&update_authority],
+            )
+            .await
+            .unwrap();
+    }
+
+    token_context
+        .token
+        .token_metadata_update_field_with_rent_transfer(
+            &payer_pubkey,
+            &artist.pubkey(),
+            Field::Uri,
+            "my.new.super.cool.token".to_string(),
+            None,
+            &[&artist],
+        )
+        .await
+        .unwrap();
+    token_context
+        .token
+        .token_metadata_update_field_with_rent_transfer(
+            &payer_pubkey,
+            &game_server.pubkey(),
+            Field::Key("level".to_string()),
+            "42".to_string(),
+            None,
+            &[&game_server],
+        )
+        .await
+        .unwrap();
+
+    let mint_info = token_context.token.get_mint_info().await.unwrap();
+    let fetched_metadata = mint_info
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
+    assert_eq!(fetched_metadata.uri, "my.new.super.cool.token");
+    assert_eq!(
+        fetched_metadata.additional_metadata,
+        vec![("level".to_string(), "42".to_string())]
+    );
+    let field_authorities = mint_info
+        .get_variable_len_extension::<TokenMetadataFieldAuthorities>()
+        .unwrap();
+    assert_eq!(field_authorities.get(&Field::Uri), Some(artist.pubkey()));
+    assert_eq!(
+        field_authorities.get(&Field::Key("level".to_string())),
+        Some(game_server.pubkey())
+    );
+
+    // field authorities can't update other fields
+    for (field, value) in [
+        (Field::Name, "ArtistToken".to_string()),
+        (Field::Key("level".to_string()), "99".to_string()),
+    ] {
+        let error = token_context
+            .token
+            .token_metadata_update_field(&artist.pubkey(), field, value, &[&artist])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error,
+            TokenClientError::Client(Box::new(TransportError::TransactionError(
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(TokenMetadataError::IncorrectUpdateAuthority as u32)
+                )
+            )))
+        );
+    }
+
+    // only the update authority can set field authorities
+    let error = token_context
+        .token
+        .token_metadata_set_field_authority_with_rent_transfer(
+            &payer_pubkey,
+            &artist.pubkey(),
+            Field::Name,
+            Some(artist.pubkey()),
+            None,
+            &[&artist],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenMetadataError::IncorrectUpdateAuthority as u32)
+            )
+        )))
+    );
+
+    // a revoked field authority can't update its field anymore
+    token_context
+        .token
+        .token_metadata_set_field_authority_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            Field::Uri,
+            None,
+            None,
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+    let error = token_context
+        .token
+        .token_metadata_update_field(
+            &artist.pubkey(),
+            Field::Uri,
+            "my.stolen.token".to_string(),
+            &[&artist],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenMetadataError::IncorrectUpdateAuthority as u32)
+            )
+        )))
+    );
+}
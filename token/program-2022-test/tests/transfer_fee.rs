@@ -12,9 +12,10 @@ use {
         error::TokenError,
         extension::{
             transfer_fee::{
-                TransferFee, TransferFeeAmount, TransferFeeConfig, MAX_FEE_BASIS_POINTS,
+                TransferFee, TransferFeeAmount, TransferFeeConfig, TransferFeeExemptAccount,
+                MAX_FEE_BASIS_POINTS,
             },
-            BaseStateWithExtensions,
+            BaseStateWithExtensions, ExtensionType,
         },
         instruction,
     },
@@ -80,6 +81,7 @@ struct TokenWithAccounts {
     token: Token<ProgramBanksClientProcessTransaction>,
     token_unchecked: Token<ProgramBanksClientProcessTransaction>,
     transfer_fee_config: TransferFeeConfig,
+    transfer_fee_config_authority: Keypair,
     withdraw_withheld_authority: Keypair,
     freeze_authority: Keypair,
     alice: Keypair,
@@ -148,6 +150,7 @@ async fn create_mint_with_accounts(alice_amount: u64) -> TokenWithAccounts {
         token,
         token_unchecked,
         transfer_fee_config,
+        transfer_fee_config_authority,
         withdraw_withheld_authority,
         freeze_authority: freeze_authority.unwrap(),
         alice,
@@ -1175,6 +1178,144 @@ async fn no_fees_from_self_transfer() {
     assert_eq!(extension.withheld_amount, 0.into());
 }
 
+#[tokio::test]
+async fn no_fees_from_exempt_account() {
+    let amount = TEST_MAXIMUM_FEE;
+    let alice_amount = amount * 100;
+    let TokenWithAccounts {
+        token,
+        transfer_fee_config,
+        transfer_fee_config_authority,
+        alice,
+        alice_account,
+        bob_account,
+        ..
+    } = create_mint_with_accounts(alice_amount).await;
+
+    // no space for the extension
+    let error = token
+        .set_transfer_fee_exemption(
+            &alice_account,
+            &transfer_fee_config_authority.pubkey(),
+            true,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        )))
+    );
+
+    token
+        .reallocate(
+            &alice_account,
+            &alice.pubkey(),
+            &[ExtensionType::TransferFeeExemptAccount],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    // wrong authority
+    let error = token
+        .set_transfer_fee_exemption(&alice_account, &alice.pubkey(), true, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    token
+        .set_transfer_fee_exemption(
+            &alice_account,
+            &transfer_fee_config_authority.pubkey(),
+            true,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    let extension = alice_state
+        .get_extension::<TransferFeeExemptAccount>()
+        .unwrap();
+    assert!(bool::from(extension.exempt));
+
+    // fee expected by the caller must be zero
+    let fee = transfer_fee_config.calculate_epoch_fee(0, amount).unwrap();
+    let error = token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            amount,
+            fee,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::FeeMismatch as u32)
+            )
+        )))
+    );
+
+    // transfer from exempt account, no fee assessed
+    token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            amount,
+            0,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, amount);
+    let extension = bob_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, 0.into());
+
+    // remove exemption, fee assessed again
+    token
+        .set_transfer_fee_exemption(
+            &alice_account,
+            &transfer_fee_config_authority.pubkey(),
+            false,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            amount,
+            fee,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, 2 * amount - fee);
+    let extension = bob_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, fee.into());
+}
+
 async fn create_and_transfer_to_account(
     token: &Token<ProgramBanksClientProcessTransaction>,
     source: &Pubkey,
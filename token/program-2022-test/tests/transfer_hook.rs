@@ -22,7 +22,7 @@ use {
         error::TokenError,
         extension::{
             transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig},
-            transfer_hook::{TransferHook, TransferHookAccount},
+            transfer_hook::{TransferHook, TransferHookAccount, TransferHookBudget},
             BaseStateWithExtensions,
         },
         instruction, offchain,
@@ -471,6 +471,113 @@ async fn success_transfer() {
     );
 }
 
+#[tokio::test]
+async fn transfer_hook_budget() {
+    let authority = Keypair::new();
+    let program_id = Pubkey::new_unique();
+    let mint = Keypair::new();
+    let mut program_test = setup_program_test(&program_id);
+    // the validation account requires 4 extra accounts
+    add_validation_account(&mut program_test, &mint.pubkey(), &program_id);
+
+    let context = program_test.start_with_context().await;
+    let context = Arc::new(tokio::sync::Mutex::new(context));
+    let mut context = TestContext {
+        context,
+        token_context: None,
+    };
+    context
+        .init_token_with_mint_keypair_and_freeze_authority(
+            mint,
+            vec![
+                ExtensionInitializationParams::TransferHook {
+                    authority: Some(authority.pubkey()),
+                    program_id: Some(program_id),
+                },
+                ExtensionInitializationParams::TransferHookBudget {
+                    max_extra_accounts: 3,
+                    max_compute_units: 50_000,
+                },
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+    let token_context = context.token_context.take().unwrap();
+
+    let amount = 10;
+    let (alice_account, bob_account) =
+        setup_accounts(&token_context, Keypair::new(), Keypair::new(), amount).await;
+
+    // fail, the transfer hook requires more extra accounts than declared
+    let err = token_context
+        .token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &token_context.alice.pubkey(),
+            amount,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::TransferHookBudgetExceeded as u32)
+            )
+        )))
+    );
+
+    // fail, wrong signature
+    let wrong = Keypair::new();
+    let err = token_context
+        .token
+        .update_transfer_hook_budget(&wrong.pubkey(), 4, 50_000, &[&wrong])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // success, the budget fits the extra accounts
+    token_context
+        .token
+        .update_transfer_hook_budget(&authority.pubkey(), 4, 50_000, &[&authority])
+        .await
+        .unwrap();
+    let state = token_context.token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<TransferHookBudget>().unwrap();
+    assert_eq!(u16::from(extension.max_extra_accounts), 4);
+    assert_eq!(u32::from(extension.max_compute_units), 50_000);
+
+    token_context
+        .token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &token_context.alice.pubkey(),
+            amount,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap();
+    let destination = token_context
+        .token
+        .get_account_info(&bob_account)
+        .await
+        .unwrap();
+    assert_eq!(destination.base.amount, amount);
+}
+
 #[tokio::test]
 async fn success_transfer_with_fee() {
     let authority = Keypair::new();
@@ -1003,6 +1110,7 @@ async fn success_confidential_transfer() {
             None,
             None,
             amount,
+            false,
             None,
             &alice_meta.elgamal_keypair,
             &alice_meta.aes_key,
@@ -269,6 +269,36 @@ pub enum TokenError {
     /// Transferring, minting, and burning is paused on this mint
     #[error("Transferring, minting, and burning is paused on this mint")]
     MintPaused,
+    /// All freeze delegate slots on the mint are taken
+    #[error("All freeze delegate slots on the mint are taken")]
+    FreezeDelegateLimitReached,
+    /// Freeze delegate not found on the mint
+    #[error("Freeze delegate not found on the mint")]
+    FreezeDelegateNotFound,
+
+    //70
+    /// Operation not allowed by the freeze delegate's scope
+    #[error("Operation not allowed by the freeze delegate's scope")]
+    FreezeDelegateScopeExceeded,
+    /// Invalid display hint in token metadata
+    #[error("Invalid display hint in token metadata")]
+    InvalidDisplayHint,
+    /// Transfer hook requires more extra accounts than the mint's budget
+    /// allows
+    #[error("Transfer hook requires more extra accounts than the mint's budget allows")]
+    TransferHookBudgetExceeded,
+    /// The available balance of a confidential account in reveal mode must be
+    /// revealed before it is debited
+    #[error("Available balance must be revealed before it is debited")]
+    ConfidentialTransferBalanceNotRevealed,
+    /// A confidential transfer from or to an account in reveal mode must
+    /// reveal its amount
+    #[error("Transfer amount must be revealed for an account in reveal mode")]
+    ConfidentialTransferAmountNotRevealed,
+    /// The revealed amount of a confidential transfer does not match the
+    /// encrypted transfer amount
+    #[error("Revealed transfer amount does not match the encrypted amount")]
+    ConfidentialTransferRevealedAmountMismatch,
 }
 impl From<TokenError> for ProgramError {
     fn from(e: TokenError) -> Self {
@@ -465,6 +495,30 @@ impl PrintProgramError for TokenError {
             TokenError::MintPaused => {
                 msg!("Transferring, minting, and burning is paused on this mint")
             }
+            TokenError::FreezeDelegateLimitReached => {
+                msg!("All freeze delegate slots on the mint are taken")
+            }
+            TokenError::FreezeDelegateNotFound => {
+                msg!("Freeze delegate not found on the mint")
+            }
+            TokenError::FreezeDelegateScopeExceeded => {
+                msg!("Operation not allowed by the freeze delegate's scope")
+            }
+            TokenError::InvalidDisplayHint => {
+                msg!("Invalid display hint in token metadata")
+            }
+            TokenError::TransferHookBudgetExceeded => {
+                msg!("Transfer hook requires more extra accounts than the mint's budget allows")
+            }
+            TokenError::ConfidentialTransferBalanceNotRevealed => {
+                msg!("Available balance must be revealed before it is debited")
+            }
+            TokenError::ConfidentialTransferAmountNotRevealed => {
+                msg!("Transfer amount must be revealed for an account in reveal mode")
+            }
+            TokenError::ConfidentialTransferRevealedAmountMismatch => {
+                msg!("Revealed transfer amount does not match the encrypted amount")
+            }
         }
     }
 }
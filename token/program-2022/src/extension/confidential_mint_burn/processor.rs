@@ -14,7 +14,10 @@ use {
                 verify_proof::{verify_burn_proof, verify_mint_proof},
                 ConfidentialMintBurn,
             },
-            confidential_transfer::{ConfidentialTransferAccount, ConfidentialTransferMint},
+            confidential_transfer::{
+                ConfidentialTransferAccount, ConfidentialTransferMint,
+                ConfidentialTransferRevealMode,
+            },
             pausable::PausableConfig,
             BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
         },
@@ -251,6 +254,10 @@ fn process_confidential_mint(
 
     confidential_transfer_account.increment_pending_balance_credit_counter()?;
 
+    if let Ok(reveal_mode) = token_account.get_extension_mut::<ConfidentialTransferRevealMode>() {
+        reveal_mode.credit_pending_balance_confidentially();
+    }
+
     // update supply
     if mint_burn_extension.supply_elgamal_pubkey != proof_context.mint_pubkeys.supply {
         return Err(ProgramError::InvalidInstructionData);
@@ -380,6 +387,10 @@ fn process_confidential_burn(
     confidential_transfer_account.decryptable_available_balance =
         data.new_decryptable_available_balance;
 
+    if let Ok(reveal_mode) = token_account.get_extension_mut::<ConfidentialTransferRevealMode>() {
+        reveal_mode.debit_available_balance_confidentially()?;
+    }
+
     if let Some(auditor_pubkey) = Option::<PodElGamalPubkey>::from(auditor_elgamal_pubkey) {
         if auditor_pubkey != proof_context.burn_pubkeys.auditor {
             return Err(ProgramError::InvalidInstructionData);
@@ -10,15 +10,21 @@ use {
     solana_zk_sdk::{
         encryption::{
             auth_encryption::{AeCiphertext, AeKey},
-            elgamal::{ElGamalKeypair, ElGamalPubkey, ElGamalSecretKey},
+            elgamal::{
+                ElGamal, ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey, ElGamalSecretKey,
+            },
+        },
+        zk_elgamal_proof_program::proof_data::{
+            BatchedGroupedCiphertext3HandlesValidityProofContext, ZeroCiphertextProofData,
         },
-        zk_elgamal_proof_program::proof_data::ZeroCiphertextProofData,
     },
     spl_pod::primitives::PodU64,
     spl_token_confidential_transfer_proof_generation::{
         transfer::{transfer_split_proof_data, TransferProofData},
         transfer_with_fee::{transfer_with_fee_split_proof_data, TransferWithFeeProofData},
+        try_combine_lo_hi_ciphertexts,
         withdraw::{withdraw_proof_data, WithdrawProofData},
+        TRANSFER_AMOUNT_LO_BITS,
     },
 };
 
@@ -54,6 +60,40 @@ impl EmptyAccountAccountInfo {
     }
 }
 
+/// Confidential transfer extension information needed to construct a
+/// `DiscloseBalance` or `RevealAvailableBalance` instruction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct DiscloseBalanceAccountInfo {
+    /// The available balance
+    pub(crate) available_balance: EncryptedBalance,
+}
+impl DiscloseBalanceAccountInfo {
+    /// Create the `DiscloseBalance` instruction account information from
+    /// `ConfidentialTransferAccount`.
+    pub fn new(account: &ConfidentialTransferAccount) -> Self {
+        Self {
+            available_balance: account.available_balance,
+        }
+    }
+
+    /// Create a balance disclosure proof data.
+    pub fn generate_proof_data(
+        &self,
+        elgamal_keypair: &ElGamalKeypair,
+        amount: u64,
+    ) -> Result<ZeroCiphertextProofData, TokenError> {
+        let available_balance: ElGamalCiphertext = self
+            .available_balance
+            .try_into()
+            .map_err(|_| TokenError::MalformedCiphertext)?;
+        let remaining_balance = available_balance - ElGamal::encode(amount);
+
+        ZeroCiphertextProofData::new(elgamal_keypair, &remaining_balance)
+            .map_err(|_| TokenError::ProofGeneration)
+    }
+}
+
 /// Confidential Transfer extension information needed to construct an
 /// `ApplyPendingBalance` instruction.
 #[repr(C)]
@@ -309,6 +349,36 @@ impl TransferAccountInfo {
         .map_err(|e| -> TokenError { e.into() })
     }
 
+    /// Create the proof data that reveals the transfer amount encrypted under
+    /// the source ElGamal public key in the context of a transfer amount
+    /// ciphertext validity proof.
+    pub fn generate_revealed_transfer_amount_proof_data(
+        transfer_amount: u64,
+        source_elgamal_keypair: &ElGamalKeypair,
+        ciphertext_validity_proof_context: &BatchedGroupedCiphertext3HandlesValidityProofContext,
+    ) -> Result<ZeroCiphertextProofData, TokenError> {
+        let source_transfer_amount_lo: ElGamalCiphertext = ciphertext_validity_proof_context
+            .grouped_ciphertext_lo
+            .try_extract_ciphertext(0)
+            .and_then(|ciphertext| ciphertext.try_into())
+            .map_err(|_| TokenError::MalformedCiphertext)?;
+        let source_transfer_amount_hi: ElGamalCiphertext = ciphertext_validity_proof_context
+            .grouped_ciphertext_hi
+            .try_extract_ciphertext(0)
+            .and_then(|ciphertext| ciphertext.try_into())
+            .map_err(|_| TokenError::MalformedCiphertext)?;
+        let source_transfer_amount = try_combine_lo_hi_ciphertexts(
+            &source_transfer_amount_lo,
+            &source_transfer_amount_hi,
+            TRANSFER_AMOUNT_LO_BITS,
+        )
+        .ok_or(TokenError::MalformedCiphertext)?;
+        let remaining_amount = source_transfer_amount - ElGamal::encode(transfer_amount);
+
+        ZeroCiphertextProofData::new(source_elgamal_keypair, &remaining_amount)
+            .map_err(|_| TokenError::ProofGeneration)
+    }
+
     /// Update the decryptable available balance.
     pub fn new_decryptable_available_balance(
         &self,
@@ -268,6 +268,13 @@ pub enum ConfidentialTransferInstruction {
     /// pre-verified into a context state account, in which case, only their
     /// context state account addresses need to be provided.
     ///
+    /// If the source or destination account is in reveal mode, the
+    /// instruction data must be followed by a `RevealedTransferAmountData`
+    /// with the plaintext transfer amount. It must be accompanied by a
+    /// `VerifyZeroCiphertext` instruction, or the address of its context state
+    /// account, certifying that the transfer amount ciphertext under the source
+    /// ElGamal public key minus the revealed amount encrypts 0.
+    ///
     /// Fails if the associated mint is extended as `NonTransferable`.
     ///
     ///   * Single owner/delegate
@@ -283,7 +290,10 @@ pub enum ConfidentialTransferInstruction {
     ///      state account.
     ///   7. `[]` (Optional) Range proof record account or context state
     ///      account.
-    ///   8. `[signer]` The single source account owner.
+    ///   8. `[]` (Optional) Instructions sysvar, followed by the record
+    ///      account if the proof is to be read from one, or context state
+    ///      account of the revealed transfer amount proof.
+    ///   9. `[signer]` The single source account owner.
     ///
     ///   * Multisignature owner/delegate
     ///   1. `[writable]` The source SPL Token account.
@@ -298,12 +308,16 @@ pub enum ConfidentialTransferInstruction {
     ///      state account.
     ///   7. `[]` (Optional) Range proof record account or context state
     ///      account.
-    ///   8. `[]` The multisig  source account owner.
-    ///   9. .. `[signer]` Required M signer accounts for the SPL Token Multisig
-    ///      account.
+    ///   8. `[]` (Optional) Instructions sysvar, followed by the record
+    ///      account if the proof is to be read from one, or context state
+    ///      account of the revealed transfer amount proof.
+    ///   9. `[]` The multisig  source account owner.
+    ///   10. .. `[signer]` Required M signer accounts for the SPL Token
+    ///       Multisig account.
     ///
     /// Data expected by this instruction:
-    ///   `TransferInstructionData`
+    ///   `TransferInstructionData`, optionally followed by
+    ///   `RevealedTransferAmountData`
     Transfer,
 
     /// Applies the pending balance to the available balance, based on the
@@ -438,7 +452,9 @@ pub enum ConfidentialTransferInstruction {
     ///
     /// The same restrictions for the `Transfer` applies to
     /// `TransferWithFee`. Namely, the instruction fails if the
-    /// associated mint is extended as `NonTransferable`.
+    /// associated mint is extended as `NonTransferable`, and the transfer
+    /// amount must be revealed with a `RevealedTransferAmountData` if the
+    /// source or destination account is in reveal mode.
     ///
     ///   * Transfer without fee
     ///   1. `[writable]` The source SPL Token account.
@@ -457,7 +473,10 @@ pub enum ConfidentialTransferInstruction {
     ///      context state account.
     ///   9. `[]` (Optional) Range proof record account or context state
     ///      account.
-    ///   10. `[signer]` The source account owner.
+    ///   10. `[]` (Optional) Instructions sysvar, followed by the record
+    ///       account if the proof is to be read from one, or context state
+    ///       account of the revealed transfer amount proof.
+    ///   11. `[signer]` The source account owner.
     ///
     ///   * Transfer with fee
     ///   1. `[writable]` The source SPL Token account.
@@ -476,12 +495,16 @@ pub enum ConfidentialTransferInstruction {
     ///      context state account.
     ///   9. `[]` (Optional) Range proof record account or context state
     ///      account.
-    ///   10. `[]` The multisig  source account owner.
-    ///   11. .. `[signer]` Required M signer accounts for the SPL Token
+    ///   10. `[]` (Optional) Instructions sysvar, followed by the record
+    ///       account if the proof is to be read from one, or context state
+    ///       account of the revealed transfer amount proof.
+    ///   11. `[]` The multisig  source account owner.
+    ///   12. .. `[signer]` Required M signer accounts for the SPL Token
     ///       Multisig
     ///
     /// Data expected by this instruction:
-    ///   `TransferWithFeeInstructionData`
+    ///   `TransferWithFeeInstructionData`, optionally followed by
+    ///   `RevealedTransferAmountData`
     TransferWithFee,
 
     /// Configures confidential transfers for a token account.
@@ -514,6 +537,122 @@ pub enum ConfidentialTransferInstruction {
     /// Data expected by this instruction:
     ///   None
     ConfigureAccountWithRegistry,
+
+    /// Discloses the available balance of a confidential token account.
+    ///
+    /// The account owner proves that the available balance ciphertext holds a
+    /// public amount and the program records the amount along with the
+    /// current slot in the balance disclosure receipt account of the token
+    /// account, derived with `get_balance_disclosure_address`. The receipt is
+    /// created on the first disclosure and overwritten on the next ones.
+    ///
+    /// The proof certifies that the available balance ciphertext minus the
+    /// disclosed amount encrypts 0. In order for this instruction to be
+    /// successfully processed, it must be accompanied by the
+    /// `VerifyZeroCiphertext` instruction of the `zk_elgamal_proof` program in
+    /// the same transaction or the address of a context state account for the
+    /// proof must be provided.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner/delegate
+    ///   0. `[]` The SPL Token account.
+    ///   1. `[writable]` The balance disclosure receipt account.
+    ///   2. `[signer, writable]` The payer funding the receipt account.
+    ///   3. `[]` System program.
+    ///   4. `[]` Instructions sysvar if `VerifyZeroCiphertext` is included in
+    ///      the same transaction or context state account if
+    ///      `VerifyZeroCiphertext` is pre-verified into a context state
+    ///      account.
+    ///   5. `[]` (Optional) Record account if the accompanying proof is to be
+    ///      read from a record account.
+    ///   6. `[signer]` The single account owner.
+    ///
+    ///   * Multisignature owner/delegate
+    ///   0. `[]` The SPL Token account.
+    ///   1. `[writable]` The balance disclosure receipt account.
+    ///   2. `[signer, writable]` The payer funding the receipt account.
+    ///   3. `[]` System program.
+    ///   4. `[]` Instructions sysvar if `VerifyZeroCiphertext` is included in
+    ///      the same transaction or context state account if
+    ///      `VerifyZeroCiphertext` is pre-verified into a context state
+    ///      account.
+    ///   5. `[]` (Optional) Record account if the accompanying proof is to be
+    ///      read from a record account.
+    ///   6. `[]` The multisig account owner.
+    ///   7. .. `[signer]` Required M signer accounts for the SPL Token Multisig
+    ///      account.
+    ///
+    /// Data expected by this instruction:
+    ///   `DiscloseBalanceInstructionData`
+    DiscloseBalance,
+
+    /// Enables reveal mode on a confidential token account.
+    ///
+    /// In reveal mode, the plaintext amounts encrypted by the pending and
+    /// available balances are tracked in the `ConfidentialTransferRevealMode`
+    /// extension. Deposits, withdrawals and applied pending balances update
+    /// the tracked amounts, while confidential debits require the available
+    /// balance to be revealed first with `RevealAvailableBalance`. Reveal mode
+    /// can't be disabled once enabled.
+    ///
+    /// The token account must have been reallocated to fit the
+    /// `ConfidentialTransferRevealMode` extension. The available balance is
+    /// unrevealed when reveal mode is enabled.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner/delegate
+    ///   0. `[writable]` The SPL Token account.
+    ///   1. `[signer]` The single account owner.
+    ///
+    ///   * Multisignature owner/delegate
+    ///   0. `[writable]` The SPL Token account.
+    ///   1. `[]` The multisig account owner.
+    ///   2. .. `[signer]` Required M signer accounts for the SPL Token Multisig
+    ///      account.
+    ///
+    /// Data expected by this instruction:
+    ///   None
+    EnableRevealMode,
+
+    /// Reveals the available balance of a confidential token account in
+    /// reveal mode.
+    ///
+    /// The proof certifies that the available balance ciphertext minus the
+    /// revealed amount encrypts 0. In order for this instruction to be
+    /// successfully processed, it must be accompanied by the
+    /// `VerifyZeroCiphertext` instruction of the `zk_elgamal_proof` program in
+    /// the same transaction or the address of a context state account for the
+    /// proof must be provided.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner/delegate
+    ///   0. `[writable]` The SPL Token account.
+    ///   1. `[]` Instructions sysvar if `VerifyZeroCiphertext` is included in
+    ///      the same transaction or context state account if
+    ///      `VerifyZeroCiphertext` is pre-verified into a context state
+    ///      account.
+    ///   2. `[]` (Optional) Record account if the accompanying proof is to be
+    ///      read from a record account.
+    ///   3. `[signer]` The single account owner.
+    ///
+    ///   * Multisignature owner/delegate
+    ///   0. `[writable]` The SPL Token account.
+    ///   1. `[]` Instructions sysvar if `VerifyZeroCiphertext` is included in
+    ///      the same transaction or context state account if
+    ///      `VerifyZeroCiphertext` is pre-verified into a context state
+    ///      account.
+    ///   2. `[]` (Optional) Record account if the accompanying proof is to be
+    ///      read from a record account.
+    ///   3. `[]` The multisig account owner.
+    ///   4. .. `[signer]` Required M signer accounts for the SPL Token Multisig
+    ///      account.
+    ///
+    /// Data expected by this instruction:
+    ///   `RevealAvailableBalanceInstructionData`
+    RevealAvailableBalance,
 }
 
 /// Data expected by `ConfidentialTransferInstruction::InitializeMint`
@@ -576,6 +715,35 @@ pub struct EmptyAccountInstructionData {
     pub proof_instruction_offset: i8,
 }
 
+/// Data expected by `ConfidentialTransferInstruction::DiscloseBalance`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct DiscloseBalanceInstructionData {
+    /// The disclosed available balance
+    pub amount: PodU64,
+    /// Relative location of the `ProofInstruction::VerifyZeroCiphertext`
+    /// instruction to the `DiscloseBalance` instruction in the transaction. If
+    /// the offset is `0`, then use a context state account for the proof.
+    pub proof_instruction_offset: i8,
+}
+
+/// Data expected by `ConfidentialTransferInstruction::RevealAvailableBalance`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct RevealAvailableBalanceInstructionData {
+    /// The revealed available balance
+    pub amount: PodU64,
+    /// Relative location of the `ProofInstruction::VerifyZeroCiphertext`
+    /// instruction to the `RevealAvailableBalance` instruction in the
+    /// transaction. If the offset is `0`, then use a context state account for
+    /// the proof.
+    pub proof_instruction_offset: i8,
+}
+
 /// Data expected by `ConfidentialTransferInstruction::Deposit`
 #[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
@@ -643,6 +811,22 @@ pub struct TransferInstructionData {
     pub range_proof_instruction_offset: i8,
 }
 
+/// Data that may follow the `TransferInstructionData` or
+/// `TransferWithFeeInstructionData` to reveal the transfer amount, as required
+/// for transfers from or to an account in reveal mode
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct RevealedTransferAmountData {
+    /// The plaintext transfer amount
+    pub amount: PodU64,
+    /// Relative location of the `ProofInstruction::VerifyZeroCiphertext`
+    /// instruction to the transfer instruction in the transaction. If the
+    /// offset is `0`, then use a context state account for the proof.
+    pub proof_instruction_offset: i8,
+}
+
 /// Data expected by `ConfidentialTransferInstruction::ApplyPendingBalance`
 #[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
@@ -1354,6 +1538,153 @@ pub fn transfer(
     Ok(instructions)
 }
 
+/// Reveals the transfer amount of an inner `Transfer` or `TransferWithFee`
+/// instruction, adding the accounts of its proof before the authority and
+/// appending a `RevealedTransferAmountData` to the instruction data
+fn add_revealed_transfer_amount(
+    instruction: &mut Instruction,
+    multisig_signers: &[&Pubkey],
+    revealed_transfer_amount: (u64, ProofLocation<ZeroCiphertextProofData>),
+) {
+    let (amount, proof_data_location) = revealed_transfer_amount;
+    let mut proof_accounts = vec![];
+    let proof_instruction_offset = match proof_data_location {
+        ProofLocation::InstructionOffset(proof_instruction_offset, proof_data) => {
+            proof_accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+            if let ProofData::RecordAccount(record_address, _) = proof_data {
+                proof_accounts.push(AccountMeta::new_readonly(*record_address, false));
+            }
+            proof_instruction_offset.into()
+        }
+        ProofLocation::ContextStateAccount(context_state_account) => {
+            proof_accounts.push(AccountMeta::new_readonly(*context_state_account, false));
+            0
+        }
+    };
+    let authority_index = instruction
+        .accounts
+        .len()
+        .saturating_sub(multisig_signers.len())
+        .saturating_sub(1);
+    instruction
+        .accounts
+        .splice(authority_index..authority_index, proof_accounts);
+    instruction
+        .data
+        .extend_from_slice(bytemuck::bytes_of(&RevealedTransferAmountData {
+            amount: amount.into(),
+            proof_instruction_offset,
+        }));
+}
+
+/// Adds the `VerifyZeroCiphertext` instruction of a revealed transfer amount
+/// after the proof instructions of a `Transfer` or `TransferWithFee`
+fn push_revealed_transfer_amount_proof_instruction(
+    instructions: &mut Vec<Instruction>,
+    revealed_transfer_amount: (u64, ProofLocation<ZeroCiphertextProofData>),
+) -> Result<(), ProgramError> {
+    if let (_, ProofLocation::InstructionOffset(proof_instruction_offset, proof_data)) =
+        revealed_transfer_amount
+    {
+        let proof_instruction_offset: i8 = proof_instruction_offset.into();
+        if proof_instruction_offset as usize != instructions.len() {
+            return Err(TokenError::InvalidProofInstructionOffset.into());
+        }
+        match proof_data {
+            ProofData::InstructionData(data) => instructions
+                .push(ProofInstruction::VerifyZeroCiphertext.encode_verify_proof(None, data)),
+            ProofData::RecordAccount(address, offset) => instructions.push(
+                ProofInstruction::VerifyZeroCiphertext
+                    .encode_verify_proof_from_account(None, address, offset),
+            ),
+        };
+    }
+    Ok(())
+}
+
+/// Create an inner `Transfer` instruction revealing the transfer amount, as
+/// required if the source or destination account is in reveal mode
+///
+/// This instruction is suitable for use with a cross-program `invoke`
+#[allow(clippy::too_many_arguments)]
+pub fn inner_transfer_with_revealed_amount(
+    token_program_id: &Pubkey,
+    source_token_account: &Pubkey,
+    mint: &Pubkey,
+    destination_token_account: &Pubkey,
+    new_source_decryptable_available_balance: &DecryptableBalance,
+    transfer_amount_auditor_ciphertext_lo: &PodElGamalCiphertext,
+    transfer_amount_auditor_ciphertext_hi: &PodElGamalCiphertext,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    equality_proof_data_location: ProofLocation<CiphertextCommitmentEqualityProofData>,
+    ciphertext_validity_proof_data_location: ProofLocation<
+        BatchedGroupedCiphertext3HandlesValidityProofData,
+    >,
+    range_proof_data_location: ProofLocation<BatchedRangeProofU128Data>,
+    revealed_transfer_amount: (u64, ProofLocation<ZeroCiphertextProofData>),
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = inner_transfer(
+        token_program_id,
+        source_token_account,
+        mint,
+        destination_token_account,
+        new_source_decryptable_available_balance,
+        transfer_amount_auditor_ciphertext_lo,
+        transfer_amount_auditor_ciphertext_hi,
+        authority,
+        multisig_signers,
+        equality_proof_data_location,
+        ciphertext_validity_proof_data_location,
+        range_proof_data_location,
+    )?;
+    add_revealed_transfer_amount(&mut instruction, multisig_signers, revealed_transfer_amount);
+    Ok(instruction)
+}
+
+/// Create a `Transfer` instruction revealing the transfer amount, as required
+/// if the source or destination account is in reveal mode
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_with_revealed_amount(
+    token_program_id: &Pubkey,
+    source_token_account: &Pubkey,
+    mint: &Pubkey,
+    destination_token_account: &Pubkey,
+    new_source_decryptable_available_balance: &DecryptableBalance,
+    transfer_amount_auditor_ciphertext_lo: &PodElGamalCiphertext,
+    transfer_amount_auditor_ciphertext_hi: &PodElGamalCiphertext,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    equality_proof_data_location: ProofLocation<CiphertextCommitmentEqualityProofData>,
+    ciphertext_validity_proof_data_location: ProofLocation<
+        BatchedGroupedCiphertext3HandlesValidityProofData,
+    >,
+    range_proof_data_location: ProofLocation<BatchedRangeProofU128Data>,
+    revealed_transfer_amount: (u64, ProofLocation<ZeroCiphertextProofData>),
+) -> Result<Vec<Instruction>, ProgramError> {
+    let mut instructions = transfer(
+        token_program_id,
+        source_token_account,
+        mint,
+        destination_token_account,
+        new_source_decryptable_available_balance,
+        transfer_amount_auditor_ciphertext_lo,
+        transfer_amount_auditor_ciphertext_hi,
+        authority,
+        multisig_signers,
+        equality_proof_data_location,
+        ciphertext_validity_proof_data_location,
+        range_proof_data_location,
+    )?;
+    add_revealed_transfer_amount(
+        &mut instructions[0],
+        multisig_signers,
+        revealed_transfer_amount,
+    );
+    push_revealed_transfer_amount_proof_instruction(&mut instructions, revealed_transfer_amount)?;
+    Ok(instructions)
+}
+
 /// Create a inner `ApplyPendingBalance` instruction
 ///
 /// This instruction is suitable for use with a cross-program `invoke`
@@ -1772,6 +2103,101 @@ pub fn transfer_with_fee(
     Ok(instructions)
 }
 
+/// Create an inner `TransferWithFee` instruction revealing the transfer
+/// amount, as required if the source or destination account is in reveal mode
+///
+/// This instruction is suitable for use with a cross-program `invoke`
+#[allow(clippy::too_many_arguments)]
+pub fn inner_transfer_with_fee_with_revealed_amount(
+    token_program_id: &Pubkey,
+    source_token_account: &Pubkey,
+    mint: &Pubkey,
+    destination_token_account: &Pubkey,
+    new_source_decryptable_available_balance: &DecryptableBalance,
+    transfer_amount_auditor_ciphertext_lo: &PodElGamalCiphertext,
+    transfer_amount_auditor_ciphertext_hi: &PodElGamalCiphertext,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    equality_proof_data_location: ProofLocation<CiphertextCommitmentEqualityProofData>,
+    transfer_amount_ciphertext_validity_proof_data_location: ProofLocation<
+        BatchedGroupedCiphertext3HandlesValidityProofData,
+    >,
+    fee_sigma_proof_data_location: ProofLocation<PercentageWithCapProofData>,
+    fee_ciphertext_validity_proof_data_location: ProofLocation<
+        BatchedGroupedCiphertext2HandlesValidityProofData,
+    >,
+    range_proof_data_location: ProofLocation<BatchedRangeProofU256Data>,
+    revealed_transfer_amount: (u64, ProofLocation<ZeroCiphertextProofData>),
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = inner_transfer_with_fee(
+        token_program_id,
+        source_token_account,
+        mint,
+        destination_token_account,
+        new_source_decryptable_available_balance,
+        transfer_amount_auditor_ciphertext_lo,
+        transfer_amount_auditor_ciphertext_hi,
+        authority,
+        multisig_signers,
+        equality_proof_data_location,
+        transfer_amount_ciphertext_validity_proof_data_location,
+        fee_sigma_proof_data_location,
+        fee_ciphertext_validity_proof_data_location,
+        range_proof_data_location,
+    )?;
+    add_revealed_transfer_amount(&mut instruction, multisig_signers, revealed_transfer_amount);
+    Ok(instruction)
+}
+
+/// Create a `TransferWithFee` instruction revealing the transfer amount, as
+/// required if the source or destination account is in reveal mode
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_with_fee_with_revealed_amount(
+    token_program_id: &Pubkey,
+    source_token_account: &Pubkey,
+    mint: &Pubkey,
+    destination_token_account: &Pubkey,
+    new_source_decryptable_available_balance: &DecryptableBalance,
+    transfer_amount_auditor_ciphertext_lo: &PodElGamalCiphertext,
+    transfer_amount_auditor_ciphertext_hi: &PodElGamalCiphertext,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    equality_proof_data_location: ProofLocation<CiphertextCommitmentEqualityProofData>,
+    transfer_amount_ciphertext_validity_proof_data_location: ProofLocation<
+        BatchedGroupedCiphertext3HandlesValidityProofData,
+    >,
+    fee_sigma_proof_data_location: ProofLocation<PercentageWithCapProofData>,
+    fee_ciphertext_validity_proof_data_location: ProofLocation<
+        BatchedGroupedCiphertext2HandlesValidityProofData,
+    >,
+    range_proof_data_location: ProofLocation<BatchedRangeProofU256Data>,
+    revealed_transfer_amount: (u64, ProofLocation<ZeroCiphertextProofData>),
+) -> Result<Vec<Instruction>, ProgramError> {
+    let mut instructions = transfer_with_fee(
+        token_program_id,
+        source_token_account,
+        mint,
+        destination_token_account,
+        new_source_decryptable_available_balance,
+        transfer_amount_auditor_ciphertext_lo,
+        transfer_amount_auditor_ciphertext_hi,
+        authority,
+        multisig_signers,
+        equality_proof_data_location,
+        transfer_amount_ciphertext_validity_proof_data_location,
+        fee_sigma_proof_data_location,
+        fee_ciphertext_validity_proof_data_location,
+        range_proof_data_location,
+    )?;
+    add_revealed_transfer_amount(
+        &mut instructions[0],
+        multisig_signers,
+        revealed_transfer_amount,
+    );
+    push_revealed_transfer_amount_proof_instruction(&mut instructions, revealed_transfer_amount)?;
+    Ok(instructions)
+}
+
 /// Create a `ConfigureAccountWithRegistry` instruction
 pub fn configure_account_with_registry(
     token_program_id: &Pubkey,
@@ -1799,3 +2225,214 @@ pub fn configure_account_with_registry(
         &(),
     ))
 }
+
+/// Create an inner `DiscloseBalance` instruction
+///
+/// This instruction is suitable for use with a cross-program `invoke`
+#[allow(clippy::too_many_arguments)]
+pub fn inner_disclose_balance(
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    payer: &Pubkey,
+    amount: u64,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_data_location: ProofLocation<ZeroCiphertextProofData>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*token_account, false),
+        AccountMeta::new(
+            get_balance_disclosure_address(token_account, token_program_id),
+            false,
+        ),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let proof_instruction_offset = match proof_data_location {
+        ProofLocation::InstructionOffset(proof_instruction_offset, proof_data) => {
+            accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+            if let ProofData::RecordAccount(record_address, _) = proof_data {
+                accounts.push(AccountMeta::new_readonly(*record_address, false));
+            }
+            proof_instruction_offset.into()
+        }
+        ProofLocation::ContextStateAccount(context_state_account) => {
+            accounts.push(AccountMeta::new_readonly(*context_state_account, false));
+            0
+        }
+    };
+
+    accounts.push(AccountMeta::new_readonly(
+        *authority,
+        multisig_signers.is_empty(),
+    ));
+
+    for multisig_signer in multisig_signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**multisig_signer, true));
+    }
+
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialTransferExtension,
+        ConfidentialTransferInstruction::DiscloseBalance,
+        &DiscloseBalanceInstructionData {
+            amount: amount.into(),
+            proof_instruction_offset,
+        },
+    ))
+}
+
+/// Create a `DiscloseBalance` instruction
+pub fn disclose_balance(
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    payer: &Pubkey,
+    amount: u64,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_data_location: ProofLocation<ZeroCiphertextProofData>,
+) -> Result<Vec<Instruction>, ProgramError> {
+    let mut instructions = vec![inner_disclose_balance(
+        token_program_id,
+        token_account,
+        payer,
+        amount,
+        authority,
+        multisig_signers,
+        proof_data_location,
+    )?];
+
+    if let ProofLocation::InstructionOffset(proof_instruction_offset, proof_data) =
+        proof_data_location
+    {
+        // This constructor appends the proof instruction right after the
+        // `DiscloseBalance` instruction. This means that the proof instruction
+        // offset must be always be 1. To use an arbitrary proof instruction
+        // offset, use the `inner_disclose_balance` constructor.
+        let proof_instruction_offset: i8 = proof_instruction_offset.into();
+        if proof_instruction_offset != 1 {
+            return Err(TokenError::InvalidProofInstructionOffset.into());
+        }
+        match proof_data {
+            ProofData::InstructionData(data) => instructions
+                .push(ProofInstruction::VerifyZeroCiphertext.encode_verify_proof(None, data)),
+            ProofData::RecordAccount(address, offset) => instructions.push(
+                ProofInstruction::VerifyZeroCiphertext
+                    .encode_verify_proof_from_account(None, address, offset),
+            ),
+        };
+    };
+
+    Ok(instructions)
+}
+
+/// Create an `EnableRevealMode` instruction
+pub fn enable_reveal_mode(
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+) -> Result<Instruction, ProgramError> {
+    enable_or_disable_balance_credits(
+        ConfidentialTransferInstruction::EnableRevealMode,
+        token_program_id,
+        token_account,
+        authority,
+        multisig_signers,
+    )
+}
+
+/// Create an inner `RevealAvailableBalance` instruction
+///
+/// This instruction is suitable for use with a cross-program `invoke`
+pub fn inner_reveal_available_balance(
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_data_location: ProofLocation<ZeroCiphertextProofData>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![AccountMeta::new(*token_account, false)];
+
+    let proof_instruction_offset = match proof_data_location {
+        ProofLocation::InstructionOffset(proof_instruction_offset, proof_data) => {
+            accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+            if let ProofData::RecordAccount(record_address, _) = proof_data {
+                accounts.push(AccountMeta::new_readonly(*record_address, false));
+            }
+            proof_instruction_offset.into()
+        }
+        ProofLocation::ContextStateAccount(context_state_account) => {
+            accounts.push(AccountMeta::new_readonly(*context_state_account, false));
+            0
+        }
+    };
+
+    accounts.push(AccountMeta::new_readonly(
+        *authority,
+        multisig_signers.is_empty(),
+    ));
+
+    for multisig_signer in multisig_signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**multisig_signer, true));
+    }
+
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialTransferExtension,
+        ConfidentialTransferInstruction::RevealAvailableBalance,
+        &RevealAvailableBalanceInstructionData {
+            amount: amount.into(),
+            proof_instruction_offset,
+        },
+    ))
+}
+
+/// Create a `RevealAvailableBalance` instruction
+pub fn reveal_available_balance(
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_data_location: ProofLocation<ZeroCiphertextProofData>,
+) -> Result<Vec<Instruction>, ProgramError> {
+    let mut instructions = vec![inner_reveal_available_balance(
+        token_program_id,
+        token_account,
+        amount,
+        authority,
+        multisig_signers,
+        proof_data_location,
+    )?];
+
+    if let ProofLocation::InstructionOffset(proof_instruction_offset, proof_data) =
+        proof_data_location
+    {
+        // This constructor appends the proof instruction right after the
+        // `RevealAvailableBalance` instruction. This means that the proof
+        // instruction offset must be always be 1. To use an arbitrary proof
+        // instruction offset, use the `inner_reveal_available_balance`
+        // constructor.
+        let proof_instruction_offset: i8 = proof_instruction_offset.into();
+        if proof_instruction_offset != 1 {
+            return Err(TokenError::InvalidProofInstructionOffset.into());
+        }
+        match proof_data {
+            ProofData::InstructionData(data) => instructions
+                .push(ProofInstruction::VerifyZeroCiphertext.encode_verify_proof(None, data)),
+            ProofData::RecordAccount(address, offset) => instructions.push(
+                ProofInstruction::VerifyZeroCiphertext
+                    .encode_verify_proof_from_account(None, address, offset),
+            ),
+        };
+    };
+
+    Ok(instructions)
+}
@@ -4,12 +4,13 @@ use {
         extension::{Extension, ExtensionType},
     },
     bytemuck::{Pod, Zeroable},
-    solana_program::entrypoint::ProgramResult,
+    solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey},
     solana_zk_sdk::encryption::pod::{
         auth_encryption::PodAeCiphertext,
         elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
     },
     spl_pod::{
+        bytemuck::pod_from_bytes,
         optional_keys::{OptionalNonZeroElGamalPubkey, OptionalNonZeroPubkey},
         primitives::{PodBool, PodU64},
     },
@@ -26,6 +27,9 @@ pub const PENDING_BALANCE_LO_BIT_LENGTH: u32 = 16;
 /// The default maximum pending balance credit counter.
 pub const DEFAULT_MAXIMUM_PENDING_BALANCE_CREDIT_COUNTER: u64 = 65536;
 
+/// Seed for the balance disclosure receipt address of a token account
+pub const BALANCE_DISCLOSURE_SEED: &[u8] = b"balance-disclosure";
+
 /// Confidential Transfer Extension instructions
 pub mod instruction;
 
@@ -199,3 +203,162 @@ impl ConfidentialTransferAccount {
         Ok(())
     }
 }
+
+/// Reveal mode state of a confidential account
+///
+/// Once enabled by the account owner, the plaintext amounts encrypted by the
+/// pending and available balances are tracked in public running totals. Public
+/// amounts, such as deposits and withdrawals, update the totals directly, as do
+/// confidential transfers, which must reveal their amount with a
+/// `RevealedTransferAmountData` proven against the transfer ciphertext. A
+/// confidential mint or burn makes the corresponding total unrevealed, and the
+/// owner must prove the new available balance with `RevealAvailableBalance`
+/// before the account can be debited again.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ConfidentialTransferRevealMode {
+    /// The amount encrypted by the pending balance, if
+    /// `pending_balance_revealed` is `true`
+    pub revealed_pending_balance: PodU64,
+
+    /// The amount encrypted by the available balance, if
+    /// `available_balance_revealed` is `true`
+    pub revealed_available_balance: PodU64,
+
+    /// `false` once an unrevealed amount is credited to the pending balance,
+    /// until the pending balance is applied
+    pub pending_balance_revealed: PodBool,
+
+    /// `false` once an unrevealed amount is debited from or applied to the
+    /// available balance, until the available balance is revealed
+    pub available_balance_revealed: PodBool,
+}
+
+impl Extension for ConfidentialTransferRevealMode {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialTransferRevealMode;
+}
+
+impl ConfidentialTransferRevealMode {
+    /// Records a credit of a public or revealed amount to the pending balance.
+    pub fn credit_pending_balance(&mut self, amount: u64) -> ProgramResult {
+        if bool::from(self.pending_balance_revealed) {
+            self.revealed_pending_balance = u64::from(self.revealed_pending_balance)
+                .checked_add(amount)
+                .ok_or(TokenError::Overflow)?
+                .into();
+        }
+        Ok(())
+    }
+
+    /// Records a credit of an unrevealed amount to the pending balance.
+    pub fn credit_pending_balance_confidentially(&mut self) {
+        self.pending_balance_revealed = false.into();
+        self.revealed_pending_balance = 0.into();
+    }
+
+    /// Records a debit of a public or revealed amount from the available
+    /// balance.
+    pub fn debit_available_balance(&mut self, amount: u64) -> ProgramResult {
+        self.assert_available_balance_revealed()?;
+        self.revealed_available_balance = u64::from(self.revealed_available_balance)
+            .checked_sub(amount)
+            .ok_or(TokenError::InsufficientFunds)?
+            .into();
+        Ok(())
+    }
+
+    /// Records a debit of an unrevealed amount from the available balance.
+    pub fn debit_available_balance_confidentially(&mut self) -> ProgramResult {
+        self.assert_available_balance_revealed()?;
+        self.available_balance_revealed = false.into();
+        self.revealed_available_balance = 0.into();
+        Ok(())
+    }
+
+    /// Records that the pending balance was applied to the available balance.
+    pub fn apply_pending_balance(&mut self) -> ProgramResult {
+        if bool::from(self.pending_balance_revealed) && bool::from(self.available_balance_revealed)
+        {
+            self.revealed_available_balance = u64::from(self.revealed_available_balance)
+                .checked_add(self.revealed_pending_balance.into())
+                .ok_or(TokenError::Overflow)?
+                .into();
+        } else {
+            self.available_balance_revealed = false.into();
+            self.revealed_available_balance = 0.into();
+        }
+        // the pending balance is zero once applied
+        self.pending_balance_revealed = true.into();
+        self.revealed_pending_balance = 0.into();
+        Ok(())
+    }
+
+    /// Records the proven amount encrypted by the available balance.
+    pub fn reveal_available_balance(&mut self, amount: u64) {
+        self.available_balance_revealed = true.into();
+        self.revealed_available_balance = amount.into();
+    }
+
+    fn assert_available_balance_revealed(&self) -> ProgramResult {
+        if bool::from(self.available_balance_revealed) {
+            Ok(())
+        } else {
+            Err(TokenError::ConfidentialTransferBalanceNotRevealed.into())
+        }
+    }
+}
+
+/// Receipt of the last `DiscloseBalance` instruction of a token account,
+/// stored at `get_balance_disclosure_address`.
+///
+/// The receipt records that the available balance of the token account was
+/// proven to hold `amount` at `slot`. It is only ever written by the token
+/// program, so it can be trusted as an attestation of the balance.
+///
+/// The receipt is a PDA of the token program derived from
+/// `[BALANCE_DISCLOSURE_SEED, token_account]`. Unlike mints and token
+/// accounts, it has no account type nor extensions: its data is exactly
+/// `BalanceDisclosure::LEN` bytes, laid out as
+///
+/// | Offset | Size | Field           | Encoding          |
+/// |--------|------|-----------------|-------------------|
+/// | 0      | 32   | `token_account` | pubkey            |
+/// | 32     | 8    | `slot`          | little-endian u64 |
+/// | 40     | 8    | `amount`        | little-endian u64 |
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct BalanceDisclosure {
+    /// The token account whose available balance was disclosed
+    pub token_account: Pubkey,
+    /// The slot at which the balance was disclosed
+    pub slot: PodU64,
+    /// The disclosed available balance
+    pub amount: PodU64,
+}
+
+impl BalanceDisclosure {
+    /// Size of the receipt account
+    pub const LEN: usize = std::mem::size_of::<BalanceDisclosure>();
+
+    /// Deserializes the data of a receipt account
+    pub fn unpack(input: &[u8]) -> Result<&Self, ProgramError> {
+        pod_from_bytes::<Self>(input)
+    }
+}
+
+/// Derives the balance disclosure receipt address of a token account, along
+/// with its bump seed
+pub fn get_balance_disclosure_address_and_bump_seed(
+    token_account: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BALANCE_DISCLOSURE_SEED, token_account.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives the balance disclosure receipt address of a token account
+pub fn get_balance_disclosure_address(token_account: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    get_balance_disclosure_address_and_bump_seed(token_account, program_id).0
+}
@@ -2,8 +2,10 @@
 #[cfg(feature = "zk-ops")]
 use {
     crate::extension::confidential_mint_burn::ConfidentialMintBurn,
-    crate::extension::non_transferable::NonTransferableAccount,
+    crate::extension::non_transferable::NonTransferableAccount, bytemuck::Pod,
+    spl_pod::bytemuck::pod_get_packed_len,
     spl_token_confidential_transfer_ciphertext_arithmetic as ciphertext_arithmetic,
+    std::slice::Iter,
 };
 use {
     crate::{
@@ -32,14 +34,14 @@ use {
         clock::Clock,
         entrypoint::ProgramResult,
         msg,
-        program::invoke,
+        program::{invoke, invoke_signed},
         program_error::ProgramError,
         pubkey::Pubkey,
         system_instruction,
         sysvar::{rent::Rent, Sysvar},
     },
     spl_elgamal_registry::state::ElGamalRegistry,
-    spl_pod::bytemuck::pod_from_bytes,
+    spl_pod::bytemuck::{pod_from_bytes, pod_from_bytes_mut},
     spl_token_confidential_transfer_proof_extraction::{
         instruction::verify_and_extract_context, transfer::TransferProofContext,
         transfer_with_fee::TransferWithFeeProofContext,
@@ -472,6 +474,10 @@ fn process_deposit(
 
     confidential_transfer_account.increment_pending_balance_credit_counter()?;
 
+    if let Ok(reveal_mode) = token_account.get_extension_mut::<ConfidentialTransferRevealMode>() {
+        reveal_mode.credit_pending_balance(amount)?;
+    }
+
     Ok(())
 }
 
@@ -594,6 +600,10 @@ fn process_withdraw(
         .ok_or(TokenError::Overflow)?
         .into();
 
+    if let Ok(reveal_mode) = token_account.get_extension_mut::<ConfidentialTransferRevealMode>() {
+        reveal_mode.debit_available_balance(amount)?;
+    }
+
     Ok(())
 }
 
@@ -611,6 +621,7 @@ fn process_transfer(
     fee_sigma_proof_instruction_offset: Option<i64>,
     fee_ciphertext_validity_proof_instruction_offset: Option<i64>,
     range_proof_instruction_offset: i64,
+    revealed_transfer_amount: Option<&RevealedTransferAmountData>,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let source_account_info = next_account_info(account_info_iter)?;
@@ -639,7 +650,7 @@ fn process_transfer(
     //   - If the mint is extended for fees and the instruction is not a
     //     self-transfer, then
     //   transfer fee is required.
-    let authority_info = if mint.get_extension::<TransferFeeConfig>().is_err() {
+    let (authority_info, transfer_amount) = if mint.get_extension::<TransferFeeConfig>().is_err() {
         // Transfer fee is not required. Decode the zero-knowledge proof as
         // `TransferContext`.
         //
@@ -653,6 +664,20 @@ fn process_transfer(
             range_proof_instruction_offset,
         )?;
 
+        let transfer_amount = verify_revealed_transfer_amount(
+            account_info_iter,
+            revealed_transfer_amount,
+            &proof_context.transfer_pubkeys.source,
+            &proof_context
+                .ciphertext_lo
+                .try_extract_ciphertext(0)
+                .map_err(TokenError::from)?,
+            &proof_context
+                .ciphertext_hi
+                .try_extract_ciphertext(0)
+                .map_err(TokenError::from)?,
+        )?;
+
         let authority_info = next_account_info(account_info_iter)?;
 
         // Check that the auditor encryption public key associated wth the confidential
@@ -688,11 +713,17 @@ fn process_transfer(
             account_info_iter.as_slice(),
             &proof_context,
             new_source_decryptable_available_balance,
+            transfer_amount,
         )?;
 
-        process_destination_for_transfer(destination_account_info, mint_info, &proof_context)?;
+        process_destination_for_transfer(
+            destination_account_info,
+            mint_info,
+            &proof_context,
+            transfer_amount,
+        )?;
 
-        authority_info
+        (authority_info, transfer_amount)
     } else {
         // Transfer fee is required.
         let transfer_fee_config = mint.get_extension::<TransferFeeConfig>()?;
@@ -720,6 +751,20 @@ fn process_transfer(
             fee_parameters,
         )?;
 
+        let transfer_amount = verify_revealed_transfer_amount(
+            account_info_iter,
+            revealed_transfer_amount,
+            &proof_context.transfer_with_fee_pubkeys.source,
+            &proof_context
+                .ciphertext_lo
+                .try_extract_ciphertext(0)
+                .map_err(TokenError::from)?,
+            &proof_context
+                .ciphertext_hi
+                .try_extract_ciphertext(0)
+                .map_err(TokenError::from)?,
+        )?;
+
         let authority_info = next_account_info(account_info_iter)?;
 
         // Check that the encryption public keys associated with the mint confidential
@@ -769,17 +814,31 @@ fn process_transfer(
             account_info_iter.as_slice(),
             &proof_context,
             new_source_decryptable_available_balance,
+            transfer_amount,
         )?;
 
         let is_self_transfer = source_account_info.key == destination_account_info.key;
+        let credited_amount = if is_self_transfer {
+            transfer_amount
+        } else {
+            transfer_amount
+                .map(|amount| {
+                    fee_parameters
+                        .calculate_fee(amount)
+                        .and_then(|fee| amount.checked_sub(fee))
+                        .ok_or(TokenError::Overflow)
+                })
+                .transpose()?
+        };
         process_destination_for_transfer_with_fee(
             destination_account_info,
             mint_info,
             &proof_context,
             is_self_transfer,
+            credited_amount,
         )?;
 
-        authority_info
+        (authority_info, transfer_amount)
     };
 
     if let Some(program_id) = transfer_hook::get_program_id(&mint) {
@@ -800,16 +859,16 @@ fn process_transfer(
         // can't doubly-borrow the mint data either
         drop(mint_data);
 
-        // Since the amount is unknown during a confidential transfer, pass in
-        // u64::MAX as a convention.
-        spl_transfer_hook_interface::onchain::invoke_execute(
+        // Unless the amount was revealed, it is unknown during a confidential
+        // transfer, so pass in u64::MAX as a convention.
+        transfer_hook::invoke_execute(
             &program_id,
             source_account_info.clone(),
             mint_info.clone(),
             destination_account_info.clone(),
             authority_info.clone(),
             account_info_iter.as_slice(),
-            u64::MAX,
+            transfer_amount.unwrap_or(u64::MAX),
         )?;
 
         // unset transferring flag
@@ -820,7 +879,72 @@ fn process_transfer(
     Ok(())
 }
 
+/// Verifies the optional proof that the plaintext transfer amount is the
+/// amount encrypted under the source ElGamal public key, returning the amount
+#[cfg(feature = "zk-ops")]
+fn verify_revealed_transfer_amount(
+    account_info_iter: &mut Iter<AccountInfo>,
+    revealed_transfer_amount: Option<&RevealedTransferAmountData>,
+    source_elgamal_pubkey: &PodElGamalPubkey,
+    source_transfer_amount_lo: &PodElGamalCiphertext,
+    source_transfer_amount_hi: &PodElGamalCiphertext,
+) -> Result<Option<u64>, ProgramError> {
+    let Some(revealed_transfer_amount) = revealed_transfer_amount else {
+        return Ok(None);
+    };
+
+    // zero-knowledge proof certifies that the source transfer amount ciphertext
+    // minus the revealed amount encrypts 0
+    let proof_context =
+        verify_and_extract_context::<ZeroCiphertextProofData, ZeroCiphertextProofContext>(
+            account_info_iter,
+            revealed_transfer_amount.proof_instruction_offset as i64,
+            None,
+        )?;
+
+    if proof_context.pubkey != *source_elgamal_pubkey {
+        return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+    }
+
+    let source_transfer_amount = ciphertext_arithmetic::add_with_lo_hi(
+        &PodElGamalCiphertext::zeroed(),
+        source_transfer_amount_lo,
+        source_transfer_amount_hi,
+    )
+    .ok_or(TokenError::CiphertextArithmeticFailed)?;
+
+    let amount = u64::from(revealed_transfer_amount.amount);
+    let remaining_amount = ciphertext_arithmetic::subtract_from(&source_transfer_amount, amount)
+        .ok_or(TokenError::CiphertextArithmeticFailed)?;
+    if remaining_amount != proof_context.ciphertext {
+        return Err(TokenError::ConfidentialTransferRevealedAmountMismatch.into());
+    }
+
+    Ok(Some(amount))
+}
+
+/// Decodes the data of a [`Transfer`] or [`TransferWithFee`] instruction,
+/// which may be followed by a [`RevealedTransferAmountData`]
+#[cfg(feature = "zk-ops")]
+fn decode_transfer_instruction_data<T: Pod>(
+    input: &[u8],
+) -> Result<(&T, Option<&RevealedTransferAmountData>), ProgramError> {
+    let data_len = pod_get_packed_len::<T>().saturating_add(1);
+    if input.len() > data_len {
+        let (data, revealed_transfer_amount) = input.split_at(data_len);
+        Ok((
+            decode_instruction_data::<T>(data)?,
+            Some(pod_from_bytes::<RevealedTransferAmountData>(
+                revealed_transfer_amount,
+            )?),
+        ))
+    } else {
+        Ok((decode_instruction_data::<T>(input)?, None))
+    }
+}
+
 /// Processes the changes for the sending party of a confidential transfer
+#[allow(clippy::too_many_arguments)]
 #[cfg(feature = "zk-ops")]
 fn process_source_for_transfer(
     program_id: &Pubkey,
@@ -830,6 +954,7 @@ fn process_source_for_transfer(
     signers: &[AccountInfo],
     proof_context: &TransferProofContext,
     new_source_decryptable_available_balance: DecryptableBalance,
+    transfer_amount: Option<u64>,
 ) -> ProgramResult {
     check_program_account(source_account_info.owner)?;
     let authority_info_data_len = authority_info.data_len();
@@ -894,6 +1019,12 @@ fn process_source_for_transfer(
     confidential_transfer_account.decryptable_available_balance =
         new_source_decryptable_available_balance;
 
+    if let Ok(reveal_mode) = token_account.get_extension_mut::<ConfidentialTransferRevealMode>() {
+        let transfer_amount =
+            transfer_amount.ok_or(TokenError::ConfidentialTransferAmountNotRevealed)?;
+        reveal_mode.debit_available_balance(transfer_amount)?;
+    }
+
     Ok(())
 }
 
@@ -902,6 +1033,7 @@ fn process_destination_for_transfer(
     destination_account_info: &AccountInfo,
     mint_info: &AccountInfo,
     proof_context: &TransferProofContext,
+    transfer_amount: Option<u64>,
 ) -> ProgramResult {
     check_program_account(destination_account_info.owner)?;
     let destination_token_account_data = &mut destination_account_info.data.borrow_mut();
@@ -953,6 +1085,14 @@ fn process_destination_for_transfer(
 
     destination_confidential_transfer_account.increment_pending_balance_credit_counter()?;
 
+    if let Ok(reveal_mode) =
+        destination_token_account.get_extension_mut::<ConfidentialTransferRevealMode>()
+    {
+        let transfer_amount =
+            transfer_amount.ok_or(TokenError::ConfidentialTransferAmountNotRevealed)?;
+        reveal_mode.credit_pending_balance(transfer_amount)?;
+    }
+
     Ok(())
 }
 
@@ -966,6 +1106,7 @@ fn process_source_for_transfer_with_fee(
     signers: &[AccountInfo],
     proof_context: &TransferWithFeeProofContext,
     new_source_decryptable_available_balance: DecryptableBalance,
+    transfer_amount: Option<u64>,
 ) -> ProgramResult {
     check_program_account(source_account_info.owner)?;
     let authority_info_data_len = authority_info.data_len();
@@ -1032,6 +1173,12 @@ fn process_source_for_transfer_with_fee(
     confidential_transfer_account.decryptable_available_balance =
         new_source_decryptable_available_balance;
 
+    if let Ok(reveal_mode) = token_account.get_extension_mut::<ConfidentialTransferRevealMode>() {
+        let transfer_amount =
+            transfer_amount.ok_or(TokenError::ConfidentialTransferAmountNotRevealed)?;
+        reveal_mode.debit_available_balance(transfer_amount)?;
+    }
+
     Ok(())
 }
 
@@ -1041,6 +1188,7 @@ fn process_destination_for_transfer_with_fee(
     mint_info: &AccountInfo,
     proof_context: &TransferWithFeeProofContext,
     is_self_transfer: bool,
+    credited_amount: Option<u64>,
 ) -> ProgramResult {
     check_program_account(destination_account_info.owner)?;
     let destination_token_account_data = &mut destination_account_info.data.borrow_mut();
@@ -1143,6 +1291,14 @@ fn process_destination_for_transfer_with_fee(
             .ok_or(TokenError::CiphertextArithmeticFailed)?;
     }
 
+    if let Ok(reveal_mode) =
+        destination_token_account.get_extension_mut::<ConfidentialTransferRevealMode>()
+    {
+        let credited_amount =
+            credited_amount.ok_or(TokenError::ConfidentialTransferAmountNotRevealed)?;
+        reveal_mode.credit_pending_balance(credited_amount)?;
+    }
+
     Ok(())
 }
 
@@ -1193,6 +1349,10 @@ fn process_apply_pending_balance(
     confidential_transfer_account.pending_balance_lo = EncryptedBalance::zeroed();
     confidential_transfer_account.pending_balance_hi = EncryptedBalance::zeroed();
 
+    if let Ok(reveal_mode) = token_account.get_extension_mut::<ConfidentialTransferRevealMode>() {
+        reveal_mode.apply_pending_balance()?;
+    }
+
     Ok(())
 }
 
@@ -1259,6 +1419,236 @@ fn process_allow_non_confidential_credits(
     Ok(())
 }
 
+/// Processes an [`EnableRevealMode`] instruction.
+fn process_enable_reveal_mode(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    check_program_account(token_account_info.owner)?;
+    let token_account_data = &mut token_account_info.data.borrow_mut();
+    let mut token_account = PodStateWithExtensionsMut::<PodAccount>::unpack(token_account_data)?;
+
+    Processor::validate_owner(
+        program_id,
+        &token_account.base.owner,
+        authority_info,
+        authority_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    let confidential_transfer_account =
+        token_account.get_extension::<ConfidentialTransferAccount>()?;
+    // The pending balance is known to be zero only if nothing has been credited
+    // since the last `ApplyPendingBalance`
+    let pending_balance_revealed =
+        u64::from(confidential_transfer_account.pending_balance_credit_counter) == 0;
+
+    let reveal_mode = token_account.init_extension::<ConfidentialTransferRevealMode>(false)?;
+    reveal_mode.pending_balance_revealed = pending_balance_revealed.into();
+
+    Ok(())
+}
+
+/// Processes a [`RevealAvailableBalance`] instruction.
+#[cfg(feature = "zk-ops")]
+fn process_reveal_available_balance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    proof_instruction_offset: i64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+
+    // zero-knowledge proof certifies that the available balance ciphertext minus
+    // the revealed amount encrypts 0
+    let proof_context = verify_and_extract_context::<
+        ZeroCiphertextProofData,
+        ZeroCiphertextProofContext,
+    >(account_info_iter, proof_instruction_offset, None)?;
+
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    check_program_account(token_account_info.owner)?;
+    let token_account_data = &mut token_account_info.data.borrow_mut();
+    let mut token_account = PodStateWithExtensionsMut::<PodAccount>::unpack(token_account_data)?;
+
+    Processor::validate_owner(
+        program_id,
+        &token_account.base.owner,
+        authority_info,
+        authority_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    let confidential_transfer_account =
+        token_account.get_extension::<ConfidentialTransferAccount>()?;
+
+    if confidential_transfer_account.elgamal_pubkey != proof_context.pubkey {
+        msg!("Encryption public-key mismatch");
+        return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+    }
+
+    // Prevent unnecessary ciphertext arithmetic syscalls if the revealed amount
+    // is zero
+    let remaining_balance = if amount > 0 {
+        ciphertext_arithmetic::subtract_from(
+            &confidential_transfer_account.available_balance,
+            amount,
+        )
+        .ok_or(TokenError::CiphertextArithmeticFailed)?
+    } else {
+        confidential_transfer_account.available_balance
+    };
+    if remaining_balance != proof_context.ciphertext {
+        return Err(TokenError::ConfidentialTransferBalanceMismatch.into());
+    }
+
+    token_account
+        .get_extension_mut::<ConfidentialTransferRevealMode>()?
+        .reveal_available_balance(amount);
+
+    Ok(())
+}
+
+/// Processes a [`DiscloseBalance`] instruction.
+#[cfg(feature = "zk-ops")]
+fn process_disclose_balance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    proof_instruction_offset: i64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let receipt_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // zero-knowledge proof certifies that the available balance ciphertext minus
+    // the disclosed amount encrypts 0
+    let proof_context = verify_and_extract_context::<
+        ZeroCiphertextProofData,
+        ZeroCiphertextProofContext,
+    >(account_info_iter, proof_instruction_offset, None)?;
+
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    check_program_account(token_account_info.owner)?;
+    {
+        let token_account_data = &token_account_info.data.borrow();
+        let token_account = PodStateWithExtensions::<PodAccount>::unpack(token_account_data)?;
+
+        Processor::validate_owner(
+            program_id,
+            &token_account.base.owner,
+            authority_info,
+            authority_info_data_len,
+            account_info_iter.as_slice(),
+        )?;
+
+        let confidential_transfer_account =
+            token_account.get_extension::<ConfidentialTransferAccount>()?;
+
+        if confidential_transfer_account.elgamal_pubkey != proof_context.pubkey {
+            msg!("Encryption public-key mismatch");
+            return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+        }
+
+        // Prevent unnecessary ciphertext arithmetic syscalls if the disclosed
+        // amount is zero
+        let remaining_balance = if amount > 0 {
+            ciphertext_arithmetic::subtract_from(
+                &confidential_transfer_account.available_balance,
+                amount,
+            )
+            .ok_or(TokenError::CiphertextArithmeticFailed)?
+        } else {
+            confidential_transfer_account.available_balance
+        };
+        if remaining_balance != proof_context.ciphertext {
+            return Err(TokenError::ConfidentialTransferBalanceMismatch.into());
+        }
+    }
+
+    let (receipt_address, bump_seed) =
+        get_balance_disclosure_address_and_bump_seed(token_account_info.key, program_id);
+    if receipt_address != *receipt_info.key {
+        msg!("Balance disclosure receipt address mismatch");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // create the receipt account on the first disclosure
+    if receipt_info.owner != program_id {
+        let receipt_signer_seeds: &[&[_]] = &[
+            BALANCE_DISCLOSURE_SEED,
+            token_account_info.key.as_ref(),
+            &[bump_seed],
+        ];
+        let rent = Rent::get()?;
+        let required_lamports = rent
+            .minimum_balance(BalanceDisclosure::LEN)
+            .saturating_sub(receipt_info.lamports());
+
+        if receipt_info.lamports() == 0 {
+            invoke_signed(
+                &system_instruction::create_account(
+                    payer_info.key,
+                    receipt_info.key,
+                    required_lamports,
+                    BalanceDisclosure::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    payer_info.clone(),
+                    receipt_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[receipt_signer_seeds],
+            )?;
+        } else {
+            // the receipt address may have been pre-funded, in which case it
+            // can't be created with `create_account`
+            if required_lamports > 0 {
+                invoke(
+                    &system_instruction::transfer(
+                        payer_info.key,
+                        receipt_info.key,
+                        required_lamports,
+                    ),
+                    &[
+                        payer_info.clone(),
+                        receipt_info.clone(),
+                        system_program_info.clone(),
+                    ],
+                )?;
+            }
+            invoke_signed(
+                &system_instruction::allocate(receipt_info.key, BalanceDisclosure::LEN as u64),
+                &[receipt_info.clone(), system_program_info.clone()],
+                &[receipt_signer_seeds],
+            )?;
+            invoke_signed(
+                &system_instruction::assign(receipt_info.key, program_id),
+                &[receipt_info.clone(), system_program_info.clone()],
+                &[receipt_signer_seeds],
+            )?;
+        }
+    }
+
+    let receipt_data = &mut receipt_info.data.borrow_mut();
+    let receipt = pod_from_bytes_mut::<BalanceDisclosure>(receipt_data)?;
+    receipt.token_account = *token_account_info.key;
+    receipt.slot = Clock::get()?.slot.into();
+    receipt.amount = amount.into();
+
+    Ok(())
+}
+
 #[allow(dead_code)]
 pub(crate) fn process_instruction(
     program_id: &Pubkey,
@@ -1339,7 +1729,8 @@ pub(crate) fn process_instruction(
             msg!("ConfidentialTransferInstruction::Transfer");
             #[cfg(feature = "zk-ops")]
             {
-                let data = decode_instruction_data::<TransferInstructionData>(input)?;
+                let (data, revealed_transfer_amount) =
+                    decode_transfer_instruction_data::<TransferInstructionData>(input)?;
                 process_transfer(
                     program_id,
                     accounts,
@@ -1351,6 +1742,7 @@ pub(crate) fn process_instruction(
                     None,
                     None,
                     data.range_proof_instruction_offset as i64,
+                    revealed_transfer_amount,
                 )
             }
             #[cfg(not(feature = "zk-ops"))]
@@ -1391,7 +1783,8 @@ pub(crate) fn process_instruction(
             msg!("ConfidentialTransferInstruction::TransferWithFee");
             #[cfg(feature = "zk-ops")]
             {
-                let data = decode_instruction_data::<TransferWithFeeInstructionData>(input)?;
+                let (data, revealed_transfer_amount) =
+                    decode_transfer_instruction_data::<TransferWithFeeInstructionData>(input)?;
                 process_transfer(
                     program_id,
                     accounts,
@@ -1403,6 +1796,7 @@ pub(crate) fn process_instruction(
                     Some(data.fee_sigma_proof_instruction_offset as i64),
                     Some(data.fee_ciphertext_validity_proof_instruction_offset as i64),
                     data.range_proof_instruction_offset as i64,
+                    revealed_transfer_amount,
                 )
             }
             #[cfg(not(feature = "zk-ops"))]
@@ -1412,5 +1806,39 @@ pub(crate) fn process_instruction(
             msg!("ConfidentialTransferInstruction::ConfigureAccountWithRegistry");
             process_configure_account_with_registry(program_id, accounts)
         }
+        ConfidentialTransferInstruction::DiscloseBalance => {
+            msg!("ConfidentialTransferInstruction::DiscloseBalance");
+            #[cfg(feature = "zk-ops")]
+            {
+                let data = decode_instruction_data::<DiscloseBalanceInstructionData>(input)?;
+                process_disclose_balance(
+                    program_id,
+                    accounts,
+                    data.amount.into(),
+                    data.proof_instruction_offset as i64,
+                )
+            }
+            #[cfg(not(feature = "zk-ops"))]
+            Err(ProgramError::InvalidInstructionData)
+        }
+        ConfidentialTransferInstruction::EnableRevealMode => {
+            msg!("ConfidentialTransferInstruction::EnableRevealMode");
+            process_enable_reveal_mode(program_id, accounts)
+        }
+        ConfidentialTransferInstruction::RevealAvailableBalance => {
+            msg!("ConfidentialTransferInstruction::RevealAvailableBalance");
+            #[cfg(feature = "zk-ops")]
+            {
+                let data = decode_instruction_data::<RevealAvailableBalanceInstructionData>(input)?;
+                process_reveal_available_balance(
+                    program_id,
+                    accounts,
+                    data.amount.into(),
+                    data.proof_instruction_offset as i64,
+                )
+            }
+            #[cfg(not(feature = "zk-ops"))]
+            Err(ProgramError::InvalidInstructionData)
+        }
     }
 }
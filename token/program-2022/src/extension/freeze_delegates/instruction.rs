@@ -0,0 +1,162 @@
+#[cfg(feature = "serde-traits")]
+use serde::{Deserialize, Serialize};
+use {
+    crate::{
+        check_program_account,
+        extension::freeze_delegates::{FreezeDelegateScope, PodFreezeDelegateScope},
+        instruction::{encode_instruction, TokenInstruction},
+    },
+    bytemuck::{Pod, Zeroable},
+    num_enum::{IntoPrimitive, TryFromPrimitive},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+/// Freeze delegates extension instructions
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum FreezeDelegateInstruction {
+    /// Initialize the freeze delegates extension for the given mint account,
+    /// with no registered delegates
+    ///
+    /// Fails if the account has already been initialized, so must be called
+    /// before `InitializeMint`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]`  The mint account to initialize.
+    ///
+    /// Data expected by this instruction:
+    ///   None
+    Initialize,
+    /// Register a freeze delegate on the mint, or update the scope of an
+    /// already registered delegate.
+    ///
+    /// Fails if the mint has no freeze authority, or if all delegate slots
+    /// are taken.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[signer]` The mint's freeze authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[]` The mint's multisignature freeze authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::freeze_delegates::instruction::SetDelegateInstructionData`
+    SetDelegate,
+    /// Remove a freeze delegate from the mint.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[signer]` The mint's freeze authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[]` The mint's multisignature freeze authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::freeze_delegates::instruction::RemoveDelegateInstructionData`
+    RemoveDelegate,
+}
+
+/// Data expected by `FreezeDelegateInstruction::SetDelegate`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct SetDelegateInstructionData {
+    /// The delegate to register
+    pub delegate: Pubkey,
+    /// The operations allowed to the delegate, see `FreezeDelegateScope`
+    pub scope: PodFreezeDelegateScope,
+}
+
+/// Data expected by `FreezeDelegateInstruction::RemoveDelegate`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct RemoveDelegateInstructionData {
+    /// The delegate to remove
+    pub delegate: Pubkey,
+}
+
+/// Create an `Initialize` instruction
+pub fn initialize(token_program_id: &Pubkey, mint: &Pubkey) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![AccountMeta::new(*mint, false)];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::FreezeDelegateExtension,
+        FreezeDelegateInstruction::Initialize,
+        &(),
+    ))
+}
+
+/// Create a `SetDelegate` instruction
+pub fn set_delegate(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    freeze_authority: &Pubkey,
+    signers: &[&Pubkey],
+    delegate: &Pubkey,
+    scope: FreezeDelegateScope,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*freeze_authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::FreezeDelegateExtension,
+        FreezeDelegateInstruction::SetDelegate,
+        &SetDelegateInstructionData {
+            delegate: *delegate,
+            scope: scope.into(),
+        },
+    ))
+}
+
+/// Create a `RemoveDelegate` instruction
+pub fn remove_delegate(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    freeze_authority: &Pubkey,
+    signers: &[&Pubkey],
+    delegate: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*freeze_authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::FreezeDelegateExtension,
+        FreezeDelegateInstruction::RemoveDelegate,
+        &RemoveDelegateInstructionData {
+            delegate: *delegate,
+        },
+    ))
+}
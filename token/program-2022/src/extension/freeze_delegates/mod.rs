@@ -0,0 +1,163 @@
+#[cfg(feature = "serde-traits")]
+use serde::{Deserialize, Serialize};
+use {
+    crate::{
+        error::TokenError,
+        extension::{Extension, ExtensionType},
+    },
+    bytemuck::{Pod, Zeroable},
+    num_enum::{IntoPrimitive, TryFromPrimitive},
+    solana_program::{program_error::ProgramError, pubkey::Pubkey},
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+};
+
+/// Instruction types for the freeze delegates extension
+pub mod instruction;
+/// Instruction processor for the freeze delegates extension
+pub mod processor;
+
+/// Maximum number of freeze delegates that can be registered on a mint
+pub const MAX_FREEZE_DELEGATES: usize = 4;
+
+/// Operations a freeze delegate is allowed to perform
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum FreezeDelegateScope {
+    /// The delegate can only freeze accounts
+    FreezeOnly,
+    /// The delegate can freeze and thaw accounts
+    FreezeAndThaw,
+}
+
+type PodFreezeDelegateScope = u8;
+
+/// A single freeze delegate entry
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct FreezeDelegate {
+    /// The delegate, or `None` if the slot is unused
+    pub delegate: OptionalNonZeroPubkey,
+    /// The operations allowed to the delegate, see `FreezeDelegateScope`
+    pub scope: PodFreezeDelegateScope,
+}
+impl FreezeDelegate {
+    /// Check if the delegate is allowed to freeze (`freeze == true`) or thaw
+    /// (`freeze == false`) accounts
+    pub fn allows(&self, freeze: bool) -> bool {
+        match FreezeDelegateScope::try_from(self.scope) {
+            Ok(FreezeDelegateScope::FreezeOnly) => freeze,
+            Ok(FreezeDelegateScope::FreezeAndThaw) => true,
+            Err(_) => false,
+        }
+    }
+}
+
+/// Freeze delegates extension data for mints, allowing additional authorities
+/// to freeze (and optionally thaw) accounts on behalf of the freeze authority
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct FreezeDelegates {
+    /// Registered delegates, unused slots have no delegate
+    pub delegates: [FreezeDelegate; MAX_FREEZE_DELEGATES],
+}
+impl FreezeDelegates {
+    /// Find the entry for the given delegate
+    pub fn get_delegate(&self, delegate: &Pubkey) -> Option<&FreezeDelegate> {
+        self.delegates
+            .iter()
+            .find(|entry| Option::<Pubkey>::from(entry.delegate).as_ref() == Some(delegate))
+    }
+
+    /// Add the delegate, or update its scope if it is already registered
+    pub fn set_delegate(
+        &mut self,
+        delegate: &Pubkey,
+        scope: FreezeDelegateScope,
+    ) -> Result<(), ProgramError> {
+        let slot = match self
+            .delegates
+            .iter()
+            .position(|entry| Option::<Pubkey>::from(entry.delegate).as_ref() == Some(delegate))
+        {
+            Some(index) => &mut self.delegates[index],
+            None => self
+                .delegates
+                .iter_mut()
+                .find(|entry| Option::<Pubkey>::from(entry.delegate).is_none())
+                .ok_or(TokenError::FreezeDelegateLimitReached)?,
+        };
+        slot.delegate = Some(*delegate).try_into()?;
+        slot.scope = scope.into();
+        Ok(())
+    }
+
+    /// Remove the delegate
+    pub fn remove_delegate(&mut self, delegate: &Pubkey) -> Result<(), ProgramError> {
+        let slot = self
+            .delegates
+            .iter_mut()
+            .find(|entry| Option::<Pubkey>::from(entry.delegate).as_ref() == Some(delegate))
+            .ok_or(TokenError::FreezeDelegateNotFound)?;
+        *slot = FreezeDelegate::default();
+        Ok(())
+    }
+}
+impl Extension for FreezeDelegates {
+    const TYPE: ExtensionType = ExtensionType::FreezeDelegates;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_remove_delegates() {
+        let mut extension = FreezeDelegates::default();
+        let delegates: Vec<Pubkey> = (0..=MAX_FREEZE_DELEGATES)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+
+        for delegate in &delegates[..MAX_FREEZE_DELEGATES] {
+            extension
+                .set_delegate(delegate, FreezeDelegateScope::FreezeOnly)
+                .unwrap();
+        }
+        assert_eq!(
+            extension.set_delegate(
+                &delegates[MAX_FREEZE_DELEGATES],
+                FreezeDelegateScope::FreezeOnly
+            ),
+            Err(TokenError::FreezeDelegateLimitReached.into())
+        );
+
+        // updating an existing delegate doesn't take a new slot
+        extension
+            .set_delegate(&delegates[0], FreezeDelegateScope::FreezeAndThaw)
+            .unwrap();
+        let entry = extension.get_delegate(&delegates[0]).unwrap();
+        assert!(entry.allows(true));
+        assert!(entry.allows(false));
+        let entry = extension.get_delegate(&delegates[1]).unwrap();
+        assert!(entry.allows(true));
+        assert!(!entry.allows(false));
+
+        extension.remove_delegate(&delegates[1]).unwrap();
+        assert!(extension.get_delegate(&delegates[1]).is_none());
+        assert_eq!(
+            extension.remove_delegate(&delegates[1]),
+            Err(TokenError::FreezeDelegateNotFound.into())
+        );
+        extension
+            .set_delegate(
+                &delegates[MAX_FREEZE_DELEGATES],
+                FreezeDelegateScope::FreezeOnly,
+            )
+            .unwrap();
+    }
+}
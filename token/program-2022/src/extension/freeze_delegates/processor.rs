@@ -0,0 +1,99 @@
+use {
+    crate::{
+        check_program_account,
+        error::TokenError,
+        extension::{
+            freeze_delegates::{
+                instruction::{
+                    FreezeDelegateInstruction, RemoveDelegateInstructionData,
+                    SetDelegateInstructionData,
+                },
+                FreezeDelegateScope, FreezeDelegates,
+            },
+            BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
+        },
+        instruction::{decode_instruction_data, decode_instruction_type},
+        pod::{PodCOption, PodMint},
+        processor::Processor,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        pubkey::Pubkey,
+    },
+};
+
+fn process_initialize(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut mint_data)?;
+
+    mint.init_extension::<FreezeDelegates>(true)?;
+
+    Ok(())
+}
+
+/// Add, update or remove a freeze delegate, authorized by the mint's freeze
+/// authority
+fn process_update_delegates(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    update: impl FnOnce(&mut FreezeDelegates) -> ProgramResult,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    match &mint.base.freeze_authority {
+        PodCOption {
+            option: PodCOption::<Pubkey>::SOME,
+            value: authority,
+        } => Processor::validate_owner(
+            program_id,
+            authority,
+            authority_info,
+            authority_info_data_len,
+            account_info_iter.as_slice(),
+        ),
+        _ => Err(TokenError::MintCannotFreeze.into()),
+    }?;
+
+    let extension = mint.get_extension_mut::<FreezeDelegates>()?;
+    update(extension)
+}
+
+pub(crate) fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+
+    match decode_instruction_type(input)? {
+        FreezeDelegateInstruction::Initialize => {
+            msg!("FreezeDelegateInstruction::Initialize");
+            process_initialize(program_id, accounts)
+        }
+        FreezeDelegateInstruction::SetDelegate => {
+            msg!("FreezeDelegateInstruction::SetDelegate");
+            let SetDelegateInstructionData { delegate, scope } = decode_instruction_data(input)?;
+            let scope =
+                FreezeDelegateScope::try_from(*scope).or(Err(TokenError::InvalidInstruction))?;
+            process_update_delegates(program_id, accounts, |extension| {
+                extension.set_delegate(delegate, scope)
+            })
+        }
+        FreezeDelegateInstruction::RemoveDelegate => {
+            msg!("FreezeDelegateInstruction::RemoveDelegate");
+            let RemoveDelegateInstructionData { delegate } = decode_instruction_data(input)?;
+            process_update_delegates(program_id, accounts, |extension| {
+                extension.remove_delegate(delegate)
+            })
+        }
+    }
+}
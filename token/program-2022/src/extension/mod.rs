@@ -7,12 +7,16 @@ use {
         error::TokenError,
         extension::{
             confidential_mint_burn::ConfidentialMintBurn,
-            confidential_transfer::{ConfidentialTransferAccount, ConfidentialTransferMint},
+            confidential_transfer::{
+                ConfidentialTransferAccount, ConfidentialTransferMint,
+                ConfidentialTransferRevealMode,
+            },
             confidential_transfer_fee::{
                 ConfidentialTransferFeeAmount, ConfidentialTransferFeeConfig,
             },
             cpi_guard::CpiGuard,
             default_account_state::DefaultAccountState,
+            freeze_delegates::FreezeDelegates,
             group_member_pointer::GroupMemberPointer,
             group_pointer::GroupPointer,
             immutable_owner::ImmutableOwner,
@@ -24,8 +28,8 @@ use {
             pausable::{PausableAccount, PausableConfig},
             permanent_delegate::PermanentDelegate,
             scaled_ui_amount::ScaledUiAmountConfig,
-            transfer_fee::{TransferFeeAmount, TransferFeeConfig},
-            transfer_hook::{TransferHook, TransferHookAccount},
+            transfer_fee::{TransferFeeAmount, TransferFeeConfig, TransferFeeExemptAccount},
+            transfer_hook::{TransferHook, TransferHookAccount, TransferHookBudget},
         },
         pod::{PodAccount, PodMint},
         state::{Account, Mint, Multisig, PackedSizeOf},
@@ -58,6 +62,8 @@ pub mod confidential_transfer_fee;
 pub mod cpi_guard;
 /// Default Account State extension
 pub mod default_account_state;
+/// Freeze Delegates extension
+pub mod freeze_delegates;
 /// Group Member Pointer extension
 pub mod group_member_pointer;
 /// Group Pointer extension
@@ -1124,6 +1130,20 @@ pub enum ExtensionType {
     Pausable,
     /// Indicates that the account belongs to a pausable mint
     PausableAccount,
+    /// Includes additional authorities allowed to freeze (and optionally
+    /// thaw) accounts
+    FreezeDelegates,
+    /// Indicates that transfers from or into the account are exempt from
+    /// transfer fees
+    TransferFeeExemptAccount,
+    /// Mint declares the extra accounts and compute units its transfer hook
+    /// may require
+    TransferHookBudget,
+    /// Account tracks the plaintext amounts of its confidential balances
+    ConfidentialTransferRevealMode,
+    /// Mint contains authorities allowed to update single token-metadata
+    /// fields
+    TokenMetadataFieldAuthorities,
 
     /// Test variable-length mint extension
     #[cfg(test)]
@@ -1159,6 +1179,7 @@ impl ExtensionType {
     const fn sized(&self) -> bool {
         match self {
             ExtensionType::TokenMetadata => false,
+            ExtensionType::TokenMetadataFieldAuthorities => false,
             #[cfg(test)]
             ExtensionType::VariableLenMintTest => false,
             _ => true,
@@ -1209,6 +1230,15 @@ impl ExtensionType {
             ExtensionType::ScaledUiAmount => pod_get_packed_len::<ScaledUiAmountConfig>(),
             ExtensionType::Pausable => pod_get_packed_len::<PausableConfig>(),
             ExtensionType::PausableAccount => pod_get_packed_len::<PausableAccount>(),
+            ExtensionType::FreezeDelegates => pod_get_packed_len::<FreezeDelegates>(),
+            ExtensionType::TransferFeeExemptAccount => {
+                pod_get_packed_len::<TransferFeeExemptAccount>()
+            }
+            ExtensionType::TransferHookBudget => pod_get_packed_len::<TransferHookBudget>(),
+            ExtensionType::ConfidentialTransferRevealMode => {
+                pod_get_packed_len::<ConfidentialTransferRevealMode>()
+            }
+            ExtensionType::TokenMetadataFieldAuthorities => unreachable!(),
             #[cfg(test)]
             ExtensionType::AccountPaddingTest => pod_get_packed_len::<AccountPaddingTest>(),
             #[cfg(test)]
@@ -1275,7 +1305,10 @@ impl ExtensionType {
             | ExtensionType::ConfidentialMintBurn
             | ExtensionType::TokenGroupMember
             | ExtensionType::ScaledUiAmount
-            | ExtensionType::Pausable => AccountType::Mint,
+            | ExtensionType::Pausable
+            | ExtensionType::FreezeDelegates
+            | ExtensionType::TransferHookBudget
+            | ExtensionType::TokenMetadataFieldAuthorities => AccountType::Mint,
             ExtensionType::ImmutableOwner
             | ExtensionType::TransferFeeAmount
             | ExtensionType::ConfidentialTransferAccount
@@ -1284,7 +1317,9 @@ impl ExtensionType {
             | ExtensionType::TransferHookAccount
             | ExtensionType::CpiGuard
             | ExtensionType::ConfidentialTransferFeeAmount
-            | ExtensionType::PausableAccount => AccountType::Account,
+            | ExtensionType::PausableAccount
+            | ExtensionType::TransferFeeExemptAccount
+            | ExtensionType::ConfidentialTransferRevealMode => AccountType::Account,
             #[cfg(test)]
             ExtensionType::VariableLenMintTest => AccountType::Mint,
             #[cfg(test)]
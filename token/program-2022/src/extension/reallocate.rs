@@ -25,6 +25,24 @@ pub fn process_reallocate(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     new_extension_types: Vec<ExtensionType>,
+) -> ProgramResult {
+    reallocate(program_id, accounts, new_extension_types, false)
+}
+
+/// Processes a [ReallocateIdempotent](enum.TokenInstruction.html) instruction
+pub fn process_reallocate_idempotent(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_extension_types: Vec<ExtensionType>,
+) -> ProgramResult {
+    reallocate(program_id, accounts, new_extension_types, true)
+}
+
+fn reallocate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_extension_types: Vec<ExtensionType>,
+    refund_excess_lamports: bool,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let token_account_info = next_account_info(account_info_iter)?;
@@ -63,6 +81,15 @@ pub fn process_reallocate(
 
     // if account is already large enough, return early
     if token_account_info.data_len() >= needed_account_len {
+        if refund_excess_lamports {
+            let rent_exempt_reserve = Rent::get()?.minimum_balance(token_account_info.data_len());
+            refund_lamports_above_reserve(
+                token_account_info,
+                payer_info,
+                native_token_amount,
+                rent_exempt_reserve,
+            )?;
+        }
         return Ok(());
     }
 
@@ -91,6 +118,13 @@ pub fn process_reallocate(
                 system_program_info.clone(),
             ],
         )?;
+    } else if refund_excess_lamports {
+        refund_lamports_above_reserve(
+            token_account_info,
+            payer_info,
+            native_token_amount,
+            new_rent_exempt_reserve,
+        )?;
     }
 
     // set account_type, if needed
@@ -114,3 +148,37 @@ pub fn process_reallocate(
 
     Ok(())
 }
+
+/// Moves any lamports held by the token account above its rent-exempt reserve
+/// to the destination. Native accounts are left untouched, since their extra
+/// lamports are wrapped SOL waiting to be synced.
+fn refund_lamports_above_reserve(
+    token_account_info: &AccountInfo,
+    destination_info: &AccountInfo,
+    native_token_amount: Option<u64>,
+    rent_exempt_reserve: u64,
+) -> ProgramResult {
+    if native_token_amount.is_some() || token_account_info.key == destination_info.key {
+        return Ok(());
+    }
+
+    let refund_amount = token_account_info
+        .lamports()
+        .saturating_sub(rent_exempt_reserve);
+    if refund_amount == 0 {
+        return Ok(());
+    }
+
+    msg!("refunding {} excess lamports", refund_amount);
+    let source_starting_lamports = token_account_info.lamports();
+    **token_account_info.lamports.borrow_mut() = source_starting_lamports
+        .checked_sub(refund_amount)
+        .ok_or(TokenError::Overflow)?;
+
+    let destination_starting_lamports = destination_info.lamports();
+    **destination_info.lamports.borrow_mut() = destination_starting_lamports
+        .checked_add(refund_amount)
+        .ok_or(TokenError::Overflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,313 @@
+//! Display hints for rendering UI amounts
+//!
+//! Wallets render mints with many decimals inconsistently, so a mint may
+//! describe how its amounts should be displayed through the token-metadata
+//! additional fields. All hints live under the [`DISPLAY_HINT_KEY_PREFIX`]
+//! key namespace, which is reserved: the token-metadata processor rejects
+//! unknown keys and invalid values within it.
+//!
+//! | Key                          | Value                                   |
+//! |------------------------------|-----------------------------------------|
+//! | `display.significant_digits` | integer in `1..=MAX_SIGNIFICANT_DIGITS` |
+//! | `display.rounding_mode`      | `down`, `up`, `half_up`, `half_even`    |
+//! | `display.decimal_separator`  | single non-digit character              |
+//! | `display.grouping_separator` | single non-digit character              |
+//!
+//! All hints are optional, values are limited to
+//! [`MAX_DISPLAY_HINT_VALUE_LEN`] bytes.
+
+use {
+    crate::error::TokenError, solana_program::program_error::ProgramError,
+    spl_token_metadata_interface::state::TokenMetadata, std::str::FromStr,
+};
+
+/// Prefix of all display hint keys
+pub const DISPLAY_HINT_KEY_PREFIX: &str = "display.";
+/// Key of the number of significant digits to display
+pub const SIGNIFICANT_DIGITS_KEY: &str = "display.significant_digits";
+/// Key of the rounding mode applied when dropping digits
+pub const ROUNDING_MODE_KEY: &str = "display.rounding_mode";
+/// Key of the character separating the integer and fractional parts
+pub const DECIMAL_SEPARATOR_KEY: &str = "display.decimal_separator";
+/// Key of the character separating groups of three integer digits
+pub const GROUPING_SEPARATOR_KEY: &str = "display.grouping_separator";
+
+/// Maximum length in bytes of a display hint value
+pub const MAX_DISPLAY_HINT_VALUE_LEN: usize = 16;
+/// Maximum number of significant digits, the number of digits in `u64::MAX`
+pub const MAX_SIGNIFICANT_DIGITS: u8 = 20;
+
+/// Rounding mode applied when an amount has more digits than displayed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round towards zero, never overstating an amount
+    #[default]
+    Down,
+    /// Round away from zero
+    Up,
+    /// Round to the nearest, ties away from zero
+    HalfUp,
+    /// Round to the nearest, ties to the even neighbour
+    HalfEven,
+}
+impl RoundingMode {
+    /// Value stored in the metadata for the rounding mode
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Down => "down",
+            Self::Up => "up",
+            Self::HalfUp => "half_up",
+            Self::HalfEven => "half_even",
+        }
+    }
+
+    /// Rounds `amount` to a multiple of `factor`
+    fn round(&self, amount: u128, factor: u128) -> u128 {
+        let quotient = amount / factor;
+        let remainder = amount % factor;
+        let round_up = match self {
+            Self::Down => false,
+            Self::Up => remainder > 0,
+            Self::HalfUp => remainder * 2 >= factor,
+            Self::HalfEven => {
+                remainder * 2 > factor || (remainder * 2 == factor && quotient % 2 == 1)
+            }
+        };
+        (quotient + u128::from(round_up)) * factor
+    }
+}
+impl FromStr for RoundingMode {
+    type Err = ProgramError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "down" => Ok(Self::Down),
+            "up" => Ok(Self::Up),
+            "half_up" => Ok(Self::HalfUp),
+            "half_even" => Ok(Self::HalfEven),
+            _ => Err(TokenError::InvalidDisplayHint.into()),
+        }
+    }
+}
+
+/// Display hints parsed from the token-metadata additional fields
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisplayHints {
+    /// Number of significant digits to display, integer digits are never
+    /// dropped
+    pub significant_digits: Option<u8>,
+    /// Rounding mode applied when dropping digits, defaults to
+    /// [`RoundingMode::Down`]
+    pub rounding_mode: Option<RoundingMode>,
+    /// Character separating the integer and fractional parts, defaults to `.`
+    pub decimal_separator: Option<char>,
+    /// Character separating groups of three integer digits, no grouping if
+    /// unset
+    pub grouping_separator: Option<char>,
+}
+impl DisplayHints {
+    /// Parses the display hints from the metadata additional fields
+    ///
+    /// Fails if a key within the namespace is unknown or has an invalid value,
+    /// or if the grouping separator is the same as the decimal separator.
+    pub fn try_from_metadata(token_metadata: &TokenMetadata) -> Result<Self, ProgramError> {
+        let mut hints = Self::default();
+        for (key, value) in &token_metadata.additional_metadata {
+            hints.set(key, value)?;
+        }
+        if hints.grouping_separator == Some(hints.decimal_separator.unwrap_or('.')) {
+            return Err(TokenError::InvalidDisplayHint.into());
+        }
+        Ok(hints)
+    }
+
+    /// Sets the hint for the given key, ignoring keys outside the namespace
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ProgramError> {
+        if !is_display_hint_key(key) {
+            return Ok(());
+        }
+        if value.len() > MAX_DISPLAY_HINT_VALUE_LEN {
+            return Err(TokenError::InvalidDisplayHint.into());
+        }
+        match key {
+            SIGNIFICANT_DIGITS_KEY => {
+                let significant_digits = value
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|d| (1..=MAX_SIGNIFICANT_DIGITS).contains(d))
+                    .ok_or(TokenError::InvalidDisplayHint)?;
+                self.significant_digits = Some(significant_digits);
+            }
+            ROUNDING_MODE_KEY => self.rounding_mode = Some(value.parse()?),
+            DECIMAL_SEPARATOR_KEY => self.decimal_separator = Some(parse_separator(value)?),
+            GROUPING_SEPARATOR_KEY => self.grouping_separator = Some(parse_separator(value)?),
+            _ => return Err(TokenError::InvalidDisplayHint.into()),
+        }
+        Ok(())
+    }
+
+    /// Formats a raw amount as a UI amount string following the hints
+    pub fn format_ui_amount(&self, amount: u64, decimals: u8) -> String {
+        let decimals = decimals as usize;
+        let mut amount = u128::from(amount);
+
+        if let Some(significant_digits) = self.significant_digits {
+            let digits = amount.to_string().len();
+            let integer_digits = digits.saturating_sub(decimals);
+            let kept_digits = (significant_digits as usize).max(integer_digits);
+            if digits > kept_digits {
+                let factor = 10u128.pow((digits - kept_digits) as u32);
+                amount = self.rounding_mode.unwrap_or_default().round(amount, factor);
+            }
+        }
+
+        // Left-pad zeros to decimals + 1, so we at least have an integer zero
+        let digits = format!("{:01$}", amount, decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+
+        let mut ui_amount = String::with_capacity(digits.len() * 2);
+        for (i, digit) in integer.chars().enumerate() {
+            if let Some(separator) = self.grouping_separator {
+                if i > 0 && (integer.len() - i) % 3 == 0 {
+                    ui_amount.push(separator);
+                }
+            }
+            ui_amount.push(digit);
+        }
+        if !fraction.is_empty() {
+            ui_amount.push(self.decimal_separator.unwrap_or('.'));
+            ui_amount.push_str(fraction);
+        }
+        ui_amount
+    }
+}
+
+/// Checks whether the key is within the display hint namespace
+pub fn is_display_hint_key(key: &str) -> bool {
+    key.starts_with(DISPLAY_HINT_KEY_PREFIX)
+}
+
+fn parse_separator(value: &str) -> Result<char, ProgramError> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_ascii_digit() && c != '-' => Ok(c),
+        _ => Err(TokenError::InvalidDisplayHint.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata_with(fields: &[(&str, &str)]) -> TokenMetadata {
+        TokenMetadata {
+            additional_metadata: fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_hints() {
+        let metadata = metadata_with(&[
+            ("website", "example.com"),
+            (SIGNIFICANT_DIGITS_KEY, "4"),
+            (ROUNDING_MODE_KEY, "half_even"),
+            (DECIMAL_SEPARATOR_KEY, ","),
+            (GROUPING_SEPARATOR_KEY, " "),
+        ]);
+        assert_eq!(
+            DisplayHints::try_from_metadata(&metadata).unwrap(),
+            DisplayHints {
+                significant_digits: Some(4),
+                rounding_mode: Some(RoundingMode::HalfEven),
+                decimal_separator: Some(','),
+                grouping_separator: Some(' '),
+            }
+        );
+        assert_eq!(
+            DisplayHints::try_from_metadata(&metadata_with(&[])).unwrap(),
+            DisplayHints::default()
+        );
+    }
+
+    #[test]
+    fn parse_invalid_hints() {
+        for fields in [
+            [(SIGNIFICANT_DIGITS_KEY, "0")],
+            [(SIGNIFICANT_DIGITS_KEY, "21")],
+            [(ROUNDING_MODE_KEY, "nearest")],
+            [(DECIMAL_SEPARATOR_KEY, "..")],
+            [(GROUPING_SEPARATOR_KEY, "1")],
+            [("display.unknown", "1")],
+            [(ROUNDING_MODE_KEY, "half_even_with_padding")],
+        ] {
+            assert_eq!(
+                DisplayHints::try_from_metadata(&metadata_with(&fields)),
+                Err(TokenError::InvalidDisplayHint.into())
+            );
+        }
+        assert_eq!(
+            DisplayHints::try_from_metadata(&metadata_with(&[
+                (DECIMAL_SEPARATOR_KEY, ","),
+                (GROUPING_SEPARATOR_KEY, ","),
+            ])),
+            Err(TokenError::InvalidDisplayHint.into())
+        );
+    }
+
+    #[test]
+    fn format_ui_amount() {
+        let hints = DisplayHints::default();
+        assert_eq!(hints.format_ui_amount(1_234_567_890, 9), "1.23456789");
+        assert_eq!(hints.format_ui_amount(1_000_000_000, 9), "1");
+        assert_eq!(hints.format_ui_amount(0, 9), "0");
+        assert_eq!(hints.format_ui_amount(1_234, 0), "1234");
+
+        let hints = DisplayHints {
+            significant_digits: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(hints.format_ui_amount(1_239_000_000, 9), "1.23");
+        assert_eq!(hints.format_ui_amount(123_456, 9), "0.000123");
+        // integer digits are never dropped
+        assert_eq!(hints.format_ui_amount(123_456_789_000, 6), "123456");
+
+        let hints = DisplayHints {
+            significant_digits: Some(2),
+            rounding_mode: Some(RoundingMode::HalfUp),
+            decimal_separator: Some(','),
+            grouping_separator: Some('.'),
+        };
+        assert_eq!(hints.format_ui_amount(1_250, 3), "1,3");
+        assert_eq!(hints.format_ui_amount(9_960, 3), "10");
+        assert_eq!(hints.format_ui_amount(1_234_567_890, 3), "1.234.568");
+        assert_eq!(
+            hints.format_ui_amount(u64::MAX, 0),
+            "18.446.744.073.709.551.615"
+        );
+    }
+
+    #[test]
+    fn rounding_modes() {
+        assert_eq!(RoundingMode::Down.round(1_250, 100), 1_200);
+        assert_eq!(RoundingMode::Up.round(1_201, 100), 1_300);
+        assert_eq!(RoundingMode::Up.round(1_200, 100), 1_200);
+        assert_eq!(RoundingMode::HalfUp.round(1_250, 100), 1_300);
+        assert_eq!(RoundingMode::HalfUp.round(1_249, 100), 1_200);
+        assert_eq!(RoundingMode::HalfEven.round(1_250, 100), 1_200);
+        assert_eq!(RoundingMode::HalfEven.round(1_350, 100), 1_400);
+        assert_eq!(RoundingMode::HalfEven.round(1_251, 100), 1_300);
+        for mode in [
+            RoundingMode::Down,
+            RoundingMode::Up,
+            RoundingMode::HalfUp,
+            RoundingMode::HalfEven,
+        ] {
+            assert_eq!(mode.as_str().parse::<RoundingMode>().unwrap(), mode);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Field-level update authorities
+//!
+//! The metadata update authority may delegate the updates of single fields,
+//! e.g. the URI to an artist, or an additional key to a game server. The
+//! authorities are stored in their own variable-length mint extension,
+//! [`TokenMetadataFieldAuthorities`], next to the token-metadata, and are set
+//! or revoked by the update authority with the [`SetFieldAuthority`]
+//! instruction.
+//!
+//! A field authority can only update its own field, and only while the
+//! metadata has an update authority, which can still update every field.
+
+use {
+    crate::extension::{Extension, ExtensionType},
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        borsh1::{get_instance_packed_len, try_from_slice_unchecked},
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    spl_discriminator::{discriminator::ArrayDiscriminator, SplDiscriminate},
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::Field,
+    spl_type_length_value::variable_len_pack::VariableLenPack,
+};
+
+/// Authorities allowed to update single token-metadata fields, in addition to
+/// the metadata update authority
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct TokenMetadataFieldAuthorities {
+    /// The fields and their authorities, at most one authority per field
+    pub authorities: Vec<(Field, Pubkey)>,
+}
+impl TokenMetadataFieldAuthorities {
+    /// Gives the authority of a field, if delegated
+    pub fn get(&self, field: &Field) -> Option<Pubkey> {
+        self.authorities
+            .iter()
+            .find(|(authority_field, _)| authority_field == field)
+            .map(|(_, authority)| *authority)
+    }
+
+    /// Sets the authority of a field, or revokes it if `None`
+    pub fn set(&mut self, field: Field, authority: Option<Pubkey>) {
+        let index = self
+            .authorities
+            .iter()
+            .position(|(authority_field, _)| *authority_field == field);
+        match (index, authority) {
+            (Some(index), Some(authority)) => self.authorities[index].1 = authority,
+            (Some(index), None) => {
+                self.authorities.remove(index);
+            }
+            (None, Some(authority)) => self.authorities.push((field, authority)),
+            (None, None) => {}
+        }
+    }
+}
+impl Extension for TokenMetadataFieldAuthorities {
+    const TYPE: ExtensionType = ExtensionType::TokenMetadataFieldAuthorities;
+}
+impl VariableLenPack for TokenMetadataFieldAuthorities {
+    fn pack_into_slice(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        borsh::to_writer(&mut dst[..], self).map_err(Into::into)
+    }
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        try_from_slice_unchecked(src).map_err(Into::into)
+    }
+    fn get_packed_len(&self) -> Result<usize, ProgramError> {
+        get_instance_packed_len(self).map_err(Into::into)
+    }
+}
+
+/// Set field authority instruction data
+///
+/// Sets or revokes the authority of a single token-metadata field, allocating
+/// the `TokenMetadataFieldAuthorities` extension if needed. Assumes that the
+/// mint has enough lamports for the new rent-exemption.
+///
+/// Accounts expected by this instruction:
+///
+///   0. `[w]` Metadata account (the mint)
+///   1. `[s]` Update authority
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("spl_token_2022:token_metadata:set_field_authority")]
+pub struct SetFieldAuthority {
+    /// Field whose authority is set
+    pub field: Field,
+    /// New authority of the field, or `None` to revoke it
+    pub authority: OptionalNonZeroPubkey,
+}
+impl SetFieldAuthority {
+    /// Unpacks the instruction data, including its discriminator
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        if input.len() < ArrayDiscriminator::LENGTH {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (discriminator, rest) = input.split_at(ArrayDiscriminator::LENGTH);
+        if discriminator != Self::SPL_DISCRIMINATOR_SLICE {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Self::try_from_slice(rest).map_err(Into::into)
+    }
+
+    /// Packs the instruction data, including its discriminator
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = Self::SPL_DISCRIMINATOR_SLICE.to_vec();
+        buf.append(&mut borsh::to_vec(self).unwrap());
+        buf
+    }
+}
+
+/// Creates a `SetFieldAuthority` instruction
+pub fn set_field_authority(
+    program_id: &Pubkey,
+    metadata: &Pubkey,
+    update_authority: &Pubkey,
+    field: Field,
+    authority: Option<Pubkey>,
+) -> Instruction {
+    let data = SetFieldAuthority {
+        field,
+        authority: authority.try_into().unwrap(),
+    };
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*update_authority, true),
+        ],
+        data: data.pack(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_authorities() {
+        let artist = Pubkey::new_unique();
+        let game_server = Pubkey::new_unique();
+        let level = Field::Key("level".to_string());
+        let mut field_authorities = TokenMetadataFieldAuthorities::default();
+
+        field_authorities.set(Field::Uri, Some(game_server));
+        field_authorities.set(Field::Uri, Some(artist));
+        field_authorities.set(level.clone(), Some(game_server));
+        assert_eq!(field_authorities.get(&Field::Uri), Some(artist));
+        assert_eq!(field_authorities.get(&level), Some(game_server));
+        assert_eq!(field_authorities.get(&Field::Name), None);
+        assert_eq!(field_authorities.authorities.len(), 2);
+
+        field_authorities.set(Field::Uri, None);
+        field_authorities.set(Field::Name, None);
+        assert_eq!(field_authorities.get(&Field::Uri), None);
+        assert_eq!(field_authorities.authorities, vec![(level, game_server)]);
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let mut field_authorities = TokenMetadataFieldAuthorities::default();
+        field_authorities.set(Field::Key("level".to_string()), Some(Pubkey::new_unique()));
+        let mut data = vec![0; field_authorities.get_packed_len().unwrap()];
+        field_authorities.pack_into_slice(&mut data).unwrap();
+        assert_eq!(
+            TokenMetadataFieldAuthorities::unpack_from_slice(&data).unwrap(),
+            field_authorities
+        );
+
+        let instruction = SetFieldAuthority {
+            field: Field::Uri,
+            authority: Some(Pubkey::new_unique()).try_into().unwrap(),
+        };
+        assert_eq!(
+            SetFieldAuthority::unpack(&instruction.pack()).unwrap(),
+            instruction
+        );
+        assert_eq!(
+            SetFieldAuthority::unpack(&[0; 8]).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+    }
+}
@@ -3,6 +3,12 @@ use {
     spl_token_metadata_interface::state::TokenMetadata,
 };
 
+/// Display hints for rendering UI amounts
+pub mod display_hints;
+
+/// Field-level update authorities
+pub mod field_authorities;
+
 /// Instruction processor for the `TokenMetadata` extension
 pub mod processor;
 
@@ -5,7 +5,12 @@ use {
         check_program_account,
         error::TokenError,
         extension::{
-            alloc_and_serialize_variable_len_extension, metadata_pointer::MetadataPointer,
+            alloc_and_serialize_variable_len_extension,
+            metadata_pointer::MetadataPointer,
+            token_metadata::{
+                display_hints::{is_display_hint_key, DisplayHints},
+                field_authorities::{SetFieldAuthority, TokenMetadataFieldAuthorities},
+            },
             BaseStateWithExtensions, PodStateWithExtensions,
         },
         pod::{PodCOption, PodMint},
@@ -24,7 +29,7 @@ use {
         instruction::{
             Emit, Initialize, RemoveKey, TokenMetadataInstruction, UpdateAuthority, UpdateField,
         },
-        state::TokenMetadata,
+        state::{Field, TokenMetadata},
     },
 };
 
@@ -43,6 +48,23 @@ fn check_update_authority(
     Ok(())
 }
 
+fn check_field_update_authority(
+    update_authority_info: &AccountInfo,
+    token_metadata: &TokenMetadata,
+    field_authorities: Option<&TokenMetadataFieldAuthorities>,
+    field: &Field,
+) -> Result<(), ProgramError> {
+    // a field authority can only update its field while the metadata is mutable
+    if update_authority_info.is_signer
+        && Option::<Pubkey>::from(token_metadata.update_authority).is_some()
+        && field_authorities.and_then(|field_authorities| field_authorities.get(field))
+            == Some(*update_authority_info.key)
+    {
+        return Ok(());
+    }
+    check_update_authority(update_authority_info, &token_metadata.update_authority)
+}
+
 /// Processes a [`Initialize`](enum.TokenMetadataInstruction.html) instruction.
 pub fn process_initialize(
     _program_id: &Pubkey,
@@ -119,17 +141,33 @@ pub fn process_update_field(
 
     // deserialize the metadata, but scope the data borrow since we'll probably
     // realloc the account
-    let mut token_metadata = {
+    let (mut token_metadata, field_authorities) = {
         let buffer = metadata_info.try_borrow_data()?;
         let mint = PodStateWithExtensions::<PodMint>::unpack(&buffer)?;
-        mint.get_variable_len_extension::<TokenMetadata>()?
+        (
+            mint.get_variable_len_extension::<TokenMetadata>()?,
+            mint.get_variable_len_extension::<TokenMetadataFieldAuthorities>()
+                .ok(),
+        )
     };
 
-    check_update_authority(update_authority_info, &token_metadata.update_authority)?;
+    check_field_update_authority(
+        update_authority_info,
+        &token_metadata,
+        field_authorities.as_ref(),
+        &data.field,
+    )?;
+
+    // Display hints are validated so wallets can rely on them
+    let is_display_hint = matches!(&data.field, Field::Key(key) if is_display_hint_key(key));
 
     // Update the field
     token_metadata.update(data.field, data.value);
 
+    if is_display_hint {
+        DisplayHints::try_from_metadata(&token_metadata)?;
+    }
+
     // Update / realloc the account
     alloc_and_serialize_variable_len_extension::<PodMint, _>(metadata_info, &token_metadata, true)?;
 
@@ -162,6 +200,43 @@ pub fn process_remove_key(
     Ok(())
 }
 
+/// Processes a [`SetFieldAuthority`] instruction.
+pub fn process_set_field_authority(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetFieldAuthority,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+
+    // deserialize the field authorities, but scope the data borrow since we'll
+    // probably realloc the account
+    let (token_metadata, mut field_authorities) = {
+        let buffer = metadata_info.try_borrow_data()?;
+        let mint = PodStateWithExtensions::<PodMint>::unpack(&buffer)?;
+        (
+            mint.get_variable_len_extension::<TokenMetadata>()?,
+            mint.get_variable_len_extension::<TokenMetadataFieldAuthorities>()
+                .unwrap_or_default(),
+        )
+    };
+
+    check_update_authority(update_authority_info, &token_metadata.update_authority)?;
+
+    field_authorities.set(data.field, data.authority.into());
+
+    // Update / realloc the account, assumes that there's enough SOL for the new
+    // rent-exemption
+    alloc_and_serialize_variable_len_extension::<PodMint, _>(
+        metadata_info,
+        &field_authorities,
+        true,
+    )?;
+
+    Ok(())
+}
+
 /// Processes a [`UpdateAuthority`](enum.TokenMetadataInstruction.html)
 /// instruction.
 pub fn process_update_authority(
@@ -154,6 +154,31 @@ pub enum TransferFeeInstruction {
         /// Maximum fee assessed on transfers
         maximum_fee: u64,
     },
+    /// Exempt a token account from transfer fees, or remove its exemption.
+    /// Transfers from or into an exempt account are not assessed any fee.
+    ///
+    /// Initializes the `TransferFeeExemptAccount` extension if not already
+    /// present, so the account must have enough space allocated for it, e.g.
+    /// through `Reallocate` by the account owner.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The token account.
+    ///   1. `[]` The token mint. Must include the `TransferFeeConfig`
+    ///      extension.
+    ///   2. `[signer]` The mint's `transfer_fee_config_authority`.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The token account.
+    ///   1. `[]` The token mint. Must include the `TransferFeeConfig`
+    ///      extension.
+    ///   2. `[]` The mint's multisignature `transfer_fee_config_authority`.
+    ///   3. `..3+M` `[signer]` M signer accounts.
+    SetTransferFeeExemption {
+        /// Whether the account is exempt from transfer fees
+        exempt: bool,
+    },
 }
 impl TransferFeeInstruction {
     /// Unpacks a byte buffer into a `TransferFeeInstruction`
@@ -199,6 +224,15 @@ impl TransferFeeInstruction {
                     maximum_fee,
                 }
             }
+            6 => {
+                let (&exempt, _) = rest.split_first().ok_or(InvalidInstruction)?;
+                let exempt = match exempt {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(InvalidInstruction.into()),
+                };
+                Self::SetTransferFeeExemption { exempt }
+            }
             _ => return Err(TokenError::InvalidInstruction.into()),
         })
     }
@@ -246,6 +280,10 @@ impl TransferFeeInstruction {
                 buffer.extend_from_slice(&transfer_fee_basis_points.to_le_bytes());
                 buffer.extend_from_slice(&maximum_fee.to_le_bytes());
             }
+            Self::SetTransferFeeExemption { exempt } => {
+                buffer.push(6);
+                buffer.push(exempt.into());
+            }
         }
     }
 }
@@ -420,6 +458,31 @@ pub fn set_transfer_fee(
     })
 }
 
+/// Creates a `SetTransferFeeExemption` instruction
+pub fn set_transfer_fee_exemption(
+    token_program_id: &Pubkey,
+    account: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    signers: &[&Pubkey],
+    exempt: bool,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(*account, false));
+    accounts.push(AccountMeta::new_readonly(*mint, false));
+    accounts.push(AccountMeta::new_readonly(*authority, signers.is_empty()));
+    for signer in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer, true));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: encode_instruction_data(TransferFeeInstruction::SetTransferFeeExemption { exempt }),
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -496,5 +559,14 @@ mod test {
         assert_eq!(packed, expect);
         let unpacked = TransferFeeInstruction::unpack(&expect).unwrap();
         assert_eq!(unpacked, check);
+
+        let check = TransferFeeInstruction::SetTransferFeeExemption { exempt: true };
+        let mut packed = vec![];
+        check.pack(&mut packed);
+        let expect = [6, 1];
+        assert_eq!(packed, expect);
+        let unpacked = TransferFeeInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+        assert!(TransferFeeInstruction::unpack(&[6, 2]).is_err());
     }
 }
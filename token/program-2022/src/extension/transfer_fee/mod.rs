@@ -3,13 +3,13 @@ use serde::{Deserialize, Serialize};
 use {
     crate::{
         error::TokenError,
-        extension::{Extension, ExtensionType},
+        extension::{BaseState, BaseStateWithExtensions, Extension, ExtensionType},
     },
     bytemuck::{Pod, Zeroable},
     solana_program::{clock::Epoch, entrypoint::ProgramResult},
     spl_pod::{
         optional_keys::OptionalNonZeroPubkey,
-        primitives::{PodU16, PodU64},
+        primitives::{PodBool, PodU16, PodU64},
     },
     std::{
         cmp,
@@ -192,6 +192,31 @@ impl Extension for TransferFeeAmount {
     const TYPE: ExtensionType = ExtensionType::TransferFeeAmount;
 }
 
+/// Transfer fee exemption for accounts, managed by the mint's transfer fee
+/// config authority
+#[repr(C)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TransferFeeExemptAccount {
+    /// No transfer fee is assessed on transfers from or into this account
+    pub exempt: PodBool,
+}
+impl Extension for TransferFeeExemptAccount {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeExemptAccount;
+}
+
+/// Determine if transfers from or into this account are exempt from transfer
+/// fees
+pub fn is_transfer_fee_exempt<BSE: BaseStateWithExtensions<S>, S: BaseState>(
+    account_state: &BSE,
+) -> bool {
+    if let Ok(extension) = account_state.get_extension::<TransferFeeExemptAccount>() {
+        return extension.exempt.into();
+    }
+    false
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use {super::*, proptest::prelude::*, solana_program::pubkey::Pubkey, std::convert::TryFrom};
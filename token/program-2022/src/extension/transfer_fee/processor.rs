@@ -5,7 +5,7 @@ use {
         extension::{
             transfer_fee::{
                 instruction::TransferFeeInstruction, TransferFee, TransferFeeAmount,
-                TransferFeeConfig, MAX_FEE_BASIS_POINTS,
+                TransferFeeConfig, TransferFeeExemptAccount, MAX_FEE_BASIS_POINTS,
             },
             BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensions,
             PodStateWithExtensionsMut,
@@ -111,6 +111,53 @@ fn process_set_transfer_fee(
     Ok(())
 }
 
+fn process_set_transfer_fee_exemption(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    exempt: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    // unnecessary check, but helps for clarity
+    check_program_account(mint_account_info.owner)?;
+
+    let mint_data = mint_account_info.data.borrow();
+    let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+    let extension = mint.get_extension::<TransferFeeConfig>()?;
+
+    let transfer_fee_config_authority =
+        Option::<Pubkey>::from(extension.transfer_fee_config_authority)
+            .ok_or(TokenError::NoAuthorityExists)?;
+    Processor::validate_owner(
+        program_id,
+        &transfer_fee_config_authority,
+        authority_info,
+        authority_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    let mut token_account_data = token_account_info.data.borrow_mut();
+    let mut token_account =
+        PodStateWithExtensionsMut::<PodAccount>::unpack(&mut token_account_data)?;
+    if token_account.base.mint != *mint_account_info.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+
+    let extension =
+        if let Ok(extension) = token_account.get_extension_mut::<TransferFeeExemptAccount>() {
+            extension
+        } else {
+            token_account.init_extension::<TransferFeeExemptAccount>(true)?
+        };
+    extension.exempt = exempt.into();
+
+    Ok(())
+}
+
 fn process_withdraw_withheld_tokens_from_mint(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -322,5 +369,9 @@ pub(crate) fn process_instruction(
             msg!("TransferFeeInstruction: SetTransferFee");
             process_set_transfer_fee(program_id, accounts, transfer_fee_basis_points, maximum_fee)
         }
+        TransferFeeInstruction::SetTransferFeeExemption { exempt } => {
+            msg!("TransferFeeInstruction: SetTransferFeeExemption");
+            process_set_transfer_fee_exemption(program_id, accounts, exempt)
+        }
     }
 }
@@ -12,7 +12,10 @@ use {
         program_error::ProgramError,
         pubkey::Pubkey,
     },
-    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_pod::{
+        optional_keys::OptionalNonZeroPubkey,
+        primitives::{PodU16, PodU32},
+    },
     std::convert::TryInto,
 };
 
@@ -55,6 +58,41 @@ pub enum TransferHookInstruction {
     /// Data expected by this instruction:
     ///   `crate::extension::transfer_hook::UpdateInstructionData`
     Update,
+    /// Initialize the transfer hook budget of a new mint, declaring the
+    /// maximum number of extra accounts and an estimate of the compute units
+    /// its transfer hook program may require.
+    ///
+    /// Transfers fail if the extra account metas of the transfer hook program
+    /// exceed the budget, and transfer hook programs are expected to reject
+    /// extra account metas exceeding it when they are created or updated.
+    ///
+    /// Fails if the mint has already been initialized, so must be called before
+    /// `InitializeMint`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to initialize.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::transfer_hook::instruction::BudgetInstructionData`
+    InitializeBudget,
+    /// Update the transfer hook budget. Only supported for mints that include
+    /// the `TransferHook` and `TransferHookBudget` extensions.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The transfer hook authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's transfer hook authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::transfer_hook::instruction::BudgetInstructionData`
+    UpdateBudget,
 }
 
 /// Data expected by `Initialize`
@@ -79,6 +117,18 @@ pub struct UpdateInstructionData {
     pub program_id: OptionalNonZeroPubkey,
 }
 
+/// Data expected by `InitializeBudget` and `UpdateBudget`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct BudgetInstructionData {
+    /// Maximum number of extra account metas of the transfer hook program
+    pub max_extra_accounts: PodU16,
+    /// Maximum compute units the transfer hook program may consume
+    pub max_compute_units: PodU32,
+}
+
 /// Create an `Initialize` instruction
 pub fn initialize(
     token_program_id: &Pubkey,
@@ -126,3 +176,53 @@ pub fn update(
         },
     ))
 }
+
+/// Create an `InitializeBudget` instruction
+pub fn initialize_budget(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    max_extra_accounts: u16,
+    max_compute_units: u32,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![AccountMeta::new(*mint, false)];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TransferHookExtension,
+        TransferHookInstruction::InitializeBudget,
+        &BudgetInstructionData {
+            max_extra_accounts: max_extra_accounts.into(),
+            max_compute_units: max_compute_units.into(),
+        },
+    ))
+}
+
+/// Create an `UpdateBudget` instruction
+pub fn update_budget(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    signers: &[&Pubkey],
+    max_extra_accounts: u16,
+    max_compute_units: u32,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TransferHookExtension,
+        TransferHookInstruction::UpdateBudget,
+        &BudgetInstructionData {
+            max_extra_accounts: max_extra_accounts.into(),
+            max_compute_units: max_compute_units.into(),
+        },
+    ))
+}
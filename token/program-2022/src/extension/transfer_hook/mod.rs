@@ -2,15 +2,27 @@
 use serde::{Deserialize, Serialize};
 use {
     crate::{
+        error::TokenError,
         extension::{
             BaseState, BaseStateWithExtensions, BaseStateWithExtensionsMut, Extension,
-            ExtensionType, PodStateWithExtensionsMut,
+            ExtensionType, PodStateWithExtensions, PodStateWithExtensionsMut,
         },
-        pod::PodAccount,
+        pod::{PodAccount, PodMint},
     },
     bytemuck::{Pod, Zeroable},
-    solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
-    spl_pod::{optional_keys::OptionalNonZeroPubkey, primitives::PodBool},
+    solana_program::{
+        account_info::AccountInfo, compute_units::sol_remaining_compute_units,
+        entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey,
+    },
+    spl_pod::{
+        optional_keys::OptionalNonZeroPubkey,
+        primitives::{PodBool, PodU16, PodU32},
+    },
+    spl_tlv_account_resolution::state::ExtraAccountMetaList,
+    spl_transfer_hook_interface::{
+        get_extra_account_metas_address, instruction::ExecuteInstruction,
+    },
+    spl_type_length_value::state::TlvStateBorrowed,
 };
 
 /// Instructions for the `TransferHook` extension
@@ -41,6 +53,51 @@ pub struct TransferHookAccount {
     pub transferring: PodBool,
 }
 
+/// Budget declared by the mint for its transfer hook, so that wallets can rely
+/// on bounded transaction shapes.
+#[repr(C)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TransferHookBudget {
+    /// Maximum number of extra account metas the transfer hook program may
+    /// require
+    pub max_extra_accounts: PodU16,
+    /// Maximum compute units the transfer hook program may consume, including
+    /// the cost of invoking it, so that wallets can size the compute budget of
+    /// transfers
+    pub max_compute_units: PodU32,
+}
+
+impl TransferHookBudget {
+    /// Checks that `extra_accounts` extra account metas fit the budget
+    pub fn check_extra_accounts(&self, extra_accounts: usize) -> Result<(), ProgramError> {
+        if extra_accounts > usize::from(u16::from(self.max_extra_accounts)) {
+            msg!(
+                "Transfer hook requires {} extra accounts, the mint allows at most {}",
+                extra_accounts,
+                u16::from(self.max_extra_accounts)
+            );
+            return Err(TokenError::TransferHookBudgetExceeded.into());
+        }
+        Ok(())
+    }
+
+    /// Checks that the transfer hook program consumed at most the declared
+    /// compute units
+    pub fn check_compute_units(&self, consumed_compute_units: u64) -> Result<(), ProgramError> {
+        if consumed_compute_units > u64::from(u32::from(self.max_compute_units)) {
+            msg!(
+                "Transfer hook consumed {} compute units, the mint allows at most {}",
+                consumed_compute_units,
+                u32::from(self.max_compute_units)
+            );
+            return Err(TokenError::TransferHookBudgetExceeded.into());
+        }
+        Ok(())
+    }
+}
+
 impl Extension for TransferHook {
     const TYPE: ExtensionType = ExtensionType::TransferHook;
 }
@@ -49,6 +106,10 @@ impl Extension for TransferHookAccount {
     const TYPE: ExtensionType = ExtensionType::TransferHookAccount;
 }
 
+impl Extension for TransferHookBudget {
+    const TYPE: ExtensionType = ExtensionType::TransferHookBudget;
+}
+
 /// Attempts to get the transfer hook program id from the TLV data, returning
 /// None if the extension is not found
 pub fn get_program_id<S: BaseState, BSE: BaseStateWithExtensions<S>>(
@@ -60,6 +121,68 @@ pub fn get_program_id<S: BaseState, BSE: BaseStateWithExtensions<S>>(
         .and_then(|e| Option::<Pubkey>::from(e.program_id))
 }
 
+/// Checks the extra account metas that the transfer hook program requires for
+/// a transfer against the budget of the mint, if it has one, returning the
+/// budget. The validation account is looked up in `additional_accounts` the
+/// same way as when invoking the transfer hook program.
+pub fn check_budget(
+    mint_info: &AccountInfo,
+    transfer_hook_program_id: &Pubkey,
+    additional_accounts: &[AccountInfo],
+) -> Result<Option<TransferHookBudget>, ProgramError> {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+    let Ok(budget) = mint.get_extension::<TransferHookBudget>() else {
+        return Ok(None);
+    };
+
+    let validation_pubkey =
+        get_extra_account_metas_address(mint_info.key, transfer_hook_program_id);
+    let Some(validation_info) = additional_accounts
+        .iter()
+        .find(|info| *info.key == validation_pubkey)
+    else {
+        return Ok(Some(*budget));
+    };
+    let validation_data = validation_info.try_borrow_data()?;
+    let tlv_state = TlvStateBorrowed::unpack(&validation_data)?;
+    let extra_account_metas =
+        ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&tlv_state)?;
+    budget.check_extra_accounts(extra_account_metas.data().len())?;
+    Ok(Some(*budget))
+}
+
+/// Invokes the `Execute` instruction of the transfer hook program, checking
+/// the extra accounts and the compute units it consumes against the budget of
+/// the mint, if it has one
+pub fn invoke_execute<'a>(
+    transfer_hook_program_id: &Pubkey,
+    source_info: AccountInfo<'a>,
+    mint_info: AccountInfo<'a>,
+    destination_info: AccountInfo<'a>,
+    authority_info: AccountInfo<'a>,
+    additional_accounts: &[AccountInfo<'a>],
+    amount: u64,
+) -> ProgramResult {
+    let budget = check_budget(&mint_info, transfer_hook_program_id, additional_accounts)?;
+    let remaining_compute_units = sol_remaining_compute_units();
+    spl_transfer_hook_interface::onchain::invoke_execute(
+        transfer_hook_program_id,
+        source_info,
+        mint_info,
+        destination_info,
+        authority_info,
+        additional_accounts,
+        amount,
+    )?;
+    if let Some(budget) = budget {
+        budget.check_compute_units(
+            remaining_compute_units.saturating_sub(sol_remaining_compute_units()),
+        )?;
+    }
+    Ok(())
+}
+
 /// Helper function to set the transferring flag before calling into transfer
 /// hook
 pub fn set_transferring<BSE: BaseStateWithExtensionsMut<S>, S: BaseState>(
@@ -78,3 +201,26 @@ pub fn unset_transferring(account_info: &AccountInfo) -> Result<(), ProgramError
     account_extension.transferring = false.into();
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn transfer_hook_budget() {
+        let budget = TransferHookBudget {
+            max_extra_accounts: 3.into(),
+            max_compute_units: 50_000.into(),
+        };
+        assert_eq!(budget.check_extra_accounts(3), Ok(()));
+        assert_eq!(
+            budget.check_extra_accounts(4),
+            Err(TokenError::TransferHookBudgetExceeded.into())
+        );
+        assert_eq!(budget.check_compute_units(50_000), Ok(()));
+        assert_eq!(
+            budget.check_compute_units(50_001),
+            Err(TokenError::TransferHookBudgetExceeded.into())
+        );
+    }
+}
@@ -5,11 +5,12 @@ use {
         extension::{
             transfer_hook::{
                 instruction::{
-                    InitializeInstructionData, TransferHookInstruction, UpdateInstructionData,
+                    BudgetInstructionData, InitializeInstructionData, TransferHookInstruction,
+                    UpdateInstructionData,
                 },
-                TransferHook,
+                TransferHook, TransferHookBudget,
             },
-            BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
+            BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
         },
         instruction::{decode_instruction_data, decode_instruction_type},
         pod::PodMint,
@@ -85,6 +86,50 @@ fn process_update(
     Ok(())
 }
 
+fn process_initialize_budget(
+    accounts: &[AccountInfo],
+    data: &BudgetInstructionData,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut mint_data)?;
+
+    let extension = mint.init_extension::<TransferHookBudget>(true)?;
+    extension.max_extra_accounts = data.max_extra_accounts;
+    extension.max_compute_units = data.max_compute_units;
+    Ok(())
+}
+
+fn process_update_budget(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &BudgetInstructionData,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let owner_info_data_len = owner_info.data_len();
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    let authority = Option::<Pubkey>::from(mint.get_extension::<TransferHook>()?.authority)
+        .ok_or(TokenError::NoAuthorityExists)?;
+
+    Processor::validate_owner(
+        program_id,
+        &authority,
+        owner_info,
+        owner_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    let extension = mint.get_extension_mut::<TransferHookBudget>()?;
+    extension.max_extra_accounts = data.max_extra_accounts;
+    extension.max_compute_units = data.max_compute_units;
+    Ok(())
+}
+
 pub(crate) fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -107,5 +152,15 @@ pub(crate) fn process_instruction(
             } = decode_instruction_data(input)?;
             process_update(program_id, accounts, transfer_hook_program_id)
         }
+        TransferHookInstruction::InitializeBudget => {
+            msg!("TransferHookInstruction::InitializeBudget");
+            let data = decode_instruction_data::<BudgetInstructionData>(input)?;
+            process_initialize_budget(accounts, data)
+        }
+        TransferHookInstruction::UpdateBudget => {
+            msg!("TransferHookInstruction::UpdateBudget");
+            let data = decode_instruction_data::<BudgetInstructionData>(input)?;
+            process_update_budget(program_id, accounts, data)
+        }
     }
 }
@@ -717,6 +717,43 @@ pub enum TokenInstruction<'a> {
     ScaledUiAmountExtension,
     /// Instruction prefix for instructions to the pausable extension
     PausableExtension,
+    // 45
+    /// The common instruction prefix for freeze delegates extension
+    /// instructions.
+    ///
+    /// See `extension::freeze_delegates::instruction::FreezeDelegateInstruction`
+    /// for further details about the extended instructions that share this
+    /// instruction prefix
+    FreezeDelegateExtension,
+    /// Like `Reallocate`, checks whether a token account is large enough for a
+    /// list of `ExtensionTypes` and reallocates it if not, but additionally
+    /// returns any lamports held above the rent-exempt reserve to the payer.
+    /// Does nothing beyond the refund if the account is already large enough,
+    /// so it is safe to include unconditionally before enabling extensions.
+    ///
+    /// Lamports above the reserve of a native account are never refunded,
+    /// since they are wrapped SOL that has not been synced yet.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[writable]` The account to reallocate.
+    ///   1. `[signer, writable]` The payer account to fund reallocation and
+    ///      receive any refund
+    ///   2. `[]` System program for reallocation funding
+    ///   3. `[signer]` The account's owner.
+    ///
+    ///   * Multisignature owner
+    ///   0. `[writable]` The account to reallocate.
+    ///   1. `[signer, writable]` The payer account to fund reallocation and
+    ///      receive any refund
+    ///   2. `[]` System program for reallocation funding
+    ///   3. `[]` The account's multisignature owner/delegate.
+    ///   4. ..`4+M` `[signer]` M signer accounts.
+    ReallocateIdempotent {
+        /// New extension types to include in the reallocated account
+        extension_types: Vec<ExtensionType>,
+    },
 }
 impl<'a> TokenInstruction<'a> {
     /// Unpacks a byte buffer into a
@@ -859,6 +896,14 @@ impl<'a> TokenInstruction<'a> {
             42 => Self::ConfidentialMintBurnExtension,
             43 => Self::ScaledUiAmountExtension,
             44 => Self::PausableExtension,
+            45 => Self::FreezeDelegateExtension,
+            46 => {
+                let mut extension_types = vec![];
+                for chunk in rest.chunks(size_of::<ExtensionType>()) {
+                    extension_types.push(chunk.try_into()?);
+                }
+                Self::ReallocateIdempotent { extension_types }
+            }
             _ => return Err(TokenError::InvalidInstruction.into()),
         })
     }
@@ -1039,6 +1084,15 @@ impl<'a> TokenInstruction<'a> {
             &Self::PausableExtension => {
                 buf.push(44);
             }
+            &Self::FreezeDelegateExtension => {
+                buf.push(45);
+            }
+            Self::ReallocateIdempotent { extension_types } => {
+                buf.push(46);
+                for extension_type in extension_types {
+                    buf.extend_from_slice(&<[u8; 2]>::from(*extension_type));
+                }
+            }
         };
         buf
     }
@@ -1903,6 +1957,30 @@ pub fn reallocate(
     })
 }
 
+/// Creates a `ReallocateIdempotent` instruction
+pub fn reallocate_idempotent(
+    token_program_id: &Pubkey,
+    account_pubkey: &Pubkey,
+    payer: &Pubkey,
+    owner_pubkey: &Pubkey,
+    signer_pubkeys: &[&Pubkey],
+    extension_types: &[ExtensionType],
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = reallocate(
+        token_program_id,
+        account_pubkey,
+        payer,
+        owner_pubkey,
+        signer_pubkeys,
+        extension_types,
+    )?;
+    instruction.data = TokenInstruction::ReallocateIdempotent {
+        extension_types: extension_types.to_vec(),
+    }
+    .pack();
+    Ok(instruction)
+}
+
 /// Creates a `CreateNativeMint` instruction
 pub fn create_native_mint(
     token_program_id: &Pubkey,
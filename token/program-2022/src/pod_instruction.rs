@@ -117,6 +117,9 @@ pub(crate) enum PodTokenInstruction {
     ConfidentialMintBurnExtension,
     ScaledUiAmountExtension,
     PausableExtension,
+    // 45
+    FreezeDelegateExtension,
+    ReallocateIdempotent, // &[ExtensionType]
 }
 
 fn unpack_pubkey_option(input: &[u8]) -> Result<PodCOption<Pubkey>, ProgramError> {
@@ -201,7 +204,9 @@ mod tests {
                     let _ = std::str::from_utf8(&input[1..])
                         .map_err(|_| ProgramError::InvalidInstructionData)?;
                 }
-                PodTokenInstruction::GetAccountDataSize | PodTokenInstruction::Reallocate => {
+                PodTokenInstruction::GetAccountDataSize
+                | PodTokenInstruction::Reallocate
+                | PodTokenInstruction::ReallocateIdempotent => {
                     let _ = input[1..]
                         .chunks(std::mem::size_of::<ExtensionType>())
                         .map(ExtensionType::try_from)
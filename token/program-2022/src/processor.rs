@@ -12,6 +12,7 @@ use {
             },
             cpi_guard::{self, in_cpi, CpiGuard},
             default_account_state::{self, DefaultAccountState},
+            freeze_delegates::{self, FreezeDelegates},
             group_member_pointer::{self, GroupMemberPointer},
             group_pointer::{self, GroupPointer},
             immutable_owner::ImmutableOwner,
@@ -24,7 +25,8 @@ use {
             permanent_delegate::{get_permanent_delegate, PermanentDelegate},
             reallocate,
             scaled_ui_amount::{self, ScaledUiAmountConfig},
-            token_group, token_metadata,
+            token_group,
+            token_metadata::{self, field_authorities::SetFieldAuthority},
             transfer_fee::{self, TransferFeeAmount, TransferFeeConfig},
             transfer_hook::{self, TransferHook, TransferHookAccount},
             AccountType, BaseStateWithExtensions, BaseStateWithExtensionsMut, ExtensionType,
@@ -286,6 +288,31 @@ impl Processor {
         Self::_process_initialize_multisig(accounts, m, false)
     }
 
+    /// Checks if a transfer is exempt from the transfer fee, which is the
+    /// case if either the source or the destination account is exempt
+    fn is_transfer_fee_exempt(
+        source_account: &PodStateWithExtensionsMut<PodAccount>,
+        source_account_info: &AccountInfo,
+        destination_account_info: &AccountInfo,
+    ) -> Result<bool, ProgramError> {
+        if transfer_fee::is_transfer_fee_exempt(source_account) {
+            return Ok(true);
+        }
+        // self-transfer, can't double-borrow the underlying data
+        if source_account_info.key == destination_account_info.key {
+            return Ok(false);
+        }
+        // an invalid destination account is rejected later on
+        let destination_account_data = destination_account_info.try_borrow_data()?;
+        Ok(
+            PodStateWithExtensions::<PodAccount>::unpack(&destination_account_data)
+                .map(|destination_account| {
+                    transfer_fee::is_transfer_fee_exempt(&destination_account)
+                })
+                .unwrap_or(false),
+        )
+    }
+
     /// Processes a [`Transfer`](enum.TokenInstruction.html) instruction.
     pub fn process_transfer(
         program_id: &Pubkey,
@@ -343,9 +370,17 @@ impl Processor {
 
                 let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>()
                 {
-                    transfer_fee_config
-                        .calculate_epoch_fee(Clock::get()?.epoch, amount)
-                        .ok_or(TokenError::Overflow)?
+                    if Self::is_transfer_fee_exempt(
+                        &source_account,
+                        source_account_info,
+                        destination_account_info,
+                    )? {
+                        0
+                    } else {
+                        transfer_fee_config
+                            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                            .ok_or(TokenError::Overflow)?
+                    }
                 } else {
                     0
                 };
@@ -539,7 +574,7 @@ impl Processor {
                 // must drop these to avoid the double-borrow during CPI
                 drop(source_account_data);
                 drop(destination_account_data);
-                spl_transfer_hook_interface::onchain::invoke_execute(
+                transfer_hook::invoke_execute(
                     &program_id,
                     source_account_info.clone(),
                     mint_info.clone(),
@@ -1298,19 +1333,39 @@ impl Processor {
 
         let mint_data = mint_info.data.borrow();
         let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
-        match &mint.base.freeze_authority {
+        let freeze_authority = match &mint.base.freeze_authority {
             PodCOption {
                 option: PodCOption::<Pubkey>::SOME,
                 value: authority,
-            } => Self::validate_owner(
-                program_id,
-                authority,
-                authority_info,
-                authority_info_data_len,
-                account_info_iter.as_slice(),
-            ),
-            _ => Err(TokenError::MintCannotFreeze.into()),
-        }?;
+            } => authority,
+            _ => return Err(TokenError::MintCannotFreeze.into()),
+        };
+
+        // A registered freeze delegate may act in place of the freeze authority,
+        // limited to the operations allowed by its scope
+        let delegate = if authority_info.key != freeze_authority {
+            mint.get_extension::<FreezeDelegates>()
+                .ok()
+                .and_then(|extension| extension.get_delegate(authority_info.key))
+        } else {
+            None
+        };
+        let authority = match delegate {
+            Some(delegate) => {
+                if !delegate.allows(freeze) {
+                    return Err(TokenError::FreezeDelegateScopeExceeded.into());
+                }
+                authority_info.key
+            }
+            None => freeze_authority,
+        };
+        Self::validate_owner(
+            program_id,
+            authority,
+            authority_info,
+            authority_info_data_len,
+            account_info_iter.as_slice(),
+        )?;
 
         source_account.base.state = if freeze {
             AccountState::Frozen.into()
@@ -1808,6 +1863,14 @@ impl Processor {
                         .collect::<Result<Vec<_>, _>>()?;
                     reallocate::process_reallocate(program_id, accounts, extension_types)
                 }
+                PodTokenInstruction::ReallocateIdempotent => {
+                    msg!("Instruction: ReallocateIdempotent");
+                    let extension_types = input[1..]
+                        .chunks(std::mem::size_of::<ExtensionType>())
+                        .map(ExtensionType::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    reallocate::process_reallocate_idempotent(program_id, accounts, extension_types)
+                }
                 PodTokenInstruction::MemoTransferExtension => {
                     memo_transfer::processor::process_instruction(program_id, accounts, &input[1..])
                 }
@@ -1885,9 +1948,20 @@ impl Processor {
                     msg!("Instruction: PausableExtension");
                     pausable::processor::process_instruction(program_id, accounts, &input[1..])
                 }
+                PodTokenInstruction::FreezeDelegateExtension => {
+                    msg!("Instruction: FreezeDelegateExtension");
+                    freeze_delegates::processor::process_instruction(
+                        program_id,
+                        accounts,
+                        &input[1..],
+                    )
+                }
             }
         } else if let Ok(instruction) = TokenMetadataInstruction::unpack(input) {
             token_metadata::processor::process_instruction(program_id, accounts, instruction)
+        } else if let Ok(data) = SetFieldAuthority::unpack(input) {
+            msg!("Instruction: SetFieldAuthority");
+            token_metadata::processor::process_set_field_authority(program_id, accounts, data)
         } else if let Ok(instruction) = TokenGroupInstruction::unpack(input) {
             token_group::processor::process_instruction(program_id, accounts, instruction)
         } else {
@@ -13,7 +13,8 @@ use {
     spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
     spl_token_2022::{
         extension::{
-            transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+            transfer_hook::{TransferHookAccount, TransferHookBudget},
+            BaseStateWithExtensions, StateWithExtensions,
         },
         state::{Account, Mint},
     },
@@ -111,6 +112,11 @@ pub fn process_initialize_extra_account_meta_list(
         return Err(TransferHookError::IncorrectMintAuthority.into());
     }
 
+    // Check that the extra account metas fit the budget declared by the mint
+    if let Ok(budget) = mint.get_extension::<TransferHookBudget>() {
+        budget.check_extra_accounts(extra_account_metas.len())?;
+    }
+
     // Check validation account
     let (expected_validation_address, bump_seed) =
         get_extra_account_metas_address_and_bump_seed(mint_info.key, program_id);
@@ -171,6 +177,11 @@ pub fn process_update_extra_account_meta_list(
         return Err(TransferHookError::IncorrectMintAuthority.into());
     }
 
+    // Check that the extra account metas fit the budget declared by the mint
+    if let Ok(budget) = mint.get_extension::<TransferHookBudget>() {
+        budget.check_extra_accounts(extra_account_metas.len())?;
+    }
+
     // Check validation account
     let expected_validation_address = get_extra_account_metas_address(mint_info.key, program_id);
     if expected_validation_address != *extra_account_metas_info.key {